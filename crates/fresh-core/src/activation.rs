@@ -0,0 +1,128 @@
+//! Plugin activation events
+//!
+//! A plugin package can list activation events under `fresh.activationEvents`
+//! in its `package.json`. Plugins that declare any are not executed at startup;
+//! the host keeps them pending until the editor reports a matching event.
+
+use std::fmt;
+
+/// An event that causes a deferred plugin to be executed
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ActivationEvent {
+    /// `*` - load eagerly at startup (same as declaring no events)
+    Startup,
+    /// `onStartupFinished` - once the editor has drawn its first frame
+    StartupFinished,
+    /// `onLanguage:<id>` - when a buffer with this language is opened
+    Language(String),
+    /// `onCommand:<name>` - before the named plugin action is executed
+    Command(String),
+}
+
+impl ActivationEvent {
+    /// Parse an activation event string as written in a manifest
+    pub fn parse(s: &str) -> Option<Self> {
+        let s = s.trim();
+        if s == "*" {
+            return Some(Self::Startup);
+        }
+        if s == "onStartupFinished" {
+            return Some(Self::StartupFinished);
+        }
+        let (kind, arg) = s.split_once(':')?;
+        let arg = arg.trim();
+        if arg.is_empty() {
+            return None;
+        }
+        match kind {
+            "onLanguage" => Some(Self::Language(arg.to_lowercase())),
+            "onCommand" => Some(Self::Command(arg.to_string())),
+            _ => None,
+        }
+    }
+
+    /// Whether an event reported by the editor satisfies this declared event
+    pub fn matches(&self, event: &ActivationEvent) -> bool {
+        match (self, event) {
+            (Self::Language(declared), Self::Language(lang)) => declared.eq_ignore_ascii_case(lang),
+            _ => self == event,
+        }
+    }
+}
+
+impl fmt::Display for ActivationEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Startup => write!(f, "*"),
+            Self::StartupFinished => write!(f, "onStartupFinished"),
+            Self::Language(lang) => write!(f, "onLanguage:{}", lang),
+            Self::Command(name) => write!(f, "onCommand:{}", name),
+        }
+    }
+}
+
+/// Extract the activation events declared by a parsed `package.json`.
+///
+/// Returns an empty list when the manifest declares no events or includes
+/// `*`, both of which mean the plugin should load at startup. Unrecognized
+/// events are skipped.
+pub fn manifest_activation_events(manifest: &serde_json::Value) -> Vec<ActivationEvent> {
+    let declared = manifest
+        .get("fresh")
+        .and_then(|fresh| fresh.get("activationEvents"))
+        .and_then(|events| events.as_array());
+    let Some(declared) = declared else {
+        return Vec::new();
+    };
+
+    let events: Vec<ActivationEvent> = declared
+        .iter()
+        .filter_map(|value| value.as_str().and_then(ActivationEvent::parse))
+        .collect();
+    if events.contains(&ActivationEvent::Startup) {
+        return Vec::new();
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_round_trips() {
+        for s in ["*", "onStartupFinished", "onLanguage:rust", "onCommand:foo"] {
+            assert_eq!(ActivationEvent::parse(s).unwrap().to_string(), s);
+        }
+        assert_eq!(ActivationEvent::parse("onLanguage:"), None);
+        assert_eq!(ActivationEvent::parse("onSomething:x"), None);
+    }
+
+    #[test]
+    fn test_language_matches_case_insensitively() {
+        let declared = ActivationEvent::parse("onLanguage:Rust").unwrap();
+        assert!(declared.matches(&ActivationEvent::Language("rust".to_string())));
+        assert!(!declared.matches(&ActivationEvent::Language("python".to_string())));
+        assert!(!declared.matches(&ActivationEvent::Command("rust".to_string())));
+    }
+
+    #[test]
+    fn test_manifest_activation_events() {
+        let manifest = serde_json::json!({
+            "fresh": { "activationEvents": ["onLanguage:rust", "bogus", "onCommand:foo"] }
+        });
+        assert_eq!(
+            manifest_activation_events(&manifest),
+            vec![
+                ActivationEvent::Language("rust".to_string()),
+                ActivationEvent::Command("foo".to_string()),
+            ]
+        );
+
+        let eager = serde_json::json!({
+            "fresh": { "activationEvents": ["onLanguage:rust", "*"] }
+        });
+        assert!(manifest_activation_events(&eager).is_empty());
+        assert!(manifest_activation_events(&serde_json::json!({})).is_empty());
+    }
+}
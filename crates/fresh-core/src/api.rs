@@ -147,6 +147,31 @@ pub struct VirtualBufferResult {
     pub split_id: Option<u64>,
 }
 
+/// A node of the split layout tree returned by `editor.getLayout`
+///
+/// Leaves are panes showing a buffer; containers divide their space between
+/// `children[0]` (top or left) and `children[1]` (bottom or right).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct LayoutNode {
+    /// Split ID, usable with `focusSplit`, `setSplitRatio`, `splitEditor`, ...
+    #[ts(type = "number")]
+    pub split_id: usize,
+    /// Buffer shown in the pane (leaves only)
+    #[ts(type = "number | null")]
+    pub buffer_id: Option<usize>,
+    /// "horizontal" (stacked) or "vertical" (side by side), containers only
+    #[ts(type = "\"horizontal\" | \"vertical\" | null")]
+    pub direction: Option<String>,
+    /// Share of the space given to the first child (containers only)
+    pub ratio: Option<f32>,
+    /// Whether this is the focused pane
+    pub active: bool,
+    /// The two children of a container (empty for leaves)
+    pub children: Vec<LayoutNode>,
+}
+
 /// Response from the editor for async plugin operations
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -241,6 +266,30 @@ pub struct CursorInfo {
     pub selection: Option<Range<usize>>,
 }
 
+/// A selection to set with `setSelections`: the cursor is at `head` and the
+/// selection extends to `anchor` (equal to `head` for a plain cursor)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct SelectionSpec {
+    /// Byte offset where the selection starts (stays put when extending)
+    pub anchor: usize,
+    /// Byte offset of the cursor
+    pub head: usize,
+}
+
+/// One edit of an `applyEdits` call: replace `range` with `text`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct BufferEdit {
+    /// Byte range to replace, in the buffer before any of the edits
+    #[ts(type = "{ start: number; end: number }")]
+    pub range: Range<usize>,
+    /// Text to put in its place (empty to delete)
+    pub text: String,
+}
+
 /// Specification for an action to execute, with optional repeat count
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -272,6 +321,15 @@ pub struct BufferInfo {
     pub modified: bool,
     /// Length of buffer in bytes
     pub length: usize,
+    /// Increases with every edit; pass it as `baseVersion` to `insertText`
+    /// or `deleteRange` so edits made in the meantime are accounted for
+    #[ts(type = "number")]
+    pub version: u64,
+    /// Number of steps that can be undone
+    pub undo_depth: usize,
+    /// Whether editing is disabled in the buffer (plugin edits, undo and
+    /// redo are refused too)
+    pub read_only: bool,
 }
 
 fn serialize_path<S: serde::Serializer>(path: &Option<PathBuf>, s: S) -> Result<S::Ok, S::Error> {
@@ -409,6 +467,33 @@ impl OverlayColorSpec {
     }
 }
 
+/// One line of a `setGutterTexts` call
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct GutterText {
+    /// Line number (0-indexed)
+    pub line: usize,
+    /// Text shown in the gutter column, cut to the column's width
+    pub text: String,
+    /// Text color - RGB array or theme key string (defaults to the line
+    /// number color)
+    #[serde(default)]
+    #[ts(optional)]
+    pub color: Option<OverlayColorSpec>,
+}
+
+/// One line of a `setLineBackgrounds` call
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct LineBackground {
+    /// Line number (0-indexed)
+    pub line: usize,
+    /// Background color - RGB array or theme key string
+    pub color: OverlayColorSpec,
+}
+
 /// Options for adding an overlay with theme support.
 ///
 /// This struct provides a type-safe way to specify overlay styling
@@ -550,6 +635,564 @@ pub struct CompositeHunk {
     pub new_count: usize,
 }
 
+/// Token granularity for `editor.computeDiff`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "lowercase")]
+#[ts(export)]
+pub enum DiffGranularity {
+    /// Compare whole lines (default)
+    #[default]
+    Line,
+    /// Compare words, whitespace runs and punctuation
+    Word,
+}
+
+/// Options for `editor.computeDiff`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct ComputeDiffOptions {
+    /// "line" (default) or "word"
+    #[serde(default)]
+    #[ts(optional)]
+    pub granularity: Option<DiffGranularity>,
+    /// Return `TsCompositeHunk` objects that can be passed straight to
+    /// `createCompositeBuffer` / `updateCompositeAlignment` (line granularity only)
+    #[serde(default)]
+    #[ts(optional)]
+    pub composite: Option<bool>,
+}
+
+/// A changed region returned by `editor.computeDiff`
+///
+/// Start/count fields are in units of the requested granularity (lines or
+/// word tokens); offsets are byte offsets into the respective input text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct DiffHunk {
+    /// First changed unit in the old text (0-indexed)
+    pub old_start: usize,
+    /// Number of units removed from the old text
+    pub old_count: usize,
+    /// First changed unit in the new text (0-indexed)
+    pub new_start: usize,
+    /// Number of units inserted in the new text
+    pub new_count: usize,
+    /// Byte offset of the hunk in the old text
+    pub old_offset: usize,
+    /// Byte offset of the hunk in the new text
+    pub new_offset: usize,
+    /// Text removed from the old side
+    pub old_text: String,
+    /// Text inserted on the new side
+    pub new_text: String,
+}
+
+/// A node captured by `editor.querySyntax`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SyntaxCapture {
+    /// Capture name without the `@` (e.g. "name" for `@name`)
+    pub name: String,
+    /// Byte offset of the start of the node
+    pub start: usize,
+    /// Byte offset of the end of the node (exclusive)
+    pub end: usize,
+    /// Text of the node
+    pub text: String,
+}
+
+/// Options for `editor.runSearch`
+///
+/// Unset fields keep the current state of the search toggles.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct SearchOptions {
+    /// Treat the pattern as a regular expression
+    #[serde(default)]
+    #[ts(optional)]
+    pub regex: Option<bool>,
+    /// Match case
+    #[serde(default)]
+    #[ts(optional)]
+    pub case_sensitive: Option<bool>,
+    /// Only match whole words
+    #[serde(default)]
+    #[ts(optional)]
+    pub whole_word: Option<bool>,
+    /// Search the whole project instead of the active buffer
+    #[serde(default)]
+    #[ts(optional)]
+    pub project: Option<bool>,
+}
+
+/// Options for `editor.search`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct BufferSearchOptions {
+    /// Treat the pattern as a regular expression (default: false)
+    #[serde(default)]
+    #[ts(optional)]
+    pub regex: Option<bool>,
+    /// Match case (default: false)
+    #[serde(default)]
+    #[ts(optional)]
+    pub case_sensitive: Option<bool>,
+    /// Only match whole words (default: false)
+    #[serde(default)]
+    #[ts(optional)]
+    pub whole_word: Option<bool>,
+    /// Stop after this many matches (default: no limit)
+    #[serde(default)]
+    #[ts(optional)]
+    pub max_results: Option<usize>,
+}
+
+/// A match found by `editor.search`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct SearchMatch {
+    /// Byte offset of the start of the match
+    pub start: usize,
+    /// Byte offset of the end of the match (exclusive)
+    pub end: usize,
+}
+
+/// Options for `editor.on` that limit how often a handler runs, for
+/// high-frequency events such as `cursor_moved` and `viewport_changed`
+///
+/// Events held back are coalesced: the handler gets the latest one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct EventOptions {
+    /// Run once no event has arrived for this many milliseconds
+    #[serde(default)]
+    #[ts(optional, type = "number")]
+    pub debounce_ms: Option<u64>,
+    /// With `debounceMs`, also run right away for the first event of a burst
+    #[serde(default)]
+    #[ts(optional)]
+    pub leading: Option<bool>,
+    /// Run at most once per this many milliseconds; with `debounceMs`, the
+    /// longest a burst of events can hold the handler back
+    #[serde(default)]
+    #[ts(optional, type = "number")]
+    pub throttle_ms: Option<u64>,
+}
+
+/// An entry of an `editor.quickPick` list
+///
+/// The promise resolves with the chosen entries as passed in, so `value` can
+/// carry whatever the plugin needs to act on the choice.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct QuickPickItem {
+    /// Text shown in the list and matched against the filter
+    pub label: String,
+    /// Secondary text shown next to the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub description: Option<String>,
+    /// Icon or short prefix shown before the label
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub icon: Option<String>,
+    /// Start out checked (multi-select only)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub picked: Option<bool>,
+    /// Arbitrary data returned with the item
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional, type = "unknown")]
+    pub value: Option<JsonValue>,
+}
+
+/// Options for `editor.quickPick`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct QuickPickOptions {
+    /// Label shown in front of the filter input
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Let the user check several items (Tab toggles); resolves to an array
+    #[serde(default)]
+    #[ts(optional)]
+    pub can_pick_many: Option<bool>,
+    /// Also match the filter against item descriptions
+    #[serde(default)]
+    #[ts(optional)]
+    pub match_on_description: Option<bool>,
+    /// Hint shown in the filter input while it is empty
+    #[serde(default)]
+    #[ts(optional)]
+    pub placeholder: Option<String>,
+}
+
+/// Options for `editor.showPicker`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct PickerOptions {
+    /// Label shown in front of the filter input
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Items to choose from; the chosen ones are returned as passed in
+    pub items: Vec<QuickPickItem>,
+    /// Let the user check several items (Tab toggles); resolves to an array
+    #[serde(default)]
+    #[ts(optional)]
+    pub multi_select: Option<bool>,
+    /// Hint shown in the filter input while it is empty
+    #[serde(default)]
+    #[ts(optional)]
+    pub placeholder: Option<String>,
+    /// Also match the filter against item descriptions
+    #[serde(default)]
+    #[ts(optional)]
+    pub match_on_description: Option<bool>,
+}
+
+impl PickerOptions {
+    /// Split into the items and options of the equivalent `editor.quickPick`
+    pub fn into_quick_pick(self) -> (Vec<QuickPickItem>, QuickPickOptions) {
+        (
+            self.items,
+            QuickPickOptions {
+                title: self.title,
+                can_pick_many: self.multi_select,
+                match_on_description: self.match_on_description,
+                placeholder: self.placeholder,
+            },
+        )
+    }
+}
+
+/// Options for `editor.showInput`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct InputOptions {
+    /// Text the input starts with (selected, so typing replaces it)
+    #[serde(default)]
+    #[ts(optional)]
+    pub initial: Option<String>,
+    /// Show `*` instead of the typed characters and keep them off the clipboard
+    #[serde(default)]
+    #[ts(optional)]
+    pub password: Option<bool>,
+}
+
+/// Options for `editor.openFileBrowser`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct FileBrowserOptions {
+    /// Directory to start in; the user cannot browse above it
+    /// (defaults to the working directory, unscoped)
+    #[serde(default)]
+    #[ts(optional)]
+    pub root: Option<String>,
+    /// File name globs such as `"*.rs"`; other files are hidden
+    #[serde(default)]
+    #[ts(optional)]
+    pub filter: Option<Vec<String>>,
+    /// Label shown in front of the path input
+    #[serde(default)]
+    #[ts(optional)]
+    pub title: Option<String>,
+    /// Choose a directory instead of a file
+    #[serde(default)]
+    #[ts(optional)]
+    pub directories: Option<bool>,
+}
+
+/// Options for `editor.fetch`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct FetchOptions {
+    /// HTTP method (default `"GET"`)
+    #[serde(default)]
+    #[ts(optional)]
+    pub method: Option<String>,
+    /// Request headers
+    #[serde(default)]
+    #[ts(optional, type = "Record<string, string>")]
+    pub headers: Option<HashMap<String, String>>,
+    /// Request body, sent as-is
+    #[serde(default)]
+    #[ts(optional)]
+    pub body: Option<String>,
+    /// Give up after this many milliseconds
+    /// (default: the `plugin_fetch.default_timeout_ms` setting)
+    #[serde(default)]
+    #[ts(optional, type = "number")]
+    pub timeout_ms: Option<u64>,
+}
+
+/// A completion returned by a plugin completion provider
+///
+/// Shown in the completion popup next to the language server's items.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct CompletionItem {
+    /// Text shown in the list and matched against the typed prefix
+    pub label: String,
+    /// Text inserted when the item is accepted (defaults to the label)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub insert_text: Option<String>,
+    /// Secondary text shown next to the label, e.g. a signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub detail: Option<String>,
+    /// "function", "method", "variable", "class", "struct", "constant" or
+    /// "keyword", used to pick the icon
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub kind: Option<String>,
+}
+
+/// What a completion provider's handler is called with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct CompletionRequest {
+    /// Buffer the completion was requested in
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// File path of the buffer, if any
+    pub path: Option<String>,
+    /// Language of the buffer
+    pub language: String,
+    /// Byte offset of the cursor
+    #[ts(type = "number")]
+    pub position: usize,
+    /// Word fragment before the cursor that the items will be filtered by
+    pub prefix: String,
+}
+
+/// A completion provider registered with `editor.registerCompletionProvider`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct CompletionProvider {
+    /// Plugin that registered the provider
+    pub plugin_name: String,
+    /// Languages the provider is asked for (all languages when empty)
+    pub languages: Vec<String>,
+    /// Characters that request completion immediately, like `.`
+    pub trigger_characters: Vec<String>,
+    /// Global function called with a `CompletionRequest`
+    pub handler_name: String,
+}
+
+impl CompletionProvider {
+    /// Whether the provider should be asked for completions in `language`
+    pub fn handles_language(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+/// An item added to a right-click context menu with `editor.addContextMenuItem`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct ContextMenuItem {
+    /// Plugin that added the item
+    pub plugin_name: String,
+    /// Menu the item appears in: "editor", "tab" or "file_explorer"
+    pub menu: String,
+    /// Text shown for the item
+    pub label: String,
+    /// Action or plugin command run when the item is chosen
+    pub action: String,
+    /// Context key that must be set for the item to be shown
+    pub when: Option<String>,
+}
+
+/// Options for `editor.addMenuItem`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct MenuItemOptions {
+    /// Text shown for the item
+    pub label: String,
+    /// Action or plugin command run when the item is chosen
+    pub command: String,
+    /// Context key that must be set for the item to be enabled
+    #[serde(default)]
+    #[ts(optional)]
+    pub when: Option<String>,
+}
+
+/// An item added to the menu bar with `editor.addMenuItem`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PluginMenuItem {
+    /// Plugin that added the item
+    pub plugin_name: String,
+    /// Menu the item goes in: a top-level menu label, optionally followed by
+    /// submenu labels separated by `/` (e.g. "Tools/Git")
+    pub path: String,
+    /// Text shown for the item
+    pub label: String,
+    /// Action or plugin command run when the item is chosen
+    pub command: String,
+    /// Context key that must be set for the item to be enabled
+    pub when: Option<String>,
+}
+
+impl PluginMenuItem {
+    /// Labels of the menus on the way to the item, outermost first
+    pub fn path_segments(&self) -> impl Iterator<Item = &str> {
+        self.path
+            .split('/')
+            .map(str::trim)
+            .filter(|segment| !segment.is_empty())
+    }
+}
+
+/// What a hover provider's handler is called with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct HoverRequest {
+    /// Buffer the hover was requested in
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// File path of the buffer, if any
+    pub path: Option<String>,
+    /// Language of the buffer
+    pub language: String,
+    /// Byte offset of the hovered position (the cursor or the mouse)
+    #[ts(type = "number")]
+    pub position: usize,
+}
+
+/// A hover provider registered with `editor.registerHoverProvider`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct HoverProvider {
+    /// Plugin that registered the provider
+    pub plugin_name: String,
+    /// Languages the provider is asked for (all languages when empty)
+    pub languages: Vec<String>,
+    /// Global function called with a `HoverRequest`
+    pub handler_name: String,
+}
+
+impl HoverProvider {
+    /// Whether the provider should be asked for hover content in `language`
+    pub fn handles_language(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+/// What an inline suggestion provider's handler is called with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct InlineSuggestionRequest {
+    /// Buffer the cursor is in
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// File path of the buffer, if any
+    pub path: Option<String>,
+    /// Language of the buffer
+    pub language: String,
+    /// Byte offset of the cursor
+    #[ts(type = "number")]
+    pub position: usize,
+    /// Text before the cursor (at most a few kilobytes)
+    pub text_before: String,
+    /// Text after the cursor (at most a few kilobytes)
+    pub text_after: String,
+}
+
+/// An inline suggestion provider registered with
+/// `editor.registerInlineSuggestionProvider`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct InlineSuggestionProvider {
+    /// Plugin that registered the provider
+    pub plugin_name: String,
+    /// Languages the provider is asked for (all languages when empty)
+    pub languages: Vec<String>,
+    /// Global function called with an `InlineSuggestionRequest`
+    pub handler_name: String,
+}
+
+impl InlineSuggestionProvider {
+    /// Whether the provider should be asked for suggestions in `language`
+    pub fn handles_language(&self, language: &str) -> bool {
+        self.languages.is_empty() || self.languages.iter().any(|l| l == language)
+    }
+}
+
+/// What a formatter's handler is called with
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct FormatRequest {
+    /// Buffer being formatted
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// File path of the buffer, if any
+    pub path: Option<String>,
+    /// Language of the buffer
+    pub language: String,
+    /// Full text of the buffer
+    pub text: String,
+}
+
+/// A formatter registered with `editor.registerFormatter`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PluginFormatter {
+    /// Plugin that registered the formatter
+    pub plugin_name: String,
+    /// Language the formatter formats
+    pub language: String,
+    /// Global function called with a `FormatRequest`
+    pub handler_name: String,
+}
+
+/// What an evaluator's handler is called with, once per selection
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct EvaluateRequest {
+    /// Buffer the selection is in
+    #[ts(type = "number")]
+    pub buffer_id: usize,
+    /// File path of the buffer, if any
+    pub path: Option<String>,
+    /// Language of the buffer
+    pub language: String,
+    /// Selected text to evaluate
+    pub expression: String,
+}
+
+/// An evaluator registered with `editor.registerEvaluator`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct PluginEvaluator {
+    /// Plugin that registered the evaluator
+    pub plugin_name: String,
+    /// Language whose selections the evaluator evaluates
+    pub language: String,
+    /// Global function called with an `EvaluateRequest`
+    pub handler_name: String,
+}
+
 /// Options for creating a composite buffer (used by plugin API)
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -684,6 +1327,19 @@ pub struct EditorStateSnapshot {
     /// Global editor mode for modal editing (e.g., "vi-normal", "vi-insert")
     /// When set, this mode's keybindings take precedence over normal key handling
     pub editor_mode: Option<String>,
+    /// Data plugins stored with `setSessionData`, by plugin name then key.
+    /// Restored from the session by the editor; otherwise kept up to date by
+    /// the plugin runtime as plugins set values
+    #[ts(type = "any")]
+    pub plugin_session_data: HashMap<String, HashMap<String, serde_json::Value>>,
+    /// Buffer IDs below this one have been handed out; those missing from
+    /// `buffers` were closed. Newer IDs may belong to buffers created since
+    /// the snapshot was taken
+    pub next_buffer_id: usize,
+    /// Bytes the user deleted within the edits each buffer can still rebase
+    /// plugin edits through: a range based on an older `version` may reach
+    /// this far past the current length
+    pub buffer_rebase_slack: HashMap<BufferId, usize>,
 }
 
 impl EditorStateSnapshot {
@@ -705,6 +1361,9 @@ impl EditorStateSnapshot {
             config: serde_json::Value::Null,
             user_config: serde_json::Value::Null,
             editor_mode: None,
+            plugin_session_data: HashMap::new(),
+            next_buffer_id: 0,
+            buffer_rebase_slack: HashMap::new(),
         }
     }
 }
@@ -729,23 +1388,145 @@ pub enum MenuPosition {
     After(String),
 }
 
+/// Why a plugin API call was refused
+///
+/// Plugins get it as an instance of the JS error class named by
+/// [`class_name`](Self::class_name), a subclass of `PluginError` whose `code`
+/// is [`code`](Self::code).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginError {
+    /// No open buffer has this ID (it was closed, or never existed)
+    InvalidBufferId { buffer_id: BufferId },
+    /// The buffer doesn't allow the operation (editing is disabled in it)
+    ReadOnlyBuffer { buffer_id: BufferId },
+    /// A position or range lies outside the buffer, or ends before it starts
+    OutOfRange {
+        buffer_id: BufferId,
+        range: Range<usize>,
+        length: usize,
+    },
+    /// The plugin isn't allowed to call the method
+    PermissionDenied { method: String, reason: String },
+    /// The editor no longer accepts commands (it is shutting down)
+    ChannelClosed,
+}
+
+impl PluginError {
+    /// JS class name and error code of every variant
+    pub const CLASSES: [(&'static str, &'static str); 5] = [
+        ("InvalidBufferIdError", "INVALID_BUFFER_ID"),
+        ("ReadOnlyBufferError", "READ_ONLY_BUFFER"),
+        ("OutOfRangeError", "OUT_OF_RANGE"),
+        ("PermissionDeniedError", "PERMISSION_DENIED"),
+        ("ChannelClosedError", "CHANNEL_CLOSED"),
+    ];
+
+    fn index(&self) -> usize {
+        match self {
+            Self::InvalidBufferId { .. } => 0,
+            Self::ReadOnlyBuffer { .. } => 1,
+            Self::OutOfRange { .. } => 2,
+            Self::PermissionDenied { .. } => 3,
+            Self::ChannelClosed => 4,
+        }
+    }
+
+    /// Name of the JS error class thrown for this error
+    pub fn class_name(&self) -> &'static str {
+        Self::CLASSES[self.index()].0
+    }
+
+    /// Stable code plugins can branch on (the `code` of the thrown error)
+    pub fn code(&self) -> &'static str {
+        Self::CLASSES[self.index()].1
+    }
+}
+
+impl std::fmt::Display for PluginError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::InvalidBufferId { buffer_id } => {
+                write!(f, "buffer {} does not exist", buffer_id.0)
+            }
+            Self::ReadOnlyBuffer { buffer_id } => {
+                write!(f, "buffer {} is read-only", buffer_id.0)
+            }
+            Self::OutOfRange {
+                buffer_id, range, ..
+            } if range.start > range.end => write!(
+                f,
+                "range {}..{} in buffer {} ends before it starts",
+                range.start, range.end, buffer_id.0
+            ),
+            Self::OutOfRange {
+                buffer_id,
+                range,
+                length,
+            } => write!(
+                f,
+                "range {}..{} is outside buffer {} (length {})",
+                range.start, range.end, buffer_id.0, length
+            ),
+            Self::PermissionDenied { method, reason } => {
+                write!(f, "editor.{} is not available: {}", method, reason)
+            }
+            Self::ChannelClosed => write!(f, "the editor no longer accepts plugin commands"),
+        }
+    }
+}
+
+impl std::error::Error for PluginError {}
+
 /// Plugin command - allows plugins to send commands to the editor
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub enum PluginCommand {
     /// Insert text at a position in a buffer
+    ///
+    /// With a `base_version`, `position` refers to the buffer at that version
+    /// and is moved past the user's edits since.
     InsertText {
         buffer_id: BufferId,
         position: usize,
         text: String,
+        base_version: Option<u64>,
     },
 
     /// Delete a range of text from a buffer
+    ///
+    /// With a `base_version`, `range` refers to the buffer at that version
+    /// and is moved past the user's edits since.
     DeleteRange {
         buffer_id: BufferId,
         range: Range<usize>,
+        base_version: Option<u64>,
+    },
+
+    /// Apply several edits to a buffer as a single undo step
+    ///
+    /// Every range refers to the buffer before the edits (at `base_version`,
+    /// if given); ranges must not overlap.
+    ApplyEdits {
+        buffer_id: BufferId,
+        edits: Vec<BufferEdit>,
+        base_version: Option<u64>,
     },
 
+    /// Start grouping a plugin's edits to a buffer into a single undo step
+    ///
+    /// The group is closed by `EndUndoGroup`, or by the editor if it stays
+    /// open for too long.
+    BeginUndoGroup { buffer_id: BufferId },
+
+    /// Close the undo group opened by `BeginUndoGroup`
+    EndUndoGroup { buffer_id: BufferId },
+
+    /// Undo the last edit step in a buffer
+    Undo { buffer_id: BufferId },
+
+    /// Redo the last undone edit step in a buffer
+    Redo { buffer_id: BufferId },
+
     /// Add an overlay to a buffer, returns handle via response channel
     ///
     /// Colors can be specified as RGB tuples or theme keys. When theme keys
@@ -767,6 +1548,10 @@ pub enum PluginCommand {
     /// Set status message
     SetStatus { message: String },
 
+    /// Set the text of a plugin segment of the status bar template
+    /// (an empty text clears it)
+    SetStatusBarSegment { name: String, text: String },
+
     /// Apply a theme by name
     ApplyTheme { theme_name: String },
 
@@ -780,6 +1565,62 @@ pub enum PluginCommand {
     /// Unregister a command by name
     UnregisterCommand { name: String },
 
+    /// Register a plugin completion provider
+    /// Its items are merged with the language server's in the completion popup
+    RegisterCompletionProvider { provider: CompletionProvider },
+
+    /// Completion items returned by a provider for a completion request
+    ProvideCompletions {
+        request_id: u64,
+        items: Vec<CompletionItem>,
+    },
+
+    /// Register a plugin hover provider
+    /// Its content is shown in the hover popup next to the language server's
+    RegisterHoverProvider { provider: HoverProvider },
+
+    /// Markdown returned by a hover provider for a hover request (`None`
+    /// when it has nothing to show)
+    ProvideHover {
+        request_id: u64,
+        markdown: Option<String>,
+    },
+
+    /// Register a plugin inline suggestion provider
+    /// Its suggestions are shown as ghost text at the cursor
+    RegisterInlineSuggestionProvider { provider: InlineSuggestionProvider },
+
+    /// Text returned by an inline suggestion provider to insert at the
+    /// cursor (`None` when it has nothing to suggest)
+    ProvideInlineSuggestion {
+        request_id: u64,
+        text: Option<String>,
+    },
+
+    /// Register a plugin formatter for a language
+    /// It replaces the language's `formatter` command for `format_buffer`
+    /// and format-on-save
+    RegisterFormatter { formatter: PluginFormatter },
+
+    /// Text returned by a formatter for a format request (`None` when it
+    /// failed or left the text as it was)
+    ProvideFormattedText {
+        request_id: u64,
+        text: Option<String>,
+    },
+
+    /// Register a plugin evaluator for a language
+    /// It evaluates selections for the Evaluate Selection commands before
+    /// the built-in calculator is tried
+    RegisterEvaluator { evaluator: PluginEvaluator },
+
+    /// Results returned by an evaluator, one per selection (`None` where the
+    /// built-in calculator should be used instead)
+    ProvideEvaluation {
+        request_id: u64,
+        results: Vec<Option<String>>,
+    },
+
     /// Open a file in the editor (in background, without switching focus)
     OpenFileInBackground { path: PathBuf },
 
@@ -794,6 +1635,25 @@ pub enum PluginCommand {
         callback_id: JsCallbackId,
     },
 
+    /// Make an HTTP request (async, resolves callback with a FetchResponse)
+    Fetch {
+        url: String,
+        options: FetchOptions,
+        callback_id: JsCallbackId,
+    },
+
+    /// A plugin was unloaded (or is being reloaded): drop the providers,
+    /// formatters, menu items and process subscriptions it registered
+    PluginUnloaded { plugin_name: String },
+
+    /// Ask the user to allow a permission declared in a plugin's
+    /// `plugin.json` (e.g. "fs-read"); the editor grants it through the
+    /// plugin thread if they agree
+    RequestPluginPermission {
+        plugin_name: String,
+        capability: String,
+    },
+
     /// Delay/sleep for a duration (async, resolves callback when done)
     Delay {
         callback_id: JsCallbackId,
@@ -819,6 +1679,15 @@ pub enum PluginCommand {
     /// Kill a background process by ID
     KillBackgroundProcess { process_id: u64 },
 
+    /// Send a background process's output lines to the plugin that started
+    /// it, for its `onStdout`/`onStderr` listeners
+    SubscribeProcessOutput {
+        /// Plugin whose listeners receive the lines
+        plugin_name: String,
+        /// Process started with SpawnBackgroundProcess
+        process_id: u64,
+    },
+
     /// Wait for a process to complete and get its result
     /// Used with processes started via SpawnProcess
     SpawnProcessWait {
@@ -839,6 +1708,12 @@ pub enum PluginCommand {
     /// Enable/disable line numbers for a buffer
     SetLineNumbers { buffer_id: BufferId, enabled: bool },
 
+    /// Override how a buffer is displayed (wrap column, tab width, conceal rules, ...)
+    SetBufferDisplayOptions {
+        buffer_id: BufferId,
+        options: BufferDisplayOptions,
+    },
+
     /// Submit a transformed view stream for a viewport
     SubmitViewTransform {
         buffer_id: BufferId,
@@ -893,6 +1768,23 @@ pub enum PluginCommand {
     /// Clear all virtual texts from a buffer
     ClearVirtualTexts { buffer_id: BufferId },
 
+    /// Create a marker: a position in a buffer that moves with edits
+    CreateMarker {
+        buffer_id: BufferId,
+        /// ID the plugin refers to the marker by
+        marker_id: u64,
+        position: usize,
+        /// Plugin that owns the marker (deleted when the plugin is unloaded)
+        plugin_name: String,
+    },
+
+    /// Delete a marker created with `CreateMarker`
+    DeleteMarker { marker_id: u64 },
+
+    /// Get the current byte offset of a marker (async, resolves to a number,
+    /// or null if the marker or its buffer is gone)
+    GetMarkerPosition { marker_id: u64, request_id: u64 },
+
     /// Add a virtual LINE (full line above/below a position)
     /// Used for git blame headers, code coverage, inline documentation, etc.
     /// These lines do NOT show line numbers in the gutter.
@@ -947,6 +1839,65 @@ pub enum PluginCommand {
         namespace: String,
     },
 
+    /// Register a fixed-width gutter column for a namespace
+    /// Registering an existing namespace again changes its width
+    RegisterGutter {
+        plugin_name: String,
+        /// Namespace for grouping (e.g., "blame", "coverage")
+        namespace: String,
+        /// Width of the column in cells
+        width: usize,
+    },
+
+    /// Remove a gutter column and its text from every buffer
+    UnregisterGutter { namespace: String },
+
+    /// Set the text of a line in a gutter column
+    SetGutterText {
+        buffer_id: BufferId,
+        namespace: String,
+        /// Line number (0-indexed)
+        line: usize,
+        text: String,
+        color: Option<OverlayColorSpec>,
+    },
+
+    /// Replace all text of a gutter column in a buffer
+    SetGutterTexts {
+        buffer_id: BufferId,
+        namespace: String,
+        lines: Vec<GutterText>,
+    },
+
+    /// Clear all text of a gutter column in a buffer
+    ClearGutterText {
+        buffer_id: BufferId,
+        namespace: String,
+    },
+
+    /// Tint the background of a whole line
+    SetLineBackground {
+        buffer_id: BufferId,
+        /// Namespace for grouping (e.g., "inline-diff")
+        namespace: String,
+        /// Line number (0-indexed)
+        line: usize,
+        color: OverlayColorSpec,
+    },
+
+    /// Replace all line backgrounds of a namespace
+    SetLineBackgrounds {
+        buffer_id: BufferId,
+        namespace: String,
+        lines: Vec<LineBackground>,
+    },
+
+    /// Clear all line backgrounds for a specific namespace
+    ClearLineBackgrounds {
+        buffer_id: BufferId,
+        namespace: String,
+    },
+
     /// Set file explorer decorations for a namespace
     SetFileExplorerDecorations {
         /// Namespace for grouping (e.g., "git-status")
@@ -961,6 +1912,9 @@ pub enum PluginCommand {
         namespace: String,
     },
 
+    /// Show the file explorer with a file or directory expanded and selected
+    RevealInExplorer { path: PathBuf },
+
     /// Open a file at a specific line and column
     /// Line and column are 1-indexed to match git grep output
     OpenFileAtLocation {
@@ -1000,6 +1954,37 @@ pub enum PluginCommand {
         callback_id: JsCallbackId,
     },
 
+    /// Show a filterable list and resolve the callback with the chosen
+    /// item(s), or null if cancelled
+    StartQuickPick {
+        items: Vec<QuickPickItem>,
+        options: QuickPickOptions,
+        callback_id: JsCallbackId,
+    },
+
+    /// Show a message with a list of buttons and resolve the callback with
+    /// the chosen button's label, or null if dismissed
+    ShowConfirm {
+        message: String,
+        buttons: Vec<String>,
+        callback_id: JsCallbackId,
+    },
+
+    /// Read a line of text in the prompt and resolve the callback with it,
+    /// or null if cancelled
+    ShowInput {
+        prompt: String,
+        options: InputOptions,
+        callback_id: JsCallbackId,
+    },
+
+    /// Show the file browser and resolve the callback with the chosen
+    /// path, or null if cancelled
+    OpenFileBrowser {
+        options: FileBrowserOptions,
+        callback_id: JsCallbackId,
+    },
+
     /// Update the suggestions list for the current prompt
     /// Uses the editor's Suggestion type
     SetPromptSuggestions { suggestions: Vec<Suggestion> },
@@ -1024,6 +2009,27 @@ pub enum PluginCommand {
     /// Remove a top-level menu
     RemoveMenu { menu_label: String },
 
+    /// Add an item to a right-click context menu
+    AddContextMenuItem { item: ContextMenuItem },
+
+    /// Add a plugin-owned item to a menu bar menu, creating the menu and any
+    /// submenus on its path as needed
+    AddPluginMenuItem { item: PluginMenuItem },
+
+    /// Remove an item a plugin added to the menu bar
+    RemovePluginMenuItem {
+        plugin_name: String,
+        path: String,
+        label: String,
+    },
+
+    /// Remove an item a plugin added to a right-click context menu
+    RemoveContextMenuItem {
+        plugin_name: String,
+        menu: String,
+        label: String,
+    },
+
     /// Create a new virtual buffer (not backed by a file)
     CreateVirtualBuffer {
         /// Display name (e.g., "*Diagnostics*")
@@ -1054,6 +2060,8 @@ pub enum PluginCommand {
         editing_disabled: bool,
         /// Whether this buffer should be hidden from tabs (for composite source buffers)
         hidden_from_tabs: bool,
+        /// Per-buffer display overrides (wrap column, tab width, conceal rules, ...)
+        display: Option<BufferDisplayOptions>,
         /// Optional request ID for async response
         request_id: Option<u64>,
     },
@@ -1083,6 +2091,8 @@ pub enum PluginCommand {
         editing_disabled: bool,
         /// Whether line wrapping is enabled for this split (None = use global setting)
         line_wrap: Option<bool>,
+        /// Per-buffer display overrides (wrap column, tab width, conceal rules, ...)
+        display: Option<BufferDisplayOptions>,
         /// Optional request ID for async response (if set, editor will send back buffer ID)
         request_id: Option<u64>,
     },
@@ -1128,6 +2138,8 @@ pub enum PluginCommand {
         editing_disabled: bool,
         /// Whether line wrapping is enabled for this split (None = use global setting)
         line_wrap: Option<bool>,
+        /// Per-buffer display overrides (wrap column, tab width, conceal rules, ...)
+        display: Option<BufferDisplayOptions>,
         /// Optional request ID for async response
         request_id: Option<u64>,
     },
@@ -1190,12 +2202,37 @@ pub enum PluginCommand {
         ratio: f32,
     },
 
+    /// Draw a split with another theme than the editor's
+    SetSplitTheme {
+        split_id: SplitId,
+        /// Theme name, or None for the editor theme
+        theme: Option<String>,
+    },
+
     /// Distribute splits evenly - make all given splits equal size
     DistributeSplitsEvenly {
         /// Split IDs to distribute evenly
         split_ids: Vec<SplitId>,
     },
 
+    /// Get the split layout tree (async, resolves to `LayoutNode`)
+    GetLayout {
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Split a pane or container (async, resolves to the new split ID)
+    SplitEditor {
+        /// Split to divide; a container is split as a whole
+        split_id: SplitId,
+        /// "horizontal" (stacked) or "vertical" (side by side)
+        direction: String,
+        /// Buffer for the new pane (default: the buffer of the split)
+        buffer_id: Option<BufferId>,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
     /// Set cursor position in a buffer (also scrolls viewport to show cursor)
     SetBufferCursor {
         buffer_id: BufferId,
@@ -1203,6 +2240,18 @@ pub enum PluginCommand {
         position: usize,
     },
 
+    /// Replace the active buffer's cursors with one per selection
+    SetSelections { selections: Vec<SelectionSpec> },
+
+    /// Add a cursor to the active buffer (it becomes the primary cursor)
+    AddCursor {
+        /// Byte offset position for the cursor
+        position: usize,
+    },
+
+    /// Remove all cursors of the active buffer except the original one
+    ClearSecondaryCursors,
+
     /// Send an arbitrary LSP request and return the raw JSON response
     SendLspRequest {
         language: String,
@@ -1247,6 +2296,13 @@ pub enum PluginCommand {
         actions: Vec<ActionSpec>,
     },
 
+    /// Search the active buffer or the project, as if the pattern had been
+    /// entered in the search prompt
+    RunSearch {
+        pattern: String,
+        options: SearchOptions,
+    },
+
     /// Get text from a buffer range (for yank operations)
     GetBufferText {
         /// Buffer ID
@@ -1259,6 +2315,15 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Compute a diff between two texts with the editor's diff engine (async)
+    ComputeDiff {
+        old_text: String,
+        new_text: String,
+        options: ComputeDiffOptions,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
     /// Get byte offset of the start of a line (async)
     /// Line is 0-indexed (0 = first line)
     GetLineStartPosition {
@@ -1270,6 +2335,62 @@ pub enum PluginCommand {
         request_id: u64,
     },
 
+    /// Get lines `start_line..end_line` of a buffer, without their line
+    /// endings (async); lines past the end of the buffer are left out
+    GetBufferLines {
+        buffer_id: BufferId,
+        /// First line (0-indexed)
+        start_line: usize,
+        /// Line after the last one (exclusive)
+        end_line: usize,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Get the number of lines in a buffer (async)
+    GetLineCount {
+        buffer_id: BufferId,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Convert a byte offset in a buffer to a 0-indexed line and byte
+    /// column (async)
+    OffsetToLineCol {
+        buffer_id: BufferId,
+        offset: usize,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Convert a 0-indexed line and byte column in a buffer to a byte
+    /// offset (async)
+    LineColToOffset {
+        buffer_id: BufferId,
+        line: usize,
+        column: usize,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Run a tree-sitter query against a buffer's syntax tree (async)
+    QuerySyntax {
+        buffer_id: BufferId,
+        /// Query in tree-sitter's S-expression query language
+        query: String,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
+    /// Find the matches of a pattern in a buffer (async)
+    SearchBuffer {
+        buffer_id: BufferId,
+        pattern: String,
+        options: BufferSearchOptions,
+        /// Request ID for async response
+        request_id: u64,
+    },
+
     /// Set the global editor mode (for modal editing like vi mode)
     /// When set, the mode's keybindings take precedence over normal editing
     SetEditorMode {
@@ -1318,13 +2439,33 @@ pub enum PluginCommand {
         right_split: SplitId,
     },
 
+    /// Add a split to a scroll sync group
+    /// The group's anchors are cleared, so it scrolls proportionally until new
+    /// anchors are set
+    AddScrollSyncSplit {
+        /// The group ID returned by CreateScrollSyncGroup
+        group_id: u32,
+        /// The split to add, after the existing members
+        split_id: SplitId,
+    },
+
+    /// Remove a split from a scroll sync group
+    RemoveScrollSyncSplit {
+        /// The group ID returned by CreateScrollSyncGroup
+        group_id: u32,
+        /// The split to remove
+        split_id: SplitId,
+    },
+
     /// Set sync anchors for a scroll sync group
-    /// Anchors map corresponding line numbers between left and right buffers
+    /// Anchors map corresponding line numbers between the group's buffers.
+    /// Without anchors the group scrolls proportionally.
     SetScrollSyncAnchors {
         /// The group ID returned by CreateScrollSyncGroup
         group_id: u32,
-        /// List of (left_line, right_line) pairs marking corresponding positions
-        anchors: Vec<(usize, usize)>,
+        /// Line numbers marking corresponding positions, one per split in the
+        /// group's order (left, right, then splits added later)
+        anchors: Vec<Vec<usize>>,
     },
 
     /// Remove a scroll sync group
@@ -1412,6 +2553,15 @@ pub enum PluginCommand {
     /// Reload the grammar registry to apply registered grammars
     /// Call this after registering one or more grammars to rebuild the syntax set
     ReloadGrammars,
+
+    /// Store a value under `key` for a plugin in the session file,
+    /// or remove the key when `value` is None
+    SetSessionData {
+        plugin_name: String,
+        key: String,
+        #[ts(type = "any")]
+        value: Option<serde_json::Value>,
+    },
 }
 
 // =============================================================================
@@ -1495,6 +2645,26 @@ pub struct LspServerPackConfig {
     pub initialization_options: Option<JsonValue>,
 }
 
+/// Seed colors for `generateTheme`
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+#[ts(export)]
+pub struct ThemeSeed {
+    /// Theme name (defaults to "generated")
+    #[serde(default)]
+    #[ts(optional)]
+    pub name: Option<String>,
+
+    /// Background color as a hex string (e.g., "#1e1e2e")
+    pub bg: String,
+
+    /// Foreground color as a hex string
+    pub fg: String,
+
+    /// Accent color used for the cursor, selection and highlights
+    pub accent: String,
+}
+
 /// Hunk status for Review Diff
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
 #[ts(export)]
@@ -1568,6 +2738,22 @@ pub struct SpawnResult {
     pub exit_code: i32,
 }
 
+/// Response from `editor.fetch`
+///
+/// HTTP error statuses resolve normally; the promise is rejected only when
+/// the request could not be made or its response could not be read.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct FetchResponse {
+    /// HTTP status code
+    pub status: u16,
+    /// Response headers, with lowercase names
+    #[ts(type = "Record<string, string>")]
+    pub headers: HashMap<String, String>,
+    /// Response body as text (invalid UTF-8 is replaced)
+    pub body: String,
+}
+
 /// Result from spawning a background process
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
@@ -1642,6 +2828,49 @@ pub struct JsDiagnostic {
     pub source: Option<String>,
 }
 
+/// Rule hiding (or replacing) matching text when a buffer is displayed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields)]
+#[ts(export)]
+pub struct ConcealRule {
+    /// Regular expression matched against each line
+    pub pattern: String,
+    /// Text shown in place of each match (default: hide the match)
+    #[serde(default)]
+    #[ts(optional)]
+    pub replacement: Option<String>,
+}
+
+/// Per-buffer display overrides set by plugins
+///
+/// Fields left unset keep their current value.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, TS)]
+#[serde(deny_unknown_fields, rename_all = "camelCase")]
+#[ts(export, rename_all = "camelCase")]
+pub struct BufferDisplayOptions {
+    /// Wrap long lines at this column instead of the window edge while line
+    /// wrapping is on (0 = window edge)
+    #[serde(default)]
+    #[ts(optional)]
+    pub wrap_column: Option<u16>,
+    /// Enable or disable line wrapping in the splits showing the buffer
+    #[serde(default)]
+    #[ts(optional)]
+    pub line_wrap: Option<bool>,
+    /// Display width of a tab character
+    #[serde(default)]
+    #[ts(optional)]
+    pub tab_width: Option<usize>,
+    /// Show whitespace indicators for tab characters
+    #[serde(default)]
+    #[ts(optional)]
+    pub show_whitespace: Option<bool>,
+    /// Conceal rules, replacing any previous ones (`[]` removes them)
+    #[serde(default)]
+    #[ts(optional)]
+    pub conceal: Option<Vec<ConcealRule>>,
+}
+
 /// Options for createVirtualBuffer
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(deny_unknown_fields)]
@@ -1677,6 +2906,10 @@ pub struct CreateVirtualBufferOptions {
     #[serde(default)]
     #[ts(optional)]
     pub entries: Option<Vec<JsTextPropertyEntry>>,
+    /// Display overrides (wrap column, tab width, conceal rules, ...)
+    #[serde(default)]
+    #[ts(optional)]
+    pub display: Option<BufferDisplayOptions>,
 }
 
 /// Options for createVirtualBufferInSplit
@@ -1726,6 +2959,10 @@ pub struct CreateVirtualBufferInSplitOptions {
     #[serde(default)]
     #[ts(optional)]
     pub entries: Option<Vec<JsTextPropertyEntry>>,
+    /// Display overrides (wrap column, tab width, conceal rules, ...)
+    #[serde(default)]
+    #[ts(optional)]
+    pub display: Option<BufferDisplayOptions>,
 }
 
 /// Options for createVirtualBufferInExistingSplit
@@ -1767,6 +3004,10 @@ pub struct CreateVirtualBufferInExistingSplitOptions {
     #[serde(default)]
     #[ts(optional)]
     pub entries: Option<Vec<JsTextPropertyEntry>>,
+    /// Display overrides (wrap column, tab width, conceal rules, ...)
+    #[serde(default)]
+    #[ts(optional)]
+    pub display: Option<BufferDisplayOptions>,
 }
 
 /// Result of getTextPropertiesAtCursor - array of property objects
@@ -1793,6 +3034,16 @@ mod fromjs_impls {
         }
     }
 
+    impl<'js> FromJs<'js> for BufferDisplayOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "BufferDisplayOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
     impl<'js> FromJs<'js> for CreateVirtualBufferOptions {
         fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
             rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
@@ -1842,6 +3093,56 @@ mod fromjs_impls {
         }
     }
 
+    impl<'js> FromJs<'js> for OverlayColorSpec {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "value",
+                to: "OverlayColorSpec",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for GutterText {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "GutterText",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for LineBackground {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "LineBackground",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for BufferEdit {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "BufferEdit",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for SelectionSpec {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "SelectionSpec",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
     impl<'js> FromJs<'js> for ActionPopupAction {
         fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
             rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
@@ -1892,6 +3193,126 @@ mod fromjs_impls {
         }
     }
 
+    impl<'js> FromJs<'js> for ComputeDiffOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "ComputeDiffOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for SearchOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "SearchOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for BufferSearchOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "BufferSearchOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for EventOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "EventOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for QuickPickItem {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "QuickPickItem",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for CompletionItem {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "CompletionItem",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for QuickPickOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "QuickPickOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for InputOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "InputOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for MenuItemOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "MenuItemOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for PickerOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "PickerOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for FetchOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "FetchOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
+    impl<'js> FromJs<'js> for FileBrowserOptions {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "FileBrowserOptions",
+                message: Some(e.to_string()),
+            })
+        }
+    }
+
     impl<'js> FromJs<'js> for CreateCompositeBufferOptions {
         fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
             // Use two-step deserialization for complex nested structures
@@ -1938,6 +3359,16 @@ mod fromjs_impls {
             })
         }
     }
+
+    impl<'js> FromJs<'js> for ThemeSeed {
+        fn from_js(_ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+            rquickjs_serde::from_value(value).map_err(|e| rquickjs::Error::FromJs {
+                from: "object",
+                to: "ThemeSeed",
+                message: Some(e.to_string()),
+            })
+        }
+    }
 }
 
 /// Plugin API context - provides safe access to editor functionality
@@ -2013,12 +3444,17 @@ impl PluginApi {
             buffer_id,
             position,
             text,
+            base_version: None,
         })
     }
 
     /// Delete a range of text from a buffer
     pub fn delete_range(&self, buffer_id: BufferId, range: Range<usize>) -> Result<(), String> {
-        self.send_command(PluginCommand::DeleteRange { buffer_id, range })
+        self.send_command(PluginCommand::DeleteRange {
+            buffer_id,
+            range,
+            base_version: None,
+        })
     }
 
     /// Add an overlay (decoration) to a buffer
@@ -2193,6 +3629,7 @@ impl PluginApi {
             show_cursors: true,
             editing_disabled: false,
             hidden_from_tabs: false,
+            display: None,
             request_id: None,
         })
     }
@@ -2375,10 +3812,12 @@ mod tests {
                 buffer_id,
                 position,
                 text,
+                base_version,
             } => {
                 assert_eq!(buffer_id.0, 1);
                 assert_eq!(position, 0);
                 assert_eq!(text, "test");
+                assert_eq!(base_version, None);
             }
             _ => panic!("Wrong command type"),
         }
@@ -2488,6 +3927,9 @@ mod tests {
                 path: Some(std::path::PathBuf::from("/test/file.txt")),
                 modified: true,
                 length: 100,
+                version: 0,
+                undo_depth: 0,
+                read_only: false,
             };
             snapshot.buffers.insert(BufferId(1), buffer_info);
         }
@@ -2527,6 +3969,9 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file1.txt")),
                     modified: false,
                     length: 50,
+                    version: 0,
+                    undo_depth: 0,
+                    read_only: false,
                 },
             );
             snapshot.buffers.insert(
@@ -2536,6 +3981,9 @@ mod tests {
                     path: Some(std::path::PathBuf::from("/file2.txt")),
                     modified: true,
                     length: 100,
+                    version: 0,
+                    undo_depth: 0,
+                    read_only: false,
                 },
             );
             snapshot.buffers.insert(
@@ -2545,6 +3993,9 @@ mod tests {
                     path: None,
                     modified: false,
                     length: 0,
+                    version: 0,
+                    undo_depth: 0,
+                    read_only: false,
                 },
             );
         }
@@ -2713,4 +4164,23 @@ mod tests {
             err
         );
     }
+
+    #[test]
+    fn test_plugin_error_class_and_code() {
+        let error = PluginError::OutOfRange {
+            buffer_id: BufferId(2),
+            range: 5..20,
+            length: 10,
+        };
+        assert_eq!(error.class_name(), "OutOfRangeError");
+        assert_eq!(error.code(), "OUT_OF_RANGE");
+        assert_eq!(
+            error.to_string(),
+            "range 5..20 is outside buffer 2 (length 10)"
+        );
+
+        let error = PluginError::ChannelClosed;
+        assert_eq!(error.class_name(), "ChannelClosedError");
+        assert_eq!(error.code(), "CHANNEL_CLOSED");
+    }
 }
@@ -22,6 +22,11 @@ pub struct PluginConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[schemars(extend("readOnly" = true))]
     pub path: Option<PathBuf>,
+
+    /// Permissions from the plugin's `plugin.json` that the user has allowed
+    /// (e.g. "fs-read", "process")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<String>,
 }
 
 impl Default for PluginConfig {
@@ -29,6 +34,7 @@ impl Default for PluginConfig {
         Self {
             enabled: true,
             path: None,
+            permissions: Vec::new(),
         }
     }
 }
@@ -38,6 +44,7 @@ impl PluginConfig {
         Self {
             enabled: true,
             path: Some(path),
+            permissions: Vec::new(),
         }
     }
 }
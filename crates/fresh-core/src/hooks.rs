@@ -3,15 +3,22 @@
 //! Hooks allow plugins to subscribe to editor events and react to them.
 
 use anyhow::Result;
+use fresh_plugin_api_macros::plugin_api_events;
 use std::collections::HashMap;
 use std::ops::Range;
 use std::path::PathBuf;
+use ts_rs::TS;
 
 use crate::action::Action;
 use crate::api::{ViewTokenWire, ViewTokenWireKind};
 use crate::{BufferId, CursorId, SplitId};
 
 /// Arguments passed to hook callbacks
+///
+/// Plugins receive them as the `EditorEventMap` payloads generated by
+/// `#[plugin_api_events]`; keep the field annotations in step with
+/// `hook_args_to_json`.
+#[plugin_api_events]
 #[derive(Debug, Clone, serde::Serialize)]
 pub enum HookArgs {
     /// Before a file is opened
@@ -56,12 +63,14 @@ pub enum HookArgs {
     /// Before text is deleted
     BeforeDelete {
         buffer_id: BufferId,
+        #[plugin_api(ts_fields = "start: number; end: number")]
         range: Range<usize>,
     },
 
     /// After text was deleted
     AfterDelete {
         buffer_id: BufferId,
+        #[plugin_api(ts_fields = "start: number; end: number")]
         range: Range<usize>,
         deleted_text: String,
         /// Byte position where the deletion occurred
@@ -76,9 +85,21 @@ pub enum HookArgs {
         lines_removed: usize,
     },
 
+    /// A buffer's text changed; fired once per editor tick with every
+    /// change made since the last time, whoever made it
+    BufferChanged {
+        buffer_id: BufferId,
+        /// Apply in order: each range refers to the buffer after the
+        /// changes before it
+        changes: Vec<BufferChange>,
+        /// Buffer version after the changes (`getBufferInfo(id).version`)
+        version: u64,
+    },
+
     /// Cursor moved to a new position
     CursorMoved {
         buffer_id: BufferId,
+        #[plugin_api(ts_type = "number")]
         cursor_id: CursorId,
         old_position: usize,
         new_position: usize,
@@ -87,7 +108,22 @@ pub enum HookArgs {
     },
 
     /// Buffer became active
-    BufferActivated { buffer_id: BufferId },
+    BufferActivated {
+        buffer_id: BufferId,
+        /// Buffer that was active before, if any
+        previous_buffer_id: Option<BufferId>,
+        /// Split the buffer became active in
+        split_id: SplitId,
+    },
+
+    /// Another split received focus
+    SplitFocused {
+        split_id: SplitId,
+        /// Split that had focus before (it may since have been closed)
+        previous_split_id: SplitId,
+        /// Buffer shown in the newly focused split
+        buffer_id: BufferId,
+    },
 
     /// Buffer was deactivated
     BufferDeactivated { buffer_id: BufferId },
@@ -101,10 +137,16 @@ pub enum HookArgs {
     },
 
     /// Before a command/action is executed
-    PreCommand { action: Action },
+    PreCommand {
+        #[plugin_api(ts_type = "string")]
+        action: Action,
+    },
 
     /// After a command/action was executed
-    PostCommand { action: Action },
+    PostCommand {
+        #[plugin_api(ts_type = "string")]
+        action: Action,
+    },
 
     /// Editor has been idle for N milliseconds (no input)
     Idle { milliseconds: u64 },
@@ -150,7 +192,10 @@ pub enum HookArgs {
     },
 
     /// Request keyboard shortcuts data (key, action) for the help buffer
-    KeyboardShortcuts { bindings: Vec<(String, String)> },
+    KeyboardShortcuts {
+        #[plugin_api(ts_type = "{ key: string; action: string }[]")]
+        bindings: Vec<(String, String)>,
+    },
 
     /// LSP find references response received
     LspReferences {
@@ -256,10 +301,30 @@ pub enum HookArgs {
         /// The output data
         data: String,
     },
+
+    /// The UI locale was changed at runtime
+    LocaleChanged {
+        /// The newly active locale code (e.g. "de", "pt-BR")
+        locale: String,
+    },
+
+    /// A saved session was restored; data stored with `setSessionData`
+    /// is available from `getSessionData`
+    SessionRestored,
+}
+
+/// A change to a buffer's text for the BufferChanged hook
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, TS)]
+pub struct BufferChange {
+    /// Byte range that was replaced
+    #[ts(type = "{ start: number; end: number }")]
+    pub range: Range<usize>,
+    /// Text put in its place (empty for a deletion)
+    pub text: String,
 }
 
 /// Information about a single line for the LinesChanged hook
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, TS)]
 pub struct LineInfo {
     /// Line number (0-based)
     pub line_number: usize,
@@ -272,7 +337,7 @@ pub struct LineInfo {
 }
 
 /// Location information for LSP references
-#[derive(Debug, Clone, serde::Serialize)]
+#[derive(Debug, Clone, serde::Serialize, TS)]
 pub struct LspLocation {
     /// File path
     pub file: String,
@@ -364,8 +429,27 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "content": content,
             })
         }
-        HookArgs::BufferActivated { buffer_id } => {
-            serde_json::json!({ "buffer_id": buffer_id.0 })
+        HookArgs::BufferActivated {
+            buffer_id,
+            previous_buffer_id,
+            split_id,
+        } => {
+            serde_json::json!({
+                "buffer_id": buffer_id.0,
+                "previous_buffer_id": previous_buffer_id.map(|id| id.0),
+                "split_id": split_id.0,
+            })
+        }
+        HookArgs::SplitFocused {
+            split_id,
+            previous_split_id,
+            buffer_id,
+        } => {
+            serde_json::json!({
+                "split_id": split_id.0,
+                "previous_split_id": previous_split_id.0,
+                "buffer_id": buffer_id.0,
+            })
         }
         HookArgs::BufferDeactivated { buffer_id } => {
             serde_json::json!({ "buffer_id": buffer_id.0 })
@@ -683,6 +767,23 @@ pub fn hook_args_to_json(args: &HookArgs) -> Result<String> {
                 "data": data,
             })
         }
+        HookArgs::LocaleChanged { locale } => {
+            serde_json::json!({ "locale": locale })
+        }
+        HookArgs::SessionRestored => {
+            serde_json::json!({})
+        }
+        HookArgs::BufferChanged {
+            buffer_id,
+            changes,
+            version,
+        } => {
+            serde_json::json!({
+                "buffer_id": buffer_id.0,
+                "changes": changes,
+                "version": version,
+            })
+        }
     };
 
     serde_json::to_string(&json_value)
@@ -24,6 +24,9 @@ pub trait PluginServiceBridge: Send + Sync + 'static {
     /// Get a list of builtin theme names
     fn get_builtin_themes(&self) -> serde_json::Value;
 
+    /// Generate theme JSON from seed colors
+    fn generate_theme(&self, seed: &crate::api::ThemeSeed) -> Result<serde_json::Value, String>;
+
     /// Register custom i18n strings for a plugin
     fn register_plugin_strings(
         &self,
@@ -75,6 +78,9 @@ impl PluginServiceBridge for NoopServiceBridge {
     fn get_builtin_themes(&self) -> serde_json::Value {
         serde_json::Value::Null
     }
+    fn generate_theme(&self, _seed: &crate::api::ThemeSeed) -> Result<serde_json::Value, String> {
+        Err("theme generation is not available".to_string())
+    }
     fn register_plugin_strings(
         &self,
         _plugin_name: &str,
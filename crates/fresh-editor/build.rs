@@ -177,7 +177,7 @@ fn collect_theme_files(
     Ok(())
 }
 
-/// Generate a Rust file with the list of available locales from the locales directory
+/// Generate Rust files with the list of available locales and their embedded locale files
 fn generate_locale_options() -> Result<(), Box<dyn std::error::Error>> {
     let locales_dir = Path::new("locales");
 
@@ -221,6 +221,30 @@ pub const GENERATED_LOCALE_OPTIONS: &[Option<&str>] = &[
     // but it doesn't hurt to use it for consistency
     fs::write(&dest_path, content)?;
 
+    // Embed the locale files themselves; they are parsed when the first string is translated
+    let dest_path = Path::new(&out_dir).join("locale_files.rs");
+    let file_entries: Vec<String> = locales
+        .iter()
+        .map(|l| {
+            format!(
+                r#"("{}", include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/locales/{}.json")))"#,
+                l, l
+            )
+        })
+        .collect();
+    let content = format!(
+        r#"// Auto-generated by build.rs from locales/*.json files
+// DO NOT EDIT MANUALLY
+
+/// Locale files embedded in the binary, as (locale, JSON source) pairs
+pub const LOCALE_FILES: &[(&str, &str)] = &[
+    {}
+];
+"#,
+        file_entries.join(",\n    ")
+    );
+    fs::write(&dest_path, content)?;
+
     println!(
         "cargo::warning=Generated locale options with {} locales",
         locales.len()
@@ -8,12 +8,20 @@ const editor = getEditor();
  * Shows indicators in the gutter for lines that have been modified since the last save.
  * This tracks in-memory changes, not git changes.
  *
- * This plugin uses a simpler approach: it marks lines as modified when edits happen
- * (after_insert/after_delete hooks), and clears all modified markers on save.
- * It doesn't compare content - it just tracks which lines have been touched since save.
+ * Once edits pause, and the editor's saved-diff reports a change, the buffer is
+ * diffed line by line against the saved file and every hunk is marked by type. The indicators live
+ * on the buffer, so every split showing it gets them. Without line information
+ * (large file mode) or a readable saved file, the changed lines are marked as
+ * modified instead. Inside modified lines, the words that changed are
+ * highlighted as well.
  *
  * Indicator symbols:
- * - │ (blue): Line has been modified since last save
+ * - │ (cyan): Line added since last save
+ * - │ (blue): Line modified since last save
+ * - ▴ (violet): Line(s) deleted above
+ *
+ * Commands move to the next/previous change and revert the change under the
+ * cursor to its saved text.
  */
 
 // =============================================================================
@@ -22,20 +30,45 @@ const editor = getEditor();
 
 const NAMESPACE = "buffer-modified";
 const PRIORITY = 5; // Lower than git-gutter (10) and diagnostics
+const WORDS_NAMESPACE = "buffer-modified-words";
+/** Modified hunks longer than this aren't word-diffed */
+const MAX_WORD_DIFF_BYTES = 10000;
+/** Edits within this many milliseconds share a single diff of the buffer */
+const REFRESH_DELAY_MS = 150;
+
+// Colors (RGB) - Blue tones to distinguish from git gutter (green/yellow/red)
+const COLORS = {
+  added: [80, 200, 220] as [number, number, number],    // Cyan
+  modified: [100, 149, 237] as [number, number, number], // Cornflower blue
+  deleted: [190, 120, 230] as [number, number, number],  // Violet
+};
 
-// Colors (RGB) - Blue to distinguish from git gutter (green/yellow/red)
-const COLOR = [100, 149, 237] as [number, number, number]; // Cornflower blue
-
-// Symbol
-const SYMBOL = "│";
+// Symbols
+const SYMBOLS = {
+  added: "│",
+  modified: "│",
+  deleted: "▴",
+};
 
 // =============================================================================
 // Types
 // =============================================================================
 
+type ChangeKind = "added" | "modified" | "deleted";
+
 interface BufferState {
   /** Whether we're tracking this buffer */
   tracking: boolean;
+  /** File path for this buffer */
+  filePath: string;
+  /** Content of the file at the last open/save, or null if it couldn't be read */
+  savedText: string | null;
+  /** Line hunks from the last diff against the saved content */
+  hunks: DiffHunk[];
+  /** Bumped on every refresh so that stale diff results are dropped */
+  generation: number;
+  /** Generation the current `hunks` were computed for */
+  hunksGeneration: number;
 }
 
 // =============================================================================
@@ -45,6 +78,53 @@ interface BufferState {
 /** State per buffer */
 const bufferStates: Map<number, BufferState> = new Map();
 
+// =============================================================================
+// Helpers
+// =============================================================================
+
+/**
+ * Get UTF-8 byte length of a string
+ */
+function getByteLength(str: string): number {
+  let len = 0;
+  for (let i = 0; i < str.length; i++) {
+    const code = str.charCodeAt(i);
+    if (code < 0x80) len += 1;
+    else if (code < 0x800) len += 2;
+    else if (code >= 0xd800 && code <= 0xdbff) {
+      len += 4;
+      i++;
+    } else len += 3;
+  }
+  return len;
+}
+
+function hunkKind(hunk: DiffHunk): ChangeKind {
+  if (hunk.oldCount === 0) return "added";
+  if (hunk.newCount === 0) return "deleted";
+  return "modified";
+}
+
+/**
+ * First and last line (inclusive) carrying a hunk's indicator.
+ * A deletion is marked on the line that followed the deleted text.
+ */
+function hunkLines(hunk: DiffHunk): [number, number] {
+  if (hunk.newCount === 0) return [hunk.newStart, hunk.newStart];
+  return [hunk.newStart, hunk.newStart + hunk.newCount - 1];
+}
+
+/**
+ * Line (0-indexed) of the primary cursor in a buffer
+ */
+async function cursorLine(bufferId: number): Promise<number> {
+  const { line } = await editor.offsetToLineCol(
+    bufferId,
+    editor.getCursorPosition()
+  );
+  return line;
+}
+
 // =============================================================================
 // Line Tracking
 // =============================================================================
@@ -53,12 +133,18 @@ const bufferStates: Map<number, BufferState> = new Map();
  * Initialize state for a buffer (on file open)
  * Starts with no modified lines since file was just loaded
  */
-function initBufferState(bufferId: number): void {
+function initBufferState(bufferId: number, filePath: string): void {
   bufferStates.set(bufferId, {
     tracking: true,
+    filePath,
+    savedText: editor.readFile(filePath),
+    hunks: [],
+    generation: 0,
+    hunksGeneration: 0,
   });
   // Clear any leftover indicators
   editor.clearLineIndicators(bufferId, NAMESPACE);
+  editor.clearNamespace(bufferId, WORDS_NAMESPACE);
 }
 
 /**
@@ -66,7 +152,28 @@ function initBufferState(bufferId: number): void {
  * Removes all modified markers since buffer now matches disk
  */
 function clearModifiedState(bufferId: number): void {
+  const state = bufferStates.get(bufferId);
+  if (state) {
+    state.savedText = editor.readFile(state.filePath);
+    state.hunks = [];
+    state.hunksGeneration = ++state.generation;
+  }
   editor.clearLineIndicators(bufferId, NAMESPACE);
+  editor.clearNamespace(bufferId, WORDS_NAMESPACE);
+}
+
+function setIndicator(bufferId: number, line: number, kind: ChangeKind): void {
+  const color = COLORS[kind];
+  editor.setLineIndicator(
+    bufferId,
+    line,
+    NAMESPACE,
+    SYMBOLS[kind],
+    color[0],
+    color[1],
+    color[2],
+    PRIORITY
+  );
 }
 
 /**
@@ -83,49 +190,128 @@ function markLinesModified(bufferId: number, startLine: number, endLine: number)
   // Add indicator for each affected line
   // Note: If an indicator already exists at this position, it will be updated
   for (let line = startLine; line <= endLine; line++) {
-    editor.setLineIndicator(
-      bufferId,
-      line,
-      NAMESPACE,
-      SYMBOL,
-      COLOR[0],
-      COLOR[1],
-      COLOR[2],
-      PRIORITY
-    );
+    setIndicator(bufferId, line, "modified");
+  }
+}
+
+/**
+ * Replace the indicators with one per hunk line, typed by change kind
+ */
+function applyHunkIndicators(bufferId: number, hunks: DiffHunk[]): void {
+  editor.clearLineIndicators(bufferId, NAMESPACE);
+  for (const hunk of hunks) {
+    const kind = hunkKind(hunk);
+    const [start, end] = hunkLines(hunk);
+    for (let line = start; line <= end; line++) {
+      setIndicator(bufferId, line, kind);
+    }
   }
 }
 
-function reapplyIndicatorsFromDiff(bufferId: number): void {
+/**
+ * Highlight the words that changed inside each modified hunk
+ *
+ * Returns false if the buffer was edited again or closed meanwhile.
+ */
+async function applyWordHighlights(
+  bufferId: number,
+  state: BufferState,
+  generation: number,
+  hunks: DiffHunk[]
+): Promise<boolean> {
+  const ranges: [number, number][] = [];
+  for (const hunk of hunks) {
+    if (hunkKind(hunk) !== "modified") continue;
+    if (hunk.oldText.length + hunk.newText.length > MAX_WORD_DIFF_BYTES) continue;
+    const words = (await editor.computeDiff(hunk.oldText, hunk.newText, {
+      granularity: "word",
+    })) as DiffHunk[];
+    for (const word of words) {
+      const length = getByteLength(word.newText);
+      if (length > 0) {
+        const start = hunk.newOffset + word.newOffset;
+        ranges.push([start, start + length]);
+      }
+    }
+  }
+
+  if (bufferStates.get(bufferId) !== state || state.generation !== generation) {
+    return false;
+  }
+  editor.clearNamespace(bufferId, WORDS_NAMESPACE);
+  for (const [start, end] of ranges) {
+    editor.addOverlay(bufferId, WORDS_NAMESPACE, start, end, {
+      bg: "editor.diff_add_highlight_bg",
+    });
+  }
+  return true;
+}
+
+/**
+ * Recompute the indicators of a buffer against its saved content, once no
+ * other edit has followed for `delayMs`
+ */
+async function refreshIndicators(
+  bufferId: number,
+  delayMs: number = REFRESH_DELAY_MS
+): Promise<void> {
+  const state = bufferStates.get(bufferId);
+  if (!state || !state.tracking) return;
+  const generation = ++state.generation;
+
+  // Reading and diffing the whole buffer on every keystroke is too slow for
+  // large files: only the last edit of a burst goes on
+  if (delayMs > 0) {
+    await editor.delay(delayMs);
+    if (bufferStates.get(bufferId) !== state || state.generation !== generation) {
+      return;
+    }
+  }
+
   const diff = editor.getBufferSavedDiff(bufferId);
   if (!diff) return;
 
   // If buffer matches saved snapshot, clear everything.
   if (diff.equal) {
+    state.hunks = [];
+    state.hunksGeneration = generation;
     editor.clearLineIndicators(bufferId, NAMESPACE);
+    editor.clearNamespace(bufferId, WORDS_NAMESPACE);
     return;
   }
 
-  const ranges = diff.line_ranges;
-  // If line info is unavailable, leave existing indicators (best effort).
-  if (!ranges) return;
+  if (state.savedText === null) {
+    // Without the saved text there are no hunks to navigate or revert
+    state.hunks = [];
+    state.hunksGeneration = generation;
 
-  // Reset namespace to drop stale indicators outside the changed ranges.
-  editor.clearLineIndicators(bufferId, NAMESPACE);
-  for (const [start, end] of ranges) {
-    for (let line = start; line < end; line++) {
-      editor.setLineIndicator(
-        bufferId,
-        line,
-        NAMESPACE,
-        SYMBOL,
-        COLOR[0],
-        COLOR[1],
-        COLOR[2],
-        PRIORITY
-      );
+    // If line info is unavailable, leave existing indicators (best effort).
+    const ranges = diff.line_ranges;
+    if (!ranges) return;
+
+    // Reset namespace to drop stale indicators outside the changed ranges.
+    editor.clearLineIndicators(bufferId, NAMESPACE);
+    editor.clearNamespace(bufferId, WORDS_NAMESPACE);
+    for (const [start, end] of ranges) {
+      markLinesModified(bufferId, start, end - 1);
     }
+    return;
+  }
+
+  const current = await editor.getBufferText(
+    bufferId,
+    0,
+    editor.getBufferLength(bufferId)
+  );
+  const hunks = (await editor.computeDiff(state.savedText, current)) as DiffHunk[];
+
+  // Drop the result if the buffer was edited again or closed meanwhile
+  if (!(await applyWordHighlights(bufferId, state, generation, hunks))) {
+    return;
   }
+  state.hunks = hunks;
+  state.hunksGeneration = generation;
+  applyHunkIndicators(bufferId, hunks);
 }
 
 // =============================================================================
@@ -146,7 +332,7 @@ globalThis.onBufferModifiedAfterFileOpen = function (args: {
   }
 
   // Initialize tracking - file just loaded, no modifications yet
-  initBufferState(bufferId);
+  initBufferState(bufferId, args.path);
   editor.debug(editor.t("status.initialized", { path: args.path }));
 
   return true;
@@ -164,7 +350,7 @@ globalThis.onBufferModifiedBufferActivated = function (args: {
   if (!bufferStates.has(bufferId)) {
     const filePath = editor.getBufferPath(bufferId);
     if (filePath && filePath !== "") {
-      initBufferState(bufferId);
+      initBufferState(bufferId, filePath);
     }
   }
 
@@ -191,8 +377,8 @@ globalThis.onBufferModifiedAfterSave = function (args: {
  * Handle after insert - mark affected lines as modified
  *
  * Note: Line indicators automatically track position changes via byte-position markers.
- * We only need to add new indicators for the modified lines; existing indicators
- * will automatically shift to stay on the correct lines.
+ * Touched lines are marked right away; the refresh then replaces them with the
+ * typed markers from the diff once it completes.
  */
 globalThis.onBufferModifiedAfterInsert = function (args: {
   buffer_id: number;
@@ -213,7 +399,7 @@ globalThis.onBufferModifiedAfterInsert = function (args: {
   // Mark all affected lines (from start_line to end_line inclusive)
   // The indicator markers will automatically track their positions
   markLinesModified(bufferId, args.start_line, args.end_line);
-  reapplyIndicatorsFromDiff(bufferId);
+  refreshIndicators(bufferId);
 
   return true;
 };
@@ -244,7 +430,7 @@ globalThis.onBufferModifiedAfterDelete = function (args: {
   // Mark the line where deletion occurred
   // Markers for deleted lines are automatically cleaned up
   markLinesModified(bufferId, args.start_line, args.start_line);
-  reapplyIndicatorsFromDiff(bufferId);
+  refreshIndicators(bufferId);
 
   return true;
 };
@@ -259,6 +445,101 @@ globalThis.onBufferModifiedBufferClosed = function (args: {
   return true;
 };
 
+// =============================================================================
+// Commands
+// =============================================================================
+
+/**
+ * Hunks of the active buffer, or null (with a status message) when there are
+ * none or they are still being recomputed after an edit
+ *
+ * A diff still waiting for edits to pause is run right away.
+ */
+async function activeHunks(): Promise<{ bufferId: number; hunks: DiffHunk[] } | null> {
+  const bufferId = editor.getActiveBufferId();
+  const state = bufferStates.get(bufferId);
+  if (state && state.hunksGeneration !== state.generation) {
+    await refreshIndicators(bufferId, 0);
+  }
+  if (!state || state.hunks.length === 0) {
+    editor.setStatus(editor.t("status.no_changes"));
+    return null;
+  }
+  if (state.hunksGeneration !== state.generation) {
+    editor.setStatus(editor.t("status.updating"));
+    return null;
+  }
+  return { bufferId, hunks: state.hunks };
+}
+
+async function jumpToChange(direction: 1 | -1): Promise<void> {
+  const active = await activeHunks();
+  if (!active) return;
+  const { bufferId, hunks } = active;
+
+  // Wrap around at either end of the buffer
+  const line = await cursorLine(bufferId);
+  let index: number;
+  if (direction > 0) {
+    index = hunks.findIndex((hunk) => hunkLines(hunk)[0] > line);
+    if (index < 0) index = 0;
+  } else {
+    index = -1;
+    hunks.forEach((hunk, i) => {
+      if (hunkLines(hunk)[1] < line) index = i;
+    });
+    if (index < 0) index = hunks.length - 1;
+  }
+
+  editor.setBufferCursor(bufferId, hunks[index].newOffset);
+  editor.setStatus(
+    editor.t("status.change_position", {
+      index: String(index + 1),
+      count: String(hunks.length),
+    })
+  );
+}
+
+globalThis.buffer_modified_next_change = async function (): Promise<void> {
+  await jumpToChange(1);
+};
+
+globalThis.buffer_modified_prev_change = async function (): Promise<void> {
+  await jumpToChange(-1);
+};
+
+/**
+ * Replace the change under the cursor with its saved text
+ */
+globalThis.buffer_modified_revert_change = async function (): Promise<void> {
+  const active = await activeHunks();
+  if (!active) return;
+  const { bufferId, hunks } = active;
+
+  const line = await cursorLine(bufferId);
+  const hunk = hunks.find((h) => {
+    const [start, end] = hunkLines(h);
+    return line >= start && line <= end;
+  });
+  if (!hunk) {
+    editor.setStatus(editor.t("status.no_change_at_cursor"));
+    return;
+  }
+
+  const end = hunk.newOffset + getByteLength(hunk.newText);
+  if (end > hunk.newOffset) {
+    editor.deleteRange(bufferId, hunk.newOffset, end);
+  }
+  if (hunk.oldText.length > 0) {
+    editor.insertText(bufferId, hunk.newOffset, hunk.oldText);
+  }
+  editor.setBufferCursor(bufferId, hunk.newOffset);
+  editor.setStatus(editor.t("status.reverted"));
+
+  // Edits made by a plugin fire no after_insert/after_delete hooks
+  refreshIndicators(bufferId);
+};
+
 // =============================================================================
 // Registration
 // =============================================================================
@@ -271,11 +552,31 @@ editor.on("after_insert", "onBufferModifiedAfterInsert");
 editor.on("after_delete", "onBufferModifiedAfterDelete");
 editor.on("buffer_closed", "onBufferModifiedBufferClosed");
 
+// Register commands
+editor.registerCommand(
+  "%cmd.next_change",
+  "%cmd.next_change_desc",
+  "buffer_modified_next_change",
+  null
+);
+editor.registerCommand(
+  "%cmd.prev_change",
+  "%cmd.prev_change_desc",
+  "buffer_modified_prev_change",
+  null
+);
+editor.registerCommand(
+  "%cmd.revert_change",
+  "%cmd.revert_change_desc",
+  "buffer_modified_revert_change",
+  null
+);
+
 // Initialize for the current buffer
 const initBufferId = editor.getActiveBufferId();
 const initPath = editor.getBufferPath(initBufferId);
 if (initPath && initPath !== "") {
-  initBufferState(initBufferId);
+  initBufferState(initBufferId, initPath);
 }
 
 editor.debug(editor.t("status.loaded"));
@@ -0,0 +1,632 @@
+/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+
+/**
+ * Git Stage Plugin - Pre-commit review of a single file
+ *
+ * Lists the unstaged and staged hunks of the current file next to a live
+ * `git status`, and lets them be staged, unstaged or discarded one at a time:
+ * - n/p: jump to the next/previous hunk
+ * - s: stage the hunk under the cursor
+ * - u: unstage the hunk under the cursor
+ * - x: discard the unstaged hunk under the cursor (asks first)
+ * - c: write the commit message in a scratch buffer; saving it offers to commit
+ *
+ * Hunks are applied with `git apply` from patch files written to the git
+ * directory, so the result is exactly what `git add -p` would produce.
+ */
+
+// =============================================================================
+// Types and Interfaces
+// =============================================================================
+
+type HunkSection = "unstaged" | "staged";
+
+interface DiffHunk {
+  header: string;  // The "@@ -a,b +c,d @@" line
+  lines: string[]; // Body lines, including the leading " ", "+" or "-"
+}
+
+interface FileDiff {
+  header: string[]; // "diff --git", "index", "---" and "+++" lines
+  hunks: DiffHunk[];
+}
+
+interface HunkLocation {
+  section: HunkSection;
+  index: number;
+  offset: number; // Byte offset of the hunk header in the view
+}
+
+interface GitStageState {
+  isOpen: boolean;
+  bufferId: number | null;
+  splitId: number | null;
+  sourceBufferId: number | null;
+  filePath: string | null;
+  gitDir: string | null;
+  unstaged: FileDiff | null;
+  staged: FileDiff | null;
+  status: string[];
+  hunkLocations: HunkLocation[];
+  pendingDiscard: number | null;
+  commitMessagePath: string | null;
+}
+
+// =============================================================================
+// State Management
+// =============================================================================
+
+const stageState: GitStageState = {
+  isOpen: false,
+  bufferId: null,
+  splitId: null,
+  sourceBufferId: null,
+  filePath: null,
+  gitDir: null,
+  unstaged: null,
+  staged: null,
+  status: [],
+  hunkLocations: [],
+  pendingDiscard: null,
+  commitMessagePath: null,
+};
+
+// =============================================================================
+// Color Definitions
+// =============================================================================
+
+const colors = {
+  title: [255, 200, 100] as [number, number, number],    // Gold
+  header: [150, 200, 255] as [number, number, number],   // Light blue
+  status: [180, 180, 180] as [number, number, number],   // Light gray
+  diffAdd: [100, 255, 100] as [number, number, number],  // Green for additions
+  diffDel: [255, 100, 100] as [number, number, number],  // Red for deletions
+  diffHunk: [150, 150, 255] as [number, number, number], // Blue for hunk headers
+  footer: [120, 120, 120] as [number, number, number],   // Gray
+};
+
+// =============================================================================
+// Mode Definition
+// =============================================================================
+
+editor.defineMode(
+  "git-stage",
+  "normal", // inherit from normal mode for cursor movement
+  [
+    ["n", "git_stage_next_hunk"],
+    ["p", "git_stage_prev_hunk"],
+    ["s", "git_stage_stage_hunk"],
+    ["u", "git_stage_unstage_hunk"],
+    ["x", "git_stage_discard_hunk"],
+    ["c", "git_stage_commit"],
+    ["r", "git_stage_refresh"],
+    ["q", "git_stage_close"],
+    ["Escape", "git_stage_close"],
+  ],
+  true // read-only
+);
+
+// =============================================================================
+// Git Command Execution
+// =============================================================================
+
+async function git(args: string[]): Promise<SpawnResult> {
+  return await editor.spawnProcess("git", args, editor.getCwd());
+}
+
+/**
+ * Parse the output of `git diff` for a single file
+ */
+function parseFileDiff(output: string): FileDiff | null {
+  const diff: FileDiff = { header: [], hunks: [] };
+  for (const line of output.split("\n")) {
+    if (line.startsWith("@@")) {
+      diff.hunks.push({ header: line, lines: [] });
+    } else if (diff.hunks.length > 0) {
+      if (line.length > 0) {
+        diff.hunks[diff.hunks.length - 1].lines.push(line);
+      }
+    } else if (line.length > 0) {
+      diff.header.push(line);
+    }
+  }
+  return diff.hunks.length > 0 ? diff : null;
+}
+
+async function fetchFileDiff(filePath: string, cached: boolean): Promise<FileDiff | null> {
+  const args = ["diff", "--no-color", "--no-ext-diff"];
+  if (cached) args.push("--cached");
+  args.push("--", filePath);
+  const result = await git(args);
+  if (result.exit_code !== 0) {
+    editor.setStatus(editor.t("status.git_error", { error: result.stderr.trim() }));
+    return null;
+  }
+  return parseFileDiff(result.stdout);
+}
+
+async function fetchGitDir(): Promise<string | null> {
+  const result = await git(["rev-parse", "--absolute-git-dir"]);
+  return result.exit_code === 0 ? result.stdout.trim() : null;
+}
+
+async function fetchStatus(): Promise<string[]> {
+  const result = await git(["status", "--short", "--branch"]);
+  if (result.exit_code !== 0) return [];
+  return result.stdout.split("\n").filter(line => line.length > 0);
+}
+
+async function reloadState(): Promise<void> {
+  if (stageState.filePath === null) return;
+  stageState.unstaged = await fetchFileDiff(stageState.filePath, false);
+  stageState.staged = await fetchFileDiff(stageState.filePath, true);
+  stageState.status = await fetchStatus();
+}
+
+/**
+ * Apply a single hunk with `git apply`, optionally to the index and/or reversed
+ */
+async function applyHunk(diff: FileDiff, hunk: DiffHunk, options: string[]): Promise<boolean> {
+  const patch = [...diff.header, hunk.header, ...hunk.lines].join("\n") + "\n";
+  const patchPath = editor.pathJoin(stageState.gitDir!, "fresh-stage-hunk.patch");
+  if (!editor.writeFile(patchPath, patch)) {
+    editor.setStatus(editor.t("status.git_error", { error: patchPath }));
+    return false;
+  }
+  const result = await git(["apply", ...options, patchPath]);
+  if (result.exit_code !== 0) {
+    editor.setStatus(editor.t("status.git_error", { error: result.stderr.trim() }));
+    return false;
+  }
+  return true;
+}
+
+// =============================================================================
+// Stage View
+// =============================================================================
+
+/**
+ * Calculate UTF-8 byte length of a string manually since TextEncoder is not available
+ */
+function getByteLength(str: string): number {
+  let s = 0;
+  for (let i = 0; i < str.length; i++) {
+    const code = str.charCodeAt(i);
+    if (code <= 0x7f) s += 1;
+    else if (code <= 0x7ff) s += 2;
+    else if (code >= 0xd800 && code <= 0xdfff) {
+      s += 4; i++;
+    } else s += 3;
+  }
+  return s;
+}
+
+function displayPath(filePath: string): string {
+  const cwd = editor.getCwd();
+  if (filePath.startsWith(cwd + "/")) {
+    return filePath.slice(cwd.length + 1);
+  }
+  return filePath;
+}
+
+interface StyledLine {
+  text: string;
+  color: [number, number, number] | null;
+  bold?: boolean;
+  properties?: Record<string, unknown>;
+}
+
+function buildLines(): StyledLine[] {
+  const lines: StyledLine[] = [];
+  lines.push({
+    text: editor.t("panel.title", { file: displayPath(stageState.filePath!) }),
+    color: colors.title,
+    bold: true,
+  });
+  lines.push({ text: "", color: null });
+
+  lines.push({ text: editor.t("panel.status_header"), color: colors.header, bold: true });
+  for (const line of stageState.status) {
+    lines.push({ text: "  " + line, color: colors.status });
+  }
+  lines.push({ text: "", color: null });
+
+  const sections: [HunkSection, FileDiff | null, string][] = [
+    ["unstaged", stageState.unstaged, "panel.unstaged_header"],
+    ["staged", stageState.staged, "panel.staged_header"],
+  ];
+  for (const [section, diff, headerKey] of sections) {
+    const hunks = diff ? diff.hunks : [];
+    lines.push({
+      text: editor.t(headerKey, { count: String(hunks.length) }),
+      color: colors.header,
+      bold: true,
+    });
+    if (hunks.length === 0) {
+      lines.push({ text: editor.t("panel.no_hunks"), color: colors.footer });
+    }
+    hunks.forEach((hunk, index) => {
+      const properties = { type: "hunk", section, index };
+      lines.push({ text: hunk.header, color: colors.diffHunk, properties });
+      for (const line of hunk.lines) {
+        const color = line.startsWith("+") ? colors.diffAdd
+          : line.startsWith("-") ? colors.diffDel
+          : null;
+        lines.push({ text: line, color, properties });
+      }
+    });
+    lines.push({ text: "", color: null });
+  }
+
+  lines.push({ text: editor.t("panel.footer"), color: colors.footer });
+  return lines;
+}
+
+/**
+ * Rebuild the view, keeping the cursor on the same hunk slot if possible
+ */
+function updateStageView(focus: { section: HunkSection; index: number } | null): void {
+  if (stageState.bufferId === null) return;
+  const bufferId = stageState.bufferId;
+  const lines = buildLines();
+
+  const entries: TextPropertyEntry[] = lines.map(line => ({
+    text: line.text + "\n",
+    properties: line.properties ?? {},
+  }));
+  editor.setVirtualBufferContent(bufferId, entries);
+
+  editor.clearNamespace(bufferId, "gitstage");
+  stageState.hunkLocations = [];
+  let offset = 0;
+  let previous: Record<string, unknown> | undefined;
+  for (const line of lines) {
+    const length = getByteLength(line.text);
+    if (line.color !== null && length > 0) {
+      editor.addOverlay(bufferId, "gitstage", offset, offset + length, {
+        fg: line.color,
+        bold: line.bold ?? false,
+      });
+    }
+    if (line.properties && line.properties !== previous) {
+      stageState.hunkLocations.push({
+        section: line.properties.section as HunkSection,
+        index: line.properties.index as number,
+        offset,
+      });
+    }
+    previous = line.properties;
+    offset += length + 1;
+  }
+
+  if (focus !== null) {
+    const inSection = stageState.hunkLocations.filter(h => h.section === focus.section);
+    const target = inSection[Math.min(focus.index, inSection.length - 1)]
+      ?? stageState.hunkLocations[0];
+    if (target) {
+      editor.setBufferCursor(bufferId, target.offset);
+    }
+  }
+}
+
+function getHunkAtCursor(): { section: HunkSection; index: number } | null {
+  if (stageState.bufferId === null) return null;
+  const props = editor.getTextPropertiesAtCursor(stageState.bufferId);
+  for (const prop of props) {
+    if (prop.type === "hunk" && typeof prop.index === "number") {
+      return { section: prop.section as HunkSection, index: prop.index };
+    }
+  }
+  return null;
+}
+
+function jumpToHunk(direction: 1 | -1): void {
+  if (stageState.bufferId === null || stageState.hunkLocations.length === 0) return;
+  const cursor = editor.getCursorPosition();
+  const locations = direction > 0
+    ? stageState.hunkLocations.filter(h => h.offset > cursor)
+    : stageState.hunkLocations.filter(h => h.offset < cursor).reverse();
+  const current = getHunkAtCursor();
+  // When inside a hunk, "previous" means the start of the one before it
+  const target = locations.find(h =>
+    direction > 0 || current === null || h.section !== current.section || h.index !== current.index
+  );
+  if (target) {
+    editor.setBufferCursor(stageState.bufferId, target.offset);
+  }
+}
+
+async function refreshStageView(focus: { section: HunkSection; index: number } | null): Promise<void> {
+  await reloadState();
+  updateStageView(focus);
+}
+
+// =============================================================================
+// Public Commands
+// =============================================================================
+
+globalThis.git_stage_hunks = async function(): Promise<void> {
+  const sourceBufferId = editor.getActiveBufferId();
+  const filePath = editor.getBufferPath(sourceBufferId);
+  if (!filePath) {
+    editor.setStatus(editor.t("status.no_file"));
+    return;
+  }
+
+  const gitDir = await fetchGitDir();
+  if (gitDir === null) {
+    editor.setStatus(editor.t("status.not_a_repo"));
+    return;
+  }
+
+  if (stageState.isOpen) {
+    globalThis.git_stage_close();
+  }
+
+  stageState.splitId = editor.getActiveSplitId();
+  stageState.sourceBufferId = sourceBufferId;
+  stageState.filePath = filePath;
+  stageState.gitDir = gitDir;
+  await reloadState();
+
+  const result = await editor.createVirtualBufferInExistingSplit({
+    name: "*Stage Hunks*",
+    mode: "git-stage",
+    readOnly: true,
+    entries: [],
+    splitId: stageState.splitId,
+    showLineNumbers: false,
+    showCursors: true,
+    editingDisabled: true,
+  });
+
+  if (result === null) {
+    stageState.splitId = null;
+    editor.setStatus(editor.t("status.failed_open"));
+    return;
+  }
+
+  stageState.isOpen = true;
+  stageState.bufferId = result.bufferId;
+  updateStageView({ section: "unstaged", index: 0 });
+  editor.setStatus(editor.t("status.ready"));
+};
+
+globalThis.git_stage_close = function(): void {
+  if (!stageState.isOpen) return;
+
+  if (stageState.splitId !== null && stageState.sourceBufferId !== null) {
+    editor.setSplitBuffer(stageState.splitId, stageState.sourceBufferId);
+  }
+  if (stageState.bufferId !== null) {
+    editor.closeBuffer(stageState.bufferId);
+  }
+
+  stageState.isOpen = false;
+  stageState.bufferId = null;
+  stageState.splitId = null;
+  stageState.sourceBufferId = null;
+  stageState.unstaged = null;
+  stageState.staged = null;
+  stageState.hunkLocations = [];
+  stageState.pendingDiscard = null;
+};
+
+globalThis.git_stage_refresh = async function(): Promise<void> {
+  if (!stageState.isOpen) return;
+  await refreshStageView(getHunkAtCursor());
+};
+
+globalThis.git_stage_next_hunk = function(): void {
+  jumpToHunk(1);
+};
+
+globalThis.git_stage_prev_hunk = function(): void {
+  jumpToHunk(-1);
+};
+
+globalThis.git_stage_stage_hunk = async function(): Promise<void> {
+  const hunk = getHunkAtCursor();
+  if (hunk === null || hunk.section !== "unstaged" || stageState.unstaged === null) {
+    editor.setStatus(editor.t("status.move_to_unstaged"));
+    return;
+  }
+  if (await applyHunk(stageState.unstaged, stageState.unstaged.hunks[hunk.index], ["--cached"])) {
+    await refreshStageView(hunk);
+    editor.setStatus(editor.t("status.staged"));
+  }
+};
+
+globalThis.git_stage_unstage_hunk = async function(): Promise<void> {
+  const hunk = getHunkAtCursor();
+  if (hunk === null || hunk.section !== "staged" || stageState.staged === null) {
+    editor.setStatus(editor.t("status.move_to_staged"));
+    return;
+  }
+  if (await applyHunk(stageState.staged, stageState.staged.hunks[hunk.index], ["--cached", "--reverse"])) {
+    await refreshStageView(hunk);
+    editor.setStatus(editor.t("status.unstaged"));
+  }
+};
+
+globalThis.git_stage_discard_hunk = function(): void {
+  const hunk = getHunkAtCursor();
+  if (hunk === null || hunk.section !== "unstaged") {
+    editor.setStatus(editor.t("status.move_to_unstaged"));
+    return;
+  }
+  stageState.pendingDiscard = hunk.index;
+  editor.startPrompt(editor.t("prompt.discard_confirm"), "git-stage-discard");
+  const suggestions: PromptSuggestion[] = [
+    { text: editor.t("prompt.discard_yes"), description: "", value: "discard" },
+    { text: editor.t("prompt.discard_no"), description: "", value: "keep" },
+  ];
+  editor.setPromptSuggestions(suggestions);
+};
+
+globalThis.git_stage_commit = async function(): Promise<void> {
+  const gitDir = stageState.gitDir ?? await fetchGitDir();
+  if (gitDir === null) {
+    editor.setStatus(editor.t("status.not_a_repo"));
+    return;
+  }
+
+  // `git diff --cached --quiet` exits with 1 when something is staged
+  const staged = await git(["diff", "--cached", "--quiet"]);
+  if (staged.exit_code === 0) {
+    editor.setStatus(editor.t("status.nothing_staged"));
+    return;
+  }
+
+  const stat = await git(["diff", "--cached", "--stat", "--no-color"]);
+  const template = [
+    "",
+    ...editor.t("commit.template_help").split("\n"),
+    "#",
+    ...stat.stdout.split("\n").filter(line => line.length > 0).map(line => "# " + line),
+    "",
+  ].join("\n");
+
+  const messagePath = editor.pathJoin(gitDir, "COMMIT_EDITMSG");
+  if (!editor.writeFile(messagePath, template)) {
+    editor.setStatus(editor.t("status.git_error", { error: messagePath }));
+    return;
+  }
+  stageState.commitMessagePath = messagePath;
+  editor.openFile(messagePath, 1, 1);
+  editor.setStatus(editor.t("status.write_message"));
+};
+
+// =============================================================================
+// Event Handlers
+// =============================================================================
+
+async function discardHunk(index: number): Promise<void> {
+  if (stageState.unstaged === null) return;
+  if (await applyHunk(stageState.unstaged, stageState.unstaged.hunks[index], ["--reverse"])) {
+    await refreshStageView({ section: "unstaged", index });
+    editor.setStatus(editor.t("status.discarded"));
+  }
+}
+
+async function commitStaged(messagePath: string): Promise<void> {
+  const result = await git(["commit", "--cleanup=strip", "-F", messagePath]);
+  if (result.exit_code !== 0) {
+    editor.setStatus(editor.t("status.git_error", { error: (result.stderr || result.stdout).trim() }));
+    return;
+  }
+  const messageBuffer = editor.findBufferByPath(messagePath);
+  if (messageBuffer > 0) {
+    editor.closeBuffer(messageBuffer);
+  }
+  stageState.commitMessagePath = null;
+  const summary = await git(["log", "-1", "--format=%h %s"]);
+  editor.setStatus(editor.t("status.committed", { summary: summary.stdout.trim() }));
+  if (stageState.isOpen) {
+    await refreshStageView(null);
+  }
+}
+
+globalThis.onGitStagePromptConfirmed = function(args: {
+  prompt_type: string;
+  selected_index: number | null;
+  input: string;
+}): boolean {
+  if (args.prompt_type === "git-stage-discard") {
+    const index = stageState.pendingDiscard;
+    stageState.pendingDiscard = null;
+    if (index !== null && (args.input.trim() === "discard" || args.selected_index === 0)) {
+      discardHunk(index);
+    } else {
+      editor.setStatus(editor.t("status.cancelled"));
+    }
+    return false;
+  }
+
+  if (args.prompt_type === "git-stage-commit") {
+    const messagePath = stageState.commitMessagePath;
+    if (messagePath !== null && (args.input.trim() === "commit" || args.selected_index === 0)) {
+      commitStaged(messagePath);
+    } else {
+      editor.setStatus(editor.t("status.cancelled"));
+    }
+    return false;
+  }
+
+  return true;
+};
+
+globalThis.onGitStagePromptCancelled = function(args: { prompt_type: string }): boolean {
+  if (args.prompt_type === "git-stage-discard" || args.prompt_type === "git-stage-commit") {
+    stageState.pendingDiscard = null;
+    editor.setStatus(editor.t("status.cancelled"));
+  }
+  return true;
+};
+
+globalThis.onGitStageAfterFileSave = async function(data: {
+  buffer_id: number;
+  path: string;
+}): Promise<void> {
+  const messagePath = stageState.commitMessagePath;
+  if (messagePath !== null &&
+      (data.path === messagePath || editor.findBufferByPath(messagePath) === data.buffer_id)) {
+    editor.startPrompt(editor.t("prompt.commit_confirm"), "git-stage-commit");
+    const suggestions: PromptSuggestion[] = [
+      { text: editor.t("prompt.commit_yes"), description: "", value: "commit" },
+      { text: editor.t("prompt.commit_no"), description: "", value: "keep" },
+    ];
+    editor.setPromptSuggestions(suggestions);
+    return;
+  }
+
+  // Keep the hunk list and status current while the file is being edited
+  if (stageState.isOpen && data.path === stageState.filePath) {
+    await refreshStageView(null);
+  }
+};
+
+globalThis.onGitStageBufferActivated = async function(data: { buffer_id: number }): Promise<void> {
+  if (stageState.isOpen && data.buffer_id === stageState.bufferId) {
+    await refreshStageView(getHunkAtCursor());
+  }
+};
+
+globalThis.onGitStageBufferClosed = function(data: { buffer_id: number }): void {
+  if (stageState.isOpen && data.buffer_id === stageState.bufferId) {
+    stageState.bufferId = null;
+    globalThis.git_stage_close();
+  }
+};
+
+editor.on("prompt_confirmed", "onGitStagePromptConfirmed");
+editor.on("prompt_cancelled", "onGitStagePromptCancelled");
+editor.on("after_file_save", "onGitStageAfterFileSave");
+editor.on("buffer_activated", "onGitStageBufferActivated");
+editor.on("buffer_closed", "onGitStageBufferClosed");
+
+// =============================================================================
+// Command Registration
+// =============================================================================
+
+editor.registerCommand(
+  "%cmd.git_stage_hunks",
+  "%cmd.git_stage_hunks_desc",
+  "git_stage_hunks",
+  null
+);
+
+editor.registerCommand(
+  "%cmd.git_stage_commit",
+  "%cmd.git_stage_commit_desc",
+  "git_stage_commit",
+  null
+);
+
+// =============================================================================
+// Plugin Initialization
+// =============================================================================
+
+editor.debug("Git Stage plugin initialized - Use 'Git Stage Hunks' command to open");
@@ -23,10 +23,45 @@ interface ProcessHandle<T> extends PromiseLike<T> {
 	/** Cancel/kill the operation. Returns true if cancelled, false if already completed */
 	kill(): Promise<boolean>;
 }
+/** Handle for a background process, which can also stream its output */
+interface BackgroundProcessHandle extends ProcessHandle<BackgroundProcessResult> {
+	/** ID of the process, as passed to `killBackgroundProcess` */
+	readonly processId: number;
+	/** Call `listener` with each line the process writes to stdout (without the newline) */
+	onStdout(listener: (line: string) => void): BackgroundProcessHandle;
+	/** Call `listener` with each line the process writes to stderr (without the newline) */
+	onStderr(listener: (line: string) => void): BackgroundProcessHandle;
+}
 /** Buffer identifier */
 type BufferId = number;
 /** Split identifier */
 type SplitId = number;
+/** Code of a `PluginError`, one per subclass */
+type PluginErrorCode = "INVALID_BUFFER_ID" | "READ_ONLY_BUFFER" | "OUT_OF_RANGE" | "PERMISSION_DENIED" | "CHANNEL_CLOSED";
+/** Base class of the errors API calls throw; branch on `code` or `instanceof` */
+declare class PluginError extends Error {
+	readonly code: PluginErrorCode;
+}
+/** No open buffer has the given ID (it was closed, or never existed) */
+declare class InvalidBufferIdError extends PluginError {
+	readonly code: "INVALID_BUFFER_ID";
+}
+/** Editing is disabled in the buffer */
+declare class ReadOnlyBufferError extends PluginError {
+	readonly code: "READ_ONLY_BUFFER";
+}
+/** A position or range lies outside the buffer, or ends before it starts */
+declare class OutOfRangeError extends PluginError {
+	readonly code: "OUT_OF_RANGE";
+}
+/** The plugin isn't allowed to call the method (capability disabled or not granted) */
+declare class PermissionDeniedError extends PluginError {
+	readonly code: "PERMISSION_DENIED";
+}
+/** The editor no longer accepts commands (it is shutting down) */
+declare class ChannelClosedError extends PluginError {
+	readonly code: "CHANNEL_CLOSED";
+}
 type TextPropertyEntry = {
 	/**
 	* Text content for this entry
@@ -37,6 +72,39 @@ type TextPropertyEntry = {
 	*/
 	properties?: Record<string, unknown>;
 };
+type BufferDisplayOptions = {
+	/**
+	* Wrap long lines at this column instead of the window edge while line
+	* wrapping is on (0 = window edge)
+	*/
+	wrapColumn?: number;
+	/**
+	* Enable or disable line wrapping in the splits showing the buffer
+	*/
+	lineWrap?: boolean;
+	/**
+	* Display width of a tab character
+	*/
+	tabWidth?: number;
+	/**
+	* Show whitespace indicators for tab characters
+	*/
+	showWhitespace?: boolean;
+	/**
+	* Conceal rules, replacing any previous ones (`[]` removes them)
+	*/
+	conceal?: Array<ConcealRule>;
+};
+type ConcealRule = {
+	/**
+	* Regular expression matched against each line
+	*/
+	pattern: string;
+	/**
+	* Text shown in place of each match (default: hide the match)
+	*/
+	replacement?: string;
+};
 type TsCompositeLayoutConfig = {
 	/**
 	* Layout type: "side-by-side", "stacked", or "unified"
@@ -132,6 +200,95 @@ type TsCreateCompositeBufferOptions = {
 	*/
 	hunks: Array<TsCompositeHunk> | null;
 };
+type DiffGranularity = "line" | "word";
+type DiffHunk = {
+	/**
+	* First changed unit in the old text (0-indexed)
+	*/
+	oldStart: number;
+	/**
+	* Number of units removed from the old text
+	*/
+	oldCount: number;
+	/**
+	* First changed unit in the new text (0-indexed)
+	*/
+	newStart: number;
+	/**
+	* Number of units inserted in the new text
+	*/
+	newCount: number;
+	/**
+	* Byte offset of the hunk in the old text
+	*/
+	oldOffset: number;
+	/**
+	* Byte offset of the hunk in the new text
+	*/
+	newOffset: number;
+	/**
+	* Text removed from the old side
+	*/
+	oldText: string;
+	/**
+	* Text inserted on the new side
+	*/
+	newText: string;
+};
+type SyntaxCapture = {
+	/**
+	* Capture name without the `@` (e.g. "name" for `@name`)
+	*/
+	name: string;
+	/**
+	* Byte offset of the start of the node
+	*/
+	start: number;
+	/**
+	* Byte offset of the end of the node (exclusive)
+	*/
+	end: number;
+	/**
+	* Text of the node
+	*/
+	text: string;
+};
+type SearchMatch = {
+	/**
+	* Byte offset of the start of the match
+	*/
+	start: number;
+	/**
+	* Byte offset of the end of the match (exclusive)
+	*/
+	end: number;
+};
+type LayoutNode = {
+	/**
+	* Split ID, usable with `focusSplit`, `setSplitRatio`, `splitEditor`, ...
+	*/
+	splitId: number;
+	/**
+	* Buffer shown in the pane (leaves only)
+	*/
+	bufferId: number | null;
+	/**
+	* "horizontal" (stacked) or "vertical" (side by side), containers only
+	*/
+	direction: "horizontal" | "vertical" | null;
+	/**
+	* Share of the space given to the first child (containers only)
+	*/
+	ratio: number | null;
+	/**
+	* Whether this is the focused pane
+	*/
+	active: boolean;
+	/**
+	* The two children of a container (empty for leaves)
+	*/
+	children: Array<LayoutNode>;
+};
 type ViewportInfo = {
 	/**
 	* Byte position of the first visible line
@@ -250,6 +407,20 @@ type BufferInfo = {
 	* Length of buffer in bytes
 	*/
 	length: number;
+	/**
+	* Increases with every edit; pass it as `baseVersion` to `insertText`
+	* or `deleteRange` so edits made in the meantime are accounted for
+	*/
+	version: number;
+	/**
+	* Number of steps that can be undone
+	*/
+	undo_depth: number;
+	/**
+	* Whether editing is disabled in the buffer (plugin edits, undo and
+	* redo are refused too)
+	*/
+	read_only: boolean;
 };
 type JsDiagnostic = {
 	/**
@@ -303,6 +474,70 @@ type ActionSpec = {
 	*/
 	count: number;
 };
+type SelectionSpec = {
+	/**
+	* Byte offset where the selection starts (stays put when extending)
+	*/
+	anchor: number;
+	/**
+	* Byte offset of the cursor
+	*/
+	head: number;
+};
+type BufferEdit = {
+	/**
+	* Byte range to replace, in the buffer before any of the edits
+	*/
+	range: {
+		start: number;
+		end: number;
+	};
+	/**
+	* Text to put in its place (empty to delete)
+	*/
+	text: string;
+};
+type OverlayColorSpec = [number, number, number] | string;
+type GutterText = {
+	/**
+	* Line number (0-indexed)
+	*/
+	line: number;
+	/**
+	* Text shown in the gutter column, cut to the column's width
+	*/
+	text: string;
+	/**
+	* Text color - RGB array or theme key string (defaults to the line
+	* number color)
+	*/
+	color?: OverlayColorSpec;
+};
+type LineBackground = {
+	/**
+	* Line number (0-indexed)
+	*/
+	line: number;
+	/**
+	* Background color - RGB array or theme key string
+	*/
+	color: OverlayColorSpec;
+};
+type EventOptions = {
+	/**
+	* Run once no event has arrived for this many milliseconds
+	*/
+	debounceMs?: number;
+	/**
+	* With `debounceMs`, also run right away for the first event of a burst
+	*/
+	leading?: boolean;
+	/**
+	* Run at most once per this many milliseconds; with `debounceMs`, the
+	* longest a burst of events can hold the handler back
+	*/
+	throttleMs?: number;
+};
 type TsActionPopupAction = {
 	/**
 	* Unique action identifier (returned in ActionPopupResult)
@@ -359,6 +594,127 @@ type FormatterPackConfig = {
 	*/
 	args: Array<string>;
 };
+type CompletionRequest = {
+	/**
+	* Buffer the completion was requested in
+	*/
+	bufferId: number;
+	/**
+	* File path of the buffer, if any
+	*/
+	path: string | null;
+	/**
+	* Language of the buffer
+	*/
+	language: string;
+	/**
+	* Byte offset of the cursor
+	*/
+	position: number;
+	/**
+	* Word fragment before the cursor that the items will be filtered by
+	*/
+	prefix: string;
+};
+type CompletionItem = {
+	/**
+	* Text shown in the list and matched against the typed prefix
+	*/
+	label: string;
+	/**
+	* Text inserted when the item is accepted (defaults to the label)
+	*/
+	insertText?: string;
+	/**
+	* Secondary text shown next to the label, e.g. a signature
+	*/
+	detail?: string;
+	/**
+	* "function", "method", "variable", "class", "struct", "constant" or
+	* "keyword", used to pick the icon
+	*/
+	kind?: string;
+};
+type HoverRequest = {
+	/**
+	* Buffer the hover was requested in
+	*/
+	bufferId: number;
+	/**
+	* File path of the buffer, if any
+	*/
+	path: string | null;
+	/**
+	* Language of the buffer
+	*/
+	language: string;
+	/**
+	* Byte offset of the hovered position (the cursor or the mouse)
+	*/
+	position: number;
+};
+type InlineSuggestionRequest = {
+	/**
+	* Buffer the cursor is in
+	*/
+	bufferId: number;
+	/**
+	* File path of the buffer, if any
+	*/
+	path: string | null;
+	/**
+	* Language of the buffer
+	*/
+	language: string;
+	/**
+	* Byte offset of the cursor
+	*/
+	position: number;
+	/**
+	* Text before the cursor (at most a few kilobytes)
+	*/
+	textBefore: string;
+	/**
+	* Text after the cursor (at most a few kilobytes)
+	*/
+	textAfter: string;
+};
+type FormatRequest = {
+	/**
+	* Buffer being formatted
+	*/
+	bufferId: number;
+	/**
+	* File path of the buffer, if any
+	*/
+	path: string | null;
+	/**
+	* Language of the buffer
+	*/
+	language: string;
+	/**
+	* Full text of the buffer
+	*/
+	text: string;
+};
+type EvaluateRequest = {
+	/**
+	* Buffer the selection is in
+	*/
+	bufferId: number;
+	/**
+	* File path of the buffer, if any
+	*/
+	path: string | null;
+	/**
+	* Language of the buffer
+	*/
+	language: string;
+	/**
+	* Selected text to evaluate
+	*/
+	expression: string;
+};
 type BackgroundProcessResult = {
 	/**
 	* Unique process ID for later reference
@@ -375,6 +731,24 @@ type BufferSavedDiff = {
 	byte_ranges: Array<[number, number]>;
 	line_ranges: Array<[number, number]> | null;
 };
+type BufferSearchOptions = {
+	/**
+	* Treat the pattern as a regular expression (default: false)
+	*/
+	regex?: boolean;
+	/**
+	* Match case (default: false)
+	*/
+	caseSensitive?: boolean;
+	/**
+	* Only match whole words (default: false)
+	*/
+	wholeWord?: boolean;
+	/**
+	* Stop after this many matches (default: no limit)
+	*/
+	maxResults?: number;
+};
 type TsCompositeHunk = {
 	/**
 	* Starting line in old buffer (0-indexed)
@@ -393,6 +767,17 @@ type TsCompositeHunk = {
 	*/
 	newCount: number;
 };
+type ComputeDiffOptions = {
+	/**
+	* "line" (default) or "word"
+	*/
+	granularity?: DiffGranularity;
+	/**
+	* Return `TsCompositeHunk` objects that can be passed straight to
+	* `createCompositeBuffer` / `updateCompositeAlignment` (line granularity only)
+	*/
+	composite?: boolean;
+};
 type TsCreateCompositeBufferOptions = {
 	/**
 	* Buffer name (displayed in tabs/title)
@@ -452,6 +837,10 @@ type CreateVirtualBufferInExistingSplitOptions = {
 	* Initial content entries with optional properties
 	*/
 	entries?: Array<TextPropertyEntry>;
+	/**
+	* Display overrides (wrap column, tab width, conceal rules, ...)
+	*/
+	display?: BufferDisplayOptions;
 };
 type CreateVirtualBufferInSplitOptions = {
 	/**
@@ -498,6 +887,10 @@ type CreateVirtualBufferInSplitOptions = {
 	* Initial content entries with optional properties
 	*/
 	entries?: Array<TextPropertyEntry>;
+	/**
+	* Display overrides (wrap column, tab width, conceal rules, ...)
+	*/
+	display?: BufferDisplayOptions;
 };
 type CreateVirtualBufferOptions = {
 	/**
@@ -532,6 +925,72 @@ type CreateVirtualBufferOptions = {
 	* Initial content entries with optional properties
 	*/
 	entries?: Array<TextPropertyEntry>;
+	/**
+	* Display overrides (wrap column, tab width, conceal rules, ...)
+	*/
+	display?: BufferDisplayOptions;
+};
+type FetchOptions = {
+	/**
+	* HTTP method (default `"GET"`)
+	*/
+	method?: string;
+	/**
+	* Request headers
+	*/
+	headers?: Record<string, string>;
+	/**
+	* Request body, sent as-is
+	*/
+	body?: string;
+	/**
+	* Give up after this many milliseconds
+	* (default: the `plugin_fetch.default_timeout_ms` setting)
+	*/
+	timeoutMs?: number;
+};
+type FetchResponse = {
+	/**
+	* HTTP status code
+	*/
+	status: number;
+	/**
+	* Response headers, with lowercase names
+	*/
+	headers: Record<string, string>;
+	/**
+	* Response body as text (invalid UTF-8 is replaced)
+	*/
+	body: string;
+};
+type FileBrowserOptions = {
+	/**
+	* Directory to start in; the user cannot browse above it
+	* (defaults to the working directory, unscoped)
+	*/
+	root?: string;
+	/**
+	* File name globs such as `"*.rs"`; other files are hidden
+	*/
+	filter?: Array<string>;
+	/**
+	* Label shown in front of the path input
+	*/
+	title?: string;
+	/**
+	* Choose a directory instead of a file
+	*/
+	directories?: boolean;
+};
+type InputOptions = {
+	/**
+	* Text the input starts with (selected, so typing replaces it)
+	*/
+	initial?: string;
+	/**
+	* Show `*` instead of the typed characters and keep them off the clipboard
+	*/
+	password?: boolean;
 };
 type LanguagePackConfig = {
 	/**
@@ -586,43 +1045,155 @@ type LspServerPackConfig = {
 	*/
 	initializationOptions: Record<string, unknown> | null;
 };
-type SpawnResult = {
+type MenuItemOptions = {
 	/**
-	* Complete stdout as string
+	* Text shown for the item
 	*/
-	stdout: string;
+	label: string;
 	/**
-	* Complete stderr as string
+	* Action or plugin command run when the item is chosen
 	*/
-	stderr: string;
+	command: string;
 	/**
-	* Process exit code (0 usually means success, -1 if killed)
+	* Context key that must be set for the item to be enabled
 	*/
-	exit_code: number;
+	when?: string;
 };
-type PromptSuggestion = {
+type PickerOptions = {
 	/**
-	* The text to display
+	* Label shown in front of the filter input
 	*/
-	text: string;
+	title?: string;
 	/**
-	* Optional description
+	* Items to choose from; the chosen ones are returned as passed in
 	*/
-	description?: string;
+	items: Array<QuickPickItem>;
 	/**
-	* The value to use when selected (defaults to text if None)
+	* Let the user check several items (Tab toggles); resolves to an array
 	*/
-	value?: string;
+	multiSelect?: boolean;
 	/**
-	* Whether this suggestion is disabled (greyed out, defaults to false)
+	* Hint shown in the filter input while it is empty
 	*/
-	disabled?: boolean;
+	placeholder?: string;
 	/**
-	* Optional keyboard shortcut
+	* Also match the filter against item descriptions
 	*/
-	keybinding?: string;
+	matchOnDescription?: boolean;
+};
+type QuickPickItem = {
+	/**
+	* Text shown in the list and matched against the filter
+	*/
+	label: string;
+	/**
+	* Secondary text shown next to the label
+	*/
+	description?: string;
+	/**
+	* Icon or short prefix shown before the label
+	*/
+	icon?: string;
+	/**
+	* Start out checked (multi-select only)
+	*/
+	picked?: boolean;
+	/**
+	* Arbitrary data returned with the item
+	*/
+	value?: unknown;
+};
+type QuickPickOptions = {
+	/**
+	* Label shown in front of the filter input
+	*/
+	title?: string;
+	/**
+	* Let the user check several items (Tab toggles); resolves to an array
+	*/
+	canPickMany?: boolean;
+	/**
+	* Also match the filter against item descriptions
+	*/
+	matchOnDescription?: boolean;
+	/**
+	* Hint shown in the filter input while it is empty
+	*/
+	placeholder?: string;
+};
+type SearchOptions = {
+	/**
+	* Treat the pattern as a regular expression
+	*/
+	regex?: boolean;
+	/**
+	* Match case
+	*/
+	caseSensitive?: boolean;
+	/**
+	* Only match whole words
+	*/
+	wholeWord?: boolean;
+	/**
+	* Search the whole project instead of the active buffer
+	*/
+	project?: boolean;
+};
+type SpawnResult = {
+	/**
+	* Complete stdout as string
+	*/
+	stdout: string;
+	/**
+	* Complete stderr as string
+	*/
+	stderr: string;
+	/**
+	* Process exit code (0 usually means success, -1 if killed)
+	*/
+	exit_code: number;
+};
+type PromptSuggestion = {
+	/**
+	* The text to display
+	*/
+	text: string;
+	/**
+	* Optional description
+	*/
+	description?: string;
+	/**
+	* The value to use when selected (defaults to text if None)
+	*/
+	value?: string;
+	/**
+	* Whether this suggestion is disabled (greyed out, defaults to false)
+	*/
+	disabled?: boolean;
+	/**
+	* Optional keyboard shortcut
+	*/
+	keybinding?: string;
+};
+type TextPropertiesAtCursor = Array<Record<string, unknown>>;
+type ThemeSeed = {
+	/**
+	* Theme name (defaults to "generated")
+	*/
+	name?: string;
+	/**
+	* Background color as a hex string (e.g., "#1e1e2e")
+	*/
+	bg: string;
+	/**
+	* Foreground color as a hex string
+	*/
+	fg: string;
+	/**
+	* Accent color used for the cursor, selection and highlights
+	*/
+	accent: string;
 };
-type TextPropertiesAtCursor = Array<Record<string, unknown>>;
 type TsHighlightSpan = {
 	start: number;
 	end: number;
@@ -640,10 +1211,517 @@ type VirtualBufferResult = {
 	*/
 	splitId: number | null;
 };
+type BufferChange = {
+	/**
+	* Byte range that was replaced
+	*/
+	range: {
+		start: number;
+		end: number;
+	};
+	/**
+	* Text put in its place (empty for a deletion)
+	*/
+	text: string;
+};
+type LineInfo = {
+	/**
+	* Line number (0-based)
+	*/
+	line_number: number;
+	/**
+	* Byte offset where the line starts in the buffer
+	*/
+	byte_start: number;
+	/**
+	* Byte offset where the line ends (exclusive)
+	*/
+	byte_end: number;
+	/**
+	* The content of the line
+	*/
+	content: string;
+};
+type LspLocation = {
+	/**
+	* File path
+	*/
+	file: string;
+	/**
+	* Line number (1-based)
+	*/
+	line: number;
+	/**
+	* Column number (1-based)
+	*/
+	column: number;
+};
+/**
+* Events plugins can subscribe to with `editor.on`, and the data their
+* handlers receive
+*/
+interface EditorEventMap {
+	/**
+	* Before a file is opened
+	*/
+	before_file_open: {
+		path: string;
+	};
+	/**
+	* After a file is successfully opened
+	*/
+	after_file_open: {
+		buffer_id: BufferId;
+		path: string;
+	};
+	/**
+	* Before a buffer is saved to disk
+	*/
+	before_file_save: {
+		buffer_id: BufferId;
+		path: string;
+	};
+	/**
+	* After a buffer is successfully saved
+	*/
+	after_file_save: {
+		buffer_id: BufferId;
+		path: string;
+	};
+	/**
+	* A buffer was closed
+	*/
+	buffer_closed: {
+		buffer_id: BufferId;
+	};
+	/**
+	* Before text is inserted
+	*/
+	before_insert: {
+		buffer_id: BufferId;
+		position: number;
+		text: string;
+	};
+	/**
+	* After text was inserted
+	*/
+	after_insert: {
+		buffer_id: BufferId;
+		position: number;
+		text: string;
+		/**
+		* Byte position where the affected range starts
+		*/
+		affected_start: number;
+		/**
+		* Byte position where the affected range ends (after the inserted text)
+		*/
+		affected_end: number;
+		/**
+		* Line number where insertion occurred (0-indexed)
+		*/
+		start_line: number;
+		/**
+		* Line number where insertion ended (0-indexed)
+		*/
+		end_line: number;
+		/**
+		* Number of lines added by this insertion
+		*/
+		lines_added: number;
+	};
+	/**
+	* Before text is deleted
+	*/
+	before_delete: {
+		buffer_id: BufferId;
+		start: number;
+		end: number;
+	};
+	/**
+	* After text was deleted
+	*/
+	after_delete: {
+		buffer_id: BufferId;
+		start: number;
+		end: number;
+		deleted_text: string;
+		/**
+		* Byte position where the deletion occurred
+		*/
+		affected_start: number;
+		/**
+		* Length of the deleted content in bytes
+		*/
+		deleted_len: number;
+		/**
+		* Line number where deletion started (0-indexed)
+		*/
+		start_line: number;
+		/**
+		* Line number where deletion ended (0-indexed, in original buffer)
+		*/
+		end_line: number;
+		/**
+		* Number of lines removed by this deletion
+		*/
+		lines_removed: number;
+	};
+	/**
+	* A buffer's text changed; fired once per editor tick with every
+	* change made since the last time, whoever made it
+	*/
+	buffer_changed: {
+		buffer_id: BufferId;
+		/**
+		* Apply in order: each range refers to the buffer after the
+		* changes before it
+		*/
+		changes: BufferChange[];
+		/**
+		* Buffer version after the changes (`getBufferInfo(id).version`)
+		*/
+		version: number;
+	};
+	/**
+	* Cursor moved to a new position
+	*/
+	cursor_moved: {
+		buffer_id: BufferId;
+		cursor_id: number;
+		old_position: number;
+		new_position: number;
+		/**
+		* Line number at new position (1-indexed)
+		*/
+		line: number;
+	};
+	/**
+	* Buffer became active
+	*/
+	buffer_activated: {
+		buffer_id: BufferId;
+		/**
+		* Buffer that was active before, if any
+		*/
+		previous_buffer_id: BufferId | null;
+		/**
+		* Split the buffer became active in
+		*/
+		split_id: SplitId;
+	};
+	/**
+	* Another split received focus
+	*/
+	split_focused: {
+		split_id: SplitId;
+		/**
+		* Split that had focus before (it may since have been closed)
+		*/
+		previous_split_id: SplitId;
+		/**
+		* Buffer shown in the newly focused split
+		*/
+		buffer_id: BufferId;
+	};
+	/**
+	* Buffer was deactivated
+	*/
+	buffer_deactivated: {
+		buffer_id: BufferId;
+	};
+	/**
+	* LSP diagnostics were updated for a file
+	*/
+	diagnostics_updated: {
+		/**
+		* The URI of the file that was updated
+		*/
+		uri: string;
+		/**
+		* Number of diagnostics in the update
+		*/
+		count: number;
+	};
+	/**
+	* Before a command/action is executed
+	*/
+	pre_command: {
+		action: string;
+	};
+	/**
+	* After a command/action was executed
+	*/
+	post_command: {
+		action: string;
+	};
+	/**
+	* Editor has been idle for N milliseconds (no input)
+	*/
+	idle: {
+		milliseconds: number;
+	};
+	/**
+	* Editor is initializing
+	*/
+	editor_initialized: Record<string, never>;
+	/**
+	* Rendering is starting for a buffer (called once per buffer before render_line hooks)
+	*/
+	render_start: {
+		buffer_id: BufferId;
+	};
+	/**
+	* A line is being rendered (called during the rendering pass)
+	*/
+	render_line: {
+		buffer_id: BufferId;
+		line_number: number;
+		byte_start: number;
+		byte_end: number;
+		content: string;
+	};
+	/**
+	* Lines have changed and need processing (batched for efficiency)
+	*/
+	lines_changed: {
+		buffer_id: BufferId;
+		lines: LineInfo[];
+	};
+	/**
+	* Prompt input changed (user typed/edited)
+	*/
+	prompt_changed: {
+		prompt_type: string;
+		input: string;
+	};
+	/**
+	* Prompt was confirmed (user pressed Enter)
+	*/
+	prompt_confirmed: {
+		prompt_type: string;
+		input: string;
+		selected_index: number | null;
+	};
+	/**
+	* Prompt was cancelled (user pressed Escape/Ctrl+G)
+	*/
+	prompt_cancelled: {
+		prompt_type: string;
+		input: string;
+	};
+	/**
+	* Prompt suggestion selection changed (user navigated with Up/Down)
+	*/
+	prompt_selection_changed: {
+		prompt_type: string;
+		selected_index: number;
+	};
+	/**
+	* Request keyboard shortcuts data (key, action) for the help buffer
+	*/
+	keyboard_shortcuts: {
+		bindings: {
+			key: string;
+			action: string;
+		}[];
+	};
+	/**
+	* LSP find references response received
+	*/
+	lsp_references: {
+		/**
+		* The symbol name being queried
+		*/
+		symbol: string;
+		/**
+		* The locations where the symbol is referenced
+		*/
+		locations: LspLocation[];
+	};
+	/**
+	* View transform request
+	*/
+	view_transform_request: {
+		buffer_id: BufferId;
+		split_id: SplitId;
+		/**
+		* Byte offset of the viewport start
+		*/
+		viewport_start: number;
+		/**
+		* Byte offset of the viewport end
+		*/
+		viewport_end: number;
+		/**
+		* Base tokens (Text, Newline, Space) from the source
+		*/
+		tokens: ViewTokenWire[];
+	};
+	/**
+	* Mouse click event
+	*/
+	mouse_click: {
+		/**
+		* Column (x coordinate) in screen cells
+		*/
+		column: number;
+		/**
+		* Row (y coordinate) in screen cells
+		*/
+		row: number;
+		/**
+		* Mouse button: "left", "right", "middle"
+		*/
+		button: string;
+		/**
+		* Modifier keys
+		*/
+		modifiers: string;
+		/**
+		* Content area X offset
+		*/
+		content_x: number;
+		/**
+		* Content area Y offset
+		*/
+		content_y: number;
+	};
+	/**
+	* Mouse move/hover event
+	*/
+	mouse_move: {
+		/**
+		* Column (x coordinate) in screen cells
+		*/
+		column: number;
+		/**
+		* Row (y coordinate) in screen cells
+		*/
+		row: number;
+		/**
+		* Content area X offset
+		*/
+		content_x: number;
+		/**
+		* Content area Y offset
+		*/
+		content_y: number;
+	};
+	/**
+	* LSP server request (server -> client)
+	*/
+	lsp_server_request: {
+		/**
+		* The language/server that sent the request
+		*/
+		language: string;
+		/**
+		* The JSON-RPC method name
+		*/
+		method: string;
+		/**
+		* The server command used to spawn this LSP
+		*/
+		server_command: string;
+		/**
+		* The request parameters as a JSON string
+		*/
+		params: string | null;
+	};
+	/**
+	* Viewport changed (scrolled or resized)
+	*/
+	viewport_changed: {
+		split_id: SplitId;
+		buffer_id: BufferId;
+		top_byte: number;
+		width: number;
+		height: number;
+	};
+	/**
+	* LSP server failed to start or crashed
+	*/
+	lsp_server_error: {
+		/**
+		* The language that failed
+		*/
+		language: string;
+		/**
+		* The server command that failed
+		*/
+		server_command: string;
+		/**
+		* Error type: "not_found", "spawn_failed", "timeout", "crash"
+		*/
+		error_type: string;
+		/**
+		* Human-readable error message
+		*/
+		message: string;
+	};
+	/**
+	* User clicked the LSP status indicator
+	*/
+	lsp_status_clicked: {
+		/**
+		* The language of the current buffer
+		*/
+		language: string;
+		/**
+		* Whether there's an active error
+		*/
+		has_error: boolean;
+	};
+	/**
+	* User selected an action from an action popup
+	*/
+	action_popup_result: {
+		/**
+		* The popup ID
+		*/
+		popup_id: string;
+		/**
+		* The action ID selected, or "dismissed"
+		*/
+		action_id: string;
+	};
+	/**
+	* Background process output (streaming)
+	*/
+	process_output: {
+		/**
+		* The process ID
+		*/
+		process_id: number;
+		/**
+		* The output data
+		*/
+		data: string;
+	};
+	/**
+	* The UI locale was changed at runtime
+	*/
+	locale_changed: {
+		/**
+		* The newly active locale code (e.g. "de", "pt-BR")
+		*/
+		locale: string;
+	};
+	/**
+	* A saved session was restored; data stored with `setSessionData`
+	* is available from `getSessionData`
+	*/
+	session_restored: Record<string, never>;
+}
 /**
 * Main editor API interface
 */
 interface EditorAPI {
+	/** Methods grouped under `editor.buffers` */
+	readonly buffers: EditorBuffersAPI;
+	/** Methods grouped under `editor.fs` */
+	readonly fs: EditorFsAPI;
 	/**
 	* Get the active buffer ID (0 if none)
 	*/
@@ -661,18 +1739,94 @@ interface EditorAPI {
 	warn(msg: string): void;
 	error(msg: string): void;
 	setStatus(msg: string): void;
+	/**
+	* Set the text shown for `{name}` in the user's status bar template
+	* (`editor.status_bar_template`); an empty text clears it
+	*/
+	setStatusBarSegment(name: string, text: string): void;
 	copyToClipboard(text: string): void;
 	setClipboard(text: string): void;
 	/**
 	* Register a command - reads plugin name from __pluginName__ global
 	* context is optional - can be omitted, null, undefined, or a string
 	*/
-	registerCommand(name: string, description: string, handlerName: string, context?: unknown): boolean;
+	registerCommand(name: string, description: string, handlerName: string, context?: string | null): boolean;
 	/**
 	* Unregister a command by name
 	*/
 	unregisterCommand(name: string): boolean;
 	/**
+	* Register a completion provider
+	* `handlerName` is called with a `CompletionRequest` when completion is
+	* requested in one of `languages` (every language when empty) and returns
+	* `CompletionItem[]` or a Promise of them, shown together with the language
+	* server's items. Typing one of `triggerCharacters` requests completion
+	* right away.
+	*/
+	registerCompletionProvider(languages: string[], triggerCharacters: string[], handlerName: string): boolean;
+	/**
+	* Register a hover provider
+	* `handlerName` is called with a `HoverRequest` when hover is requested
+	* in one of `languages` (every language when empty) and returns a
+	* markdown string, `null` when it has nothing to show, or a Promise of
+	* either. The markdown is shown in the hover popup together with the
+	* language server's hover.
+	*/
+	registerHoverProvider(languages: string[], handlerName: string): boolean;
+	/**
+	* Register an inline suggestion provider
+	* `handlerName` is called with an `InlineSuggestionRequest` when the
+	* cursor rests in a buffer of one of `languages` (every language when
+	* empty) and returns the text to suggest at the cursor, `null` when it
+	* has nothing to suggest, or a Promise of either. The suggestion is shown
+	* as ghost text; Tab accepts it and Ctrl+Right accepts its next word.
+	*/
+	registerInlineSuggestionProvider(languages: string[], handlerName: string): boolean;
+	/**
+	* Register a formatter for a language
+	* `handlerName` is called with a `FormatRequest` by the `format_buffer`
+	* action (and on save when the language has `format_on_save`) and
+	* returns the formatted text, `null` to leave the buffer alone, or a
+	* Promise of either. It replaces the language's `formatter` command.
+	*/
+	registerFormatter(language: string, handlerName: string): boolean;
+	/**
+	* Register an evaluator for a language
+	* The Evaluate Selection commands call `handlerName` with an
+	* `EvaluateRequest` for each selection in a buffer of `language`. It
+	* returns the result as a string or number, `null` to let the built-in
+	* calculator evaluate the selection, or a Promise of either.
+	*/
+	registerEvaluator(language: string, handlerName: string): boolean;
+	/**
+	* Add an item to a right-click context menu
+	* `menu` is "editor", "tab" or "file_explorer". Choosing the item runs
+	* `action` (a built-in action or a command handler name); `when` names a
+	* context key (like "has_selection" or one set with `setContext`) that
+	* must be set for the item to be shown. Adding an item with the same
+	* label again replaces it.
+	*/
+	addContextMenuItem(menu: string, label: string, action: string, when?: string | null): boolean;
+	/**
+	* Remove an item this plugin added to a context menu
+	*/
+	removeContextMenuItem(menu: string, label: string): boolean;
+	/**
+	* Add an item to the menu bar
+	* `path` names the menu: a top-level label such as "Tools", optionally
+	* followed by submenu labels ("Tools/Git"). Menus and submenus that don't
+	* exist yet are created. Choosing the item runs `options.command` (a
+	* built-in action or a command handler name); `options.when` names a
+	* context key that must be set for the item to be enabled. Adding an
+	* item with the same label again replaces it, and the plugin's items are
+	* removed when it is unloaded.
+	*/
+	addMenuItem(path: string, options: MenuItemOptions): boolean;
+	/**
+	* Remove an item this plugin added to the menu bar
+	*/
+	removeMenuItem(path: string, label: string): boolean;
+	/**
 	* Set a context (for keybinding conditions)
 	*/
 	setContext(name: string, active: boolean): boolean;
@@ -684,7 +1838,7 @@ interface EditorAPI {
 	* Translate a string - reads plugin name from __pluginName__ global
 	* Args is optional - can be omitted, undefined, null, or an object
 	*/
-	t(key: string, ...args: unknown[]): string;
+	t(key: string, args?: Record<string, string> | null): string;
 	/**
 	* Get cursor position in active buffer
 	*/
@@ -703,7 +1857,8 @@ interface EditorAPI {
 	isBufferModified(bufferId: number): boolean;
 	/**
 	* Save a buffer to a specific file path
-	* Used by :w filename to save unnamed buffers or save-as
+	* Used by :w filename to save unnamed buffers or save-as.
+	* Throws `InvalidBufferIdError`
 	*/
 	saveBufferToPath(bufferId: number, path: string): boolean;
 	/**
@@ -745,18 +1900,76 @@ interface EditorAPI {
 	getBufferSavedDiff(bufferId: number): BufferSavedDiff | null;
 	/**
 	* Insert text at a position in a buffer
+	* `baseVersion` is the buffer version (`getBufferInfo(id).version`) the
+	* position was computed against; the position is then moved past edits
+	* the user made since, and the insert is skipped if they conflict.
+	* Throws `InvalidBufferIdError`, `ReadOnlyBufferError` if editing is
+	* disabled in the buffer, or `OutOfRangeError`
 	*/
-	insertText(bufferId: number, position: number, text: string): boolean;
+	insertText(bufferId: number, position: number, text: string, baseVersion?: number): boolean;
 	/**
 	* Delete a range from a buffer
+	* `baseVersion` works as for `insertText`.
+	* Throws like `insertText`
+	*/
+	deleteRange(bufferId: number, start: number, end: number, baseVersion?: number): boolean;
+	/**
+	* Apply several edits to a buffer at once, as a single undo step
+	* 
+	* Every range refers to the buffer before any of the edits, so offsets
+	* don't need adjusting for the edits before them. Ranges must not
+	* overlap; if they do, or any edit is skipped, nothing is applied.
+	* `baseVersion` works as for `insertText`.
+	* Throws like `insertText`
+	*/
+	applyEdits(bufferId: number, edits: BufferEdit[], baseVersion?: number): boolean;
+	/**
+	* Start grouping edits to a buffer into a single undo step
+	* 
+	* Every insert and delete made until `endUndoGroup` is undone and redone
+	* together. The group is closed automatically if the action or handler
+	* making the edits throws, or if it stays open for more than 5 seconds.
+	* Groups don't nest: a second call before `endUndoGroup` is ignored.
+	* Throws `InvalidBufferIdError`
+	*/
+	beginUndoGroup(bufferId: number): boolean;
+	/**
+	* Close the undo group started with `beginUndoGroup`
+	* 
+	* Throws like `beginUndoGroup`
+	*/
+	endUndoGroup(bufferId: number): boolean;
+	/**
+	* Undo the last edit step in a buffer
+	* 
+	* A group of edits made between `beginUndoGroup` and `endUndoGroup` is
+	* one step. Throws `InvalidBufferIdError`, or `ReadOnlyBufferError` if
+	* editing is disabled in the buffer
+	*/
+	undo(bufferId: number): boolean;
+	/**
+	* Redo the last edit step undone in a buffer
+	* 
+	* Throws like `undo`
 	*/
-	deleteRange(bufferId: number, start: number, end: number): boolean;
+	redo(bufferId: number): boolean;
+	/**
+	* Get the number of steps that can be undone in a buffer
+	* 
+	* Read from the editor state, so undos and edits sent during the same
+	* call are not counted yet
+	*/
+	getUndoDepth(bufferId: number): number;
 	/**
 	* Insert text at cursor position in active buffer
+	* 
+	* Throws `ReadOnlyBufferError` if editing is disabled in the buffer
 	*/
 	insertAtCursor(text: string): boolean;
 	/**
 	* Open a file, optionally at a specific line/column
+	* 
+	* A leading `~` and `$VAR` / `${VAR}` references in the path are expanded.
 	*/
 	openFile(path: string, line: number | null, column: number | null): boolean;
 	/**
@@ -769,16 +1982,34 @@ interface EditorAPI {
 	showBuffer(bufferId: number): boolean;
 	/**
 	* Close a buffer
+	* 
+	* Closing a buffer that is already gone does nothing, so this never throws
 	*/
 	closeBuffer(bufferId: number): boolean;
 	/**
 	* Subscribe to an editor event
+	* 
+	* The handler is a function or the name of a global function, and gets
+	* the event's `EditorEventMap` payload. Events outside the map take any
+	* handler. `options` can limit how often the handler runs for events
+	* that fire on every keystroke, such as `cursor_moved`.
 	*/
-	on(eventName: string, handlerName: string): void;
+	on<E extends keyof EditorEventMap>(eventName: E, handler: (data: EditorEventMap[E]) => void, options?: EventOptions): void;
+	on<E extends keyof EditorEventMap>(eventName: E, handlerName: string, options?: EventOptions): void;
+	on(eventName: string, handler: string | ((data: unknown) => void), options?: EventOptions): void;
 	/**
 	* Unsubscribe from an event
 	*/
-	off(eventName: string, handlerName: string): void;
+	off<E extends keyof EditorEventMap>(eventName: E, handler: (data: EditorEventMap[E]) => void): void;
+	off(eventName: string, handler: string | ((data: unknown) => void)): void;
+	/**
+	* Register a handler to run just before this plugin is reloaded
+	* 
+	* The editor removes the plugin's commands, event handlers, timers and
+	* overlays itself; use this to clean up anything else the plugin set up,
+	* such as background processes or status bar segments.
+	*/
+	onPluginReload(handler: string | (() => void)): void;
 	/**
 	* Get an environment variable
 	*/
@@ -825,6 +2056,18 @@ interface EditorAPI {
 	*/
 	readDir(path: string): DirEntry[];
 	/**
+	* Store a value for this plugin in the session file
+	* 
+	* The value is copied as JSON and saved with the rest of the session, so
+	* panels can rebuild their state when the workspace is reopened (listen
+	* for `session_restored`). Passing `null` or `undefined` removes the key.
+	*/
+	setSessionData(key: string, value: unknown): boolean;
+	/**
+	* Get a value this plugin stored with `setSessionData`, or null
+	*/
+	getSessionData(key: string): unknown;
+	/**
 	* Get current config as JS object
 	*/
 	getConfig(): unknown;
@@ -880,6 +2123,12 @@ interface EditorAPI {
 	*/
 	getBuiltinThemes(): unknown;
 	/**
+	* Generate theme JSON from seed colors (bg, fg, accent)
+	* 
+	* Throws if a seed color is not a valid hex color.
+	*/
+	generateTheme(seed: ThemeSeed): unknown;
+	/**
 	* Delete a custom theme (alias for deleteThemeSync)
 	*/
 	deleteTheme(name: string): boolean;
@@ -974,6 +2223,18 @@ interface EditorAPI {
 	*/
 	clearFileExplorerDecorations(namespace: string): boolean;
 	/**
+	* Show the file explorer with a file or directory expanded and selected
+	* 
+	* Relative paths are resolved against the working directory.
+	*/
+	revealInExplorer(path: string): boolean;
+	/**
+	* Let the user choose a file (or directory) in the file browser (async)
+	* 
+	* Resolves with the absolute path of the choice, or null if cancelled.
+	*/
+	openFileBrowser(options?: FileBrowserOptions): Promise<string | null>;
+	/**
 	* Add virtual text (inline text that doesn't exist in the buffer)
 	*/
 	addVirtualText(bufferId: number, virtualTextId: string, position: number, text: string, r: number, g: number, b: number, before: boolean, useBg: boolean): boolean;
@@ -998,11 +2259,58 @@ interface EditorAPI {
 	*/
 	addVirtualLine(bufferId: number, position: number, text: string, fgR: number, fgG: number, fgB: number, bgR: number, bgG: number, bgB: number, above: boolean, namespace: string, priority: number): boolean;
 	/**
+	* Create a marker at a byte offset in a buffer and return its ID
+	* 
+	* The marker moves with edits: text inserted before or at it pushes it
+	* forward, and deleting a range around it moves it to the range start.
+	* Markers are deleted with their buffer or when the plugin is unloaded.
+	*/
+	createMarker(bufferId: number, offset: number): number;
+	/**
+	* Delete a marker created with `createMarker`
+	*/
+	deleteMarker(markerId: number): boolean;
+	/**
+	* Get the current byte offset of a marker (async, returns request_id)
+	* 
+	* Resolves to null if the marker was deleted or its buffer closed.
+	*/
+	getMarkerPosition(markerId: number): Promise<number | null>;
+	/**
 	* Show a prompt and wait for user input (async)
 	* Returns the user input or null if cancelled
 	*/
 	prompt(label: string, initialValue: string): Promise<string | null>;
 	/**
+	* Show a filterable list of items and wait for a choice (async)
+	* 
+	* Resolves with the chosen item, the checked items when
+	* `options.canPickMany` is set, or null if cancelled.
+	*/
+	quickPick(items: QuickPickItem[], options?: QuickPickOptions): Promise<QuickPickItem | QuickPickItem[] | null>;
+	/**
+	* Show a picker described by a single options object and wait for a choice (async)
+	* 
+	* Same list as `quickPick`: resolves with the chosen item, the checked
+	* items when `multiSelect` is set, or null if cancelled.
+	*/
+	showPicker(options: PickerOptions): Promise<QuickPickItem | QuickPickItem[] | null>;
+	/**
+	* Show a message with buttons and wait for one to be chosen (async)
+	* 
+	* Resolves with the label of the chosen button, or null if the popup is
+	* dismissed with Escape. Without buttons, a single OK button is shown.
+	*/
+	showConfirm(message: string, buttons?: string[]): Promise<string | null>;
+	/**
+	* Read a line of text in the prompt (async)
+	* 
+	* Resolves with the entered text, or null if cancelled. With
+	* `options.password`, the input is masked and is not passed to
+	* `prompt_changed` handlers or the clipboard.
+	*/
+	showInput(prompt: string, options?: InputOptions): Promise<string | null>;
+	/**
 	* Start an interactive prompt
 	*/
 	startPrompt(label: string, promptType: string): boolean;
@@ -1049,14 +2357,48 @@ interface EditorAPI {
 	*/
 	setSplitRatio(splitId: number, ratio: number): boolean;
 	/**
+	* Draw a split with another theme, e.g. a light theme for a preview
+	* Pass null to go back to the editor theme. Unknown themes are ignored.
+	*/
+	setSplitTheme(splitId: number, theme: string | null): boolean;
+	/**
 	* Distribute all splits evenly
 	*/
 	distributeSplitsEvenly(): boolean;
 	/**
+	* Get the split layout tree (async, returns request_id)
+	*/
+	getLayout(): Promise<LayoutNode>;
+	/**
+	* Split a pane or container (async, returns request_id)
+	* 
+	* `direction` is "horizontal" (stacked) or "vertical" (side by side).
+	* The new pane shows `bufferId` (default: the split's buffer), becomes
+	* active, and its split ID is what the promise resolves to.
+	*/
+	splitEditor(splitId: number, direction: string, bufferId?: number): Promise<number>;
+	/**
 	* Set cursor position in a buffer
+	* 
+	* Throws `InvalidBufferIdError`
 	*/
 	setBufferCursor(bufferId: number, position: number): boolean;
 	/**
+	* Replace the active buffer's cursors with one per selection
+	* 
+	* Each selection puts a cursor at `head`, selecting back to `anchor`.
+	* The last one becomes the primary cursor. The change can be undone.
+	*/
+	setSelections(selections: SelectionSpec[]): boolean;
+	/**
+	* Add a cursor at a byte offset in the active buffer (it becomes the primary cursor)
+	*/
+	addCursor(position: number): boolean;
+	/**
+	* Remove all cursors of the active buffer except the original one
+	*/
+	clearSecondaryCursors(): boolean;
+	/**
 	* Set a line indicator in the gutter
 	*/
 	setLineIndicator(bufferId: number, line: number, namespace: string, symbol: string, r: number, g: number, b: number, priority: number): boolean;
@@ -1065,15 +2407,74 @@ interface EditorAPI {
 	*/
 	clearLineIndicators(bufferId: number, namespace: string): boolean;
 	/**
+	* Register a gutter column `width` cells wide for a namespace
+	* 
+	* The column is drawn left of the indicator column, followed by a space,
+	* in each buffer that `setGutterText` or `setGutterTexts` has put text
+	* in. Registering the namespace again changes its width. Widths over 40
+	* are clamped to 40.
+	*/
+	registerGutter(namespace: string, width: number): boolean;
+	/**
+	* Remove a gutter column and its text from every buffer
+	*/
+	unregisterGutter(namespace: string): boolean;
+	/**
+	* Set the text of a line in a registered gutter column
+	* 
+	* Text wider than the column is cut off. `color` is an RGB array or a
+	* theme key (the line number color when omitted). The text moves with
+	* its line as the buffer is edited.
+	*/
+	setGutterText(bufferId: number, namespace: string, line: number, text: string, color?: OverlayColorSpec): boolean;
+	/**
+	* Replace all text of a gutter column in a buffer with `lines`
+	*/
+	setGutterTexts(bufferId: number, namespace: string, lines: GutterText[]): boolean;
+	/**
+	* Clear the text of a gutter column in a buffer (the column stays)
+	*/
+	clearGutterText(bufferId: number, namespace: string): boolean;
+	/**
+	* Tint the background of a whole line, across the full editor width
+	* 
+	* `color` is an RGB array or a theme key such as "editor.diff_add_bg".
+	* Backgrounds move with their line as the buffer is edited. Setting a
+	* line again in the same namespace replaces its color; when namespaces
+	* overlap, the most recently set background wins.
+	*/
+	setLineBackground(bufferId: number, namespace: string, line: number, color: OverlayColorSpec): boolean;
+	/**
+	* Replace all line backgrounds of a namespace with `lines`
+	*/
+	setLineBackgrounds(bufferId: number, namespace: string, lines: LineBackground[]): boolean;
+	/**
+	* Clear line backgrounds in a namespace
+	*/
+	clearLineBackgrounds(bufferId: number, namespace: string): boolean;
+	/**
 	* Enable or disable line numbers for a buffer
 	*/
 	setLineNumbers(bufferId: number, enabled: boolean): boolean;
 	/**
-	* Create a scroll sync group for anchor-based synchronized scrolling
+	* Override how a buffer is displayed (wrap column, tab width, whitespace,
+	* conceal rules). Unset fields keep their current value.
+	*/
+	setBufferDisplayOptions(bufferId: number, options: BufferDisplayOptions): boolean;
+	/**
+	* Create a scroll sync group for synchronized scrolling
 	*/
 	createScrollSyncGroup(groupId: number, leftSplit: number, rightSplit: number): boolean;
 	/**
-	* Set sync anchors for a scroll sync group
+	* Add a split to a scroll sync group
+	*/
+	addScrollSyncSplit(groupId: number, splitId: number): boolean;
+	/**
+	* Remove a split from a scroll sync group
+	*/
+	removeScrollSyncSplit(groupId: number, splitId: number): boolean;
+	/**
+	* Set sync anchors for a scroll sync group, each with one line per split
 	*/
 	setScrollSyncAnchors(groupId: number, anchors: number[][]): boolean;
 	/**
@@ -1087,6 +2488,12 @@ interface EditorAPI {
 	*/
 	executeActions(actions: ActionSpec[]): boolean;
 	/**
+	* Search the active buffer, or the project with `options.project`, as if
+	* `pattern` had been entered in the search prompt. Unset options keep the
+	* current search toggles.
+	*/
+	runSearch(pattern: string, options?: SearchOptions): boolean;
+	/**
 	* Show an action popup
 	* 
 	* Takes a typed ActionPopupOptions struct - serde validates field names at runtime
@@ -1124,7 +2531,8 @@ interface EditorAPI {
 	/**
 	* Set virtual buffer content (takes array of entry objects)
 	* 
-	* Note: entries should be TextPropertyEntry[] - uses manual parsing for HashMap support
+	* Note: entries should be TextPropertyEntry[] - uses manual parsing for HashMap support.
+	* Works in read-only buffers too; throws `InvalidBufferIdError`
 	*/
 	setVirtualBufferContent(bufferId: number, entriesArr: Record<string, unknown>[]): boolean;
 	/**
@@ -1136,6 +2544,15 @@ interface EditorAPI {
 	*/
 	spawnProcess(command: string, args: string[], cwd?: string): ProcessHandle<SpawnResult>;
 	/**
+	* Make an HTTP request (async)
+	* 
+	* Only hosts listed in the `plugin_fetch.allowed_hosts` setting can be
+	* reached. Resolves with the status, headers and body for any HTTP
+	* status; rejects when the host is not allowed, the request fails or
+	* times out, or the body exceeds `plugin_fetch.max_response_bytes`.
+	*/
+	fetch(url: string, options?: FetchOptions): Promise<FetchResponse>;
+	/**
 	* Wait for a process to complete and get its result (async)
 	*/
 	spawnProcessWait(processId: number): Promise<SpawnResult>;
@@ -1144,17 +2561,80 @@ interface EditorAPI {
 	*/
 	getBufferText(bufferId: number, start: number, end: number): Promise<string>;
 	/**
+	* Get lines `startLine` (inclusive) to `endLine` (exclusive) of a
+	* buffer, without their line endings (async, returns request_id)
+	* 
+	* Lines past the end of the buffer are left out.
+	*/
+	getBufferLines(bufferId: number, startLine: number, endLine: number): Promise<string[]>;
+	/**
+	* Get the number of lines in a buffer (async, returns request_id)
+	* 
+	* A buffer ending with a newline has an empty last line.
+	*/
+	getLineCount(bufferId: number): Promise<number>;
+	/**
+	* Convert a byte offset to a 0-indexed line and byte column (async,
+	* returns request_id)
+	*/
+	offsetToLineCol(bufferId: number, offset: number): Promise<{
+		line: number;
+		column: number;
+	}>;
+	/**
+	* Convert a 0-indexed line and byte column to a byte offset (async,
+	* returns request_id)
+	* 
+	* The column may point at the end of the line, but not past it.
+	*/
+	lineColToOffset(bufferId: number, line: number, column: number): Promise<number>;
+	/**
+	* Run a tree-sitter query against a buffer's syntax tree (async,
+	* returns request_id)
+	* 
+	* Resolves to the captures sorted by start offset. Rejects if the
+	* buffer's language has no tree-sitter grammar or the query is invalid.
+	*/
+	querySyntax(bufferId: number, query: string): Promise<SyntaxCapture[]>;
+	/**
+	* Find the matches of `pattern` in a buffer (async, returns request_id)
+	* 
+	* The search runs in the editor with the same engine as the search
+	* prompt, reading large files in chunks, so plugins don't need to pull
+	* the text into JS. Resolves to the matches in order, as byte ranges;
+	* empty matches are skipped. Rejects if `opts.regex` is set and the
+	* pattern is not a valid regular expression.
+	*/
+	search(bufferId: number, pattern: string, opts?: BufferSearchOptions): Promise<SearchMatch[]>;
+	/**
+	* Compute a line or word diff between two texts (async, returns request_id)
+	* 
+	* Resolves to `DiffHunk[]`, or `TsCompositeHunk[]` when `opts.composite` is set.
+	*/
+	computeDiff(oldText: string, newText: string, opts?: ComputeDiffOptions): Promise<DiffHunk[] | TsCompositeHunk[]>;
+	/**
 	* Delay/sleep (async, returns request_id)
 	*/
 	delay(durationMs: number): Promise<void>;
 	/**
+	* Run a function on a worker thread (async, returns request_id)
+	* 
+	* The function runs in its own QuickJS runtime without the `editor` API:
+	* it is passed as a function or its source text, and its arguments and
+	* result are copied as JSON. Resolves to the function's result (awaited
+	* if it returns a promise) and rejects with the error it throws.
+	*/
+	runInWorker(source: string | ((...args: any[]) => unknown), args?: unknown[]): Promise<unknown>;
+	/**
 	* Send LSP request (async, returns request_id)
 	*/
 	sendLspRequest(language: string, method: string, params: Record<string, unknown> | null): Promise<unknown>;
 	/**
 	* Spawn a background process (async, returns request_id which is also process_id)
+	* The handle resolves when the process exits; `onStdout`/`onStderr`
+	* on it receive the process's output line by line while it runs.
 	*/
-	spawnBackgroundProcess(command: string, args: string[], cwd?: string): ProcessHandle<BackgroundProcessResult>;
+	spawnBackgroundProcess(command: string, args: string[], cwd?: string): BackgroundProcessHandle;
 	/**
 	* Kill a background process
 	*/
@@ -1181,11 +2661,167 @@ interface EditorAPI {
 	reloadPlugin(name: string): Promise<boolean>;
 	/**
 	* List all loaded plugins (async)
-	* Returns array of { name: string, path: string, enabled: boolean }
+	* Returns array of { name, path, enabled, activationEvents, activatedBy }.
+	* `activatedBy` is the event that loaded the plugin ("startup", "onLanguage:rust", ...),
+	* or null while the plugin is waiting for one of its `activationEvents`.
 	*/
 	listPlugins(): Promise<Array<{
 		name: string;
 		path: string;
 		enabled: boolean;
+		activationEvents: string[];
+		activatedBy: string | null;
 	}>>;
 }
+/**
+* `editor.buffers` methods
+*/
+interface EditorBuffersAPI {
+	/**
+	* Get buffer length in bytes
+	*/
+	getBufferLength(bufferId: number): number;
+	/**
+	* Insert text at a position in a buffer
+	* `baseVersion` is the buffer version (`getBufferInfo(id).version`) the
+	* position was computed against; the position is then moved past edits
+	* the user made since, and the insert is skipped if they conflict.
+	* Throws `InvalidBufferIdError`, `ReadOnlyBufferError` if editing is
+	* disabled in the buffer, or `OutOfRangeError`
+	*/
+	insertText(bufferId: number, position: number, text: string, baseVersion?: number): boolean;
+	/**
+	* Delete a range from a buffer
+	* `baseVersion` works as for `insertText`.
+	* Throws like `insertText`
+	*/
+	deleteRange(bufferId: number, start: number, end: number, baseVersion?: number): boolean;
+	/**
+	* Apply several edits to a buffer at once, as a single undo step
+	* 
+	* Every range refers to the buffer before any of the edits, so offsets
+	* don't need adjusting for the edits before them. Ranges must not
+	* overlap; if they do, or any edit is skipped, nothing is applied.
+	* `baseVersion` works as for `insertText`.
+	* Throws like `insertText`
+	*/
+	applyEdits(bufferId: number, edits: BufferEdit[], baseVersion?: number): boolean;
+	/**
+	* Start grouping edits to a buffer into a single undo step
+	* 
+	* Every insert and delete made until `endUndoGroup` is undone and redone
+	* together. The group is closed automatically if the action or handler
+	* making the edits throws, or if it stays open for more than 5 seconds.
+	* Groups don't nest: a second call before `endUndoGroup` is ignored.
+	* Throws `InvalidBufferIdError`
+	*/
+	beginUndoGroup(bufferId: number): boolean;
+	/**
+	* Close the undo group started with `beginUndoGroup`
+	* 
+	* Throws like `beginUndoGroup`
+	*/
+	endUndoGroup(bufferId: number): boolean;
+	/**
+	* Undo the last edit step in a buffer
+	* 
+	* A group of edits made between `beginUndoGroup` and `endUndoGroup` is
+	* one step. Throws `InvalidBufferIdError`, or `ReadOnlyBufferError` if
+	* editing is disabled in the buffer
+	*/
+	undo(bufferId: number): boolean;
+	/**
+	* Redo the last edit step undone in a buffer
+	* 
+	* Throws like `undo`
+	*/
+	redo(bufferId: number): boolean;
+	/**
+	* Get the number of steps that can be undone in a buffer
+	* 
+	* Read from the editor state, so undos and edits sent during the same
+	* call are not counted yet
+	*/
+	getUndoDepth(bufferId: number): number;
+	/**
+	* Insert text at cursor position in active buffer
+	* 
+	* Throws `ReadOnlyBufferError` if editing is disabled in the buffer
+	*/
+	insertAtCursor(text: string): boolean;
+	/**
+	* Get buffer text range (async, returns request_id)
+	*/
+	getBufferText(bufferId: number, start: number, end: number): Promise<string>;
+	/**
+	* Get lines `startLine` (inclusive) to `endLine` (exclusive) of a
+	* buffer, without their line endings (async, returns request_id)
+	* 
+	* Lines past the end of the buffer are left out.
+	*/
+	getBufferLines(bufferId: number, startLine: number, endLine: number): Promise<string[]>;
+	/**
+	* Get the number of lines in a buffer (async, returns request_id)
+	* 
+	* A buffer ending with a newline has an empty last line.
+	*/
+	getLineCount(bufferId: number): Promise<number>;
+	/**
+	* Convert a byte offset to a 0-indexed line and byte column (async,
+	* returns request_id)
+	*/
+	offsetToLineCol(bufferId: number, offset: number): Promise<{
+		line: number;
+		column: number;
+	}>;
+	/**
+	* Convert a 0-indexed line and byte column to a byte offset (async,
+	* returns request_id)
+	* 
+	* The column may point at the end of the line, but not past it.
+	*/
+	lineColToOffset(bufferId: number, line: number, column: number): Promise<number>;
+	/**
+	* Run a tree-sitter query against a buffer's syntax tree (async,
+	* returns request_id)
+	* 
+	* Resolves to the captures sorted by start offset. Rejects if the
+	* buffer's language has no tree-sitter grammar or the query is invalid.
+	*/
+	querySyntax(bufferId: number, query: string): Promise<SyntaxCapture[]>;
+	/**
+	* Find the matches of `pattern` in a buffer (async, returns request_id)
+	* 
+	* The search runs in the editor with the same engine as the search
+	* prompt, reading large files in chunks, so plugins don't need to pull
+	* the text into JS. Resolves to the matches in order, as byte ranges;
+	* empty matches are skipped. Rejects if `opts.regex` is set and the
+	* pattern is not a valid regular expression.
+	*/
+	search(bufferId: number, pattern: string, opts?: BufferSearchOptions): Promise<SearchMatch[]>;
+}
+/**
+* `editor.fs` methods
+*/
+interface EditorFsAPI {
+	/**
+	* Check if file exists
+	*/
+	fileExists(path: string): boolean;
+	/**
+	* Read file contents
+	*/
+	readFile(path: string): string | null;
+	/**
+	* Write file contents
+	*/
+	writeFile(path: string, content: string): boolean;
+	/**
+	* Read directory contents (returns array of {name, is_file, is_dir})
+	*/
+	readDir(path: string): DirEntry[];
+	/**
+	* Get file stat information
+	*/
+	fileStat(path: string): unknown;
+}
@@ -69,6 +69,8 @@ interface PackageManifest {
   fresh?: {
     min_version?: string;
     entry?: string;
+    /** Events that load the plugin (e.g. "onLanguage:rust"); omitted = at startup */
+    activationEvents?: string[];
     themes?: Array<{
       file: string;
       name: string;
@@ -1152,6 +1154,7 @@ interface PkgManagerState {
   selectedIndex: number;
   focus: FocusTarget;  // What element has Tab focus
   isLoading: boolean;
+  loadedPlugins: Array<{ path: string; activationEvents: string[]; activatedBy: string | null }>;
 }
 
 const pkgState: PkgManagerState = {
@@ -1165,6 +1168,7 @@ const pkgState: PkgManagerState = {
   selectedIndex: 0,
   focus: { type: "list" },
   isLoading: false,
+  loadedPlugins: [],
 };
 
 // Theme-aware color configuration
@@ -1635,6 +1639,13 @@ function buildListViewEntries(): TextPropertyEntry[] {
     if (metaLine.length > DETAIL_WIDTH - 2) metaLine = metaLine.slice(0, DETAIL_WIDTH - 5) + "...";
     rightLines.push({ text: metaLine, type: "detail-meta" });
 
+    // Activation status for installed plugins
+    if (selectedItem.installedPackage?.type === "plugin") {
+      let activation = getActivationStatus(selectedItem.installedPackage);
+      if (activation.length > DETAIL_WIDTH - 2) activation = activation.slice(0, DETAIL_WIDTH - 5) + "...";
+      rightLines.push({ text: activation, type: "detail-meta" });
+    }
+
     rightLines.push({ text: "", type: "blank" });
 
     // Description (wrapped)
@@ -1903,6 +1914,20 @@ function applyPkgManagerHighlighting(): void {
   }
 }
 
+/**
+ * Describe whether an installed plugin is loaded and what activated it
+ */
+function getActivationStatus(pkg: InstalledPackage): string {
+  const plugin = pkgState.loadedPlugins.find(p => p.path.startsWith(pkg.path));
+  if (!plugin) {
+    return "Not loaded";
+  }
+  if (plugin.activatedBy === null) {
+    return `Waiting for: ${plugin.activationEvents.join(", ")}`;
+  }
+  return `Activated by: ${plugin.activatedBy}`;
+}
+
 /**
  * Update the package manager view
  */
@@ -1940,6 +1965,7 @@ async function openPackageManager(): Promise<void> {
   // This allows viewing/managing installed packages without waiting for network
   pkgState.items = buildPackageList();
   pkgState.isLoading = false;
+  pkgState.loadedPlugins = await editor.listPlugins();
 
   // Build initial entries
   const entries = buildListViewEntries();
@@ -1964,9 +1990,10 @@ async function openPackageManager(): Promise<void> {
 
   // Sync registry in background and update view when done
   // User can still interact with installed packages during sync
-  syncRegistry().then(() => {
+  syncRegistry().then(async () => {
     if (pkgState.isOpen) {
       pkgState.items = buildPackageList();
+      pkgState.loadedPlugins = await editor.listPlugins();
       updatePkgManagerView();
     }
   });
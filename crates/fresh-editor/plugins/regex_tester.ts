@@ -0,0 +1,586 @@
+/// <reference path="./lib/fresh.d.ts" />
+const editor = getEditor();
+
+
+/**
+ * Regex Tester Plugin - Try out a regular expression on sample text
+ *
+ * Opens two panes below the current one: an editable sample text buffer and
+ * a panel listing the matches and capture groups of the pattern. Matches are
+ * highlighted in the sample as the pattern is typed or the sample is edited.
+ * In the panel:
+ * - e / Enter: edit the pattern (matches update live while typing)
+ * - i / m / s: toggle the ignore-case, multiline and dot-all flags
+ * - b: search the original buffer with the pattern
+ * - p: search the project with the pattern
+ * - q / Escape: close the tester
+ *
+ * Patterns are evaluated with JavaScript regular expressions; the editor's
+ * search uses Rust regex syntax, which lacks look-around and backreferences.
+ */
+
+// =============================================================================
+// Types and Interfaces
+// =============================================================================
+
+type RegexFlag = "i" | "m" | "s";
+
+interface RegexMatch {
+  start: number;  // Byte offset in the sample
+  end: number;
+  line: number;   // 1-indexed
+  column: number; // 1-indexed, in characters
+  text: string;
+  groups: { name: string; text: string | undefined; start: number; end: number }[];
+}
+
+interface RegexTesterState {
+  isOpen: boolean;
+  sourceSplitId: number | null;
+  sampleBufferId: number | null;
+  sampleSplitId: number | null;
+  panelBufferId: number | null;
+  panelSplitId: number | null;
+  pattern: string;
+  flags: Record<RegexFlag, boolean>;
+  sample: string;
+  matches: RegexMatch[];
+  error: string | null;
+  patternBeforeEdit: string | null;
+}
+
+// =============================================================================
+// State Management
+// =============================================================================
+
+const testerState: RegexTesterState = {
+  isOpen: false,
+  sourceSplitId: null,
+  sampleBufferId: null,
+  sampleSplitId: null,
+  panelBufferId: null,
+  panelSplitId: null,
+  pattern: "",
+  flags: { i: false, m: false, s: false },
+  sample: "",
+  matches: [],
+  error: null,
+  patternBeforeEdit: null,
+};
+
+// Stop listing (and highlighting) matches after this many
+const MAX_MATCHES = 500;
+
+// Largest sample taken from the original buffer when nothing is selected
+const MAX_SAMPLE_BYTES = 4096;
+
+// The `d` flag (match indices) gives capture group positions where supported
+const HAS_INDICES = (() => {
+  try {
+    new RegExp("", "d");
+    return true;
+  } catch (_e) {
+    return false;
+  }
+})();
+
+// =============================================================================
+// Color Definitions
+// =============================================================================
+
+const colors = {
+  title: [255, 200, 100] as [number, number, number],   // Gold
+  header: [150, 200, 255] as [number, number, number],  // Light blue
+  error: [255, 100, 100] as [number, number, number],   // Red
+  footer: [120, 120, 120] as [number, number, number],  // Gray
+  match: [70, 90, 60] as [number, number, number],      // Green-tinted background
+  // Backgrounds for capture groups 1, 2, 3, ... (cycled)
+  groups: [
+    [60, 80, 120],
+    [110, 70, 110],
+    [120, 90, 50],
+    [50, 100, 100],
+  ] as [number, number, number][],
+};
+
+// =============================================================================
+// Mode Definition
+// =============================================================================
+
+editor.defineMode(
+  "regex-tester",
+  "normal", // inherit from normal mode for cursor movement
+  [
+    ["e", "regex_tester_edit_pattern"],
+    ["Return", "regex_tester_edit_pattern"],
+    ["i", "regex_tester_toggle_ignore_case"],
+    ["m", "regex_tester_toggle_multiline"],
+    ["s", "regex_tester_toggle_dot_all"],
+    ["b", "regex_tester_search_buffer"],
+    ["p", "regex_tester_search_project"],
+    ["q", "regex_tester_close"],
+    ["Escape", "regex_tester_close"],
+  ],
+  true // read-only
+);
+
+// =============================================================================
+// Matching
+// =============================================================================
+
+/**
+ * Byte offset of every UTF-16 index of `str` (plus one past the end), since
+ * overlays are placed by byte offset and TextEncoder is not available
+ */
+function byteOffsets(str: string): number[] {
+  const offsets: number[] = new Array(str.length + 1);
+  let bytes = 0;
+  for (let i = 0; i < str.length; i++) {
+    offsets[i] = bytes;
+    const code = str.charCodeAt(i);
+    if (code <= 0x7f) bytes += 1;
+    else if (code <= 0x7ff) bytes += 2;
+    else if (code >= 0xd800 && code <= 0xdbff) {
+      // The low surrogate shares the 4 bytes of the pair
+      offsets[i + 1] = bytes;
+      bytes += 4;
+      i++;
+    } else bytes += 3;
+  }
+  offsets[str.length] = bytes;
+  return offsets;
+}
+
+function flagString(): string {
+  return (["i", "m", "s"] as RegexFlag[]).filter(flag => testerState.flags[flag]).join("");
+}
+
+/**
+ * Names of the capture groups by group number; unnamed groups are numbered
+ */
+function groupNames(pattern: string): string[] {
+  const names: string[] = [""];
+  let inClass = false;
+  for (let i = 0; i < pattern.length; i++) {
+    const c = pattern[i];
+    if (c === "\\") {
+      i++;
+    } else if (inClass) {
+      inClass = c !== "]";
+    } else if (c === "[") {
+      inClass = true;
+    } else if (c === "(") {
+      if (pattern[i + 1] !== "?") {
+        names.push(String(names.length));
+      } else if (pattern[i + 2] === "<" && pattern[i + 3] !== "=" && pattern[i + 3] !== "!") {
+        const close = pattern.indexOf(">", i);
+        names.push(close > 0 ? pattern.slice(i + 3, close) : String(names.length));
+      }
+    }
+  }
+  return names;
+}
+
+function runPattern(): void {
+  testerState.matches = [];
+  testerState.error = null;
+  if (testerState.pattern === "") return;
+
+  let regex: RegExp;
+  try {
+    regex = new RegExp(testerState.pattern, "g" + (HAS_INDICES ? "d" : "") + flagString());
+  } catch (e) {
+    testerState.error = e instanceof Error ? e.message : String(e);
+    return;
+  }
+
+  const sample = testerState.sample;
+  const offsets = byteOffsets(sample);
+  const names = groupNames(testerState.pattern);
+  let m: RegExpExecArray | null;
+  while (testerState.matches.length < MAX_MATCHES && (m = regex.exec(sample)) !== null) {
+    if (m[0].length === 0) {
+      // Step past empty matches so the loop terminates
+      regex.lastIndex++;
+    }
+
+    const before = sample.slice(0, m.index);
+    const lineStart = before.lastIndexOf("\n") + 1;
+    const indices = (m as RegExpExecArray & { indices?: ([number, number] | undefined)[] }).indices;
+    const groups: RegexMatch["groups"] = [];
+    for (let g = 1; g < m.length; g++) {
+      const span = indices ? indices[g] : undefined;
+      groups.push({
+        name: names[g] ?? String(g),
+        text: m[g],
+        start: span ? offsets[span[0]] : -1,
+        end: span ? offsets[span[1]] : -1,
+      });
+    }
+    testerState.matches.push({
+      start: offsets[m.index],
+      end: offsets[m.index + m[0].length],
+      line: before.split("\n").length,
+      column: m.index - lineStart + 1,
+      text: m[0],
+      groups,
+    });
+  }
+}
+
+/**
+ * The pattern for the editor's (Rust regex) search, with flags inlined
+ */
+function searchPattern(): string {
+  const inline = (testerState.flags.m ? "m" : "") + (testerState.flags.s ? "s" : "");
+  return inline ? `(?${inline})${testerState.pattern}` : testerState.pattern;
+}
+
+// =============================================================================
+// Views
+// =============================================================================
+
+function highlightSample(): void {
+  const bufferId = testerState.sampleBufferId;
+  if (bufferId === null) return;
+  editor.clearNamespace(bufferId, "regex-tester");
+  for (const match of testerState.matches) {
+    if (match.end > match.start) {
+      editor.addOverlay(bufferId, "regex-tester", match.start, match.end, { bg: colors.match });
+    }
+    match.groups.forEach((group, index) => {
+      if (group.start >= 0 && group.end > group.start) {
+        editor.addOverlay(bufferId, "regex-tester", group.start, group.end, {
+          bg: colors.groups[index % colors.groups.length],
+        });
+      }
+    });
+  }
+}
+
+function quote(text: string | undefined): string {
+  if (text === undefined) return editor.t("panel.unmatched");
+  const escaped = JSON.stringify(text);
+  return escaped.length > 60 ? escaped.slice(0, 57) + "...\"" : escaped;
+}
+
+interface StyledLine {
+  text: string;
+  color: [number, number, number] | null;
+  bold?: boolean;
+}
+
+function buildLines(): StyledLine[] {
+  const lines: StyledLine[] = [];
+  lines.push({ text: editor.t("panel.title"), color: colors.title, bold: true });
+  lines.push({ text: "", color: null });
+
+  const pattern = testerState.pattern === ""
+    ? editor.t("panel.no_pattern")
+    : `/${testerState.pattern}/${flagString()}`;
+  lines.push({ text: editor.t("panel.pattern", { pattern }), color: null });
+
+  const check = (flag: RegexFlag) => testerState.flags[flag] ? "[x]" : "[ ]";
+  lines.push({
+    text: editor.t("panel.flags", { i: check("i"), m: check("m"), s: check("s") }),
+    color: null,
+  });
+  lines.push({ text: "", color: null });
+
+  if (testerState.error !== null) {
+    lines.push({ text: editor.t("panel.error", { error: testerState.error }), color: colors.error });
+  } else if (testerState.pattern !== "") {
+    const count = testerState.matches.length >= MAX_MATCHES
+      ? editor.t("panel.match_count_limited", { max: String(MAX_MATCHES) })
+      : editor.t("panel.match_count", { count: String(testerState.matches.length) });
+    lines.push({ text: count, color: colors.header, bold: true });
+    testerState.matches.forEach((match, index) => {
+      lines.push({
+        text: editor.t("panel.match", {
+          index: String(index + 1),
+          line: String(match.line),
+          column: String(match.column),
+          text: quote(match.text),
+        }),
+        color: null,
+      });
+      for (const group of match.groups) {
+        lines.push({ text: `      ${group.name} = ${quote(group.text)}`, color: null });
+      }
+    });
+  }
+
+  lines.push({ text: "", color: null });
+  lines.push({ text: editor.t("panel.footer"), color: colors.footer });
+  return lines;
+}
+
+/**
+ * Calculate UTF-8 byte length of a string manually since TextEncoder is not available
+ */
+function getByteLength(str: string): number {
+  return byteOffsets(str)[str.length];
+}
+
+function updatePanel(): void {
+  const bufferId = testerState.panelBufferId;
+  if (bufferId === null) return;
+  const lines = buildLines();
+
+  editor.setVirtualBufferContent(bufferId, lines.map(line => ({
+    text: line.text + "\n",
+    properties: {},
+  })));
+
+  editor.clearNamespace(bufferId, "regex-tester");
+  let offset = 0;
+  for (const line of lines) {
+    const length = getByteLength(line.text);
+    if (line.color !== null && length > 0) {
+      editor.addOverlay(bufferId, "regex-tester", offset, offset + length, {
+        fg: line.color,
+        bold: line.bold ?? false,
+      });
+    }
+    offset += length + 1;
+  }
+}
+
+function refresh(): void {
+  runPattern();
+  highlightSample();
+  updatePanel();
+}
+
+async function reloadSample(): Promise<void> {
+  const bufferId = testerState.sampleBufferId;
+  if (bufferId === null) return;
+  testerState.sample = await editor.getBufferText(bufferId, 0, editor.getBufferLength(bufferId));
+  refresh();
+}
+
+/**
+ * The selection of the active buffer, or else its beginning
+ */
+async function initialSample(bufferId: number): Promise<string> {
+  const cursor = editor.getPrimaryCursor() as { selection: { start: number; end: number } | null } | null;
+  if (cursor && cursor.selection && cursor.selection.end > cursor.selection.start) {
+    return await editor.getBufferText(bufferId, cursor.selection.start, cursor.selection.end);
+  }
+  const length = Math.min(editor.getBufferLength(bufferId), MAX_SAMPLE_BYTES);
+  const text = await editor.getBufferText(bufferId, 0, length);
+  // Cut at the last complete line
+  const lastNewline = text.lastIndexOf("\n");
+  return length < editor.getBufferLength(bufferId) && lastNewline >= 0
+    ? text.slice(0, lastNewline + 1)
+    : text;
+}
+
+// =============================================================================
+// Public Commands
+// =============================================================================
+
+globalThis.regex_tester_open = async function(): Promise<void> {
+  if (testerState.isOpen) {
+    if (testerState.panelSplitId !== null) {
+      editor.focusSplit(testerState.panelSplitId);
+    }
+    return;
+  }
+
+  const sourceBufferId = editor.getActiveBufferId();
+  testerState.sourceSplitId = editor.getActiveSplitId();
+  testerState.sample = await initialSample(sourceBufferId);
+
+  try {
+    const sample = await editor.createVirtualBufferInSplit({
+      name: "*Regex Sample*",
+      readOnly: false,
+      entries: [{ text: testerState.sample, properties: {} }],
+      ratio: 0.5,
+      panelId: "regex-tester-sample",
+      showLineNumbers: true,
+      showCursors: true,
+    });
+    testerState.sampleBufferId = sample.bufferId;
+    testerState.sampleSplitId = sample.splitId ?? editor.getActiveSplitId();
+
+    const panel = await editor.createVirtualBufferInSplit({
+      name: "*Regex Tester*",
+      mode: "regex-tester",
+      readOnly: true,
+      entries: [],
+      ratio: 0.5,
+      // Beside the sample rather than below it, so the matches and their
+      // groups get the full height of the lower half
+      direction: "vertical",
+      panelId: "regex-tester-panel",
+      showLineNumbers: false,
+      showCursors: true,
+      editingDisabled: true,
+    });
+    testerState.panelBufferId = panel.bufferId;
+    testerState.panelSplitId = panel.splitId ?? editor.getActiveSplitId();
+  } catch (e) {
+    editor.debug(`Regex tester failed to open: ${e instanceof Error ? e.message : String(e)}`);
+    editor.setStatus(editor.t("status.failed_open"));
+    globalThis.regex_tester_close();
+    return;
+  }
+
+  testerState.isOpen = true;
+  refresh();
+  globalThis.regex_tester_edit_pattern();
+};
+
+globalThis.regex_tester_close = function(): void {
+  if (testerState.panelBufferId !== null) {
+    editor.closeBuffer(testerState.panelBufferId);
+  }
+  if (testerState.panelSplitId !== null && testerState.panelSplitId !== testerState.sourceSplitId) {
+    editor.closeSplit(testerState.panelSplitId);
+  }
+  if (testerState.sampleBufferId !== null) {
+    editor.closeBuffer(testerState.sampleBufferId);
+  }
+  if (testerState.sampleSplitId !== null && testerState.sampleSplitId !== testerState.sourceSplitId) {
+    editor.closeSplit(testerState.sampleSplitId);
+  }
+  if (testerState.sourceSplitId !== null) {
+    editor.focusSplit(testerState.sourceSplitId);
+  }
+
+  testerState.isOpen = false;
+  testerState.sourceSplitId = null;
+  testerState.sampleBufferId = null;
+  testerState.sampleSplitId = null;
+  testerState.panelBufferId = null;
+  testerState.panelSplitId = null;
+  testerState.matches = [];
+  testerState.patternBeforeEdit = null;
+};
+
+globalThis.regex_tester_edit_pattern = function(): void {
+  if (!testerState.isOpen) return;
+  testerState.patternBeforeEdit = testerState.pattern;
+  editor.startPromptWithInitial(editor.t("prompt.pattern"), "regex-tester-pattern", testerState.pattern);
+};
+
+function toggleFlag(flag: RegexFlag): void {
+  if (!testerState.isOpen) return;
+  testerState.flags[flag] = !testerState.flags[flag];
+  refresh();
+}
+
+globalThis.regex_tester_toggle_ignore_case = function(): void {
+  toggleFlag("i");
+};
+
+globalThis.regex_tester_toggle_multiline = function(): void {
+  toggleFlag("m");
+};
+
+globalThis.regex_tester_toggle_dot_all = function(): void {
+  toggleFlag("s");
+};
+
+function runSearch(project: boolean): void {
+  if (testerState.pattern === "" || testerState.error !== null) {
+    editor.setStatus(editor.t("status.no_valid_pattern"));
+    return;
+  }
+  if (testerState.sourceSplitId !== null) {
+    editor.focusSplit(testerState.sourceSplitId);
+  }
+  editor.runSearch(searchPattern(), {
+    regex: true,
+    caseSensitive: !testerState.flags.i,
+    wholeWord: false,
+    project,
+  });
+}
+
+globalThis.regex_tester_search_buffer = function(): void {
+  runSearch(false);
+};
+
+globalThis.regex_tester_search_project = function(): void {
+  runSearch(true);
+};
+
+// =============================================================================
+// Event Handlers
+// =============================================================================
+
+globalThis.onRegexTesterPromptChanged = function(args: { prompt_type: string; input: string }): boolean {
+  if (args.prompt_type !== "regex-tester-pattern") return true;
+  testerState.pattern = args.input;
+  refresh();
+  return true;
+};
+
+globalThis.onRegexTesterPromptConfirmed = function(args: {
+  prompt_type: string;
+  selected_index: number | null;
+  input: string;
+}): boolean {
+  if (args.prompt_type !== "regex-tester-pattern") return true;
+  testerState.pattern = args.input;
+  testerState.patternBeforeEdit = null;
+  refresh();
+  if (testerState.panelSplitId !== null) {
+    editor.focusSplit(testerState.panelSplitId);
+  }
+  return false;
+};
+
+globalThis.onRegexTesterPromptCancelled = function(args: { prompt_type: string }): boolean {
+  if (args.prompt_type !== "regex-tester-pattern") return true;
+  if (testerState.patternBeforeEdit !== null) {
+    testerState.pattern = testerState.patternBeforeEdit;
+    testerState.patternBeforeEdit = null;
+    refresh();
+  }
+  return true;
+};
+
+globalThis.onRegexTesterSampleEdited = async function(args: { buffer_id: number }): Promise<void> {
+  if (testerState.isOpen && args.buffer_id === testerState.sampleBufferId) {
+    await reloadSample();
+  }
+};
+
+globalThis.onRegexTesterBufferClosed = function(args: { buffer_id: number }): void {
+  if (!testerState.isOpen) return;
+  if (args.buffer_id === testerState.sampleBufferId) {
+    testerState.sampleBufferId = null;
+    globalThis.regex_tester_close();
+  } else if (args.buffer_id === testerState.panelBufferId) {
+    testerState.panelBufferId = null;
+    globalThis.regex_tester_close();
+  }
+};
+
+editor.on("prompt_changed", "onRegexTesterPromptChanged");
+editor.on("prompt_confirmed", "onRegexTesterPromptConfirmed");
+editor.on("prompt_cancelled", "onRegexTesterPromptCancelled");
+editor.on("after_insert", "onRegexTesterSampleEdited");
+editor.on("after_delete", "onRegexTesterSampleEdited");
+editor.on("buffer_closed", "onRegexTesterBufferClosed");
+
+// =============================================================================
+// Command Registration
+// =============================================================================
+
+editor.registerCommand(
+  "%cmd.regex_tester",
+  "%cmd.regex_tester_desc",
+  "regex_tester_open",
+  null
+);
+
+// =============================================================================
+// Plugin Initialization
+// =============================================================================
+
+editor.debug("Regex Tester plugin initialized - Use 'Regex Tester' command to open");
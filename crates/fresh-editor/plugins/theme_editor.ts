@@ -130,7 +130,7 @@ function loadThemeSections(): ThemeSection[] {
   const properties = schema.properties || {};
 
   // Section ordering
-  const sectionOrder = ["editor", "ui", "search", "diagnostic", "syntax"];
+  const sectionOrder = ["editor", "ui", "search", "diagnostic", "syntax", "terminal"];
 
   for (const [sectionName, sectionSchema] of Object.entries(properties)) {
     // Skip "name" field - it's not a color section
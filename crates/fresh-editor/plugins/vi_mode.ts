@@ -1573,6 +1573,8 @@ editor.defineMode("vi-insert", null, [
   // Pass through to standard editor shortcuts
   ["C-p", "command_palette"],
   ["C-q", "quit"],
+  // Vim's digraph key: C-k followed by two characters (a: -> ä)
+  ["C-k", "insert_digraph"],
 ], false); // read_only = false to allow normal typing
 
 // Define vi-find-char mode - binds all printable chars to the handler
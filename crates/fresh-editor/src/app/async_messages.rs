@@ -913,6 +913,7 @@ impl Editor {
 
         self.file_explorer = Some(view);
         self.set_status_message(t!("status.file_explorer_ready").to_string());
+        self.apply_pending_file_explorer_reveal();
     }
 
     /// Handle file explorer node toggle completed
@@ -934,6 +935,7 @@ impl Editor {
         view.update_scroll_for_selection();
         self.file_explorer = Some(view);
         self.file_explorer_sync_in_progress = false;
+        self.apply_pending_file_explorer_reveal();
     }
 }
 
@@ -968,6 +970,42 @@ impl Editor {
             .resolve_callback(callback_id, serde_json::to_string(&result).unwrap());
     }
 
+    /// Send a line of background process output to the plugin listening to it
+    pub(super) fn deliver_process_output(&self, process_id: u64, is_stderr: bool, data: &str) {
+        if let Some(plugin_name) = self.process_output_subscribers.get(&process_id) {
+            self.plugin_manager.deliver_process_output(
+                plugin_name.clone(),
+                process_id,
+                is_stderr,
+                data.strip_suffix('\n').unwrap_or(data).to_string(),
+            );
+        }
+    }
+
+    /// Handle a completed plugin HTTP request
+    pub(super) fn handle_plugin_fetch_response(
+        &mut self,
+        callback_id: fresh_core::api::JsCallbackId,
+        result: Result<fresh_core::api::FetchResponse, String>,
+    ) {
+        match result {
+            Ok(response) => {
+                tracing::debug!(
+                    "Fetch {} completed: status={}, body_len={}",
+                    callback_id,
+                    response.status,
+                    response.body.len()
+                );
+                self.plugin_manager
+                    .resolve_callback(callback_id, serde_json::to_string(&response).unwrap());
+            }
+            Err(e) => {
+                tracing::debug!("Fetch {} failed: {}", callback_id, e);
+                self.plugin_manager.reject_callback(callback_id, e);
+            }
+        }
+    }
+
     /// Process TypeScript plugin commands
     ///
     /// Returns true if any commands were processed
@@ -21,6 +21,7 @@ use crate::view::prompt::PromptType;
 use crate::view::split::SplitViewState;
 
 use super::help;
+use super::types::TabMruCycle;
 use super::Editor;
 
 impl Editor {
@@ -228,10 +229,15 @@ impl Editor {
         state
             .margins
             .set_line_numbers(self.config.editor.line_numbers);
+        state.trailing_whitespace_overlay.enabled =
+            self.config.editor.highlight_trailing_whitespace;
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Create metadata for this buffer
         let mut metadata =
@@ -252,6 +258,13 @@ impl Editor {
         // Store metadata for this buffer
         self.buffer_metadata.insert(buffer_id, metadata);
 
+        // Dependency code opens read-only until the user chooses to edit it
+        if self.config.editor.protect_library_files
+            && super::types::BufferMetadata::is_dependency_path(path)
+        {
+            self.protect_library_buffer(buffer_id);
+        }
+
         // Add buffer to the active split's tabs (but don't switch to it)
         let active_split = self.split_manager.active_split();
         if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
@@ -275,6 +288,7 @@ impl Editor {
 
         // Track file for auto-revert and conflict detection
         self.watch_file(path);
+        crate::services::crash_report::record_file_opened(path);
 
         // Fire AfterFileOpen hook for plugins
         self.plugin_manager.run_hook(
@@ -284,10 +298,43 @@ impl Editor {
                 path: path.to_path_buf(),
             },
         );
+        self.activate_language_plugins(buffer_id);
 
         Ok(buffer_id)
     }
 
+    /// Make a buffer read-only because it holds library code
+    ///
+    /// Unlike other read-only buffers, trying to edit it offers to allow editing
+    /// (see `report_editing_disabled`).
+    pub(super) fn protect_library_buffer(&mut self, buffer_id: BufferId) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+        }
+        if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
+            metadata.read_only = true;
+            metadata.library_protected = true;
+        }
+    }
+
+    /// Lift the library-code protection from the active buffer
+    pub(super) fn allow_editing_library_buffer(&mut self) {
+        let buffer_id = self.active_buffer();
+        let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) else {
+            return;
+        };
+        if !metadata.library_protected {
+            return;
+        }
+        metadata.read_only = false;
+        metadata.library_protected = false;
+        let name = metadata.display_name.clone();
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = false;
+        }
+        self.set_status_message(t!("buffer.library_editing_allowed", name = name).to_string());
+    }
+
     /// Open a local file (always uses local filesystem, not remote)
     ///
     /// This is used for opening local files like log files when in remote mode.
@@ -335,8 +382,11 @@ impl Editor {
         )?;
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Create metadata
         let metadata =
@@ -539,13 +589,18 @@ impl Editor {
         state
             .margins
             .set_line_numbers(self.config.editor.line_numbers);
+        state.trailing_whitespace_overlay.enabled =
+            self.config.editor.highlight_trailing_whitespace;
         // Set default line ending for new buffers from config
         state
             .buffer
             .set_default_line_ending(self.config.editor.default_line_ending.to_line_ending());
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
         self.buffer_metadata
             .insert(buffer_id, crate::app::types::BufferMetadata::new());
 
@@ -634,10 +689,15 @@ impl Editor {
         state
             .margins
             .set_line_numbers(self.config.editor.line_numbers);
+        state.trailing_whitespace_overlay.enabled =
+            self.config.editor.highlight_trailing_whitespace;
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Create metadata for this buffer (no file path)
         let metadata =
@@ -808,8 +868,11 @@ impl Editor {
             .set_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Set virtual buffer metadata
         let metadata = super::types::BufferMetadata::virtual_buffer(name, mode, read_only);
@@ -876,6 +939,30 @@ impl Editor {
         Ok(())
     }
 
+    /// Append entries to the end of a virtual buffer
+    ///
+    /// Existing text and properties are kept and the cursor does not move, so
+    /// content can be streamed in while the buffer is being read.
+    pub fn append_virtual_buffer_content(
+        &mut self,
+        buffer_id: BufferId,
+        entries: Vec<crate::primitives::text_property::TextPropertyEntry>,
+    ) -> Result<(), String> {
+        let state = self
+            .buffers
+            .get_mut(&buffer_id)
+            .ok_or_else(|| "Buffer not found".to_string())?;
+
+        let (text, properties) =
+            crate::primitives::text_property::TextPropertyManager::from_entries(entries);
+        let offset = state.buffer.len();
+        state.buffer.insert(offset, &text);
+        state.buffer.clear_modified();
+        state.text_properties.append(properties, offset);
+
+        Ok(())
+    }
+
     /// Open the built-in help manual in a read-only buffer
     ///
     /// If a help manual buffer already exists, switch to it instead of creating a new one.
@@ -984,6 +1071,43 @@ impl Editor {
         self.set_active_buffer(buffer_id);
     }
 
+    /// Open a read-only buffer listing the directories Fresh uses
+    ///
+    /// Shows where config, data, cache and logs live and whether each came
+    /// from the platform default, an environment override or portable mode.
+    pub fn show_paths(&mut self) {
+        let existing_buffer = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == help::PATHS_BUFFER_NAME)
+            .map(|(id, _)| *id);
+
+        if let Some(buffer_id) = existing_buffer {
+            self.set_active_buffer(buffer_id);
+            return;
+        }
+
+        let mut content = String::from("Fresh Directories\n");
+        content.push_str("=================\n\n");
+        content.push_str("Press 'q' to close this buffer.\n\n");
+        content.push_str(&crate::services::log_dirs::paths_report(&self.dir_context));
+
+        let buffer_id = self.create_virtual_buffer(
+            help::PATHS_BUFFER_NAME.to_string(),
+            "special".to_string(),
+            true,
+        );
+
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.buffer.insert(0, &content);
+            state.buffer.clear_modified();
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+
+        self.set_active_buffer(buffer_id);
+    }
+
     /// Show warnings by opening the warning log file directly
     ///
     /// If there are no warnings, shows a brief status message.
@@ -1058,6 +1182,33 @@ impl Editor {
         self.open_warning_log();
     }
 
+    /// Path shown for a buffer in the status bar and buffer pickers
+    ///
+    /// File-backed buffers show their workspace-relative path, or their absolute
+    /// path when `editor.show_absolute_paths` is set. Other buffers show their
+    /// display name.
+    pub fn buffer_display_path(&self, buffer_id: BufferId) -> String {
+        let Some(metadata) = self.buffer_metadata.get(&buffer_id) else {
+            return "[No Name]".to_string();
+        };
+        match metadata.file_path() {
+            Some(path) if self.config.editor.show_absolute_paths => {
+                path.to_string_lossy().into_owned()
+            }
+            _ => metadata.display_name.clone(),
+        }
+    }
+
+    /// Load deferred plugins that activate on a buffer's language
+    pub(crate) fn activate_language_plugins(&self, buffer_id: BufferId) {
+        if let Some(state) = self.buffers.get(&buffer_id) {
+            self.plugin_manager
+                .activate(fresh_core::activation::ActivationEvent::Language(
+                    state.language.clone(),
+                ));
+        }
+    }
+
     /// Get text properties at the cursor position in the active buffer
     pub fn get_text_properties_at_cursor(
         &self,
@@ -1174,10 +1325,18 @@ impl Editor {
                 .set_split_buffer(split_id, replacement_buffer);
         }
 
-        self.buffers.remove(&id);
+        if let Some(path) = self
+            .buffers
+            .remove(&id)
+            .and_then(|state| state.buffer.file_path().map(|p| p.to_path_buf()))
+        {
+            crate::services::crash_report::record_file_closed(&path);
+        }
         self.event_logs.remove(&id);
         self.seen_byte_ranges.remove(&id);
         self.buffer_metadata.remove(&id);
+        self.plugin_markers
+            .retain(|_, marker| marker.buffer_id != id);
         if let Some((request_id, _, _)) = self.semantic_tokens_in_flight.remove(&id) {
             self.pending_semantic_token_requests.remove(&request_id);
         }
@@ -1716,6 +1875,73 @@ impl Editor {
         }
     }
 
+    /// Step through the current split's tabs in most-recently-used order
+    ///
+    /// Consecutive presses keep walking the order captured by the first
+    /// one; ending the walk leaves the chosen tab as the most recent one,
+    /// as if it had been switched to directly.
+    pub fn cycle_tab_mru(&mut self, forward: bool) {
+        let active_split = self.split_manager.active_split();
+        let active_buffer = self.active_buffer();
+        let mut cycle = match self.tab_mru_cycle.take() {
+            Some(cycle)
+                if cycle.split_id == active_split
+                    && cycle.order.get(cycle.position) == Some(&active_buffer) =>
+            {
+                cycle
+            }
+            _ => {
+                let Some(view_state) = self.split_view_states.get(&active_split) else {
+                    return;
+                };
+                let order: Vec<BufferId> = view_state
+                    .mru_buffers(active_buffer)
+                    .into_iter()
+                    .filter(|id| {
+                        !self
+                            .buffer_metadata
+                            .get(id)
+                            .is_some_and(|m| m.hidden_from_tabs)
+                    })
+                    .collect();
+                TabMruCycle {
+                    split_id: active_split,
+                    order,
+                    position: 0,
+                    focus_history: view_state.focus_history.clone(),
+                }
+            }
+        };
+        if cycle.order.len() < 2 {
+            return;
+        }
+
+        let len = cycle.order.len();
+        cycle.position = if forward {
+            (cycle.position + 1) % len
+        } else {
+            (cycle.position + len - 1) % len
+        };
+        let target = cycle.order[cycle.position];
+
+        self.position_history.commit_pending_movement();
+        let current_state = self.active_state();
+        let position = current_state.cursors.primary().position;
+        let anchor = current_state.cursors.primary().anchor;
+        self.position_history
+            .record_movement(active_buffer, position, anchor);
+        self.position_history.commit_pending_movement();
+
+        self.set_active_buffer(target);
+
+        // Tabs passed over on the way don't count as visited
+        if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
+            view_state.focus_history = cycle.focus_history.clone();
+            view_state.push_focus(cycle.order[0]);
+        }
+        self.tab_mru_cycle = Some(cycle);
+    }
+
     /// Navigate back in position history
     pub fn navigate_back(&mut self) {
         // Set flag to prevent recording this navigation movement
@@ -0,0 +1,145 @@
+//! Keeping the editor's caches within `editor.cache_budget_mb`.
+//!
+//! The main loop periodically records the size of every evictable cache in
+//! the [`crate::services::cache_budget::CacheBudget`] and drops the least recently used ones when the total
+//! is over budget. Dropped caches are rebuilt the next time they are needed.
+
+use super::Editor;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::cache_budget::{format_bytes, CacheKey};
+use rust_i18n::t;
+use std::time::Duration;
+
+/// How often cache sizes are checked against the budget
+const CACHE_BUDGET_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Display name of the Memory Usage buffer
+const MEMORY_USAGE_BUFFER_NAME: &str = "*Memory Usage*";
+
+/// Buffer mode of the Memory Usage buffer ('g' refreshes it)
+const MEMORY_USAGE_MODE: &str = "memory-usage";
+
+impl Editor {
+    /// Record cache sizes and evict caches while over budget (rate limited)
+    pub(super) fn enforce_cache_budget(&mut self) {
+        if self.time_source.elapsed_since(self.last_cache_budget_check)
+            < CACHE_BUDGET_CHECK_INTERVAL
+        {
+            return;
+        }
+        self.last_cache_budget_check = self.time_source.now();
+        self.record_cache_usage();
+
+        for key in self.cache_budget.evictions() {
+            tracing::debug!("Cache budget exceeded, evicting {:?}", key);
+            match key {
+                CacheKey::Highlight(buffer_id) => {
+                    if let Some(state) = self.buffers.get_mut(&buffer_id) {
+                        state.highlighter.invalidate_all();
+                    }
+                }
+                CacheKey::FileIndex => self.file_provider.clear_cache(),
+                CacheKey::ClipboardHistory => self.clipboard.clear_history(),
+            }
+            self.cache_budget.evicted(key);
+        }
+    }
+
+    /// Report the current size of every cache to the budget.
+    ///
+    /// Buffers shown in a split count as just used, so their highlighting is
+    /// evicted only after that of hidden buffers.
+    fn record_cache_usage(&mut self) {
+        let visible: Vec<_> = self
+            .split_manager
+            .root()
+            .leaf_split_ids()
+            .into_iter()
+            .filter_map(|split_id| self.split_manager.buffer_for_split(split_id))
+            .collect();
+
+        let mut usage: Vec<(CacheKey, usize)> = self
+            .buffers
+            .iter()
+            .map(|(id, state)| (CacheKey::Highlight(*id), state.highlighter.cache_bytes()))
+            .collect();
+        usage.push((CacheKey::FileIndex, self.file_provider.cache_bytes()));
+        usage.push((CacheKey::ClipboardHistory, self.clipboard.history_bytes()));
+        self.cache_budget.record(usage);
+
+        for buffer_id in visible {
+            self.cache_budget.touch(CacheKey::Highlight(buffer_id));
+        }
+    }
+
+    /// Open (or refresh) a read-only buffer listing cache sizes against the budget
+    pub fn show_memory_usage(&mut self) {
+        self.record_cache_usage();
+
+        let budget = match self.cache_budget.limit_bytes() {
+            Some(limit) => format_bytes(limit),
+            None => t!("memory_usage.unlimited").to_string(),
+        };
+        let mut entries = vec![
+            TextPropertyEntry::text(format!("{}\n\n", t!("memory_usage.title"))),
+            TextPropertyEntry::text(format!(
+                "{}\n\n",
+                t!(
+                    "memory_usage.summary",
+                    used = format_bytes(self.cache_budget.total_bytes()),
+                    budget = budget
+                )
+            )),
+        ];
+
+        let usage = self.cache_budget.usage();
+        if usage.is_empty() {
+            entries.push(TextPropertyEntry::text(format!(
+                "  {}\n",
+                t!("memory_usage.empty")
+            )));
+        }
+        for (key, bytes) in usage {
+            let label = match key {
+                CacheKey::Highlight(buffer_id) => t!(
+                    "memory_usage.highlight",
+                    buffer = self
+                        .buffer_metadata
+                        .get(&buffer_id)
+                        .map(|m| m.display_name.clone())
+                        .unwrap_or_default()
+                )
+                .to_string(),
+                CacheKey::FileIndex => t!("memory_usage.file_index").to_string(),
+                CacheKey::ClipboardHistory => t!("memory_usage.clipboard_history").to_string(),
+            };
+            entries.push(TextPropertyEntry::text(format!(
+                "  {:>10}  {}\n",
+                format_bytes(bytes),
+                label
+            )));
+        }
+
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == MEMORY_USAGE_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                MEMORY_USAGE_BUFFER_NAME.to_string(),
+                MEMORY_USAGE_MODE.to_string(),
+                true,
+            )
+        });
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to fill memory usage buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+    }
+}
@@ -17,7 +17,8 @@ impl Editor {
 
     /// Save calibration and close wizard
     pub fn save_calibration(&mut self, wizard: CalibrationWizard) {
-        let translator = wizard.build_translator();
+        let mut translator = wizard.build_translator();
+        translator.set_layout(self.key_translator.layout());
         let count = translator.len();
 
         // Save to config file
@@ -4,6 +4,10 @@
 //! Uses only lowercase ASCII letters for navigation (s, g, a, y, r) because they work
 //! on virtually every terminal since 1970.
 //!
+//! Besides fixing broken escape sequences, the last group lets AZERTY/Dvorak
+//! users pick stand-in chords for punctuation shortcuts their layout can't type.
+//! Results are saved to this machine's `key_calibration.json`, not the config.
+//!
 //! The wizard operates in two phases:
 //! 1. Capture Phase: User presses each target key, wizard records what the terminal sends
 //! 2. Verify Phase: User can test their mappings work correctly before saving
@@ -190,6 +194,38 @@ pub fn calibration_groups() -> Vec<CalibrationGroup> {
                 },
             ],
         },
+        // Group 6: Punctuation chords from the default keymap that are awkward or
+        // missing on non-QWERTY layouts (6 keys). Press whatever chord should
+        // stand in for each one.
+        CalibrationGroup {
+            name: "Layout-Sensitive Chords",
+            targets: vec![
+                CalibrationTarget {
+                    name: "CTRL+/",
+                    expected: ExpectedKey::new(KeyCode::Char('/'), KeyModifiers::CONTROL),
+                },
+                CalibrationTarget {
+                    name: "CTRL+]",
+                    expected: ExpectedKey::new(KeyCode::Char(']'), KeyModifiers::CONTROL),
+                },
+                CalibrationTarget {
+                    name: "CTRL+.",
+                    expected: ExpectedKey::new(KeyCode::Char('.'), KeyModifiers::CONTROL),
+                },
+                CalibrationTarget {
+                    name: "ALT+[",
+                    expected: ExpectedKey::new(KeyCode::Char('['), KeyModifiers::ALT),
+                },
+                CalibrationTarget {
+                    name: "ALT+]",
+                    expected: ExpectedKey::new(KeyCode::Char(']'), KeyModifiers::ALT),
+                },
+                CalibrationTarget {
+                    name: "ALT+.",
+                    expected: ExpectedKey::new(KeyCode::Char('.'), KeyModifiers::ALT),
+                },
+            ],
+        },
     ]
 }
 
@@ -749,7 +785,7 @@ mod tests {
         let wizard = CalibrationWizard::new();
         let (step, total) = wizard.current_step_info();
         assert_eq!(step, 1);
-        assert_eq!(total, 24); // 4 + 4 + 8 + 4 + 4 = 24 keys
+        assert_eq!(total, 30); // 4 + 4 + 8 + 4 + 4 + 6 = 30 keys
     }
 
     #[test]
@@ -956,7 +992,7 @@ mod tests {
         let mut wizard = CalibrationWizard::new();
 
         // Skip all keys to get to verify phase
-        for _ in 0..24 {
+        for _ in 0..30 {
             let key = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::NONE);
             wizard.handle_capture_key(key);
         }
@@ -0,0 +1,506 @@
+//! Keybinding Cheat Sheet
+//!
+//! A searchable overlay listing the bindings of the active key context, grouped
+//! by category. Entries are collected from the live keymap (so user overrides
+//! and keymap switches are reflected), from plugin modes and from
+//! plugin-registered commands.
+
+use crate::input::keybindings::{Action, KeyContext};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use rust_i18n::t;
+
+/// Number of rows moved by PageUp/PageDown
+const PAGE_SIZE: usize = 10;
+
+/// Category under which a binding is listed
+///
+/// The declaration order is the display order.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheatSheetCategory {
+    Movement,
+    Selection,
+    Editing,
+    Clipboard,
+    MultiCursor,
+    Search,
+    File,
+    Navigation,
+    View,
+    Lsp,
+    Macros,
+    Bookmarks,
+    Prompt,
+    Popup,
+    FileExplorer,
+    Menu,
+    Terminal,
+    Settings,
+    /// Bindings of a plugin-defined mode (contains the mode name)
+    Mode(String),
+    /// Plugin commands
+    Plugin,
+    Other,
+}
+
+impl CheatSheetCategory {
+    /// Categorize a built-in action
+    pub fn for_action(action: &Action) -> Self {
+        use Action::*;
+        match action {
+            MoveLeft | MoveRight | MoveUp | MoveDown | MoveWordLeft | MoveWordRight
+            | MoveWordEnd | MoveSubwordLeft | MoveSubwordRight | MoveSubwordEnd | MoveLineStart
+            | MoveLineEnd | MovePageUp | MovePageDown | MoveDocumentStart | MoveDocumentEnd
+            | SmartHome | ScrollUp | ScrollDown | Recenter | GoToMatchingBracket
+            | GoToIndentBlockStart | GoToIndentBlockEnd => Self::Movement,
+            SelectLeft
+            | SelectRight
+            | SelectUp
+            | SelectDown
+            | SelectToParagraphUp
+            | SelectToParagraphDown
+            | SelectWordLeft
+            | SelectWordRight
+            | SelectWordEnd
+            | SelectSubwordLeft
+            | SelectSubwordRight
+            | SelectSubwordEnd
+            | SelectLineStart
+            | SelectLineEnd
+            | SelectDocumentStart
+            | SelectDocumentEnd
+            | SelectPageUp
+            | SelectPageDown
+            | SelectAll
+            | SelectWord
+            | SelectLine
+            | SelectIndentBlock
+            | ExpandSelection
+            | BlockSelectLeft
+            | BlockSelectRight
+            | BlockSelectUp
+            | BlockSelectDown
+            | SetMark => Self::Selection,
+            InsertChar(_)
+            | InsertNewline
+            | InsertTab
+            | DeleteBackward
+            | DeleteForward
+            | DeleteWordBackward
+            | DeleteWordForward
+            | DeleteSubwordBackward
+            | DeleteSubwordForward
+            | DeleteLine
+            | DeleteToLineEnd
+            | DeleteToLineStart
+            | TransposeChars
+            | OpenLine
+            | DedentSelection
+            | ToggleComment
+            | Undo
+            | Redo
+            | ToUpperCase
+            | ToLowerCase
+            | SortLines
+            | ReflowParagraph
+            | ReflowSelection
+            | EvaluateSelection
+            | EvaluateAndReplaceSelection
+            | GenerateDocComment
+            | InsertDate
+            | InsertTime
+            | InsertUnicodeCharacter
+            | InsertDigraph
+            | FormatBuffer
+            | TrimTrailingWhitespace
+            | EnsureFinalNewline => Self::Editing,
+            Copy
+            | CopyWithTheme(_)
+            | CopyAsMarkdown
+            | CopyWithReference
+            | CopyPermalink
+            | CopyRelativePath
+            | CopyAbsolutePath
+            | CopyFileName
+            | Cut
+            | Paste
+            | PasteFromClipboardHistory
+            | ClipboardHistoryTogglePin
+            | ForgetClipboardHistory
+            | YankWordForward
+            | YankWordBackward
+            | YankToLineEnd
+            | YankToLineStart => Self::Clipboard,
+            AddCursorAbove | AddCursorBelow | AddCursorNextMatch | RemoveSecondaryCursors => {
+                Self::MultiCursor
+            }
+            Search
+            | FindInSelection
+            | FindNext
+            | FindPrevious
+            | FindSelectionNext
+            | FindSelectionPrevious
+            | Replace
+            | QueryReplace
+            | SearchInProject
+            | ProjectSearchOpenResult
+            | ProjectSearchCancel
+            | ToggleSearchCaseSensitive
+            | ToggleSearchWholeWord
+            | ToggleSearchRegex
+            | ToggleSearchConfirmEach => Self::Search,
+            Save
+            | SaveAs
+            | Open
+            | SwitchProject
+            | OpenRecentWorkspace
+            | OpenRecentWorkspaceInNewProcess
+            | TogglePinWorkspace
+            | New
+            | Close
+            | CloseTab
+            | Quit
+            | ForceQuit
+            | Revert
+            | ToggleAutoRevert
+            | ReopenWithEncoding
+            | ToggleHexView
+            | ToggleAnsiColors => Self::File,
+            GotoLine | JumpToNextError | JumpToPreviousError | NextBuffer | PrevBuffer
+            | SwitchToPreviousTab | SwitchToTabByName | NextTabMru | PrevTabMru
+            | MoveTabToSplit | ScrollTabsLeft | ScrollTabsRight | NavigateBack
+            | NavigateForward | SplitHorizontal | SplitVertical | CloseSplit | NextSplit
+            | PrevSplit | IncreaseSplitSize | DecreaseSplitSize | ToggleMaximizeSplit
+            | ToggleZoomSplit | ToggleScrollSync | SyncScrollAllSplits | StopScrollSync
+            | QuickOpen | CommandPalette => Self::Navigation,
+            ToggleLineWrap
+            | ToggleComposeMode
+            | SetComposeWidth
+            | SelectTheme
+            | SelectSplitTheme
+            | ImportBase16Theme
+            | ToggleLineNumbers
+            | ToggleMenuBar
+            | ToggleTabBar
+            | ToggleFileExplorer
+            | ToggleInlayHints
+            | ToggleMouseHover
+            | ToggleMouseCapture
+            | SetBackground
+            | SetBackgroundBlend
+            | FocusFloatingWindow(_)
+            | CloseFloatingWindow => Self::View,
+            LspCompletion | LspGotoDefinition | LspReferences | LspRename | LspHover
+            | LspSignatureHelp | LspCodeActions | LspRestart | LspStop | LspDetectServers
+            | ShowLspStatus => Self::Lsp,
+            StartMacroRecording
+            | StopMacroRecording
+            | PlayMacro(_)
+            | ToggleMacroRecording(_)
+            | ShowMacro(_)
+            | ListMacros
+            | PromptRecordMacro
+            | PromptPlayMacro
+            | PlayLastMacro => Self::Macros,
+            SetBookmark(_) | JumpToBookmark(_) | ClearBookmark(_) | ListBookmarks
+            | PromptSetBookmark | PromptJumpToBookmark => Self::Bookmarks,
+            PromptConfirm
+            | PromptConfirmWithText(_)
+            | PromptCancel
+            | PromptBackspace
+            | PromptDelete
+            | PromptMoveLeft
+            | PromptMoveRight
+            | PromptMoveStart
+            | PromptMoveEnd
+            | PromptSelectPrev
+            | PromptSelectNext
+            | PromptPageUp
+            | PromptPageDown
+            | PromptAcceptSuggestion
+            | PromptMoveWordLeft
+            | PromptMoveWordRight
+            | PromptDeleteWordForward
+            | PromptDeleteWordBackward
+            | PromptDeleteToLineEnd
+            | PromptCopy
+            | PromptCut
+            | PromptPaste
+            | PromptMoveLeftSelecting
+            | PromptMoveRightSelecting
+            | PromptMoveHomeSelecting
+            | PromptMoveEndSelecting
+            | PromptSelectWordLeft
+            | PromptSelectWordRight
+            | PromptSelectAll
+            | FileBrowserToggleHidden => Self::Prompt,
+            PopupSelectNext | PopupSelectPrev | PopupPageUp | PopupPageDown | PopupConfirm
+            | PopupCancel | PinPopup => Self::Popup,
+            FocusFileExplorer
+            | FocusEditor
+            | FileExplorerUp
+            | FileExplorerDown
+            | FileExplorerPageUp
+            | FileExplorerPageDown
+            | FileExplorerExpand
+            | FileExplorerCollapse
+            | FileExplorerOpen
+            | FileExplorerRefresh
+            | FileExplorerNewFile
+            | FileExplorerNewDirectory
+            | FileExplorerDelete
+            | FileExplorerRename
+            | FileExplorerToggleHidden
+            | FileExplorerToggleGitignored => Self::FileExplorer,
+            MenuActivate | MenuClose | MenuLeft | MenuRight | MenuUp | MenuDown | MenuExecute
+            | MenuOpen(_) | ShowContextMenu => Self::Menu,
+            OpenTerminal
+            | CloseTerminal
+            | FocusTerminal
+            | TerminalEscape
+            | ToggleKeyboardCapture
+            | TerminalPaste
+            | ShellCommand
+            | ShellCommandReplace => Self::Terminal,
+            OpenSettings | CloseSettings | SettingsSave | SettingsReset | SettingsToggleFocus
+            | SettingsActivate | SettingsSearch | SettingsHelp | SettingsIncrement
+            | SettingsDecrement => Self::Settings,
+            PluginAction(_) | ReloadPlugin => Self::Plugin,
+            _ => Self::Other,
+        }
+    }
+
+    /// Localized label shown as the group header
+    pub fn label(&self) -> String {
+        match self {
+            Self::Movement => t!("cheat_sheet.category.movement").to_string(),
+            Self::Selection => t!("cheat_sheet.category.selection").to_string(),
+            Self::Editing => t!("cheat_sheet.category.editing").to_string(),
+            Self::Clipboard => t!("cheat_sheet.category.clipboard").to_string(),
+            Self::MultiCursor => t!("cheat_sheet.category.multi_cursor").to_string(),
+            Self::Search => t!("cheat_sheet.category.search").to_string(),
+            Self::File => t!("cheat_sheet.category.file").to_string(),
+            Self::Navigation => t!("cheat_sheet.category.navigation").to_string(),
+            Self::View => t!("cheat_sheet.category.view").to_string(),
+            Self::Lsp => t!("cheat_sheet.category.lsp").to_string(),
+            Self::Macros => t!("cheat_sheet.category.macros").to_string(),
+            Self::Bookmarks => t!("cheat_sheet.category.bookmarks").to_string(),
+            Self::Prompt => t!("cheat_sheet.category.prompt").to_string(),
+            Self::Popup => t!("cheat_sheet.category.popup").to_string(),
+            Self::FileExplorer => t!("cheat_sheet.category.file_explorer").to_string(),
+            Self::Menu => t!("cheat_sheet.category.menu").to_string(),
+            Self::Terminal => t!("cheat_sheet.category.terminal").to_string(),
+            Self::Settings => t!("cheat_sheet.category.settings").to_string(),
+            Self::Mode(name) => t!("cheat_sheet.category.mode", mode = name).to_string(),
+            Self::Plugin => t!("cheat_sheet.category.plugin").to_string(),
+            Self::Other => t!("cheat_sheet.category.other").to_string(),
+        }
+    }
+}
+
+/// A single line of the cheat sheet
+#[derive(Debug, Clone, PartialEq)]
+pub struct CheatSheetEntry {
+    pub category: CheatSheetCategory,
+    /// Key description as rendered by the keymap (empty for unbound commands)
+    pub key: String,
+    pub description: String,
+}
+
+/// A displayed row: either a category header or a binding
+#[derive(Debug, Clone, PartialEq)]
+pub enum CheatSheetRow<'a> {
+    Header(String),
+    Entry(&'a CheatSheetEntry),
+}
+
+/// State of the cheat sheet overlay
+#[derive(Debug, Clone)]
+pub struct CheatSheet {
+    /// Key context the bindings were collected for
+    pub context: KeyContext,
+    entries: Vec<CheatSheetEntry>,
+    /// Current search text
+    pub query: String,
+    /// Index of the first visible row
+    pub scroll: usize,
+}
+
+impl CheatSheet {
+    pub fn new(context: KeyContext, mut entries: Vec<CheatSheetEntry>) -> Self {
+        entries.sort_by(|a, b| {
+            a.category
+                .cmp(&b.category)
+                .then_with(|| a.description.cmp(&b.description))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        entries.dedup();
+        Self {
+            context,
+            entries,
+            query: String::new(),
+            scroll: 0,
+        }
+    }
+
+    /// Rows matching the current query, with a header before each category
+    pub fn rows(&self) -> Vec<CheatSheetRow<'_>> {
+        let query = self.query.to_lowercase();
+        let mut rows = Vec::new();
+        let mut current: Option<&CheatSheetCategory> = None;
+
+        for entry in &self.entries {
+            let label = entry.category.label();
+            if !query.is_empty()
+                && !entry.description.to_lowercase().contains(&query)
+                && !entry.key.to_lowercase().contains(&query)
+                && !label.to_lowercase().contains(&query)
+            {
+                continue;
+            }
+            if current != Some(&entry.category) {
+                current = Some(&entry.category);
+                rows.push(CheatSheetRow::Header(label));
+            }
+            rows.push(CheatSheetRow::Entry(entry));
+        }
+
+        rows
+    }
+
+    /// Handle a key press. Returns false if the cheat sheet should close.
+    pub fn handle_key(&mut self, event: &KeyEvent) -> bool {
+        let max_scroll = self.rows().len().saturating_sub(1);
+
+        match event.code {
+            KeyCode::Esc => {
+                if self.query.is_empty() {
+                    return false;
+                }
+                self.query.clear();
+                self.scroll = 0;
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.scroll = 0;
+            }
+            KeyCode::Char(c)
+                if !event
+                    .modifiers
+                    .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.query.push(c);
+                self.scroll = 0;
+            }
+            KeyCode::Up => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Down => self.scroll = (self.scroll + 1).min(max_scroll),
+            KeyCode::PageUp => self.scroll = self.scroll.saturating_sub(PAGE_SIZE),
+            KeyCode::PageDown => self.scroll = (self.scroll + PAGE_SIZE).min(max_scroll),
+            KeyCode::Home => self.scroll = 0,
+            KeyCode::End => self.scroll = max_scroll,
+            _ => {}
+        }
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(category: CheatSheetCategory, key: &str, description: &str) -> CheatSheetEntry {
+        CheatSheetEntry {
+            category,
+            key: key.to_string(),
+            description: description.to_string(),
+        }
+    }
+
+    fn sample() -> CheatSheet {
+        CheatSheet::new(
+            KeyContext::Normal,
+            vec![
+                entry(CheatSheetCategory::File, "Ctrl+S", "Save file"),
+                entry(CheatSheetCategory::Movement, "Up", "Move up"),
+                entry(CheatSheetCategory::Movement, "Down", "Move down"),
+                entry(CheatSheetCategory::Plugin, "", "Git: blame"),
+            ],
+        )
+    }
+
+    fn descriptions(sheet: &CheatSheet) -> Vec<String> {
+        sheet
+            .rows()
+            .into_iter()
+            .filter_map(|row| match row {
+                CheatSheetRow::Entry(e) => Some(e.description.clone()),
+                CheatSheetRow::Header(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_rows_grouped_by_category_order() {
+        let sheet = sample();
+        let rows = sheet.rows();
+
+        // 3 categories + 4 entries
+        assert_eq!(rows.len(), 7);
+        assert!(matches!(rows[0], CheatSheetRow::Header(_)));
+        assert_eq!(
+            descriptions(&sheet),
+            vec!["Move down", "Move up", "Save file", "Git: blame"]
+        );
+    }
+
+    #[test]
+    fn test_query_filters_by_description_and_key() {
+        let mut sheet = sample();
+        for c in "ctrl+s".chars() {
+            sheet.handle_key(&KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        assert_eq!(descriptions(&sheet), vec!["Save file"]);
+        assert_eq!(sheet.rows().len(), 2);
+
+        sheet.query = "MOVE".to_string();
+        assert_eq!(descriptions(&sheet), vec!["Move down", "Move up"]);
+    }
+
+    #[test]
+    fn test_escape_clears_query_before_closing() {
+        let mut sheet = sample();
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        sheet.query = "save".to_string();
+
+        assert!(sheet.handle_key(&esc));
+        assert!(sheet.query.is_empty());
+        assert!(!sheet.handle_key(&esc));
+    }
+
+    #[test]
+    fn test_scroll_is_clamped() {
+        let mut sheet = sample();
+        sheet.handle_key(&KeyEvent::new(KeyCode::PageDown, KeyModifiers::NONE));
+        assert_eq!(sheet.scroll, 6);
+        sheet.handle_key(&KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+        assert_eq!(sheet.scroll, 5);
+        sheet.handle_key(&KeyEvent::new(KeyCode::Home, KeyModifiers::NONE));
+        assert_eq!(sheet.scroll, 0);
+    }
+
+    #[test]
+    fn test_action_categories() {
+        assert_eq!(
+            CheatSheetCategory::for_action(&Action::MoveUp),
+            CheatSheetCategory::Movement
+        );
+        assert_eq!(
+            CheatSheetCategory::for_action(&Action::PluginAction("x".into())),
+            CheatSheetCategory::Plugin
+        );
+        assert_eq!(
+            CheatSheetCategory::for_action(&Action::LspHover),
+            CheatSheetCategory::Lsp
+        );
+    }
+}
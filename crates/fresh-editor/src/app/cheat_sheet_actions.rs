@@ -0,0 +1,135 @@
+//! Keybinding cheat sheet action handling
+//!
+//! Collects the bindings shown in the cheat sheet and routes input to it.
+
+use super::cheat_sheet::{CheatSheet, CheatSheetCategory, CheatSheetEntry};
+use super::Editor;
+use crate::input::commands::{Command, CommandSource};
+use crate::input::handler::InputResult;
+use crate::input::keybindings::{format_keybinding, Action, KeyContext, KeybindingResolver};
+use crossterm::event::KeyEvent;
+
+impl Editor {
+    /// Open the cheat sheet, or close it if it is already open
+    pub fn toggle_cheat_sheet(&mut self) {
+        if self.cheat_sheet.is_some() {
+            self.cheat_sheet = None;
+        } else {
+            self.open_cheat_sheet();
+        }
+    }
+
+    /// Open the cheat sheet for the current key context
+    pub fn open_cheat_sheet(&mut self) {
+        let context = self.get_key_context();
+        let commands = self.command_registry.read().unwrap().get_all();
+        let describe = |action: &Action| describe_action(action, &commands);
+
+        let mut entries: Vec<CheatSheetEntry> = Vec::new();
+        let mut bound: Vec<Action> = Vec::new();
+
+        for (key, action) in self.keybindings.get_context_bindings(context) {
+            entries.push(CheatSheetEntry {
+                category: CheatSheetCategory::for_action(&action),
+                key,
+                description: describe(&action),
+            });
+            bound.push(action);
+        }
+
+        // Plugin modes only receive keys in these contexts (see handle_key)
+        if matches!(context, KeyContext::Normal | KeyContext::FileExplorer) {
+            let mut modes: Vec<String> = self.editor_mode.iter().cloned().collect();
+            if let Some(mode) = self.active_buffer_mode() {
+                if !modes.iter().any(|m| m == mode) {
+                    modes.push(mode.to_string());
+                }
+            }
+
+            for mode in modes {
+                let category = CheatSheetCategory::Mode(mode.clone());
+                let mut mode_bindings: Vec<(String, String)> = self
+                    .mode_registry
+                    .get_all_keybindings(&mode)
+                    .into_iter()
+                    .map(|((code, modifiers), name)| (format_keybinding(&code, &modifiers), name))
+                    .collect();
+                if let Some(buffer_mode) = self.mode_registry.get(&mode) {
+                    mode_bindings.extend(buffer_mode.chord_keybindings.iter().map(
+                        |(sequence, name)| {
+                            let keys: Vec<String> = sequence
+                                .iter()
+                                .map(|(code, modifiers)| format_keybinding(code, modifiers))
+                                .collect();
+                            (keys.join(" "), name.clone())
+                        },
+                    ));
+                }
+
+                for (key, name) in mode_bindings {
+                    let action = Action::from_str(&name, &std::collections::HashMap::new())
+                        .unwrap_or(Action::PluginAction(name));
+                    entries.push(CheatSheetEntry {
+                        category: category.clone(),
+                        key,
+                        description: describe(&action),
+                    });
+                    bound.push(action);
+                }
+            }
+        }
+
+        // Plugin commands without a binding are still listed so they can be discovered
+        for command in &commands {
+            let available = command.contexts.is_empty() || command.contexts.contains(&context);
+            if matches!(command.source, CommandSource::Plugin(_))
+                && available
+                && !bound.contains(&command.action)
+            {
+                entries.push(CheatSheetEntry {
+                    category: CheatSheetCategory::Plugin,
+                    key: String::new(),
+                    description: command.get_localized_name(),
+                });
+            }
+        }
+
+        self.cheat_sheet = Some(CheatSheet::new(context, entries));
+    }
+
+    /// Handle input when the cheat sheet is open
+    pub fn handle_cheat_sheet_input(&mut self, event: &KeyEvent) -> InputResult {
+        let Some(sheet) = self.cheat_sheet.as_mut() else {
+            return InputResult::Ignored;
+        };
+
+        // The key that opened the cheat sheet also closes it
+        if self.keybindings.resolve(event, sheet.context) == Action::ToggleCheatSheet {
+            self.cheat_sheet = None;
+            return InputResult::Consumed;
+        }
+
+        if !sheet.handle_key(event) {
+            self.cheat_sheet = None;
+        }
+
+        InputResult::Consumed
+    }
+
+    /// Check if the cheat sheet is open
+    pub fn is_cheat_sheet_active(&self) -> bool {
+        self.cheat_sheet.is_some()
+    }
+}
+
+/// Describe an action, using the registered command name for plugin actions
+fn describe_action(action: &Action, commands: &[Command]) -> String {
+    if let Action::PluginAction(name) = action {
+        return commands
+            .iter()
+            .find(|c| &c.action == action)
+            .map(|c| c.get_localized_name())
+            .unwrap_or_else(|| name.clone());
+    }
+    KeybindingResolver::format_action(action)
+}
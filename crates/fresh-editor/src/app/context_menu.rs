@@ -0,0 +1,398 @@
+//! Right-click context menus
+//!
+//! A context menu is a small popup opened by right-clicking in the text
+//! area, on a tab or in the file explorer (or with the `show_context_menu`
+//! action). Its items depend on where it was opened: built-in actions whose
+//! `when` condition holds, followed by items plugins added with
+//! `editor.addContextMenuItem`. The menu is navigated with the arrow keys,
+//! Enter runs the highlighted item, and Esc or a click elsewhere closes it.
+
+use super::types::TabContextMenuItem;
+use crate::model::event::{BufferId, SplitId};
+use crate::primitives::display_width::str_width;
+use crate::view::ui::menu::MenuContext;
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::layout::Rect;
+use std::collections::HashSet;
+
+/// Narrowest menu, borders included
+const MIN_WIDTH: u16 = 22;
+
+/// Where a context menu can be opened
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuKind {
+    /// The text area of a split
+    Editor,
+    /// A tab in a split's tab bar
+    Tab,
+    /// An entry in the file explorer
+    FileExplorer,
+}
+
+impl ContextMenuKind {
+    /// Parse the menu name plugins pass to `addContextMenuItem`
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "editor" => Some(Self::Editor),
+            "tab" => Some(Self::Tab),
+            "file_explorer" => Some(Self::FileExplorer),
+            _ => None,
+        }
+    }
+}
+
+/// What the context menu was opened on
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuTarget {
+    /// The text area of the active split
+    Editor,
+    /// A tab in a split's tab bar
+    Tab {
+        buffer_id: BufferId,
+        split_id: SplitId,
+    },
+    /// The file explorer's selected entry
+    FileExplorer,
+}
+
+impl ContextMenuTarget {
+    pub fn kind(&self) -> ContextMenuKind {
+        match self {
+            Self::Editor => ContextMenuKind::Editor,
+            Self::Tab { .. } => ContextMenuKind::Tab,
+            Self::FileExplorer => ContextMenuKind::FileExplorer,
+        }
+    }
+}
+
+/// What choosing a context menu item does
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMenuCommand {
+    /// A tab operation on the menu's tab
+    Tab(TabContextMenuItem),
+    /// A built-in action or plugin command, by name
+    Action(String),
+}
+
+/// A row of a context menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMenuEntry {
+    Item {
+        label: String,
+        command: ContextMenuCommand,
+        /// Context key that must be set for the item to be shown
+        when: Option<String>,
+        /// Key shown next to the label
+        keybinding: Option<String>,
+    },
+    Separator,
+}
+
+impl ContextMenuEntry {
+    /// An item that runs a built-in action or plugin command
+    pub fn action(label: impl Into<String>, action: &str, when: Option<&str>) -> Self {
+        Self::Item {
+            label: label.into(),
+            command: ContextMenuCommand::Action(action.to_string()),
+            when: when.map(str::to_string),
+            keybinding: None,
+        }
+    }
+
+    fn is_item(&self) -> bool {
+        matches!(self, Self::Item { .. })
+    }
+
+    /// Display width of the row's text, without padding
+    fn width(&self) -> usize {
+        match self {
+            Self::Item {
+                label, keybinding, ..
+            } => {
+                str_width(label)
+                    + keybinding
+                        .as_ref()
+                        .map(|key| str_width(key) + 2)
+                        .unwrap_or(0)
+            }
+            Self::Separator => 0,
+        }
+    }
+}
+
+/// Result of a key press in an open context menu
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ContextMenuKeyResult {
+    /// The menu stays open
+    Handled,
+    /// The menu should close without running anything
+    Close,
+    /// Run the command of the highlighted item
+    Activate(ContextMenuCommand),
+}
+
+/// An open context menu
+#[derive(Debug, Clone)]
+pub struct ContextMenu {
+    pub target: ContextMenuTarget,
+    /// Rows shown, after `when` conditions were checked
+    pub entries: Vec<ContextMenuEntry>,
+    /// Screen area, borders included
+    pub area: Rect,
+    /// Index into `entries` of the highlighted item
+    pub highlighted: usize,
+}
+
+impl ContextMenu {
+    /// Build a menu anchored at `(x, y)`, kept inside a `screen_width` by
+    /// `screen_height` screen
+    ///
+    /// Items whose `when` condition is neither a set menu context key nor an
+    /// active plugin context are left out. Returns `None` when no item is
+    /// left to show.
+    pub fn new(
+        target: ContextMenuTarget,
+        entries: Vec<ContextMenuEntry>,
+        context: &MenuContext,
+        custom_contexts: &HashSet<String>,
+        (x, y): (u16, u16),
+        (screen_width, screen_height): (u16, u16),
+    ) -> Option<Self> {
+        let visible = entries.into_iter().filter(|entry| match entry {
+            ContextMenuEntry::Item {
+                when: Some(when), ..
+            } => context.get(when) || custom_contexts.contains(when),
+            _ => true,
+        });
+
+        // Drop separators at the ends and next to each other, which the
+        // filtering above can leave behind
+        let mut entries: Vec<ContextMenuEntry> = Vec::new();
+        for entry in visible {
+            if entry.is_item() || entries.last().is_some_and(ContextMenuEntry::is_item) {
+                entries.push(entry);
+            }
+        }
+        if entries.last() == Some(&ContextMenuEntry::Separator) {
+            entries.pop();
+        }
+        let highlighted = entries.iter().position(ContextMenuEntry::is_item)?;
+
+        // " label  key " inside the borders
+        let content_width = entries
+            .iter()
+            .map(ContextMenuEntry::width)
+            .max()
+            .unwrap_or(0);
+        let width = (content_width as u16 + 4).max(MIN_WIDTH).min(screen_width);
+        let height = (entries.len() as u16 + 2).min(screen_height);
+        let area = Rect::new(
+            x.min(screen_width.saturating_sub(width)),
+            y.min(screen_height.saturating_sub(height)),
+            width,
+            height,
+        );
+
+        Some(Self {
+            target,
+            entries,
+            area,
+            highlighted,
+        })
+    }
+
+    /// Whether a screen position is inside the menu (borders included)
+    pub fn contains(&self, col: u16, row: u16) -> bool {
+        col >= self.area.x
+            && col < self.area.x + self.area.width
+            && row >= self.area.y
+            && row < self.area.y + self.area.height
+    }
+
+    /// Index of the entry shown at a screen position
+    pub fn entry_at(&self, col: u16, row: u16) -> Option<usize> {
+        if !self.contains(col, row) || row == self.area.y {
+            return None;
+        }
+        let idx = (row - self.area.y - 1) as usize;
+        (idx < self.entries.len()).then_some(idx)
+    }
+
+    /// Highlight an entry if it is an item; returns whether the highlight changed
+    pub fn highlight(&mut self, idx: usize) -> bool {
+        if idx == self.highlighted || !self.entries.get(idx).is_some_and(|e| e.is_item()) {
+            return false;
+        }
+        self.highlighted = idx;
+        true
+    }
+
+    /// The command of an item
+    pub fn command(&self, idx: usize) -> Option<ContextMenuCommand> {
+        match self.entries.get(idx)? {
+            ContextMenuEntry::Item { command, .. } => Some(command.clone()),
+            ContextMenuEntry::Separator => None,
+        }
+    }
+
+    /// Move the highlight to the next item, wrapping around
+    pub fn next_item(&mut self) {
+        let len = self.entries.len();
+        if let Some(idx) = (1..=len)
+            .map(|step| (self.highlighted + step) % len)
+            .find(|&idx| self.entries[idx].is_item())
+        {
+            self.highlighted = idx;
+        }
+    }
+
+    /// Move the highlight to the previous item, wrapping around
+    pub fn prev_item(&mut self) {
+        let len = self.entries.len();
+        if let Some(idx) = (1..=len)
+            .map(|step| (self.highlighted + len - step) % len)
+            .find(|&idx| self.entries[idx].is_item())
+        {
+            self.highlighted = idx;
+        }
+    }
+
+    /// Handle a key press
+    pub fn handle_key(&mut self, event: &KeyEvent) -> ContextMenuKeyResult {
+        match event.code {
+            KeyCode::Down | KeyCode::Tab => self.next_item(),
+            KeyCode::Up | KeyCode::BackTab => self.prev_item(),
+            KeyCode::Home => {
+                self.highlighted = self.entries.iter().position(|e| e.is_item()).unwrap_or(0);
+            }
+            KeyCode::End => {
+                self.highlighted = self.entries.iter().rposition(|e| e.is_item()).unwrap_or(0);
+            }
+            KeyCode::Enter | KeyCode::Char(' ') => {
+                return match self.command(self.highlighted) {
+                    Some(command) => ContextMenuKeyResult::Activate(command),
+                    None => ContextMenuKeyResult::Close,
+                };
+            }
+            _ => return ContextMenuKeyResult::Close,
+        }
+        ContextMenuKeyResult::Handled
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn entries() -> Vec<ContextMenuEntry> {
+        vec![
+            ContextMenuEntry::action("Cut", "cut", Some("has_selection")),
+            ContextMenuEntry::action("Copy", "copy", Some("has_selection")),
+            ContextMenuEntry::action("Paste", "paste", None),
+            ContextMenuEntry::Separator,
+            ContextMenuEntry::action("Go to Definition", "lsp_goto_definition", Some("lsp")),
+            ContextMenuEntry::Separator,
+            ContextMenuEntry::action("Explain", "explain", Some("my-plugin-context")),
+        ]
+    }
+
+    fn menu(context: &MenuContext, custom: &[&str]) -> Option<ContextMenu> {
+        let custom: HashSet<String> = custom.iter().map(|c| c.to_string()).collect();
+        ContextMenu::new(
+            ContextMenuTarget::FileExplorer,
+            entries(),
+            context,
+            &custom,
+            (10, 5),
+            (80, 24),
+        )
+    }
+
+    fn labels(menu: &ContextMenu) -> Vec<&str> {
+        menu.entries
+            .iter()
+            .map(|entry| match entry {
+                ContextMenuEntry::Item { label, .. } => label.as_str(),
+                ContextMenuEntry::Separator => "-",
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_when_conditions_filter_items_and_separators() {
+        let plain = menu(&MenuContext::new(), &[]).unwrap();
+        assert_eq!(labels(&plain), vec!["Paste"]);
+
+        let context = MenuContext::new().with("has_selection", true);
+        let full = menu(&context, &["my-plugin-context"]).unwrap();
+        assert_eq!(labels(&full), vec!["Cut", "Copy", "Paste", "-", "Explain"]);
+    }
+
+    #[test]
+    fn test_menu_without_items_is_not_opened() {
+        let menu = ContextMenu::new(
+            ContextMenuTarget::FileExplorer,
+            vec![
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action("Cut", "cut", Some("has_selection")),
+            ],
+            &MenuContext::new(),
+            &HashSet::new(),
+            (0, 0),
+            (80, 24),
+        );
+        assert!(menu.is_none());
+    }
+
+    #[test]
+    fn test_area_stays_on_screen() {
+        let mut menu = menu(&MenuContext::new().with("lsp", true), &[]).unwrap();
+        assert_eq!(menu.area, Rect::new(10, 5, MIN_WIDTH, 5));
+
+        menu = ContextMenu::new(
+            ContextMenuTarget::FileExplorer,
+            entries(),
+            &MenuContext::new().with("lsp", true),
+            &HashSet::new(),
+            (78, 23),
+            (80, 24),
+        )
+        .unwrap();
+        assert_eq!(menu.area, Rect::new(80 - MIN_WIDTH, 19, MIN_WIDTH, 5));
+        assert_eq!(menu.entry_at(80 - MIN_WIDTH + 1, 20), Some(0));
+        assert_eq!(menu.entry_at(80 - MIN_WIDTH + 1, 19), None);
+        assert!(!menu.contains(0, 0));
+    }
+
+    #[test]
+    fn test_keyboard_navigation_skips_separators() {
+        let context = MenuContext::new().with("has_selection", true);
+        let mut menu = menu(&context, &["my-plugin-context"]).unwrap();
+        assert_eq!(menu.highlighted, 0);
+
+        menu.handle_key(&key(KeyCode::Up));
+        assert_eq!(menu.highlighted, 4, "Up wraps to the last item");
+        menu.handle_key(&key(KeyCode::Up));
+        assert_eq!(menu.highlighted, 2, "separators are skipped");
+        menu.handle_key(&key(KeyCode::Down));
+        menu.handle_key(&key(KeyCode::Down));
+        assert_eq!(menu.highlighted, 0, "Down wraps to the first item");
+        menu.handle_key(&key(KeyCode::End));
+        assert_eq!(menu.highlighted, 4);
+
+        assert!(!menu.highlight(3), "separators cannot be highlighted");
+        assert_eq!(
+            menu.handle_key(&key(KeyCode::Enter)),
+            ContextMenuKeyResult::Activate(ContextMenuCommand::Action("explain".into()))
+        );
+        assert_eq!(
+            menu.handle_key(&key(KeyCode::Esc)),
+            ContextMenuKeyResult::Close
+        );
+    }
+}
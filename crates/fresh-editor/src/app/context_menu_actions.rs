@@ -0,0 +1,370 @@
+//! Opening, navigating and running right-click context menus
+//!
+//! See [`super::context_menu`] for the menu itself.
+
+use super::context_menu::{
+    ContextMenu, ContextMenuCommand, ContextMenuEntry, ContextMenuKeyResult, ContextMenuKind,
+    ContextMenuTarget,
+};
+use super::types::{PathCopy, TabContextMenuItem};
+use super::Editor;
+use crate::input::handler::InputResult;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::{BufferId, Event, SplitId};
+use crate::view::ui::context_keys;
+use anyhow::Result as AnyhowResult;
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use rust_i18n::t;
+use std::collections::HashMap;
+
+impl Editor {
+    /// Open the context menu for `target` with its top-left corner at `(x, y)`
+    pub(super) fn open_context_menu(&mut self, target: ContextMenuTarget, x: u16, y: u16) {
+        self.update_menu_context();
+        let entries = self.context_menu_entries(target.kind());
+        self.context_menu = ContextMenu::new(
+            target,
+            entries,
+            &self.menu_state.context,
+            &self.active_custom_contexts,
+            (x, y),
+            (self.terminal_width, self.terminal_height),
+        );
+    }
+
+    /// Open the context menu for the focused file explorer entry or text
+    /// cursor (the `show_context_menu` action)
+    pub(super) fn show_context_menu_at_cursor(&mut self) {
+        if self.key_context == KeyContext::FileExplorer {
+            let (Some(area), Some(explorer)) =
+                (self.cached_layout.file_explorer_area, &self.file_explorer)
+            else {
+                return;
+            };
+            let selected_row = explorer
+                .get_selected_index()
+                .unwrap_or(0)
+                .saturating_sub(explorer.get_scroll_offset()) as u16;
+            let y = area.y + 1 + selected_row + 1;
+            self.open_context_menu(ContextMenuTarget::FileExplorer, area.x + 2, y);
+            return;
+        }
+
+        let split_id = self.split_manager.active_split();
+        let Some(content_rect) = self
+            .cached_layout
+            .split_areas
+            .iter()
+            .find(|(id, ..)| *id == split_id)
+            .map(|(_, _, content_rect, ..)| *content_rect)
+        else {
+            return;
+        };
+        let state = self.active_state();
+        let position = state.cursors.primary().position;
+        let gutter_width = state.margins.left_total_width() as u16;
+        let (x, y) = match (
+            self.cached_layout.find_visual_row(split_id, position),
+            self.cached_layout.byte_to_visual_column(split_id, position),
+        ) {
+            (Some(row), Some(col)) => (
+                content_rect.x + gutter_width + col as u16,
+                content_rect.y + row as u16 + 1,
+            ),
+            _ => (content_rect.x + gutter_width, content_rect.y),
+        };
+        self.open_context_menu(ContextMenuTarget::Editor, x, y);
+    }
+
+    /// Built-in items of a menu followed by the items plugins added to it
+    fn context_menu_entries(&self, kind: ContextMenuKind) -> Vec<ContextMenuEntry> {
+        let lsp = Some(context_keys::LSP_AVAILABLE);
+        let mut entries = match kind {
+            ContextMenuKind::Editor => vec![
+                ContextMenuEntry::action(
+                    t!("menu.edit.cut"),
+                    "cut",
+                    Some(context_keys::HAS_SELECTION),
+                ),
+                ContextMenuEntry::action(
+                    t!("menu.edit.copy"),
+                    "copy",
+                    Some(context_keys::HAS_SELECTION),
+                ),
+                ContextMenuEntry::action(t!("menu.edit.paste"), "paste", None),
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action(t!("menu.edit.select_all"), "select_all", None),
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action(
+                    t!("menu.lsp.goto_definition"),
+                    "lsp_goto_definition",
+                    lsp,
+                ),
+                ContextMenuEntry::action(t!("menu.lsp.find_references"), "lsp_references", lsp),
+                ContextMenuEntry::action(t!("menu.lsp.rename_symbol"), "lsp_rename", lsp),
+                ContextMenuEntry::action(t!("menu.lsp.code_actions"), "lsp_code_actions", lsp),
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action(
+                    t!("menu.edit.format_buffer"),
+                    "format_buffer",
+                    Some(context_keys::FORMATTER_AVAILABLE),
+                ),
+            ],
+            ContextMenuKind::Tab => {
+                let mut entries = Vec::new();
+                for &item in TabContextMenuItem::all() {
+                    if item == TabContextMenuItem::CopyRelativePath {
+                        entries.push(ContextMenuEntry::Separator);
+                    }
+                    entries.push(ContextMenuEntry::Item {
+                        label: item.label(),
+                        command: ContextMenuCommand::Tab(item),
+                        when: None,
+                        keybinding: None,
+                    });
+                }
+                entries
+            }
+            ContextMenuKind::FileExplorer => vec![
+                ContextMenuEntry::action(
+                    t!("menu.explorer.new_file"),
+                    "file_explorer_new_file",
+                    None,
+                ),
+                ContextMenuEntry::action(
+                    t!("menu.explorer.new_folder"),
+                    "file_explorer_new_directory",
+                    None,
+                ),
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action(t!("menu.explorer.open"), "file_explorer_open", None),
+                ContextMenuEntry::action(t!("menu.explorer.rename"), "file_explorer_rename", None),
+                ContextMenuEntry::action(t!("menu.explorer.delete"), "file_explorer_delete", None),
+                ContextMenuEntry::Separator,
+                ContextMenuEntry::action(
+                    t!("menu.explorer.refresh"),
+                    "file_explorer_refresh",
+                    None,
+                ),
+            ],
+        };
+
+        let plugin_entries: Vec<ContextMenuEntry> = self
+            .context_menu_items
+            .iter()
+            .filter(|item| ContextMenuKind::from_name(&item.menu) == Some(kind))
+            .map(|item| ContextMenuEntry::action(&item.label, &item.action, item.when.as_deref()))
+            .collect();
+        if !plugin_entries.is_empty() {
+            entries.push(ContextMenuEntry::Separator);
+            entries.extend(plugin_entries);
+        }
+
+        let key_context = match kind {
+            ContextMenuKind::FileExplorer => KeyContext::FileExplorer,
+            ContextMenuKind::Editor | ContextMenuKind::Tab => KeyContext::Normal,
+        };
+        for entry in &mut entries {
+            if let ContextMenuEntry::Item {
+                command: ContextMenuCommand::Action(action),
+                keybinding,
+                ..
+            } = entry
+            {
+                *keybinding = self
+                    .keybindings
+                    .find_keybinding_for_action(action, key_context);
+            }
+        }
+        entries
+    }
+
+    /// Check if a context menu is open
+    pub fn is_context_menu_open(&self) -> bool {
+        self.context_menu.is_some()
+    }
+
+    /// Handle a key press while a context menu is open
+    pub fn handle_context_menu_input(&mut self, event: &KeyEvent) -> InputResult {
+        let Some(menu) = self.context_menu.as_mut() else {
+            return InputResult::Ignored;
+        };
+        match menu.handle_key(event) {
+            ContextMenuKeyResult::Handled => {}
+            ContextMenuKeyResult::Close => self.context_menu = None,
+            ContextMenuKeyResult::Activate(command) => {
+                let target = menu.target;
+                self.context_menu = None;
+                if let Err(e) = self.execute_context_menu_command(target, command) {
+                    tracing::error!("Context menu action failed: {}", e);
+                }
+            }
+        }
+        InputResult::Consumed
+    }
+
+    /// Handle a left click while a context menu is open
+    ///
+    /// A click on an item runs it; a click outside the menu closes it.
+    pub(super) fn handle_context_menu_click(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
+        let Some(menu) = self.context_menu.as_ref() else {
+            return Ok(());
+        };
+        if !menu.contains(col, row) {
+            self.context_menu = None;
+            return Ok(());
+        }
+        let Some(command) = menu.entry_at(col, row).and_then(|idx| menu.command(idx)) else {
+            // Border or separator
+            return Ok(());
+        };
+        let target = menu.target;
+        self.context_menu = None;
+        self.execute_context_menu_command(target, command)
+    }
+
+    /// Run a context menu item
+    fn execute_context_menu_command(
+        &mut self,
+        target: ContextMenuTarget,
+        command: ContextMenuCommand,
+    ) -> AnyhowResult<()> {
+        match command {
+            ContextMenuCommand::Tab(item) => {
+                if let ContextMenuTarget::Tab {
+                    buffer_id,
+                    split_id,
+                } = target
+                {
+                    self.execute_tab_context_menu_action(item, buffer_id, split_id);
+                }
+                Ok(())
+            }
+            ContextMenuCommand::Action(name) => {
+                // Actions from a tab's menu apply to that tab
+                if let ContextMenuTarget::Tab {
+                    buffer_id,
+                    split_id,
+                } = target
+                {
+                    self.focus_split(split_id, buffer_id);
+                }
+                let action =
+                    Action::from_str(&name, &HashMap::new()).unwrap_or(Action::PluginAction(name));
+                self.handle_action(action)
+            }
+        }
+    }
+
+    /// Run a built-in tab menu item
+    fn execute_tab_context_menu_action(
+        &mut self,
+        item: TabContextMenuItem,
+        buffer_id: BufferId,
+        split_id: SplitId,
+    ) {
+        match item {
+            TabContextMenuItem::Close => {
+                self.close_tab_in_split(buffer_id, split_id);
+            }
+            TabContextMenuItem::CloseOthers => self.close_other_tabs_in_split(buffer_id, split_id),
+            TabContextMenuItem::CloseToRight => {
+                self.close_tabs_to_right_in_split(buffer_id, split_id)
+            }
+            TabContextMenuItem::CloseToLeft => {
+                self.close_tabs_to_left_in_split(buffer_id, split_id)
+            }
+            TabContextMenuItem::CloseAll => self.close_all_tabs_in_split(split_id),
+            TabContextMenuItem::CopyRelativePath => {
+                self.copy_buffer_path(buffer_id, PathCopy::Relative)
+            }
+            TabContextMenuItem::CopyAbsolutePath => {
+                self.copy_buffer_path(buffer_id, PathCopy::Absolute)
+            }
+            TabContextMenuItem::CopyFileName => {
+                self.copy_buffer_path(buffer_id, PathCopy::FileName)
+            }
+        }
+    }
+
+    /// Select the file explorer entry shown on `row` before its menu opens
+    pub(super) fn select_file_explorer_row(&mut self, row: u16, explorer_area: Rect) {
+        // The first row is the title bar
+        if row <= explorer_area.y {
+            return;
+        }
+        let Some(explorer) = self.file_explorer.as_mut() else {
+            return;
+        };
+        let index = (row - explorer_area.y - 1) as usize + explorer.get_scroll_offset();
+        if let Some(&(node_id, _)) = explorer.get_display_nodes().get(index) {
+            explorer.set_selected(Some(node_id));
+        }
+        self.key_context = KeyContext::FileExplorer;
+    }
+
+    /// Focus the right-clicked split and move the cursor to the click,
+    /// unless the click is inside the selection the menu would act on
+    pub(super) fn place_cursor_for_context_menu(
+        &mut self,
+        col: u16,
+        row: u16,
+        split_id: SplitId,
+        buffer_id: BufferId,
+        content_rect: Rect,
+    ) {
+        self.focus_split(split_id, buffer_id);
+        if self.is_terminal_buffer(buffer_id) || self.is_composite_buffer(buffer_id) {
+            return;
+        }
+        self.key_context = KeyContext::Normal;
+
+        let cached_mappings = self
+            .cached_layout
+            .view_line_mappings
+            .get(&split_id)
+            .cloned();
+        let fallback = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.top_byte)
+            .unwrap_or(0);
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let gutter_width = state.margins.left_total_width() as u16;
+        let Some(target_position) = Self::screen_to_buffer_position(
+            col,
+            row,
+            content_rect,
+            gutter_width,
+            &cached_mappings,
+            fallback,
+            true,
+        ) else {
+            return;
+        };
+
+        let cursor = state.cursors.primary();
+        if cursor
+            .selection_range()
+            .is_some_and(|range| range.contains(&target_position))
+        {
+            return;
+        }
+        let event = Event::MoveCursor {
+            cursor_id: state.cursors.primary_id(),
+            old_position: cursor.position,
+            new_position: target_position,
+            old_anchor: cursor.anchor,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        };
+        if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
+            event_log.append(event.clone());
+        }
+        state.apply(&event);
+    }
+}
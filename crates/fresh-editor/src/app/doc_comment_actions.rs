@@ -0,0 +1,47 @@
+//! Generate Doc Comment: insert a doc comment skeleton for the function at
+//! the cursor.
+//!
+//! The skeleton comes from `primitives::doc_comment`. It is inserted as a
+//! snippet, so Tab moves through the description, the parameters and the
+//! return value.
+
+use super::Editor;
+use crate::primitives::doc_comment::doc_comment_snippet;
+use crate::primitives::highlighter::Language;
+use rust_i18n::t;
+
+impl Editor {
+    /// Insert a doc comment for the function whose signature the cursor is on
+    pub(super) fn generate_doc_comment(&mut self) {
+        let state = self.active_state();
+        let language = Language::from_id(&state.language)
+            .or_else(|| state.buffer.file_path().and_then(Language::from_path));
+        let Some(language) = language else {
+            let language = state.language.clone();
+            self.set_status_message(t!("doc_comment.unsupported", language = language).to_string());
+            return;
+        };
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+        let position = state.cursors.primary().position;
+
+        match doc_comment_snippet(&content, &language, position) {
+            Some(doc) => self.insert_snippet(doc.insert_at, &doc.snippet, "Generate doc comment"),
+            None if matches!(
+                language,
+                Language::Rust | Language::Python | Language::JavaScript | Language::TypeScript
+            ) =>
+            {
+                self.set_status_message(t!("doc_comment.no_function").to_string());
+            }
+            None => self.set_status_message(
+                t!(
+                    "doc_comment.unsupported",
+                    language = language.display_name()
+                )
+                .to_string(),
+            ),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Files that aren't valid UTF-8.
+//!
+//! Invalid UTF-8 sequences are kept byte for byte and displayed as U+FFFD.
+//! While the active buffer has any, a banner offers "Toggle Hex View" to see
+//! the actual bytes and "Reopen with Encoding" to decode the file as a legacy
+//! single-byte encoding instead. Saving asks for confirmation before writing
+//! a file that has lost some of its invalid sequences.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::model::encoding::Encoding;
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+impl Editor {
+    /// Display name of the active buffer
+    fn active_display_name(&self) -> String {
+        self.buffer_metadata
+            .get(&self.active_buffer())
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default()
+    }
+
+    /// Text of the invalid UTF-8 banner, or `None` when the active buffer is
+    /// valid UTF-8 or already shown as hex
+    pub(super) fn invalid_utf8_banner(&self) -> Option<String> {
+        let state = self.active_state();
+        if state.buffer.invalid_utf8_sequences() == 0 || state.hex_view {
+            return None;
+        }
+        Some(
+            t!(
+                "encoding.invalid_utf8_banner",
+                name = self.active_display_name(),
+                hex_command = t!("cmd.toggle_hex_view"),
+                reopen_command = t!("cmd.reopen_with_encoding")
+            )
+            .to_string(),
+        )
+    }
+
+    /// Toggle showing the active buffer's unprintable bytes as hex
+    pub(super) fn toggle_hex_view(&mut self) {
+        let state = self.active_state_mut();
+        state.hex_view = !state.hex_view;
+        let message = if state.hex_view {
+            t!("encoding.hex_view_on")
+        } else {
+            t!("encoding.hex_view_off")
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Open a prompt listing the encodings the active file can be reopened with
+    pub(super) fn start_reopen_with_encoding_prompt(&mut self) {
+        if self.active_state().buffer.file_path().is_none() {
+            self.set_status_message(t!("encoding.reopen_no_file").to_string());
+            return;
+        }
+        let current = self.active_state().buffer.encoding();
+
+        let suggestions: Vec<Suggestion> = Encoding::ALL
+            .into_iter()
+            .map(|encoding| Suggestion {
+                text: encoding.display_name().to_string(),
+                description: (encoding == current).then(|| t!("prompt.current").to_string()),
+                value: Some(encoding.id().to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+        let current_index = Encoding::ALL
+            .iter()
+            .position(|encoding| *encoding == current)
+            .unwrap_or(0);
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("encoding.reopen_prompt").to_string(),
+            PromptType::ReopenWithEncoding,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(current_index);
+        }
+    }
+
+    /// Handle ReopenWithEncoding prompt confirmation.
+    ///
+    /// Reloading discards the buffer's content, so modified buffers are
+    /// refused. Large files are only decoded as UTF-8, which they can be
+    /// without reading them whole.
+    pub(super) fn handle_reopen_with_encoding(&mut self, input: &str) {
+        let Some(encoding) = Encoding::from_id(input) else {
+            self.set_status_message(t!("encoding.unknown", input = input).to_string());
+            return;
+        };
+        let buffer = &self.active_state().buffer;
+        let Some(path) = buffer.file_path().map(|p| p.to_path_buf()) else {
+            self.set_status_message(t!("encoding.reopen_no_file").to_string());
+            return;
+        };
+        if buffer.is_modified() {
+            self.set_status_message(t!("encoding.reopen_modified").to_string());
+            return;
+        }
+        if buffer.is_large_file() && encoding != Encoding::Utf8 {
+            self.set_status_message(t!("encoding.reopen_large_file").to_string());
+            return;
+        }
+
+        match self.reload_active_buffer(&path, encoding) {
+            Ok(()) => self.set_status_message(
+                t!("encoding.reopened", encoding = encoding.display_name()).to_string(),
+            ),
+            Err(e) => self.set_status_message(
+                t!("encoding.reopen_failed", error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Confirmation message if saving the active buffer would change invalid
+    /// UTF-8 sequences the file has on disk (e.g. after they were replaced
+    /// with U+FFFD by pasting the text back), `None` if it keeps them all
+    pub(super) fn invalid_utf8_save_prompt(&self) -> Option<String> {
+        let buffer = &self.active_state().buffer;
+        let changed = buffer
+            .invalid_utf8_sequences()
+            .saturating_sub(buffer.current_invalid_utf8_sequences());
+        (changed > 0).then(|| {
+            t!(
+                "encoding.save_changes_invalid_prompt",
+                count = changed,
+                name = self.active_display_name()
+            )
+            .to_string()
+        })
+    }
+}
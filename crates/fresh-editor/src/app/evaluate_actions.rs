@@ -0,0 +1,261 @@
+//! Evaluate Selection / Evaluate and Replace Selection: calculator-style
+//! evaluation of the selected text.
+//!
+//! Each selection is evaluated by the plugin evaluator registered for the
+//! buffer's language, falling back to the built-in calculator in
+//! `primitives::evaluate` when there is none or it answers `null`. The results
+//! are either listed in a popup or replace their selections in one undoable
+//! edit.
+
+use super::Editor;
+use crate::model::event::{BufferId, CursorId, Event};
+use crate::primitives::evaluate::{self, Date};
+use chrono::Datelike;
+use rust_i18n::t;
+use std::ops::Range;
+
+/// Selections sent to a plugin evaluator, waiting for its results
+pub(super) struct PendingEvaluation {
+    pub(super) request_id: u64,
+    buffer_id: BufferId,
+    /// Buffer version the selections were read at
+    version: u64,
+    selections: Vec<Selection>,
+    replace: bool,
+}
+
+/// A selection and the text in it
+struct Selection {
+    cursor_id: CursorId,
+    range: Range<usize>,
+    text: String,
+}
+
+impl Editor {
+    /// Evaluate every selection, showing the results in a popup or replacing
+    /// the selections with them
+    pub(super) fn evaluate_selection(&mut self, replace: bool) {
+        let state = self.active_state_mut();
+        let mut ranges: Vec<_> = state
+            .cursors
+            .iter()
+            .filter_map(|(cursor_id, cursor)| {
+                cursor.selection_range().map(|range| (cursor_id, range))
+            })
+            .filter(|(_, range)| !range.is_empty())
+            .collect();
+        if ranges.is_empty() {
+            self.set_status_message(t!("evaluate.no_selection").to_string());
+            return;
+        }
+        ranges.sort_by_key(|(_, range)| range.start);
+        let selections: Vec<_> = ranges
+            .into_iter()
+            .map(|(cursor_id, range)| Selection {
+                cursor_id,
+                text: state.get_text_range(range.start, range.end),
+                range,
+            })
+            .collect();
+
+        let language = self.active_state().language.clone();
+        let evaluator = self
+            .plugin_evaluators
+            .iter()
+            .find(|evaluator| evaluator.language == language)
+            .cloned();
+        let Some(evaluator) = evaluator else {
+            let results = selections.iter().map(|_| None).collect();
+            self.finish_evaluation(selections, results, replace);
+            return;
+        };
+
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        let path = state
+            .buffer
+            .file_path()
+            .map(|path| path.to_string_lossy().into_owned());
+        let requests = selections
+            .iter()
+            .map(|selection| fresh_core::api::EvaluateRequest {
+                buffer_id: buffer_id.0,
+                path: path.clone(),
+                language: language.clone(),
+                expression: selection.text.clone(),
+            })
+            .collect();
+        let version = state.buffer.version();
+
+        let request_id = self.next_lsp_request_id;
+        self.next_lsp_request_id += 1;
+        self.plugin_manager
+            .request_evaluation(&evaluator, request_id, requests);
+
+        // A newer request replaces any still waiting
+        self.pending_evaluation = Some(PendingEvaluation {
+            request_id,
+            buffer_id,
+            version,
+            selections,
+            replace,
+        });
+        self.set_status_message(
+            t!("evaluate.evaluating", evaluator = evaluator.handler_name).to_string(),
+        );
+    }
+
+    /// Finish an evaluation with a plugin evaluator's results
+    pub(super) fn handle_plugin_evaluation(
+        &mut self,
+        request_id: u64,
+        results: Vec<Option<String>>,
+    ) {
+        if self
+            .pending_evaluation
+            .as_ref()
+            .is_none_or(|pending| pending.request_id != request_id)
+        {
+            tracing::debug!(
+                "Ignoring evaluator results for outdated request {}",
+                request_id
+            );
+            return;
+        }
+        let Some(pending) = self.pending_evaluation.take() else {
+            return;
+        };
+        let unchanged = self.active_buffer() == pending.buffer_id
+            && self.active_state().buffer.version() == pending.version;
+        if !unchanged {
+            tracing::debug!("Buffer changed while evaluating, discarding the results");
+            self.set_status_message(t!("evaluate.abandoned").to_string());
+            return;
+        }
+        self.finish_evaluation(pending.selections, results, pending.replace);
+    }
+
+    /// Evaluate what the plugin left to the built-in calculator, then show
+    /// or apply the results
+    fn finish_evaluation(
+        &mut self,
+        selections: Vec<Selection>,
+        results: Vec<Option<String>>,
+        replace: bool,
+    ) {
+        let now = chrono::Local::now().date_naive();
+        let today = Date::from_ymd(now.year(), now.month(), now.day())
+            .expect("chrono only produces valid dates");
+        let results: Vec<Result<String, String>> = selections
+            .iter()
+            .enumerate()
+            .map(
+                |(index, selection)| match results.get(index).cloned().flatten() {
+                    Some(result) => Ok(result),
+                    None => {
+                        evaluate::evaluate(&selection.text, today).map(|value| value.to_string())
+                    }
+                },
+            )
+            .collect();
+
+        if replace {
+            self.replace_selections_with_results(selections, results);
+        } else {
+            self.show_evaluation_popup(&selections, &results);
+        }
+    }
+
+    /// Replace each selection that evaluated with its result, as one
+    /// undoable edit
+    fn replace_selections_with_results(
+        &mut self,
+        selections: Vec<Selection>,
+        results: Vec<Result<String, String>>,
+    ) {
+        let mut events = Vec::new();
+        let mut first_error = None;
+        // Back to front, so earlier ranges stay valid
+        for (selection, result) in selections.into_iter().zip(results).rev() {
+            let result = match result {
+                Ok(result) => result,
+                Err(error) => {
+                    first_error = Some((selection.text, error));
+                    continue;
+                }
+            };
+            if result == selection.text {
+                continue;
+            }
+            events.push(Event::Delete {
+                range: selection.range.clone(),
+                deleted_text: selection.text,
+                cursor_id: selection.cursor_id,
+            });
+            events.push(Event::Insert {
+                position: selection.range.start,
+                text: result,
+                cursor_id: selection.cursor_id,
+            });
+        }
+
+        let replaced = events.len() / 2;
+        if !events.is_empty() {
+            if let Some(bulk_edit) =
+                self.apply_events_as_bulk_edit(events, "Evaluate Selection".to_string())
+            {
+                self.active_event_log_mut().append(bulk_edit);
+            }
+        }
+        match first_error {
+            Some((expression, error)) => self.set_status_message(
+                t!(
+                    "evaluate.failed",
+                    expression = expression.trim(),
+                    error = error
+                )
+                .to_string(),
+            ),
+            None => self.set_status_message(t!("evaluate.replaced", count = replaced).to_string()),
+        }
+    }
+
+    /// List `expression = result` for each selection in a popup at the cursor
+    fn show_evaluation_popup(
+        &mut self,
+        selections: &[Selection],
+        results: &[Result<String, String>],
+    ) {
+        use crate::view::popup::{Popup, PopupPosition};
+        use ratatui::style::Style;
+
+        let lines: Vec<String> = selections
+            .iter()
+            .zip(results)
+            .map(|(selection, result)| {
+                let expression = selection.text.split_whitespace().collect::<Vec<_>>();
+                let expression = expression.join(" ");
+                match result {
+                    Ok(result) => format!("{} = {}", expression, result),
+                    Err(error) => format!("{}: {}", expression, error),
+                }
+            })
+            .collect();
+        let width = lines
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let mut popup = Popup::text(lines, &self.theme);
+        popup.title = Some(t!("evaluate.popup_title").to_string());
+        popup.transient = true;
+        popup.position = PopupPosition::BelowCursor;
+        popup.width = (width as u16 + 4).clamp(20, 80);
+        popup.max_height = 12;
+        popup.border_style = Style::default().fg(self.theme.popup_border_fg);
+        popup.background_style = Style::default().bg(self.theme.popup_bg);
+
+        self.active_state_mut().popups.show(popup);
+    }
+}
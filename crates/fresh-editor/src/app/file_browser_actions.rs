@@ -0,0 +1,102 @@
+//! File browser opened by the `editor.openFileBrowser` plugin API.
+//!
+//! This reuses the Open File dialog. When the plugin passes a `root`, the
+//! browser cannot navigate above it, and `filter` globs narrow the listed
+//! files. With `directories`, Enter on a folder picks it instead of entering
+//! it. The plugin's promise resolves with the chosen absolute path, or null
+//! when cancelled.
+
+use super::file_open::FileOpenState;
+use super::Editor;
+use crate::config_io::expand_path;
+use crate::view::prompt::PromptType;
+use fresh_core::api::{FileBrowserOptions, JsCallbackId};
+use rust_i18n::t;
+use std::path::PathBuf;
+
+/// State of a plugin file browser while its prompt is open
+pub(super) struct PluginFileBrowserState {
+    callback_id: JsCallbackId,
+    /// Pick directories instead of files
+    pub(super) directories: bool,
+}
+
+impl Editor {
+    /// Open the file browser for a plugin
+    pub(super) fn handle_open_file_browser(
+        &mut self,
+        options: FileBrowserOptions,
+        callback_id: JsCallbackId,
+    ) {
+        // Only one plugin file browser can be open; the previous one counts as cancelled
+        self.resolve_plugin_file_browser(None);
+
+        let root = options.root.as_deref().map(|root| {
+            let expanded = expand_path(root);
+            if expanded.is_absolute() {
+                expanded
+            } else {
+                self.working_dir.join(expanded)
+            }
+        });
+        let directories = options.directories.unwrap_or(false);
+
+        let title = options
+            .title
+            .unwrap_or_else(|| t!("file_browser.plugin_prompt").to_string());
+        self.start_prompt(title, PromptType::PluginFileBrowser);
+        self.plugin_file_browser = Some(PluginFileBrowserState {
+            callback_id,
+            directories,
+        });
+
+        let initial_dir = root
+            .clone()
+            .unwrap_or_else(|| self.file_dialog_initial_dir());
+        let mut state =
+            FileOpenState::new(initial_dir.clone(), self.config.file_browser.show_hidden);
+        state.restrict(root, &options.filter.unwrap_or_default());
+        self.file_open_state = Some(state);
+        self.load_file_open_directory(initial_dir);
+    }
+
+    /// Whether the open file browser was started by a plugin
+    pub(super) fn is_plugin_file_browser(&self) -> bool {
+        self.prompt
+            .as_ref()
+            .is_some_and(|p| p.prompt_type == PromptType::PluginFileBrowser)
+    }
+
+    /// Close the plugin file browser and resolve it with `path`
+    ///
+    /// A path outside the browser's root is refused and the browser stays open.
+    pub(super) fn finish_plugin_file_browser(&mut self, path: PathBuf) {
+        if let Some(state) = &self.file_open_state {
+            if !state.in_scope(&path) {
+                self.set_status_message(
+                    t!(
+                        "file_browser.outside_root",
+                        path = path.display().to_string()
+                    )
+                    .to_string(),
+                );
+                return;
+            }
+        }
+        self.file_open_state = None;
+        self.file_browser_layout = None;
+        self.prompt = None;
+        self.resolve_plugin_file_browser(Some(path));
+    }
+
+    /// Resolve an open plugin file browser with the chosen path, or null
+    pub(super) fn resolve_plugin_file_browser(&mut self, path: Option<PathBuf>) {
+        if let Some(state) = self.plugin_file_browser.take() {
+            let result = path.map(|p| p.to_string_lossy().into_owned());
+            self.plugin_manager.resolve_callback(
+                state.callback_id,
+                serde_json::to_string(&result).unwrap_or_else(|_| "null".to_string()),
+            );
+        }
+    }
+}
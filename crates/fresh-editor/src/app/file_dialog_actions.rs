@@ -0,0 +1,169 @@
+//! Open and Save As dialogs
+//!
+//! Uses the operating system's file dialogs when `file_browser.native_dialogs`
+//! is enabled, and the built-in file browser prompt otherwise or whenever the
+//! native dialog cannot be shown.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::file_dialog::{
+    run_file_dialog, FileDialogError, FileDialogKind, FileDialogSelection,
+};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+impl Editor {
+    /// Ask for a file to open
+    pub(super) fn show_open_file_dialog(&mut self) {
+        if !self.show_native_file_dialog(FileDialogKind::Open) {
+            self.start_open_file_prompt();
+        }
+    }
+
+    /// Ask for a path to save the active buffer to
+    pub(super) fn show_save_as_dialog(&mut self) {
+        if !self.show_native_file_dialog(FileDialogKind::SaveAs) {
+            self.start_save_as_prompt();
+        }
+    }
+
+    fn start_open_file_prompt(&mut self) {
+        self.start_prompt(t!("file.open_prompt").to_string(), PromptType::OpenFile);
+        self.prefill_open_file_prompt();
+        self.init_file_open_state();
+    }
+
+    fn start_save_as_prompt(&mut self) {
+        // Get current filename as default suggestion
+        let current_path = self
+            .active_state()
+            .buffer
+            .file_path()
+            .map(|p| {
+                // Make path relative to working_dir if possible
+                p.strip_prefix(&self.working_dir)
+                    .unwrap_or(p)
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .unwrap_or_default();
+        self.start_prompt_with_initial_text(
+            t!("file.save_as_prompt").to_string(),
+            PromptType::SaveFileAs,
+            current_path,
+        );
+        self.init_file_open_state();
+    }
+
+    /// Start a native file dialog in the background.
+    /// Returns false if native dialogs are disabled or cannot be used here.
+    fn show_native_file_dialog(&mut self, kind: FileDialogKind) -> bool {
+        // A local dialog cannot browse a remote filesystem
+        if !self.config.file_browser.native_dialogs || self.remote_connection_info().is_some() {
+            return false;
+        }
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return false;
+        };
+
+        let title = match kind {
+            FileDialogKind::Open => t!("file_dialog.open_title"),
+            FileDialogKind::SaveAs => t!("file_dialog.save_as_title"),
+        }
+        .to_string();
+        let file_name = match kind {
+            FileDialogKind::Open => None,
+            FileDialogKind::SaveAs => self
+                .active_state()
+                .buffer
+                .file_path()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().into_owned()),
+        };
+        let dir = self.file_dialog_initial_dir();
+        let buffer_id = self.active_buffer();
+        let sender = bridge.sender();
+
+        // The dialog helpers block until the user is done
+        runtime.spawn_blocking(move || {
+            let result = run_file_dialog(kind, &title, &dir, file_name.as_deref());
+            let _ = sender.send(AsyncMessage::NativeFileDialogClosed {
+                kind,
+                buffer_id,
+                result,
+            });
+        });
+
+        self.set_status_message(t!("file_dialog.waiting").to_string());
+        true
+    }
+
+    /// Handle the file picked (or not) in a native dialog
+    pub(super) fn handle_native_file_dialog_closed(
+        &mut self,
+        kind: FileDialogKind,
+        buffer_id: BufferId,
+        result: Result<Option<FileDialogSelection>, FileDialogError>,
+    ) {
+        let selection = match result {
+            Ok(Some(selection)) => selection,
+            Ok(None) => {
+                self.set_status_message(t!("file_dialog.cancelled").to_string());
+                return;
+            }
+            Err(e) => {
+                tracing::warn!("Native file dialog unavailable: {}", e);
+                let message = match e {
+                    FileDialogError::Unavailable => t!("file_dialog.unavailable").to_string(),
+                    FileDialogError::Failed(error) => {
+                        t!("file_dialog.failed", error = error).to_string()
+                    }
+                };
+                self.set_status_message(message);
+
+                // Fall back to the built-in file browser unless another prompt took over
+                if self.prompt.is_none() {
+                    match kind {
+                        FileDialogKind::Open => self.start_open_file_prompt(),
+                        FileDialogKind::SaveAs if self.active_buffer() == buffer_id => {
+                            self.start_save_as_prompt()
+                        }
+                        FileDialogKind::SaveAs => {}
+                    }
+                }
+                return;
+            }
+        };
+
+        match kind {
+            FileDialogKind::Open => {
+                let path = selection.path;
+                if let Err(e) = self.open_file(&path) {
+                    self.set_status_message(
+                        t!("file.error_opening", error = e.to_string()).to_string(),
+                    );
+                } else {
+                    self.set_status_message(
+                        t!("buffer.opened", name = path.display().to_string()).to_string(),
+                    );
+                }
+            }
+            FileDialogKind::SaveAs => {
+                // Save the buffer the dialog was opened for, even if focus moved
+                if !self.buffers.contains_key(&buffer_id) {
+                    self.set_status_message(t!("file_dialog.buffer_closed").to_string());
+                    return;
+                }
+                if self.active_buffer() != buffer_id {
+                    self.switch_buffer(buffer_id);
+                }
+                if selection.overwrite_confirmed {
+                    self.perform_save_file_as(selection.path);
+                } else {
+                    self.handle_save_file_as(&selection.path.to_string_lossy());
+                }
+            }
+        }
+    }
+}
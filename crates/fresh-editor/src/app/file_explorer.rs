@@ -87,34 +87,61 @@ impl Editor {
         if let Some(metadata) = self.buffer_metadata.get(&self.active_buffer()) {
             if let Some(file_path) = metadata.file_path() {
                 let target_path = file_path.clone();
-                let working_dir = self.working_dir.clone();
-
-                if target_path.starts_with(&working_dir) {
-                    if let Some(mut view) = self.file_explorer.take() {
-                        tracing::trace!(
-                            "sync_file_explorer_to_active_file: taking file_explorer for async expand to {:?}",
-                            target_path
-                        );
-                        if let (Some(runtime), Some(bridge)) =
-                            (&self.tokio_runtime, &self.async_bridge)
-                        {
-                            let sender = bridge.sender();
-                            // Mark sync as in progress so render knows to keep the layout
-                            self.file_explorer_sync_in_progress = true;
-
-                            runtime.spawn(async move {
-                                let _success = view.expand_and_select_file(&target_path).await;
-                                let _ = sender.send(AsyncMessage::FileExplorerExpandedToPath(view));
-                            });
-                        } else {
-                            self.file_explorer = Some(view);
-                        }
-                    }
-                }
+                self.expand_file_explorer_to(target_path);
+            }
+        }
+    }
+
+    /// Expand the file explorer down to `target_path` and select it
+    fn expand_file_explorer_to(&mut self, target_path: PathBuf) {
+        if !target_path.starts_with(&self.working_dir) {
+            return;
+        }
+        if let Some(mut view) = self.file_explorer.take() {
+            tracing::trace!(
+                "expand_file_explorer_to: taking file_explorer for async expand to {:?}",
+                target_path
+            );
+            if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                let sender = bridge.sender();
+                // Mark sync as in progress so render knows to keep the layout
+                self.file_explorer_sync_in_progress = true;
+
+                runtime.spawn(async move {
+                    let _success = view.expand_and_select_file(&target_path).await;
+                    let _ = sender.send(AsyncMessage::FileExplorerExpandedToPath(view));
+                });
+            } else {
+                self.file_explorer = Some(view);
             }
         }
     }
 
+    /// Show the file explorer and select `path` in it (for editor.revealInExplorer)
+    pub fn handle_reveal_in_explorer(&mut self, path: PathBuf) {
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.working_dir.join(&path)
+        };
+        self.pending_file_explorer_reveal = Some(normalize_path(&path));
+        self.show_file_explorer();
+        self.apply_pending_file_explorer_reveal();
+    }
+
+    /// Reveal the path requested by a plugin once the explorer is idle
+    ///
+    /// The tree may still be loading or expanding to the active file; in that
+    /// case this is called again when that finishes.
+    pub(super) fn apply_pending_file_explorer_reveal(&mut self) {
+        if self.file_explorer.is_none() || self.file_explorer_sync_in_progress {
+            return;
+        }
+        if let Some(path) = self.pending_file_explorer_reveal.take() {
+            self.expand_file_explorer_to(path);
+        }
+    }
+
     pub fn focus_file_explorer(&mut self) {
         if self.file_explorer_visible {
             // Dismiss transient popups and clear hover state when focusing file explorer
@@ -143,7 +170,9 @@ impl Editor {
                 Ok(home) => home,
                 Err(e) => {
                     tracing::error!("Failed to get remote home directory: {}", e);
-                    self.set_status_message(format!("Failed to get remote home: {}", e));
+                    self.set_status_message(
+                        t!("file_browser.remote_home_failed", error = e.to_string()).to_string(),
+                    );
                     return;
                 }
             }
@@ -421,6 +450,10 @@ impl Editor {
                                 self.set_status_message(
                                     t!("explorer.created_file", name = &filename).to_string(),
                                 );
+                                self.notify_lsp_watched_files(&[(
+                                    path_clone.clone(),
+                                    lsp_types::FileChangeType::CREATED,
+                                )]);
 
                                 // Open the file in the buffer
                                 let _ = self.open_file(&path_clone);
@@ -574,6 +607,7 @@ impl Editor {
                     }
                 }
                 self.set_status_message(t!("explorer.moved_to_trash", name = &name).to_string());
+                self.notify_lsp_watched_files(&[(path, lsp_types::FileChangeType::DELETED)]);
 
                 // Ensure focus remains on file explorer
                 self.key_context = KeyContext::FileExplorer;
@@ -719,6 +753,10 @@ impl Editor {
                     self.set_status_message(
                         t!("explorer.renamed", old = &original_name, new = &new_name).to_string(),
                     );
+                    self.notify_lsp_watched_files(&[
+                        (original_path, lsp_types::FileChangeType::DELETED),
+                        (new_path, lsp_types::FileChangeType::CREATED),
+                    ]);
                 }
                 Err(e) => {
                     self.set_status_message(
@@ -4,8 +4,11 @@
 //! It renders a structured popup above the prompt with sortable columns,
 //! navigation shortcuts, and filtering.
 
+use crate::i18n::{date_format, format_decimal};
 use crate::input::fuzzy::fuzzy_match;
 use crate::model::filesystem::{DirEntry, EntryType};
+use crate::services::editorconfig::glob_to_regex;
+use regex::Regex;
 use rust_i18n::t;
 use std::cmp::Ordering;
 use std::path::{Path, PathBuf};
@@ -94,6 +97,12 @@ pub struct FileOpenState {
 
     /// Whether to show hidden files
     pub show_hidden: bool,
+
+    /// Directory the browser cannot leave (set for plugin file browsers)
+    pub scope: Option<PathBuf>,
+
+    /// Only files whose names match one of these are listed (empty = all)
+    name_filters: Vec<Regex>,
 }
 
 impl FileOpenState {
@@ -114,9 +123,29 @@ impl FileOpenState {
             shortcuts,
             selected_shortcut: 0,
             show_hidden,
+            scope: None,
+            name_filters: Vec::new(),
         }
     }
 
+    /// Keep browsing inside `scope` and only list files matching one of
+    /// `name_globs` (directories are always listed)
+    pub fn restrict(&mut self, scope: Option<PathBuf>, name_globs: &[String]) {
+        self.scope = scope;
+        self.name_filters = name_globs
+            .iter()
+            .filter_map(|glob| Regex::new(&glob_to_regex(glob)).ok())
+            .collect();
+        self.update_shortcuts();
+    }
+
+    /// Check if `path` is inside the browsable scope
+    pub fn in_scope(&self, path: &Path) -> bool {
+        self.scope
+            .as_ref()
+            .is_none_or(|scope| path.starts_with(scope))
+    }
+
     /// Build navigation shortcuts for the given directory
     fn build_shortcuts(current_dir: &Path) -> Vec<NavigationShortcut> {
         let mut shortcuts = Vec::new();
@@ -187,7 +216,9 @@ impl FileOpenState {
 
     /// Update shortcuts when directory changes
     pub fn update_shortcuts(&mut self) {
-        self.shortcuts = Self::build_shortcuts(&self.current_dir);
+        let mut shortcuts = Self::build_shortcuts(&self.current_dir);
+        shortcuts.retain(|shortcut| self.in_scope(&shortcut.path));
+        self.shortcuts = shortcuts;
         self.selected_shortcut = 0;
     }
 
@@ -195,8 +226,12 @@ impl FileOpenState {
     pub fn set_entries(&mut self, entries: Vec<DirEntry>) {
         let mut result: Vec<FileOpenEntry> = Vec::new();
 
-        // Add ".." entry for parent directory navigation (unless at root)
-        if let Some(parent) = self.current_dir.parent() {
+        // Add ".." entry for parent directory navigation (unless at root or scope)
+        if let Some(parent) = self
+            .current_dir
+            .parent()
+            .filter(|parent| self.in_scope(parent))
+        {
             let parent_entry =
                 DirEntry::new(parent.to_path_buf(), "..".to_string(), EntryType::Directory);
             result.push(FileOpenEntry {
@@ -211,6 +246,7 @@ impl FileOpenState {
             entries
                 .into_iter()
                 .filter(|e| self.show_hidden || !Self::is_hidden(&e.name))
+                .filter(|e| e.is_dir() || self.matches_name_filters(&e.name))
                 .map(|fs_entry| FileOpenEntry {
                     fs_entry,
                     matches_filter: true,
@@ -235,6 +271,11 @@ impl FileOpenState {
         self.entries.clear();
     }
 
+    /// Check if a file name passes the `restrict` globs
+    fn matches_name_filters(&self, name: &str) -> bool {
+        self.name_filters.is_empty() || self.name_filters.iter().any(|re| re.is_match(name))
+    }
+
     /// Check if a filename is hidden (starts with .)
     fn is_hidden(name: &str) -> bool {
         name.starts_with('.')
@@ -595,11 +636,11 @@ pub fn format_size(size: u64) -> String {
     const GB: u64 = MB * 1024;
 
     if size >= GB {
-        format!("{:.1} GB", size as f64 / GB as f64)
+        format!("{} GB", format_decimal(size as f64 / GB as f64, 1))
     } else if size >= MB {
-        format!("{:.1} MB", size as f64 / MB as f64)
+        format!("{} MB", format_decimal(size as f64 / MB as f64, 1))
     } else if size >= KB {
-        format!("{:.1} KB", size as f64 / KB as f64)
+        format!("{} KB", format_decimal(size as f64 / KB as f64, 1))
     } else {
         format!("{} B", size)
     }
@@ -612,27 +653,28 @@ pub fn format_modified(time: SystemTime) -> String {
         Ok(duration) => {
             let secs = duration.as_secs();
             if secs < 60 {
-                "just now".to_string()
+                t!("file_browser.just_now").to_string()
             } else if secs < 3600 {
-                format!("{} min ago", secs / 60)
+                t!("file_browser.minutes_ago", count = secs / 60).to_string()
             } else if secs < 86400 {
-                format!("{} hr ago", secs / 3600)
+                t!("file_browser.hours_ago", count = secs / 3600).to_string()
             } else if secs < 86400 * 7 {
-                format!("{} days ago", secs / 86400)
+                t!("file_browser.days_ago", count = secs / 86400).to_string()
             } else {
-                // Format as date
-                let datetime: chrono::DateTime<chrono::Local> = time.into();
-                datetime.format("%Y-%m-%d").to_string()
+                format_date(time)
             }
         }
-        Err(_) => {
-            // Time is in the future
-            let datetime: chrono::DateTime<chrono::Local> = time.into();
-            datetime.format("%Y-%m-%d").to_string()
-        }
+        // Time is in the future
+        Err(_) => format_date(time),
     }
 }
 
+/// Format a timestamp as a date in the current locale's format
+fn format_date(time: SystemTime) -> String {
+    let datetime: chrono::DateTime<chrono::Local> = time.into();
+    datetime.format(&date_format()).to_string()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -710,6 +752,27 @@ mod tests {
         assert_eq!(state.matching_count(), 2);
     }
 
+    #[test]
+    fn test_restrict_to_scope_and_globs() {
+        let mut state = FileOpenState::new(PathBuf::from("/test"), false);
+        state.restrict(Some(PathBuf::from("/test")), &["*.rs".to_string()]);
+        state.set_entries(vec![
+            make_entry("main.rs", false),
+            make_entry("notes.txt", false),
+            make_entry("src", true),
+        ]);
+
+        // No ".." above the scope, and files not matching the globs are hidden
+        let names: Vec<&str> = state
+            .entries
+            .iter()
+            .map(|e| e.fs_entry.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["src", "main.rs"]);
+        assert!(state.shortcuts.iter().all(|s| s.path.starts_with("/test")));
+        assert!(!state.in_scope(Path::new("/other")));
+    }
+
     #[test]
     fn test_filter_case_insensitive() {
         // Use root path so no ".." entry is added
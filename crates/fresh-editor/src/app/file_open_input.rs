@@ -5,8 +5,8 @@
 
 use super::file_open::{FileOpenSection, SortMode};
 use super::Editor;
+use crate::config_io::expand_path;
 use crate::input::keybindings::Action;
-use crate::primitives::path_utils::expand_tilde;
 use crate::view::prompt::PromptType;
 use rust_i18n::t;
 
@@ -18,19 +18,30 @@ impl Editor {
             .map(|p| {
                 matches!(
                     p.prompt_type,
-                    PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                    PromptType::OpenFile
+                        | PromptType::SwitchProject
+                        | PromptType::SaveFileAs
+                        | PromptType::PluginFileBrowser
                 )
             })
             .unwrap_or(false)
             && self.file_open_state.is_some()
     }
 
-    /// Check if we're in folder-only selection mode (Switch Project)
+    /// Check if we're in folder-only selection mode (Switch Project, or a
+    /// plugin file browser picking directories)
     fn is_folder_open_mode(&self) -> bool {
-        self.prompt
-            .as_ref()
-            .map(|p| p.prompt_type == PromptType::SwitchProject)
-            .unwrap_or(false)
+        let picks_directories = self.is_plugin_file_browser()
+            && self
+                .plugin_file_browser
+                .as_ref()
+                .is_some_and(|state| state.directories);
+        picks_directories
+            || self
+                .prompt
+                .as_ref()
+                .map(|p| p.prompt_type == PromptType::SwitchProject)
+                .unwrap_or(false)
     }
 
     /// Check if we're in save mode (Save As)
@@ -174,13 +185,13 @@ impl Editor {
 
         // If there's any prompt input, try to resolve it as a path
         if !prompt_input.is_empty() {
-            // Expand tilde and resolve path
-            let tilde_expanded = expand_tilde(&prompt_input);
-            let expanded_path = if tilde_expanded.is_absolute() {
-                tilde_expanded
+            // Expand ~ and environment variables, then resolve path
+            let expanded = expand_path(&prompt_input);
+            let expanded_path = if expanded.is_absolute() {
+                expanded
             } else {
                 // Relative path (including plain filename) - resolve against current directory
-                current_dir.join(&prompt_input)
+                current_dir.join(&expanded)
             };
 
             if expanded_path.is_dir() {
@@ -200,7 +211,10 @@ impl Editor {
                 // Only allowed in file mode, not folder mode
                 self.file_open_open_file(expanded_path);
                 return;
-            } else if !is_folder_mode && Self::should_create_new_file(&prompt_input) {
+            } else if !is_folder_mode
+                && !self.is_plugin_file_browser()
+                && Self::should_create_new_file(&prompt_input)
+            {
                 // File doesn't exist but input looks like a filename - create new file
                 // This handles cases like "newfile.txt" or "/path/to/newfile.txt"
                 self.file_open_create_new_file(expanded_path);
@@ -263,6 +277,11 @@ impl Editor {
 
     /// Select a folder as the new project root (for SwitchProject mode)
     fn file_open_select_folder(&mut self, path: std::path::PathBuf) {
+        if self.is_plugin_file_browser() {
+            self.finish_plugin_file_browser(path);
+            return;
+        }
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
@@ -285,6 +304,11 @@ impl Editor {
 
     /// Open a file from the file browser
     fn file_open_open_file(&mut self, path: std::path::PathBuf) {
+        if self.is_plugin_file_browser() {
+            self.finish_plugin_file_browser(path);
+            return;
+        }
+
         // Close the file browser
         self.file_open_state = None;
         self.prompt = None;
@@ -408,12 +432,12 @@ impl Editor {
                 .unwrap_or_else(|| std::env::current_dir().unwrap_or_default());
 
             // Build the full path
-            // Expand tilde and resolve path
-            let tilde_expanded = expand_tilde(&filter);
-            let full_path = if tilde_expanded.is_absolute() {
-                tilde_expanded
+            // Expand ~ and environment variables, then resolve path
+            let expanded = expand_path(&filter);
+            let full_path = if expanded.is_absolute() {
+                expanded
             } else {
-                current_dir.join(&filter)
+                current_dir.join(&expanded)
             };
 
             // Get the parent directory and filename
@@ -8,11 +8,15 @@
 //! - File modification time tracking
 //! - Save conflict detection
 
-use crate::model::buffer::SudoSaveRequired;
+use crate::model::buffer::{SudoSaveRequired, SAVE_CHUNK_SIZE};
+use crate::model::encoding::Encoding;
+use crate::services::async_bridge::AsyncMessage;
 use crate::view::prompt::PromptType;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
-use lsp_types::TextDocumentContentChangeEvent;
+use lsp_types::{FileChangeType, TextDocumentContentChangeEvent};
 use rust_i18n::t;
 
 use crate::model::event::{BufferId, EventLog};
@@ -21,6 +25,13 @@ use crate::state::EditorState;
 
 use super::{BufferMetadata, Editor};
 
+/// Saves at least this large are written a few chunks per frame, with their
+/// progress in the status bar
+const SAVE_PROGRESS_MIN_BYTES: usize = 100 * 1024 * 1024;
+
+/// How long one poll of a large save may spend writing before the next frame
+const SAVE_POLL_BUDGET: Duration = Duration::from_millis(50);
+
 impl Editor {
     /// Save the active buffer
     pub fn save(&mut self) -> anyhow::Result<()> {
@@ -30,21 +41,166 @@ impl Editor {
             .file_path()
             .map(|p| p.to_path_buf());
 
+        if let Some(path) = &path {
+            if self.begin_large_save(path, false)? {
+                return Ok(());
+            }
+        }
+
         match self.active_state_mut().buffer.save() {
             Ok(()) => self.finalize_save(path),
+            Err(e) => self.handle_save_error(e),
+        }
+    }
+
+    /// Ask to retry a save that needs elevated permissions with sudo;
+    /// any other error is returned
+    fn handle_save_error(&mut self, e: anyhow::Error) -> anyhow::Result<()> {
+        if let Some(sudo_info) = e.downcast_ref::<SudoSaveRequired>() {
+            let info = sudo_info.clone();
+            self.start_prompt(
+                t!("prompt.sudo_save_confirm").to_string(),
+                PromptType::ConfirmSudoSave { info },
+            );
+            Ok(())
+        } else {
+            Err(e)
+        }
+    }
+
+    /// Start saving the active buffer to `path` a few chunks per frame if
+    /// it's large and local, so the status bar can show the progress.
+    ///
+    /// Returns false if the buffer should be saved right away instead.
+    pub(crate) fn begin_large_save(&mut self, path: &Path, save_as: bool) -> anyhow::Result<bool> {
+        let buffer = &self.active_state().buffer;
+        if buffer.total_bytes() < SAVE_PROGRESS_MIN_BYTES
+            || buffer.filesystem().remote_connection_info().is_some()
+        {
+            return Ok(false);
+        }
+
+        let save = buffer.begin_save(path)?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        tracing::info!("Saving {} in chunks", path.display());
+        self.status_message =
+            Some(t!("file.saving_progress", name = &name, percent = 0).to_string());
+        self.save_in_progress = Some(super::SaveInProgress {
+            buffer_id: self.active_buffer(),
+            save,
+            name,
+            save_as,
+        });
+        Ok(true)
+    }
+
+    /// Whether a large save is still being written
+    pub fn is_saving(&self) -> bool {
+        self.save_in_progress.is_some()
+    }
+
+    /// Write the next chunks of a large save, and finish it once all of the
+    /// content is written. Returns true if the status changed (needs render).
+    pub fn poll_save_in_progress(&mut self) -> bool {
+        let Some(mut progress) = self.save_in_progress.take() else {
+            return false;
+        };
+        let Some(state) = self.buffers.get(&progress.buffer_id) else {
+            return true;
+        };
+        let buffer = &state.buffer;
+
+        let started = Instant::now();
+        let result = loop {
+            match buffer.continue_save(&mut progress.save, SAVE_CHUNK_SIZE) {
+                Ok(false) if started.elapsed() < SAVE_POLL_BUDGET => {}
+                result => break result,
+            }
+        };
+
+        match result {
+            Ok(false) => {
+                let percent = progress.save.written() * 100 / buffer.total_bytes() as u64;
+                self.status_message = Some(
+                    t!(
+                        "file.saving_progress",
+                        name = &progress.name,
+                        percent = percent
+                    )
+                    .to_string(),
+                );
+                self.save_in_progress = Some(progress);
+            }
+            Ok(true) => self.finish_large_save(progress),
             Err(e) => {
-                if let Some(sudo_info) = e.downcast_ref::<SudoSaveRequired>() {
-                    let info = sudo_info.clone();
-                    self.start_prompt(
-                        t!("prompt.sudo_save_confirm").to_string(),
-                        PromptType::ConfirmSudoSave { info },
+                self.pending_close_buffer = None;
+                self.set_status_message(t!("file.error_saving", error = e.to_string()).to_string());
+            }
+        }
+        true
+    }
+
+    /// Replace the file with a fully written large save and finish the save
+    /// as [`Self::save`] or Save As would have
+    fn finish_large_save(&mut self, progress: super::SaveInProgress) {
+        let super::SaveInProgress {
+            buffer_id,
+            save,
+            save_as,
+            ..
+        } = progress;
+        let path = save.dest_path().to_path_buf();
+        tracing::info!("Saved {}", path.display());
+
+        // Finishing a save works on the active buffer
+        let old_active = self.active_buffer();
+        if old_active != buffer_id {
+            self.set_active_buffer(buffer_id);
+        }
+        let result = self.active_state_mut().buffer.finish_save(save);
+        if save_as {
+            match result {
+                Ok(()) => self.finish_save_file_as(path),
+                Err(e) => {
+                    self.pending_close_buffer = None;
+                    self.set_status_message(
+                        t!("file.error_saving", error = e.to_string()).to_string(),
+                    );
+                }
+            }
+        } else {
+            let result = result
+                .and_then(|()| self.finalize_save(Some(path)))
+                .or_else(|e| self.handle_save_error(e));
+            match result {
+                Ok(()) if self.pending_close_buffer == Some(buffer_id) => {
+                    self.pending_close_buffer = None;
+                    self.close_saved_buffer(buffer_id);
+                }
+                Ok(()) => {}
+                Err(e) => {
+                    self.pending_close_buffer = None;
+                    self.set_status_message(
+                        t!("file.save_failed", error = e.to_string()).to_string(),
                     );
-                    Ok(())
-                } else {
-                    Err(e)
                 }
             }
         }
+        if old_active != buffer_id && self.buffers.contains_key(&old_active) {
+            self.set_active_buffer(old_active);
+        }
+    }
+
+    /// Close a buffer that was just saved, for Save and Close
+    pub(crate) fn close_saved_buffer(&mut self, buffer_id: BufferId) {
+        if let Err(e) = self.force_close_buffer(buffer_id) {
+            self.set_status_message(t!("file.cannot_close", error = e.to_string()).to_string());
+        } else {
+            self.set_status_message(t!("buffer.saved_and_closed").to_string());
+        }
     }
 
     /// Internal helper to finalize save state (mark as saved, notify LSP, etc.)
@@ -104,7 +260,15 @@ impl Editor {
         }
 
         // Run on-save actions (formatters, linters, etc.)
-        match self.run_on_save_actions() {
+        let result = self.run_on_save_actions();
+        self.report_on_save_actions(result);
+
+        Ok(())
+    }
+
+    /// Show the outcome of on-save actions, replacing the "Saved" message
+    pub(super) fn report_on_save_actions(&mut self, result: Result<bool, String>) {
+        match result {
             Ok(true) => {
                 // Actions ran successfully - if status_message was set by run_on_save_actions
                 // (e.g., for missing optional formatters), keep it. Otherwise update status.
@@ -121,8 +285,6 @@ impl Editor {
                 self.status_message = Some(e);
             }
         }
-
-        Ok(())
     }
 
     /// Revert the active buffer to the last saved version on disk
@@ -142,6 +304,22 @@ impl Editor {
             return Ok(false);
         }
 
+        // Keep a file reopened with another encoding in that encoding
+        let encoding = self.active_state().buffer.encoding();
+        self.reload_active_buffer(&path, encoding)?;
+
+        self.status_message = Some(t!("status.reverted").to_string());
+        Ok(true)
+    }
+
+    /// Replace the active buffer with `path` loaded fresh from disk in
+    /// `encoding`, keeping the cursors and scroll position where possible and
+    /// clearing the undo history
+    pub(super) fn reload_active_buffer(
+        &mut self,
+        path: &Path,
+        encoding: Encoding,
+    ) -> anyhow::Result<()> {
         // Save scroll position (from SplitViewState) and cursor positions before reloading
         let active_split = self.split_manager.active_split();
         let (old_top_byte, old_left_column) = self
@@ -152,15 +330,24 @@ impl Editor {
         let old_cursors = self.active_state().cursors.clone();
 
         // Load the file content fresh from disk
-        let mut new_state = EditorState::from_file_with_languages(
-            &path,
-            self.terminal_width,
-            self.terminal_height,
-            self.config.editor.large_file_threshold_bytes as usize,
-            &self.grammar_registry,
-            &self.config.languages,
-            std::sync::Arc::clone(&self.filesystem),
-        )?;
+        let mut new_state = match encoding {
+            Encoding::Utf8 => EditorState::from_file_with_languages(
+                path,
+                self.terminal_width,
+                self.terminal_height,
+                self.config.editor.large_file_threshold_bytes as usize,
+                &self.grammar_registry,
+                &self.config.languages,
+                std::sync::Arc::clone(&self.filesystem),
+            )?,
+            _ => EditorState::from_file_with_encoding(
+                path,
+                encoding,
+                &self.grammar_registry,
+                &self.config.languages,
+                std::sync::Arc::clone(&self.filesystem),
+            )?,
+        };
 
         // Restore cursor positions (clamped to valid range for new file size)
         let new_file_size = new_state.buffer.len();
@@ -175,7 +362,7 @@ impl Editor {
         // Replace the current buffer with the new state
         let buffer_id = self.active_buffer();
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            *state = new_state;
+            state.reload_from(new_state);
             // Note: line_wrap_enabled is now in SplitViewState.viewport
         }
 
@@ -188,24 +375,22 @@ impl Editor {
 
         // Clear the undo/redo history for this buffer
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
-            *event_log = EventLog::new();
+            *event_log = EventLog::new().with_branch_limit(self.config.editor.undo_branch_limit);
         }
 
         // Clear seen_byte_ranges so plugins get notified of all visible lines
         self.seen_byte_ranges.remove(&buffer_id);
 
         // Update the file modification time
-        if let Ok(metadata) = self.filesystem.metadata(&path) {
+        if let Ok(metadata) = self.filesystem.metadata(path) {
             if let Some(mtime) = metadata.modified {
-                self.file_mod_times.insert(path.clone(), mtime);
+                self.file_mod_times.insert(path.to_path_buf(), mtime);
             }
         }
 
         // Notify LSP that the file was changed
-        self.notify_lsp_file_changed(&path);
-
-        self.status_message = Some(t!("status.reverted").to_string());
-        Ok(true)
+        self.notify_lsp_file_changed(path);
+        Ok(())
     }
 
     /// Toggle auto-revert mode
@@ -230,8 +415,9 @@ impl Editor {
         }
 
         // Check poll interval
-        let poll_interval =
-            std::time::Duration::from_millis(self.config.editor.auto_revert_poll_interval_ms);
+        let poll_interval = self.background_poll_interval(std::time::Duration::from_millis(
+            self.config.editor.auto_revert_poll_interval_ms,
+        ));
         let elapsed = self.time_source.elapsed_since(self.last_auto_revert_poll);
         tracing::trace!(
             "poll_file_changes: elapsed={:?}, poll_interval={:?}",
@@ -243,6 +429,13 @@ impl Editor {
         }
         self.last_auto_revert_poll = self.time_source.now();
 
+        // Filesystems that push change notifications (remote agents) are
+        // watched instead of polled; changes arrive as AsyncMessage::FileChanged
+        if self.filesystem.supports_watch() && self.async_bridge.is_some() {
+            self.sync_file_watches();
+            return false;
+        }
+
         // Collect paths of open files that need checking
         let files_to_check: Vec<PathBuf> = self
             .buffers
@@ -282,14 +475,74 @@ impl Editor {
         any_changed
     }
 
+    /// Watch newly opened files and stop watching closed ones
+    fn sync_file_watches(&mut self) {
+        let Some(bridge) = &self.async_bridge else {
+            return;
+        };
+
+        let open_files: HashSet<PathBuf> = self
+            .buffers
+            .values()
+            .filter_map(|state| state.buffer.file_path().map(PathBuf::from))
+            .collect();
+
+        let closed: Vec<PathBuf> = self
+            .watched_files
+            .difference(&open_files)
+            .cloned()
+            .collect();
+        for path in closed {
+            self.watched_files.remove(&path);
+            if let Err(e) = self.filesystem.unwatch(&path) {
+                tracing::debug!("Failed to unwatch {:?}: {}", path, e);
+            }
+        }
+
+        let sender = bridge.sender();
+        for path in open_files {
+            if self.watched_files.contains(&path) {
+                continue;
+            }
+            // Record the current mtime so the first notification can be
+            // compared against it
+            if !self.file_mod_times.contains_key(&path) {
+                match self
+                    .filesystem
+                    .metadata(&path)
+                    .ok()
+                    .and_then(|m| m.modified)
+                {
+                    Some(mtime) => {
+                        self.file_mod_times.insert(path.clone(), mtime);
+                    }
+                    None => continue, // Not saved yet; try again next poll
+                }
+            }
+            let sender = sender.clone();
+            let on_change = std::sync::Arc::new(move |changed: PathBuf| {
+                let _ = sender.send(AsyncMessage::FileChanged {
+                    path: changed.display().to_string(),
+                });
+            });
+            match self.filesystem.watch(&path, on_change) {
+                Ok(()) => {
+                    self.watched_files.insert(path);
+                }
+                Err(e) => tracing::warn!("Failed to watch {:?}: {}", path, e),
+            }
+        }
+    }
+
     /// Poll for file tree changes (called from main loop)
     ///
     /// Checks modification times of expanded directories to detect new/deleted files.
     /// Returns true if any directory was refreshed (requires re-render).
     pub fn poll_file_tree_changes(&mut self) -> bool {
         // Check poll interval
-        let poll_interval =
-            std::time::Duration::from_millis(self.config.editor.file_tree_poll_interval_ms);
+        let poll_interval = self.background_poll_interval(std::time::Duration::from_millis(
+            self.config.editor.file_tree_poll_interval_ms,
+        ));
         if self.time_source.elapsed_since(self.last_file_tree_poll) < poll_interval {
             return false;
         }
@@ -341,19 +594,61 @@ impl Editor {
             return false;
         }
 
-        // Refresh each changed directory
+        // Refresh each changed directory, noting the entries that came and went
+        let mut file_changes = Vec::new();
         if let (Some(runtime), Some(explorer)) = (&self.tokio_runtime, &mut self.file_explorer) {
             for node_id in dirs_to_refresh {
                 let tree = explorer.tree_mut();
+                let Some(dir_path) = tree.get_node(node_id).map(|n| n.entry.path.clone()) else {
+                    continue;
+                };
+                let before = child_paths(tree, node_id);
                 if let Err(e) = runtime.block_on(tree.refresh_node(node_id)) {
                     tracing::warn!("Failed to refresh directory: {}", e);
+                    continue;
                 }
+                let after = tree
+                    .get_node_by_path(&dir_path)
+                    .map(|node| child_paths(tree, node.id))
+                    .unwrap_or_default();
+                file_changes.extend(
+                    after
+                        .difference(&before)
+                        .map(|path| (path.clone(), FileChangeType::CREATED)),
+                );
+                file_changes.extend(
+                    before
+                        .difference(&after)
+                        .map(|path| (path.clone(), FileChangeType::DELETED)),
+                );
             }
         }
+        self.notify_lsp_watched_files(&file_changes);
 
         true
     }
 
+    /// Report files created, changed or deleted on disk to the language servers
+    ///
+    /// Servers only hear about the files matching the watchers they registered
+    /// for `workspace/didChangeWatchedFiles`. Edits to open documents reach
+    /// them through `didChange`/`didSave` instead.
+    pub(crate) fn notify_lsp_watched_files(&self, changes: &[(PathBuf, FileChangeType)]) {
+        let Some(lsp) = &self.lsp else {
+            return;
+        };
+        let changes: Vec<(lsp_types::Uri, FileChangeType)> = changes
+            .iter()
+            .filter_map(|(path, change)| {
+                let uri = url::Url::from_file_path(path).ok()?;
+                Some((uri.as_str().parse().ok()?, *change))
+            })
+            .collect();
+        if !changes.is_empty() {
+            lsp.did_change_watched_files(&changes);
+        }
+    }
+
     /// Notify LSP server about a newly opened file
     /// Handles language detection, spawning LSP clients, and sending didOpen notifications
     pub(crate) fn notify_lsp_file_opened(
@@ -642,7 +937,7 @@ impl Editor {
 
         // Replace the buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
-            *state = new_state;
+            state.reload_from(new_state);
 
             // Restore cursor positions (clamped to valid range for new file size)
             let mut restored_cursors = old_cursors;
@@ -655,7 +950,7 @@ impl Editor {
 
         // Clear the undo/redo history for this buffer
         if let Some(event_log) = self.event_logs.get_mut(&buffer_id) {
-            *event_log = EventLog::new();
+            *event_log = EventLog::new().with_branch_limit(self.config.editor.undo_branch_limit);
         }
 
         // Clear seen_byte_ranges so plugins get notified of all visible lines
@@ -771,6 +1066,7 @@ impl Editor {
 
                 // Update the modification time tracking for this file
                 self.watch_file(&path);
+                self.notify_lsp_watched_files(&[(path.clone(), FileChangeType::CHANGED)]);
             }
         }
     }
@@ -796,3 +1092,19 @@ impl Editor {
         }
     }
 }
+
+/// Paths of the loaded children of a file tree node
+fn child_paths(
+    tree: &crate::view::file_tree::FileTree,
+    node_id: crate::view::file_tree::NodeId,
+) -> HashSet<PathBuf> {
+    tree.get_node(node_id)
+        .map(|node| {
+            node.children
+                .iter()
+                .filter_map(|&child| tree.get_node(child))
+                .map(|child| child.entry.path.clone())
+                .collect()
+        })
+        .unwrap_or_default()
+}
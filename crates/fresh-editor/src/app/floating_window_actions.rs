@@ -0,0 +1,186 @@
+//! Floating tool window actions
+//!
+//! Pinning popups into floating windows, keyboard focus and mouse
+//! move/resize, and keeping the Windows menu in sync with the open windows.
+
+use super::Editor;
+use crate::config::MenuItem;
+use crate::input::handler::InputResult;
+use crate::view::floating_window::{
+    FloatingWindowHit, FloatingWindowId, FloatingWindowInput, FloatingWindowManager,
+};
+use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use rust_i18n::t;
+use std::collections::HashMap;
+
+/// Menu id of the Windows menu (see `MenuConfig::translated_menus`)
+pub(crate) const WINDOWS_MENU_ID: &str = "Windows";
+
+impl Editor {
+    /// Screen area floating windows are kept inside
+    fn floating_window_bounds(&self) -> Rect {
+        Rect::new(0, 0, self.terminal_width, self.terminal_height)
+    }
+
+    /// Pin the topmost popup into a floating window at its current position
+    pub fn pin_popup(&mut self) {
+        let Some(popup) = self.active_state().popups.top().cloned() else {
+            self.set_status_message(t!("floating_window.no_popup").to_string());
+            return;
+        };
+        if !FloatingWindowManager::can_pin(&popup) {
+            self.set_status_message(t!("floating_window.cannot_pin").to_string());
+            return;
+        }
+
+        // Keep the window where the popup was last drawn
+        let bounds = self.floating_window_bounds();
+        let area = self
+            .cached_layout
+            .popup_areas
+            .last()
+            .map(|(_, area, ..)| *area)
+            .unwrap_or_else(|| popup.calculate_area(bounds, None));
+
+        self.hide_popup();
+        let id = self.floating_windows.pin(popup, area);
+        self.floating_windows.move_by(id, 0, 0, bounds);
+        self.set_status_message(t!("floating_window.pinned").to_string());
+    }
+
+    /// Focus a floating window by id, or cycle to the next one when `id` is None
+    pub fn focus_floating_window(&mut self, id: Option<FloatingWindowId>) {
+        let focused = match id {
+            Some(id) => self.floating_windows.focus(id),
+            None => self.floating_windows.focus_next(),
+        };
+        if focused {
+            self.on_editor_focus_lost();
+        } else {
+            self.set_status_message(t!("floating_window.none").to_string());
+        }
+    }
+
+    /// Close the focused floating window (or the topmost one)
+    pub fn close_floating_window(&mut self) {
+        if !self.floating_windows.close_active() {
+            self.set_status_message(t!("floating_window.none").to_string());
+        }
+    }
+
+    /// Handle input while a floating window has keyboard focus.
+    /// Returns None when the key should be processed by the editor instead.
+    pub fn handle_floating_window_input(&mut self, event: &KeyEvent) -> Option<InputResult> {
+        let bounds = self.floating_window_bounds();
+        match self.floating_windows.handle_key(event, bounds) {
+            FloatingWindowInput::Consumed => Some(InputResult::Consumed),
+            FloatingWindowInput::Unhandled => None,
+        }
+    }
+
+    /// Handle a left click on a floating window: raise it, focus it and start
+    /// a move (title bar) or resize (bottom-right corner) drag.
+    /// Returns false if the click was not on a floating window.
+    pub(super) fn handle_floating_window_click(&mut self, col: u16, row: u16) -> bool {
+        let Some((id, hit)) = self.floating_windows.hit_test(col, row) else {
+            // Clicking anywhere else hands the keyboard back to the editor
+            self.floating_windows.unfocus();
+            return false;
+        };
+
+        self.floating_windows.focus(id);
+        if hit != FloatingWindowHit::Body {
+            if let Some(window) = self.floating_windows.get(id) {
+                self.mouse_state.dragging_floating_window = Some((id, hit));
+                self.mouse_state.drag_start_floating_window = Some(((col, row), window.area));
+            }
+        }
+        true
+    }
+
+    /// Continue a floating window drag. Returns false if no drag is in progress.
+    pub(super) fn handle_floating_window_drag(&mut self, col: u16, row: u16) -> bool {
+        let (Some((id, hit)), Some(((start_col, start_row), start_area))) = (
+            self.mouse_state.dragging_floating_window,
+            self.mouse_state.drag_start_floating_window,
+        ) else {
+            return false;
+        };
+
+        let dx = col as i32 - start_col as i32;
+        let dy = row as i32 - start_row as i32;
+        let bounds = self.floating_window_bounds();
+        match hit {
+            FloatingWindowHit::ResizeHandle => {
+                let (current_w, current_h) = self
+                    .floating_windows
+                    .get(id)
+                    .map(|w| (w.area.width as i32, w.area.height as i32))
+                    .unwrap_or_default();
+                self.floating_windows.resize_by(
+                    id,
+                    start_area.width as i32 + dx - current_w,
+                    start_area.height as i32 + dy - current_h,
+                    bounds,
+                );
+            }
+            _ => self.floating_windows.move_to(
+                id,
+                start_area.x as i32 + dx,
+                start_area.y as i32 + dy,
+                bounds,
+            ),
+        }
+        true
+    }
+
+    /// Scroll the floating window under the mouse. Returns false if there is none.
+    pub(super) fn handle_floating_window_scroll(&mut self, col: u16, row: u16, delta: i32) -> bool {
+        match self.floating_windows.hit_test(col, row) {
+            Some((id, _)) => {
+                self.floating_windows.scroll(id, delta);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rebuild the Windows menu so it lists the open floating windows
+    pub(super) fn update_windows_menu(&mut self) {
+        let Some(menu) = self
+            .menus
+            .menus
+            .iter_mut()
+            .find(|m| m.id.as_deref() == Some(WINDOWS_MENU_ID))
+        else {
+            return;
+        };
+
+        // Keep the fixed items above the first separator
+        let fixed = menu
+            .items
+            .iter()
+            .position(|item| matches!(item, MenuItem::Separator { .. }))
+            .unwrap_or(menu.items.len());
+        menu.items.truncate(fixed);
+        menu.items.push(MenuItem::Separator { separator: true });
+
+        for (idx, window) in self.floating_windows.all().iter().rev().enumerate() {
+            let mut args = HashMap::new();
+            args.insert("id".to_string(), serde_json::json!(window.id));
+            menu.items.push(MenuItem::Action {
+                label: format!("{}: {}", idx + 1, window.title),
+                action: "focus_floating_window".to_string(),
+                args,
+                when: None,
+                checkbox: None,
+            });
+        }
+    }
+
+    /// Check if a floating window has keyboard focus
+    pub fn is_floating_window_focused(&self) -> bool {
+        self.floating_windows.focused().is_some()
+    }
+}
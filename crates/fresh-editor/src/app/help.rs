@@ -11,3 +11,6 @@ pub const HELP_MANUAL_BUFFER_NAME: &str = "*Fresh Manual*";
 
 /// The name of the keyboard shortcuts buffer
 pub const KEYBOARD_SHORTCUTS_BUFFER_NAME: &str = "*Keyboard Shortcuts*";
+
+/// The name of the buffer listing Fresh's directories
+pub const PATHS_BUFFER_NAME: &str = "*Paths*";
@@ -0,0 +1,351 @@
+//! Inline suggestions (ghost text) from plugin providers.
+//!
+//! When the cursor has rested for `editor.inline_suggestion_delay_ms` in a
+//! buffer whose language has inline suggestion providers, they are asked for
+//! text to insert at the cursor. The first non-empty answer is shown as dim
+//! virtual text: its first line after the cursor and the rest below it.
+//! Tab accepts the whole suggestion, moving to the end of the next word
+//! accepts one word, typing the suggestion's next character keeps the rest,
+//! and anything else that moves the cursor or edits the buffer dismisses it.
+
+use super::Editor;
+use crate::input::keybindings::{Action, KeyContext};
+use crate::model::event::{BufferId, Event};
+use crate::view::virtual_text::{VirtualTextId, VirtualTextPosition};
+use ratatui::style::{Modifier, Style};
+use std::time::{Duration, Instant};
+
+/// Bytes of text on each side of the cursor sent to providers
+const CONTEXT_BYTES: usize = 4096;
+
+/// Where a suggestion is asked for: the cursor in a version of a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct SuggestionAnchor {
+    buffer_id: BufferId,
+    version: u64,
+    position: usize,
+}
+
+/// A suggestion shown as ghost text
+struct ShownSuggestion {
+    anchor: SuggestionAnchor,
+    text: String,
+    virtual_texts: Vec<VirtualTextId>,
+}
+
+/// Debouncing and display state of inline suggestions
+#[derive(Default)]
+pub(super) struct InlineSuggestionState {
+    /// Where the cursor rests and since when
+    resting: Option<(SuggestionAnchor, Instant)>,
+    /// Last request sent to the providers and where it was made
+    requested: Option<(u64, SuggestionAnchor)>,
+    next_request_id: u64,
+    shown: Option<ShownSuggestion>,
+}
+
+/// Length of the part of `text` accepted by one "accept word": leading
+/// spaces and then a word or a run of punctuation, or a single line break
+fn next_word_len(text: &str) -> usize {
+    if let Some(rest) = text.strip_prefix("\r\n") {
+        return text.len() - rest.len();
+    }
+    if text.starts_with('\n') {
+        return 1;
+    }
+    let spaces = text.len() - text.trim_start_matches([' ', '\t']).len();
+    let rest = &text[spaces..];
+    let is_word = |c: char| c.is_alphanumeric() || c == '_';
+    let first_is_word = rest.chars().next().is_some_and(is_word);
+    let word = rest
+        .find(|c: char| c.is_whitespace() || is_word(c) != first_is_word)
+        .unwrap_or(rest.len());
+    spaces + word
+}
+
+impl Editor {
+    /// Where a suggestion could be shown right now, if anywhere
+    ///
+    /// Suggestions need a single cursor without a selection in an editable
+    /// buffer with providers for its language, and no prompt, popup or menu.
+    fn inline_suggestion_anchor(&self) -> Option<SuggestionAnchor> {
+        if self.get_key_context() != KeyContext::Normal || self.is_editing_disabled() {
+            return None;
+        }
+        let buffer_id = self.active_buffer();
+        if self.is_terminal_buffer(buffer_id) {
+            return None;
+        }
+        let state = self.active_state();
+        if !self
+            .inline_suggestion_providers
+            .iter()
+            .any(|provider| provider.handles_language(&state.language))
+        {
+            return None;
+        }
+        if state.cursors.count() != 1 {
+            return None;
+        }
+        let cursor = state.cursors.primary();
+        if cursor
+            .anchor
+            .is_some_and(|anchor| anchor != cursor.position)
+        {
+            return None;
+        }
+        Some(SuggestionAnchor {
+            buffer_id,
+            version: state.buffer.version(),
+            position: cursor.position,
+        })
+    }
+
+    /// Dismiss a suggestion the cursor moved away from, and ask the
+    /// providers for a new one once the cursor has rested long enough
+    ///
+    /// Returns true if the screen needs to be redrawn.
+    pub(super) fn poll_inline_suggestion(&mut self) -> bool {
+        let anchor = self.inline_suggestion_anchor();
+        let mut changed = false;
+        if self
+            .inline_suggestions
+            .shown
+            .as_ref()
+            .is_some_and(|shown| Some(shown.anchor) != anchor)
+        {
+            self.dismiss_inline_suggestion();
+            changed = true;
+        }
+
+        let Some(anchor) = anchor else {
+            self.inline_suggestions.resting = None;
+            return changed;
+        };
+        let since = match self.inline_suggestions.resting {
+            Some((resting, since)) if resting == anchor => since,
+            _ => {
+                self.inline_suggestions.resting = Some((anchor, self.time_source.now()));
+                return changed;
+            }
+        };
+        let delay = Duration::from_millis(self.config.editor.inline_suggestion_delay_ms);
+        let already_requested = self
+            .inline_suggestions
+            .requested
+            .is_some_and(|(_, requested)| requested == anchor);
+        if self.inline_suggestions.shown.is_none()
+            && !already_requested
+            && self.time_source.elapsed_since(since) >= delay
+        {
+            self.request_inline_suggestion(anchor);
+        }
+        changed
+    }
+
+    /// Ask the providers for the active buffer's language for a suggestion
+    fn request_inline_suggestion(&mut self, anchor: SuggestionAnchor) {
+        let language = self.active_state().language.clone();
+        let providers: Vec<_> = self
+            .inline_suggestion_providers
+            .iter()
+            .filter(|provider| provider.handles_language(&language))
+            .cloned()
+            .collect();
+
+        let position = anchor.position;
+        let state = self.active_state_mut();
+        let text_before = state.get_text_range(position.saturating_sub(CONTEXT_BYTES), position);
+        let after_end = (position + CONTEXT_BYTES).min(state.buffer.len());
+        let text_after = state.get_text_range(position, after_end);
+        let request = fresh_core::api::InlineSuggestionRequest {
+            buffer_id: anchor.buffer_id.0,
+            path: state
+                .buffer
+                .file_path()
+                .map(|path| path.to_string_lossy().into_owned()),
+            language,
+            position,
+            text_before,
+            text_after,
+        };
+
+        self.inline_suggestions.next_request_id += 1;
+        let request_id = self.inline_suggestions.next_request_id;
+        for provider in &providers {
+            self.plugin_manager
+                .request_inline_suggestion(provider, request_id, request.clone());
+        }
+        self.inline_suggestions.requested = Some((request_id, anchor));
+    }
+
+    /// Handle a provider's answer: the first suggestion for the current
+    /// request is shown if the cursor has not moved since it was asked for
+    pub(crate) fn handle_plugin_inline_suggestion(
+        &mut self,
+        request_id: u64,
+        text: Option<String>,
+    ) {
+        let Some((requested_id, anchor)) = self.inline_suggestions.requested else {
+            return;
+        };
+        if requested_id != request_id {
+            tracing::debug!(
+                "Ignoring inline suggestion for outdated request {}",
+                request_id
+            );
+            return;
+        }
+        let Some(text) = text.filter(|text| !text.is_empty()) else {
+            return;
+        };
+        if self.inline_suggestions.shown.is_some()
+            || self.inline_suggestion_anchor() != Some(anchor)
+        {
+            return;
+        }
+        self.show_inline_suggestion(anchor, text);
+    }
+
+    /// Show `text` as ghost text at the anchor
+    fn show_inline_suggestion(&mut self, anchor: SuggestionAnchor, text: String) {
+        let style = Style::default()
+            .fg(self.theme.line_number_fg)
+            .add_modifier(Modifier::ITALIC);
+        let tab = " ".repeat(self.config.editor.tab_size);
+        let Some(state) = self.buffers.get_mut(&anchor.buffer_id) else {
+            return;
+        };
+
+        let mut virtual_texts = Vec::new();
+        for (i, line) in text.split('\n').enumerate() {
+            let line = line.trim_end_matches('\r').replace('\t', &tab);
+            let placement = if i == 0 {
+                if line.is_empty() {
+                    continue;
+                }
+                VirtualTextPosition::Ghost
+            } else {
+                VirtualTextPosition::LineBelow
+            };
+            virtual_texts.push(state.virtual_texts.add(
+                &mut state.marker_list,
+                anchor.position,
+                line,
+                style,
+                placement,
+                i as i32,
+            ));
+        }
+
+        self.inline_suggestions.shown = Some(ShownSuggestion {
+            anchor,
+            text,
+            virtual_texts,
+        });
+    }
+
+    /// Hide the suggestion being shown, if any
+    pub(super) fn dismiss_inline_suggestion(&mut self) {
+        let Some(shown) = self.inline_suggestions.shown.take() else {
+            return;
+        };
+        if let Some(state) = self.buffers.get_mut(&shown.anchor.buffer_id) {
+            for id in shown.virtual_texts {
+                state.virtual_texts.remove(&mut state.marker_list, id);
+            }
+        }
+    }
+
+    /// Insert the first `len` bytes of the suggestion being shown and keep
+    /// showing the rest
+    fn accept_inline_suggestion(&mut self, len: usize) {
+        let Some(text) = self
+            .inline_suggestions
+            .shown
+            .as_ref()
+            .map(|shown| shown.text.clone())
+        else {
+            return;
+        };
+        self.dismiss_inline_suggestion();
+        let (accepted, rest) = text.split_at(len.min(text.len()));
+        if accepted.is_empty() {
+            return;
+        }
+
+        let state = self.active_state();
+        let cursor_id = state.cursors.primary_id();
+        let position = state.cursors.primary().position;
+        let event = Event::Insert {
+            position,
+            text: accepted.to_string(),
+            cursor_id,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+
+        if rest.is_empty() {
+            return;
+        }
+        if let Some(anchor) = self.inline_suggestion_anchor() {
+            // The rest is still the suggestion here; don't ask again
+            self.inline_suggestions.resting = Some((anchor, self.time_source.now()));
+            if let Some((_, requested)) = self.inline_suggestions.requested.as_mut() {
+                *requested = anchor;
+            }
+            self.show_inline_suggestion(anchor, rest.to_string());
+        }
+    }
+
+    /// Handle an action while a suggestion is shown
+    ///
+    /// Returns true if the action was consumed by the suggestion.
+    pub(super) fn handle_inline_suggestion_action(&mut self, action: &Action) -> bool {
+        let Some(shown_anchor) = self.inline_suggestions.shown.as_ref().map(|s| s.anchor) else {
+            return false;
+        };
+        // A prompt or popup opened since the last poll gets the key
+        if self.inline_suggestion_anchor() != Some(shown_anchor) {
+            self.dismiss_inline_suggestion();
+            return false;
+        }
+        let Some(text) = self
+            .inline_suggestions
+            .shown
+            .as_ref()
+            .map(|shown| shown.text.as_str())
+        else {
+            return false;
+        };
+        let len = match action {
+            Action::InsertTab => text.len(),
+            Action::MoveWordEnd | Action::MoveWordRight => next_word_len(text),
+            Action::InsertChar(c) if text.starts_with(*c) && !self.overwrite_mode => c.len_utf8(),
+            Action::None => return false,
+            _ => {
+                self.dismiss_inline_suggestion();
+                return false;
+            }
+        };
+        self.accept_inline_suggestion(len);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_next_word_len() {
+        assert_eq!(next_word_len("foo(bar)"), 3);
+        assert_eq!(next_word_len("  bar_baz + 1"), 9);
+        assert_eq!(next_word_len("(bar)"), 1);
+        assert_eq!(next_word_len("), x"), 2);
+        assert_eq!(next_word_len("\n    x"), 1);
+        assert_eq!(next_word_len("\r\nx"), 2);
+        assert_eq!(next_word_len("  "), 2);
+        assert_eq!(next_word_len(""), 0);
+    }
+}
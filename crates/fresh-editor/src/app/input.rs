@@ -1,4 +1,5 @@
 use super::*;
+use crate::input::actions::overwrite_char_events;
 use crate::services::plugins::hooks::HookArgs;
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
@@ -57,6 +58,14 @@ impl Editor {
         // Determine the current context first
         let mut context = self.get_key_context();
 
+        // Pinning a popup must be checked before transient popups are dismissed below
+        if matches!(context, crate::input::keybindings::KeyContext::Popup)
+            && self.keybindings.resolve(&key_event, context) == Action::PinPopup
+        {
+            self.pin_popup();
+            return Ok(());
+        }
+
         // Special case: Hover and Signature Help popups should be dismissed on any key press
         // EXCEPT for Ctrl+C when the popup has a text selection (allow copy first)
         if matches!(context, crate::input::keybindings::KeyContext::Popup) {
@@ -225,6 +234,22 @@ impl Editor {
 
         // Record action to macro if recording
         self.record_macro_action(&action);
+        crate::services::crash_report::record_action(&action);
+
+        // Tab and word motions accept a shown inline suggestion
+        if self.handle_inline_suggestion_action(&action) {
+            return Ok(());
+        }
+
+        // While a snippet is being filled in, Tab moves to its next tabstop
+        if matches!(action, Action::InsertTab) && self.next_snippet_tabstop() {
+            return Ok(());
+        }
+
+        // Any other action ends a Ctrl+Tab walk through the tabs
+        if !matches!(action, Action::NextTabMru | Action::PrevTabMru) {
+            self.tab_mru_cycle = None;
+        }
 
         match action {
             Action::Quit => self.quit(),
@@ -234,48 +259,22 @@ impl Editor {
             Action::Save => {
                 // Check if buffer has a file path - if not, redirect to SaveAs
                 if self.active_state().buffer.file_path().is_none() {
-                    self.start_prompt_with_initial_text(
-                        t!("file.save_as_prompt").to_string(),
-                        PromptType::SaveFileAs,
-                        String::new(),
-                    );
-                    self.init_file_open_state();
+                    self.show_save_as_dialog();
                 } else if self.check_save_conflict().is_some() {
                     // Check if file was modified externally since we opened/saved it
                     self.start_prompt(
                         t!("file.file_changed_prompt").to_string(),
                         PromptType::ConfirmSaveConflict,
                     );
+                } else if let Some(prompt) = self.invalid_utf8_save_prompt() {
+                    // Saving would replace bytes that aren't valid UTF-8
+                    self.start_prompt(prompt, PromptType::ConfirmSaveInvalidUtf8);
                 } else {
                     self.save()?;
                 }
             }
-            Action::SaveAs => {
-                // Get current filename as default suggestion
-                let current_path = self
-                    .active_state()
-                    .buffer
-                    .file_path()
-                    .map(|p| {
-                        // Make path relative to working_dir if possible
-                        p.strip_prefix(&self.working_dir)
-                            .unwrap_or(p)
-                            .to_string_lossy()
-                            .to_string()
-                    })
-                    .unwrap_or_default();
-                self.start_prompt_with_initial_text(
-                    t!("file.save_as_prompt").to_string(),
-                    PromptType::SaveFileAs,
-                    current_path,
-                );
-                self.init_file_open_state();
-            }
-            Action::Open => {
-                self.start_prompt(t!("file.open_prompt").to_string(), PromptType::OpenFile);
-                self.prefill_open_file_prompt();
-                self.init_file_open_state();
-            }
+            Action::SaveAs => self.show_save_as_dialog(),
+            Action::Open => self.show_open_file_dialog(),
             Action::SwitchProject => {
                 self.start_prompt(
                     t!("file.switch_project_prompt").to_string(),
@@ -283,6 +282,11 @@ impl Editor {
                 );
                 self.init_folder_open_state();
             }
+            Action::OpenRecentWorkspace => self.start_open_recent_workspace_prompt(false),
+            Action::OpenRecentWorkspaceInNewProcess => {
+                self.start_open_recent_workspace_prompt(true)
+            }
+            Action::TogglePinWorkspace => self.toggle_pin_workspace(),
             Action::GotoLine => self.start_prompt(
                 t!("file.goto_line_prompt").to_string(),
                 PromptType::GotoLine,
@@ -320,6 +324,9 @@ impl Editor {
                     }
                 }
             }
+            Action::ReopenWithEncoding => self.start_reopen_with_encoding_prompt(),
+            Action::ToggleHexView => self.toggle_hex_view(),
+            Action::ToggleAnsiColors => self.toggle_ansi_colors(),
             Action::ToggleAutoRevert => {
                 self.toggle_auto_revert();
             }
@@ -378,20 +385,46 @@ impl Editor {
                 self.copy_selection()
             }
             Action::CopyWithTheme(theme) => self.copy_selection_with_theme(&theme),
+            Action::CopyAsMarkdown => self.copy_selection_as_markdown(),
+            Action::CopyWithReference => self.copy_selection_with_reference(),
+            Action::CopyPermalink => self.copy_selection_permalink(),
+            Action::CopyRelativePath => {
+                self.copy_buffer_path(self.active_buffer(), PathCopy::Relative)
+            }
+            Action::CopyAbsolutePath => {
+                self.copy_buffer_path(self.active_buffer(), PathCopy::Absolute)
+            }
+            Action::CopyFileName => self.copy_buffer_path(self.active_buffer(), PathCopy::FileName),
             Action::Cut => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 self.cut_selection()
             }
             Action::Paste => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 self.paste()
             }
+            Action::PasteFromClipboardHistory => {
+                if self.is_editing_disabled() {
+                    self.report_editing_disabled();
+                    return Ok(());
+                }
+                self.start_paste_from_clipboard_history_prompt()
+            }
+            Action::ClipboardHistoryTogglePin => self.toggle_clipboard_history_pin(),
+            Action::ForgetClipboardHistory => {
+                self.clipboard.clear_history();
+                self.set_status_message(t!("clipboard.history_cleared").to_string());
+            }
+            Action::InsertDate => self.insert_current_time(&crate::i18n::date_format()),
+            Action::InsertTime => self.insert_current_time(&crate::i18n::time_format()),
+            Action::InsertUnicodeCharacter => self.start_insert_unicode_prompt(),
+            Action::InsertDigraph => self.start_digraph_prompt(),
             Action::YankWordForward => self.yank_word_forward(),
             Action::YankWordBackward => self.yank_word_backward(),
             Action::YankToLineEnd => self.yank_to_line_end(),
@@ -402,12 +435,21 @@ impl Editor {
             Action::Redo => {
                 self.handle_redo();
             }
+            Action::ShowUndoTree => {
+                self.show_undo_tree();
+            }
+            Action::UndoTreeSwitchBranch => {
+                self.undo_tree_switch_branch();
+            }
             Action::ShowHelp => {
                 self.open_help_manual();
             }
             Action::ShowKeyboardShortcuts => {
                 self.open_keyboard_shortcuts();
             }
+            Action::ToggleCheatSheet => {
+                self.toggle_cheat_sheet();
+            }
             Action::ShowWarnings => {
                 self.show_warnings_popup();
             }
@@ -417,6 +459,18 @@ impl Editor {
             Action::ShowLspStatus => {
                 self.show_lsp_status_popup();
             }
+            Action::ShowMemoryUsage => {
+                self.show_memory_usage();
+            }
+            Action::ShowPaths => {
+                self.show_paths();
+            }
+            Action::ShowRemoteAuditLog => {
+                self.show_remote_audit_log();
+            }
+            Action::ExportRemoteAuditLog => {
+                self.export_remote_audit_log();
+            }
             Action::ClearWarnings => {
                 self.clear_warnings();
             }
@@ -540,6 +594,9 @@ impl Editor {
             Action::LspRestart => {
                 self.handle_lsp_restart();
             }
+            Action::LspDetectServers => {
+                self.handle_lsp_detect_servers();
+            }
             Action::LspStop => {
                 self.handle_lsp_stop();
             }
@@ -549,9 +606,16 @@ impl Editor {
             Action::DumpConfig => {
                 self.dump_config();
             }
+            Action::SafeModeReenable => self.start_safe_mode_reenable_prompt(),
+            Action::ReloadPlugin => self.start_reload_plugin_prompt(),
             Action::SelectTheme => {
                 self.start_select_theme_prompt();
             }
+            Action::SelectSplitTheme => self.start_select_split_theme_prompt(),
+            Action::ImportBase16Theme => self.start_prompt(
+                t!("view.import_base16_prompt").to_string(),
+                PromptType::ImportBase16Theme,
+            ),
             Action::SelectKeybindingMap => {
                 self.start_select_keybinding_map_prompt();
             }
@@ -599,6 +663,9 @@ impl Editor {
                     false,
                 );
             }
+            Action::SearchInProject => self.start_project_search_prompt(),
+            Action::ProjectSearchOpenResult => self.open_project_search_result(),
+            Action::ProjectSearchCancel => self.cancel_project_search(),
             Action::FindInSelection => {
                 self.start_search_prompt(
                     t!("file.search_prompt").to_string(),
@@ -624,6 +691,9 @@ impl Editor {
             Action::NextBuffer => self.next_buffer(),
             Action::PrevBuffer => self.prev_buffer(),
             Action::SwitchToPreviousTab => self.switch_to_previous_tab(),
+            Action::NextTabMru => self.cycle_tab_mru(true),
+            Action::PrevTabMru => self.cycle_tab_mru(false),
+            Action::MoveTabToSplit => self.start_move_tab_to_split_prompt(),
             Action::SwitchToTabByName => self.start_switch_to_tab_prompt(),
 
             // Tab scrolling (manual scroll - don't auto-adjust)
@@ -651,9 +721,15 @@ impl Editor {
             Action::IncreaseSplitSize => self.adjust_split_size(0.05),
             Action::DecreaseSplitSize => self.adjust_split_size(-0.05),
             Action::ToggleMaximizeSplit => self.toggle_maximize_split(),
+            Action::ToggleZoomSplit => self.toggle_zoom_split(),
+            Action::ToggleScrollSync => self.toggle_scroll_sync(),
+            Action::SyncScrollAllSplits => self.sync_scroll_all_splits(),
+            Action::StopScrollSync => self.stop_scroll_sync(),
             Action::ToggleFileExplorer => self.toggle_file_explorer(),
             Action::ToggleMenuBar => self.toggle_menu_bar(),
             Action::ToggleTabBar => self.toggle_tab_bar(),
+            Action::ToggleAbsolutePaths => self.toggle_absolute_paths(),
+            Action::ToggleOverwriteMode => self.toggle_overwrite_mode(),
             Action::ToggleLineNumbers => self.toggle_line_numbers(),
             Action::ToggleMouseCapture => self.toggle_mouse_capture(),
             Action::ToggleMouseHover => self.toggle_mouse_hover(),
@@ -717,33 +793,16 @@ impl Editor {
             Action::FileExplorerToggleHidden => self.file_explorer_toggle_hidden(),
             Action::FileExplorerToggleGitignored => self.file_explorer_toggle_gitignored(),
             Action::RemoveSecondaryCursors => {
-                // Convert action to events and apply them
-                if let Some(events) = self.action_to_events(Action::RemoveSecondaryCursors) {
-                    // Wrap in batch for atomic undo
-                    let batch = Event::Batch {
-                        events: events.clone(),
-                        description: "Remove secondary cursors".to_string(),
-                    };
-                    self.active_event_log_mut().append(batch.clone());
-                    self.apply_event_to_active_buffer(&batch);
-
-                    // Ensure the primary cursor is visible after removing secondary cursors
-                    let active_split = self.split_manager.active_split();
-                    let active_buffer = self.active_buffer();
-                    if let Some(view_state) = self.split_view_states.get_mut(&active_split) {
-                        let state = self.buffers.get_mut(&active_buffer).unwrap();
-                        let primary = *state.cursors.primary();
-                        view_state
-                            .viewport
-                            .ensure_visible(&mut state.buffer, &primary);
-                    }
-                }
+                self.remove_secondary_cursors();
             }
 
             // Menu navigation actions
             Action::MenuActivate => {
                 self.handle_menu_activate();
             }
+            Action::ShowContextMenu => {
+                self.show_context_menu_at_cursor();
+            }
             Action::MenuClose => {
                 self.handle_menu_close();
             }
@@ -798,6 +857,21 @@ impl Editor {
             Action::ToggleComment => {
                 self.toggle_comment();
             }
+            Action::ReflowParagraph => {
+                self.reflow_paragraph();
+            }
+            Action::ReflowSelection => {
+                self.reflow_selection();
+            }
+            Action::EvaluateSelection => {
+                self.evaluate_selection(false);
+            }
+            Action::EvaluateAndReplaceSelection => {
+                self.evaluate_selection(true);
+            }
+            Action::GenerateDocComment => {
+                self.generate_doc_comment();
+            }
             Action::GoToMatchingBracket => {
                 self.goto_matching_bracket();
             }
@@ -955,7 +1029,7 @@ impl Editor {
             Action::None => {}
             Action::DeleteBackward => {
                 if self.is_editing_disabled() {
-                    self.set_status_message(t!("buffer.editing_disabled").to_string());
+                    self.report_editing_disabled();
                     return Ok(());
                 }
                 // Normal backspace handling
@@ -977,6 +1051,11 @@ impl Editor {
             }
             Action::PluginAction(action_name) => {
                 tracing::debug!("handle_action: PluginAction('{}')", action_name);
+                // Load any deferred plugin waiting for this command before running it
+                self.plugin_manager
+                    .activate(fresh_core::activation::ActivationEvent::Command(
+                        action_name.clone(),
+                    ));
                 // Execute the plugin callback via TypeScript plugin thread
                 // Use non-blocking version to avoid deadlock with async plugin ops
                 #[cfg(feature = "plugins")]
@@ -1157,6 +1236,15 @@ impl Editor {
             Action::PopupCancel => {
                 self.handle_popup_cancel();
             }
+            Action::PinPopup => {
+                self.pin_popup();
+            }
+            Action::FocusFloatingWindow(id) => {
+                self.focus_floating_window(id);
+            }
+            Action::CloseFloatingWindow => {
+                self.close_floating_window();
+            }
             Action::InsertChar(c) => {
                 if self.is_prompting() {
                     return self.handle_insert_char_prompt(c);
@@ -1166,7 +1254,7 @@ impl Editor {
             }
             // Prompt clipboard actions
             Action::PromptCopy => {
-                if let Some(prompt) = &self.prompt {
+                if let Some(prompt) = self.prompt.as_ref().filter(|p| !p.masked) {
                     let text = prompt.selected_text().unwrap_or_else(|| prompt.get_text());
                     if !text.is_empty() {
                         self.clipboard.copy(text);
@@ -1175,7 +1263,7 @@ impl Editor {
                 }
             }
             Action::PromptCut => {
-                if let Some(prompt) = &self.prompt {
+                if let Some(prompt) = self.prompt.as_ref().filter(|p| !p.masked) {
                     let text = prompt.selected_text().unwrap_or_else(|| prompt.get_text());
                     if !text.is_empty() {
                         self.clipboard.copy(text);
@@ -1819,19 +1907,29 @@ impl Editor {
             .map(|vs| vs.viewport.top_byte)
             .unwrap_or(0);
 
+        // A click on a line pinned by sticky scroll jumps to that line
+        let sticky_header = self
+            .cached_layout
+            .sticky_scroll_headers
+            .get(&split_id)
+            .and_then(|headers| headers.get(row.saturating_sub(content_rect.y) as usize))
+            .copied();
+
         // Calculate clicked position in buffer
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
             let gutter_width = state.margins.left_total_width() as u16;
 
-            let Some(target_position) = Self::screen_to_buffer_position(
-                col,
-                row,
-                content_rect,
-                gutter_width,
-                &cached_mappings,
-                fallback,
-                true, // Allow gutter clicks - position cursor at start of line
-            ) else {
+            let Some(target_position) = sticky_header.or_else(|| {
+                Self::screen_to_buffer_position(
+                    col,
+                    row,
+                    content_rect,
+                    gutter_width,
+                    &cached_mappings,
+                    fallback,
+                    true, // Allow gutter clicks - position cursor at start of line
+                )
+            }) else {
                 return Ok(());
             };
 
@@ -1901,6 +1999,14 @@ impl Editor {
                     .record_movement(buffer_id, target_position, None);
             }
 
+            if sticky_header.is_some() {
+                // Scroll back to the header even after scrolling with the mouse
+                if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                    view_state.viewport.clear_skip_ensure_visible();
+                }
+                return Ok(());
+            }
+
             // Set up drag selection state for potential text selection
             self.mouse_state.dragging_text_selection = true;
             self.mouse_state.drag_selection_split = Some(split_id);
@@ -2105,8 +2211,8 @@ impl Editor {
             .map(|info| {
                 let is_current = info.name == *current_theme_name;
                 let description = match (is_current, info.pack.is_empty()) {
-                    (true, true) => Some("(current)".to_string()),
-                    (true, false) => Some(format!("{} (current)", info.pack)),
+                    (true, true) => Some(t!("prompt.current").to_string()),
+                    (true, false) => Some(format!("{} {}", info.pack, t!("prompt.current"))),
                     (false, true) => None,
                     (false, false) => Some(info.pack.clone()),
                 };
@@ -2158,7 +2264,7 @@ impl Editor {
                     t!("view.theme_changed", theme = self.theme.name.clone()).to_string(),
                 );
             } else {
-                self.set_status_message(format!("Theme '{}' not found", theme_name));
+                self.set_status_message(t!("view.theme_not_found", theme = theme_name).to_string());
             }
         }
     }
@@ -2225,7 +2331,7 @@ impl Editor {
                 crate::input::commands::Suggestion {
                     text: map_name.to_string(),
                     description: if is_current {
-                        Some("(current)".to_string())
+                        Some(t!("prompt.current").to_string())
                     } else {
                         None
                     },
@@ -2309,7 +2415,7 @@ impl Editor {
                 crate::input::commands::Suggestion {
                     text: description.to_string(),
                     description: if is_current {
-                        Some("(current)".to_string())
+                        Some(t!("prompt.current").to_string())
                     } else {
                         None
                     },
@@ -2347,13 +2453,10 @@ impl Editor {
         use crate::config::CursorStyle;
 
         if let Some(style) = CursorStyle::parse(style_name) {
-            // Update the config in memory
+            // Update the config in memory; the frontend applies the new
+            // shape to the terminal after the next frame
             self.config.editor.cursor_style = style;
 
-            // Apply the cursor style to the terminal
-            use std::io::stdout;
-            let _ = crossterm::execute!(stdout(), style.to_crossterm_style());
-
             // Persist to config file
             self.save_cursor_style_to_config();
 
@@ -2407,14 +2510,19 @@ impl Editor {
                     if english_name == native_name {
                         // Same name (e.g., English/English)
                         if is_current {
-                            format!("{} (current)", english_name)
+                            format!("{} {}", english_name, t!("prompt.current"))
                         } else {
                             english_name.to_string()
                         }
                     } else {
                         // Different names (e.g., German/Deutsch)
                         if is_current {
-                            format!("{} / {} (current)", english_name, native_name)
+                            format!(
+                                "{} / {} {}",
+                                english_name,
+                                native_name,
+                                t!("prompt.current")
+                            )
                         } else {
                             format!("{} / {}", english_name, native_name)
                         }
@@ -2422,7 +2530,7 @@ impl Editor {
                 } else {
                     // Unknown locale
                     if is_current {
-                        "(current)".to_string()
+                        t!("prompt.current").to_string()
                     } else {
                         String::new()
                     }
@@ -2461,19 +2569,11 @@ impl Editor {
     /// Apply a locale and persist it to config
     pub(super) fn apply_locale(&mut self, locale_name: &str) {
         if !locale_name.is_empty() {
-            // Update the locale at runtime
-            crate::i18n::set_locale(locale_name);
-
             // Update the config in memory
             self.config.locale = crate::config::LocaleName(Some(locale_name.to_string()));
 
-            // Regenerate menus with the new locale
-            self.menus = crate::config::MenuConfig::translated();
-
-            // Refresh command palette commands with new locale
-            if let Ok(mut registry) = self.command_registry.write() {
-                registry.refresh_builtin_commands();
-            }
+            // Switch translations and refresh menus/commands
+            self.activate_configured_locale();
 
             // Persist to config file
             self.save_locale_to_config();
@@ -2482,6 +2582,39 @@ impl Editor {
         }
     }
 
+    /// Make `config.locale` the active locale (auto-detecting when unset) and
+    /// rebuild everything that caches translated strings.
+    pub(super) fn activate_configured_locale(&mut self) {
+        match self.config.locale.as_option() {
+            Some(locale) => crate::i18n::set_locale(locale),
+            None => crate::i18n::init(),
+        }
+        let locale = crate::i18n::current_locale();
+        tracing::info!("Locale changed to '{}'", locale);
+
+        // Regenerate menus with the new locale
+        self.menus = crate::config::MenuConfig::translated();
+
+        // Refresh command palette commands with new locale
+        if let Ok(mut registry) = self.command_registry.write() {
+            registry.refresh_builtin_commands();
+        }
+
+        // Let plugins rebuild any text they rendered with the old locale
+        self.plugin_manager
+            .run_hook("locale_changed", HookArgs::LocaleChanged { locale });
+    }
+
+    /// Insert the current local time at each cursor, formatted with a strftime pattern
+    fn insert_current_time(&mut self, pattern: &str) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        let text = chrono::Local::now().format(pattern).to_string();
+        self.paste_text(text);
+    }
+
     /// Save the current locale setting to the user's config file
     fn save_locale_to_config(&mut self) {
         // Create the directory if it doesn't exist
@@ -2555,11 +2688,7 @@ impl Editor {
         let suggestions: Vec<crate::input::commands::Suggestion> = open_buffers
             .iter()
             .map(|&buffer_id| {
-                let display_name = self
-                    .buffer_metadata
-                    .get(&buffer_id)
-                    .map(|m| m.display_name.clone())
-                    .unwrap_or_else(|| format!("Buffer {:?}", buffer_id));
+                let display_name = self.buffer_display_path(buffer_id);
 
                 let is_current = buffer_id == self.active_buffer();
                 let is_modified = self
@@ -2568,9 +2697,9 @@ impl Editor {
                     .is_some_and(|b| b.buffer.is_modified());
 
                 let description = match (is_current, is_modified) {
-                    (true, true) => Some("(current, modified)".to_string()),
-                    (true, false) => Some("(current)".to_string()),
-                    (false, true) => Some("(modified)".to_string()),
+                    (true, true) => Some(t!("prompt.current_modified").to_string()),
+                    (true, false) => Some(t!("prompt.current").to_string()),
+                    (false, true) => Some(t!("prompt.modified").to_string()),
                     (false, false) => None,
                 };
 
@@ -2660,14 +2789,19 @@ impl Editor {
     fn handle_insert_char_editor(&mut self, c: char) -> AnyhowResult<()> {
         // Check if editing is disabled (show_cursors = false)
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return Ok(());
         }
 
         // Cancel any pending LSP requests since the text is changing
         self.cancel_pending_lsp_requests();
 
-        if let Some(events) = self.action_to_events(Action::InsertChar(c)) {
+        let events = if self.overwrite_mode {
+            Some(overwrite_char_events(self.active_state_mut(), c))
+        } else {
+            self.action_to_events(Action::InsertChar(c))
+        };
+        if let Some(events) = events {
             if events.len() > 1 {
                 // Multi-cursor: use optimized bulk edit (O(n) instead of O(n²))
                 let description = format!("Insert '{}'", c);
@@ -2701,6 +2835,12 @@ impl Editor {
     /// (cursor movements, text edits, etc.). It handles batching for multi-cursor,
     /// position history tracking, and editing permission checks.
     fn apply_action_as_events(&mut self, action: Action) -> AnyhowResult<()> {
+        // Word motions stop at camelCase/snake_case boundaries when configured
+        let action = match action.subword_variant() {
+            Some(subword) if self.config.editor.subword_navigation => subword,
+            _ => action,
+        };
+
         // Check if active buffer is a composite buffer - handle scroll/movement specially
         let buffer_id = self.active_buffer();
         if self.is_composite_buffer(buffer_id) {
@@ -2720,13 +2860,19 @@ impl Editor {
                 | Action::DeleteForward
                 | Action::DeleteWordBackward
                 | Action::DeleteWordForward
+                | Action::DeleteSubwordBackward
+                | Action::DeleteSubwordForward
                 | Action::DeleteLine
                 | Action::DedentSelection
                 | Action::ToggleComment
+                | Action::ReflowParagraph
+                | Action::ReflowSelection
+                | Action::EvaluateAndReplaceSelection
+                | Action::GenerateDocComment
         );
 
         if is_editing_action && self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return Ok(());
         }
 
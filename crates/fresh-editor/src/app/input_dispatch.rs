@@ -25,7 +25,9 @@ impl Editor {
             || self.active_state().popups.is_visible()
             || self.menu_state.active_menu.is_some()
             || self.settings_state.as_ref().is_some_and(|s| s.visible)
-            || self.calibration_wizard.is_some();
+            || self.calibration_wizard.is_some()
+            || self.cheat_sheet.is_some()
+            || self.is_floating_window_focused();
 
         if in_modal {
             return None;
@@ -72,15 +74,19 @@ impl Editor {
             return Some(result);
         }
 
+        // Cheat sheet overlay captures keys for its search field
+        if self.cheat_sheet.is_some() {
+            return Some(self.handle_cheat_sheet_input(event));
+        }
+
+        // An open context menu takes every key; keys it doesn't use close it
+        if self.context_menu.is_some() {
+            return Some(self.handle_context_menu_input(event));
+        }
+
         // Menu is next
         if self.menu_state.active_menu.is_some() {
-            let all_menus: Vec<crate::config::Menu> = self
-                .menus
-                .menus
-                .iter()
-                .chain(self.menu_state.plugin_menus.iter())
-                .cloned()
-                .collect();
+            let all_menus: Vec<crate::config::Menu> = self.menu_state.all_menus(&self.menus.menus);
 
             let mut handler = MenuInputHandler::new(&mut self.menu_state, &all_menus);
             let result = handler.dispatch_input(event, &mut ctx);
@@ -153,6 +159,14 @@ impl Editor {
             return Some(result);
         }
 
+        // A focused floating window handles its own keys; other keys return
+        // focus to the editor and are processed normally
+        if self.is_floating_window_focused() {
+            if let Some(result) = self.handle_floating_window_input(event) {
+                return Some(result);
+            }
+        }
+
         None
     }
 
@@ -253,9 +267,22 @@ impl Editor {
                 }
             }
 
+            DeferredAction::ToggleQuickPickItem => {
+                self.toggle_quick_pick_item();
+            }
+
+            DeferredAction::ToggleRenamePreviewEdit => {
+                self.toggle_rename_preview_edit();
+            }
+
             // Popup actions
             DeferredAction::ClosePopup => {
-                self.hide_popup();
+                // A plugin's confirm popup must resolve its promise on close
+                if self.pending_confirm_callback.is_some() {
+                    self.handle_popup_cancel();
+                } else {
+                    self.hide_popup();
+                }
             }
             DeferredAction::ConfirmPopup => {
                 self.handle_action(Action::PopupConfirm)?;
@@ -432,7 +459,7 @@ impl Editor {
     }
 
     /// Convert a menu action string to a keybinding Action.
-    fn menu_action_to_action(
+    pub(super) fn menu_action_to_action(
         &self,
         action_name: &str,
         args: std::collections::HashMap<String, serde_json::Value>,
@@ -5,6 +5,8 @@
 
 use super::Editor;
 use crate::input::commands::Suggestion;
+use crate::services::lsp::detect;
+use crate::types::LspServerConfig;
 use crate::view::prompt::{Prompt, PromptType};
 use rust_i18n::t;
 
@@ -160,4 +162,127 @@ impl Editor {
             }
         }
     }
+
+    /// Handle the LspDetectServers action.
+    ///
+    /// Lists the known language servers with whether each is installed. Enter
+    /// on an installed one enables it (see `enable_detected_lsp_server`); the
+    /// others say how to install them.
+    pub fn handle_lsp_detect_servers(&mut self) {
+        let detected = detect::detect_servers();
+        let suggestions: Vec<Suggestion> = detected
+            .iter()
+            .map(|detected| {
+                let server = detected.server;
+                let description = match &detected.path {
+                    Some(path) if self.is_lsp_server_active(server) => {
+                        t!("lsp.detect_active", path = path.display().to_string())
+                    }
+                    Some(path) => t!("lsp.detect_installed", path = path.display().to_string()),
+                    None => t!("lsp.detect_not_installed", install = server.install),
+                };
+                Suggestion {
+                    text: format!("{}: {}", server.languages.join(", "), server.command),
+                    description: Some(description.to_string()),
+                    value: Some(server.command.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("lsp.detect_prompt").to_string(),
+            PromptType::EnableLspServer,
+            suggestions,
+        ));
+        // Start on the first installed server
+        if let Some(prompt) = self.prompt.as_mut() {
+            if let Some(index) = detected.iter().position(|d| d.path.is_some()) {
+                prompt.selected_suggestion = Some(index);
+            }
+        }
+    }
+
+    /// Whether every language of `server` is set to start it automatically
+    fn is_lsp_server_active(&self, server: &detect::KnownServer) -> bool {
+        server.languages.iter().all(|language| {
+            self.config.lsp.get(*language).is_some_and(|config| {
+                config.enabled && config.auto_start && config.command == server.command
+            })
+        })
+    }
+
+    /// Enable a detected server for its languages with auto-start, save the
+    /// config and start it for open files.
+    ///
+    /// A language already configured with this server keeps its settings;
+    /// otherwise it gets the server's default command line.
+    pub(super) fn enable_detected_lsp_server(&mut self, command: &str) {
+        let Some(server) = detect::known_server(command) else {
+            return;
+        };
+        if detect::find_in_path(server.command).is_none() {
+            self.set_status_message(
+                t!(
+                    "lsp.server_not_installed",
+                    command = server.command,
+                    install = server.install
+                )
+                .to_string(),
+            );
+            return;
+        }
+
+        for language in server.languages {
+            let config = self.config.lsp.entry(language.to_string()).or_default();
+            if config.command != server.command {
+                *config = LspServerConfig {
+                    command: server.command.to_string(),
+                    args: server.args.iter().map(|arg| arg.to_string()).collect(),
+                    ..Default::default()
+                };
+            }
+            config.enabled = true;
+            config.auto_start = true;
+            let config = config.clone();
+
+            let has_buffers = self.buffer_metadata.values().any(|meta| {
+                meta.file_path().is_some_and(|path| {
+                    crate::services::lsp::manager::detect_language(path, &self.config.languages)
+                        .is_some_and(|detected| detected == *language)
+                })
+            });
+            if let Some(lsp) = self.lsp.as_mut() {
+                lsp.set_language_config(language.to_string(), config);
+                // (Re)start with the new command for the files already open
+                let running = lsp.running_servers().iter().any(|l| l == language);
+                if has_buffers || running {
+                    lsp.manual_restart(language);
+                    self.reopen_buffers_for_language(language);
+                }
+            }
+        }
+
+        if let Err(e) = self.save_config() {
+            tracing::warn!("Failed to save config after enabling LSP server: {}", e);
+        } else {
+            let config_path = self.dir_context.config_path();
+            self.emit_event(
+                "config_changed",
+                serde_json::json!({
+                    "path": config_path.to_string_lossy(),
+                }),
+            );
+        }
+        self.set_status_message(
+            t!(
+                "lsp.server_enabled",
+                command = server.command,
+                languages = server.languages.join(", ")
+            )
+            .to_string(),
+        );
+    }
 }
@@ -13,6 +13,7 @@
 use anyhow::Result as AnyhowResult;
 use rust_i18n::t;
 use std::io;
+use std::path::PathBuf;
 use std::time::{Duration, Instant};
 
 use lsp_types::TextDocumentContentChangeEvent;
@@ -20,6 +21,7 @@ use lsp_types::TextDocumentContentChangeEvent;
 use crate::model::event::{BufferId, Event};
 use crate::primitives::word_navigation::{find_word_end, find_word_start};
 use crate::services::lsp::manager::detect_language;
+use crate::view::popup::HoverSection;
 use crate::view::prompt::{Prompt, PromptType};
 
 use super::{uri_to_path, Editor, SemanticTokenRangeRequest};
@@ -47,9 +49,44 @@ impl Editor {
         self.pending_completion_request = None;
         self.lsp_status.clear();
 
+        self.show_completion_items(items);
+        Ok(())
+    }
+
+    /// Handle completion items from a plugin completion provider
+    pub(crate) fn handle_plugin_completions(
+        &mut self,
+        request_id: u64,
+        items: Vec<fresh_core::api::CompletionItem>,
+    ) {
+        if self.pending_plugin_completion_request != Some(request_id) {
+            tracing::debug!(
+                "Ignoring plugin completions for outdated request {}",
+                request_id
+            );
+            return;
+        }
+
+        let items = items.into_iter().map(plugin_completion_item).collect();
+        self.show_completion_items(items);
+    }
+
+    /// Show completion items in the completion popup
+    ///
+    /// The language server and plugin providers answer independently, so
+    /// items arriving while the popup is open are added to it.
+    fn show_completion_items(&mut self, items: Vec<lsp_types::CompletionItem>) {
         if items.is_empty() {
             tracing::debug!("No completion items received");
-            return Ok(());
+            return;
+        }
+
+        if self.active_state().popups.is_completion_popup() {
+            if let Some(existing) = self.completion_items.as_mut() {
+                existing.extend(items);
+                self.refilter_completion_popup();
+                return;
+            }
         }
 
         // Get the partial word at cursor to filter completions
@@ -89,7 +126,7 @@ impl Editor {
 
         if filtered_items.is_empty() {
             tracing::debug!("No completion items match prefix '{}'", prefix);
-            return Ok(());
+            return;
         }
 
         // Convert CompletionItem to PopupListItem
@@ -166,8 +203,6 @@ impl Editor {
             "Showing completion popup with {} items",
             self.completion_items.as_ref().map_or(0, |i| i.len())
         );
-
-        Ok(())
     }
 
     /// Handle LSP go-to-definition response
@@ -201,15 +236,8 @@ impl Editor {
             let buffer_id = self.open_file(&path)?;
 
             // Check if file is outside project root (library file)
-            let is_library_file = self.is_library_file(&path);
-            if is_library_file {
-                // Mark as read-only
-                if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                    state.editing_disabled = true;
-                }
-                if let Some(metadata) = self.buffer_metadata.get_mut(&buffer_id) {
-                    metadata.read_only = true;
-                }
+            if self.config.editor.protect_library_files && self.is_library_file(&path) {
+                self.protect_library_buffer(buffer_id);
             }
 
             // Move cursor to the definition position
@@ -411,9 +439,12 @@ impl Editor {
                 result.is_ok()
             })
             .unwrap_or(false);
+        let sent_to_plugins = self.request_plugin_completions(request_id);
 
-        if sent {
+        if sent || sent_to_plugins {
             self.next_lsp_request_id += 1;
+        }
+        if sent {
             self.pending_completion_request = Some(request_id);
             self.lsp_status = "LSP: completion...".to_string();
         }
@@ -421,6 +452,45 @@ impl Editor {
         Ok(())
     }
 
+    /// Ask the plugin completion providers for the active buffer's language
+    /// for items, answered under `request_id`. Returns whether any were asked.
+    fn request_plugin_completions(&mut self, request_id: u64) -> bool {
+        use crate::primitives::word_navigation::find_completion_word_start;
+
+        let language = self.active_state().language.clone();
+        let providers: Vec<_> = self
+            .completion_providers
+            .iter()
+            .filter(|provider| provider.handles_language(&language))
+            .cloned()
+            .collect();
+        if providers.is_empty() {
+            return false;
+        }
+
+        let buffer_id = self.active_buffer();
+        let state = self.active_state_mut();
+        let position = state.cursors.primary().position;
+        let word_start = find_completion_word_start(&state.buffer, position);
+        let request = fresh_core::api::CompletionRequest {
+            buffer_id: buffer_id.0,
+            path: state
+                .buffer
+                .file_path()
+                .map(|path| path.to_string_lossy().into_owned()),
+            language,
+            position,
+            prefix: state.get_text_range(word_start, position),
+        };
+
+        for provider in &providers {
+            self.plugin_manager
+                .request_completions(provider, request_id, request.clone());
+        }
+        self.pending_plugin_completion_request = Some(request_id);
+        true
+    }
+
     /// Check if the inserted character should trigger completion
     /// and if so, request completion automatically (possibly after a delay).
     ///
@@ -447,7 +517,11 @@ impl Editor {
             .lsp
             .as_ref()
             .map(|lsp| lsp.is_completion_trigger_char(c, &language))
-            .unwrap_or(false);
+            .unwrap_or(false)
+            || self.completion_providers.iter().any(|provider| {
+                provider.handles_language(&language)
+                    && provider.trigger_characters.contains(&c.to_string())
+            });
 
         // Check if quick suggestions is enabled and this is a word character
         let quick_suggestions_enabled = self.config.editor.quick_suggestions;
@@ -470,7 +544,7 @@ impl Editor {
         // Case 2: Word character with quick suggestions - schedule delayed trigger
         if quick_suggestions_enabled && is_word_char {
             let delay_ms = self.config.editor.quick_suggestions_delay_ms;
-            let trigger_time = Instant::now() + Duration::from_millis(delay_ms);
+            let trigger_time = self.time_source.now() + Duration::from_millis(delay_ms);
 
             tracing::debug!(
                 "Scheduling completion trigger in {}ms for language {} (char '{}')",
@@ -560,9 +634,12 @@ impl Editor {
                 result.is_ok()
             })
             .unwrap_or(false);
+        let sent_to_plugins = self.request_plugin_hover(request_id, cursor_pos);
 
-        if sent {
+        if sent || sent_to_plugins {
             self.next_lsp_request_id += 1;
+        }
+        if sent {
             self.pending_hover_request = Some(request_id);
             self.lsp_status = "LSP: hover...".to_string();
         }
@@ -609,9 +686,12 @@ impl Editor {
                 result.is_ok()
             })
             .unwrap_or(false);
+        let sent_to_plugins = self.request_plugin_hover(request_id, byte_pos);
 
-        if sent {
+        if sent || sent_to_plugins {
             self.next_lsp_request_id += 1;
+        }
+        if sent {
             self.pending_hover_request = Some(request_id);
             self.lsp_status = "LSP: hover...".to_string();
         }
@@ -619,6 +699,39 @@ impl Editor {
         Ok(())
     }
 
+    /// Ask the plugin hover providers for the active buffer's language for
+    /// markdown at `position`, answered under `request_id`. Returns whether
+    /// any were asked.
+    fn request_plugin_hover(&mut self, request_id: u64, position: usize) -> bool {
+        let language = self.active_state().language.clone();
+        let providers: Vec<_> = self
+            .hover_providers
+            .iter()
+            .filter(|provider| provider.handles_language(&language))
+            .cloned()
+            .collect();
+        if providers.is_empty() {
+            return false;
+        }
+
+        let request = fresh_core::api::HoverRequest {
+            buffer_id: self.active_buffer().0,
+            path: self
+                .active_state()
+                .buffer
+                .file_path()
+                .map(|path| path.to_string_lossy().into_owned()),
+            language,
+            position,
+        };
+        for provider in &providers {
+            self.plugin_manager
+                .request_hover(provider, request_id, request.clone());
+        }
+        self.pending_plugin_hover_request = Some(request_id);
+        true
+    }
+
     /// Handle hover response from LSP
     pub(crate) fn handle_hover_response(
         &mut self,
@@ -637,7 +750,10 @@ impl Editor {
         self.lsp_status.clear();
 
         if contents.is_empty() {
-            self.set_status_message(t!("lsp.no_hover").to_string());
+            // Plugin providers may still have something to show
+            if self.pending_plugin_hover_request != Some(request_id) {
+                self.set_status_message(t!("lsp.no_hover").to_string());
+            }
             self.hover_symbol_range = None;
             return;
         }
@@ -694,65 +810,106 @@ impl Editor {
         } else {
             // No range provided by LSP - compute word boundaries at hover position
             // This prevents the popup from following the mouse within the same word
-            if let Some((hover_byte_pos, _, _, _)) = self.mouse_state.lsp_hover_state {
-                let state = self.active_state();
-                let start_byte = find_word_start(&state.buffer, hover_byte_pos);
-                let end_byte = find_word_end(&state.buffer, hover_byte_pos);
-                if start_byte < end_byte {
-                    self.hover_symbol_range = Some((start_byte, end_byte));
-                    tracing::debug!(
-                        "Hover symbol range (computed from word boundaries): {}..{}",
-                        start_byte,
-                        end_byte
-                    );
-                } else {
-                    self.hover_symbol_range = None;
-                }
-            } else {
-                self.hover_symbol_range = None;
-            }
+            self.hover_symbol_range = self.mouse_hover_word_range();
         }
 
-        // Create a popup with the hover contents
+        let section = if is_markdown {
+            HoverSection::markdown(contents)
+        } else {
+            HoverSection::plain(contents)
+        };
+        self.show_hover_section(request_id, section);
+        tracing::info!("Showing hover popup (markdown={})", is_markdown);
+
+        // Mark hover request as sent to prevent duplicate popups during race conditions
+        // (e.g., when mouse moves while a hover response is pending)
+        self.mouse_state.lsp_hover_request_sent = true;
+    }
+
+    /// Handle markdown from a plugin hover provider
+    pub(crate) fn handle_plugin_hover(&mut self, request_id: u64, markdown: Option<String>) {
+        if self.pending_plugin_hover_request != Some(request_id) {
+            tracing::debug!("Ignoring plugin hover for outdated request {}", request_id);
+            return;
+        }
+        let Some(markdown) = markdown.filter(|markdown| !markdown.trim().is_empty()) else {
+            return;
+        };
+
+        if self.hover_symbol_range.is_none() {
+            self.hover_symbol_range = self.mouse_hover_word_range();
+        }
+        self.show_hover_section(request_id, HoverSection::markdown(markdown));
+        self.mouse_state.lsp_hover_request_sent = true;
+    }
+
+    /// Word under the mouse hover position, used as the hovered symbol when
+    /// the hover source gives no range
+    fn mouse_hover_word_range(&self) -> Option<(usize, usize)> {
+        let (hover_byte_pos, _, _, _) = self.mouse_state.lsp_hover_state?;
+        let state = self.active_state();
+        let start_byte = find_word_start(&state.buffer, hover_byte_pos);
+        let end_byte = find_word_end(&state.buffer, hover_byte_pos);
+        if start_byte < end_byte {
+            tracing::debug!(
+                "Hover symbol range (computed from word boundaries): {}..{}",
+                start_byte,
+                end_byte
+            );
+            Some((start_byte, end_byte))
+        } else {
+            None
+        }
+    }
+
+    /// Show a section of hover documentation
+    ///
+    /// The language server and plugin providers answer the same hover request
+    /// independently, so sections for the request whose popup is open are
+    /// added to it instead of replacing it.
+    fn show_hover_section(&mut self, request_id: u64, section: HoverSection) {
         use crate::view::popup::{Popup, PopupPosition};
-        use ratatui::style::Style;
 
-        // Use markdown rendering if the content is markdown
-        let mut popup = if is_markdown {
-            Popup::markdown(&contents, &self.theme, Some(&self.grammar_registry))
+        let merging = self.hover_popup_request == Some(request_id)
+            && self.active_state().popups.is_hover_popup();
+        let position = if merging {
+            self.hover_sections.push(section);
+            self.active_state()
+                .popups
+                .top()
+                .map(|popup| popup.position)
+                .unwrap_or(PopupPosition::BelowCursor)
         } else {
-            // Plain text - split by lines
-            let lines: Vec<String> = contents.lines().map(|s| s.to_string()).collect();
-            Popup::text(lines, &self.theme)
+            self.hover_sections = vec![section];
+            self.hover_popup_request = Some(request_id);
+            // Use mouse position if this was a mouse-triggered hover, otherwise use cursor position
+            if let Some((x, y)) = self.mouse_hover_screen_position.take() {
+                // Position below the mouse, offset by 1 row
+                PopupPosition::Fixed { x, y: y + 1 }
+            } else {
+                PopupPosition::BelowCursor
+            }
         };
 
-        // Configure popup properties
+        let mut popup = Popup::hover(
+            &self.hover_sections,
+            &self.theme,
+            Some(&self.grammar_registry),
+        );
         popup.title = Some(t!("lsp.popup_hover").to_string());
-        popup.transient = true;
-        // Use mouse position if this was a mouse-triggered hover, otherwise use cursor position
-        popup.position = if let Some((x, y)) = self.mouse_hover_screen_position.take() {
-            // Position below the mouse, offset by 1 row
-            PopupPosition::Fixed { x, y: y + 1 }
-        } else {
-            PopupPosition::BelowCursor
-        };
+        popup.position = position;
         popup.width = 80;
         // Use dynamic max_height based on terminal size (60% of height, min 15, max 40)
         // This allows hover popups to show more documentation on larger terminals
         let dynamic_height = (self.terminal_height * 60 / 100).clamp(15, 40);
         popup.max_height = dynamic_height;
-        popup.border_style = Style::default().fg(self.theme.popup_border_fg);
-        popup.background_style = Style::default().bg(self.theme.popup_bg);
 
-        // Show the popup
         if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            if merging {
+                state.popups.hide();
+            }
             state.popups.show(popup);
-            tracing::info!("Showing hover popup (markdown={})", is_markdown);
         }
-
-        // Mark hover request as sent to prevent duplicate popups during race conditions
-        // (e.g., when mouse moves while a hover response is pending)
-        self.mouse_state.lsp_hover_request_sent = true;
     }
 
     /// Apply inlay hints to editor state as virtual text
@@ -1112,6 +1269,7 @@ impl Editor {
                     end_line,
                     end_char,
                     diagnostics,
+                    None,
                 );
                 if result.is_ok() {
                     tracing::info!(
@@ -1274,6 +1432,7 @@ impl Editor {
         &mut self,
         buffer_id: BufferId,
         mut edits: Vec<lsp_types::TextEdit>,
+        description: &str,
     ) -> AnyhowResult<usize> {
         if edits.is_empty() {
             return Ok(0);
@@ -1354,12 +1513,12 @@ impl Editor {
             changes += 1;
         }
 
-        // Apply all rename changes using bulk edit for O(n) performance
+        // Apply all changes using bulk edit for O(n) performance
         if !batch_events.is_empty() {
             self.apply_events_to_buffer_as_bulk_edit(
                 buffer_id,
                 batch_events,
-                "LSP Rename".to_string(),
+                description.to_string(),
             )?;
         }
 
@@ -1387,80 +1546,12 @@ impl Editor {
                     })
                 );
 
-                // Apply the workspace edit
-                let mut total_changes = 0;
-
-                // Handle changes (map of URI -> Vec<TextEdit>)
-                if let Some(changes) = workspace_edit.changes {
-                    for (uri, edits) in changes {
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
-                }
-
-                // Handle document_changes (TextDocumentEdit[])
-                // This is what rust-analyzer sends instead of changes
-                if let Some(document_changes) = workspace_edit.document_changes {
-                    use lsp_types::DocumentChanges;
-
-                    let text_edits = match document_changes {
-                        DocumentChanges::Edits(edits) => edits,
-                        DocumentChanges::Operations(ops) => {
-                            // Extract TextDocumentEdit from operations
-                            ops.into_iter()
-                                .filter_map(|op| {
-                                    if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
-                                        Some(edit)
-                                    } else {
-                                        None
-                                    }
-                                })
-                                .collect()
-                        }
-                    };
-
-                    for text_doc_edit in text_edits {
-                        let uri = text_doc_edit.text_document.uri;
-
-                        if let Ok(path) = uri_to_path(&uri) {
-                            let buffer_id = self.open_file(&path)?;
-
-                            // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
-                            let edits: Vec<lsp_types::TextEdit> = text_doc_edit
-                                .edits
-                                .into_iter()
-                                .map(|one_of| match one_of {
-                                    lsp_types::OneOf::Left(text_edit) => text_edit,
-                                    lsp_types::OneOf::Right(annotated) => annotated.text_edit,
-                                })
-                                .collect();
-
-                            // Log the edits for debugging
-                            tracing::info!(
-                                "Applying {} edits from rust-analyzer for {:?}:",
-                                edits.len(),
-                                path
-                            );
-                            for (i, edit) in edits.iter().enumerate() {
-                                tracing::info!(
-                                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
-                                    i,
-                                    edit.range.start.line,
-                                    edit.range.start.character,
-                                    edit.range.end.line,
-                                    edit.range.end.character,
-                                    edit.new_text
-                                );
-                            }
+                let file_edits = Self::rename_edits_by_file(workspace_edit);
 
-                            total_changes += self.apply_lsp_text_edits(buffer_id, edits)?;
-                        }
-                    }
+                // Risky edits are shown for review first; the rest apply directly
+                if !self.start_rename_preview(&file_edits)? {
+                    self.apply_rename_edits(file_edits)?;
                 }
-
-                self.status_message = Some(t!("lsp.renamed", count = total_changes).to_string());
             }
             Err(error) => {
                 // Per LSP spec: ContentModified errors (-32801) should NOT be shown to user
@@ -1482,6 +1573,93 @@ impl Editor {
         Ok(())
     }
 
+    /// Text edits of a rename's WorkspaceEdit, grouped by file
+    fn rename_edits_by_file(
+        workspace_edit: lsp_types::WorkspaceEdit,
+    ) -> Vec<(PathBuf, Vec<lsp_types::TextEdit>)> {
+        let mut files = Vec::new();
+
+        // Handle changes (map of URI -> Vec<TextEdit>)
+        if let Some(changes) = workspace_edit.changes {
+            for (uri, edits) in changes {
+                if let Ok(path) = uri_to_path(&uri) {
+                    files.push((path, edits));
+                }
+            }
+        }
+
+        // Handle document_changes (TextDocumentEdit[])
+        // This is what rust-analyzer sends instead of changes
+        if let Some(document_changes) = workspace_edit.document_changes {
+            use lsp_types::DocumentChanges;
+
+            let text_edits = match document_changes {
+                DocumentChanges::Edits(edits) => edits,
+                DocumentChanges::Operations(ops) => {
+                    // Extract TextDocumentEdit from operations
+                    ops.into_iter()
+                        .filter_map(|op| {
+                            if let lsp_types::DocumentChangeOperation::Edit(edit) = op {
+                                Some(edit)
+                            } else {
+                                None
+                            }
+                        })
+                        .collect()
+                }
+            };
+
+            for text_doc_edit in text_edits {
+                let uri = text_doc_edit.text_document.uri;
+
+                if let Ok(path) = uri_to_path(&uri) {
+                    // Extract TextEdit from OneOf<TextEdit, AnnotatedTextEdit>
+                    let edits: Vec<lsp_types::TextEdit> = text_doc_edit
+                        .edits
+                        .into_iter()
+                        .map(|one_of| match one_of {
+                            lsp_types::OneOf::Left(text_edit) => text_edit,
+                            lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+                        })
+                        .collect();
+                    files.push((path, edits));
+                }
+            }
+        }
+
+        files
+    }
+
+    /// Apply a rename's text edits, opening the files they touch
+    pub(super) fn apply_rename_edits(
+        &mut self,
+        file_edits: Vec<(PathBuf, Vec<lsp_types::TextEdit>)>,
+    ) -> AnyhowResult<()> {
+        let mut total_changes = 0;
+        for (path, edits) in file_edits {
+            let buffer_id = self.open_file(&path)?;
+
+            // Log the edits for debugging
+            tracing::info!("Applying {} rename edits for {:?}:", edits.len(), path);
+            for (i, edit) in edits.iter().enumerate() {
+                tracing::info!(
+                    "  Edit {}: line {}:{}-{}:{} -> {:?}",
+                    i,
+                    edit.range.start.line,
+                    edit.range.start.character,
+                    edit.range.end.line,
+                    edit.range.end.character,
+                    edit.new_text
+                );
+            }
+
+            total_changes += self.apply_lsp_text_edits(buffer_id, edits, "LSP Rename")?;
+        }
+
+        self.status_message = Some(t!("lsp.renamed", count = total_changes).to_string());
+        Ok(())
+    }
+
     /// Apply events to a specific buffer using bulk edit optimization (O(n) vs O(n²))
     ///
     /// This is similar to `apply_events_as_bulk_edit` but works on a specific buffer
@@ -2213,6 +2391,29 @@ impl Editor {
     }
 }
 
+/// Convert a plugin's completion item to the LSP item the completion popup shows
+fn plugin_completion_item(item: fresh_core::api::CompletionItem) -> lsp_types::CompletionItem {
+    use lsp_types::CompletionItemKind;
+
+    let kind = match item.kind.as_deref() {
+        Some("function") => Some(CompletionItemKind::FUNCTION),
+        Some("method") => Some(CompletionItemKind::METHOD),
+        Some("variable") => Some(CompletionItemKind::VARIABLE),
+        Some("class") => Some(CompletionItemKind::CLASS),
+        Some("struct") => Some(CompletionItemKind::STRUCT),
+        Some("constant") => Some(CompletionItemKind::CONSTANT),
+        Some("keyword") => Some(CompletionItemKind::KEYWORD),
+        _ => None,
+    };
+    lsp_types::CompletionItem {
+        label: item.label,
+        kind,
+        detail: item.detail,
+        insert_text: item.insert_text,
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::model::filesystem::StdFileSystem;
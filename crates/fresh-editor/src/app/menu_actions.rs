@@ -11,11 +11,9 @@ use anyhow::Result as AnyhowResult;
 impl Editor {
     /// Get all menus (built-in menus + plugin menus) with DynamicSubmenus expanded.
     fn all_menus(&self) -> Vec<Menu> {
-        self.menus
-            .menus
-            .iter()
-            .chain(self.menu_state.plugin_menus.iter())
-            .cloned()
+        self.menu_state
+            .all_menus(&self.menus.menus)
+            .into_iter()
             .map(|mut menu| {
                 menu.expand_dynamic_items();
                 menu
@@ -258,7 +256,7 @@ impl Editor {
 
                 self.close_menu_with_auto_hide();
 
-                if let Some(action) = Action::from_str(&action_name, &action_args) {
+                if let Some(action) = self.menu_action_to_action(&action_name, action_args) {
                     return Ok(Some(self.handle_action(action)));
                 }
                 Ok(Some(Ok(())))
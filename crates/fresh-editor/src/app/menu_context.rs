@@ -24,6 +24,7 @@ impl Editor {
         let inlay_hints = self.config.editor.enable_inlay_hints;
         let has_selection = self.has_active_selection();
         let menu_bar = self.menu_bar_visible;
+        let has_floating_windows = !self.floating_windows.is_empty();
 
         // File explorer state
         let show_hidden = self.is_file_explorer_showing_hidden();
@@ -49,6 +50,7 @@ impl Editor {
             .set(context_keys::FILE_EXPLORER_SHOW_GITIGNORED, show_gitignored)
             .set(context_keys::HAS_SELECTION, has_selection)
             .set(context_keys::MENU_BAR, menu_bar)
+            .set(context_keys::HAS_FLOATING_WINDOWS, has_floating_windows)
             .set(context_keys::FORMATTER_AVAILABLE, formatter_available);
     }
 
@@ -115,8 +117,17 @@ impl Editor {
             .unwrap_or(false)
     }
 
-    /// Check if a formatter is configured for the current buffer's language.
+    /// Check if a formatter (configured or from a plugin) is available for the
+    /// current buffer's language.
     fn is_formatter_available(&self) -> bool {
+        let language = &self.active_state().language;
+        if self
+            .plugin_formatters
+            .iter()
+            .any(|formatter| &formatter.language == language)
+        {
+            return true;
+        }
         self.buffer_metadata
             .get(&self.active_buffer())
             .and_then(|metadata| {
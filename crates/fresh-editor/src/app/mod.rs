@@ -1,16 +1,28 @@
 mod async_messages;
 mod buffer_management;
+mod cache_budget_actions;
 mod calibration_actions;
 pub mod calibration_wizard;
+pub mod cheat_sheet;
+mod cheat_sheet_actions;
 mod clipboard;
 mod composite_buffer_actions;
+pub mod context_menu;
+mod context_menu_actions;
+mod doc_comment_actions;
+mod encoding_actions;
+mod evaluate_actions;
 pub mod event_debug;
 mod event_debug_actions;
+mod file_browser_actions;
+mod file_dialog_actions;
 mod file_explorer;
 pub mod file_open;
 mod file_open_input;
 mod file_operations;
+mod floating_window_actions;
 mod help;
+mod inline_suggestion_actions;
 mod input;
 mod input_dispatch;
 mod lsp_actions;
@@ -20,13 +32,25 @@ mod menu_context;
 mod mouse_input;
 mod on_save_actions;
 mod plugin_commands;
+mod plugin_permission_actions;
+mod plugin_reload_actions;
 mod popup_actions;
+mod project_search_actions;
 mod prompt_actions;
+mod prompt_filter_actions;
+mod quick_pick_actions;
+mod recent_workspace_actions;
 mod recovery_actions;
+mod reflow_actions;
+mod remote_audit_actions;
+mod rename_preview_actions;
 mod render;
+mod rpc_actions;
+pub mod safe_mode;
 pub mod session;
 mod settings_actions;
 mod shell_command;
+mod snippet_actions;
 mod split_actions;
 mod tab_drag;
 mod terminal;
@@ -35,6 +59,7 @@ mod terminal_mouse;
 mod toggle_actions;
 pub mod types;
 mod undo_actions;
+mod unicode_actions;
 mod view_actions;
 pub mod warning_domains;
 
@@ -76,10 +101,10 @@ pub(crate) fn normalize_path(path: &std::path::Path) -> std::path::PathBuf {
 
 use self::types::{
     Bookmark, CachedLayout, EventLineInfo, InteractiveReplaceState, LspMessageEntry,
-    LspProgressInfo, MacroRecordingState, MouseState, SearchState, TabContextMenu,
-    DEFAULT_BACKGROUND_FILE,
+    LspProgressInfo, MacroRecordingState, MouseState, PathCopy, PluginGutter, PluginMarker,
+    SearchState, TabMruCycle, DEFAULT_BACKGROUND_FILE,
 };
-use crate::config::Config;
+use crate::config::{Config, CursorStyle};
 use crate::config_io::{ConfigLayer, ConfigResolver, DirectoryContext};
 use crate::input::actions::action_to_events as convert_action_to_events;
 use crate::input::buffer_mode::ModeRegistry;
@@ -95,8 +120,10 @@ use crate::model::filesystem::FileSystem;
 use crate::services::async_bridge::{AsyncBridge, AsyncMessage};
 use crate::services::fs::FsManager;
 use crate::services::lsp::manager::{detect_language, LspManager};
+use crate::services::plugins::watcher::{self, PluginChange, PluginWatcher};
 use crate::services::plugins::PluginManager;
 use crate::services::recovery::{RecoveryConfig, RecoveryService};
+use crate::services::suggestion_filter::BACKGROUND_FILTER_THRESHOLD;
 use crate::services::time_source::{RealTimeSource, SharedTimeSource};
 use crate::state::EditorState;
 use crate::types::LspServerConfig;
@@ -122,9 +149,13 @@ use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+/// How much slower background polling runs while the terminal is unfocused
+const UNFOCUSED_POLL_INTERVAL_FACTOR: u32 = 10;
 
 // Re-export BufferId from event module for backward compatibility
+pub use self::safe_mode::{SafeMode, SafeModeComponent};
 pub use self::types::{BufferKind, BufferMetadata, HoverTarget};
 pub use self::warning_domains::{
     GeneralWarningDomain, LspWarningDomain, WarningAction, WarningActionId, WarningDomain,
@@ -238,6 +269,9 @@ pub struct Editor {
     /// Plugin-provided status message (displayed alongside the core status)
     plugin_status_message: Option<String>,
 
+    /// Status bar template segments set by plugins, keyed by segment name
+    plugin_status_segments: HashMap<String, String>,
+
     /// Accumulated plugin errors (for test assertions)
     /// These are collected when plugin error messages are received
     plugin_errors: Vec<String>,
@@ -277,6 +311,10 @@ pub struct Editor {
     /// Used to detect viewport changes that occur between renders (e.g., scroll events)
     previous_viewports: HashMap<SplitId, (usize, u16, u16)>,
 
+    /// Split that had focus at the end of the last render frame, for
+    /// split_focused hook detection
+    previous_active_split: Option<SplitId>,
+
     /// Scroll sync manager for anchor-based synchronized scrolling
     /// Used for side-by-side diff views where two panes need to scroll together
     scroll_sync_manager: ScrollSyncManager,
@@ -297,6 +335,9 @@ pub struct Editor {
     /// Process spawner for plugin command execution (local or remote)
     process_spawner: Arc<dyn crate::services::remote::ProcessSpawner>,
 
+    /// Audit trail of remote writes and spawns (`None` when editing locally)
+    remote_audit_log: Option<crate::services::remote::AuditLog>,
+
     /// Whether file explorer is visible
     file_explorer_visible: bool,
 
@@ -314,6 +355,9 @@ pub struct Editor {
     /// Pending show_gitignored setting to apply when file explorer is initialized (from session restore)
     pending_file_explorer_show_gitignored: Option<bool>,
 
+    /// Path a plugin asked to reveal, applied once the file explorer is loaded and idle
+    pending_file_explorer_reveal: Option<PathBuf>,
+
     /// File explorer decorations by namespace
     file_explorer_decorations: HashMap<String, Vec<crate::view::file_tree::FileExplorerDecoration>>,
 
@@ -369,6 +413,13 @@ pub struct Editor {
     /// Stored when completion popup is shown, used for re-filtering as user types
     completion_items: Option<Vec<lsp_types::CompletionItem>>,
 
+    /// Completion providers registered by plugins
+    completion_providers: Vec<fresh_core::api::CompletionProvider>,
+
+    /// Completion request plugin providers are answering (if any)
+    /// Their items are merged into the completion popup as they arrive
+    pending_plugin_completion_request: Option<u64>,
+
     /// Scheduled completion trigger time (for debounced quick suggestions)
     /// When Some, completion will be triggered when this instant is reached
     scheduled_completion_trigger: Option<Instant>,
@@ -379,6 +430,30 @@ pub struct Editor {
     /// Pending LSP hover request ID (if any)
     pending_hover_request: Option<u64>,
 
+    /// Hover providers registered by plugins
+    hover_providers: Vec<fresh_core::api::HoverProvider>,
+
+    /// Markers created by plugins, by the ID the plugin refers to them by
+    plugin_markers: HashMap<u64, PluginMarker>,
+
+    /// Gutter columns registered by plugins, in registration order
+    plugin_gutters: Vec<PluginGutter>,
+
+    /// Inline suggestion providers registered by plugins
+    inline_suggestion_providers: Vec<fresh_core::api::InlineSuggestionProvider>,
+
+    /// Debouncing and display state of inline suggestions
+    inline_suggestions: inline_suggestion_actions::InlineSuggestionState,
+
+    /// Hover request plugin providers are answering (if any)
+    pending_plugin_hover_request: Option<u64>,
+
+    /// Hover request whose documentation the hover popup shows
+    hover_popup_request: Option<u64>,
+
+    /// Documentation shown in the hover popup, one section per source
+    hover_sections: Vec<crate::view::popup::HoverSection>,
+
     /// Pending LSP find references request ID (if any)
     pending_references_request: Option<u64>,
 
@@ -391,6 +466,24 @@ pub struct Editor {
     /// Pending LSP code actions request ID (if any)
     pending_code_actions_request: Option<u64>,
 
+    /// LSP code actions being applied as part of a save (if any)
+    pending_save_code_actions: Option<on_save_actions::SaveCodeActions>,
+
+    /// Formatters registered by plugins, at most one per language
+    plugin_formatters: Vec<fresh_core::api::PluginFormatter>,
+
+    /// Plugin formatter request awaiting its result (if any)
+    pending_plugin_format: Option<on_save_actions::PendingPluginFormat>,
+
+    /// Evaluators registered by plugins, at most one per language
+    plugin_evaluators: Vec<fresh_core::api::PluginEvaluator>,
+
+    /// Plugin evaluator request awaiting its results (if any)
+    pending_evaluation: Option<evaluate_actions::PendingEvaluation>,
+
+    /// Snippet whose tabstops Tab is stepping through (if any)
+    snippet_session: Option<snippet_actions::SnippetSession>,
+
     /// Pending LSP inlay hints request ID (if any)
     pending_inlay_hints_request: Option<u64>,
 
@@ -447,8 +540,11 @@ pub struct Editor {
     /// Mouse state for scrollbar dragging
     mouse_state: MouseState,
 
-    /// Tab context menu state (right-click on tabs)
-    tab_context_menu: Option<TabContextMenu>,
+    /// Open right-click context menu
+    context_menu: Option<context_menu::ContextMenu>,
+
+    /// Items plugins added to the context menus
+    context_menu_items: Vec<fresh_core::api::ContextMenuItem>,
 
     /// Cached layout areas from last render (for mouse hit testing)
     pub(crate) cached_layout: CachedLayout,
@@ -467,6 +563,16 @@ pub struct Editor {
     /// Plugin manager (handles both enabled and disabled cases)
     plugin_manager: PluginManager,
 
+    /// Watches the plugin directories so changed plugins are reloaded
+    plugin_watcher: PluginWatcher,
+
+    /// Last time the plugin directories were checked for changes
+    last_plugin_poll: std::time::Instant,
+
+    /// Permissions plugins have asked the user for this session, as
+    /// (plugin name, capability), so each is only asked for once
+    plugin_permission_requests: HashSet<(String, String)>,
+
     /// Track which byte ranges have been seen per buffer (for lines_changed optimization)
     /// Maps buffer_id -> set of (byte_start, byte_end) ranges that have been processed
     /// Using byte ranges instead of line numbers makes this agnostic to line number shifts
@@ -480,6 +586,10 @@ pub struct Editor {
     /// Maps process_id to abort handle
     background_process_handles: HashMap<u64, tokio::task::AbortHandle>,
 
+    /// Background processes whose output lines are sent to a plugin's
+    /// `onStdout`/`onStderr` listeners, mapped to that plugin's name
+    process_output_subscribers: HashMap<u64, String>,
+
     /// Prompt histories keyed by prompt type name (e.g., "search", "replace", "goto_line", "plugin:custom_name")
     /// This provides a generic history system that works for all prompt types including plugin prompts.
     prompt_histories: HashMap<String, crate::input::input_history::InputHistory>,
@@ -489,6 +599,25 @@ pub struct Editor {
     /// When cancelled, the callback is resolved with null.
     pending_async_prompt_callback: Option<fresh_core::api::JsCallbackId>,
 
+    /// Pending `editor.showConfirm` callback, resolved with the chosen
+    /// button's label when its popup closes (null when dismissed)
+    pending_confirm_callback: Option<fresh_core::api::JsCallbackId>,
+
+    /// Open `editor.quickPick` list (resolved when its prompt closes)
+    quick_pick: Option<quick_pick_actions::QuickPickState>,
+
+    /// Open `editor.openFileBrowser` dialog (resolved when its prompt closes)
+    plugin_file_browser: Option<file_browser_actions::PluginFileBrowserState>,
+
+    /// Open review of a rename with risky edits (applied when its prompt is confirmed)
+    rename_preview: Option<rename_preview_actions::RenamePreviewState>,
+
+    /// Characters inserted from the Insert Unicode Character picker, most recent first
+    recent_unicode_chars: Vec<char>,
+
+    /// Ctrl+Tab walk in progress (ended by any other action)
+    tab_mru_cycle: Option<TabMruCycle>,
+
     /// LSP progress tracking (token -> progress info)
     lsp_progress: std::collections::HashMap<String, LspProgressInfo>,
 
@@ -523,6 +652,21 @@ pub struct Editor {
     /// Whether to confirm each replacement (interactive/query-replace mode)
     search_confirm_each: bool,
 
+    /// Most recent project-wide search (results stream into a virtual buffer)
+    project_search: Option<project_search_actions::ProjectSearchRun>,
+
+    /// Most recent background filtering of prompt suggestions
+    prompt_filter: Option<prompt_filter_actions::PromptFilterRun>,
+
+    /// Components still disabled when started with `--safe-mode`
+    safe_mode: Option<SafeMode>,
+
+    /// Profile and `--config-set` overrides re-applied on every config reload
+    config_overrides: crate::config_io::ConfigOverrides,
+
+    /// Local socket for external tools (`editor.rpc_server`)
+    rpc_server: Option<crate::services::rpc::RpcServer>,
+
     /// Macro storage (key -> list of recorded actions)
     macros: HashMap<char, Vec<Action>>,
 
@@ -546,6 +690,16 @@ pub struct Editor {
     #[cfg(feature = "plugins")]
     plugin_render_requested: bool,
 
+    /// Whether the first frame has been rendered (fires `onStartupFinished`)
+    startup_finished: bool,
+
+    /// Undo groups opened by plugins, keyed by buffer, with when they were opened
+    plugin_undo_groups: HashMap<BufferId, Instant>,
+
+    /// Values plugins stored with `setSessionData`, by plugin name then key;
+    /// saved with the session
+    plugin_session_data: HashMap<String, HashMap<String, serde_json::Value>>,
+
     /// Pending chord sequence for multi-key bindings (e.g., C-x C-s in Emacs)
     /// Stores the keys pressed so far in a chord sequence
     chord_state: Vec<(crossterm::event::KeyCode, crossterm::event::KeyModifiers)>,
@@ -567,10 +721,23 @@ pub struct Editor {
     /// Last time we polled for directory changes (for file tree refresh)
     last_file_tree_poll: std::time::Instant,
 
+    /// Last time we checked the system clipboard for the clipboard history
+    last_clipboard_poll: std::time::Instant,
+
+    /// Sizes and recency of evictable caches, checked against `editor.cache_budget_mb`
+    cache_budget: crate::services::cache_budget::CacheBudget,
+
+    /// Last time cache sizes were checked against the budget
+    last_cache_budget_check: std::time::Instant,
+
     /// Last known modification times for open files (for auto-revert)
     /// Maps file path to last known modification time
     file_mod_times: HashMap<PathBuf, std::time::SystemTime>,
 
+    /// Open files whose filesystem pushes change notifications for them
+    /// (see `FileSystem::watch`), so they are not polled
+    watched_files: HashSet<PathBuf>,
+
     /// Last known modification times for expanded directories (for file tree refresh)
     /// Maps directory path to last known modification time
     dir_mod_times: HashMap<PathBuf, std::time::SystemTime>,
@@ -594,6 +761,13 @@ pub struct Editor {
     /// Time source for testable time operations
     time_source: SharedTimeSource,
 
+    /// Resolve plugin `editor.delay` calls against `time_source` instead of
+    /// sleeping on the async runtime (set by the `testing` harness)
+    virtual_plugin_delays: bool,
+
+    /// Plugin delays waiting for `time_source` to reach their deadline
+    pending_plugin_delays: Vec<(std::time::Instant, fresh_core::api::JsCallbackId)>,
+
     /// Last auto-save time for rate limiting
     last_auto_save: std::time::Instant,
 
@@ -605,6 +779,18 @@ pub struct Editor {
     /// When set, this mode's keybindings take precedence over normal key handling
     editor_mode: Option<String>,
 
+    /// Whether typed characters replace the character under the cursor
+    /// instead of being inserted (toggled with the Insert key)
+    overwrite_mode: bool,
+
+    /// Terminal cursor style last handed to the frontend, so the cursor
+    /// shape sequence is only emitted when the style actually changes
+    applied_cursor_style: Option<CursorStyle>,
+
+    /// Whether the terminal window has focus (from terminal focus events;
+    /// stays true on terminals that don't report focus)
+    terminal_focused: bool,
+
     /// Warning log receiver and path (for tracking warnings)
     warning_log: Option<(std::sync::mpsc::Receiver<()>, PathBuf)>,
 
@@ -659,6 +845,12 @@ pub struct Editor {
     /// Event debug dialog state (when event debug modal is open)
     pub(crate) event_debug: Option<event_debug::EventDebug>,
 
+    /// Keybinding cheat sheet state (when the cheat sheet overlay is open)
+    pub(crate) cheat_sheet: Option<cheat_sheet::CheatSheet>,
+
+    /// Popups pinned into floating tool windows (persist across buffers and cursor moves)
+    pub(crate) floating_windows: crate::view::floating_window::FloatingWindowManager,
+
     /// Key translator for input calibration (loaded from config)
     pub(crate) key_translator: crate::input::key_translator::KeyTranslator,
 
@@ -683,6 +875,9 @@ pub struct Editor {
 
     /// Stdin streaming state (if reading from stdin)
     stdin_streaming: Option<StdinStreamingState>,
+
+    /// Large save being written a few chunks per frame (if any)
+    save_in_progress: Option<SaveInProgress>,
 }
 
 /// State for tracking stdin streaming in background
@@ -699,6 +894,18 @@ pub struct StdinStreamingState {
     pub thread_handle: Option<std::thread::JoinHandle<anyhow::Result<()>>>,
 }
 
+/// State for a large save that is written a few chunks per frame
+pub struct SaveInProgress {
+    /// Buffer being saved
+    pub buffer_id: BufferId,
+    /// The save itself, with how far it has got
+    pub save: crate::model::buffer::PendingSave,
+    /// File name shown in the progress message
+    pub name: String,
+    /// Whether this is a Save As, which also updates the buffer's metadata
+    pub save_as: bool,
+}
+
 impl Editor {
     /// Create a new editor with the given configuration and terminal dimensions
     /// Uses system directories for state (recovery, sessions, etc.)
@@ -805,7 +1012,8 @@ impl Editor {
         let working_dir = working_dir.canonicalize().unwrap_or(working_dir);
 
         // Load all themes into registry
-        let theme_loader = crate::view::theme::ThemeLoader::new();
+        let theme_loader =
+            crate::view::theme::ThemeLoader::new().with_color_vision(config.view.color_vision);
         let theme_registry = theme_loader.load_all();
 
         // Get active theme from registry, falling back to default if not found
@@ -844,10 +1052,14 @@ impl Editor {
         );
         // Apply line_numbers default from config (fixes #539)
         state.margins.set_line_numbers(config.editor.line_numbers);
+        state.trailing_whitespace_overlay.enabled = config.editor.highlight_trailing_whitespace;
         // Note: line_wrap_enabled is now stored in SplitViewState.viewport
         tracing::info!("EditorState created for buffer {:?}", buffer_id);
         buffers.insert(buffer_id, state);
-        event_logs.insert(buffer_id, EventLog::new());
+        event_logs.insert(
+            buffer_id,
+            EventLog::new().with_branch_limit(config.editor.undo_branch_limit),
+        );
 
         // Create metadata for the initial empty buffer
         let mut buffer_metadata = HashMap::new();
@@ -932,6 +1144,7 @@ impl Editor {
         // 3. From embedded plugins (for cargo-binstall, when embed-plugins feature is enabled)
         // 4. User plugins directory (~/.config/fresh/plugins)
         // 5. Package manager installed plugins (~/.config/fresh/plugins/packages/*)
+        let mut watched_plugin_dirs = Vec::new();
         if plugin_manager.is_active() {
             let mut plugin_dirs: Vec<std::path::PathBuf> = vec![];
 
@@ -996,10 +1209,10 @@ impl Editor {
             }
 
             // Load from all found plugin directories, respecting config
-            for plugin_dir in plugin_dirs {
+            for plugin_dir in &plugin_dirs {
                 tracing::info!("Loading TypeScript plugins from: {:?}", plugin_dir);
                 let (errors, discovered_plugins) =
-                    plugin_manager.load_plugins_from_dir_with_config(&plugin_dir, &config.plugins);
+                    plugin_manager.load_plugins_from_dir_with_config(plugin_dir, &config.plugins);
 
                 // Merge discovered plugins into config
                 // discovered_plugins already contains the merged config (saved enabled state + discovered path)
@@ -1020,6 +1233,7 @@ impl Editor {
                     );
                 }
             }
+            watched_plugin_dirs = plugin_dirs;
         }
 
         // Extract config values before moving config into the struct
@@ -1046,8 +1260,13 @@ impl Editor {
         };
 
         // Cache raw user config at startup (to avoid re-reading file every frame)
-        let user_config_raw = Config::read_user_config_raw(&working_dir);
+        let user_config_raw = Config::read_user_config_raw(&working_dir, &dir_context);
 
+        let keyboard_layout = config.editor.keyboard_layout;
+        let mut clipboard = crate::services::clipboard::Clipboard::new();
+        clipboard.set_history_size(config.editor.clipboard_history_size);
+        let cache_budget =
+            crate::services::cache_budget::CacheBudget::new(config.editor.cache_budget_mb);
         let mut editor = Editor {
             buffers,
             event_logs,
@@ -1063,11 +1282,12 @@ impl Editor {
             ansi_background_path: None,
             background_fade: crate::primitives::ansi_background::DEFAULT_BACKGROUND_FADE,
             keybindings,
-            clipboard: crate::services::clipboard::Clipboard::new(),
+            clipboard,
             should_quit: false,
             restart_with_dir: None,
             status_message: None,
             plugin_status_message: None,
+            plugin_status_segments: HashMap::new(),
             plugin_errors: Vec::new(),
             prompt: None,
             terminal_width: width,
@@ -1080,17 +1300,20 @@ impl Editor {
             split_manager,
             split_view_states,
             previous_viewports: HashMap::new(),
+            previous_active_split: None,
             scroll_sync_manager: ScrollSyncManager::new(),
             file_explorer: None,
             fs_manager,
             filesystem,
             local_filesystem: Arc::new(crate::model::filesystem::StdFileSystem),
             process_spawner: Arc::new(crate::services::remote::LocalProcessSpawner),
+            remote_audit_log: None,
             file_explorer_visible: false,
             file_explorer_sync_in_progress: false,
             file_explorer_width_percent: file_explorer_width,
             pending_file_explorer_show_hidden: None,
             pending_file_explorer_show_gitignored: None,
+            pending_file_explorer_reveal: None,
             menu_bar_visible: show_menu_bar,
             file_explorer_decorations: HashMap::new(),
             file_explorer_decoration_cache:
@@ -1109,13 +1332,29 @@ impl Editor {
             next_lsp_request_id: 0,
             pending_completion_request: None,
             completion_items: None,
+            completion_providers: Vec::new(),
+            pending_plugin_completion_request: None,
             scheduled_completion_trigger: None,
             pending_goto_definition_request: None,
             pending_hover_request: None,
+            hover_providers: Vec::new(),
+            plugin_markers: HashMap::new(),
+            plugin_gutters: Vec::new(),
+            inline_suggestion_providers: Vec::new(),
+            inline_suggestions: Default::default(),
+            pending_plugin_hover_request: None,
+            hover_popup_request: None,
+            hover_sections: Vec::new(),
             pending_references_request: None,
             pending_references_symbol: String::new(),
             pending_signature_help_request: None,
             pending_code_actions_request: None,
+            pending_save_code_actions: None,
+            plugin_formatters: Vec::new(),
+            pending_plugin_format: None,
+            plugin_evaluators: Vec::new(),
+            pending_evaluation: None,
+            snippet_session: None,
             pending_inlay_hints_request: None,
             pending_semantic_token_requests: HashMap::new(),
             semantic_tokens_in_flight: HashMap::new(),
@@ -1138,15 +1377,20 @@ impl Editor {
             interactive_replace_state: None,
             lsp_status: String::new(),
             mouse_state: MouseState::default(),
-            tab_context_menu: None,
+            context_menu: None,
+            context_menu_items: Vec::new(),
             cached_layout: CachedLayout::default(),
             command_registry,
             quick_open_registry,
             file_provider,
             plugin_manager,
+            plugin_watcher: PluginWatcher::new(watched_plugin_dirs),
+            last_plugin_poll: time_source.now(),
+            plugin_permission_requests: HashSet::new(),
             seen_byte_ranges: HashMap::new(),
             panel_ids: HashMap::new(),
             background_process_handles: HashMap::new(),
+            process_output_subscribers: HashMap::new(),
             prompt_histories: {
                 // Load prompt histories from disk if available
                 let mut histories = HashMap::new();
@@ -1162,6 +1406,12 @@ impl Editor {
                 histories
             },
             pending_async_prompt_callback: None,
+            pending_confirm_callback: None,
+            quick_pick: None,
+            plugin_file_browser: None,
+            rename_preview: None,
+            recent_unicode_chars: Vec::new(),
+            tab_mru_cycle: None,
             lsp_progress: std::collections::HashMap::new(),
             lsp_server_statuses: std::collections::HashMap::new(),
             lsp_window_messages: Vec::new(),
@@ -1174,6 +1424,11 @@ impl Editor {
             search_whole_word: false,
             search_use_regex: false,
             search_confirm_each: false,
+            project_search: None,
+            prompt_filter: None,
+            safe_mode: None,
+            config_overrides: Default::default(),
+            rpc_server: None,
             macros: HashMap::new(),
             macro_recording: None,
             last_macro_register: None,
@@ -1182,13 +1437,20 @@ impl Editor {
             pending_plugin_actions: Vec::new(),
             #[cfg(feature = "plugins")]
             plugin_render_requested: false,
+            startup_finished: false,
+            plugin_undo_groups: HashMap::new(),
+            plugin_session_data: HashMap::new(),
             chord_state: Vec::new(),
             pending_lsp_confirmation: None,
             pending_close_buffer: None,
             auto_revert_enabled: true,
             last_auto_revert_poll: time_source.now(),
             last_file_tree_poll: time_source.now(),
+            last_clipboard_poll: time_source.now(),
+            cache_budget,
+            last_cache_budget_check: time_source.now(),
             file_mod_times: HashMap::new(),
+            watched_files: HashSet::new(),
             dir_mod_times: HashMap::new(),
             file_rapid_change_counts: HashMap::new(),
             file_open_state: None,
@@ -1203,9 +1465,14 @@ impl Editor {
             },
             full_redraw_requested: false,
             time_source: time_source.clone(),
+            virtual_plugin_delays: false,
+            pending_plugin_delays: Vec::new(),
             last_auto_save: time_source.now(),
             active_custom_contexts: HashSet::new(),
             editor_mode: None,
+            overwrite_mode: false,
+            applied_cursor_style: None,
+            terminal_focused: true,
             warning_log: None,
             status_log_path: None,
             warning_domains: WarningDomainRegistry::new(),
@@ -1222,16 +1489,25 @@ impl Editor {
             settings_state: None,
             calibration_wizard: None,
             event_debug: None,
-            key_translator: crate::input::key_translator::KeyTranslator::load_default()
-                .unwrap_or_default(),
+            cheat_sheet: None,
+            floating_windows: crate::view::floating_window::FloatingWindowManager::new(),
+            key_translator: {
+                let mut translator =
+                    crate::input::key_translator::KeyTranslator::load_default().unwrap_or_default();
+                translator.set_layout(keyboard_layout);
+                translator
+            },
             color_capability,
             stdin_streaming: None,
+            save_in_progress: None,
             review_hunks: Vec::new(),
             active_action_popup: None,
             composite_buffers: HashMap::new(),
             composite_view_states: HashMap::new(),
         };
 
+        editor.start_rpc_server();
+
         #[cfg(feature = "plugins")]
         {
             editor.update_plugin_state_snapshot();
@@ -1371,6 +1647,23 @@ impl Editor {
         self.active_state().editing_disabled
     }
 
+    /// Tell the user an edit was refused because editing is disabled.
+    /// For protected library code this asks whether to allow editing instead.
+    pub(crate) fn report_editing_disabled(&mut self) {
+        let protected_name = self
+            .buffer_metadata
+            .get(&self.active_buffer())
+            .filter(|metadata| metadata.library_protected)
+            .map(|metadata| metadata.display_name.clone());
+        match protected_name {
+            Some(name) => self.start_prompt(
+                t!("prompt.edit_library_file", name = name).to_string(),
+                PromptType::ConfirmEditLibraryFile,
+            ),
+            None => self.set_status_message(t!("buffer.editing_disabled").to_string()),
+        }
+    }
+
     /// Resolve a keybinding for the current mode
     ///
     /// First checks the global editor mode (for vi mode and other modal editing).
@@ -1531,6 +1824,11 @@ impl Editor {
         self.process_spawner = spawner;
     }
 
+    /// Set the audit log shared with the remote filesystem and process spawner
+    pub fn set_remote_audit_log(&mut self, audit_log: crate::services::remote::AuditLog) {
+        self.remote_audit_log = Some(audit_log);
+    }
+
     /// Get remote connection info if editing remote files
     ///
     /// Returns `Some("user@host")` for remote editing, `None` for local.
@@ -1538,6 +1836,19 @@ impl Editor {
         self.filesystem.remote_connection_info()
     }
 
+    /// Set the profile and command-line overrides layered over the config.
+    ///
+    /// The caller has already applied them to the config the editor was
+    /// created with; they are kept so config reloads apply them again.
+    pub fn set_config_overrides(&mut self, overrides: crate::config_io::ConfigOverrides) {
+        self.config_overrides = overrides;
+    }
+
+    /// Name of the config profile selected with `--profile`, if any
+    pub fn active_profile(&self) -> Option<&str> {
+        self.config_overrides.profile.as_deref()
+    }
+
     /// Get the status log path
     pub fn get_status_log_path(&self) -> Option<&PathBuf> {
         self.status_log_path.as_ref()
@@ -1658,7 +1969,7 @@ impl Editor {
                 if self.mouse_state.lsp_hover_request_sent {
                     return false; // Already sent request for this position
                 }
-                if start_time.elapsed() < hover_delay {
+                if self.time_source.elapsed_since(start_time) < hover_delay {
                     return false; // Timer hasn't expired yet
                 }
                 Some((byte_pos, screen_x, screen_y))
@@ -1701,6 +2012,36 @@ impl Editor {
         false
     }
 
+    /// Drive plugin `editor.delay` calls from the time source rather than
+    /// real time, so a test clock decides when they resolve
+    pub fn set_virtual_plugin_delays(&mut self, enabled: bool) {
+        self.virtual_plugin_delays = enabled;
+    }
+
+    /// Number of plugin delays waiting for the time source to advance
+    pub fn pending_plugin_delay_count(&self) -> usize {
+        self.pending_plugin_delays.len()
+    }
+
+    /// Resolve the plugin delays whose deadline has passed, earliest first
+    ///
+    /// Returns true if any delay was resolved.
+    pub fn check_plugin_delays(&mut self) -> bool {
+        let now = self.time_source.now();
+        let (mut due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending_plugin_delays)
+            .into_iter()
+            .partition(|(deadline, _)| *deadline <= now);
+        self.pending_plugin_delays = pending;
+        due.sort_by_key(|(deadline, _)| *deadline);
+
+        let resolved = !due.is_empty();
+        for (_, callback_id) in due {
+            self.plugin_manager
+                .resolve_callback(callback_id, "null".to_string());
+        }
+        resolved
+    }
+
     /// Check if completion trigger timer has expired and trigger completion if so
     ///
     /// This implements debounced completion - we wait for quick_suggestions_delay_ms
@@ -1713,7 +2054,7 @@ impl Editor {
         };
 
         // Check if the timer has expired
-        if Instant::now() < trigger_time {
+        if self.time_source.now() < trigger_time {
             return false;
         }
 
@@ -1844,7 +2185,11 @@ impl Editor {
         // Emit buffer_activated hook for plugins
         self.plugin_manager.run_hook(
             "buffer_activated",
-            crate::services::plugins::hooks::HookArgs::BufferActivated { buffer_id },
+            crate::services::plugins::hooks::HookArgs::BufferActivated {
+                buffer_id,
+                previous_buffer_id: Some(previous),
+                split_id: active_split,
+            },
         );
     }
 
@@ -1899,6 +2244,15 @@ impl Editor {
                 }
                 // Note: We don't sync file explorer here to avoid flicker during split focus changes.
                 // File explorer syncs when explicitly focused via focus_file_explorer().
+
+                self.plugin_manager.run_hook(
+                    "buffer_activated",
+                    crate::services::plugins::hooks::HookArgs::BufferActivated {
+                        buffer_id,
+                        previous_buffer_id: Some(previous_buffer),
+                        split_id,
+                    },
+                );
             }
         } else {
             // Same split, different buffer (tab switch) - use set_active_buffer for terminal resume
@@ -2114,6 +2468,27 @@ impl Editor {
         // Apply bulk edits
         let _delta = state.buffer.apply_bulk_edits(&edit_refs);
 
+        // Journal them in the order applied; descending positions keep each
+        // edit's offset valid after the ones before it
+        let version = state.buffer.version();
+        for (pos, del_len, text) in &edits {
+            state
+                .change_log
+                .record(version, *pos..*pos + *del_len, text);
+            if *del_len > 0 {
+                state
+                    .edit_journal
+                    .record(crate::model::edit::Edit::delete(version, *pos, *del_len));
+            }
+            if !text.is_empty() {
+                state.edit_journal.record(crate::model::edit::Edit::insert(
+                    version,
+                    *pos,
+                    text.len(),
+                ));
+            }
+        }
+
         // Snapshot the tree after edits (for redo) - O(1) Arc clone
         let new_tree = state.buffer.snapshot_piece_tree();
 
@@ -2400,20 +2775,11 @@ impl Editor {
 
         let active_split = self.split_manager.active_split();
 
-        // Check if this split is in a scroll sync group (anchor-based sync for diffs)
-        // Mark both splits to skip ensure_visible so cursor doesn't override scroll
-        // The sync_scroll_groups() at render time will sync the other split
-        if let Some(group) = self.scroll_sync_manager.find_group_for_split(active_split) {
-            let left = group.left_split;
-            let right = group.right_split;
-            if let Some(vs) = self.split_view_states.get_mut(&left) {
-                vs.viewport.set_skip_ensure_visible();
-            }
-            if let Some(vs) = self.split_view_states.get_mut(&right) {
-                vs.viewport.set_skip_ensure_visible();
-            }
-            // Continue to scroll the active split normally below
-        }
+        // Check if this split is in a scroll sync group
+        // Mark all its splits to skip ensure_visible so cursor doesn't override scroll
+        // The sync_scroll_groups() at render time will sync the other splits
+        // Continue to scroll the active split normally below
+        self.skip_ensure_visible_for_scroll_sync_group(active_split);
 
         // Fall back to simple sync_group (same delta to all splits)
         let sync_group = self
@@ -2476,42 +2842,18 @@ impl Editor {
     fn handle_set_viewport_event(&mut self, top_line: usize) {
         let active_split = self.split_manager.active_split();
 
-        // Check if this split is in a scroll sync group (anchor-based sync for diffs)
-        // If so, set the group's scroll_line and let render sync the viewports
-        if self.scroll_sync_manager.is_split_synced(active_split) {
-            if let Some(group) = self
-                .scroll_sync_manager
-                .find_group_for_split_mut(active_split)
-            {
-                // Convert line to left buffer space if coming from right split
-                let scroll_line = if group.is_left_split(active_split) {
-                    top_line
-                } else {
-                    group.right_to_left_line(top_line)
-                };
-                group.set_scroll_line(scroll_line);
-            }
-
-            // Mark both splits to skip ensure_visible
-            if let Some(group) = self.scroll_sync_manager.find_group_for_split(active_split) {
-                let left = group.left_split;
-                let right = group.right_split;
-                if let Some(vs) = self.split_view_states.get_mut(&left) {
-                    vs.viewport.set_skip_ensure_visible();
-                }
-                if let Some(vs) = self.split_view_states.get_mut(&right) {
-                    vs.viewport.set_skip_ensure_visible();
-                }
-            }
-            return;
-        }
+        // Check if this split is in a scroll sync group
+        // If so, scroll only the active split and let render sync the others
+        let scroll_synced = self.skip_ensure_visible_for_scroll_sync_group(active_split);
 
         // Fall back to simple sync_group (same line to all splits)
         let sync_group = self
             .split_view_states
             .get(&active_split)
             .and_then(|vs| vs.sync_group);
-        let splits_to_scroll = if let Some(group_id) = sync_group {
+        let splits_to_scroll = if scroll_synced {
+            vec![active_split]
+        } else if let Some(group_id) = sync_group {
             self.split_manager
                 .get_splits_in_group(group_id, &self.split_view_states)
         } else {
@@ -2536,6 +2878,21 @@ impl Editor {
         }
     }
 
+    /// Mark every split in the scroll sync group of `split_id` to skip
+    /// ensure_visible, so the positions set by scrolling or syncing aren't
+    /// undone by the cursors. Returns false if the split isn't synced.
+    fn skip_ensure_visible_for_scroll_sync_group(&mut self, split_id: SplitId) -> bool {
+        let Some(group) = self.scroll_sync_manager.find_group_for_split(split_id) else {
+            return false;
+        };
+        for member in &group.splits {
+            if let Some(vs) = self.split_view_states.get_mut(member) {
+                vs.viewport.set_skip_ensure_visible();
+            }
+        }
+        true
+    }
+
     /// Handle Recenter event using SplitViewState's viewport
     fn handle_recenter_event(&mut self) {
         let active_split = self.split_manager.active_split();
@@ -2820,6 +3177,7 @@ impl Editor {
             PromptType::OpenFile
                 | PromptType::SwitchProject
                 | PromptType::SaveFileAs
+                | PromptType::PluginFileBrowser
                 | PromptType::Command
         );
 
@@ -2892,7 +3250,17 @@ impl Editor {
             let line_str = &input[1..];
             self.get_goto_line_suggestions(line_str)
         } else {
-            // File mode (default)
+            // File mode (default): large projects are filtered in the background
+            if !input.is_empty() {
+                let files = self
+                    .file_provider
+                    .filter_source(&self.working_dir.display().to_string());
+                if files.len() >= BACKGROUND_FILTER_THRESHOLD
+                    && self.start_prompt_filter(files, Some(FileProvider::MAX_RESULTS))
+                {
+                    return;
+                }
+            }
             self.get_file_suggestions(input)
         };
 
@@ -2941,7 +3309,7 @@ impl Editor {
                     Some((
                         Suggestion {
                             text: display_name,
-                            description: Some(path.display().to_string()),
+                            description: Some(self.buffer_display_path(*buffer_id)),
                             value: Some(buffer_id.0.to_string()),
                             disabled: false,
                             keybinding: None,
@@ -2996,7 +3364,7 @@ impl Editor {
     }
 
     /// Get file suggestions for Quick Open
-    fn get_file_suggestions(&self, query: &str) -> Vec<Suggestion> {
+    fn get_file_suggestions(&mut self, query: &str) -> Vec<Suggestion> {
         // Use the file provider's file loading mechanism
         let cwd = self.working_dir.display().to_string();
         let context = QuickOpenContext {
@@ -3018,6 +3386,8 @@ impl Editor {
                 .map(|s| s.to_string()),
         };
 
+        self.cache_budget
+            .touch(crate::services::cache_budget::CacheKey::FileIndex);
         self.file_provider.suggestions(query, &context)
     }
 
@@ -3059,18 +3429,13 @@ impl Editor {
         }
     }
 
-    /// Initialize the file open dialog state
+    /// Directory the Open/Save As dialogs start in
     ///
-    /// Called when the Open File prompt is started. Determines the initial directory
-    /// (from current buffer's directory or working directory) and triggers async
-    /// directory loading.
-    fn init_file_open_state(&mut self) {
-        // Determine initial directory
+    /// For terminal buffers, use the terminal's initial CWD or fall back to project root.
+    /// This avoids showing the terminal backing file directory which is confusing for users.
+    fn file_dialog_initial_dir(&self) -> PathBuf {
         let buffer_id = self.active_buffer();
-
-        // For terminal buffers, use the terminal's initial CWD or fall back to project root
-        // This avoids showing the terminal backing file directory which is confusing for users
-        let initial_dir = if self.is_terminal_buffer(buffer_id) {
+        if self.is_terminal_buffer(buffer_id) {
             self.get_terminal_id(buffer_id)
                 .and_then(|tid| self.terminal_manager.get(tid))
                 .and_then(|handle| handle.cwd())
@@ -3082,7 +3447,16 @@ impl Editor {
                 .and_then(|path| path.parent())
                 .map(|p| p.to_path_buf())
                 .unwrap_or_else(|| self.working_dir.clone())
-        };
+        }
+    }
+
+    /// Initialize the file open dialog state
+    ///
+    /// Called when the Open File prompt is started. Determines the initial directory
+    /// (from current buffer's directory or working directory) and triggers async
+    /// directory loading.
+    fn init_file_open_state(&mut self) {
+        let initial_dir = self.file_dialog_initial_dir();
 
         // Create the file open state with config-based show_hidden setting
         let show_hidden = self.config.file_browser.show_hidden;
@@ -3134,6 +3508,12 @@ impl Editor {
 
     /// Load directory contents for the file open dialog
     fn load_file_open_directory(&mut self, path: PathBuf) {
+        // Plugin file browsers cannot leave their root
+        let path = match self.file_open_state.as_ref().and_then(|s| s.scope.clone()) {
+            Some(scope) if !path.starts_with(&scope) => scope,
+            _ => path,
+        };
+
         // Update state to loading
         if let Some(state) = &mut self.file_open_state {
             state.current_dir = path.clone();
@@ -3239,6 +3619,17 @@ impl Editor {
                     self.file_open_state = None;
                     self.file_browser_layout = None;
                 }
+                PromptType::PluginFileBrowser => {
+                    self.file_open_state = None;
+                    self.file_browser_layout = None;
+                    self.resolve_plugin_file_browser(None);
+                }
+                PromptType::QuickPick => {
+                    self.cancel_quick_pick();
+                }
+                PromptType::RenamePreview => {
+                    self.cancel_rename_preview();
+                }
                 PromptType::AsyncPrompt => {
                     // Resolve the pending async prompt callback with null (cancelled)
                     if let Some(callback_id) = self.pending_async_prompt_callback.take() {
@@ -3301,6 +3692,7 @@ impl Editor {
     /// Returns (input, prompt_type, selected_index)
     /// Returns None if trying to confirm a disabled command
     pub fn confirm_prompt(&mut self) -> Option<(String, PromptType, Option<usize>)> {
+        self.finish_prompt_filter_now();
         if let Some(prompt) = self.prompt.take() {
             let selected_index = prompt.selected_suggestion;
             // For command, file, theme, plugin, and LSP stop prompts, prefer the selected suggestion over raw input
@@ -3310,12 +3702,24 @@ impl Editor {
                     | PromptType::OpenFile
                     | PromptType::SwitchProject
                     | PromptType::SaveFileAs
+                    | PromptType::PluginFileBrowser
                     | PromptType::StopLspServer
+                    | PromptType::EnableLspServer
                     | PromptType::SelectTheme { .. }
+                    | PromptType::SelectSplitTheme
+                    | PromptType::MoveTabToSplit
                     | PromptType::SelectLocale
                     | PromptType::SwitchToTab
+                    | PromptType::SwitchUndoBranch
+                    | PromptType::PasteFromClipboardHistory
+                    | PromptType::InsertUnicode
+                    | PromptType::OpenRecentWorkspace { .. }
+                    | PromptType::SafeModeReenable
+                    | PromptType::ReloadPlugin
                     | PromptType::SetLanguage
+                    | PromptType::ReopenWithEncoding
                     | PromptType::Plugin { .. }
+                    | PromptType::QuickPick
             ) {
                 // Use the selected suggestion if any
                 if let Some(selected_idx) = prompt.selected_suggestion {
@@ -3401,9 +3805,10 @@ impl Editor {
     fn prompt_type_to_history_key(prompt_type: &crate::view::prompt::PromptType) -> Option<String> {
         use crate::view::prompt::PromptType;
         match prompt_type {
-            PromptType::Search | PromptType::ReplaceSearch | PromptType::QueryReplaceSearch => {
-                Some("search".to_string())
-            }
+            PromptType::Search
+            | PromptType::ReplaceSearch
+            | PromptType::QueryReplaceSearch
+            | PromptType::ProjectSearch => Some("search".to_string()),
             PromptType::Replace { .. } | PromptType::QueryReplace { .. } => {
                 Some("replace".to_string())
             }
@@ -3419,6 +3824,73 @@ impl Editor {
         self.editor_mode.clone()
     }
 
+    /// Whether overwrite mode is active
+    pub fn is_overwrite_mode(&self) -> bool {
+        self.overwrite_mode
+    }
+
+    /// Terminal cursor style for the current mode.
+    ///
+    /// A plugin editor mode listed in `cursor_style_modes` wins, then
+    /// overwrite mode, then the configured `cursor_style`. The cursor stops
+    /// blinking while the terminal is unfocused.
+    pub fn cursor_style(&self) -> CursorStyle {
+        let editor = &self.config.editor;
+        let style = if let Some(style) = self
+            .editor_mode
+            .as_ref()
+            .and_then(|mode| editor.cursor_style_modes.get(mode))
+        {
+            *style
+        } else if self.overwrite_mode {
+            editor.cursor_style_overwrite
+        } else {
+            editor.cursor_style
+        };
+        if self.terminal_focused {
+            style
+        } else {
+            style.steady()
+        }
+    }
+
+    /// Whether the terminal window has focus
+    pub fn is_terminal_focused(&self) -> bool {
+        self.terminal_focused
+    }
+
+    /// Record a terminal focus change (FocusGained / FocusLost).
+    ///
+    /// While unfocused the frontend redraws less often and background
+    /// polling slows down; both resume on the next loop once focus returns.
+    pub fn set_terminal_focused(&mut self, focused: bool) {
+        if self.terminal_focused != focused {
+            tracing::debug!("Terminal focus changed: focused={}", focused);
+        }
+        self.terminal_focused = focused;
+    }
+
+    /// Interval for background polling, stretched while the terminal is
+    /// unfocused so an editor left in a background pane stays idle
+    pub(crate) fn background_poll_interval(&self, interval: Duration) -> Duration {
+        if self.terminal_focused {
+            interval
+        } else {
+            interval * UNFOCUSED_POLL_INTERVAL_FACTOR
+        }
+    }
+
+    /// Return the cursor style to apply to the terminal if it changed since
+    /// the last call. The frontend emits it after drawing each frame.
+    pub fn take_cursor_style_change(&mut self) -> Option<CursorStyle> {
+        let style = self.cursor_style();
+        if self.applied_cursor_style == Some(style) {
+            return None;
+        }
+        self.applied_cursor_style = Some(style);
+        Some(style)
+    }
+
     /// Get access to the command registry
     pub fn command_registry(&self) -> &Arc<RwLock<CommandRegistry>> {
         &self.command_registry
@@ -3492,6 +3964,9 @@ impl Editor {
             return;
         };
 
+        // A filter still running for the previous input is stale now
+        self.cancel_prompt_filter();
+
         match prompt_type {
             PromptType::Command => {
                 let selection_active = self.has_active_selection();
@@ -3540,7 +4015,10 @@ impl Editor {
                     history.reset_navigation();
                 }
             }
-            PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs => {
+            PromptType::OpenFile
+            | PromptType::SwitchProject
+            | PromptType::SaveFileAs
+            | PromptType::PluginFileBrowser => {
                 // For OpenFile/SwitchProject/SaveFileAs, update the file browser filter (native implementation)
                 self.update_file_open_filter();
             }
@@ -3563,23 +4041,35 @@ impl Editor {
                 // Note: filter_suggestions checks suggestions_set_for_input to skip
                 // filtering if the plugin has already provided filtered results for
                 // this input (handles the async race condition with run_hook).
-                if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(false);
-                }
+                self.filter_prompt_suggestions(false);
             }
             PromptType::SwitchToTab
+            | PromptType::SwitchUndoBranch
+            | PromptType::PasteFromClipboardHistory
+            | PromptType::SafeModeReenable
+            | PromptType::ReloadPlugin
             | PromptType::SelectTheme { .. }
+            | PromptType::SelectSplitTheme
+            | PromptType::MoveTabToSplit
             | PromptType::StopLspServer
-            | PromptType::SetLanguage => {
-                if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(false);
-                }
+            | PromptType::EnableLspServer
+            | PromptType::SetLanguage
+            | PromptType::ReopenWithEncoding
+            | PromptType::OpenRecentWorkspace { .. } => {
+                self.filter_prompt_suggestions(false);
+            }
+            PromptType::QuickPick => {
+                self.update_quick_pick_suggestions();
+            }
+            PromptType::InsertUnicode => {
+                self.update_insert_unicode_suggestions();
+            }
+            PromptType::Digraph => {
+                self.update_digraph_input();
             }
             PromptType::SelectLocale => {
                 // Locale selection also matches on description (language names)
-                if let Some(prompt) = &mut self.prompt {
-                    prompt.filter_suggestions(true);
-                }
+                self.filter_prompt_suggestions(true);
             }
             _ => {}
         }
@@ -3755,7 +4245,9 @@ impl Editor {
                     request_id,
                     actions,
                 } => {
-                    self.handle_code_actions_response(request_id, actions);
+                    if !self.handle_save_code_actions_response(request_id, &actions) {
+                        self.handle_code_actions_response(request_id, actions);
+                    }
                 }
                 AsyncMessage::LspPulledDiagnostics {
                     request_id: _,
@@ -3825,6 +4317,7 @@ impl Editor {
                             );
                         }
                         PluginAsyncMessage::ProcessStdout { process_id, data } => {
+                            self.deliver_process_output(process_id, false, &data);
                             self.plugin_manager.run_hook(
                                 "onProcessStdout",
                                 crate::services::plugins::hooks::HookArgs::ProcessOutput {
@@ -3834,6 +4327,7 @@ impl Editor {
                             );
                         }
                         PluginAsyncMessage::ProcessStderr { process_id, data } => {
+                            self.deliver_process_output(process_id, true, &data);
                             self.plugin_manager.run_hook(
                                 "onProcessStderr",
                                 crate::services::plugins::hooks::HookArgs::ProcessOutput {
@@ -3848,6 +4342,7 @@ impl Editor {
                             exit_code,
                         } => {
                             self.background_process_handles.remove(&process_id);
+                            self.process_output_subscribers.remove(&process_id);
                             let result = fresh_core::api::BackgroundProcessResult {
                                 process_id,
                                 exit_code,
@@ -3900,6 +4395,32 @@ impl Editor {
                 AsyncMessage::FileOpenDirectoryLoaded(result) => {
                     self.handle_file_open_directory_loaded(result);
                 }
+                AsyncMessage::NativeFileDialogClosed {
+                    kind,
+                    buffer_id,
+                    result,
+                } => {
+                    self.handle_native_file_dialog_closed(kind, buffer_id, result);
+                }
+                AsyncMessage::ProjectSearchResults {
+                    search_id,
+                    matches,
+                    files_searched,
+                } => {
+                    self.handle_project_search_results(search_id, matches, files_searched);
+                }
+                AsyncMessage::ProjectSearchFinished { search_id, summary } => {
+                    self.handle_project_search_finished(search_id, summary);
+                }
+                AsyncMessage::PromptFilterResults { filter_id, matches } => {
+                    self.handle_prompt_filter_results(filter_id, matches);
+                }
+                AsyncMessage::PromptFilterFinished { filter_id } => {
+                    self.handle_prompt_filter_finished(filter_id);
+                }
+                AsyncMessage::RpcRequest { method, reply } => {
+                    let _ = reply.send(self.handle_rpc_request(method));
+                }
                 AsyncMessage::TerminalOutput { terminal_id } => {
                     // Terminal output received - check if we should auto-jump back to terminal mode
                     tracing::trace!("Terminal output received for {:?}", terminal_id);
@@ -4005,9 +4526,21 @@ impl Editor {
                         exit_code,
                     );
                 }
+                AsyncMessage::PluginFetchResponse {
+                    callback_id,
+                    result,
+                } => {
+                    self.handle_plugin_fetch_response(
+                        fresh_core::api::JsCallbackId::from(callback_id),
+                        result,
+                    );
+                }
             }
         }
 
+        // Tell plugins about the edits since the last iteration
+        self.report_buffer_changes();
+
         // Update plugin state snapshot BEFORE processing commands
         // This ensures plugins have access to current editor state (cursor positions, etc.)
         #[cfg(feature = "plugins")]
@@ -4042,9 +4575,19 @@ impl Editor {
         // Poll for file changes (auto-revert) and file tree changes
         let file_changes = self.poll_file_changes();
         let tree_changes = self.poll_file_tree_changes();
+        let plugin_changes = self.poll_plugin_changes();
+        let inline_suggestion_changes = self.poll_inline_suggestion();
+        self.poll_system_clipboard();
+        self.enforce_cache_budget();
 
         // Trigger render if any async messages, plugin commands were processed, or plugin requested render
-        needs_render || processed_any_commands || plugin_render || file_changes || tree_changes
+        needs_render
+            || processed_any_commands
+            || plugin_render
+            || file_changes
+            || tree_changes
+            || plugin_changes
+            || inline_suggestion_changes
     }
 
     /// Update LSP status bar string from active progress operations
@@ -4105,6 +4648,32 @@ impl Editor {
         self.lsp_status = format!("LSP [{}]", status_parts.join(", "));
     }
 
+    /// Fire `buffer_changed` for each buffer edited since the last call
+    fn report_buffer_changes(&mut self) {
+        let mut changed: Vec<(BufferId, Vec<fresh_core::hooks::BufferChange>, u64)> = self
+            .buffers
+            .iter_mut()
+            .filter_map(|(&buffer_id, state)| {
+                let version = state.buffer.version();
+                let changes = state
+                    .change_log
+                    .take(version, state.buffer.len(), || state.buffer.to_string())?;
+                Some((buffer_id, changes, version))
+            })
+            .collect();
+        changed.sort_by_key(|(buffer_id, _, _)| buffer_id.0);
+        for (buffer_id, changes, version) in changed {
+            self.plugin_manager.run_hook(
+                "buffer_changed",
+                crate::services::plugins::hooks::HookArgs::BufferChanged {
+                    buffer_id,
+                    changes,
+                    version,
+                },
+            );
+        }
+    }
+
     /// Update the plugin state snapshot with current editor state
     #[cfg(feature = "plugins")]
     fn update_plugin_state_snapshot(&mut self) {
@@ -4120,10 +4689,12 @@ impl Editor {
             snapshot.active_split_id = self.split_manager.active_split().0;
 
             // Clear and update buffer info
+            snapshot.next_buffer_id = self.next_buffer_id;
             snapshot.buffers.clear();
             snapshot.buffer_saved_diffs.clear();
             snapshot.buffer_cursor_positions.clear();
             snapshot.buffer_text_properties.clear();
+            snapshot.buffer_rebase_slack.clear();
 
             for (buffer_id, state) in &self.buffers {
                 let buffer_info = BufferInfo {
@@ -4131,8 +4702,18 @@ impl Editor {
                     path: state.buffer.file_path().map(|p| p.to_path_buf()),
                     modified: state.buffer.is_modified(),
                     length: state.buffer.len(),
+                    version: state.buffer.version(),
+                    undo_depth: self
+                        .event_logs
+                        .get(buffer_id)
+                        .map(|log| log.undo_depth())
+                        .unwrap_or(0),
+                    read_only: state.editing_disabled,
                 };
                 snapshot.buffers.insert(*buffer_id, buffer_info);
+                snapshot
+                    .buffer_rebase_slack
+                    .insert(*buffer_id, state.edit_journal.user_deleted_bytes());
 
                 // Skip diffing in large file mode - too expensive
                 // TODO: Enable when we have an efficient streaming diff algorithm
@@ -4241,11 +4822,35 @@ impl Editor {
                 buffer_id,
                 position,
                 text,
+                base_version,
+            } => {
+                self.handle_insert_text(buffer_id, position, text, base_version);
+            }
+            PluginCommand::DeleteRange {
+                buffer_id,
+                range,
+                base_version,
+            } => {
+                self.handle_delete_range(buffer_id, range, base_version);
+            }
+            PluginCommand::ApplyEdits {
+                buffer_id,
+                edits,
+                base_version,
             } => {
-                self.handle_insert_text(buffer_id, position, text);
+                self.handle_apply_edits(buffer_id, edits, base_version);
             }
-            PluginCommand::DeleteRange { buffer_id, range } => {
-                self.handle_delete_range(buffer_id, range);
+            PluginCommand::BeginUndoGroup { buffer_id } => {
+                self.handle_begin_undo_group(buffer_id);
+            }
+            PluginCommand::EndUndoGroup { buffer_id } => {
+                self.handle_end_undo_group(buffer_id);
+            }
+            PluginCommand::Undo { buffer_id } => {
+                self.handle_plugin_undo(buffer_id, false);
+            }
+            PluginCommand::Redo { buffer_id } => {
+                self.handle_plugin_undo(buffer_id, true);
             }
             PluginCommand::InsertAtCursor { text } => {
                 self.handle_insert_at_cursor(text);
@@ -4315,6 +4920,23 @@ impl Editor {
             PluginCommand::ClearVirtualTexts { buffer_id } => {
                 self.handle_clear_virtual_texts(buffer_id);
             }
+            PluginCommand::CreateMarker {
+                buffer_id,
+                marker_id,
+                position,
+                plugin_name,
+            } => {
+                self.handle_create_marker(buffer_id, marker_id, position, plugin_name);
+            }
+            PluginCommand::DeleteMarker { marker_id } => {
+                self.handle_delete_marker(marker_id);
+            }
+            PluginCommand::GetMarkerPosition {
+                marker_id,
+                request_id,
+            } => {
+                self.handle_get_marker_position(marker_id, request_id);
+            }
             PluginCommand::AddVirtualLine {
                 buffer_id,
                 position,
@@ -4356,6 +4978,33 @@ impl Editor {
             PluginCommand::RemoveMenu { menu_label } => {
                 self.handle_remove_menu(menu_label);
             }
+            PluginCommand::AddPluginMenuItem { item } => {
+                self.handle_add_plugin_menu_item(item);
+            }
+            PluginCommand::SetSessionData {
+                plugin_name,
+                key,
+                value,
+            } => {
+                self.handle_set_session_data(plugin_name, key, value);
+            }
+            PluginCommand::RemovePluginMenuItem {
+                plugin_name,
+                path,
+                label,
+            } => {
+                self.handle_remove_plugin_menu_item(&plugin_name, &path, &label);
+            }
+            PluginCommand::AddContextMenuItem { item } => {
+                self.handle_add_context_menu_item(item);
+            }
+            PluginCommand::RemoveContextMenuItem {
+                plugin_name,
+                menu,
+                label,
+            } => {
+                self.handle_remove_context_menu_item(&plugin_name, &menu, &label);
+            }
 
             // ==================== Split Commands ====================
             PluginCommand::FocusSplit { split_id } => {
@@ -4383,15 +5032,39 @@ impl Editor {
             PluginCommand::SetSplitRatio { split_id, ratio } => {
                 self.handle_set_split_ratio(split_id, ratio);
             }
+            PluginCommand::SetSplitTheme { split_id, theme } => {
+                self.set_split_theme(split_id, theme);
+            }
             PluginCommand::DistributeSplitsEvenly { split_ids: _ } => {
                 self.handle_distribute_splits_evenly();
             }
+            PluginCommand::GetLayout { request_id } => {
+                self.handle_get_layout(request_id);
+            }
+            PluginCommand::SplitEditor {
+                split_id,
+                direction,
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_split_editor(split_id, direction, buffer_id, request_id);
+            }
             PluginCommand::SetBufferCursor {
                 buffer_id,
                 position,
             } => {
                 self.handle_set_buffer_cursor(buffer_id, position);
             }
+            PluginCommand::SetSelections { selections } => {
+                let selections: Vec<_> = selections.iter().map(|s| (s.anchor, s.head)).collect();
+                self.set_selections(&selections);
+            }
+            PluginCommand::AddCursor { position } => {
+                self.add_cursor_at(position);
+            }
+            PluginCommand::ClearSecondaryCursors => {
+                self.remove_secondary_cursors();
+            }
 
             // ==================== View/Layout Commands ====================
             PluginCommand::SetLayoutHints {
@@ -4405,6 +5078,9 @@ impl Editor {
             PluginCommand::SetLineNumbers { buffer_id, enabled } => {
                 self.handle_set_line_numbers(buffer_id, enabled);
             }
+            PluginCommand::SetBufferDisplayOptions { buffer_id, options } => {
+                self.handle_set_buffer_display_options(buffer_id, options);
+            }
             PluginCommand::SubmitViewTransform {
                 buffer_id,
                 split_id,
@@ -4437,19 +5113,78 @@ impl Editor {
             } => {
                 self.handle_clear_line_indicators(buffer_id, namespace);
             }
-            PluginCommand::SetFileExplorerDecorations {
+            PluginCommand::RegisterGutter {
+                plugin_name,
                 namespace,
-                decorations,
+                width,
             } => {
-                self.handle_set_file_explorer_decorations(namespace, decorations);
-            }
-            PluginCommand::ClearFileExplorerDecorations { namespace } => {
-                self.handle_clear_file_explorer_decorations(&namespace);
+                self.handle_register_gutter(plugin_name, namespace, width);
             }
-
-            // ==================== Status/Prompt Commands ====================
-            PluginCommand::SetStatus { message } => {
-                self.handle_set_status(message);
+            PluginCommand::UnregisterGutter { namespace } => {
+                self.handle_unregister_gutter(&namespace);
+            }
+            PluginCommand::SetGutterText {
+                buffer_id,
+                namespace,
+                line,
+                text,
+                color,
+            } => {
+                self.handle_set_gutter_text(buffer_id, namespace, line, text, color);
+            }
+            PluginCommand::SetGutterTexts {
+                buffer_id,
+                namespace,
+                lines,
+            } => {
+                self.handle_set_gutter_texts(buffer_id, namespace, lines);
+            }
+            PluginCommand::ClearGutterText {
+                buffer_id,
+                namespace,
+            } => {
+                self.handle_clear_gutter_text(buffer_id, namespace);
+            }
+            PluginCommand::SetLineBackground {
+                buffer_id,
+                namespace,
+                line,
+                color,
+            } => {
+                self.handle_set_line_background(buffer_id, namespace, line, color);
+            }
+            PluginCommand::SetLineBackgrounds {
+                buffer_id,
+                namespace,
+                lines,
+            } => {
+                self.handle_set_line_backgrounds(buffer_id, namespace, lines);
+            }
+            PluginCommand::ClearLineBackgrounds {
+                buffer_id,
+                namespace,
+            } => {
+                self.handle_clear_line_backgrounds(buffer_id, namespace);
+            }
+            PluginCommand::SetFileExplorerDecorations {
+                namespace,
+                decorations,
+            } => {
+                self.handle_set_file_explorer_decorations(namespace, decorations);
+            }
+            PluginCommand::ClearFileExplorerDecorations { namespace } => {
+                self.handle_clear_file_explorer_decorations(&namespace);
+            }
+            PluginCommand::RevealInExplorer { path } => {
+                self.handle_reveal_in_explorer(path);
+            }
+
+            // ==================== Status/Prompt Commands ====================
+            PluginCommand::SetStatus { message } => {
+                self.handle_set_status(message);
+            }
+            PluginCommand::SetStatusBarSegment { name, text } => {
+                self.handle_set_status_bar_segment(name, text);
             }
             PluginCommand::ApplyTheme { theme_name } => {
                 self.apply_theme(&theme_name);
@@ -4493,6 +5228,33 @@ impl Editor {
             } => {
                 self.handle_start_prompt_async(label, initial_value, callback_id);
             }
+            PluginCommand::StartQuickPick {
+                items,
+                options,
+                callback_id,
+            } => {
+                self.handle_start_quick_pick(items, options, callback_id);
+            }
+            PluginCommand::ShowConfirm {
+                message,
+                buttons,
+                callback_id,
+            } => {
+                self.handle_show_confirm(message, buttons, callback_id);
+            }
+            PluginCommand::ShowInput {
+                prompt,
+                options,
+                callback_id,
+            } => {
+                self.handle_show_input(prompt, options, callback_id);
+            }
+            PluginCommand::OpenFileBrowser {
+                options,
+                callback_id,
+            } => {
+                self.handle_open_file_browser(options, callback_id);
+            }
             PluginCommand::SetPromptSuggestions { suggestions } => {
                 self.handle_set_prompt_suggestions(suggestions);
             }
@@ -4504,6 +5266,42 @@ impl Editor {
             PluginCommand::UnregisterCommand { name } => {
                 self.handle_unregister_command(name);
             }
+            PluginCommand::RegisterCompletionProvider { provider } => {
+                self.handle_register_completion_provider(provider);
+            }
+            PluginCommand::ProvideCompletions { request_id, items } => {
+                self.handle_plugin_completions(request_id, items);
+            }
+            PluginCommand::RegisterHoverProvider { provider } => {
+                self.handle_register_hover_provider(provider);
+            }
+            PluginCommand::ProvideHover {
+                request_id,
+                markdown,
+            } => {
+                self.handle_plugin_hover(request_id, markdown);
+            }
+            PluginCommand::RegisterInlineSuggestionProvider { provider } => {
+                self.handle_register_inline_suggestion_provider(provider);
+            }
+            PluginCommand::ProvideInlineSuggestion { request_id, text } => {
+                self.handle_plugin_inline_suggestion(request_id, text);
+            }
+            PluginCommand::RegisterFormatter { formatter } => {
+                self.handle_register_formatter(formatter);
+            }
+            PluginCommand::ProvideFormattedText { request_id, text } => {
+                self.handle_plugin_formatted_text(request_id, text);
+            }
+            PluginCommand::RegisterEvaluator { evaluator } => {
+                self.handle_register_evaluator(evaluator);
+            }
+            PluginCommand::ProvideEvaluation {
+                request_id,
+                results,
+            } => {
+                self.handle_plugin_evaluation(request_id, results);
+            }
             PluginCommand::DefineMode {
                 name,
                 parent,
@@ -4595,6 +5393,33 @@ impl Editor {
                 }
             }
 
+            PluginCommand::Fetch {
+                url,
+                options,
+                callback_id,
+            } => {
+                // Check the allow-list up front so disallowed requests fail
+                // without touching the network
+                let config = self.config.plugin_fetch.clone();
+                if let Err(e) = crate::services::plugins::fetch::check_url(&config, &url) {
+                    self.plugin_manager.reject_callback(callback_id, e);
+                } else if let (Some(runtime), Some(bridge)) =
+                    (&self.tokio_runtime, &self.async_bridge)
+                {
+                    let sender = bridge.sender();
+                    runtime.spawn_blocking(move || {
+                        let result = crate::services::plugins::fetch::fetch(&config, &url, options);
+                        let _ = sender.send(AsyncMessage::PluginFetchResponse {
+                            callback_id: callback_id.as_u64(),
+                            result,
+                        });
+                    });
+                } else {
+                    self.plugin_manager
+                        .reject_callback(callback_id, "Async runtime not available".to_string());
+                }
+            }
+
             PluginCommand::SpawnProcessWait {
                 process_id,
                 callback_id,
@@ -4614,12 +5439,30 @@ impl Editor {
                 );
             }
 
+            PluginCommand::PluginUnloaded { plugin_name } => {
+                self.handle_plugin_unloaded(&plugin_name);
+            }
+
+            PluginCommand::RequestPluginPermission {
+                plugin_name,
+                capability,
+            } => {
+                self.handle_request_plugin_permission(plugin_name, capability);
+            }
+
             PluginCommand::Delay {
                 callback_id,
                 duration_ms,
             } => {
-                // Spawn async delay via tokio
-                if let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) {
+                if self.virtual_plugin_delays {
+                    // Resolved by check_plugin_delays once the time source gets there
+                    let deadline =
+                        self.time_source.now() + std::time::Duration::from_millis(duration_ms);
+                    self.pending_plugin_delays.push((deadline, callback_id));
+                } else if let (Some(runtime), Some(bridge)) =
+                    (&self.tokio_runtime, &self.async_bridge)
+                {
+                    // Spawn async delay via tokio
                     let sender = bridge.sender();
                     let callback_id_u64 = callback_id.as_u64();
                     runtime.spawn(async move {
@@ -4662,11 +5505,14 @@ impl Editor {
                     let callback_id_u64 = callback_id.as_u64();
 
                     let handle = runtime.spawn(async move {
+                        // kill_on_drop so that aborting this task (killBackgroundProcess)
+                        // also stops the process
                         let mut child = match TokioCommand::new(&command)
                             .args(&args)
                             .current_dir(&effective_cwd)
                             .stdout(std::process::Stdio::piped())
                             .stderr(std::process::Stdio::piped())
+                            .kill_on_drop(true)
                             .spawn()
                         {
                             Ok(child) => child,
@@ -4691,9 +5537,10 @@ impl Editor {
                         let pid = process_id;
 
                         // Spawn stdout reader
+                        let mut readers = Vec::new();
                         if let Some(stdout) = stdout {
                             let sender = sender_stdout;
-                            tokio::spawn(async move {
+                            readers.push(tokio::spawn(async move {
                                 let reader = BufReader::new(stdout);
                                 let mut lines = reader.lines();
                                 while let Ok(Some(line)) = lines.next_line().await {
@@ -4706,13 +5553,13 @@ impl Editor {
                                         ),
                                     );
                                 }
-                            });
+                            }));
                         }
 
                         // Spawn stderr reader
                         if let Some(stderr) = stderr {
                             let sender = sender_stderr;
-                            tokio::spawn(async move {
+                            readers.push(tokio::spawn(async move {
                                 let reader = BufReader::new(stderr);
                                 let mut lines = reader.lines();
                                 while let Ok(Some(line)) = lines.next_line().await {
@@ -4725,7 +5572,7 @@ impl Editor {
                                         ),
                                     );
                                 }
-                            });
+                            }));
                         }
 
                         // Wait for process to complete
@@ -4734,6 +5581,12 @@ impl Editor {
                             Err(_) => -1,
                         };
 
+                        // Send all output before the exit, so output listeners
+                        // have seen every line when the handle resolves
+                        for reader in readers {
+                            let _ = reader.await;
+                        }
+
                         let _ = sender.send(crate::services::async_bridge::AsyncMessage::Plugin(
                             fresh_core::api::PluginAsyncMessage::ProcessExit {
                                 process_id,
@@ -4756,10 +5609,31 @@ impl Editor {
             PluginCommand::KillBackgroundProcess { process_id } => {
                 if let Some(handle) = self.background_process_handles.remove(&process_id) {
                     handle.abort();
+                    self.process_output_subscribers.remove(&process_id);
+                    // The aborted task never reports the exit, so resolve the
+                    // handle here (its callback ID is the process ID)
+                    let result = fresh_core::api::BackgroundProcessResult {
+                        process_id,
+                        exit_code: -1,
+                    };
+                    self.plugin_manager.resolve_callback(
+                        fresh_core::api::JsCallbackId::from(process_id),
+                        serde_json::to_string(&result).unwrap(),
+                    );
                     tracing::debug!("Killed background process {}", process_id);
                 }
             }
 
+            PluginCommand::SubscribeProcessOutput {
+                plugin_name,
+                process_id,
+            } => {
+                if self.background_process_handles.contains_key(&process_id) {
+                    self.process_output_subscribers
+                        .insert(process_id, plugin_name);
+                }
+            }
+
             // ==================== Virtual Buffer Commands (complex, kept inline) ====================
             PluginCommand::CreateVirtualBuffer {
                 name,
@@ -4784,6 +5658,7 @@ impl Editor {
                 show_cursors,
                 editing_disabled,
                 hidden_from_tabs,
+                display,
                 request_id,
             } => {
                 let buffer_id = self.create_virtual_buffer(name.clone(), mode.clone(), read_only);
@@ -4822,6 +5697,9 @@ impl Editor {
                         // Switch to the new buffer to display it
                         self.set_active_buffer(buffer_id);
                         tracing::debug!("Switched to virtual buffer {:?}", buffer_id);
+                        if let Some(display) = display {
+                            self.handle_set_buffer_display_options(buffer_id, display);
+                        }
 
                         // Send response if request_id is present
                         if let Some(req_id) = request_id {
@@ -4859,6 +5737,7 @@ impl Editor {
                 show_cursors,
                 editing_disabled,
                 line_wrap,
+                display,
                 request_id,
             } => {
                 // Check if this panel already exists (for idempotent operations)
@@ -4874,6 +5753,9 @@ impl Editor {
                             } else {
                                 tracing::info!("Updated existing panel '{}' content", pid);
                             }
+                            if let Some(display) = display {
+                                self.handle_set_buffer_display_options(existing_buffer_id, display);
+                            }
 
                             // Find and focus the split that contains this buffer
                             let splits = self.split_manager.splits_for_buffer(existing_buffer_id);
@@ -4989,6 +5871,11 @@ impl Editor {
                         }
                     };
 
+                // Display overrides go last so lineWrap applies to the new split
+                if let Some(display) = display {
+                    self.handle_set_buffer_display_options(buffer_id, display);
+                }
+
                 // Send response with buffer ID and split ID via callback resolution
                 // NOTE: Using VirtualBufferResult type for type-safe JSON serialization
                 if let Some(req_id) = request_id {
@@ -5036,6 +5923,7 @@ impl Editor {
                 show_cursors,
                 editing_disabled,
                 line_wrap,
+                display,
                 request_id,
             } => {
                 // Create the virtual buffer
@@ -5085,6 +5973,10 @@ impl Editor {
                     );
                 }
 
+                if let Some(display) = display {
+                    self.handle_set_buffer_display_options(buffer_id, display);
+                }
+
                 // Send response with buffer ID and split ID via callback resolution
                 if let Some(req_id) = request_id {
                     let result = fresh_core::api::VirtualBufferResult {
@@ -5122,6 +6014,9 @@ impl Editor {
             PluginCommand::ExecuteActions { actions } => {
                 self.handle_execute_actions(actions);
             }
+            PluginCommand::RunSearch { pattern, options } => {
+                self.handle_run_search(pattern, options);
+            }
             PluginCommand::GetBufferText {
                 buffer_id,
                 start,
@@ -5130,6 +6025,14 @@ impl Editor {
             } => {
                 self.handle_get_buffer_text(buffer_id, start, end, request_id);
             }
+            PluginCommand::ComputeDiff {
+                old_text,
+                new_text,
+                options,
+                request_id,
+            } => {
+                self.handle_compute_diff(old_text, new_text, options, request_id);
+            }
             PluginCommand::GetLineStartPosition {
                 buffer_id,
                 line,
@@ -5137,6 +6040,50 @@ impl Editor {
             } => {
                 self.handle_get_line_start_position(buffer_id, line, request_id);
             }
+            PluginCommand::GetBufferLines {
+                buffer_id,
+                start_line,
+                end_line,
+                request_id,
+            } => {
+                self.handle_get_buffer_lines(buffer_id, start_line..end_line, request_id);
+            }
+            PluginCommand::GetLineCount {
+                buffer_id,
+                request_id,
+            } => {
+                self.handle_get_line_count(buffer_id, request_id);
+            }
+            PluginCommand::OffsetToLineCol {
+                buffer_id,
+                offset,
+                request_id,
+            } => {
+                self.handle_offset_to_line_col(buffer_id, offset, request_id);
+            }
+            PluginCommand::LineColToOffset {
+                buffer_id,
+                line,
+                column,
+                request_id,
+            } => {
+                self.handle_line_col_to_offset(buffer_id, line, column, request_id);
+            }
+            PluginCommand::QuerySyntax {
+                buffer_id,
+                query,
+                request_id,
+            } => {
+                self.handle_query_syntax(buffer_id, &query, request_id);
+            }
+            PluginCommand::SearchBuffer {
+                buffer_id,
+                pattern,
+                options,
+                request_id,
+            } => {
+                self.handle_search_buffer(buffer_id, &pattern, &options, request_id);
+            }
             PluginCommand::SetEditorMode { mode } => {
                 self.handle_set_editor_mode(mode);
             }
@@ -5254,11 +6201,9 @@ impl Editor {
                 left_split,
                 right_split,
             } => {
-                let success = self.scroll_sync_manager.create_group_with_id(
-                    group_id,
-                    left_split,
-                    right_split,
-                );
+                let success = self
+                    .scroll_sync_manager
+                    .create_group_with_id(group_id, vec![left_split, right_split]);
                 if success {
                     tracing::debug!(
                         "Created scroll sync group {} for splits {:?} and {:?}",
@@ -5273,15 +6218,29 @@ impl Editor {
                     );
                 }
             }
+            PluginCommand::AddScrollSyncSplit { group_id, split_id } => {
+                if self.scroll_sync_manager.add_split(group_id, split_id) {
+                    tracing::debug!("Added {:?} to scroll sync group {}", split_id, group_id);
+                } else {
+                    tracing::warn!("Scroll sync group {} not found", group_id);
+                }
+            }
+            PluginCommand::RemoveScrollSyncSplit { group_id, split_id } => {
+                if self
+                    .scroll_sync_manager
+                    .remove_split_from_group(group_id, split_id)
+                {
+                    tracing::debug!("Removed {:?} from scroll sync group {}", split_id, group_id);
+                } else {
+                    tracing::warn!("{:?} is not in scroll sync group {}", split_id, group_id);
+                }
+            }
             PluginCommand::SetScrollSyncAnchors { group_id, anchors } => {
                 use crate::view::scroll_sync::SyncAnchor;
                 let anchor_count = anchors.len();
                 let sync_anchors: Vec<SyncAnchor> = anchors
                     .into_iter()
-                    .map(|(left_line, right_line)| SyncAnchor {
-                        left_line,
-                        right_line,
-                    })
+                    .map(|lines| SyncAnchor { lines })
                     .collect();
                 self.scroll_sync_manager.set_anchors(group_id, sync_anchors);
                 tracing::debug!(
@@ -5428,14 +6387,20 @@ impl Editor {
     #[cfg(feature = "plugins")]
     fn handle_list_plugins(&mut self, callback_id: JsCallbackId) {
         let plugins = self.plugin_manager.list_plugins();
-        // Serialize to JSON array of { name, path, enabled }
+        // Serialize to JSON array of { name, path, enabled, activationEvents, activatedBy }
         let json_array: Vec<serde_json::Value> = plugins
             .iter()
             .map(|p| {
                 serde_json::json!({
                     "name": p.name,
                     "path": p.path.to_string_lossy(),
-                    "enabled": p.enabled
+                    "enabled": p.enabled,
+                    "activationEvents": p
+                        .activation_events
+                        .iter()
+                        .map(|e| e.to_string())
+                        .collect::<Vec<_>>(),
+                    "activatedBy": p.activated_by
                 })
             })
             .collect();
@@ -5525,6 +6490,189 @@ impl Editor {
         }
     }
 
+    /// Resolve a plugin's buffer query with a JSON value, or reject it
+    fn resolve_buffer_query<T: serde::Serialize>(
+        &mut self,
+        request_id: u64,
+        result: Result<T, String>,
+    ) {
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        match result {
+            Ok(value) => {
+                let json = serde_json::to_string(&value).unwrap_or_else(|_| "null".to_string());
+                self.plugin_manager.resolve_callback(callback_id, json);
+            }
+            Err(error) => {
+                self.plugin_manager.reject_callback(callback_id, error);
+            }
+        }
+    }
+
+    /// Get lines of a buffer without their line endings
+    fn handle_get_buffer_lines(
+        &mut self,
+        buffer_id: BufferId,
+        lines: std::ops::Range<usize>,
+        request_id: u64,
+    ) {
+        let result = match self.buffers.get(&buffer_id) {
+            Some(_) if lines.start > lines.end => {
+                Err(format!("Invalid line range {}..{}", lines.start, lines.end))
+            }
+            Some(state) => Ok(lines
+                .map_while(|line| state.buffer.get_line(line))
+                .map(|bytes| {
+                    let text = String::from_utf8_lossy(&bytes);
+                    text.trim_end_matches(['\n', '\r']).to_string()
+                })
+                .collect::<Vec<_>>()),
+            None => Err(format!("Buffer {:?} not found", buffer_id)),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
+    /// Get the number of lines in a buffer
+    fn handle_get_line_count(&mut self, buffer_id: BufferId, request_id: u64) {
+        let result = match self.buffers.get(&buffer_id) {
+            Some(state) => state
+                .buffer
+                .line_count()
+                .ok_or_else(|| "Line count is not known for this large file".to_string()),
+            None => Err(format!("Buffer {:?} not found", buffer_id)),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
+    /// Convert a byte offset in a buffer to a line and byte column
+    fn handle_offset_to_line_col(&mut self, buffer_id: BufferId, offset: usize, request_id: u64) {
+        let result = match self.buffers.get(&buffer_id) {
+            Some(state) if offset > state.buffer.len() => Err(format!(
+                "Offset {} is past the end of the buffer ({} bytes)",
+                offset,
+                state.buffer.len()
+            )),
+            Some(state) => state
+                .buffer
+                .offset_to_position(offset)
+                .map(|position| {
+                    serde_json::json!({ "line": position.line, "column": position.column })
+                })
+                .ok_or_else(|| "Line numbers are not known for this large file".to_string()),
+            None => Err(format!("Buffer {:?} not found", buffer_id)),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
+    /// Convert a line and byte column in a buffer to a byte offset
+    fn handle_line_col_to_offset(
+        &mut self,
+        buffer_id: BufferId,
+        line: usize,
+        column: usize,
+        request_id: u64,
+    ) {
+        let result = match self.buffers.get(&buffer_id) {
+            Some(state) => match (
+                state.buffer.line_start_offset(line),
+                state.buffer.get_line(line),
+            ) {
+                (Some(start), Some(bytes)) => {
+                    let content_len = bytes
+                        .iter()
+                        .rposition(|b| *b != b'\n' && *b != b'\r')
+                        .map_or(0, |last| last + 1);
+                    if column <= content_len {
+                        Ok(start + column)
+                    } else {
+                        Err(format!(
+                            "Column {} is past the end of line {} ({} bytes)",
+                            column, line, content_len
+                        ))
+                    }
+                }
+                _ => Err(format!("Line {} is out of range", line)),
+            },
+            None => Err(format!("Buffer {:?} not found", buffer_id)),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
+    /// Run a tree-sitter query against a buffer's syntax tree
+    fn handle_query_syntax(&mut self, buffer_id: BufferId, query: &str, request_id: u64) {
+        use crate::primitives::highlighter::Language;
+        use crate::primitives::syntax_query::query_syntax;
+        use fresh_core::api::SyntaxCapture;
+
+        let result = match self.buffers.get(&buffer_id) {
+            Some(state) => {
+                let language = Language::from_id(&state.language)
+                    .or_else(|| state.buffer.file_path().and_then(Language::from_path));
+                match (language, state.buffer.to_string()) {
+                    (None, _) => Err(format!(
+                        "No tree-sitter grammar for language '{}'",
+                        state.language
+                    )),
+                    (Some(_), None) => Err("Buffer text is not fully loaded".to_string()),
+                    (Some(language), Some(text)) => {
+                        query_syntax(&text, &language, query).map(|captures| {
+                            captures
+                                .into_iter()
+                                .map(|capture| SyntaxCapture {
+                                    name: capture.name,
+                                    start: capture.range.start,
+                                    end: capture.range.end,
+                                    text: text[capture.range].to_string(),
+                                })
+                                .collect::<Vec<_>>()
+                        })
+                    }
+                }
+            }
+            None => Err(format!("Buffer {:?} not found", buffer_id)),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
+    /// Find the matches of a pattern in a buffer, with the same pattern
+    /// rules as the search prompt
+    fn handle_search_buffer(
+        &mut self,
+        buffer_id: BufferId,
+        pattern: &str,
+        options: &fresh_core::api::BufferSearchOptions,
+        request_id: u64,
+    ) {
+        use fresh_core::api::SearchMatch;
+
+        let source = render::search_regex_pattern(
+            pattern,
+            options.regex.unwrap_or(false),
+            options.whole_word.unwrap_or(false),
+        );
+        let regex = regex::bytes::RegexBuilder::new(&source)
+            .case_insensitive(!options.case_sensitive.unwrap_or(false))
+            .build();
+
+        let result = match (regex, self.buffers.get_mut(&buffer_id)) {
+            (_, None) => Err(format!("Buffer {:?} not found", buffer_id)),
+            (Err(e), _) => Err(format!("Invalid regex: {}", e)),
+            (Ok(regex), Some(state)) => state
+                .buffer
+                .find_all_regex(&regex, options.max_results)
+                .map(|ranges| {
+                    ranges
+                        .into_iter()
+                        .map(|range| SearchMatch {
+                            start: range.start,
+                            end: range.end,
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .map_err(|e| e.to_string()),
+        };
+        self.resolve_buffer_query(request_id, result);
+    }
+
     /// Set the global editor mode (for vi mode)
     fn handle_set_editor_mode(&mut self, mode: Option<String>) {
         self.editor_mode = mode.clone();
@@ -5587,7 +6735,7 @@ impl Editor {
 /// - Special keys: "RET", "TAB", "ESC", "SPC", "DEL", "BS"
 /// - Modifiers: "C-" (Control), "M-" (Alt/Meta), "S-" (Shift)
 /// - Combinations: "C-n", "M-x", "C-M-s", etc.
-fn parse_key_string(key_str: &str) -> Option<(KeyCode, KeyModifiers)> {
+pub(crate) fn parse_key_string(key_str: &str) -> Option<(KeyCode, KeyModifiers)> {
     use crossterm::event::{KeyCode, KeyModifiers};
 
     let mut modifiers = KeyModifiers::NONE;
@@ -5618,6 +6766,7 @@ fn parse_key_string(key_str: &str) -> Option<(KeyCode, KeyModifiers)> {
         "ESC" | "ESCAPE" => KeyCode::Esc,
         "SPC" | "SPACE" => KeyCode::Char(' '),
         "DEL" | "DELETE" => KeyCode::Delete,
+        "INS" | "INSERT" => KeyCode::Insert,
         "BS" | "BACKSPACE" => KeyCode::Backspace,
         "UP" => KeyCode::Up,
         "DOWN" => KeyCode::Down,
@@ -5730,8 +6879,10 @@ mod tests {
     }
 
     #[test]
-    fn test_action_to_events_insert_char() {
-        let config = Config::default();
+    fn test_cursor_style_follows_mode() {
+        let mut config = Config::default();
+        config.editor.cursor_style = CursorStyle::BlinkingBar;
+        config.editor.cursor_style_overwrite = CursorStyle::SteadyUnderline;
         let (dir_context, _temp) = test_dir_context();
         let mut editor = Editor::new(
             config,
@@ -5743,24 +6894,33 @@ mod tests {
         )
         .unwrap();
 
-        let events = editor.action_to_events(Action::InsertChar('a'));
-        assert!(events.is_some());
+        // Reported once, then only when it changes
+        assert_eq!(
+            editor.take_cursor_style_change(),
+            Some(CursorStyle::BlinkingBar)
+        );
+        assert_eq!(editor.take_cursor_style_change(), None);
 
-        let events = events.unwrap();
-        assert_eq!(events.len(), 1);
+        editor.toggle_overwrite_mode();
+        assert_eq!(
+            editor.take_cursor_style_change(),
+            Some(CursorStyle::SteadyUnderline)
+        );
 
-        match &events[0] {
-            Event::Insert { position, text, .. } => {
-                assert_eq!(*position, 0);
-                assert_eq!(text, "a");
-            }
-            _ => panic!("Expected Insert event"),
-        }
+        // A plugin mode listed in cursor_style_modes takes precedence
+        editor.handle_set_editor_mode(Some("vi-normal".to_string()));
+        assert_eq!(
+            editor.take_cursor_style_change(),
+            Some(CursorStyle::SteadyBlock)
+        );
+        editor.handle_set_editor_mode(Some("vi-find-char".to_string()));
+        assert_eq!(editor.cursor_style(), CursorStyle::SteadyUnderline);
     }
 
     #[test]
-    fn test_action_to_events_move_right() {
-        let config = Config::default();
+    fn test_unfocused_terminal_pauses_blinking_and_slows_polling() {
+        let mut config = Config::default();
+        config.editor.cursor_style = CursorStyle::BlinkingBar;
         let (dir_context, _temp) = test_dir_context();
         let mut editor = Editor::new(
             config,
@@ -5771,22 +6931,85 @@ mod tests {
             test_filesystem(),
         )
         .unwrap();
+        let interval = Duration::from_millis(100);
+        assert!(editor.is_terminal_focused());
+        assert_eq!(editor.background_poll_interval(interval), interval);
+        editor.take_cursor_style_change();
 
-        // Insert some text first
-        let state = editor.active_state_mut();
-        state.apply(&Event::Insert {
-            position: 0,
-            text: "hello".to_string(),
-            cursor_id: state.cursors.primary_id(),
-        });
-
-        let events = editor.action_to_events(Action::MoveRight);
-        assert!(events.is_some());
+        editor.set_terminal_focused(false);
+        assert_eq!(
+            editor.take_cursor_style_change(),
+            Some(CursorStyle::SteadyBar)
+        );
+        assert!(editor.background_poll_interval(interval) > interval);
 
-        let events = events.unwrap();
-        assert_eq!(events.len(), 1);
+        // Everything resumes as soon as focus returns
+        editor.set_terminal_focused(true);
+        assert_eq!(
+            editor.take_cursor_style_change(),
+            Some(CursorStyle::BlinkingBar)
+        );
+        assert_eq!(editor.background_poll_interval(interval), interval);
+    }
 
-        match &events[0] {
+    #[test]
+    fn test_action_to_events_insert_char() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+
+        let events = editor.action_to_events(Action::InsertChar('a'));
+        assert!(events.is_some());
+
+        let events = events.unwrap();
+        assert_eq!(events.len(), 1);
+
+        match &events[0] {
+            Event::Insert { position, text, .. } => {
+                assert_eq!(*position, 0);
+                assert_eq!(text, "a");
+            }
+            _ => panic!("Expected Insert event"),
+        }
+    }
+
+    #[test]
+    fn test_action_to_events_move_right() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+
+        // Insert some text first
+        let state = editor.active_state_mut();
+        state.apply(&Event::Insert {
+            position: 0,
+            text: "hello".to_string(),
+            cursor_id: state.cursors.primary_id(),
+        });
+
+        let events = editor.action_to_events(Action::MoveRight);
+        assert!(events.is_some());
+
+        let events = events.unwrap();
+        assert_eq!(events.len(), 1);
+
+        match &events[0] {
             Event::MoveCursor {
                 new_position,
                 new_anchor,
@@ -7362,4 +8585,527 @@ mod tests {
             .sum();
         assert!(view_state.tab_scroll_offset <= total_width);
     }
+
+    #[test]
+    fn test_plugin_completions_are_merged_into_popup() {
+        use fresh_core::api::{CompletionItem, CompletionProvider};
+
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.active_state_mut().language = "rust".to_string();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "fo".to_string(),
+            cursor_id,
+        });
+
+        let item = |label: &str| CompletionItem {
+            label: label.to_string(),
+            insert_text: None,
+            detail: None,
+            kind: None,
+        };
+        for handler_name in ["wordsA", "wordsB"] {
+            editor
+                .handle_plugin_command(PluginCommand::RegisterCompletionProvider {
+                    provider: CompletionProvider {
+                        plugin_name: "words".to_string(),
+                        languages: vec!["rust".to_string()],
+                        trigger_characters: vec![],
+                        handler_name: handler_name.to_string(),
+                    },
+                })
+                .unwrap();
+        }
+
+        // Without a language server only the providers are asked
+        editor.request_completion().unwrap();
+        assert_eq!(editor.pending_completion_request, None);
+        let request_id = editor.pending_plugin_completion_request.unwrap();
+
+        // Items not matching the typed prefix are filtered out
+        editor
+            .handle_plugin_command(PluginCommand::ProvideCompletions {
+                request_id,
+                items: vec![item("food"), item("bar")],
+            })
+            .unwrap();
+        let popups = &editor.active_state().popups;
+        assert!(popups.is_completion_popup());
+        assert_eq!(popups.top().unwrap().item_count(), 1);
+
+        // A second provider's items join the open popup
+        editor
+            .handle_plugin_command(PluginCommand::ProvideCompletions {
+                request_id,
+                items: vec![item("format")],
+            })
+            .unwrap();
+        assert_eq!(editor.active_state().popups.top().unwrap().item_count(), 2);
+
+        // Answers to an older request are ignored
+        editor
+            .handle_plugin_command(PluginCommand::ProvideCompletions {
+                request_id: request_id + 1,
+                items: vec![item("fork")],
+            })
+            .unwrap();
+        assert_eq!(editor.active_state().popups.top().unwrap().item_count(), 2);
+    }
+
+    #[test]
+    fn test_plugin_hover_coexists_with_lsp_hover() {
+        use fresh_core::api::HoverProvider;
+
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.active_state_mut().language = "rust".to_string();
+        editor
+            .handle_plugin_command(PluginCommand::RegisterHoverProvider {
+                provider: HoverProvider {
+                    plugin_name: "docs".to_string(),
+                    languages: vec!["rust".to_string()],
+                    handler_name: "provideDocs".to_string(),
+                },
+            })
+            .unwrap();
+
+        // Without a language server only the provider is asked
+        editor.request_hover().unwrap();
+        assert_eq!(editor.pending_hover_request, None);
+        let request_id = editor.pending_plugin_hover_request.unwrap();
+
+        editor
+            .handle_plugin_command(PluginCommand::ProvideHover {
+                request_id,
+                markdown: Some("**from plugin**".to_string()),
+            })
+            .unwrap();
+        assert!(editor.active_state().popups.is_hover_popup());
+        assert_eq!(editor.hover_sections.len(), 1);
+
+        // The language server's answer to the same request joins the popup
+        editor.pending_hover_request = Some(request_id);
+        editor.handle_hover_response(request_id, "from lsp".to_string(), true, None);
+        assert_eq!(editor.active_state().popups.all().len(), 1);
+        assert_eq!(editor.hover_sections.len(), 2);
+
+        // Answers to an older request are ignored
+        editor
+            .handle_plugin_command(PluginCommand::ProvideHover {
+                request_id: request_id + 1,
+                markdown: Some("stale".to_string()),
+            })
+            .unwrap();
+        assert_eq!(editor.hover_sections.len(), 2);
+    }
+
+    #[test]
+    fn test_plugin_formatter_result_is_one_undoable_edit() {
+        use fresh_core::api::PluginFormatter;
+
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.active_state_mut().language = "markdown".to_string();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "#  title".to_string(),
+            cursor_id,
+        });
+        editor
+            .handle_plugin_command(PluginCommand::RegisterFormatter {
+                formatter: PluginFormatter {
+                    plugin_name: "md".to_string(),
+                    language: "markdown".to_string(),
+                    handler_name: "formatMarkdown".to_string(),
+                },
+            })
+            .unwrap();
+
+        // Unsaved buffers can be formatted by plugins
+        editor.format_buffer().unwrap();
+        let request_id = editor.pending_plugin_format.as_ref().unwrap().request_id;
+        editor
+            .handle_plugin_command(PluginCommand::ProvideFormattedText {
+                request_id,
+                text: Some("# title\n".to_string()),
+            })
+            .unwrap();
+        assert!(editor.pending_plugin_format.is_none());
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "# title\n"
+        );
+
+        editor.handle_undo();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "#  title"
+        );
+
+        // A result for text the user has since edited is discarded
+        editor.format_buffer().unwrap();
+        let request_id = editor.pending_plugin_format.as_ref().unwrap().request_id;
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 8,
+            text: "!".to_string(),
+            cursor_id,
+        });
+        editor
+            .handle_plugin_command(PluginCommand::ProvideFormattedText {
+                request_id,
+                text: Some("# title\n".to_string()),
+            })
+            .unwrap();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "#  title!"
+        );
+    }
+
+    #[test]
+    fn test_plugin_formatter_runs_on_save() {
+        use crate::config::{FormatterConfig, LanguageConfig};
+        use fresh_core::api::PluginFormatter;
+
+        let mut config = Config::default();
+        config.languages.insert(
+            "plaintext".to_string(),
+            LanguageConfig {
+                extensions: vec!["txt".to_string()],
+                filenames: vec![],
+                grammar: "plaintext".to_string(),
+                comment_prefix: None,
+                auto_indent: false,
+                highlighter: Default::default(),
+                textmate_grammar: None,
+                show_whitespace_tabs: true,
+                use_tabs: false,
+                tab_size: None,
+                // Replaced by the plugin formatter
+                formatter: Some(FormatterConfig {
+                    command: "sort".to_string(),
+                    args: vec![],
+                    stdin: true,
+                    timeout_ms: 5000,
+                }),
+                format_on_save: true,
+                code_actions_on_save: vec![],
+                on_save: vec![],
+            },
+        );
+        let (dir_context, temp) = test_dir_context();
+        let file_path = temp.path().join("notes.txt");
+        std::fs::write(&file_path, "b\na\n").unwrap();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.open_file(&file_path).unwrap();
+        let language = editor.active_state().language.clone();
+        editor
+            .handle_plugin_command(PluginCommand::RegisterFormatter {
+                formatter: PluginFormatter {
+                    plugin_name: "upper".to_string(),
+                    language,
+                    handler_name: "formatUpper".to_string(),
+                },
+            })
+            .unwrap();
+
+        editor.save().unwrap();
+        let request_id = editor.pending_plugin_format.as_ref().unwrap().request_id;
+        editor
+            .handle_plugin_command(PluginCommand::ProvideFormattedText {
+                request_id,
+                text: Some("B\nA\n".to_string()),
+            })
+            .unwrap();
+
+        // The formatted text is saved again
+        assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "B\nA\n");
+        assert!(!editor.active_state().buffer.is_modified());
+    }
+
+    #[test]
+    fn test_plugin_evaluator_falls_back_to_calculator() {
+        use crate::model::cursor::Cursor;
+        use fresh_core::api::PluginEvaluator;
+
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.active_state_mut().language = "python".to_string();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "len('abc') and 6 * 7".to_string(),
+            cursor_id,
+        });
+        let cursors = &mut editor.active_state_mut().cursors;
+        *cursors.primary_mut() = Cursor::with_selection(0, 10);
+        cursors.add(Cursor::with_selection(15, 20));
+        editor
+            .handle_plugin_command(PluginCommand::RegisterEvaluator {
+                evaluator: PluginEvaluator {
+                    plugin_name: "py".to_string(),
+                    language: "python".to_string(),
+                    handler_name: "evalPython".to_string(),
+                },
+            })
+            .unwrap();
+
+        editor.evaluate_selection(true);
+        let request_id = editor.pending_evaluation.as_ref().unwrap().request_id;
+        // The plugin leaves the arithmetic to the built-in calculator
+        editor
+            .handle_plugin_command(PluginCommand::ProvideEvaluation {
+                request_id,
+                results: vec![Some("3".to_string()), None],
+            })
+            .unwrap();
+        assert!(editor.pending_evaluation.is_none());
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "3 and 42"
+        );
+
+        editor.handle_undo();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "len('abc') and 6 * 7"
+        );
+    }
+
+    #[test]
+    fn test_plugin_edits_move_past_user_typing() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        let buffer_id = editor.active_buffer();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "let x=1;".to_string(),
+            cursor_id,
+        });
+
+        // A formatter reads the buffer here...
+        let base_version = editor.active_state().buffer.version();
+
+        // ...while the user types at the start
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "// a\n".to_string(),
+            cursor_id,
+        });
+
+        // Its edits, one after another, use offsets into what it read
+        for command in [
+            PluginCommand::DeleteRange {
+                buffer_id,
+                range: 5..6,
+                base_version: Some(base_version),
+            },
+            PluginCommand::InsertText {
+                buffer_id,
+                position: 5,
+                text: " = ".to_string(),
+                base_version: Some(base_version),
+            },
+        ] {
+            editor.handle_plugin_command(command).unwrap();
+        }
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "// a\nlet x = 1;"
+        );
+
+        // An edit inside text the user has since deleted is skipped
+        let read_version = editor.active_state().buffer.version();
+        editor.apply_event_to_active_buffer(&Event::Delete {
+            range: 9..14,
+            deleted_text: "x = 1".to_string(),
+            cursor_id,
+        });
+        editor
+            .handle_plugin_command(PluginCommand::InsertText {
+                buffer_id,
+                position: 11,
+                text: "y".to_string(),
+                base_version: Some(read_version),
+            })
+            .unwrap();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "// a\nlet ;"
+        );
+    }
+
+    #[test]
+    fn test_plugin_apply_edits_is_one_undo_step() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        let buffer_id = editor.active_buffer();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "var x=1;".to_string(),
+            cursor_id,
+        });
+        let edit = |start: usize, end: usize, text: &str| fresh_core::api::BufferEdit {
+            range: start..end,
+            text: text.to_string(),
+        };
+
+        // Offsets refer to the text before the edits, whatever their order
+        editor
+            .handle_plugin_command(PluginCommand::ApplyEdits {
+                buffer_id,
+                edits: vec![edit(5, 6, " = "), edit(0, 3, "let"), edit(5, 5, "/*eq*/")],
+                base_version: None,
+            })
+            .unwrap();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "let x/*eq*/ = 1;"
+        );
+
+        editor.handle_undo();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "var x=1;"
+        );
+
+        // Overlapping edits are rejected as a whole
+        editor
+            .handle_plugin_command(PluginCommand::ApplyEdits {
+                buffer_id,
+                edits: vec![edit(0, 5, "a"), edit(4, 6, "b")],
+                base_version: None,
+            })
+            .unwrap();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "var x=1;"
+        );
+    }
+
+    #[test]
+    fn test_generate_doc_comment_tabstops() {
+        let config = Config::default();
+        let (dir_context, _temp) = test_dir_context();
+        let mut editor = Editor::new(
+            config,
+            80,
+            24,
+            dir_context,
+            crate::view::color_support::ColorCapability::TrueColor,
+            test_filesystem(),
+        )
+        .unwrap();
+        editor.active_state_mut().language = "rust".to_string();
+        let cursor_id = editor.active_state().cursors.primary_id();
+        editor.apply_event_to_active_buffer(&Event::Insert {
+            position: 0,
+            text: "fn f(x: u8) {}\n".to_string(),
+            cursor_id,
+        });
+        editor.apply_event_to_active_buffer(&Event::MoveCursor {
+            cursor_id,
+            old_position: 15,
+            new_position: 3,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+
+        // The skeleton is a single undo step
+        editor.handle_action(Action::GenerateDocComment).unwrap();
+        editor.handle_undo();
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "fn f(x: u8) {}\n"
+        );
+
+        editor.handle_action(Action::GenerateDocComment).unwrap();
+        assert_eq!(editor.active_state().cursors.primary().position, 4);
+        for ch in "Doc".chars() {
+            editor.handle_action(Action::InsertChar(ch)).unwrap();
+        }
+
+        // Tab moves to the parameter, which has shifted past the typed text
+        editor.handle_action(Action::InsertTab).unwrap();
+        assert_eq!(editor.active_state().cursors.primary().position, 44);
+        editor.handle_action(Action::InsertChar('x')).unwrap();
+
+        // After the last tabstop Tab leaves the snippet, then inserts as usual
+        editor.handle_action(Action::InsertTab).unwrap();
+        assert_eq!(editor.active_state().cursors.primary().position, 46);
+        assert!(editor.snippet_session.is_none());
+        assert_eq!(
+            editor.active_state().buffer.to_string().unwrap(),
+            "/// Doc\n///\n/// # Arguments\n///\n/// * `x` - x\nfn f(x: u8) {}\n"
+        );
+    }
 }
@@ -7,6 +7,7 @@
 //! - Split separator dragging
 //! - Text selection via mouse
 
+use super::context_menu::ContextMenuTarget;
 use super::*;
 use crate::input::keybindings::Action;
 use crate::model::event::{SplitDirection, SplitId};
@@ -68,6 +69,11 @@ impl Editor {
             return Ok(false);
         }
 
+        // When the cheat sheet is open, ignore mouse events
+        if self.cheat_sheet.is_some() {
+            return Ok(false);
+        }
+
         // Cancel LSP rename prompt on any mouse interaction
         let mut needs_render = false;
         if let Some(ref prompt) = self.prompt {
@@ -148,6 +154,9 @@ impl Editor {
                 self.mouse_state.drag_start_popup_scroll = None;
                 // Clear popup text selection drag state (selection remains in popup)
                 self.mouse_state.selecting_in_popup = None;
+                // Clear floating window move/resize drag state
+                self.mouse_state.dragging_floating_window = None;
+                self.mouse_state.drag_start_floating_window = None;
 
                 // If we finished dragging a separator, resize visible terminals
                 if was_dragging_separator {
@@ -204,6 +213,8 @@ impl Editor {
                     // Scroll the popup content (works for all popups including completion)
                     self.scroll_popup(-3);
                     needs_render = true;
+                } else if self.handle_floating_window_scroll(col, row, -3) {
+                    needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
                     if self.terminal_mode && self.is_terminal_buffer(self.active_buffer()) {
@@ -229,6 +240,8 @@ impl Editor {
                     // Scroll the popup content (works for all popups including completion)
                     self.scroll_popup(3);
                     needs_render = true;
+                } else if self.handle_floating_window_scroll(col, row, 3) {
+                    needs_render = true;
                 } else {
                     // If in terminal mode, exit to scrollback mode first so scrolling works
                     if self.terminal_mode && self.is_terminal_buffer(self.active_buffer()) {
@@ -278,13 +291,8 @@ impl Editor {
 
             // If hovering over a menu dropdown item, check if it's a submenu and open it
             if let Some(HoverTarget::MenuDropdownItem(_, item_idx)) = new_target.clone() {
-                let all_menus: Vec<crate::config::Menu> = self
-                    .menus
-                    .menus
-                    .iter()
-                    .chain(self.menu_state.plugin_menus.iter())
-                    .cloned()
-                    .collect();
+                let all_menus: Vec<crate::config::Menu> =
+                    self.menu_state.all_menus(&self.menus.menus);
 
                 // If this item is the parent of the currently open submenu, keep it open.
                 // This prevents blinking when hovering over the parent item of an open submenu.
@@ -357,13 +365,8 @@ impl Editor {
                     self.menu_state.submenu_path.truncate(depth);
                 }
 
-                let all_menus: Vec<crate::config::Menu> = self
-                    .menus
-                    .menus
-                    .iter()
-                    .chain(self.menu_state.plugin_menus.iter())
-                    .cloned()
-                    .collect();
+                let all_menus: Vec<crate::config::Menu> =
+                    self.menu_state.all_menus(&self.menus.menus);
 
                 // Get the items at this depth
                 if let Some(items) = self
@@ -397,11 +400,10 @@ impl Editor {
             }
         }
 
-        // Handle tab context menu hover - update highlighted item
-        if let Some(HoverTarget::TabContextMenuItem(item_idx)) = new_target.clone() {
-            if let Some(ref mut menu) = self.tab_context_menu {
-                if menu.highlighted != item_idx {
-                    menu.highlighted = item_idx;
+        // Handle context menu hover - update highlighted item
+        if let Some(HoverTarget::ContextMenuItem(item_idx)) = new_target.clone() {
+            if let Some(ref mut menu) = self.context_menu {
+                if menu.highlight(item_idx) {
                     return true;
                 }
             }
@@ -577,7 +579,7 @@ impl Editor {
         }
 
         // Start tracking new hover position
-        self.mouse_state.lsp_hover_state = Some((byte_pos, std::time::Instant::now(), col, row));
+        self.mouse_state.lsp_hover_state = Some((byte_pos, self.time_source.now(), col, row));
         self.mouse_state.lsp_hover_request_sent = false;
     }
 
@@ -597,23 +599,10 @@ impl Editor {
 
     /// Compute what hover target is at the given position
     fn compute_hover_target(&self, col: u16, row: u16) -> Option<HoverTarget> {
-        // Check tab context menu first (it's rendered on top)
-        if let Some(ref menu) = self.tab_context_menu {
-            let menu_x = menu.position.0;
-            let menu_y = menu.position.1;
-            let menu_width = 22u16;
-            let items = super::types::TabContextMenuItem::all();
-            let menu_height = items.len() as u16 + 2;
-
-            if col >= menu_x
-                && col < menu_x + menu_width
-                && row > menu_y
-                && row < menu_y + menu_height - 1
-            {
-                let item_idx = (row - menu_y - 1) as usize;
-                if item_idx < items.len() {
-                    return Some(HoverTarget::TabContextMenuItem(item_idx));
-                }
+        // Check context menu first (it's rendered on top)
+        if let Some(ref menu) = self.context_menu {
+            if let Some(idx) = menu.entry_at(col, row) {
+                return Some(HoverTarget::ContextMenuItem(idx));
             }
         }
 
@@ -987,11 +976,9 @@ impl Editor {
         row: u16,
         modifiers: crossterm::event::KeyModifiers,
     ) -> AnyhowResult<()> {
-        // Check if click is on tab context menu first
-        if self.tab_context_menu.is_some() {
-            if let Some(result) = self.handle_tab_context_menu_click(col, row) {
-                return result;
-            }
+        // An open context menu takes the click, and closes on a click outside it
+        if self.context_menu.is_some() {
+            return self.handle_context_menu_click(col, row);
         }
 
         // Dismiss transient popups (like hover) when clicking outside them
@@ -1196,13 +1183,7 @@ impl Editor {
 
         // Check if click is on an open menu dropdown
         if let Some(active_idx) = self.menu_state.active_menu {
-            let all_menus: Vec<crate::config::Menu> = self
-                .menus
-                .menus
-                .iter()
-                .chain(self.menu_state.plugin_menus.iter())
-                .cloned()
-                .collect();
+            let all_menus: Vec<crate::config::Menu> = self.menu_state.all_menus(&self.menus.menus);
 
             if let Some(menu) = all_menus.get(active_idx) {
                 // Handle click on menu dropdown chain (including submenus)
@@ -1216,6 +1197,11 @@ impl Editor {
             return Ok(());
         }
 
+        // Check if click is on a floating window (rendered above the splits)
+        if self.handle_floating_window_click(col, row) {
+            return Ok(());
+        }
+
         // Check if click is on file explorer
         if let Some(explorer_area) = self.cached_layout.file_explorer_area {
             if col >= explorer_area.x
@@ -1267,6 +1253,21 @@ impl Editor {
         // Check if click is on status bar indicators
         if let Some((status_row, _status_x, _status_width)) = self.cached_layout.status_bar_area {
             if row == status_row {
+                // Check template segments with a configured click command
+                let segment_command = self
+                    .cached_layout
+                    .status_bar_segment_commands
+                    .iter()
+                    .find(|((seg_row, seg_start, seg_end), _)| {
+                        row == *seg_row && col >= *seg_start && col < *seg_end
+                    })
+                    .map(|(_, command)| command.clone());
+                if let Some(command) = segment_command {
+                    let action = Action::from_str(&command, &HashMap::new())
+                        .unwrap_or(Action::PluginAction(command));
+                    return self.handle_action(action);
+                }
+
                 // Check line ending indicator - click opens line ending selector
                 if let Some((le_row, le_start, le_end)) =
                     self.cached_layout.status_bar_line_ending_area
@@ -1536,6 +1537,11 @@ impl Editor {
 
     /// Handle mouse drag event
     pub(super) fn handle_mouse_drag(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
+        // If moving or resizing a floating window, update its area
+        if self.handle_floating_window_drag(col, row) {
+            return Ok(());
+        }
+
         // If dragging scrollbar, update scroll position
         if let Some(dragging_split_id) = self.mouse_state.dragging_scrollbar {
             // Find the buffer and scrollbar rect for this split
@@ -1813,23 +1819,15 @@ impl Editor {
 
     /// Handle right-click event
     pub(super) fn handle_right_click(&mut self, col: u16, row: u16) -> AnyhowResult<()> {
-        // First check if a tab context menu is open and the click is on a menu item
-        if let Some(ref menu) = self.tab_context_menu {
-            let menu_x = menu.position.0;
-            let menu_y = menu.position.1;
-            let menu_width = 22u16; // "Close to the Right" + padding
-            let menu_height = super::types::TabContextMenuItem::all().len() as u16 + 2; // items + borders
-
-            // Check if click is inside the menu
-            if col >= menu_x
-                && col < menu_x + menu_width
-                && row >= menu_y
-                && row < menu_y + menu_height
-            {
-                // Click inside menu - let left-click handler deal with it
-                return Ok(());
-            }
+        // Right-clicking inside an open context menu does nothing
+        if self
+            .context_menu
+            .as_ref()
+            .is_some_and(|menu| menu.contains(col, row))
+        {
+            return Ok(());
         }
+        self.context_menu = None;
 
         // Check if right-click is on a tab
         let tab_hit =
@@ -1841,87 +1839,44 @@ impl Editor {
                     _ => None,
                 },
             );
-
         if let Some((split_id, buffer_id)) = tab_hit {
-            // Open tab context menu
-            self.tab_context_menu = Some(TabContextMenu::new(buffer_id, split_id, col, row + 1));
-        } else {
-            // Click outside tab - close context menu if open
-            self.tab_context_menu = None;
-        }
-
-        Ok(())
-    }
-
-    /// Handle left-click on tab context menu
-    pub(super) fn handle_tab_context_menu_click(
-        &mut self,
-        col: u16,
-        row: u16,
-    ) -> Option<AnyhowResult<()>> {
-        let menu = self.tab_context_menu.as_ref()?;
-        let menu_x = menu.position.0;
-        let menu_y = menu.position.1;
-        let menu_width = 22u16;
-        let items = super::types::TabContextMenuItem::all();
-        let menu_height = items.len() as u16 + 2; // items + borders
-
-        // Check if click is inside the menu area
-        if col < menu_x || col >= menu_x + menu_width || row < menu_y || row >= menu_y + menu_height
-        {
-            // Click outside menu - close it
-            self.tab_context_menu = None;
-            return Some(Ok(()));
-        }
-
-        // Check if click is on the border (first or last row)
-        if row == menu_y || row == menu_y + menu_height - 1 {
-            return Some(Ok(()));
+            self.open_context_menu(
+                ContextMenuTarget::Tab {
+                    buffer_id,
+                    split_id,
+                },
+                col,
+                row + 1,
+            );
+            return Ok(());
         }
 
-        // Calculate which item was clicked (accounting for border)
-        let item_idx = (row - menu_y - 1) as usize;
-        if item_idx >= items.len() {
-            return Some(Ok(()));
+        // Check if right-click is in the file explorer
+        if let Some(explorer_area) = self.cached_layout.file_explorer_area {
+            if col >= explorer_area.x
+                && col < explorer_area.x + explorer_area.width
+                && row >= explorer_area.y
+                && row < explorer_area.y + explorer_area.height
+            {
+                self.select_file_explorer_row(row, explorer_area);
+                self.open_context_menu(ContextMenuTarget::FileExplorer, col, row);
+                return Ok(());
+            }
         }
 
-        // Get the menu state before closing it
-        let buffer_id = menu.buffer_id;
-        let split_id = menu.split_id;
-        let item = items[item_idx];
-
-        // Close the menu
-        self.tab_context_menu = None;
-
-        // Execute the action
-        Some(self.execute_tab_context_menu_action(item, buffer_id, split_id))
-    }
-
-    /// Execute a tab context menu action
-    fn execute_tab_context_menu_action(
-        &mut self,
-        item: super::types::TabContextMenuItem,
-        buffer_id: BufferId,
-        split_id: SplitId,
-    ) -> AnyhowResult<()> {
-        use super::types::TabContextMenuItem;
-
-        match item {
-            TabContextMenuItem::Close => {
-                self.close_tab_in_split(buffer_id, split_id);
-            }
-            TabContextMenuItem::CloseOthers => {
-                self.close_other_tabs_in_split(buffer_id, split_id);
-            }
-            TabContextMenuItem::CloseToRight => {
-                self.close_tabs_to_right_in_split(buffer_id, split_id);
-            }
-            TabContextMenuItem::CloseToLeft => {
-                self.close_tabs_to_left_in_split(buffer_id, split_id);
-            }
-            TabContextMenuItem::CloseAll => {
-                self.close_all_tabs_in_split(split_id);
-            }
+        // Check if right-click is in a split's text area
+        let split_hit = self.cached_layout.split_areas.iter().find_map(
+            |(split_id, buffer_id, content_rect, _, _, _)| {
+                (col >= content_rect.x
+                    && col < content_rect.x + content_rect.width
+                    && row >= content_rect.y
+                    && row < content_rect.y + content_rect.height)
+                    .then_some((*split_id, *buffer_id, *content_rect))
+            },
+        );
+        if let Some((split_id, buffer_id, content_rect)) = split_hit {
+            self.place_cursor_for_context_menu(col, row, split_id, buffer_id, content_rect);
+            self.open_context_menu(ContextMenuTarget::Editor, col, row);
         }
 
         Ok(())
@@ -1,16 +1,23 @@
 //! On-save action execution.
 //!
 //! This module handles running configured actions when files are saved,
-//! such as formatters, linters, and other tools.
+//! such as LSP code actions, formatters, linters, and other tools.
 
+use std::collections::VecDeque;
 use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::time::Duration;
+use std::time::{Duration, Instant};
+
+use lsp_types::{CodeActionKind, CodeActionOrCommand, TextEdit, Uri, WorkspaceEdit};
 
 use super::Editor;
-use crate::config::{FormatterConfig, OnSaveAction};
-use crate::model::event::Event;
+use crate::config::{FormatterConfig, LanguageConfig, OnSaveAction};
+use crate::config_io::{expand_path, expand_path_with};
+use crate::model::event::{BufferId, Event};
+use crate::primitives::highlighter::Language;
+use crate::primitives::trailing_whitespace::{self, multiline_string_ranges};
+use crate::services::editorconfig;
 use crate::services::lsp::manager::detect_language;
 use rust_i18n::t;
 
@@ -24,6 +31,50 @@ enum ActionResult {
     Error(String),
 }
 
+/// LSP code actions still to apply to a buffer that was just saved.
+///
+/// Kinds are requested one at a time so that each request sees the edits made
+/// for the previous one. Formatting and on-save commands run once every kind
+/// has been applied or has timed out.
+pub(super) struct SaveCodeActions {
+    buffer_id: BufferId,
+    path: PathBuf,
+    lang_config: LanguageConfig,
+    /// Kinds not requested yet
+    remaining: VecDeque<String>,
+    /// Kind, request ID and deadline of the request in flight
+    in_flight: Option<(String, u64, Instant)>,
+    /// Kinds skipped because the server did not answer in time
+    timed_out: Vec<String>,
+    ran_any_action: bool,
+}
+
+/// How long to wait for a plugin formatter (the default formatter command timeout)
+const PLUGIN_FORMAT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A plugin formatter request awaiting its result.
+///
+/// The result only applies while the buffer is active and unchanged since
+/// its text was sent.
+pub(super) struct PendingPluginFormat {
+    pub(super) request_id: u64,
+    buffer_id: BufferId,
+    /// Buffer version the text was taken at
+    version: u64,
+    handler_name: String,
+    deadline: Instant,
+    /// Set when formatting on save; the rest of the on-save actions run
+    /// once the result arrives
+    on_save: Option<PluginFormatOnSave>,
+}
+
+/// On-save state carried across a plugin formatter request
+struct PluginFormatOnSave {
+    path: PathBuf,
+    lang_config: LanguageConfig,
+    ran_any_action: bool,
+}
+
 impl Editor {
     /// Run on-save actions for the active buffer after a successful save.
     /// This includes format-on-save (if enabled) and any on_save actions.
@@ -37,17 +88,40 @@ impl Editor {
 
         let mut ran_any_action = false;
 
-        // Run whitespace cleanup actions first (before formatter)
-        if self.config.editor.trim_trailing_whitespace_on_save {
-            if self.trim_trailing_whitespace()? {
-                ran_any_action = true;
+        // .editorconfig settings take precedence over the editor config
+        let editorconfig = editorconfig::settings_for(self.filesystem.as_ref(), &path);
+        let trim_whitespace = editorconfig
+            .trim_trailing_whitespace
+            .unwrap_or(self.config.editor.trim_trailing_whitespace_on_save);
+        let final_newline = editorconfig
+            .insert_final_newline
+            .unwrap_or(self.config.editor.ensure_final_newline_on_save);
+
+        // The actions rewrite the buffer as text, which would replace bytes
+        // that aren't valid UTF-8
+        if self.active_state().buffer.current_invalid_utf8_sequences() > 0 {
+            let configured = trim_whitespace
+                || final_newline
+                || detect_language(&path, &self.config.languages)
+                    .and_then(|language| self.config.languages.get(&language))
+                    .is_some_and(|lang_config| {
+                        lang_config.format_on_save
+                            || !lang_config.on_save.is_empty()
+                            || !lang_config.code_actions_on_save.is_empty()
+                    });
+            if configured {
+                self.status_message = Some(t!("encoding.on_save_skipped").to_string());
             }
+            return Ok(false);
         }
 
-        if self.config.editor.ensure_final_newline_on_save {
-            if self.ensure_final_newline()? {
-                ran_any_action = true;
-            }
+        // Run whitespace cleanup actions first (before formatter)
+        if trim_whitespace && self.trim_trailing_whitespace()? {
+            ran_any_action = true;
+        }
+
+        if final_newline && self.ensure_final_newline()? {
+            ran_any_action = true;
         }
 
         // If whitespace cleanup made changes, re-save
@@ -69,10 +143,39 @@ impl Editor {
             None => return Ok(ran_any_action),
         };
 
+        // LSP code actions are applied asynchronously; formatting and on-save
+        // commands continue once they are done
+        if !lang_config.code_actions_on_save.is_empty()
+            && self.start_save_code_actions(&path, &lang_config, ran_any_action)
+        {
+            return Ok(ran_any_action);
+        }
+
+        self.run_formatter_and_on_save_commands(&path, &lang_config, ran_any_action)
+    }
+
+    /// Run the formatter (if format_on_save is enabled) and on_save commands
+    /// for the active buffer.
+    fn run_formatter_and_on_save_commands(
+        &mut self,
+        path: &Path,
+        lang_config: &LanguageConfig,
+        mut ran_any_action: bool,
+    ) -> Result<bool, String> {
         // Run formatter if format_on_save is enabled
         if lang_config.format_on_save {
-            if let Some(ref formatter) = lang_config.formatter {
-                match self.run_formatter(formatter, &path) {
+            if let Some(formatter) = self.plugin_formatter_for_active_buffer() {
+                // The remaining on-save commands run once the plugin answers
+                let on_save = PluginFormatOnSave {
+                    path: path.to_path_buf(),
+                    lang_config: lang_config.clone(),
+                    ran_any_action,
+                };
+                if self.start_plugin_format(&formatter, Some(on_save)) {
+                    return Ok(ran_any_action);
+                }
+            } else if let Some(ref formatter) = lang_config.formatter {
+                match self.run_formatter(formatter, path) {
                     ActionResult::Success(output) => {
                         self.replace_buffer_with_output(&output)?;
                         // Re-save after formatting
@@ -95,7 +198,16 @@ impl Editor {
             }
         }
 
-        // Run on_save actions (linters, etc.)
+        self.run_on_save_commands(path, lang_config, ran_any_action)
+    }
+
+    /// Run the on_save commands (linters, etc.) for the active buffer
+    fn run_on_save_commands(
+        &mut self,
+        path: &Path,
+        lang_config: &LanguageConfig,
+        mut ran_any_action: bool,
+    ) -> Result<bool, String> {
         let project_root = std::env::current_dir()
             .unwrap_or_else(|_| path.parent().unwrap_or(Path::new(".")).to_path_buf());
 
@@ -104,7 +216,7 @@ impl Editor {
                 continue;
             }
 
-            match self.run_on_save_action(action, &path, &project_root) {
+            match self.run_on_save_action(action, path, &project_root) {
                 ActionResult::Success(_) => {
                     ran_any_action = true;
                 }
@@ -120,9 +232,265 @@ impl Editor {
         Ok(ran_any_action)
     }
 
+    /// Start applying `code_actions_on_save` to the active buffer.
+    /// Returns false if no language server can take the requests.
+    fn start_save_code_actions(
+        &mut self,
+        path: &Path,
+        lang_config: &LanguageConfig,
+        ran_any_action: bool,
+    ) -> bool {
+        let mut pending = SaveCodeActions {
+            buffer_id: self.active_buffer(),
+            path: path.to_path_buf(),
+            lang_config: lang_config.clone(),
+            remaining: lang_config.code_actions_on_save.iter().cloned().collect(),
+            in_flight: None,
+            timed_out: Vec::new(),
+            ran_any_action,
+        };
+        if !self.request_next_save_code_action(&mut pending) {
+            return false;
+        }
+
+        // A newer save replaces any pipeline still running
+        self.pending_save_code_actions = Some(pending);
+        self.status_message = Some(t!("status.code_actions_on_save").to_string());
+        true
+    }
+
+    /// Request the next code action kind for a save.
+    /// Returns false if there is nothing left to request or the server is gone.
+    fn request_next_save_code_action(&mut self, pending: &mut SaveCodeActions) -> bool {
+        let Some(kind) = pending.remaining.pop_front() else {
+            return false;
+        };
+        let Some(uri) = self
+            .buffer_metadata
+            .get(&pending.buffer_id)
+            .and_then(|m| m.file_uri())
+            .cloned()
+        else {
+            return false;
+        };
+        let Some(state) = self.buffers.get(&pending.buffer_id) else {
+            return false;
+        };
+
+        // Ask for actions covering the whole document
+        let (end_line, end_char) = state.buffer.position_to_lsp_position(state.buffer.len());
+        let diagnostics = self
+            .stored_diagnostics
+            .get(uri.as_str())
+            .cloned()
+            .unwrap_or_default();
+        let request_id = self.next_lsp_request_id;
+        let only = vec![CodeActionKind::from(kind.clone())];
+
+        let sent = self
+            .with_lsp_for_buffer(pending.buffer_id, |handle, uri, _language| {
+                handle
+                    .code_actions(
+                        request_id,
+                        uri.clone(),
+                        0,
+                        0,
+                        end_line as u32,
+                        end_char as u32,
+                        diagnostics,
+                        Some(only),
+                    )
+                    .is_ok()
+            })
+            .unwrap_or(false);
+        if !sent {
+            return false;
+        }
+
+        tracing::debug!("Requested '{}' code actions on save", kind);
+        self.next_lsp_request_id += 1;
+        let timeout = Duration::from_millis(self.config.editor.code_actions_on_save_timeout_ms);
+        pending.in_flight = Some((kind, request_id, Instant::now() + timeout));
+        true
+    }
+
+    /// Apply a code actions response if it belongs to a save in progress.
+    /// Returns false if the response is for some other request.
+    pub(super) fn handle_save_code_actions_response(
+        &mut self,
+        request_id: u64,
+        actions: &[CodeActionOrCommand],
+    ) -> bool {
+        let is_ours = self
+            .pending_save_code_actions
+            .as_ref()
+            .and_then(|p| p.in_flight.as_ref())
+            .is_some_and(|(_, id, _)| *id == request_id);
+        if !is_ours {
+            return false;
+        }
+        let Some(mut pending) = self.pending_save_code_actions.take() else {
+            return false;
+        };
+        let Some((kind, _, _)) = pending.in_flight.take() else {
+            return false;
+        };
+
+        if !self.save_code_actions_still_apply(&pending) {
+            return true;
+        }
+
+        match self.apply_save_code_action(&pending, &kind, actions) {
+            Ok(false) => {}
+            Ok(true) => {
+                // Re-save so the file on disk has the fixes even if formatting fails
+                if let Err(e) = self.active_state_mut().buffer.save() {
+                    self.status_message = Some(format!(
+                        "Failed to re-save after code actions on save: {}",
+                        e
+                    ));
+                    return true;
+                }
+                self.active_event_log_mut().mark_saved();
+                pending.ran_any_action = true;
+            }
+            Err(e) => {
+                tracing::warn!("Failed to apply '{}' code action on save: {}", kind, e);
+            }
+        }
+
+        self.continue_save_code_actions(pending);
+        true
+    }
+
+    /// Skip a code action request the server did not answer in time.
+    /// Returns true if a save pipeline moved on.
+    pub fn check_save_code_actions_timer(&mut self) -> bool {
+        let expired = self
+            .pending_save_code_actions
+            .as_ref()
+            .and_then(|p| p.in_flight.as_ref())
+            .is_some_and(|(_, _, deadline)| Instant::now() >= *deadline);
+        if !expired {
+            return false;
+        }
+        let Some(mut pending) = self.pending_save_code_actions.take() else {
+            return false;
+        };
+        let Some((kind, _, _)) = pending.in_flight.take() else {
+            return false;
+        };
+
+        tracing::warn!(
+            "Language server did not return '{}' code actions within {}ms, skipping",
+            kind,
+            self.config.editor.code_actions_on_save_timeout_ms
+        );
+        if self.save_code_actions_still_apply(&pending) {
+            pending.timed_out.push(kind);
+            self.continue_save_code_actions(pending);
+        }
+        true
+    }
+
+    /// Request the next kind, or run the rest of the on-save actions once all
+    /// kinds are done.
+    fn continue_save_code_actions(&mut self, mut pending: SaveCodeActions) {
+        if self.request_next_save_code_action(&mut pending) {
+            self.pending_save_code_actions = Some(pending);
+            return;
+        }
+
+        let result = self.run_formatter_and_on_save_commands(
+            &pending.path,
+            &pending.lang_config,
+            pending.ran_any_action,
+        );
+        if self.pending_plugin_format.is_some() {
+            // A plugin formatter reports the outcome once it answers
+            return;
+        }
+        self.status_message = Some(t!("status.file_saved").to_string());
+        self.report_on_save_actions(result);
+
+        let message_unchanged = self.status_message.as_deref() == Some(&t!("status.file_saved"))
+            || self.status_message.as_deref() == Some(&t!("status.file_saved_with_actions"));
+        if !pending.timed_out.is_empty() && message_unchanged {
+            self.status_message = Some(
+                t!(
+                    "status.code_actions_on_save_timed_out",
+                    kinds = pending.timed_out.join(", ")
+                )
+                .to_string(),
+            );
+        }
+    }
+
+    /// Code action edits are computed against the saved text, so they only
+    /// apply while that buffer is still active and unmodified.
+    fn save_code_actions_still_apply(&mut self, pending: &SaveCodeActions) -> bool {
+        let unchanged = self.active_buffer() == pending.buffer_id
+            && self
+                .event_logs
+                .get(&pending.buffer_id)
+                .is_some_and(|log| log.is_at_saved_position());
+        if !unchanged {
+            tracing::debug!("Buffer changed while running code actions on save, stopping");
+            self.status_message = Some(t!("status.code_actions_on_save_abandoned").to_string());
+        }
+        unchanged
+    }
+
+    /// Apply the first action of the requested kind that carries an edit.
+    /// Returns whether the buffer changed.
+    fn apply_save_code_action(
+        &mut self,
+        pending: &SaveCodeActions,
+        kind: &str,
+        actions: &[CodeActionOrCommand],
+    ) -> anyhow::Result<bool> {
+        // Actions that only carry a command would need the server to execute
+        // them, and may edit other files, so they are skipped
+        let edit = actions.iter().find_map(|action| match action {
+            CodeActionOrCommand::CodeAction(action)
+                if action.disabled.is_none()
+                    && action
+                        .kind
+                        .as_ref()
+                        .is_some_and(|k| code_action_kind_matches(k.as_str(), kind)) =>
+            {
+                action.edit.clone()
+            }
+            _ => None,
+        });
+        let Some(edit) = edit else {
+            return Ok(false);
+        };
+        let Some(uri) = self
+            .buffer_metadata
+            .get(&pending.buffer_id)
+            .and_then(|m| m.file_uri())
+            .cloned()
+        else {
+            return Ok(false);
+        };
+
+        let edits = text_edits_for_document(edit, &uri);
+        let changes = self.apply_lsp_text_edits(pending.buffer_id, edits, "LSP Code Action")?;
+        Ok(changes > 0)
+    }
+
     /// Format the current buffer using the configured formatter.
     /// Returns Ok(()) if formatting succeeded, or Err with an error message.
     pub fn format_buffer(&mut self) -> Result<(), String> {
+        // Plugin formatters go by the buffer's language, so unsaved buffers work too
+        if let Some(formatter) = self.plugin_formatter_for_active_buffer() {
+            if !self.start_plugin_format(&formatter, None) {
+                return Err("Buffer is too large to format".to_string());
+            }
+            return Ok(());
+        }
+
         let path = match self.active_state().buffer.file_path() {
             Some(p) => p.to_path_buf(),
             None => {
@@ -167,12 +535,180 @@ impl Editor {
         }
     }
 
+    /// The plugin formatter registered for the active buffer's language
+    fn plugin_formatter_for_active_buffer(&self) -> Option<fresh_core::api::PluginFormatter> {
+        let language = &self.active_state().language;
+        self.plugin_formatters
+            .iter()
+            .find(|formatter| &formatter.language == language)
+            .cloned()
+    }
+
+    /// Send the active buffer's text to a plugin formatter.
+    /// Returns false if the buffer's text is not fully loaded.
+    fn start_plugin_format(
+        &mut self,
+        formatter: &fresh_core::api::PluginFormatter,
+        on_save: Option<PluginFormatOnSave>,
+    ) -> bool {
+        let buffer_id = self.active_buffer();
+        let state = self.active_state();
+        let Some(text) = state.buffer.to_string() else {
+            return false;
+        };
+        let version = state.buffer.version();
+        let request = fresh_core::api::FormatRequest {
+            buffer_id: buffer_id.0,
+            path: state
+                .buffer
+                .file_path()
+                .map(|path| path.to_string_lossy().into_owned()),
+            language: state.language.clone(),
+            text,
+        };
+
+        let request_id = self.next_lsp_request_id;
+        self.next_lsp_request_id += 1;
+        self.plugin_manager
+            .request_format(formatter, request_id, request);
+
+        // A newer request replaces any still waiting
+        self.pending_plugin_format = Some(PendingPluginFormat {
+            request_id,
+            buffer_id,
+            version,
+            handler_name: formatter.handler_name.clone(),
+            deadline: Instant::now() + PLUGIN_FORMAT_TIMEOUT,
+            on_save,
+        });
+        self.status_message = Some(
+            t!(
+                "format.plugin_formatting",
+                formatter = formatter.handler_name.clone()
+            )
+            .to_string(),
+        );
+        true
+    }
+
+    /// Apply a plugin formatter's result as a single undoable edit
+    pub(super) fn handle_plugin_formatted_text(&mut self, request_id: u64, text: Option<String>) {
+        if self
+            .pending_plugin_format
+            .as_ref()
+            .is_none_or(|pending| pending.request_id != request_id)
+        {
+            tracing::debug!(
+                "Ignoring formatter result for outdated request {}",
+                request_id
+            );
+            return;
+        }
+        let Some(pending) = self.pending_plugin_format.take() else {
+            return;
+        };
+        if !self.plugin_format_still_applies(&pending) {
+            return;
+        }
+
+        let changed = match text {
+            Some(text) if self.active_state().buffer.to_string().as_ref() != Some(&text) => {
+                if let Err(e) = self.replace_buffer_with_output(&text) {
+                    self.set_status_message(
+                        t!("error.format_failed", error = e.to_string()).to_string(),
+                    );
+                    return;
+                }
+                true
+            }
+            _ => false,
+        };
+
+        match pending.on_save {
+            Some(on_save) => self.finish_plugin_format_on_save(on_save, changed),
+            None if changed => self.set_status_message(
+                t!("format.formatted_with", formatter = pending.handler_name).to_string(),
+            ),
+            None => self.set_status_message(
+                t!("format.plugin_unchanged", formatter = pending.handler_name).to_string(),
+            ),
+        }
+    }
+
+    /// Give up on a plugin formatter that did not answer in time.
+    /// Returns true if a request timed out.
+    pub fn check_plugin_format_timer(&mut self) -> bool {
+        let expired = self
+            .pending_plugin_format
+            .as_ref()
+            .is_some_and(|pending| Instant::now() >= pending.deadline);
+        if !expired {
+            return false;
+        }
+        let Some(pending) = self.pending_plugin_format.take() else {
+            return false;
+        };
+
+        tracing::warn!(
+            "Formatter '{}' did not answer within {}ms, skipping",
+            pending.handler_name,
+            PLUGIN_FORMAT_TIMEOUT.as_millis()
+        );
+        if !self.plugin_format_still_applies(&pending) {
+            return true;
+        }
+        if let Some(on_save) = pending.on_save {
+            self.finish_plugin_format_on_save(on_save, false);
+            let message_unchanged = self.status_message.as_deref()
+                == Some(&t!("status.file_saved"))
+                || self.status_message.as_deref() == Some(&t!("status.file_saved_with_actions"));
+            if !message_unchanged {
+                return true;
+            }
+        }
+        self.set_status_message(
+            t!("format.plugin_timed_out", formatter = pending.handler_name).to_string(),
+        );
+        true
+    }
+
+    /// A formatter result is computed from the text it was sent, so it only
+    /// applies while that buffer is still active and unedited.
+    fn plugin_format_still_applies(&mut self, pending: &PendingPluginFormat) -> bool {
+        let unchanged = self.active_buffer() == pending.buffer_id
+            && self.active_state().buffer.version() == pending.version;
+        if !unchanged {
+            tracing::debug!("Buffer changed while formatting, discarding the result");
+            self.set_status_message(t!("format.plugin_abandoned").to_string());
+        }
+        unchanged
+    }
+
+    /// Re-save a buffer formatted on save and run its remaining on-save commands
+    fn finish_plugin_format_on_save(&mut self, on_save: PluginFormatOnSave, changed: bool) {
+        let mut ran_any_action = on_save.ran_any_action;
+        if changed {
+            if let Err(e) = self.active_state_mut().buffer.save() {
+                self.status_message = Some(format!("Failed to re-save after format: {}", e));
+                return;
+            }
+            self.active_event_log_mut().mark_saved();
+            ran_any_action = true;
+        }
+        let result = self.run_on_save_commands(&on_save.path, &on_save.lang_config, ran_any_action);
+        self.status_message = Some(t!("status.file_saved").to_string());
+        self.report_on_save_actions(result);
+    }
+
     /// Run a formatter on the current buffer content.
     fn run_formatter(&mut self, formatter: &FormatterConfig, file_path: &Path) -> ActionResult {
         let file_path_str = file_path.display().to_string();
 
         // Check if command exists
-        if !command_exists(&formatter.command) {
+        let command = expand_path(&formatter.command)
+            .to_string_lossy()
+            .into_owned();
+        if !command_exists(&command) {
             return ActionResult::CommandNotFound(formatter.command.clone());
         }
 
@@ -180,7 +716,7 @@ impl Editor {
         let shell = detect_shell();
 
         // Build the full command string with arguments
-        let mut cmd_parts = vec![formatter.command.clone()];
+        let mut cmd_parts = vec![command];
         for arg in &formatter.args {
             cmd_parts.push(arg.replace("$FILE", &file_path_str));
         }
@@ -292,14 +828,15 @@ impl Editor {
         let file_path_str = file_path.display().to_string();
 
         // Check if command exists
-        if !command_exists(&action.command) {
+        let command = expand_path(&action.command).to_string_lossy().into_owned();
+        if !command_exists(&command) {
             return ActionResult::CommandNotFound(action.command.clone());
         }
 
         // Build the command
         let shell = detect_shell();
 
-        let mut cmd_parts = vec![action.command.clone()];
+        let mut cmd_parts = vec![command];
         for arg in &action.args {
             cmd_parts.push(arg.replace("$FILE", &file_path_str));
         }
@@ -317,8 +854,10 @@ impl Editor {
             .working_dir
             .as_ref()
             .map(|wd| {
-                let expanded = wd.replace("$FILE", &file_path_str);
-                Path::new(&expanded).to_path_buf()
+                expand_path_with(wd, |name| match name {
+                    "FILE" => Some(file_path_str.clone()),
+                    _ => std::env::var(name).ok(),
+                })
             })
             .unwrap_or_else(|| project_root.to_path_buf());
 
@@ -473,27 +1012,23 @@ impl Editor {
     }
 
     /// Trim trailing whitespace from all lines in the active buffer.
+    /// Lines ending inside a multi-line string literal are left alone.
     /// Returns Ok(true) if any changes were made, Ok(false) if buffer unchanged.
     pub fn trim_trailing_whitespace(&mut self) -> Result<bool, String> {
         let content = self.active_state().buffer.to_string().unwrap_or_default();
 
-        // Process each line and trim trailing whitespace
-        let trimmed: String = content
-            .lines()
-            .map(|line| line.trim_end())
-            .collect::<Vec<_>>()
-            .join("\n");
+        let protected = self
+            .active_state()
+            .buffer
+            .file_path()
+            .and_then(Language::from_path)
+            .map(|language| multiline_string_ranges(&content, &language))
+            .unwrap_or_default();
 
-        // Preserve original trailing newline if present
-        let trimmed = if content.ends_with('\n') && !trimmed.ends_with('\n') {
-            format!("{}\n", trimmed)
-        } else {
-            trimmed
-        };
-
-        if trimmed == content {
+        let Some(trimmed) = trailing_whitespace::trim_trailing_whitespace(&content, &protected)
+        else {
             return Ok(false);
-        }
+        };
 
         self.replace_buffer_with_output(&trimmed)?;
         Ok(true)
@@ -519,6 +1054,45 @@ impl Editor {
     }
 }
 
+/// Whether an action kind falls under a requested kind
+/// (`source.fixAll.eslint` is a `source.fixAll` action)
+fn code_action_kind_matches(action_kind: &str, requested: &str) -> bool {
+    action_kind == requested
+        || action_kind
+            .strip_prefix(requested)
+            .is_some_and(|rest| rest.starts_with('.'))
+}
+
+/// Text edits a workspace edit makes to one document
+fn text_edits_for_document(edit: WorkspaceEdit, uri: &Uri) -> Vec<TextEdit> {
+    let mut edits = Vec::new();
+    if let Some(mut changes) = edit.changes {
+        edits.extend(changes.remove(uri).unwrap_or_default());
+    }
+    if let Some(document_changes) = edit.document_changes {
+        let document_edits = match document_changes {
+            lsp_types::DocumentChanges::Edits(edits) => edits,
+            lsp_types::DocumentChanges::Operations(ops) => ops
+                .into_iter()
+                .filter_map(|op| match op {
+                    lsp_types::DocumentChangeOperation::Edit(edit) => Some(edit),
+                    _ => None,
+                })
+                .collect(),
+        };
+        for document_edit in document_edits {
+            if document_edit.text_document.uri != *uri {
+                continue;
+            }
+            edits.extend(document_edit.edits.into_iter().map(|edit| match edit {
+                lsp_types::OneOf::Left(text_edit) => text_edit,
+                lsp_types::OneOf::Right(annotated) => annotated.text_edit,
+            }));
+        }
+    }
+    edits
+}
+
 /// Check if a command exists in the system PATH.
 fn command_exists(command: &str) -> bool {
     // Use 'which' on Unix or 'where' on Windows to check if command exists
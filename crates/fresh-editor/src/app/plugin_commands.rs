@@ -2,16 +2,169 @@
 //!
 //! This module groups plugin commands by domain for better maintainability.
 
-use crate::model::event::{BufferId, CursorId, Event, OverlayFace, SplitId};
+use crate::config_io::expand_path;
+use crate::model::event::{BufferId, CursorId, Event, OverlayFace, SplitDirection, SplitId};
+use crate::model::line_diff::{diff_hunks, split_lines_inclusive, split_words};
+use crate::state::EditorState;
+use crate::view::conceal::ConcealRule;
+use crate::view::margin::MAX_GUTTER_COLUMN_WIDTH;
 use crate::view::overlay::{OverlayHandle, OverlayNamespace};
-use crate::view::split::SplitViewState;
+use crate::view::split::{SplitNode, SplitViewState};
 use anyhow::Result as AnyhowResult;
 use fresh_core::api::{
-    LayoutHints, MenuPosition, OverlayOptions, PluginResponse, ViewTransformPayload,
+    BufferEdit, CompositeHunk, ComputeDiffOptions, DiffGranularity, DiffHunk, LayoutHints,
+    LayoutNode, MenuPosition, OverlayOptions, PluginResponse, SearchOptions, ViewTransformPayload,
 };
+use std::ops::Range;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
+use super::types::{PluginGutter, PluginMarker};
 use super::Editor;
 
+/// How long a plugin undo group may stay open before the editor closes it
+const PLUGIN_UNDO_GROUP_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Expand `~` and environment variables in a path passed to `openFile`
+fn expand_plugin_path(path: PathBuf) -> PathBuf {
+    match path.to_str() {
+        Some(s) => expand_path(s),
+        None => path,
+    }
+}
+
+/// Convert the split tree into the shape returned by `editor.getLayout`
+fn layout_node(node: &SplitNode, active: SplitId) -> LayoutNode {
+    match node {
+        SplitNode::Leaf {
+            buffer_id,
+            split_id,
+        } => LayoutNode {
+            split_id: split_id.0,
+            buffer_id: Some(buffer_id.0),
+            direction: None,
+            ratio: None,
+            active: *split_id == active,
+            children: Vec::new(),
+        },
+        SplitNode::Split {
+            direction,
+            first,
+            second,
+            ratio,
+            split_id,
+        } => LayoutNode {
+            split_id: split_id.0,
+            buffer_id: None,
+            direction: Some(
+                match direction {
+                    SplitDirection::Horizontal => "horizontal",
+                    SplitDirection::Vertical => "vertical",
+                }
+                .to_string(),
+            ),
+            ratio: Some(*ratio),
+            active: false,
+            children: vec![layout_node(first, active), layout_node(second, active)],
+        },
+    }
+}
+
+/// Carry a plugin edit's range, computed against `base_version` of the
+/// buffer, past the edits made since. None (and a warning) when the user
+/// changed the text the edit targets, or the edit no longer fits the buffer.
+fn rebase_plugin_edit(
+    state: &EditorState,
+    range: Range<usize>,
+    base_version: Option<u64>,
+) -> Option<Range<usize>> {
+    let range = match base_version {
+        Some(base_version) => match state.edit_journal.transform_range(
+            range.clone(),
+            base_version,
+            state.buffer.version(),
+        ) {
+            Ok(range) => range,
+            Err(e) => {
+                tracing::warn!(
+                    "Skipping plugin edit at {:?} based on version {}: {:?}",
+                    range,
+                    base_version,
+                    e
+                );
+                return None;
+            }
+        },
+        None => range,
+    };
+    if range.start > range.end || range.end > state.buffer.len() {
+        tracing::warn!("Skipping plugin edit outside the buffer: {:?}", range);
+        return None;
+    }
+    Some(range)
+}
+
+/// Apply a plugin's edit, keeping it out of what later plugin edits are
+/// moved past
+fn apply_plugin_edit(state: &mut EditorState, event: &Event) {
+    let version = state.buffer.version();
+    state.apply(event);
+    if state.buffer.version() != version {
+        state.edit_journal.mark_from_plugin(state.buffer.version());
+    }
+}
+
+/// Set a line's text in a plugin gutter column; lines past the end of the
+/// buffer are ignored
+fn set_gutter_text(
+    state: &mut EditorState,
+    namespace: &str,
+    line: usize,
+    text: String,
+    color: Option<fresh_core::api::OverlayColorSpec>,
+) {
+    use crate::view::margin::GutterTextColor;
+
+    let Some(line_start) = state.buffer.line_start_offset(line) else {
+        tracing::debug!("Ignoring gutter text for line {} past the end", line);
+        return;
+    };
+    let color = color.map(|color| match color {
+        fresh_core::api::OverlayColorSpec::Rgb(r, g, b) => {
+            GutterTextColor::Color(ratatui::style::Color::Rgb(r, g, b))
+        }
+        fresh_core::api::OverlayColorSpec::ThemeKey(key) => GutterTextColor::ThemeKey(key),
+    });
+    state.margins.set_gutter_text(
+        line_start,
+        namespace,
+        crate::view::margin::GutterCell::new(text, color),
+    );
+}
+
+/// Tint a line for a plugin; lines past the end of the buffer are ignored
+fn set_line_background(
+    state: &mut EditorState,
+    namespace: String,
+    line: usize,
+    color: fresh_core::api::OverlayColorSpec,
+) {
+    use crate::view::line_background::LineBackgroundColor;
+
+    let Some(line_start) = state.buffer.line_start_offset(line) else {
+        tracing::debug!("Ignoring line background for line {} past the end", line);
+        return;
+    };
+    let color = match color {
+        fresh_core::api::OverlayColorSpec::Rgb(r, g, b) => {
+            LineBackgroundColor::Color(ratatui::style::Color::Rgb(r, g, b))
+        }
+        fresh_core::api::OverlayColorSpec::ThemeKey(key) => LineBackgroundColor::ThemeKey(key),
+    };
+    state.line_backgrounds.set(line_start, namespace, color);
+}
+
 impl Editor {
     // ==================== Menu Helpers ====================
 
@@ -230,6 +383,50 @@ impl Editor {
         }
     }
 
+    /// Handle CreateMarker command
+    pub(super) fn handle_create_marker(
+        &mut self,
+        buffer_id: BufferId,
+        marker_id: u64,
+        position: usize,
+        plugin_name: String,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            tracing::warn!("CreateMarker: buffer {:?} not found", buffer_id);
+            return;
+        };
+        let position = position.min(state.buffer.len());
+        let marker = PluginMarker {
+            plugin_name,
+            buffer_id,
+            marker_id: state.marker_list.create(position, false),
+        };
+        self.plugin_markers.insert(marker_id, marker);
+    }
+
+    /// Handle DeleteMarker command
+    pub(super) fn handle_delete_marker(&mut self, marker_id: u64) {
+        if let Some(marker) = self.plugin_markers.remove(&marker_id) {
+            if let Some(state) = self.buffers.get_mut(&marker.buffer_id) {
+                state.marker_list.delete(marker.marker_id);
+            }
+        }
+    }
+
+    /// Handle GetMarkerPosition command
+    pub(super) fn handle_get_marker_position(&mut self, marker_id: u64, request_id: u64) {
+        let position = self.plugin_markers.get(&marker_id).and_then(|marker| {
+            self.buffers
+                .get(&marker.buffer_id)?
+                .marker_list
+                .get_position(marker.marker_id)
+        });
+        self.plugin_manager.resolve_callback(
+            fresh_core::api::JsCallbackId::from(request_id),
+            serde_json::to_string(&position).unwrap_or_default(),
+        );
+    }
+
     /// Handle ClearVirtualTextNamespace command
     pub(super) fn handle_clear_virtual_text_namespace(
         &mut self,
@@ -393,6 +590,66 @@ impl Editor {
         }
     }
 
+    /// Handle AddPluginMenuItem command
+    pub(super) fn handle_add_plugin_menu_item(&mut self, item: fresh_core::api::PluginMenuItem) {
+        tracing::info!(
+            "Plugin '{}' added menu item '{}' to '{}'",
+            item.plugin_name,
+            item.label,
+            item.path
+        );
+        self.menu_state.plugin_items.add(item);
+    }
+
+    /// Handle RemovePluginMenuItem command
+    pub(super) fn handle_remove_plugin_menu_item(
+        &mut self,
+        plugin_name: &str,
+        path: &str,
+        label: &str,
+    ) {
+        if !self
+            .menu_state
+            .plugin_items
+            .remove(plugin_name, path, label)
+        {
+            tracing::warn!(
+                "Plugin '{}' has no menu item '{}' in '{}'",
+                plugin_name,
+                label,
+                path
+            );
+        }
+    }
+
+    /// Handle AddContextMenuItem command
+    pub(super) fn handle_add_context_menu_item(&mut self, item: fresh_core::api::ContextMenuItem) {
+        if super::context_menu::ContextMenuKind::from_name(&item.menu).is_none() {
+            tracing::warn!(
+                "Plugin '{}' added '{}' to unknown context menu '{}'",
+                item.plugin_name,
+                item.label,
+                item.menu
+            );
+            return;
+        }
+        // Adding an item again (e.g. after a plugin reload) replaces it
+        self.handle_remove_context_menu_item(&item.plugin_name, &item.menu, &item.label);
+        self.context_menu_items.push(item);
+    }
+
+    /// Handle RemoveContextMenuItem command
+    pub(super) fn handle_remove_context_menu_item(
+        &mut self,
+        plugin_name: &str,
+        menu: &str,
+        label: &str,
+    ) {
+        self.context_menu_items.retain(|item| {
+            item.plugin_name != plugin_name || item.menu != menu || item.label != label
+        });
+    }
+
     // ==================== Split Commands ====================
 
     /// Handle FocusSplit command
@@ -472,6 +729,69 @@ impl Editor {
         tracing::debug!("Distributed splits evenly");
     }
 
+    /// Handle GetLayout command
+    pub(super) fn handle_get_layout(&mut self, request_id: u64) {
+        let layout = layout_node(self.split_manager.root(), self.split_manager.active_split());
+        self.plugin_manager.resolve_callback(
+            fresh_core::api::JsCallbackId::from(request_id),
+            serde_json::to_string(&layout).unwrap_or_default(),
+        );
+    }
+
+    /// Handle SplitEditor command
+    pub(super) fn handle_split_editor(
+        &mut self,
+        split_id: SplitId,
+        direction: String,
+        buffer_id: Option<BufferId>,
+        request_id: u64,
+    ) {
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        match self.split_editor(split_id, &direction, buffer_id) {
+            Ok(new_split_id) => self
+                .plugin_manager
+                .resolve_callback(callback_id, new_split_id.0.to_string()),
+            Err(err) => self.plugin_manager.reject_callback(callback_id, err),
+        }
+    }
+
+    /// Split `split_id` in `direction`, showing `buffer_id` (or the split's
+    /// own buffer) in the new pane, which becomes active
+    fn split_editor(
+        &mut self,
+        split_id: SplitId,
+        direction: &str,
+        buffer_id: Option<BufferId>,
+    ) -> Result<SplitId, String> {
+        let direction = match direction {
+            "horizontal" => SplitDirection::Horizontal,
+            "vertical" => SplitDirection::Vertical,
+            other => return Err(format!("invalid split direction '{}'", other)),
+        };
+        let node = self
+            .split_manager
+            .root()
+            .find(split_id)
+            .ok_or_else(|| format!("split {} not found", split_id.0))?;
+        let buffer_id = buffer_id
+            .or_else(|| node.buffer_id())
+            .unwrap_or_else(|| self.active_buffer());
+        if !self.buffers.contains_key(&buffer_id) {
+            return Err(format!("buffer {} not found", buffer_id.0));
+        }
+
+        self.save_current_split_view_state();
+        let new_split_id = self
+            .split_manager
+            .split_at(split_id, direction, buffer_id, 0.5)?;
+        let mut view_state =
+            SplitViewState::with_buffer(self.terminal_width, self.terminal_height, buffer_id);
+        view_state.viewport.line_wrap_enabled = self.config.editor.line_wrap;
+        self.split_view_states.insert(new_split_id, view_state);
+        self.restore_current_split_view_state();
+        Ok(new_split_id)
+    }
+
     /// Handle SetBufferCursor command
     pub(super) fn handle_set_buffer_cursor(&mut self, buffer_id: BufferId, position: usize) {
         // Find all splits that display this buffer and update their view states
@@ -618,14 +938,18 @@ impl Editor {
         buffer_id: BufferId,
         position: usize,
         text: String,
+        base_version: Option<u64>,
     ) {
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let Some(range) = rebase_plugin_edit(state, position..position, base_version) else {
+                return;
+            };
             let event = Event::Insert {
-                position,
+                position: range.start,
                 text,
                 cursor_id: CursorId(0),
             };
-            state.apply(&event);
+            apply_plugin_edit(state, &event);
             if let Some(log) = self.event_logs.get_mut(&buffer_id) {
                 log.append(event);
             }
@@ -637,21 +961,195 @@ impl Editor {
         &mut self,
         buffer_id: BufferId,
         range: std::ops::Range<usize>,
+        base_version: Option<u64>,
     ) {
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            let Some(range) = rebase_plugin_edit(state, range, base_version) else {
+                return;
+            };
             let deleted_text = state.get_text_range(range.start, range.end);
             let event = Event::Delete {
                 range,
                 deleted_text,
                 cursor_id: CursorId(0),
             };
-            state.apply(&event);
+            apply_plugin_edit(state, &event);
             if let Some(log) = self.event_logs.get_mut(&buffer_id) {
                 log.append(event);
             }
         }
     }
 
+    /// Handle ApplyEdits command
+    ///
+    /// All ranges are rebased before any edit is applied, then the edits are
+    /// applied from the end of the buffer backwards so each range is still
+    /// valid when its turn comes. Text inserted where a replaced range starts
+    /// goes before the replacement; inserts at the same position keep their order.
+    pub(super) fn handle_apply_edits(
+        &mut self,
+        buffer_id: BufferId,
+        edits: Vec<BufferEdit>,
+        base_version: Option<u64>,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        let mut rebased = Vec::with_capacity(edits.len());
+        for edit in edits {
+            let Some(range) = rebase_plugin_edit(state, edit.range, base_version) else {
+                return;
+            };
+            rebased.push((range, edit.text));
+        }
+        rebased.sort_by_key(|(range, _)| (range.start, range.end));
+        if let Some(pair) = rebased
+            .windows(2)
+            .find(|pair| pair[0].0.end > pair[1].0.start)
+        {
+            tracing::warn!(
+                "Skipping plugin edits with overlapping ranges {:?} and {:?}",
+                pair[0].0,
+                pair[1].0
+            );
+            return;
+        }
+
+        let mut events = Vec::new();
+        for (range, text) in rebased.into_iter().rev() {
+            if !range.is_empty() {
+                let deleted_text = state.get_text_range(range.start, range.end);
+                let event = Event::Delete {
+                    range: range.clone(),
+                    deleted_text,
+                    cursor_id: CursorId(0),
+                };
+                apply_plugin_edit(state, &event);
+                events.push(event);
+            }
+            if !text.is_empty() {
+                let event = Event::Insert {
+                    position: range.start,
+                    text,
+                    cursor_id: CursorId(0),
+                };
+                apply_plugin_edit(state, &event);
+                events.push(event);
+            }
+        }
+        if events.is_empty() {
+            return;
+        }
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.append(Event::Batch {
+                events,
+                description: "Plugin edit".to_string(),
+            });
+        }
+    }
+
+    /// Handle BeginUndoGroup command
+    pub(super) fn handle_begin_undo_group(&mut self, buffer_id: BufferId) {
+        let Some(log) = self.event_logs.get_mut(&buffer_id) else {
+            return;
+        };
+        if log.begin_undo_group() {
+            self.plugin_undo_groups.insert(buffer_id, Instant::now());
+        } else {
+            tracing::debug!("Undo group already open for buffer {:?}", buffer_id);
+        }
+    }
+
+    /// Handle EndUndoGroup command
+    pub(super) fn handle_end_undo_group(&mut self, buffer_id: BufferId) {
+        if self.plugin_undo_groups.remove(&buffer_id).is_none() {
+            return;
+        }
+        if let Some(log) = self.event_logs.get_mut(&buffer_id) {
+            log.end_undo_group("Plugin edit");
+        }
+    }
+
+    /// Handle SetSessionData command
+    pub(super) fn handle_set_session_data(
+        &mut self,
+        plugin_name: String,
+        key: String,
+        value: Option<serde_json::Value>,
+    ) {
+        match value {
+            Some(value) => {
+                self.plugin_session_data
+                    .entry(plugin_name)
+                    .or_default()
+                    .insert(key, value);
+            }
+            None => {
+                if let Some(data) = self.plugin_session_data.get_mut(&plugin_name) {
+                    data.remove(&key);
+                    if data.is_empty() {
+                        self.plugin_session_data.remove(&plugin_name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Handle Undo and Redo commands
+    ///
+    /// The active buffer goes through the same path as the Undo and Redo
+    /// actions; other buffers have the events applied directly, like plugin edits.
+    pub(super) fn handle_plugin_undo(&mut self, buffer_id: BufferId, redo: bool) {
+        if buffer_id == self.active_buffer() {
+            if redo {
+                self.handle_redo();
+            } else {
+                self.handle_undo();
+            }
+            return;
+        }
+
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+        if state.editing_disabled {
+            return;
+        }
+        let Some(log) = self.event_logs.get_mut(&buffer_id) else {
+            return;
+        };
+        let events = if redo { log.redo() } else { log.undo() };
+        for event in &events {
+            apply_plugin_edit(state, event);
+        }
+        state.buffer.set_modified(!log.is_at_saved_position());
+    }
+
+    /// Close plugin undo groups that have been open for longer than
+    /// `PLUGIN_UNDO_GROUP_TIMEOUT`, so a plugin that never ends its group
+    /// doesn't fold the user's own edits into it.
+    /// Returns true if any group was closed.
+    pub fn check_plugin_undo_group_timer(&mut self) -> bool {
+        if self.plugin_undo_groups.is_empty() {
+            return false;
+        }
+        let expired: Vec<BufferId> = self
+            .plugin_undo_groups
+            .iter()
+            .filter(|(_, opened)| opened.elapsed() >= PLUGIN_UNDO_GROUP_TIMEOUT)
+            .map(|(id, _)| *id)
+            .collect();
+        for buffer_id in &expired {
+            tracing::warn!(
+                "Closing plugin undo group for buffer {:?} after {:?}",
+                buffer_id,
+                PLUGIN_UNDO_GROUP_TIMEOUT
+            );
+            self.handle_end_undo_group(*buffer_id);
+        }
+        !expired.is_empty()
+    }
+
     /// Handle InsertAtCursor command
     pub(super) fn handle_insert_at_cursor(&mut self, text: String) {
         // Insert text at current cursor position in active buffer
@@ -756,6 +1254,7 @@ impl Editor {
         column: Option<usize>,
     ) -> AnyhowResult<()> {
         // Open the file
+        let path = expand_plugin_path(path);
         if let Err(e) = self.open_file(&path) {
             tracing::error!("Failed to open file from plugin: {}", e);
             return Ok(());
@@ -788,6 +1287,7 @@ impl Editor {
         self.restore_current_split_view_state();
 
         // Open the file in the now-active split
+        let path = expand_plugin_path(path);
         if let Err(e) = self.open_file(&path) {
             tracing::error!("Failed to open file from plugin: {}", e);
             return Ok(());
@@ -801,6 +1301,7 @@ impl Editor {
     /// Handle OpenFileInBackground command
     pub(super) fn handle_open_file_in_background(&mut self, path: std::path::PathBuf) {
         // Open file in a new tab without switching to it
+        let path = expand_plugin_path(path);
         if let Err(e) = self.open_file_no_focus(&path) {
             tracing::error!("Failed to open file in background: {}", e);
         } else {
@@ -857,6 +1358,51 @@ impl Editor {
         }
     }
 
+    /// Handle SetBufferDisplayOptions command
+    pub(super) fn handle_set_buffer_display_options(
+        &mut self,
+        buffer_id: BufferId,
+        options: fresh_core::api::BufferDisplayOptions,
+    ) {
+        let Some(state) = self.buffers.get_mut(&buffer_id) else {
+            return;
+        };
+
+        if let Some(column) = options.wrap_column {
+            state.wrap_column = (column > 0).then_some(column);
+        }
+        if let Some(width) = options.tab_width.filter(|&w| w > 0) {
+            state.tab_size = width;
+        }
+        if let Some(show) = options.show_whitespace {
+            state.show_whitespace_tabs = show;
+        }
+        if let Some(rules) = options.conceal {
+            state.conceal_rules = rules
+                .into_iter()
+                .filter_map(|rule| {
+                    ConcealRule::new(&rule.pattern, rule.replacement)
+                        .map_err(|e| {
+                            tracing::warn!(
+                                "Ignoring invalid conceal pattern {:?}: {}",
+                                rule.pattern,
+                                e
+                            )
+                        })
+                        .ok()
+                })
+                .collect();
+        }
+
+        if let Some(wrap) = options.line_wrap {
+            for split_id in self.split_manager.splits_for_buffer(buffer_id) {
+                if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
+                    view_state.viewport.line_wrap_enabled = wrap;
+                }
+            }
+        }
+    }
+
     /// Handle SubmitViewTransform command
     pub(super) fn handle_submit_view_transform(
         &mut self,
@@ -929,8 +1475,151 @@ impl Editor {
         }
     }
 
+    /// Handle RegisterGutter command
+    pub(super) fn handle_register_gutter(
+        &mut self,
+        plugin_name: String,
+        namespace: String,
+        width: usize,
+    ) {
+        let width = width.min(MAX_GUTTER_COLUMN_WIDTH);
+        // Buffers already showing the column pick up the new width
+        for state in self.buffers.values_mut() {
+            if state.margins.has_gutter_column(&namespace) {
+                state.margins.set_gutter_column(&namespace, width);
+            }
+        }
+        match self
+            .plugin_gutters
+            .iter_mut()
+            .find(|gutter| gutter.namespace == namespace)
+        {
+            Some(gutter) => {
+                gutter.plugin_name = plugin_name;
+                gutter.width = width;
+            }
+            None => self.plugin_gutters.push(PluginGutter {
+                plugin_name,
+                namespace,
+                width,
+            }),
+        }
+    }
+
+    /// Handle UnregisterGutter command
+    pub(super) fn handle_unregister_gutter(&mut self, namespace: &str) {
+        self.plugin_gutters
+            .retain(|gutter| gutter.namespace != namespace);
+        for state in self.buffers.values_mut() {
+            state.margins.remove_gutter_column(namespace);
+        }
+    }
+
+    /// Add a registered gutter column to a buffer if it doesn't show it yet
+    ///
+    /// Returns the buffer, or `None` if the buffer or the column is unknown.
+    fn gutter_buffer_state(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: &str,
+    ) -> Option<&mut EditorState> {
+        let Some(gutter) = self
+            .plugin_gutters
+            .iter()
+            .find(|gutter| gutter.namespace == namespace)
+        else {
+            tracing::warn!("Gutter '{}' is not registered", namespace);
+            return None;
+        };
+        let state = self.buffers.get_mut(&buffer_id)?;
+        if !state.margins.has_gutter_column(namespace) {
+            state.margins.set_gutter_column(namespace, gutter.width);
+        }
+        Some(state)
+    }
+
+    /// Handle SetGutterText command
+    pub(super) fn handle_set_gutter_text(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: String,
+        line: usize,
+        text: String,
+        color: Option<fresh_core::api::OverlayColorSpec>,
+    ) {
+        if let Some(state) = self.gutter_buffer_state(buffer_id, &namespace) {
+            set_gutter_text(state, &namespace, line, text, color);
+        }
+    }
+
+    /// Handle SetGutterTexts command
+    pub(super) fn handle_set_gutter_texts(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: String,
+        lines: Vec<fresh_core::api::GutterText>,
+    ) {
+        if let Some(state) = self.gutter_buffer_state(buffer_id, &namespace) {
+            state.margins.clear_gutter_text(&namespace);
+            for line in lines {
+                set_gutter_text(state, &namespace, line.line, line.text, line.color);
+            }
+        }
+    }
+
+    /// Handle ClearGutterText command
+    pub(super) fn handle_clear_gutter_text(&mut self, buffer_id: BufferId, namespace: String) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.margins.clear_gutter_text(&namespace);
+        }
+    }
+
+    /// Handle SetLineBackground command
+    pub(super) fn handle_set_line_background(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: String,
+        line: usize,
+        color: fresh_core::api::OverlayColorSpec,
+    ) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            set_line_background(state, namespace, line, color);
+        }
+    }
+
+    /// Handle SetLineBackgrounds command
+    pub(super) fn handle_set_line_backgrounds(
+        &mut self,
+        buffer_id: BufferId,
+        namespace: String,
+        lines: Vec<fresh_core::api::LineBackground>,
+    ) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.line_backgrounds.clear_namespace(&namespace);
+            for background in lines {
+                set_line_background(state, namespace.clone(), background.line, background.color);
+            }
+        }
+    }
+
+    /// Handle ClearLineBackgrounds command
+    pub(super) fn handle_clear_line_backgrounds(&mut self, buffer_id: BufferId, namespace: String) {
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.line_backgrounds.clear_namespace(&namespace);
+        }
+    }
+
     // ==================== Status/Prompt Commands ====================
 
+    /// Handle SetStatusBarSegment command
+    pub(super) fn handle_set_status_bar_segment(&mut self, name: String, text: String) {
+        if text.is_empty() {
+            self.plugin_status_segments.remove(&name);
+        } else {
+            self.plugin_status_segments.insert(name, text);
+        }
+    }
+
     /// Handle SetStatus command
     pub(super) fn handle_set_status(&mut self, message: String) {
         if message.trim().is_empty() {
@@ -1034,6 +1723,71 @@ impl Editor {
         );
     }
 
+    /// Handle ShowConfirm command (for editor.showConfirm() API)
+    pub(super) fn handle_show_confirm(
+        &mut self,
+        message: String,
+        mut buttons: Vec<String>,
+        callback_id: fresh_core::api::JsCallbackId,
+    ) {
+        // Only one confirm popup can be open; the previous one counts as dismissed
+        if let Some(previous) = self.pending_confirm_callback.replace(callback_id) {
+            self.hide_popup();
+            self.plugin_manager
+                .resolve_callback(previous, "null".to_string());
+        }
+
+        if buttons.is_empty() {
+            buttons.push(rust_i18n::t!("confirm.ok").to_string());
+        }
+        let items = buttons
+            .into_iter()
+            .map(|label| crate::model::event::PopupListItemData {
+                text: label.clone(),
+                detail: None,
+                icon: None,
+                data: Some(label),
+            })
+            .collect();
+
+        self.show_popup(crate::model::event::PopupData {
+            title: None,
+            description: Some(message),
+            transient: false,
+            content: crate::model::event::PopupContentData::List { items, selected: 0 },
+            position: crate::model::event::PopupPositionData::Centered,
+            width: 60,
+            max_height: 15,
+            bordered: true,
+        });
+    }
+
+    /// Handle ShowInput command (for editor.showInput() API)
+    ///
+    /// Resolved like editor.prompt(), but without firing `prompt_changed`,
+    /// so a password never reaches other plugins.
+    pub(super) fn handle_show_input(
+        &mut self,
+        prompt: String,
+        options: fresh_core::api::InputOptions,
+        callback_id: fresh_core::api::JsCallbackId,
+    ) {
+        use crate::view::prompt::{Prompt, PromptType};
+
+        if let Some(previous) = self.pending_async_prompt_callback.replace(callback_id) {
+            self.plugin_manager
+                .resolve_callback(previous, "null".to_string());
+        }
+
+        let mut input = Prompt::with_initial_text(
+            prompt,
+            PromptType::AsyncPrompt,
+            options.initial.unwrap_or_default(),
+        );
+        input.masked = options.password.unwrap_or(false);
+        self.prompt = Some(input);
+    }
+
     /// Handle SetPromptSuggestions command
     pub(super) fn handle_set_prompt_suggestions(
         &mut self,
@@ -1063,7 +1817,7 @@ impl Editor {
         if let Some(prompt) = &mut self.prompt {
             // Set original_suggestions for Rust-side filtering (used by prompts that
             // don't handle their own filtering like theme editor dropdowns)
-            prompt.original_suggestions = Some(internal_suggestions.clone());
+            prompt.original_suggestions = Some(Arc::new(internal_suggestions.clone()));
             prompt.suggestions = internal_suggestions;
             // Select first suggestion by default
             prompt.selected_suggestion = if prompt.suggestions.is_empty() {
@@ -1111,6 +1865,91 @@ impl Editor {
         self.command_registry.read().unwrap().unregister(&name);
     }
 
+    /// Handle RegisterCompletionProvider command
+    pub(super) fn handle_register_completion_provider(
+        &mut self,
+        provider: fresh_core::api::CompletionProvider,
+    ) {
+        // Re-registering (e.g. after a plugin reload) replaces the provider
+        self.completion_providers.retain(|p| {
+            p.plugin_name != provider.plugin_name || p.handler_name != provider.handler_name
+        });
+        tracing::debug!(
+            "Registered completion provider '{}' from plugin '{}'",
+            provider.handler_name,
+            provider.plugin_name
+        );
+        self.completion_providers.push(provider);
+    }
+
+    /// Handle RegisterHoverProvider command
+    pub(super) fn handle_register_hover_provider(
+        &mut self,
+        provider: fresh_core::api::HoverProvider,
+    ) {
+        // Re-registering (e.g. after a plugin reload) replaces the provider
+        self.hover_providers.retain(|p| {
+            p.plugin_name != provider.plugin_name || p.handler_name != provider.handler_name
+        });
+        tracing::debug!(
+            "Registered hover provider '{}' from plugin '{}'",
+            provider.handler_name,
+            provider.plugin_name
+        );
+        self.hover_providers.push(provider);
+    }
+
+    /// Handle RegisterFormatter command
+    pub(super) fn handle_register_formatter(
+        &mut self,
+        formatter: fresh_core::api::PluginFormatter,
+    ) {
+        // The latest registration for a language wins
+        self.plugin_formatters
+            .retain(|f| f.language != formatter.language);
+        tracing::debug!(
+            "Registered formatter '{}' for {} from plugin '{}'",
+            formatter.handler_name,
+            formatter.language,
+            formatter.plugin_name
+        );
+        self.plugin_formatters.push(formatter);
+    }
+
+    /// Handle RegisterEvaluator command
+    pub(super) fn handle_register_evaluator(
+        &mut self,
+        evaluator: fresh_core::api::PluginEvaluator,
+    ) {
+        // The latest registration for a language wins
+        self.plugin_evaluators
+            .retain(|e| e.language != evaluator.language);
+        tracing::debug!(
+            "Registered evaluator '{}' for {} from plugin '{}'",
+            evaluator.handler_name,
+            evaluator.language,
+            evaluator.plugin_name
+        );
+        self.plugin_evaluators.push(evaluator);
+    }
+
+    /// Handle RegisterInlineSuggestionProvider command
+    pub(super) fn handle_register_inline_suggestion_provider(
+        &mut self,
+        provider: fresh_core::api::InlineSuggestionProvider,
+    ) {
+        // Re-registering (e.g. after a plugin reload) replaces the provider
+        self.inline_suggestion_providers.retain(|p| {
+            p.plugin_name != provider.plugin_name || p.handler_name != provider.handler_name
+        });
+        tracing::debug!(
+            "Registered inline suggestion provider '{}' from plugin '{}'",
+            provider.handler_name,
+            provider.plugin_name
+        );
+        self.inline_suggestion_providers.push(provider);
+    }
+
     /// Handle DefineMode command
     pub(super) fn handle_define_mode(
         &mut self,
@@ -1204,6 +2043,43 @@ impl Editor {
         }
     }
 
+    // ==================== Diff Commands ====================
+
+    /// Handle ComputeDiff command
+    pub(super) fn handle_compute_diff(
+        &mut self,
+        old_text: String,
+        new_text: String,
+        options: ComputeDiffOptions,
+        request_id: u64,
+    ) {
+        let callback_id = fresh_core::api::JsCallbackId::from(request_id);
+        match compute_diff_json(&old_text, &new_text, &options) {
+            Ok(json) => self.plugin_manager.resolve_callback(callback_id, json),
+            Err(err) => self.plugin_manager.reject_callback(callback_id, err),
+        }
+    }
+
+    // ==================== Search Commands ====================
+
+    /// Handle RunSearch command
+    pub(super) fn handle_run_search(&mut self, pattern: String, options: SearchOptions) {
+        if let Some(regex) = options.regex {
+            self.search_use_regex = regex;
+        }
+        if let Some(case_sensitive) = options.case_sensitive {
+            self.search_case_sensitive = case_sensitive;
+        }
+        if let Some(whole_word) = options.whole_word {
+            self.search_whole_word = whole_word;
+        }
+        if options.project == Some(true) {
+            self.start_project_search(&pattern);
+        } else {
+            self.perform_search(&pattern);
+        }
+    }
+
     // ==================== Clipboard Commands ====================
 
     /// Handle SetClipboard command
@@ -1401,3 +2277,71 @@ impl Editor {
         }
     }
 }
+
+/// Diff two texts for `editor.computeDiff` and serialize the hunks for JS
+fn compute_diff_json(
+    old_text: &str,
+    new_text: &str,
+    options: &ComputeDiffOptions,
+) -> Result<String, String> {
+    let granularity = options.granularity.unwrap_or_default();
+    let composite = options.composite.unwrap_or(false);
+    if composite && granularity != DiffGranularity::Line {
+        return Err("computeDiff: composite hunks require line granularity".to_string());
+    }
+
+    let split: fn(&str) -> Vec<&str> = match granularity {
+        DiffGranularity::Line => split_lines_inclusive,
+        DiffGranularity::Word => split_words,
+    };
+    let old_tokens = split(old_text);
+    let new_tokens = split(new_text);
+    let hunks = diff_hunks(&old_tokens, &new_tokens);
+
+    let json = if composite {
+        let hunks: Vec<CompositeHunk> = hunks
+            .into_iter()
+            .map(|h| CompositeHunk {
+                old_start: h.old.start,
+                old_count: h.old.len(),
+                new_start: h.new.start,
+                new_count: h.new.len(),
+            })
+            .collect();
+        serde_json::to_string(&hunks)
+    } else {
+        let old_offsets = token_offsets(&old_tokens);
+        let new_offsets = token_offsets(&new_tokens);
+        let hunks: Vec<DiffHunk> = hunks
+            .into_iter()
+            .map(|h| {
+                let old_range = old_offsets[h.old.start]..old_offsets[h.old.end];
+                let new_range = new_offsets[h.new.start]..new_offsets[h.new.end];
+                DiffHunk {
+                    old_start: h.old.start,
+                    old_count: h.old.len(),
+                    new_start: h.new.start,
+                    new_count: h.new.len(),
+                    old_offset: old_range.start,
+                    new_offset: new_range.start,
+                    old_text: old_text[old_range].to_string(),
+                    new_text: new_text[new_range].to_string(),
+                }
+            })
+            .collect();
+        serde_json::to_string(&hunks)
+    };
+    json.map_err(|e| e.to_string())
+}
+
+/// Byte offset of each token, followed by the total length
+fn token_offsets(tokens: &[&str]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(tokens.len() + 1);
+    let mut pos = 0;
+    offsets.push(pos);
+    for token in tokens {
+        pos += token.len();
+        offsets.push(pos);
+    }
+    offsets
+}
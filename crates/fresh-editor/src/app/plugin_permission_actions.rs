@@ -0,0 +1,102 @@
+//! Consent prompts for the permissions plugins declare in `plugin.json`.
+//!
+//! A plugin with a manifest can only read files, write files, run processes,
+//! make network requests or read environment variables once the user has
+//! allowed it to. The first call that needs a permission asks for it here;
+//! allowed permissions are saved in the plugin's config so they are only
+//! asked for once.
+
+use super::Editor;
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+
+/// What a permission lets a plugin do, for the consent prompt
+pub(super) fn plugin_permission_action(capability: &str) -> String {
+    match capability {
+        "fs-read" => t!("plugin_permission.fs_read").to_string(),
+        "fs-write" => t!("plugin_permission.fs_write").to_string(),
+        "process" => t!("plugin_permission.process").to_string(),
+        "network" => t!("plugin_permission.network").to_string(),
+        "env" => t!("plugin_permission.env").to_string(),
+        other => other.to_string(),
+    }
+}
+
+impl Editor {
+    /// Ask the user whether a plugin may use a permission from its manifest
+    pub(super) fn handle_request_plugin_permission(
+        &mut self,
+        plugin_name: String,
+        capability: String,
+    ) {
+        let granted = self
+            .config
+            .plugins
+            .get(&plugin_name)
+            .is_some_and(|plugin| plugin.permissions.contains(&capability));
+        if granted {
+            // Allowed already (e.g. the plugin asked again before the grant arrived)
+            self.plugin_manager
+                .grant_permission(&plugin_name, &capability);
+            return;
+        }
+        // Don't interrupt another prompt: the plugin asks again on its next call
+        if self.prompt.is_some() {
+            return;
+        }
+        if !self
+            .plugin_permission_requests
+            .insert((plugin_name.clone(), capability.clone()))
+        {
+            return;
+        }
+
+        self.start_prompt(
+            t!(
+                "prompt.plugin_permission",
+                plugin = &plugin_name,
+                action = plugin_permission_action(&capability)
+            )
+            .to_string(),
+            PromptType::ConfirmPluginPermission {
+                plugin_name,
+                capability,
+            },
+        );
+    }
+
+    /// Allow a plugin to use a permission, remembering it in the config
+    pub(super) fn grant_plugin_permission(&mut self, plugin_name: &str, capability: &str) {
+        let plugin = self
+            .config
+            .plugins
+            .entry(plugin_name.to_string())
+            .or_default();
+        if !plugin
+            .permissions
+            .iter()
+            .any(|granted| granted == capability)
+        {
+            plugin.permissions.push(capability.to_string());
+        }
+        if let Err(e) = self.save_config() {
+            tracing::warn!(
+                "Failed to save config after granting {} to {}: {}",
+                capability,
+                plugin_name,
+                e
+            );
+        }
+
+        self.plugin_manager
+            .grant_permission(plugin_name, capability);
+        self.set_status_message(
+            t!(
+                "plugin_permission.granted",
+                plugin = plugin_name,
+                action = plugin_permission_action(capability)
+            )
+            .to_string(),
+        );
+    }
+}
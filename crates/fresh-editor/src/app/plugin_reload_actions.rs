@@ -0,0 +1,172 @@
+//! Hot-reload of plugins whose files change on disk.
+//!
+//! The plugin directories are polled like open files are for auto-revert.
+//! A changed plugin is torn down (handlers, commands, overlays and the
+//! providers it registered with the editor) and loaded again; a new plugin
+//! file is loaded and a deleted one is unloaded. Plugins can also be
+//! reloaded by hand with the "Reload Plugin" command.
+
+use super::{watcher, Editor, PluginChange};
+use crate::input::commands::Suggestion;
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+impl Editor {
+    /// Reload, load or unload plugins whose files changed since the last poll
+    ///
+    /// Returns true if any plugin was reloaded, loaded or unloaded.
+    pub(super) fn poll_plugin_changes(&mut self) -> bool {
+        if self.time_source.elapsed_since(self.last_plugin_poll) < watcher::POLL_INTERVAL {
+            return false;
+        }
+        self.last_plugin_poll = self.time_source.now();
+
+        let changes = self.plugin_watcher.poll();
+        let mut any_handled = false;
+        for change in changes {
+            any_handled |= self.handle_plugin_change(change);
+        }
+        any_handled
+    }
+
+    #[cfg(feature = "plugins")]
+    fn handle_plugin_change(&mut self, change: PluginChange) -> bool {
+        let plugins = self.plugin_manager.list_plugins();
+        match change {
+            PluginChange::Changed { name, path } => {
+                match plugins.iter().find(|plugin| plugin.name == name) {
+                    // Still waiting for its activation event: it picks up the
+                    // new source when it is activated
+                    Some(plugin) if plugin.activated_by.is_none() => false,
+                    Some(_) => {
+                        self.reload_plugin_by_name(&name);
+                        true
+                    }
+                    None => {
+                        let enabled = self
+                            .config
+                            .plugins
+                            .entry(name.clone())
+                            .or_insert_with(|| {
+                                fresh_core::config::PluginConfig::new_with_path(path.clone())
+                            })
+                            .enabled;
+                        if !enabled {
+                            return false;
+                        }
+                        match self.plugin_manager.load_plugin(&path) {
+                            Ok(()) => self.set_status_message(
+                                t!("plugin_reload.loaded", name = &name).to_string(),
+                            ),
+                            Err(e) => self.set_status_message(
+                                t!("plugin_reload.failed", name = &name, error = e.to_string())
+                                    .to_string(),
+                            ),
+                        }
+                        true
+                    }
+                }
+            }
+            PluginChange::Removed { name } => {
+                if !plugins.iter().any(|plugin| plugin.name == name) {
+                    return false;
+                }
+                if let Err(e) = self.plugin_manager.unload_plugin(&name) {
+                    tracing::warn!("Failed to unload removed plugin {}: {}", name, e);
+                    return false;
+                }
+                self.set_status_message(t!("plugin_reload.unloaded", name = &name).to_string());
+                true
+            }
+        }
+    }
+
+    #[cfg(not(feature = "plugins"))]
+    fn handle_plugin_change(&mut self, _change: PluginChange) -> bool {
+        false
+    }
+
+    /// Prompt for a loaded plugin to reload
+    pub(super) fn start_reload_plugin_prompt(&mut self) {
+        #[cfg(feature = "plugins")]
+        let mut names: Vec<String> = self
+            .plugin_manager
+            .list_plugins()
+            .into_iter()
+            .filter(|plugin| plugin.activated_by.is_some())
+            .map(|plugin| plugin.name)
+            .collect();
+        #[cfg(not(feature = "plugins"))]
+        let mut names: Vec<String> = Vec::new();
+
+        if names.is_empty() {
+            self.set_status_message(t!("plugin_reload.none").to_string());
+            return;
+        }
+        names.sort();
+
+        let suggestions = names.into_iter().map(Suggestion::new).collect();
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("plugin_reload.prompt").to_string(),
+            PromptType::ReloadPlugin,
+            suggestions,
+        ));
+    }
+
+    /// Tear down a plugin and load it again from its file
+    pub(super) fn reload_plugin_by_name(&mut self, name: &str) {
+        #[cfg(feature = "plugins")]
+        let result = self.plugin_manager.reload_plugin(name);
+        #[cfg(not(feature = "plugins"))]
+        let result: anyhow::Result<()> = Err(anyhow::anyhow!("Plugin system not active"));
+
+        match result {
+            Ok(()) => {
+                self.set_status_message(t!("plugin_reload.reloaded", name = name).to_string())
+            }
+            Err(e) => self.set_status_message(
+                t!("plugin_reload.failed", name = name, error = e.to_string()).to_string(),
+            ),
+        }
+    }
+
+    /// Forget everything an unloaded plugin registered with the editor
+    pub(super) fn handle_plugin_unloaded(&mut self, plugin_name: &str) {
+        self.completion_providers
+            .retain(|provider| provider.plugin_name != plugin_name);
+        self.hover_providers
+            .retain(|provider| provider.plugin_name != plugin_name);
+        self.inline_suggestion_providers
+            .retain(|provider| provider.plugin_name != plugin_name);
+        self.plugin_formatters
+            .retain(|formatter| formatter.plugin_name != plugin_name);
+        self.plugin_evaluators
+            .retain(|evaluator| evaluator.plugin_name != plugin_name);
+        self.context_menu_items
+            .retain(|item| item.plugin_name != plugin_name);
+        self.menu_state.plugin_items.remove_plugin(plugin_name);
+        self.process_output_subscribers
+            .retain(|_, subscriber| subscriber != plugin_name);
+
+        let gutters: Vec<String> = self
+            .plugin_gutters
+            .iter()
+            .filter(|gutter| gutter.plugin_name == plugin_name)
+            .map(|gutter| gutter.namespace.clone())
+            .collect();
+        for namespace in gutters {
+            self.handle_unregister_gutter(&namespace);
+        }
+
+        let buffers = &mut self.buffers;
+        self.plugin_markers.retain(|_, marker| {
+            if marker.plugin_name != plugin_name {
+                return true;
+            }
+            if let Some(state) = buffers.get_mut(&marker.buffer_id) {
+                state.marker_list.delete(marker.marker_id);
+            }
+            false
+        });
+    }
+}
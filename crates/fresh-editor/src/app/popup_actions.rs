@@ -21,6 +21,24 @@ impl Editor {
     ///
     /// Returns `PopupConfirmResult` indicating what the caller should do next.
     pub fn handle_popup_confirm(&mut self) -> PopupConfirmResult {
+        // Check if this is a confirm popup (from plugin showConfirm)
+        if let Some(callback_id) = self.pending_confirm_callback.take() {
+            let button = self
+                .active_state()
+                .popups
+                .top()
+                .and_then(|p| p.selected_item())
+                .and_then(|item| item.data.clone());
+
+            self.hide_popup();
+            self.plugin_manager.resolve_callback(
+                callback_id,
+                serde_json::to_string(&button).unwrap_or_else(|_| "null".to_string()),
+            );
+
+            return PopupConfirmResult::EarlyReturn;
+        }
+
         // Check if this is an action popup (from plugin showActionPopup)
         if let Some((popup_id, _actions)) = &self.active_action_popup {
             let popup_id = popup_id.clone();
@@ -171,6 +189,14 @@ impl Editor {
             self.active_action_popup.as_ref().map(|(id, _)| id)
         );
 
+        // A dismissed confirm popup (from plugin showConfirm) resolves with null
+        if let Some(callback_id) = self.pending_confirm_callback.take() {
+            self.hide_popup();
+            self.plugin_manager
+                .resolve_callback(callback_id, "null".to_string());
+            return;
+        }
+
         // Check if this is an action popup (from plugin showActionPopup)
         if let Some((popup_id, _actions)) = self.active_action_popup.take() {
             tracing::info!(
@@ -196,8 +222,10 @@ impl Editor {
             self.set_status_message(t!("lsp.startup_cancelled_msg").to_string());
         }
         self.hide_popup();
-        // Clear completion items when popup is closed
+        // Clear completion items when popup is closed, and ignore plugin
+        // providers still working on them
         self.completion_items = None;
+        self.pending_plugin_completion_request = None;
     }
 
     /// Handle typing a character while completion popup is open.
@@ -267,7 +295,7 @@ impl Editor {
 
     /// Re-filter the completion popup based on current prefix.
     /// If no items match, dismiss the popup.
-    fn refilter_completion_popup(&mut self) {
+    pub(super) fn refilter_completion_popup(&mut self) {
         // Get stored completion items
         let items = match &self.completion_items {
             Some(items) if !items.is_empty() => items.clone(),
@@ -0,0 +1,286 @@
+//! Project-wide search with results streamed into a virtual buffer.
+//!
+//! The directory walk runs on a blocking task and sends matches back one file
+//! at a time, so the results buffer fills in while the search is running.
+
+use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::project_search::{
+    search_project, ProjectSearchMatch, ProjectSearchQuery, ProjectSearchSummary,
+};
+use crate::view::prompt::PromptType;
+use rust_i18n::t;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Display name of the project search results buffer
+pub const PROJECT_SEARCH_BUFFER_NAME: &str = "*Search Results*";
+
+/// Buffer mode of the results buffer (Enter opens a match, Escape cancels)
+pub const PROJECT_SEARCH_MODE: &str = "project-search";
+
+/// State of the most recent project search
+pub(super) struct ProjectSearchRun {
+    id: u64,
+    buffer_id: BufferId,
+    cancel: Arc<AtomicBool>,
+    matches: usize,
+    files_with_matches: usize,
+    done: bool,
+}
+
+impl Editor {
+    /// Open the prompt for a project-wide search
+    pub fn start_project_search_prompt(&mut self) {
+        self.start_search_prompt(
+            t!("project_search.prompt").to_string(),
+            PromptType::ProjectSearch,
+            false,
+        );
+    }
+
+    /// Search the working directory for `pattern`, replacing any running search
+    pub fn start_project_search(&mut self, pattern: &str) {
+        if pattern.is_empty() {
+            return;
+        }
+        if self.remote_connection_info().is_some() {
+            self.set_status_message(t!("project_search.remote_unsupported").to_string());
+            return;
+        }
+        let query = ProjectSearchQuery {
+            pattern: pattern.to_string(),
+            case_sensitive: self.search_case_sensitive,
+            whole_word: self.search_whole_word,
+            use_regex: self.search_use_regex,
+        };
+        let regex = match query.to_regex() {
+            Ok(regex) => regex,
+            Err(e) => {
+                self.set_status_message(
+                    t!("error.invalid_regex", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return;
+        };
+        let sender = bridge.sender();
+        let runtime = runtime.handle().clone();
+
+        let previous_id = self.project_search.as_ref().map_or(0, |run| {
+            run.cancel.store(true, Ordering::Relaxed);
+            run.id
+        });
+        let id = previous_id + 1;
+
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == PROJECT_SEARCH_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                PROJECT_SEARCH_BUFFER_NAME.to_string(),
+                PROJECT_SEARCH_MODE.to_string(),
+                true,
+            )
+        });
+        let header = t!(
+            "project_search.header",
+            query = pattern,
+            dir = self.working_dir.display().to_string()
+        );
+        let entries = vec![TextPropertyEntry::text(format!("{}\n\n", header))];
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to fill project search buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.project_search = Some(ProjectSearchRun {
+            id,
+            buffer_id,
+            cancel: Arc::clone(&cancel),
+            matches: 0,
+            files_with_matches: 0,
+            done: false,
+        });
+        self.set_status_message(t!("project_search.searching").to_string());
+
+        let root = self.working_dir.clone();
+        let max_file_size = self.config.editor.large_file_threshold_bytes;
+        runtime.spawn_blocking(move || {
+            let summary = search_project(
+                &root,
+                &regex,
+                max_file_size,
+                &cancel,
+                |matches, files_searched| {
+                    let _ = sender.send(AsyncMessage::ProjectSearchResults {
+                        search_id: id,
+                        matches,
+                        files_searched,
+                    });
+                },
+            );
+            let _ = sender.send(AsyncMessage::ProjectSearchFinished {
+                search_id: id,
+                summary,
+            });
+        });
+    }
+
+    /// Stop the running project search, keeping the results found so far
+    pub fn cancel_project_search(&mut self) {
+        match &self.project_search {
+            Some(run) if !run.done => {
+                run.cancel.store(true, Ordering::Relaxed);
+            }
+            _ => self.set_status_message(t!("project_search.not_running").to_string()),
+        }
+    }
+
+    /// Append one file's matches to the results buffer and update the counter
+    pub(super) fn handle_project_search_results(
+        &mut self,
+        search_id: u64,
+        matches: Vec<ProjectSearchMatch>,
+        files_searched: usize,
+    ) {
+        let Some(run) = self
+            .project_search
+            .as_mut()
+            .filter(|run| run.id == search_id && !run.done)
+        else {
+            return;
+        };
+        let buffer_id = run.buffer_id;
+        run.matches += matches.len();
+        if !matches.is_empty() {
+            run.files_with_matches += 1;
+        }
+        let (count, files) = (run.matches, run.files_with_matches);
+
+        if let Some(first) = matches.first() {
+            let entries = result_entries(&self.working_dir, &first.path, &matches);
+            if self
+                .append_virtual_buffer_content(buffer_id, entries)
+                .is_err()
+            {
+                // The results buffer was closed: stop searching
+                if let Some(run) = &mut self.project_search {
+                    run.cancel.store(true, Ordering::Relaxed);
+                    run.done = true;
+                }
+                return;
+            }
+        }
+
+        self.set_status_message(
+            t!(
+                "project_search.progress",
+                count = count,
+                files = files,
+                searched = files_searched
+            )
+            .to_string(),
+        );
+    }
+
+    /// Append the summary line once the search has finished or was cancelled
+    pub(super) fn handle_project_search_finished(
+        &mut self,
+        search_id: u64,
+        summary: ProjectSearchSummary,
+    ) {
+        let Some(run) = self
+            .project_search
+            .as_mut()
+            .filter(|run| run.id == search_id && !run.done)
+        else {
+            return;
+        };
+        run.done = true;
+        let buffer_id = run.buffer_id;
+
+        let message = if run.matches == 0 && !summary.cancelled {
+            t!(
+                "project_search.no_matches",
+                searched = summary.files_searched
+            )
+            .to_string()
+        } else {
+            t!(
+                "project_search.summary",
+                count = run.matches,
+                files = run.files_with_matches,
+                searched = summary.files_searched
+            )
+            .to_string()
+        };
+        let message = if summary.cancelled {
+            t!("project_search.cancelled", summary = message).to_string()
+        } else {
+            message
+        };
+
+        let _ = self.append_virtual_buffer_content(
+            buffer_id,
+            vec![TextPropertyEntry::text(format!("{}\n", message))],
+        );
+        self.set_status_message(message);
+    }
+
+    /// Open the file and line of the search result under the cursor
+    pub fn open_project_search_result(&mut self) {
+        let location = self.get_text_properties_at_cursor().and_then(|props| {
+            props.iter().find_map(|p| {
+                let file = p.get("file").and_then(|v| v.as_str())?;
+                let line = p.get("line").and_then(|v| v.as_u64()).unwrap_or(1);
+                let column = p.get("column").and_then(|v| v.as_u64());
+                Some((file.to_string(), line as usize, column.map(|c| c as usize)))
+            })
+        });
+        let Some((file, line, column)) = location else {
+            return;
+        };
+
+        match self.open_file(Path::new(&file)) {
+            Ok(_) => self.goto_line_col(line, column),
+            Err(e) => {
+                self.set_status_message(t!("file.error_opening", error = e.to_string()).to_string())
+            }
+        }
+    }
+}
+
+/// Results buffer lines for one file: its path followed by each matching line
+fn result_entries(
+    working_dir: &Path,
+    path: &Path,
+    matches: &[ProjectSearchMatch],
+) -> Vec<TextPropertyEntry> {
+    let file = serde_json::json!(path.to_string_lossy());
+    let display = path.strip_prefix(working_dir).unwrap_or(path);
+
+    let mut entries = vec![TextPropertyEntry::text(format!("{}\n", display.display()))
+        .with_property("file", file.clone())];
+    entries.extend(matches.iter().map(|m| {
+        TextPropertyEntry::text(format!("{:>6}:{}: {}\n", m.line, m.column, m.text))
+            .with_property("file", file.clone())
+            .with_property("line", serde_json::json!(m.line))
+            .with_property("column", serde_json::json!(m.column))
+    }));
+    entries.push(TextPropertyEntry::text("\n"));
+    entries
+}
@@ -5,11 +5,13 @@
 use rust_i18n::t;
 
 use super::normalize_path;
+use super::plugin_permission_actions::plugin_permission_action;
 use super::BufferId;
 use super::BufferMetadata;
 use super::Editor;
+use super::SafeModeComponent;
+use crate::config_io::expand_path;
 use crate::input::keybindings::Action;
-use crate::primitives::path_utils::expand_tilde;
 use crate::services::plugins::hooks::HookArgs;
 use crate::view::prompt::PromptType;
 
@@ -35,8 +37,8 @@ impl Editor {
     ) -> PromptResult {
         match prompt_type {
             PromptType::OpenFile => {
-                // Expand tilde to home directory first
-                let expanded_path = expand_tilde(&input);
+                // Expand ~ and environment variables first
+                let expanded_path = expand_path(&input);
                 let resolved_path = if expanded_path.is_absolute() {
                     normalize_path(&expanded_path)
                 } else {
@@ -54,8 +56,8 @@ impl Editor {
                 }
             }
             PromptType::SwitchProject => {
-                // Expand tilde to home directory first
-                let expanded_path = expand_tilde(&input);
+                // Expand ~ and environment variables first
+                let expanded_path = expand_path(&input);
                 let resolved_path = if expanded_path.is_absolute() {
                     normalize_path(&expanded_path)
                 } else {
@@ -221,6 +223,9 @@ impl Editor {
                     self.set_status_message(t!("buffer.revert_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmEditLibraryFile => {
+                self.allow_editing_library_buffer();
+            }
             PromptType::ConfirmSaveConflict => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "o" || input_lower == "overwrite" {
@@ -233,6 +238,18 @@ impl Editor {
                     self.set_status_message(t!("buffer.save_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmSaveInvalidUtf8 => {
+                let input_lower = input.trim().to_lowercase();
+                if input_lower == "s" || input_lower == "save" {
+                    if let Err(e) = self.save() {
+                        self.set_status_message(
+                            t!("file.save_failed", error = e.to_string()).to_string(),
+                        );
+                    }
+                } else {
+                    self.set_status_message(t!("buffer.save_cancelled").to_string());
+                }
+            }
             PromptType::ConfirmSudoSave { info } => {
                 let input_lower = input.trim().to_lowercase();
                 if input_lower == "y" || input_lower == "yes" {
@@ -334,12 +351,46 @@ impl Editor {
                     self.set_status_message(t!("explorer.delete_cancelled").to_string());
                 }
             }
+            PromptType::ConfirmPluginPermission {
+                plugin_name,
+                capability,
+            } => {
+                let input_lower = input.trim().to_lowercase();
+                if input_lower == "y" || input_lower == "yes" {
+                    self.grant_plugin_permission(&plugin_name, &capability);
+                } else {
+                    self.set_status_message(
+                        t!(
+                            "plugin_permission.denied",
+                            plugin = &plugin_name,
+                            action = plugin_permission_action(&capability)
+                        )
+                        .to_string(),
+                    );
+                }
+            }
             PromptType::StopLspServer => {
                 self.handle_stop_lsp_server(&input);
             }
+            PromptType::EnableLspServer => {
+                self.enable_detected_lsp_server(input.trim());
+            }
             PromptType::SelectTheme { .. } => {
                 self.apply_theme(input.trim());
             }
+            PromptType::SelectSplitTheme => {
+                let theme = Some(input.trim().to_string()).filter(|name| !name.is_empty());
+                self.set_split_theme(self.split_manager.active_split(), theme);
+            }
+            PromptType::MoveTabToSplit => {
+                self.move_active_tab_to_split(input.trim());
+            }
+            PromptType::ProjectSearch => {
+                self.start_project_search(&input);
+            }
+            PromptType::ImportBase16Theme => {
+                self.import_base16_theme(&input);
+            }
             PromptType::SelectKeybindingMap => {
                 self.apply_keybinding_map(input.trim());
             }
@@ -357,6 +408,33 @@ impl Editor {
                     self.switch_to_tab(BufferId(id));
                 }
             }
+            PromptType::SwitchUndoBranch => {
+                if let Ok(id) = input.trim().parse::<usize>() {
+                    self.switch_undo_branch(id);
+                }
+            }
+            PromptType::PasteFromClipboardHistory => {
+                if let Ok(index) = input.trim().parse::<usize>() {
+                    self.paste_from_clipboard_history(index);
+                }
+            }
+            PromptType::InsertUnicode => {
+                self.insert_unicode_char(&input);
+            }
+            PromptType::OpenRecentWorkspace { new_process } => {
+                self.open_recent_workspace(&input, new_process);
+            }
+            PromptType::Digraph => {
+                // The digraph is inserted as soon as its second character is typed
+            }
+            PromptType::ReloadPlugin => {
+                self.reload_plugin_by_name(input.trim());
+            }
+            PromptType::SafeModeReenable => {
+                if let Some(component) = SafeModeComponent::from_id(input.trim()) {
+                    self.reenable_safe_mode_component(component);
+                }
+            }
             PromptType::QueryReplaceConfirm => {
                 // This is handled by InsertChar, not PromptConfirm
                 // But if somehow Enter is pressed, treat it as skip (n)
@@ -373,9 +451,34 @@ impl Editor {
             PromptType::SetLanguage => {
                 self.handle_set_language(&input);
             }
+            PromptType::ReopenWithEncoding => {
+                self.handle_reopen_with_encoding(&input);
+            }
             PromptType::ShellCommand { replace } => {
                 self.handle_shell_command(&input, replace);
             }
+            PromptType::PluginFileBrowser => {
+                // Only reached when the dialog state is gone; resolve the typed path
+                let expanded_path = expand_path(&input);
+                let resolved_path = if expanded_path.is_absolute() {
+                    normalize_path(&expanded_path)
+                } else {
+                    normalize_path(&self.working_dir.join(&expanded_path))
+                };
+                self.resolve_plugin_file_browser(Some(resolved_path));
+            }
+            PromptType::QuickPick => {
+                // The input is the item index when a suggestion was highlighted
+                let selected = selected_index.and_then(|_| input.parse::<usize>().ok());
+                self.confirm_quick_pick(selected);
+            }
+            PromptType::RenamePreview => {
+                if let Err(e) = self.confirm_rename_preview() {
+                    self.set_status_message(
+                        t!("lsp.rename_failed", error = e.to_string()).to_string(),
+                    );
+                }
+            }
             PromptType::AsyncPrompt => {
                 // Resolve the pending async prompt callback with the input text
                 if let Some(callback_id) = self.pending_async_prompt_callback.take() {
@@ -389,9 +492,9 @@ impl Editor {
     }
 
     /// Handle SaveFileAs prompt confirmation.
-    fn handle_save_file_as(&mut self, input: &str) {
-        // Expand tilde to home directory first
-        let expanded_path = expand_tilde(input);
+    pub(super) fn handle_save_file_as(&mut self, input: &str) {
+        // Expand ~ and environment variables first
+        let expanded_path = expand_path(input);
         let full_path = if expanded_path.is_absolute() {
             normalize_path(&expanded_path)
         } else {
@@ -433,75 +536,84 @@ impl Editor {
             before_len
         );
 
-        match self.active_state_mut().buffer.save_to_file(&full_path) {
-            Ok(()) => {
-                let after_save_idx = self.active_event_log().current_index();
-                let after_save_len = self.active_event_log().len();
-                tracing::debug!(
-                    "SaveFileAs AFTER buffer.save_to_file: event_log index={}, len={}",
-                    after_save_idx,
-                    after_save_len
-                );
+        let result = match self.begin_large_save(&full_path, true) {
+            Ok(true) => return,
+            Ok(false) => self.active_state_mut().buffer.save_to_file(&full_path),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => self.finish_save_file_as(full_path),
+            Err(e) => {
+                self.pending_close_buffer = None;
+                self.set_status_message(t!("file.error_saving", error = e.to_string()).to_string());
+            }
+        }
+    }
 
-                let metadata = BufferMetadata::with_file(full_path.clone(), &self.working_dir);
-                self.buffer_metadata.insert(self.active_buffer(), metadata);
+    /// Update the active buffer's metadata and state after it was saved as
+    /// `full_path`, then close it if that was pending
+    pub(crate) fn finish_save_file_as(&mut self, full_path: std::path::PathBuf) {
+        let after_save_idx = self.active_event_log().current_index();
+        let after_save_len = self.active_event_log().len();
+        tracing::debug!(
+            "SaveFileAs AFTER buffer.save_to_file: event_log index={}, len={}",
+            after_save_idx,
+            after_save_len
+        );
 
-                // Auto-detect language if it's currently "text"
-                // This ensures syntax highlighting works immediately after "Save As"
-                if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
-                    if state.language == "text" {
-                        if let Some(filename) = full_path.file_name().and_then(|n| n.to_str()) {
-                            state.set_language_from_name(filename, &self.grammar_registry);
-                        }
-                    }
+        let metadata = BufferMetadata::with_file(full_path.clone(), &self.working_dir);
+        self.buffer_metadata.insert(self.active_buffer(), metadata);
+
+        // Auto-detect language if it's currently "text"
+        // This ensures syntax highlighting works immediately after "Save As"
+        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            if state.language == "text" {
+                if let Some(filename) = full_path.file_name().and_then(|n| n.to_str()) {
+                    state.set_language_from_name(filename, &self.grammar_registry);
                 }
+            }
+        }
 
-                self.active_event_log_mut().mark_saved();
-                tracing::debug!(
-                    "SaveFileAs AFTER mark_saved: event_log index={}, len={}",
-                    self.active_event_log().current_index(),
-                    self.active_event_log().len()
-                );
+        self.active_event_log_mut().mark_saved();
+        tracing::debug!(
+            "SaveFileAs AFTER mark_saved: event_log index={}, len={}",
+            self.active_event_log().current_index(),
+            self.active_event_log().len()
+        );
 
-                if let Ok(metadata) = self.filesystem.metadata(&full_path) {
-                    if let Some(mtime) = metadata.modified {
-                        self.file_mod_times.insert(full_path.clone(), mtime);
-                    }
-                }
+        if let Ok(metadata) = self.filesystem.metadata(&full_path) {
+            if let Some(mtime) = metadata.modified {
+                self.file_mod_times.insert(full_path.clone(), mtime);
+            }
+        }
 
-                self.notify_lsp_save();
+        self.notify_lsp_save();
 
-                self.emit_event(
-                    crate::model::control_event::events::FILE_SAVED.name,
-                    serde_json::json!({"path": full_path.display().to_string()}),
-                );
+        self.emit_event(
+            crate::model::control_event::events::FILE_SAVED.name,
+            serde_json::json!({"path": full_path.display().to_string()}),
+        );
 
-                self.plugin_manager.run_hook(
-                    "after_file_save",
-                    crate::services::plugins::hooks::HookArgs::AfterFileSave {
-                        buffer_id: self.active_buffer(),
-                        path: full_path.clone(),
-                    },
-                );
+        self.plugin_manager.run_hook(
+            "after_file_save",
+            crate::services::plugins::hooks::HookArgs::AfterFileSave {
+                buffer_id: self.active_buffer(),
+                path: full_path.clone(),
+            },
+        );
 
-                if let Some(buffer_to_close) = self.pending_close_buffer.take() {
-                    if let Err(e) = self.force_close_buffer(buffer_to_close) {
-                        self.set_status_message(
-                            t!("file.saved_cannot_close", error = e.to_string()).to_string(),
-                        );
-                    } else {
-                        self.set_status_message(t!("buffer.saved_and_closed").to_string());
-                    }
-                } else {
-                    self.set_status_message(
-                        t!("file.saved_as", path = full_path.display().to_string()).to_string(),
-                    );
-                }
-            }
-            Err(e) => {
-                self.pending_close_buffer = None;
-                self.set_status_message(t!("file.error_saving", error = e.to_string()).to_string());
+        if let Some(buffer_to_close) = self.pending_close_buffer.take() {
+            if let Err(e) = self.force_close_buffer(buffer_to_close) {
+                self.set_status_message(
+                    t!("file.saved_cannot_close", error = e.to_string()).to_string(),
+                );
+            } else {
+                self.set_status_message(t!("buffer.saved_and_closed").to_string());
             }
+        } else {
+            self.set_status_message(
+                t!("file.saved_as", path = full_path.display().to_string()).to_string(),
+            );
         }
     }
 
@@ -603,7 +715,7 @@ impl Editor {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
                 state.language = "Plain Text".to_string();
                 state.highlighter = HighlightEngine::None;
-                self.set_status_message("Language set to Plain Text".to_string());
+                self.set_status_message(t!("status.language_plain_text").to_string());
             }
             return;
         }
@@ -625,10 +737,11 @@ impl Editor {
                 if let Some(lang) = ts_language {
                     state.reference_highlighter.set_language(&lang);
                 }
-                self.set_status_message(format!("Language set to {}", trimmed));
+                self.set_status_message(t!("status.language_set", language = trimmed).to_string());
+                self.activate_language_plugins(buffer_id);
             }
         } else {
-            self.set_status_message(format!("Unknown language: {}", input));
+            self.set_status_message(t!("status.language_unknown", language = input).to_string());
         }
     }
 
@@ -679,12 +792,11 @@ impl Editor {
                     return true; // Early return
                 }
                 self.set_active_buffer(old_active);
-                if let Err(e) = self.force_close_buffer(buffer_id) {
-                    self.set_status_message(
-                        t!("file.cannot_close", error = e.to_string()).to_string(),
-                    );
+                if self.is_saving() {
+                    // Closed once the save has been written
+                    self.pending_close_buffer = Some(buffer_id);
                 } else {
-                    self.set_status_message(t!("buffer.saved_and_closed").to_string());
+                    self.close_saved_buffer(buffer_id);
                 }
             } else {
                 self.pending_close_buffer = Some(buffer_id);
@@ -0,0 +1,212 @@
+//! Filtering of large prompt suggestion lists on a background task.
+//!
+//! Short lists are filtered inline. Longer ones (every project file, every
+//! symbol) are scored on a blocking task that streams its matches back, so
+//! the prompt keeps up with typing however many candidates there are.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::services::async_bridge::AsyncMessage;
+use crate::services::suggestion_filter::{
+    score_candidates, sort_matches, top_matches, FilterSource, SuggestionSource,
+    BACKGROUND_FILTER_THRESHOLD,
+};
+use crate::view::prompt::PromptType;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Matches shown while a filter is still running
+const STREAMED_RESULTS: usize = 100;
+
+/// State of the most recent background prompt filter
+pub(super) struct PromptFilterRun {
+    id: u64,
+    /// The prompt and input being filtered; results for anything else are stale
+    prompt_type: PromptType,
+    input: String,
+    source: Arc<dyn FilterSource>,
+    cancel: Arc<AtomicBool>,
+    matches: Vec<(usize, i32)>,
+    /// Keep only the best this many matches (None keeps them all)
+    limit: Option<usize>,
+    /// Whether any results have been shown for this input yet
+    shown: bool,
+    done: bool,
+}
+
+impl Editor {
+    /// Fuzzy filter the prompt's original suggestions against its input,
+    /// in the background when there are many of them
+    pub(super) fn filter_prompt_suggestions(&mut self, match_description: bool) {
+        let Some(prompt) = &self.prompt else {
+            return;
+        };
+        // Plugins that already filtered for this input are left alone
+        let set_by_plugin = prompt.suggestions_set_for_input.as_ref() == Some(&prompt.input);
+        let large = prompt
+            .original_suggestions
+            .as_ref()
+            .filter(|original| original.len() >= BACKGROUND_FILTER_THRESHOLD)
+            .filter(|_| !prompt.input.is_empty() && !set_by_plugin)
+            .map(Arc::clone);
+        if let Some(original) = large {
+            let source = Arc::new(SuggestionSource {
+                suggestions: original,
+                match_description,
+            });
+            if self.start_prompt_filter(source, None) {
+                return;
+            }
+        }
+        if let Some(prompt) = &mut self.prompt {
+            prompt.filter_suggestions(match_description);
+        }
+    }
+
+    /// Score `source` against the prompt's input on a blocking task,
+    /// replacing any filter still running. Returns false when there is no
+    /// runtime to run it on, so the caller should filter inline.
+    pub(super) fn start_prompt_filter(
+        &mut self,
+        source: Arc<dyn FilterSource>,
+        limit: Option<usize>,
+    ) -> bool {
+        let (Some(runtime), Some(bridge)) = (&self.tokio_runtime, &self.async_bridge) else {
+            return false;
+        };
+        let Some(prompt) = &self.prompt else {
+            return false;
+        };
+        let sender = bridge.sender();
+        let runtime = runtime.handle().clone();
+
+        let previous_id = self.prompt_filter.as_ref().map_or(0, |run| {
+            run.cancel.store(true, Ordering::Relaxed);
+            run.id
+        });
+        let id = previous_id + 1;
+        let query = prompt.input.clone();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.prompt_filter = Some(PromptFilterRun {
+            id,
+            prompt_type: prompt.prompt_type.clone(),
+            input: query.clone(),
+            source: Arc::clone(&source),
+            cancel: Arc::clone(&cancel),
+            matches: Vec::new(),
+            limit,
+            shown: false,
+            done: false,
+        });
+
+        runtime.spawn_blocking(move || {
+            let completed = score_candidates(source.as_ref(), &query, &cancel, |matches| {
+                if !matches.is_empty() {
+                    let _ = sender.send(AsyncMessage::PromptFilterResults {
+                        filter_id: id,
+                        matches,
+                    });
+                }
+            });
+            if completed {
+                let _ = sender.send(AsyncMessage::PromptFilterFinished { filter_id: id });
+            }
+        });
+        true
+    }
+
+    /// Stop the running prompt filter (its input has changed)
+    pub(super) fn cancel_prompt_filter(&mut self) {
+        if let Some(run) = &mut self.prompt_filter {
+            run.cancel.store(true, Ordering::Relaxed);
+            run.done = true;
+        }
+    }
+
+    /// Finish the running prompt filter on this thread, so that confirming
+    /// the prompt picks from the results for what was typed
+    pub(super) fn finish_prompt_filter_now(&mut self) {
+        let Some(run) = self.active_prompt_filter(None) else {
+            return;
+        };
+        run.cancel.store(true, Ordering::Relaxed);
+        let source = Arc::clone(&run.source);
+        let query = run.input.clone();
+        run.matches = (0..source.len())
+            .filter_map(|index| source.score(&query, index).map(|score| (index, score)))
+            .collect();
+        self.complete_prompt_filter();
+    }
+
+    /// Show the best matches found so far by the running prompt filter
+    pub(super) fn handle_prompt_filter_results(
+        &mut self,
+        filter_id: u64,
+        matches: Vec<(usize, i32)>,
+    ) {
+        let Some(run) = self.active_prompt_filter(Some(filter_id)) else {
+            return;
+        };
+        run.matches.extend(matches);
+        let keep_selection = run.shown;
+        run.shown = true;
+        let suggestions = top_matches(&run.matches, STREAMED_RESULTS)
+            .into_iter()
+            .map(|(index, _)| run.source.suggestion(index))
+            .collect();
+        self.show_filtered_suggestions(suggestions, keep_selection);
+    }
+
+    /// Show every match once the prompt filter has scored all candidates
+    pub(super) fn handle_prompt_filter_finished(&mut self, filter_id: u64) {
+        if self.active_prompt_filter(Some(filter_id)).is_some() {
+            self.complete_prompt_filter();
+        }
+    }
+
+    /// The running prompt filter (with ID `filter_id`, if given), unless the
+    /// prompt it was filtering for has changed since it started
+    fn active_prompt_filter(&mut self, filter_id: Option<u64>) -> Option<&mut PromptFilterRun> {
+        let prompt = self.prompt.as_ref()?;
+        self.prompt_filter.as_mut().filter(|run| {
+            !run.done
+                && filter_id.is_none_or(|id| id == run.id)
+                && run.prompt_type == prompt.prompt_type
+                && run.input == prompt.input
+        })
+    }
+
+    /// Replace the prompt's suggestions with all of the running filter's matches
+    fn complete_prompt_filter(&mut self) {
+        let Some(run) = &mut self.prompt_filter else {
+            return;
+        };
+        run.done = true;
+        let keep_selection = run.shown;
+        let mut matches = std::mem::take(&mut run.matches);
+        sort_matches(&mut matches);
+        if let Some(limit) = run.limit {
+            matches.truncate(limit);
+        }
+        let suggestions = matches
+            .into_iter()
+            .map(|(index, _)| run.source.suggestion(index))
+            .collect();
+        self.show_filtered_suggestions(suggestions, keep_selection);
+    }
+
+    /// Set the prompt's suggestions, selecting the first one unless
+    /// `keep_selection` and the selected index is still in range
+    fn show_filtered_suggestions(&mut self, suggestions: Vec<Suggestion>, keep_selection: bool) {
+        let Some(prompt) = &mut self.prompt else {
+            return;
+        };
+        prompt.suggestions = suggestions;
+        let len = prompt.suggestions.len();
+        prompt.selected_suggestion = match prompt.selected_suggestion {
+            _ if len == 0 => None,
+            Some(selected) if keep_selection && selected < len => Some(selected),
+            _ => Some(0),
+        };
+    }
+}
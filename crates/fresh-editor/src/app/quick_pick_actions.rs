@@ -0,0 +1,216 @@
+//! Quick pick: a filterable list shown for the `editor.quickPick` plugin API.
+//!
+//! The list is a prompt whose suggestions are the plugin's items, fuzzy
+//! filtered by the input. With `canPickMany`, Tab checks the highlighted item
+//! and Enter returns every checked item. The plugin's promise resolves with
+//! the chosen item(s) as they were passed in, or null when cancelled.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::{fuzzy_match, FuzzyMatch};
+use crate::view::prompt::{Prompt, PromptType};
+use fresh_core::api::{JsCallbackId, QuickPickItem, QuickPickOptions};
+use rust_i18n::t;
+
+/// State of the quick pick list while its prompt is open
+pub(super) struct QuickPickState {
+    items: Vec<QuickPickItem>,
+    /// Checked state per item (multi-select only)
+    picked: Vec<bool>,
+    can_pick_many: bool,
+    match_on_description: bool,
+    callback_id: JsCallbackId,
+}
+
+impl QuickPickState {
+    /// Items matching `input`, best match first, as prompt suggestions whose
+    /// value is the item index
+    fn suggestions(&self, input: &str) -> Vec<Suggestion> {
+        let mut matches: Vec<(usize, i32)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| {
+                let label = fuzzy_match(input, &item.label);
+                let description = match (&item.description, self.match_on_description) {
+                    (Some(description), true) => fuzzy_match(input, description),
+                    _ => FuzzyMatch::no_match(),
+                };
+                (label.matched || description.matched)
+                    .then(|| (index, label.score.max(description.score)))
+            })
+            .collect();
+        matches.sort_by(|a, b| b.1.cmp(&a.1));
+
+        matches
+            .into_iter()
+            .map(|(index, _)| {
+                let item = &self.items[index];
+                let mut text = String::new();
+                if self.can_pick_many {
+                    text.push_str(if self.picked[index] { "[x] " } else { "[ ] " });
+                }
+                if let Some(icon) = &item.icon {
+                    text.push_str(icon);
+                    text.push(' ');
+                }
+                text.push_str(&item.label);
+                Suggestion {
+                    text,
+                    description: item.description.clone(),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Editor {
+    /// Open the quick pick list for a plugin
+    pub(super) fn handle_start_quick_pick(
+        &mut self,
+        items: Vec<QuickPickItem>,
+        options: QuickPickOptions,
+        callback_id: JsCallbackId,
+    ) {
+        // Only one quick pick can be open; the previous one counts as cancelled
+        self.cancel_quick_pick();
+
+        let can_pick_many = options.can_pick_many.unwrap_or(false);
+        let state = QuickPickState {
+            picked: items
+                .iter()
+                .map(|item| can_pick_many && item.picked.unwrap_or(false))
+                .collect(),
+            items,
+            can_pick_many,
+            match_on_description: options.match_on_description.unwrap_or(false),
+            callback_id,
+        };
+
+        let title = options
+            .title
+            .unwrap_or_else(|| t!("prompt.quick_pick").to_string());
+        let mut prompt =
+            Prompt::with_suggestions(title, PromptType::QuickPick, state.suggestions(""));
+        prompt.placeholder = options.placeholder;
+        self.prompt = Some(prompt);
+        self.quick_pick = Some(state);
+    }
+
+    /// Re-filter the quick pick list for the current input
+    pub(super) fn update_quick_pick_suggestions(&mut self) {
+        let (Some(state), Some(prompt)) = (&self.quick_pick, &mut self.prompt) else {
+            return;
+        };
+        prompt.suggestions = state.suggestions(&prompt.input);
+        prompt.selected_suggestion = (!prompt.suggestions.is_empty()).then_some(0);
+    }
+
+    /// Check or uncheck the highlighted quick pick item (multi-select only)
+    pub(super) fn toggle_quick_pick_item(&mut self) {
+        let (Some(state), Some(prompt)) = (&mut self.quick_pick, &mut self.prompt) else {
+            return;
+        };
+        if !state.can_pick_many {
+            return;
+        }
+        let Some(index) = prompt
+            .selected_suggestion
+            .and_then(|selected| prompt.suggestions.get(selected))
+            .and_then(|suggestion| suggestion.value.as_deref())
+            .and_then(|value| value.parse::<usize>().ok())
+        else {
+            return;
+        };
+        state.picked[index] = !state.picked[index];
+
+        // Rebuild in place so the highlight stays on the toggled item
+        let selected = prompt.selected_suggestion;
+        prompt.suggestions = state.suggestions(&prompt.input);
+        prompt.selected_suggestion = selected;
+    }
+
+    /// Resolve the quick pick with the highlighted item index (if any)
+    pub(super) fn confirm_quick_pick(&mut self, selected: Option<usize>) {
+        let Some(state) = self.quick_pick.take() else {
+            return;
+        };
+
+        let result = if state.can_pick_many {
+            let mut chosen: Vec<&QuickPickItem> = state
+                .items
+                .iter()
+                .zip(&state.picked)
+                .filter_map(|(item, picked)| picked.then_some(item))
+                .collect();
+            // Enter without checking anything picks the highlighted item
+            if chosen.is_empty() {
+                chosen.extend(selected.and_then(|index| state.items.get(index)));
+            }
+            serde_json::to_string(&chosen)
+        } else {
+            serde_json::to_string(&selected.and_then(|index| state.items.get(index)))
+        };
+
+        self.plugin_manager.resolve_callback(
+            state.callback_id,
+            result.unwrap_or_else(|_| "null".to_string()),
+        );
+    }
+
+    /// Resolve an open quick pick with null
+    pub(super) fn cancel_quick_pick(&mut self) {
+        if let Some(state) = self.quick_pick.take() {
+            self.plugin_manager
+                .resolve_callback(state.callback_id, "null".to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: &str, description: Option<&str>) -> QuickPickItem {
+        QuickPickItem {
+            label: label.to_string(),
+            description: description.map(str::to_string),
+            icon: None,
+            picked: None,
+            value: None,
+        }
+    }
+
+    #[test]
+    fn test_quick_pick_suggestions_filter_and_mark_picked() {
+        let mut state = QuickPickState {
+            items: vec![
+                item("apple", Some("fruit")),
+                item("carrot", Some("vegetable")),
+                item("banana", Some("fruit")),
+            ],
+            picked: vec![false, true, false],
+            can_pick_many: true,
+            match_on_description: false,
+            callback_id: JsCallbackId::new(1),
+        };
+
+        let all = state.suggestions("");
+        assert_eq!(
+            all.iter().map(|s| s.text.as_str()).collect::<Vec<_>>(),
+            vec!["[ ] apple", "[x] carrot", "[ ] banana"]
+        );
+
+        let filtered = state.suggestions("ban");
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].value.as_deref(), Some("2"));
+
+        assert!(state.suggestions("fruit").is_empty());
+        state.match_on_description = true;
+        assert_eq!(state.suggestions("fruit").len(), 2);
+    }
+}
@@ -0,0 +1,195 @@
+//! Open Recent Workspace picker and workspace pinning
+//!
+//! The picker lists the workspaces of
+//! [`recent_workspaces`](crate::services::recent_workspaces), pinned ones
+//! first. Choosing one switches the editor to it like Switch Project does, or,
+//! for Open Recent Workspace in New Process, starts another editor process on
+//! it in a new terminal so both workspaces stay open.
+
+use super::Editor;
+use crate::config_io::expand_path;
+use crate::input::commands::Suggestion;
+use crate::services::recent_workspaces::RecentWorkspaces;
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Quote an argument for the shell a terminal runs
+fn shell_quote(arg: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\"\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+impl Editor {
+    fn load_recent_workspaces(&self) -> RecentWorkspaces {
+        RecentWorkspaces::load(&self.dir_context.recent_workspaces_path())
+    }
+
+    fn save_recent_workspaces(&self, workspaces: &RecentWorkspaces) {
+        if let Err(e) = workspaces.save(&self.dir_context.recent_workspaces_path()) {
+            tracing::warn!("Failed to save recent workspaces: {}", e);
+        }
+    }
+
+    /// The working directory as it is recorded in the recent workspace list
+    fn workspace_path(&self) -> PathBuf {
+        self.working_dir
+            .canonicalize()
+            .unwrap_or_else(|_| self.working_dir.clone())
+    }
+
+    /// Record the working directory as opened now, dropping stale workspaces
+    pub fn record_recent_workspace(&self) {
+        let mut workspaces = self.load_recent_workspaces();
+        workspaces.record(&self.workspace_path(), now_secs());
+        workspaces.prune(Path::is_dir);
+        self.save_recent_workspaces(&workspaces);
+    }
+
+    /// Open the Open Recent Workspace picker
+    ///
+    /// With `new_process`, the chosen workspace is opened by another editor
+    /// process instead of replacing this one's.
+    pub fn start_open_recent_workspace_prompt(&mut self, new_process: bool) {
+        let mut workspaces = self.load_recent_workspaces();
+        workspaces.prune(Path::is_dir);
+
+        let current = self.workspace_path();
+        let pinned_label = t!("file.workspace_pinned_label").to_string();
+        let suggestions: Vec<Suggestion> = workspaces
+            .sorted()
+            .into_iter()
+            .filter(|workspace| workspace.path != current)
+            .map(|workspace| {
+                let opened = super::file_open::format_modified(
+                    UNIX_EPOCH + Duration::from_secs(workspace.last_opened),
+                );
+                let path = workspace.path.to_string_lossy().to_string();
+                Suggestion {
+                    text: path.clone(),
+                    description: Some(if workspace.pinned {
+                        format!("{} · {}", pinned_label, opened)
+                    } else {
+                        opened
+                    }),
+                    value: Some(path),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+
+        if suggestions.is_empty() {
+            self.set_status_message(t!("file.no_recent_workspaces").to_string());
+            return;
+        }
+
+        let message = if new_process {
+            t!("file.recent_workspace_new_process_prompt")
+        } else {
+            t!("file.recent_workspace_prompt")
+        };
+        self.prompt = Some(Prompt::with_suggestions(
+            message.to_string(),
+            PromptType::OpenRecentWorkspace { new_process },
+            suggestions,
+        ));
+    }
+
+    /// Open a workspace chosen in the picker (or a typed directory)
+    pub(super) fn open_recent_workspace(&mut self, input: &str, new_process: bool) {
+        let path = expand_path(input.trim());
+        if !path.is_dir() {
+            let mut workspaces = self.load_recent_workspaces();
+            workspaces.prune(Path::is_dir);
+            self.save_recent_workspaces(&workspaces);
+            self.set_status_message(
+                t!(
+                    "file.workspace_not_found",
+                    path = path.display().to_string()
+                )
+                .to_string(),
+            );
+            return;
+        }
+
+        if new_process {
+            self.open_workspace_in_new_process(&path);
+        } else {
+            self.change_working_dir(path);
+        }
+    }
+
+    /// Start another editor process on `path` in a new terminal
+    fn open_workspace_in_new_process(&mut self, path: &Path) {
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe,
+            Err(e) => {
+                self.set_status_message(
+                    t!("file.workspace_new_process_failed", error = e.to_string()).to_string(),
+                );
+                return;
+            }
+        };
+        let Some(terminal_id) = self.open_terminal_in(path.to_path_buf()) else {
+            return;
+        };
+
+        // The shell reads the command once it has started
+        let command = format!(
+            "{} {}\r",
+            shell_quote(&exe.to_string_lossy()),
+            shell_quote(&path.to_string_lossy())
+        );
+        if let Some(terminal) = self.terminal_manager.get(terminal_id) {
+            terminal.write(command.as_bytes());
+        }
+        self.set_status_message(
+            t!(
+                "file.workspace_new_process",
+                path = path.display().to_string()
+            )
+            .to_string(),
+        );
+    }
+
+    /// Pin the working directory to the top of the recent workspaces, or unpin it
+    pub fn toggle_pin_workspace(&mut self) {
+        let path = self.workspace_path();
+        let mut workspaces = self.load_recent_workspaces();
+        let pinned = workspaces.toggle_pin(&path, now_secs());
+        self.save_recent_workspaces(&workspaces);
+
+        let path = path.display().to_string();
+        let message = if pinned {
+            t!("file.workspace_pinned", path = path)
+        } else {
+            t!("file.workspace_unpinned", path = path)
+        };
+        self.set_status_message(message.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(not(windows))]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("/home/me/my project"), "'/home/me/my project'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+    }
+}
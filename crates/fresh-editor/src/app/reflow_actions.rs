@@ -0,0 +1,119 @@
+//! Reflow Paragraph / Reflow Selection: re-wrap prose to `reflow_column`.
+//!
+//! The wrapping itself lives in `primitives::reflow`; this picks the lines to
+//! reflow, the comment prefix of the buffer's language, and applies the result
+//! as one undoable edit.
+
+use super::Editor;
+use crate::model::event::Event;
+use crate::primitives::reflow;
+use rust_i18n::t;
+use std::ops::Range;
+
+impl Editor {
+    /// Re-wrap the paragraph containing the cursor
+    pub(super) fn reflow_paragraph(&mut self) {
+        self.reflow_lines(false);
+    }
+
+    /// Re-wrap the lines covered by the selection, or the paragraph at the
+    /// cursor when nothing is selected
+    pub(super) fn reflow_selection(&mut self) {
+        self.reflow_lines(true);
+    }
+
+    fn reflow_lines(&mut self, use_selection: bool) {
+        let comment_prefix = self
+            .config
+            .languages
+            .get(&self.active_state().language)
+            .and_then(|lang_config| lang_config.comment_prefix.clone());
+        let markers = reflow::markers_for(comment_prefix.as_deref());
+        let column = self.config.editor.reflow_column;
+        let tab_size = self.config.editor.tab_size;
+
+        let state = self.active_state();
+        let Some(content) = state.buffer.to_string() else {
+            return;
+        };
+        let cursor = *state.cursors.primary();
+        let cursor_id = state.cursors.primary_id();
+        let line_ending = state.buffer.line_ending().as_str();
+
+        let line_of = |pos: usize| content[..pos.min(content.len())].matches('\n').count();
+        let lines = match cursor.selection_range().filter(|_| use_selection) {
+            Some(range) => {
+                // A selection ending at the start of a line doesn't include it
+                let last = line_of(range.end.saturating_sub(1).max(range.start));
+                line_of(range.start)..last + 1
+            }
+            None => match reflow::paragraph_at(&content, line_of(cursor.position), &markers) {
+                Some(lines) => lines,
+                None => {
+                    self.set_status_message(t!("reflow.no_paragraph").to_string());
+                    return;
+                }
+            },
+        };
+
+        let range = byte_range_of_lines(&content, lines.clone());
+        let text = &content[range.clone()];
+        let reflowed = reflow::reflow(text, column, &markers, tab_size, line_ending);
+        if reflowed == text {
+            self.set_status_message(t!("reflow.unchanged", column = column).to_string());
+            return;
+        }
+
+        // Leave the cursor at the end of the reflowed text's last line
+        let inserted_end = range.start + reflowed.len();
+        let new_position = range.start + reflowed.trim_end_matches(['\r', '\n']).len();
+        let line_count = reflowed.lines().count();
+        let events = vec![
+            Event::Delete {
+                range: range.clone(),
+                deleted_text: text.to_string(),
+                cursor_id,
+            },
+            Event::Insert {
+                position: range.start,
+                text: reflowed,
+                cursor_id,
+            },
+            Event::MoveCursor {
+                cursor_id,
+                old_position: inserted_end,
+                new_position,
+                old_anchor: None,
+                new_anchor: None,
+                old_sticky_column: 0,
+                new_sticky_column: 0,
+            },
+        ];
+        let batch = Event::Batch {
+            events,
+            description: "Reflow".to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        self.set_status_message(t!("reflow.done", from = lines.len(), to = line_count).to_string());
+    }
+}
+
+/// Byte range covering `lines` of `text`, including the last line's ending
+fn byte_range_of_lines(text: &str, lines: Range<usize>) -> Range<usize> {
+    let mut start = text.len();
+    let mut end = text.len();
+    let mut offset = 0;
+    for (index, line) in text.split_inclusive('\n').enumerate() {
+        if index == lines.start {
+            start = offset;
+        }
+        offset += line.len();
+        if index + 1 == lines.end {
+            end = offset;
+            break;
+        }
+    }
+    start..end
+}
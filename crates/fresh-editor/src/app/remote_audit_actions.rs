@@ -0,0 +1,114 @@
+//! Viewing and exporting the audit trail of a remote session.
+//!
+//! When editing over SSH, the [`crate::services::remote::AuditLog`] records
+//! every write, delete and process spawn sent to the remote host. These
+//! actions show it in a read-only buffer and export it as JSON Lines.
+
+use super::Editor;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::services::remote::AuditSource;
+use rust_i18n::t;
+
+/// Display name of the Remote Audit Log buffer
+const REMOTE_AUDIT_BUFFER_NAME: &str = "*Remote Audit Log*";
+
+/// Buffer mode of the Remote Audit Log buffer ('g' refreshes, 'e' exports)
+const REMOTE_AUDIT_MODE: &str = "remote-audit-log";
+
+impl Editor {
+    /// Open (or refresh) a read-only buffer listing the remote operations so far
+    pub fn show_remote_audit_log(&mut self) {
+        let Some(audit_log) = self.remote_audit_log.clone() else {
+            self.set_status_message(t!("remote_audit.not_remote").to_string());
+            return;
+        };
+
+        let connection = self
+            .remote_connection_info()
+            .unwrap_or_default()
+            .to_string();
+        let mut entries = vec![
+            TextPropertyEntry::text(format!("{}\n\n", t!("remote_audit.title"))),
+            TextPropertyEntry::text(format!(
+                "{}\n\n",
+                t!("remote_audit.summary", connection = connection)
+            )),
+        ];
+
+        let audit_entries = audit_log.entries();
+        if audit_entries.is_empty() {
+            entries.push(TextPropertyEntry::text(format!(
+                "  {}\n",
+                t!("remote_audit.empty")
+            )));
+        }
+        for entry in audit_entries {
+            let source = match entry.source {
+                AuditSource::User => t!("remote_audit.source_user"),
+                AuditSource::Plugin => t!("remote_audit.source_plugin"),
+            };
+            entries.push(TextPropertyEntry::text(format!(
+                "  {}  {:<8} {:<10} {}\n",
+                entry.time.format("%Y-%m-%d %H:%M:%S"),
+                source,
+                entry.operation,
+                entry.target
+            )));
+            if let Some(error) = entry.error {
+                entries.push(TextPropertyEntry::text(format!(
+                    "      {}\n",
+                    t!("remote_audit.failed", error = error)
+                )));
+            }
+        }
+
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == REMOTE_AUDIT_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                REMOTE_AUDIT_BUFFER_NAME.to_string(),
+                REMOTE_AUDIT_MODE.to_string(),
+                true,
+            )
+        });
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to fill remote audit log buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Write the audit trail to a local JSON Lines file in the log directory and open it
+    pub fn export_remote_audit_log(&mut self) {
+        let Some(audit_log) = self.remote_audit_log.clone() else {
+            self.set_status_message(t!("remote_audit.not_remote").to_string());
+            return;
+        };
+
+        // Not a `.log` file, so stale log cleanup leaves exports alone
+        let path = crate::services::log_dirs::log_dir().join(format!(
+            "remote-audit-{}.jsonl",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        if let Err(e) = std::fs::write(&path, audit_log.export_json_lines()) {
+            self.set_status_message(
+                t!("remote_audit.export_failed", error = e.to_string()).to_string(),
+            );
+            return;
+        }
+        // Use open_local_file since the export is always local
+        if let Err(e) = self.open_local_file(&path) {
+            tracing::warn!("Failed to open remote audit export: {}", e);
+        }
+        self.set_status_message(
+            t!("remote_audit.exported", path = path.display().to_string()).to_string(),
+        );
+    }
+}
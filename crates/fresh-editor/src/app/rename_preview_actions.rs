@@ -0,0 +1,182 @@
+//! Rename preview: review risky LSP rename edits before applying them.
+//!
+//! When a rename's WorkspaceEdit has edits that look risky (see
+//! [`rename_conflicts`](crate::primitives::rename_conflicts)), every edit is
+//! listed in a prompt with its file, line and risk. Risky edits start
+//! unchecked; Tab toggles the highlighted edit and Enter applies the checked
+//! ones. Renames without risky edits are applied directly.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::primitives::highlighter::Language;
+use crate::primitives::rename_conflicts::{rename_risks, RenameRisk};
+use crate::view::prompt::{Prompt, PromptType};
+use anyhow::Result as AnyhowResult;
+use rust_i18n::t;
+use std::path::{Path, PathBuf};
+
+/// One edit of the rename under review
+struct PreviewEdit {
+    path: PathBuf,
+    edit: lsp_types::TextEdit,
+    /// Trimmed text of the line the edit starts on
+    line_text: String,
+    risk: Option<RenameRisk>,
+    included: bool,
+}
+
+/// State of the rename preview while its prompt is open
+pub(super) struct RenamePreviewState {
+    edits: Vec<PreviewEdit>,
+}
+
+impl RenamePreviewState {
+    /// One suggestion per edit, whose value is the edit index
+    fn suggestions(&self, working_dir: &Path) -> Vec<Suggestion> {
+        self.edits
+            .iter()
+            .enumerate()
+            .map(|(index, edit)| {
+                let path = edit.path.strip_prefix(working_dir).unwrap_or(&edit.path);
+                Suggestion {
+                    text: format!(
+                        "{} {}:{}  {}",
+                        if edit.included { "[x]" } else { "[ ]" },
+                        path.display(),
+                        edit.edit.range.start.line + 1,
+                        edit.line_text
+                    ),
+                    description: edit.risk.map(|risk| {
+                        match risk {
+                            RenameRisk::NameInScope => t!("lsp.rename_risk_name_in_scope"),
+                            RenameRisk::InString => t!("lsp.rename_risk_in_string"),
+                            RenameRisk::InComment => t!("lsp.rename_risk_in_comment"),
+                        }
+                        .to_string()
+                    }),
+                    value: Some(index.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Editor {
+    /// Show the rename preview if any edit is risky
+    ///
+    /// Returns false, without opening anything, when every edit looks safe.
+    pub(super) fn start_rename_preview(
+        &mut self,
+        file_edits: &[(PathBuf, Vec<lsp_types::TextEdit>)],
+    ) -> AnyhowResult<bool> {
+        let mut edits = Vec::new();
+        for (path, text_edits) in file_edits {
+            let Some(language) = Language::from_path(path) else {
+                edits.extend(text_edits.iter().map(|edit| PreviewEdit {
+                    path: path.clone(),
+                    edit: edit.clone(),
+                    line_text: String::new(),
+                    risk: None,
+                    included: true,
+                }));
+                continue;
+            };
+
+            let buffer_id = self.open_file(path)?;
+            let Some(state) = self.buffers.get(&buffer_id) else {
+                continue;
+            };
+            let content = state.buffer.to_string().unwrap_or_default();
+            let ranges: Vec<_> = text_edits
+                .iter()
+                .map(|edit| {
+                    let start = state.buffer.lsp_position_to_byte(
+                        edit.range.start.line as usize,
+                        edit.range.start.character as usize,
+                    );
+                    let end = state.buffer.lsp_position_to_byte(
+                        edit.range.end.line as usize,
+                        edit.range.end.character as usize,
+                    );
+                    (start..end, edit.new_text.as_str())
+                })
+                .collect();
+            let risks = rename_risks(&content, &language, &ranges);
+
+            for (edit, risk) in text_edits.iter().zip(risks) {
+                let line_text = state
+                    .buffer
+                    .get_line(edit.range.start.line as usize)
+                    .map(|bytes| String::from_utf8_lossy(&bytes).trim().to_string())
+                    .unwrap_or_default();
+                edits.push(PreviewEdit {
+                    path: path.clone(),
+                    edit: edit.clone(),
+                    line_text,
+                    risk,
+                    included: risk.is_none(),
+                });
+            }
+        }
+
+        let risky = edits.iter().filter(|edit| edit.risk.is_some()).count();
+        if risky == 0 {
+            return Ok(false);
+        }
+
+        let state = RenamePreviewState { edits };
+        let mut prompt = Prompt::with_suggestions(
+            t!("lsp.rename_preview", count = risky).to_string(),
+            PromptType::RenamePreview,
+            state.suggestions(&self.working_dir),
+        );
+        prompt.selected_suggestion = Some(0);
+        self.prompt = Some(prompt);
+        self.rename_preview = Some(state);
+        Ok(true)
+    }
+
+    /// Check or uncheck the highlighted edit
+    pub(super) fn toggle_rename_preview_edit(&mut self) {
+        let (Some(state), Some(prompt)) = (&mut self.rename_preview, &mut self.prompt) else {
+            return;
+        };
+        let Some(edit) = prompt
+            .selected_suggestion
+            .and_then(|selected| state.edits.get_mut(selected))
+        else {
+            return;
+        };
+        edit.included = !edit.included;
+
+        let selected = prompt.selected_suggestion;
+        prompt.suggestions = state.suggestions(&self.working_dir);
+        prompt.selected_suggestion = selected;
+    }
+
+    /// Apply the checked edits
+    pub(super) fn confirm_rename_preview(&mut self) -> AnyhowResult<()> {
+        let Some(state) = self.rename_preview.take() else {
+            return Ok(());
+        };
+
+        let mut file_edits: Vec<(PathBuf, Vec<lsp_types::TextEdit>)> = Vec::new();
+        for edit in state.edits.into_iter().filter(|edit| edit.included) {
+            match file_edits.iter_mut().find(|(path, _)| *path == edit.path) {
+                Some((_, edits)) => edits.push(edit.edit),
+                None => file_edits.push((edit.path, vec![edit.edit])),
+            }
+        }
+        self.apply_rename_edits(file_edits)
+    }
+
+    /// Drop the rename without applying anything
+    pub(super) fn cancel_rename_preview(&mut self) {
+        if self.rename_preview.take().is_some() {
+            self.set_status_message(t!("lsp.rename_preview_cancelled").to_string());
+        }
+    }
+}
@@ -15,8 +15,8 @@ impl Editor {
         let active_split = self.split_manager.active_split();
         self.pre_sync_ensure_visible(active_split);
 
-        // Synchronize scroll sync groups (anchor-based scroll for side-by-side diffs)
-        // This sets viewport positions based on the authoritative scroll_line in each group
+        // Synchronize scroll sync groups (anchored or proportional scroll across splits)
+        // This sets the other splits' viewport positions from the active split's
         self.sync_scroll_groups();
 
         // NOTE: Viewport sync with cursor is handled by split_rendering.rs which knows the
@@ -84,6 +84,7 @@ impl Editor {
                     | PromptType::Replace { .. }
                     | PromptType::QueryReplaceSearch
                     | PromptType::QueryReplace { .. }
+                    | PromptType::ProjectSearch
             )
         });
 
@@ -95,7 +96,10 @@ impl Editor {
         let has_file_browser = self.prompt.as_ref().is_some_and(|p| {
             matches!(
                 p.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                PromptType::OpenFile
+                    | PromptType::SwitchProject
+                    | PromptType::SaveFileAs
+                    | PromptType::PluginFileBrowser
             )
         }) && self.file_open_state.is_some();
 
@@ -120,7 +124,21 @@ impl Editor {
             .split(size);
 
         let menu_bar_area = main_chunks[0];
-        let main_content_area = main_chunks[1];
+        let mut main_content_area = main_chunks[1];
+
+        // Safe mode and invalid UTF-8 banners take the first lines of the content area
+        let banners = [self.safe_mode_banner(), self.invalid_utf8_banner()];
+        for banner in banners.into_iter().flatten() {
+            if main_content_area.height > 1 {
+                let banner_area = ratatui::layout::Rect {
+                    height: 1,
+                    ..main_content_area
+                };
+                main_content_area.y += 1;
+                main_content_area.height -= 1;
+                Self::render_banner(frame, banner_area, &banner, &self.theme);
+            }
+        }
         let status_bar_idx = 2;
         let search_options_idx = 3;
         let prompt_line_idx = 4;
@@ -227,7 +245,8 @@ impl Editor {
                     // Fire view_transform_request hook with base tokens
                     // This allows plugins to transform the view (e.g., soft breaks for markdown)
                     let visible_count = split_area.height as usize;
-                    let is_binary = state.buffer.is_binary();
+                    let is_binary = state.buffer.is_binary() || state.hex_view;
+                    let ansi_colors = state.renders_ansi_colors();
                     let line_ending = state.buffer.line_ending();
                     let base_tokens =
                         crate::view::ui::split_rendering::SplitRenderer::build_base_tokens_for_hook(
@@ -236,6 +255,7 @@ impl Editor {
                             self.config.editor.estimated_line_length,
                             visible_count,
                             is_binary,
+                            ansi_colors,
                             line_ending,
                         );
                     let viewport_start = viewport_top_byte;
@@ -358,34 +378,56 @@ impl Editor {
 
         let is_maximized = self.split_manager.is_maximized();
 
-        let (split_areas, tab_layouts, close_split_areas, maximize_split_areas, view_line_mappings) =
-            SplitRenderer::render_content(
-                frame,
-                editor_content_area,
-                &self.split_manager,
-                &mut self.buffers,
-                &self.buffer_metadata,
-                &mut self.event_logs,
-                &self.composite_buffers,
-                &mut self.composite_view_states,
-                &self.theme,
-                self.ansi_background.as_ref(),
-                self.background_fade,
-                lsp_waiting,
-                self.config.editor.large_file_threshold_bytes,
-                self.config.editor.line_wrap,
-                self.config.editor.estimated_line_length,
-                self.config.editor.highlight_context_bytes,
-                Some(&mut self.split_view_states),
-                hide_cursor,
-                hovered_tab,
-                hovered_close_split,
-                hovered_maximize_split,
-                is_maximized,
-                self.config.editor.relative_line_numbers,
-                self.tab_bar_visible,
-                self.config.editor.use_terminal_bg,
-            );
+        // Splits drawn with their own theme (unknown names fall back to the editor theme)
+        let split_themes: std::collections::HashMap<_, _> = self
+            .split_view_states
+            .iter()
+            .filter_map(|(split_id, view_state)| {
+                let theme = self.theme_registry.get(view_state.theme.as_deref()?)?;
+                Some((*split_id, theme))
+            })
+            .collect();
+
+        let (
+            split_areas,
+            tab_layouts,
+            close_split_areas,
+            maximize_split_areas,
+            view_line_mappings,
+            sticky_scroll_headers,
+        ) = SplitRenderer::render_content(
+            frame,
+            editor_content_area,
+            &self.split_manager,
+            &mut self.buffers,
+            &self.buffer_metadata,
+            &mut self.event_logs,
+            &self.composite_buffers,
+            &mut self.composite_view_states,
+            &self.theme,
+            &split_themes,
+            self.ansi_background.as_ref(),
+            self.background_fade,
+            lsp_waiting,
+            self.config.editor.large_file_threshold_bytes,
+            self.config.editor.line_wrap,
+            self.config.editor.estimated_line_length,
+            self.config.editor.highlight_context_bytes,
+            Some(&mut self.split_view_states),
+            hide_cursor,
+            hovered_tab,
+            hovered_close_split,
+            hovered_maximize_split,
+            is_maximized,
+            self.config.editor.relative_line_numbers,
+            self.tab_bar_visible,
+            self.config.editor.use_terminal_bg,
+            if self.config.editor.sticky_scroll {
+                self.config.editor.sticky_scroll_max_depth
+            } else {
+                0
+            },
+        );
 
         // Detect viewport changes and fire hooks
         // Compare against previous frame's viewport state (stored in self.previous_viewports)
@@ -448,7 +490,27 @@ impl Editor {
             );
         }
 
-        // Render terminal content on top of split content for terminal buffers
+        // Fire split_focused when focus moved to another split since the last
+        // frame, however it moved (keyboard, mouse, splitting, closing, plugins)
+        let active_split = self.split_manager.active_split();
+        let previous_split = self.previous_active_split.replace(active_split);
+        if let Some(previous_split) = previous_split.filter(|&split| split != active_split) {
+            if let Some(buffer_id) = self.split_manager.get_buffer_id(active_split) {
+                self.plugin_manager.run_hook(
+                    "split_focused",
+                    crate::services::plugins::hooks::HookArgs::SplitFocused {
+                        split_id: active_split,
+                        previous_split_id: previous_split,
+                        buffer_id,
+                    },
+                );
+            }
+        }
+
+        // Render terminal content on top of split content for terminal buffers,
+        // keeping terminals on the current theme's ANSI palette (no-op unless it changed)
+        self.terminal_manager
+            .set_ansi_palette(self.theme.ansi_palette_rgb());
         self.render_terminal_splits(frame, &split_areas);
 
         self.cached_layout.split_areas = split_areas;
@@ -456,6 +518,7 @@ impl Editor {
         self.cached_layout.close_split_areas = close_split_areas;
         self.cached_layout.maximize_split_areas = maximize_split_areas;
         self.cached_layout.view_line_mappings = view_line_mappings;
+        self.cached_layout.sticky_scroll_headers = sticky_scroll_headers;
         self.cached_layout.separator_areas = self
             .split_manager
             .get_separators_with_ids(editor_content_area);
@@ -471,7 +534,10 @@ impl Editor {
             // For OpenFile/SwitchProject/SaveFileAs prompt, render the file browser popup
             if matches!(
                 prompt.prompt_type,
-                PromptType::OpenFile | PromptType::SwitchProject | PromptType::SaveFileAs
+                PromptType::OpenFile
+                    | PromptType::SwitchProject
+                    | PromptType::SaveFileAs
+                    | PromptType::PluginFileBrowser
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     // Calculate popup area: position above prompt line, covering status bar
@@ -536,11 +602,7 @@ impl Editor {
         }
 
         // Clone all immutable values before the mutable borrow
-        let display_name = self
-            .buffer_metadata
-            .get(&self.active_buffer())
-            .map(|m| m.display_name.clone())
-            .unwrap_or_else(|| "[No Name]".to_string());
+        let display_name = self.buffer_display_path(self.active_buffer());
         let status_message = self.status_message.clone();
         let plugin_status_message = self.plugin_status_message.clone();
         let prompt = self.prompt.clone();
@@ -566,7 +628,7 @@ impl Editor {
                 };
 
             // Compute status bar hover state for styling
-            use crate::view::ui::status_bar::StatusBarHover;
+            use crate::view::ui::status_bar::{StatusBarCustomization, StatusBarHover};
             let status_bar_hover = match &self.mouse_state.hover_target {
                 Some(HoverTarget::StatusBarLspIndicator) => StatusBarHover::LspIndicator,
                 Some(HoverTarget::StatusBarWarningBadge) => StatusBarHover::WarningBadge,
@@ -579,6 +641,14 @@ impl Editor {
 
             // Get remote connection info if editing remote files
             let remote_connection = self.remote_connection_info().map(|s| s.to_string());
+            let profile = self.active_profile().map(|s| s.to_string());
+            let zoomed = self.split_manager.is_zoomed();
+
+            // Clone the status bar customization (the active state is borrowed mutably)
+            let status_bar_template = self.config.editor.status_bar_template.clone();
+            let status_bar_segments = self.config.editor.status_bar_segments.clone();
+            let plugin_status_segments = self.plugin_status_segments.clone();
+            let editor_mode = self.editor_mode.clone();
 
             let status_bar_layout = StatusBarRenderer::render_status_bar(
                 frame,
@@ -596,6 +666,14 @@ impl Editor {
                 general_warning_count,        // Pass general warning count for badge
                 status_bar_hover,             // Pass hover state for indicator styling
                 remote_connection.as_deref(), // Pass remote connection info
+                profile.as_deref(),           // Pass active config profile
+                zoomed,                       // Pass split zoom state
+                StatusBarCustomization {
+                    template: &status_bar_template,
+                    segment_rules: &status_bar_segments,
+                    plugin_segments: &plugin_status_segments,
+                    mode: editor_mode.as_deref(),
+                },
             );
 
             // Store status bar layout for click detection
@@ -608,6 +686,7 @@ impl Editor {
                 status_bar_layout.line_ending_indicator;
             self.cached_layout.status_bar_language_area = status_bar_layout.language_indicator;
             self.cached_layout.status_bar_message_area = status_bar_layout.message_area;
+            self.cached_layout.status_bar_segment_commands = status_bar_layout.segment_commands;
         }
 
         // Render search options bar when in search prompt
@@ -660,6 +739,7 @@ impl Editor {
                 prompt.prompt_type,
                 crate::view::prompt::PromptType::OpenFile
                     | crate::view::prompt::PromptType::SwitchProject
+                    | crate::view::prompt::PromptType::PluginFileBrowser
             ) {
                 if let Some(file_open_state) = &self.file_open_state {
                     StatusBarRenderer::render_file_open_prompt(
@@ -687,6 +767,16 @@ impl Editor {
             }
         }
 
+        // Render floating tool windows above the splits, below popups and menus
+        if !self.floating_windows.is_empty() {
+            crate::view::floating_window::render_floating_windows(
+                frame,
+                size,
+                &self.floating_windows,
+                &self.theme,
+            );
+        }
+
         // Render popups from the active buffer state
         // Clone theme to avoid borrow checker issues with active_state_mut()
         let theme_clone = self.theme.clone();
@@ -801,6 +891,7 @@ impl Editor {
         // Render menu bar last so dropdown appears on top of all other content
         // Update menu context with current editor state
         self.update_menu_context();
+        self.update_windows_menu();
 
         // Render settings modal (before menu bar so menus can overlay)
         // Check visibility first to avoid borrow conflict with dimming
@@ -845,6 +936,12 @@ impl Editor {
             crate::view::event_debug::render_event_debug(frame, size, debug, &self.theme);
         }
 
+        // Render keybinding cheat sheet if open
+        if let Some(ref sheet) = self.cheat_sheet {
+            crate::view::dimming::apply_dimming(frame, size);
+            crate::view::cheat_sheet::render_cheat_sheet(frame, size, sheet, &self.theme);
+        }
+
         if self.menu_bar_visible {
             self.cached_layout.menu_layout = Some(crate::view::ui::MenuRenderer::render(
                 frame,
@@ -859,9 +956,9 @@ impl Editor {
             self.cached_layout.menu_layout = None;
         }
 
-        // Render tab context menu if open
-        if let Some(ref menu) = self.tab_context_menu {
-            self.render_tab_context_menu(frame, menu);
+        // Render context menu if open
+        if let Some(ref menu) = self.context_menu {
+            self.render_context_menu(frame, menu);
         }
 
         // Render tab drag drop zone overlay if dragging a tab
@@ -913,6 +1010,13 @@ impl Editor {
             frame.buffer_mut(),
             self.color_capability,
         );
+
+        // The first completed frame ends startup
+        if !self.startup_finished {
+            self.startup_finished = true;
+            self.plugin_manager
+                .activate(fresh_core::activation::ActivationEvent::StartupFinished);
+        }
     }
 
     /// Render the Quick Open hints line showing available mode prefixes
@@ -944,6 +1048,23 @@ impl Editor {
         frame.render_widget(paragraph, area);
     }
 
+    /// Render a warning banner across the top of the content area
+    fn render_banner(
+        frame: &mut Frame,
+        area: ratatui::layout::Rect,
+        text: &str,
+        theme: &crate::view::theme::Theme,
+    ) {
+        use ratatui::style::{Modifier, Style};
+        use ratatui::widgets::Paragraph;
+
+        let style = Style::default()
+            .fg(theme.status_warning_indicator_fg)
+            .bg(theme.status_warning_indicator_bg)
+            .add_modifier(Modifier::BOLD);
+        frame.render_widget(Paragraph::new(format!(" {}", text)).style(style), area);
+    }
+
     /// Apply dimming effect to UI elements outside the focused terminal area
     /// This visually indicates that keyboard capture mode is active
     fn apply_keyboard_capture_dimming(
@@ -1067,67 +1188,63 @@ impl Editor {
         }
     }
 
-    /// Render the tab context menu
-    fn render_tab_context_menu(&self, frame: &mut Frame, menu: &TabContextMenu) {
+    /// Render the right-click context menu
+    fn render_context_menu(&self, frame: &mut Frame, menu: &super::context_menu::ContextMenu) {
+        use super::context_menu::ContextMenuEntry;
+        use crate::primitives::display_width::str_width;
         use ratatui::style::Style;
         use ratatui::text::{Line, Span};
         use ratatui::widgets::{Block, Borders, Clear, Paragraph};
 
-        let items = super::types::TabContextMenuItem::all();
-        let menu_width = 22u16; // "Close to the Right" + padding
-        let menu_height = items.len() as u16 + 2; // items + borders
-
-        // Adjust position to stay within screen bounds
-        let screen_width = frame.area().width;
-        let screen_height = frame.area().height;
-
-        let menu_x = if menu.position.0 + menu_width > screen_width {
-            screen_width.saturating_sub(menu_width)
-        } else {
-            menu.position.0
-        };
-
-        let menu_y = if menu.position.1 + menu_height > screen_height {
-            screen_height.saturating_sub(menu_height)
-        } else {
-            menu.position.1
-        };
-
-        let area = ratatui::layout::Rect::new(menu_x, menu_y, menu_width, menu_height);
-
-        // Clear the area first
+        // The menu was laid out for the screen size when it opened
+        let area = menu.area.intersection(frame.area());
+        if area.is_empty() {
+            return;
+        }
         frame.render_widget(Clear, area);
 
-        // Build the menu lines
-        let mut lines = Vec::new();
-        for (idx, item) in items.iter().enumerate() {
-            let is_highlighted = idx == menu.highlighted;
-
-            let style = if is_highlighted {
-                Style::default()
-                    .fg(self.theme.menu_highlight_fg)
-                    .bg(self.theme.menu_highlight_bg)
-            } else {
-                Style::default()
-                    .fg(self.theme.menu_dropdown_fg)
-                    .bg(self.theme.menu_dropdown_bg)
-            };
-
-            // Pad the label to fill the menu width
-            let label = item.label();
-            let content_width = (menu_width as usize).saturating_sub(2); // -2 for borders
-            let padded_label = format!(" {:<width$}", label, width = content_width - 1);
-
-            lines.push(Line::from(vec![Span::styled(padded_label, style)]));
-        }
+        let content_width = (area.width as usize).saturating_sub(2); // -2 for borders
+        let lines: Vec<Line> = menu
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| match entry {
+                ContextMenuEntry::Item {
+                    label, keybinding, ..
+                } => {
+                    let style = if idx == menu.highlighted {
+                        Style::default()
+                            .fg(self.theme.menu_highlight_fg)
+                            .bg(self.theme.menu_highlight_bg)
+                    } else {
+                        Style::default()
+                            .fg(self.theme.menu_dropdown_fg)
+                            .bg(self.theme.menu_dropdown_bg)
+                    };
+                    // " label ... key " padded to the menu width
+                    let key = keybinding.as_deref().unwrap_or("");
+                    let padding =
+                        content_width.saturating_sub(str_width(label) + str_width(key) + 2);
+                    Line::from(Span::styled(
+                        format!(" {}{}{} ", label, " ".repeat(padding), key),
+                        style,
+                    ))
+                }
+                ContextMenuEntry::Separator => Line::from(Span::styled(
+                    "─".repeat(content_width),
+                    Style::default()
+                        .fg(self.theme.menu_separator_fg)
+                        .bg(self.theme.menu_dropdown_bg),
+                )),
+            })
+            .collect();
 
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(self.theme.menu_border_fg))
             .style(Style::default().bg(self.theme.menu_dropdown_bg));
 
-        let paragraph = Paragraph::new(lines).block(block);
-        frame.render_widget(paragraph, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
     }
 
     /// Render the tab drag drop zone overlay
@@ -1385,6 +1502,7 @@ impl Editor {
         self.mouse_state.lsp_hover_state = None;
         self.mouse_state.lsp_hover_request_sent = false;
         self.pending_hover_request = None;
+        self.pending_plugin_hover_request = None;
 
         // Clear hover symbol highlight if present
         if let Some(handle) = self.hover_symbol_overlay.take() {
@@ -2110,20 +2228,7 @@ impl Editor {
         let ns = self.search_namespace.clone();
 
         // Build regex pattern if regex mode is enabled, or escape for literal search
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
+        let regex_pattern = search_regex_pattern(query, use_regex, whole_word);
 
         // Build regex with case sensitivity
         let regex = regex::RegexBuilder::new(&regex_pattern)
@@ -2174,9 +2279,10 @@ impl Editor {
         let visible_text = state.get_text_range(visible_start, visible_end);
 
         // Find all matches using regex
-        for mat in regex.find_iter(&visible_text) {
-            let absolute_pos = visible_start + mat.start();
-            let match_len = mat.end() - mat.start();
+        let ansi_colors = state.renders_ansi_colors();
+        for range in search_match_ranges(&regex, &visible_text, ansi_colors) {
+            let absolute_pos = visible_start + range.start;
+            let match_len = range.len();
 
             // Add overlay for this match
             let search_style = ratatui::style::Style::default().fg(search_fg).bg(search_bg);
@@ -2242,20 +2348,7 @@ impl Editor {
         };
 
         // Build regex pattern
-        let regex_pattern = if use_regex {
-            if whole_word {
-                format!(r"\b{}\b", query)
-            } else {
-                query.to_string()
-            }
-        } else {
-            let escaped = regex::escape(query);
-            if whole_word {
-                format!(r"\b{}\b", escaped)
-            } else {
-                escaped
-            }
-        };
+        let regex_pattern = search_regex_pattern(query, use_regex, whole_word);
 
         // Build regex with case sensitivity
         let regex = match regex::RegexBuilder::new(&regex_pattern)
@@ -2274,10 +2367,12 @@ impl Editor {
 
         // Find all matches within the search range (store position and length for overlays)
         let search_slice = &buffer_content[search_start..search_end];
-        let match_ranges: Vec<(usize, usize)> = regex
-            .find_iter(search_slice)
-            .map(|m| (search_start + m.start(), m.end() - m.start()))
-            .collect();
+        let ansi_colors = self.active_state().renders_ansi_colors();
+        let match_ranges: Vec<(usize, usize)> =
+            search_match_ranges(&regex, search_slice, ansi_colors)
+                .into_iter()
+                .map(|range| (search_start + range.start, range.len()))
+                .collect();
 
         if match_ranges.is_empty() {
             self.search_state = None;
@@ -3777,7 +3872,10 @@ impl Editor {
             .set_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs.insert(buffer_id, EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            EventLog::new().with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -3797,6 +3895,7 @@ impl Editor {
             lsp_enabled: false,
             lsp_disabled_reason: Some("Virtual macro buffer".to_string()),
             read_only: false, // Allow editing for saving
+            library_protected: false,
             binary: false,
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
@@ -3852,7 +3951,10 @@ impl Editor {
             .set_line_numbers(self.config.editor.line_numbers);
 
         self.buffers.insert(buffer_id, state);
-        self.event_logs.insert(buffer_id, EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            EventLog::new().with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Set buffer content
         if let Some(state) = self.buffers.get_mut(&buffer_id) {
@@ -3872,6 +3974,7 @@ impl Editor {
             lsp_enabled: false,
             lsp_disabled_reason: Some("Virtual macro list buffer".to_string()),
             read_only: true,
+            library_protected: false,
             binary: false,
             lsp_opened_with: std::collections::HashSet::new(),
             hidden_from_tabs: false,
@@ -4081,10 +4184,15 @@ impl Editor {
 
     /// Synchronize viewports for all scroll sync groups
     ///
-    /// This syncs the inactive split's viewport to match the active split's position.
+    /// This syncs the inactive splits' viewports to match the active split's position.
     /// By deriving from the active split's actual viewport, we capture all viewport
     /// changes regardless of source (scroll events, cursor movements, etc.).
     fn sync_scroll_groups(&mut self) {
+        // Forget splits that were closed since the last render
+        let split_view_states = &self.split_view_states;
+        self.scroll_sync_manager
+            .retain_splits(|split_id| split_view_states.contains_key(&split_id));
+
         let active_split = self.split_manager.active_split();
         let group_count = self.scroll_sync_manager.groups().len();
 
@@ -4098,25 +4206,12 @@ impl Editor {
 
         // Collect sync info: for each group where active split participates,
         // get the active split's current line position
-        let sync_info: Vec<_> = self
+        let sync_info: Vec<(SplitId, usize)> = self
             .scroll_sync_manager
             .groups()
             .iter()
             .filter_map(|group| {
-                tracing::debug!(
-                    "sync_scroll_groups: checking group {}, left={:?}, right={:?}",
-                    group.id,
-                    group.left_split,
-                    group.right_split
-                );
-
-                if !group.contains_split(active_split) {
-                    tracing::debug!(
-                        "sync_scroll_groups: active split {:?} not in group",
-                        active_split
-                    );
-                    return None;
-                }
+                let active_index = group.split_index(active_split)?;
 
                 // Get active split's current viewport top_byte
                 let active_top_byte = self
@@ -4128,35 +4223,51 @@ impl Editor {
                 // Get active split's buffer to convert bytes → line
                 let active_buffer_id = self.split_manager.buffer_for_split(active_split)?;
                 let buffer_state = self.buffers.get(&active_buffer_id)?;
-                let buffer_len = buffer_state.buffer.len();
                 let active_line = buffer_state.buffer.get_line_number(active_top_byte);
 
                 tracing::debug!(
-                    "sync_scroll_groups: active_split={:?}, buffer_id={:?}, top_byte={}, buffer_len={}, active_line={}",
+                    "sync_scroll_groups: group {} (proportional={}), active_split={:?}, active_line={}",
+                    group.id,
+                    group.is_proportional(),
                     active_split,
-                    active_buffer_id,
-                    active_top_byte,
-                    buffer_len,
                     active_line
                 );
 
-                // Determine the other split and compute its target line
-                let (other_split, other_line) = if group.is_left_split(active_split) {
-                    // Active is left, sync right
-                    (group.right_split, group.left_to_right_line(active_line))
+                let max_top_lines: Vec<usize> = if group.is_proportional() {
+                    group
+                        .splits
+                        .iter()
+                        .map(|&split_id| self.split_max_top_line(split_id))
+                        .collect()
                 } else {
-                    // Active is right, sync left
-                    (group.left_split, group.right_to_left_line(active_line))
+                    Vec::new()
                 };
 
-                tracing::debug!(
-                    "sync_scroll_groups: syncing other_split={:?} to line {}",
-                    other_split,
-                    other_line
-                );
-
-                Some((other_split, other_line))
+                // Compute the target line of every other split
+                Some(
+                    group
+                        .splits
+                        .iter()
+                        .enumerate()
+                        .filter(|&(index, _)| index != active_index)
+                        .map(|(index, &other_split)| {
+                            let other_line = group.target_line(
+                                active_index,
+                                index,
+                                active_line,
+                                &max_top_lines,
+                            );
+                            tracing::debug!(
+                                "sync_scroll_groups: syncing other_split={:?} to line {}",
+                                other_split,
+                                other_line
+                            );
+                            (other_split, other_line)
+                        })
+                        .collect::<Vec<_>>(),
+                )
             })
+            .flatten()
             .collect();
 
         // Apply sync to other splits
@@ -4165,29 +4276,56 @@ impl Editor {
                 if let Some(state) = self.buffers.get_mut(&buffer_id) {
                     let buffer = &mut state.buffer;
                     if let Some(view_state) = self.split_view_states.get_mut(&other_split) {
-                        view_state.viewport.scroll_to(buffer, target_line);
+                        // scroll_to takes 1-based line numbers
+                        view_state.viewport.scroll_to(buffer, target_line + 1);
                     }
                 }
             }
         }
     }
 
+    /// Last line a split can scroll to the top of its viewport
+    fn split_max_top_line(&self, split_id: SplitId) -> usize {
+        let Some(state) = self
+            .split_manager
+            .buffer_for_split(split_id)
+            .and_then(|buffer_id| self.buffers.get(&buffer_id))
+        else {
+            return 0;
+        };
+        let total_lines = state
+            .buffer
+            .line_count()
+            .unwrap_or_else(|| state.buffer.get_line_number(state.buffer.len()) + 1);
+        let viewport_height = self
+            .split_view_states
+            .get(&split_id)
+            .map(|vs| vs.viewport.visible_line_count())
+            .unwrap_or(0);
+        total_lines.saturating_sub(viewport_height)
+    }
+
     /// Pre-sync ensure_visible for scroll sync groups
     ///
     /// When the active split is in a scroll sync group, we need to update its viewport
     /// BEFORE sync_scroll_groups runs. This ensures cursor movements like 'G' (go to end)
-    /// properly sync to the other split.
+    /// properly sync to the other splits.
     ///
     /// After updating the active split's viewport, we mark the OTHER splits in the group
     /// to skip ensure_visible so the sync position isn't undone during rendering.
     fn pre_sync_ensure_visible(&mut self, active_split: SplitId) {
         // Check if active split is in any scroll sync group
-        let group_info = self
+        let Some(other_splits) = self
             .scroll_sync_manager
             .find_group_for_split(active_split)
-            .map(|g| (g.left_split, g.right_split));
-
-        let Some((left_split, right_split)) = group_info else {
+            .map(|g| {
+                g.splits
+                    .iter()
+                    .copied()
+                    .filter(|&s| s != active_split)
+                    .collect::<Vec<_>>()
+            })
+        else {
             return;
         };
 
@@ -4210,19 +4348,48 @@ impl Editor {
             }
         }
 
-        // Mark the OTHER split to skip ensure_visible so the sync position isn't undone
-        let other_split = if active_split == left_split {
-            right_split
-        } else {
-            left_split
-        };
-
-        if let Some(view_state) = self.split_view_states.get_mut(&other_split) {
-            view_state.viewport.set_skip_ensure_visible();
-            tracing::debug!(
-                "pre_sync_ensure_visible: marked other split {:?} to skip ensure_visible",
-                other_split
-            );
+        // Mark the OTHER splits to skip ensure_visible so the sync position isn't undone
+        for other_split in other_splits {
+            if let Some(view_state) = self.split_view_states.get_mut(&other_split) {
+                view_state.viewport.set_skip_ensure_visible();
+                tracing::debug!(
+                    "pre_sync_ensure_visible: marked other split {:?} to skip ensure_visible",
+                    other_split
+                );
+            }
         }
     }
 }
+
+/// Regex source for a search query: the query itself in regex mode, escaped
+/// otherwise, and wrapped in word boundaries for whole-word searches
+pub(crate) fn search_regex_pattern(query: &str, use_regex: bool, whole_word: bool) -> String {
+    let pattern = if use_regex {
+        query.to_string()
+    } else {
+        regex::escape(query)
+    };
+    if whole_word {
+        format!(r"\b{}\b", pattern)
+    } else {
+        pattern
+    }
+}
+
+/// Byte ranges of the regex matches in `text`. When escape sequences render
+/// as colors, the text is searched as it appears on screen, so a match can
+/// span sequences that split it in the buffer.
+fn search_match_ranges(
+    regex: &regex::Regex,
+    text: &str,
+    ansi_colors: bool,
+) -> Vec<std::ops::Range<usize>> {
+    if !ansi_colors || !crate::primitives::ansi::contains_ansi_codes(text) {
+        return regex.find_iter(text).map(|m| m.range()).collect();
+    }
+    let stripped = crate::primitives::ansi::StrippedText::new(text);
+    regex
+        .find_iter(&stripped.text)
+        .map(|m| stripped.source_range(m.range()))
+        .collect()
+}
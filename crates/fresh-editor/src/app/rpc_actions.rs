@@ -0,0 +1,100 @@
+//! Handlers for requests from external tools over the local RPC socket.
+//!
+//! See [`crate::services::rpc`] for the protocol; this module runs each
+//! request against the editor on the main loop.
+
+use super::Editor;
+use crate::input::keybindings::Action;
+use crate::services::rpc::{socket_path, RpcMethod, RpcServer};
+use lsp_types::DiagnosticSeverity;
+use serde_json::{json, Value};
+use std::path::Path;
+
+impl Editor {
+    /// Start the RPC server if `editor.rpc_server` is enabled
+    pub(super) fn start_rpc_server(&mut self) {
+        if !self.config.editor.rpc_server {
+            return;
+        }
+        let Some(bridge) = &self.async_bridge else {
+            return;
+        };
+        let path = socket_path(&self.dir_context.rpc_dir(), std::process::id());
+        match RpcServer::start(path, bridge.sender()) {
+            Ok(server) => self.rpc_server = Some(server),
+            Err(e) => tracing::warn!("Failed to start RPC server: {}", e),
+        }
+    }
+
+    /// Socket the RPC server is listening on, if it is running
+    pub fn rpc_socket_path(&self) -> Option<&Path> {
+        self.rpc_server.as_ref().map(|server| server.socket_path())
+    }
+
+    /// Run one RPC request, returning the JSON result sent back to the client
+    pub(super) fn handle_rpc_request(&mut self, method: RpcMethod) -> Result<Value, String> {
+        match method {
+            RpcMethod::Open { path, line, column } => {
+                let path = self.working_dir.join(path);
+                let buffer_id = self.open_file(&path).map_err(|e| e.to_string())?;
+                if let Some(line) = line {
+                    self.goto_line_col(line, column);
+                }
+                Ok(json!({ "buffer_id": buffer_id.0 }))
+            }
+            RpcMethod::Diagnostics { path } => {
+                let filter = path.map(|p| {
+                    let p = self.working_dir.join(p);
+                    p.canonicalize().unwrap_or(p)
+                });
+                Ok(Value::Array(self.rpc_diagnostics(filter.as_deref())))
+            }
+            RpcMethod::Command { action, args } => {
+                let parsed = Action::from_str(&action, &args)
+                    .ok_or_else(|| format!("unknown action '{}'", action))?;
+                self.handle_action(parsed).map_err(|e| e.to_string())?;
+                Ok(Value::Null)
+            }
+        }
+    }
+
+    /// Stored LSP diagnostics as a flat list, optionally for a single file
+    fn rpc_diagnostics(&self, only: Option<&Path>) -> Vec<Value> {
+        let mut by_path: Vec<_> = self
+            .stored_diagnostics
+            .iter()
+            .filter_map(|(uri, diagnostics)| {
+                let path = url::Url::parse(uri).ok()?.to_file_path().ok()?;
+                Some((path, diagnostics))
+            })
+            .filter(|(path, _)| only.is_none_or(|only| only == path))
+            .collect();
+        by_path.sort_by(|a, b| a.0.cmp(&b.0));
+
+        by_path
+            .into_iter()
+            .flat_map(|(path, diagnostics)| {
+                diagnostics.iter().map(move |d| {
+                    json!({
+                        "path": path,
+                        "line": d.range.start.line + 1,
+                        "column": d.range.start.character + 1,
+                        "severity": severity_name(d.severity),
+                        "message": d.message,
+                        "source": d.source,
+                    })
+                })
+            })
+            .collect()
+    }
+}
+
+fn severity_name(severity: Option<DiagnosticSeverity>) -> &'static str {
+    match severity {
+        Some(DiagnosticSeverity::ERROR) => "error",
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "information",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "unknown",
+    }
+}
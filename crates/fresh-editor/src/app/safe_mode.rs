@@ -0,0 +1,250 @@
+//! Safe mode: starting without user configuration, user themes or plugins.
+//!
+//! `fresh --safe-mode` starts with the default config, no plugins and only the
+//! embedded themes, so a broken config file or plugin can't get in the way of
+//! fixing it. A banner stays visible while anything is still disabled, and the
+//! "Safe Mode: Re-enable" command turns components back on one at a time.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::view::prompt::{Prompt, PromptType};
+use crate::view::theme::ThemeLoader;
+use rust_i18n::t;
+
+/// A component that safe mode disables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SafeModeComponent {
+    /// The user and project config layers
+    UserConfig,
+    /// Themes from the user themes directory and theme packages
+    UserThemes,
+    /// All plugins, including the bundled ones
+    Plugins,
+}
+
+impl SafeModeComponent {
+    pub const ALL: [Self; 3] = [Self::UserConfig, Self::UserThemes, Self::Plugins];
+
+    /// Stable identifier used as the prompt suggestion value
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::UserConfig => "config",
+            Self::UserThemes => "themes",
+            Self::Plugins => "plugins",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.id() == id)
+    }
+
+    /// Localized name shown in the banner and the re-enable prompt
+    pub fn label(self) -> String {
+        match self {
+            Self::UserConfig => t!("safe_mode.component_config"),
+            Self::UserThemes => t!("safe_mode.component_themes"),
+            Self::Plugins => t!("safe_mode.component_plugins"),
+        }
+        .to_string()
+    }
+}
+
+/// Which components are still disabled in a safe mode session
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafeMode {
+    pub config_disabled: bool,
+    pub themes_disabled: bool,
+    pub plugins_disabled: bool,
+}
+
+impl SafeMode {
+    /// Safe mode with every component disabled, as started by `--safe-mode`
+    pub fn new() -> Self {
+        Self {
+            config_disabled: true,
+            themes_disabled: true,
+            plugins_disabled: true,
+        }
+    }
+
+    pub fn is_disabled(&self, component: SafeModeComponent) -> bool {
+        match component {
+            SafeModeComponent::UserConfig => self.config_disabled,
+            SafeModeComponent::UserThemes => self.themes_disabled,
+            SafeModeComponent::Plugins => self.plugins_disabled,
+        }
+    }
+
+    /// Components that are still disabled, in display order
+    pub fn disabled(&self) -> Vec<SafeModeComponent> {
+        SafeModeComponent::ALL
+            .into_iter()
+            .filter(|c| self.is_disabled(*c))
+            .collect()
+    }
+
+    /// Whether anything is still disabled
+    pub fn is_active(&self) -> bool {
+        self.config_disabled || self.themes_disabled || self.plugins_disabled
+    }
+
+    fn enable(&mut self, component: SafeModeComponent) {
+        match component {
+            SafeModeComponent::UserConfig => self.config_disabled = false,
+            SafeModeComponent::UserThemes => self.themes_disabled = false,
+            SafeModeComponent::Plugins => self.plugins_disabled = false,
+        }
+    }
+}
+
+impl Default for SafeMode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Editor {
+    /// Put the editor in safe mode.
+    ///
+    /// The caller is responsible for having created the editor with the
+    /// default config and plugins disabled as requested by `safe_mode`; this
+    /// drops user themes from the registry and shows the banner.
+    pub fn enable_safe_mode(&mut self, safe_mode: SafeMode) {
+        self.safe_mode = Some(safe_mode);
+        if safe_mode.themes_disabled {
+            self.reload_themes();
+        }
+    }
+
+    /// Components still disabled, or `None` when not started in safe mode
+    pub fn safe_mode(&self) -> Option<SafeMode> {
+        self.safe_mode
+    }
+
+    /// Text of the safe mode banner, or `None` once nothing is disabled
+    pub(super) fn safe_mode_banner(&self) -> Option<String> {
+        let safe_mode = self.safe_mode.filter(SafeMode::is_active)?;
+        let disabled: Vec<String> = safe_mode
+            .disabled()
+            .into_iter()
+            .map(|c| c.label())
+            .collect();
+        Some(
+            t!(
+                "safe_mode.banner",
+                disabled = disabled.join(", "),
+                command = t!("cmd.safe_mode_reenable")
+            )
+            .to_string(),
+        )
+    }
+
+    /// Theme loader honoring safe mode (embedded themes only while disabled)
+    pub(super) fn theme_loader(&self) -> ThemeLoader {
+        let loader = if self.safe_mode.is_some_and(|m| m.themes_disabled) {
+            ThemeLoader::with_user_dir(None)
+        } else {
+            ThemeLoader::new()
+        };
+        loader.with_color_vision(self.config.view.color_vision)
+    }
+
+    /// Whether user config files should be ignored when reloading config
+    pub(super) fn user_config_disabled(&self) -> bool {
+        self.safe_mode.is_some_and(|m| m.config_disabled)
+    }
+
+    /// Open a prompt listing the components safe mode still disables
+    pub fn start_safe_mode_reenable_prompt(&mut self) {
+        let Some(safe_mode) = self.safe_mode.filter(SafeMode::is_active) else {
+            self.set_status_message(t!("safe_mode.not_active").to_string());
+            return;
+        };
+
+        let suggestions: Vec<Suggestion> = safe_mode
+            .disabled()
+            .into_iter()
+            .map(|component| Suggestion {
+                text: component.label(),
+                description: (component == SafeModeComponent::Plugins)
+                    .then(|| t!("safe_mode.plugins_restart_hint").to_string()),
+                value: Some(component.id().to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("safe_mode.reenable_prompt").to_string(),
+            PromptType::SafeModeReenable,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Turn a component disabled by safe mode back on.
+    ///
+    /// Config and themes are reloaded in place. Plugins can only be loaded
+    /// when the editor is created, so enabling them restarts the editor in
+    /// the same directory (refused while buffers have unsaved changes).
+    pub fn reenable_safe_mode_component(&mut self, component: SafeModeComponent) {
+        if !self.safe_mode.is_some_and(|m| m.is_disabled(component)) {
+            return;
+        }
+        if component == SafeModeComponent::Plugins {
+            let modified = self.count_modified_buffers();
+            if modified > 0 {
+                self.set_status_message(
+                    t!("safe_mode.plugins_unsaved", count = modified).to_string(),
+                );
+                return;
+            }
+        }
+
+        if let Some(safe_mode) = self.safe_mode.as_mut() {
+            safe_mode.enable(component);
+        }
+        match component {
+            SafeModeComponent::UserConfig => self.reload_config(),
+            SafeModeComponent::UserThemes => self.reload_themes(),
+            SafeModeComponent::Plugins => self.request_restart(self.working_dir.clone()),
+        }
+
+        self.set_status_message(
+            t!("safe_mode.reenabled", component = component.label()).to_string(),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_safe_mode_tracks_disabled_components() {
+        let mut safe_mode = SafeMode::new();
+        assert_eq!(safe_mode.disabled(), SafeModeComponent::ALL.to_vec());
+
+        safe_mode.enable(SafeModeComponent::UserThemes);
+        assert_eq!(
+            safe_mode.disabled(),
+            vec![SafeModeComponent::UserConfig, SafeModeComponent::Plugins]
+        );
+        assert!(safe_mode.is_active());
+
+        safe_mode.enable(SafeModeComponent::UserConfig);
+        safe_mode.enable(SafeModeComponent::Plugins);
+        assert!(!safe_mode.is_active());
+    }
+
+    #[test]
+    fn test_component_ids_round_trip() {
+        for component in SafeModeComponent::ALL {
+            assert_eq!(SafeModeComponent::from_id(component.id()), Some(component));
+        }
+        assert_eq!(SafeModeComponent::from_id("bogus"), None);
+    }
+}
@@ -32,11 +32,13 @@ use crate::model::event::{BufferId, SplitDirection, SplitId};
 use crate::services::terminal::TerminalId;
 use crate::session::{
     FileExplorerState, PersistedFileSession, SearchOptions, SerializedBookmark, SerializedCursor,
-    SerializedFileState, SerializedScroll, SerializedSplitDirection, SerializedSplitNode,
-    SerializedSplitViewState, SerializedTabRef, SerializedTerminalSession, SerializedViewMode,
-    Session, SessionConfigOverrides, SessionError, SessionHistories, SESSION_VERSION,
+    SerializedFileState, SerializedScroll, SerializedScrollSyncGroup, SerializedSplitDirection,
+    SerializedSplitNode, SerializedSplitViewState, SerializedTabRef, SerializedTerminalSession,
+    SerializedViewMode, Session, SessionConfigOverrides, SessionError, SessionHistories,
+    SESSION_VERSION,
 };
 use crate::state::ViewMode;
+use crate::view::scroll_sync::{ScrollSyncGroup, SyncAnchor};
 use crate::view::split::{SplitNode, SplitViewState};
 
 use super::types::Bookmark;
@@ -262,6 +264,9 @@ impl Editor {
         let bookmarks =
             serialize_bookmarks(&self.bookmarks, &self.buffer_metadata, &self.working_dir);
 
+        // Capture scroll sync groups
+        let scroll_sync_groups = serialize_scroll_sync_groups(self.scroll_sync_manager.groups());
+
         // Capture external files (files outside working_dir)
         // These are stored as absolute paths since they can't be made relative
         let external_files: Vec<PathBuf> = self
@@ -288,6 +293,8 @@ impl Editor {
             bookmarks,
             terminals,
             external_files,
+            scroll_sync_groups,
+            plugin_data: self.plugin_session_data.clone(),
             saved_at: std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -613,6 +620,22 @@ impl Editor {
             }
         }
 
+        // 8. Restore scroll sync groups
+        for group in &session.scroll_sync_groups {
+            self.restore_scroll_sync_group(group, &split_id_map);
+        }
+
+        // 9. Restore plugin data and let plugins rebuild their state from it
+        self.plugin_session_data = session.plugin_data.clone();
+        #[cfg(feature = "plugins")]
+        if let Some(snapshot_handle) = self.plugin_manager.state_snapshot_handle() {
+            snapshot_handle.write().unwrap().plugin_session_data = self.plugin_session_data.clone();
+        }
+        self.plugin_manager.run_hook(
+            "session_restored",
+            crate::services::plugins::hooks::HookArgs::SessionRestored,
+        );
+
         tracing::debug!(
             "Session restore complete: {} splits, {} buffers",
             self.split_view_states.len(),
@@ -622,6 +645,37 @@ impl Editor {
         Ok(())
     }
 
+    /// Restore a scroll sync group, leaving out splits that weren't restored
+    fn restore_scroll_sync_group(
+        &mut self,
+        group: &SerializedScrollSyncGroup,
+        split_id_map: &HashMap<usize, SplitId>,
+    ) {
+        // (position in the saved group, restored split)
+        let restored: Vec<(usize, SplitId)> = group
+            .splits
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| split_id_map.get(id).map(|&split_id| (index, split_id)))
+            .collect();
+        if restored.is_empty() {
+            return;
+        }
+
+        let anchors = group
+            .anchors
+            .iter()
+            .filter(|lines| lines.len() == group.splits.len())
+            .map(|lines| SyncAnchor {
+                lines: restored.iter().map(|&(index, _)| lines[index]).collect(),
+            })
+            .collect();
+        let group_id = self
+            .scroll_sync_manager
+            .create_group(restored.iter().map(|&(_, split_id)| split_id).collect());
+        self.scroll_sync_manager.set_anchors(group_id, anchors);
+    }
+
     /// Restore a terminal from serialized session metadata.
     ///
     /// Uses the incremental streaming architecture for fast restore:
@@ -721,7 +775,7 @@ impl Editor {
             std::sync::Arc::clone(&self.filesystem),
         ) {
             if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                *state = new_state;
+                state.reload_from(new_state);
                 // Move cursor to end of buffer
                 let total = state.buffer.total_bytes();
                 state.primary_cursor_mut().position = total;
@@ -908,27 +962,29 @@ impl Editor {
         let mut active_buffer_id: Option<BufferId> = None;
 
         if !split_state.open_tabs.is_empty() {
+            // Buffer restored for each entry of open_tabs, by tab index
+            let mut tab_buffers = Vec::with_capacity(split_state.open_tabs.len());
             for tab in &split_state.open_tabs {
-                match tab {
-                    SerializedTabRef::File(rel_path) => {
-                        if let Some(&buffer_id) = path_to_buffer.get(rel_path) {
-                            if !view_state.open_buffers.contains(&buffer_id) {
-                                view_state.open_buffers.push(buffer_id);
-                            }
-                            if terminal_buffers.values().any(|&tid| tid == buffer_id) {
-                                view_state.viewport.line_wrap_enabled = false;
-                            }
-                        }
+                let restored = match tab {
+                    SerializedTabRef::File(rel_path) => path_to_buffer.get(rel_path).copied(),
+                    SerializedTabRef::Terminal(index) => terminal_buffers.get(index).copied(),
+                };
+                if let Some(buffer_id) = restored {
+                    if !view_state.open_buffers.contains(&buffer_id) {
+                        view_state.open_buffers.push(buffer_id);
                     }
-                    SerializedTabRef::Terminal(index) => {
-                        if let Some(&buffer_id) = terminal_buffers.get(index) {
-                            if !view_state.open_buffers.contains(&buffer_id) {
-                                view_state.open_buffers.push(buffer_id);
-                            }
-                            view_state.viewport.line_wrap_enabled = false;
-                        }
+                    if terminal_buffers.values().any(|&tid| tid == buffer_id) {
+                        view_state.viewport.line_wrap_enabled = false;
                     }
                 }
+                tab_buffers.push(restored);
+            }
+
+            // Restore the split's most-recently-used tab order
+            for &tab_index in &split_state.focus_history {
+                if let Some(&Some(buffer_id)) = tab_buffers.get(tab_index) {
+                    view_state.push_focus(buffer_id);
+                }
             }
 
             if let Some(active_idx) = split_state.active_tab_index {
@@ -1016,6 +1072,7 @@ impl Editor {
         };
         view_state.compose_width = split_state.compose_width;
         view_state.tab_scroll_offset = split_state.tab_scroll_offset;
+        view_state.theme = split_state.theme.clone();
     }
 }
 
@@ -1121,11 +1178,14 @@ fn serialize_split_view_state(
     let mut open_files = Vec::new();
     let mut active_tab_index = None;
 
+    let mut tab_indices = HashMap::new();
+
     for buffer_id in &view_state.open_buffers {
         let tab_index = open_tabs.len();
         if let Some(terminal_id) = terminal_buffers.get(buffer_id) {
             if let Some(idx) = terminal_indices.get(terminal_id) {
                 open_tabs.push(SerializedTabRef::Terminal(*idx));
+                tab_indices.insert(*buffer_id, tab_index);
                 if Some(*buffer_id) == active_buffer {
                     active_tab_index = Some(tab_index);
                 }
@@ -1140,12 +1200,19 @@ fn serialize_split_view_state(
         {
             open_tabs.push(SerializedTabRef::File(rel_path.to_path_buf()));
             open_files.push(rel_path.to_path_buf());
+            tab_indices.insert(*buffer_id, tab_index);
             if Some(*buffer_id) == active_buffer {
                 active_tab_index = Some(tab_index);
             }
         }
     }
 
+    let focus_history = view_state
+        .focus_history
+        .iter()
+        .filter_map(|buffer_id| tab_indices.get(buffer_id).copied())
+        .collect();
+
     // Derive active_file_index for backward compatibility
     let active_file_index = active_tab_index
         .and_then(|idx| open_tabs.get(idx))
@@ -1207,6 +1274,8 @@ fn serialize_split_view_state(
             ViewMode::Compose => SerializedViewMode::Compose,
         },
         compose_width: view_state.compose_width,
+        theme: view_state.theme.clone(),
+        focus_history,
     }
 }
 
@@ -1236,6 +1305,20 @@ fn serialize_bookmarks(
         .collect()
 }
 
+fn serialize_scroll_sync_groups(groups: &[ScrollSyncGroup]) -> Vec<SerializedScrollSyncGroup> {
+    groups
+        .iter()
+        .map(|group| SerializedScrollSyncGroup {
+            splits: group.splits.iter().map(|split_id| split_id.0).collect(),
+            anchors: group
+                .anchors
+                .iter()
+                .map(|anchor| anchor.lines.clone())
+                .collect(),
+        })
+        .collect()
+}
+
 /// Collect all unique file paths from split_states
 fn collect_file_paths_from_states(
     split_states: &HashMap<usize, SerializedSplitViewState>,
@@ -64,6 +64,7 @@ impl Editor {
     /// Save the settings from the modal to config
     pub fn save_settings(&mut self) {
         let old_theme = self.config.theme.clone();
+        let old_color_vision = self.config.view.color_vision;
         let old_locale = self.config.locale.clone();
         let old_plugins = self.config.plugins.clone();
 
@@ -96,36 +97,27 @@ impl Editor {
         self.config = new_config.clone();
 
         // Refresh cached raw user config for plugins
-        self.user_config_raw = Config::read_user_config_raw(&self.working_dir);
+        self.user_config_raw = Config::read_user_config_raw(&self.working_dir, &self.dir_context);
 
         // Apply runtime changes
+        if old_color_vision != self.config.view.color_vision {
+            self.reload_themes();
+        }
         if old_theme != self.config.theme {
             if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
                 self.theme = theme;
                 tracing::info!("Theme changed to '{}'", self.config.theme.0);
             } else {
                 tracing::error!("Theme '{}' not found", self.config.theme.0);
-                self.set_status_message(format!("Theme '{}' not found", self.config.theme.0));
+                self.set_status_message(
+                    t!("view.theme_not_found", theme = self.config.theme.0.as_str()).to_string(),
+                );
             }
         }
 
         // Apply locale change at runtime
         if old_locale != self.config.locale {
-            if let Some(locale) = self.config.locale.as_option() {
-                crate::i18n::set_locale(locale);
-                // Regenerate menus with the new locale
-                self.menus = crate::config::MenuConfig::translated();
-                tracing::info!("Locale changed to '{}'", locale);
-            } else {
-                // Auto-detect from environment
-                crate::i18n::init();
-                self.menus = crate::config::MenuConfig::translated();
-                tracing::info!("Locale reset to auto-detect");
-            }
-            // Refresh command palette commands with new locale
-            if let Ok(mut registry) = self.command_registry.write() {
-                registry.refresh_builtin_commands();
-            }
+            self.activate_configured_locale();
         }
 
         // Handle plugin enable/disable changes
@@ -134,6 +126,24 @@ impl Editor {
         // Update keybindings
         self.keybindings = KeybindingResolver::new(&self.config);
 
+        // Update LSP configs (running servers are notified of settings changes)
+        if let Some(ref mut lsp) = self.lsp {
+            for (language, lsp_config) in &self.config.lsp {
+                lsp.set_language_config(language.clone(), lsp_config.clone());
+            }
+        }
+
+        for event_log in self.event_logs.values_mut() {
+            event_log.set_branch_limit(self.config.editor.undo_branch_limit);
+        }
+        self.apply_trailing_whitespace_highlight();
+        self.key_translator
+            .set_layout(self.config.editor.keyboard_layout);
+        self.clipboard
+            .set_history_size(self.config.editor.clipboard_history_size);
+        self.cache_budget
+            .set_limit_mb(self.config.editor.cache_budget_mb);
+
         // Save ONLY the changes to disk (preserves external edits to the config file)
         let resolver = ConfigResolver::new(self.dir_context.clone(), self.working_dir.clone());
 
@@ -559,7 +569,14 @@ impl Editor {
                     tracing::info!("Loading newly enabled plugin: {}", name);
                     if let Err(e) = self.plugin_manager.load_plugin(path) {
                         tracing::error!("Failed to load plugin '{}': {}", name, e);
-                        self.set_status_message(format!("Failed to load plugin '{}': {}", name, e));
+                        self.set_status_message(
+                            t!(
+                                "status.plugin_load_failed",
+                                name = name,
+                                error = e.to_string()
+                            )
+                            .to_string(),
+                        );
                     }
                 }
             } else {
@@ -567,7 +584,14 @@ impl Editor {
                 tracing::info!("Unloading disabled plugin: {}", name);
                 if let Err(e) = self.plugin_manager.unload_plugin(&name) {
                     tracing::error!("Failed to unload plugin '{}': {}", name, e);
-                    self.set_status_message(format!("Failed to unload plugin '{}': {}", name, e));
+                    self.set_status_message(
+                        t!(
+                            "status.plugin_unload_failed",
+                            name = name,
+                            error = e.to_string()
+                        )
+                        .to_string(),
+                    );
                 }
             }
         }
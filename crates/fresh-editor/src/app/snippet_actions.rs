@@ -0,0 +1,169 @@
+//! Snippet insertion with tabstop navigation.
+//!
+//! Inserting a snippet selects its first tabstop; Tab selects the next one,
+//! and after the last it moves the cursor to `$0` (or the end of the snippet)
+//! and ends the session. Tabstops are tracked with markers, so they follow
+//! the text typed into earlier placeholders.
+
+use super::Editor;
+use crate::model::event::{BufferId, Event};
+use crate::model::marker::MarkerId;
+use crate::primitives::snippet::expand_snippet;
+use std::collections::VecDeque;
+
+/// A snippet whose tabstops Tab is stepping through
+pub(super) struct SnippetSession {
+    buffer_id: BufferId,
+    /// Start and end of the whole snippet; Tab outside it ends the session
+    bounds: (MarkerId, MarkerId),
+    /// Start and end markers of the tabstops not visited yet
+    tabstops: VecDeque<(MarkerId, MarkerId)>,
+    /// Where the cursor goes after the last tabstop
+    exit: MarkerId,
+}
+
+impl Editor {
+    /// Insert an LSP snippet into the active buffer as one undoable edit and
+    /// select its first tabstop
+    pub(super) fn insert_snippet(&mut self, position: usize, snippet: &str, description: &str) {
+        self.end_snippet_session();
+
+        let expanded = expand_snippet(snippet);
+        let cursor_id = self.active_state().cursors.primary_id();
+        let inserted_end = position + expanded.text.len();
+        let first = expanded
+            .tabstops
+            .first()
+            .map(|range| position + range.start..position + range.end);
+        let exit = position + expanded.cursor_offset;
+        let (new_anchor, new_position) = match &first {
+            Some(range) => ((range.start != range.end).then_some(range.start), range.end),
+            None => (None, exit),
+        };
+        let batch = Event::Batch {
+            events: vec![
+                Event::Insert {
+                    position,
+                    text: expanded.text.clone(),
+                    cursor_id,
+                },
+                Event::MoveCursor {
+                    cursor_id,
+                    old_position: inserted_end,
+                    new_position,
+                    old_anchor: None,
+                    new_anchor,
+                    old_sticky_column: 0,
+                    new_sticky_column: 0,
+                },
+            ],
+            description: description.to_string(),
+        };
+        self.active_event_log_mut().append(batch.clone());
+        self.apply_event_to_active_buffer(&batch);
+
+        if expanded.tabstops.len() < 2 && first.is_none_or(|range| range.end == exit) {
+            // Nothing left for Tab to move to
+            return;
+        }
+        let buffer_id = self.active_buffer();
+        let markers = &mut self.active_state_mut().marker_list;
+        let tabstops = expanded
+            .tabstops
+            .iter()
+            .skip(1)
+            .map(|range| {
+                (
+                    markers.create(position + range.start, true),
+                    markers.create(position + range.end, false),
+                )
+            })
+            .collect();
+        self.snippet_session = Some(SnippetSession {
+            buffer_id,
+            bounds: (
+                markers.create(position, true),
+                markers.create(inserted_end, false),
+            ),
+            tabstops,
+            exit: markers.create(exit, false),
+        });
+    }
+
+    /// Select the next tabstop of the snippet being filled in.
+    /// Returns false (so Tab inserts as usual) when there is no snippet or
+    /// the cursor has left it.
+    pub(super) fn next_snippet_tabstop(&mut self) -> bool {
+        let Some(mut session) = self.snippet_session.take() else {
+            return false;
+        };
+        if session.buffer_id != self.active_buffer() {
+            self.snippet_session = Some(session);
+            self.end_snippet_session();
+            return false;
+        }
+
+        let state = self.active_state();
+        let position = |marker| state.marker_list.get_position(marker);
+        let cursor = state.cursors.primary().position;
+        let inside = match (position(session.bounds.0), position(session.bounds.1)) {
+            (Some(start), Some(end)) => (start..=end).contains(&cursor),
+            _ => false,
+        };
+        if !inside {
+            self.snippet_session = Some(session);
+            self.end_snippet_session();
+            return false;
+        }
+
+        let next = session.tabstops.pop_front();
+        let target = match next {
+            Some((start, end)) => position(start).zip(position(end)),
+            None => position(session.exit).map(|exit| (exit, exit)),
+        };
+        let is_last = next.is_none();
+        self.snippet_session = Some(session);
+        if let Some((start, end)) = target {
+            self.select_snippet_range(start, end);
+        }
+        if is_last {
+            self.end_snippet_session();
+        }
+        true
+    }
+
+    /// Stop tracking the snippet's tabstops
+    pub(super) fn end_snippet_session(&mut self) {
+        let Some(session) = self.snippet_session.take() else {
+            return;
+        };
+        let Some(state) = self.buffers.get_mut(&session.buffer_id) else {
+            return;
+        };
+        let markers = session
+            .tabstops
+            .into_iter()
+            .flat_map(|(start, end)| [start, end])
+            .chain([session.bounds.0, session.bounds.1, session.exit]);
+        for marker in markers {
+            state.marker_list.delete(marker);
+        }
+    }
+
+    /// Move the primary cursor to `end`, selecting back to `start`
+    fn select_snippet_range(&mut self, start: usize, end: usize) {
+        let cursor_id = self.active_state().cursors.primary_id();
+        let cursor = *self.active_state().cursors.primary();
+        let event = Event::MoveCursor {
+            cursor_id,
+            old_position: cursor.position,
+            new_position: end,
+            old_anchor: cursor.anchor,
+            new_anchor: (start != end).then_some(start),
+            old_sticky_column: cursor.sticky_column,
+            new_sticky_column: 0,
+        };
+        self.active_event_log_mut().append(event.clone());
+        self.apply_event_to_active_buffer(&event);
+    }
+}
@@ -5,11 +5,13 @@
 //! - Closing splits
 //! - Navigating between splits
 //! - Managing per-split view states (cursors, viewport)
-//! - Split size adjustment and maximize
+//! - Split size adjustment, maximize and zoom
 
 use rust_i18n::t;
 
+use crate::input::commands::Suggestion;
 use crate::model::event::{BufferId, Event, SplitDirection, SplitId};
+use crate::view::prompt::{Prompt, PromptType};
 use crate::view::split::SplitViewState;
 
 use super::Editor;
@@ -139,7 +141,9 @@ impl Editor {
 
     /// Common split switching logic
     fn switch_split(&mut self, next: bool) {
+        let previous_buffer = self.active_buffer();
         self.save_current_split_view_state();
+        let was_zoomed = self.split_manager.is_zoomed();
         if next {
             self.split_manager.next_split();
         } else {
@@ -147,12 +151,21 @@ impl Editor {
         }
         self.restore_current_split_view_state();
 
+        // Leaving a zoomed split restores the layout
+        if was_zoomed && !self.split_manager.is_zoomed() {
+            self.resize_visible_terminals();
+        }
+
         let buffer_id = self.active_buffer();
 
         // Emit buffer_activated hook for plugins
         self.plugin_manager.run_hook(
             "buffer_activated",
-            crate::services::plugins::hooks::HookArgs::BufferActivated { buffer_id },
+            crate::services::plugins::hooks::HookArgs::BufferActivated {
+                buffer_id,
+                previous_buffer_id: Some(previous_buffer),
+                split_id: self.split_manager.active_split(),
+            },
         );
 
         // Enter terminal mode if switching to a terminal split
@@ -311,6 +324,240 @@ impl Editor {
         }
     }
 
+    /// Toggle zoom for the active split.
+    /// Unlike maximize, the zoom ends when another split is focused.
+    pub fn toggle_zoom_split(&mut self) {
+        match self.split_manager.toggle_zoom() {
+            Ok(zoomed) => {
+                if zoomed {
+                    self.set_status_message(t!("split.zoomed").to_string());
+                } else {
+                    self.set_status_message(t!("split.restored").to_string());
+                }
+                // Resize visible terminals to match new split dimensions
+                self.resize_visible_terminals();
+            }
+            Err(e) => self.set_status_message(e),
+        }
+    }
+
+    /// Add the active split to the editor's scroll sync group, or take it out
+    /// of the group it is in.
+    ///
+    /// The first split toggled on waits in a group of its own; every split
+    /// toggled on after it scrolls along with it.
+    pub fn toggle_scroll_sync(&mut self) {
+        let active_split = self.split_manager.active_split();
+        if self.scroll_sync_manager.remove_split(active_split) {
+            self.set_status_message(t!("split.scroll_sync_left").to_string());
+            return;
+        }
+
+        let group_id = match self.scroll_sync_manager.latest_editor_group() {
+            Some(group) => group.id,
+            None => self.scroll_sync_manager.create_group(Vec::new()),
+        };
+        self.scroll_sync_manager.add_split(group_id, active_split);
+        let others = self
+            .scroll_sync_manager
+            .get_group(group_id)
+            .map_or(0, |group| group.splits.len() - 1);
+        let message = if others == 0 {
+            t!("split.scroll_sync_waiting")
+        } else {
+            t!("split.scroll_sync_joined", count = others)
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Scroll all splits together, by percentage of their scroll range
+    pub fn sync_scroll_all_splits(&mut self) {
+        let splits = self.split_manager.root().leaf_split_ids();
+        if splits.len() < 2 {
+            self.set_status_message(t!("split.scroll_sync_single").to_string());
+            return;
+        }
+        let count = splits.len();
+        self.scroll_sync_manager.create_group(splits);
+        self.set_status_message(t!("split.scroll_sync_all", count = count).to_string());
+    }
+
+    /// Stop syncing the splits of the active split's scroll sync group
+    pub fn stop_scroll_sync(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let Some(group_id) = self
+            .scroll_sync_manager
+            .find_group_for_split(active_split)
+            .map(|group| group.id)
+        else {
+            self.set_status_message(t!("split.scroll_sync_none").to_string());
+            return;
+        };
+        self.scroll_sync_manager.remove_group(group_id);
+        self.set_status_message(t!("split.scroll_sync_stopped").to_string());
+    }
+
+    /// Use `theme` for a split instead of the editor theme (None clears it)
+    pub fn set_split_theme(&mut self, split_id: SplitId, theme: Option<String>) {
+        if let Some(name) = &theme {
+            if !self.theme_registry.contains(name) {
+                self.set_status_message(t!("view.theme_not_found", theme = name).to_string());
+                return;
+            }
+        }
+        let Some(view_state) = self.split_view_states.get_mut(&split_id) else {
+            return;
+        };
+        view_state.theme = theme;
+        let message = match &view_state.theme {
+            Some(name) => t!("split.theme_set", theme = name),
+            None => t!("split.theme_cleared"),
+        };
+        self.set_status_message(message.to_string());
+    }
+
+    /// Start the prompt choosing the active split's theme
+    pub(super) fn start_select_split_theme_prompt(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let current = self
+            .split_view_states
+            .get(&active_split)
+            .and_then(|view_state| view_state.theme.clone());
+
+        // The first entry goes back to the editor theme
+        let mut suggestions = vec![Suggestion {
+            text: t!("split.editor_theme", theme = self.theme.name.clone()).to_string(),
+            description: current.is_none().then(|| t!("prompt.current").to_string()),
+            value: Some(String::new()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        }];
+        suggestions.extend(self.theme_registry.list().iter().map(|info| {
+            Suggestion {
+                text: info.name.clone(),
+                description: (current.as_deref() == Some(info.name.as_str()))
+                    .then(|| t!("prompt.current").to_string()),
+                value: Some(info.name.clone()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            }
+        }));
+        let selected = current
+            .as_deref()
+            .and_then(|name| suggestions.iter().position(|s| s.get_value() == name))
+            .unwrap_or(0);
+
+        let mut prompt = Prompt::with_suggestions(
+            t!("split.theme_prompt").to_string(),
+            PromptType::SelectSplitTheme,
+            suggestions,
+        );
+        prompt.selected_suggestion = Some(selected);
+        self.prompt = Some(prompt);
+    }
+
+    /// Start the prompt choosing the split the active tab moves to
+    ///
+    /// Other splits are listed by the tab they show; the last entry opens
+    /// the tab in a new split beside the current one.
+    pub(super) fn start_move_tab_to_split_prompt(&mut self) {
+        let active_split = self.split_manager.active_split();
+        let tab_count = self
+            .split_view_states
+            .get(&active_split)
+            .map_or(0, |vs| vs.open_buffers.len());
+        let other_splits: Vec<SplitId> = self
+            .split_manager
+            .root()
+            .leaf_split_ids()
+            .into_iter()
+            .filter(|&id| id != active_split)
+            .collect();
+        if other_splits.is_empty() && tab_count <= 1 {
+            self.set_status_message(t!("split.move_tab_only_tab").to_string());
+            return;
+        }
+
+        let mut suggestions: Vec<Suggestion> = other_splits
+            .iter()
+            .enumerate()
+            .map(|(index, &split_id)| {
+                // Name the split after its tab, as the tab bar shows it
+                let showing = self
+                    .split_manager
+                    .get_buffer_id(split_id)
+                    .and_then(|buffer_id| {
+                        let file_name = self
+                            .buffers
+                            .get(&buffer_id)
+                            .and_then(|state| state.buffer.file_path())
+                            .and_then(|path| path.file_name())
+                            .map(|name| name.to_string_lossy().into_owned());
+                        file_name.or_else(|| {
+                            self.buffer_metadata
+                                .get(&buffer_id)
+                                .map(|meta| meta.display_name.clone())
+                        })
+                    })
+                    .unwrap_or_default();
+                Suggestion {
+                    text: t!("split.move_tab_target", number = index + 1, name = showing)
+                        .to_string(),
+                    description: None,
+                    value: Some(split_id.0.to_string()),
+                    disabled: false,
+                    keybinding: None,
+                    source: None,
+                }
+            })
+            .collect();
+        if tab_count > 1 {
+            suggestions.push(Suggestion {
+                text: t!("split.move_tab_new_split").to_string(),
+                description: None,
+                value: Some("new".to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            });
+        }
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("split.move_tab_prompt").to_string(),
+            PromptType::MoveTabToSplit,
+            suggestions,
+        ));
+    }
+
+    /// Move the active tab to the split picked in the Move Tab to Split prompt
+    pub(super) fn move_active_tab_to_split(&mut self, target: &str) {
+        let buffer_id = self.active_buffer();
+        let active_split = self.split_manager.active_split();
+        if target == "new" {
+            self.create_split_from_tab(
+                buffer_id,
+                active_split,
+                active_split,
+                SplitDirection::Vertical,
+                false,
+            );
+            return;
+        }
+        let target_split = target
+            .parse()
+            .ok()
+            .map(SplitId)
+            .filter(|&id| id != active_split && self.split_view_states.contains_key(&id));
+        match target_split {
+            Some(target_split) => {
+                self.move_tab_to_split(buffer_id, active_split, target_split, None)
+            }
+            None => self.set_status_message(t!("split.move_tab_invalid").to_string()),
+        }
+    }
+
     /// Get cached separator areas for testing
     /// Returns (split_id, direction, x, y, length) tuples
     pub fn get_separator_areas(&self) -> &[(SplitId, SplitDirection, u16, u16, u16)] {
@@ -238,7 +238,7 @@ impl Editor {
     }
 
     /// Move a tab from one split to another
-    fn move_tab_to_split(
+    pub(super) fn move_tab_to_split(
         &mut self,
         buffer_id: BufferId,
         source_split_id: SplitId,
@@ -294,7 +294,7 @@ impl Editor {
     }
 
     /// Create a new split from a dropped tab
-    fn create_split_from_tab(
+    pub(super) fn create_split_from_tab(
         &mut self,
         buffer_id: BufferId,
         source_split_id: SplitId,
@@ -31,6 +31,11 @@ use rust_i18n::t;
 impl Editor {
     /// Open a new terminal in the current split
     pub fn open_terminal(&mut self) {
+        self.open_terminal_in(self.working_dir.clone());
+    }
+
+    /// Open a new terminal in the current split with its shell started in `cwd`
+    pub(super) fn open_terminal_in(&mut self, cwd: std::path::PathBuf) -> Option<TerminalId> {
         // Get the current split dimensions for the terminal size
         let (cols, rows) = self.get_terminal_dimensions();
 
@@ -60,7 +65,7 @@ impl Editor {
         match self.terminal_manager.spawn(
             cols,
             rows,
-            Some(self.working_dir.clone()),
+            Some(cwd),
             Some(log_path.clone()),
             backing_path_for_spawn,
         ) {
@@ -110,12 +115,14 @@ impl Editor {
                     terminal_id,
                     buffer_id
                 );
+                Some(terminal_id)
             }
             Err(e) => {
                 self.set_status_message(
                     t!("terminal.failed_to_open", error = e.to_string()).to_string(),
                 );
                 tracing::error!("Failed to open terminal: {}", e);
+                None
             }
         }
     }
@@ -180,8 +187,11 @@ impl Editor {
         self.terminal_buffers.insert(buffer_id, terminal_id);
 
         // Initialize event log for undo/redo
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         // Set up split view state
         if let Some(view_state) = self.split_view_states.get_mut(&split_id) {
@@ -236,8 +246,11 @@ impl Editor {
         );
         self.buffer_metadata.insert(buffer_id, metadata);
         self.terminal_buffers.insert(buffer_id, terminal_id);
-        self.event_logs
-            .insert(buffer_id, crate::model::event::EventLog::new());
+        self.event_logs.insert(
+            buffer_id,
+            crate::model::event::EventLog::new()
+                .with_branch_limit(self.config.editor.undo_branch_limit),
+        );
 
         buffer_id
     }
@@ -519,7 +532,7 @@ impl Editor {
             ) {
                 // Replace buffer state
                 if let Some(state) = self.buffers.get_mut(&buffer_id) {
-                    *state = new_state;
+                    state.reload_from(new_state);
                     // Move cursor to end of buffer
                     state.primary_cursor_mut().position = state.buffer.total_bytes();
                     // Terminal buffers should never be considered "modified"
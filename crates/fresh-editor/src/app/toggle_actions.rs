@@ -1,8 +1,8 @@
 //! Toggle actions and configuration operations for the Editor.
 //!
 //! This module contains toggle methods and configuration operations:
-//! - Toggle line numbers, debug highlights, menu bar
-//! - Toggle mouse capture, mouse hover, inlay hints
+//! - Toggle line numbers, debug highlights, ANSI colors, menu bar
+//! - Toggle mouse capture, mouse hover, inlay hints, overwrite mode
 //! - Reset buffer settings
 //! - Config dump, save, and reload
 
@@ -11,6 +11,7 @@ use rust_i18n::t;
 use crate::config::Config;
 use crate::config_io::{ConfigLayer, ConfigResolver};
 use crate::input::keybindings::KeybindingResolver;
+use crate::primitives::path_utils::expand_tilde;
 use crate::services::lsp::manager::detect_language;
 
 use super::Editor;
@@ -45,6 +46,19 @@ impl Editor {
         }
     }
 
+    /// Toggle between rendering the active buffer's ANSI escape sequences as
+    /// colors and showing them raw
+    pub fn toggle_ansi_colors(&mut self) {
+        if let Some(state) = self.buffers.get_mut(&self.active_buffer()) {
+            state.ansi_colors = !state.ansi_colors;
+            if state.ansi_colors {
+                self.set_status_message(t!("toggle.ansi_colors_on").to_string());
+            } else {
+                self.set_status_message(t!("toggle.ansi_colors_off").to_string());
+            }
+        }
+    }
+
     /// Toggle menu bar visibility
     pub fn toggle_menu_bar(&mut self) {
         self.menu_bar_visible = !self.menu_bar_visible;
@@ -151,6 +165,30 @@ impl Editor {
         }
     }
 
+    /// Toggle between workspace-relative and absolute buffer paths in the
+    /// status bar and buffer pickers
+    pub fn toggle_absolute_paths(&mut self) {
+        self.config.editor.show_absolute_paths = !self.config.editor.show_absolute_paths;
+
+        if self.config.editor.show_absolute_paths {
+            self.set_status_message(t!("toggle.absolute_paths_enabled").to_string());
+        } else {
+            self.set_status_message(t!("toggle.absolute_paths_disabled").to_string());
+        }
+    }
+
+    /// Toggle overwrite mode, where typed characters replace the character
+    /// under the cursor. The terminal cursor shape follows on the next frame.
+    pub fn toggle_overwrite_mode(&mut self) {
+        self.overwrite_mode = !self.overwrite_mode;
+
+        if self.overwrite_mode {
+            self.set_status_message(t!("toggle.overwrite_mode_enabled").to_string());
+        } else {
+            self.set_status_message(t!("toggle.overwrite_mode_disabled").to_string());
+        }
+    }
+
     /// Check if mouse hover is enabled
     pub fn is_mouse_hover_enabled(&self) -> bool {
         self.config.editor.mouse_hover_enabled
@@ -236,6 +274,15 @@ impl Editor {
             .map_err(|e| format!("Failed to save config: {}", e))
     }
 
+    /// Apply `editor.highlight_trailing_whitespace` to open file buffers
+    pub(super) fn apply_trailing_whitespace_highlight(&mut self) {
+        for (id, state) in self.buffers.iter_mut() {
+            let is_virtual = self.buffer_metadata.get(id).is_some_and(|m| m.is_virtual());
+            state.trailing_whitespace_overlay.enabled =
+                !is_virtual && self.config.editor.highlight_trailing_whitespace;
+        }
+    }
+
     /// Reload configuration from the config file
     ///
     /// This reloads the config from disk, applies runtime changes (theme, keybindings),
@@ -243,10 +290,21 @@ impl Editor {
     /// Uses the layered config system to properly merge with defaults.
     pub fn reload_config(&mut self) {
         let old_theme = self.config.theme.clone();
-        self.config = Config::load_with_layers(&self.dir_context, &self.working_dir);
+        let old_color_vision = self.config.view.color_vision;
+        let config = if self.user_config_disabled() {
+            Config::default()
+        } else {
+            Config::load_with_layers(&self.dir_context, &self.working_dir)
+        };
+        self.config = self.config_overrides.apply(config);
 
         // Refresh cached raw user config for plugins
-        self.user_config_raw = Config::read_user_config_raw(&self.working_dir);
+        self.user_config_raw = Config::read_user_config_raw(&self.working_dir, &self.dir_context);
+
+        // Themes are adjusted for the color vision as they load
+        if old_color_vision != self.config.view.color_vision {
+            self.reload_themes();
+        }
 
         // Apply theme change if needed
         if old_theme != self.config.theme {
@@ -268,8 +326,21 @@ impl Editor {
             }
         }
 
+        self.apply_trailing_whitespace_highlight();
+
+        // Apply the undo branch limit to open buffers
+        for event_log in self.event_logs.values_mut() {
+            event_log.set_branch_limit(self.config.editor.undo_branch_limit);
+        }
+        self.key_translator
+            .set_layout(self.config.editor.keyboard_layout);
+        self.clipboard
+            .set_history_size(self.config.editor.clipboard_history_size);
+        self.cache_budget
+            .set_limit_mb(self.config.editor.cache_budget_mb);
+
         // Emit event so plugins know config changed
-        let config_path = Config::find_config_path(&self.working_dir);
+        let config_path = Config::find_config_path(&self.working_dir, &self.dir_context);
         self.emit_event(
             "config_changed",
             serde_json::json!({
@@ -283,10 +354,7 @@ impl Editor {
     /// Call this after installing new theme packages or saving new themes.
     /// This rescans all theme directories and updates the available themes list.
     pub fn reload_themes(&mut self) {
-        use crate::view::theme::ThemeLoader;
-
-        let theme_loader = ThemeLoader::new();
-        self.theme_registry = theme_loader.load_all();
+        self.theme_registry = self.theme_loader().load_all();
 
         // Re-apply current theme if it still exists, otherwise it might have been updated
         if let Some(theme) = self.theme_registry.get_cloned(&self.config.theme) {
@@ -301,4 +369,65 @@ impl Editor {
         // Emit event so plugins know themes changed
         self.emit_event("themes_changed", serde_json::json!({}));
     }
+
+    /// Import a base16/base24 YAML scheme as a user theme and apply it
+    pub fn import_base16_theme(&mut self, path: &str) {
+        match self.write_base16_theme(path) {
+            Ok(name) => {
+                self.emit_event("themes_changed", serde_json::json!({}));
+                self.apply_theme(&name);
+                self.set_status_message(t!("view.theme_imported", theme = name).to_string());
+            }
+            Err(e) => {
+                self.set_status_message(t!("view.theme_import_failed", error = e).to_string());
+            }
+        }
+    }
+
+    /// Convert the scheme at `path`, save it to the themes directory and
+    /// register it. Returns the new theme's name.
+    fn write_base16_theme(&mut self, path: &str) -> Result<String, String> {
+        use crate::view::theme::{import_base16, Theme, ThemeFile};
+
+        let path = expand_tilde(path.trim());
+        let path = if path.is_absolute() {
+            path
+        } else {
+            self.working_dir.join(path)
+        };
+        let bytes = self
+            .filesystem
+            .read_file(&path)
+            .map_err(|e| format!("{}: {}", path.display(), e))?;
+        let yaml = String::from_utf8_lossy(&bytes);
+
+        let json = import_base16(&yaml, None).map_err(|e| e.to_string())?;
+        let theme_file: ThemeFile =
+            serde_json::from_value(json.clone()).map_err(|e| e.to_string())?;
+        let name = theme_file.name.clone();
+        if self
+            .theme_registry
+            .list()
+            .iter()
+            .any(|info| info.name == name && info.pack != "user")
+        {
+            return Err(t!("view.theme_exists", theme = name).to_string());
+        }
+
+        let themes_dir = self.dir_context.themes_dir();
+        let contents = serde_json::to_string_pretty(&json).map_err(|e| e.to_string())?;
+        self.filesystem
+            .create_dir_all(&themes_dir)
+            .and_then(|_| {
+                self.filesystem.write_file(
+                    &themes_dir.join(format!("{}.json", name)),
+                    contents.as_bytes(),
+                )
+            })
+            .map_err(|e| e.to_string())?;
+
+        let theme: Theme = theme_file.into();
+        self.theme_registry.insert(&name, theme, "user");
+        Ok(name)
+    }
 }
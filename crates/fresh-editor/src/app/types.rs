@@ -2,6 +2,7 @@ use crate::app::file_open::SortMode;
 use crate::input::keybindings::Action;
 use crate::model::event::{BufferId, SplitDirection, SplitId};
 use crate::services::async_bridge::LspMessageType;
+use crate::view::floating_window::{FloatingWindowHit, FloatingWindowId};
 use ratatui::layout::Rect;
 use rust_i18n::t;
 use std::collections::{HashMap, HashSet};
@@ -46,6 +47,27 @@ pub(super) struct Bookmark {
     pub position: usize,
 }
 
+/// A marker a plugin created with `editor.createMarker`
+#[derive(Debug, Clone)]
+pub(super) struct PluginMarker {
+    /// Plugin that created the marker
+    pub plugin_name: String,
+    /// Buffer whose marker list holds the marker
+    pub buffer_id: BufferId,
+    /// The marker in that list
+    pub marker_id: crate::model::marker::MarkerId,
+}
+
+/// A gutter column a plugin registered with `editor.registerGutter`
+#[derive(Debug, Clone)]
+pub(super) struct PluginGutter {
+    /// Plugin that registered the column
+    pub plugin_name: String,
+    pub namespace: String,
+    /// Width of the column in cells
+    pub width: usize,
+}
+
 /// State for interactive replace (query-replace)
 #[derive(Debug, Clone)]
 pub(super) struct InteractiveReplaceState {
@@ -99,6 +121,10 @@ pub struct BufferMetadata {
     /// Whether the buffer is read-only (typically true for virtual buffers)
     pub read_only: bool,
 
+    /// Whether the buffer is read-only because it holds library code (see
+    /// `editor.protect_library_files`); the user can still choose to edit it
+    pub library_protected: bool,
+
     /// Whether the buffer contains binary content
     /// Binary buffers are automatically read-only and render unprintable chars as code points
     pub binary: bool,
@@ -167,6 +193,7 @@ impl BufferMetadata {
             lsp_enabled: true,
             lsp_disabled_reason: None,
             read_only: false,
+            library_protected: false,
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
@@ -186,6 +213,7 @@ impl BufferMetadata {
             lsp_enabled: false, // No file path, so no LSP
             lsp_disabled_reason: Some(t!("lsp.disabled.unnamed").to_string()),
             read_only: false,
+            library_protected: false,
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
@@ -224,6 +252,7 @@ impl BufferMetadata {
             lsp_enabled,
             lsp_disabled_reason,
             read_only: false,
+            library_protected: false,
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
@@ -238,11 +267,12 @@ impl BufferMetadata {
     /// - Files in common vendor/dependency directories (.cargo, node_modules, etc.)
     pub fn is_library_path(path: &Path, working_dir: &Path) -> bool {
         // Check if outside working directory
-        if !path.starts_with(working_dir) {
-            return true;
-        }
+        !path.starts_with(working_dir) || Self::is_dependency_path(path)
+    }
 
-        // Check for common library paths within the project
+    /// Check if a path is inside a dependency directory (.cargo, node_modules, etc.),
+    /// wherever that directory is
+    pub fn is_dependency_path(path: &Path) -> bool {
         let path_str = path.to_string_lossy();
 
         // Rust: .cargo directory (can be within project for vendor'd crates)
@@ -330,6 +360,7 @@ impl BufferMetadata {
             lsp_enabled: false, // Virtual buffers don't use LSP
             lsp_disabled_reason: Some(t!("lsp.disabled.virtual").to_string()),
             read_only,
+            library_protected: false,
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: false,
@@ -347,6 +378,7 @@ impl BufferMetadata {
             lsp_enabled: false,
             lsp_disabled_reason: Some(t!("lsp.disabled.virtual").to_string()),
             read_only: true, // Hidden buffers are always read-only
+            library_protected: false,
             binary: false,
             lsp_opened_with: HashSet::new(),
             hidden_from_tabs: true,
@@ -448,8 +480,8 @@ pub enum HoverTarget {
     SearchOptionRegex,
     /// Hovering over the search options "Confirm Each" checkbox
     SearchOptionConfirmEach,
-    /// Hovering over a tab context menu item (item_index)
-    TabContextMenuItem(usize),
+    /// Hovering over a context menu entry (entry_index)
+    ContextMenuItem(usize),
 }
 
 /// Tab context menu items
@@ -465,6 +497,12 @@ pub enum TabContextMenuItem {
     CloseToLeft,
     /// Close all tabs
     CloseAll,
+    /// Copy the buffer's workspace-relative path
+    CopyRelativePath,
+    /// Copy the buffer's absolute path
+    CopyAbsolutePath,
+    /// Copy the buffer's file name
+    CopyFileName,
 }
 
 impl TabContextMenuItem {
@@ -476,6 +514,9 @@ impl TabContextMenuItem {
             Self::CloseToRight,
             Self::CloseToLeft,
             Self::CloseAll,
+            Self::CopyRelativePath,
+            Self::CopyAbsolutePath,
+            Self::CopyFileName,
         ]
     }
 
@@ -487,54 +528,37 @@ impl TabContextMenuItem {
             Self::CloseToRight => t!("tab.close_to_right").to_string(),
             Self::CloseToLeft => t!("tab.close_to_left").to_string(),
             Self::CloseAll => t!("tab.close_all").to_string(),
+            Self::CopyRelativePath => t!("tab.copy_relative_path").to_string(),
+            Self::CopyAbsolutePath => t!("tab.copy_absolute_path").to_string(),
+            Self::CopyFileName => t!("tab.copy_file_name").to_string(),
         }
     }
 }
 
-/// State for tab context menu (right-click popup on tabs)
-#[derive(Debug, Clone)]
-pub struct TabContextMenu {
-    /// The buffer ID this context menu is for
-    pub buffer_id: BufferId,
-    /// The split ID where the tab is located
-    pub split_id: SplitId,
-    /// Screen position where the menu should appear (x, y)
-    pub position: (u16, u16),
-    /// Currently highlighted menu item index
-    pub highlighted: usize,
+/// Which form of a buffer's path to copy to the clipboard
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathCopy {
+    /// Path relative to the workspace root (absolute when outside it)
+    Relative,
+    /// Absolute path
+    Absolute,
+    /// File name only
+    FileName,
 }
 
-impl TabContextMenu {
-    /// Create a new tab context menu
-    pub fn new(buffer_id: BufferId, split_id: SplitId, x: u16, y: u16) -> Self {
-        Self {
-            buffer_id,
-            split_id,
-            position: (x, y),
-            highlighted: 0,
-        }
-    }
-
-    /// Get the currently highlighted item
-    pub fn highlighted_item(&self) -> TabContextMenuItem {
-        TabContextMenuItem::all()[self.highlighted]
-    }
-
-    /// Move highlight down
-    pub fn next_item(&mut self) {
-        let items = TabContextMenuItem::all();
-        self.highlighted = (self.highlighted + 1) % items.len();
-    }
-
-    /// Move highlight up
-    pub fn prev_item(&mut self) {
-        let items = TabContextMenuItem::all();
-        self.highlighted = if self.highlighted == 0 {
-            items.len() - 1
-        } else {
-            self.highlighted - 1
-        };
-    }
+/// Ctrl+Tab walk through a split's tabs in most-recently-used order
+///
+/// The order is captured when the walk starts, so repeated presses go
+/// deeper into the history instead of toggling between two tabs.
+#[derive(Debug, Clone)]
+pub(super) struct TabMruCycle {
+    pub split_id: SplitId,
+    /// Tabs in MRU order; the first is the tab the walk started from
+    pub order: Vec<BufferId>,
+    /// Index into `order` of the tab shown now
+    pub position: usize,
+    /// The split's focus history before the walk started
+    pub focus_history: Vec<BufferId>,
 }
 
 /// Drop zone for tab drag-and-drop
@@ -649,6 +673,10 @@ pub(super) struct MouseState {
     pub drag_start_popup_scroll: Option<usize>,
     /// Whether we're currently selecting text in a popup (popup index)
     pub selecting_in_popup: Option<usize>,
+    /// Floating window being dragged, and whether by its title bar or resize handle
+    pub dragging_floating_window: Option<(FloatingWindowId, FloatingWindowHit)>,
+    /// Mouse position and window area when the floating window drag started
+    pub drag_start_floating_window: Option<((u16, u16), Rect)>,
 }
 
 /// Mapping from visual row to buffer positions for mouse click handling
@@ -727,6 +755,9 @@ pub(crate) struct CachedLayout {
     /// Maps visual row index to character position mappings
     /// Used to translate screen coordinates to buffer byte positions
     pub view_line_mappings: HashMap<SplitId, Vec<ViewLineMapping>>,
+    /// Line starts of the scope headers pinned by sticky scroll, by split,
+    /// drawn over the first rows of the split's content area
+    pub sticky_scroll_headers: HashMap<SplitId, Vec<usize>>,
     /// Settings modal layout for hit testing
     pub settings_layout: Option<crate::view::settings::SettingsLayout>,
     /// Status bar area (row, x, width)
@@ -741,6 +772,8 @@ pub(crate) struct CachedLayout {
     pub status_bar_language_area: Option<(u16, u16, u16)>,
     /// Status bar message area (row, start_col, end_col) - clickable to show status log
     pub status_bar_message_area: Option<(u16, u16, u16)>,
+    /// Status bar template segments with a click command ((row, start_col, end_col), command)
+    pub status_bar_segment_commands: Vec<((u16, u16, u16), String)>,
     /// Search options layout for checkbox hit testing
     pub search_options_layout: Option<crate::view::ui::status_bar::SearchOptionsLayout>,
     /// Menu bar layout for hit testing
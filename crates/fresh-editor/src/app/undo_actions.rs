@@ -1,13 +1,33 @@
 //! Undo and redo action handlers.
 
 use super::Editor;
+use crate::model::event::BufferId;
+use crate::primitives::text_property::TextPropertyEntry;
+use crate::view::prompt::PromptType;
 use rust_i18n::t;
 
+/// Display name of the Undo Tree buffer
+pub const UNDO_TREE_BUFFER_NAME: &str = "*Undo Tree*";
+
+/// Buffer mode of the Undo Tree buffer (Enter switches to the branch under the cursor)
+pub const UNDO_TREE_MODE: &str = "undo-tree";
+
+/// Format an event log timestamp (milliseconds since epoch) as local time
+fn format_timestamp(timestamp: u64) -> String {
+    chrono::DateTime::from_timestamp_millis(timestamp as i64)
+        .map(|t| {
+            t.with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_default()
+}
+
 impl Editor {
     /// Handle Undo action - revert the last edit operation.
     pub fn handle_undo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return;
         }
 
@@ -38,7 +58,7 @@ impl Editor {
     /// Handle Redo action - reapply an undone edit operation.
     pub fn handle_redo(&mut self) {
         if self.is_editing_disabled() {
-            self.set_status_message(t!("buffer.editing_disabled").to_string());
+            self.report_editing_disabled();
             return;
         }
 
@@ -52,4 +72,189 @@ impl Editor {
         // Update modified status based on event log position
         self.update_modified_from_event_log();
     }
+
+    /// Open the Undo Tree for the active buffer, listing the alternate branches
+    /// of its undo history. Reopening it (or pressing 'g' in it) refreshes it.
+    pub fn show_undo_tree(&mut self) {
+        let source = self
+            .undo_tree_source()
+            .unwrap_or_else(|| self.active_buffer());
+        let Some(event_log) = self.event_logs.get(&source) else {
+            return;
+        };
+        let source_name = self
+            .buffer_metadata
+            .get(&source)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_default();
+
+        let line = |text: String, branch: Option<usize>| {
+            let entry = TextPropertyEntry::text(text)
+                .with_property("undo_tree_source", serde_json::json!(source.0));
+            match branch {
+                Some(id) => entry.with_property("undo_branch", serde_json::json!(id)),
+                None => entry,
+            }
+        };
+
+        let edits = |entries: &[crate::model::event::LogEntry]| {
+            entries.iter().filter(|e| e.event.is_write_action()).count()
+        };
+        let history = event_log.entries();
+        let mut entries = vec![
+            line(format!("Undo Tree: {}\n", source_name), None),
+            line("\n".to_string(), None),
+            line(format!("{}\n\n", t!("undo_tree.help")), None),
+            line(
+                format!(
+                    "* {}  {}/{}  {}\n",
+                    t!("undo_tree.current"),
+                    edits(&history[..event_log.current_index()]),
+                    edits(history),
+                    history
+                        .last()
+                        .map(|e| format_timestamp(e.timestamp))
+                        .unwrap_or_default()
+                ),
+                None,
+            ),
+        ];
+
+        let summaries = event_log.branch_summaries();
+        if summaries.is_empty() {
+            entries.push(line(format!("  {}\n", t!("undo_tree.no_branches")), None));
+        }
+        for branch in summaries {
+            entries.push(line(
+                format!(
+                    "{}`- [{}] {}  {}\n",
+                    "   ".repeat(branch.depth + 1),
+                    branch.id,
+                    t!(
+                        "undo_tree.branch",
+                        fork = branch.fork_edit,
+                        count = branch.edit_count
+                    ),
+                    format_timestamp(branch.timestamp)
+                ),
+                Some(branch.id),
+            ));
+        }
+
+        let existing = self
+            .buffer_metadata
+            .iter()
+            .find(|(_, m)| m.display_name == UNDO_TREE_BUFFER_NAME)
+            .map(|(id, _)| *id);
+        let buffer_id = existing.unwrap_or_else(|| {
+            self.create_virtual_buffer(
+                UNDO_TREE_BUFFER_NAME.to_string(),
+                UNDO_TREE_MODE.to_string(),
+                true,
+            )
+        });
+        if let Err(e) = self.set_virtual_buffer_content(buffer_id, entries) {
+            tracing::warn!("Failed to fill undo tree buffer: {}", e);
+            return;
+        }
+        if let Some(state) = self.buffers.get_mut(&buffer_id) {
+            state.editing_disabled = true;
+            state.margins.set_line_numbers(false);
+        }
+        self.set_active_buffer(buffer_id);
+    }
+
+    /// Switch to an undo branch: the one under the cursor when the Undo Tree
+    /// is active, otherwise pick one of the active buffer's branches from a prompt.
+    pub fn undo_tree_switch_branch(&mut self) {
+        if let Some(source) = self.undo_tree_source() {
+            let branch = self.get_text_properties_at_cursor().and_then(|props| {
+                props
+                    .iter()
+                    .find_map(|p| p.get("undo_branch").and_then(|v| v.as_u64()))
+            });
+            match branch {
+                Some(id) if self.buffers.contains_key(&source) => {
+                    self.set_active_buffer(source);
+                    self.switch_undo_branch(id as usize);
+                }
+                _ => self.set_status_message(t!("undo_tree.no_branch_at_cursor").to_string()),
+            }
+            return;
+        }
+
+        let summaries = self.active_event_log().branch_summaries();
+        if summaries.is_empty() {
+            self.set_status_message(t!("undo_tree.no_branches").to_string());
+            return;
+        }
+
+        let suggestions: Vec<crate::input::commands::Suggestion> = summaries
+            .iter()
+            .map(|branch| crate::input::commands::Suggestion {
+                text: format!(
+                    "[{}] {}",
+                    branch.id,
+                    t!(
+                        "undo_tree.branch",
+                        fork = branch.fork_edit,
+                        count = branch.edit_count
+                    )
+                ),
+                description: Some(format_timestamp(branch.timestamp)),
+                value: Some(branch.id.to_string()),
+                disabled: false,
+                keybinding: None,
+                source: None,
+            })
+            .collect();
+
+        self.prompt = Some(crate::view::prompt::Prompt::with_suggestions(
+            t!("undo_tree.switch_prompt").to_string(),
+            PromptType::SwitchUndoBranch,
+            suggestions,
+        ));
+        if let Some(prompt) = self.prompt.as_mut() {
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Replace the active buffer's history with the undo branch `id`
+    pub(super) fn switch_undo_branch(&mut self, id: usize) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+
+        let Some(events) = self.active_event_log_mut().switch_to_branch(id) else {
+            self.set_status_message(t!("undo_tree.branch_not_found", id = id).to_string());
+            return;
+        };
+        for event in &events {
+            self.apply_event_to_active_buffer(event);
+        }
+        self.update_modified_from_event_log();
+        self.set_status_message(t!("undo_tree.switched", id = id).to_string());
+    }
+
+    /// The buffer whose history the active Undo Tree buffer shows, if the
+    /// active buffer is an Undo Tree
+    fn undo_tree_source(&self) -> Option<BufferId> {
+        let active = self.active_buffer();
+        let is_tree = self
+            .buffer_metadata
+            .get(&active)
+            .and_then(|m| m.virtual_mode())
+            == Some(UNDO_TREE_MODE);
+        if !is_tree {
+            return None;
+        }
+        self.buffers
+            .get(&active)?
+            .text_properties
+            .all()
+            .iter()
+            .find_map(|p| p.get("undo_tree_source").and_then(|v| v.as_u64()))
+            .map(|id| BufferId(id as usize))
+    }
 }
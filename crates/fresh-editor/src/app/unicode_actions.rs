@@ -0,0 +1,145 @@
+//! Insert Unicode Character picker and Vim-style digraph input.
+//!
+//! The picker lists the characters of
+//! [`unicode_chars`](crate::primitives::unicode_chars), recently inserted ones
+//! first, and filters on name, codepoint and category. Typing a codepoint
+//! (`U+2192`, `0x2192`) offers that character even when it is not in the
+//! table. Insert Digraph reads two characters and inserts what they map to in
+//! [`digraphs`](crate::primitives::digraphs) or the `digraphs` config table.
+
+use super::Editor;
+use crate::input::commands::Suggestion;
+use crate::primitives::{digraphs, unicode_chars};
+use crate::view::prompt::{Prompt, PromptType};
+use rust_i18n::t;
+
+/// How many recently inserted characters the picker remembers
+const MAX_RECENT_UNICODE_CHARS: usize = 16;
+
+fn char_suggestion(ch: char, name: &str, category: &str) -> Suggestion {
+    Suggestion {
+        text: format!("{}  {}", ch, name),
+        description: Some(format!(
+            "{} · {}",
+            unicode_chars::codepoint_label(ch),
+            category
+        )),
+        value: Some(ch.to_string()),
+        disabled: false,
+        keybinding: None,
+        source: None,
+    }
+}
+
+impl Editor {
+    /// Open the Insert Unicode Character picker
+    pub fn start_insert_unicode_prompt(&mut self) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+
+        let recent_label = t!("unicode.recent").to_string();
+        let mut suggestions: Vec<Suggestion> = self
+            .recent_unicode_chars
+            .iter()
+            .map(|&ch| {
+                let name = unicode_chars::lookup(ch).map_or("", |entry| entry.name);
+                char_suggestion(ch, name, &recent_label)
+            })
+            .collect();
+        suggestions.extend(
+            unicode_chars::UNICODE_CHARS
+                .iter()
+                .map(|entry| char_suggestion(entry.ch, entry.name, entry.category)),
+        );
+
+        self.prompt = Some(Prompt::with_suggestions(
+            t!("unicode.prompt").to_string(),
+            PromptType::InsertUnicode,
+            suggestions,
+        ));
+    }
+
+    /// Filter the picker, offering a typed codepoint as the first suggestion
+    pub(super) fn update_insert_unicode_suggestions(&mut self) {
+        let Some(prompt) = self.prompt.as_mut() else {
+            return;
+        };
+        prompt.filter_suggestions(true);
+        if let Some(ch) = unicode_chars::parse_codepoint(&prompt.input) {
+            let (name, category) =
+                unicode_chars::lookup(ch).map_or(("", ""), |entry| (entry.name, entry.category));
+            let value = ch.to_string();
+            prompt
+                .suggestions
+                .retain(|s| s.value.as_ref() != Some(&value));
+            prompt
+                .suggestions
+                .insert(0, char_suggestion(ch, name, category));
+            prompt.selected_suggestion = Some(0);
+        }
+    }
+
+    /// Insert the character picked in the Insert Unicode Character prompt
+    pub(super) fn insert_unicode_char(&mut self, input: &str) {
+        let ch = unicode_chars::parse_codepoint(input).or_else(|| {
+            let mut chars = input.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        });
+        let Some(ch) = ch else {
+            self.set_status_message(t!("unicode.invalid", input = input).to_string());
+            return;
+        };
+
+        self.recent_unicode_chars.retain(|&c| c != ch);
+        self.recent_unicode_chars.insert(0, ch);
+        self.recent_unicode_chars.truncate(MAX_RECENT_UNICODE_CHARS);
+
+        self.paste_text(ch.to_string());
+        self.set_status_message(
+            t!(
+                "unicode.inserted",
+                char = ch,
+                codepoint = unicode_chars::codepoint_label(ch)
+            )
+            .to_string(),
+        );
+    }
+
+    /// Open the Insert Digraph prompt, which reads two characters
+    pub fn start_digraph_prompt(&mut self) {
+        if self.is_editing_disabled() {
+            self.report_editing_disabled();
+            return;
+        }
+        self.prompt = Some(Prompt::new(
+            t!("digraph.prompt").to_string(),
+            PromptType::Digraph,
+        ));
+    }
+
+    /// Insert the digraph once two characters have been typed
+    pub(super) fn update_digraph_input(&mut self) {
+        let Some(prompt) = self.prompt.as_ref() else {
+            return;
+        };
+        let mut chars = prompt.input.chars();
+        let (Some(first), Some(second)) = (chars.next(), chars.next()) else {
+            return;
+        };
+        self.prompt = None;
+
+        match digraphs::lookup(&self.config.editor.digraphs, first, second) {
+            Some(text) => {
+                self.paste_text(text.clone());
+                self.set_status_message(t!("digraph.inserted", text = text).to_string());
+            }
+            None => {
+                self.set_status_message(
+                    t!("digraph.unknown", digraph = format!("{}{}", first, second)).to_string(),
+                );
+            }
+        }
+    }
+}
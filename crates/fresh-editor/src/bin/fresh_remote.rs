@@ -0,0 +1,170 @@
+//! Drive a running Fresh instance from the command line.
+//!
+//! The editor must have `editor.rpc_server` enabled. By default the most
+//! recently started instance is used; pass `--socket` or set
+//! `FRESH_RPC_SOCKET` to pick another one.
+//!
+//! ```text
+//! fresh-remote open src/main.rs:20
+//! fresh-remote diagnostics src/main.rs
+//! fresh-remote command save
+//! ```
+
+use clap::{Parser, Subcommand};
+use fresh::config_io::DirectoryContext;
+use fresh::services::rpc::{self, RpcMethod};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser, Debug)]
+#[command(name = "fresh-remote")]
+#[command(about = "Send commands to a running Fresh editor", long_about = None)]
+#[command(version)]
+struct Args {
+    /// Socket of the editor to talk to (default: $FRESH_RPC_SOCKET, then the
+    /// most recently started editor)
+    #[arg(long, value_name = "PATH")]
+    socket: Option<PathBuf>,
+
+    #[command(subcommand)]
+    request: Request,
+}
+
+#[derive(Subcommand, Debug)]
+enum Request {
+    /// Open a file, optionally at a line and column (file.rs:10:5)
+    Open { location: String },
+    /// Print LSP diagnostics, for one file or for all open files
+    Diagnostics { file: Option<PathBuf> },
+    /// Run an editor action by name (as used in keybindings, e.g. "save")
+    Command { action: String },
+}
+
+/// Split `path[:line[:column]]`, leaving non-numeric suffixes in the path
+fn parse_location(location: &str) -> (PathBuf, Option<usize>, Option<usize>) {
+    let mut numbers = Vec::new();
+    let mut path = location;
+    while numbers.len() < 2 {
+        match path.rsplit_once(':') {
+            Some((rest, n)) if !rest.is_empty() => match n.parse::<usize>() {
+                Ok(n) => {
+                    numbers.push(n);
+                    path = rest;
+                }
+                Err(_) => break,
+            },
+            _ => break,
+        }
+    }
+    numbers.reverse();
+    (
+        PathBuf::from(path),
+        numbers.first().copied(),
+        numbers.get(1).copied(),
+    )
+}
+
+/// Make a path absolute relative to this process, since the editor may run elsewhere
+fn absolute(path: PathBuf) -> PathBuf {
+    std::env::current_dir()
+        .map(|cwd| cwd.join(&path))
+        .unwrap_or(path)
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    let socket = args
+        .socket
+        .or_else(|| std::env::var_os("FRESH_RPC_SOCKET").map(PathBuf::from))
+        .or_else(|| {
+            let dir = DirectoryContext::from_system().ok()?.rpc_dir();
+            rpc::find_socket(&dir)
+        });
+    let Some(socket) = socket else {
+        eprintln!("No running Fresh instance found (is editor.rpc_server enabled?)");
+        return ExitCode::FAILURE;
+    };
+
+    let method = match args.request {
+        Request::Open { location } => {
+            let (path, line, column) = parse_location(&location);
+            RpcMethod::Open {
+                path: absolute(path),
+                line,
+                column,
+            }
+        }
+        Request::Diagnostics { file } => RpcMethod::Diagnostics {
+            path: file.map(absolute),
+        },
+        Request::Command { action } => RpcMethod::Command {
+            action,
+            args: HashMap::new(),
+        },
+    };
+    let is_diagnostics = matches!(method, RpcMethod::Diagnostics { .. });
+
+    #[cfg(unix)]
+    let response = rpc::send_request(&socket, &method);
+    #[cfg(not(unix))]
+    let response: std::io::Result<rpc::RpcResponse> = Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "fresh-remote is only available on Unix",
+    ));
+
+    match response {
+        Ok(rpc::RpcResponse {
+            error: Some(error), ..
+        }) => {
+            eprintln!("Error: {}", error);
+            ExitCode::FAILURE
+        }
+        Ok(rpc::RpcResponse { result, .. }) => {
+            let result = result.unwrap_or_default();
+            match result.as_array() {
+                Some(diagnostics) if is_diagnostics => {
+                    for d in diagnostics {
+                        println!(
+                            "{}:{}:{}: {}: {}",
+                            d["path"].as_str().unwrap_or_default(),
+                            d["line"],
+                            d["column"],
+                            d["severity"].as_str().unwrap_or_default(),
+                            d["message"].as_str().unwrap_or_default()
+                        );
+                    }
+                }
+                _ if result.is_null() => {}
+                _ => println!("{}", result),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Failed to reach {}: {}", socket.display(), e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_location() {
+        assert_eq!(
+            parse_location("foo.rs:20"),
+            (PathBuf::from("foo.rs"), Some(20), None)
+        );
+        assert_eq!(
+            parse_location("foo.rs:20:5"),
+            (PathBuf::from("foo.rs"), Some(20), Some(5))
+        );
+        assert_eq!(
+            parse_location("C:dir/foo.rs"),
+            (PathBuf::from("C:dir/foo.rs"), None, None)
+        );
+    }
+}
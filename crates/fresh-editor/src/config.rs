@@ -1,4 +1,5 @@
 use crate::types::{context_keys, LspServerConfig, ProcessLimits};
+use crate::view::theme::ColorVision;
 
 use rust_i18n::t;
 use schemars::JsonSchema;
@@ -154,6 +155,16 @@ impl CursorStyle {
         "_ Solid underline",
     ];
 
+    /// The non-blinking variant of this style
+    pub fn steady(self) -> Self {
+        match self {
+            Self::BlinkingBlock => Self::SteadyBlock,
+            Self::BlinkingBar => Self::SteadyBar,
+            Self::BlinkingUnderline => Self::SteadyUnderline,
+            other => other,
+        }
+    }
+
     /// Convert to crossterm cursor style (runtime only)
     #[cfg(feature = "runtime")]
     pub fn to_crossterm_style(self) -> crossterm::cursor::SetCursorStyle {
@@ -313,6 +324,38 @@ impl JsonSchema for AcceptSuggestionOnEnter {
     }
 }
 
+/// Physical keyboard layout, used to resolve shortcuts by key position
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyboardLayout {
+    /// US QWERTY - keybindings are resolved by the character typed
+    #[default]
+    Qwerty,
+    /// French AZERTY
+    Azerty,
+    /// German QWERTZ
+    Qwertz,
+    /// US Dvorak
+    Dvorak,
+    /// Colemak
+    Colemak,
+}
+
+impl JsonSchema for KeyboardLayout {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("KeyboardLayout")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Physical keyboard layout, used to resolve shortcuts by key position",
+            "type": "string",
+            "enum": ["qwerty", "azerty", "qwertz", "dvorak", "colemak"],
+            "default": "qwerty"
+        })
+    }
+}
+
 impl PartialEq<KeybindingMapName> for str {
     fn eq(&self, other: &KeybindingMapName) -> bool {
         self == other.0
@@ -371,6 +414,10 @@ pub struct Config {
     #[serde(default)]
     pub terminal: TerminalConfig,
 
+    /// Display settings that apply to every theme
+    #[serde(default)]
+    pub view: ViewConfig,
+
     /// Custom keybindings (overrides for the active map)
     #[serde(default)]
     pub keybindings: Vec<Keybinding>,
@@ -406,6 +453,10 @@ pub struct Config {
     /// Package manager settings for plugin/theme installation
     #[serde(default)]
     pub packages: PackagesConfig,
+
+    /// Limits on the HTTP requests plugins make with `editor.fetch`
+    #[serde(default)]
+    pub plugin_fetch: PluginFetchConfig,
 }
 
 fn default_keybinding_map_name() -> KeybindingMapName {
@@ -446,6 +497,28 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub syntax_highlighting: bool,
 
+    /// Highlight whitespace at the end of lines.
+    /// The color comes from the theme's `editor.trailing_whitespace_bg`; the run
+    /// the cursor is in is left unhighlighted while typing.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub highlight_trailing_whitespace: bool,
+
+    /// Pin the first lines of the functions, classes or Markdown sections
+    /// enclosing the top of the view while scrolling through their bodies.
+    /// Clicking a pinned line jumps to it.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll: bool,
+
+    /// Maximum number of scope headers pinned by sticky scroll.
+    /// Default: 5
+    #[serde(default = "default_sticky_scroll_max_depth")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub sticky_scroll_max_depth: usize,
+
     /// Whether the menu bar is visible by default.
     /// The menu bar provides access to menus (File, Edit, View, etc.) at the top of the screen.
     /// Can be toggled at runtime via command palette or keybinding.
@@ -462,6 +535,14 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub show_tab_bar: bool,
 
+    /// Show absolute file paths in the status bar and buffer pickers instead
+    /// of paths relative to the workspace root.
+    /// Can be toggled at runtime via command palette.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub show_absolute_paths: bool,
+
     /// Use the terminal's default background color instead of the theme's editor background.
     /// When enabled, the editor background inherits from the terminal emulator,
     /// allowing transparency or custom terminal backgrounds to show through.
@@ -477,6 +558,42 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Display"))]
     pub cursor_style: CursorStyle,
 
+    /// Cursor style while overwrite mode is active (toggled with the Insert key).
+    /// Default: steady_underline
+    #[serde(default = "default_cursor_style_overwrite")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub cursor_style_overwrite: CursorStyle,
+
+    /// Cursor style for editor modes set by plugins, keyed by mode name
+    /// (e.g. "vi-normal", "vi-insert"). While a listed mode is active its style
+    /// takes precedence over `cursor_style` and `cursor_style_overwrite`.
+    /// Default: {"vi-normal": "steady_block", "vi-insert": "steady_bar"}
+    #[serde(default = "default_cursor_style_modes")]
+    #[schemars(extend("x-section" = "Display"))]
+    pub cursor_style_modes: HashMap<String, CursorStyle>,
+
+    // ===== Status Bar =====
+    /// Layout of the status bar as a template of `{name}` segments and literal
+    /// text, e.g. "{mode} {file}{modified} {diagnostics} … {branch} {pos} {encoding}".
+    /// Everything after `…` is right-aligned. Segments: mode, file, modified,
+    /// pos, diagnostics, cursors, chord, messages, remote, profile, line_ending,
+    /// encoding, language, lsp, warnings, update, palette. Any other name shows
+    /// the text a plugin set with `editor.setStatusBarSegment`.
+    /// Empty uses the built-in layout.
+    /// Default: ""
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Status Bar"))]
+    pub status_bar_template: String,
+
+    /// Per-segment rules for `status_bar_template`, keyed by segment name:
+    /// `command` is the action or plugin command run when the segment is
+    /// clicked, and `min_width` hides the segment while the status bar is
+    /// narrower than that many columns.
+    /// Default: {}
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Status Bar"))]
+    pub status_bar_segments: HashMap<String, StatusBarSegmentConfig>,
+
     // ===== Editing =====
     /// Number of spaces per tab character
     #[serde(default = "default_tab_size")]
@@ -513,6 +630,60 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Editing"))]
     pub ensure_final_newline_on_save: bool,
 
+    /// Extra digraphs for the Insert Digraph command, mapping two typed
+    /// characters to the text they insert (e.g. `{"sh": "¯\\_(ツ)_/¯"}`).
+    /// Entries override the built-in Vim/RFC 1345 table.
+    /// Default: {}
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub digraphs: HashMap<String, String>,
+
+    /// How long to wait for the language server to answer each
+    /// `code_actions_on_save` request before skipping it, in milliseconds.
+    /// Default: 750
+    #[serde(default = "default_code_actions_on_save_timeout")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub code_actions_on_save_timeout_ms: u64,
+
+    /// Make word motions (move, select and delete by word) stop at camelCase
+    /// and snake_case boundaries inside identifiers.
+    /// The subword actions are available separately either way.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub subword_navigation: bool,
+
+    /// Number of recently copied values kept for Paste From Clipboard History.
+    /// Pinned entries are kept in addition to these.
+    /// Set to 0 to stop recording new values.
+    /// Default: 20
+    #[serde(default = "default_clipboard_history_size")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub clipboard_history_size: usize,
+
+    /// Also record text copied in other applications in the clipboard history,
+    /// by checking the system clipboard once per second.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub clipboard_history_poll_system: bool,
+
+    /// Column that Reflow Paragraph and Reflow Selection wrap text to.
+    /// Comment prefixes and list indentation count toward the width.
+    /// Default: 80
+    #[serde(default = "default_reflow_column")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub reflow_column: usize,
+
+    /// Open library code read-only: files in dependency directories
+    /// (node_modules, .cargo, site-packages, ...) and files outside the
+    /// workspace reached through go-to-definition.
+    /// Trying to edit one asks whether to allow editing it.
+    /// Default: true
+    #[serde(default = "default_true")]
+    #[schemars(extend("x-section" = "Editing"))]
+    pub protect_library_files: bool,
+
     // ===== Bracket Matching =====
     /// Highlight matching bracket pairs when cursor is on a bracket.
     /// Default: true
@@ -561,6 +732,13 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Completion"))]
     pub accept_suggestion_on_enter: AcceptSuggestionOnEnter,
 
+    /// Delay in milliseconds the cursor must rest before plugin inline
+    /// suggestion providers are asked for ghost text.
+    /// Default: 300
+    #[serde(default = "default_inline_suggestion_delay")]
+    #[schemars(extend("x-section" = "Completion"))]
+    pub inline_suggestion_delay_ms: u64,
+
     // ===== LSP =====
     /// Whether to enable LSP inlay hints (type hints, parameter hints, etc.)
     #[serde(default = "default_true")]
@@ -620,6 +798,18 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Recovery"))]
     pub auto_revert_poll_interval_ms: u64,
 
+    // ===== Integration =====
+    /// Listen on a local socket so external tools (such as `fresh-remote`) can
+    /// open files, read diagnostics and run commands in this editor.
+    /// Clients must present the session token written next to the socket.
+    /// Only available on Linux and macOS: there is no Windows named-pipe
+    /// transport, so on Windows the setting is ignored with a warning in the
+    /// log. Takes effect on the next start.
+    /// Default: false
+    #[serde(default = "default_false")]
+    #[schemars(extend("x-section" = "Integration"))]
+    pub rpc_server: bool,
+
     // ===== Keyboard =====
     /// Enable keyboard enhancement: disambiguate escape codes using CSI-u sequences.
     /// This allows unambiguous reading of Escape and modified keys.
@@ -654,6 +844,16 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Keyboard"))]
     pub keyboard_report_all_keys_as_escape_codes: bool,
 
+    /// Resolve Ctrl/Alt shortcuts by physical key position on this layout.
+    /// Terminals don't report scancodes, so when a non-QWERTY layout is set the
+    /// character a modified key produces is mapped to the US QWERTY key in the
+    /// same position before keybindings are looked up (on AZERTY, Ctrl+A then
+    /// triggers the Ctrl+Q binding). Plain typing is not affected.
+    /// Default: "qwerty"
+    #[serde(default)]
+    #[schemars(extend("x-section" = "Keyboard"))]
+    pub keyboard_layout: KeyboardLayout,
+
     // ===== Performance =====
     /// Maximum time in milliseconds for syntax highlighting per frame
     #[serde(default = "default_highlight_timeout")]
@@ -665,6 +865,24 @@ pub struct EditorConfig {
     #[schemars(extend("x-section" = "Performance"))]
     pub snapshot_interval: usize,
 
+    /// Maximum number of alternate undo branches kept per buffer.
+    /// Typing after an undo keeps the undone edits as a branch that can be
+    /// restored from the Undo Tree. The oldest branches are pruned first.
+    /// Set to 0 to discard undone edits (linear undo).
+    #[serde(default = "default_undo_branch_limit")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub undo_branch_limit: usize,
+
+    /// Memory budget in megabytes shared by the editor's caches (syntax
+    /// highlighting, the file index and clipboard history). When the total
+    /// goes over, the least recently used caches are dropped and rebuilt on
+    /// demand. See the Memory Usage command for current sizes.
+    /// Set to 0 for no limit.
+    /// Default: 256
+    #[serde(default = "default_cache_budget_mb")]
+    #[schemars(extend("x-section" = "Performance"))]
+    pub cache_budget_mb: usize,
+
     /// Number of bytes to look back/forward from the viewport for syntax highlighting context.
     /// Larger values improve accuracy for multi-line constructs (strings, comments, nested blocks)
     /// but may slow down highlighting for very large files.
@@ -708,6 +926,12 @@ fn default_tab_size() -> usize {
 /// Files smaller will use exact algorithms (full line tracking, complete parsing)
 pub const LARGE_FILE_THRESHOLD_BYTES: u64 = 1024 * 1024; // 1MB
 
+/// Default value of `editor.cache_budget_mb`
+pub const DEFAULT_CACHE_BUDGET_MB: usize = 256;
+
+/// Default number of unpinned entries kept in the clipboard history
+pub const DEFAULT_CLIPBOARD_HISTORY_SIZE: usize = 20;
+
 fn default_large_file_threshold() -> u64 {
     LARGE_FILE_THRESHOLD_BYTES
 }
@@ -720,10 +944,25 @@ fn default_false() -> bool {
     false
 }
 
+fn default_cursor_style_overwrite() -> CursorStyle {
+    CursorStyle::SteadyUnderline
+}
+
+fn default_cursor_style_modes() -> HashMap<String, CursorStyle> {
+    HashMap::from([
+        ("vi-normal".to_string(), CursorStyle::SteadyBlock),
+        ("vi-insert".to_string(), CursorStyle::SteadyBar),
+    ])
+}
+
 fn default_quick_suggestions_delay() -> u64 {
     10 // 10ms like VS Code
 }
 
+fn default_inline_suggestion_delay() -> u64 {
+    300
+}
+
 fn default_accept_suggestion_on_enter() -> AcceptSuggestionOnEnter {
     AcceptSuggestionOnEnter::On
 }
@@ -732,6 +971,10 @@ fn default_scroll_offset() -> usize {
     3
 }
 
+fn default_sticky_scroll_max_depth() -> usize {
+    5
+}
+
 fn default_highlight_timeout() -> u64 {
     5
 }
@@ -740,6 +983,14 @@ fn default_snapshot_interval() -> usize {
     100
 }
 
+fn default_undo_branch_limit() -> usize {
+    crate::model::event::DEFAULT_UNDO_BRANCH_LIMIT
+}
+
+fn default_cache_budget_mb() -> usize {
+    DEFAULT_CACHE_BUDGET_MB
+}
+
 fn default_estimated_line_length() -> usize {
     80
 }
@@ -752,6 +1003,18 @@ fn default_highlight_context_bytes() -> usize {
     10_000 // 10KB context for accurate syntax highlighting
 }
 
+fn default_code_actions_on_save_timeout() -> u64 {
+    750 // Long enough for a warm server, short enough not to be noticed
+}
+
+fn default_clipboard_history_size() -> usize {
+    DEFAULT_CLIPBOARD_HISTORY_SIZE
+}
+
+fn default_reflow_column() -> usize {
+    80
+}
+
 fn default_mouse_hover_delay() -> u64 {
     500 // 500ms delay before showing hover info
 }
@@ -777,9 +1040,14 @@ impl Default for EditorConfig {
             relative_line_numbers: false,
             scroll_offset: default_scroll_offset(),
             syntax_highlighting: true,
+            highlight_trailing_whitespace: true,
+            sticky_scroll: false,
+            sticky_scroll_max_depth: default_sticky_scroll_max_depth(),
             line_wrap: true,
             highlight_timeout_ms: default_highlight_timeout(),
             snapshot_interval: default_snapshot_interval(),
+            undo_branch_limit: default_undo_branch_limit(),
+            cache_budget_mb: default_cache_budget_mb(),
             large_file_threshold_bytes: default_large_file_threshold(),
             estimated_line_length: default_estimated_line_length(),
             enable_inlay_hints: true,
@@ -791,23 +1059,38 @@ impl Default for EditorConfig {
             mouse_hover_delay_ms: default_mouse_hover_delay(),
             double_click_time_ms: default_double_click_time(),
             auto_revert_poll_interval_ms: default_auto_revert_poll_interval(),
+            rpc_server: false,
             file_tree_poll_interval_ms: default_file_tree_poll_interval(),
             default_line_ending: LineEndingOption::default(),
             trim_trailing_whitespace_on_save: false,
             ensure_final_newline_on_save: false,
+            digraphs: HashMap::new(),
+            code_actions_on_save_timeout_ms: default_code_actions_on_save_timeout(),
+            subword_navigation: false,
+            clipboard_history_size: default_clipboard_history_size(),
+            clipboard_history_poll_system: false,
+            reflow_column: default_reflow_column(),
+            protect_library_files: true,
             highlight_matching_brackets: true,
             rainbow_brackets: true,
             cursor_style: CursorStyle::default(),
+            cursor_style_overwrite: default_cursor_style_overwrite(),
+            cursor_style_modes: default_cursor_style_modes(),
+            status_bar_template: String::new(),
+            status_bar_segments: HashMap::new(),
             keyboard_disambiguate_escape_codes: true,
             keyboard_report_event_types: false,
             keyboard_report_alternate_keys: true,
             keyboard_report_all_keys_as_escape_codes: false,
+            keyboard_layout: KeyboardLayout::default(),
             quick_suggestions: true,
             quick_suggestions_delay_ms: default_quick_suggestions_delay(),
             suggest_on_trigger_characters: true,
             accept_suggestion_on_enter: default_accept_suggestion_on_enter(),
+            inline_suggestion_delay_ms: default_inline_suggestion_delay(),
             show_menu_bar: true,
             show_tab_bar: true,
+            show_absolute_paths: false,
             use_terminal_bg: false,
         }
     }
@@ -858,6 +1141,28 @@ impl Default for TerminalConfig {
     }
 }
 
+/// Display settings that apply to every theme
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ViewConfig {
+    /// Adjust the diagnostic and diff colors of every theme to hues that stay
+    /// distinguishable with a color vision deficiency
+    /// Default: normal (themes are used as written)
+    #[serde(default)]
+    pub color_vision: ColorVision,
+}
+
+/// Rules for one segment of `editor.status_bar_template`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct StatusBarSegmentConfig {
+    /// Action (e.g. "set_language") or plugin command run when the segment is clicked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    /// Hide the segment while the status bar is narrower than this many columns
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_width: Option<u16>,
+}
+
 /// Warning notification configuration
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct WarningsConfig {
@@ -896,6 +1201,44 @@ impl Default for PackagesConfig {
     }
 }
 
+/// Limits on plugin HTTP requests (`editor.fetch`)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct PluginFetchConfig {
+    /// Hosts plugins may send requests to. Entries match a host exactly,
+    /// `*.example.com` also matches its subdomains, and `*` allows any host.
+    /// Default: [] (plugins cannot make requests)
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// Timeout for requests that don't set `timeoutMs`, in milliseconds
+    /// Default: 30000
+    #[serde(default = "default_plugin_fetch_timeout_ms")]
+    pub default_timeout_ms: u64,
+
+    /// Largest response body a request may return, in bytes
+    /// Default: 10485760 (10 MiB)
+    #[serde(default = "default_plugin_fetch_max_response_bytes")]
+    pub max_response_bytes: u64,
+}
+
+fn default_plugin_fetch_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_plugin_fetch_max_response_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+impl Default for PluginFetchConfig {
+    fn default() -> Self {
+        Self {
+            allowed_hosts: Vec::new(),
+            default_timeout_ms: default_plugin_fetch_timeout_ms(),
+            max_response_bytes: default_plugin_fetch_max_response_bytes(),
+        }
+    }
+}
+
 // Re-export PluginConfig from fresh-core for shared type usage
 pub use fresh_core::config::PluginConfig;
 
@@ -917,6 +1260,12 @@ pub struct FileBrowserConfig {
     /// Whether to show hidden files (starting with .) by default in Open File dialog
     #[serde(default = "default_false")]
     pub show_hidden: bool,
+
+    /// Use the operating system's file dialogs for Open and Save As
+    /// (zenity/kdialog, osascript or PowerShell). Falls back to the built-in
+    /// file browser when no dialog program is available or when editing remotely.
+    #[serde(default = "default_false")]
+    pub native_dialogs: bool,
 }
 
 /// A single key in a sequence
@@ -1085,6 +1434,11 @@ pub struct LanguageConfig {
     #[serde(default)]
     pub format_on_save: bool,
 
+    /// LSP code action kinds to apply on save, in order, before formatting
+    /// (e.g., ["source.fixAll", "source.organizeImports"])
+    #[serde(default)]
+    pub code_actions_on_save: Vec<String>,
+
     /// Actions to run when a file of this language is saved (linters, etc.)
     /// Actions are run in order; if any fails (non-zero exit), subsequent actions don't run
     /// Note: Use `formatter` + `format_on_save` for formatting, not on_save
@@ -1118,6 +1472,9 @@ pub struct BufferConfig {
     /// Whether to format on save
     pub format_on_save: bool,
 
+    /// LSP code action kinds to apply on save
+    pub code_actions_on_save: Vec<String>,
+
     /// Actions to run when saving
     pub on_save: Vec<OnSaveAction>,
 
@@ -1148,6 +1505,7 @@ impl BufferConfig {
             show_whitespace_tabs: true, // Global default
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: Vec::new(),
             on_save: Vec::new(),
             highlighter: HighlighterPreference::Auto,
             textmate_grammar: None,
@@ -1176,6 +1534,9 @@ impl BufferConfig {
                 // Format on save: from language config
                 config.format_on_save = lang_config.format_on_save;
 
+                // Code actions on save: from language config
+                config.code_actions_on_save = lang_config.code_actions_on_save.clone();
+
                 // On save actions: from language config
                 config.on_save = lang_config.on_save.clone();
 
@@ -1324,6 +1685,7 @@ impl Default for Config {
             file_explorer: FileExplorerConfig::default(),
             file_browser: FileBrowserConfig::default(),
             terminal: TerminalConfig::default(),
+            view: ViewConfig::default(),
             keybindings: vec![], // User customizations only; defaults come from active_keybinding_map
             keybinding_maps: HashMap::new(), // User-defined maps go here
             active_keybinding_map: default_keybinding_map_name(),
@@ -1332,6 +1694,7 @@ impl Default for Config {
             warnings: WarningsConfig::default(),
             plugins: HashMap::new(), // Populated when scanning for plugins
             packages: PackagesConfig::default(),
+            plugin_fetch: PluginFetchConfig::default(),
         }
     }
 }
@@ -1405,6 +1768,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.file.open_recent_workspace").to_string(),
+                        action: "open_recent_workspace".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.file.quit").to_string(),
                         action: "quit".to_string(),
@@ -1594,6 +1964,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.select_split_theme").to_string(),
+                        action: "select_split_theme".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Action {
                         label: t!("menu.view.select_locale").to_string(),
                         action: "select_locale".to_string(),
@@ -1658,6 +2035,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.view.toggle_zoom_split").to_string(),
+                        action: "toggle_zoom_split".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                     MenuItem::Separator { separator: true },
                     MenuItem::Submenu {
                         label: t!("menu.terminal").to_string(),
@@ -1930,6 +2314,13 @@ impl MenuConfig {
                         when: None,
                         checkbox: None,
                     },
+                    MenuItem::Action {
+                        label: t!("menu.lsp.detect_servers").to_string(),
+                        action: "lsp_detect_servers".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
                 ],
             },
             // Explorer menu (only visible when file explorer is focused)
@@ -1999,6 +2390,30 @@ impl MenuConfig {
                     },
                 ],
             },
+            // Windows menu (only visible while floating windows are open).
+            // Entries for the open windows are appended after the separator at runtime.
+            Menu {
+                id: Some("Windows".to_string()),
+                label: t!("menu.windows").to_string(),
+                when: Some(context_keys::HAS_FLOATING_WINDOWS.to_string()),
+                items: vec![
+                    MenuItem::Action {
+                        label: t!("menu.windows.focus_next").to_string(),
+                        action: "focus_floating_window".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Action {
+                        label: t!("menu.windows.close").to_string(),
+                        action: "close_floating_window".to_string(),
+                        args: HashMap::new(),
+                        when: None,
+                        checkbox: None,
+                    },
+                    MenuItem::Separator { separator: true },
+                ],
+            },
             // Help menu
             Menu {
                 id: Some("Help".to_string()),
@@ -2151,6 +2566,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2175,6 +2591,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2199,6 +2616,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2227,6 +2645,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2251,6 +2670,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2282,6 +2702,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2301,6 +2722,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2335,6 +2757,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2358,6 +2781,7 @@ impl Config {
                 tab_size: Some(8), // Makefiles traditionally use 8-space tabs
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2377,6 +2801,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2401,6 +2826,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2420,6 +2846,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2444,6 +2871,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2463,6 +2891,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2488,6 +2917,7 @@ impl Config {
                     timeout_ms: 10000,
                 }),
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2507,6 +2937,7 @@ impl Config {
                 tab_size: Some(8),
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2526,6 +2957,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2545,6 +2977,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2571,6 +3004,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2590,6 +3024,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2610,6 +3045,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2634,6 +3070,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2658,6 +3095,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2677,6 +3115,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2696,6 +3135,7 @@ impl Config {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -2747,6 +3187,16 @@ impl Config {
             "files": { "watcher": "server" }
         });
 
+        // rust-analyzer replaces its configuration with the `workspace/configuration`
+        // response, so serve the same options plus inlay hints from the "rust-analyzer" section
+        let mut ra_settings = ra_init_options.clone();
+        ra_settings["inlayHints"] = serde_json::json!({
+            "typeHints": { "enable": true },
+            "parameterHints": { "enable": true },
+            "chainingHints": { "enable": true },
+            "closureReturnTypeHints": { "enable": "always" }
+        });
+
         lsp.insert(
             "rust".to_string(),
             LspServerConfig {
@@ -2756,6 +3206,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: Some(ra_init_options),
+                settings: Some(serde_json::json!({ "rust-analyzer": ra_settings })),
             },
         );
 
@@ -2769,6 +3220,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2781,6 +3233,7 @@ impl Config {
             auto_start: false,
             process_limits: ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         };
         lsp.insert("javascript".to_string(), ts_lsp.clone());
         lsp.insert("typescript".to_string(), ts_lsp);
@@ -2795,6 +3248,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2808,6 +3262,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2821,6 +3276,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
         lsp.insert(
@@ -2832,6 +3288,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2845,6 +3302,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2858,6 +3316,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2871,6 +3330,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2885,6 +3345,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2899,6 +3360,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2913,6 +3375,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2927,6 +3390,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2941,6 +3405,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -2955,6 +3420,7 @@ impl Config {
                 auto_start: false,
                 process_limits: ProcessLimits::default(),
                 initialization_options: None,
+                settings: None,
             },
         );
     }
@@ -3275,6 +3741,7 @@ mod tests {
                     timeout_ms: 10000,
                 }),
                 format_on_save: true,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
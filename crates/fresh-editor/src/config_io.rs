@@ -323,6 +323,34 @@ impl ConfigResolver {
         self.load_layer_from_path(&self.session_config_path())
     }
 
+    /// Get the path to a named profile file (`profiles/<name>.json` in the config dir).
+    pub fn profile_path(&self, name: &str) -> PathBuf {
+        self.dir_context
+            .profiles_dir()
+            .join(format!("{}.json", name))
+    }
+
+    /// Load a named profile from disk.
+    ///
+    /// Unlike the other layers a missing profile is an error, since it was
+    /// explicitly requested.
+    pub fn load_profile_layer(&self, name: &str) -> Result<PartialConfig, ConfigError> {
+        if name.is_empty() || name.contains(['/', '\\']) || name.starts_with('.') {
+            return Err(ConfigError::ValidationError(format!(
+                "Invalid profile name '{}'",
+                name
+            )));
+        }
+        let path = self.profile_path(name);
+        self.load_layer_from_path(&path)?.ok_or_else(|| {
+            ConfigError::IoError(format!(
+                "Profile '{}' not found: {} does not exist",
+                name,
+                path.display()
+            ))
+        })
+    }
+
     /// Load a layer from a specific path, applying migrations if needed.
     fn load_layer_from_path(&self, path: &Path) -> Result<Option<PartialConfig>, ConfigError> {
         if !path.exists() {
@@ -590,6 +618,129 @@ impl ConfigResolver {
     }
 }
 
+// ============================================================================
+// Session Overrides
+// ============================================================================
+
+/// Settings layered over the resolved config for a single run: a named
+/// profile (`--profile`) and `key=value` overrides (`--config-set`, `--theme`).
+///
+/// Overrides are never written to any config file; they are re-applied
+/// whenever the config is reloaded.
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOverrides {
+    /// Name of the active profile, shown in the status bar
+    pub profile: Option<String>,
+    /// Profile settings with the command-line overrides merged on top
+    layer: Option<PartialConfig>,
+}
+
+impl ConfigOverrides {
+    /// Load the named profile (if any) and merge `settings` over it.
+    ///
+    /// Later settings win over earlier ones for the same key.
+    pub fn load(
+        dir_context: &DirectoryContext,
+        profile: Option<&str>,
+        settings: &[(String, Value)],
+    ) -> Result<Self, ConfigError> {
+        let mut layer = if settings.is_empty() {
+            None
+        } else {
+            Some(partial_from_settings(settings)?)
+        };
+
+        if let Some(name) = profile {
+            // The working dir is irrelevant for profiles, which live in the config dir
+            let resolver = ConfigResolver::new(dir_context.clone(), PathBuf::new());
+            let profile_layer = resolver.load_profile_layer(name)?;
+            let mut merged = layer.unwrap_or_default();
+            merged.merge_from(&profile_layer);
+            layer = Some(merged);
+        }
+
+        Ok(Self {
+            profile: profile.map(str::to_string),
+            layer,
+        })
+    }
+
+    /// Layer the overrides over an already resolved config.
+    pub fn apply(&self, config: Config) -> Config {
+        let Some(layer) = &self.layer else {
+            return config;
+        };
+        let mut merged = layer.clone();
+        merged.merge_from(&PartialConfig::from(&config));
+        merged.resolve()
+    }
+}
+
+/// Parse a `--config-set` argument of the form `section.key=value`.
+///
+/// The value is read as JSON when possible (`true`, `4`, `["a"]`) and as a
+/// plain string otherwise. Key segments may be written in camelCase.
+pub fn parse_config_setting(arg: &str) -> Result<(String, Value), ConfigError> {
+    let (key, raw) = arg
+        .split_once('=')
+        .ok_or_else(|| ConfigError::ParseError(format!("Expected KEY=VALUE, got '{}'", arg)))?;
+    let key = key.trim();
+    if key.is_empty() || key.split('.').any(str::is_empty) {
+        return Err(ConfigError::ParseError(format!(
+            "Invalid config key '{}'",
+            key
+        )));
+    }
+    let value = serde_json::from_str(raw.trim()).unwrap_or_else(|_| Value::String(raw.to_string()));
+    Ok((key.to_string(), value))
+}
+
+/// Convert a camelCase key segment to the snake_case used by the config.
+fn snake_case_segment(segment: &str) -> String {
+    let mut out = String::with_capacity(segment.len() + 4);
+    for (i, ch) in segment.chars().enumerate() {
+        if ch.is_ascii_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.push(ch.to_ascii_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
+/// Build a partial config from dotted `key = value` settings, rejecting keys
+/// that don't name a config field.
+fn partial_from_settings(settings: &[(String, Value)]) -> Result<PartialConfig, ConfigError> {
+    let mut root = Value::Object(Default::default());
+    let mut pointers = Vec::with_capacity(settings.len());
+    for (key, value) in settings {
+        let pointer: String = key
+            .split('.')
+            .map(|segment| format!("/{}", snake_case_segment(segment)))
+            .collect();
+        set_json_pointer(&mut root, &pointer, value.clone());
+        pointers.push((key, pointer));
+    }
+
+    let partial: PartialConfig = serde_json::from_value(root)
+        .map_err(|e| ConfigError::ParseError(format!("Invalid config override: {}", e)))?;
+
+    // Unknown keys are silently dropped by deserialization; catch them here
+    let round_trip = serde_json::to_value(&partial).unwrap_or_default();
+    for (key, pointer) in pointers {
+        if round_trip.pointer(&pointer).is_none_or(Value::is_null) {
+            return Err(ConfigError::ValidationError(format!(
+                "Unknown config key '{}'",
+                key
+            )));
+        }
+    }
+    Ok(partial)
+}
+
 /// Recursively collect all non-null leaf paths in a JSON value.
 fn collect_paths<F>(value: &Value, prefix: &str, collector: &mut F)
 where
@@ -628,57 +779,30 @@ fn diff_partial_config(current: &PartialConfig, parent: &PartialConfig) -> Parti
 }
 
 impl Config {
-    /// Get the system config file paths (without local/working directory).
-    ///
-    /// On macOS, prioritizes `~/.config/fresh/config.json` if it exists.
-    /// Then checks the standard system config directory.
-    fn system_config_paths() -> Vec<PathBuf> {
-        let mut paths = Vec::with_capacity(2);
-
-        // macOS: Prioritize ~/.config/fresh/config.json
-        #[cfg(target_os = "macos")]
-        if let Some(home) = dirs::home_dir() {
-            let path = home.join(".config").join("fresh").join(Config::FILENAME);
-            if path.exists() {
-                paths.push(path);
-            }
-        }
-
-        // Standard system paths (XDG on Linux, AppSupport on macOS, Roaming on Windows)
-        if let Some(config_dir) = dirs::config_dir() {
-            let path = config_dir.join("fresh").join(Config::FILENAME);
-            if !paths.contains(&path) && path.exists() {
-                paths.push(path);
-            }
-        }
-
-        paths
-    }
-
     /// Get all config search paths, checking local (working directory) first.
     ///
     /// Search order:
     /// 1. `{working_dir}/config.json` (project-local config)
-    /// 2. System config paths (see `system_config_paths()`)
+    /// 2. `{config_dir}/config.json` (user config, see [`DirectoryContext`])
     ///
     /// Only returns paths that exist on disk.
-    fn config_search_paths(working_dir: &Path) -> Vec<PathBuf> {
-        let local = Self::local_config_path(working_dir);
-        let mut paths = Vec::with_capacity(3);
-
-        if local.exists() {
-            paths.push(local);
-        }
-
-        paths.extend(Self::system_config_paths());
-        paths
+    fn config_search_paths(working_dir: &Path, dir_context: &DirectoryContext) -> Vec<PathBuf> {
+        [
+            Self::local_config_path(working_dir),
+            dir_context.config_path(),
+        ]
+        .into_iter()
+        .filter(|path| path.exists())
+        .collect()
     }
 
     /// Find the first existing config file, checking local directory first.
     ///
     /// Returns `None` if no config file exists anywhere.
-    pub fn find_config_path(working_dir: &Path) -> Option<PathBuf> {
-        Self::config_search_paths(working_dir).into_iter().next()
+    pub fn find_config_path(working_dir: &Path, dir_context: &DirectoryContext) -> Option<PathBuf> {
+        Self::config_search_paths(working_dir, dir_context)
+            .into_iter()
+            .next()
     }
 
     /// Load configuration using the 4-level layer system.
@@ -705,9 +829,12 @@ impl Config {
     /// with defaults). Useful for plugins that need to distinguish between
     /// user-set values and defaults.
     ///
-    /// Checks working directory first, then system paths.
-    pub fn read_user_config_raw(working_dir: &Path) -> serde_json::Value {
-        for path in Self::config_search_paths(working_dir) {
+    /// Checks working directory first, then the user config directory.
+    pub fn read_user_config_raw(
+        working_dir: &Path,
+        dir_context: &DirectoryContext,
+    ) -> serde_json::Value {
+        for path in Self::config_search_paths(working_dir, dir_context) {
             if let Ok(contents) = std::fs::read_to_string(&path) {
                 match serde_json::from_str(&contents) {
                     Ok(value) => return value,
@@ -774,10 +901,182 @@ fn is_empty_diff(value: &serde_json::Value) -> bool {
     }
 }
 
+// ============================================================================
+// Path Expansion
+// ============================================================================
+
+/// Expand a user-supplied path: a leading `~`, `$VAR` and `${VAR}` everywhere,
+/// and `%VAR%` on Windows.
+///
+/// This is the shared resolver for every place a user types or configures a
+/// path (open/save prompts, config values, on-save actions, plugin `openFile`).
+/// Unset variables are left as written, so a typo shows up in the resulting
+/// path instead of silently becoming an empty string.
+pub fn expand_path(input: &str) -> PathBuf {
+    expand_path_with(input, |name| std::env::var(name).ok())
+}
+
+/// Like [`expand_path`], but variables are looked up with `lookup` (which can
+/// provide values such as `$FILE` before falling back to the environment).
+pub fn expand_path_with(input: &str, lookup: impl Fn(&str) -> Option<String>) -> PathBuf {
+    PathBuf::from(expand_vars(
+        input,
+        dirs::home_dir().as_deref(),
+        &lookup,
+        cfg!(windows),
+    ))
+}
+
+/// Expansion behind [`expand_path_with`], with the home directory and
+/// `%VAR%` support passed in so every syntax can be tested on any platform.
+fn expand_vars(
+    input: &str,
+    home: Option<&Path>,
+    lookup: &dyn Fn(&str) -> Option<String>,
+    percent_vars: bool,
+) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut rest = input;
+
+    // `~` only means home on its own or followed by a separator (`~user` is
+    // not supported and is kept literally)
+    if let (Some(after), Some(home)) = (rest.strip_prefix('~'), home) {
+        if after.is_empty() || after.starts_with(std::path::is_separator) {
+            out.push_str(&home.to_string_lossy());
+            rest = after;
+        }
+    }
+
+    let is_name_start = |c: char| c.is_ascii_alphabetic() || c == '_';
+    let is_name_char = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    while let Some(pos) = rest.find(|c| c == '$' || (percent_vars && c == '%')) {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+
+        // (variable name, length of the whole reference)
+        let reference = if let Some(braced) = tail.strip_prefix("${") {
+            braced.find('}').map(|end| (&braced[..end], end + 3))
+        } else if let Some(bare) = tail.strip_prefix('$') {
+            let len = bare.find(|c| !is_name_char(c)).unwrap_or(bare.len());
+            bare.starts_with(is_name_start)
+                .then(|| (&bare[..len], len + 1))
+        } else {
+            let quoted = &tail[1..];
+            quoted
+                .find('%')
+                .filter(|&end| end > 0)
+                .map(|end| (&quoted[..end], end + 2))
+        };
+
+        match reference.and_then(|(name, len)| Some((lookup(name)?, len))) {
+            Some((value, len)) => {
+                out.push_str(&value);
+                rest = &tail[len..];
+            }
+            None => {
+                out.push_str(&tail[..1]);
+                rest = &tail[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Environment variable overriding the config directory
+pub const CONFIG_DIR_ENV: &str = "FRESH_CONFIG_DIR";
+
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV: &str = "FRESH_DATA_DIR";
+
+/// Name of the directory next to the executable that turns on portable mode
+pub const PORTABLE_DIR_NAME: &str = "portable";
+
+/// The portable directory next to the running executable, if there is one
+///
+/// When it exists, config, data, cache and logs all live inside it so the
+/// editor can be carried around (e.g. on a USB stick) without touching the
+/// user's profile.
+pub fn portable_root() -> Option<PathBuf> {
+    let exe = std::env::current_exe().ok()?;
+    let exe = exe.canonicalize().unwrap_or(exe);
+    let dir = exe.parent()?.join(PORTABLE_DIR_NAME);
+    dir.is_dir().then_some(dir)
+}
+
+/// Where a directory of a [`DirectoryContext`] was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirectorySource {
+    /// The platform's standard location
+    System,
+    /// An environment variable (`FRESH_CONFIG_DIR` / `FRESH_DATA_DIR`)
+    Environment,
+    /// The portable directory next to the executable
+    Portable,
+}
+
+impl DirectorySource {
+    /// Short description for diagnostics
+    pub fn describe(&self) -> &'static str {
+        match self {
+            Self::System => "system default",
+            Self::Environment => "environment override",
+            Self::Portable => "portable mode",
+        }
+    }
+}
+
+/// Platform directories for Fresh, before overrides are applied
+///
+/// All paths already include the `fresh` component.
+#[derive(Debug, Clone, Default)]
+struct SystemDirs {
+    config_dir: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    cache_dir: Option<PathBuf>,
+    home_dir: Option<PathBuf>,
+    documents_dir: Option<PathBuf>,
+    downloads_dir: Option<PathBuf>,
+}
+
+impl SystemDirs {
+    /// Look up the platform directories: XDG on Linux, Known Folders on
+    /// Windows and Application Support on macOS
+    fn detect() -> Self {
+        let home_dir = dirs::home_dir();
+
+        #[allow(unused_mut)] // mut needed on macOS only
+        let mut config_dir = dirs::config_dir().map(|d| d.join("fresh"));
+
+        // macOS: keep using ~/.config/fresh (or $XDG_CONFIG_HOME/fresh) when it
+        // already exists, as earlier versions put the config there
+        #[cfg(target_os = "macos")]
+        {
+            let xdg_config = std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .filter(|p| p.is_absolute())
+                .or_else(|| home_dir.as_ref().map(|h| h.join(".config")));
+            if let Some(legacy) = xdg_config.map(|d| d.join("fresh")).filter(|d| d.is_dir()) {
+                config_dir = Some(legacy);
+            }
+        }
+
+        Self {
+            config_dir,
+            data_dir: dirs::data_dir().map(|d| d.join("fresh")),
+            cache_dir: dirs::cache_dir().map(|d| d.join("fresh")),
+            home_dir,
+            documents_dir: dirs::document_dir(),
+            downloads_dir: dirs::download_dir(),
+        }
+    }
+}
+
 /// Directory paths for editor state and configuration
 ///
 /// This struct holds all directory paths that the editor needs.
-/// Only the top-level `main` function should use `dirs::*` to construct this;
+/// Only [`DirectoryContext::from_system`] consults the platform directories;
 /// all other code should receive it by construction/parameter passing.
 ///
 /// This design ensures:
@@ -794,6 +1093,16 @@ pub struct DirectoryContext {
     /// e.g., ~/.config/fresh on Linux, ~/Library/Application Support/fresh on macOS
     pub config_dir: std::path::PathBuf,
 
+    /// Cache directory for files that can be recreated (extracted plugins)
+    /// e.g., ~/.cache/fresh on Linux, ~/Library/Caches/fresh on macOS
+    pub cache_dir: std::path::PathBuf,
+
+    /// Where `config_dir` was taken from
+    pub config_source: DirectorySource,
+
+    /// Where `data_dir` was taken from
+    pub data_source: DirectorySource,
+
     /// User's home directory (for file open dialog shortcuts)
     pub home_dir: Option<std::path::PathBuf>,
 
@@ -806,39 +1115,76 @@ pub struct DirectoryContext {
 
 impl DirectoryContext {
     /// Create a DirectoryContext from the system directories
-    /// This should ONLY be called from main()
+    ///
+    /// `FRESH_CONFIG_DIR` and `FRESH_DATA_DIR` take precedence, then the
+    /// portable directory next to the executable, then the platform defaults.
     pub fn from_system() -> std::io::Result<Self> {
-        let data_dir = dirs::data_dir()
-            .ok_or_else(|| {
-                std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Could not determine data directory",
-                )
-            })?
-            .join("fresh");
+        Self::resolve(SystemDirs::detect(), portable_root(), |name| {
+            std::env::var_os(name)
+        })
+    }
 
-        #[allow(unused_mut)] // mut needed on macOS only
-        let mut config_dir = dirs::config_dir()
-            .ok_or_else(|| {
-                std::io::Error::new(
-                    std::io::ErrorKind::NotFound,
-                    "Could not determine config directory",
-                )
-            })?
-            .join("fresh");
-
-        // macOS: Prioritize ~/.config/fresh
-        #[cfg(target_os = "macos")]
-        if let Some(home) = dirs::home_dir() {
-            config_dir = home.join(".config").join("fresh");
-        }
+    /// Resolve the directories from the platform defaults, the portable
+    /// directory (if any) and environment overrides read through `env`
+    fn resolve(
+        system: SystemDirs,
+        portable_root: Option<PathBuf>,
+        env: impl Fn(&str) -> Option<std::ffi::OsString>,
+    ) -> std::io::Result<Self> {
+        let override_dir = |name: &str| {
+            let value = PathBuf::from(env(name)?);
+            if value.is_absolute() {
+                Some(value)
+            } else {
+                tracing::warn!(
+                    "Ignoring {}={}: the path must be absolute",
+                    name,
+                    value.display()
+                );
+                None
+            }
+        };
+        let missing = |what: &str| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Could not determine {} directory", what),
+            )
+        };
+        let pick = |name: &str, portable: Option<PathBuf>, system: Option<PathBuf>, what: &str| {
+            if let Some(dir) = override_dir(name) {
+                Ok((dir, DirectorySource::Environment))
+            } else if let Some(dir) = portable {
+                Ok((dir, DirectorySource::Portable))
+            } else {
+                system
+                    .map(|dir| (dir, DirectorySource::System))
+                    .ok_or_else(|| missing(what))
+            }
+        };
+
+        let portable = |sub: &str| portable_root.as_ref().map(|root| root.join(sub));
+        let (config_dir, config_source) = pick(
+            CONFIG_DIR_ENV,
+            portable("config"),
+            system.config_dir,
+            "config",
+        )?;
+        let (data_dir, data_source) =
+            pick(DATA_DIR_ENV, portable("data"), system.data_dir, "data")?;
+        // Without a platform cache directory, cached files go with the data
+        let cache_dir = portable("cache")
+            .or(system.cache_dir)
+            .unwrap_or_else(|| data_dir.join("cache"));
 
         Ok(Self {
             data_dir,
             config_dir,
-            home_dir: dirs::home_dir(),
-            documents_dir: dirs::document_dir(),
-            downloads_dir: dirs::download_dir(),
+            cache_dir,
+            config_source,
+            data_source,
+            home_dir: system.home_dir,
+            documents_dir: system.documents_dir,
+            downloads_dir: system.downloads_dir,
         })
     }
 
@@ -848,6 +1194,9 @@ impl DirectoryContext {
         Self {
             data_dir: temp_dir.join("data"),
             config_dir: temp_dir.join("config"),
+            cache_dir: temp_dir.join("cache"),
+            config_source: DirectorySource::System,
+            data_source: DirectorySource::System,
             home_dir: Some(temp_dir.join("home")),
             documents_dir: Some(temp_dir.join("documents")),
             downloads_dir: Some(temp_dir.join("downloads")),
@@ -859,11 +1208,26 @@ impl DirectoryContext {
         self.data_dir.join("recovery")
     }
 
+    /// Get the crash reports directory path
+    pub fn crash_reports_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("crash-reports")
+    }
+
+    /// Get the directory holding RPC sockets and their session tokens
+    pub fn rpc_dir(&self) -> std::path::PathBuf {
+        self.data_dir.join("rpc")
+    }
+
     /// Get the sessions directory path
     pub fn sessions_dir(&self) -> std::path::PathBuf {
         self.data_dir.join("sessions")
     }
 
+    /// Get the recent workspaces list path
+    pub fn recent_workspaces_path(&self) -> std::path::PathBuf {
+        self.data_dir.join("recent_workspaces.json")
+    }
+
     /// Get the history file path for a specific prompt type
     /// This is the generic method used by prompt_histories HashMap.
     /// history_name can be: "search", "replace", "goto_line", "plugin:custom_name", etc.
@@ -904,6 +1268,11 @@ impl DirectoryContext {
         self.config_dir.join(Config::FILENAME)
     }
 
+    /// Get the directory holding named config profiles
+    pub fn profiles_dir(&self) -> std::path::PathBuf {
+        self.config_dir.join("profiles")
+    }
+
     /// Get the themes directory path
     pub fn themes_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("themes")
@@ -918,6 +1287,16 @@ impl DirectoryContext {
     pub fn plugins_dir(&self) -> std::path::PathBuf {
         self.config_dir.join("plugins")
     }
+
+    /// Get the key calibration file path
+    pub fn key_calibration_path(&self) -> std::path::PathBuf {
+        self.config_dir.join("key_calibration.json")
+    }
+
+    /// Get the directory embedded plugins are extracted to
+    pub fn embedded_plugins_dir(&self) -> std::path::PathBuf {
+        self.cache_dir.join("embedded-plugins")
+    }
 }
 
 #[cfg(test)]
@@ -2019,4 +2398,201 @@ mod tests {
             );
         }
     }
+
+    fn expand_test(input: &str, percent_vars: bool) -> String {
+        let lookup = |name: &str| match name {
+            "PROJ" => Some("/work/proj".to_string()),
+            "USERPROFILE" => Some(r"C:\Users\me".to_string()),
+            "FILE" => Some("src/main.rs".to_string()),
+            _ => None,
+        };
+        expand_vars(input, Some(Path::new("/home/me")), &lookup, percent_vars)
+    }
+
+    #[test]
+    fn expand_path_tilde() {
+        assert_eq!(expand_test("~", false), "/home/me");
+        assert_eq!(expand_test("~/notes.md", false), "/home/me/notes.md");
+        // Only a leading tilde followed by a separator is the home directory
+        assert_eq!(expand_test("~user/x", false), "~user/x");
+        assert_eq!(expand_test("a/~/b", false), "a/~/b");
+    }
+
+    #[test]
+    fn expand_path_dollar_vars() {
+        assert_eq!(expand_test("$PROJ/src", false), "/work/proj/src");
+        assert_eq!(expand_test("${PROJ}src", false), "/work/projsrc");
+        assert_eq!(expand_test("~/$FILE", false), "/home/me/src/main.rs");
+        // Unknown or malformed references are kept as written
+        assert_eq!(expand_test("$NOPE/x", false), "$NOPE/x");
+        assert_eq!(expand_test("${NOPE}/x", false), "${NOPE}/x");
+        assert_eq!(expand_test("${PROJ", false), "${PROJ");
+        assert_eq!(expand_test("cost$5", false), "cost$5");
+        assert_eq!(expand_test("end$", false), "end$");
+    }
+
+    #[test]
+    fn expand_path_windows_percent_vars() {
+        assert_eq!(
+            expand_test(r"%USERPROFILE%\Documents", true),
+            r"C:\Users\me\Documents"
+        );
+        assert_eq!(expand_test("%PROJ%%FILE%", true), "/work/projsrc/main.rs");
+        assert_eq!(expand_test("100%", true), "100%");
+        assert_eq!(expand_test("%%", true), "%%");
+        assert_eq!(expand_test("%NOPE%/x", true), "%NOPE%/x");
+        // `%VAR%` is only expanded where it is the platform syntax
+        assert_eq!(expand_test("%PROJ%/x", false), "%PROJ%/x");
+    }
+
+    #[test]
+    fn expand_path_with_overrides_environment() {
+        let path = expand_path_with("${FILE}.bak", |name| {
+            (name == "FILE").then(|| "a.txt".to_string())
+        });
+        assert_eq!(path, PathBuf::from("a.txt.bak"));
+    }
+
+    #[test]
+    fn parse_config_setting_reads_json_or_string() {
+        let (key, value) = parse_config_setting("editor.tab_size=2").unwrap();
+        assert_eq!(key, "editor.tab_size");
+        assert_eq!(value, serde_json::json!(2));
+        let (_, value) = parse_config_setting("theme=high-contrast").unwrap();
+        assert_eq!(value, serde_json::json!("high-contrast"));
+        assert!(parse_config_setting("editor.tab_size").is_err());
+        assert!(parse_config_setting("editor..tab_size=2").is_err());
+    }
+
+    #[test]
+    fn config_overrides_layer_over_profile_and_base() {
+        let (temp, resolver) = create_test_resolver();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        std::fs::create_dir_all(dir_context.profiles_dir()).unwrap();
+        std::fs::write(
+            resolver.profile_path("writing"),
+            r#"{"editor": {"line_wrap": true, "tab_size": 8}}"#,
+        )
+        .unwrap();
+
+        let settings = vec![
+            parse_config_setting("editor.tabSize=3").unwrap(),
+            parse_config_setting("editor.relative_line_numbers=true").unwrap(),
+        ];
+        let overrides = ConfigOverrides::load(&dir_context, Some("writing"), &settings).unwrap();
+        assert_eq!(overrides.profile.as_deref(), Some("writing"));
+
+        let mut base = Config::default();
+        base.editor.scroll_offset = 7;
+        let config = overrides.apply(base);
+        assert_eq!(config.editor.tab_size, 3);
+        assert!(config.editor.line_wrap);
+        assert!(config.editor.relative_line_numbers);
+        assert_eq!(config.editor.scroll_offset, 7);
+    }
+
+    #[test]
+    fn config_overrides_reject_unknown_keys_and_profiles() {
+        let (temp, _resolver) = create_test_resolver();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+
+        let settings = vec![parse_config_setting("editor.no_such_option=1").unwrap()];
+        assert!(ConfigOverrides::load(&dir_context, None, &settings).is_err());
+        assert!(ConfigOverrides::load(&dir_context, Some("missing"), &[]).is_err());
+        assert!(ConfigOverrides::load(&dir_context, Some("../config"), &[]).is_err());
+
+        let overrides = ConfigOverrides::load(&dir_context, None, &[]).unwrap();
+        assert_eq!(
+            overrides.apply(Config::default()).editor.tab_size,
+            Config::default().editor.tab_size
+        );
+    }
+
+    fn system_dirs(root: &Path) -> SystemDirs {
+        SystemDirs {
+            config_dir: Some(root.join("xdg-config/fresh")),
+            data_dir: Some(root.join("xdg-data/fresh")),
+            cache_dir: Some(root.join("xdg-cache/fresh")),
+            home_dir: Some(root.join("home")),
+            documents_dir: None,
+            downloads_dir: None,
+        }
+    }
+
+    #[test]
+    fn directory_context_uses_system_dirs_by_default() {
+        let root = Path::new("/sys-root");
+        let dirs = DirectoryContext::resolve(system_dirs(root), None, |_| None).unwrap();
+        assert_eq!(dirs.config_dir, root.join("xdg-config/fresh"));
+        assert_eq!(dirs.data_dir, root.join("xdg-data/fresh"));
+        assert_eq!(dirs.cache_dir, root.join("xdg-cache/fresh"));
+        assert_eq!(dirs.config_source, DirectorySource::System);
+        assert_eq!(dirs.data_source, DirectorySource::System);
+        assert_eq!(dirs.home_dir, Some(root.join("home")));
+    }
+
+    #[test]
+    fn directory_context_portable_mode_keeps_everything_together() {
+        let root = Path::new("/sys-root");
+        let portable = PathBuf::from("/usb/fresh/portable");
+        let dirs =
+            DirectoryContext::resolve(system_dirs(root), Some(portable.clone()), |_| None).unwrap();
+        assert_eq!(dirs.config_dir, portable.join("config"));
+        assert_eq!(dirs.data_dir, portable.join("data"));
+        assert_eq!(dirs.cache_dir, portable.join("cache"));
+        assert_eq!(dirs.config_source, DirectorySource::Portable);
+        assert_eq!(dirs.data_source, DirectorySource::Portable);
+    }
+
+    #[test]
+    fn directory_context_env_overrides_win() {
+        let root = Path::new("/sys-root");
+        let env = |name: &str| match name {
+            CONFIG_DIR_ENV => Some("/custom/config".into()),
+            DATA_DIR_ENV => Some("relative/data".into()),
+            _ => None,
+        };
+        let portable = PathBuf::from("/usb/fresh/portable");
+        let dirs =
+            DirectoryContext::resolve(system_dirs(root), Some(portable.clone()), env).unwrap();
+        assert_eq!(dirs.config_dir, PathBuf::from("/custom/config"));
+        assert_eq!(dirs.config_source, DirectorySource::Environment);
+        // Relative overrides are ignored
+        assert_eq!(dirs.data_dir, portable.join("data"));
+        assert_eq!(dirs.data_source, DirectorySource::Portable);
+    }
+
+    #[test]
+    fn directory_context_without_system_dirs() {
+        let env = |name: &str| (name == DATA_DIR_ENV).then(|| "/custom/data".into());
+        assert!(DirectoryContext::resolve(SystemDirs::default(), None, env).is_err());
+
+        let env = |name: &str| {
+            Some(match name {
+                CONFIG_DIR_ENV => "/custom/config".into(),
+                _ => "/custom/data".into(),
+            })
+        };
+        let dirs = DirectoryContext::resolve(SystemDirs::default(), None, env).unwrap();
+        assert_eq!(dirs.cache_dir, PathBuf::from("/custom/data/cache"));
+    }
+
+    #[test]
+    fn config_search_paths_use_directory_context() {
+        let (temp, _resolver) = create_test_resolver();
+        let dir_context = DirectoryContext::for_testing(temp.path());
+        let working_dir = temp.path().join("project");
+        assert_eq!(Config::find_config_path(&working_dir, &dir_context), None);
+
+        std::fs::create_dir_all(&dir_context.config_dir).unwrap();
+        std::fs::write(dir_context.config_path(), r#"{"editor": {"tab_size": 3}}"#).unwrap();
+        assert_eq!(
+            Config::find_config_path(&working_dir, &dir_context),
+            Some(dir_context.config_path())
+        );
+        assert_eq!(
+            Config::read_user_config_raw(&working_dir, &dir_context)["editor"]["tab_size"],
+            3
+        );
+    }
 }
@@ -1,7 +1,8 @@
 //! Internationalization (i18n) support for Fresh Editor
 //!
 //! This module provides locale detection and translation support using rust-i18n.
-//! Translations are embedded at compile time from JSON files in the `locales/` directory.
+//! The JSON files in the `locales/` directory are embedded at compile time and
+//! parsed the first time a string is translated.
 //!
 //! # Usage
 //!
@@ -16,9 +17,12 @@
 //! ```
 
 use once_cell::sync::Lazy;
+use rust_i18n::SimpleBackend;
 use std::collections::HashMap;
 use std::sync::RwLock;
 
+include!(concat!(env!("OUT_DIR"), "/locale_files.rs"));
+
 /// Type alias for the nested plugin strings map.
 /// Structure: plugin_name -> locale -> key -> translated_string
 type PluginStringsMap = HashMap<String, HashMap<String, HashMap<String, String>>>;
@@ -188,6 +192,25 @@ pub fn available_locales() -> Vec<&'static str> {
     rust_i18n::available_locales!()
 }
 
+/// Build the translation backend from the embedded locale files.
+///
+/// Used by `rust_i18n::i18n!` in place of expanding every string into its own
+/// initializer: with all locales that initializer no longer fits the 2 MiB
+/// stack of a spawned thread in debug builds.
+pub fn embedded_backend() -> SimpleBackend {
+    let mut backend = SimpleBackend::new();
+    for (locale, json) in LOCALE_FILES {
+        let strings: HashMap<&str, serde_json::Value> = serde_json::from_str(json)
+            .unwrap_or_else(|e| panic!("locales/{}.json is not valid JSON: {}", locale, e));
+        let strings: HashMap<&str, &str> = strings
+            .iter()
+            .filter_map(|(key, value)| Some((*key, value.as_str()?)))
+            .collect();
+        backend.add_translations(locale, &strings);
+    }
+    backend
+}
+
 /// Get the display name for a locale code.
 ///
 /// Returns a tuple of (English name, Native name) for display in UI.
@@ -212,6 +235,32 @@ pub fn locale_display_name(locale: &str) -> Option<(&'static str, &'static str)>
     }
 }
 
+/// Format a number with a fixed number of decimals using the current locale's
+/// decimal separator, e.g. `1.5` in English and `1,5` in German.
+pub fn format_decimal(value: f64, decimals: usize) -> String {
+    format_decimal_for_locale(value, decimals, &current_locale())
+}
+
+fn format_decimal_for_locale(value: f64, decimals: usize, locale: &str) -> String {
+    let formatted = format!("{:.*}", decimals, value);
+    let separator = rust_i18n::t!("format.decimal_separator", locale = locale);
+    if separator == "." {
+        formatted
+    } else {
+        formatted.replacen('.', &separator, 1)
+    }
+}
+
+/// Get the strftime pattern used to display dates in the current locale.
+pub fn date_format() -> String {
+    rust_i18n::t!("format.date").to_string()
+}
+
+/// Get the strftime pattern used to display times of day in the current locale.
+pub fn time_format() -> String {
+    rust_i18n::t!("format.time").to_string()
+}
+
 /// Get the translated message for "switched to project".
 ///
 /// This is a helper function for use by the binary crate (main.rs) since
@@ -253,6 +302,13 @@ mod tests {
         assert_eq!(msg, "Locale changed to es");
     }
 
+    #[test]
+    fn test_format_decimal_uses_locale_separator() {
+        assert_eq!(format_decimal_for_locale(1.5, 1, "de"), "1,5");
+        assert_eq!(format_decimal_for_locale(1.5, 1, "en"), "1.5");
+        assert_eq!(format_decimal_for_locale(1024.0, 0, "fr"), "1024");
+    }
+
     #[test]
     fn test_available_locales_includes_en() {
         let locales = available_locales();
@@ -4,10 +4,12 @@ use crate::input::keybindings::Action;
 use crate::model::buffer::{Buffer, LineEnding};
 use crate::model::cursor::{Position2D, SelectionMode};
 use crate::model::event::{CursorId, Event};
+use crate::primitives::ansi::escape_sequence_ranges;
 use crate::primitives::display_width::{byte_offset_at_visual_column, str_width};
+use crate::primitives::indent_block::find_indent_block;
 use crate::primitives::word_navigation::{
-    find_word_end, find_word_end_right, find_word_start, find_word_start_left,
-    find_word_start_right,
+    find_subword_end_right, find_subword_start_left, find_subword_start_right, find_word_end,
+    find_word_end_right, find_word_start, find_word_start_left, find_word_start_right,
 };
 use crate::state::EditorState;
 use std::ops::Range;
@@ -107,6 +109,74 @@ fn next_position_for_crlf(buffer: &Buffer, pos: usize, max_pos: usize) -> usize
     buffer.next_grapheme_boundary(pos).min(max_pos)
 }
 
+/// Byte ranges of the ANSI escape sequences on the line containing `pos`
+fn ansi_escapes_on_line(
+    buffer: &mut Buffer,
+    pos: usize,
+    estimated_line_length: usize,
+) -> Vec<Range<usize>> {
+    let mut iter = buffer.line_iterator(pos, estimated_line_length);
+    let line_start = iter.current_position();
+    match iter.next_line() {
+        Some((_, line_content)) => escape_sequence_ranges(&line_content)
+            .into_iter()
+            .map(|range| line_start + range.start..line_start + range.end)
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Calculate position when moving left. With `skip_ansi` (escape sequences
+/// render as colors), sequences are skipped as the zero-width text they are
+/// on screen, so the cursor always passes one visible character.
+fn prev_position(
+    buffer: &mut Buffer,
+    pos: usize,
+    skip_ansi: bool,
+    estimated_line_length: usize,
+) -> usize {
+    let mut new_pos = adjust_position_for_crlf_left(buffer, buffer.prev_grapheme_boundary(pos));
+    if !skip_ansi {
+        return new_pos;
+    }
+    loop {
+        let escapes = ansi_escapes_on_line(buffer, new_pos, estimated_line_length);
+        let Some(escape) = escapes.iter().find(|range| range.contains(&new_pos)) else {
+            return new_pos;
+        };
+        if escape.start == 0 {
+            return 0;
+        }
+        new_pos =
+            adjust_position_for_crlf_left(buffer, buffer.prev_grapheme_boundary(escape.start));
+    }
+}
+
+/// Calculate position when moving right, skipping escape sequences on both
+/// sides of the next visible character with `skip_ansi` (see [`prev_position`])
+fn next_position(
+    buffer: &mut Buffer,
+    pos: usize,
+    max_pos: usize,
+    skip_ansi: bool,
+    estimated_line_length: usize,
+) -> usize {
+    if !skip_ansi {
+        return next_position_for_crlf(buffer, pos, max_pos);
+    }
+    let skip_escapes = |buffer: &mut Buffer, mut pos: usize| {
+        for escape in ansi_escapes_on_line(buffer, pos, estimated_line_length) {
+            if escape.contains(&pos) {
+                pos = escape.end;
+            }
+        }
+        pos
+    };
+    let pos = skip_escapes(buffer, pos);
+    let new_pos = next_position_for_crlf(buffer, pos, max_pos);
+    skip_escapes(buffer, new_pos).min(max_pos)
+}
+
 /// Convert deletion ranges to Delete events
 ///
 /// This is a common pattern used across many deletion actions.
@@ -744,6 +814,54 @@ fn insert_char_events(
     }
 }
 
+/// Build the events for typing `ch` in overwrite mode.
+///
+/// The character under each cursor is replaced, except at the end of a line
+/// or of the buffer where `ch` is inserted. A selection is replaced exactly
+/// as in insert mode.
+pub fn overwrite_char_events(state: &mut EditorState, ch: char) -> Vec<Event> {
+    // Sort cursors by position (reverse order) to avoid position shifts
+    let mut cursor_info: Vec<_> = state
+        .cursors
+        .iter()
+        .map(|(cursor_id, cursor)| (cursor_id, cursor.selection_range(), cursor.position))
+        .collect();
+    cursor_info.sort_by_key(|(_, selection, position)| {
+        std::cmp::Reverse(selection.as_ref().map(|r| r.start).unwrap_or(*position))
+    });
+
+    let buffer_len = state.buffer.len();
+    let mut events = Vec::new();
+    for (cursor_id, selection, position) in cursor_info {
+        let replaced = selection.or_else(|| {
+            if position >= buffer_len {
+                return None;
+            }
+            let next = next_position_for_crlf(&state.buffer, position, buffer_len);
+            match state.buffer.slice_bytes(position..position + 1).first() {
+                Some(b'\n') | Some(b'\r') | None => None,
+                Some(_) => Some(position..next),
+            }
+        });
+
+        let insert_position = replaced.as_ref().map(|r| r.start).unwrap_or(position);
+        if let Some(range) = replaced {
+            let deleted_text = state.get_text_range(range.start, range.end);
+            events.push(Event::Delete {
+                range,
+                deleted_text,
+                cursor_id,
+            });
+        }
+        events.push(Event::Insert {
+            position: insert_position,
+            text: ch.to_string(),
+            cursor_id,
+        });
+    }
+    events
+}
+
 /// Calculate the maximum valid cursor position in the buffer.
 /// This is the end of the last line (excluding trailing newline).
 /// For empty buffers, returns 0.
@@ -1240,9 +1358,14 @@ pub fn action_to_events(
         // Basic movement - move each cursor
         // Uses grapheme cluster boundaries for proper handling of combining characters
         Action::MoveLeft => {
+            let skip_ansi = state.renders_ansi_colors();
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = state.buffer.prev_grapheme_boundary(cursor.position);
-                let new_pos = adjust_position_for_crlf_left(&state.buffer, new_pos);
+                let new_pos = prev_position(
+                    &mut state.buffer,
+                    cursor.position,
+                    skip_ansi,
+                    estimated_line_length,
+                );
 
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
@@ -1263,9 +1386,16 @@ pub fn action_to_events(
         }
 
         Action::MoveRight => {
+            let skip_ansi = state.renders_ansi_colors();
             for (cursor_id, cursor) in state.cursors.iter() {
                 let max_pos = max_cursor_position(&state.buffer);
-                let new_pos = next_position_for_crlf(&state.buffer, cursor.position, max_pos);
+                let new_pos = next_position(
+                    &mut state.buffer,
+                    cursor.position,
+                    max_pos,
+                    skip_ansi,
+                    estimated_line_length,
+                );
 
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
@@ -1436,9 +1566,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::MoveWordLeft => {
+        Action::MoveWordLeft | Action::MoveSubwordLeft => {
+            let find = if matches!(action, Action::MoveSubwordLeft) {
+                find_subword_start_left
+            } else {
+                find_word_start_left
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_start_left(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1457,9 +1592,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::MoveWordRight => {
+        Action::MoveWordRight | Action::MoveSubwordRight => {
+            let find = if matches!(action, Action::MoveSubwordRight) {
+                find_subword_start_right
+            } else {
+                find_word_start_right
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_start_right(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1478,9 +1618,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::MoveWordEnd => {
+        Action::MoveWordEnd | Action::MoveSubwordEnd => {
+            let find = if matches!(action, Action::MoveSubwordEnd) {
+                find_subword_end_right
+            } else {
+                find_word_end_right
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_end_right(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 // Preserve anchor if deselect_on_move is false (Emacs mark mode)
                 let new_anchor = if cursor.deselect_on_move {
                     None
@@ -1639,9 +1784,14 @@ pub fn action_to_events(
         // Selection movement - same as regular movement but keeps anchor
         // Uses grapheme cluster boundaries for proper handling of combining characters
         Action::SelectLeft => {
+            let skip_ansi = state.renders_ansi_colors();
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = state.buffer.prev_grapheme_boundary(cursor.position);
-                let new_pos = adjust_position_for_crlf_left(&state.buffer, new_pos);
+                let new_pos = prev_position(
+                    &mut state.buffer,
+                    cursor.position,
+                    skip_ansi,
+                    estimated_line_length,
+                );
 
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
@@ -1657,9 +1807,16 @@ pub fn action_to_events(
         }
 
         Action::SelectRight => {
+            let skip_ansi = state.renders_ansi_colors();
             for (cursor_id, cursor) in state.cursors.iter() {
                 let max_pos = max_cursor_position(&state.buffer);
-                let new_pos = next_position_for_crlf(&state.buffer, cursor.position, max_pos);
+                let new_pos = next_position(
+                    &mut state.buffer,
+                    cursor.position,
+                    max_pos,
+                    skip_ansi,
+                    estimated_line_length,
+                );
 
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
@@ -1859,9 +2016,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::SelectWordLeft => {
+        Action::SelectWordLeft | Action::SelectSubwordLeft => {
+            let find = if matches!(action, Action::SelectSubwordLeft) {
+                find_subword_start_left
+            } else {
+                find_word_start_left
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_start_left(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -1875,9 +2037,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::SelectWordRight => {
+        Action::SelectWordRight | Action::SelectSubwordRight => {
+            let find = if matches!(action, Action::SelectSubwordRight) {
+                find_subword_start_right
+            } else {
+                find_word_start_right
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_start_right(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -1891,9 +2058,14 @@ pub fn action_to_events(
             }
         }
 
-        Action::SelectWordEnd => {
+        Action::SelectWordEnd | Action::SelectSubwordEnd => {
+            let find = if matches!(action, Action::SelectSubwordEnd) {
+                find_subword_end_right
+            } else {
+                find_word_end_right
+            };
             for (cursor_id, cursor) in state.cursors.iter() {
-                let new_pos = find_word_end_right(&state.buffer, cursor.position);
+                let new_pos = find(&state.buffer, cursor.position);
                 let anchor = cursor.anchor.unwrap_or(cursor.position);
                 events.push(Event::MoveCursor {
                     cursor_id,
@@ -2154,7 +2326,12 @@ pub fn action_to_events(
             apply_deletions(state, deletions, &mut events);
         }
 
-        Action::DeleteWordBackward => {
+        Action::DeleteWordBackward | Action::DeleteSubwordBackward => {
+            let find = if matches!(action, Action::DeleteSubwordBackward) {
+                find_subword_start_left
+            } else {
+                find_word_start_left
+            };
             // Collect ranges first to avoid borrow checker issues
             let deletions: Vec<_> = state
                 .cursors
@@ -2163,7 +2340,7 @@ pub fn action_to_events(
                     if let Some(range) = cursor.selection_range() {
                         Some((cursor_id, range))
                     } else {
-                        let word_start = find_word_start_left(&state.buffer, cursor.position);
+                        let word_start = find(&state.buffer, cursor.position);
                         if word_start < cursor.position {
                             Some((cursor_id, word_start..cursor.position))
                         } else {
@@ -2177,7 +2354,12 @@ pub fn action_to_events(
             apply_deletions(state, deletions, &mut events);
         }
 
-        Action::DeleteWordForward => {
+        Action::DeleteWordForward | Action::DeleteSubwordForward => {
+            let find = if matches!(action, Action::DeleteSubwordForward) {
+                find_subword_start_right
+            } else {
+                find_word_start_right
+            };
             // Collect ranges first to avoid borrow checker issues
             let deletions: Vec<_> = state
                 .cursors
@@ -2186,7 +2368,7 @@ pub fn action_to_events(
                     if let Some(range) = cursor.selection_range() {
                         Some((cursor_id, range))
                     } else {
-                        let word_end = find_word_start_right(&state.buffer, cursor.position);
+                        let word_end = find(&state.buffer, cursor.position);
                         if cursor.position < word_end {
                             Some((cursor_id, cursor.position..word_end))
                         } else {
@@ -2425,6 +2607,9 @@ pub fn action_to_events(
         | Action::SaveAs
         | Action::Open
         | Action::SwitchProject
+        | Action::OpenRecentWorkspace
+        | Action::OpenRecentWorkspaceInNewProcess
+        | Action::TogglePinWorkspace
         | Action::New
         | Action::Close
         | Action::CloseTab
@@ -2433,6 +2618,9 @@ pub fn action_to_events(
         | Action::PrevBuffer
         | Action::SwitchToPreviousTab
         | Action::SwitchToTabByName
+        | Action::NextTabMru
+        | Action::PrevTabMru
+        | Action::MoveTabToSplit
         | Action::NavigateBack
         | Action::NavigateForward
         | Action::SplitHorizontal
@@ -2442,8 +2630,21 @@ pub fn action_to_events(
         | Action::PrevSplit
         | Action::Copy
         | Action::CopyWithTheme(_)
+        | Action::CopyAsMarkdown
+        | Action::CopyWithReference
+        | Action::CopyPermalink
+        | Action::CopyRelativePath
+        | Action::CopyAbsolutePath
+        | Action::CopyFileName
         | Action::Cut
         | Action::Paste
+        | Action::PasteFromClipboardHistory
+        | Action::ClipboardHistoryTogglePin
+        | Action::ForgetClipboardHistory
+        | Action::InsertDate
+        | Action::InsertTime
+        | Action::InsertUnicodeCharacter
+        | Action::InsertDigraph
         | Action::YankWordForward
         | Action::YankWordBackward
         | Action::YankToLineEnd
@@ -2460,18 +2661,34 @@ pub fn action_to_events(
         | Action::IncreaseSplitSize
         | Action::DecreaseSplitSize
         | Action::ToggleMaximizeSplit
+        | Action::ToggleZoomSplit
+        | Action::ToggleScrollSync
+        | Action::SyncScrollAllSplits
+        | Action::StopScrollSync
         | Action::Undo
         | Action::Redo
+        | Action::ShowUndoTree
+        | Action::UndoTreeSwitchBranch
         | Action::GoToMatchingBracket
         | Action::JumpToNextError
         | Action::JumpToPreviousError
         | Action::ShowKeyboardShortcuts
+        | Action::ToggleCheatSheet
         | Action::ShowWarnings
         | Action::ShowStatusLog
         | Action::ShowLspStatus
+        | Action::ShowMemoryUsage
+        | Action::ShowPaths
+        | Action::ShowRemoteAuditLog
+        | Action::ExportRemoteAuditLog
         | Action::ClearWarnings
         | Action::SmartHome
         | Action::ToggleComment
+        | Action::ReflowParagraph
+        | Action::ReflowSelection
+        | Action::EvaluateSelection
+        | Action::EvaluateAndReplaceSelection
+        | Action::GenerateDocComment
         | Action::SetBookmark(_)
         | Action::JumpToBookmark(_)
         | Action::ClearBookmark(_)
@@ -2527,9 +2744,14 @@ pub fn action_to_events(
         | Action::PopupPageDown
         | Action::PopupConfirm
         | Action::PopupCancel
+        | Action::PinPopup
+        | Action::FocusFloatingWindow(_)
+        | Action::CloseFloatingWindow
         | Action::ToggleFileExplorer
         | Action::ToggleMenuBar
         | Action::ToggleTabBar
+        | Action::ToggleAbsolutePaths
+        | Action::ToggleOverwriteMode
         | Action::FocusFileExplorer
         | Action::FocusEditor
         | Action::SetBackground
@@ -2557,11 +2779,14 @@ pub fn action_to_events(
         | Action::LspCodeActions
         | Action::LspRestart
         | Action::LspStop
+        | Action::LspDetectServers
         | Action::ToggleInlayHints
         | Action::ToggleMouseHover
         | Action::ToggleLineNumbers
         | Action::ToggleMouseCapture
         | Action::DumpConfig
+        | Action::SafeModeReenable
+        | Action::ReloadPlugin
         | Action::Search
         | Action::FindInSelection
         | Action::FindNext
@@ -2570,6 +2795,9 @@ pub fn action_to_events(
         | Action::FindSelectionPrevious
         | Action::Replace
         | Action::QueryReplace
+        | Action::SearchInProject
+        | Action::ProjectSearchOpenResult
+        | Action::ProjectSearchCancel
         | Action::MenuActivate
         | Action::MenuClose
         | Action::MenuLeft
@@ -2578,17 +2806,23 @@ pub fn action_to_events(
         | Action::MenuDown
         | Action::MenuExecute
         | Action::MenuOpen(_)
+        | Action::ShowContextMenu
         | Action::SwitchKeybindingMap(_)
         | Action::PluginAction(_)
         | Action::None
         | Action::ScrollTabsLeft
         | Action::ScrollTabsRight
         | Action::SelectTheme
+        | Action::SelectSplitTheme
+        | Action::ImportBase16Theme
         | Action::SelectKeybindingMap
         | Action::SelectCursorStyle
         | Action::SelectLocale
         | Action::Revert
         | Action::ToggleAutoRevert
+        | Action::ReopenWithEncoding
+        | Action::ToggleHexView
+        | Action::ToggleAnsiColors
         | Action::FormatBuffer
         | Action::TrimTrailingWhitespace
         | Action::EnsureFinalNewline
@@ -2661,6 +2895,58 @@ pub fn action_to_events(
             }
         }
 
+        Action::SelectIndentBlock => {
+            // Select the whole lines of the indentation block around each cursor
+            for (cursor_id, cursor) in state.cursors.iter() {
+                if let Some(block) = find_indent_block(
+                    &mut state.buffer,
+                    cursor.position,
+                    tab_size,
+                    estimated_line_length,
+                    usize::MAX,
+                ) {
+                    events.push(Event::MoveCursor {
+                        cursor_id,
+                        old_position: cursor.position,
+                        new_position: block.end,
+                        old_anchor: cursor.anchor,
+                        new_anchor: Some(block.start),
+                        old_sticky_column: cursor.sticky_column,
+                        new_sticky_column: 0,
+                    });
+                }
+            }
+        }
+
+        Action::GoToIndentBlockStart | Action::GoToIndentBlockEnd => {
+            // Jump to the first text of the block's first or last line
+            let to_start = matches!(action, Action::GoToIndentBlockStart);
+            for (cursor_id, cursor) in state.cursors.iter() {
+                if let Some(block) = find_indent_block(
+                    &mut state.buffer,
+                    cursor.position,
+                    tab_size,
+                    estimated_line_length,
+                    usize::MAX,
+                ) {
+                    let new_position = if to_start {
+                        block.first_text
+                    } else {
+                        block.last_text
+                    };
+                    events.push(Event::MoveCursor {
+                        cursor_id,
+                        old_position: cursor.position,
+                        new_position,
+                        old_anchor: cursor.anchor,
+                        new_anchor: None,
+                        old_sticky_column: cursor.sticky_column,
+                        new_sticky_column: 0,
+                    });
+                }
+            }
+        }
+
         Action::ExpandSelection => {
             // Expand selection for each cursor
             for (cursor_id, cursor) in state.cursors.iter() {
@@ -2771,6 +3057,40 @@ mod tests {
         assert_eq!(state.cursors.primary().position, 5);
     }
 
+    #[test]
+    fn test_overwrite_char_replaces_until_line_end() {
+        let mut state = EditorState::new(
+            80,
+            24,
+            crate::config::LARGE_FILE_THRESHOLD_BYTES as usize,
+            test_fs(),
+        );
+        state.apply(&Event::Insert {
+            position: 0,
+            text: "ab\ncd".to_string(),
+            cursor_id: CursorId(0),
+        });
+        state.apply(&Event::MoveCursor {
+            cursor_id: CursorId(0),
+            old_position: 5,
+            new_position: 1,
+            old_anchor: None,
+            new_anchor: None,
+            old_sticky_column: 0,
+            new_sticky_column: 0,
+        });
+
+        // Replaces 'b', then reaches the newline and inserts instead
+        for ch in ['X', 'Y', 'Z'] {
+            for event in overwrite_char_events(&mut state, ch) {
+                state.apply(&event);
+            }
+        }
+
+        assert_eq!(state.buffer.to_string().unwrap(), "aXYZ\ncd");
+        assert_eq!(state.cursors.primary().position, 4);
+    }
+
     #[test]
     fn test_move_down_basic() {
         let mut state = EditorState::new(
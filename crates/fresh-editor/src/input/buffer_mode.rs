@@ -106,6 +106,58 @@ impl ModeRegistry {
 
         registry.register(special_mode);
 
+        // Undo Tree viewer: Enter restores the branch under the cursor
+        let undo_tree_mode = BufferMode::new("undo-tree")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+                "undo_tree_switch_branch",
+            )
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "show_undo_tree");
+
+        registry.register(undo_tree_mode);
+
+        // Project search results: Enter opens a match, Escape stops the search
+        let project_search_mode = BufferMode::new("project-search")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Enter,
+                KeyModifiers::NONE,
+                "project_search_open_result",
+            )
+            .with_binding(KeyCode::Esc, KeyModifiers::NONE, "project_search_cancel")
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "search_in_project");
+
+        registry.register(project_search_mode);
+
+        // Memory usage report: 'g' refreshes the sizes
+        let memory_usage_mode = BufferMode::new("memory-usage")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(KeyCode::Char('g'), KeyModifiers::NONE, "show_memory_usage");
+
+        registry.register(memory_usage_mode);
+
+        // Remote audit log: 'g' refreshes, 'e' exports it to a file
+        let remote_audit_mode = BufferMode::new("remote-audit-log")
+            .with_parent("special")
+            .with_read_only(true)
+            .with_binding(
+                KeyCode::Char('g'),
+                KeyModifiers::NONE,
+                "show_remote_audit_log",
+            )
+            .with_binding(
+                KeyCode::Char('e'),
+                KeyModifiers::NONE,
+                "export_remote_audit_log",
+            );
+
+        registry.register(remote_audit_mode);
+
         registry
     }
 
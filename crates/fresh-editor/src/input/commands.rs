@@ -173,6 +173,30 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.open_recent_workspace").to_string(),
+            description: t!("cmd.open_recent_workspace_desc").to_string(),
+            action: Action::OpenRecentWorkspace,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.open_recent_workspace_new_process").to_string(),
+            description: t!("cmd.open_recent_workspace_new_process_desc").to_string(),
+            action: Action::OpenRecentWorkspaceInNewProcess,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_pin_workspace").to_string(),
+            description: t!("cmd.toggle_pin_workspace_desc").to_string(),
+            action: Action::TogglePinWorkspace,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.save_file").to_string(),
             description: t!("cmd.save_file_desc").to_string(),
@@ -229,6 +253,30 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.reopen_with_encoding").to_string(),
+            description: t!("cmd.reopen_with_encoding_desc").to_string(),
+            action: Action::ReopenWithEncoding,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_hex_view").to_string(),
+            description: t!("cmd.toggle_hex_view_desc").to_string(),
+            action: Action::ToggleHexView,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_ansi_colors").to_string(),
+            description: t!("cmd.toggle_ansi_colors_desc").to_string(),
+            action: Action::ToggleAnsiColors,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.format_buffer").to_string(),
             description: t!("cmd.format_buffer_desc").to_string(),
@@ -278,6 +326,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.show_undo_tree").to_string(),
+            description: t!("cmd.show_undo_tree_desc").to_string(),
+            action: Action::ShowUndoTree,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.undo_tree_switch_branch").to_string(),
+            description: t!("cmd.undo_tree_switch_branch_desc").to_string(),
+            action: Action::UndoTreeSwitchBranch,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.copy").to_string(),
             description: t!("cmd.copy_desc").to_string(),
@@ -294,6 +358,54 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.copy_as_markdown").to_string(),
+            description: t!("cmd.copy_as_markdown_desc").to_string(),
+            action: Action::CopyAsMarkdown,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.copy_with_reference").to_string(),
+            description: t!("cmd.copy_with_reference_desc").to_string(),
+            action: Action::CopyWithReference,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.copy_permalink").to_string(),
+            description: t!("cmd.copy_permalink_desc").to_string(),
+            action: Action::CopyPermalink,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.copy_relative_path").to_string(),
+            description: t!("cmd.copy_relative_path_desc").to_string(),
+            action: Action::CopyRelativePath,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.copy_absolute_path").to_string(),
+            description: t!("cmd.copy_absolute_path_desc").to_string(),
+            action: Action::CopyAbsolutePath,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.copy_file_name").to_string(),
+            description: t!("cmd.copy_file_name_desc").to_string(),
+            action: Action::CopyFileName,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.cut").to_string(),
             description: t!("cmd.cut_desc").to_string(),
@@ -310,6 +422,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.paste_from_clipboard_history").to_string(),
+            description: t!("cmd.paste_from_clipboard_history_desc").to_string(),
+            action: Action::PasteFromClipboardHistory,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.forget_clipboard_history").to_string(),
+            description: t!("cmd.forget_clipboard_history_desc").to_string(),
+            action: Action::ForgetClipboardHistory,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.delete_line").to_string(),
             description: t!("cmd.delete_line_desc").to_string(),
@@ -334,6 +462,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.delete_subword_backward").to_string(),
+            description: t!("cmd.delete_subword_backward_desc").to_string(),
+            action: Action::DeleteSubwordBackward,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.delete_subword_forward").to_string(),
+            description: t!("cmd.delete_subword_forward_desc").to_string(),
+            action: Action::DeleteSubwordForward,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.delete_to_end_of_line").to_string(),
             description: t!("cmd.delete_to_end_of_line_desc").to_string(),
@@ -374,6 +518,78 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.reflow_paragraph").to_string(),
+            description: t!("cmd.reflow_paragraph_desc").to_string(),
+            action: Action::ReflowParagraph,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.reflow_selection").to_string(),
+            description: t!("cmd.reflow_selection_desc").to_string(),
+            action: Action::ReflowSelection,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.evaluate_selection").to_string(),
+            description: t!("cmd.evaluate_selection_desc").to_string(),
+            action: Action::EvaluateSelection,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.evaluate_and_replace_selection").to_string(),
+            description: t!("cmd.evaluate_and_replace_selection_desc").to_string(),
+            action: Action::EvaluateAndReplaceSelection,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.generate_doc_comment").to_string(),
+            description: t!("cmd.generate_doc_comment_desc").to_string(),
+            action: Action::GenerateDocComment,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.insert_date").to_string(),
+            description: t!("cmd.insert_date_desc").to_string(),
+            action: Action::InsertDate,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.insert_time").to_string(),
+            description: t!("cmd.insert_time_desc").to_string(),
+            action: Action::InsertTime,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.insert_unicode_character").to_string(),
+            description: t!("cmd.insert_unicode_character_desc").to_string(),
+            action: Action::InsertUnicodeCharacter,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.insert_digraph").to_string(),
+            description: t!("cmd.insert_digraph_desc").to_string(),
+            action: Action::InsertDigraph,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.open_line").to_string(),
             description: t!("cmd.open_line_desc").to_string(),
@@ -423,6 +639,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.select_indent_block").to_string(),
+            description: t!("cmd.select_indent_block_desc").to_string(),
+            action: Action::SelectIndentBlock,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.expand_selection").to_string(),
             description: t!("cmd.expand_selection_desc").to_string(),
@@ -497,6 +721,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.move_tab_to_split").to_string(),
+            description: t!("cmd.move_tab_to_split_desc").to_string(),
+            action: Action::MoveTabToSplit,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Split operations
         Command {
             name: t!("cmd.split_horizontal").to_string(),
@@ -562,6 +794,63 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_zoom_split").to_string(),
+            description: t!("cmd.toggle_zoom_split_desc").to_string(),
+            action: Action::ToggleZoomSplit,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_scroll_sync").to_string(),
+            description: t!("cmd.toggle_scroll_sync_desc").to_string(),
+            action: Action::ToggleScrollSync,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.sync_scroll_all_splits").to_string(),
+            description: t!("cmd.sync_scroll_all_splits_desc").to_string(),
+            action: Action::SyncScrollAllSplits,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.stop_scroll_sync").to_string(),
+            description: t!("cmd.stop_scroll_sync_desc").to_string(),
+            action: Action::StopScrollSync,
+            contexts: vec![KeyContext::Normal, KeyContext::Terminal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        // Floating tool windows
+        Command {
+            name: t!("cmd.pin_popup").to_string(),
+            description: t!("cmd.pin_popup_desc").to_string(),
+            action: Action::PinPopup,
+            contexts: vec![KeyContext::Popup],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.focus_floating_window").to_string(),
+            description: t!("cmd.focus_floating_window_desc").to_string(),
+            action: Action::FocusFloatingWindow(None),
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.close_floating_window").to_string(),
+            description: t!("cmd.close_floating_window_desc").to_string(),
+            action: Action::CloseFloatingWindow,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // View toggles
         Command {
             name: t!("cmd.toggle_line_numbers").to_string(),
@@ -681,6 +970,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.show_context_menu").to_string(),
+            description: t!("cmd.show_context_menu_desc").to_string(),
+            action: Action::ShowContextMenu,
+            contexts: vec![KeyContext::Normal, KeyContext::FileExplorer],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.toggle_menu_bar").to_string(),
             description: t!("cmd.toggle_menu_bar_desc").to_string(),
@@ -705,6 +1002,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_absolute_paths").to_string(),
+            description: t!("cmd.toggle_absolute_paths_desc").to_string(),
+            action: Action::ToggleAbsolutePaths,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.toggle_overwrite_mode").to_string(),
+            description: t!("cmd.toggle_overwrite_mode_desc").to_string(),
+            action: Action::ToggleOverwriteMode,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.focus_file_explorer").to_string(),
             description: t!("cmd.focus_file_explorer_desc").to_string(),
@@ -870,6 +1183,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.search_in_project").to_string(),
+            description: t!("cmd.search_in_project_desc").to_string(),
+            action: Action::SearchInProject,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.project_search_cancel").to_string(),
+            description: t!("cmd.project_search_cancel_desc").to_string(),
+            action: Action::ProjectSearchCancel,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Navigation
         Command {
             name: t!("cmd.goto_line").to_string(),
@@ -951,6 +1280,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.detect_lsp_servers").to_string(),
+            description: t!("cmd.detect_lsp_servers_desc").to_string(),
+            action: Action::LspDetectServers,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.toggle_mouse_hover").to_string(),
             description: t!("cmd.toggle_mouse_hover_desc").to_string(),
@@ -1000,6 +1337,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.goto_indent_block_start").to_string(),
+            description: t!("cmd.goto_indent_block_start_desc").to_string(),
+            action: Action::GoToIndentBlockStart,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.goto_indent_block_end").to_string(),
+            description: t!("cmd.goto_indent_block_end_desc").to_string(),
+            action: Action::GoToIndentBlockEnd,
+            contexts: vec![KeyContext::Normal],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Error navigation
         Command {
             name: t!("cmd.jump_to_next_error").to_string(),
@@ -1108,6 +1461,14 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.toggle_cheat_sheet").to_string(),
+            description: t!("cmd.toggle_cheat_sheet_desc").to_string(),
+            action: Action::ToggleCheatSheet,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.show_warnings").to_string(),
             description: t!("cmd.show_warnings_desc").to_string(),
@@ -1124,6 +1485,38 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.show_memory_usage").to_string(),
+            description: t!("cmd.show_memory_usage_desc").to_string(),
+            action: Action::ShowMemoryUsage,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_paths").to_string(),
+            description: t!("cmd.show_paths_desc").to_string(),
+            action: Action::ShowPaths,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.show_remote_audit_log").to_string(),
+            description: t!("cmd.show_remote_audit_log_desc").to_string(),
+            action: Action::ShowRemoteAuditLog,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.export_remote_audit_log").to_string(),
+            description: t!("cmd.export_remote_audit_log_desc").to_string(),
+            action: Action::ExportRemoteAuditLog,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.clear_warnings").to_string(),
             description: t!("cmd.clear_warnings_desc").to_string(),
@@ -1141,6 +1534,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.safe_mode_reenable").to_string(),
+            description: t!("cmd.safe_mode_reenable_desc").to_string(),
+            action: Action::SafeModeReenable,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.reload_plugin").to_string(),
+            description: t!("cmd.reload_plugin_desc").to_string(),
+            action: Action::ReloadPlugin,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         Command {
             name: t!("cmd.toggle_inlay_hints").to_string(),
             description: t!("cmd.toggle_inlay_hints_desc").to_string(),
@@ -1158,6 +1567,22 @@ pub fn get_all_commands() -> Vec<Command> {
             custom_contexts: vec![],
             source: CommandSource::Builtin,
         },
+        Command {
+            name: t!("cmd.select_split_theme").to_string(),
+            description: t!("cmd.select_split_theme_desc").to_string(),
+            action: Action::SelectSplitTheme,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
+        Command {
+            name: t!("cmd.import_base16_theme").to_string(),
+            description: t!("cmd.import_base16_theme_desc").to_string(),
+            action: Action::ImportBase16Theme,
+            contexts: vec![],
+            custom_contexts: vec![],
+            source: CommandSource::Builtin,
+        },
         // Keybinding map selection
         Command {
             name: t!("cmd.select_keybinding_map").to_string(),
@@ -147,6 +147,10 @@ pub enum DeferredAction {
     PromptSelectionChanged {
         selected_index: usize,
     },
+    /// Check or uncheck the highlighted item of a multi-select quick pick
+    ToggleQuickPickItem,
+    /// Check or uncheck the highlighted edit of a rename preview
+    ToggleRenamePreviewEdit,
 
     // Popup actions
     ClosePopup,
@@ -384,14 +384,9 @@ impl InputHistory {
 /// Get the data directory for Fresh editor state
 /// Returns $XDG_DATA_HOME/fresh or ~/.local/share/fresh on Linux
 /// Returns ~/Library/Application Support/fresh on macOS
+/// (see [`crate::config_io::DirectoryContext::from_system`] for overrides)
 pub fn get_data_dir() -> std::io::Result<std::path::PathBuf> {
-    let data_dir = dirs::data_dir().ok_or_else(|| {
-        std::io::Error::new(
-            std::io::ErrorKind::NotFound,
-            "Could not determine data directory",
-        )
-    })?;
-    Ok(data_dir.join("fresh"))
+    Ok(crate::config_io::DirectoryContext::from_system()?.data_dir)
 }
 
 /// Get the path for search history file
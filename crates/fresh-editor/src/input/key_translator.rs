@@ -9,7 +9,12 @@
 //! 3. KeybindingResolver.resolve(normalized) → Action
 //!
 //! This keeps calibration separate from keymap customization.
+//!
+//! When a non-QWERTY `keyboard_layout` is configured, Ctrl/Alt chords are also
+//! remapped to the US QWERTY key at the same physical position, so keybindings
+//! follow key position rather than the character printed on the key.
 
+use crate::config::KeyboardLayout;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -24,6 +29,8 @@ use std::path::Path;
 pub struct KeyTranslator {
     /// Translation table: raw event → normalized event
     translations: HashMap<KeyEventKey, KeyEventKey>,
+    /// Layout used to resolve modified keys by physical position
+    layout: KeyboardLayout,
 }
 
 /// A serializable key event (simplified version of crossterm::KeyEvent)
@@ -203,19 +210,61 @@ impl KeyTranslator {
     pub fn new() -> Self {
         Self {
             translations: HashMap::new(),
+            layout: KeyboardLayout::default(),
         }
     }
 
     /// Translate a raw terminal event to a normalized event
     ///
-    /// If a translation exists for the raw event, returns the normalized event.
-    /// Otherwise, returns the raw event unchanged.
+    /// Modified keys are first resolved by physical position for the configured
+    /// layout. If a calibration translation exists for the result, returns the
+    /// normalized event; otherwise returns it unchanged.
     pub fn translate(&self, raw: KeyEvent) -> KeyEvent {
-        let key = KeyEventKey::from_key_event(&raw);
+        let positional = self.resolve_physical_key(raw);
+        let key = KeyEventKey::from_key_event(&positional);
         if let Some(normalized) = self.translations.get(&key) {
             normalized.to_key_event()
         } else {
-            raw
+            positional
+        }
+    }
+
+    /// Set the layout used to resolve modified keys by physical position
+    pub fn set_layout(&mut self, layout: KeyboardLayout) {
+        self.layout = layout;
+    }
+
+    /// The layout used to resolve modified keys by physical position
+    pub fn layout(&self) -> KeyboardLayout {
+        self.layout
+    }
+
+    /// Map a Ctrl/Alt chord to the US QWERTY key at the same physical position
+    ///
+    /// Unmodified and Shift-only keys are text input and are left alone, as is
+    /// Ctrl+Alt, which is how many terminals report AltGr.
+    fn resolve_physical_key(&self, event: KeyEvent) -> KeyEvent {
+        let KeyCode::Char(c) = event.code else {
+            return event;
+        };
+        let ctrl = event.modifiers.contains(KeyModifiers::CONTROL);
+        let alt = event.modifiers.contains(KeyModifiers::ALT);
+        if ctrl == alt {
+            return event;
+        }
+
+        let lower = c.to_lowercase().next().unwrap_or(c);
+        let Some(mapped) = qwerty_position(self.layout, lower) else {
+            return event;
+        };
+        let mapped = if c.is_uppercase() {
+            mapped.to_ascii_uppercase()
+        } else {
+            mapped
+        };
+        KeyEvent {
+            code: KeyCode::Char(mapped),
+            ..event
         }
     }
 
@@ -257,6 +306,8 @@ impl KeyTranslator {
     ///
     /// Returns an empty translator if the file doesn't exist.
     /// Returns an error if the file exists but is invalid.
+    ///
+    /// The layout is not stored in the file; callers set it from the config.
     pub fn load_from_file(path: &Path) -> Result<Self, std::io::Error> {
         if !path.exists() {
             return Ok(Self::new());
@@ -320,7 +371,9 @@ impl KeyTranslator {
 
     /// Get the default calibration file path
     pub fn default_path() -> Option<std::path::PathBuf> {
-        dirs::config_dir().map(|p| p.join("fresh").join("key_calibration.json"))
+        crate::config_io::DirectoryContext::from_system()
+            .ok()
+            .map(|dirs| dirs.key_calibration_path())
     }
 
     /// Load from the default config location
@@ -345,6 +398,109 @@ impl KeyTranslator {
     }
 }
 
+/// The US QWERTY character on the key that produces `c` on `layout`
+///
+/// Returns None when the key is in the same place as on QWERTY.
+fn qwerty_position(layout: KeyboardLayout, c: char) -> Option<char> {
+    // Pairs of (layout character, QWERTY character on the same key)
+    let table: &[(char, char)] = match layout {
+        KeyboardLayout::Qwerty => &[],
+        KeyboardLayout::Azerty => &[
+            ('a', 'q'),
+            ('z', 'w'),
+            ('q', 'a'),
+            ('w', 'z'),
+            ('m', ';'),
+            (',', 'm'),
+            (';', ','),
+            (':', '.'),
+            ('!', '/'),
+            ('^', '['),
+            ('$', ']'),
+            ('ù', '\''),
+            ('*', '\\'),
+            ('&', '1'),
+            ('é', '2'),
+            ('"', '3'),
+            ('\'', '4'),
+            ('(', '5'),
+            ('-', '6'),
+            ('è', '7'),
+            ('_', '8'),
+            ('ç', '9'),
+            ('à', '0'),
+            (')', '-'),
+        ],
+        KeyboardLayout::Qwertz => &[
+            ('z', 'y'),
+            ('y', 'z'),
+            ('ü', '['),
+            ('+', ']'),
+            ('ö', ';'),
+            ('ä', '\''),
+            ('#', '\\'),
+            ('ß', '-'),
+            ('´', '='),
+            ('-', '/'),
+        ],
+        KeyboardLayout::Dvorak => &[
+            ('\'', 'q'),
+            (',', 'w'),
+            ('.', 'e'),
+            ('p', 'r'),
+            ('y', 't'),
+            ('f', 'y'),
+            ('g', 'u'),
+            ('c', 'i'),
+            ('r', 'o'),
+            ('l', 'p'),
+            ('/', '['),
+            ('=', ']'),
+            ('o', 's'),
+            ('e', 'd'),
+            ('u', 'f'),
+            ('i', 'g'),
+            ('d', 'h'),
+            ('h', 'j'),
+            ('t', 'k'),
+            ('n', 'l'),
+            ('s', ';'),
+            ('-', '\''),
+            (';', 'z'),
+            ('q', 'x'),
+            ('j', 'c'),
+            ('k', 'v'),
+            ('x', 'b'),
+            ('b', 'n'),
+            ('w', ','),
+            ('v', '.'),
+            ('z', '/'),
+            ('[', '-'),
+            (']', '='),
+        ],
+        KeyboardLayout::Colemak => &[
+            ('f', 'e'),
+            ('p', 'r'),
+            ('g', 't'),
+            ('j', 'y'),
+            ('l', 'u'),
+            ('u', 'i'),
+            ('y', 'o'),
+            (';', 'p'),
+            ('r', 's'),
+            ('s', 'd'),
+            ('t', 'f'),
+            ('d', 'g'),
+            ('n', 'j'),
+            ('e', 'k'),
+            ('i', 'l'),
+            ('o', ';'),
+            ('k', 'n'),
+        ],
+    };
+    table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -419,6 +575,52 @@ mod tests {
         assert_eq!(key, deserialized);
     }
 
+    #[test]
+    fn test_layout_resolves_modified_keys_by_position() {
+        let mut translator = KeyTranslator::new();
+        translator.set_layout(KeyboardLayout::Azerty);
+
+        // The AZERTY 'a' key sits where QWERTY has 'q'
+        let ctrl_a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+        assert_eq!(translator.translate(ctrl_a).code, KeyCode::Char('q'));
+
+        // AZERTY '!' is on the QWERTY '/' key (toggle comment stays reachable)
+        let ctrl_bang = KeyEvent::new(KeyCode::Char('!'), KeyModifiers::CONTROL);
+        assert_eq!(translator.translate(ctrl_bang).code, KeyCode::Char('/'));
+
+        // Plain typing and AltGr (Ctrl+Alt) are untouched
+        let plain_a = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(translator.translate(plain_a).code, KeyCode::Char('a'));
+        let altgr = KeyEvent::new(
+            KeyCode::Char('a'),
+            KeyModifiers::CONTROL.union(KeyModifiers::ALT),
+        );
+        assert_eq!(translator.translate(altgr).code, KeyCode::Char('a'));
+    }
+
+    #[test]
+    fn test_layout_applies_before_calibration() {
+        let mut translator = KeyTranslator::new();
+        translator.set_layout(KeyboardLayout::Dvorak);
+
+        // Dvorak 'j' is the QWERTY 'c' key; calibration then remaps Ctrl+C
+        let raw = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL);
+        let expected = KeyEvent::new(KeyCode::Char('x'), KeyModifiers::CONTROL);
+        translator.add_translation(raw, expected);
+
+        let ctrl_j = KeyEvent::new(KeyCode::Char('j'), KeyModifiers::CONTROL);
+        assert_eq!(translator.translate(ctrl_j).code, KeyCode::Char('x'));
+
+        // Shift is preserved through the positional mapping
+        let alt_shift_j = KeyEvent::new(
+            KeyCode::Char('J'),
+            KeyModifiers::ALT.union(KeyModifiers::SHIFT),
+        );
+        let result = translator.translate(alt_shift_j);
+        assert_eq!(result.code, KeyCode::Char('C'));
+        assert!(result.modifiers.contains(KeyModifiers::SHIFT));
+    }
+
     #[test]
     fn test_roundtrip_key_event() {
         let original = KeyEvent::new(KeyCode::Home, KeyModifiers::SHIFT);
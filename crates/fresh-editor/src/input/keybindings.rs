@@ -60,6 +60,7 @@ pub fn format_keybinding(keycode: &KeyCode, modifiers: &KeyModifiers) -> String
         KeyCode::Enter => result.push_str("Enter"),
         KeyCode::Backspace => result.push_str("Backspace"),
         KeyCode::Delete => result.push_str("Del"),
+        KeyCode::Insert => result.push_str("Ins"),
         KeyCode::Tab => result.push_str("Tab"),
         KeyCode::Esc => result.push_str("Esc"),
         KeyCode::Left => result.push('←'),
@@ -79,6 +80,50 @@ pub fn format_keybinding(keycode: &KeyCode, modifiers: &KeyModifiers) -> String
     result
 }
 
+/// Convert a key event into the `key` and `modifiers` strings of a keybinding
+/// entry in config files (e.g. `"PageUp"` with `["ctrl", "shift"]`).
+/// Returns None for keys a binding can't express, such as bare modifiers.
+pub fn key_event_to_binding(
+    keycode: &KeyCode,
+    modifiers: &KeyModifiers,
+) -> Option<(String, Vec<String>)> {
+    let key = match keycode {
+        KeyCode::Enter => "Enter".to_string(),
+        KeyCode::Backspace => "Backspace".to_string(),
+        KeyCode::Delete => "Delete".to_string(),
+        KeyCode::Insert => "Insert".to_string(),
+        KeyCode::Tab => "Tab".to_string(),
+        KeyCode::BackTab => "BackTab".to_string(),
+        KeyCode::Esc => "Escape".to_string(),
+        KeyCode::Left => "Left".to_string(),
+        KeyCode::Right => "Right".to_string(),
+        KeyCode::Up => "Up".to_string(),
+        KeyCode::Down => "Down".to_string(),
+        KeyCode::Home => "Home".to_string(),
+        KeyCode::End => "End".to_string(),
+        KeyCode::PageUp => "PageUp".to_string(),
+        KeyCode::PageDown => "PageDown".to_string(),
+        KeyCode::Char(' ') => "Space".to_string(),
+        // Key names are matched case-insensitively, so Shift is carried by the modifiers
+        KeyCode::Char(c) if c.is_ascii() => c.to_ascii_lowercase().to_string(),
+        KeyCode::F(n) => format!("F{}", n),
+        _ => return None,
+    };
+
+    let mut names = Vec::new();
+    if modifiers.contains(KeyModifiers::CONTROL) {
+        names.push("ctrl".to_string());
+    }
+    if modifiers.contains(KeyModifiers::ALT) {
+        names.push("alt".to_string());
+    }
+    if modifiers.contains(KeyModifiers::SHIFT) {
+        names.push("shift".to_string());
+    }
+
+    Some((key, names))
+}
+
 /// Returns a priority score for a keybinding key.
 /// Lower scores indicate canonical/preferred keys, higher scores indicate terminal equivalents.
 /// This helps ensure deterministic selection when multiple keybindings exist for an action.
@@ -228,6 +273,9 @@ pub enum Action {
     MoveWordLeft,
     MoveWordRight,
     MoveWordEnd, // Move to end of current word
+    MoveSubwordLeft,
+    MoveSubwordRight,
+    MoveSubwordEnd, // Move to end of current camelCase/snake_case part
     MoveLineStart,
     MoveLineEnd,
     MovePageUp,
@@ -245,6 +293,9 @@ pub enum Action {
     SelectWordLeft,
     SelectWordRight,
     SelectWordEnd, // Select to end of current word
+    SelectSubwordLeft,
+    SelectSubwordRight,
+    SelectSubwordEnd,
     SelectLineStart,
     SelectLineEnd,
     SelectDocumentStart,
@@ -254,6 +305,7 @@ pub enum Action {
     SelectAll,
     SelectWord,
     SelectLine,
+    SelectIndentBlock,
     ExpandSelection,
 
     // Block/rectangular selection (column-wise)
@@ -267,6 +319,8 @@ pub enum Action {
     DeleteForward,
     DeleteWordBackward,
     DeleteWordForward,
+    DeleteSubwordBackward,
+    DeleteSubwordForward,
     DeleteLine,
     DeleteToLineEnd,
     DeleteToLineStart,
@@ -282,8 +336,17 @@ pub enum Action {
     // Clipboard
     Copy,
     CopyWithTheme(String),
+    CopyAsMarkdown,    // Copy selection as a fenced Markdown code block
+    CopyWithReference, // Copy selection prefixed with a `path:line-range` reference
+    CopyPermalink,     // Copy a git permalink (remote URL + commit + lines)
+    CopyRelativePath,  // Copy the buffer's workspace-relative path
+    CopyAbsolutePath,  // Copy the buffer's absolute path
+    CopyFileName,      // Copy the buffer's file name
     Cut,
     Paste,
+    PasteFromClipboardHistory, // Pick a recently copied value to paste
+    ClipboardHistoryTogglePin, // Pin/unpin the selected clipboard history entry
+    ForgetClipboardHistory,    // Forget unpinned clipboard history entries
 
     // Vi-style yank (copy without selection, then restore cursor)
     YankWordForward,
@@ -302,6 +365,9 @@ pub enum Action {
     SaveAs,
     Open,
     SwitchProject,
+    OpenRecentWorkspace,
+    OpenRecentWorkspaceInNewProcess,
+    TogglePinWorkspace,
     New,
     Close,
     CloseTab,
@@ -309,6 +375,9 @@ pub enum Action {
     ForceQuit,
     Revert,
     ToggleAutoRevert,
+    ReopenWithEncoding, // Reload the file from disk decoded with a chosen encoding
+    ToggleHexView,      // Show the buffer's bytes as hex where they aren't printable text
+    ToggleAnsiColors,   // Show ANSI escape sequences as colors or as raw codes
     FormatBuffer,
     TrimTrailingWhitespace,
     EnsureFinalNewline,
@@ -316,6 +385,8 @@ pub enum Action {
     // Navigation
     GotoLine,
     GoToMatchingBracket,
+    GoToIndentBlockStart,
+    GoToIndentBlockEnd,
     JumpToNextError,
     JumpToPreviousError,
 
@@ -354,15 +425,22 @@ pub enum Action {
     // Undo/redo
     Undo,
     Redo,
+    ShowUndoTree,
+    UndoTreeSwitchBranch,
 
     // View
     ScrollUp,
     ScrollDown,
     ShowHelp,
     ShowKeyboardShortcuts,
+    ToggleCheatSheet, // Toggle the searchable keybinding cheat sheet overlay
     ShowWarnings,
     ShowStatusLog,
     ShowLspStatus,
+    ShowMemoryUsage,
+    ShowPaths,
+    ShowRemoteAuditLog,
+    ExportRemoteAuditLog,
     ClearWarnings,
     CommandPalette, // TODO: Consider dropping this now that we have QuickOpen
     /// Quick Open - unified prompt with prefix-based provider routing
@@ -371,6 +449,8 @@ pub enum Action {
     ToggleComposeMode,
     SetComposeWidth,
     SelectTheme,
+    SelectSplitTheme,
+    ImportBase16Theme, // Convert a base16/base24 scheme into a user theme
     SelectKeybindingMap,
     SelectCursorStyle,
     SelectLocale,
@@ -380,6 +460,9 @@ pub enum Action {
     PrevBuffer,
     SwitchToPreviousTab,
     SwitchToTabByName,
+    NextTabMru,     // Next tab in the split's most-recently-used order (Ctrl+Tab)
+    PrevTabMru,     // Previous tab in most-recently-used order
+    MoveTabToSplit, // Move the active tab into another split
 
     // Tab scrolling
     ScrollTabsLeft,
@@ -398,6 +481,13 @@ pub enum Action {
     IncreaseSplitSize,
     DecreaseSplitSize,
     ToggleMaximizeSplit,
+    ToggleZoomSplit,
+    /// Add the active split to the scroll sync group, or remove it
+    ToggleScrollSync,
+    /// Scroll all splits together
+    SyncScrollAllSplits,
+    /// Stop syncing the active split's scroll sync group
+    StopScrollSync,
 
     // Prompt mode actions
     PromptConfirm,
@@ -444,12 +534,21 @@ pub enum Action {
     PopupConfirm,
     PopupCancel,
 
+    // Floating tool windows
+    PinPopup,
+    FocusFloatingWindow(Option<usize>), // None = cycle to the next window
+    CloseFloatingWindow,
+
     // File explorer operations
     ToggleFileExplorer,
     // Menu bar visibility
     ToggleMenuBar,
     // Tab bar visibility
     ToggleTabBar,
+    // Absolute vs workspace-relative buffer paths
+    ToggleAbsolutePaths,
+    // Typed characters replace the character under the cursor
+    ToggleOverwriteMode,
     FocusFileExplorer,
     FocusEditor,
     FileExplorerUp,
@@ -477,6 +576,7 @@ pub enum Action {
     LspCodeActions,
     LspRestart,
     LspStop,
+    LspDetectServers,
     ToggleInlayHints,
     ToggleMouseHover,
 
@@ -497,6 +597,8 @@ pub enum Action {
 
     // Config operations
     DumpConfig,
+    SafeModeReenable, // Pick components to turn back on after --safe-mode
+    ReloadPlugin,     // Pick a loaded plugin and reload it from disk
 
     // Search and replace
     Search,
@@ -506,7 +608,10 @@ pub enum Action {
     FindSelectionNext,     // Quick find next occurrence of selection (Ctrl+F3)
     FindSelectionPrevious, // Quick find previous occurrence of selection (Ctrl+Shift+F3)
     Replace,
-    QueryReplace, // Interactive replace (y/n/!/q for each match)
+    QueryReplace,            // Interactive replace (y/n/!/q for each match)
+    SearchInProject,         // Search all files under the working directory
+    ProjectSearchOpenResult, // Open the project search result under the cursor
+    ProjectSearchCancel,     // Stop a running project search
 
     // Menu navigation
     MenuActivate,     // Open menu bar (Alt or F10)
@@ -517,6 +622,7 @@ pub enum Action {
     MenuDown,         // Navigate to next item in menu
     MenuExecute,      // Execute selected menu item (Enter)
     MenuOpen(String), // Open a specific menu by name (e.g., "File", "Edit")
+    ShowContextMenu,  // Open the right-click context menu at the cursor (Shift+F10)
 
     // Keybinding map switching
     SwitchKeybindingMap(String), // Switch to a named keybinding map (e.g., "default", "emacs", "vscode")
@@ -553,6 +659,23 @@ pub enum Action {
     ToLowerCase, // Convert selection to lowercase
     SortLines,   // Sort selected lines alphabetically
 
+    // Reflow
+    ReflowParagraph, // Re-wrap the paragraph at the cursor to the reflow column
+    ReflowSelection, // Re-wrap the selected lines to the reflow column
+
+    // Calculator
+    EvaluateSelection,           // Show the result of evaluating each selection
+    EvaluateAndReplaceSelection, // Replace each selection with its result
+    GenerateDocComment,          // Insert a doc comment skeleton for the function at the cursor
+
+    // Locale-aware insertion
+    InsertDate, // Insert today's date in the current locale's format
+    InsertTime, // Insert the current time in the current locale's format
+
+    // Unicode input
+    InsertUnicodeCharacter, // Pick a character by name, codepoint or category
+    InsertDigraph,          // Insert the character for a two-character digraph
+
     // Input calibration
     CalibrateInput, // Open the input calibration wizard
 
@@ -589,6 +712,9 @@ impl Action {
             "move_word_left" => Self::MoveWordLeft,
             "move_word_right" => Self::MoveWordRight,
             "move_word_end" => Self::MoveWordEnd,
+            "move_subword_left" => Self::MoveSubwordLeft,
+            "move_subword_right" => Self::MoveSubwordRight,
+            "move_subword_end" => Self::MoveSubwordEnd,
             "move_line_start" => Self::MoveLineStart,
             "move_line_end" => Self::MoveLineEnd,
             "move_page_up" => Self::MovePageUp,
@@ -605,6 +731,9 @@ impl Action {
             "select_word_left" => Self::SelectWordLeft,
             "select_word_right" => Self::SelectWordRight,
             "select_word_end" => Self::SelectWordEnd,
+            "select_subword_left" => Self::SelectSubwordLeft,
+            "select_subword_right" => Self::SelectSubwordRight,
+            "select_subword_end" => Self::SelectSubwordEnd,
             "select_line_start" => Self::SelectLineStart,
             "select_line_end" => Self::SelectLineEnd,
             "select_document_start" => Self::SelectDocumentStart,
@@ -614,6 +743,7 @@ impl Action {
             "select_all" => Self::SelectAll,
             "select_word" => Self::SelectWord,
             "select_line" => Self::SelectLine,
+            "select_indent_block" => Self::SelectIndentBlock,
             "expand_selection" => Self::ExpandSelection,
 
             // Block/rectangular selection
@@ -626,6 +756,8 @@ impl Action {
             "delete_forward" => Self::DeleteForward,
             "delete_word_backward" => Self::DeleteWordBackward,
             "delete_word_forward" => Self::DeleteWordForward,
+            "delete_subword_backward" => Self::DeleteSubwordBackward,
+            "delete_subword_forward" => Self::DeleteSubwordForward,
             "delete_line" => Self::DeleteLine,
             "delete_to_line_end" => Self::DeleteToLineEnd,
             "delete_to_line_start" => Self::DeleteToLineStart,
@@ -640,8 +772,17 @@ impl Action {
                 let theme = args.get("theme").and_then(|v| v.as_str()).unwrap_or("");
                 Self::CopyWithTheme(theme.to_string())
             }
+            "copy_as_markdown" => Self::CopyAsMarkdown,
+            "copy_with_reference" => Self::CopyWithReference,
+            "copy_permalink" => Self::CopyPermalink,
+            "copy_relative_path" => Self::CopyRelativePath,
+            "copy_absolute_path" => Self::CopyAbsolutePath,
+            "copy_file_name" => Self::CopyFileName,
             "cut" => Self::Cut,
             "paste" => Self::Paste,
+            "paste_from_clipboard_history" => Self::PasteFromClipboardHistory,
+            "clipboard_history_toggle_pin" => Self::ClipboardHistoryTogglePin,
+            "forget_clipboard_history" => Self::ForgetClipboardHistory,
 
             // Vi-style yank actions
             "yank_word_forward" => Self::YankWordForward,
@@ -658,6 +799,9 @@ impl Action {
             "save_as" => Self::SaveAs,
             "open" => Self::Open,
             "switch_project" => Self::SwitchProject,
+            "open_recent_workspace" => Self::OpenRecentWorkspace,
+            "open_recent_workspace_new_process" => Self::OpenRecentWorkspaceInNewProcess,
+            "toggle_pin_workspace" => Self::TogglePinWorkspace,
             "new" => Self::New,
             "close" => Self::Close,
             "close_tab" => Self::CloseTab,
@@ -665,9 +809,14 @@ impl Action {
             "force_quit" => Self::ForceQuit,
             "revert" => Self::Revert,
             "toggle_auto_revert" => Self::ToggleAutoRevert,
+            "reopen_with_encoding" => Self::ReopenWithEncoding,
+            "toggle_hex_view" => Self::ToggleHexView,
+            "toggle_ansi_colors" => Self::ToggleAnsiColors,
             "format_buffer" => Self::FormatBuffer,
             "goto_line" => Self::GotoLine,
             "goto_matching_bracket" => Self::GoToMatchingBracket,
+            "goto_indent_block_start" => Self::GoToIndentBlockStart,
+            "goto_indent_block_end" => Self::GoToIndentBlockEnd,
             "jump_to_next_error" => Self::JumpToNextError,
             "jump_to_previous_error" => Self::JumpToPreviousError,
 
@@ -702,14 +851,21 @@ impl Action {
 
             "undo" => Self::Undo,
             "redo" => Self::Redo,
+            "show_undo_tree" => Self::ShowUndoTree,
+            "undo_tree_switch_branch" => Self::UndoTreeSwitchBranch,
 
             "scroll_up" => Self::ScrollUp,
             "scroll_down" => Self::ScrollDown,
             "show_help" => Self::ShowHelp,
             "keyboard_shortcuts" => Self::ShowKeyboardShortcuts,
+            "toggle_cheat_sheet" => Self::ToggleCheatSheet,
             "show_warnings" => Self::ShowWarnings,
             "show_status_log" => Self::ShowStatusLog,
             "show_lsp_status" => Self::ShowLspStatus,
+            "show_memory_usage" => Self::ShowMemoryUsage,
+            "show_paths" => Self::ShowPaths,
+            "show_remote_audit_log" => Self::ShowRemoteAuditLog,
+            "export_remote_audit_log" => Self::ExportRemoteAuditLog,
             "clear_warnings" => Self::ClearWarnings,
             "command_palette" => Self::CommandPalette,
             "quick_open" => Self::QuickOpen,
@@ -719,6 +875,9 @@ impl Action {
 
             "next_buffer" => Self::NextBuffer,
             "prev_buffer" => Self::PrevBuffer,
+            "next_tab_mru" => Self::NextTabMru,
+            "prev_tab_mru" => Self::PrevTabMru,
+            "move_tab_to_split" => Self::MoveTabToSplit,
 
             "navigate_back" => Self::NavigateBack,
             "navigate_forward" => Self::NavigateForward,
@@ -731,6 +890,10 @@ impl Action {
             "increase_split_size" => Self::IncreaseSplitSize,
             "decrease_split_size" => Self::DecreaseSplitSize,
             "toggle_maximize_split" => Self::ToggleMaximizeSplit,
+            "toggle_zoom_split" => Self::ToggleZoomSplit,
+            "toggle_scroll_sync" => Self::ToggleScrollSync,
+            "sync_scroll_all_splits" => Self::SyncScrollAllSplits,
+            "stop_scroll_sync" => Self::StopScrollSync,
 
             "prompt_confirm" => Self::PromptConfirm,
             "prompt_cancel" => Self::PromptCancel,
@@ -769,9 +932,19 @@ impl Action {
             "popup_confirm" => Self::PopupConfirm,
             "popup_cancel" => Self::PopupCancel,
 
+            "pin_popup" => Self::PinPopup,
+            "focus_floating_window" => Self::FocusFloatingWindow(
+                args.get("id")
+                    .and_then(|v| v.as_u64())
+                    .map(|id| id as usize),
+            ),
+            "close_floating_window" => Self::CloseFloatingWindow,
+
             "toggle_file_explorer" => Self::ToggleFileExplorer,
             "toggle_menu_bar" => Self::ToggleMenuBar,
             "toggle_tab_bar" => Self::ToggleTabBar,
+            "toggle_absolute_paths" => Self::ToggleAbsolutePaths,
+            "toggle_overwrite_mode" => Self::ToggleOverwriteMode,
             "focus_file_explorer" => Self::FocusFileExplorer,
             "focus_editor" => Self::FocusEditor,
             "file_explorer_up" => Self::FileExplorerUp,
@@ -798,6 +971,7 @@ impl Action {
             "lsp_code_actions" => Self::LspCodeActions,
             "lsp_restart" => Self::LspRestart,
             "lsp_stop" => Self::LspStop,
+            "lsp_detect_servers" => Self::LspDetectServers,
             "toggle_inlay_hints" => Self::ToggleInlayHints,
             "toggle_mouse_hover" => Self::ToggleMouseHover,
 
@@ -807,6 +981,8 @@ impl Action {
             "set_background" => Self::SetBackground,
             "set_background_blend" => Self::SetBackgroundBlend,
             "select_theme" => Self::SelectTheme,
+            "select_split_theme" => Self::SelectSplitTheme,
+            "import_base16_theme" => Self::ImportBase16Theme,
             "select_keybinding_map" => Self::SelectKeybindingMap,
             "select_locale" => Self::SelectLocale,
 
@@ -818,6 +994,8 @@ impl Action {
             "reset_buffer_settings" => Self::ResetBufferSettings,
 
             "dump_config" => Self::DumpConfig,
+            "safe_mode_reenable" => Self::SafeModeReenable,
+            "reload_plugin" => Self::ReloadPlugin,
 
             "search" => Self::Search,
             "find_in_selection" => Self::FindInSelection,
@@ -827,6 +1005,9 @@ impl Action {
             "find_selection_previous" => Self::FindSelectionPrevious,
             "replace" => Self::Replace,
             "query_replace" => Self::QueryReplace,
+            "search_in_project" => Self::SearchInProject,
+            "project_search_open_result" => Self::ProjectSearchOpenResult,
+            "project_search_cancel" => Self::ProjectSearchCancel,
 
             "menu_activate" => Self::MenuActivate,
             "menu_close" => Self::MenuClose,
@@ -835,6 +1016,7 @@ impl Action {
             "menu_up" => Self::MenuUp,
             "menu_down" => Self::MenuDown,
             "menu_execute" => Self::MenuExecute,
+            "show_context_menu" => Self::ShowContextMenu,
             "menu_open" => {
                 let name = args.get("name")?.as_str()?;
                 Self::MenuOpen(name.to_string())
@@ -862,6 +1044,21 @@ impl Action {
             "to_lower_case" => Self::ToLowerCase,
             "sort_lines" => Self::SortLines,
 
+            // Reflow
+            "reflow_paragraph" => Self::ReflowParagraph,
+            "reflow_selection" => Self::ReflowSelection,
+            "evaluate_selection" => Self::EvaluateSelection,
+            "evaluate_and_replace_selection" => Self::EvaluateAndReplaceSelection,
+            "generate_doc_comment" => Self::GenerateDocComment,
+
+            // Locale-aware insertion
+            "insert_date" => Self::InsertDate,
+            "insert_time" => Self::InsertTime,
+
+            // Unicode input
+            "insert_unicode_character" => Self::InsertUnicodeCharacter,
+            "insert_digraph" => Self::InsertDigraph,
+
             // Input calibration
             "calibrate_input" => Self::CalibrateInput,
 
@@ -897,12 +1094,17 @@ impl Action {
                 | Action::MoveWordLeft
                 | Action::MoveWordRight
                 | Action::MoveWordEnd
+                | Action::MoveSubwordLeft
+                | Action::MoveSubwordRight
+                | Action::MoveSubwordEnd
                 | Action::MoveLineStart
                 | Action::MoveLineEnd
                 | Action::MovePageUp
                 | Action::MovePageDown
                 | Action::MoveDocumentStart
                 | Action::MoveDocumentEnd
+                | Action::GoToIndentBlockStart
+                | Action::GoToIndentBlockEnd
                 // Selection actions
                 | Action::SelectLeft
                 | Action::SelectRight
@@ -913,6 +1115,9 @@ impl Action {
                 | Action::SelectWordLeft
                 | Action::SelectWordRight
                 | Action::SelectWordEnd
+                | Action::SelectSubwordLeft
+                | Action::SelectSubwordRight
+                | Action::SelectSubwordEnd
                 | Action::SelectLineStart
                 | Action::SelectLineEnd
                 | Action::SelectDocumentStart
@@ -922,6 +1127,7 @@ impl Action {
                 | Action::SelectAll
                 | Action::SelectWord
                 | Action::SelectLine
+                | Action::SelectIndentBlock
                 | Action::ExpandSelection
                 // Block selection
                 | Action::BlockSelectLeft
@@ -936,6 +1142,8 @@ impl Action {
                 | Action::DeleteForward
                 | Action::DeleteWordBackward
                 | Action::DeleteWordForward
+                | Action::DeleteSubwordBackward
+                | Action::DeleteSubwordForward
                 | Action::DeleteLine
                 | Action::DeleteToLineEnd
                 | Action::DeleteToLineStart
@@ -962,6 +1170,8 @@ impl Action {
                 | Action::DeleteForward
                 | Action::DeleteWordBackward
                 | Action::DeleteWordForward
+                | Action::DeleteSubwordBackward
+                | Action::DeleteSubwordForward
                 | Action::DeleteLine
                 | Action::DeleteToLineEnd
                 | Action::DeleteToLineStart
@@ -971,6 +1181,22 @@ impl Action {
                 | Action::Paste
         )
     }
+
+    /// The camelCase/snake_case aware counterpart of a word motion, used when
+    /// `editor.subword_navigation` makes subwords the default word motion.
+    pub fn subword_variant(&self) -> Option<Action> {
+        Some(match self {
+            Action::MoveWordLeft => Action::MoveSubwordLeft,
+            Action::MoveWordRight => Action::MoveSubwordRight,
+            Action::MoveWordEnd => Action::MoveSubwordEnd,
+            Action::SelectWordLeft => Action::SelectSubwordLeft,
+            Action::SelectWordRight => Action::SelectSubwordRight,
+            Action::SelectWordEnd => Action::SelectSubwordEnd,
+            Action::DeleteWordBackward => Action::DeleteSubwordBackward,
+            Action::DeleteWordForward => Action::DeleteSubwordForward,
+            _ => return None,
+        })
+    }
 }
 
 /// Result of chord resolution
@@ -1168,6 +1394,7 @@ impl KeybindingResolver {
                 | Action::SaveAs
                 | Action::ShowHelp
                 | Action::ShowKeyboardShortcuts
+                | Action::ToggleCheatSheet
                 | Action::PromptCancel  // Esc should always cancel
                 | Action::PopupCancel // Esc should always cancel
         )
@@ -1187,6 +1414,7 @@ impl KeybindingResolver {
                 | Action::MenuOpen(_)
                 | Action::ShowHelp
                 | Action::ShowKeyboardShortcuts
+                | Action::ToggleCheatSheet
                 | Action::Quit
                 | Action::ForceQuit
                 // Split navigation
@@ -1196,9 +1424,12 @@ impl KeybindingResolver {
                 | Action::SplitVertical
                 | Action::CloseSplit
                 | Action::ToggleMaximizeSplit
+                | Action::ToggleZoomSplit
                 // Tab/buffer navigation
                 | Action::NextBuffer
                 | Action::PrevBuffer
+                | Action::NextTabMru
+                | Action::PrevTabMru
                 | Action::Close
                 | Action::ScrollTabsLeft
                 | Action::ScrollTabsRight
@@ -1487,6 +1718,7 @@ impl KeybindingResolver {
             KeyCode::Backspace => (2, 2),
             KeyCode::Delete => (2, 3),
             KeyCode::Esc => (2, 4),
+            KeyCode::Insert => (2, 5),
             KeyCode::Left => (3, 0),
             KeyCode::Right => (3, 1),
             KeyCode::Up => (3, 2),
@@ -1527,6 +1759,12 @@ impl KeybindingResolver {
         None
     }
 
+    /// Parse the `key` and `modifiers` of a config keybinding entry into the
+    /// key combination it matches
+    pub fn parse_binding(key: &str, modifiers: &[String]) -> Option<(KeyCode, KeyModifiers)> {
+        Some((Self::parse_key(key)?, Self::parse_modifiers(modifiers)))
+    }
+
     /// Parse a key string to KeyCode
     fn parse_key(key: &str) -> Option<KeyCode> {
         let lower = key.to_lowercase();
@@ -1534,6 +1772,7 @@ impl KeybindingResolver {
             "enter" => Some(KeyCode::Enter),
             "backspace" => Some(KeyCode::Backspace),
             "delete" | "del" => Some(KeyCode::Delete),
+            "insert" | "ins" => Some(KeyCode::Insert),
             "tab" => Some(KeyCode::Tab),
             "backtab" => Some(KeyCode::BackTab),
             "esc" | "escape" => Some(KeyCode::Esc),
@@ -1619,13 +1858,60 @@ impl KeybindingResolver {
         bindings
     }
 
+    /// Get the effective bindings for a single context (used by the cheat sheet)
+    ///
+    /// Merges default and custom bindings of the context with the Global context,
+    /// following resolution priority (custom over default, Global over context).
+    /// Chord sequences are included with their keys separated by spaces.
+    /// Returns (key_description, action) pairs; explicit unbindings are skipped.
+    pub fn get_context_bindings(&self, context: KeyContext) -> Vec<(String, Action)> {
+        let mut contexts = vec![context];
+        if context != KeyContext::Global {
+            contexts.push(KeyContext::Global);
+        }
+
+        // Apply from lowest to highest priority so later inserts win
+        let mut keys: HashMap<(KeyCode, KeyModifiers), Action> = HashMap::new();
+        let mut chords: HashMap<Vec<(KeyCode, KeyModifiers)>, Action> = HashMap::new();
+        for ctx in &contexts {
+            for layer in [&self.default_bindings, &self.bindings] {
+                if let Some(map) = layer.get(ctx) {
+                    keys.extend(map.iter().map(|(k, a)| (*k, a.clone())));
+                }
+            }
+            for layer in [&self.default_chord_bindings, &self.chord_bindings] {
+                if let Some(map) = layer.get(ctx) {
+                    chords.extend(map.iter().map(|(k, a)| (k.clone(), a.clone())));
+                }
+            }
+        }
+
+        let single = keys
+            .into_iter()
+            .map(|((code, modifiers), action)| (Self::format_key(code, modifiers), action));
+        let chorded = chords.into_iter().map(|(sequence, action)| {
+            let keys: Vec<String> = sequence
+                .iter()
+                .map(|(code, modifiers)| Self::format_key(*code, *modifiers))
+                .collect();
+            (keys.join(" "), action)
+        });
+
+        let mut bindings: Vec<(String, Action)> = single
+            .chain(chorded)
+            .filter(|(_, action)| !matches!(action, Action::None))
+            .collect();
+        bindings.sort_by(|a, b| a.0.cmp(&b.0));
+        bindings
+    }
+
     /// Format a key combination as a readable string
     fn format_key(key_code: KeyCode, modifiers: KeyModifiers) -> String {
         format_keybinding(&key_code, &modifiers)
     }
 
     /// Format an action as a readable description
-    fn format_action(action: &Action) -> String {
+    pub fn format_action(action: &Action) -> String {
         match action {
             Action::InsertChar(c) => t!("action.insert_char", char = c),
             Action::InsertNewline => t!("action.insert_newline"),
@@ -1637,6 +1923,9 @@ impl KeybindingResolver {
             Action::MoveWordLeft => t!("action.move_word_left"),
             Action::MoveWordRight => t!("action.move_word_right"),
             Action::MoveWordEnd => t!("action.move_word_end"),
+            Action::MoveSubwordLeft => t!("action.move_subword_left"),
+            Action::MoveSubwordRight => t!("action.move_subword_right"),
+            Action::MoveSubwordEnd => t!("action.move_subword_end"),
             Action::MoveLineStart => t!("action.move_line_start"),
             Action::MoveLineEnd => t!("action.move_line_end"),
             Action::MovePageUp => t!("action.move_page_up"),
@@ -1652,6 +1941,9 @@ impl KeybindingResolver {
             Action::SelectWordLeft => t!("action.select_word_left"),
             Action::SelectWordRight => t!("action.select_word_right"),
             Action::SelectWordEnd => t!("action.select_word_end"),
+            Action::SelectSubwordLeft => t!("action.select_subword_left"),
+            Action::SelectSubwordRight => t!("action.select_subword_right"),
+            Action::SelectSubwordEnd => t!("action.select_subword_end"),
             Action::SelectLineStart => t!("action.select_line_start"),
             Action::SelectLineEnd => t!("action.select_line_end"),
             Action::SelectDocumentStart => t!("action.select_document_start"),
@@ -1661,6 +1953,7 @@ impl KeybindingResolver {
             Action::SelectAll => t!("action.select_all"),
             Action::SelectWord => t!("action.select_word"),
             Action::SelectLine => t!("action.select_line"),
+            Action::SelectIndentBlock => t!("action.select_indent_block"),
             Action::ExpandSelection => t!("action.expand_selection"),
             Action::BlockSelectLeft => t!("action.block_select_left"),
             Action::BlockSelectRight => t!("action.block_select_right"),
@@ -1670,6 +1963,8 @@ impl KeybindingResolver {
             Action::DeleteForward => t!("action.delete_forward"),
             Action::DeleteWordBackward => t!("action.delete_word_backward"),
             Action::DeleteWordForward => t!("action.delete_word_forward"),
+            Action::DeleteSubwordBackward => t!("action.delete_subword_backward"),
+            Action::DeleteSubwordForward => t!("action.delete_subword_forward"),
             Action::DeleteLine => t!("action.delete_line"),
             Action::DeleteToLineEnd => t!("action.delete_to_line_end"),
             Action::DeleteToLineStart => t!("action.delete_to_line_start"),
@@ -1680,8 +1975,17 @@ impl KeybindingResolver {
             Action::Copy => t!("action.copy"),
             Action::CopyWithTheme(theme) if theme.is_empty() => t!("action.copy_with_formatting"),
             Action::CopyWithTheme(theme) => t!("action.copy_with_theme", theme = theme),
+            Action::CopyAsMarkdown => t!("action.copy_as_markdown"),
+            Action::CopyWithReference => t!("action.copy_with_reference"),
+            Action::CopyPermalink => t!("action.copy_permalink"),
+            Action::CopyRelativePath => t!("action.copy_relative_path"),
+            Action::CopyAbsolutePath => t!("action.copy_absolute_path"),
+            Action::CopyFileName => t!("action.copy_file_name"),
             Action::Cut => t!("action.cut"),
             Action::Paste => t!("action.paste"),
+            Action::PasteFromClipboardHistory => t!("action.paste_from_clipboard_history"),
+            Action::ClipboardHistoryTogglePin => t!("action.clipboard_history_toggle_pin"),
+            Action::ForgetClipboardHistory => t!("action.forget_clipboard_history"),
             Action::YankWordForward => t!("action.yank_word_forward"),
             Action::YankWordBackward => t!("action.yank_word_backward"),
             Action::YankToLineEnd => t!("action.yank_to_line_end"),
@@ -1694,6 +1998,11 @@ impl KeybindingResolver {
             Action::SaveAs => t!("action.save_as"),
             Action::Open => t!("action.open"),
             Action::SwitchProject => t!("action.switch_project"),
+            Action::OpenRecentWorkspace => t!("action.open_recent_workspace"),
+            Action::OpenRecentWorkspaceInNewProcess => {
+                t!("action.open_recent_workspace_new_process")
+            }
+            Action::TogglePinWorkspace => t!("action.toggle_pin_workspace"),
             Action::New => t!("action.new"),
             Action::Close => t!("action.close"),
             Action::CloseTab => t!("action.close_tab"),
@@ -1701,11 +2010,16 @@ impl KeybindingResolver {
             Action::ForceQuit => t!("action.force_quit"),
             Action::Revert => t!("action.revert"),
             Action::ToggleAutoRevert => t!("action.toggle_auto_revert"),
+            Action::ReopenWithEncoding => t!("action.reopen_with_encoding"),
+            Action::ToggleHexView => t!("action.toggle_hex_view"),
+            Action::ToggleAnsiColors => t!("action.toggle_ansi_colors"),
             Action::FormatBuffer => t!("action.format_buffer"),
             Action::TrimTrailingWhitespace => t!("action.trim_trailing_whitespace"),
             Action::EnsureFinalNewline => t!("action.ensure_final_newline"),
             Action::GotoLine => t!("action.goto_line"),
             Action::GoToMatchingBracket => t!("action.goto_matching_bracket"),
+            Action::GoToIndentBlockStart => t!("action.goto_indent_block_start"),
+            Action::GoToIndentBlockEnd => t!("action.goto_indent_block_end"),
             Action::JumpToNextError => t!("action.jump_to_next_error"),
             Action::JumpToPreviousError => t!("action.jump_to_previous_error"),
             Action::SmartHome => t!("action.smart_home"),
@@ -1732,13 +2046,20 @@ impl KeybindingResolver {
             Action::PromptJumpToBookmark => t!("action.prompt_jump_to_bookmark"),
             Action::Undo => t!("action.undo"),
             Action::Redo => t!("action.redo"),
+            Action::ShowUndoTree => t!("action.show_undo_tree"),
+            Action::UndoTreeSwitchBranch => t!("action.undo_tree_switch_branch"),
             Action::ScrollUp => t!("action.scroll_up"),
             Action::ScrollDown => t!("action.scroll_down"),
             Action::ShowHelp => t!("action.show_help"),
             Action::ShowKeyboardShortcuts => t!("action.show_keyboard_shortcuts"),
+            Action::ToggleCheatSheet => t!("action.toggle_cheat_sheet"),
             Action::ShowWarnings => t!("action.show_warnings"),
             Action::ShowStatusLog => t!("action.show_status_log"),
             Action::ShowLspStatus => t!("action.show_lsp_status"),
+            Action::ShowMemoryUsage => t!("action.show_memory_usage"),
+            Action::ShowPaths => t!("action.show_paths"),
+            Action::ShowRemoteAuditLog => t!("action.show_remote_audit_log"),
+            Action::ExportRemoteAuditLog => t!("action.export_remote_audit_log"),
             Action::ClearWarnings => t!("action.clear_warnings"),
             Action::CommandPalette => t!("action.command_palette"),
             Action::QuickOpen => t!("action.quick_open"),
@@ -1757,6 +2078,10 @@ impl KeybindingResolver {
             Action::IncreaseSplitSize => t!("action.increase_split_size"),
             Action::DecreaseSplitSize => t!("action.decrease_split_size"),
             Action::ToggleMaximizeSplit => t!("action.toggle_maximize_split"),
+            Action::ToggleZoomSplit => t!("action.toggle_zoom_split"),
+            Action::ToggleScrollSync => t!("action.toggle_scroll_sync"),
+            Action::SyncScrollAllSplits => t!("action.sync_scroll_all_splits"),
+            Action::StopScrollSync => t!("action.stop_scroll_sync"),
             Action::PromptConfirm => t!("action.prompt_confirm"),
             Action::PromptConfirmWithText(ref text) => {
                 format!("{} ({})", t!("action.prompt_confirm"), text).into()
@@ -1795,9 +2120,14 @@ impl KeybindingResolver {
             Action::PopupPageDown => t!("action.popup_page_down"),
             Action::PopupConfirm => t!("action.popup_confirm"),
             Action::PopupCancel => t!("action.popup_cancel"),
+            Action::PinPopup => t!("action.pin_popup"),
+            Action::FocusFloatingWindow(_) => t!("action.focus_floating_window"),
+            Action::CloseFloatingWindow => t!("action.close_floating_window"),
             Action::ToggleFileExplorer => t!("action.toggle_file_explorer"),
             Action::ToggleMenuBar => t!("action.toggle_menu_bar"),
             Action::ToggleTabBar => t!("action.toggle_tab_bar"),
+            Action::ToggleAbsolutePaths => t!("action.toggle_absolute_paths"),
+            Action::ToggleOverwriteMode => t!("action.toggle_overwrite_mode"),
             Action::FocusFileExplorer => t!("action.focus_file_explorer"),
             Action::FocusEditor => t!("action.focus_editor"),
             Action::FileExplorerUp => t!("action.file_explorer_up"),
@@ -1823,6 +2153,7 @@ impl KeybindingResolver {
             Action::LspCodeActions => t!("action.lsp_code_actions"),
             Action::LspRestart => t!("action.lsp_restart"),
             Action::LspStop => t!("action.lsp_stop"),
+            Action::LspDetectServers => t!("action.lsp_detect_servers"),
             Action::ToggleInlayHints => t!("action.toggle_inlay_hints"),
             Action::ToggleMouseHover => t!("action.toggle_mouse_hover"),
             Action::ToggleLineNumbers => t!("action.toggle_line_numbers"),
@@ -1837,6 +2168,8 @@ impl KeybindingResolver {
             Action::ToggleTabIndicators => t!("action.toggle_tab_indicators"),
             Action::ResetBufferSettings => t!("action.reset_buffer_settings"),
             Action::DumpConfig => t!("action.dump_config"),
+            Action::SafeModeReenable => t!("action.safe_mode_reenable"),
+            Action::ReloadPlugin => t!("action.reload_plugin"),
             Action::Search => t!("action.search"),
             Action::FindInSelection => t!("action.find_in_selection"),
             Action::FindNext => t!("action.find_next"),
@@ -1845,6 +2178,9 @@ impl KeybindingResolver {
             Action::FindSelectionPrevious => t!("action.find_selection_previous"),
             Action::Replace => t!("action.replace"),
             Action::QueryReplace => t!("action.query_replace"),
+            Action::SearchInProject => t!("action.search_in_project"),
+            Action::ProjectSearchOpenResult => t!("action.project_search_open_result"),
+            Action::ProjectSearchCancel => t!("action.project_search_cancel"),
             Action::MenuActivate => t!("action.menu_activate"),
             Action::MenuClose => t!("action.menu_close"),
             Action::MenuLeft => t!("action.menu_left"),
@@ -1853,16 +2189,22 @@ impl KeybindingResolver {
             Action::MenuDown => t!("action.menu_down"),
             Action::MenuExecute => t!("action.menu_execute"),
             Action::MenuOpen(name) => t!("action.menu_open", name = name),
+            Action::ShowContextMenu => t!("action.show_context_menu"),
             Action::SwitchKeybindingMap(map) => t!("action.switch_keybinding_map", map = map),
             Action::PluginAction(name) => t!("action.plugin_action", name = name),
             Action::ScrollTabsLeft => t!("action.scroll_tabs_left"),
             Action::ScrollTabsRight => t!("action.scroll_tabs_right"),
             Action::SelectTheme => t!("action.select_theme"),
+            Action::SelectSplitTheme => t!("action.select_split_theme"),
+            Action::ImportBase16Theme => t!("action.import_base16_theme"),
             Action::SelectKeybindingMap => t!("action.select_keybinding_map"),
             Action::SelectCursorStyle => t!("action.select_cursor_style"),
             Action::SelectLocale => t!("action.select_locale"),
             Action::SwitchToPreviousTab => t!("action.switch_to_previous_tab"),
             Action::SwitchToTabByName => t!("action.switch_to_tab_by_name"),
+            Action::NextTabMru => t!("action.next_tab_mru"),
+            Action::PrevTabMru => t!("action.prev_tab_mru"),
+            Action::MoveTabToSplit => t!("action.move_tab_to_split"),
             Action::OpenTerminal => t!("action.open_terminal"),
             Action::CloseTerminal => t!("action.close_terminal"),
             Action::FocusTerminal => t!("action.focus_terminal"),
@@ -1884,6 +2226,15 @@ impl KeybindingResolver {
             Action::ToUpperCase => t!("action.to_uppercase"),
             Action::ToLowerCase => t!("action.to_lowercase"),
             Action::SortLines => t!("action.sort_lines"),
+            Action::ReflowParagraph => t!("action.reflow_paragraph"),
+            Action::ReflowSelection => t!("action.reflow_selection"),
+            Action::EvaluateSelection => t!("action.evaluate_selection"),
+            Action::EvaluateAndReplaceSelection => t!("action.evaluate_and_replace_selection"),
+            Action::GenerateDocComment => t!("action.generate_doc_comment"),
+            Action::InsertDate => t!("action.insert_date"),
+            Action::InsertTime => t!("action.insert_time"),
+            Action::InsertUnicodeCharacter => t!("action.insert_unicode_character"),
+            Action::InsertDigraph => t!("action.insert_digraph"),
             Action::CalibrateInput => t!("action.calibrate_input"),
             Action::EventDebug => t!("action.event_debug"),
             Action::None => t!("action.none"),
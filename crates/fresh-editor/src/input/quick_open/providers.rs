@@ -9,7 +9,9 @@
 use super::{QuickOpenContext, QuickOpenProvider, QuickOpenResult};
 use crate::input::commands::Suggestion;
 use crate::input::fuzzy::fuzzy_match;
+use crate::services::suggestion_filter::FilterSource;
 use rust_i18n::t;
+use std::sync::Arc;
 
 // ============================================================================
 // Command Provider (prefix: ">")
@@ -325,7 +327,7 @@ impl QuickOpenProvider for GotoLineProvider {
 /// using git ls-files, fd, find, or directory traversal.
 pub struct FileProvider {
     /// Cached file list (populated lazily)
-    file_cache: std::sync::Arc<std::sync::RwLock<Option<Vec<FileEntry>>>>,
+    file_cache: std::sync::Arc<std::sync::RwLock<Option<Arc<Vec<FileEntry>>>>>,
     /// Frecency data for ranking
     frecency: std::sync::Arc<std::sync::RwLock<std::collections::HashMap<String, FrecencyData>>>,
 }
@@ -336,6 +338,46 @@ struct FileEntry {
     frecency_score: f64,
 }
 
+impl FileEntry {
+    /// Fuzzy match score of the path, boosted by frecency
+    fn score(&self, query: &str) -> Option<i32> {
+        let match_result = fuzzy_match(query, &self.relative_path);
+        // Boost score by frecency (normalized)
+        let frecency_boost = (self.frecency_score / 100.0).min(20.0) as i32;
+        match_result
+            .matched
+            .then_some(match_result.score + frecency_boost)
+    }
+
+    fn suggestion(&self) -> Suggestion {
+        Suggestion {
+            text: self.relative_path.clone(),
+            description: None,
+            value: Some(self.relative_path.clone()),
+            disabled: false,
+            keybinding: None,
+            source: None,
+        }
+    }
+}
+
+/// The project's files, for filtering in the background
+struct FileCandidates(Arc<Vec<FileEntry>>);
+
+impl FilterSource for FileCandidates {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    fn score(&self, query: &str, index: usize) -> Option<i32> {
+        self.0[index].score(query)
+    }
+
+    fn suggestion(&self, index: usize) -> Suggestion {
+        self.0[index].suggestion()
+    }
+}
+
 #[derive(Clone)]
 struct FrecencyData {
     access_count: u32,
@@ -343,6 +385,9 @@ struct FrecencyData {
 }
 
 impl FileProvider {
+    /// Most files listed for a query
+    pub const MAX_RESULTS: usize = 100;
+
     pub fn new() -> Self {
         Self {
             file_cache: std::sync::Arc::new(std::sync::RwLock::new(None)),
@@ -357,6 +402,19 @@ impl FileProvider {
         }
     }
 
+    /// Approximate memory held by the file cache, in bytes
+    pub fn cache_bytes(&self) -> usize {
+        let Ok(cache) = self.file_cache.read() else {
+            return 0;
+        };
+        cache.as_ref().map_or(0, |files| {
+            files
+                .iter()
+                .map(|f| std::mem::size_of::<FileEntry>() + f.relative_path.capacity())
+                .sum()
+        })
+    }
+
     /// Record file access for frecency ranking
     pub fn record_access(&self, path: &str) {
         if let Ok(mut frecency) = self.frecency.write() {
@@ -395,12 +453,18 @@ impl FileProvider {
         0.0
     }
 
+    /// The project's files as candidates for background filtering, scored
+    /// the same way as `suggestions` scores them
+    pub fn filter_source(&self, cwd: &str) -> Arc<dyn FilterSource> {
+        Arc::new(FileCandidates(self.load_files(cwd)))
+    }
+
     /// Load files from the project directory
-    fn load_files(&self, cwd: &str) -> Vec<FileEntry> {
+    fn load_files(&self, cwd: &str) -> Arc<Vec<FileEntry>> {
         // Check cache first
         if let Ok(cache) = self.file_cache.read() {
             if let Some(files) = cache.as_ref() {
-                return files.clone();
+                return Arc::clone(files);
             }
         }
 
@@ -412,17 +476,19 @@ impl FileProvider {
             .unwrap_or_else(Vec::new);
 
         // Add frecency scores
-        let files: Vec<FileEntry> = files
-            .into_iter()
-            .map(|path| FileEntry {
-                frecency_score: self.get_frecency_score(&path),
-                relative_path: path,
-            })
-            .collect();
+        let files: Arc<Vec<FileEntry>> = Arc::new(
+            files
+                .into_iter()
+                .map(|path| FileEntry {
+                    frecency_score: self.get_frecency_score(&path),
+                    relative_path: path,
+                })
+                .collect(),
+        );
 
         // Update cache
         if let Ok(mut cache) = self.file_cache.write() {
-            *cache = Some(files.clone());
+            *cache = Some(Arc::clone(&files));
         }
 
         files
@@ -547,52 +613,36 @@ impl QuickOpenProvider for FileProvider {
             }];
         }
 
-        let max_results = 100;
-
-        let mut scored_files: Vec<(FileEntry, i32)> = if query.is_empty() {
+        let mut scored_files: Vec<(usize, i32)> = if query.is_empty() {
             // Sort by frecency when no query
-            let mut files = files;
-            files.sort_by(|a, b| {
-                b.frecency_score
-                    .partial_cmp(&a.frecency_score)
+            let mut order: Vec<usize> = (0..files.len()).collect();
+            order.sort_by(|&a, &b| {
+                files[b]
+                    .frecency_score
+                    .partial_cmp(&files[a].frecency_score)
                     .unwrap_or(std::cmp::Ordering::Equal)
             });
-            files
+            order
                 .into_iter()
-                .take(max_results)
-                .map(|f| (f, 0))
+                .take(Self::MAX_RESULTS)
+                .map(|index| (index, 0))
                 .collect()
         } else {
             // Filter and score by fuzzy match
             files
-                .into_iter()
-                .filter_map(|file| {
-                    let match_result = fuzzy_match(query, &file.relative_path);
-                    if match_result.matched {
-                        // Boost score by frecency (normalized)
-                        let frecency_boost = (file.frecency_score / 100.0).min(20.0) as i32;
-                        Some((file, match_result.score + frecency_boost))
-                    } else {
-                        None
-                    }
-                })
+                .iter()
+                .enumerate()
+                .filter_map(|(index, file)| file.score(query).map(|score| (index, score)))
                 .collect()
         };
 
         // Sort by score
         scored_files.sort_by(|a, b| b.1.cmp(&a.1));
-        scored_files.truncate(max_results);
+        scored_files.truncate(Self::MAX_RESULTS);
 
         scored_files
             .into_iter()
-            .map(|(file, _)| Suggestion {
-                text: file.relative_path.clone(),
-                description: None,
-                value: Some(file.relative_path),
-                disabled: false,
-                keybinding: None,
-                source: None,
-            })
+            .map(|(index, _)| files[index].suggestion())
             .collect()
     }
 
@@ -1,7 +1,12 @@
 // Editor library - exposes all core modules for testing
 
-// Initialize i18n with translations from locales/ directory
-rust_i18n::i18n!("locales", fallback = "en");
+// Initialize i18n with translations from locales/ directory. The macro is
+// pointed at no files: `i18n::embedded_backend` parses them at runtime instead.
+rust_i18n::i18n!(
+    "locales/none",
+    fallback = "en",
+    backend = crate::i18n::embedded_backend()
+);
 
 pub mod i18n;
 
@@ -31,6 +36,10 @@ pub mod input;
 #[cfg(feature = "runtime")]
 pub mod services;
 
+// Headless harness for driving the editor from tests (require the "testing" feature)
+#[cfg(all(feature = "runtime", any(test, feature = "testing")))]
+pub mod testing;
+
 // View module - available for runtime, WASM, and dev-bins (schema generation)
 // Most submodules are runtime-only, but theme types are always available
 #[cfg(any(feature = "runtime", feature = "wasm", feature = "dev-bins"))]
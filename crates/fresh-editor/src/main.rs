@@ -7,7 +7,7 @@ use crossterm::event::{
 use fresh::input::key_translator::KeyTranslator;
 #[cfg(target_os = "linux")]
 use fresh::services::gpm::{gpm_to_crossterm, GpmClient};
-use fresh::services::terminal_modes::{self, KeyboardConfig, TerminalModes};
+use fresh::services::terminal_modes::{KeyboardConfig, TerminalModes};
 use fresh::services::tracing_setup;
 use fresh::{
     app::Editor,
@@ -45,10 +45,29 @@ struct Args {
     #[arg(long)]
     no_plugins: bool,
 
+    /// Start with default settings, no plugins and only built-in themes, for
+    /// troubleshooting a broken configuration or plugin
+    #[arg(long)]
+    safe_mode: bool,
+
     /// Path to configuration file
     #[arg(long, value_name = "PATH")]
     config: Option<PathBuf>,
 
+    /// Override a config value for this session only (e.g., editor.tab_size=2).
+    /// Can be repeated.
+    #[arg(long = "config-set", value_name = "KEY=VALUE")]
+    config_set: Vec<String>,
+
+    /// Use a theme for this session only
+    #[arg(long, value_name = "NAME")]
+    theme: Option<String>,
+
+    /// Layer a named profile (profiles/NAME.json in the config directory) over
+    /// the config. Defaults to $FRESH_PROFILE.
+    #[arg(long, value_name = "NAME", conflicts_with = "safe_mode")]
+    profile: Option<String>,
+
     /// Path to log file for editor diagnostics (default: system temp dir)
     #[arg(long, value_name = "PATH")]
     log_file: Option<PathBuf>,
@@ -119,6 +138,8 @@ struct IterationOutcome {
 
 struct SetupState {
     config: config::Config,
+    /// Profile and command-line overrides, kept so config reloads re-apply them
+    config_overrides: fresh::config_io::ConfigOverrides,
     tracing_handles: Option<TracingHandles>,
     terminal: Terminal<ratatui::backend::CrosstermBackend<io::Stdout>>,
     terminal_size: (u16, u16),
@@ -135,6 +156,8 @@ struct SetupState {
     process_spawner: std::sync::Arc<dyn remote::ProcessSpawner>,
     /// Remote session resources - must be kept alive for remote editing
     _remote_session: Option<RemoteSession>,
+    /// Audit trail of remote writes and spawns (remote editing only)
+    audit_log: Option<remote::AuditLog>,
     /// Key translator for input calibration
     key_translator: KeyTranslator,
     #[cfg(target_os = "linux")]
@@ -526,6 +549,8 @@ struct FilesystemResult {
     process_spawner: std::sync::Arc<dyn remote::ProcessSpawner>,
     /// Remote session resources - must be kept alive for remote editing
     remote_session: Option<RemoteSession>,
+    /// Audit trail of remote writes and spawns (remote editing only)
+    audit_log: Option<remote::AuditLog>,
 }
 
 /// Create filesystem for local or remote editing
@@ -537,6 +562,7 @@ fn create_filesystem(remote_info: &Option<RemoteLocation>) -> AnyhowResult<Files
             filesystem: std::sync::Arc::new(StdFileSystem),
             process_spawner: std::sync::Arc::new(remote::LocalProcessSpawner),
             remote_session: None,
+            audit_log: None,
         })
     }
 }
@@ -567,11 +593,14 @@ fn connect_remote(remote: &RemoteLocation) -> AnyhowResult<FilesystemResult> {
 
     tracing::info!("Connected to remote host: {}", connection_string);
 
-    let filesystem = std::sync::Arc::new(remote::RemoteFileSystem::new(
-        channel.clone(),
-        connection_string,
-    ));
-    let process_spawner = std::sync::Arc::new(remote::RemoteProcessSpawner::new(channel));
+    let audit_log = remote::AuditLog::new();
+    let filesystem = std::sync::Arc::new(
+        remote::RemoteFileSystem::new(channel.clone(), connection_string)
+            .with_audit_log(audit_log.clone()),
+    );
+    let process_spawner = std::sync::Arc::new(
+        remote::RemoteProcessSpawner::new(channel).with_audit_log(audit_log.clone()),
+    );
 
     Ok(FilesystemResult {
         filesystem,
@@ -580,9 +609,52 @@ fn connect_remote(remote: &RemoteLocation) -> AnyhowResult<FilesystemResult> {
             _connection: connection,
             _runtime: rt,
         }),
+        audit_log: Some(audit_log),
     })
 }
 
+/// Load the profile (`--profile` or `$FRESH_PROFILE`) and the `--config-set` /
+/// `--theme` overrides layered over the config for this session.
+fn load_config_overrides(
+    args: &Args,
+    dir_context: &DirectoryContext,
+) -> AnyhowResult<fresh::config_io::ConfigOverrides> {
+    let profile = args.profile.clone().or_else(|| {
+        // Safe mode ignores every config file, profiles included
+        if args.safe_mode {
+            return None;
+        }
+        std::env::var("FRESH_PROFILE")
+            .ok()
+            .filter(|name| !name.is_empty())
+    });
+
+    let mut settings = Vec::with_capacity(args.config_set.len() + 1);
+    for arg in &args.config_set {
+        match fresh::config_io::parse_config_setting(arg) {
+            Ok(setting) => settings.push(setting),
+            Err(e) => {
+                eprintln!("Error: Invalid --config-set '{}': {}", arg, e);
+                anyhow::bail!("Invalid --config-set '{}': {}", arg, e);
+            }
+        }
+    }
+    if let Some(theme) = &args.theme {
+        settings.push((
+            "theme".to_string(),
+            serde_json::Value::String(theme.clone()),
+        ));
+    }
+
+    match fresh::config_io::ConfigOverrides::load(dir_context, profile.as_deref(), &settings) {
+        Ok(overrides) => Ok(overrides),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            anyhow::bail!(io::Error::new(io::ErrorKind::InvalidData, e.to_string()));
+        }
+    }
+}
+
 fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     let log_file = args
         .log_file
@@ -598,11 +670,11 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
     signal_handler::install_signal_handlers();
     tracing::info!("Signal handlers installed");
 
-    let original_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(move |panic| {
-        terminal_modes::emergency_cleanup();
-        original_hook(panic);
-    }));
+    fresh::services::crash_report::install_panic_hook(
+        DirectoryContext::from_system()
+            .ok()
+            .map(|dirs| dirs.crash_reports_dir()),
+    );
 
     // Check if we should read from stdin
     // This can be triggered by --stdin flag or by using "-" as a file argument
@@ -705,6 +777,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         filesystem,
         process_spawner,
         remote_session,
+        audit_log,
     } = create_filesystem(&remote_info)?;
 
     let mut working_dir = None;
@@ -736,7 +809,10 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     let dir_context = fresh::config_io::DirectoryContext::from_system()?;
 
-    let mut config = if let Some(config_path) = &args.config {
+    let mut config = if args.safe_mode {
+        // Safe mode ignores every config file until re-enabled from inside the editor
+        config::Config::default()
+    } else if let Some(config_path) = &args.config {
         // Explicit config file overrides layered system
         match config::Config::load_from_file(config_path) {
             Ok(cfg) => cfg,
@@ -753,6 +829,9 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         config::Config::load_with_layers(&dir_context, &effective_working_dir)
     };
 
+    let config_overrides = load_config_overrides(args, &dir_context)?;
+    config = config_overrides.apply(config);
+
     // CLI flag overrides config
     if args.no_upgrade_check {
         config.check_for_updates = false;
@@ -788,11 +867,6 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         tracing::info!("Using GPM for mouse capture");
     }
 
-    // Set cursor style from config
-    use crossterm::ExecutableCommand;
-    let _ = stdout().execute(config.editor.cursor_style.to_crossterm_style());
-    tracing::info!("Set cursor style to {:?}", config.editor.cursor_style);
-
     let backend = ratatui::backend::CrosstermBackend::new(stdout());
     let mut terminal = Terminal::new(backend)?;
     terminal.clear()?;
@@ -814,6 +888,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
 
     Ok(SetupState {
         config,
+        config_overrides,
         tracing_handles,
         terminal,
         terminal_size: (size.width, size.height),
@@ -828,6 +903,7 @@ fn initialize_app(args: &Args) -> AnyhowResult<SetupState> {
         filesystem,
         process_spawner,
         _remote_session: remote_session,
+        audit_log,
     })
 }
 
@@ -1495,7 +1571,8 @@ fn main() -> AnyhowResult<()> {
 
     // Handle --show-paths early (no terminal setup needed)
     if args.show_paths {
-        fresh::services::log_dirs::print_all_paths();
+        let dir_context = fresh::config_io::DirectoryContext::from_system()?;
+        fresh::services::log_dirs::print_all_paths(&dir_context);
         return Ok(());
     }
 
@@ -1503,7 +1580,10 @@ fn main() -> AnyhowResult<()> {
     if args.dump_config {
         let dir_context = fresh::config_io::DirectoryContext::from_system()?;
         let working_dir = std::env::current_dir().unwrap_or_default();
-        let config = if let Some(config_path) = &args.config {
+        let config_overrides = load_config_overrides(&args, &dir_context)?;
+        let config = if args.safe_mode {
+            config::Config::default()
+        } else if let Some(config_path) = &args.config {
             match config::Config::load_from_file(config_path) {
                 Ok(cfg) => cfg,
                 Err(e) => {
@@ -1522,6 +1602,7 @@ fn main() -> AnyhowResult<()> {
         } else {
             config::Config::load_with_layers(&dir_context, &working_dir)
         };
+        let config = config_overrides.apply(config);
 
         // Pretty-print the config as JSON
         match serde_json::to_string_pretty(&config) {
@@ -1548,7 +1629,8 @@ fn main() -> AnyhowResult<()> {
     }
 
     let SetupState {
-        config,
+        mut config,
+        config_overrides,
         mut tracing_handles,
         mut terminal,
         terminal_size,
@@ -1566,6 +1648,7 @@ fn main() -> AnyhowResult<()> {
         filesystem,
         process_spawner,
         _remote_session,
+        audit_log,
     } = initialize_app(&args).context("Failed to initialize application")?;
 
     let mut current_working_dir = initial_working_dir;
@@ -1577,6 +1660,9 @@ fn main() -> AnyhowResult<()> {
     // Track whether we should restore session on restart (for project switching)
     let mut restore_session_on_restart = false;
 
+    // Components still disabled by --safe-mode (carried across restarts)
+    let mut safe_mode = args.safe_mode.then(fresh::app::SafeMode::new);
+
     // Main editor loop - supports restarting with a new working directory
     // Returns (loop_result, last_update_result) tuple
     let (result, last_update_result) = loop {
@@ -1595,14 +1681,22 @@ fn main() -> AnyhowResult<()> {
             terminal_height,
             current_working_dir.clone(),
             dir_context.clone(),
-            !args.no_plugins,
+            !args.no_plugins && !safe_mode.is_some_and(|m| m.plugins_disabled),
             color_capability,
             fs,
         )
         .context("Failed to create editor instance")?;
 
+        if let Some(mode) = safe_mode {
+            editor.enable_safe_mode(mode);
+        }
+        editor.set_config_overrides(config_overrides.clone());
+
         // Set the process spawner (LocalProcessSpawner for local, RemoteProcessSpawner for remote)
         editor.set_process_spawner(process_spawner.clone());
+        if let Some(audit_log) = &audit_log {
+            editor.set_remote_audit_log(audit_log.clone());
+        }
 
         #[cfg(target_os = "linux")]
         if gpm_client.is_some() {
@@ -1643,6 +1737,12 @@ fn main() -> AnyhowResult<()> {
             editor.set_status_message(fresh::i18n::switched_to_project_message(&path));
         }
 
+        // Local directories the editor is opened on (rather than files) and
+        // projects switched to go in the Open Recent Workspace list
+        if editor.remote_connection_info().is_none() && (!first_run || file_locations.is_empty()) {
+            editor.record_recent_workspace();
+        }
+
         if let Err(e) = editor.start_recovery_session() {
             tracing::warn!("Failed to start recovery session: {}", e);
         }
@@ -1661,6 +1761,14 @@ fn main() -> AnyhowResult<()> {
         let restart_dir = iteration.restart_dir;
         let loop_result = iteration.loop_result;
 
+        // Keep config re-enabled from safe mode for the next editor instance
+        if let Some(mode) = editor.safe_mode() {
+            if safe_mode.is_some_and(|m| m.config_disabled) && !mode.config_disabled {
+                config = editor.config().clone();
+            }
+            safe_mode = Some(mode);
+        }
+
         drop(editor);
 
         if let Some(new_dir) = restart_dir {
@@ -1761,11 +1869,26 @@ where
     use std::time::Instant;
 
     const FRAME_DURATION: Duration = Duration::from_millis(16); // 60fps
+                                                                // While the terminal is unfocused (e.g. fresh sits in a background pane)
+                                                                // redraw and wake up less often; focus events bring the normal rate back
+    const UNFOCUSED_FRAME_DURATION: Duration = Duration::from_millis(250); // 4fps
     let mut last_render = Instant::now();
     let mut needs_render = true;
     let mut pending_event: Option<CrosstermEvent> = None;
 
     loop {
+        // While a large save is being written, only draw its progress between
+        // chunks: input and async messages wait, so the buffer can't change
+        if editor.is_saving() {
+            editor.poll_save_in_progress();
+            if last_render.elapsed() >= FRAME_DURATION {
+                terminal.draw(|frame| editor.render(frame))?;
+                last_render = Instant::now();
+            }
+            needs_render = true;
+            continue;
+        }
+
         // Process async messages and poll for file changes (auto-revert, file tree)
         if editor.process_async_messages() {
             needs_render = true;
@@ -1786,6 +1909,21 @@ where
             needs_render = true;
         }
 
+        // Skip code actions on save the language server is too slow to answer
+        if editor.check_save_code_actions_timer() {
+            needs_render = true;
+        }
+
+        // Stop waiting for plugin formatters that never answer
+        if editor.check_plugin_format_timer() {
+            needs_render = true;
+        }
+
+        // Close plugin undo groups that were never ended
+        if editor.check_plugin_undo_group_timer() {
+            needs_render = true;
+        }
+
         // Check for warnings and open warning log if any occurred
         if editor.check_warning_log() {
             needs_render = true;
@@ -1817,8 +1955,18 @@ where
             break;
         }
 
-        if needs_render && last_render.elapsed() >= FRAME_DURATION {
+        let (frame_duration, idle_timeout) = if editor.is_terminal_focused() {
+            (FRAME_DURATION, Duration::from_millis(50))
+        } else {
+            (UNFOCUSED_FRAME_DURATION, UNFOCUSED_FRAME_DURATION)
+        };
+
+        if needs_render && last_render.elapsed() >= frame_duration {
             terminal.draw(|frame| editor.render(frame))?;
+            if let Some(style) = editor.take_cursor_style_change() {
+                // DECSCUSR: cursor shape follows insert/overwrite/modal modes
+                let _ = crossterm::execute!(stdout(), style.to_crossterm_style());
+            }
             last_render = Instant::now();
             needs_render = false;
         }
@@ -1827,9 +1975,9 @@ where
             Some(e)
         } else {
             let timeout = if needs_render {
-                FRAME_DURATION.saturating_sub(last_render.elapsed())
+                frame_duration.saturating_sub(last_render.elapsed())
             } else {
-                Duration::from_millis(50)
+                idle_timeout
             };
 
             poll_event(timeout)?
@@ -1877,7 +2025,14 @@ where
                 editor.paste_text(text);
                 needs_render = true;
             }
-            _ => {}
+            CrosstermEvent::FocusGained => {
+                editor.set_terminal_focused(true);
+                needs_render = true;
+            }
+            CrosstermEvent::FocusLost => {
+                editor.set_terminal_focused(false);
+                needs_render = true;
+            }
         }
     }
 
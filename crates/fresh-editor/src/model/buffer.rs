@@ -1,6 +1,7 @@
 /// Text buffer that uses PieceTree with integrated line tracking
 /// Architecture where the tree is the single source of truth for text and line information
-use crate::model::filesystem::{FileMetadata, FileSystem, WriteOp};
+use crate::model::encoding::{self, Encoder, Encoding};
+use crate::model::filesystem::{FileMetadata, FileSystem, FileWriter, WriteOp};
 use crate::model::piece_tree::{
     BufferData, BufferLocation, Cursor, PieceInfo, PieceRangeIter, PieceTree, Position,
     StringBuffer, TreeStats,
@@ -54,6 +55,9 @@ pub const LOAD_CHUNK_SIZE: usize = 1024 * 1024;
 /// Chunk alignment for lazy loading (64 KB)
 pub const CHUNK_ALIGNMENT: usize = 64 * 1024;
 
+/// Chunk size to stream to disk when saving (1 MB)
+pub const SAVE_CHUNK_SIZE: usize = 1024 * 1024;
+
 /// Line ending format used in the file
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum LineEnding {
@@ -86,6 +90,118 @@ impl LineEnding {
     }
 }
 
+/// Converts line endings of content streamed in chunks.
+///
+/// A trailing `\r` is held back until the next chunk, so a CRLF split across
+/// two chunks still converts to a single line ending.
+struct LineEndingConverter {
+    target: LineEnding,
+    pending_cr: bool,
+}
+
+impl LineEndingConverter {
+    fn new(target: LineEnding) -> Self {
+        Self {
+            target,
+            pending_cr: false,
+        }
+    }
+
+    /// Convert the next chunk
+    fn convert(&mut self, chunk: &[u8]) -> Vec<u8> {
+        let mut input = Vec::with_capacity(chunk.len() + 1);
+        if std::mem::take(&mut self.pending_cr) {
+            input.push(b'\r');
+        }
+        input.extend_from_slice(chunk);
+        if input.last() == Some(&b'\r') {
+            input.pop();
+            self.pending_cr = true;
+        }
+        TextBuffer::convert_line_endings_to(&input, self.target)
+    }
+
+    /// Convert whatever was held back after the last chunk
+    fn finish(&mut self) -> Vec<u8> {
+        if std::mem::take(&mut self.pending_cr) {
+            TextBuffer::convert_line_endings_to(b"\r", self.target)
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Write `bytes` to `out`, through `encoder` if the file isn't UTF-8
+fn write_encoded(
+    out: &mut dyn Write,
+    encoder: Option<&mut Encoder>,
+    bytes: &[u8],
+) -> io::Result<()> {
+    match encoder {
+        Some(encoder) => out.write_all(&encoder.encode(bytes)?),
+        None => out.write_all(bytes),
+    }
+}
+
+/// How far the buffer content has been streamed out, with the line ending
+/// and encoding state carried over to the next chunk
+struct ContentStream {
+    /// Buffer bytes written so far (before line ending conversion)
+    written: usize,
+    converter: Option<LineEndingConverter>,
+    encoder: Option<Encoder>,
+}
+
+impl ContentStream {
+    /// Write the next chunk of buffer content
+    fn write(&mut self, out: &mut dyn Write, chunk: &[u8]) -> io::Result<()> {
+        match self.converter.as_mut() {
+            Some(converter) => {
+                write_encoded(out, self.encoder.as_mut(), &converter.convert(chunk))?
+            }
+            None => write_encoded(out, self.encoder.as_mut(), chunk)?,
+        }
+        self.written += chunk.len();
+        Ok(())
+    }
+
+    /// Write whatever was held back after the last chunk
+    fn finish(&mut self, out: &mut dyn Write) -> io::Result<()> {
+        if let Some(converter) = self.converter.as_mut() {
+            write_encoded(out, self.encoder.as_mut(), &converter.finish())?;
+        }
+        if let Some(encoder) = &self.encoder {
+            encoder.finish()?;
+        }
+        Ok(())
+    }
+}
+
+/// A save to a local file whose content is streamed into a temp file a
+/// piece at a time by [`TextBuffer::continue_save`], so the caller can
+/// report progress in between
+pub struct PendingSave {
+    dest_path: PathBuf,
+    temp_path: PathBuf,
+    temp_file: Box<dyn FileWriter>,
+    original_metadata: Option<FileMetadata>,
+    stream: ContentStream,
+    /// Buffer version the content is being written from
+    version: u64,
+}
+
+impl PendingSave {
+    /// The file being saved to
+    pub fn dest_path(&self) -> &Path {
+        &self.dest_path
+    }
+
+    /// Buffer bytes written so far
+    pub fn written(&self) -> u64 {
+        self.stream.written as u64
+    }
+}
+
 /// A write recipe built from the piece tree for saving
 struct WriteRecipe {
     /// The source file path for Copy operations (if any)
@@ -221,6 +337,15 @@ pub struct TextBuffer {
     /// unprintable characters as code points.
     is_binary: bool,
 
+    /// Encoding of the file on disk. Text in other encodings than UTF-8 is
+    /// decoded when loaded and encoded back when saved.
+    encoding: Encoding,
+
+    /// Invalid UTF-8 sequences in the file when it was loaded or last saved
+    /// (for large files, in the sample read at load). They are kept byte for
+    /// byte and displayed as U+FFFD.
+    invalid_utf8_sequences: usize,
+
     /// Line ending format detected from the file (or default for new files)
     line_ending: LineEnding,
 
@@ -255,6 +380,8 @@ impl TextBuffer {
             recovery_pending: false,
             large_file: false,
             is_binary: false,
+            encoding: Encoding::Utf8,
+            invalid_utf8_sequences: 0,
             line_ending,
             original_line_ending: line_ending,
             saved_file_size: None,
@@ -321,6 +448,8 @@ impl TextBuffer {
             recovery_pending: false,
             large_file: false,
             is_binary: false,
+            encoding: Encoding::Utf8,
+            invalid_utf8_sequences: 0,
             saved_file_size: Some(bytes), // Treat initial content as "saved" state
             version: 0,
         }
@@ -351,6 +480,8 @@ impl TextBuffer {
             recovery_pending: false,
             large_file: false,
             is_binary: false,
+            encoding: Encoding::Utf8,
+            invalid_utf8_sequences: 0,
             line_ending,
             original_line_ending: line_ending,
             saved_file_size: None,
@@ -385,12 +516,41 @@ impl TextBuffer {
         }
     }
 
+    /// Load a file saved in `encoding`, decoding it to UTF-8. The whole file
+    /// is read whatever its size, and saving encodes the text back.
+    pub fn load_from_file_with_encoding<P: AsRef<Path>>(
+        path: P,
+        encoding: Encoding,
+        fs: Arc<dyn FileSystem + Send + Sync>,
+    ) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        if encoding == Encoding::Utf8 {
+            return Self::load_small_file(path, fs);
+        }
+
+        let contents = fs.read_file(path)?;
+        let line_ending = Self::detect_line_ending(&contents);
+        let mut buffer = Self::from_bytes(encoding.decode(&contents).into_bytes(), fs);
+        buffer.file_path = Some(path.to_path_buf());
+        buffer.modified = false;
+        buffer.large_file = false;
+        buffer.encoding = encoding;
+        buffer.line_ending = line_ending;
+        buffer.original_line_ending = line_ending;
+        Ok(buffer)
+    }
+
     /// Load a small file with full eager loading and line indexing
     fn load_small_file(path: &Path, fs: Arc<dyn FileSystem + Send + Sync>) -> anyhow::Result<Self> {
         let contents = fs.read_file(path)?;
 
         // Detect if this is a binary file
         let is_binary = Self::detect_binary(&contents);
+        let invalid_utf8_sequences = if is_binary {
+            0
+        } else {
+            encoding::invalid_utf8_sequences(&contents)
+        };
 
         // Detect line ending format (CRLF/LF/CR) - used for Enter key insertion
         let line_ending = Self::detect_line_ending(&contents);
@@ -401,6 +561,7 @@ impl TextBuffer {
         buffer.modified = false;
         buffer.large_file = false;
         buffer.is_binary = is_binary;
+        buffer.invalid_utf8_sequences = invalid_utf8_sequences;
         buffer.line_ending = line_ending;
         buffer.original_line_ending = line_ending;
         Ok(buffer)
@@ -420,6 +581,18 @@ impl TextBuffer {
         let sample = fs.read_range(path, 0, sample_size)?;
         let is_binary = Self::detect_binary(&sample);
         let line_ending = Self::detect_line_ending(&sample);
+        // Ignore a character split by the end of the sample
+        let complete_sample = match std::str::from_utf8(&sample) {
+            Err(e) if e.error_len().is_none() && sample_size < file_size => {
+                &sample[..e.valid_up_to()]
+            }
+            _ => &sample[..],
+        };
+        let invalid_utf8_sequences = if is_binary {
+            0
+        } else {
+            encoding::invalid_utf8_sequences(complete_sample)
+        };
 
         // Create an unloaded buffer that references the entire file
         let buffer = StringBuffer {
@@ -457,6 +630,8 @@ impl TextBuffer {
             recovery_pending: false,
             large_file: true,
             is_binary,
+            encoding: Encoding::Utf8,
+            invalid_utf8_sequences,
             line_ending,
             original_line_ending: line_ending,
             saved_file_size: Some(file_size),
@@ -466,8 +641,14 @@ impl TextBuffer {
 
     /// Save the buffer to its associated file
     pub fn save(&mut self) -> anyhow::Result<()> {
+        self.save_with_progress(&mut |_, _| {})
+    }
+
+    /// Save the buffer to its associated file, reporting progress as
+    /// [`Self::save_to_file_with_progress`] does
+    pub fn save_with_progress(&mut self, progress: &mut dyn FnMut(u64, u64)) -> anyhow::Result<()> {
         if let Some(path) = &self.file_path {
-            self.save_to_file(path.clone())
+            self.save_to_file_with_progress(path.clone(), progress)
         } else {
             anyhow::bail!(io::Error::new(
                 io::ErrorKind::NotFound,
@@ -616,19 +797,29 @@ impl TextBuffer {
 
     /// Save the buffer to a specific file
     ///
-    /// Uses the write recipe approach for both local and remote filesystems:
-    /// - Copy ops reference unchanged regions in the source file
-    /// - Insert ops contain new/modified data
-    ///
-    /// For remote filesystems, the recipe is sent to the agent which reconstructs
-    /// the file server-side, avoiding transfer of unchanged content.
+    /// Local files are streamed chunk by chunk into a temp file which then
+    /// replaces the destination, so peak memory stays at about one chunk no
+    /// matter how large the buffer is. If the file is owned by another user,
+    /// the temp file is copied over it instead to preserve its ownership.
     ///
-    /// For local filesystems with ownership concerns (file owned by another user),
-    /// uses in-place writing to preserve ownership. Otherwise uses atomic writes.
+    /// For remote filesystems, a write recipe is sent to the agent which
+    /// reconstructs the file server-side: Copy ops reference unchanged regions
+    /// of the source file and Insert ops carry new/modified data, avoiding
+    /// transfer of unchanged content.
     ///
     /// If the line ending format has been changed (via set_line_ending), all content
     /// will be converted to the new format during save.
     pub fn save_to_file<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
+        self.save_to_file_with_progress(path, &mut |_, _| {})
+    }
+
+    /// Save the buffer to a specific file, calling `progress` with
+    /// `(bytes_written, total_bytes)` after each chunk of a local save
+    pub fn save_to_file_with_progress<P: AsRef<Path>>(
+        &mut self,
+        path: P,
+        progress: &mut dyn FnMut(u64, u64),
+    ) -> anyhow::Result<()> {
         let dest_path = path.as_ref();
         let total = self.total_bytes();
 
@@ -639,114 +830,234 @@ impl TextBuffer {
             return Ok(());
         }
 
-        // Build the write recipe (unified for all filesystem types)
-        let recipe = self.build_write_recipe()?;
-        let ops = recipe.to_write_ops();
-
-        // Check if we need in-place writing to preserve file ownership (local only)
-        // Remote filesystems handle this differently
-        let is_local = self.fs.remote_connection_info().is_none();
-        let use_inplace = is_local && self.should_use_inplace_write(dest_path);
-
-        if use_inplace {
-            // In-place write: write directly to preserve ownership
-            self.save_with_inplace_write(dest_path, &recipe)?;
-        } else if !recipe.has_copy_ops() && !is_local {
-            // Remote with no Copy ops: use write_file directly (more efficient)
-            let data = recipe.flatten_inserts();
+        if self.fs.remote_connection_info().is_none() {
+            self.save_streaming(dest_path, progress)?;
+        } else if self.encoding != Encoding::Utf8 {
+            // Encoded content can't be patched from the original file's bytes
+            let mut data = Vec::new();
+            self.write_contents(&mut data, progress)?;
             self.fs.write_file(dest_path, &data)?;
-        } else if is_local {
-            // Local: use write_file or write_patched with sudo fallback
-            let write_result = if !recipe.has_copy_ops() {
+        } else {
+            let recipe = self.build_write_recipe()?;
+            if !recipe.has_copy_ops() {
+                // No Copy ops: use write_file directly (more efficient)
                 let data = recipe.flatten_inserts();
-                self.fs.write_file(dest_path, &data)
+                self.fs.write_file(dest_path, &data)?;
             } else {
+                let ops = recipe.to_write_ops();
                 let src_for_patch = recipe.src_path.as_deref().unwrap_or(dest_path);
-                self.fs.write_patched(src_for_patch, dest_path, &ops)
-            };
-
-            if let Err(e) = write_result {
-                if e.kind() == io::ErrorKind::PermissionDenied {
-                    // Create temp file and return sudo error
-                    let original_metadata = self.fs.metadata_if_exists(dest_path);
-                    let (temp_path, mut temp_file) = self.create_temp_file(dest_path)?;
-                    self.write_recipe_to_file(&mut temp_file, &recipe)?;
-                    temp_file.sync_all()?;
-                    drop(temp_file);
-                    return Err(self.make_sudo_error(temp_path, dest_path, original_metadata));
-                }
-                return Err(e.into());
+                self.fs.write_patched(src_for_patch, dest_path, &ops)?;
             }
-        } else {
-            // Remote with Copy ops: use write_patched
-            let src_for_patch = recipe.src_path.as_deref().unwrap_or(dest_path);
-            self.fs.write_patched(src_for_patch, dest_path, &ops)?;
         }
 
         self.finalize_save(dest_path)?;
         Ok(())
     }
 
-    /// Write using in-place mode to preserve file ownership.
-    ///
-    /// This is used when the file is owned by a different user and we need
-    /// to write directly to the existing file to preserve its ownership.
-    fn save_with_inplace_write(
+    /// Save to a local file through a temp file the content is streamed into
+    fn save_streaming(
         &self,
         dest_path: &Path,
-        recipe: &WriteRecipe,
+        progress: &mut dyn FnMut(u64, u64),
     ) -> anyhow::Result<()> {
-        let original_metadata = self.fs.metadata_if_exists(dest_path);
-
-        match self.fs.open_file_for_write(dest_path) {
-            Ok(mut out_file) => {
-                // Write recipe content directly to file
-                self.write_recipe_to_file(&mut out_file, recipe)?;
-                out_file.sync_all()?;
-                Ok(())
-            }
-            Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
-                // Fall back to atomic write, which will likely also fail
-                // and trigger sudo fallback
-                let ops = recipe.to_write_ops();
-                let src_for_patch = recipe.src_path.as_deref().unwrap_or(dest_path);
+        let total = self.total_bytes() as u64;
+        let mut save = self.begin_save(dest_path)?;
+        while !self.continue_save(&mut save, SAVE_CHUNK_SIZE)? {
+            progress(save.written(), total);
+        }
+        progress(total, total);
+        self.replace_with_temp_file(save)
+    }
+
+    /// Start saving the buffer to the local file `path` through a temp file.
+    ///
+    /// Write the content with [`Self::continue_save`], then complete the save
+    /// with [`Self::finish_save`]. The buffer must not change in between.
+    pub fn begin_save<P: AsRef<Path>>(&self, path: P) -> io::Result<PendingSave> {
+        let dest_path = path.as_ref().to_path_buf();
+        let original_metadata = self.fs.metadata_if_exists(&dest_path);
+        let (temp_path, temp_file) = self.create_temp_file(&dest_path)?;
+        Ok(PendingSave {
+            dest_path,
+            temp_path,
+            temp_file,
+            original_metadata,
+            stream: self.content_stream(),
+            version: self.version,
+        })
+    }
 
-                match self.fs.write_patched(src_for_patch, dest_path, &ops) {
-                    Ok(()) => Ok(()),
-                    Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
-                        // Create temp file for sudo fallback
-                        let (temp_path, mut temp_file) = self.create_temp_file(dest_path)?;
-                        self.write_recipe_to_file(&mut temp_file, recipe)?;
-                        temp_file.sync_all()?;
-                        drop(temp_file);
-                        Err(self.make_sudo_error(temp_path, dest_path, original_metadata))
+    /// Write up to `max_bytes` more of the content into a pending save.
+    ///
+    /// Returns whether all of it has been written. On failure the temp file
+    /// is removed and the save can't be continued.
+    pub fn continue_save(&self, save: &mut PendingSave, max_bytes: usize) -> io::Result<bool> {
+        let result = if save.version != self.version {
+            Err(io::Error::other(
+                "the buffer changed while it was being saved",
+            ))
+        } else {
+            let end = save.stream.written + max_bytes;
+            self.write_content_range(&mut save.temp_file, &mut save.stream, end)
+                .and_then(|()| {
+                    let done = save.stream.written >= self.total_bytes();
+                    if done {
+                        save.stream.finish(&mut save.temp_file)?;
+                        save.temp_file.sync_all()?;
                     }
-                    Err(e) => Err(e.into()),
-                }
+                    Ok(done)
+                })
+        };
+        if result.is_err() {
+            let _ = self.fs.remove_file(&save.temp_path);
+        }
+        result
+    }
+
+    /// Complete a pending save whose content has all been written: replace
+    /// the destination with the temp file and mark the buffer saved
+    pub fn finish_save(&mut self, save: PendingSave) -> anyhow::Result<()> {
+        let dest_path = save.dest_path.clone();
+        self.replace_with_temp_file(save)?;
+        self.finalize_save(&dest_path)
+    }
+
+    /// Replace the destination of a fully written save with its temp file.
+    ///
+    /// When the destination can't be replaced (or overwritten, for files owned
+    /// by another user), the temp file is handed to the sudo save flow.
+    fn replace_with_temp_file(&self, save: PendingSave) -> anyhow::Result<()> {
+        let PendingSave {
+            dest_path,
+            temp_path,
+            temp_file,
+            original_metadata,
+            ..
+        } = save;
+        drop(temp_file);
+        let dest_path = dest_path.as_path();
+
+        let result = if self.should_use_inplace_write(dest_path) {
+            self.copy_into_place(&temp_path, dest_path)
+        } else {
+            self.move_into_place(&temp_path, dest_path, original_metadata.as_ref())
+        };
+        // A temp file outside the destination's directory means that directory
+        // isn't writable, which has always required sudo
+        let temp_elsewhere = temp_path.parent() != dest_path.parent();
+        match result {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied || temp_elsewhere => {
+                Err(self.make_sudo_error(temp_path, dest_path, original_metadata))
             }
-            Err(e) => Err(e.into()),
+            Err(e) => {
+                let _ = self.fs.remove_file(&temp_path);
+                Err(e.into())
+            }
+        }
+    }
+
+    /// Replace `dest_path` with the temp file, keeping the original permissions
+    fn move_into_place(
+        &self,
+        temp_path: &Path,
+        dest_path: &Path,
+        original_metadata: Option<&FileMetadata>,
+    ) -> io::Result<()> {
+        if let Some(perms) = original_metadata.and_then(|m| m.permissions.as_ref()) {
+            let _ = self.fs.set_permissions(temp_path, perms);
         }
+        self.fs.rename(temp_path, dest_path)
+    }
+
+    /// Overwrite `dest_path` in place with the temp file's content, preserving
+    /// its ownership, then remove the temp file
+    fn copy_into_place(&self, temp_path: &Path, dest_path: &Path) -> io::Result<()> {
+        let mut out_file = self.fs.open_file_for_write(dest_path)?;
+        let mut temp_file = self.fs.open_file(temp_path)?;
+        io::copy(&mut temp_file, &mut out_file)?;
+        out_file.sync_all()?;
+        drop(temp_file);
+        let _ = self.fs.remove_file(temp_path);
+        Ok(())
     }
 
-    /// Write the recipe content to a file writer.
-    fn write_recipe_to_file(
+    /// Stream the buffer content to `out` in chunks of at most `SAVE_CHUNK_SIZE`.
+    ///
+    /// `progress` gets `(bytes_written, total_bytes)` in buffer bytes (before
+    /// line ending conversion) after each chunk.
+    fn write_contents(
         &self,
-        out_file: &mut Box<dyn crate::model::filesystem::FileWriter>,
-        recipe: &WriteRecipe,
+        out: &mut dyn Write,
+        progress: &mut dyn FnMut(u64, u64),
     ) -> io::Result<()> {
-        for action in &recipe.actions {
-            match action {
-                RecipeAction::Copy { offset, len } => {
-                    // Read from source and write to output
-                    let src_path = recipe.src_path.as_ref().ok_or_else(|| {
-                        io::Error::new(io::ErrorKind::InvalidData, "Copy action without source")
-                    })?;
-                    let data = self.fs.read_range(src_path, *offset, *len as usize)?;
-                    out_file.write_all(&data)?;
-                }
-                RecipeAction::Insert { index } => {
-                    out_file.write_all(&recipe.insert_data[*index])?;
+        let total = self.total_bytes();
+        let mut stream = self.content_stream();
+        while stream.written < total {
+            let end = stream.written + SAVE_CHUNK_SIZE;
+            self.write_content_range(out, &mut stream, end)?;
+            progress(stream.written as u64, total as u64);
+        }
+        stream.finish(out)
+    }
+
+    /// Start streaming the content, converting line endings if the format
+    /// was changed and encoding the text if the file isn't UTF-8
+    fn content_stream(&self) -> ContentStream {
+        ContentStream {
+            written: 0,
+            converter: (self.line_ending != self.original_line_ending)
+                .then(|| LineEndingConverter::new(self.line_ending)),
+            encoder: (self.encoding != Encoding::Utf8).then(|| Encoder::new(self.encoding)),
+        }
+    }
+
+    /// Write the content from where `stream` got to up to `end` (or the end
+    /// of the buffer) in chunks of at most `SAVE_CHUNK_SIZE`.
+    ///
+    /// Unloaded regions are read one chunk at a time and loaded ones are written
+    /// without copying.
+    fn write_content_range(
+        &self,
+        out: &mut dyn Write,
+        stream: &mut ContentStream,
+        end: usize,
+    ) -> io::Result<()> {
+        let start = stream.written;
+        let end = end.min(self.total_bytes());
+
+        for piece_view in self.piece_tree.iter_pieces_in_range(start, end) {
+            let buffer_id = piece_view.location.buffer_id();
+            let buffer = self.buffers.get(buffer_id).ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("Buffer {} not found", buffer_id),
+                )
+            })?;
+
+            let mut offset = start.saturating_sub(piece_view.doc_offset);
+            let piece_end = (end - piece_view.doc_offset).min(piece_view.bytes);
+            while offset < piece_end {
+                let len = (piece_end - offset).min(SAVE_CHUNK_SIZE);
+                match &buffer.data {
+                    BufferData::Loaded { data, .. } => {
+                        let start = piece_view.buffer_offset + offset;
+                        stream.write(out, &data[start..start + len])?;
+                    }
+                    BufferData::Unloaded {
+                        file_path,
+                        file_offset,
+                        ..
+                    } => {
+                        let data = self.fs.read_range(
+                            file_path,
+                            (*file_offset + piece_view.buffer_offset + offset) as u64,
+                            len,
+                        )?;
+                        stream.write(out, &data)?;
+                    }
                 }
+                offset += len;
             }
         }
         Ok(())
@@ -755,6 +1066,9 @@ impl TextBuffer {
     /// Finalize save state after successful write.
     fn finalize_save(&mut self, dest_path: &Path) -> anyhow::Result<()> {
         let new_size = self.fs.metadata(dest_path)?.size as usize;
+        if !self.large_file {
+            self.invalid_utf8_sequences = self.current_invalid_utf8_sequences();
+        }
         tracing::debug!(
             "Buffer::save: updating saved_file_size from {:?} to {}",
             self.saved_file_size,
@@ -1797,6 +2111,28 @@ impl TextBuffer {
         self.is_binary
     }
 
+    /// Get the encoding of the file on disk
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Number of invalid UTF-8 sequences the file had when it was loaded or
+    /// last saved
+    pub fn invalid_utf8_sequences(&self) -> usize {
+        self.invalid_utf8_sequences
+    }
+
+    /// Number of invalid UTF-8 sequences in the current content. Large files
+    /// aren't fully loaded, so for them this is the count found at load.
+    pub fn current_invalid_utf8_sequences(&self) -> usize {
+        if self.large_file {
+            return self.invalid_utf8_sequences;
+        }
+        self.get_all_text()
+            .map(|bytes| encoding::invalid_utf8_sequences(&bytes))
+            .unwrap_or(self.invalid_utf8_sequences)
+    }
+
     /// Get the line ending format for this buffer
     pub fn line_ending(&self) -> LineEnding {
         self.line_ending
@@ -2210,6 +2546,64 @@ impl TextBuffer {
         None
     }
 
+    /// Find all non-empty matches of a regex, in order, stopping after
+    /// `max_results` matches.
+    ///
+    /// The buffer is read in chunks (loading unloaded regions of large files
+    /// as needed), so the whole text never has to be in one string. Each
+    /// chunk is read with 4KB of overlap on both sides, so anchors and word
+    /// boundaries see the surrounding text and matches can end past the
+    /// chunk. A match that reaches the end of what was read is retried from
+    /// its start, in case it continues further.
+    pub fn find_all_regex(
+        &mut self,
+        regex: &Regex,
+        max_results: Option<usize>,
+    ) -> Result<Vec<Range<usize>>> {
+        const CHUNK_SIZE: usize = 1048576; // 1MB chunks
+        const OVERLAP: usize = 4096; // 4KB overlap for regex
+
+        let len = self.len();
+        let limit = max_results.unwrap_or(usize::MAX);
+        let mut matches = Vec::new();
+        let mut pos = 0;
+
+        while pos < len && matches.len() < limit {
+            // Matches must start in pos..chunk_end, but may end up to window_end
+            let chunk_end = (pos + CHUNK_SIZE).min(len);
+            let window_start = pos.saturating_sub(OVERLAP);
+            let window_end = (chunk_end + OVERLAP).min(len);
+            let bytes = self.get_text_range_mut(window_start, window_end - window_start)?;
+            let mut next = chunk_end;
+            let mut at = pos - window_start;
+
+            while let Some(m) = regex.find_at(&bytes, at) {
+                let start = window_start + m.start();
+                if start >= chunk_end {
+                    break;
+                }
+                if m.end() == bytes.len() && window_end < len && start > pos {
+                    // The match may continue past what was read: retry from its start
+                    next = start;
+                    break;
+                }
+                if !m.is_empty() {
+                    matches.push(start..window_start + m.end());
+                    if matches.len() >= limit {
+                        break;
+                    }
+                }
+                at = if m.is_empty() { m.end() + 1 } else { m.end() };
+                if at > bytes.len() {
+                    break;
+                }
+            }
+            pos = next;
+        }
+
+        Ok(matches)
+    }
+
     /// Replace a range with replacement text
     pub fn replace_range(&mut self, range: Range<usize>, replacement: &str) -> bool {
         if range.start >= self.len() {
@@ -3239,6 +3633,52 @@ mod tests {
         assert_eq!(buffer.get_all_text().unwrap(), b"ba");
     }
 
+    #[test]
+    fn test_find_all_regex_across_chunks() {
+        const CHUNK: usize = 1048576;
+
+        // "abcd" straddles the first chunk boundary, a run of "y" is longer
+        // than the overlap read past a chunk, and "foo" starts exactly on a
+        // chunk boundary, mid-line
+        let mut text = vec![b'x'; CHUNK - 2];
+        text.extend_from_slice(b"abcd");
+        text.resize(CHUNK + 10000, b'x');
+        text.resize(2 * CHUNK + 8000, b'y');
+        text.resize(3 * CHUNK - 2, b'x');
+        text.extend_from_slice(b"zzfoo\nfoo\n");
+        let mut buffer = TextBuffer::from_bytes(text, test_fs());
+
+        let regex = Regex::new("abcd").unwrap();
+        assert_eq!(
+            buffer.find_all_regex(&regex, None).unwrap(),
+            vec![CHUNK - 2..CHUNK + 2]
+        );
+
+        let regex = Regex::new("y+").unwrap();
+        assert_eq!(
+            buffer.find_all_regex(&regex, None).unwrap(),
+            vec![CHUNK + 10000..2 * CHUNK + 8000]
+        );
+
+        // Anchors see the text before each chunk
+        let regex = Regex::new("(?m)^foo").unwrap();
+        assert_eq!(
+            buffer.find_all_regex(&regex, None).unwrap(),
+            vec![3 * CHUNK + 4..3 * CHUNK + 7]
+        );
+
+        let regex = Regex::new("o").unwrap();
+        assert_eq!(buffer.find_all_regex(&regex, Some(3)).unwrap().len(), 3);
+
+        // Empty matches are skipped
+        let mut buffer = TextBuffer::from_bytes(b"a foo boo".to_vec(), test_fs());
+        let regex = Regex::new("o*").unwrap();
+        assert_eq!(
+            buffer.find_all_regex(&regex, None).unwrap(),
+            vec![3..5, 7..9]
+        );
+    }
+
     // ===== Phase 1-3: Large File Support Tests =====
 
     mod large_file_support {
@@ -3813,6 +4253,85 @@ mod tests {
             );
         }
 
+        /// Test that saving a large file streams it in chunks, in place, with progress
+        #[test]
+        fn test_large_file_save_streams_chunks_with_progress() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("huge.txt");
+
+            let line = b"0123456789abcdef\n";
+            let original: Vec<u8> = line
+                .iter()
+                .copied()
+                .cycle()
+                .take(SAVE_CHUNK_SIZE * 3 + 123)
+                .collect();
+            std::fs::write(&file_path, &original).unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 100, test_fs()).unwrap();
+            assert!(buffer.large_file);
+            buffer.insert_bytes(0, b"HEAD\n".to_vec());
+
+            let mut reports = Vec::new();
+            buffer
+                .save_to_file_with_progress(&file_path, &mut |written, total| {
+                    reports.push((written, total))
+                })
+                .unwrap();
+
+            let total = (original.len() + 5) as u64;
+            assert!(reports.len() > 3, "expected one report per chunk");
+            assert!(reports.windows(2).all(|w| w[0].0 < w[1].0));
+            assert!(reports.iter().all(|&(_, t)| t == total));
+            assert_eq!(reports.last(), Some(&(total, total)));
+
+            let saved = std::fs::read(&file_path).unwrap();
+            assert_eq!(&saved[..5], b"HEAD\n");
+            assert!(saved[5..] == original[..]);
+            // The temp file was moved into place
+            assert!(!file_path.with_extension("tmp").exists());
+        }
+
+        /// Test that a pending save can be written a little at a time and
+        /// fails if the buffer changes before it's finished
+        #[test]
+        fn test_large_file_pending_save() {
+            let temp_dir = TempDir::new().unwrap();
+            let file_path = temp_dir.path().join("huge.txt");
+            let original: Vec<u8> = b"0123456789abcdef\n"
+                .iter()
+                .copied()
+                .cycle()
+                .take(SAVE_CHUNK_SIZE * 2 + 123)
+                .collect();
+            std::fs::write(&file_path, &original).unwrap();
+
+            let mut buffer = TextBuffer::load_from_file(&file_path, 100, test_fs()).unwrap();
+            buffer.insert_bytes(0, b"HEAD\n".to_vec());
+
+            let mut save = buffer.begin_save(&file_path).unwrap();
+            let mut steps = 0;
+            while !buffer.continue_save(&mut save, 1000 * 1000).unwrap() {
+                steps += 1;
+                assert_eq!(save.written(), steps * 1000 * 1000);
+            }
+            assert_eq!(steps, 2);
+            // Nothing replaced the file yet
+            assert!(std::fs::read(&file_path).unwrap() == original);
+
+            buffer.finish_save(save).unwrap();
+            assert!(!buffer.is_modified());
+            let saved = std::fs::read(&file_path).unwrap();
+            assert_eq!(&saved[..5], b"HEAD\n");
+            assert!(saved[5..] == original[..]);
+
+            let mut save = buffer.begin_save(&file_path).unwrap();
+            assert!(!buffer.continue_save(&mut save, 1000).unwrap());
+            buffer.insert_bytes(0, b"x".to_vec());
+            assert!(buffer.continue_save(&mut save, 1000).is_err());
+            assert!(!file_path.with_extension("tmp").exists());
+        }
+
         /// Test that save_to_file handles edits at multiple positions
         #[test]
         fn test_large_file_save_with_multiple_edits() {
@@ -4273,6 +4792,22 @@ mod tests {
             assert_eq!(result, b"No line endings here".to_vec());
         }
 
+        #[test]
+        fn test_line_ending_converter_joins_crlf_split_across_chunks() {
+            let mut converter = LineEndingConverter::new(LineEnding::LF);
+            let mut output = converter.convert(b"one\r");
+            output.extend(converter.convert(b"\ntwo\r"));
+            output.extend(converter.finish());
+            assert_eq!(output, b"one\ntwo\n");
+
+            let mut converter = LineEndingConverter::new(LineEnding::CRLF);
+            let mut output = converter.convert(b"a\r");
+            output.extend(converter.convert(b"\n"));
+            output.extend(converter.convert(b"b\n"));
+            output.extend(converter.finish());
+            assert_eq!(output, b"a\r\nb\r\n");
+        }
+
         #[test]
         fn test_set_line_ending_marks_modified() {
             let mut buffer = TextBuffer::from_bytes(b"Hello\nWorld\n".to_vec(), test_fs());
@@ -1,3 +1,6 @@
+use fresh_core::hooks::BufferChange;
+use std::ops::Range;
+
 /// Represents a single edit operation in the buffer's history
 #[derive(Clone, Debug)]
 pub struct Edit {
@@ -31,3 +34,343 @@ impl Edit {
         }
     }
 }
+
+/// Most recent buffer versions kept in an [`EditJournal`]
+const MAX_JOURNAL_VERSIONS: u64 = 1024;
+
+/// Why a range could not be carried to the current buffer version
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransformError {
+    /// The buffer changed in a way the journal doesn't describe (reload,
+    /// undo of a bulk edit, ...), or the base version is too old
+    Untracked,
+    /// An edit since the base version touched the inside of the range
+    Conflict,
+}
+
+#[derive(Clone, Debug)]
+struct JournalEntry {
+    edit: Edit,
+    from_plugin: bool,
+}
+
+/// Recent edits to a buffer, each tagged with the buffer version it produced
+///
+/// Plugins compute offsets against the buffer as they last read it, and the
+/// user may keep typing before their edits arrive. The journal carries such
+/// offsets through the edits made in between, like operational transform
+/// does for concurrent edits.
+#[derive(Clone, Debug, Default)]
+pub struct EditJournal {
+    entries: std::collections::VecDeque<JournalEntry>,
+}
+
+impl EditJournal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an edit; edits applied together (bulk edits) share a version
+    /// and are recorded in the order they were applied
+    pub fn record(&mut self, edit: Edit) {
+        let version = edit.version;
+        self.entries.push_back(JournalEntry {
+            edit,
+            from_plugin: false,
+        });
+        while self
+            .entries
+            .front()
+            .is_some_and(|entry| version.wrapping_sub(entry.edit.version) >= MAX_JOURNAL_VERSIONS)
+        {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Mark the edits that produced `version` as made by a plugin, so other
+    /// plugin edits are not moved by them
+    pub fn mark_from_plugin(&mut self, version: u64) {
+        for entry in self.entries.iter_mut().rev() {
+            if entry.edit.version != version {
+                break;
+            }
+            entry.from_plugin = true;
+        }
+    }
+
+    /// Bytes deleted by the edits not made by plugins
+    ///
+    /// A range based on a version the journal still tracks lies within the
+    /// current buffer grown by this much, since those deletions are the
+    /// only edits it is moved back through.
+    pub fn user_deleted_bytes(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.from_plugin)
+            .map(|entry| match entry.edit.kind {
+                EditKind::Delete { len, .. } => len,
+                EditKind::Insert { .. } => 0,
+            })
+            .sum()
+    }
+
+    /// Map `range` in the buffer at `base_version` to the buffer at
+    /// `current_version`, through the edits not made by plugins
+    ///
+    /// Text inserted at the start of the range ends up before it, text
+    /// inserted at its end after it. An empty range is an insertion point.
+    pub fn transform_range(
+        &self,
+        range: Range<usize>,
+        base_version: u64,
+        current_version: u64,
+    ) -> Result<Range<usize>, TransformError> {
+        let steps = current_version.wrapping_sub(base_version);
+        if steps == 0 {
+            return Ok(range);
+        }
+        if steps >= MAX_JOURNAL_VERSIONS {
+            return Err(TransformError::Untracked);
+        }
+
+        let since_base: Vec<&JournalEntry> = self
+            .entries
+            .iter()
+            .filter(|entry| {
+                let age = current_version.wrapping_sub(entry.edit.version);
+                age < steps
+            })
+            .collect();
+
+        // Every version since the base must be described by the journal
+        let mut versions: Vec<u64> = since_base.iter().map(|e| e.edit.version).collect();
+        versions.dedup();
+        if versions.len() as u64 != steps {
+            return Err(TransformError::Untracked);
+        }
+
+        let (mut start, mut end) = (range.start, range.end);
+        for entry in since_base.iter().filter(|entry| !entry.from_plugin) {
+            match entry.edit.kind {
+                EditKind::Insert { offset, len } => {
+                    if offset <= start {
+                        start += len;
+                        end += len;
+                    } else if offset < end {
+                        return Err(TransformError::Conflict);
+                    }
+                }
+                EditKind::Delete { offset, len } => {
+                    if offset + len <= start {
+                        start -= len;
+                        end -= len;
+                    } else if offset >= end {
+                        // Entirely after the range
+                    } else if start <= offset && offset + len <= end {
+                        end -= len;
+                    } else {
+                        return Err(TransformError::Conflict);
+                    }
+                }
+            }
+        }
+        Ok(start..end)
+    }
+}
+
+/// Changes to a buffer's text not yet reported to plugins
+///
+/// Edits are recorded with the text they inserted as they are applied. Edits
+/// the log doesn't see (a reload, undoing a bulk edit, ...) leave gaps in the
+/// versions, and are then reported as one change replacing the whole text.
+#[derive(Clone, Debug)]
+pub struct ChangeLog {
+    /// Buffer version and length when changes were last taken
+    version: u64,
+    length: usize,
+    /// The buffer was replaced by a new one since
+    replaced: bool,
+    changes: Vec<(u64, BufferChange)>,
+}
+
+impl ChangeLog {
+    /// A log for a buffer currently at `version` with `length` bytes
+    pub fn new(version: u64, length: usize) -> Self {
+        Self {
+            version,
+            length,
+            replaced: false,
+            changes: Vec::new(),
+        }
+    }
+
+    /// Record that `range` was replaced by `text`, producing `version`
+    pub fn record(&mut self, version: u64, range: Range<usize>, text: &str) {
+        self.changes.push((
+            version,
+            BufferChange {
+                range,
+                text: text.to_string(),
+            },
+        ));
+    }
+
+    /// Record that the buffer was swapped for a new one (its version may
+    /// have started over)
+    pub fn mark_replaced(&mut self) {
+        self.replaced = true;
+    }
+
+    /// Take the changes that brought the buffer to `version`, or None if it
+    /// hasn't changed since the last call
+    ///
+    /// `text` gives the whole buffer for when the changes aren't all known;
+    /// if it can't (the buffer isn't fully loaded), no changes are listed.
+    pub fn take(
+        &mut self,
+        version: u64,
+        length: usize,
+        text: impl FnOnce() -> Option<String>,
+    ) -> Option<Vec<BufferChange>> {
+        if version == self.version && !self.replaced {
+            self.changes.clear();
+            return None;
+        }
+        let mut versions: Vec<u64> = self.changes.iter().map(|(v, _)| *v).collect();
+        versions.dedup();
+        let tracked = !self.replaced && versions.len() as u64 == version.wrapping_sub(self.version);
+        let changes = if tracked {
+            self.changes.drain(..).map(|(_, change)| change).collect()
+        } else {
+            self.changes.clear();
+            text()
+                .map(|text| BufferChange {
+                    range: 0..self.length,
+                    text,
+                })
+                .into_iter()
+                .collect()
+        };
+        self.version = version;
+        self.length = length;
+        self.replaced = false;
+        Some(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn journal(edits: &[Edit]) -> EditJournal {
+        let mut journal = EditJournal::new();
+        for edit in edits {
+            journal.record(edit.clone());
+        }
+        journal
+    }
+
+    #[test]
+    fn test_offsets_move_past_edits_before_them() {
+        // "hello world": the user types "big " before "world" (v1), then
+        // deletes "hello " (v2)
+        let journal = journal(&[Edit::insert(1, 6, 4), Edit::delete(2, 0, 6)]);
+
+        // Replacing "world" read at v0
+        assert_eq!(journal.transform_range(6..11, 0, 2), Ok(4..9));
+        // Read at v1, only the deletion applies
+        assert_eq!(journal.transform_range(10..15, 1, 2), Ok(4..9));
+        // Nothing happened since v2
+        assert_eq!(journal.transform_range(10..15, 2, 2), Ok(10..15));
+        // Edits after the range leave it alone
+        assert_eq!(journal.transform_range(0..3, 0, 1), Ok(0..3));
+        assert_eq!(journal.user_deleted_bytes(), 6);
+    }
+
+    #[test]
+    fn test_insertion_points_land_after_text_typed_there() {
+        let journal = journal(&[Edit::insert(1, 5, 1)]);
+        assert_eq!(journal.transform_range(5..5, 0, 1), Ok(6..6));
+        // A range ending where the user typed doesn't grow
+        assert_eq!(journal.transform_range(2..5, 0, 1), Ok(2..5));
+    }
+
+    #[test]
+    fn test_edits_inside_the_range_conflict() {
+        let inserted = journal(&[Edit::insert(1, 3, 1)]);
+        assert_eq!(
+            inserted.transform_range(2..5, 0, 1),
+            Err(TransformError::Conflict)
+        );
+
+        // Deleting across the range start
+        let deleted = journal(&[Edit::delete(1, 1, 3)]);
+        assert_eq!(
+            deleted.transform_range(2..6, 0, 1),
+            Err(TransformError::Conflict)
+        );
+        // Deleting text inside the range just shrinks it
+        assert_eq!(deleted.transform_range(0..6, 0, 1), Ok(0..3));
+    }
+
+    #[test]
+    fn test_plugin_edits_are_not_transformed_against() {
+        let mut journal = journal(&[Edit::insert(1, 0, 3)]);
+        journal.mark_from_plugin(1);
+        journal.record(Edit::insert(2, 0, 2));
+        assert_eq!(journal.transform_range(10..10, 0, 2), Ok(12..12));
+
+        journal.record(Edit::delete(3, 0, 4));
+        journal.mark_from_plugin(3);
+        assert_eq!(journal.user_deleted_bytes(), 0);
+    }
+
+    #[test]
+    fn test_untracked_changes_are_reported() {
+        // v2 happened without being recorded
+        let journal = journal(&[Edit::insert(1, 0, 1), Edit::insert(3, 0, 1)]);
+        assert_eq!(
+            journal.transform_range(4..4, 0, 3),
+            Err(TransformError::Untracked)
+        );
+        assert_eq!(journal.transform_range(4..4, 2, 3), Ok(5..5));
+        assert_eq!(
+            journal.transform_range(0..0, 0, MAX_JOURNAL_VERSIONS),
+            Err(TransformError::Untracked)
+        );
+    }
+
+    #[test]
+    fn test_change_log_lists_changes_in_order() {
+        let change = |range: Range<usize>, text: &str| BufferChange {
+            range,
+            text: text.to_string(),
+        };
+        // "hello": type "!" at the end (v1), then a bulk edit (v2)
+        let mut log = ChangeLog::new(0, 5);
+        assert_eq!(log.take(0, 5, || None), None);
+        log.record(1, 5..5, "!");
+        log.record(2, 5..6, "?");
+        log.record(2, 0..1, "");
+        assert_eq!(
+            log.take(2, 5, || None),
+            Some(vec![change(5..5, "!"), change(5..6, "?"), change(0..1, "")])
+        );
+        assert_eq!(log.take(2, 5, || None), None);
+
+        // v3 wasn't recorded: the whole text is replaced
+        log.record(4, 0..0, "x");
+        assert_eq!(
+            log.take(4, 6, || Some("xello?".to_string())),
+            Some(vec![change(0..5, "xello?")])
+        );
+
+        // A replaced buffer starts over at version 0
+        log.mark_replaced();
+        assert_eq!(
+            log.take(0, 3, || Some("new".to_string())),
+            Some(vec![change(0..6, "new")])
+        );
+        assert_eq!(log.take(0, 3, || None), None);
+    }
+}
@@ -0,0 +1,227 @@
+//! Text encodings a file can be opened with
+//!
+//! Buffers hold UTF-8. Files in a single-byte legacy encoding are decoded to
+//! UTF-8 when opened with "Reopen with Encoding" and encoded back when saved,
+//! so their bytes round-trip. Files opened as UTF-8 are kept byte for byte,
+//! including any invalid sequences, which are only displayed as U+FFFD.
+
+use std::io;
+
+/// Characters of Windows-1252 bytes 0x80-0x9F. The five bytes the code page
+/// leaves undefined map to the C1 controls of the same value, as in the
+/// WHATWG Encoding Standard, so every byte decodes and round-trips.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{0081}', '\u{201A}', '\u{0192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{02C6}', '\u{2030}', '\u{0160}', '\u{2039}', '\u{0152}', '\u{008D}', '\u{017D}', '\u{008F}',
+    '\u{0090}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{02DC}', '\u{2122}', '\u{0161}', '\u{203A}', '\u{0153}', '\u{009D}', '\u{017E}', '\u{0178}',
+];
+
+/// Encoding of a file on disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Encoding {
+    #[default]
+    Utf8,
+    /// ISO-8859-1: each byte is the code point of the same value
+    Latin1,
+    /// Windows-1252: Latin-1 with printable characters in 0x80-0x9F
+    Windows1252,
+}
+
+impl Encoding {
+    pub const ALL: [Self; 3] = [Self::Utf8, Self::Latin1, Self::Windows1252];
+
+    /// Stable identifier used as the prompt suggestion value
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Utf8 => "utf-8",
+            Self::Latin1 => "iso-8859-1",
+            Self::Windows1252 => "windows-1252",
+        }
+    }
+
+    /// Parse an encoding from its ID or a common alias
+    pub fn from_id(id: &str) -> Option<Self> {
+        match id.trim().to_lowercase().as_str() {
+            "utf-8" | "utf8" => Some(Self::Utf8),
+            "iso-8859-1" | "latin-1" | "latin1" => Some(Self::Latin1),
+            "windows-1252" | "cp1252" => Some(Self::Windows1252),
+            _ => None,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Latin1 => "ISO-8859-1 (Latin-1)",
+            Self::Windows1252 => "Windows-1252",
+        }
+    }
+
+    /// Decode file bytes to text. UTF-8 decoding is lossy; the single-byte
+    /// encodings decode every byte.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            Self::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Self::Latin1 => bytes.iter().map(|&b| b as char).collect(),
+            Self::Windows1252 => bytes
+                .iter()
+                .map(|&b| match b {
+                    0x80..=0x9F => WINDOWS_1252_HIGH[(b - 0x80) as usize],
+                    _ => b as char,
+                })
+                .collect(),
+        }
+    }
+
+    /// The byte `c` encodes to, if the encoding can represent it
+    fn encode_char(self, c: char) -> Option<u8> {
+        match self {
+            Self::Utf8 => None,
+            Self::Latin1 => u8::try_from(u32::from(c)).ok(),
+            Self::Windows1252 => match WINDOWS_1252_HIGH.iter().position(|&high| high == c) {
+                Some(index) => Some(0x80 + index as u8),
+                None => u8::try_from(u32::from(c))
+                    .ok()
+                    .filter(|b| !(0x80..=0x9F).contains(b)),
+            },
+        }
+    }
+}
+
+/// Encodes UTF-8 text streamed in chunks that may split characters
+pub struct Encoder {
+    encoding: Encoding,
+    /// Start of a character split by the previous chunk
+    pending: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new(encoding: Encoding) -> Self {
+        Self {
+            encoding,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Encode the next chunk of UTF-8 text.
+    ///
+    /// Fails on invalid UTF-8 and on characters the encoding can't
+    /// represent, rather than writing replacement bytes.
+    pub fn encode(&mut self, chunk: &[u8]) -> io::Result<Vec<u8>> {
+        let mut input = std::mem::take(&mut self.pending);
+        input.extend_from_slice(chunk);
+        let complete = match std::str::from_utf8(&input) {
+            Ok(_) => input.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(e) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "invalid UTF-8 can't be saved as {}: {}",
+                        self.encoding.display_name(),
+                        e
+                    ),
+                ))
+            }
+        };
+        self.pending = input.split_off(complete);
+
+        // The prefix was validated above
+        let text = std::str::from_utf8(&input).unwrap_or_default();
+        text.chars()
+            .map(|c| {
+                self.encoding.encode_char(c).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "'{}' (U+{:04X}) can't be saved as {}",
+                            c,
+                            u32::from(c),
+                            self.encoding.display_name()
+                        ),
+                    )
+                })
+            })
+            .collect()
+    }
+
+    /// Check that the text didn't end in the middle of a character
+    pub fn finish(&self) -> io::Result<()> {
+        if self.pending.is_empty() {
+            Ok(())
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "text ends with an incomplete UTF-8 sequence",
+            ))
+        }
+    }
+}
+
+/// Number of invalid UTF-8 sequences in `bytes`, counting an incomplete
+/// sequence at the end as one
+pub fn invalid_utf8_sequences(mut bytes: &[u8]) -> usize {
+    let mut count = 0;
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(_) => return count,
+            Err(e) => {
+                count += 1;
+                match e.error_len() {
+                    Some(len) => bytes = &bytes[e.valid_up_to() + len..],
+                    None => return count,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_single_byte_encodings_round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        for encoding in [Encoding::Latin1, Encoding::Windows1252] {
+            let text = encoding.decode(&bytes);
+            // Encode in small chunks so multi-byte characters are split
+            let mut encoder = Encoder::new(encoding);
+            let mut encoded = Vec::new();
+            for chunk in text.as_bytes().chunks(3) {
+                encoded.extend(encoder.encode(chunk).unwrap());
+            }
+            encoder.finish().unwrap();
+            assert_eq!(encoded, bytes, "{:?}", encoding);
+        }
+        assert_eq!(Encoding::Windows1252.decode(b"\x80 caf\xe9"), "€ café");
+    }
+
+    #[test]
+    fn test_encode_rejects_unrepresentable_characters() {
+        let mut encoder = Encoder::new(Encoding::Latin1);
+        assert!(encoder.encode("€".as_bytes()).is_err());
+
+        let mut encoder = Encoder::new(Encoding::Latin1);
+        encoder.encode(&"é".as_bytes()[..1]).unwrap();
+        assert!(encoder.finish().is_err());
+    }
+
+    #[test]
+    fn test_invalid_utf8_sequences() {
+        assert_eq!(invalid_utf8_sequences("héllo".as_bytes()), 0);
+        assert_eq!(invalid_utf8_sequences(b"caf\xe9 na\xefve"), 2);
+        assert_eq!(invalid_utf8_sequences(b"\xff\xfe"), 2);
+        assert_eq!(invalid_utf8_sequences(&"é".as_bytes()[..1]), 1);
+    }
+
+    #[test]
+    fn test_encoding_ids_round_trip() {
+        for encoding in Encoding::ALL {
+            assert_eq!(Encoding::from_id(encoding.id()), Some(encoding));
+        }
+        assert_eq!(Encoding::from_id("Latin1"), Some(Encoding::Latin1));
+        assert_eq!(Encoding::from_id("ebcdic"), None);
+    }
+}
@@ -537,6 +537,85 @@ pub struct Snapshot {
     pub cursor_positions: Vec<(CursorId, usize, Option<usize>)>,
 }
 
+/// Default number of alternate undo branches kept per buffer
+pub const DEFAULT_UNDO_BRANCH_LIMIT: usize = 32;
+
+/// Undone edits that were replaced by new edits
+///
+/// A branch diverges from the history it was cut from at `fork_index`: its
+/// entries follow the first `fork_index` entries of that history. Branches
+/// that forked off this one are kept in `children`, with fork indices counted
+/// from the start of the full history rather than from this branch.
+#[derive(Debug, Clone)]
+pub struct UndoBranch {
+    /// Stable identifier, used to select the branch from the Undo Tree
+    pub id: usize,
+
+    /// Number of shared history entries before this branch diverges
+    pub fork_index: usize,
+
+    /// Entries of the branch, in the order they were applied
+    pub entries: Vec<LogEntry>,
+
+    /// Branches that diverge from this one
+    pub children: Vec<UndoBranch>,
+}
+
+impl UndoBranch {
+    /// Timestamp of the most recent entry in this branch
+    pub fn last_timestamp(&self) -> u64 {
+        self.entries.last().map(|e| e.timestamp).unwrap_or(0)
+    }
+
+    /// Number of branches in this subtree, including this one
+    fn count(&self) -> usize {
+        1 + self.children.iter().map(UndoBranch::count).sum::<usize>()
+    }
+
+    /// Move the fork points of this subtree back by `n` entries
+    fn shift_back(&mut self, n: usize) {
+        self.fork_index -= n;
+        for child in &mut self.children {
+            child.shift_back(n);
+        }
+    }
+
+    /// Path of branch ids from this branch down to `id`
+    fn path_to(&self, id: usize) -> Option<Vec<usize>> {
+        if self.id == id {
+            return Some(vec![id]);
+        }
+        self.children.iter().find_map(|child| {
+            let mut path = child.path_to(id)?;
+            path.insert(0, self.id);
+            Some(path)
+        })
+    }
+}
+
+/// Description of one alternate branch for display in the Undo Tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UndoBranchSummary {
+    /// Branch identifier (see [`EventLog::switch_to_branch`])
+    pub id: usize,
+    /// Nesting depth (0 for branches forking off the current history)
+    pub depth: usize,
+    /// Number of edits shared with the parent history before the fork
+    pub fork_edit: usize,
+    /// Number of edits in the branch
+    pub edit_count: usize,
+    /// Timestamp of the branch's most recent entry (milliseconds since epoch)
+    pub timestamp: u64,
+}
+
+/// Number of entries in `entries` that are write actions (undo steps)
+fn count_edits(entries: &[LogEntry]) -> usize {
+    entries
+        .iter()
+        .filter(|entry| entry.event.is_write_action())
+        .count()
+}
+
 /// The event log - append-only log of all events
 pub struct EventLog {
     /// All logged events
@@ -558,6 +637,19 @@ pub struct EventLog {
     /// Index at which the buffer was last saved (for tracking modified status)
     /// When current_index equals saved_at_index, the buffer is not modified
     saved_at_index: Option<usize>,
+
+    /// Index at which the open undo group started, if any
+    /// Events appended while a group is open are collapsed into one entry when it ends
+    undo_group_start: Option<usize>,
+
+    /// Undone edits that were replaced by new edits, forking off `entries`
+    branches: Vec<UndoBranch>,
+
+    /// Maximum number of branches kept (0 discards undone edits)
+    branch_limit: usize,
+
+    /// Identifier for the next branch
+    next_branch_id: usize,
 }
 
 impl EventLog {
@@ -571,9 +663,25 @@ impl EventLog {
             #[cfg(feature = "runtime")]
             stream_file: None,
             saved_at_index: Some(0), // New buffer starts at "saved" state (index 0)
+            undo_group_start: None,
+            branches: Vec::new(),
+            branch_limit: DEFAULT_UNDO_BRANCH_LIMIT,
+            next_branch_id: 1,
         }
     }
 
+    /// Set the maximum number of undo branches kept, pruning the oldest
+    pub fn with_branch_limit(mut self, limit: usize) -> Self {
+        self.set_branch_limit(limit);
+        self
+    }
+
+    /// Set the maximum number of undo branches kept, pruning the oldest
+    pub fn set_branch_limit(&mut self, limit: usize) {
+        self.branch_limit = limit;
+        self.prune_branches();
+    }
+
     /// Mark the current position as the saved point
     /// Call this when the buffer is saved to disk
     pub fn mark_saved(&mut self) {
@@ -684,9 +792,10 @@ impl EventLog {
 
     /// Append an event to the log
     pub fn append(&mut self, event: Event) -> usize {
-        // If we're not at the end, truncate future events
+        // If we're not at the end, move future events to an undo branch
         if self.current_index < self.entries.len() {
-            self.entries.truncate(self.current_index);
+            self.stash_tail(self.current_index);
+            self.prune_branches();
 
             // Invalidate saved_at_index if it pointed to a truncated entry
             if let Some(saved_idx) = self.saved_at_index {
@@ -694,6 +803,11 @@ impl EventLog {
                     self.saved_at_index = None;
                 }
             }
+
+            // An open undo group can't start past the truncated end
+            if let Some(start) = self.undo_group_start {
+                self.undo_group_start = Some(start.min(self.current_index));
+            }
         }
 
         // Stream event to file if enabled (runtime only)
@@ -754,6 +868,14 @@ impl EventLog {
         self.current_index < self.entries.len()
     }
 
+    /// Number of steps that can be undone (write actions before the current position)
+    pub fn undo_depth(&self) -> usize {
+        self.entries[..self.current_index]
+            .iter()
+            .filter(|entry| entry.event.is_write_action())
+            .count()
+    }
+
     /// Move back through events (for undo)
     /// Collects all events up to and including the first write action, returns their inverses
     /// This processes readonly events (like scrolling) and stops at write events (like Insert/Delete)
@@ -811,6 +933,209 @@ impl EventLog {
         events
     }
 
+    /// Start an undo group at the current position
+    /// Returns false if a group is already open (groups don't nest)
+    pub fn begin_undo_group(&mut self) -> bool {
+        if self.undo_group_start.is_some() {
+            return false;
+        }
+        self.undo_group_start = Some(self.current_index);
+        true
+    }
+
+    /// Check if an undo group is open
+    pub fn has_open_undo_group(&self) -> bool {
+        self.undo_group_start.is_some()
+    }
+
+    /// Close the open undo group, collapsing the events appended since it
+    /// started into a single batch so they undo and redo as one step
+    /// Returns false if no group was open
+    pub fn end_undo_group(&mut self, description: &str) -> bool {
+        let Some(start) = self.undo_group_start.take() else {
+            return false;
+        };
+        let end = self.current_index;
+        // Nothing to collapse, or the group was partly undone while open
+        if end <= start + 1 {
+            return true;
+        }
+
+        // Events without an inverse (overlays, viewport changes) would make the
+        // whole batch non-undoable, so they are dropped from the group
+        let events: Vec<Event> = self
+            .entries
+            .drain(start..end)
+            .map(|entry| entry.event)
+            .filter(|event| event.inverse().is_some())
+            .collect();
+        self.current_index = start;
+        if !events.is_empty() {
+            let entry = LogEntry::new(Event::Batch {
+                events,
+                description: description.to_string(),
+            })
+            .with_description(description.to_string());
+            self.entries.insert(start, entry);
+            self.current_index += 1;
+        }
+
+        // Branches forking inside the collapsed range no longer match the history
+        let removed = end - self.current_index;
+        self.branches
+            .retain(|b| b.fork_index <= start || b.fork_index >= end);
+        for branch in &mut self.branches {
+            if branch.fork_index >= end {
+                branch.shift_back(removed);
+            }
+        }
+
+        self.saved_at_index = match self.saved_at_index {
+            Some(saved) if saved <= start => Some(saved),
+            Some(saved) if saved >= end => Some(saved - removed),
+            _ => None,
+        };
+        true
+    }
+
+    /// Cut `entries[fork_index..]` off the current history, keeping it as a
+    /// branch if it contains any edits
+    fn stash_tail(&mut self, fork_index: usize) {
+        let tail = self.entries.split_off(fork_index);
+        // Branches forking off the tail move with it
+        let (children, siblings) = std::mem::take(&mut self.branches)
+            .into_iter()
+            .partition(|b| b.fork_index > fork_index);
+        self.branches = siblings;
+
+        if self.branch_limit == 0 || !tail.iter().any(|e| e.event.modifies_buffer()) {
+            return;
+        }
+        self.branches.push(UndoBranch {
+            id: self.next_branch_id,
+            fork_index,
+            entries: tail,
+            children,
+        });
+        self.next_branch_id += 1;
+    }
+
+    /// Drop the least recently edited branches until the limit is met
+    fn prune_branches(&mut self) {
+        while self.branch_count() > self.branch_limit {
+            let Some(oldest) = self
+                .branches
+                .iter()
+                .enumerate()
+                .min_by_key(|(_, b)| b.last_timestamp())
+                .map(|(i, _)| i)
+            else {
+                break;
+            };
+            self.branches.remove(oldest);
+        }
+    }
+
+    /// Total number of alternate undo branches, including nested ones
+    pub fn branch_count(&self) -> usize {
+        self.branches.iter().map(UndoBranch::count).sum()
+    }
+
+    /// Alternate branches forking off the current history
+    pub fn branches(&self) -> &[UndoBranch] {
+        &self.branches
+    }
+
+    /// Describe all alternate branches in tree order (parents before children,
+    /// siblings by fork point)
+    pub fn branch_summaries(&self) -> Vec<UndoBranchSummary> {
+        fn walk(
+            branches: &[UndoBranch],
+            parent: &[LogEntry],
+            parent_start: usize,
+            parent_edits: usize,
+            depth: usize,
+            out: &mut Vec<UndoBranchSummary>,
+        ) {
+            let mut sorted: Vec<&UndoBranch> = branches.iter().collect();
+            sorted.sort_by_key(|b| (b.fork_index, b.id));
+            for branch in sorted {
+                let shared = branch
+                    .fork_index
+                    .saturating_sub(parent_start)
+                    .min(parent.len());
+                let fork_edit = parent_edits + count_edits(&parent[..shared]);
+                out.push(UndoBranchSummary {
+                    id: branch.id,
+                    depth,
+                    fork_edit,
+                    edit_count: count_edits(&branch.entries),
+                    timestamp: branch.last_timestamp(),
+                });
+                walk(
+                    &branch.children,
+                    &branch.entries,
+                    branch.fork_index,
+                    fork_edit,
+                    depth + 1,
+                    out,
+                );
+            }
+        }
+
+        let mut out = Vec::new();
+        walk(&self.branches, &self.entries, 0, 0, 0, &mut out);
+        out
+    }
+
+    /// Make the branch `id` the current history
+    ///
+    /// The edits after the fork point are kept as a branch in turn, so no
+    /// history is lost. Returns the events that take the buffer from its
+    /// current state to the end of the selected branch, or None if the
+    /// branch doesn't exist or an undo group is open.
+    pub fn switch_to_branch(&mut self, id: usize) -> Option<Vec<Event>> {
+        if self.undo_group_start.is_some() {
+            return None;
+        }
+        let path = self.branches.iter().find_map(|b| b.path_to(id))?;
+        let fork = self.branches.iter().find(|b| b.id == path[0])?.fork_index;
+
+        // Move the buffer back (or forward) to the fork point
+        let mut events = Vec::new();
+        if self.current_index > fork {
+            events.extend(
+                self.entries[fork..self.current_index]
+                    .iter()
+                    .rev()
+                    .filter_map(|entry| entry.event.inverse()),
+            );
+        } else {
+            events.extend(
+                self.entries[self.current_index..fork]
+                    .iter()
+                    .map(|entry| entry.event.clone()),
+            );
+        }
+
+        // Walk down the tree, swapping each branch on the path into place
+        for step in path {
+            let pos = self.branches.iter().position(|b| b.id == step)?;
+            let branch = self.branches.remove(pos);
+            self.stash_tail(branch.fork_index);
+            self.entries.extend(branch.entries);
+            self.branches.extend(branch.children);
+        }
+
+        events.extend(self.entries[fork..].iter().map(|entry| entry.event.clone()));
+        self.current_index = self.entries.len();
+        if self.saved_at_index.is_some_and(|saved| saved > fork) {
+            self.saved_at_index = None;
+        }
+        self.prune_branches();
+        Some(events)
+    }
+
     /// Get all events from the log
     pub fn entries(&self) -> &[LogEntry] {
         &self.entries
@@ -835,6 +1160,8 @@ impl EventLog {
         self.entries.clear();
         self.current_index = 0;
         self.snapshots.clear();
+        self.undo_group_start = None;
+        self.branches.clear();
     }
 
     /// Save event log to JSON Lines format
@@ -1098,6 +1425,116 @@ mod tests {
         assert_eq!(log.current_index(), 2);
     }
 
+    fn insert(position: usize, text: &str) -> Event {
+        Event::Insert {
+            position,
+            text: text.to_string(),
+            cursor_id: CursorId(0),
+        }
+    }
+
+    #[test]
+    fn test_undone_edits_kept_as_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+
+        assert_eq!(log.branch_count(), 1);
+        let summaries = log.branch_summaries();
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].fork_edit, 1);
+        assert_eq!(summaries[0].edit_count, 1);
+        assert_eq!(summaries[0].depth, 0);
+    }
+
+    #[test]
+    fn test_switch_to_branch_swaps_histories() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.append(insert(1, "c"));
+        let id = log.branch_summaries()[0].id;
+
+        // Undo "c", then redo "b"
+        let events = log.switch_to_branch(id).unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], Event::Delete { deleted_text, .. } if deleted_text == "c"));
+        assert!(matches!(&events[1], Event::Insert { text, .. } if text == "b"));
+        assert_eq!(log.current_index(), 2);
+        assert!(!log.can_redo());
+
+        // The replaced "c" edit is now the alternate branch
+        assert_eq!(log.branch_count(), 1);
+        let other = log.branch_summaries()[0].id;
+        let events = log.switch_to_branch(other).unwrap();
+        assert!(matches!(&events[1], Event::Insert { text, .. } if text == "c"));
+        assert!(log.switch_to_branch(999).is_none());
+    }
+
+    #[test]
+    fn test_switch_to_nested_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(insert(1, "b"));
+        log.undo();
+        log.undo();
+        log.append(insert(0, "x")); // branch 1: "a", "b"
+        log.undo();
+        log.append(insert(0, "y")); // branch 2: "x"
+
+        // Restore "a b", then fork off it again
+        let first = log
+            .branch_summaries()
+            .iter()
+            .find(|b| b.edit_count == 2)
+            .unwrap()
+            .id;
+        log.switch_to_branch(first).unwrap();
+        log.undo();
+        log.append(insert(1, "z")); // nested branch: "b" after "a"
+
+        let summaries = log.branch_summaries();
+        assert_eq!(log.branch_count(), 3);
+        let nested = summaries.iter().find(|b| b.fork_edit == 1).unwrap();
+        let events = log.switch_to_branch(nested.id).unwrap();
+        assert!(matches!(&events.last().unwrap(), Event::Insert { text, .. } if text == "b"));
+        assert_eq!(log.branch_count(), 3);
+    }
+
+    #[test]
+    fn test_branch_limit_prunes_oldest() {
+        let mut log = EventLog::new().with_branch_limit(2);
+        log.append(insert(0, "a"));
+        for text in ["b", "c", "d", "e"] {
+            log.undo();
+            log.append(insert(0, text));
+        }
+        assert_eq!(log.branch_count(), 2);
+
+        log.set_branch_limit(0);
+        assert_eq!(log.branch_count(), 0);
+        log.undo();
+        log.append(insert(0, "f"));
+        assert_eq!(log.branch_count(), 0);
+    }
+
+    #[test]
+    fn test_cursor_only_tail_not_kept_as_branch() {
+        let mut log = EventLog::new();
+        log.append(insert(0, "a"));
+        log.append(Event::AddCursor {
+            cursor_id: CursorId(1),
+            position: 0,
+            anchor: None,
+        });
+        log.undo();
+        log.append(insert(1, "b"));
+        assert_eq!(log.branch_count(), 0);
+    }
+
     /// Test for v0.1.77 panic: "range end index 148 out of range for slice of length 125"
     ///
     /// The bug occurs when:
@@ -1154,4 +1591,73 @@ mod tests {
             "Should not be at saved position after undo + new edit"
         );
     }
+
+    #[test]
+    fn test_undo_group_undoes_as_one_step() {
+        let mut log = EventLog::new();
+        log.append(Event::Insert {
+            position: 0,
+            text: "a".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.mark_saved();
+
+        assert!(log.begin_undo_group());
+        assert!(!log.begin_undo_group(), "groups don't nest");
+        log.append(Event::Insert {
+            position: 1,
+            text: "b".to_string(),
+            cursor_id: CursorId(0),
+        });
+        log.append(Event::AddOverlay {
+            namespace: None,
+            range: 0..1,
+            face: OverlayFace::Background { color: (0, 0, 0) },
+            priority: 0,
+            message: None,
+            extend_to_line_end: false,
+        });
+        log.append(Event::Delete {
+            range: 0..1,
+            deleted_text: "a".to_string(),
+            cursor_id: CursorId(0),
+        });
+        assert!(log.end_undo_group("plugin edit"));
+        assert!(!log.has_open_undo_group());
+
+        // The group became a single entry; the overlay was dropped from it
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.current_index(), 2);
+        match log.last_event() {
+            Some(Event::Batch { events, .. }) => assert_eq!(events.len(), 2),
+            other => panic!("expected a batch, got {other:?}"),
+        }
+
+        assert_eq!(log.undo_depth(), 2);
+
+        let undone = log.undo();
+        assert_eq!(undone.len(), 1);
+        assert_eq!(log.current_index(), 1);
+        assert_eq!(log.undo_depth(), 1);
+        assert!(log.is_at_saved_position());
+
+        assert_eq!(log.redo().len(), 1);
+        assert_eq!(log.undo_depth(), 2);
+        assert!(!log.is_at_saved_position());
+    }
+
+    #[test]
+    fn test_empty_undo_group_leaves_log_unchanged() {
+        let mut log = EventLog::new();
+        assert!(!log.end_undo_group("nothing open"));
+
+        log.begin_undo_group();
+        log.append(Event::Insert {
+            position: 0,
+            text: "a".to_string(),
+            cursor_id: CursorId(0),
+        });
+        assert!(log.end_undo_group("single edit"));
+        assert!(matches!(log.last_event(), Some(Event::Insert { .. })));
+    }
 }
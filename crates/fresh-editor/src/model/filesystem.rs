@@ -296,6 +296,9 @@ impl FileReader for StdFileReader {}
 // FileSystem Trait
 // ============================================================================
 
+/// Callback for `FileSystem::watch`, called with the path that changed
+pub type WatchCallback = std::sync::Arc<dyn Fn(PathBuf) + Send + Sync>;
+
 /// Unified trait for all filesystem operations
 ///
 /// This trait provides both file content I/O and directory operations.
@@ -499,6 +502,36 @@ pub trait FileSystem: Send + Sync {
             .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "home directory not found"))
     }
 
+    // ========================================================================
+    // Change Notifications
+    // ========================================================================
+
+    /// Whether this filesystem can push change notifications via `watch`
+    ///
+    /// When false, callers detect external changes by polling `metadata`.
+    fn supports_watch(&self) -> bool {
+        false
+    }
+
+    /// Start watching a file or directory for changes made outside the editor
+    ///
+    /// `on_change` is called from a background task with the path that changed:
+    /// `path` itself, or an entry inside it when `path` is a directory.
+    /// Watching a path again replaces the previous watch.
+    fn watch(&self, path: &Path, on_change: WatchCallback) -> io::Result<()> {
+        let _ = (path, on_change);
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "file watching is not supported by this filesystem",
+        ))
+    }
+
+    /// Stop watching a path previously passed to `watch`
+    fn unwatch(&self, path: &Path) -> io::Result<()> {
+        let _ = path;
+        Ok(())
+    }
+
     /// Write file using sudo (for root-owned files).
     ///
     /// This writes the file with elevated privileges, preserving the specified
@@ -3,6 +3,9 @@
 //! This module provides a simple but robust diff algorithm that correctly handles
 //! insertions, deletions, and modifications. It uses a longest common subsequence (LCS)
 //! approach to identify which lines are unchanged, then marks the ranges that differ.
+//!
+//! The same LCS engine backs [`diff_hunks`], which works on any token sequence
+//! (lines or words) and is exposed to plugins through `editor.computeDiff`.
 
 use std::ops::Range;
 
@@ -82,6 +85,153 @@ pub fn diff_lines(saved: &[u8], current: &[u8]) -> LineDiff {
     }
 }
 
+/// A region where two token sequences differ, as index ranges into each side.
+///
+/// An empty `old` range is a pure insertion, an empty `new` range a pure deletion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Hunk {
+    pub old: Range<usize>,
+    pub new: Range<usize>,
+}
+
+/// Upper bound on LCS table cells; larger inputs get one coarse hunk instead.
+const MAX_LCS_CELLS: usize = 16_000_000;
+
+/// Compute the hunks that turn `old` into `new`.
+///
+/// Common leading and trailing tokens are stripped before running the LCS, so
+/// the quadratic table only covers the changed middle. If that middle is still
+/// too large, it is reported as a single replacement hunk.
+pub fn diff_hunks<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Hunk> {
+    let prefix = old
+        .iter()
+        .zip(new.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+
+    if old_mid.is_empty() && new_mid.is_empty() {
+        return vec![];
+    }
+    if old_mid.is_empty()
+        || new_mid.is_empty()
+        || old_mid.len().saturating_mul(new_mid.len()) > MAX_LCS_CELLS
+    {
+        return vec![Hunk {
+            old: prefix..prefix + old_mid.len(),
+            new: prefix..prefix + new_mid.len(),
+        }];
+    }
+
+    let lcs = longest_common_subsequence(old_mid, new_mid);
+    let end = LineMatch {
+        saved_idx: old_mid.len(),
+        current_idx: new_mid.len(),
+    };
+
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    for m in lcs.iter().chain(std::iter::once(&end)) {
+        if m.saved_idx > i || m.current_idx > j {
+            hunks.push(Hunk {
+                old: prefix + i..prefix + m.saved_idx,
+                new: prefix + j..prefix + m.current_idx,
+            });
+        }
+        i = m.saved_idx + 1;
+        j = m.current_idx + 1;
+    }
+    hunks
+}
+
+/// Split text into lines, keeping each line's terminator so tokens concatenate
+/// back to the original text.
+pub fn split_lines_inclusive(text: &str) -> Vec<&str> {
+    text.split_inclusive('\n').collect()
+}
+
+/// Split text into word tokens: runs of word characters, runs of whitespace,
+/// and single punctuation characters. Tokens concatenate back to the original.
+pub fn split_words(text: &str) -> Vec<&str> {
+    #[derive(PartialEq)]
+    enum Class {
+        Word,
+        Space,
+        Other,
+    }
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            Class::Word
+        } else if c.is_whitespace() {
+            Class::Space
+        } else {
+            Class::Other
+        }
+    };
+
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let mut prev: Option<Class> = None;
+    for (idx, c) in text.char_indices() {
+        let cls = class(c);
+        let continues = matches!(&prev, Some(p) if *p == cls && cls != Class::Other);
+        if !continues && idx > start {
+            tokens.push(&text[start..idx]);
+            start = idx;
+        }
+        prev = Some(cls);
+    }
+    if start < text.len() {
+        tokens.push(&text[start..]);
+    }
+    tokens
+}
+
+/// Character ranges that differ between two versions of a line, compared
+/// word by word.
+///
+/// Returns `(old_ranges, new_ranges)` as character (not byte) offsets into
+/// each line. Only the words that changed are covered, so a one-word edit in
+/// a long line is highlighted on its own instead of from the first to the
+/// last differing character.
+pub fn word_diff_ranges(old: &str, new: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
+    let old_tokens = split_words(old);
+    let new_tokens = split_words(new);
+    let old_offsets = token_char_offsets(&old_tokens);
+    let new_offsets = token_char_offsets(&new_tokens);
+
+    let mut old_ranges = Vec::new();
+    let mut new_ranges = Vec::new();
+    for hunk in diff_hunks(&old_tokens, &new_tokens) {
+        if !hunk.old.is_empty() {
+            old_ranges.push(old_offsets[hunk.old.start]..old_offsets[hunk.old.end]);
+        }
+        if !hunk.new.is_empty() {
+            new_ranges.push(new_offsets[hunk.new.start]..new_offsets[hunk.new.end]);
+        }
+    }
+    (merge_ranges(old_ranges), merge_ranges(new_ranges))
+}
+
+/// Character offset where each token starts, plus the total length at the end
+fn token_char_offsets(tokens: &[&str]) -> Vec<usize> {
+    let mut offsets = Vec::with_capacity(tokens.len() + 1);
+    let mut offset = 0;
+    offsets.push(0);
+    for token in tokens {
+        offset += token.chars().count();
+        offsets.push(offset);
+    }
+    offsets
+}
+
 /// Represents a match between saved and current line indices
 #[derive(Debug, Clone, Copy)]
 struct LineMatch {
@@ -91,7 +241,7 @@ struct LineMatch {
 
 /// Find the longest common subsequence of lines between saved and current.
 /// Returns a list of LineMatch with both saved and current indices.
-fn longest_common_subsequence(saved: &[&[u8]], current: &[&[u8]]) -> Vec<LineMatch> {
+fn longest_common_subsequence<T: PartialEq>(saved: &[T], current: &[T]) -> Vec<LineMatch> {
     let n = saved.len();
     let m = current.len();
 
@@ -303,6 +453,76 @@ pub fn merge_ranges(ranges: Vec<Range<usize>>) -> Vec<Range<usize>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_word_diff_ranges_highlight_changed_words_only() {
+        let (old, new) = word_diff_ranges(
+            "let total = price * quantity + shipping;",
+            "let total = price * amount + shipping;",
+        );
+        assert_eq!(old, vec![20..28]);
+        assert_eq!(new, vec![20..26]);
+
+        // Two separate edits stay separate instead of spanning the middle
+        let (old, new) = word_diff_ranges("a b c d e", "x b c d y");
+        assert_eq!(old, vec![0..1, 8..9]);
+        assert_eq!(new, vec![0..1, 8..9]);
+
+        // Offsets count characters, not bytes
+        let (old, new) = word_diff_ranges("héllo wörld", "héllo world");
+        assert_eq!(old, vec![6..11]);
+        assert_eq!(new, vec![6..11]);
+
+        // Pure insertion only marks the new side
+        let (old, new) = word_diff_ranges("foo()", "foo(bar)");
+        assert!(old.is_empty());
+        assert_eq!(new, vec![4..7]);
+    }
+
+    #[test]
+    fn test_diff_hunks_replace_insert_delete() {
+        let old = ["a", "b", "c", "d"];
+        let new = ["a", "x", "c", "d", "e"];
+        assert_eq!(
+            diff_hunks(&old, &new),
+            vec![
+                Hunk {
+                    old: 1..2,
+                    new: 1..2
+                },
+                Hunk {
+                    old: 4..4,
+                    new: 4..5
+                },
+            ]
+        );
+
+        let hunks = diff_hunks(&["a", "b", "c"], &["a", "c"]);
+        assert_eq!(
+            hunks,
+            vec![Hunk {
+                old: 1..2,
+                new: 1..1
+            }]
+        );
+
+        assert!(diff_hunks(&["same"], &["same"]).is_empty());
+    }
+
+    #[test]
+    fn test_split_words_round_trips() {
+        let text = "let foo_bar = baz(1, 2);\n";
+        let tokens = split_words(text);
+        assert_eq!(
+            tokens,
+            vec![
+                "let", " ", "foo_bar", " ", "=", " ", "baz", "(", "1", ",", " ", "2", ")", ";",
+                "\n"
+            ]
+        );
+        assert_eq!(tokens.concat(), text);
+        assert_eq!(split_lines_inclusive("a\nb").concat(), "a\nb");
+    }
+
     #[test]
     fn test_identical_content() {
         let content = b"line 1\nline 2\nline 3\n";
@@ -8,6 +8,7 @@ pub mod control_event;
 pub mod cursor;
 pub mod document_model;
 pub mod edit;
+pub mod encoding;
 pub mod event;
 pub mod filesystem;
 pub mod line_diff;
@@ -5,10 +5,12 @@
 
 use crate::config::{
     AcceptSuggestionOnEnter, CursorStyle, FileBrowserConfig, FileExplorerConfig, FormatterConfig,
-    HighlighterPreference, Keybinding, KeybindingMapName, KeymapConfig, LanguageConfig,
-    LineEndingOption, OnSaveAction, PluginConfig, TerminalConfig, ThemeName, WarningsConfig,
+    HighlighterPreference, Keybinding, KeybindingMapName, KeyboardLayout, KeymapConfig,
+    LanguageConfig, LineEndingOption, OnSaveAction, PluginConfig, StatusBarSegmentConfig,
+    TerminalConfig, ThemeName, ViewConfig, WarningsConfig,
 };
 use crate::types::LspServerConfig;
+use crate::view::theme::ColorVision;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -81,6 +83,7 @@ pub struct PartialConfig {
     pub file_explorer: Option<PartialFileExplorerConfig>,
     pub file_browser: Option<PartialFileBrowserConfig>,
     pub terminal: Option<PartialTerminalConfig>,
+    pub view: Option<PartialViewConfig>,
     pub keybindings: Option<Vec<Keybinding>>,
     pub keybinding_maps: Option<HashMap<String, KeymapConfig>>,
     pub active_keybinding_map: Option<KeybindingMapName>,
@@ -89,6 +92,7 @@ pub struct PartialConfig {
     pub warnings: Option<PartialWarningsConfig>,
     pub plugins: Option<HashMap<String, PartialPluginConfig>>,
     pub packages: Option<PartialPackagesConfig>,
+    pub plugin_fetch: Option<PartialPluginFetchConfig>,
 }
 
 impl Merge for PartialConfig {
@@ -103,8 +107,10 @@ impl Merge for PartialConfig {
         merge_partial(&mut self.file_explorer, &other.file_explorer);
         merge_partial(&mut self.file_browser, &other.file_browser);
         merge_partial(&mut self.terminal, &other.terminal);
+        merge_partial(&mut self.view, &other.view);
         merge_partial(&mut self.warnings, &other.warnings);
         merge_partial(&mut self.packages, &other.packages);
+        merge_partial(&mut self.plugin_fetch, &other.plugin_fetch);
 
         // Lists: higher precedence replaces (per design doc)
         self.keybindings.merge_from(&other.keybindings);
@@ -139,9 +145,14 @@ pub struct PartialEditorConfig {
     pub relative_line_numbers: Option<bool>,
     pub scroll_offset: Option<usize>,
     pub syntax_highlighting: Option<bool>,
+    pub highlight_trailing_whitespace: Option<bool>,
+    pub sticky_scroll: Option<bool>,
+    pub sticky_scroll_max_depth: Option<usize>,
     pub line_wrap: Option<bool>,
     pub highlight_timeout_ms: Option<u64>,
     pub snapshot_interval: Option<usize>,
+    pub undo_branch_limit: Option<usize>,
+    pub cache_budget_mb: Option<usize>,
     pub large_file_threshold_bytes: Option<u64>,
     pub estimated_line_length: Option<usize>,
     pub enable_inlay_hints: Option<bool>,
@@ -153,23 +164,38 @@ pub struct PartialEditorConfig {
     pub mouse_hover_delay_ms: Option<u64>,
     pub double_click_time_ms: Option<u64>,
     pub auto_revert_poll_interval_ms: Option<u64>,
+    pub rpc_server: Option<bool>,
     pub file_tree_poll_interval_ms: Option<u64>,
     pub default_line_ending: Option<LineEndingOption>,
     pub trim_trailing_whitespace_on_save: Option<bool>,
     pub ensure_final_newline_on_save: Option<bool>,
+    pub digraphs: Option<HashMap<String, String>>,
+    pub code_actions_on_save_timeout_ms: Option<u64>,
+    pub subword_navigation: Option<bool>,
+    pub clipboard_history_size: Option<usize>,
+    pub clipboard_history_poll_system: Option<bool>,
+    pub reflow_column: Option<usize>,
+    pub protect_library_files: Option<bool>,
     pub highlight_matching_brackets: Option<bool>,
     pub rainbow_brackets: Option<bool>,
     pub cursor_style: Option<CursorStyle>,
+    pub cursor_style_overwrite: Option<CursorStyle>,
+    pub cursor_style_modes: Option<HashMap<String, CursorStyle>>,
+    pub status_bar_template: Option<String>,
+    pub status_bar_segments: Option<HashMap<String, StatusBarSegmentConfig>>,
     pub keyboard_disambiguate_escape_codes: Option<bool>,
     pub keyboard_report_event_types: Option<bool>,
     pub keyboard_report_alternate_keys: Option<bool>,
     pub keyboard_report_all_keys_as_escape_codes: Option<bool>,
+    pub keyboard_layout: Option<KeyboardLayout>,
     pub quick_suggestions: Option<bool>,
     pub quick_suggestions_delay_ms: Option<u64>,
     pub suggest_on_trigger_characters: Option<bool>,
     pub accept_suggestion_on_enter: Option<AcceptSuggestionOnEnter>,
+    pub inline_suggestion_delay_ms: Option<u64>,
     pub show_menu_bar: Option<bool>,
     pub show_tab_bar: Option<bool>,
+    pub show_absolute_paths: Option<bool>,
     pub use_terminal_bg: Option<bool>,
 }
 
@@ -183,10 +209,17 @@ impl Merge for PartialEditorConfig {
         self.scroll_offset.merge_from(&other.scroll_offset);
         self.syntax_highlighting
             .merge_from(&other.syntax_highlighting);
+        self.highlight_trailing_whitespace
+            .merge_from(&other.highlight_trailing_whitespace);
+        self.sticky_scroll.merge_from(&other.sticky_scroll);
+        self.sticky_scroll_max_depth
+            .merge_from(&other.sticky_scroll_max_depth);
         self.line_wrap.merge_from(&other.line_wrap);
         self.highlight_timeout_ms
             .merge_from(&other.highlight_timeout_ms);
         self.snapshot_interval.merge_from(&other.snapshot_interval);
+        self.undo_branch_limit.merge_from(&other.undo_branch_limit);
+        self.cache_budget_mb.merge_from(&other.cache_budget_mb);
         self.large_file_threshold_bytes
             .merge_from(&other.large_file_threshold_bytes);
         self.estimated_line_length
@@ -208,6 +241,7 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.double_click_time_ms);
         self.auto_revert_poll_interval_ms
             .merge_from(&other.auto_revert_poll_interval_ms);
+        self.rpc_server.merge_from(&other.rpc_server);
         self.file_tree_poll_interval_ms
             .merge_from(&other.file_tree_poll_interval_ms);
         self.default_line_ending
@@ -216,10 +250,28 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.trim_trailing_whitespace_on_save);
         self.ensure_final_newline_on_save
             .merge_from(&other.ensure_final_newline_on_save);
+        merge_hashmap(&mut self.digraphs, &other.digraphs);
+        self.code_actions_on_save_timeout_ms
+            .merge_from(&other.code_actions_on_save_timeout_ms);
+        self.subword_navigation
+            .merge_from(&other.subword_navigation);
+        self.clipboard_history_size
+            .merge_from(&other.clipboard_history_size);
+        self.clipboard_history_poll_system
+            .merge_from(&other.clipboard_history_poll_system);
+        self.reflow_column.merge_from(&other.reflow_column);
+        self.protect_library_files
+            .merge_from(&other.protect_library_files);
         self.highlight_matching_brackets
             .merge_from(&other.highlight_matching_brackets);
         self.rainbow_brackets.merge_from(&other.rainbow_brackets);
         self.cursor_style.merge_from(&other.cursor_style);
+        self.cursor_style_overwrite
+            .merge_from(&other.cursor_style_overwrite);
+        merge_hashmap(&mut self.cursor_style_modes, &other.cursor_style_modes);
+        self.status_bar_template
+            .merge_from(&other.status_bar_template);
+        merge_hashmap(&mut self.status_bar_segments, &other.status_bar_segments);
         self.keyboard_disambiguate_escape_codes
             .merge_from(&other.keyboard_disambiguate_escape_codes);
         self.keyboard_report_event_types
@@ -228,6 +280,7 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.keyboard_report_alternate_keys);
         self.keyboard_report_all_keys_as_escape_codes
             .merge_from(&other.keyboard_report_all_keys_as_escape_codes);
+        self.keyboard_layout.merge_from(&other.keyboard_layout);
         self.quick_suggestions.merge_from(&other.quick_suggestions);
         self.quick_suggestions_delay_ms
             .merge_from(&other.quick_suggestions_delay_ms);
@@ -235,8 +288,12 @@ impl Merge for PartialEditorConfig {
             .merge_from(&other.suggest_on_trigger_characters);
         self.accept_suggestion_on_enter
             .merge_from(&other.accept_suggestion_on_enter);
+        self.inline_suggestion_delay_ms
+            .merge_from(&other.inline_suggestion_delay_ms);
         self.show_menu_bar.merge_from(&other.show_menu_bar);
         self.show_tab_bar.merge_from(&other.show_tab_bar);
+        self.show_absolute_paths
+            .merge_from(&other.show_absolute_paths);
         self.use_terminal_bg.merge_from(&other.use_terminal_bg);
     }
 }
@@ -268,11 +325,13 @@ impl Merge for PartialFileExplorerConfig {
 #[serde(default)]
 pub struct PartialFileBrowserConfig {
     pub show_hidden: Option<bool>,
+    pub native_dialogs: Option<bool>,
 }
 
 impl Merge for PartialFileBrowserConfig {
     fn merge_from(&mut self, other: &Self) {
         self.show_hidden.merge_from(&other.show_hidden);
+        self.native_dialogs.merge_from(&other.native_dialogs);
     }
 }
 
@@ -290,6 +349,19 @@ impl Merge for PartialTerminalConfig {
     }
 }
 
+/// Partial view configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialViewConfig {
+    pub color_vision: Option<ColorVision>,
+}
+
+impl Merge for PartialViewConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.color_vision.merge_from(&other.color_vision);
+    }
+}
+
 /// Partial warnings configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -317,6 +389,25 @@ impl Merge for PartialPackagesConfig {
     }
 }
 
+/// Partial limits on plugin HTTP requests.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PartialPluginFetchConfig {
+    pub allowed_hosts: Option<Vec<String>>,
+    pub default_timeout_ms: Option<u64>,
+    pub max_response_bytes: Option<u64>,
+}
+
+impl Merge for PartialPluginFetchConfig {
+    fn merge_from(&mut self, other: &Self) {
+        self.allowed_hosts.merge_from(&other.allowed_hosts);
+        self.default_timeout_ms
+            .merge_from(&other.default_timeout_ms);
+        self.max_response_bytes
+            .merge_from(&other.max_response_bytes);
+    }
+}
+
 /// Partial plugin configuration.
 #[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
@@ -325,12 +416,15 @@ pub struct PartialPluginConfig {
     pub enabled: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<std::path::PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub permissions: Option<Vec<String>>,
 }
 
 impl Merge for PartialPluginConfig {
     fn merge_from(&mut self, other: &Self) {
         self.enabled.merge_from(&other.enabled);
         self.path.merge_from(&other.path);
+        self.permissions.merge_from(&other.permissions);
     }
 }
 
@@ -350,6 +444,7 @@ pub struct PartialLanguageConfig {
     pub tab_size: Option<usize>,
     pub formatter: Option<FormatterConfig>,
     pub format_on_save: Option<bool>,
+    pub code_actions_on_save: Option<Vec<String>>,
     pub on_save: Option<Vec<OnSaveAction>>,
 }
 
@@ -368,6 +463,8 @@ impl Merge for PartialLanguageConfig {
         self.tab_size.merge_from(&other.tab_size);
         self.formatter.merge_from(&other.formatter);
         self.format_on_save.merge_from(&other.format_on_save);
+        self.code_actions_on_save
+            .merge_from(&other.code_actions_on_save);
         self.on_save.merge_from(&other.on_save);
     }
 }
@@ -388,6 +485,8 @@ impl Merge for LspServerConfig {
         if self.initialization_options.is_none() {
             self.initialization_options = other.initialization_options.clone();
         }
+        // Settings are deep-merged so layers can override individual keys
+        self.settings = crate::types::merge_settings(other.settings.as_ref(), self.settings.take());
     }
 }
 
@@ -402,9 +501,14 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             relative_line_numbers: Some(cfg.relative_line_numbers),
             scroll_offset: Some(cfg.scroll_offset),
             syntax_highlighting: Some(cfg.syntax_highlighting),
+            highlight_trailing_whitespace: Some(cfg.highlight_trailing_whitespace),
+            sticky_scroll: Some(cfg.sticky_scroll),
+            sticky_scroll_max_depth: Some(cfg.sticky_scroll_max_depth),
             line_wrap: Some(cfg.line_wrap),
             highlight_timeout_ms: Some(cfg.highlight_timeout_ms),
             snapshot_interval: Some(cfg.snapshot_interval),
+            undo_branch_limit: Some(cfg.undo_branch_limit),
+            cache_budget_mb: Some(cfg.cache_budget_mb),
             large_file_threshold_bytes: Some(cfg.large_file_threshold_bytes),
             estimated_line_length: Some(cfg.estimated_line_length),
             enable_inlay_hints: Some(cfg.enable_inlay_hints),
@@ -416,25 +520,40 @@ impl From<&crate::config::EditorConfig> for PartialEditorConfig {
             mouse_hover_delay_ms: Some(cfg.mouse_hover_delay_ms),
             double_click_time_ms: Some(cfg.double_click_time_ms),
             auto_revert_poll_interval_ms: Some(cfg.auto_revert_poll_interval_ms),
+            rpc_server: Some(cfg.rpc_server),
             file_tree_poll_interval_ms: Some(cfg.file_tree_poll_interval_ms),
             default_line_ending: Some(cfg.default_line_ending.clone()),
             trim_trailing_whitespace_on_save: Some(cfg.trim_trailing_whitespace_on_save),
             ensure_final_newline_on_save: Some(cfg.ensure_final_newline_on_save),
+            digraphs: Some(cfg.digraphs.clone()),
+            code_actions_on_save_timeout_ms: Some(cfg.code_actions_on_save_timeout_ms),
+            subword_navigation: Some(cfg.subword_navigation),
+            clipboard_history_size: Some(cfg.clipboard_history_size),
+            clipboard_history_poll_system: Some(cfg.clipboard_history_poll_system),
+            reflow_column: Some(cfg.reflow_column),
+            protect_library_files: Some(cfg.protect_library_files),
             highlight_matching_brackets: Some(cfg.highlight_matching_brackets),
             rainbow_brackets: Some(cfg.rainbow_brackets),
             cursor_style: Some(cfg.cursor_style),
+            cursor_style_overwrite: Some(cfg.cursor_style_overwrite),
+            cursor_style_modes: Some(cfg.cursor_style_modes.clone()),
+            status_bar_template: Some(cfg.status_bar_template.clone()),
+            status_bar_segments: Some(cfg.status_bar_segments.clone()),
             keyboard_disambiguate_escape_codes: Some(cfg.keyboard_disambiguate_escape_codes),
             keyboard_report_event_types: Some(cfg.keyboard_report_event_types),
             keyboard_report_alternate_keys: Some(cfg.keyboard_report_alternate_keys),
             keyboard_report_all_keys_as_escape_codes: Some(
                 cfg.keyboard_report_all_keys_as_escape_codes,
             ),
+            keyboard_layout: Some(cfg.keyboard_layout),
             quick_suggestions: Some(cfg.quick_suggestions),
             quick_suggestions_delay_ms: Some(cfg.quick_suggestions_delay_ms),
             suggest_on_trigger_characters: Some(cfg.suggest_on_trigger_characters),
             accept_suggestion_on_enter: Some(cfg.accept_suggestion_on_enter),
+            inline_suggestion_delay_ms: Some(cfg.inline_suggestion_delay_ms),
             show_menu_bar: Some(cfg.show_menu_bar),
             show_tab_bar: Some(cfg.show_tab_bar),
+            show_absolute_paths: Some(cfg.show_absolute_paths),
             use_terminal_bg: Some(cfg.use_terminal_bg),
         }
     }
@@ -454,11 +573,20 @@ impl PartialEditorConfig {
             syntax_highlighting: self
                 .syntax_highlighting
                 .unwrap_or(defaults.syntax_highlighting),
+            highlight_trailing_whitespace: self
+                .highlight_trailing_whitespace
+                .unwrap_or(defaults.highlight_trailing_whitespace),
+            sticky_scroll: self.sticky_scroll.unwrap_or(defaults.sticky_scroll),
+            sticky_scroll_max_depth: self
+                .sticky_scroll_max_depth
+                .unwrap_or(defaults.sticky_scroll_max_depth),
             line_wrap: self.line_wrap.unwrap_or(defaults.line_wrap),
             highlight_timeout_ms: self
                 .highlight_timeout_ms
                 .unwrap_or(defaults.highlight_timeout_ms),
             snapshot_interval: self.snapshot_interval.unwrap_or(defaults.snapshot_interval),
+            undo_branch_limit: self.undo_branch_limit.unwrap_or(defaults.undo_branch_limit),
+            cache_budget_mb: self.cache_budget_mb.unwrap_or(defaults.cache_budget_mb),
             large_file_threshold_bytes: self
                 .large_file_threshold_bytes
                 .unwrap_or(defaults.large_file_threshold_bytes),
@@ -490,6 +618,7 @@ impl PartialEditorConfig {
             auto_revert_poll_interval_ms: self
                 .auto_revert_poll_interval_ms
                 .unwrap_or(defaults.auto_revert_poll_interval_ms),
+            rpc_server: self.rpc_server.unwrap_or(defaults.rpc_server),
             file_tree_poll_interval_ms: self
                 .file_tree_poll_interval_ms
                 .unwrap_or(defaults.file_tree_poll_interval_ms),
@@ -502,11 +631,40 @@ impl PartialEditorConfig {
             ensure_final_newline_on_save: self
                 .ensure_final_newline_on_save
                 .unwrap_or(defaults.ensure_final_newline_on_save),
+            digraphs: self.digraphs.unwrap_or_else(|| defaults.digraphs.clone()),
+            code_actions_on_save_timeout_ms: self
+                .code_actions_on_save_timeout_ms
+                .unwrap_or(defaults.code_actions_on_save_timeout_ms),
+            subword_navigation: self
+                .subword_navigation
+                .unwrap_or(defaults.subword_navigation),
+            clipboard_history_size: self
+                .clipboard_history_size
+                .unwrap_or(defaults.clipboard_history_size),
+            clipboard_history_poll_system: self
+                .clipboard_history_poll_system
+                .unwrap_or(defaults.clipboard_history_poll_system),
+            reflow_column: self.reflow_column.unwrap_or(defaults.reflow_column),
+            protect_library_files: self
+                .protect_library_files
+                .unwrap_or(defaults.protect_library_files),
             highlight_matching_brackets: self
                 .highlight_matching_brackets
                 .unwrap_or(defaults.highlight_matching_brackets),
             rainbow_brackets: self.rainbow_brackets.unwrap_or(defaults.rainbow_brackets),
             cursor_style: self.cursor_style.unwrap_or(defaults.cursor_style),
+            cursor_style_overwrite: self
+                .cursor_style_overwrite
+                .unwrap_or(defaults.cursor_style_overwrite),
+            cursor_style_modes: self
+                .cursor_style_modes
+                .unwrap_or_else(|| defaults.cursor_style_modes.clone()),
+            status_bar_template: self
+                .status_bar_template
+                .unwrap_or_else(|| defaults.status_bar_template.clone()),
+            status_bar_segments: self
+                .status_bar_segments
+                .unwrap_or_else(|| defaults.status_bar_segments.clone()),
             keyboard_disambiguate_escape_codes: self
                 .keyboard_disambiguate_escape_codes
                 .unwrap_or(defaults.keyboard_disambiguate_escape_codes),
@@ -519,6 +677,7 @@ impl PartialEditorConfig {
             keyboard_report_all_keys_as_escape_codes: self
                 .keyboard_report_all_keys_as_escape_codes
                 .unwrap_or(defaults.keyboard_report_all_keys_as_escape_codes),
+            keyboard_layout: self.keyboard_layout.unwrap_or(defaults.keyboard_layout),
             quick_suggestions: self.quick_suggestions.unwrap_or(defaults.quick_suggestions),
             quick_suggestions_delay_ms: self
                 .quick_suggestions_delay_ms
@@ -529,8 +688,14 @@ impl PartialEditorConfig {
             accept_suggestion_on_enter: self
                 .accept_suggestion_on_enter
                 .unwrap_or(defaults.accept_suggestion_on_enter),
+            inline_suggestion_delay_ms: self
+                .inline_suggestion_delay_ms
+                .unwrap_or(defaults.inline_suggestion_delay_ms),
             show_menu_bar: self.show_menu_bar.unwrap_or(defaults.show_menu_bar),
             show_tab_bar: self.show_tab_bar.unwrap_or(defaults.show_tab_bar),
+            show_absolute_paths: self
+                .show_absolute_paths
+                .unwrap_or(defaults.show_absolute_paths),
             use_terminal_bg: self.use_terminal_bg.unwrap_or(defaults.use_terminal_bg),
         }
     }
@@ -566,6 +731,7 @@ impl From<&FileBrowserConfig> for PartialFileBrowserConfig {
     fn from(cfg: &FileBrowserConfig) -> Self {
         Self {
             show_hidden: Some(cfg.show_hidden),
+            native_dialogs: Some(cfg.native_dialogs),
         }
     }
 }
@@ -574,6 +740,7 @@ impl PartialFileBrowserConfig {
     pub fn resolve(self, defaults: &FileBrowserConfig) -> FileBrowserConfig {
         FileBrowserConfig {
             show_hidden: self.show_hidden.unwrap_or(defaults.show_hidden),
+            native_dialogs: self.native_dialogs.unwrap_or(defaults.native_dialogs),
         }
     }
 }
@@ -596,6 +763,22 @@ impl PartialTerminalConfig {
     }
 }
 
+impl From<&ViewConfig> for PartialViewConfig {
+    fn from(cfg: &ViewConfig) -> Self {
+        Self {
+            color_vision: Some(cfg.color_vision),
+        }
+    }
+}
+
+impl PartialViewConfig {
+    pub fn resolve(self, defaults: &ViewConfig) -> ViewConfig {
+        ViewConfig {
+            color_vision: self.color_vision.unwrap_or(defaults.color_vision),
+        }
+    }
+}
+
 impl From<&WarningsConfig> for PartialWarningsConfig {
     fn from(cfg: &WarningsConfig) -> Self {
         Self {
@@ -633,11 +816,41 @@ impl PartialPackagesConfig {
     }
 }
 
+impl From<&crate::config::PluginFetchConfig> for PartialPluginFetchConfig {
+    fn from(cfg: &crate::config::PluginFetchConfig) -> Self {
+        Self {
+            allowed_hosts: Some(cfg.allowed_hosts.clone()),
+            default_timeout_ms: Some(cfg.default_timeout_ms),
+            max_response_bytes: Some(cfg.max_response_bytes),
+        }
+    }
+}
+
+impl PartialPluginFetchConfig {
+    pub fn resolve(
+        self,
+        defaults: &crate::config::PluginFetchConfig,
+    ) -> crate::config::PluginFetchConfig {
+        crate::config::PluginFetchConfig {
+            allowed_hosts: self
+                .allowed_hosts
+                .unwrap_or_else(|| defaults.allowed_hosts.clone()),
+            default_timeout_ms: self
+                .default_timeout_ms
+                .unwrap_or(defaults.default_timeout_ms),
+            max_response_bytes: self
+                .max_response_bytes
+                .unwrap_or(defaults.max_response_bytes),
+        }
+    }
+}
+
 impl From<&PluginConfig> for PartialPluginConfig {
     fn from(cfg: &PluginConfig) -> Self {
         Self {
             enabled: Some(cfg.enabled),
             path: cfg.path.clone(),
+            permissions: Some(cfg.permissions.clone()),
         }
     }
 }
@@ -647,6 +860,9 @@ impl PartialPluginConfig {
         PluginConfig {
             enabled: self.enabled.unwrap_or(defaults.enabled),
             path: self.path.or_else(|| defaults.path.clone()),
+            permissions: self
+                .permissions
+                .unwrap_or_else(|| defaults.permissions.clone()),
         }
     }
 }
@@ -666,6 +882,7 @@ impl From<&LanguageConfig> for PartialLanguageConfig {
             tab_size: cfg.tab_size,
             formatter: cfg.formatter.clone(),
             format_on_save: Some(cfg.format_on_save),
+            code_actions_on_save: Some(cfg.code_actions_on_save.clone()),
             on_save: Some(cfg.on_save.clone()),
         }
     }
@@ -694,6 +911,9 @@ impl PartialLanguageConfig {
             tab_size: self.tab_size.or(defaults.tab_size),
             formatter: self.formatter.or_else(|| defaults.formatter.clone()),
             format_on_save: self.format_on_save.unwrap_or(defaults.format_on_save),
+            code_actions_on_save: self
+                .code_actions_on_save
+                .unwrap_or_else(|| defaults.code_actions_on_save.clone()),
             on_save: self.on_save.unwrap_or_else(|| defaults.on_save.clone()),
         }
     }
@@ -710,6 +930,7 @@ impl From<&crate::config::Config> for PartialConfig {
             file_explorer: Some(PartialFileExplorerConfig::from(&cfg.file_explorer)),
             file_browser: Some(PartialFileBrowserConfig::from(&cfg.file_browser)),
             terminal: Some(PartialTerminalConfig::from(&cfg.terminal)),
+            view: Some(PartialViewConfig::from(&cfg.view)),
             keybindings: Some(cfg.keybindings.clone()),
             keybinding_maps: Some(cfg.keybinding_maps.clone()),
             active_keybinding_map: Some(cfg.active_keybinding_map.clone()),
@@ -728,13 +949,17 @@ impl From<&crate::config::Config> for PartialConfig {
                 let non_default_plugins: HashMap<String, PartialPluginConfig> = cfg
                     .plugins
                     .iter()
-                    .filter(|(_, v)| v.enabled != default_plugin.enabled)
+                    .filter(|(_, v)| {
+                        v.enabled != default_plugin.enabled || !v.permissions.is_empty()
+                    })
                     .map(|(k, v)| {
                         (
                             k.clone(),
                             PartialPluginConfig {
                                 enabled: Some(v.enabled),
                                 path: None, // Don't save path - it's auto-discovered
+                                permissions: Some(v.permissions.clone())
+                                    .filter(|permissions| !permissions.is_empty()),
                             },
                         )
                     })
@@ -746,6 +971,7 @@ impl From<&crate::config::Config> for PartialConfig {
                 }
             },
             packages: Some(PartialPackagesConfig::from(&cfg.packages)),
+            plugin_fetch: Some(PartialPluginFetchConfig::from(&cfg.plugin_fetch)),
         }
     }
 }
@@ -833,6 +1059,10 @@ impl PartialConfig {
                 .terminal
                 .map(|e| e.resolve(&defaults.terminal))
                 .unwrap_or_else(|| defaults.terminal.clone()),
+            view: self
+                .view
+                .map(|e| e.resolve(&defaults.view))
+                .unwrap_or_else(|| defaults.view.clone()),
             keybindings: self
                 .keybindings
                 .unwrap_or_else(|| defaults.keybindings.clone()),
@@ -851,6 +1081,10 @@ impl PartialConfig {
                 .packages
                 .map(|e| e.resolve(&defaults.packages))
                 .unwrap_or_else(|| defaults.packages.clone()),
+            plugin_fetch: self
+                .plugin_fetch
+                .map(|e| e.resolve(&defaults.plugin_fetch))
+                .unwrap_or_else(|| defaults.plugin_fetch.clone()),
         }
     }
 }
@@ -871,6 +1105,7 @@ impl Default for LanguageConfig {
             tab_size: None,
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: Vec::new(),
             on_save: Vec::new(),
         }
     }
@@ -1178,6 +1413,7 @@ mod tests {
             PluginConfig {
                 enabled: true, // Default value
                 path: Some(std::path::PathBuf::from("/path/to/plugin.ts")),
+                permissions: Vec::new(),
             },
         );
 
@@ -1199,6 +1435,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/path/to/enabled.ts")),
+                permissions: Vec::new(),
             },
         );
         config.plugins.insert(
@@ -1206,6 +1443,7 @@ mod tests {
             PluginConfig {
                 enabled: false, // Not default!
                 path: Some(std::path::PathBuf::from("/path/to/disabled.ts")),
+                permissions: Vec::new(),
             },
         );
 
@@ -1238,6 +1476,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/some/path/plugin.ts")),
+                permissions: Vec::new(),
             },
         );
 
@@ -1251,6 +1490,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn plugins_with_granted_permissions_are_serialized() {
+        let mut config = crate::config::Config::default();
+        config.plugins.insert(
+            "my_plugin".to_string(),
+            PluginConfig {
+                enabled: true,
+                path: Some(std::path::PathBuf::from("/some/path/plugin.ts")),
+                permissions: vec!["fs-read".to_string()],
+            },
+        );
+
+        let partial = PartialConfig::from(&config);
+        let plugins = partial.plugins.unwrap();
+        let plugin = plugins.get("my_plugin").unwrap();
+        assert_eq!(plugin.permissions, Some(vec!["fs-read".to_string()]));
+
+        let resolved = PartialConfig {
+            plugins: Some(plugins),
+            ..Default::default()
+        }
+        .resolve();
+        assert_eq!(resolved.plugins["my_plugin"].permissions, vec!["fs-read"]);
+    }
+
     #[test]
     fn resolving_partial_with_disabled_plugin_preserves_state() {
         // Loading a config with a disabled plugin should preserve disabled state
@@ -1260,6 +1524,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false),
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1288,6 +1553,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(false), // User disabled
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1299,6 +1565,7 @@ mod tests {
                 PartialPluginConfig {
                     enabled: Some(true), // Lower layer has it enabled
                     path: None,
+                    permissions: None,
                 },
             )])),
             ..Default::default()
@@ -1325,6 +1592,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/a.ts")),
+                permissions: Vec::new(),
             },
         );
         config.plugins.insert(
@@ -1332,6 +1600,7 @@ mod tests {
             PluginConfig {
                 enabled: false,
                 path: Some(std::path::PathBuf::from("/b.ts")),
+                permissions: Vec::new(),
             },
         );
         config.plugins.insert(
@@ -1339,6 +1608,7 @@ mod tests {
             PluginConfig {
                 enabled: true,
                 path: Some(std::path::PathBuf::from("/c.ts")),
+                permissions: Vec::new(),
             },
         );
 
@@ -5,6 +5,7 @@
 
 use crate::primitives::display_width::{char_width, str_width};
 use ratatui::style::{Color, Modifier, Style};
+use std::ops::Range;
 
 /// Standard ANSI colors (codes 30-37 for fg, 40-47 for bg)
 const STANDARD_COLORS: [Color; 8] = [
@@ -70,6 +71,11 @@ impl AnsiParser {
         self.current_style
     }
 
+    /// Whether the last parsed character started or continued an unfinished escape sequence
+    pub fn in_escape(&self) -> bool {
+        self.in_escape
+    }
+
     /// Reset the parser state
     pub fn reset(&mut self) {
         self.current_style = Style::default();
@@ -299,6 +305,86 @@ pub fn strip_ansi_codes(text: &str) -> String {
     result
 }
 
+/// Byte ranges of the escape sequences in a string, in order
+///
+/// An escape sequence left unfinished at the end of the string runs to its end.
+pub fn escape_sequence_ranges(text: &str) -> Vec<Range<usize>> {
+    if !contains_ansi_codes(text) {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    let mut parser = AnsiParser::new();
+    let mut start = None;
+
+    for (idx, ch) in text.char_indices() {
+        if parser.parse_char(ch).is_some() {
+            continue;
+        }
+        let sequence_start = *start.get_or_insert(idx);
+        if !parser.in_escape() {
+            ranges.push(sequence_start..idx + ch.len_utf8());
+            start = None;
+        }
+    }
+    if let Some(start) = start {
+        ranges.push(start..text.len());
+    }
+
+    ranges
+}
+
+/// Text with its ANSI escape sequences removed, which can map byte offsets in
+/// the stripped text back to the original
+///
+/// Used to search the text a reader sees when escapes render as colors.
+#[derive(Debug, Clone)]
+pub struct StrippedText {
+    /// The text without escape sequences
+    pub text: String,
+    /// Original byte offset of each byte of `text`
+    source_offsets: Vec<usize>,
+}
+
+impl StrippedText {
+    pub fn new(text: &str) -> Self {
+        let mut stripped = String::with_capacity(text.len());
+        let mut source_offsets = Vec::with_capacity(text.len());
+        let mut parser = AnsiParser::new();
+
+        for (idx, ch) in text.char_indices() {
+            if parser.parse_char(ch).is_some() {
+                stripped.push(ch);
+                source_offsets.extend(idx..idx + ch.len_utf8());
+            }
+        }
+
+        Self {
+            text: stripped,
+            source_offsets,
+        }
+    }
+
+    /// The range of the original text that `range` of the stripped text came from
+    ///
+    /// Escape sequences before the first or after the last character of
+    /// `range` are not included.
+    pub fn source_range(&self, range: Range<usize>) -> Range<usize> {
+        let start = self.source_offset(range.start);
+        if range.is_empty() {
+            return start..start;
+        }
+        start..self.source_offsets[range.end - 1] + 1
+    }
+
+    fn source_offset(&self, offset: usize) -> usize {
+        match self.source_offsets.get(offset) {
+            Some(&source) => source,
+            None => self.source_offsets.last().map_or(0, |&last| last + 1),
+        }
+    }
+}
+
 /// Count the visual width of visible characters in a string, excluding ANSI escape sequences
 /// This is useful for calculating visual width for line wrapping
 /// Returns the total display width (e.g., CJK characters count as 2, ASCII as 1)
@@ -467,6 +553,33 @@ mod tests {
         assert_eq!(segments[0].1.bg, Some(Color::Blue));
     }
 
+    #[test]
+    fn test_escape_sequence_ranges() {
+        let text = "a\x1b[31mb\x1b[0m\x1b[1mc\x1b[3";
+        assert_eq!(
+            escape_sequence_ranges(text),
+            vec![1..6, 7..11, 11..15, 16..19]
+        );
+        assert!(escape_sequence_ranges("plain").is_empty());
+    }
+
+    #[test]
+    fn test_stripped_text_maps_back_to_source() {
+        let text = "\x1b[31merror\x1b[0m: \x1b[1mfailed\x1b[0m";
+        let stripped = StrippedText::new(text);
+        assert_eq!(stripped.text, "error: failed");
+
+        let start = stripped.text.find("error: f").unwrap();
+        let range = stripped.source_range(start..start + "error: f".len());
+        assert_eq!(&text[range], "error\x1b[0m: \x1b[1mf");
+
+        let end = stripped.text.len();
+        assert_eq!(
+            stripped.source_range(end..end),
+            text.len() - 4..text.len() - 4
+        );
+    }
+
     #[test]
     fn test_mixed_content() {
         let text = "Normal \x1b[31mRed\x1b[0m Normal";
@@ -0,0 +1,214 @@
+//! Vim-style digraphs: two typed characters that stand for one character
+//!
+//! The built-in table is a subset of RFC 1345 as used by Vim (`a:` → `ä`,
+//! `->` → `→`). Users extend or override it with the `digraphs` config table.
+
+use std::collections::HashMap;
+
+/// Built-in digraphs, keyed by the two characters typed
+const DEFAULT_DIGRAPHS: &[(&str, char)] = &[
+    // Diaeresis
+    ("a:", 'ä'),
+    ("e:", 'ë'),
+    ("i:", 'ï'),
+    ("o:", 'ö'),
+    ("u:", 'ü'),
+    ("y:", 'ÿ'),
+    ("A:", 'Ä'),
+    ("E:", 'Ë'),
+    ("I:", 'Ï'),
+    ("O:", 'Ö'),
+    ("U:", 'Ü'),
+    // Acute
+    ("a'", 'á'),
+    ("e'", 'é'),
+    ("i'", 'í'),
+    ("o'", 'ó'),
+    ("u'", 'ú'),
+    ("y'", 'ý'),
+    ("A'", 'Á'),
+    ("E'", 'É'),
+    ("I'", 'Í'),
+    ("O'", 'Ó'),
+    ("U'", 'Ú'),
+    // Grave
+    ("a!", 'à'),
+    ("e!", 'è'),
+    ("i!", 'ì'),
+    ("o!", 'ò'),
+    ("u!", 'ù'),
+    ("A!", 'À'),
+    ("E!", 'È'),
+    ("I!", 'Ì'),
+    ("O!", 'Ò'),
+    ("U!", 'Ù'),
+    // Circumflex
+    ("a>", 'â'),
+    ("e>", 'ê'),
+    ("i>", 'î'),
+    ("o>", 'ô'),
+    ("u>", 'û'),
+    ("A>", 'Â'),
+    ("E>", 'Ê'),
+    ("I>", 'Î'),
+    ("O>", 'Ô'),
+    ("U>", 'Û'),
+    // Tilde, cedilla, ring, stroke
+    ("a?", 'ã'),
+    ("n?", 'ñ'),
+    ("o?", 'õ'),
+    ("A?", 'Ã'),
+    ("N?", 'Ñ'),
+    ("O?", 'Õ'),
+    ("c,", 'ç'),
+    ("C,", 'Ç'),
+    ("aa", 'å'),
+    ("AA", 'Å'),
+    ("o/", 'ø'),
+    ("O/", 'Ø'),
+    ("ae", 'æ'),
+    ("AE", 'Æ'),
+    ("oe", 'œ'),
+    ("OE", 'Œ'),
+    ("ss", 'ß'),
+    // Punctuation
+    ("<<", '«'),
+    (">>", '»'),
+    ("!I", '¡'),
+    ("?I", '¿'),
+    ("SE", '§'),
+    ("PI", '¶'),
+    ("Co", '©'),
+    ("Rg", '®'),
+    ("TM", '™'),
+    ("-N", '–'),
+    ("-M", '—'),
+    (".M", '·'),
+    ("Sb", '•'),
+    ("'6", '‘'),
+    ("'9", '’'),
+    ("\"6", '“'),
+    ("\"9", '”'),
+    ("NS", '\u{00A0}'),
+    // Currency
+    ("Eu", '€'),
+    ("Pd", '£'),
+    ("Ye", '¥'),
+    ("Ct", '¢'),
+    // Math
+    ("+-", '±'),
+    ("*X", '×'),
+    ("-:", '÷'),
+    ("!=", '≠'),
+    ("=<", '≤'),
+    (">=", '≥'),
+    ("?2", '≈'),
+    ("=3", '≡'),
+    ("00", '∞'),
+    ("RT", '√'),
+    ("DG", '°'),
+    ("12", '½'),
+    ("14", '¼'),
+    ("34", '¾'),
+    ("1S", '¹'),
+    ("2S", '²'),
+    ("3S", '³'),
+    ("FA", '∀'),
+    ("TE", '∃'),
+    ("(-", '∈'),
+    ("(U", '∩'),
+    (")U", '∪'),
+    ("AN", '∧'),
+    ("OR", '∨'),
+    ("NO", '¬'),
+    // Arrows
+    ("<-", '←'),
+    ("->", '→'),
+    ("-!", '↑'),
+    ("-v", '↓'),
+    ("<>", '↔'),
+    ("=>", '⇒'),
+    ("==", '⇔'),
+    // Greek
+    ("a*", 'α'),
+    ("b*", 'β'),
+    ("g*", 'γ'),
+    ("d*", 'δ'),
+    ("e*", 'ε'),
+    ("z*", 'ζ'),
+    ("y*", 'η'),
+    ("h*", 'θ'),
+    ("i*", 'ι'),
+    ("k*", 'κ'),
+    ("l*", 'λ'),
+    ("m*", 'μ'),
+    ("n*", 'ν'),
+    ("c*", 'ξ'),
+    ("p*", 'π'),
+    ("r*", 'ρ'),
+    ("s*", 'σ'),
+    ("t*", 'τ'),
+    ("u*", 'υ'),
+    ("f*", 'φ'),
+    ("x*", 'χ'),
+    ("q*", 'ψ'),
+    ("w*", 'ω'),
+    ("G*", 'Γ'),
+    ("D*", 'Δ'),
+    ("H*", 'Θ'),
+    ("L*", 'Λ'),
+    ("P*", 'Π'),
+    ("S*", 'Σ'),
+    ("F*", 'Φ'),
+    ("Q*", 'Ψ'),
+    ("W*", 'Ω'),
+    // Symbols
+    ("OK", '✓'),
+    ("XX", '✗'),
+];
+
+/// Resolve a two-character digraph
+///
+/// The user table wins over the built-in one; its values may be any
+/// string, so a digraph can expand to more than one character. Like Vim,
+/// the reversed pair is tried when the typed order has no entry.
+pub fn lookup(user: &HashMap<String, String>, first: char, second: char) -> Option<String> {
+    let forward: String = [first, second].iter().collect();
+    let reversed: String = [second, first].iter().collect();
+    for key in [&forward, &reversed] {
+        if let Some(value) = user.get(key.as_str()) {
+            return Some(value.clone());
+        }
+        if let Some((_, ch)) = DEFAULT_DIGRAPHS.iter().find(|(k, _)| k == key) {
+            return Some(ch.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup_builtin_and_reversed() {
+        let user = HashMap::new();
+        assert_eq!(lookup(&user, 'a', ':').as_deref(), Some("ä"));
+        assert_eq!(lookup(&user, ':', 'a').as_deref(), Some("ä"));
+        assert_eq!(lookup(&user, '-', '>').as_deref(), Some("→"));
+        assert_eq!(lookup(&user, 'E', 'u').as_deref(), Some("€"));
+        assert_eq!(lookup(&user, 'q', 'q'), None);
+    }
+
+    #[test]
+    fn test_user_table_overrides_builtin() {
+        let mut user = HashMap::new();
+        user.insert("->".to_string(), "⟶".to_string());
+        user.insert("sh".to_string(), "¯\\_(ツ)_/¯".to_string());
+        assert_eq!(lookup(&user, '-', '>').as_deref(), Some("⟶"));
+        assert_eq!(lookup(&user, 's', 'h').as_deref(), Some("¯\\_(ツ)_/¯"));
+        // The forward built-in entry beats a reversed user entry
+        user.insert(":a".to_string(), "x".to_string());
+        assert_eq!(lookup(&user, 'a', ':').as_deref(), Some("ä"));
+    }
+}
@@ -0,0 +1,433 @@
+//! Doc comment skeletons generated from function signatures
+//!
+//! The function is found with tree-sitter, and its parameters and return
+//! type decide which sections the skeleton has: `///` with `# Arguments` and
+//! `# Returns` for Rust, JSDoc with `@param`/`@returns` for JavaScript and
+//! TypeScript, and a Google-style docstring for Python. The skeleton is an LSP
+//! snippet whose tabstops are the places to fill in.
+
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{self, Node, Parser};
+
+/// A doc comment snippet ready to insert
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocCommentSnippet {
+    /// Byte offset to insert the snippet at (the start of a line)
+    pub insert_at: usize,
+    /// LSP snippet text, indented and ending with a newline
+    pub snippet: String,
+}
+
+/// Doc comment skeleton for the function whose signature contains `position`
+///
+/// Returns `None` when the language is not supported or the position is not
+/// on a function signature (inside the body does not count).
+pub fn doc_comment_snippet(
+    text: &str,
+    language: &Language,
+    position: usize,
+) -> Option<DocCommentSnippet> {
+    let ts_language: tree_sitter::Language = match language {
+        Language::Rust => fresh_languages::tree_sitter_rust::LANGUAGE.into(),
+        Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
+        Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
+        Language::TypeScript => fresh_languages::tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        _ => return None,
+    };
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    let tree = parser.parse(text, None)?;
+
+    let source = text.as_bytes();
+    let function = function_at(tree.root_node(), position, language)?;
+    let signature = Signature::of(function, source, language);
+
+    match language {
+        Language::Rust => {
+            let insert_at = line_start(text, function.start_byte());
+            let indent = indentation(text, insert_at);
+            Some(DocCommentSnippet {
+                insert_at,
+                snippet: rust_doc(&signature, indent),
+            })
+        }
+        Language::Python => {
+            // The docstring is the first statement of the body
+            let body = function.child_by_field_name("body")?;
+            if body.start_position().row == function.start_position().row {
+                return None;
+            }
+            let insert_at = line_start(text, body.start_byte());
+            let indent = indentation(text, insert_at);
+            Some(DocCommentSnippet {
+                insert_at,
+                snippet: python_docstring(&signature, indent),
+            })
+        }
+        _ => {
+            let insert_at = line_start(text, js_comment_anchor(function).start_byte());
+            let indent = indentation(text, insert_at);
+            Some(DocCommentSnippet {
+                insert_at,
+                snippet: jsdoc(&signature, indent),
+            })
+        }
+    }
+}
+
+/// What a doc comment needs to know about a function
+struct Signature {
+    parameters: Vec<String>,
+    returns_value: bool,
+}
+
+impl Signature {
+    fn of(function: Node, source: &[u8], language: &Language) -> Self {
+        let parameters = match function.child_by_field_name("parameters") {
+            Some(params) => parameter_names(params, source, language),
+            // `x => ...` has a single parameter and no parentheses
+            None => function
+                .child_by_field_name("parameter")
+                .and_then(|param| param.utf8_text(source).ok())
+                .map(|name| vec![name.to_string()])
+                .unwrap_or_default(),
+        };
+
+        let return_type = function
+            .child_by_field_name("return_type")
+            .and_then(|node| node.utf8_text(source).ok())
+            .map(|text| text.trim_start_matches(':').trim().to_string());
+        let returns_value = match return_type.as_deref() {
+            Some("()" | "void" | "None" | "Promise<void>") => false,
+            Some(_) => true,
+            // Without a declared type, look for a `return <value>` in the body
+            None => {
+                let is_constructor = function
+                    .child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(source).ok())
+                    == Some("constructor");
+                *language != Language::Rust
+                    && !is_constructor
+                    && function
+                        .child_by_field_name("body")
+                        .is_some_and(returns_value)
+            }
+        };
+
+        Self {
+            parameters,
+            returns_value,
+        }
+    }
+}
+
+/// Innermost function whose signature (not body) contains `position`
+fn function_at<'a>(root: Node<'a>, position: usize, language: &Language) -> Option<Node<'a>> {
+    let mut node = root.descendant_for_byte_range(position, position)?;
+    loop {
+        if is_function(node, language) {
+            let in_body = node
+                .child_by_field_name("body")
+                .is_some_and(|body| position >= body.start_byte());
+            return (!in_body).then_some(node);
+        }
+        // Arrow functions and function expressions are documented where
+        // they are assigned, so the declaration line counts as the signature
+        if node.kind() == "variable_declarator" {
+            if let Some(value) = node
+                .child_by_field_name("value")
+                .filter(|value| is_function(*value, language))
+            {
+                let in_body = value
+                    .child_by_field_name("body")
+                    .is_some_and(|body| position >= body.start_byte());
+                return (!in_body).then_some(value);
+            }
+        }
+        node = node.parent()?;
+    }
+}
+
+fn is_function(node: Node, language: &Language) -> bool {
+    match language {
+        Language::Rust => matches!(node.kind(), "function_item" | "function_signature_item"),
+        Language::Python => node.kind() == "function_definition",
+        _ => matches!(
+            node.kind(),
+            "function_declaration"
+                | "generator_function_declaration"
+                | "function_expression"
+                | "function"
+                | "arrow_function"
+                | "method_definition"
+                | "function_signature"
+                | "method_signature"
+                | "abstract_method_signature"
+        ),
+    }
+}
+
+/// The statement a JSDoc comment goes above: the function itself, or the
+/// `const`/`export` statement it is declared in
+fn js_comment_anchor(function: Node) -> Node {
+    let mut anchor = function;
+    while let Some(parent) = anchor.parent() {
+        match parent.kind() {
+            "variable_declarator"
+            | "lexical_declaration"
+            | "variable_declaration"
+            | "export_statement" => anchor = parent,
+            _ => break,
+        }
+    }
+    anchor
+}
+
+fn parameter_names(params: Node, source: &[u8], language: &Language) -> Vec<String> {
+    let text = |node: Node| node.utf8_text(source).unwrap_or_default().to_string();
+    let mut cursor = params.walk();
+    let mut names = Vec::new();
+    for param in params.named_children(&mut cursor) {
+        let name = match (language, param.kind()) {
+            (_, "comment") => continue,
+            (Language::Rust, "self_parameter") => continue,
+            (Language::Rust, "parameter") => param.child_by_field_name("pattern").map(text),
+            (Language::Rust, _) => None,
+            (Language::Python, "identifier") => Some(text(param)),
+            (Language::Python, "typed_parameter") => param.named_child(0).map(text),
+            (Language::Python, "default_parameter" | "typed_default_parameter") => {
+                param.child_by_field_name("name").map(text)
+            }
+            (Language::Python, "list_splat_pattern" | "dictionary_splat_pattern") => {
+                Some(text(param))
+            }
+            (Language::Python, _) => None,
+            (_, "required_parameter" | "optional_parameter") => {
+                param.child_by_field_name("pattern").map(text)
+            }
+            (_, "assignment_pattern") => param.child_by_field_name("left").map(text),
+            (_, "rest_pattern") => param.named_child(0).map(text),
+            _ => Some(text(param)),
+        };
+        // TypeScript's `this` parameter only declares the type of `this`
+        if let Some(name) = name.filter(|name| name != "this") {
+            names.push(name);
+        }
+    }
+    // Methods take the instance (or class) implicitly
+    if *language == Language::Python
+        && names
+            .first()
+            .is_some_and(|first| first == "self" || first == "cls")
+    {
+        names.remove(0);
+    }
+    names
+}
+
+/// Whether a function body returns a value (a `return` in a nested
+/// function doesn't count)
+fn returns_value(body: Node) -> bool {
+    // An arrow function with an expression body returns it
+    if body.kind() != "statement_block"
+        && body
+            .parent()
+            .is_some_and(|parent| parent.kind() == "arrow_function")
+    {
+        return true;
+    }
+    contains_value_return(body)
+}
+
+fn contains_value_return(node: Node) -> bool {
+    if node.kind() == "return_statement" {
+        return node.named_child_count() > 0;
+    }
+    let mut cursor = node.walk();
+    let found = node.named_children(&mut cursor).any(|child| {
+        !matches!(
+            child.kind(),
+            "function_declaration"
+                | "function_expression"
+                | "arrow_function"
+                | "function_definition"
+                | "lambda"
+                | "class_declaration"
+                | "class_definition"
+        ) && contains_value_return(child)
+    });
+    found
+}
+
+fn rust_doc(signature: &Signature, indent: &str) -> String {
+    let mut lines = vec!["$1".to_string()];
+    let mut tabstop = 2;
+    if !signature.parameters.is_empty() {
+        lines.extend([String::new(), "# Arguments".to_string(), String::new()]);
+        for name in &signature.parameters {
+            lines.push(format!("* `{}` - ${}", escape(name), tabstop));
+            tabstop += 1;
+        }
+    }
+    if signature.returns_value {
+        lines.extend([String::new(), "# Returns".to_string(), String::new()]);
+        lines.push(format!("${}", tabstop));
+    }
+    lines
+        .iter()
+        .map(|line| {
+            if line.is_empty() {
+                format!("{indent}///\n")
+            } else {
+                format!("{indent}/// {line}\n")
+            }
+        })
+        .collect()
+}
+
+fn jsdoc(signature: &Signature, indent: &str) -> String {
+    let mut snippet = format!("{indent}/**\n{indent} * $1\n");
+    let mut tabstop = 2;
+    for name in &signature.parameters {
+        snippet.push_str(&format!(
+            "{indent} * @param {} ${}\n",
+            escape(name),
+            tabstop
+        ));
+        tabstop += 1;
+    }
+    if signature.returns_value {
+        snippet.push_str(&format!("{indent} * @returns ${}\n", tabstop));
+    }
+    snippet.push_str(&format!("{indent} */\n"));
+    snippet
+}
+
+fn python_docstring(signature: &Signature, indent: &str) -> String {
+    let mut snippet = format!("{indent}\"\"\"$1\n");
+    let mut tabstop = 2;
+    if !signature.parameters.is_empty() {
+        snippet.push_str(&format!("\n{indent}Args:\n"));
+        for name in &signature.parameters {
+            snippet.push_str(&format!("{indent}    {}: ${}\n", escape(name), tabstop));
+            tabstop += 1;
+        }
+    }
+    if signature.returns_value {
+        snippet.push_str(&format!("\n{indent}Returns:\n{indent}    ${}\n", tabstop));
+    }
+    snippet.push_str(&format!("{indent}\"\"\"\n"));
+    snippet
+}
+
+/// Escape text taken from the source so it is literal in a snippet
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('$', "\\$")
+        .replace('}', "\\}")
+}
+
+fn line_start(text: &str, position: usize) -> usize {
+    text[..position]
+        .rfind('\n')
+        .map_or(0, |newline| newline + 1)
+}
+
+/// Leading whitespace of the line starting at `line_start`
+fn indentation(text: &str, line_start: usize) -> &str {
+    let line = &text[line_start..];
+    let width = line.len() - line.trim_start_matches([' ', '\t']).len();
+    &line[..width]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitives::snippet::expand_snippet;
+
+    /// Insert the expanded snippet for the cursor at `|`
+    fn generate(text: &str, language: Language) -> Option<String> {
+        let position = text.find('|').unwrap();
+        let text = text.replacen('|', "", 1);
+        let doc = doc_comment_snippet(&text, &language, position)?;
+        let mut result = text.clone();
+        result.insert_str(doc.insert_at, &expand_snippet(&doc.snippet).text);
+        Some(result)
+    }
+
+    #[test]
+    fn test_rust_function() {
+        let text = "impl Foo {\n    pub fn add(&self, a: i32, b: i32) -> i32 {\n        a +| b\n    }\n}\n";
+        // Inside the body is not the signature
+        assert_eq!(generate(text, Language::Rust), None);
+
+        let text = "impl Foo {\n    pub fn a|dd(&self, a: i32, b: i32) -> i32 {\n        a + b\n    }\n}\n";
+        assert_eq!(
+            generate(text, Language::Rust).unwrap(),
+            "impl Foo {\n    /// \n    ///\n    /// # Arguments\n    ///\n    /// * `a` - \n    /// * `b` - \n    ///\n    /// # Returns\n    ///\n    /// \n    pub fn add(&self, a: i32, b: i32) -> i32 {\n        a + b\n    }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_rust_tabstops() {
+        let doc = doc_comment_snippet("fn f(x: u8) {}\n", &Language::Rust, 3).unwrap();
+        assert_eq!(doc.insert_at, 0);
+        let expanded = expand_snippet(&doc.snippet);
+        assert_eq!(
+            expanded.text,
+            "/// \n///\n/// # Arguments\n///\n/// * `x` - \n"
+        );
+        assert_eq!(expanded.tabstops, vec![4..4, 41..41]);
+    }
+
+    #[test]
+    fn test_typescript_jsdoc() {
+        let text = "export function greet|(name: string, times = 1): string {\n  return name;\n}\n";
+        assert_eq!(
+            generate(text, Language::TypeScript).unwrap(),
+            "/**\n * \n * @param name \n * @param times \n * @returns \n */\nexport function greet(name: string, times = 1): string {\n  return name;\n}\n"
+        );
+
+        let text = "class A {\n  log|(msg: string): void {}\n}\n";
+        assert_eq!(
+            generate(text, Language::TypeScript).unwrap(),
+            "class A {\n  /**\n   * \n   * @param msg \n   */\n  log(msg: string): void {}\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_javascript_arrow_function() {
+        let text = "const double| = (x) => x * 2;\n";
+        assert_eq!(
+            generate(text, Language::JavaScript).unwrap(),
+            "/**\n * \n * @param x \n * @returns \n */\nconst double = (x) => x * 2;\n"
+        );
+
+        // A return inside a nested function doesn't make the outer one return
+        let text = "function run|(cb) {\n  setTimeout(() => { return cb(); });\n}\n";
+        assert_eq!(
+            generate(text, Language::JavaScript).unwrap(),
+            "/**\n * \n * @param cb \n */\nfunction run(cb) {\n  setTimeout(() => { return cb(); });\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_python_docstring() {
+        let text = "class A:\n    def sc|ale(self, factor: float, *args) -> float:\n        return factor\n";
+        assert_eq!(
+            generate(text, Language::Python).unwrap(),
+            "class A:\n    def scale(self, factor: float, *args) -> float:\n        \"\"\"\n\n        Args:\n            factor: \n            *args: \n\n        Returns:\n            \n        \"\"\"\n        return factor\n"
+        );
+
+        let text = "def ma|in():\n    print('hi')\n";
+        assert_eq!(
+            generate(text, Language::Python).unwrap(),
+            "def main():\n    \"\"\"\n    \"\"\"\n    print('hi')\n"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_language() {
+        assert_eq!(doc_comment_snippet("int f() {}", &Language::C, 4), None);
+    }
+}
@@ -0,0 +1,674 @@
+//! Calculator-style evaluation of selected text
+//!
+//! A small expression evaluator for Evaluate Selection. It understands
+//! arithmetic (`+ - * / % ^`, parentheses, `sqrt`, `abs`, `round`, `floor`,
+//! `ceil`, `min`, `max`, `pi`, `e`) and date arithmetic on ISO dates:
+//! `2024-03-01 + 2 weeks`, `today - 2024-01-15` or `3 months + 10 days`.
+//! Durations are counted in `days`, `weeks`, `months` and `years`; adding
+//! months to a date keeps the day of the month, clamped to the month's end.
+//!
+//! Nothing here reaches outside the expression: there are no variables and no
+//! way to call into the shell, so evaluating untrusted text is safe.
+
+use std::fmt;
+
+/// Decimal places a number is rounded to for display, which hides the
+/// binary noise of sums like `0.1 + 0.2`
+const DISPLAY_DECIMALS: usize = 10;
+
+/// Nesting depth at which an expression is rejected instead of recursing further
+const MAX_DEPTH: usize = 64;
+
+/// Days either side of 1970-01-01 a date may lie, which keeps the calendar
+/// arithmetic far from `i64` overflow
+const MAX_DAYS: i64 = 1_000_000_000;
+
+/// A calendar date, stored as days since 1970-01-01
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date(i64);
+
+impl Date {
+    /// The date for a year, month (1-12) and day of the month (1-31), or
+    /// `None` if there is no such day
+    pub fn from_ymd(year: i32, month: u32, day: u32) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year as i64, month) {
+            return None;
+        }
+        Some(Self(days_from_civil(year as i64, month, day)))
+    }
+
+    fn ymd(self) -> (i64, u32, u32) {
+        civil_from_days(self.0)
+    }
+
+    /// The date `days` after 1970-01-01, or an error outside the supported range
+    fn checked(days: i64) -> Result<Self, String> {
+        if days.abs() > MAX_DAYS {
+            return Err("Date is out of range".to_string());
+        }
+        Ok(Self(days))
+    }
+
+    /// The date `months` calendar months later, clamped to the month's last day
+    fn add_months(self, months: i64) -> Result<Self, String> {
+        let (year, month, day) = self.ymd();
+        let index = (year * 12 + (month as i64 - 1))
+            .checked_add(months)
+            .ok_or_else(overflow)?;
+        let (year, month) = (index.div_euclid(12), index.rem_euclid(12) as u32 + 1);
+        if year.abs() > MAX_DAYS / 365 {
+            return Err("Date is out of range".to_string());
+        }
+        let day = day.min(days_in_month(year, month));
+        Self::checked(days_from_civil(year, month, day))
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+/// A span of calendar time; months are kept apart from days since they
+/// vary in length
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Duration {
+    pub months: i64,
+    pub days: i64,
+}
+
+impl Duration {
+    fn scale(self, factor: i64) -> Result<Self, String> {
+        Ok(Self {
+            months: self.months.checked_mul(factor).ok_or_else(overflow)?,
+            days: self.days.checked_mul(factor).ok_or_else(overflow)?,
+        })
+    }
+
+    fn negate(self) -> Result<Self, String> {
+        self.scale(-1)
+    }
+
+    fn checked_add(self, other: Self) -> Result<Self, String> {
+        Ok(Self {
+            months: self.months.checked_add(other.months).ok_or_else(overflow)?,
+            days: self.days.checked_add(other.days).ok_or_else(overflow)?,
+        })
+    }
+}
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plural = |count: i64, unit: &str| {
+            let s = if count.unsigned_abs() == 1 { "" } else { "s" };
+            format!("{} {}{}", count, unit, s)
+        };
+        let mut parts = Vec::new();
+        let (years, months) = (self.months / 12, self.months % 12);
+        if years != 0 {
+            parts.push(plural(years, "year"));
+        }
+        if months != 0 {
+            parts.push(plural(months, "month"));
+        }
+        if self.days != 0 || parts.is_empty() {
+            parts.push(plural(self.days, "day"));
+        }
+        write!(f, "{}", parts.join(" "))
+    }
+}
+
+/// The result of evaluating an expression
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Date(Date),
+    Duration(Duration),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{}", format_number(*n)),
+            Self::Date(date) => write!(f, "{}", date),
+            Self::Duration(duration) => write!(f, "{}", duration),
+        }
+    }
+}
+
+impl Value {
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Number(_) => "a number",
+            Self::Date(_) => "a date",
+            Self::Duration(_) => "a duration",
+        }
+    }
+}
+
+/// Evaluate `expression`, with `today` standing for the given date.
+/// A trailing `=` is ignored, so `2 * 21 =` works like `2 * 21`.
+pub fn evaluate(expression: &str, today: Date) -> Result<Value, String> {
+    let expression = expression.trim();
+    let expression = expression.strip_suffix('=').unwrap_or(expression);
+    let tokens = tokenize(expression)?;
+    if tokens.is_empty() {
+        return Err("Nothing to evaluate".to_string());
+    }
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        depth: 0,
+        today,
+    };
+    let value = parser.expression()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(token) => Err(format!("Unexpected {}", token)),
+    }
+}
+
+/// Round to [`DISPLAY_DECIMALS`] places and drop trailing zeros
+fn format_number(n: f64) -> String {
+    let text = format!("{:.*}", DISPLAY_DECIMALS, n);
+    let text = text.trim_end_matches('0').trim_end_matches('.');
+    match text {
+        "-0" => "0".to_string(),
+        text => text.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(f64),
+    Date(Date),
+    Ident(String),
+    Op(char),
+    LParen,
+    RParen,
+    Comma,
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "'{}'", format_number(*n)),
+            Self::Date(date) => write!(f, "'{}'", date),
+            Self::Ident(name) => write!(f, "'{}'", name),
+            Self::Op(op) => write!(f, "'{}'", op),
+            Self::LParen => write!(f, "'('"),
+            Self::RParen => write!(f, "')'"),
+            Self::Comma => write!(f, "','"),
+        }
+    }
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if let Some((date, len)) = date_at(&chars[i..])? {
+            tokens.push(Token::Date(date));
+            i += len;
+        } else if c.is_ascii_digit() || c == '.' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let literal: String = chars[start..i].iter().collect();
+            let number = literal
+                .parse()
+                .map_err(|_| format!("Invalid number '{}'", literal))?;
+            tokens.push(Token::Number(number));
+        } else if c.is_alphabetic() {
+            let start = i;
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            let name: String = chars[start..i].iter().collect();
+            tokens.push(Token::Ident(name.to_lowercase()));
+        } else {
+            tokens.push(match c {
+                '+' | '-' | '*' | '/' | '%' | '^' => Token::Op(c),
+                '×' => Token::Op('*'),
+                '÷' => Token::Op('/'),
+                '(' => Token::LParen,
+                ')' => Token::RParen,
+                ',' => Token::Comma,
+                _ => return Err(format!("Unexpected '{}'", c)),
+            });
+            i += 1;
+        }
+    }
+    Ok(tokens)
+}
+
+/// An ISO date (`YYYY-MM-DD`) at the start of `chars`, with its length
+fn date_at(chars: &[char]) -> Result<Option<(Date, usize)>, String> {
+    const LEN: usize = 10;
+    let Some(candidate) = chars.get(..LEN) else {
+        return Ok(None);
+    };
+    let shape_matches = candidate.iter().enumerate().all(|(i, c)| match i {
+        4 | 7 => *c == '-',
+        _ => c.is_ascii_digit(),
+    });
+    // `2024-01-150` is a subtraction, not a date
+    if !shape_matches || chars.get(LEN).is_some_and(|c| c.is_alphanumeric()) {
+        return Ok(None);
+    }
+    let number = |range: std::ops::Range<usize>| {
+        candidate[range]
+            .iter()
+            .fold(0u32, |n, c| n * 10 + c.to_digit(10).unwrap_or(0))
+    };
+    match Date::from_ymd(number(0..4) as i32, number(5..7), number(8..10)) {
+        Some(date) => Ok(Some((date, LEN))),
+        None => Err(format!(
+            "Invalid date '{}'",
+            candidate.iter().collect::<String>()
+        )),
+    }
+}
+
+/// Recursive descent parser that evaluates as it goes
+///
+/// ```text
+/// expression := term (('+' | '-') term)*
+/// term       := unary (('*' | '/' | '%') unary)*
+/// unary      := ('-' | '+') unary | power
+/// power      := atom ('^' unary)?
+/// atom       := number unit? | date | name | name '(' args ')' | '(' expression ')'
+/// ```
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+    depth: usize,
+    today: Date,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.position += 1;
+            return true;
+        }
+        false
+    }
+
+    fn expect(&mut self, token: &Token) -> Result<(), String> {
+        if self.eat(token) {
+            return Ok(());
+        }
+        match self.peek() {
+            Some(found) => Err(format!("Expected {}, found {}", token, found)),
+            None => Err(format!("Expected {}", token)),
+        }
+    }
+
+    fn expression(&mut self) -> Result<Value, String> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat(&Token::Op('+')) {
+                value = add(value, self.term()?)?;
+            } else if self.eat(&Token::Op('-')) {
+                value = subtract(value, self.term()?)?;
+            } else {
+                break;
+            }
+        }
+        Ok(value)
+    }
+
+    fn term(&mut self) -> Result<Value, String> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat(&Token::Op('*')) {
+                value = multiply(value, self.unary()?)?;
+            } else if self.eat(&Token::Op('/')) {
+                value = divide(value, self.unary()?)?;
+            } else if self.eat(&Token::Op('%')) {
+                let (a, b) = (number(value)?, number(self.unary()?)?);
+                if b == 0.0 {
+                    return Err("Division by zero".to_string());
+                }
+                value = Value::Number(a % b);
+            } else {
+                break;
+            }
+        }
+        Ok(value)
+    }
+
+    /// Every level of nesting passes through here, so this is where deeply
+    /// nested input is cut off
+    fn unary(&mut self) -> Result<Value, String> {
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            return Err("Expression is nested too deeply".to_string());
+        }
+        let value = if self.eat(&Token::Op('-')) {
+            match self.unary()? {
+                Value::Number(n) => Value::Number(-n),
+                Value::Duration(d) => Value::Duration(d.negate()?),
+                Value::Date(_) => return Err("Cannot negate a date".to_string()),
+            }
+        } else if self.eat(&Token::Op('+')) {
+            self.unary()?
+        } else {
+            self.power()?
+        };
+        self.depth -= 1;
+        Ok(value)
+    }
+
+    fn power(&mut self) -> Result<Value, String> {
+        let base = self.atom()?;
+        if !self.eat(&Token::Op('^')) {
+            return Ok(base);
+        }
+        let exponent = number(self.unary()?)?;
+        finite(number(base)?.powf(exponent))
+    }
+
+    fn atom(&mut self) -> Result<Value, String> {
+        match self.next() {
+            Some(Token::Number(n)) => match self.peek() {
+                Some(Token::Ident(name)) => match unit(name) {
+                    Some(unit) => {
+                        self.position += 1;
+                        Ok(Value::Duration(unit.scale(whole(n)?)?))
+                    }
+                    None => Err(format!("Unknown unit '{}'", name)),
+                },
+                _ => Ok(Value::Number(n)),
+            },
+            Some(Token::Date(date)) => Ok(Value::Date(date)),
+            Some(Token::LParen) => {
+                let value = self.expression()?;
+                self.expect(&Token::RParen)?;
+                Ok(value)
+            }
+            Some(Token::Ident(name)) if self.peek() == Some(&Token::LParen) => {
+                self.position += 1;
+                let mut args = vec![number(self.expression()?)?];
+                while self.eat(&Token::Comma) {
+                    args.push(number(self.expression()?)?);
+                }
+                self.expect(&Token::RParen)?;
+                call(&name, &args)
+            }
+            Some(Token::Ident(name)) => match name.as_str() {
+                "pi" => Ok(Value::Number(std::f64::consts::PI)),
+                "e" => Ok(Value::Number(std::f64::consts::E)),
+                "today" => Ok(Value::Date(self.today)),
+                _ => Err(format!("Unknown name '{}'", name)),
+            },
+            Some(token) => Err(format!("Unexpected {}", token)),
+            None => Err("Unexpected end of expression".to_string()),
+        }
+    }
+}
+
+/// The duration one of `name` stands for
+fn unit(name: &str) -> Option<Duration> {
+    let (months, days) = match name {
+        "d" | "day" | "days" => (0, 1),
+        "w" | "week" | "weeks" => (0, 7),
+        "month" | "months" => (1, 0),
+        "y" | "year" | "years" => (12, 0),
+        _ => return None,
+    };
+    Some(Duration { months, days })
+}
+
+fn call(name: &str, args: &[f64]) -> Result<Value, String> {
+    let single = |f: fn(f64) -> f64| match args {
+        [x] => finite(f(*x)),
+        _ => Err(format!("{}() takes one argument", name)),
+    };
+    match name {
+        "sqrt" if args.first().is_some_and(|x| *x < 0.0) => {
+            Err("Square root of a negative number".to_string())
+        }
+        "sqrt" => single(f64::sqrt),
+        "abs" => single(f64::abs),
+        "round" => single(f64::round),
+        "floor" => single(f64::floor),
+        "ceil" => single(f64::ceil),
+        "min" => Ok(Value::Number(
+            args.iter().copied().fold(f64::INFINITY, f64::min),
+        )),
+        "max" => Ok(Value::Number(
+            args.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        )),
+        _ => Err(format!("Unknown function '{}'", name)),
+    }
+}
+
+fn number(value: Value) -> Result<f64, String> {
+    match value {
+        Value::Number(n) => Ok(n),
+        other => Err(format!("Expected a number, found {}", other.kind())),
+    }
+}
+
+/// `n` as a whole number, for counting durations
+fn whole(n: f64) -> Result<i64, String> {
+    if n.fract() != 0.0 || n.abs() > 1e9 {
+        return Err(format!(
+            "Expected a whole number, found {}",
+            format_number(n)
+        ));
+    }
+    Ok(n as i64)
+}
+
+fn finite(n: f64) -> Result<Value, String> {
+    if n.is_finite() {
+        Ok(Value::Number(n))
+    } else {
+        Err("Result is not a finite number".to_string())
+    }
+}
+
+fn add(a: Value, b: Value) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => finite(a + b),
+        (Value::Date(date), Value::Duration(d)) | (Value::Duration(d), Value::Date(date)) => {
+            Ok(Value::Date(plus(date, d)?))
+        }
+        (Value::Duration(a), Value::Duration(b)) => Ok(Value::Duration(a.checked_add(b)?)),
+        (a, b) => Err(format!("Cannot add {} to {}", b.kind(), a.kind())),
+    }
+}
+
+fn subtract(a: Value, b: Value) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => finite(a - b),
+        (Value::Date(a), Value::Date(b)) => Ok(Value::Duration(Duration {
+            months: 0,
+            days: a.0 - b.0,
+        })),
+        (Value::Date(date), Value::Duration(d)) => Ok(Value::Date(plus(date, d.negate()?)?)),
+        (Value::Duration(a), Value::Duration(b)) => {
+            Ok(Value::Duration(a.checked_add(b.negate()?)?))
+        }
+        (a, b) => Err(format!("Cannot subtract {} from {}", b.kind(), a.kind())),
+    }
+}
+
+fn multiply(a: Value, b: Value) -> Result<Value, String> {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => finite(a * b),
+        (Value::Number(n), Value::Duration(d)) | (Value::Duration(d), Value::Number(n)) => {
+            Ok(Value::Duration(d.scale(whole(n)?)?))
+        }
+        (a, b) => Err(format!("Cannot multiply {} by {}", a.kind(), b.kind())),
+    }
+}
+
+fn divide(a: Value, b: Value) -> Result<Value, String> {
+    match (a, b) {
+        (_, Value::Number(0.0)) => Err("Division by zero".to_string()),
+        (Value::Number(a), Value::Number(b)) => finite(a / b),
+        (Value::Duration(d), Value::Number(n)) => {
+            let n = whole(n)?;
+            if d.months.checked_rem(n) != Some(0) || d.days.checked_rem(n) != Some(0) {
+                return Err(format!("{} does not divide evenly by {}", d, n));
+            }
+            Ok(Value::Duration(Duration {
+                months: d.months.checked_div(n).ok_or_else(overflow)?,
+                days: d.days.checked_div(n).ok_or_else(overflow)?,
+            }))
+        }
+        (a, b) => Err(format!("Cannot divide {} by {}", a.kind(), b.kind())),
+    }
+}
+
+/// `date` moved by `duration`, months first
+fn plus(date: Date, duration: Duration) -> Result<Date, String> {
+    let date = date.add_months(duration.months)?;
+    Date::checked(date.0.checked_add(duration.days).ok_or_else(overflow)?)
+}
+
+fn overflow() -> String {
+    "Duration is too large".to_string()
+}
+
+fn is_leap_year(year: i64) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i64, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since 1970-01-01 of a proleptic Gregorian date
+/// (Howard Hinnant's `days_from_civil`)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The date `days` after 1970-01-01 (Howard Hinnant's `civil_from_days`)
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days - era * 146097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expression: &str) -> Result<String, String> {
+        let today = Date::from_ymd(2024, 2, 10).unwrap();
+        evaluate(expression, today).map(|value| value.to_string())
+    }
+
+    #[test]
+    fn test_arithmetic_follows_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), Ok("7".to_string()));
+        assert_eq!(eval("(1 + 2) * 3 ="), Ok("9".to_string()));
+        assert_eq!(eval("-2^2 + 2^3^2"), Ok("508".to_string()));
+        assert_eq!(eval("10 / 4"), Ok("2.5".to_string()));
+        assert_eq!(eval("0.1 + 0.2"), Ok("0.3".to_string()));
+        assert_eq!(
+            eval("17 % 5 + max(1, 4, 2) - sqrt(16)"),
+            Ok("2".to_string())
+        );
+        assert_eq!(eval("round(pi * 100) / 100"), Ok("3.14".to_string()));
+        assert_eq!(eval("3 × 4 ÷ 2"), Ok("6".to_string()));
+    }
+
+    #[test]
+    fn test_date_arithmetic() {
+        assert_eq!(eval("2024-01-15 + 30 days"), Ok("2024-02-14".to_string()));
+        assert_eq!(eval("2024-03-01 - 2024-01-15"), Ok("46 days".to_string()));
+        assert_eq!(eval("today + 2 weeks"), Ok("2024-02-24".to_string()));
+        // Month ends clamp, and leap years count
+        assert_eq!(eval("2024-01-31 + 1 month"), Ok("2024-02-29".to_string()));
+        assert_eq!(eval("2023-01-31 + 1 month"), Ok("2023-02-28".to_string()));
+        assert_eq!(eval("1999-12-31 + 1 day"), Ok("2000-01-01".to_string()));
+        assert_eq!(
+            eval("2 * (1 year + 3 months) + 1 day"),
+            Ok("2 years 6 months 1 day".to_string())
+        );
+        assert_eq!(eval("today - 1 d"), Ok("2024-02-09".to_string()));
+        // Without the full date shape it is plain subtraction
+        assert_eq!(eval("2024-01"), Ok("2023".to_string()));
+    }
+
+    #[test]
+    fn test_errors_are_reported() {
+        assert_eq!(eval("1 / 0"), Err("Division by zero".to_string()));
+        assert_eq!(eval("2 +"), Err("Unexpected end of expression".to_string()));
+        assert_eq!(eval("(1 + 2"), Err("Expected ')'".to_string()));
+        assert_eq!(eval("1 2"), Err("Unexpected '2'".to_string()));
+        assert_eq!(eval("rm -rf"), Err("Unknown name 'rm'".to_string()));
+        assert_eq!(
+            eval("2024-01-01 + 1"),
+            Err("Cannot add a number to a date".to_string())
+        );
+        assert_eq!(
+            eval("1.5 days"),
+            Err("Expected a whole number, found 1.5".to_string())
+        );
+        assert_eq!(
+            eval("2024-02-30"),
+            Err("Invalid date '2024-02-30'".to_string())
+        );
+        assert!(eval(&"(".repeat(1000)).is_err());
+        assert!(eval(&"-".repeat(1000)).is_err());
+        assert!(eval(&"2^".repeat(1000)).is_err());
+        // Large durations are rejected rather than wrapping or panicking
+        assert_eq!(
+            eval("1000000000 years * 1000000000 * 1000000000"),
+            Err("Duration is too large".to_string())
+        );
+        assert_eq!(
+            eval("2024-01-01 + 1000000000 years"),
+            Err("Date is out of range".to_string())
+        );
+        assert_eq!(
+            eval("2024-01-01 - 1000000000 days * 1000000000"),
+            Err("Date is out of range".to_string())
+        );
+    }
+}
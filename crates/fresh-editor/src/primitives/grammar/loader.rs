@@ -40,6 +40,7 @@ pub trait GrammarLoader: Send + Sync {
 
 /// Default implementation using local filesystem.
 pub struct LocalGrammarLoader {
+    /// Fresh's config directory (holding `grammars/`)
     config_dir: Option<PathBuf>,
 }
 
@@ -47,11 +48,13 @@ impl LocalGrammarLoader {
     /// Create a new LocalGrammarLoader with default config directory.
     pub fn new() -> Self {
         Self {
-            config_dir: dirs::config_dir(),
+            config_dir: crate::config_io::DirectoryContext::from_system()
+                .ok()
+                .map(|dirs| dirs.config_dir),
         }
     }
 
-    /// Create a LocalGrammarLoader with a custom config directory.
+    /// Create a LocalGrammarLoader with a custom Fresh config directory.
     pub fn with_config_dir(config_dir: Option<PathBuf>) -> Self {
         Self { config_dir }
     }
@@ -65,13 +68,13 @@ impl Default for LocalGrammarLoader {
 
 impl GrammarLoader for LocalGrammarLoader {
     fn grammars_dir(&self) -> Option<PathBuf> {
-        self.config_dir.as_ref().map(|p| p.join("fresh/grammars"))
+        self.config_dir.as_ref().map(|p| p.join("grammars"))
     }
 
     fn languages_packages_dir(&self) -> Option<PathBuf> {
         self.config_dir
             .as_ref()
-            .map(|p| p.join("fresh/languages/packages"))
+            .map(|p| p.join("languages/packages"))
     }
 
     fn read_file(&self, path: &Path) -> io::Result<String> {
@@ -531,6 +534,7 @@ mod tests {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
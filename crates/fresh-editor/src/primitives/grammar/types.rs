@@ -129,6 +129,23 @@ impl GrammarRegistry {
         map
     }
 
+    /// Builder seeded with syntect's bundled syntaxes.
+    ///
+    /// The core WASM build leaves the bundled syntax dump out and fetches
+    /// grammars per language instead, so there it only has plain text.
+    pub fn bundled_syntaxes_builder() -> SyntaxSetBuilder {
+        #[cfg(any(feature = "runtime", feature = "wasm-bundled-syntaxes"))]
+        {
+            SyntaxSet::load_defaults_newlines().into_builder()
+        }
+        #[cfg(not(any(feature = "runtime", feature = "wasm-bundled-syntaxes")))]
+        {
+            let mut builder = SyntaxSetBuilder::new();
+            builder.add_plain_text_syntax();
+            builder
+        }
+    }
+
     /// Add embedded grammars (TOML, Odin, etc.) to a syntax set builder.
     pub fn add_embedded_grammars(builder: &mut SyntaxSetBuilder) {
         // TOML grammar
@@ -488,8 +505,7 @@ impl GrammarRegistry {
         );
 
         // Start with defaults and embedded grammars (same as Default impl)
-        let defaults = SyntaxSet::load_defaults_newlines();
-        let mut builder = defaults.into_builder();
+        let mut builder = Self::bundled_syntaxes_builder();
         Self::add_embedded_grammars(&mut builder);
 
         // Start fresh with user extensions - we'll rebuild from loaded grammars
@@ -603,8 +619,7 @@ impl GrammarRegistry {
 impl Default for GrammarRegistry {
     fn default() -> Self {
         // Create with defaults and embedded grammars only (no user grammars)
-        let defaults = SyntaxSet::load_defaults_newlines();
-        let mut builder = defaults.into_builder();
+        let mut builder = Self::bundled_syntaxes_builder();
         Self::add_embedded_grammars(&mut builder);
         let syntax_set = builder.build();
         let filename_scopes = Self::build_filename_scopes();
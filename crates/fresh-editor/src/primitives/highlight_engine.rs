@@ -479,6 +479,13 @@ impl TextMateEngine {
         self.cache = None;
     }
 
+    /// Approximate memory held by the span cache, in bytes
+    pub fn cache_bytes(&self) -> usize {
+        self.cache.as_ref().map_or(0, |c| {
+            c.spans.capacity() * std::mem::size_of::<CachedSpan>()
+        })
+    }
+
     /// Get syntax name
     pub fn syntax_name(&self) -> &str {
         &self.syntax_set.syntaxes()[self.syntax_index].name
@@ -726,6 +733,15 @@ impl HighlightEngine {
         }
     }
 
+    /// Approximate memory held by the highlight cache, in bytes
+    pub fn cache_bytes(&self) -> usize {
+        match self {
+            Self::TreeSitter(h) => h.cache_bytes(),
+            Self::TextMate(h) => h.cache_bytes(),
+            Self::None => 0,
+        }
+    }
+
     /// Check if this engine has highlighting available
     pub fn has_highlighting(&self) -> bool {
         !matches!(self, Self::None)
@@ -230,6 +230,13 @@ impl Highlighter {
         self.cache = None;
     }
 
+    /// Approximate memory held by the span cache, in bytes
+    pub fn cache_bytes(&self) -> usize {
+        self.cache.as_ref().map_or(0, |c| {
+            c.spans.capacity() * std::mem::size_of::<CachedSpan>()
+        })
+    }
+
     /// Get the current language
     pub fn language(&self) -> &Language {
         &self.language
@@ -0,0 +1,209 @@
+//! Indentation blocks
+//!
+//! An indentation block is the run of contiguous lines indented at least as
+//! far as the cursor line. It stands in for bracket matching in languages
+//! where blocks are delimited by indentation (Python, YAML, ...).
+//!
+//! Blank lines never end a block, but they are only included when more of
+//! the block follows them. On a blank line, the indentation of the next
+//! non-blank line is used (or of the previous one at the end of the buffer).
+
+use crate::model::buffer::Buffer;
+
+/// Extent of an indentation block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndentBlock {
+    /// Byte offset of the start of the first line
+    pub start: usize,
+    /// Byte offset just past the last line, including its line ending
+    pub end: usize,
+    /// Byte offset of the first non-whitespace character on the first line
+    pub first_text: usize,
+    /// Byte offset of the first non-whitespace character on the last line
+    pub last_text: usize,
+    /// Indentation width (in columns) of the block
+    pub indent: usize,
+}
+
+/// Indentation width of a line in columns, or `None` for blank lines.
+pub fn indent_width(line: &str, tab_size: usize) -> Option<usize> {
+    let content = line.trim_end_matches(['\n', '\r']);
+    if content.trim().is_empty() {
+        return None;
+    }
+    let tab_size = tab_size.max(1);
+    let mut width = 0;
+    for ch in content.chars() {
+        match ch {
+            ' ' => width += 1,
+            '\t' => width += tab_size - width % tab_size,
+            _ => break,
+        }
+    }
+    Some(width)
+}
+
+/// Length in bytes of a line's leading whitespace
+fn leading_whitespace_len(line: &str) -> usize {
+    line.len() - line.trim_start_matches([' ', '\t']).len()
+}
+
+/// Find the indentation block around `position`.
+///
+/// At most `max_lines` lines are scanned in each direction; a block larger
+/// than that is cut off at the limit. Returns `None` if the buffer only
+/// contains blank lines.
+pub fn find_indent_block(
+    buffer: &mut Buffer,
+    position: usize,
+    tab_size: usize,
+    estimated_line_length: usize,
+    max_lines: usize,
+) -> Option<IndentBlock> {
+    let mut forward = buffer.line_iterator(position, estimated_line_length);
+    let (cursor_line_start, cursor_line) = forward.next_line()?;
+
+    // (start of line, start of text, end of line) for the first and last
+    // non-blank lines of the block
+    let mut base = indent_width(&cursor_line, tab_size);
+    let cursor_bounds = (
+        cursor_line_start,
+        cursor_line_start + leading_whitespace_len(&cursor_line),
+        cursor_line_start + cursor_line.len(),
+    );
+    let mut first = base.map(|_| cursor_bounds);
+    let mut last = first;
+
+    let mut scanned = 0;
+    while scanned < max_lines {
+        let Some((line_start, line)) = forward.next_line() else {
+            break;
+        };
+        scanned += 1;
+        let Some(width) = indent_width(&line, tab_size) else {
+            continue;
+        };
+        let bounds = (
+            line_start,
+            line_start + leading_whitespace_len(&line),
+            line_start + line.len(),
+        );
+        match base {
+            None => {
+                base = Some(width);
+                first = Some(bounds);
+                last = Some(bounds);
+            }
+            Some(b) if width >= b => last = Some(bounds),
+            Some(_) => break,
+        }
+    }
+
+    let mut backward = buffer.line_iterator(cursor_line_start, estimated_line_length);
+    scanned = 0;
+    while scanned < max_lines {
+        let Some((line_start, line)) = backward.prev() else {
+            break;
+        };
+        scanned += 1;
+        let Some(width) = indent_width(&line, tab_size) else {
+            continue;
+        };
+        let bounds = (
+            line_start,
+            line_start + leading_whitespace_len(&line),
+            line_start + line.len(),
+        );
+        match base {
+            None => {
+                base = Some(width);
+                first = Some(bounds);
+                last = Some(bounds);
+            }
+            Some(b) if width >= b => first = Some(bounds),
+            Some(_) => break,
+        }
+    }
+
+    let (start, first_text, _) = first?;
+    let (_, last_text, end) = last?;
+    Some(IndentBlock {
+        start,
+        end,
+        first_text,
+        last_text,
+        indent: base?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PYTHON: &str = "class A:\n    def f(self):\n        x = 1\n\n        return x\n\n    def g(self):\n        pass\n";
+
+    fn block_at(text: &str, needle: &str) -> IndentBlock {
+        let mut buffer = Buffer::from_str_test(text);
+        let pos = text.find(needle).unwrap();
+        find_indent_block(&mut buffer, pos, 4, 80, usize::MAX).unwrap()
+    }
+
+    #[test]
+    fn test_indent_width() {
+        assert_eq!(indent_width("    x\n", 4), Some(4));
+        assert_eq!(indent_width("\tx", 4), Some(4));
+        assert_eq!(indent_width("  \tx", 4), Some(4));
+        assert_eq!(indent_width("x", 4), Some(0));
+        assert_eq!(indent_width("   \n", 4), None);
+        assert_eq!(indent_width("", 4), None);
+    }
+
+    #[test]
+    fn test_block_spans_blank_lines_inside_body() {
+        let block = block_at(PYTHON, "x = 1");
+        assert_eq!(
+            &PYTHON[block.start..block.end],
+            "        x = 1\n\n        return x\n"
+        );
+        assert_eq!(block.indent, 8);
+        assert_eq!(&PYTHON[block.first_text..block.first_text + 5], "x = 1");
+        assert_eq!(&PYTHON[block.last_text..block.last_text + 6], "return");
+    }
+
+    #[test]
+    fn test_block_includes_nested_lines() {
+        let block = block_at(PYTHON, "def g");
+        assert_eq!(
+            &PYTHON[block.start..block.end],
+            "    def f(self):\n        x = 1\n\n        return x\n\n    def g(self):\n        pass\n"
+        );
+        assert_eq!(block.indent, 4);
+    }
+
+    #[test]
+    fn test_blank_line_uses_next_line_indent() {
+        // The blank line between the two methods belongs to the class body
+        let mut buffer = Buffer::from_str_test(PYTHON);
+        let pos = PYTHON.find("\n\n    def g").unwrap() + 1;
+        let block = find_indent_block(&mut buffer, pos, 4, 80, usize::MAX).unwrap();
+        assert_eq!(block.indent, 4);
+        assert_eq!(&PYTHON[block.start..block.start + 9], "    def f");
+    }
+
+    #[test]
+    fn test_max_lines_limits_scan() {
+        let mut buffer = Buffer::from_str_test(PYTHON);
+        let pos = PYTHON.find("def g").unwrap();
+        let block = find_indent_block(&mut buffer, pos, 4, 80, 1).unwrap();
+        assert_eq!(
+            &PYTHON[block.start..block.end],
+            "    def g(self):\n        pass\n"
+        );
+    }
+
+    #[test]
+    fn test_blank_buffer_has_no_block() {
+        let mut buffer = Buffer::from_str_test("\n\n");
+        assert_eq!(find_indent_block(&mut buffer, 0, 4, 80, usize::MAX), None);
+    }
+}
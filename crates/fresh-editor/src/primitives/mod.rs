@@ -15,14 +15,19 @@
 //! | Reference highlighting | `reference_highlight_text` | `reference_highlighter` |
 
 // Pure modules - available for both runtime and WASM
+pub mod digraphs;
 pub mod display_width;
+pub mod evaluate;
 pub mod grapheme;
 pub mod line_wrapping;
 pub mod path_utils;
+pub mod reflow;
 pub mod snippet;
 pub mod text_property;
+pub mod unicode_chars;
 
 // Modules depending on model::buffer - available for both runtime and WASM
+pub mod indent_block;
 pub mod line_iterator;
 pub mod word_navigation;
 
@@ -57,6 +62,8 @@ pub mod textmate_engine;
 // Runtime-only modules (depend on tree-sitter)
 // These provide enhanced features using AST analysis
 #[cfg(feature = "runtime")]
+pub mod doc_comment;
+#[cfg(feature = "runtime")]
 pub mod highlight_engine;
 #[cfg(feature = "runtime")]
 pub mod highlighter;
@@ -64,3 +71,11 @@ pub mod highlighter;
 pub mod indent;
 #[cfg(feature = "runtime")]
 pub mod reference_highlighter;
+#[cfg(feature = "runtime")]
+pub mod rename_conflicts;
+#[cfg(feature = "runtime")]
+pub mod sticky_scroll;
+#[cfg(feature = "runtime")]
+pub mod syntax_query;
+#[cfg(feature = "runtime")]
+pub mod trailing_whitespace;
@@ -0,0 +1,269 @@
+//! Paragraph reflow (fill-paragraph)
+//!
+//! Re-wraps prose to a target column. Each line is split into a prefix and a
+//! body: the prefix is the indentation plus any comment or quote markers
+//! (`// `, `# `, `> `), and it is repeated on every wrapped line. A paragraph
+//! is a run of non-blank lines sharing the same prefix. A body starting with a
+//! list marker (`- `, `* `, `1. `) starts a new paragraph whose continuation
+//! lines are indented to line up with the item text.
+//!
+//! Reflowing is greedy and joins words with single spaces, so reflowing an
+//! already reflowed paragraph gives the same text back.
+
+use crate::primitives::display_width::char_width;
+use std::ops::Range;
+
+/// Markers that are recognised as a line prefix when the language doesn't
+/// name a comment prefix
+pub const DEFAULT_MARKERS: &[&str] = &["//", "#"];
+
+/// Quote marker recognised in every language
+const QUOTE_MARKER: &str = ">";
+
+/// Markers to recognise for a language's comment prefix (`None` when the
+/// language has no comment syntax configured)
+pub fn markers_for(comment_prefix: Option<&str>) -> Vec<&str> {
+    let mut markers: Vec<&str> = match comment_prefix.map(str::trim) {
+        Some(prefix) if !prefix.is_empty() => vec![prefix],
+        _ => DEFAULT_MARKERS.to_vec(),
+    };
+    markers.push(QUOTE_MARKER);
+    markers
+}
+
+/// A line split into its repeated prefix and its text
+struct Line<'a> {
+    prefix: &'a str,
+    body: &'a str,
+}
+
+/// A paragraph: a line range plus the prefixes to write for it
+struct Paragraph {
+    lines: Range<usize>,
+    first_prefix: String,
+    rest_prefix: String,
+}
+
+fn split_line<'a>(line: &'a str, markers: &[&str]) -> Line<'a> {
+    let mut end = indent_len(line);
+    loop {
+        let rest = &line[end..];
+        let Some(marker) = markers.iter().find(|m| rest.starts_with(**m)) else {
+            break;
+        };
+        // Doc-comment and heading variants (`///`, `//!`, `##`) extend the marker
+        let extra: usize = rest[marker.len()..]
+            .chars()
+            .take_while(|c| marker.contains(*c) || *c == '!')
+            .map(char::len_utf8)
+            .sum();
+        end += marker.len() + extra;
+        end += indent_len(&line[end..]);
+    }
+    Line {
+        prefix: &line[..end],
+        body: line[end..].trim_end(),
+    }
+}
+
+fn indent_len(s: &str) -> usize {
+    s.len() - s.trim_start_matches([' ', '\t']).len()
+}
+
+/// Length of a list marker plus the space after it at the start of `body`
+fn list_marker_len(body: &str) -> Option<usize> {
+    let bullet = body.strip_prefix(['-', '*', '+']).map(|_| 1).or_else(|| {
+        let digits = body.chars().take_while(char::is_ascii_digit).count();
+        (digits > 0 && digits <= 9 && body[digits..].starts_with(['.', ')'])).then_some(digits + 1)
+    })?;
+    let spaces = indent_len(&body[bullet..]);
+    (spaces > 0 && bullet + spaces < body.len()).then_some(bullet + spaces)
+}
+
+fn width(s: &str, tab_size: usize) -> usize {
+    s.chars()
+        .map(|c| if c == '\t' { tab_size } else { char_width(c) })
+        .sum()
+}
+
+/// Group `lines` into paragraphs, skipping blank lines
+fn paragraphs(lines: &[Line<'_>]) -> Vec<Paragraph> {
+    let mut paragraphs: Vec<Paragraph> = Vec::new();
+    for (index, line) in lines.iter().enumerate() {
+        if line.body.is_empty() {
+            continue;
+        }
+        if let Some(marker_len) = list_marker_len(line.body) {
+            paragraphs.push(Paragraph {
+                lines: index..index + 1,
+                first_prefix: format!("{}{}", line.prefix, &line.body[..marker_len]),
+                rest_prefix: format!(
+                    "{}{}",
+                    line.prefix,
+                    " ".repeat(width(&line.body[..marker_len], 1))
+                ),
+            });
+            continue;
+        }
+        match paragraphs.last_mut() {
+            Some(current) if current.lines.end == index && current.rest_prefix == line.prefix => {
+                current.lines.end = index + 1;
+            }
+            _ => paragraphs.push(Paragraph {
+                lines: index..index + 1,
+                first_prefix: line.prefix.to_string(),
+                rest_prefix: line.prefix.to_string(),
+            }),
+        }
+    }
+    paragraphs
+}
+
+/// Line range of the paragraph containing line `line` of `text`, or `None`
+/// when that line is blank
+pub fn paragraph_at(text: &str, line: usize, markers: &[&str]) -> Option<Range<usize>> {
+    let lines: Vec<Line<'_>> = text.lines().map(|l| split_line(l, markers)).collect();
+    paragraphs(&lines)
+        .into_iter()
+        .map(|p| p.lines)
+        .find(|lines| lines.contains(&line))
+}
+
+/// Re-wrap every paragraph in `text` to `column` display columns, keeping
+/// prefixes, blank lines and whether the text ends with a line ending
+pub fn reflow(
+    text: &str,
+    column: usize,
+    markers: &[&str],
+    tab_size: usize,
+    line_ending: &str,
+) -> String {
+    let lines: Vec<Line<'_>> = text.lines().map(|l| split_line(l, markers)).collect();
+    let mut output: Vec<String> = Vec::new();
+    let mut next = 0;
+    for paragraph in paragraphs(&lines) {
+        // Blank lines between paragraphs are kept, minus trailing whitespace
+        for line in &lines[next..paragraph.lines.start] {
+            output.push(line.prefix.trim_end().to_string());
+        }
+        next = paragraph.lines.end;
+
+        let mut words = lines[paragraph.lines.clone()]
+            .iter()
+            .enumerate()
+            .flat_map(|(i, line)| {
+                // The list marker is part of the first line's prefix
+                let body = if i == 0 {
+                    &line.body[paragraph.first_prefix.len() - line.prefix.len()..]
+                } else {
+                    line.body
+                };
+                body.split_whitespace()
+            });
+
+        // Every line gets at least one word, even one wider than the column
+        let mut current = paragraph.first_prefix.clone();
+        current.extend(words.next());
+        let mut current_width = width(&current, tab_size);
+        for word in words {
+            let word_width = width(word, tab_size);
+            if current_width + 1 + word_width > column {
+                output.push(std::mem::replace(
+                    &mut current,
+                    paragraph.rest_prefix.clone(),
+                ));
+                current_width = width(&current, tab_size);
+            } else {
+                current.push(' ');
+                current_width += 1;
+            }
+            current.push_str(word);
+            current_width += word_width;
+        }
+        output.push(current);
+    }
+    for line in &lines[next..] {
+        output.push(line.prefix.trim_end().to_string());
+    }
+
+    let mut result = output.join(line_ending);
+    if text.ends_with('\n') {
+        result.push_str(line_ending);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reflow_at(text: &str, column: usize, comment_prefix: Option<&str>) -> String {
+        reflow(text, column, &markers_for(comment_prefix), 4, "\n")
+    }
+
+    #[test]
+    fn test_reflow_wraps_and_joins_plain_text() {
+        let text = "one two three four five six\nseven\n\neight nine\n";
+        assert_eq!(
+            reflow_at(text, 14, None),
+            "one two three\nfour five six\nseven\n\neight nine\n"
+        );
+    }
+
+    #[test]
+    fn test_reflow_keeps_comment_and_quote_prefixes() {
+        let text = "    // alpha beta gamma delta epsilon\n    // zeta\n";
+        assert_eq!(
+            reflow_at(text, 25, Some("//")),
+            "    // alpha beta gamma\n    // delta epsilon zeta\n"
+        );
+
+        let text = "/// doc comment text that wraps\n";
+        assert_eq!(
+            reflow_at(text, 20, Some("//")),
+            "/// doc comment text\n/// that wraps\n"
+        );
+
+        let text = "> > quoted text here\n";
+        assert_eq!(reflow_at(text, 14, None), "> > quoted\n> > text here\n");
+
+        // `#` only counts as a comment prefix when the language uses it
+        let text = "# not a comment\n";
+        assert_eq!(reflow_at(text, 10, Some("--")), "# not a\ncomment\n");
+    }
+
+    #[test]
+    fn test_reflow_indents_list_items() {
+        let text = "- first item runs long\n- second\n  item\n# 1. numbered item text\n";
+        assert_eq!(
+            reflow_at(text, 16, Some("#")),
+            "- first item\n  runs long\n- second item\n# 1. numbered\n#    item text\n"
+        );
+    }
+
+    #[test]
+    fn test_reflow_is_idempotent() {
+        let text = "// - a list item with quite a few words in it\n//   and more\n// plain paragraph words that continue on\n";
+        let once = reflow_at(text, 20, Some("//"));
+        assert_eq!(reflow_at(&once, 20, Some("//")), once);
+    }
+
+    #[test]
+    fn test_reflow_keeps_long_words_and_crlf() {
+        let text = "short averyveryverylongword end\r\n";
+        assert_eq!(
+            reflow(text, 10, &markers_for(None), 4, "\r\n"),
+            "short\r\naveryveryverylongword\r\nend\r\n"
+        );
+    }
+
+    #[test]
+    fn test_paragraph_at() {
+        let text = "a\nb\n\n// c\n// d\n- e\n";
+        let markers = markers_for(Some("//"));
+        assert_eq!(paragraph_at(text, 1, &markers), Some(0..2));
+        assert_eq!(paragraph_at(text, 2, &markers), None);
+        assert_eq!(paragraph_at(text, 4, &markers), Some(3..5));
+        assert_eq!(paragraph_at(text, 5, &markers), Some(5..6));
+    }
+}
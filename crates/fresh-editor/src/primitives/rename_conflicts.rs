@@ -0,0 +1,176 @@
+//! Conflict detection for LSP rename edits
+//!
+//! A language server's rename is usually right, but some edits are worth a
+//! second look before they are applied:
+//! - the new name is already used in the scope of the edit, so the renamed
+//!   symbol may shadow (or be shadowed by) another binding
+//! - the edit is inside a string or comment, where servers that rename by
+//!   text may touch prose that only looks like the symbol
+//!
+//! Scopes are approximated with tree-sitter: the nearest enclosing function,
+//! method, closure, class or impl, or the whole file at top level.
+
+use crate::primitives::highlighter::Language;
+use crate::primitives::trailing_whitespace::{is_string_kind, parse};
+use fresh_languages::tree_sitter::Node;
+use std::ops::Range;
+
+/// Why a rename edit is risky
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRisk {
+    /// The new name is already used in the edit's scope
+    NameInScope,
+    /// The edit is inside a string literal
+    InString,
+    /// The edit is inside a comment
+    InComment,
+}
+
+/// Node kinds that open a scope for name conflicts
+const SCOPE_KINDS: &[&str] = &[
+    "function_item",
+    "function_definition",
+    "function_declaration",
+    "function_expression",
+    "method_definition",
+    "method_declaration",
+    "arrow_function",
+    "closure_expression",
+    "lambda",
+    "func_literal",
+    "class_definition",
+    "class_declaration",
+    "impl_item",
+    "trait_item",
+];
+
+/// Risk of each rename edit in one file, in the order of `edits`
+///
+/// `edits` are the byte ranges being replaced with their new text. Returns
+/// no risks when the language cannot be parsed.
+pub fn rename_risks(
+    text: &str,
+    language: &Language,
+    edits: &[(Range<usize>, &str)],
+) -> Vec<Option<RenameRisk>> {
+    let Some(tree) = parse(text, language) else {
+        return vec![None; edits.len()];
+    };
+    let root = tree.root_node();
+
+    edits
+        .iter()
+        .map(|(range, new_name)| {
+            let node = root.descendant_for_byte_range(range.start, range.end)?;
+            if let Some(risk) = literal_risk(node) {
+                return Some(risk);
+            }
+            let scope = enclosing_scope(node).unwrap_or(root);
+            let taken = uses_name(scope, text, new_name, &|ident: &Range<usize>| {
+                !edits
+                    .iter()
+                    .any(|(r, _)| r.start < ident.end && ident.start < r.end)
+            });
+            taken.then_some(RenameRisk::NameInScope)
+        })
+        .collect()
+}
+
+/// String or comment around `node`, if any
+fn literal_risk(node: Node) -> Option<RenameRisk> {
+    let mut current = Some(node);
+    while let Some(n) = current {
+        let kind = n.kind();
+        if kind.contains("comment") {
+            return Some(RenameRisk::InComment);
+        }
+        if is_string_kind(kind) {
+            return Some(RenameRisk::InString);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+fn enclosing_scope(node: Node) -> Option<Node> {
+    let mut current = node.parent();
+    while let Some(n) = current {
+        if SCOPE_KINDS.contains(&n.kind()) {
+            return Some(n);
+        }
+        current = n.parent();
+    }
+    None
+}
+
+/// Whether an identifier spelled `name` (and accepted by `keep`) appears
+/// under `node`
+fn uses_name(node: Node, text: &str, name: &str, keep: &dyn Fn(&Range<usize>) -> bool) -> bool {
+    let kind = node.kind();
+    if kind == "identifier" || kind.ends_with("_identifier") {
+        let range = node.byte_range();
+        return text.get(range.clone()) == Some(name) && keep(&range);
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if uses_name(child, text, name, keep) {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges_of(text: &str, word: &str) -> Vec<Range<usize>> {
+        text.match_indices(word)
+            .map(|(start, _)| start..start + word.len())
+            .collect()
+    }
+
+    #[test]
+    fn test_rename_risks_rust() {
+        let text = r#"fn first() {
+    let count = 1;
+    let total = 2;
+    // count the things
+    let label = "count";
+    count + total
+}
+
+fn second() {
+    let count = 3;
+}
+"#;
+        let edits: Vec<(Range<usize>, &str)> = ranges_of(text, "count")
+            .into_iter()
+            .map(|range| (range, "total"))
+            .collect();
+        let risks = rename_risks(text, &Language::Rust, &edits);
+
+        assert_eq!(
+            risks,
+            vec![
+                Some(RenameRisk::NameInScope),
+                Some(RenameRisk::InComment),
+                Some(RenameRisk::InString),
+                Some(RenameRisk::NameInScope),
+                None,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rename_risks_no_conflict() {
+        let text = "def f(a):\n    return a\n";
+        let edits: Vec<(Range<usize>, &str)> = ranges_of(text, "a")
+            .into_iter()
+            .map(|range| (range, "b"))
+            .collect();
+        assert!(rename_risks(text, &Language::Python, &edits)
+            .iter()
+            .all(Option::is_none));
+    }
+}
@@ -7,6 +7,11 @@
 //! - `${n:text}` - tabstops with default text (uses the default)
 //! - `${n|choice1,choice2|}` - choices (uses first choice)
 //! - `\\$` - escaped dollar sign
+//!
+//! The ranges of the expanded tabstops are kept so callers can step through
+//! them.
+
+use std::ops::Range;
 
 /// Result of expanding a snippet
 #[derive(Debug, Clone, PartialEq)]
@@ -15,6 +20,9 @@ pub struct ExpandedSnippet {
     pub text: String,
     /// Cursor offset from start of inserted text (where $0 was, or end if no $0)
     pub cursor_offset: usize,
+    /// Byte ranges of the tabstops `$1`, `$2`, ... in `text`, in tabstop
+    /// order (the first occurrence when a number repeats); `$0` is not included
+    pub tabstops: Vec<Range<usize>>,
 }
 
 /// Expand an LSP snippet to plain text
@@ -34,7 +42,27 @@ pub struct ExpandedSnippet {
 /// assert_eq!(result.cursor_offset, 8); // cursor at end (no $0)
 /// ```
 pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
-    let mut result = String::new();
+    let mut text = String::new();
+    let mut tabstops = Vec::new();
+    let cursor_offset = expand_into(snippet, &mut text, &mut tabstops);
+
+    // Stable sort keeps the first occurrence of a repeated tabstop first
+    tabstops.sort_by_key(|(number, _)| *number);
+    tabstops.dedup_by_key(|(number, _)| *number);
+    ExpandedSnippet {
+        cursor_offset: cursor_offset.unwrap_or(text.len()),
+        tabstops: tabstops.into_iter().map(|(_, range)| range).collect(),
+        text,
+    }
+}
+
+/// Append the expansion of `snippet` to `result`, recording tabstops.
+/// Returns the offset of `$0`, if any.
+fn expand_into(
+    snippet: &str,
+    result: &mut String,
+    tabstops: &mut Vec<(u32, Range<usize>)>,
+) -> Option<usize> {
     let mut cursor_offset: Option<usize> = None;
     let mut chars = snippet.chars().peekable();
 
@@ -54,11 +82,26 @@ pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
                 if next == '{' {
                     // ${...} syntax
                     chars.next(); // consume '{'
-                    let (expanded, is_final) = parse_brace_placeholder(&mut chars);
-                    if is_final {
-                        cursor_offset = Some(result.len());
+                    let content = parse_brace_placeholder(&mut chars);
+                    let start = result.len();
+                    let tabstop = match parse_placeholder_content(&content) {
+                        (tabstop, Placeholder::Default(default)) => {
+                            // Nested snippets in the default are expanded in place
+                            if let Some(nested) = expand_into(&default, result, tabstops) {
+                                cursor_offset.get_or_insert(nested);
+                            }
+                            tabstop
+                        }
+                        (tabstop, Placeholder::Choice(choice)) => {
+                            result.push_str(&choice);
+                            tabstop
+                        }
+                    };
+                    match tabstop {
+                        Some(0) => cursor_offset = Some(start),
+                        Some(number) => tabstops.push((number, start..result.len())),
+                        None => {}
                     }
-                    result.push_str(&expanded);
                 } else if next.is_ascii_digit() {
                     // $n syntax
                     let mut num = String::new();
@@ -69,10 +112,12 @@ pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
                             break;
                         }
                     }
-                    if num == "0" {
-                        cursor_offset = Some(result.len());
+                    // Tabstops expand to nothing
+                    match num.parse::<u32>() {
+                        Ok(0) => cursor_offset = Some(result.len()),
+                        Ok(number) => tabstops.push((number, result.len()..result.len())),
+                        Err(_) => {}
                     }
-                    // Other tabstops expand to nothing
                 } else {
                     // Not a valid placeholder, keep the $
                     result.push(c);
@@ -85,15 +130,19 @@ pub fn expand_snippet(snippet: &str) -> ExpandedSnippet {
         }
     }
 
-    ExpandedSnippet {
-        cursor_offset: cursor_offset.unwrap_or(result.len()),
-        text: result,
-    }
+    cursor_offset
 }
 
-/// Parse a ${...} placeholder
-/// Returns (expanded_text, is_final_cursor)
-fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) -> (String, bool) {
+/// Text a `${...}` placeholder expands to
+enum Placeholder {
+    /// Default text, which may itself contain snippet syntax
+    Default(String),
+    /// The first of a list of choices
+    Choice(String),
+}
+
+/// Collect the content of a ${...} placeholder, up to its matching '}'
+fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
     let mut content = String::new();
     let mut depth = 1;
 
@@ -109,29 +158,22 @@ fn parse_brace_placeholder(chars: &mut std::iter::Peekable<std::str::Chars>) ->
             }
             content.push(c);
         } else if c == '\\' {
-            // Handle escapes inside braces
-            if let Some(&next) = chars.peek() {
-                if next == '$' || next == '\\' || next == '}' || next == '|' {
-                    content.push(chars.next().unwrap());
-                    continue;
-                }
-            }
+            // Keep escapes for the nested expansion of the default text
             content.push(c);
+            if let Some(next) = chars.next() {
+                content.push(next);
+            }
         } else {
             content.push(c);
         }
     }
 
-    // Parse the content: n or n:default or n|choices|
-    let (tabstop, default) = parse_placeholder_content(&content);
-
-    let is_final = tabstop == Some(0);
-    (default, is_final)
+    content
 }
 
 /// Parse placeholder content like "1", "1:default", or "1|a,b,c|"
-/// Returns (tabstop_number, default_text)
-fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
+/// Returns (tabstop_number, placeholder_text)
+fn parse_placeholder_content(content: &str) -> (Option<u32>, Placeholder) {
     // Find the tabstop number
     let mut chars = content.chars().peekable();
     let mut num_str = String::new();
@@ -151,10 +193,7 @@ fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
         Some(':') => {
             // Default text: ${n:default}
             chars.next(); // consume ':'
-            let default: String = chars.collect();
-            // Recursively expand nested snippets in default
-            let expanded = expand_snippet(&default);
-            (tabstop, expanded.text)
+            (tabstop, Placeholder::Default(chars.collect()))
         }
         Some('|') => {
             // Choices: ${n|choice1,choice2|}
@@ -164,13 +203,29 @@ fn parse_placeholder_content(content: &str) -> (Option<u32>, String) {
             let choices = choices.trim_end_matches('|');
             // Use first choice
             let first_choice = choices.split(',').next().unwrap_or("");
-            (tabstop, first_choice.to_string())
+            (tabstop, Placeholder::Choice(unescape(first_choice)))
         }
         _ => {
             // Just a tabstop number, no default
-            (tabstop, String::new())
+            (tabstop, Placeholder::Default(String::new()))
+        }
+    }
+}
+
+/// Remove the backslash from escaped characters in a choice
+fn unescape(text: &str) -> String {
+    let mut result = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                result.push(next);
+                continue;
+            }
         }
+        result.push(c);
     }
+    result
 }
 
 /// Check if a string contains snippet syntax
@@ -271,6 +326,17 @@ mod tests {
         assert!(!is_snippet("\\$100")); // escaped
     }
 
+    #[test]
+    fn test_tabstop_ranges() {
+        let result = expand_snippet("fn ${2:name}(${1:args}) { $0 } $3 ${2:again}");
+        assert_eq!(result.text, "fn name(args) {  }  again");
+        assert_eq!(result.tabstops, vec![8..12, 3..7, 19..19]);
+        assert_eq!(result.cursor_offset, 16);
+
+        let nested = expand_snippet("${1:foo${2:bar}}");
+        assert_eq!(nested.tabstops, vec![0..6, 3..6]);
+    }
+
     #[test]
     fn test_empty_default() {
         let result = expand_snippet("foo(${1:})");
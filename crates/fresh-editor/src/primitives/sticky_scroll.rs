@@ -0,0 +1,280 @@
+//! Scope headers for sticky scroll
+//!
+//! While scrolling through the body of a function, class or section, its
+//! first line stays pinned at the top of the split. The scopes enclosing a
+//! line come from tree-sitter when the language has a grammar and the file
+//! contains definitions, from `#` headings in Markdown, and from indentation
+//! otherwise: every less indented line above a line opens a scope around it.
+
+use crate::model::buffer::Buffer;
+use crate::primitives::highlighter::Language;
+use crate::primitives::indent_block::indent_width;
+use crate::primitives::trailing_whitespace::parse;
+use fresh_languages::tree_sitter::Node;
+
+/// Node kinds whose first line is shown as a sticky header
+const HEADER_KINDS: &[&str] = &[
+    "function_item",
+    "function_definition",
+    "function_declaration",
+    "method_definition",
+    "method_declaration",
+    "constructor_declaration",
+    "class_definition",
+    "class_declaration",
+    "class_specifier",
+    "struct_item",
+    "struct_specifier",
+    "enum_item",
+    "enum_declaration",
+    "union_item",
+    "impl_item",
+    "trait_item",
+    "mod_item",
+    "macro_definition",
+    "interface_declaration",
+    "namespace_definition",
+    "namespace_declaration",
+    "module",
+    "singleton_class",
+];
+
+/// Files larger than this are not parsed; indentation is used instead
+const MAX_PARSE_BYTES: usize = 1024 * 1024;
+
+/// Lines scanned upwards when looking for indentation or heading scopes
+const MAX_SCAN_LINES: usize = 5_000;
+
+/// A multi-line scope found by tree-sitter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Scope {
+    /// Byte offset of the start of the scope's first line
+    pub header: usize,
+    /// Byte offset just past the end of the scope
+    pub end: usize,
+}
+
+/// Multi-line definitions in `text`, ordered by header
+///
+/// Returns `None` when the language cannot be parsed.
+pub fn tree_sitter_scopes(text: &str, language: &Language) -> Option<Vec<Scope>> {
+    let tree = parse(text, language)?;
+    let mut scopes = Vec::new();
+    collect_scopes(tree.root_node(), text, &mut scopes);
+    scopes.sort_by_key(|scope| (scope.header, std::cmp::Reverse(scope.end)));
+    scopes.dedup_by_key(|scope| scope.header);
+    Some(scopes)
+}
+
+fn collect_scopes(node: Node, text: &str, scopes: &mut Vec<Scope>) {
+    if HEADER_KINDS.contains(&node.kind()) && node.start_position().row < node.end_position().row {
+        let start = node.start_byte();
+        let header = text[..start].rfind('\n').map_or(0, |i| i + 1);
+        scopes.push(Scope {
+            header,
+            end: node.end_byte(),
+        });
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_scopes(child, text, scopes);
+    }
+}
+
+/// Headers of the scopes around the line starting at `line_start`, outermost first
+pub fn enclosing_scope_headers(scopes: &[Scope], line_start: usize) -> Vec<usize> {
+    scopes
+        .iter()
+        .take_while(|scope| scope.header < line_start)
+        .filter(|scope| scope.end > line_start)
+        .map(|scope| scope.header)
+        .collect()
+}
+
+/// Headers of the indentation scopes around the line starting at `line_start`,
+/// outermost first
+///
+/// A blank line belongs to the scope of the next non-blank line.
+pub fn indent_headers(
+    buffer: &mut Buffer,
+    line_start: usize,
+    tab_size: usize,
+    estimated_line_length: usize,
+) -> Vec<usize> {
+    let mut forward = buffer.line_iterator(line_start, estimated_line_length);
+    let mut indent = None;
+    for _ in 0..MAX_SCAN_LINES {
+        let Some((_, line)) = forward.next_line() else {
+            break;
+        };
+        if let Some(width) = indent_width(&line, tab_size) {
+            indent = Some(width);
+            break;
+        }
+    }
+    let Some(mut indent) = indent else {
+        return Vec::new();
+    };
+
+    let mut headers = Vec::new();
+    let mut backward = buffer.line_iterator(line_start, estimated_line_length);
+    for _ in 0..MAX_SCAN_LINES {
+        if indent == 0 {
+            break;
+        }
+        let Some((start, line)) = backward.prev() else {
+            break;
+        };
+        match indent_width(&line, tab_size) {
+            Some(width) if width < indent => {
+                headers.push(start);
+                indent = width;
+            }
+            _ => {}
+        }
+    }
+    headers.reverse();
+    headers
+}
+
+/// Level of a Markdown ATX heading (`## Title` is 2)
+fn heading_level(line: &str) -> Option<usize> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let level = line.bytes().take_while(|&b| b == b'#').count();
+    let rest = &line[level..];
+    ((1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t'])))
+        .then_some(level)
+}
+
+/// Headers of the Markdown sections around the line starting at `line_start`,
+/// outermost first
+pub fn heading_headers(
+    buffer: &mut Buffer,
+    line_start: usize,
+    estimated_line_length: usize,
+) -> Vec<usize> {
+    let mut iter = buffer.line_iterator(line_start, estimated_line_length);
+    // A heading is only inside sections of a higher level
+    let mut level = iter
+        .next_line()
+        .and_then(|(_, line)| heading_level(&line))
+        .unwrap_or(usize::MAX);
+
+    let mut headers = Vec::new();
+    let mut backward = buffer.line_iterator(line_start, estimated_line_length);
+    for _ in 0..MAX_SCAN_LINES {
+        if level == 1 {
+            break;
+        }
+        let Some((start, line)) = backward.prev() else {
+            break;
+        };
+        if let Some(heading) = heading_level(&line).filter(|&heading| heading < level) {
+            headers.push(start);
+            level = heading;
+        }
+    }
+    headers.reverse();
+    headers
+}
+
+/// Finds the scope headers to pin for a buffer, caching the tree-sitter
+/// scopes until the buffer changes
+#[derive(Debug, Default)]
+pub struct StickyScopes {
+    /// Buffer version and the scopes parsed from it (`None` if the buffer
+    /// has no grammar, is too large or has no definitions)
+    cache: Option<(u64, Option<Vec<Scope>>)>,
+}
+
+impl StickyScopes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Headers of the scopes around the line starting at `line_start`,
+    /// outermost first
+    pub fn headers(
+        &mut self,
+        buffer: &mut Buffer,
+        language: &str,
+        line_start: usize,
+        tab_size: usize,
+        estimated_line_length: usize,
+    ) -> Vec<usize> {
+        if language == "markdown" {
+            return heading_headers(buffer, line_start, estimated_line_length);
+        }
+
+        let version = buffer.version();
+        if self.cache.as_ref().map(|(v, _)| *v) != Some(version) {
+            let scopes = Self::parse_scopes(buffer);
+            self.cache = Some((version, scopes));
+        }
+        match self.cache.as_ref().and_then(|(_, scopes)| scopes.as_ref()) {
+            Some(scopes) => enclosing_scope_headers(scopes, line_start),
+            None => indent_headers(buffer, line_start, tab_size, estimated_line_length),
+        }
+    }
+
+    fn parse_scopes(buffer: &Buffer) -> Option<Vec<Scope>> {
+        if buffer.len() > MAX_PARSE_BYTES {
+            return None;
+        }
+        let language = buffer.file_path().and_then(Language::from_path)?;
+        let text = buffer.to_string()?;
+        tree_sitter_scopes(&text, &language).filter(|scopes| !scopes.is_empty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_sitter_scopes_enclose_lines() {
+        let text = "struct A;\n\nimpl A {\n    fn f() {\n        1;\n    }\n}\n";
+        let scopes = tree_sitter_scopes(text, &Language::Rust).unwrap();
+        let impl_start = text.find("impl").unwrap();
+        let fn_start = text.find("    fn").unwrap();
+        let body = text.find("        1").unwrap();
+
+        assert_eq!(
+            enclosing_scope_headers(&scopes, body),
+            vec![impl_start, fn_start]
+        );
+        assert_eq!(enclosing_scope_headers(&scopes, fn_start), vec![impl_start]);
+        assert!(enclosing_scope_headers(&scopes, 0).is_empty());
+    }
+
+    #[test]
+    fn test_indent_headers() {
+        let text = "a:\n  b:\n    c\n\n    d\n  e\nf\n";
+        let mut buf = Buffer::from_str_test(text);
+        let d = text.find("    d").unwrap();
+        let blank = text.find("\n\n").unwrap() + 1;
+        let e = text.find("  e").unwrap();
+
+        assert_eq!(indent_headers(&mut buf, d, 4, 80), vec![0, 3]);
+        assert_eq!(indent_headers(&mut buf, blank, 4, 80), vec![0, 3]);
+        assert_eq!(indent_headers(&mut buf, e, 4, 80), vec![0]);
+        assert!(indent_headers(&mut buf, text.find('f').unwrap(), 4, 80).is_empty());
+    }
+
+    #[test]
+    fn test_heading_headers() {
+        let text = "# Top\n\n## Sub\ntext\n#tag\n## Next\n";
+        let mut buf = Buffer::from_str_test(text);
+        let sub = text.find("## Sub").unwrap();
+
+        assert_eq!(
+            heading_headers(&mut buf, text.find("#tag").unwrap(), 80),
+            vec![0, sub]
+        );
+        assert_eq!(
+            heading_headers(&mut buf, text.find("## Next").unwrap(), 80),
+            vec![0]
+        );
+        assert!(heading_headers(&mut buf, 0, 80).is_empty());
+    }
+}
@@ -0,0 +1,80 @@
+//! Tree-sitter queries against buffer text
+//!
+//! Runs a query written in tree-sitter's S-expression query language (the
+//! same language as the `highlights.scm` files of grammars) and returns its
+//! captures in document order, so plugins can navigate and edit by syntax
+//! without shipping parsers of their own.
+
+use crate::primitives::highlighter::Language;
+use crate::primitives::trailing_whitespace::{grammar, parse};
+use fresh_languages::tree_sitter::{Query, QueryCursor, StreamingIterator};
+use std::ops::Range;
+
+/// A node captured by a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capture {
+    /// Capture name without the `@` (e.g. "name" for `@name`)
+    pub name: String,
+    /// Byte range of the captured node
+    pub range: Range<usize>,
+}
+
+/// Run `query` against `text` parsed as `language`
+///
+/// Captures are sorted by start offset. A node captured by several patterns
+/// is reported once per capture. Returns an error if the query doesn't
+/// compile for the language's grammar.
+pub fn query_syntax(text: &str, language: &Language, query: &str) -> Result<Vec<Capture>, String> {
+    let query = Query::new(&grammar(language), query).map_err(|e| e.to_string())?;
+    let Some(tree) = parse(text, language) else {
+        return Err(format!("Failed to parse {}", language.display_name()));
+    };
+
+    let mut captures = Vec::new();
+    let mut cursor = QueryCursor::new();
+    let mut matches = cursor.captures(&query, tree.root_node(), text.as_bytes());
+    while let Some((query_match, index)) = matches.next() {
+        let capture = query_match.captures[*index];
+        captures.push(Capture {
+            name: query.capture_names()[capture.index as usize].to_string(),
+            range: capture.node.byte_range(),
+        });
+    }
+    Ok(captures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_syntax_rust() {
+        let text = "fn first() {}\nstruct Point;\nfn second(x: u32) {}\n";
+        let captures = query_syntax(
+            text,
+            &Language::Rust,
+            "(function_item name: (identifier) @name) @function",
+        )
+        .unwrap();
+
+        let found: Vec<(&str, &str)> = captures
+            .iter()
+            .map(|c| (c.name.as_str(), &text[c.range.clone()]))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("function", "fn first() {}"),
+                ("name", "first"),
+                ("function", "fn second(x: u32) {}"),
+                ("name", "second"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_query_syntax_invalid_query() {
+        assert!(query_syntax("fn a() {}", &Language::Rust, "(function_item").is_err());
+        assert!(query_syntax("fn a() {}", &Language::Rust, "(no_such_node) @x").is_err());
+    }
+}
@@ -84,6 +84,19 @@ impl TextPropertyManager {
         self.properties.sort_by_key(|p| p.start);
     }
 
+    /// Append properties from another manager, shifted forward by `offset` bytes
+    ///
+    /// Used when text is added at the end of a buffer, so every appended
+    /// property starts at or after the existing ones.
+    pub fn append(&mut self, other: Self, offset: usize) {
+        self.properties
+            .extend(other.properties.into_iter().map(|mut property| {
+                property.start += offset;
+                property.end += offset;
+                property
+            }));
+    }
+
     /// Merge properties from another source
     ///
     /// This is useful when setting buffer content with properties
@@ -298,4 +311,23 @@ mod tests {
         assert_eq!(all[0].get("id"), Some(&json!("first")));
         assert_eq!(all[1].get("id"), Some(&json!("third")));
     }
+
+    #[test]
+    fn test_manager_append_shifts_offsets() {
+        let (text, mut manager) =
+            TextPropertyManager::from_entries(vec![
+                TextPropertyEntry::text("header\n").with_property("id", json!("header"))
+            ]);
+        let (_, appended) = TextPropertyManager::from_entries(vec![
+            TextPropertyEntry::text("plain\n"),
+            TextPropertyEntry::text("row\n").with_property("id", json!("row")),
+        ]);
+
+        manager.append(appended, text.len());
+
+        assert_eq!(manager.len(), 2);
+        let row = &manager.all()[1];
+        assert_eq!((row.start, row.end), (13, 17));
+        assert_eq!(manager.get_at(13)[0].get("id"), Some(&json!("row")));
+    }
 }
@@ -0,0 +1,157 @@
+//! Trailing whitespace detection and trimming
+//!
+//! Trailing whitespace is a run of spaces and tabs right before a line ending
+//! (`\n`, `\r\n`) or the end of the text. Trimming keeps line endings as they
+//! are and leaves alone lines that end inside a multi-line string literal,
+//! where the whitespace is part of the string's value.
+
+use crate::primitives::highlighter::Language;
+use fresh_languages::tree_sitter::{self, Node, Parser};
+use std::ops::Range;
+
+/// Byte ranges of trailing whitespace in `text`, one per affected line
+pub fn trailing_whitespace_ranges(text: &str) -> Vec<Range<usize>> {
+    let bytes = text.as_bytes();
+    let mut ranges = Vec::new();
+    let mut line_start = 0;
+    while line_start <= bytes.len() {
+        let newline = bytes[line_start..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map(|i| line_start + i);
+        let mut end = newline.unwrap_or(bytes.len());
+        if end > line_start && bytes[end - 1] == b'\r' {
+            end -= 1;
+        }
+        let mut start = end;
+        while start > line_start && matches!(bytes[start - 1], b' ' | b'\t') {
+            start -= 1;
+        }
+        if start < end {
+            ranges.push(start..end);
+        }
+        match newline {
+            Some(pos) => line_start = pos + 1,
+            None => break,
+        }
+    }
+    ranges
+}
+
+/// Remove trailing whitespace, except on lines that end inside one of the
+/// `protected` ranges. Returns `None` when nothing would change.
+pub fn trim_trailing_whitespace(text: &str, protected: &[Range<usize>]) -> Option<String> {
+    let ranges: Vec<_> = trailing_whitespace_ranges(text)
+        .into_iter()
+        .filter(|r| !protected.iter().any(|p| p.start < r.end && r.end < p.end))
+        .collect();
+    if ranges.is_empty() {
+        return None;
+    }
+
+    let mut trimmed = String::with_capacity(text.len());
+    let mut copied = 0;
+    for range in ranges {
+        trimmed.push_str(&text[copied..range.start]);
+        copied = range.end;
+    }
+    trimmed.push_str(&text[copied..]);
+    Some(trimmed)
+}
+
+/// String literals spanning more than one line, as parsed by tree-sitter.
+///
+/// Returns an empty list when the language has no parser or parsing fails,
+/// in which case every line is treated as code.
+pub fn multiline_string_ranges(text: &str, language: &Language) -> Vec<Range<usize>> {
+    let Some(tree) = parse(text, language) else {
+        return Vec::new();
+    };
+
+    let mut ranges = Vec::new();
+    collect_multiline_strings(tree.root_node(), &mut ranges);
+    ranges
+}
+
+/// The tree-sitter grammar for `language`
+pub(crate) fn grammar(language: &Language) -> tree_sitter::Language {
+    match language {
+        Language::Rust => fresh_languages::tree_sitter_rust::LANGUAGE.into(),
+        Language::Python => fresh_languages::tree_sitter_python::LANGUAGE.into(),
+        Language::JavaScript => fresh_languages::tree_sitter_javascript::LANGUAGE.into(),
+        Language::TypeScript => fresh_languages::tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        Language::Go => fresh_languages::tree_sitter_go::LANGUAGE.into(),
+        Language::C => fresh_languages::tree_sitter_c::LANGUAGE.into(),
+        Language::Cpp => fresh_languages::tree_sitter_cpp::LANGUAGE.into(),
+        Language::Java => fresh_languages::tree_sitter_java::LANGUAGE.into(),
+        Language::Php => fresh_languages::tree_sitter_php::LANGUAGE_PHP.into(),
+        Language::Ruby => fresh_languages::tree_sitter_ruby::LANGUAGE.into(),
+        Language::Bash => fresh_languages::tree_sitter_bash::LANGUAGE.into(),
+        Language::Lua => fresh_languages::tree_sitter_lua::LANGUAGE.into(),
+        Language::Pascal => fresh_languages::tree_sitter_pascal::LANGUAGE.into(),
+        Language::Json => fresh_languages::tree_sitter_json::LANGUAGE.into(),
+        Language::HTML => fresh_languages::tree_sitter_html::LANGUAGE.into(),
+        Language::CSS => fresh_languages::tree_sitter_css::LANGUAGE.into(),
+        Language::CSharp => fresh_languages::tree_sitter_c_sharp::LANGUAGE.into(),
+        Language::Odin => fresh_languages::tree_sitter_odin::LANGUAGE.into(),
+    }
+}
+
+/// Parse `text` with the tree-sitter grammar for `language`
+pub(crate) fn parse(text: &str, language: &Language) -> Option<tree_sitter::Tree> {
+    let ts_language = grammar(language);
+
+    let mut parser = Parser::new();
+    parser.set_language(&ts_language).ok()?;
+    parser.parse(text, None)
+}
+
+/// Whether a node kind is a string literal (`string_literal`,
+/// `raw_string_literal`, `template_string`, `heredoc_body`, ...)
+pub(crate) fn is_string_kind(kind: &str) -> bool {
+    kind.contains("string") || kind.contains("heredoc")
+}
+
+fn collect_multiline_strings(node: Node, ranges: &mut Vec<Range<usize>>) {
+    if is_string_kind(node.kind()) && node.start_position().row < node.end_position().row {
+        ranges.push(node.byte_range());
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_multiline_strings(child, ranges);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trailing_whitespace_ranges() {
+        let text = "a  \nb\t\r\n  \nc";
+        assert_eq!(trailing_whitespace_ranges(text), vec![1..3, 5..6, 8..10]);
+        assert_eq!(trailing_whitespace_ranges("x "), vec![1..2]);
+        assert!(trailing_whitespace_ranges("").is_empty());
+    }
+
+    #[test]
+    fn test_trim_keeps_line_endings() {
+        assert_eq!(
+            trim_trailing_whitespace("a \r\nb\t\n", &[]).as_deref(),
+            Some("a\r\nb\n")
+        );
+        assert_eq!(trim_trailing_whitespace("clean\n", &[]), None);
+    }
+
+    #[test]
+    fn test_trim_skips_lines_inside_multiline_strings() {
+        let text = "let s = r\"one  \ntwo  \n\";  \nlet t = 1; \n";
+        let protected = multiline_string_ranges(text, &Language::Rust);
+        assert_eq!(protected.len(), 1);
+        assert_eq!(
+            trim_trailing_whitespace(text, &protected).as_deref(),
+            Some("let s = r\"one  \ntwo  \n\";\nlet t = 1;\n")
+        );
+    }
+}
@@ -0,0 +1,476 @@
+//! Named Unicode characters for the Insert Unicode Character picker
+//!
+//! A curated table rather than the full Unicode name database: the symbols,
+//! letters and emoji people actually reach for, grouped into categories the
+//! picker can be browsed by. Characters outside the table can still be
+//! inserted by codepoint (`U+2192`, `0x2192`).
+
+/// A character in the picker table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnicodeChar {
+    pub ch: char,
+    /// Unicode name, lowercased
+    pub name: &'static str,
+    pub category: &'static str,
+}
+
+const fn entry(ch: char, name: &'static str, category: &'static str) -> UnicodeChar {
+    UnicodeChar { ch, name, category }
+}
+
+/// Every character in the table, grouped by category
+pub const UNICODE_CHARS: &[UnicodeChar] = &[
+    // Arrows
+    entry('←', "leftwards arrow", "Arrows"),
+    entry('↑', "upwards arrow", "Arrows"),
+    entry('→', "rightwards arrow", "Arrows"),
+    entry('↓', "downwards arrow", "Arrows"),
+    entry('↔', "left right arrow", "Arrows"),
+    entry('↕', "up down arrow", "Arrows"),
+    entry('↖', "north west arrow", "Arrows"),
+    entry('↗', "north east arrow", "Arrows"),
+    entry('↘', "south east arrow", "Arrows"),
+    entry('↙', "south west arrow", "Arrows"),
+    entry('↩', "leftwards arrow with hook", "Arrows"),
+    entry('↪', "rightwards arrow with hook", "Arrows"),
+    entry('↵', "downwards arrow with corner leftwards", "Arrows"),
+    entry('⇐', "leftwards double arrow", "Arrows"),
+    entry('⇒', "rightwards double arrow", "Arrows"),
+    entry('⇔', "left right double arrow", "Arrows"),
+    entry('⇧', "upwards white arrow", "Arrows"),
+    entry('⇥', "rightwards arrow to bar", "Arrows"),
+    entry('⟵', "long leftwards arrow", "Arrows"),
+    entry('⟶', "long rightwards arrow", "Arrows"),
+    entry('⟹', "long rightwards double arrow", "Arrows"),
+    entry('↦', "rightwards arrow from bar", "Arrows"),
+    // Math
+    entry('±', "plus-minus sign", "Math"),
+    entry('×', "multiplication sign", "Math"),
+    entry('÷', "division sign", "Math"),
+    entry('−', "minus sign", "Math"),
+    entry('≈', "almost equal to", "Math"),
+    entry('≠', "not equal to", "Math"),
+    entry('≡', "identical to", "Math"),
+    entry('≤', "less-than or equal to", "Math"),
+    entry('≥', "greater-than or equal to", "Math"),
+    entry('≪', "much less-than", "Math"),
+    entry('≫', "much greater-than", "Math"),
+    entry('∞', "infinity", "Math"),
+    entry('√', "square root", "Math"),
+    entry('∑', "n-ary summation", "Math"),
+    entry('∏', "n-ary product", "Math"),
+    entry('∫', "integral", "Math"),
+    entry('∂', "partial differential", "Math"),
+    entry('∇', "nabla", "Math"),
+    entry('∆', "increment", "Math"),
+    entry('∀', "for all", "Math"),
+    entry('∃', "there exists", "Math"),
+    entry('∄', "there does not exist", "Math"),
+    entry('∅', "empty set", "Math"),
+    entry('∈', "element of", "Math"),
+    entry('∉', "not an element of", "Math"),
+    entry('⊂', "subset of", "Math"),
+    entry('⊃', "superset of", "Math"),
+    entry('⊆', "subset of or equal to", "Math"),
+    entry('⊇', "superset of or equal to", "Math"),
+    entry('∩', "intersection", "Math"),
+    entry('∪', "union", "Math"),
+    entry('∧', "logical and", "Math"),
+    entry('∨', "logical or", "Math"),
+    entry('¬', "not sign", "Math"),
+    entry('⊕', "circled plus", "Math"),
+    entry('⊗', "circled times", "Math"),
+    entry('∘', "ring operator", "Math"),
+    entry('·', "middle dot", "Math"),
+    entry('°', "degree sign", "Math"),
+    entry('′', "prime", "Math"),
+    entry('″', "double prime", "Math"),
+    entry('‰', "per mille sign", "Math"),
+    entry('¹', "superscript one", "Math"),
+    entry('²', "superscript two", "Math"),
+    entry('³', "superscript three", "Math"),
+    entry('½', "vulgar fraction one half", "Math"),
+    entry('¼', "vulgar fraction one quarter", "Math"),
+    entry('¾', "vulgar fraction three quarters", "Math"),
+    entry('ℕ', "double-struck capital n", "Math"),
+    entry('ℤ', "double-struck capital z", "Math"),
+    entry('ℚ', "double-struck capital q", "Math"),
+    entry('ℝ', "double-struck capital r", "Math"),
+    entry('ℂ', "double-struck capital c", "Math"),
+    entry('⊤', "down tack", "Math"),
+    entry('⊥', "up tack", "Math"),
+    entry('⊢', "right tack", "Math"),
+    entry('⌈', "left ceiling", "Math"),
+    entry('⌉', "right ceiling", "Math"),
+    entry('⌊', "left floor", "Math"),
+    entry('⌋', "right floor", "Math"),
+    entry('⟨', "mathematical left angle bracket", "Math"),
+    entry('⟩', "mathematical right angle bracket", "Math"),
+    // Greek
+    entry('α', "greek small letter alpha", "Greek"),
+    entry('β', "greek small letter beta", "Greek"),
+    entry('γ', "greek small letter gamma", "Greek"),
+    entry('δ', "greek small letter delta", "Greek"),
+    entry('ε', "greek small letter epsilon", "Greek"),
+    entry('ζ', "greek small letter zeta", "Greek"),
+    entry('η', "greek small letter eta", "Greek"),
+    entry('θ', "greek small letter theta", "Greek"),
+    entry('ι', "greek small letter iota", "Greek"),
+    entry('κ', "greek small letter kappa", "Greek"),
+    entry('λ', "greek small letter lamda", "Greek"),
+    entry('μ', "greek small letter mu", "Greek"),
+    entry('ν', "greek small letter nu", "Greek"),
+    entry('ξ', "greek small letter xi", "Greek"),
+    entry('π', "greek small letter pi", "Greek"),
+    entry('ρ', "greek small letter rho", "Greek"),
+    entry('σ', "greek small letter sigma", "Greek"),
+    entry('τ', "greek small letter tau", "Greek"),
+    entry('υ', "greek small letter upsilon", "Greek"),
+    entry('φ', "greek small letter phi", "Greek"),
+    entry('χ', "greek small letter chi", "Greek"),
+    entry('ψ', "greek small letter psi", "Greek"),
+    entry('ω', "greek small letter omega", "Greek"),
+    entry('Γ', "greek capital letter gamma", "Greek"),
+    entry('Δ', "greek capital letter delta", "Greek"),
+    entry('Θ', "greek capital letter theta", "Greek"),
+    entry('Λ', "greek capital letter lamda", "Greek"),
+    entry('Π', "greek capital letter pi", "Greek"),
+    entry('Σ', "greek capital letter sigma", "Greek"),
+    entry('Φ', "greek capital letter phi", "Greek"),
+    entry('Ψ', "greek capital letter psi", "Greek"),
+    entry('Ω', "greek capital letter omega", "Greek"),
+    // Currency
+    entry('€', "euro sign", "Currency"),
+    entry('£', "pound sign", "Currency"),
+    entry('¥', "yen sign", "Currency"),
+    entry('¢', "cent sign", "Currency"),
+    entry('₹', "indian rupee sign", "Currency"),
+    entry('₽', "ruble sign", "Currency"),
+    entry('₩', "won sign", "Currency"),
+    entry('₿', "bitcoin sign", "Currency"),
+    entry('₴', "hryvnia sign", "Currency"),
+    entry('₺', "turkish lira sign", "Currency"),
+    // Punctuation
+    entry('–', "en dash", "Punctuation"),
+    entry('—', "em dash", "Punctuation"),
+    entry('…', "horizontal ellipsis", "Punctuation"),
+    entry('‘', "left single quotation mark", "Punctuation"),
+    entry('’', "right single quotation mark", "Punctuation"),
+    entry('“', "left double quotation mark", "Punctuation"),
+    entry('”', "right double quotation mark", "Punctuation"),
+    entry('„', "double low-9 quotation mark", "Punctuation"),
+    entry(
+        '«',
+        "left-pointing double angle quotation mark",
+        "Punctuation",
+    ),
+    entry(
+        '»',
+        "right-pointing double angle quotation mark",
+        "Punctuation",
+    ),
+    entry(
+        '‹',
+        "single left-pointing angle quotation mark",
+        "Punctuation",
+    ),
+    entry(
+        '›',
+        "single right-pointing angle quotation mark",
+        "Punctuation",
+    ),
+    entry('¡', "inverted exclamation mark", "Punctuation"),
+    entry('¿', "inverted question mark", "Punctuation"),
+    entry('•', "bullet", "Punctuation"),
+    entry('†', "dagger", "Punctuation"),
+    entry('‡', "double dagger", "Punctuation"),
+    entry('§', "section sign", "Punctuation"),
+    entry('¶', "pilcrow sign", "Punctuation"),
+    entry('\u{00A0}', "no-break space", "Punctuation"),
+    entry('\u{200B}', "zero width space", "Punctuation"),
+    // Symbols
+    entry('©', "copyright sign", "Symbols"),
+    entry('®', "registered sign", "Symbols"),
+    entry('™', "trade mark sign", "Symbols"),
+    entry('✓', "check mark", "Symbols"),
+    entry('✔', "heavy check mark", "Symbols"),
+    entry('✗', "ballot x", "Symbols"),
+    entry('✘', "heavy ballot x", "Symbols"),
+    entry('★', "black star", "Symbols"),
+    entry('☆', "white star", "Symbols"),
+    entry('♠', "black spade suit", "Symbols"),
+    entry('♣', "black club suit", "Symbols"),
+    entry('♥', "black heart suit", "Symbols"),
+    entry('♦', "black diamond suit", "Symbols"),
+    entry('♪', "eighth note", "Symbols"),
+    entry('♫', "beamed eighth notes", "Symbols"),
+    entry('☐', "ballot box", "Symbols"),
+    entry('☑', "ballot box with check", "Symbols"),
+    entry('☒', "ballot box with x", "Symbols"),
+    entry('⌘', "place of interest sign", "Symbols"),
+    entry('⌥', "option key", "Symbols"),
+    entry('⌫', "erase to the left", "Symbols"),
+    entry('⏎', "return symbol", "Symbols"),
+    entry('⎋', "broken circle with northwest arrow", "Symbols"),
+    entry('●', "black circle", "Symbols"),
+    entry('○', "white circle", "Symbols"),
+    entry('■', "black square", "Symbols"),
+    entry('□', "white square", "Symbols"),
+    entry('▲', "black up-pointing triangle", "Symbols"),
+    entry('▶', "black right-pointing triangle", "Symbols"),
+    entry('▼', "black down-pointing triangle", "Symbols"),
+    entry('◀', "black left-pointing triangle", "Symbols"),
+    entry('◆', "black diamond", "Symbols"),
+    entry('◇', "white diamond", "Symbols"),
+    // Box drawing
+    entry('─', "box drawings light horizontal", "Box Drawing"),
+    entry('│', "box drawings light vertical", "Box Drawing"),
+    entry('┌', "box drawings light down and right", "Box Drawing"),
+    entry('┐', "box drawings light down and left", "Box Drawing"),
+    entry('└', "box drawings light up and right", "Box Drawing"),
+    entry('┘', "box drawings light up and left", "Box Drawing"),
+    entry('├', "box drawings light vertical and right", "Box Drawing"),
+    entry('┤', "box drawings light vertical and left", "Box Drawing"),
+    entry('┬', "box drawings light down and horizontal", "Box Drawing"),
+    entry('┴', "box drawings light up and horizontal", "Box Drawing"),
+    entry(
+        '┼',
+        "box drawings light vertical and horizontal",
+        "Box Drawing",
+    ),
+    entry('═', "box drawings double horizontal", "Box Drawing"),
+    entry('║', "box drawings double vertical", "Box Drawing"),
+    entry('╔', "box drawings double down and right", "Box Drawing"),
+    entry('╗', "box drawings double down and left", "Box Drawing"),
+    entry('╚', "box drawings double up and right", "Box Drawing"),
+    entry('╝', "box drawings double up and left", "Box Drawing"),
+    entry('░', "light shade", "Box Drawing"),
+    entry('▒', "medium shade", "Box Drawing"),
+    entry('▓', "dark shade", "Box Drawing"),
+    entry('█', "full block", "Box Drawing"),
+    // Smileys & Emotion
+    entry('😀', "grinning face", "Smileys & Emotion"),
+    entry('😃', "grinning face with big eyes", "Smileys & Emotion"),
+    entry('😄', "grinning face with smiling eyes", "Smileys & Emotion"),
+    entry('😁', "beaming face with smiling eyes", "Smileys & Emotion"),
+    entry('😆', "grinning squinting face", "Smileys & Emotion"),
+    entry('😅', "grinning face with sweat", "Smileys & Emotion"),
+    entry('😂', "face with tears of joy", "Smileys & Emotion"),
+    entry('🙂', "slightly smiling face", "Smileys & Emotion"),
+    entry('😉', "winking face", "Smileys & Emotion"),
+    entry('😊', "smiling face with smiling eyes", "Smileys & Emotion"),
+    entry('😍', "smiling face with heart-eyes", "Smileys & Emotion"),
+    entry('😘', "face blowing a kiss", "Smileys & Emotion"),
+    entry('😎', "smiling face with sunglasses", "Smileys & Emotion"),
+    entry('🤔', "thinking face", "Smileys & Emotion"),
+    entry('😐', "neutral face", "Smileys & Emotion"),
+    entry('🙄', "face with rolling eyes", "Smileys & Emotion"),
+    entry('😴', "sleeping face", "Smileys & Emotion"),
+    entry('😢', "crying face", "Smileys & Emotion"),
+    entry('😭', "loudly crying face", "Smileys & Emotion"),
+    entry('😡', "enraged face", "Smileys & Emotion"),
+    entry('😱', "face screaming in fear", "Smileys & Emotion"),
+    entry('🤯', "exploding head", "Smileys & Emotion"),
+    entry('🥳', "partying face", "Smileys & Emotion"),
+    entry('🤖', "robot", "Smileys & Emotion"),
+    entry('👻', "ghost", "Smileys & Emotion"),
+    entry('💀', "skull", "Smileys & Emotion"),
+    entry('💩', "pile of poo", "Smileys & Emotion"),
+    entry('💯', "hundred points", "Smileys & Emotion"),
+    entry('💔', "broken heart", "Smileys & Emotion"),
+    entry('💜', "purple heart", "Smileys & Emotion"),
+    entry('💙', "blue heart", "Smileys & Emotion"),
+    entry('💚', "green heart", "Smileys & Emotion"),
+    // People & Body
+    entry('👍', "thumbs up", "People & Body"),
+    entry('👎', "thumbs down", "People & Body"),
+    entry('👌', "ok hand", "People & Body"),
+    entry('👏', "clapping hands", "People & Body"),
+    entry('🙌', "raising hands", "People & Body"),
+    entry('🙏', "folded hands", "People & Body"),
+    entry('👋', "waving hand", "People & Body"),
+    entry('✌', "victory hand", "People & Body"),
+    entry('🤞', "crossed fingers", "People & Body"),
+    entry('💪', "flexed biceps", "People & Body"),
+    entry('👀', "eyes", "People & Body"),
+    entry('🧠', "brain", "People & Body"),
+    entry('🤷', "person shrugging", "People & Body"),
+    entry('🤦', "person facepalming", "People & Body"),
+    // Animals & Nature
+    entry('🐶', "dog face", "Animals & Nature"),
+    entry('🐱', "cat face", "Animals & Nature"),
+    entry('🦊', "fox", "Animals & Nature"),
+    entry('🐻', "bear", "Animals & Nature"),
+    entry('🐼', "panda", "Animals & Nature"),
+    entry('🐧', "penguin", "Animals & Nature"),
+    entry('🐍', "snake", "Animals & Nature"),
+    entry('🦀', "crab", "Animals & Nature"),
+    entry('🐛', "bug", "Animals & Nature"),
+    entry('🐢', "turtle", "Animals & Nature"),
+    entry('🦄', "unicorn", "Animals & Nature"),
+    entry('🌱', "seedling", "Animals & Nature"),
+    entry('🌲', "evergreen tree", "Animals & Nature"),
+    entry('🌵', "cactus", "Animals & Nature"),
+    entry('🍀', "four leaf clover", "Animals & Nature"),
+    entry('🌸', "cherry blossom", "Animals & Nature"),
+    entry('🌍', "globe showing europe-africa", "Animals & Nature"),
+    entry('🌙', "crescent moon", "Animals & Nature"),
+    entry('☀', "sun", "Animals & Nature"),
+    entry('⭐', "star", "Animals & Nature"),
+    entry('🌈', "rainbow", "Animals & Nature"),
+    entry('⚡', "high voltage", "Animals & Nature"),
+    entry('❄', "snowflake", "Animals & Nature"),
+    entry('🔥', "fire", "Animals & Nature"),
+    entry('💧', "droplet", "Animals & Nature"),
+    // Food & Drink
+    entry('🍎', "red apple", "Food & Drink"),
+    entry('🍋', "lemon", "Food & Drink"),
+    entry('🍌', "banana", "Food & Drink"),
+    entry('🍓', "strawberry", "Food & Drink"),
+    entry('🥑', "avocado", "Food & Drink"),
+    entry('🌶', "hot pepper", "Food & Drink"),
+    entry('🍞', "bread", "Food & Drink"),
+    entry('🧀', "cheese wedge", "Food & Drink"),
+    entry('🍕', "pizza", "Food & Drink"),
+    entry('🍔', "hamburger", "Food & Drink"),
+    entry('🌮', "taco", "Food & Drink"),
+    entry('🍣', "sushi", "Food & Drink"),
+    entry('🍩', "doughnut", "Food & Drink"),
+    entry('🍰', "shortcake", "Food & Drink"),
+    entry('🍪', "cookie", "Food & Drink"),
+    entry('☕', "hot beverage", "Food & Drink"),
+    entry('🍵', "teacup without handle", "Food & Drink"),
+    entry('🍺', "beer mug", "Food & Drink"),
+    entry('🍷', "wine glass", "Food & Drink"),
+    // Travel & Places
+    entry('🚀', "rocket", "Travel & Places"),
+    entry('✈', "airplane", "Travel & Places"),
+    entry('🚗', "automobile", "Travel & Places"),
+    entry('🚲', "bicycle", "Travel & Places"),
+    entry('🚂', "locomotive", "Travel & Places"),
+    entry('🚢', "ship", "Travel & Places"),
+    entry('🏠', "house", "Travel & Places"),
+    entry('🏢', "office building", "Travel & Places"),
+    entry('🏔', "snow-capped mountain", "Travel & Places"),
+    entry('🏖', "beach with umbrella", "Travel & Places"),
+    entry('🗺', "world map", "Travel & Places"),
+    entry('🚧', "construction", "Travel & Places"),
+    entry('⏰', "alarm clock", "Travel & Places"),
+    entry('⌛', "hourglass done", "Travel & Places"),
+    // Activities
+    entry('🎉', "party popper", "Activities"),
+    entry('🎊', "confetti ball", "Activities"),
+    entry('🎁', "wrapped gift", "Activities"),
+    entry('🎈', "balloon", "Activities"),
+    entry('🏆', "trophy", "Activities"),
+    entry('🥇', "1st place medal", "Activities"),
+    entry('⚽', "soccer ball", "Activities"),
+    entry('🏀', "basketball", "Activities"),
+    entry('🎮', "video game", "Activities"),
+    entry('🎲', "game die", "Activities"),
+    entry('🎯', "bullseye", "Activities"),
+    entry('🎨', "artist palette", "Activities"),
+    entry('🎵', "musical note", "Activities"),
+    entry('🎸', "guitar", "Activities"),
+    // Objects
+    entry('💡', "light bulb", "Objects"),
+    entry('🔧', "wrench", "Objects"),
+    entry('🔨', "hammer", "Objects"),
+    entry('⚙', "gear", "Objects"),
+    entry('🔒', "locked", "Objects"),
+    entry('🔓', "unlocked", "Objects"),
+    entry('🔑', "key", "Objects"),
+    entry('🔍', "magnifying glass tilted left", "Objects"),
+    entry('🔗', "link", "Objects"),
+    entry('📌', "pushpin", "Objects"),
+    entry('📎', "paperclip", "Objects"),
+    entry('📝', "memo", "Objects"),
+    entry('📁', "file folder", "Objects"),
+    entry('📄', "page facing up", "Objects"),
+    entry('📦', "package", "Objects"),
+    entry('📅', "calendar", "Objects"),
+    entry('📈', "chart increasing", "Objects"),
+    entry('📉', "chart decreasing", "Objects"),
+    entry('📊', "bar chart", "Objects"),
+    entry('💻', "laptop", "Objects"),
+    entry('🖥', "desktop computer", "Objects"),
+    entry('⌨', "keyboard", "Objects"),
+    entry('📱', "mobile phone", "Objects"),
+    entry('🔋', "battery", "Objects"),
+    entry('🔔', "bell", "Objects"),
+    entry('📣', "megaphone", "Objects"),
+    entry('✉', "envelope", "Objects"),
+    entry('📚', "books", "Objects"),
+    entry('🧪', "test tube", "Objects"),
+    entry('💣', "bomb", "Objects"),
+    entry('🗑', "wastebasket", "Objects"),
+    // Status symbols
+    entry('✅', "check mark button", "Status"),
+    entry('❌', "cross mark", "Status"),
+    entry('❎', "cross mark button", "Status"),
+    entry('⚠', "warning sign", "Status"),
+    entry('⛔', "no entry", "Status"),
+    entry('🚫', "prohibited", "Status"),
+    entry('❓', "red question mark", "Status"),
+    entry('❗', "red exclamation mark", "Status"),
+    entry('ℹ', "information source", "Status"),
+    entry('🔴', "red circle", "Status"),
+    entry('🟠', "orange circle", "Status"),
+    entry('🟡', "yellow circle", "Status"),
+    entry('🟢', "green circle", "Status"),
+    entry('🔵', "blue circle", "Status"),
+    entry('✨', "sparkles", "Status"),
+    entry('🆕', "new button", "Status"),
+    entry('🐞', "lady beetle", "Status"),
+];
+
+/// The table entry for `ch`, if it has one
+pub fn lookup(ch: char) -> Option<&'static UnicodeChar> {
+    UNICODE_CHARS.iter().find(|entry| entry.ch == ch)
+}
+
+/// Parse a codepoint written as `U+2192`, `u+2192` or `0x2192`
+pub fn parse_codepoint(input: &str) -> Option<char> {
+    let input = input.trim();
+    let hex = input
+        .strip_prefix("U+")
+        .or_else(|| input.strip_prefix("u+"))
+        .or_else(|| input.strip_prefix("0x"))
+        .or_else(|| input.strip_prefix("0X"))?;
+    if hex.is_empty() || hex.len() > 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok().and_then(char::from_u32)
+}
+
+/// `U+XXXX` notation for `ch`
+pub fn codepoint_label(ch: char) -> String {
+    format!("U+{:04X}", ch as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_table_has_no_duplicates() {
+        let mut seen = HashSet::new();
+        for entry in UNICODE_CHARS {
+            assert!(seen.insert(entry.ch), "{} is listed twice", entry.name);
+            assert_eq!(entry.name, entry.name.to_lowercase());
+        }
+    }
+
+    #[test]
+    fn test_parse_codepoint() {
+        assert_eq!(parse_codepoint("U+2192"), Some('→'));
+        assert_eq!(parse_codepoint("0x1F680"), Some('🚀'));
+        assert_eq!(parse_codepoint(" u+e9 "), Some('é'));
+        assert_eq!(parse_codepoint("2192"), None);
+        assert_eq!(parse_codepoint("U+D800"), None);
+        assert_eq!(parse_codepoint("U+"), None);
+        assert_eq!(codepoint_label('→'), "U+2192");
+        assert_eq!(lookup('→').map(|e| e.name), Some("rightwards arrow"));
+    }
+}
@@ -423,6 +423,223 @@ pub fn find_word_start_right(buffer: &Buffer, pos: usize) -> usize {
     start + current_idx
 }
 
+// ============================================================================
+// Subword navigation (camelCase / snake_case boundaries)
+// ============================================================================
+//
+// Subword motions treat identifiers as sequences of parts: `fooBarBaz` is
+// `foo`, `Bar`, `Baz`, `HTTPServer` is `HTTP`, `Server`, and in `foo_bar`
+// underscores separate `foo` and `bar`. Whitespace and punctuation behave as
+// in the word motions above.
+
+/// Check if a grapheme separates snake_case parts
+fn is_subword_separator(g: &str) -> bool {
+    g == "_"
+}
+
+/// Check if a Word grapheme can be part of a subword (i.e. it is not an underscore)
+fn is_subword_grapheme(g: &str) -> bool {
+    get_grapheme_class(g) == CharClass::Word && !is_subword_separator(g)
+}
+
+/// Check if a new subword starts at `cur`, given the grapheme before it and
+/// the one after it (if any).
+///
+/// - `fooBar`: lowercase or digit followed by uppercase
+/// - `HTTPServer`: the last uppercase letter of an acronym followed by lowercase
+fn is_subword_boundary(prev: &str, cur: &str, next: Option<&str>) -> bool {
+    let (Some(p), Some(c)) = (prev.chars().next(), cur.chars().next()) else {
+        return false;
+    };
+    if (p.is_lowercase() || p.is_numeric()) && c.is_uppercase() {
+        return true;
+    }
+    p.is_uppercase()
+        && c.is_uppercase()
+        && next
+            .and_then(|n| n.chars().next())
+            .is_some_and(|n| n.is_lowercase())
+}
+
+/// Grapheme at `idx` in `text`, if any
+fn grapheme_at(text: &str, idx: usize) -> Option<&str> {
+    (idx < text.len()).then(|| &text[idx..next_grapheme_boundary(text, idx)])
+}
+
+/// Advance `idx` while graphemes match `pred`
+fn skip_right(text: &str, mut idx: usize, pred: impl Fn(&str) -> bool) -> usize {
+    while let Some(g) = grapheme_at(text, idx) {
+        if !pred(g) {
+            break;
+        }
+        idx += g.len();
+    }
+    idx
+}
+
+/// Move `idx` back while graphemes match `pred`
+fn skip_left(text: &str, mut idx: usize, pred: impl Fn(&str) -> bool) -> usize {
+    while idx > 0 {
+        let prev = prev_grapheme_boundary(text, idx);
+        if !pred(&text[prev..idx]) {
+            break;
+        }
+        idx = prev;
+    }
+    idx
+}
+
+/// Move past the subword starting at `idx`
+fn subword_end(text: &str, mut idx: usize) -> usize {
+    let Some(first) = grapheme_at(text, idx).filter(|g| is_subword_grapheme(g)) else {
+        return idx;
+    };
+    let mut prev = first;
+    idx += first.len();
+    while let Some(cur) = grapheme_at(text, idx).filter(|g| is_subword_grapheme(g)) {
+        let next = grapheme_at(text, idx + cur.len());
+        if is_subword_boundary(prev, cur, next) {
+            break;
+        }
+        prev = cur;
+        idx += cur.len();
+    }
+    idx
+}
+
+/// Move back to the start of the subword ending at `idx`
+fn subword_start(text: &str, mut idx: usize) -> usize {
+    let mut consumed = false;
+    while idx > 0 {
+        let prev_idx = prev_grapheme_boundary(text, idx);
+        let prev = &text[prev_idx..idx];
+        if !is_subword_grapheme(prev) {
+            break;
+        }
+        if consumed {
+            let cur = grapheme_at(text, idx).unwrap_or_default();
+            let next = grapheme_at(text, idx + cur.len());
+            if is_subword_boundary(prev, cur, next) {
+                break;
+            }
+        }
+        consumed = true;
+        idx = prev_idx;
+    }
+    idx
+}
+
+/// Find the start of the subword to the left of the given position
+///
+/// Like `find_word_start_left`, but stops at camelCase and snake_case
+/// boundaries inside identifiers.
+pub fn find_subword_start_left(buffer: &Buffer, pos: usize) -> usize {
+    if pos == 0 {
+        return 0;
+    }
+
+    let actual_pos = pos.min(buffer.len());
+    let start = actual_pos.saturating_sub(1000);
+    let bytes = buffer.slice_bytes(start..actual_pos);
+    let text = String::from_utf8_lossy(&bytes);
+
+    // Skip whitespace and trailing underscores to the left
+    let mut idx = skip_left(&text, text.len(), |g| {
+        get_grapheme_class(g) == CharClass::Whitespace
+    });
+    let after_separators = skip_left(&text, idx, is_subword_separator);
+
+    idx = if after_separators > 0
+        && is_subword_grapheme(
+            &text[prev_grapheme_boundary(&text, after_separators)..after_separators],
+        ) {
+        subword_start(&text, after_separators)
+    } else if after_separators < idx {
+        // A run of underscores with no identifier before it
+        after_separators
+    } else if idx > 0 {
+        let target_class = get_grapheme_class(&text[prev_grapheme_boundary(&text, idx)..idx]);
+        skip_left(&text, idx, |g| get_grapheme_class(g) == target_class)
+    } else {
+        idx
+    };
+
+    actual_pos.saturating_sub(text.len() - idx)
+}
+
+/// Find the end of the subword at or after the given position
+///
+/// Like `find_word_end_right`, but stops at camelCase and snake_case
+/// boundaries inside identifiers.
+pub fn find_subword_end_right(buffer: &Buffer, pos: usize) -> usize {
+    let buf_len = buffer.len();
+    if pos >= buf_len {
+        return buf_len;
+    }
+
+    let end = (pos + 1000).min(buf_len);
+    let bytes = buffer.slice_bytes(pos..end);
+    let text = String::from_utf8_lossy(&bytes);
+
+    let mut idx = skip_right(&text, 0, |g| get_grapheme_class(g) == CharClass::Whitespace);
+    let Some(g) = grapheme_at(&text, idx) else {
+        return pos + idx;
+    };
+
+    idx = match get_grapheme_class(g) {
+        CharClass::Word => {
+            let after_separators = skip_right(&text, idx, is_subword_separator);
+            match grapheme_at(&text, after_separators) {
+                Some(g) if is_subword_grapheme(g) => subword_end(&text, after_separators),
+                _ => after_separators,
+            }
+        }
+        class => skip_right(&text, idx, |g| get_grapheme_class(g) == class),
+    };
+
+    pos + idx
+}
+
+/// Find the start of the subword to the right of the given position
+///
+/// Like `find_word_start_right`, but stops at camelCase and snake_case
+/// boundaries inside identifiers.
+pub fn find_subword_start_right(buffer: &Buffer, pos: usize) -> usize {
+    let buf_len = buffer.len();
+    if pos >= buf_len {
+        return buf_len;
+    }
+
+    let end = (pos + 1000).min(buf_len);
+    let bytes = buffer.slice_bytes(pos..end);
+    let text = String::from_utf8_lossy(&bytes);
+
+    let Some(g) = grapheme_at(&text, 0) else {
+        return pos;
+    };
+
+    let idx = match get_grapheme_class(g) {
+        // Starting on whitespace: just consume it and stop
+        CharClass::Whitespace => {
+            return pos + skip_right(&text, 0, |g| get_grapheme_class(g) == CharClass::Whitespace);
+        }
+        CharClass::Word if is_subword_separator(g) => skip_right(&text, 0, is_subword_separator),
+        CharClass::Word => {
+            // Consume the subword and any underscores after it
+            let idx = subword_end(&text, 0);
+            skip_right(&text, idx, is_subword_separator)
+        }
+        CharClass::Punctuation => skip_right(&text, 0, |g| {
+            get_grapheme_class(g) == CharClass::Punctuation
+        }),
+    };
+
+    // Land at the start of the next token
+    pos + skip_right(&text, idx, |g| {
+        get_grapheme_class(g) == CharClass::Whitespace
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -474,6 +691,44 @@ mod tests {
     // Tests for byte-level word navigation (shared by Buffer and String)
     // ========================================================================
 
+    #[test]
+    fn test_find_subword_start_left() {
+        let buffer = Buffer::from_str_test("let fooBarBaz = HTTPServer_v2;");
+        assert_eq!(find_subword_start_left(&buffer, 13), 10); // "Baz"
+        assert_eq!(find_subword_start_left(&buffer, 10), 7); // "Bar"
+        assert_eq!(find_subword_start_left(&buffer, 7), 4); // "foo"
+        assert_eq!(find_subword_start_left(&buffer, 4), 0); // "let"
+        assert_eq!(find_subword_start_left(&buffer, 29), 27); // "v2"
+        assert_eq!(find_subword_start_left(&buffer, 27), 20); // "Server", skipping "_"
+        assert_eq!(find_subword_start_left(&buffer, 20), 16); // "HTTP"
+        assert_eq!(find_subword_start_left(&buffer, 16), 14); // "="
+    }
+
+    #[test]
+    fn test_find_subword_start_right() {
+        let buffer = Buffer::from_str_test("fooBarBaz snake_case_name HTTPServer");
+        assert_eq!(find_subword_start_right(&buffer, 0), 3); // "Bar"
+        assert_eq!(find_subword_start_right(&buffer, 3), 6); // "Baz"
+        assert_eq!(find_subword_start_right(&buffer, 6), 10); // "snake"
+        assert_eq!(find_subword_start_right(&buffer, 10), 16); // "case"
+        assert_eq!(find_subword_start_right(&buffer, 16), 21); // "name"
+        assert_eq!(find_subword_start_right(&buffer, 21), 26); // "HTTP"
+        assert_eq!(find_subword_start_right(&buffer, 26), 30); // "Server"
+        assert_eq!(find_subword_start_right(&buffer, 30), 36); // end
+    }
+
+    #[test]
+    fn test_find_subword_end_right() {
+        let buffer = Buffer::from_str_test("getHTTPResponse __init__ a.b");
+        assert_eq!(find_subword_end_right(&buffer, 0), 3); // "get"
+        assert_eq!(find_subword_end_right(&buffer, 3), 7); // "HTTP"
+        assert_eq!(find_subword_end_right(&buffer, 7), 15); // "Response"
+        assert_eq!(find_subword_end_right(&buffer, 15), 22); // "init" after "__"
+        assert_eq!(find_subword_end_right(&buffer, 22), 24); // trailing "__"
+        assert_eq!(find_subword_end_right(&buffer, 24), 26); // "a"
+        assert_eq!(find_subword_end_right(&buffer, 26), 27); // "."
+    }
+
     #[test]
     fn test_find_word_start_bytes_basic() {
         let s = "hello world test";
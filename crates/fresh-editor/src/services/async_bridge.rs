@@ -139,7 +139,7 @@ pub enum AsyncMessage {
     /// This is a rust-analyzer specific notification (experimental/serverStatus)
     LspServerQuiescent { language: String },
 
-    /// File changed externally (future: file watching)
+    /// File changed externally (reported by a `FileSystem::watch`)
     FileChanged { path: String },
 
     /// Git status updated (future: git integration)
@@ -164,6 +164,44 @@ pub enum AsyncMessage {
     /// File open dialog: directory listing completed
     FileOpenDirectoryLoaded(std::io::Result<Vec<crate::services::fs::DirEntry>>),
 
+    /// Native (OS) file dialog closed
+    NativeFileDialogClosed {
+        kind: crate::services::file_dialog::FileDialogKind,
+        buffer_id: crate::model::event::BufferId,
+        result: Result<
+            Option<crate::services::file_dialog::FileDialogSelection>,
+            crate::services::file_dialog::FileDialogError,
+        >,
+    },
+
+    /// Project search: matches from one file (or a progress update with none)
+    ProjectSearchResults {
+        search_id: u64,
+        matches: Vec<crate::services::project_search::ProjectSearchMatch>,
+        files_searched: usize,
+    },
+
+    /// Project search finished or was cancelled
+    ProjectSearchFinished {
+        search_id: u64,
+        summary: crate::services::project_search::ProjectSearchSummary,
+    },
+
+    /// Prompt filter: `(index, score)` of the matches in one batch of candidates
+    PromptFilterResults {
+        filter_id: u64,
+        matches: Vec<(usize, i32)>,
+    },
+
+    /// Prompt filter scored every candidate
+    PromptFilterFinished { filter_id: u64 },
+
+    /// Request from an external tool over the local RPC socket
+    RpcRequest {
+        method: crate::services::rpc::RpcMethod,
+        reply: std::sync::mpsc::Sender<Result<Value, String>>,
+    },
+
     /// Terminal output received (triggers redraw)
     TerminalOutput { terminal_id: TerminalId },
 
@@ -219,6 +257,14 @@ pub enum AsyncMessage {
         exit_code: i32,
     },
 
+    /// Plugin HTTP request (`editor.fetch`) completed
+    PluginFetchResponse {
+        /// Callback ID of the pending `fetch` promise
+        callback_id: u64,
+        /// Response, or an error message to reject the promise with
+        result: Result<fresh_core::api::FetchResponse, String>,
+    },
+
     /// LSP server status update (progress, messages, etc.)
     LspStatusUpdate {
         language: String,
@@ -0,0 +1,207 @@
+//! Memory budget for the editor's caches
+//!
+//! Caches that can be rebuilt on demand (per-buffer syntax highlighting, the
+//! quick open file index, clipboard history) report their approximate size
+//! here. When the total goes over `editor.cache_budget_mb`, the least recently
+//! used caches are dropped until it fits again. Sizes are estimates based on
+//! element counts and string lengths, not allocator statistics.
+
+use crate::model::event::BufferId;
+use std::collections::HashMap;
+
+pub use crate::config::DEFAULT_CACHE_BUDGET_MB;
+
+/// A cache accounted for by the budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheKey {
+    /// Highlighted spans of one buffer
+    Highlight(BufferId),
+    /// File list used by quick open
+    FileIndex,
+    /// Unpinned clipboard history entries
+    ClipboardHistory,
+}
+
+impl CacheKey {
+    /// Stable ordering for ties in size or recency
+    fn rank(self) -> (u8, usize) {
+        match self {
+            Self::Highlight(id) => (0, id.0),
+            Self::FileIndex => (1, 0),
+            Self::ClipboardHistory => (2, 0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct CacheEntry {
+    bytes: usize,
+    last_used: u64,
+}
+
+/// Tracks cache sizes and recency, and picks caches to evict
+#[derive(Debug, Clone)]
+pub struct CacheBudget {
+    /// Budget in bytes (0 = unlimited)
+    limit_bytes: usize,
+    /// Logical clock for LRU ordering
+    clock: u64,
+    entries: HashMap<CacheKey, CacheEntry>,
+}
+
+impl CacheBudget {
+    pub fn new(limit_mb: usize) -> Self {
+        Self {
+            limit_bytes: limit_mb.saturating_mul(1024 * 1024),
+            clock: 0,
+            entries: HashMap::new(),
+        }
+    }
+
+    pub fn set_limit_mb(&mut self, limit_mb: usize) {
+        self.limit_bytes = limit_mb.saturating_mul(1024 * 1024);
+    }
+
+    /// Budget in bytes, or `None` when unlimited
+    pub fn limit_bytes(&self) -> Option<usize> {
+        (self.limit_bytes > 0).then_some(self.limit_bytes)
+    }
+
+    /// Mark a cache as just used
+    pub fn touch(&mut self, key: CacheKey) {
+        self.clock += 1;
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.clock;
+        }
+    }
+
+    /// Record the current size of every cache.
+    ///
+    /// A cache whose size changed since the last report counts as used.
+    /// Caches missing from `usage` or reported empty are forgotten.
+    pub fn record(&mut self, usage: impl IntoIterator<Item = (CacheKey, usize)>) {
+        self.clock += 1;
+        let clock = self.clock;
+        let mut entries = HashMap::new();
+        for (key, bytes) in usage {
+            if bytes == 0 {
+                continue;
+            }
+            let last_used = match self.entries.get(&key) {
+                Some(entry) if entry.bytes == bytes => entry.last_used,
+                _ => clock,
+            };
+            entries.insert(key, CacheEntry { bytes, last_used });
+        }
+        self.entries = entries;
+    }
+
+    /// Forget a cache after it was dropped
+    pub fn evicted(&mut self, key: CacheKey) {
+        self.entries.remove(&key);
+    }
+
+    /// Total size of all recorded caches
+    pub fn total_bytes(&self) -> usize {
+        self.entries.values().map(|e| e.bytes).sum()
+    }
+
+    /// Recorded caches and their sizes, largest first
+    pub fn usage(&self) -> Vec<(CacheKey, usize)> {
+        let mut usage: Vec<_> = self.entries.iter().map(|(k, e)| (*k, e.bytes)).collect();
+        usage.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.rank().cmp(&b.0.rank())));
+        usage
+    }
+
+    /// Caches to drop, least recently used first, for the total to fit the budget
+    pub fn evictions(&self) -> Vec<CacheKey> {
+        let Some(limit) = self.limit_bytes() else {
+            return Vec::new();
+        };
+        let mut total = self.total_bytes();
+        if total <= limit {
+            return Vec::new();
+        }
+
+        let mut by_age: Vec<_> = self.entries.iter().collect();
+        by_age.sort_by_key(|(key, entry)| (entry.last_used, key.rank()));
+        let mut evict = Vec::new();
+        for (key, entry) in by_age {
+            if total <= limit {
+                break;
+            }
+            total -= entry.bytes;
+            evict.push(*key);
+        }
+        evict
+    }
+}
+
+/// Human-readable size (e.g. "1.5 MiB")
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: usize = 1024 * 1024;
+
+    #[test]
+    fn test_evicts_least_recently_used_until_within_budget() {
+        let mut budget = CacheBudget::new(3);
+        budget.record([
+            (CacheKey::Highlight(BufferId(1)), MB),
+            (CacheKey::FileIndex, 2 * MB),
+            (CacheKey::ClipboardHistory, MB),
+        ]);
+        budget.touch(CacheKey::Highlight(BufferId(1)));
+        budget.touch(CacheKey::FileIndex);
+        assert_eq!(budget.total_bytes(), 4 * MB);
+
+        // The clipboard history was used least recently and freeing it is enough
+        assert_eq!(budget.evictions(), vec![CacheKey::ClipboardHistory]);
+
+        // A size change counts as use, so the file index is now the newest
+        budget.record([
+            (CacheKey::Highlight(BufferId(1)), MB),
+            (CacheKey::FileIndex, 3 * MB),
+            (CacheKey::ClipboardHistory, MB),
+        ]);
+        assert_eq!(
+            budget.evictions(),
+            vec![CacheKey::ClipboardHistory, CacheKey::Highlight(BufferId(1))]
+        );
+    }
+
+    #[test]
+    fn test_record_forgets_missing_caches_and_zero_budget_is_unlimited() {
+        let mut budget = CacheBudget::new(0);
+        budget.record([(CacheKey::Highlight(BufferId(1)), 10 * MB)]);
+        assert!(budget.evictions().is_empty());
+
+        budget.record([(CacheKey::FileIndex, 5), (CacheKey::ClipboardHistory, 0)]);
+        assert_eq!(budget.usage(), vec![(CacheKey::FileIndex, 5)]);
+        budget.evicted(CacheKey::FileIndex);
+        assert_eq!(budget.total_bytes(), 0);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * MB), "3.0 MiB");
+    }
+}
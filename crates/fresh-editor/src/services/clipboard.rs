@@ -6,23 +6,70 @@
 //! - Uses arboard crate for reading from system clipboard
 //! - Supports copying HTML-formatted text for rich text editors
 //! - Gracefully falls back to internal clipboard if system clipboard is unavailable
+//! - Keeps a history of recently copied values, with pinning
 
 use crossterm::clipboard::CopyToClipboard;
 use crossterm::execute;
 use std::io::{stdout, Write};
 use std::sync::Mutex;
 
+pub use crate::config::DEFAULT_CLIPBOARD_HISTORY_SIZE;
+
 /// Global clipboard holder to maintain X11 clipboard ownership for the application lifetime.
 /// On X11, the clipboard owner must stay alive to respond to paste requests from other apps.
 static SYSTEM_CLIPBOARD: Mutex<Option<arboard::Clipboard>> = Mutex::new(None);
 
+/// Maximum number of characters shown in a history entry preview
+const PREVIEW_MAX_CHARS: usize = 60;
+
+/// A value in the clipboard history
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClipboardHistoryEntry {
+    pub text: String,
+    /// Pinned entries are never evicted or cleared
+    pub pinned: bool,
+}
+
+impl ClipboardHistoryEntry {
+    /// One-line preview: the first non-blank line, trimmed and truncated
+    pub fn preview(&self) -> String {
+        let line = self
+            .text
+            .lines()
+            .map(str::trim)
+            .find(|l| !l.is_empty())
+            .unwrap_or("");
+        if line.chars().count() > PREVIEW_MAX_CHARS {
+            let truncated: String = line.chars().take(PREVIEW_MAX_CHARS - 1).collect();
+            format!("{}…", truncated)
+        } else {
+            line.to_string()
+        }
+    }
+
+    /// Number of lines in the entry
+    pub fn line_count(&self) -> usize {
+        self.text.lines().count().max(1)
+    }
+}
+
 /// Clipboard manager that handles both internal and system clipboard
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct Clipboard {
     /// Internal clipboard content (always available)
     internal: String,
     /// When true, paste() uses internal clipboard only (for testing)
     internal_only: bool,
+    /// Recently copied values, most recent first
+    history: Vec<ClipboardHistoryEntry>,
+    /// Maximum number of unpinned history entries (0 disables recording)
+    history_size: usize,
+}
+
+impl Default for Clipboard {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Clipboard {
@@ -31,6 +78,8 @@ impl Clipboard {
         Self {
             internal: String::new(),
             internal_only: false,
+            history: Vec::new(),
+            history_size: DEFAULT_CLIPBOARD_HISTORY_SIZE,
         }
     }
 
@@ -47,6 +96,7 @@ impl Clipboard {
     /// Returns true if successful, false otherwise.
     pub fn copy_html(&mut self, html: &str, plain_text: &str) -> bool {
         self.internal = plain_text.to_string();
+        self.record_history(plain_text);
 
         if let Ok(mut guard) = SYSTEM_CLIPBOARD.lock() {
             // Create clipboard if it doesn't exist yet
@@ -84,6 +134,7 @@ impl Clipboard {
     /// 2. arboard crate (works via X11/Wayland APIs in Gnome Console, XFCE Terminal, etc.)
     pub fn copy(&mut self, text: String) {
         self.internal = text.clone();
+        self.record_history(&text);
 
         // Try OSC 52 first (works in modern terminals)
         // Note: This doesn't "fail" in a detectable way - it just sends escape sequences
@@ -210,6 +261,104 @@ impl Clipboard {
 
         true
     }
+
+    /// Recently copied values, most recent first
+    pub fn history(&self) -> &[ClipboardHistoryEntry] {
+        &self.history
+    }
+
+    /// Set the maximum number of unpinned history entries, evicting the oldest
+    /// ones if the history is now too long. 0 stops recording new values.
+    pub fn set_history_size(&mut self, size: usize) {
+        self.history_size = size;
+        self.evict_history();
+    }
+
+    /// Add a value to the front of the history. A value that is already in
+    /// the history is moved to the front and keeps its pin.
+    pub fn record_history(&mut self, text: &str) {
+        if text.is_empty() {
+            return;
+        }
+        let pinned = match self.history.iter().position(|e| e.text == text) {
+            Some(index) => self.history.remove(index).pinned,
+            None if self.history_size == 0 => return,
+            None => false,
+        };
+        self.history.insert(
+            0,
+            ClipboardHistoryEntry {
+                text: text.to_string(),
+                pinned,
+            },
+        );
+        self.evict_history();
+    }
+
+    /// Toggle the pin of the history entry at `index`.
+    /// Returns the new pin state, or None if there is no such entry.
+    pub fn toggle_history_pin(&mut self, index: usize) -> Option<bool> {
+        let entry = self.history.get_mut(index)?;
+        entry.pinned = !entry.pinned;
+        let pinned = entry.pinned;
+        self.evict_history();
+        Some(pinned)
+    }
+
+    /// Remove all unpinned entries from the history
+    pub fn clear_history(&mut self) {
+        self.history.retain(|e| e.pinned);
+    }
+
+    /// Approximate memory held by unpinned history entries, in bytes
+    pub fn history_bytes(&self) -> usize {
+        self.history
+            .iter()
+            .filter(|e| !e.pinned)
+            .map(|e| std::mem::size_of::<ClipboardHistoryEntry>() + e.text.capacity())
+            .sum()
+    }
+
+    /// Record the system clipboard content in the history if it changed since
+    /// it was last recorded. Returns true if a new value was recorded.
+    pub fn poll_system(&mut self) -> bool {
+        if self.internal_only || self.history_size == 0 {
+            return false;
+        }
+        let text = match SYSTEM_CLIPBOARD.lock() {
+            Ok(mut guard) => {
+                if guard.is_none() {
+                    if let Ok(cb) = arboard::Clipboard::new() {
+                        *guard = Some(cb);
+                    }
+                }
+                guard.as_mut().and_then(|cb| cb.get_text().ok())
+            }
+            Err(_) => None,
+        };
+        match text {
+            Some(text)
+                if !text.is_empty() && self.history.first().is_none_or(|e| e.text != text) =>
+            {
+                self.record_history(&text);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Drop the oldest unpinned entries beyond the history size
+    fn evict_history(&mut self) {
+        let mut unpinned = 0;
+        let size = self.history_size;
+        self.history.retain(|e| {
+            if e.pinned {
+                return true;
+            }
+            unpinned += 1;
+            unpinned <= size
+        });
+    }
 }
 
 #[cfg(test)]
@@ -231,4 +380,64 @@ mod tests {
         clipboard.copy("hello".to_string());
         assert_eq!(clipboard.get_internal(), "hello");
     }
+
+    #[test]
+    fn test_history_moves_duplicates_to_front_and_evicts_oldest() {
+        let mut clipboard = Clipboard::new();
+        clipboard.set_history_size(2);
+        clipboard.record_history("a");
+        clipboard.record_history("b");
+        clipboard.record_history("a");
+        let texts: Vec<&str> = clipboard
+            .history()
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["a", "b"]);
+
+        clipboard.record_history("c");
+        let texts: Vec<&str> = clipboard
+            .history()
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["c", "a"]);
+    }
+
+    #[test]
+    fn test_pinned_history_entries_survive_eviction_and_clear() {
+        let mut clipboard = Clipboard::new();
+        clipboard.set_history_size(1);
+        clipboard.record_history("keep");
+        assert_eq!(clipboard.toggle_history_pin(0), Some(true));
+        clipboard.record_history("x");
+        clipboard.record_history("y");
+        let texts: Vec<&str> = clipboard
+            .history()
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect();
+        assert_eq!(texts, vec!["y", "keep"]);
+
+        clipboard.clear_history();
+        assert_eq!(clipboard.history().len(), 1);
+        assert!(clipboard.history()[0].pinned);
+        assert_eq!(clipboard.toggle_history_pin(5), None);
+    }
+
+    #[test]
+    fn test_history_preview() {
+        let entry = ClipboardHistoryEntry {
+            text: "\n   fn main() {\n}\n".to_string(),
+            pinned: false,
+        };
+        assert_eq!(entry.preview(), "fn main() {");
+        assert_eq!(entry.line_count(), 3);
+
+        let long = ClipboardHistoryEntry {
+            text: "x".repeat(100),
+            pinned: false,
+        };
+        assert_eq!(long.preview().chars().count(), PREVIEW_MAX_CHARS);
+    }
 }
@@ -0,0 +1,210 @@
+//! Crash reports
+//!
+//! Installs a panic hook that restores the terminal before anything is printed,
+//! then writes a report to `{data_dir}/crash-reports/` containing the panic
+//! message, a backtrace, the most recent actions, the open files and the loaded
+//! plugins, and prints the report's path.
+//!
+//! The panicking thread can't reach the `Editor`, so the editor records that
+//! context here as it runs (the same approach `signal_handler` uses for the
+//! JavaScript execution state).
+
+use std::collections::{BTreeSet, VecDeque};
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use crate::input::keybindings::Action;
+use crate::services::terminal_modes;
+
+/// Number of recent actions kept for the report
+const MAX_RECENT_ACTIONS: usize = 50;
+
+/// Context recorded while the editor runs
+struct CrashContext {
+    recent_actions: VecDeque<String>,
+    open_files: BTreeSet<PathBuf>,
+    plugins: BTreeSet<String>,
+}
+
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext {
+    recent_actions: VecDeque::new(),
+    open_files: BTreeSet::new(),
+    plugins: BTreeSet::new(),
+});
+
+/// Set once the first report has been written; later panics (e.g. the main
+/// thread re-raising a plugin thread panic) only restore the terminal.
+static REPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Record an executed action.
+///
+/// Only the action's name is kept (`InsertChar`, not `InsertChar('x')`) so
+/// reports never contain typed text.
+pub fn record_action(action: &Action) {
+    let debug = format!("{:?}", action);
+    let name = action_name(&debug);
+    if let Ok(mut ctx) = CONTEXT.lock() {
+        if ctx.recent_actions.len() == MAX_RECENT_ACTIONS {
+            ctx.recent_actions.pop_front();
+        }
+        ctx.recent_actions.push_back(name.to_string());
+    }
+}
+
+/// Record a file opened in a buffer.
+pub fn record_file_opened(path: &Path) {
+    if let Ok(mut ctx) = CONTEXT.lock() {
+        ctx.open_files.insert(path.to_path_buf());
+    }
+}
+
+/// Record a file whose buffer was closed.
+pub fn record_file_closed(path: &Path) {
+    if let Ok(mut ctx) = CONTEXT.lock() {
+        ctx.open_files.remove(path);
+    }
+}
+
+/// Record a loaded plugin.
+pub fn record_plugin_loaded(name: &str) {
+    if let Ok(mut ctx) = CONTEXT.lock() {
+        ctx.plugins.insert(name.to_string());
+    }
+}
+
+/// Record an unloaded plugin.
+pub fn record_plugin_unloaded(name: &str) {
+    if let Ok(mut ctx) = CONTEXT.lock() {
+        ctx.plugins.remove(name);
+    }
+}
+
+/// Install the panic hook.
+///
+/// The terminal is always restored first so the default panic message (and
+/// the report path) end up on a usable terminal. Reports are only written
+/// when `reports_dir` is known.
+pub fn install_panic_hook(reports_dir: Option<PathBuf>) {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic| {
+        terminal_modes::emergency_cleanup();
+        original_hook(panic);
+
+        let Some(dir) = reports_dir.as_deref() else {
+            return;
+        };
+        if REPORTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        match write_report(dir, &panic.to_string()) {
+            Ok(path) => eprintln!("\nCrash report written to {}", path.display()),
+            Err(e) => eprintln!("\nFailed to write crash report to {:?}: {}", dir, e),
+        }
+    }));
+}
+
+/// Write a report for `panic` into `dir`, returning the report's path.
+fn write_report(dir: &Path, panic: &str) -> std::io::Result<PathBuf> {
+    std::fs::create_dir_all(dir)?;
+    let now = chrono::Local::now();
+    let path = dir.join(format!(
+        "crash-{}-{}.txt",
+        now.format("%Y%m%d-%H%M%S"),
+        std::process::id()
+    ));
+
+    let thread = std::thread::current();
+    let backtrace = std::backtrace::Backtrace::force_capture();
+    let report = format_report(
+        &now.to_rfc3339(),
+        thread.name().unwrap_or("<unnamed>"),
+        panic,
+        &backtrace.to_string(),
+    );
+    std::fs::write(&path, report)?;
+    Ok(path)
+}
+
+/// Build the report text from the panic details and the recorded context.
+fn format_report(time: &str, thread: &str, panic: &str, backtrace: &str) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "Fresh crash report");
+    let _ = writeln!(out, "Version: {}", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(out, "Time: {}", time);
+    let _ = writeln!(
+        out,
+        "Platform: {}-{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH
+    );
+    let _ = writeln!(out, "Thread: {}", thread);
+    let _ = writeln!(out, "Panic: {}", panic);
+    let _ = writeln!(out, "\nBacktrace:\n{}", backtrace.trim_end());
+
+    // try_lock: the panic may have happened while this thread held the lock
+    match CONTEXT.try_lock() {
+        Ok(ctx) => {
+            let _ = writeln!(out, "\nRecent actions (oldest first):");
+            write_list(&mut out, ctx.recent_actions.iter());
+            let _ = writeln!(out, "\nOpen files:");
+            write_list(&mut out, ctx.open_files.iter().map(|p| p.display()));
+            let _ = writeln!(out, "\nPlugins:");
+            write_list(&mut out, ctx.plugins.iter());
+        }
+        Err(_) => {
+            let _ = writeln!(out, "\n(editor context unavailable)");
+        }
+    }
+    out
+}
+
+fn write_list<T: std::fmt::Display>(out: &mut String, items: impl Iterator<Item = T>) {
+    let mut empty = true;
+    for item in items {
+        let _ = writeln!(out, "  {}", item);
+        empty = false;
+    }
+    if empty {
+        let _ = writeln!(out, "  (none)");
+    }
+}
+
+/// Strip the payload from an action's `Debug` output.
+fn action_name(debug: &str) -> &str {
+    debug.split(['(', ' ', '{']).next().unwrap_or(debug)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_action_name_strips_payload() {
+        assert_eq!(
+            action_name(&format!("{:?}", Action::InsertChar('x'))),
+            "InsertChar"
+        );
+        assert_eq!(action_name(&format!("{:?}", Action::Save)), "Save");
+    }
+
+    #[test]
+    fn test_report_includes_context() {
+        record_action(&Action::InsertChar('s'));
+        record_file_opened(Path::new("/tmp/crash_report_test.rs"));
+        record_plugin_loaded("crash_report_test_plugin");
+
+        let report = format_report("now", "main", "boom at src/x.rs:1:1", "0: frame");
+
+        assert!(report.contains("Panic: boom at src/x.rs:1:1"));
+        assert!(report.contains("0: frame"));
+        assert!(report.contains("  InsertChar"));
+        assert!(!report.contains("'s'"));
+        assert!(report.contains("/tmp/crash_report_test.rs"));
+        assert!(report.contains("crash_report_test_plugin"));
+
+        record_file_closed(Path::new("/tmp/crash_report_test.rs"));
+        record_plugin_unloaded("crash_report_test_plugin");
+    }
+}
@@ -0,0 +1,229 @@
+//! EditorConfig support for whitespace cleanup on save
+//!
+//! Only `trim_trailing_whitespace` and `insert_final_newline` are read. Files
+//! are looked up from the edited file's directory upwards until one declares
+//! `root = true`. Properties from files nearer to the edited file, and from
+//! later sections within a file, take precedence; `unset` drops a value.
+//!
+//! See <https://editorconfig.org> for the format.
+
+use crate::model::filesystem::FileSystem;
+use std::path::Path;
+
+/// Settings from `.editorconfig` files; `None` means not specified
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EditorConfigSettings {
+    pub trim_trailing_whitespace: Option<bool>,
+    pub insert_final_newline: Option<bool>,
+}
+
+/// Resolve the settings that apply to `path`
+pub fn settings_for(fs: &dyn FileSystem, path: &Path) -> EditorConfigSettings {
+    // Collect config files from the nearest directory upwards
+    let mut files = Vec::new();
+    let mut dir = path.parent();
+    while let Some(d) = dir {
+        if let Ok(bytes) = fs.read_file(&d.join(".editorconfig")) {
+            let file = parse(&String::from_utf8_lossy(&bytes));
+            let is_root = file.root;
+            files.push((d.to_path_buf(), file));
+            if is_root {
+                break;
+            }
+        }
+        dir = d.parent();
+    }
+
+    // Apply the outermost file first so nearer files override it
+    let mut settings = EditorConfigSettings::default();
+    for (dir, file) in files.iter().rev() {
+        let Ok(relative) = path.strip_prefix(dir) else {
+            continue;
+        };
+        let relative = relative.to_string_lossy().replace('\\', "/");
+        for section in &file.sections {
+            if glob_matches(&section.pattern, &relative) {
+                section.apply(&mut settings);
+            }
+        }
+    }
+    settings
+}
+
+#[derive(Debug, Default)]
+struct ConfigFile {
+    root: bool,
+    sections: Vec<Section>,
+}
+
+#[derive(Debug)]
+struct Section {
+    pattern: String,
+    /// Property name and lowercased value, in file order
+    properties: Vec<(String, String)>,
+}
+
+impl Section {
+    fn apply(&self, settings: &mut EditorConfigSettings) {
+        for (key, value) in &self.properties {
+            let slot = match key.as_str() {
+                "trim_trailing_whitespace" => &mut settings.trim_trailing_whitespace,
+                "insert_final_newline" => &mut settings.insert_final_newline,
+                _ => continue,
+            };
+            match value.as_str() {
+                "true" => *slot = Some(true),
+                "false" => *slot = Some(false),
+                "unset" => *slot = None,
+                _ => {}
+            }
+        }
+    }
+}
+
+fn parse(text: &str) -> ConfigFile {
+    let mut file = ConfigFile::default();
+    for line in text.lines() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(pattern) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            file.sections.push(Section {
+                pattern: pattern.to_string(),
+                properties: Vec::new(),
+            });
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_lowercase();
+        let value = value.trim().to_lowercase();
+        match file.sections.last_mut() {
+            Some(section) => section.properties.push((key, value)),
+            None if key == "root" => file.root = value == "true",
+            None => {}
+        }
+    }
+    file
+}
+
+/// Match a section glob against a path relative to the config file's directory
+fn glob_matches(pattern: &str, relative: &str) -> bool {
+    // A pattern without a slash matches the file name at any depth
+    let pattern = match pattern.strip_prefix('/') {
+        Some(anchored) => anchored.to_string(),
+        None if !pattern.contains('/') => format!("**/{}", pattern),
+        None => pattern.to_string(),
+    };
+    regex::Regex::new(&glob_to_regex(&pattern)).is_ok_and(|re| re.is_match(relative))
+}
+
+/// Translate an EditorConfig-style glob into an anchored regex
+pub(crate) fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let chars: Vec<char> = glob.chars().collect();
+    let mut braces = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                // `**/` also matches no directory at all
+                if chars.get(i + 2) == Some(&'/') {
+                    re.push_str("(?:.*/)?");
+                    i += 2;
+                } else {
+                    re.push_str(".*");
+                    i += 1;
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => match chars[i + 1..].iter().position(|&c| c == ']') {
+                Some(len) => {
+                    let class: String = chars[i + 1..i + 1 + len].iter().collect();
+                    let class = match class.strip_prefix('!') {
+                        Some(negated) => format!("^{}", negated),
+                        None => class,
+                    };
+                    re.push('[');
+                    re.push_str(&class.replace('\\', "\\\\"));
+                    re.push(']');
+                    i += len + 1;
+                }
+                None => re.push_str("\\["),
+            },
+            '{' => {
+                braces += 1;
+                re.push_str("(?:");
+            }
+            '}' if braces > 0 => {
+                braces -= 1;
+                re.push(')');
+            }
+            ',' if braces > 0 => re.push('|'),
+            '\\' if i + 1 < chars.len() => {
+                i += 1;
+                re.push_str(&regex::escape(&chars[i].to_string()));
+            }
+            _ => re.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    re.push('$');
+    re
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::filesystem::StdFileSystem;
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("*", "src/main.rs"));
+        assert!(glob_matches("*.md", "docs/guide.md"));
+        assert!(!glob_matches("*.md", "docs/guide.rs"));
+        assert!(glob_matches("*.{js,ts}", "web/app.ts"));
+        assert!(glob_matches("docs/*.md", "docs/guide.md"));
+        assert!(!glob_matches("docs/*.md", "src/docs/guide.md"));
+        assert!(glob_matches("src/**.rs", "src/a/b/c.rs"));
+        assert!(glob_matches("[Mm]akefile", "Makefile"));
+        assert!(!glob_matches("[!M]akefile", "Makefile"));
+    }
+
+    #[test]
+    fn test_nearer_files_and_later_sections_win() {
+        let temp = tempfile::tempdir().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("docs")).unwrap();
+        std::fs::write(
+            root.join(".editorconfig"),
+            "root = true\n\n[*]\ntrim_trailing_whitespace = true\ninsert_final_newline = true\n\n[*.md]\ntrim_trailing_whitespace = false\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("docs/.editorconfig"),
+            "[*]\ninsert_final_newline = unset\n",
+        )
+        .unwrap();
+
+        let fs = StdFileSystem;
+        assert_eq!(
+            settings_for(&fs, &root.join("src/main.rs")),
+            EditorConfigSettings {
+                trim_trailing_whitespace: Some(true),
+                insert_final_newline: Some(true),
+            }
+        );
+        assert_eq!(
+            settings_for(&fs, &root.join("docs/guide.md")),
+            EditorConfigSettings {
+                trim_trailing_whitespace: Some(false),
+                insert_final_newline: None,
+            }
+        );
+    }
+}
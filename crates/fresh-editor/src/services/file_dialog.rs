@@ -0,0 +1,305 @@
+//! Native (OS-level) file dialogs
+//!
+//! Shells out to the platform's dialog helper for Open and Save As:
+//! - Linux/BSD: `zenity`, then `kdialog` (only inside a graphical session)
+//! - macOS: `osascript`
+//! - Windows: PowerShell with `System.Windows.Forms`
+//!
+//! The helpers block until the user picks a file, so callers run
+//! [`run_file_dialog`] off the main thread. When no helper can be started the
+//! caller falls back to the built-in file browser.
+
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Which dialog to show
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileDialogKind {
+    Open,
+    SaveAs,
+}
+
+/// A file picked in a native dialog
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileDialogSelection {
+    pub path: PathBuf,
+    /// The dialog already asked before overwriting an existing file
+    pub overwrite_confirmed: bool,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum FileDialogError {
+    #[error("no native file dialog is available")]
+    Unavailable,
+    #[error("file dialog failed: {0}")]
+    Failed(String),
+}
+
+/// A dialog helper invocation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DialogCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Whether the helper asks before overwriting in Save As mode
+    pub confirms_overwrite: bool,
+}
+
+/// Show a native file dialog and wait for the user.
+///
+/// Returns `Ok(None)` if the dialog was cancelled and
+/// `Err(FileDialogError::Unavailable)` if no helper could be started.
+pub fn run_file_dialog(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> Result<Option<FileDialogSelection>, FileDialogError> {
+    for candidate in dialog_commands(kind, title, dir, file_name) {
+        let output = match Command::new(&candidate.program)
+            .args(&candidate.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .output()
+        {
+            Ok(output) => output,
+            Err(e) if e.kind() == ErrorKind::NotFound => continue,
+            Err(e) => {
+                return Err(FileDialogError::Failed(format!(
+                    "{}: {}",
+                    candidate.program, e
+                )))
+            }
+        };
+
+        // All helpers exit non-zero (or print nothing) when cancelled
+        if !output.status.success() {
+            tracing::debug!(
+                "{} exited with {}: {}",
+                candidate.program,
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+            return Ok(None);
+        }
+        let selected = String::from_utf8_lossy(&output.stdout);
+        let selected = selected.trim_end_matches(['\r', '\n']);
+        if selected.is_empty() {
+            return Ok(None);
+        }
+        return Ok(Some(FileDialogSelection {
+            path: PathBuf::from(selected),
+            overwrite_confirmed: kind == FileDialogKind::SaveAs && candidate.confirms_overwrite,
+        }));
+    }
+
+    Err(FileDialogError::Unavailable)
+}
+
+/// Dialog helpers to try on this platform, in order of preference
+pub fn dialog_commands(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> Vec<DialogCommand> {
+    if cfg!(target_os = "macos") {
+        vec![osascript_command(kind, title, dir, file_name)]
+    } else if cfg!(windows) {
+        vec![powershell_command(kind, title, dir, file_name)]
+    } else if has_graphical_session() {
+        vec![
+            zenity_command(kind, title, dir, file_name),
+            kdialog_command(kind, title, dir, file_name),
+        ]
+    } else {
+        // Over SSH or on a bare console there is nothing to show a dialog on
+        Vec::new()
+    }
+}
+
+fn has_graphical_session() -> bool {
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Initial selection: the directory (with a trailing separator) or a file inside it
+fn initial_path(dir: &Path, file_name: Option<&str>) -> String {
+    match file_name {
+        Some(name) => dir.join(name).to_string_lossy().into_owned(),
+        None => {
+            let mut dir = dir.to_string_lossy().into_owned();
+            if !dir.ends_with(std::path::MAIN_SEPARATOR) {
+                dir.push(std::path::MAIN_SEPARATOR);
+            }
+            dir
+        }
+    }
+}
+
+fn zenity_command(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> DialogCommand {
+    let mut args = vec!["--file-selection".to_string(), format!("--title={title}")];
+    if kind == FileDialogKind::SaveAs {
+        args.push("--save".to_string());
+    }
+    args.push(format!("--filename={}", initial_path(dir, file_name)));
+    DialogCommand {
+        program: "zenity".to_string(),
+        args,
+        // `--confirm-overwrite` is gone in zenity 4, so the editor asks instead
+        confirms_overwrite: false,
+    }
+}
+
+fn kdialog_command(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> DialogCommand {
+    let mode = match kind {
+        FileDialogKind::Open => "--getopenfilename",
+        FileDialogKind::SaveAs => "--getsavefilename",
+    };
+    DialogCommand {
+        program: "kdialog".to_string(),
+        args: vec![
+            "--title".to_string(),
+            title.to_string(),
+            mode.to_string(),
+            initial_path(dir, file_name),
+        ],
+        confirms_overwrite: true,
+    }
+}
+
+/// Quote a string for AppleScript
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn osascript_command(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> DialogCommand {
+    let location = format!(
+        "default location (POSIX file {})",
+        applescript_string(&dir.to_string_lossy())
+    );
+    let chooser = match kind {
+        FileDialogKind::Open => format!(
+            "choose file with prompt {} {}",
+            applescript_string(title),
+            location
+        ),
+        FileDialogKind::SaveAs => format!(
+            "choose file name with prompt {} {} default name {}",
+            applescript_string(title),
+            location,
+            applescript_string(file_name.unwrap_or_default())
+        ),
+    };
+    DialogCommand {
+        program: "osascript".to_string(),
+        args: vec!["-e".to_string(), format!("POSIX path of ({chooser})")],
+        confirms_overwrite: true,
+    }
+}
+
+/// Quote a string for PowerShell (single-quoted literal)
+fn powershell_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+fn powershell_command(
+    kind: FileDialogKind,
+    title: &str,
+    dir: &Path,
+    file_name: Option<&str>,
+) -> DialogCommand {
+    let class = match kind {
+        FileDialogKind::Open => "OpenFileDialog",
+        FileDialogKind::SaveAs => "SaveFileDialog",
+    };
+    let mut script = format!(
+        "Add-Type -AssemblyName System.Windows.Forms; \
+         $d = New-Object System.Windows.Forms.{class}; \
+         $d.Title = {}; $d.InitialDirectory = {}; ",
+        powershell_string(title),
+        powershell_string(&dir.to_string_lossy())
+    );
+    if let Some(name) = file_name {
+        script.push_str(&format!("$d.FileName = {}; ", powershell_string(name)));
+    }
+    script.push_str("if ($d.ShowDialog() -eq 'OK') { $d.FileName }");
+    DialogCommand {
+        program: "powershell".to_string(),
+        args: vec![
+            "-NoProfile".to_string(),
+            "-NonInteractive".to_string(),
+            "-Command".to_string(),
+            script,
+        ],
+        confirms_overwrite: true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zenity_and_kdialog_arguments() {
+        let dir = Path::new("/home/user/project");
+
+        let open = zenity_command(FileDialogKind::Open, "Open File", dir, None);
+        assert_eq!(
+            open.args,
+            vec![
+                "--file-selection",
+                "--title=Open File",
+                "--filename=/home/user/project/"
+            ]
+        );
+
+        let save = kdialog_command(FileDialogKind::SaveAs, "Save As", dir, Some("main.rs"));
+        assert_eq!(
+            save.args,
+            vec![
+                "--title",
+                "Save As",
+                "--getsavefilename",
+                "/home/user/project/main.rs"
+            ]
+        );
+        assert!(save.confirms_overwrite);
+    }
+
+    #[test]
+    fn test_script_quoting() {
+        let dir = Path::new("/tmp/it's \"quoted\"");
+
+        let mac = osascript_command(FileDialogKind::SaveAs, "Save As", dir, Some("a.txt"));
+        assert_eq!(
+            mac.args[1],
+            "POSIX path of (choose file name with prompt \"Save As\" \
+             default location (POSIX file \"/tmp/it's \\\"quoted\\\"\") default name \"a.txt\")"
+        );
+
+        let win = powershell_command(FileDialogKind::Open, "Open", dir, None);
+        let script = win.args.last().unwrap();
+        assert!(script.contains("OpenFileDialog"));
+        assert!(script.contains("$d.InitialDirectory = '/tmp/it''s \"quoted\"';"));
+        assert!(!script.contains("$d.FileName ="));
+    }
+}
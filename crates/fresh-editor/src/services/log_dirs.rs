@@ -3,10 +3,12 @@
 //! This module provides functions to get log file paths following the
 //! XDG Base Directory Specification. Logs are stored in:
 //! - `$XDG_STATE_HOME/fresh/logs/` (typically `~/.local/state/fresh/logs/`)
+//! - `portable/logs/` next to the executable in portable mode
 //!
 //! Each Fresh instance uses PID-based log files to support concurrent runs.
 //! On startup, stale log files from dead processes are cleaned up automatically.
 
+use crate::config_io::DirectoryContext;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::OnceLock;
@@ -40,6 +42,10 @@ pub fn log_dir() -> &'static PathBuf {
 
 /// Get the XDG state home log directory
 fn get_xdg_log_dir() -> Option<PathBuf> {
+    if let Some(root) = crate::config_io::portable_root() {
+        return Some(root.join("logs"));
+    }
+
     // First try XDG_STATE_HOME
     if let Ok(state_home) = std::env::var("XDG_STATE_HOME") {
         let path = PathBuf::from(state_home);
@@ -277,79 +283,52 @@ fn is_process_running(pid: u32) -> bool {
     }
 }
 
+/// Describe all directories used by Fresh and where they came from
+pub fn paths_report(dir_context: &DirectoryContext) -> String {
+    let config_dir = &dir_context.config_dir;
+    let data_dir = &dir_context.data_dir;
+    let logs_dir = log_dir();
+    let entry = |name: &str, path: PathBuf| format!("  {:<14}{}\n", name, path.display());
+
+    let mut report = String::new();
+    report.push_str(&format!(
+        "Config:     {} ({})\n",
+        config_dir.display(),
+        dir_context.config_source.describe()
+    ));
+    report.push_str(&entry("config.json:", dir_context.config_path()));
+    report.push_str(&entry("themes/:", dir_context.themes_dir()));
+    report.push_str(&entry("grammars/:", dir_context.grammars_dir()));
+    report.push_str(&entry("plugins/:", dir_context.plugins_dir()));
+    report.push('\n');
+
+    report.push_str(&format!(
+        "Data:       {} ({})\n",
+        data_dir.display(),
+        dir_context.data_source.describe()
+    ));
+    report.push_str(&entry("sessions/:", dir_context.sessions_dir()));
+    report.push_str(&entry("recovery/:", dir_context.recovery_dir()));
+    report.push_str(&entry("terminals/:", dir_context.terminals_dir()));
+    report.push('\n');
+
+    report.push_str(&format!(
+        "Cache:      {}\n",
+        dir_context.cache_dir.display()
+    ));
+    report.push_str(&entry("plugins:", dir_context.embedded_plugins_dir()));
+    report.push('\n');
+
+    report.push_str(&format!("Logs:       {}\n", logs_dir.display()));
+    report.push_str(&entry("lsp/:", logs_dir.join("lsp")));
+    report
+}
+
 /// Print all directories used by Fresh to stdout
-pub fn print_all_paths() {
-    use std::io::Write;
-
-    let stdout = std::io::stdout();
-    let mut handle = stdout.lock();
-
-    // Config directory (~/.config/fresh)
-    let config_dir = dirs::config_dir()
-        .map(|d| d.join("fresh"))
-        .unwrap_or_else(|| PathBuf::from("<unavailable>"));
-
-    // Data directory (~/.local/share/fresh)
-    let data_dir = dirs::data_dir()
-        .map(|d| d.join("fresh"))
-        .unwrap_or_else(|| PathBuf::from("<unavailable>"));
-
-    // State/logs directory
-    let logs_dir = log_dir().clone();
-
-    writeln!(handle, "Fresh directories:").ok();
-    writeln!(handle).ok();
-
-    writeln!(handle, "Config:     {}", config_dir.display()).ok();
-    writeln!(
-        handle,
-        "  config.json:  {}",
-        config_dir.join("config.json").display()
-    )
-    .ok();
-    writeln!(
-        handle,
-        "  themes/:      {}",
-        config_dir.join("themes").display()
-    )
-    .ok();
-    writeln!(
-        handle,
-        "  grammars/:    {}",
-        config_dir.join("grammars").display()
-    )
-    .ok();
-    writeln!(
-        handle,
-        "  plugins/:     {}",
-        config_dir.join("plugins").display()
-    )
-    .ok();
-    writeln!(handle).ok();
-
-    writeln!(handle, "Data:       {}", data_dir.display()).ok();
-    writeln!(
-        handle,
-        "  sessions/:    {}",
-        data_dir.join("sessions").display()
-    )
-    .ok();
-    writeln!(
-        handle,
-        "  recovery/:    {}",
-        data_dir.join("recovery").display()
-    )
-    .ok();
-    writeln!(
-        handle,
-        "  terminals/:   {}",
-        data_dir.join("terminals").display()
-    )
-    .ok();
-    writeln!(handle).ok();
-
-    writeln!(handle, "Logs:       {}", logs_dir.display()).ok();
-    writeln!(handle, "  lsp/:         {}", logs_dir.join("lsp").display()).ok();
+pub fn print_all_paths(dir_context: &DirectoryContext) {
+    println!("Fresh directories:");
+    println!();
+    print!("{}", paths_report(dir_context));
 }
 
 #[cfg(test)]
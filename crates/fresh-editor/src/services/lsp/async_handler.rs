@@ -11,25 +11,28 @@
 //! - LspHandle: Sync handle that can send commands to the task
 //! - Uses tokio channels for command/response communication
 
+use crate::config_io::expand_path;
 use crate::services::async_bridge::{
     AsyncBridge, AsyncMessage, LspMessageType, LspProgressValue, LspSemanticTokensResponse,
     LspServerStatus,
 };
+use crate::services::lsp::workspace::{self, DynamicRegistrations};
 use crate::services::process_limits::ProcessLimits;
 use lsp_types::{
     notification::{
-        DidChangeTextDocument, DidOpenTextDocument, DidSaveTextDocument, Initialized, Notification,
-        PublishDiagnostics,
+        DidChangeConfiguration, DidChangeTextDocument, DidChangeWatchedFiles, DidOpenTextDocument,
+        DidSaveTextDocument, Initialized, Notification, PublishDiagnostics,
     },
     request::{Initialize, Request},
-    ClientCapabilities, DidChangeTextDocumentParams, DidOpenTextDocumentParams,
-    DidSaveTextDocumentParams, InitializeParams, InitializeResult, InitializedParams,
-    PublishDiagnosticsParams, SemanticTokenModifier, SemanticTokenType,
+    ClientCapabilities, DidChangeConfigurationParams, DidChangeTextDocumentParams,
+    DidChangeWatchedFilesParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    FileChangeType, InitializeParams, InitializeResult, InitializedParams,
+    PublishDiagnosticsParams, RegistrationParams, SemanticTokenModifier, SemanticTokenType,
     SemanticTokensClientCapabilities, SemanticTokensClientCapabilitiesRequests,
     SemanticTokensFullOptions, SemanticTokensLegend, SemanticTokensParams, SemanticTokensResult,
     SemanticTokensServerCapabilities, ServerCapabilities, TextDocumentContentChangeEvent,
-    TextDocumentIdentifier, TextDocumentItem, TokenFormat, Uri, VersionedTextDocumentIdentifier,
-    WindowClientCapabilities, WorkspaceFolder,
+    TextDocumentIdentifier, TextDocumentItem, TokenFormat, UnregistrationParams, Uri,
+    VersionedTextDocumentIdentifier, WindowClientCapabilities, WorkspaceFolder,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -200,8 +203,11 @@ impl LspClientState {
 /// Create common LSP client capabilities with workDoneProgress support
 fn create_client_capabilities() -> ClientCapabilities {
     use lsp_types::{
-        GeneralClientCapabilities, RenameClientCapabilities, TextDocumentClientCapabilities,
-        WorkspaceClientCapabilities, WorkspaceEditClientCapabilities,
+        CodeActionClientCapabilities, CodeActionKind, CodeActionKindLiteralSupport,
+        CodeActionLiteralSupport, DidChangeWatchedFilesClientCapabilities,
+        DynamicRegistrationClientCapabilities, GeneralClientCapabilities, RenameClientCapabilities,
+        TextDocumentClientCapabilities, WorkspaceClientCapabilities,
+        WorkspaceEditClientCapabilities,
     };
 
     ClientCapabilities {
@@ -215,9 +221,40 @@ fn create_client_capabilities() -> ClientCapabilities {
                 document_changes: Some(true),
                 ..Default::default()
             }),
+            configuration: Some(true),
+            did_change_configuration: Some(DynamicRegistrationClientCapabilities {
+                dynamic_registration: Some(true),
+            }),
+            did_change_watched_files: Some(DidChangeWatchedFilesClientCapabilities {
+                dynamic_registration: Some(true),
+                relative_pattern_support: Some(true),
+            }),
             ..Default::default()
         }),
         text_document: Some(TextDocumentClientCapabilities {
+            // Literal support lets servers return edits directly (needed for
+            // code actions on save) instead of commands only
+            code_action: Some(CodeActionClientCapabilities {
+                code_action_literal_support: Some(CodeActionLiteralSupport {
+                    code_action_kind: CodeActionKindLiteralSupport {
+                        value_set: [
+                            CodeActionKind::EMPTY,
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::REFACTOR,
+                            CodeActionKind::REFACTOR_EXTRACT,
+                            CodeActionKind::REFACTOR_INLINE,
+                            CodeActionKind::REFACTOR_REWRITE,
+                            CodeActionKind::SOURCE,
+                            CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                            CodeActionKind::SOURCE_FIX_ALL,
+                        ]
+                        .into_iter()
+                        .map(|kind| kind.as_str().to_string())
+                        .collect(),
+                    },
+                }),
+                ..Default::default()
+            }),
             rename: Some(RenameClientCapabilities {
                 dynamic_registration: Some(true),
                 prepare_support: Some(true),
@@ -352,6 +389,13 @@ enum LspCommand {
     /// Notify document saved
     DidSave { uri: Uri, text: Option<String> },
 
+    /// Notify that the settings served for workspace/configuration changed
+    DidChangeConfiguration { settings: Option<Value> },
+
+    /// Notify that files were created, changed or deleted outside the
+    /// documents the server has open
+    DidChangeWatchedFiles { changes: Vec<(Uri, FileChangeType)> },
+
     /// Request completion at position
     Completion {
         request_id: u64,
@@ -410,6 +454,8 @@ enum LspCommand {
         end_line: u32,
         end_char: u32,
         diagnostics: Vec<lsp_types::Diagnostic>,
+        /// Only return actions of these kinds
+        only: Option<Vec<lsp_types::CodeActionKind>>,
     },
 
     /// Request document diagnostics (pull model)
@@ -495,6 +541,9 @@ struct LspState {
     /// Mapping from editor request_id to LSP JSON-RPC id for cancellation
     /// Key: editor request_id, Value: LSP JSON-RPC id
     active_requests: HashMap<u64, i64>,
+
+    /// Capabilities registered by the server at runtime (shared with stdout reader)
+    registrations: Arc<Mutex<DynamicRegistrations>>,
 }
 
 impl LspState {
@@ -868,11 +917,54 @@ impl LspState {
         tracing::trace!("LSP: did_save for {}", uri.as_str());
 
         let params = DidSaveTextDocumentParams {
-            text_document: TextDocumentIdentifier { uri },
+            text_document: TextDocumentIdentifier { uri: uri.clone() },
             text,
         };
 
-        self.send_notification::<DidSaveTextDocument>(params).await
+        self.send_notification::<DidSaveTextDocument>(params)
+            .await?;
+
+        // Servers that registered file watchers expect to hear about saved files too
+        self.handle_did_change_watched_files(vec![(uri, FileChangeType::CHANGED)])
+            .await
+    }
+
+    /// Handle did_change_watched_files command
+    ///
+    /// Only changes matching a watcher the server registered are sent.
+    async fn handle_did_change_watched_files(
+        &mut self,
+        changes: Vec<(Uri, FileChangeType)>,
+    ) -> Result<(), String> {
+        let events = self
+            .registrations
+            .lock()
+            .unwrap()
+            .watched_file_events(&changes);
+        if events.is_empty() {
+            return Ok(());
+        }
+        tracing::trace!("LSP: didChangeWatchedFiles with {} event(s)", events.len());
+
+        self.send_notification::<DidChangeWatchedFiles>(DidChangeWatchedFilesParams {
+            changes: events,
+        })
+        .await
+    }
+
+    /// Handle did_change_configuration command
+    async fn handle_did_change_configuration(
+        &mut self,
+        settings: Option<Value>,
+    ) -> Result<(), String> {
+        tracing::info!("LSP ({}): sending didChangeConfiguration", self.language);
+
+        let params = DidChangeConfigurationParams {
+            settings: settings.unwrap_or(Value::Null),
+        };
+
+        self.send_notification::<DidChangeConfiguration>(params)
+            .await
     }
 
     /// Handle completion request
@@ -1373,6 +1465,7 @@ impl LspState {
         end_line: u32,
         end_char: u32,
         diagnostics: Vec<lsp_types::Diagnostic>,
+        only: Option<Vec<lsp_types::CodeActionKind>>,
         pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
     ) -> Result<(), String> {
         use lsp_types::{
@@ -1403,7 +1496,7 @@ impl LspState {
             },
             context: CodeActionContext {
                 diagnostics,
-                only: None,
+                only,
                 trigger_kind: None,
             },
             work_done_progress_params: WorkDoneProgressParams::default(),
@@ -1937,6 +2030,9 @@ struct LspTask {
 
     /// Path to stderr log file
     stderr_log_path: std::path::PathBuf,
+
+    /// Settings served for workspace/configuration (shared with LspHandle)
+    settings: Arc<Mutex<Option<Value>>>,
 }
 
 impl LspTask {
@@ -1948,7 +2044,11 @@ impl LspTask {
         async_tx: std_mpsc::Sender<AsyncMessage>,
         process_limits: &ProcessLimits,
         stderr_log_path: std::path::PathBuf,
+        settings: Arc<Mutex<Option<Value>>>,
     ) -> Result<Self, String> {
+        // The configured command may use `~` or environment variables
+        let command = expand_path(command).to_string_lossy().into_owned();
+        let command = command.as_str();
         tracing::info!("Spawning async LSP server: {} {:?}", command, args);
         tracing::info!("Process limits: {:?}", process_limits);
         tracing::info!("LSP stderr will be logged to: {:?}", stderr_log_path);
@@ -2021,43 +2121,13 @@ impl LspTask {
             language,
             server_command: command.to_string(),
             stderr_log_path,
+            settings,
         })
     }
 
     /// Check if a command exists in PATH or as an absolute path
     fn command_exists(command: &str) -> bool {
-        use std::path::Path;
-
-        // If it's an absolute path, check if the file exists and is executable
-        if command.contains('/') || command.contains('\\') {
-            let path = Path::new(command);
-            return path.exists() && path.is_file();
-        }
-
-        // Otherwise, search in PATH
-        if let Ok(path_var) = std::env::var("PATH") {
-            #[cfg(unix)]
-            let separator = ':';
-            #[cfg(windows)]
-            let separator = ';';
-
-            for dir in path_var.split(separator) {
-                let full_path = Path::new(dir).join(command);
-                if full_path.exists() && full_path.is_file() {
-                    return true;
-                }
-                // On Windows, also check with .exe extension
-                #[cfg(windows)]
-                {
-                    let with_exe = Path::new(dir).join(format!("{}.exe", command));
-                    if with_exe.exists() && with_exe.is_file() {
-                        return true;
-                    }
-                }
-            }
-        }
-
-        false
+        super::detect::find_in_path(command).is_some()
     }
 
     /// Spawn the stdout reader task that continuously reads and dispatches LSP messages
@@ -2072,6 +2142,8 @@ impl LspTask {
         stdin_writer: Arc<tokio::sync::Mutex<ChildStdin>>,
         stderr_log_path: std::path::PathBuf,
         shutting_down: Arc<AtomicBool>,
+        settings: Arc<Mutex<Option<Value>>>,
+        registrations: Arc<Mutex<DynamicRegistrations>>,
     ) {
         tokio::spawn(async move {
             tracing::info!("LSP stdout reader task started for {}", language);
@@ -2086,6 +2158,8 @@ impl LspTask {
                             &language,
                             &server_command,
                             &stdin_writer,
+                            &settings,
+                            &registrations,
                         )
                         .await
                         {
@@ -2138,6 +2212,7 @@ impl LspTask {
             async_tx: self.async_tx.clone(),
             language: self.language.clone(),
             active_requests: HashMap::new(),
+            registrations: Arc::new(Mutex::new(DynamicRegistrations::new())),
         };
 
         let pending = Arc::new(Mutex::new(self.pending));
@@ -2157,6 +2232,8 @@ impl LspTask {
             stdin_writer.clone(),
             self.stderr_log_path,
             shutting_down.clone(),
+            self.settings,
+            state.registrations.clone(),
         );
 
         // Sequential command processing loop
@@ -2243,6 +2320,18 @@ impl LspTask {
                                 pending_commands.push(LspCommand::DidSave { uri, text });
                             }
                         }
+                        LspCommand::DidChangeConfiguration { settings } => {
+                            // Before initialization the server pulls the shared settings itself
+                            if state.initialized {
+                                let _ = state.handle_did_change_configuration(settings).await;
+                            }
+                        }
+                        LspCommand::DidChangeWatchedFiles { changes } => {
+                            // A server that hasn't started yet scans the workspace itself
+                            if state.initialized {
+                                let _ = state.handle_did_change_watched_files(changes).await;
+                            }
+                        }
                         LspCommand::Completion {
                             request_id,
                             uri,
@@ -2378,6 +2467,7 @@ impl LspTask {
                             end_line,
                             end_char,
                             diagnostics,
+                            only,
                         } => {
                             if state.initialized {
                                 tracing::info!("Processing CodeActions request for {}", uri.as_str());
@@ -2390,6 +2480,7 @@ impl LspTask {
                                         end_line,
                                         end_char,
                                         diagnostics,
+                                        only,
                                         &pending,
                                     )
                                     .await;
@@ -2657,6 +2748,7 @@ async fn read_message_from_stdout(
 
 /// Standalone function to handle and dispatch messages (for reader task)
 #[allow(clippy::type_complexity)]
+#[allow(clippy::too_many_arguments)]
 async fn handle_message_dispatch(
     message: JsonRpcMessage,
     pending: &Arc<Mutex<HashMap<i64, oneshot::Sender<Result<Value, String>>>>>,
@@ -2664,6 +2756,8 @@ async fn handle_message_dispatch(
     language: &str,
     server_command: &str,
     stdin_writer: &Arc<tokio::sync::Mutex<ChildStdin>>,
+    settings: &Arc<Mutex<Option<Value>>>,
+    registrations: &Arc<Mutex<DynamicRegistrations>>,
 ) -> Result<(), String> {
     match message {
         JsonRpcMessage::Response(response) => {
@@ -2711,43 +2805,15 @@ async fn handle_message_dispatch(
                     }
                 }
                 "workspace/configuration" => {
-                    // Return configuration with inlay hints enabled for rust-analyzer
-                    // The request contains items asking for configuration sections
-                    // We return an array with one config object per requested item
+                    // Answer each requested item from the configured settings
+                    let configs = workspace::configuration_response(
+                        settings.lock().unwrap().as_ref(),
+                        request.params.as_ref(),
+                    );
                     tracing::trace!(
-                        "Responding to workspace/configuration with inlay hints enabled"
+                        "Responding to workspace/configuration with {} item(s)",
+                        configs.len()
                     );
-
-                    // Parse request params to see how many items are requested
-                    let num_items = request
-                        .params
-                        .as_ref()
-                        .and_then(|p| p.get("items"))
-                        .and_then(|items| items.as_array())
-                        .map(|arr| arr.len())
-                        .unwrap_or(1);
-
-                    // rust-analyzer configuration with inlay hints enabled
-                    let ra_config = serde_json::json!({
-                        "inlayHints": {
-                            "typeHints": {
-                                "enable": true
-                            },
-                            "parameterHints": {
-                                "enable": true
-                            },
-                            "chainingHints": {
-                                "enable": true
-                            },
-                            "closureReturnTypeHints": {
-                                "enable": "always"
-                            }
-                        }
-                    });
-
-                    // Return one config object for each requested item
-                    let configs: Vec<Value> = (0..num_items).map(|_| ra_config.clone()).collect();
-
                     JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         id: request.id,
@@ -2756,11 +2822,34 @@ async fn handle_message_dispatch(
                     }
                 }
                 "client/registerCapability" => {
-                    // Server wants to register a capability dynamically - acknowledge
-                    tracing::trace!(
-                        "Acknowledging client/registerCapability (id={})",
-                        request.id
-                    );
+                    // Server registers a capability dynamically (file watchers, formatting, ...)
+                    tracing::trace!("Handling client/registerCapability (id={})", request.id);
+                    if let Some(params) = request
+                        .params
+                        .clone()
+                        .and_then(|p| serde_json::from_value::<RegistrationParams>(p).ok())
+                    {
+                        registrations.lock().unwrap().register(params.registrations);
+                    }
+                    JsonRpcResponse {
+                        jsonrpc: "2.0".to_string(),
+                        id: request.id,
+                        result: Some(Value::Null),
+                        error: None,
+                    }
+                }
+                "client/unregisterCapability" => {
+                    tracing::trace!("Handling client/unregisterCapability (id={})", request.id);
+                    if let Some(params) = request
+                        .params
+                        .clone()
+                        .and_then(|p| serde_json::from_value::<UnregistrationParams>(p).ok())
+                    {
+                        registrations
+                            .lock()
+                            .unwrap()
+                            .unregister(params.unregisterations);
+                    }
                     JsonRpcResponse {
                         jsonrpc: "2.0".to_string(),
                         id: request.id,
@@ -3041,6 +3130,9 @@ pub struct LspHandle {
 
     /// Runtime handle for blocking operations
     runtime: tokio::runtime::Handle,
+
+    /// Settings served for workspace/configuration (shared with the task)
+    settings: Arc<Mutex<Option<Value>>>,
 }
 
 impl LspHandle {
@@ -3059,6 +3151,7 @@ impl LspHandle {
         let command = command.to_string();
         let args = args.to_vec();
         let state = Arc::new(Mutex::new(LspClientState::Starting));
+        let settings = Arc::new(Mutex::new(None));
 
         // Create stderr log path in XDG state directory
         let stderr_log_path = crate::services::log_dirs::lsp_log_path(&language);
@@ -3071,6 +3164,7 @@ impl LspHandle {
         });
 
         let state_clone = state.clone();
+        let settings_clone = settings.clone();
         let stderr_log_path_clone = stderr_log_path.clone();
         runtime.spawn(async move {
             match LspTask::spawn(
@@ -3080,6 +3174,7 @@ impl LspHandle {
                 async_tx.clone(),
                 &process_limits,
                 stderr_log_path_clone.clone(),
+                settings_clone,
             )
             .await
             {
@@ -3115,6 +3210,7 @@ impl LspHandle {
             command_tx,
             state,
             runtime: runtime.clone(),
+            settings,
         })
     }
 
@@ -3241,6 +3337,36 @@ impl LspHandle {
             .map_err(|_| "Failed to send did_save command".to_string())
     }
 
+    /// Update the settings served for `workspace/configuration`
+    ///
+    /// Takes effect immediately for subsequent configuration requests. A running
+    /// server is additionally notified with `workspace/didChangeConfiguration`.
+    pub fn did_change_configuration(&self, settings: Option<Value>) -> Result<(), String> {
+        *self.settings.lock().unwrap() = settings.clone();
+        if !self.is_initialized() {
+            return Ok(());
+        }
+        self.command_tx
+            .try_send(LspCommand::DidChangeConfiguration { settings })
+            .map_err(|_| "Failed to send did_change_configuration command".to_string())
+    }
+
+    /// Report files created, changed or deleted on disk
+    ///
+    /// Sent as `workspace/didChangeWatchedFiles`, filtered by the file
+    /// watchers the server registered.
+    pub fn did_change_watched_files(
+        &self,
+        changes: Vec<(Uri, FileChangeType)>,
+    ) -> Result<(), String> {
+        if !self.is_initialized() {
+            return Ok(());
+        }
+        self.command_tx
+            .try_send(LspCommand::DidChangeWatchedFiles { changes })
+            .map_err(|_| "Failed to send did_change_watched_files command".to_string())
+    }
+
     /// Request completion at position
     pub fn completion(
         &self,
@@ -3362,6 +3488,7 @@ impl LspHandle {
         end_line: u32,
         end_char: u32,
         diagnostics: Vec<lsp_types::Diagnostic>,
+        only: Option<Vec<lsp_types::CodeActionKind>>,
     ) -> Result<(), String> {
         self.command_tx
             .try_send(LspCommand::CodeActions {
@@ -3372,6 +3499,7 @@ impl LspHandle {
                 end_line,
                 end_char,
                 diagnostics,
+                only,
             })
             .map_err(|_| "Failed to send code_actions command".to_string())
     }
@@ -0,0 +1,156 @@
+//! Detection of installed language servers
+//!
+//! Probes `PATH` for the well-known servers of the common languages so the
+//! "Detect Language Servers" command can show which are installed, enable one
+//! with working defaults, and say how to install the missing ones.
+
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+
+/// A language server the editor knows how to configure
+#[derive(Debug)]
+pub struct KnownServer {
+    /// Languages (config keys) the server handles
+    pub languages: &'static [&'static str],
+    /// Executable to look for and run
+    pub command: &'static str,
+    /// Arguments it needs to speak LSP on stdio
+    pub args: &'static [&'static str],
+    /// Shell command that installs it
+    pub install: &'static str,
+}
+
+/// Known servers, in the order they are listed
+pub const KNOWN_SERVERS: &[KnownServer] = &[
+    KnownServer {
+        languages: &["rust"],
+        command: "rust-analyzer",
+        args: &[],
+        install: "rustup component add rust-analyzer",
+    },
+    KnownServer {
+        languages: &["typescript", "javascript"],
+        command: "typescript-language-server",
+        args: &["--stdio"],
+        install: "npm install -g typescript-language-server typescript",
+    },
+    KnownServer {
+        languages: &["python"],
+        command: "pyright-langserver",
+        args: &["--stdio"],
+        install: "npm install -g pyright",
+    },
+    KnownServer {
+        languages: &["python"],
+        command: "pylsp",
+        args: &[],
+        install: "pip install python-lsp-server",
+    },
+    KnownServer {
+        languages: &["go"],
+        command: "gopls",
+        args: &[],
+        install: "go install golang.org/x/tools/gopls@latest",
+    },
+    KnownServer {
+        languages: &["c", "cpp"],
+        command: "clangd",
+        args: &[],
+        install: "sudo apt install clangd",
+    },
+];
+
+/// Look up a known server by its command
+pub fn known_server(command: &str) -> Option<&'static KnownServer> {
+    KNOWN_SERVERS
+        .iter()
+        .find(|server| server.command == command)
+}
+
+/// A known server and where it was found
+#[derive(Debug)]
+pub struct DetectedServer {
+    pub server: &'static KnownServer,
+    /// Path of the executable, `None` when it isn't installed
+    pub path: Option<PathBuf>,
+}
+
+/// Probe `PATH` for every known server
+pub fn detect_servers() -> Vec<DetectedServer> {
+    let path_var = std::env::var_os("PATH");
+    KNOWN_SERVERS
+        .iter()
+        .map(|server| DetectedServer {
+            server,
+            path: find_in_path_var(server.command, path_var.as_deref()),
+        })
+        .collect()
+}
+
+/// Find an executable in `PATH`, or check it exists when it is a path itself
+pub fn find_in_path(command: &str) -> Option<PathBuf> {
+    find_in_path_var(command, std::env::var_os("PATH").as_deref())
+}
+
+fn find_in_path_var(command: &str, path_var: Option<&OsStr>) -> Option<PathBuf> {
+    if command.contains('/') || command.contains('\\') {
+        let path = Path::new(command);
+        return path.is_file().then(|| path.to_path_buf());
+    }
+
+    std::env::split_paths(path_var?).find_map(|dir| {
+        let full_path = dir.join(command);
+        if full_path.is_file() {
+            return Some(full_path);
+        }
+        // On Windows, also check with .exe extension
+        #[cfg(windows)]
+        {
+            let with_exe = dir.join(format!("{}.exe", command));
+            if with_exe.is_file() {
+                return Some(with_exe);
+            }
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_in_path_var() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let bin = temp_dir.path().join("bin");
+        std::fs::create_dir(&bin).unwrap();
+        std::fs::write(bin.join("gopls"), "").unwrap();
+
+        let path_var = std::env::join_paths([temp_dir.path(), bin.as_path()]).unwrap();
+        assert_eq!(
+            find_in_path_var("gopls", Some(&path_var)),
+            Some(bin.join("gopls"))
+        );
+        assert_eq!(find_in_path_var("clangd", Some(&path_var)), None);
+        // Directories don't count
+        assert_eq!(find_in_path_var("bin", Some(&path_var)), None);
+        assert_eq!(find_in_path_var("gopls", None), None);
+
+        let absolute = bin.join("gopls");
+        assert_eq!(
+            find_in_path_var(absolute.to_str().unwrap(), None),
+            Some(absolute.clone())
+        );
+    }
+
+    #[test]
+    fn test_known_servers_are_unique() {
+        for (index, server) in KNOWN_SERVERS.iter().enumerate() {
+            assert!(KNOWN_SERVERS[..index]
+                .iter()
+                .all(|earlier| earlier.command != server.command));
+            assert_eq!(known_server(server.command).unwrap().args, server.args);
+        }
+        assert!(known_server("not-a-server").is_none());
+    }
+}
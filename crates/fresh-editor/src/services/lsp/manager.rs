@@ -9,7 +9,7 @@
 use crate::services::async_bridge::AsyncBridge;
 use crate::services::lsp::async_handler::LspHandle;
 use crate::types::LspServerConfig;
-use lsp_types::{SemanticTokensLegend, Uri};
+use lsp_types::{FileChangeType, SemanticTokensLegend, Uri};
 use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
@@ -246,7 +246,20 @@ impl LspManager {
     }
 
     /// Set configuration for a language
+    ///
+    /// A running server is notified if its workspace settings changed.
     pub fn set_language_config(&mut self, language: String, config: LspServerConfig) {
+        if let Some(handle) = self.handles.get(&language) {
+            let changed = self
+                .config
+                .get(&language)
+                .is_none_or(|old| old.settings != config.settings);
+            if changed {
+                if let Err(e) = handle.did_change_configuration(config.settings.clone()) {
+                    tracing::warn!("Failed to update LSP settings for {}: {}", language, e);
+                }
+            }
+        }
         self.config.insert(language, config);
     }
 
@@ -386,6 +399,9 @@ impl LspManager {
             config.process_limits.clone(),
         ) {
             Ok(handle) => {
+                // Settings must be in place before the server asks for them
+                let _ = handle.did_change_configuration(config.settings.clone());
+
                 // Initialize the handle (non-blocking)
                 // The handle will become ready asynchronously
                 // Use per-language root URI if set, otherwise fall back to default
@@ -599,6 +615,17 @@ impl LspManager {
         self.handles.keys().cloned().collect()
     }
 
+    /// Report files created, changed or deleted on disk to every running server
+    ///
+    /// Each server only hears about the files its registered watchers match.
+    pub fn did_change_watched_files(&self, changes: &[(Uri, FileChangeType)]) {
+        for (language, handle) in &self.handles {
+            if let Err(e) = handle.did_change_watched_files(changes.to_vec()) {
+                tracing::warn!("Failed to report file changes to {} LSP: {}", language, e);
+            }
+        }
+    }
+
     /// Check if an LSP server for a language is running and ready to serve requests
     pub fn is_server_ready(&self, language: &str) -> bool {
         self.handles
@@ -711,6 +738,7 @@ mod tests {
             process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
             auto_start: false,
             initialization_options: None,
+            settings: None,
         };
 
         manager.set_language_config("rust".to_string(), config);
@@ -734,6 +762,7 @@ mod tests {
                 process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
                 auto_start: false,
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -773,6 +802,7 @@ mod tests {
                 process_limits: crate::services::process_limits::ProcessLimits::unlimited(),
                 auto_start: false,
                 initialization_options: None,
+                settings: None,
             },
         );
 
@@ -807,6 +837,7 @@ mod tests {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -825,6 +856,7 @@ mod tests {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -843,6 +875,7 @@ mod tests {
                 tab_size: None,
                 formatter: None,
                 format_on_save: false,
+                code_actions_on_save: vec![],
                 on_save: vec![],
             },
         );
@@ -74,9 +74,15 @@
 //!   channels. `LspTask` runs in a separate tokio task, managing the server
 //!   subprocess and JSON-RPC I/O. Each handle has a unique `id` for tracking.
 //!
+//! - **`detect`**: Probes `PATH` for well-known language servers, for the
+//!   "Detect Language Servers" command.
+//!
 //! - **`diagnostics`**: Converts LSP diagnostics to editor overlays (colored
 //!   underlines for errors, warnings, etc.).
 //!
+//! - **`workspace`**: Answers `workspace/configuration` from the configured
+//!   `settings` and tracks capabilities registered via `client/registerCapability`.
+//!
 //! # Message Flow
 //!
 //! ## Outgoing Requests (Editor → Server)
@@ -115,9 +121,11 @@
 //!   (e.g., pull diagnostics only if `diagnosticProvider` is advertised)
 
 pub mod async_handler;
+pub mod detect;
 pub mod diagnostics;
 pub mod manager;
 pub mod semantic_tokens;
+pub mod workspace;
 
 // Re-export for public API (used by tests)
 pub use crate::types::LspServerConfig;
@@ -0,0 +1,362 @@
+//! Workspace-level LSP support: configuration sections and dynamic registrations
+//!
+//! Servers pull their settings with `workspace/configuration` and register
+//! capabilities after startup with `client/registerCapability`. This module
+//! holds the pure logic for both so the async handler only does the I/O.
+
+use lsp_types::{
+    DidChangeWatchedFilesRegistrationOptions, FileChangeType, FileEvent, GlobPattern, OneOf,
+    Registration, Unregistration, Uri, WatchKind,
+};
+use regex::Regex;
+use serde_json::Value;
+use std::collections::HashMap;
+
+/// Method name of the watched files capability
+const WATCHED_FILES_METHOD: &str = "workspace/didChangeWatchedFiles";
+
+/// Look up the settings for one `workspace/configuration` item.
+///
+/// `section` is a dotted path (e.g. `"python.analysis"`). A key containing the
+/// full section name takes precedence over walking the path, so both
+/// `{"rust-analyzer": {...}}` and `{"python.analysis": {...}}` work.
+/// Missing sections resolve to `null`, as the protocol requires.
+pub fn configuration_section(settings: Option<&Value>, section: Option<&str>) -> Value {
+    let Some(settings) = settings else {
+        return Value::Null;
+    };
+    let Some(section) = section.filter(|s| !s.is_empty()) else {
+        return settings.clone();
+    };
+
+    if let Some(value) = settings.get(section) {
+        return value.clone();
+    }
+
+    section
+        .split('.')
+        .try_fold(settings, |value, key| value.get(key))
+        .cloned()
+        .unwrap_or(Value::Null)
+}
+
+/// Answer a `workspace/configuration` request: one result per requested item
+pub fn configuration_response(settings: Option<&Value>, params: Option<&Value>) -> Vec<Value> {
+    let items = params
+        .and_then(|p| p.get("items"))
+        .and_then(|items| items.as_array())
+        .map(|items| items.as_slice())
+        .unwrap_or_default();
+
+    items
+        .iter()
+        .map(|item| {
+            let section = item.get("section").and_then(|s| s.as_str());
+            configuration_section(settings, section)
+        })
+        .collect()
+}
+
+/// A compiled file system watcher from a dynamic registration
+#[derive(Debug, Clone)]
+struct FileWatcher {
+    pattern: Regex,
+    kind: WatchKind,
+}
+
+/// Capabilities registered by the server through `client/registerCapability`
+#[derive(Debug, Default)]
+pub struct DynamicRegistrations {
+    /// Registration id -> method
+    methods: HashMap<String, String>,
+    /// Registration id -> file watchers (for `workspace/didChangeWatchedFiles`)
+    watchers: HashMap<String, Vec<FileWatcher>>,
+}
+
+impl DynamicRegistrations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record registrations from a `client/registerCapability` request
+    pub fn register(&mut self, registrations: Vec<Registration>) {
+        for registration in registrations {
+            tracing::debug!(
+                "LSP dynamic registration {} for {}",
+                registration.id,
+                registration.method
+            );
+            if registration.method == WATCHED_FILES_METHOD {
+                let watchers = registration
+                    .register_options
+                    .and_then(|options| {
+                        serde_json::from_value::<DidChangeWatchedFilesRegistrationOptions>(options)
+                            .ok()
+                    })
+                    .map(|options| options.watchers)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|watcher| {
+                        let pattern = match watcher.glob_pattern {
+                            GlobPattern::String(pattern) => pattern,
+                            GlobPattern::Relative(relative) => {
+                                let base = match relative.base_uri {
+                                    OneOf::Left(folder) => folder.uri,
+                                    OneOf::Right(uri) => uri,
+                                };
+                                let base = uri_path(&base)?;
+                                format!("{}/{}", base.trim_end_matches('/'), relative.pattern)
+                            }
+                        };
+                        Some(FileWatcher {
+                            pattern: glob_to_regex(&pattern)?,
+                            kind: watcher.kind.unwrap_or(
+                                WatchKind::Create | WatchKind::Change | WatchKind::Delete,
+                            ),
+                        })
+                    })
+                    .collect();
+                self.watchers.insert(registration.id.clone(), watchers);
+            }
+            self.methods.insert(registration.id, registration.method);
+        }
+    }
+
+    /// Remove registrations from a `client/unregisterCapability` request
+    pub fn unregister(&mut self, unregistrations: Vec<Unregistration>) {
+        for unregistration in unregistrations {
+            self.methods.remove(&unregistration.id);
+            self.watchers.remove(&unregistration.id);
+        }
+    }
+
+    /// Whether the server has dynamically registered the given method
+    pub fn is_registered(&self, method: &str) -> bool {
+        self.methods.values().any(|m| m == method)
+    }
+
+    /// Build the file event to report for a change, if any watcher is interested
+    pub fn watched_file_event(&self, uri: &Uri, change: FileChangeType) -> Option<FileEvent> {
+        let kind = if change == FileChangeType::CREATED {
+            WatchKind::Create
+        } else if change == FileChangeType::DELETED {
+            WatchKind::Delete
+        } else {
+            WatchKind::Change
+        };
+        let path = uri_path(uri)?;
+
+        self.watchers
+            .values()
+            .flatten()
+            .any(|watcher| watcher.kind.contains(kind) && watcher.pattern.is_match(&path))
+            .then(|| FileEvent::new(uri.clone(), change))
+    }
+
+    /// Build the file events to report for a batch of changes, skipping the
+    /// ones no watcher is interested in
+    pub fn watched_file_events(&self, changes: &[(Uri, FileChangeType)]) -> Vec<FileEvent> {
+        if !self.is_registered(WATCHED_FILES_METHOD) {
+            return Vec::new();
+        }
+        changes
+            .iter()
+            .filter_map(|(uri, change)| self.watched_file_event(uri, *change))
+            .collect()
+    }
+}
+
+/// File system path of a `file://` URI, with `/` separators
+fn uri_path(uri: &Uri) -> Option<String> {
+    let path = url::Url::parse(uri.as_str()).ok()?.to_file_path().ok()?;
+    Some(path.to_string_lossy().replace('\\', "/"))
+}
+
+/// Translate an LSP glob pattern into an anchored regex.
+///
+/// Supports `*`, `**`, `?`, `{a,b}` alternatives and `[...]` / `[!...]` ranges.
+/// Patterns without a path separator match the file name anywhere in the tree.
+fn glob_to_regex(glob: &str) -> Option<Regex> {
+    let mut regex = String::from("^");
+    if !glob.contains('/') {
+        regex.push_str("(?:.*/)?");
+    }
+
+    let chars: Vec<char> = glob.chars().collect();
+    let mut i = 0;
+    let mut in_braces = false;
+    while i < chars.len() {
+        match chars[i] {
+            '*' if chars.get(i + 1) == Some(&'*') => {
+                if chars.get(i + 2) == Some(&'/') {
+                    regex.push_str("(?:.*/)?");
+                    i += 1;
+                } else {
+                    regex.push_str(".*");
+                }
+                i += 1;
+            }
+            '*' => regex.push_str("[^/]*"),
+            '?' => regex.push_str("[^/]"),
+            '{' if !in_braces => {
+                in_braces = true;
+                regex.push_str("(?:");
+            }
+            '}' if in_braces => {
+                in_braces = false;
+                regex.push(')');
+            }
+            ',' if in_braces => regex.push('|'),
+            '[' => {
+                let end = chars[i + 1..].iter().position(|&c| c == ']')? + i + 1;
+                regex.push('[');
+                let mut class = &chars[i + 1..end];
+                if class.first() == Some(&'!') {
+                    regex.push('^');
+                    class = &class[1..];
+                }
+                for &c in class {
+                    if c == '\\' || c == '[' || c == '^' {
+                        regex.push('\\');
+                    }
+                    regex.push(c);
+                }
+                regex.push(']');
+                i = end;
+            }
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+        i += 1;
+    }
+    regex.push('$');
+
+    Regex::new(&regex).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_configuration_section_lookup() {
+        let settings = json!({
+            "rust-analyzer": { "checkOnSave": false },
+            "python": { "analysis": { "typeCheckingMode": "basic" } },
+            "gopls.ui": { "semanticTokens": true }
+        });
+
+        assert_eq!(
+            configuration_section(Some(&settings), Some("rust-analyzer")),
+            json!({ "checkOnSave": false })
+        );
+        assert_eq!(
+            configuration_section(Some(&settings), Some("python.analysis")),
+            json!({ "typeCheckingMode": "basic" })
+        );
+        assert_eq!(
+            configuration_section(Some(&settings), Some("gopls.ui")),
+            json!({ "semanticTokens": true })
+        );
+        assert_eq!(
+            configuration_section(Some(&settings), Some("missing")),
+            Value::Null
+        );
+        assert_eq!(configuration_section(Some(&settings), None), settings);
+        assert_eq!(configuration_section(None, Some("python")), Value::Null);
+    }
+
+    #[test]
+    fn test_configuration_response_per_item() {
+        let settings = json!({ "a": 1, "b": { "c": 2 } });
+        let params = json!({ "items": [{ "section": "b.c" }, { "scopeUri": "file:///x" }, { "section": "z" }] });
+
+        assert_eq!(
+            configuration_response(Some(&settings), Some(&params)),
+            vec![json!(2), settings.clone(), Value::Null]
+        );
+    }
+
+    #[test]
+    fn test_glob_to_regex() {
+        let re = glob_to_regex("**/*.{rs,toml}").unwrap();
+        assert!(re.is_match("/home/user/project/src/main.rs"));
+        assert!(re.is_match("/home/user/project/Cargo.toml"));
+        assert!(!re.is_match("/home/user/project/README.md"));
+
+        let re = glob_to_regex("Cargo.toml").unwrap();
+        assert!(re.is_match("/project/Cargo.toml"));
+        assert!(!re.is_match("/project/Cargo.lock"));
+
+        let re = glob_to_regex("/project/src/*.[!o]").unwrap();
+        assert!(re.is_match("/project/src/a.c"));
+        assert!(!re.is_match("/project/src/a.o"));
+        assert!(!re.is_match("/project/src/nested/a.c"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_watched_file_registrations() {
+        let mut registrations = DynamicRegistrations::new();
+        registrations.register(vec![
+            Registration {
+                id: "watch".to_string(),
+                method: WATCHED_FILES_METHOD.to_string(),
+                register_options: Some(json!({
+                    "watchers": [
+                        { "globPattern": "**/*.rs", "kind": 2 },
+                        {
+                            "globPattern": { "baseUri": "file:///project", "pattern": "Cargo.toml" }
+                        }
+                    ]
+                })),
+            },
+            Registration {
+                id: "fmt".to_string(),
+                method: "textDocument/formatting".to_string(),
+                register_options: None,
+            },
+        ]);
+
+        assert!(registrations.is_registered("textDocument/formatting"));
+
+        let rs = Uri::from_str("file:///project/src/lib.rs").unwrap();
+        let manifest = Uri::from_str("file:///project/Cargo.toml").unwrap();
+        let other = Uri::from_str("file:///project/notes.md").unwrap();
+
+        assert!(registrations
+            .watched_file_event(&rs, FileChangeType::CHANGED)
+            .is_some());
+        // The *.rs watcher only asked for change events
+        assert!(registrations
+            .watched_file_event(&rs, FileChangeType::CREATED)
+            .is_none());
+        assert!(registrations
+            .watched_file_event(&manifest, FileChangeType::DELETED)
+            .is_some());
+        assert!(registrations
+            .watched_file_event(&other, FileChangeType::CHANGED)
+            .is_none());
+        let events = registrations.watched_file_events(&[
+            (rs.clone(), FileChangeType::CREATED),
+            (manifest.clone(), FileChangeType::CREATED),
+            (other, FileChangeType::DELETED),
+        ]);
+        assert_eq!(
+            events,
+            vec![FileEvent::new(manifest, FileChangeType::CREATED)]
+        );
+
+        registrations.unregister(vec![Unregistration {
+            id: "watch".to_string(),
+            method: WATCHED_FILES_METHOD.to_string(),
+        }]);
+        assert!(registrations
+            .watched_file_event(&rs, FileChangeType::CHANGED)
+            .is_none());
+        assert!(registrations
+            .watched_file_events(&[(rs, FileChangeType::CHANGED)])
+            .is_empty());
+    }
+}
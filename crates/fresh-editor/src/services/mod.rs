@@ -4,7 +4,11 @@
 //! I/O, and async operations.
 
 pub mod async_bridge;
+pub mod cache_budget;
 pub mod clipboard;
+pub mod crash_report;
+pub mod editorconfig;
+pub mod file_dialog;
 pub mod fs;
 #[cfg(target_os = "linux")]
 pub mod gpm;
@@ -12,12 +16,16 @@ pub mod log_dirs;
 pub mod lsp;
 pub mod plugins;
 pub mod process_limits;
+pub mod project_search;
+pub mod recent_workspaces;
 pub mod recovery;
 pub mod release_checker;
 pub mod remote;
+pub mod rpc;
 pub mod signal_handler;
 pub mod status_log;
 pub mod styled_html;
+pub mod suggestion_filter;
 pub mod telemetry;
 pub mod terminal;
 pub mod terminal_modes;
@@ -3,6 +3,7 @@ use crate::i18n;
 use crate::input::command_registry::CommandRegistry;
 use crate::services::signal_handler;
 use crate::view::theme;
+use fresh_core::api::ThemeSeed;
 use fresh_core::services::PluginServiceBridge;
 use std::any::Any;
 use std::collections::HashMap;
@@ -43,6 +44,17 @@ impl PluginServiceBridge for EditorServiceBridge {
         theme::get_builtin_themes()
     }
 
+    fn generate_theme(&self, seed: &ThemeSeed) -> Result<serde_json::Value, String> {
+        let bg =
+            theme::parse_hex(&seed.bg).ok_or_else(|| format!("invalid bg color: {}", seed.bg))?;
+        let fg =
+            theme::parse_hex(&seed.fg).ok_or_else(|| format!("invalid fg color: {}", seed.fg))?;
+        let accent = theme::parse_hex(&seed.accent)
+            .ok_or_else(|| format!("invalid accent color: {}", seed.accent))?;
+        let name = seed.name.as_deref().unwrap_or("generated");
+        Ok(theme::generate_theme(name, bg, fg, accent))
+    }
+
     fn register_plugin_strings(
         &self,
         plugin_name: &str,
@@ -0,0 +1,285 @@
+//! HTTP requests made by plugins through `editor.fetch`
+//!
+//! Requests are only sent to hosts on the `plugin_fetch.allowed_hosts`
+//! allow-list, time out after `timeoutMs` (or the configured default), and
+//! refuse response bodies larger than `plugin_fetch.max_response_bytes`.
+//! [`check_url`] runs on the editor thread so disallowed requests are
+//! rejected immediately; [`fetch`] blocks and runs on the tokio runtime.
+//! Redirects are followed by [`fetch`] itself, checking every hop against the
+//! allow-list, so an allowed host can't send a plugin anywhere else.
+
+use crate::config::PluginFetchConfig;
+use fresh_core::api::{FetchOptions, FetchResponse};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Whether `host` matches an allow-list entry
+///
+/// Entries match exactly (ignoring case), `*.example.com` also matches any
+/// subdomain of `example.com`, and `*` matches every host.
+pub fn host_allowed(allowed_hosts: &[String], host: &str) -> bool {
+    let host = host.trim_end_matches('.').to_ascii_lowercase();
+    allowed_hosts.iter().any(|entry| {
+        let entry = entry.trim().to_ascii_lowercase();
+        if entry == "*" {
+            return true;
+        }
+        match entry.strip_prefix("*.") {
+            Some(domain) => host == domain || host.ends_with(&format!(".{}", domain)),
+            None => host == entry,
+        }
+    })
+}
+
+/// Check that a plugin may request `url`
+pub fn check_url(config: &PluginFetchConfig, url: &str) -> Result<(), String> {
+    let parsed = url::Url::parse(url).map_err(|e| format!("Invalid URL '{}': {}", url, e))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(format!(
+            "Unsupported URL scheme '{}': only http and https are allowed",
+            parsed.scheme()
+        ));
+    }
+    let host = parsed
+        .host_str()
+        .ok_or_else(|| format!("URL '{}' has no host", url))?;
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if !host_allowed(&config.allowed_hosts, host) {
+        return Err(format!(
+            "Host '{}' is not in the plugin_fetch.allowed_hosts setting",
+            host
+        ));
+    }
+    Ok(())
+}
+
+/// Redirects [`fetch`] follows before giving up
+const MAX_REDIRECTS: usize = 10;
+
+/// Send the request and read the response
+///
+/// Blocks until the response is read or the timeout passes.
+pub fn fetch(
+    config: &PluginFetchConfig,
+    url: &str,
+    options: FetchOptions,
+) -> Result<FetchResponse, String> {
+    check_url(config, url)?;
+
+    let timeout_ms = options.timeout_ms.unwrap_or(config.default_timeout_ms);
+    let agent = ureq::Agent::config_builder()
+        .timeout_global(Some(Duration::from_millis(timeout_ms)))
+        .http_status_as_error(false)
+        .max_redirects(0)
+        .build()
+        .new_agent();
+
+    let mut method = options
+        .method
+        .as_deref()
+        .unwrap_or("GET")
+        .to_ascii_uppercase();
+    let mut body = options.body;
+    let mut url = url.to_string();
+    let mut redirects = 0;
+    let mut response = loop {
+        let response = send(
+            &agent,
+            &method,
+            &url,
+            options.headers.as_ref(),
+            body.clone(),
+            timeout_ms,
+        )?;
+        let status = response.status().as_u16();
+        let location = response
+            .headers()
+            .get("location")
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+        let Some(location) = location.filter(|_| matches!(status, 301 | 302 | 303 | 307 | 308))
+        else {
+            break response;
+        };
+
+        if redirects == MAX_REDIRECTS {
+            return Err(format!("Too many redirects (more than {})", MAX_REDIRECTS));
+        }
+        redirects += 1;
+        let next = url::Url::parse(&url)
+            .and_then(|base| base.join(&location))
+            .map_err(|e| format!("Invalid redirect to '{}': {}", location, e))?;
+        check_url(config, next.as_str())
+            .map_err(|e| format!("Redirect to '{}' refused: {}", next, e))?;
+        // Like browsers, only 307 and 308 repeat the method and body
+        if !matches!(status, 307 | 308) && method != "HEAD" {
+            method = "GET".to_string();
+            body = None;
+        }
+        url = next.into();
+    };
+
+    let status = response.status().as_u16();
+    let headers: HashMap<String, String> = response
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    let body = response
+        .body_mut()
+        .with_config()
+        .limit(config.max_response_bytes)
+        .lossy_utf8(true)
+        .read_to_string()
+        .map_err(|e| match e {
+            ureq::Error::BodyExceedsLimit(limit) => format!(
+                "Response body is larger than plugin_fetch.max_response_bytes ({} bytes)",
+                limit
+            ),
+            ureq::Error::Timeout(_) => format!("Request timed out after {} ms", timeout_ms),
+            e => format!("Failed to read response body: {}", e),
+        })?;
+
+    Ok(FetchResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+/// Send one request without following redirects
+fn send(
+    agent: &ureq::Agent,
+    method: &str,
+    url: &str,
+    headers: Option<&HashMap<String, String>>,
+    body: Option<String>,
+    timeout_ms: u64,
+) -> Result<ureq::http::Response<ureq::Body>, String> {
+    let mut request = ureq::http::Request::builder()
+        .method(method)
+        .uri(url)
+        .header("User-Agent", "fresh-editor-plugin");
+    for (name, value) in headers.into_iter().flatten() {
+        request = request.header(name.as_str(), value.as_str());
+    }
+    let result = match body {
+        Some(body) => request
+            .body(body)
+            .map_err(|e| format!("Invalid request: {}", e))
+            .map(|request| agent.run(request)),
+        None => request
+            .body(())
+            .map_err(|e| format!("Invalid request: {}", e))
+            .map(|request| agent.run(request)),
+    }?;
+    result.map_err(|e| match e {
+        ureq::Error::Timeout(_) => format!("Request timed out after {} ms", timeout_ms),
+        e => format!("HTTP request failed: {}", e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(allowed_hosts: &[&str]) -> PluginFetchConfig {
+        PluginFetchConfig {
+            allowed_hosts: allowed_hosts.iter().map(|h| h.to_string()).collect(),
+            ..PluginFetchConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_host_allowed() {
+        let allowed: Vec<String> = vec!["api.github.com".into(), "*.example.com".into()];
+        assert!(host_allowed(&allowed, "api.github.com"));
+        assert!(host_allowed(&allowed, "API.GitHub.com"));
+        assert!(!host_allowed(&allowed, "github.com"));
+        assert!(host_allowed(&allowed, "example.com"));
+        assert!(host_allowed(&allowed, "a.b.example.com"));
+        assert!(!host_allowed(&allowed, "badexample.com"));
+        assert!(host_allowed(&["*".to_string()], "anything.test"));
+        assert!(!host_allowed(&[], "localhost"));
+    }
+
+    #[test]
+    fn test_check_url() {
+        let config = config(&["example.com", "127.0.0.1"]);
+        assert!(check_url(&config, "https://example.com/api?q=1").is_ok());
+        assert!(check_url(&config, "http://127.0.0.1:8080/").is_ok());
+        assert!(check_url(&config, "https://other.com/")
+            .unwrap_err()
+            .contains("allowed_hosts"));
+        assert!(check_url(&config, "file:///etc/passwd")
+            .unwrap_err()
+            .contains("scheme"));
+        assert!(check_url(&config, "not a url").is_err());
+    }
+
+    #[test]
+    fn test_fetch_rejects_disallowed_host_without_connecting() {
+        let err = fetch(
+            &PluginFetchConfig::default(),
+            "https://example.com/",
+            FetchOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("allowed_hosts"), "{}", err);
+    }
+
+    /// Serve `count` requests, redirecting `/start` to `location` and
+    /// answering anything else with its path
+    fn redirect_server(location: &'static str, count: usize) -> u16 {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let port = server.server_addr().to_ip().unwrap().port();
+        std::thread::spawn(move || {
+            for request in server.incoming_requests().take(count) {
+                let response = if request.url() == "/start" {
+                    let location = location.replace("{port}", &port.to_string());
+                    tiny_http::Response::from_string("")
+                        .with_status_code(302)
+                        .with_header(
+                            tiny_http::Header::from_bytes(&b"Location"[..], location.as_bytes())
+                                .unwrap(),
+                        )
+                } else {
+                    tiny_http::Response::from_string(request.url())
+                };
+                let _ = request.respond(response);
+            }
+        });
+        port
+    }
+
+    #[test]
+    fn test_fetch_follows_redirects_to_allowed_hosts() {
+        let port = redirect_server("/final", 2);
+        let response = fetch(
+            &config(&["127.0.0.1"]),
+            &format!("http://127.0.0.1:{}/start", port),
+            FetchOptions::default(),
+        )
+        .unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "/final");
+    }
+
+    #[test]
+    fn test_fetch_refuses_redirects_to_disallowed_hosts() {
+        let port = redirect_server("http://localhost:{port}/secret", 2);
+        let err = fetch(
+            &config(&["127.0.0.1"]),
+            &format!("http://127.0.0.1:{}/start", port),
+            FetchOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.contains("Redirect to 'http://localhost:"), "{}", err);
+        assert!(err.contains("allowed_hosts"), "{}", err);
+    }
+}
@@ -7,6 +7,7 @@
 
 use crate::config_io::DirectoryContext;
 use crate::input::command_registry::CommandRegistry;
+use fresh_core::activation::ActivationEvent;
 use fresh_core::config::PluginConfig;
 use std::collections::HashMap;
 use std::path::Path;
@@ -138,7 +139,14 @@ impl PluginManager {
         plugin_configs: &HashMap<String, PluginConfig>,
     ) -> (Vec<String>, HashMap<String, PluginConfig>) {
         if let Some(ref manager) = self.inner {
-            return manager.load_plugins_from_dir_with_config(dir, plugin_configs);
+            let (errors, discovered) =
+                manager.load_plugins_from_dir_with_config(dir, plugin_configs);
+            for (name, config) in &discovered {
+                if config.enabled {
+                    crate::services::crash_report::record_plugin_loaded(name);
+                }
+            }
+            return (errors, discovered);
         }
         (Vec::new(), HashMap::new())
     }
@@ -161,7 +169,9 @@ impl PluginManager {
             self.inner
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("Plugin system not active"))?
-                .unload_plugin(name)
+                .unload_plugin(name)?;
+            crate::services::crash_report::record_plugin_unloaded(name);
+            Ok(())
         }
         #[cfg(not(feature = "plugins"))]
         {
@@ -177,7 +187,11 @@ impl PluginManager {
             self.inner
                 .as_ref()
                 .ok_or_else(|| anyhow::anyhow!("Plugin system not active"))?
-                .load_plugin(path)
+                .load_plugin(path)?;
+            if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
+                crate::services::crash_report::record_plugin_loaded(name);
+            }
+            Ok(())
         }
         #[cfg(not(feature = "plugins"))]
         {
@@ -200,6 +214,140 @@ impl PluginManager {
         }
     }
 
+    /// Report an activation event so pending plugins waiting for it get loaded.
+    pub fn activate(&self, event: ActivationEvent) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.activate(event);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = event;
+        }
+    }
+
+    /// Allow a plugin to use a permission declared in its `plugin.json`.
+    pub fn grant_permission(&self, plugin_name: &str, capability: &str) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.grant_permission(plugin_name, capability);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (plugin_name, capability);
+        }
+    }
+
+    /// Ask a plugin completion provider for items (fire-and-forget).
+    ///
+    /// The items arrive later as `PluginCommand::ProvideCompletions`.
+    pub fn request_completions(
+        &self,
+        provider: &fresh_core::api::CompletionProvider,
+        request_id: u64,
+        request: fresh_core::api::CompletionRequest,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.request_completions(provider, request_id, request);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (provider, request_id, request);
+        }
+    }
+
+    /// Ask a plugin hover provider for markdown (fire-and-forget).
+    ///
+    /// The result arrives later as `PluginCommand::ProvideHover`.
+    pub fn request_hover(
+        &self,
+        provider: &fresh_core::api::HoverProvider,
+        request_id: u64,
+        request: fresh_core::api::HoverRequest,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.request_hover(provider, request_id, request);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (provider, request_id, request);
+        }
+    }
+
+    /// Ask a plugin inline suggestion provider for text to suggest
+    /// (fire-and-forget).
+    ///
+    /// The result arrives later as `PluginCommand::ProvideInlineSuggestion`.
+    pub fn request_inline_suggestion(
+        &self,
+        provider: &fresh_core::api::InlineSuggestionProvider,
+        request_id: u64,
+        request: fresh_core::api::InlineSuggestionRequest,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.request_inline_suggestion(provider, request_id, request);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (provider, request_id, request);
+        }
+    }
+
+    /// Ask a plugin formatter to format a buffer's text (fire-and-forget).
+    ///
+    /// The result arrives later as `PluginCommand::ProvideFormattedText`.
+    pub fn request_format(
+        &self,
+        formatter: &fresh_core::api::PluginFormatter,
+        request_id: u64,
+        request: fresh_core::api::FormatRequest,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.request_format(formatter, request_id, request);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (formatter, request_id, request);
+        }
+    }
+
+    /// Ask a plugin evaluator to evaluate selections (fire-and-forget).
+    ///
+    /// The results arrive later as `PluginCommand::ProvideEvaluation`.
+    pub fn request_evaluation(
+        &self,
+        evaluator: &fresh_core::api::PluginEvaluator,
+        request_id: u64,
+        requests: Vec<fresh_core::api::EvaluateRequest>,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.request_evaluation(evaluator, request_id, requests);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (evaluator, request_id, requests);
+        }
+    }
+
     /// Deliver a response to a pending async plugin operation.
     pub fn deliver_response(&self, response: super::api::PluginResponse) {
         #[cfg(feature = "plugins")]
@@ -314,4 +462,25 @@ impl PluginManager {
     pub fn reject_callback(&self, callback_id: fresh_core::api::JsCallbackId, error: String) {
         let _ = (callback_id, error);
     }
+
+    /// Pass a line of background process output to the listeners of the
+    /// plugin that subscribed to it (fire-and-forget)
+    pub fn deliver_process_output(
+        &self,
+        plugin_name: String,
+        process_id: u64,
+        is_stderr: bool,
+        line: String,
+    ) {
+        #[cfg(feature = "plugins")]
+        {
+            if let Some(ref manager) = self.inner {
+                manager.deliver_process_output(plugin_name, process_id, is_stderr, line);
+            }
+        }
+        #[cfg(not(feature = "plugins"))]
+        {
+            let _ = (plugin_name, process_id, is_stderr, line);
+        }
+    }
 }
@@ -11,8 +11,10 @@ pub mod api {
 }
 pub mod bridge;
 pub mod event_hooks;
+pub mod fetch;
 pub mod hooks;
 pub mod manager;
+pub mod watcher;
 
 #[cfg(feature = "embed-plugins")]
 pub mod embedded;
@@ -0,0 +1,210 @@
+//! Detects changes to plugin files so plugins can be hot-reloaded
+//!
+//! The editor polls the plugin directories it loaded plugins from (like it
+//! polls open files for auto-revert) and reloads a plugin when its source,
+//! its `.i18n.json` strings or the directory's `plugin.json` change.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+/// How often plugin directories are checked for changes
+pub const POLL_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// A change to a plugin found by [`PluginWatcher::poll`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PluginChange {
+    /// The plugin was added, or one of its files was modified
+    Changed { name: String, path: PathBuf },
+    /// The plugin's source file was deleted
+    Removed { name: String },
+}
+
+/// What a file in a plugin directory belongs to
+enum WatchedFile {
+    /// Source of the named plugin
+    Source(String),
+    /// Strings of the named plugin
+    Strings(String),
+    /// Permission manifest of every plugin in the directory
+    Manifest,
+}
+
+impl WatchedFile {
+    fn classify(path: &Path) -> Option<Self> {
+        let file_name = path.file_name()?.to_str()?;
+        if file_name == "plugin.json" {
+            return Some(Self::Manifest);
+        }
+        if let Some(name) = file_name.strip_suffix(".i18n.json") {
+            return Some(Self::Strings(name.to_string()));
+        }
+        let ext = path.extension()?.to_str()?;
+        if ext == "ts" || ext == "js" {
+            let name = path.file_stem()?.to_str()?;
+            return Some(Self::Source(name.to_string()));
+        }
+        None
+    }
+}
+
+/// Modification time and size of a file, to notice when it is rewritten
+type Stamp = (Option<SystemTime>, u64);
+
+/// Watches plugin directories for changed plugin files
+pub struct PluginWatcher {
+    dirs: Vec<PathBuf>,
+    /// Stamp of every watched file, per directory
+    files: HashMap<PathBuf, HashMap<PathBuf, Stamp>>,
+}
+
+impl PluginWatcher {
+    /// Watch the given plugin directories, taking their current state as unchanged
+    pub fn new(dirs: Vec<PathBuf>) -> Self {
+        let files = dirs.iter().map(|dir| (dir.clone(), scan(dir))).collect();
+        Self { dirs, files }
+    }
+
+    /// Plugins whose files changed since the last poll
+    pub fn poll(&mut self) -> Vec<PluginChange> {
+        let mut changes = Vec::new();
+        for dir in &self.dirs {
+            let current = scan(dir);
+            let previous = self.files.insert(dir.clone(), current.clone());
+            let previous = previous.unwrap_or_default();
+
+            // Plugin sources currently in the directory, by name
+            let sources: HashMap<String, &PathBuf> = current
+                .keys()
+                .filter_map(|path| match WatchedFile::classify(path) {
+                    Some(WatchedFile::Source(name)) => Some((name, path)),
+                    _ => None,
+                })
+                .collect();
+
+            let mut changed = BTreeSet::new();
+            let mut removed = BTreeSet::new();
+            let paths: BTreeSet<&PathBuf> = previous.keys().chain(current.keys()).collect();
+            for path in paths {
+                if previous.get(path) == current.get(path) {
+                    continue;
+                }
+                match WatchedFile::classify(path) {
+                    Some(WatchedFile::Source(name)) if !current.contains_key(path) => {
+                        removed.insert(name);
+                    }
+                    Some(WatchedFile::Source(name)) | Some(WatchedFile::Strings(name)) => {
+                        changed.insert(name);
+                    }
+                    Some(WatchedFile::Manifest) => changed.extend(sources.keys().cloned()),
+                    None => {}
+                }
+            }
+
+            for name in changed {
+                if let Some(path) = sources.get(&name) {
+                    changes.push(PluginChange::Changed {
+                        name,
+                        path: (*path).clone(),
+                    });
+                }
+            }
+            changes.extend(
+                removed
+                    .into_iter()
+                    .filter(|name| !sources.contains_key(name))
+                    .map(|name| PluginChange::Removed { name }),
+            );
+        }
+        changes
+    }
+}
+
+/// Stamps of the plugin files directly inside `dir`
+fn scan(dir: &Path) -> HashMap<PathBuf, Stamp> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return HashMap::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| WatchedFile::classify(&entry.path()).is_some())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            metadata
+                .is_file()
+                .then(|| (entry.path(), (metadata.modified().ok(), metadata.len())))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_poll_reports_changed_and_removed_plugins() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let plugin = dir.path().join("greeter.ts");
+        fs::write(&plugin, "editor.setStatus('hi');").unwrap();
+        fs::write(dir.path().join("README.md"), "docs").unwrap();
+
+        let mut watcher = PluginWatcher::new(vec![dir.path().to_path_buf()]);
+        assert!(watcher.poll().is_empty());
+
+        fs::write(&plugin, "editor.setStatus('hello');").unwrap();
+        fs::write(dir.path().join("README.md"), "more docs").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![PluginChange::Changed {
+                name: "greeter".to_string(),
+                path: plugin.clone(),
+            }]
+        );
+        assert!(watcher.poll().is_empty());
+
+        fs::write(dir.path().join("greeter.i18n.json"), "{}").unwrap();
+        let added = dir.path().join("other.js");
+        fs::write(&added, "").unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![
+                PluginChange::Changed {
+                    name: "greeter".to_string(),
+                    path: plugin.clone(),
+                },
+                PluginChange::Changed {
+                    name: "other".to_string(),
+                    path: added.clone(),
+                },
+            ]
+        );
+
+        fs::remove_file(&added).unwrap();
+        assert_eq!(
+            watcher.poll(),
+            vec![PluginChange::Removed {
+                name: "other".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn test_manifest_change_reloads_every_plugin_in_directory() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("a.ts"), "").unwrap();
+        fs::write(dir.path().join("b.ts"), "").unwrap();
+        let mut watcher = PluginWatcher::new(vec![dir.path().to_path_buf()]);
+
+        fs::write(dir.path().join("plugin.json"), r#"{"capabilities": []}"#).unwrap();
+        let names: Vec<String> = watcher
+            .poll()
+            .into_iter()
+            .map(|change| match change {
+                PluginChange::Changed { name, .. } => name,
+                PluginChange::Removed { name } => panic!("{} reported removed", name),
+            })
+            .collect();
+        assert_eq!(names, vec!["a", "b"]);
+    }
+}
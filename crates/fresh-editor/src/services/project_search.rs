@@ -0,0 +1,218 @@
+//! Project-wide text search
+//!
+//! Walks a directory tree in path order, honoring `.gitignore` and skipping
+//! hidden, binary and oversized files, and reports matches one file at a time
+//! so results can be shown while the walk is still running.
+
+use regex::Regex;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Longest line excerpt kept for a match, in bytes
+const MAX_EXCERPT_LEN: usize = 240;
+
+/// Report progress at least this often (in files searched), even without matches
+const PROGRESS_INTERVAL: usize = 200;
+
+/// Bytes inspected when deciding whether a file is binary
+const BINARY_CHECK_LEN: usize = 8192;
+
+/// What to search for, using the same options as in-buffer search
+#[derive(Debug, Clone)]
+pub struct ProjectSearchQuery {
+    pub pattern: String,
+    pub case_sensitive: bool,
+    pub whole_word: bool,
+    pub use_regex: bool,
+}
+
+impl ProjectSearchQuery {
+    /// Compile the query into a regex
+    pub fn to_regex(&self) -> Result<Regex, regex::Error> {
+        let pattern = if self.use_regex {
+            self.pattern.clone()
+        } else {
+            regex::escape(&self.pattern)
+        };
+        let pattern = if self.whole_word {
+            format!(r"\b{}\b", pattern)
+        } else {
+            pattern
+        };
+        regex::RegexBuilder::new(&pattern)
+            .case_insensitive(!self.case_sensitive)
+            .build()
+    }
+}
+
+/// A line containing at least one match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectSearchMatch {
+    pub path: PathBuf,
+    /// 1-indexed line number
+    pub line: usize,
+    /// 1-indexed byte column of the first match on the line
+    pub column: usize,
+    /// The line's text, without its line ending (truncated if very long)
+    pub text: String,
+}
+
+/// Totals for a finished search
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProjectSearchSummary {
+    pub files_searched: usize,
+    pub cancelled: bool,
+}
+
+/// Search every file under `root`.
+///
+/// `on_progress` is called with the matches of each file that has any, in
+/// path order, along with the number of files searched so far. It is also
+/// called periodically with no matches so callers can keep a live counter.
+/// The walk stops early once `cancel` is set.
+pub fn search_project(
+    root: &Path,
+    regex: &Regex,
+    max_file_size: u64,
+    cancel: &AtomicBool,
+    mut on_progress: impl FnMut(Vec<ProjectSearchMatch>, usize),
+) -> ProjectSearchSummary {
+    let walker = ignore::WalkBuilder::new(root)
+        .require_git(false)
+        .sort_by_file_path(|a, b| a.cmp(b))
+        .build();
+
+    let mut files_searched = 0;
+    for entry in walker {
+        if cancel.load(Ordering::Relaxed) {
+            return ProjectSearchSummary {
+                files_searched,
+                cancelled: true,
+            };
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if entry.metadata().map_or(true, |m| m.len() > max_file_size) {
+            continue;
+        }
+        let Ok(bytes) = std::fs::read(entry.path()) else {
+            continue;
+        };
+        if bytes[..bytes.len().min(BINARY_CHECK_LEN)].contains(&0) {
+            continue;
+        }
+
+        files_searched += 1;
+        let matches = search_text(entry.path(), &String::from_utf8_lossy(&bytes), regex);
+        if !matches.is_empty() || files_searched % PROGRESS_INTERVAL == 0 {
+            on_progress(matches, files_searched);
+        }
+    }
+
+    ProjectSearchSummary {
+        files_searched,
+        cancelled: false,
+    }
+}
+
+/// Find the lines of `text` matching `regex`
+fn search_text(path: &Path, text: &str, regex: &Regex) -> Vec<ProjectSearchMatch> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let m = regex.find(line)?;
+            let mut end = line.len().min(MAX_EXCERPT_LEN);
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            Some(ProjectSearchMatch {
+                path: path.to_path_buf(),
+                line: idx + 1,
+                column: m.start() + 1,
+                text: line[..end].to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(pattern: &str) -> ProjectSearchQuery {
+        ProjectSearchQuery {
+            pattern: pattern.to_string(),
+            case_sensitive: true,
+            whole_word: false,
+            use_regex: false,
+        }
+    }
+
+    #[test]
+    fn test_query_options() {
+        assert!(query("a.b").to_regex().unwrap().is_match("a.b"));
+        assert!(!query("a.b").to_regex().unwrap().is_match("axb"));
+
+        let mut q = query("foo");
+        q.whole_word = true;
+        q.case_sensitive = false;
+        let regex = q.to_regex().unwrap();
+        assert!(regex.is_match("let FOO = 1"));
+        assert!(!regex.is_match("foobar"));
+    }
+
+    #[test]
+    fn test_search_project_reports_files_in_path_order() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("b")).unwrap();
+        std::fs::write(dir.path().join("c.txt"), "needle\n").unwrap();
+        std::fs::write(dir.path().join("b/a.txt"), "x\n  a needle here\n").unwrap();
+        std::fs::write(dir.path().join("a.txt"), "nothing\n").unwrap();
+        std::fs::write(dir.path().join("bin.dat"), b"needle\0").unwrap();
+
+        let regex = query("needle").to_regex().unwrap();
+        let mut found = Vec::new();
+        let summary = search_project(
+            dir.path(),
+            &regex,
+            u64::MAX,
+            &AtomicBool::new(false),
+            |matches, _| found.extend(matches),
+        );
+
+        assert_eq!(summary.files_searched, 3);
+        assert!(!summary.cancelled);
+        let found: Vec<_> = found
+            .iter()
+            .map(|m| {
+                let rel = m.path.strip_prefix(dir.path()).unwrap();
+                (rel.to_string_lossy().replace('\\', "/"), m.line, m.column)
+            })
+            .collect();
+        assert_eq!(
+            found,
+            vec![("b/a.txt".to_string(), 2, 5), ("c.txt".to_string(), 1, 1)]
+        );
+    }
+
+    #[test]
+    fn test_search_project_stops_when_cancelled() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "needle\n").unwrap();
+
+        let regex = query("needle").to_regex().unwrap();
+        let summary = search_project(
+            dir.path(),
+            &regex,
+            u64::MAX,
+            &AtomicBool::new(true),
+            |_, _| panic!("no results expected after cancel"),
+        );
+        assert!(summary.cancelled);
+        assert_eq!(summary.files_searched, 0);
+    }
+}
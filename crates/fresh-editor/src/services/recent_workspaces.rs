@@ -0,0 +1,192 @@
+//! Recently opened workspaces
+//!
+//! Each directory the editor is opened on (or switched to) is recorded with
+//! the time it was last opened in `{data_dir}/recent_workspaces.json`, which
+//! backs the Open Recent Workspace picker. Pinned workspaces are listed first
+//! and kept until they are unpinned; the others are dropped once their
+//! directory no longer exists or when more than [`MAX_UNPINNED`] are recorded.
+
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// How many workspaces that aren't pinned are remembered
+pub const MAX_UNPINNED: usize = 30;
+
+/// A workspace in the recent list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentWorkspace {
+    /// Workspace directory
+    pub path: PathBuf,
+    /// When the workspace was last opened (Unix epoch seconds)
+    pub last_opened: u64,
+    /// Pinned workspaces are listed first and never dropped
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// The recent workspace list
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentWorkspaces {
+    #[serde(default)]
+    workspaces: Vec<RecentWorkspace>,
+}
+
+impl RecentWorkspaces {
+    /// Load the list, starting empty if the file is missing or unreadable
+    pub fn load(path: &Path) -> Self {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Self::default();
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring unreadable recent workspaces {:?}: {}", path, e);
+            Self::default()
+        })
+    }
+
+    /// Save the list (atomic write: temp file + rename)
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        let temp_path = path.with_extension("json.tmp");
+        {
+            let mut file = std::fs::File::create(&temp_path)?;
+            file.write_all(content.as_bytes())?;
+            file.sync_all()?;
+        }
+        std::fs::rename(&temp_path, path)
+    }
+
+    /// Workspaces to offer: pinned ones first, then most recently opened first
+    pub fn sorted(&self) -> Vec<&RecentWorkspace> {
+        let mut workspaces: Vec<&RecentWorkspace> = self.workspaces.iter().collect();
+        workspaces.sort_by(|a, b| {
+            b.pinned
+                .cmp(&a.pinned)
+                .then(b.last_opened.cmp(&a.last_opened))
+        });
+        workspaces
+    }
+
+    pub fn get(&self, path: &Path) -> Option<&RecentWorkspace> {
+        self.workspaces.iter().find(|w| w.path == path)
+    }
+
+    /// Record that a workspace was opened at `now` (Unix epoch seconds)
+    pub fn record(&mut self, path: &Path, now: u64) {
+        match self.workspaces.iter_mut().find(|w| w.path == path) {
+            Some(workspace) => workspace.last_opened = now,
+            None => self.workspaces.push(RecentWorkspace {
+                path: path.to_path_buf(),
+                last_opened: now,
+                pinned: false,
+            }),
+        }
+    }
+
+    /// Pin or unpin a workspace, recording it first if needed.
+    /// Returns whether it is now pinned.
+    pub fn toggle_pin(&mut self, path: &Path, now: u64) -> bool {
+        if self.get(path).is_none() {
+            self.record(path, now);
+        }
+        let workspace = self
+            .workspaces
+            .iter_mut()
+            .find(|w| w.path == path)
+            .expect("workspace was just recorded");
+        workspace.pinned = !workspace.pinned;
+        workspace.pinned
+    }
+
+    /// Drop workspaces that aren't pinned when `exists` says their directory
+    /// is gone, and the oldest ones beyond [`MAX_UNPINNED`]
+    pub fn prune(&mut self, exists: impl Fn(&Path) -> bool) {
+        self.workspaces
+            .sort_by(|a, b| b.last_opened.cmp(&a.last_opened));
+        let mut unpinned = 0;
+        self.workspaces.retain(|w| {
+            if w.pinned {
+                return true;
+            }
+            if !exists(&w.path) {
+                return false;
+            }
+            unpinned += 1;
+            unpinned <= MAX_UNPINNED
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn paths(list: &RecentWorkspaces) -> Vec<&str> {
+        list.sorted()
+            .into_iter()
+            .map(|w| w.path.to_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_pinned_first_then_most_recent() {
+        let mut list = RecentWorkspaces::default();
+        list.record(Path::new("/a"), 10);
+        list.record(Path::new("/b"), 20);
+        list.record(Path::new("/c"), 30);
+        assert_eq!(paths(&list), vec!["/c", "/b", "/a"]);
+
+        assert!(list.toggle_pin(Path::new("/a"), 40));
+        list.record(Path::new("/b"), 50);
+        assert_eq!(paths(&list), vec!["/a", "/b", "/c"]);
+        assert_eq!(list.get(Path::new("/b")).unwrap().last_opened, 50);
+
+        assert!(!list.toggle_pin(Path::new("/a"), 60));
+        assert_eq!(paths(&list), vec!["/b", "/c", "/a"]);
+
+        // Pinning a workspace that was never opened records it
+        assert!(list.toggle_pin(Path::new("/d"), 70));
+        assert_eq!(paths(&list)[0], "/d");
+    }
+
+    #[test]
+    fn test_prune_drops_missing_and_oldest() {
+        let mut list = RecentWorkspaces::default();
+        list.record(Path::new("/gone"), 1);
+        list.record(Path::new("/gone-pinned"), 1);
+        list.toggle_pin(Path::new("/gone-pinned"), 1);
+        for i in 0..MAX_UNPINNED as u64 + 5 {
+            list.record(Path::new(&format!("/w{}", i)), 100 + i);
+        }
+
+        list.prune(|path| !path.to_str().unwrap().starts_with("/gone"));
+        let kept = paths(&list);
+        assert_eq!(kept.len(), MAX_UNPINNED + 1);
+        assert_eq!(kept[0], "/gone-pinned");
+        assert!(!kept.contains(&"/gone"));
+        assert!(!kept.contains(&"/w4"), "the oldest are dropped");
+        assert!(kept.contains(&"/w5"));
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir
+            .path()
+            .join("nested")
+            .join("recent_workspaces.json");
+        assert_eq!(RecentWorkspaces::load(&path), RecentWorkspaces::default());
+
+        let mut list = RecentWorkspaces::default();
+        list.record(Path::new("/project"), 42);
+        list.toggle_pin(Path::new("/project"), 42);
+        list.save(&path).unwrap();
+        assert_eq!(RecentWorkspaces::load(&path), list);
+
+        std::fs::write(&path, "not json").unwrap();
+        assert_eq!(RecentWorkspaces::load(&path), RecentWorkspaces::default());
+    }
+}
@@ -17,6 +17,8 @@ VERSION = 1
 procs = {}
 # Request IDs marked for cancellation
 cancelled = set()
+# Active watches: canonical path -> (watch request id, stop Event)
+watches = {}
 # Lock for thread-safe access
 lock = threading.Lock()
 
@@ -154,6 +156,7 @@ def cmd_stat(id, p):
         r={
             "size": st.st_size,
             "mtime": int(st.st_mtime),
+            "mtime_ns": st.st_mtime_ns,
             "mode": st.st_mode,
             "uid": st.st_uid,
             "gid": st.st_gid,
@@ -332,6 +335,109 @@ def cmd_info(id, p):
     })
 
 
+# === Watch Operations ===
+
+
+def watch_snapshot(path):
+    """Snapshot a path for change detection.
+
+    Returns None if the path doesn't exist. For directories, also records
+    each child's (mtime, size) so changes to files inside are noticed.
+    """
+    try:
+        st = os.stat(path)
+    except OSError:
+        return None
+    children = None
+    if stat.S_ISDIR(st.st_mode):
+        children = {}
+        try:
+            for entry in os.scandir(path):
+                try:
+                    est = entry.stat(follow_symlinks=False)
+                    children[entry.name] = (est.st_mtime_ns, est.st_size)
+                except OSError:
+                    pass
+        except OSError:
+            pass
+    return ((st.st_mtime_ns, st.st_size, st.st_ino), children)
+
+
+def watch_events(old, new):
+    """Compare two snapshots, yielding (kind, child name or None) events."""
+    if old is None and new is None:
+        return
+    if old is None:
+        yield ("created", None)
+        return
+    if new is None:
+        yield ("removed", None)
+        return
+    old_children, new_children = old[1], new[1]
+    if old_children is None or new_children is None:
+        if old[0] != new[0]:
+            yield ("modified", None)
+        return
+    for name, sig in new_children.items():
+        if name not in old_children:
+            yield ("created", name)
+        elif old_children[name] != sig:
+            yield ("modified", name)
+    for name in old_children:
+        if name not in new_children:
+            yield ("removed", name)
+
+
+def cmd_watch(id, p):
+    """Watch a file or directory, streaming change events until unwatched.
+
+    Polls the path every `interval_ms` (default 500). Each change is sent as
+    {"kind": "created"|"modified"|"removed"} for the watched path itself, with
+    a "name" field for changes to entries inside a watched directory.
+    """
+    path = validate_path(p["path"])
+    interval = p.get("interval_ms", 500) / 1000.0
+    stop = threading.Event()
+
+    with lock:
+        previous = watches.pop(path, None)
+        watches[path] = (id, stop)
+    if previous:
+        previous[1].set()
+        send(previous[0], r={})
+
+    def poll():
+        snapshot = watch_snapshot(path)
+        while not stop.wait(interval):
+            if id in cancelled:
+                with lock:
+                    if watches.get(path, (None,))[0] == id:
+                        watches.pop(path)
+                    cancelled.discard(id)
+                send(id, e="cancelled")
+                return
+            current = watch_snapshot(path)
+            for kind, name in watch_events(snapshot, current):
+                event = {"kind": kind}
+                if name is not None:
+                    event["name"] = name
+                send(id, d=event)
+            snapshot = current
+
+    threading.Thread(target=poll, daemon=True).start()
+
+
+def cmd_unwatch(id, p):
+    """Stop watching a path, completing its watch request."""
+    path = validate_path(p["path"])
+    with lock:
+        entry = watches.pop(path, None)
+    if entry:
+        entry[1].set()
+        send(entry[0], r={})
+    send(id, r={"watching": entry is not None})
+
+
 # === Process Operations ===
 
 
@@ -450,6 +556,8 @@ METHODS = {
     "patch": cmd_patch,
     "exists": cmd_exists,
     "info": cmd_info,
+    "watch": cmd_watch,
+    "unwatch": cmd_unwatch,
     "exec": cmd_exec,
     "kill": cmd_kill,
     "cancel": cmd_cancel,
@@ -0,0 +1,168 @@
+//! Audit trail of operations that change the remote host
+//!
+//! Every write, delete and process spawn sent to the agent is recorded with
+//! when it happened and who asked for it, so an editing session on a
+//! production host can be reviewed (and exported) afterwards.
+
+use chrono::{DateTime, Local};
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::sync::{Arc, Mutex};
+
+/// Entries kept before the oldest are dropped
+const MAX_AUDIT_ENTRIES: usize = 10_000;
+
+/// Who initiated an operation
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditSource {
+    /// An editor command (save, file explorer, ...)
+    User,
+    /// A plugin (process spawns)
+    Plugin,
+}
+
+impl AuditSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Plugin => "plugin",
+        }
+    }
+}
+
+/// One recorded operation
+#[derive(Debug, Clone)]
+pub struct AuditEntry {
+    pub time: DateTime<Local>,
+    pub source: AuditSource,
+    /// Agent method, e.g. `write`, `rm` or `exec`
+    pub operation: String,
+    /// Path (or command line) the operation acted on
+    pub target: String,
+    /// Error message when the operation failed
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    /// The entry as a single JSON object
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "time": self.time.to_rfc3339(),
+            "source": self.source.as_str(),
+            "operation": self.operation,
+            "target": self.target,
+            "error": self.error,
+        })
+    }
+}
+
+/// Shared, bounded log of remote operations
+///
+/// Clones share the same entries, so the filesystem, the process spawner and
+/// the editor all see one trail.
+#[derive(Debug, Clone, Default)]
+pub struct AuditLog {
+    entries: Arc<Mutex<VecDeque<AuditEntry>>>,
+}
+
+impl AuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Record an operation and whether it succeeded
+    pub fn record<T, E: Display>(
+        &self,
+        source: AuditSource,
+        operation: &str,
+        target: impl Into<String>,
+        result: &Result<T, E>,
+    ) {
+        let entry = AuditEntry {
+            time: Local::now(),
+            source,
+            operation: operation.to_string(),
+            target: target.into(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        };
+        let Ok(mut entries) = self.entries.lock() else {
+            return;
+        };
+        if entries.len() == MAX_AUDIT_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+
+    /// All entries, oldest first
+    pub fn entries(&self) -> Vec<AuditEntry> {
+        self.entries
+            .lock()
+            .map(|entries| entries.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+
+    /// The log as JSON Lines, oldest first
+    pub fn export_json_lines(&self) -> String {
+        self.entries()
+            .iter()
+            .map(|entry| format!("{}\n", entry.to_json()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_export() {
+        let log = AuditLog::new();
+        let shared = log.clone();
+        log.record(
+            AuditSource::User,
+            "write",
+            "/etc/hosts",
+            &Ok::<(), String>(()),
+        );
+        shared.record(
+            AuditSource::Plugin,
+            "exec",
+            "rg foo",
+            &Err::<(), _>("not found"),
+        );
+
+        let entries = log.entries();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].operation, "write");
+        assert_eq!(entries[0].error, None);
+        assert_eq!(entries[1].source, AuditSource::Plugin);
+        assert_eq!(entries[1].error.as_deref(), Some("not found"));
+
+        let lines: Vec<serde_json::Value> = log
+            .export_json_lines()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines[0]["source"], "user");
+        assert_eq!(lines[0]["target"], "/etc/hosts");
+        assert_eq!(lines[0]["error"], serde_json::Value::Null);
+        assert_eq!(lines[1]["operation"], "exec");
+    }
+
+    #[test]
+    fn test_oldest_entries_are_dropped() {
+        let log = AuditLog::new();
+        for i in 0..MAX_AUDIT_ENTRIES + 1 {
+            log.record(
+                AuditSource::User,
+                "rm",
+                i.to_string(),
+                &Ok::<(), String>(()),
+            );
+        }
+        let entries = log.entries();
+        assert_eq!(entries.len(), MAX_AUDIT_ENTRIES);
+        assert_eq!(entries[0].target, "1");
+    }
+}
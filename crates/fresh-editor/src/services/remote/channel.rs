@@ -258,6 +258,31 @@ impl AgentChannel {
             .block_on(self.request_with_data(method, params))
     }
 
+    /// Send a long-lived streaming request, calling `on_data` for each chunk
+    ///
+    /// Returns once the request is sent; chunks are delivered from a task on
+    /// the channel's runtime until the agent completes the request. This can
+    /// be called from outside the Tokio runtime context.
+    pub fn subscribe_blocking(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        on_data: impl Fn(serde_json::Value) + Send + 'static,
+    ) -> Result<(), ChannelError> {
+        let (mut data_rx, result_rx) = self
+            .runtime_handle
+            .block_on(self.request_streaming(method, params))?;
+        self.runtime_handle.spawn(async move {
+            while let Some(chunk) = data_rx.recv().await {
+                on_data(chunk);
+            }
+            if let Ok(Err(e)) = result_rx.await {
+                tracing::debug!("Agent subscription ended with error: {}", e);
+            }
+        });
+        Ok(())
+    }
+
     /// Cancel a request
     pub async fn cancel(&self, request_id: u64) -> Result<(), ChannelError> {
         use crate::services::remote::protocol::cancel_params;
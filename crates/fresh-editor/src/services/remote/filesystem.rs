@@ -3,23 +3,31 @@
 //! Implements the FileSystem trait for remote operations via SSH agent.
 
 use crate::model::filesystem::{
-    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter, WriteOp,
+    DirEntry, EntryType, FileMetadata, FilePermissions, FileReader, FileSystem, FileWriter,
+    WatchCallback, WriteOp,
 };
+use crate::services::remote::audit::{AuditLog, AuditSource};
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{
     append_params, decode_base64, ls_params, patch_params, read_params, stat_params,
-    sudo_write_params, truncate_params, write_params, PatchOp, RemoteDirEntry, RemoteMetadata,
+    sudo_write_params, truncate_params, unwatch_params, watch_params, write_params, PatchOp,
+    RemoteDirEntry, RemoteMetadata, RemoteWatchEvent,
 };
 use std::io::{self, Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, UNIX_EPOCH};
 
+/// How often the agent checks watched paths for changes
+const WATCH_INTERVAL_MS: u64 = 500;
+
 /// Remote filesystem that communicates with the Python agent
 pub struct RemoteFileSystem {
     channel: Arc<AgentChannel>,
     /// Display string for the connection
     connection_string: String,
+    /// Where writes and deletes are recorded
+    audit: AuditLog,
 }
 
 impl RemoteFileSystem {
@@ -28,9 +36,29 @@ impl RemoteFileSystem {
         Self {
             channel,
             connection_string,
+            audit: AuditLog::new(),
         }
     }
 
+    /// Record writes and deletes in `audit` (shared with the process spawner)
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = audit;
+        self
+    }
+
+    /// Send a request that changes the remote filesystem, recording it in the audit log
+    fn request_mutation(
+        &self,
+        method: &str,
+        target: &Path,
+        params: serde_json::Value,
+    ) -> io::Result<serde_json::Value> {
+        let result = self.channel.request_blocking(method, params);
+        self.audit
+            .record(AuditSource::User, method, target.to_string_lossy(), &result);
+        result.map_err(Self::to_io_error)
+    }
+
     /// Get the connection string for display
     pub fn connection_string(&self) -> &str {
         &self.connection_string
@@ -65,10 +93,10 @@ impl RemoteFileSystem {
 
     /// Convert remote metadata to FileMetadata
     fn convert_metadata(rm: &RemoteMetadata, name: &str) -> FileMetadata {
-        let modified = if rm.mtime > 0 {
-            Some(UNIX_EPOCH + Duration::from_secs(rm.mtime as u64))
-        } else {
-            None
+        let modified = match rm.mtime_ns {
+            Some(ns) if ns > 0 => Some(UNIX_EPOCH + Duration::from_nanos(ns as u64)),
+            _ if rm.mtime > 0 => Some(UNIX_EPOCH + Duration::from_secs(rm.mtime as u64)),
+            _ => None,
         };
 
         let is_hidden = name.starts_with('.');
@@ -173,9 +201,7 @@ impl FileSystem for RemoteFileSystem {
 
     fn write_file(&self, path: &Path, data: &[u8]) -> io::Result<()> {
         let path_str = path.to_string_lossy();
-        self.channel
-            .request_blocking("write", write_params(&path_str, data))
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("write", path, write_params(&path_str, data))?;
         Ok(())
     }
 
@@ -184,6 +210,7 @@ impl FileSystem for RemoteFileSystem {
         self.write_file(path, &[])?;
         Ok(Box::new(RemoteFileWriter::new(
             self.channel.clone(),
+            self.audit.clone(),
             path.to_path_buf(),
         )))
     }
@@ -197,6 +224,7 @@ impl FileSystem for RemoteFileSystem {
     fn open_file_for_write(&self, path: &Path) -> io::Result<Box<dyn FileWriter>> {
         Ok(Box::new(RemoteFileWriter::new(
             self.channel.clone(),
+            self.audit.clone(),
             path.to_path_buf(),
         )))
     }
@@ -205,15 +233,14 @@ impl FileSystem for RemoteFileSystem {
         // Use append-only writer that sends only new data
         Ok(Box::new(AppendingRemoteFileWriter::new(
             self.channel.clone(),
+            self.audit.clone(),
             path.to_path_buf(),
         )))
     }
 
     fn set_file_length(&self, path: &Path, len: u64) -> io::Result<()> {
         let path_str = path.to_string_lossy();
-        self.channel
-            .request_blocking("truncate", truncate_params(&path_str, len))
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("truncate", path, truncate_params(&path_str, len))?;
         Ok(())
     }
 
@@ -235,9 +262,11 @@ impl FileSystem for RemoteFileSystem {
             Some(dst_str.as_ref())
         };
 
-        self.channel
-            .request_blocking("patch", patch_params(&src_str, dst_param, &patch_ops))
-            .map_err(Self::to_io_error)?;
+        self.request_mutation(
+            "patch",
+            dst_path,
+            patch_params(&src_str, dst_param, &patch_ops),
+        )?;
         Ok(())
     }
 
@@ -246,9 +275,7 @@ impl FileSystem for RemoteFileSystem {
             "from": from.to_string_lossy(),
             "to": to.to_string_lossy()
         });
-        self.channel
-            .request_blocking("mv", params)
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("mv", to, params)?;
         Ok(())
     }
 
@@ -257,27 +284,20 @@ impl FileSystem for RemoteFileSystem {
             "from": from.to_string_lossy(),
             "to": to.to_string_lossy()
         });
-        let result = self
-            .channel
-            .request_blocking("cp", params)
-            .map_err(Self::to_io_error)?;
+        let result = self.request_mutation("cp", to, params)?;
 
         Ok(result.get("size").and_then(|v| v.as_u64()).unwrap_or(0))
     }
 
     fn remove_file(&self, path: &Path) -> io::Result<()> {
         let params = serde_json::json!({"path": path.to_string_lossy()});
-        self.channel
-            .request_blocking("rm", params)
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("rm", path, params)?;
         Ok(())
     }
 
     fn remove_dir(&self, path: &Path) -> io::Result<()> {
         let params = serde_json::json!({"path": path.to_string_lossy()});
-        self.channel
-            .request_blocking("rmdir", params)
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("rmdir", path, params)?;
         Ok(())
     }
 
@@ -345,9 +365,7 @@ impl FileSystem for RemoteFileSystem {
                 "path": path.to_string_lossy(),
                 "mode": permissions.mode()
             });
-            self.channel
-                .request_blocking("chmod", params)
-                .map_err(Self::to_io_error)?;
+            self.request_mutation("chmod", path, params)?;
         }
         #[cfg(not(unix))]
         {
@@ -373,9 +391,7 @@ impl FileSystem for RemoteFileSystem {
 
     fn create_dir(&self, path: &Path) -> io::Result<()> {
         let params = serde_json::json!({"path": path.to_string_lossy()});
-        self.channel
-            .request_blocking("mkdir", params)
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("mkdir", path, params)?;
         Ok(())
     }
 
@@ -384,9 +400,7 @@ impl FileSystem for RemoteFileSystem {
             "path": path.to_string_lossy(),
             "parents": true
         });
-        self.channel
-            .request_blocking("mkdir", params)
-            .map_err(Self::to_io_error)?;
+        self.request_mutation("mkdir", path, params)?;
         Ok(())
     }
 
@@ -414,6 +428,39 @@ impl FileSystem for RemoteFileSystem {
         Some(&self.connection_string)
     }
 
+    fn supports_watch(&self) -> bool {
+        true
+    }
+
+    fn watch(&self, path: &Path, on_change: WatchCallback) -> io::Result<()> {
+        let path_str = path.to_string_lossy();
+        let watched = path.to_path_buf();
+        self.channel
+            .subscribe_blocking(
+                "watch",
+                watch_params(&path_str, WATCH_INTERVAL_MS),
+                move |data| match serde_json::from_value::<RemoteWatchEvent>(data) {
+                    Ok(event) => {
+                        tracing::trace!("Remote watch event for {:?}: {:?}", watched, event);
+                        match event.name {
+                            Some(name) => on_change(watched.join(name)),
+                            None => on_change(watched.clone()),
+                        }
+                    }
+                    Err(e) => tracing::warn!("Invalid remote watch event: {}", e),
+                },
+            )
+            .map_err(Self::to_io_error)
+    }
+
+    fn unwatch(&self, path: &Path) -> io::Result<()> {
+        let path_str = path.to_string_lossy();
+        self.channel
+            .request_blocking("unwatch", unwatch_params(&path_str))
+            .map_err(Self::to_io_error)?;
+        Ok(())
+    }
+
     fn home_dir(&self) -> io::Result<PathBuf> {
         let result = self
             .channel
@@ -454,12 +501,11 @@ impl FileSystem for RemoteFileSystem {
         gid: u32,
     ) -> io::Result<()> {
         let path_str = path.to_string_lossy();
-        self.channel
-            .request_blocking(
-                "sudo_write",
-                sudo_write_params(&path_str, data, mode, uid, gid),
-            )
-            .map_err(Self::to_io_error)?;
+        self.request_mutation(
+            "sudo_write",
+            path,
+            sudo_write_params(&path_str, data, mode, uid, gid),
+        )?;
         Ok(())
     }
 }
@@ -494,14 +540,16 @@ impl FileReader for RemoteFileReader {}
 /// Remote file writer - buffers writes and flushes on sync
 struct RemoteFileWriter {
     channel: Arc<AgentChannel>,
+    audit: AuditLog,
     path: PathBuf,
     buffer: Vec<u8>,
 }
 
 impl RemoteFileWriter {
-    fn new(channel: Arc<AgentChannel>, path: PathBuf) -> Self {
+    fn new(channel: Arc<AgentChannel>, audit: AuditLog, path: PathBuf) -> Self {
         Self {
             channel,
+            audit,
             path,
             buffer: Vec::new(),
         }
@@ -523,9 +571,12 @@ impl Write for RemoteFileWriter {
 impl FileWriter for RemoteFileWriter {
     fn sync_all(&self) -> io::Result<()> {
         let path_str = self.path.to_string_lossy();
-        self.channel
-            .request_blocking("write", write_params(&path_str, &self.buffer))
-            .map_err(RemoteFileSystem::to_io_error)?;
+        let result = self
+            .channel
+            .request_blocking("write", write_params(&path_str, &self.buffer));
+        self.audit
+            .record(AuditSource::User, "write", path_str, &result);
+        result.map_err(RemoteFileSystem::to_io_error)?;
         Ok(())
     }
 }
@@ -533,14 +584,16 @@ impl FileWriter for RemoteFileWriter {
 /// Remote file writer for append operations - only sends new data
 struct AppendingRemoteFileWriter {
     channel: Arc<AgentChannel>,
+    audit: AuditLog,
     path: PathBuf,
     buffer: Vec<u8>,
 }
 
 impl AppendingRemoteFileWriter {
-    fn new(channel: Arc<AgentChannel>, path: PathBuf) -> Self {
+    fn new(channel: Arc<AgentChannel>, audit: AuditLog, path: PathBuf) -> Self {
         Self {
             channel,
+            audit,
             path,
             buffer: Vec::new(),
         }
@@ -564,9 +617,12 @@ impl FileWriter for AppendingRemoteFileWriter {
             return Ok(());
         }
         let path_str = self.path.to_string_lossy();
-        self.channel
-            .request_blocking("append", append_params(&path_str, &self.buffer))
-            .map_err(RemoteFileSystem::to_io_error)?;
+        let result = self
+            .channel
+            .request_blocking("append", append_params(&path_str, &self.buffer));
+        self.audit
+            .record(AuditSource::User, "append", path_str, &result);
+        result.map_err(RemoteFileSystem::to_io_error)?;
         Ok(())
     }
 }
@@ -580,6 +636,7 @@ mod tests {
         let rm = RemoteMetadata {
             size: 1234,
             mtime: 1700000000,
+            mtime_ns: None,
             mode: 0o644,
             uid: 1000,
             gid: 1000,
@@ -3,12 +3,14 @@
 //! This module provides remote file system access and process execution
 //! via an SSH connection to a Python agent running on the remote host.
 
+mod audit;
 mod channel;
 mod connection;
 mod filesystem;
 mod protocol;
 mod spawner;
 
+pub use audit::{AuditEntry, AuditLog, AuditSource};
 pub use channel::AgentChannel;
 /// Re-export for integration tests - spawns a local agent without SSH
 #[doc(hidden)]
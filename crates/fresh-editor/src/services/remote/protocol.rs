@@ -97,6 +97,9 @@ pub struct RemoteDirEntry {
 pub struct RemoteMetadata {
     pub size: u64,
     pub mtime: i64,
+    /// Modification time in nanoseconds (agents before watch support omit it)
+    #[serde(default)]
+    pub mtime_ns: Option<i64>,
     pub mode: u32,
     #[serde(default)]
     pub uid: u32,
@@ -110,6 +113,18 @@ pub struct RemoteMetadata {
     pub link: bool,
 }
 
+/// Change reported by a `watch` request
+#[derive(Debug, Clone, Deserialize)]
+#[allow(dead_code)]
+pub struct RemoteWatchEvent {
+    /// "created", "modified" or "removed"
+    pub kind: String,
+    /// Entry inside a watched directory that changed (absent when the
+    /// watched path itself changed)
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
 /// Process execution result
 #[derive(Debug, Clone, Deserialize)]
 #[allow(dead_code)]
@@ -206,6 +221,19 @@ pub fn cancel_params(request_id: u64) -> serde_json::Value {
     serde_json::json!({"id": request_id})
 }
 
+/// Build params for watch request
+pub fn watch_params(path: &str, interval_ms: u64) -> serde_json::Value {
+    serde_json::json!({
+        "path": path,
+        "interval_ms": interval_ms
+    })
+}
+
+/// Build params for unwatch request
+pub fn unwatch_params(path: &str) -> serde_json::Value {
+    serde_json::json!({"path": path})
+}
+
 /// Build params for append request
 pub fn append_params(path: &str, data: &[u8]) -> serde_json::Value {
     serde_json::json!({
@@ -7,6 +7,7 @@
 //! the Editor's SpawnProcess handler to allow plugins like live_grep,
 //! git_grep, and fuzzy finder to work transparently on remote files.
 
+use crate::services::remote::audit::{AuditLog, AuditSource};
 use crate::services::remote::channel::{AgentChannel, ChannelError};
 use crate::services::remote::protocol::{decode_base64, exec_params};
 use std::sync::Arc;
@@ -90,12 +91,23 @@ impl ProcessSpawner for LocalProcessSpawner {
 /// Remote process spawner via SSH agent
 pub struct RemoteProcessSpawner {
     channel: Arc<AgentChannel>,
+    /// Where spawns are recorded
+    audit: AuditLog,
 }
 
 impl RemoteProcessSpawner {
     /// Create a new remote process spawner
     pub fn new(channel: Arc<AgentChannel>) -> Self {
-        Self { channel }
+        Self {
+            channel,
+            audit: AuditLog::new(),
+        }
+    }
+
+    /// Record spawns in `audit` (shared with the remote filesystem)
+    pub fn with_audit_log(mut self, audit: AuditLog) -> Self {
+        self.audit = audit;
+        self
     }
 }
 
@@ -108,7 +120,21 @@ impl ProcessSpawner for RemoteProcessSpawner {
         cwd: Option<String>,
     ) -> Result<SpawnResult, SpawnError> {
         let params = exec_params(&command, &args, cwd.as_deref());
+        // Only plugins spawn processes through the spawner
+        let command_line = std::iter::once(command.as_str())
+            .chain(args.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let result = self.exec(params).await;
+        self.audit
+            .record(AuditSource::Plugin, "exec", command_line, &result);
+        result
+    }
+}
 
+impl RemoteProcessSpawner {
+    /// Run an `exec` request and collect its output
+    async fn exec(&self, params: serde_json::Value) -> Result<SpawnResult, SpawnError> {
         // Use streaming request to get live output
         let (mut data_rx, result_rx) = self.channel.request_streaming("exec", params).await?;
 
@@ -138,6 +138,28 @@ fn test_cancel_params() {
     assert_eq!(params["id"], 42);
 }
 
+#[test]
+fn test_watch_params() {
+    let params = watch_params("/home/user/src", 250);
+    assert_eq!(params["path"], "/home/user/src");
+    assert_eq!(params["interval_ms"], 250);
+
+    let params = unwatch_params("/home/user/src");
+    assert_eq!(params["path"], "/home/user/src");
+}
+
+#[test]
+fn test_remote_watch_event_parsing() {
+    let event: RemoteWatchEvent = serde_json::from_str(r#"{"kind":"modified"}"#).unwrap();
+    assert_eq!(event.kind, "modified");
+    assert_eq!(event.name, None);
+
+    let event: RemoteWatchEvent =
+        serde_json::from_str(r#"{"kind":"created","name":"new.rs"}"#).unwrap();
+    assert_eq!(event.kind, "created");
+    assert_eq!(event.name.as_deref(), Some("new.rs"));
+}
+
 #[test]
 fn test_remote_dir_entry_parsing() {
     let json = r#"{
@@ -178,6 +200,7 @@ fn test_remote_metadata_parsing() {
     let meta: RemoteMetadata = serde_json::from_str(json).unwrap();
     assert_eq!(meta.size, 5678);
     assert_eq!(meta.mtime, 1700000000);
+    assert_eq!(meta.mtime_ns, None);
     assert_eq!(meta.mode, 33188);
     assert_eq!(meta.uid, 1000);
     assert!(!meta.dir);
@@ -0,0 +1,357 @@
+//! Local RPC server for driving a running editor from external tools
+//!
+//! Each request and response is a single line of JSON sent over a Unix socket
+//! in the data directory (`<data_dir>/rpc/fresh-<pid>.sock`). Requests must
+//! carry the session token stored next to the socket in a file only the
+//! current user can read, so other local users can't drive the editor.
+//!
+//! ```text
+//! -> {"id":1,"token":"…","method":"open","params":{"path":"src/main.rs","line":20}}
+//! <- {"id":1,"result":{"buffer_id":3}}
+//! ```
+//!
+//! Requests are forwarded to the main loop as [`AsyncMessage::RpcRequest`] and
+//! answered through a reply channel, so handlers run with full editor access.
+//!
+//! Only Unix sockets are implemented: on Windows, which would need a named
+//! pipe, [`RpcServer::start`] fails with `Unsupported`.
+
+// Only the wire types are used on platforms without Unix sockets
+#![cfg_attr(not(unix), allow(dead_code))]
+
+use crate::services::async_bridge::AsyncMessage;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// How long a connection waits for the editor to answer a request
+const REPLY_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Number of random bytes in a session token
+const TOKEN_LEN: usize = 32;
+
+/// A request as sent on the wire
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    pub id: u64,
+    pub token: String,
+    pub method: String,
+    #[serde(default)]
+    pub params: Value,
+}
+
+/// A response as sent on the wire (exactly one of `result` and `error` is set)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse {
+    pub id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RpcResponse {
+    fn new(id: u64, outcome: Result<Value, String>) -> Self {
+        match outcome {
+            Ok(result) => Self {
+                id,
+                result: Some(result),
+                error: None,
+            },
+            Err(error) => Self {
+                id,
+                result: None,
+                error: Some(error),
+            },
+        }
+    }
+}
+
+/// The operations exposed to external tools
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "method", content = "params", rename_all = "snake_case")]
+pub enum RpcMethod {
+    /// Open a file, optionally jumping to a 1-indexed line and column
+    Open {
+        path: PathBuf,
+        #[serde(default)]
+        line: Option<usize>,
+        #[serde(default)]
+        column: Option<usize>,
+    },
+    /// List LSP diagnostics, for one file or for all files
+    Diagnostics {
+        #[serde(default)]
+        path: Option<PathBuf>,
+    },
+    /// Run an editor action by its keybinding name (e.g. `save`)
+    Command {
+        action: String,
+        #[serde(default)]
+        args: HashMap<String, Value>,
+    },
+}
+
+impl RpcMethod {
+    /// Build a method from the `method` and `params` fields of a request
+    pub fn parse(method: &str, params: &Value) -> Result<Self, String> {
+        let params = if params.is_null() {
+            Value::Object(Default::default())
+        } else {
+            params.clone()
+        };
+        serde_json::from_value(serde_json::json!({ "method": method, "params": params }))
+            .map_err(|e| format!("invalid request for '{}': {}", method, e))
+    }
+}
+
+/// Socket path for the editor process with the given id
+pub fn socket_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("fresh-{}.sock", pid))
+}
+
+/// Token file belonging to a socket
+pub fn token_path(socket: &Path) -> PathBuf {
+    socket.with_extension("token")
+}
+
+/// Find the socket of the most recently started editor in `dir`
+pub fn find_socket(dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(dir)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "sock"))
+        .filter(|path| token_path(path).exists())
+        .max_by_key(|path| path.metadata().and_then(|m| m.modified()).ok())
+}
+
+/// A running RPC server; the socket and token files are removed on drop
+pub struct RpcServer {
+    socket_path: PathBuf,
+    shutdown: Arc<AtomicBool>,
+}
+
+impl RpcServer {
+    /// Listen on `socket_path`, forwarding authenticated requests to `sender`
+    #[cfg(unix)]
+    pub fn start(socket_path: PathBuf, sender: mpsc::Sender<AsyncMessage>) -> io::Result<Self> {
+        use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+        use std::os::unix::net::UnixListener;
+
+        if let Some(dir) = socket_path.parent() {
+            std::fs::DirBuilder::new()
+                .recursive(true)
+                .mode(0o700)
+                .create(dir)?;
+        }
+        let token = generate_token()?;
+        let token_file = token_path(&socket_path);
+        let _ = std::fs::remove_file(&token_file);
+        std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&token_file)?
+            .write_all(token.as_bytes())?;
+
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path)?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let stop = Arc::clone(&shutdown);
+        let token = Arc::new(token);
+        std::thread::Builder::new()
+            .name("rpc-server".to_string())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::Relaxed) {
+                        break;
+                    }
+                    let Ok(stream) = stream else {
+                        continue;
+                    };
+                    let sender = sender.clone();
+                    let token = Arc::clone(&token);
+                    let _ = std::thread::Builder::new()
+                        .name("rpc-connection".to_string())
+                        .spawn(move || {
+                            if let Ok(reader) = stream.try_clone() {
+                                serve_connection(BufReader::new(reader), stream, &token, &sender);
+                            }
+                        });
+                }
+            })?;
+
+        tracing::info!("RPC server listening on {}", socket_path.display());
+        Ok(Self {
+            socket_path,
+            shutdown,
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn start(_socket_path: PathBuf, _sender: mpsc::Sender<AsyncMessage>) -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the RPC server needs Unix sockets; Windows named pipes are not supported",
+        ))
+    }
+
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+}
+
+impl Drop for RpcServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // Wake the accept loop so it sees the shutdown flag
+        #[cfg(unix)]
+        let _ = std::os::unix::net::UnixStream::connect(&self.socket_path);
+        let _ = std::fs::remove_file(&self.socket_path);
+        let _ = std::fs::remove_file(token_path(&self.socket_path));
+    }
+}
+
+/// Answer requests on one connection until the client disconnects
+fn serve_connection(
+    reader: impl BufRead,
+    mut writer: impl Write,
+    token: &str,
+    sender: &mpsc::Sender<AsyncMessage>,
+) {
+    for line in reader.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = match serde_json::from_str::<RpcRequest>(&line) {
+            Ok(request) if request.token != token => {
+                RpcResponse::new(request.id, Err("invalid session token".to_string()))
+            }
+            Ok(request) => RpcResponse::new(request.id, dispatch(&request, sender)),
+            Err(e) => RpcResponse::new(0, Err(format!("malformed request: {}", e))),
+        };
+        let Ok(json) = serde_json::to_string(&response) else {
+            break;
+        };
+        if writeln!(writer, "{}", json).is_err() {
+            break;
+        }
+    }
+}
+
+/// Hand a request to the editor and wait for its answer
+fn dispatch(request: &RpcRequest, sender: &mpsc::Sender<AsyncMessage>) -> Result<Value, String> {
+    let method = RpcMethod::parse(&request.method, &request.params)?;
+    let (reply, answer) = mpsc::channel();
+    sender
+        .send(AsyncMessage::RpcRequest { method, reply })
+        .map_err(|_| "editor is shutting down".to_string())?;
+    answer
+        .recv_timeout(REPLY_TIMEOUT)
+        .map_err(|_| "editor did not respond".to_string())?
+}
+
+/// Random hex token read from the system's entropy source
+#[cfg(unix)]
+fn generate_token() -> io::Result<String> {
+    use std::io::Read;
+    let mut bytes = [0u8; TOKEN_LEN];
+    std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+/// Send one request to the editor listening on `socket`, using its token file
+#[cfg(unix)]
+pub fn send_request(socket: &Path, method: &RpcMethod) -> io::Result<RpcResponse> {
+    let token = std::fs::read_to_string(token_path(socket))?;
+    let mut request = serde_json::to_value(method)?;
+    request["id"] = Value::from(1);
+    request["token"] = Value::from(token.trim());
+
+    let mut stream = std::os::unix::net::UnixStream::connect(socket)?;
+    writeln!(stream, "{}", request)?;
+    let mut line = String::new();
+    BufReader::new(stream).read_line(&mut line)?;
+    Ok(serde_json::from_str(&line)?)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// Answer every forwarded request with its method, as the editor would
+    fn echo_editor(receiver: mpsc::Receiver<AsyncMessage>) {
+        std::thread::spawn(move || {
+            while let Ok(message) = receiver.recv() {
+                if let AsyncMessage::RpcRequest { method, reply } = message {
+                    let _ = reply.send(serde_json::to_value(method).map_err(|e| e.to_string()));
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_parse_method() {
+        let method =
+            RpcMethod::parse("open", &serde_json::json!({ "path": "a.rs", "line": 3 })).unwrap();
+        assert_eq!(
+            method,
+            RpcMethod::Open {
+                path: PathBuf::from("a.rs"),
+                line: Some(3),
+                column: None,
+            }
+        );
+        assert_eq!(
+            RpcMethod::parse("diagnostics", &Value::Null).unwrap(),
+            RpcMethod::Diagnostics { path: None }
+        );
+        assert!(RpcMethod::parse("format_disk", &Value::Null).is_err());
+    }
+
+    #[test]
+    fn test_round_trip_through_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        echo_editor(receiver);
+        let socket = socket_path(dir.path(), 42);
+        let server = RpcServer::start(socket.clone(), sender).unwrap();
+        assert_eq!(find_socket(dir.path()), Some(socket.clone()));
+
+        let method = RpcMethod::Command {
+            action: "save".to_string(),
+            args: HashMap::new(),
+        };
+        let response = send_request(&socket, &method).unwrap();
+        assert_eq!(response.error, None);
+        assert_eq!(response.result.unwrap()["method"], "command");
+
+        drop(server);
+        assert!(!socket.exists());
+        assert!(!token_path(&socket).exists());
+    }
+
+    #[test]
+    fn test_rejects_wrong_token() {
+        let dir = tempfile::tempdir().unwrap();
+        let (sender, receiver) = mpsc::channel();
+        echo_editor(receiver);
+        let socket = socket_path(dir.path(), 7);
+        let _server = RpcServer::start(socket.clone(), sender).unwrap();
+
+        std::fs::write(token_path(&socket), "guess").unwrap();
+        let response = send_request(&socket, &RpcMethod::Diagnostics { path: None }).unwrap();
+        assert_eq!(response.error.as_deref(), Some("invalid session token"));
+    }
+}
@@ -0,0 +1,180 @@
+//! Background fuzzy filtering of large prompt suggestion lists
+//!
+//! Scoring tens of thousands of candidates on every keystroke would stall the
+//! UI thread, so lists above [`BACKGROUND_FILTER_THRESHOLD`] are scored on a
+//! blocking task instead. Matches are reported in batches so the prompt can
+//! show the best ones found so far, and a newer query cancels the older one.
+
+use crate::input::commands::Suggestion;
+use crate::input::fuzzy::{fuzzy_match, FuzzyMatch};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Candidate lists at least this long are filtered in the background
+pub const BACKGROUND_FILTER_THRESHOLD: usize = 5_000;
+
+/// Candidates scored between cancellation checks and progress reports
+const BATCH_SIZE: usize = 4_096;
+
+/// A list of candidates that can be scored against a query off the UI thread
+pub trait FilterSource: Send + Sync {
+    /// Number of candidates
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Score of candidate `index` against `query`, or None if it doesn't match
+    fn score(&self, query: &str, index: usize) -> Option<i32>;
+
+    /// The suggestion to show for candidate `index`
+    fn suggestion(&self, index: usize) -> Suggestion;
+}
+
+/// Plain prompt suggestions, matched on their text (and optionally description)
+pub struct SuggestionSource {
+    pub suggestions: Arc<Vec<Suggestion>>,
+    pub match_description: bool,
+}
+
+impl FilterSource for SuggestionSource {
+    fn len(&self) -> usize {
+        self.suggestions.len()
+    }
+
+    fn score(&self, query: &str, index: usize) -> Option<i32> {
+        let suggestion = &self.suggestions[index];
+        let text = fuzzy_match(query, &suggestion.text);
+        let description = match (&suggestion.description, self.match_description) {
+            (Some(description), true) => fuzzy_match(query, description),
+            _ => FuzzyMatch::no_match(),
+        };
+        (text.matched || description.matched).then(|| text.score.max(description.score))
+    }
+
+    fn suggestion(&self, index: usize) -> Suggestion {
+        self.suggestions[index].clone()
+    }
+}
+
+/// Score every candidate of `source` against `query`.
+///
+/// `on_batch` is called with the `(index, score)` of the matches in each
+/// batch of candidates, in index order. Returns false if `cancel` was set
+/// before all candidates were scored.
+pub fn score_candidates(
+    source: &dyn FilterSource,
+    query: &str,
+    cancel: &AtomicBool,
+    mut on_batch: impl FnMut(Vec<(usize, i32)>),
+) -> bool {
+    let len = source.len();
+    let mut start = 0;
+    while start < len {
+        if cancel.load(Ordering::Relaxed) {
+            return false;
+        }
+        let end = (start + BATCH_SIZE).min(len);
+        let matches = (start..end)
+            .filter_map(|index| source.score(query, index).map(|score| (index, score)))
+            .collect();
+        on_batch(matches);
+        start = end;
+    }
+    true
+}
+
+/// Order matches best first, keeping the candidates' order among equal scores
+pub fn sort_matches(matches: &mut [(usize, i32)]) {
+    matches.sort_unstable_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+}
+
+/// The best `limit` matches, best first
+pub fn top_matches(matches: &[(usize, i32)], limit: usize) -> Vec<(usize, i32)> {
+    let mut top = matches.to_vec();
+    if top.len() > limit {
+        top.select_nth_unstable_by(limit, |a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        top.truncate(limit);
+    }
+    sort_matches(&mut top);
+    top
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(texts: &[&str]) -> SuggestionSource {
+        SuggestionSource {
+            suggestions: Arc::new(
+                texts
+                    .iter()
+                    .map(|text| Suggestion::new(text.to_string()))
+                    .collect(),
+            ),
+            match_description: false,
+        }
+    }
+
+    #[test]
+    fn test_score_candidates_reports_matches_in_batches() {
+        let texts: Vec<String> = (0..BATCH_SIZE * 2 + 10)
+            .map(|i| {
+                if i % 2 == 0 {
+                    format!("src/file_{}.rs", i)
+                } else {
+                    format!("docs/page_{}.md", i)
+                }
+            })
+            .collect();
+        let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+        let source = source(&texts);
+
+        let mut batches = Vec::new();
+        let done = score_candidates(&source, "rs", &AtomicBool::new(false), |batch| {
+            batches.push(batch)
+        });
+
+        assert!(done);
+        assert_eq!(batches.len(), 3);
+        let matched: Vec<usize> = batches.concat().into_iter().map(|(i, _)| i).collect();
+        assert_eq!(matched.len(), texts.len().div_ceil(2));
+        assert!(matched.iter().all(|i| i % 2 == 0));
+    }
+
+    #[test]
+    fn test_score_candidates_stops_when_cancelled() {
+        let texts = vec!["alpha"; BATCH_SIZE * 3];
+        let source = source(&texts);
+        let cancel = AtomicBool::new(false);
+
+        let mut batches = 0;
+        let done = score_candidates(&source, "a", &cancel, |_| {
+            batches += 1;
+            cancel.store(true, Ordering::Relaxed);
+        });
+
+        assert!(!done);
+        assert_eq!(batches, 1);
+    }
+
+    #[test]
+    fn test_top_matches_orders_by_score_then_index() {
+        let matches = vec![(0, 5), (1, 9), (2, 5), (3, 1), (4, 9)];
+        assert_eq!(top_matches(&matches, 3), vec![(1, 9), (4, 9), (0, 5)]);
+        assert_eq!(top_matches(&matches, 10).len(), 5);
+    }
+
+    #[test]
+    fn test_suggestion_source_matches_description() {
+        let mut source = source(&["Deutsch"]);
+        source.suggestions = Arc::new(vec![Suggestion {
+            description: Some("German".to_string()),
+            ..Suggestion::new("de".to_string())
+        }]);
+        assert_eq!(source.score("german", 0), None);
+        source.match_description = true;
+        assert!(source.score("german", 0).is_some());
+    }
+}
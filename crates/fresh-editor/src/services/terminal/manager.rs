@@ -18,7 +18,7 @@
 //! to append any new scrollback lines to the backing file. This ensures scrollback is
 //! written incrementally as lines scroll off screen, avoiding O(n) work on mode switches.
 
-use super::term::TerminalState;
+use super::term::{AnsiPalette, TerminalState, DEFAULT_ANSI_PALETTE};
 use crate::services::async_bridge::AsyncBridge;
 use portable_pty::{native_pty_system, CommandBuilder, PtySize};
 use std::collections::HashMap;
@@ -110,6 +110,8 @@ pub struct TerminalManager {
     next_id: usize,
     /// Async bridge for sending notifications to main loop
     async_bridge: Option<AsyncBridge>,
+    /// ANSI palette applied to every terminal (follows the active theme)
+    palette: AnsiPalette,
 }
 
 impl TerminalManager {
@@ -119,6 +121,20 @@ impl TerminalManager {
             terminals: HashMap::new(),
             next_id: 0,
             async_bridge: None,
+            palette: DEFAULT_ANSI_PALETTE,
+        }
+    }
+
+    /// Set the ANSI palette used by all current and future terminals
+    pub fn set_ansi_palette(&mut self, palette: AnsiPalette) {
+        if self.palette == palette {
+            return;
+        }
+        self.palette = palette;
+        for handle in self.terminals.values() {
+            if let Ok(mut state) = handle.state.lock() {
+                state.set_palette(palette);
+            }
         }
     }
 
@@ -184,7 +200,9 @@ impl TerminalManager {
                 .map_err(|e| format!("Failed to spawn shell: {}", e))?;
 
             // Create terminal state
-            let state = Arc::new(Mutex::new(TerminalState::new(cols, rows)));
+            let mut initial_state = TerminalState::new(cols, rows);
+            initial_state.set_palette(self.palette);
+            let state = Arc::new(Mutex::new(initial_state));
 
             // Initialize backing_file_history_end if backing file already exists (session restore)
             // This ensures enter_terminal_mode doesn't truncate existing history to 0
@@ -52,4 +52,4 @@ pub mod pty;
 pub mod term;
 
 pub use manager::{detect_shell, TerminalId, TerminalManager};
-pub use term::{TerminalCell, TerminalState};
+pub use term::{AnsiPalette, TerminalCell, TerminalState, DEFAULT_ANSI_PALETTE};
@@ -38,6 +38,29 @@ use std::io::{self, Write};
 // Keep a generous scrollback so sync-to-buffer can include deep history.
 const SCROLLBACK_LINES: usize = 200_000;
 
+/// RGB values for the 16 ANSI colors, in SGR order (black..white, then bright variants)
+pub type AnsiPalette = [(u8, u8, u8); 16];
+
+/// Palette used until the editor supplies one from the active theme
+pub const DEFAULT_ANSI_PALETTE: AnsiPalette = [
+    (0, 0, 0),       // Black
+    (205, 49, 49),   // Red
+    (13, 188, 121),  // Green
+    (229, 229, 16),  // Yellow
+    (36, 114, 200),  // Blue
+    (188, 63, 188),  // Magenta
+    (17, 168, 205),  // Cyan
+    (229, 229, 229), // White
+    (102, 102, 102), // Bright Black
+    (241, 76, 76),   // Bright Red
+    (35, 209, 139),  // Bright Green
+    (245, 245, 67),  // Bright Yellow
+    (59, 142, 234),  // Bright Blue
+    (214, 112, 214), // Bright Magenta
+    (41, 184, 219),  // Bright Cyan
+    (255, 255, 255), // Bright White
+];
+
 /// Event listener that does nothing (we handle events ourselves)
 struct NullListener;
 
@@ -65,6 +88,8 @@ pub struct TerminalState {
     synced_history_lines: usize,
     /// Byte offset in backing file where scrollback ends (for truncation)
     backing_file_history_end: u64,
+    /// RGB values used for the 16 ANSI colors
+    palette: AnsiPalette,
 }
 
 impl TerminalState {
@@ -86,6 +111,18 @@ impl TerminalState {
             terminal_title: String::new(),
             synced_history_lines: 0,
             backing_file_history_end: 0,
+            palette: DEFAULT_ANSI_PALETTE,
+        }
+    }
+
+    /// Set the RGB values used for the 16 ANSI colors
+    ///
+    /// Affects rendering and scrollback lines flushed from now on; lines already
+    /// written to the backing file keep the colors they were written with.
+    pub fn set_palette(&mut self, palette: AnsiPalette) {
+        if self.palette != palette {
+            self.palette = palette;
+            self.dirty = true;
         }
     }
 
@@ -163,8 +200,8 @@ impl TerminalState {
             let c = cell.c;
 
             // Convert colors
-            let fg = color_to_rgb(&cell.fg);
-            let bg = color_to_rgb(&cell.bg);
+            let fg = color_to_rgb(&cell.fg, &self.palette);
+            let bg = color_to_rgb(&cell.bg, &self.palette);
 
             // Check flags
             let flags = cell.flags;
@@ -394,8 +431,8 @@ impl TerminalState {
 
         for col in 0..self.cols as usize {
             let cell = &row_data[Column(col)];
-            let fg = color_to_rgb(&cell.fg);
-            let bg = color_to_rgb(&cell.bg);
+            let fg = color_to_rgb(&cell.fg, &self.palette);
+            let bg = color_to_rgb(&cell.bg, &self.palette);
             let flags = cell.flags;
             let bold = flags.contains(Flags::BOLD);
             let italic = flags.contains(Flags::ITALIC);
@@ -550,38 +587,37 @@ impl Default for TerminalCell {
     }
 }
 
-/// Convert alacritty color to RGB
-fn color_to_rgb(color: &alacritty_terminal::vte::ansi::Color) -> Option<(u8, u8, u8)> {
-    use alacritty_terminal::vte::ansi::Color;
+/// Convert alacritty color to RGB, resolving the 16 ANSI colors through `palette`
+fn color_to_rgb(
+    color: &alacritty_terminal::vte::ansi::Color,
+    palette: &AnsiPalette,
+) -> Option<(u8, u8, u8)> {
+    use alacritty_terminal::vte::ansi::{Color, NamedColor};
 
     match color {
         Color::Spec(rgb) => Some((rgb.r, rgb.g, rgb.b)),
         Color::Named(named) => {
-            // Convert named colors to RGB
-            // Using standard ANSI color palette
-            let rgb = match named {
-                alacritty_terminal::vte::ansi::NamedColor::Black => (0, 0, 0),
-                alacritty_terminal::vte::ansi::NamedColor::Red => (205, 49, 49),
-                alacritty_terminal::vte::ansi::NamedColor::Green => (13, 188, 121),
-                alacritty_terminal::vte::ansi::NamedColor::Yellow => (229, 229, 16),
-                alacritty_terminal::vte::ansi::NamedColor::Blue => (36, 114, 200),
-                alacritty_terminal::vte::ansi::NamedColor::Magenta => (188, 63, 188),
-                alacritty_terminal::vte::ansi::NamedColor::Cyan => (17, 168, 205),
-                alacritty_terminal::vte::ansi::NamedColor::White => (229, 229, 229),
-                alacritty_terminal::vte::ansi::NamedColor::BrightBlack => (102, 102, 102),
-                alacritty_terminal::vte::ansi::NamedColor::BrightRed => (241, 76, 76),
-                alacritty_terminal::vte::ansi::NamedColor::BrightGreen => (35, 209, 139),
-                alacritty_terminal::vte::ansi::NamedColor::BrightYellow => (245, 245, 67),
-                alacritty_terminal::vte::ansi::NamedColor::BrightBlue => (59, 142, 234),
-                alacritty_terminal::vte::ansi::NamedColor::BrightMagenta => (214, 112, 214),
-                alacritty_terminal::vte::ansi::NamedColor::BrightCyan => (41, 184, 219),
-                alacritty_terminal::vte::ansi::NamedColor::BrightWhite => (255, 255, 255),
-                alacritty_terminal::vte::ansi::NamedColor::Foreground => return None,
-                alacritty_terminal::vte::ansi::NamedColor::Background => return None,
-                alacritty_terminal::vte::ansi::NamedColor::Cursor => return None,
+            let idx = match named {
+                NamedColor::Black => 0,
+                NamedColor::Red => 1,
+                NamedColor::Green => 2,
+                NamedColor::Yellow => 3,
+                NamedColor::Blue => 4,
+                NamedColor::Magenta => 5,
+                NamedColor::Cyan => 6,
+                NamedColor::White => 7,
+                NamedColor::BrightBlack => 8,
+                NamedColor::BrightRed => 9,
+                NamedColor::BrightGreen => 10,
+                NamedColor::BrightYellow => 11,
+                NamedColor::BrightBlue => 12,
+                NamedColor::BrightMagenta => 13,
+                NamedColor::BrightCyan => 14,
+                NamedColor::BrightWhite => 15,
+                // Foreground, Background, Cursor and dim variants use the theme defaults
                 _ => return None,
             };
-            Some(rgb)
+            Some(palette[idx])
         }
         Color::Indexed(idx) => {
             // Convert 256-color index to RGB
@@ -589,25 +625,7 @@ fn color_to_rgb(color: &alacritty_terminal::vte::ansi::Color) -> Option<(u8, u8,
             let idx = *idx as usize;
             if idx < 16 {
                 // Standard colors (same as named)
-                let colors = [
-                    (0, 0, 0),       // Black
-                    (205, 49, 49),   // Red
-                    (13, 188, 121),  // Green
-                    (229, 229, 16),  // Yellow
-                    (36, 114, 200),  // Blue
-                    (188, 63, 188),  // Magenta
-                    (17, 168, 205),  // Cyan
-                    (229, 229, 229), // White
-                    (102, 102, 102), // Bright Black
-                    (241, 76, 76),   // Bright Red
-                    (35, 209, 139),  // Bright Green
-                    (245, 245, 67),  // Bright Yellow
-                    (59, 142, 234),  // Bright Blue
-                    (214, 112, 214), // Bright Magenta
-                    (41, 184, 219),  // Bright Cyan
-                    (255, 255, 255), // Bright White
-                ];
-                Some(colors[idx])
+                Some(palette[idx])
             } else if idx < 232 {
                 // 216 color cube (6x6x6)
                 let idx = idx - 16;
@@ -654,6 +672,27 @@ mod tests {
         assert!(state.is_dirty());
     }
 
+    #[test]
+    fn test_palette_maps_ansi_colors() {
+        let mut state = TerminalState::new(80, 24);
+        // Red foreground (SGR 31), then 256-color index 9 (bright red)
+        state.process_output(b"\x1b[31mA\x1b[38;5;9mB\x1b[0m");
+        let line = state.get_line(0);
+        assert_eq!(line[0].fg, Some(DEFAULT_ANSI_PALETTE[1]));
+        assert_eq!(line[1].fg, Some(DEFAULT_ANSI_PALETTE[9]));
+
+        let mut palette = DEFAULT_ANSI_PALETTE;
+        palette[1] = (255, 85, 85);
+        palette[9] = (255, 110, 110);
+        state.mark_clean();
+        state.set_palette(palette);
+        assert!(state.is_dirty());
+
+        let line = state.get_line(0);
+        assert_eq!(line[0].fg, Some((255, 85, 85)));
+        assert_eq!(line[1].fg, Some((255, 110, 110)));
+    }
+
     #[test]
     fn test_flush_new_scrollback_no_history() {
         // When there's no scrollback history, flush should return 0
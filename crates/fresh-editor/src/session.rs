@@ -83,6 +83,14 @@ pub struct Session {
     #[serde(default)]
     pub external_files: Vec<PathBuf>,
 
+    /// Groups of splits that scroll together
+    #[serde(default)]
+    pub scroll_sync_groups: Vec<SerializedScrollSyncGroup>,
+
+    /// Values plugins stored with `setSessionData` (plugin name -> key -> value)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub plugin_data: HashMap<String, HashMap<String, serde_json::Value>>,
+
     /// Timestamp when session was saved (Unix epoch seconds)
     pub saved_at: u64,
 }
@@ -149,6 +157,14 @@ pub struct SerializedSplitViewState {
     /// Compose width if in compose mode
     #[serde(default)]
     pub compose_width: Option<u16>,
+
+    /// Theme the split is drawn with instead of the editor theme
+    #[serde(default)]
+    pub theme: Option<String>,
+
+    /// Tabs in the order they were last focused (indices into open_tabs, most recent last)
+    #[serde(default)]
+    pub focus_history: Vec<usize>,
 }
 
 /// Per-file state within a split
@@ -291,6 +307,16 @@ pub enum SerializedTabRef {
     Terminal(usize),
 }
 
+/// Splits that scroll together
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SerializedScrollSyncGroup {
+    /// Split IDs of the members (as in `split_layout`), primary first
+    pub splits: Vec<usize>,
+    /// Sync anchors, each with one line per member (empty for proportional sync)
+    #[serde(default)]
+    pub anchors: Vec<Vec<usize>>,
+}
+
 /// Persisted metadata for a terminal session
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SerializedTerminalSession {
@@ -727,6 +753,8 @@ impl Session {
             bookmarks: HashMap::new(),
             terminals: Vec::new(),
             external_files: Vec::new(),
+            scroll_sync_groups: Vec::new(),
+            plugin_data: HashMap::new(),
             saved_at: SystemTime::now()
                 .duration_since(UNIX_EPOCH)
                 .unwrap_or_default()
@@ -976,6 +1004,8 @@ mod tests {
                 tab_scroll_offset: 0,
                 view_mode: SerializedViewMode::Source,
                 compose_width: None,
+                theme: None,
+                focus_history: vec![1],
             },
         );
 
@@ -992,6 +1022,12 @@ mod tests {
         session.search_options.case_sensitive = true;
         session.search_options.use_regex = true;
 
+        // Add plugin data
+        session.plugin_data.insert(
+            "todo".to_string(),
+            HashMap::from([("items".to_string(), serde_json::json!(["write tests"]))]),
+        );
+
         // Serialize and deserialize
         let json = serde_json::to_string_pretty(&session).unwrap();
         let restored: Session = serde_json::from_str(&json).unwrap();
@@ -1003,11 +1039,16 @@ mod tests {
         assert!(restored.bookmarks.contains_key(&'m'));
         assert!(restored.search_options.case_sensitive);
         assert!(restored.search_options.use_regex);
+        assert_eq!(
+            restored.plugin_data["todo"]["items"],
+            serde_json::json!(["write tests"])
+        );
 
         // Verify split state
         let split_state = restored.split_states.get(&1).unwrap();
         assert_eq!(split_state.open_files.len(), 2);
         assert_eq!(split_state.open_files[0], PathBuf::from("README.md"));
+        assert_eq!(split_state.focus_history, vec![1]);
     }
 
     #[test]
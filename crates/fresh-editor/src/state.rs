@@ -3,6 +3,7 @@ use crate::model::cursor::{Cursor, Cursors};
 use crate::model::document_model::{
     DocumentCapabilities, DocumentModel, DocumentPosition, ViewportContent, ViewportLine,
 };
+use crate::model::edit::{ChangeLog, Edit, EditJournal};
 use crate::model::event::{
     Event, MarginContentData, MarginPositionData, OverlayFace as EventOverlayFace, PopupData,
     PopupPositionData,
@@ -14,14 +15,19 @@ use crate::primitives::highlight_engine::HighlightEngine;
 use crate::primitives::highlighter::Language;
 use crate::primitives::indent::IndentCalculator;
 use crate::primitives::reference_highlighter::ReferenceHighlighter;
+use crate::primitives::sticky_scroll::StickyScopes;
 use crate::primitives::text_property::TextPropertyManager;
 use crate::view::bracket_highlight_overlay::BracketHighlightOverlay;
+use crate::view::conceal::ConcealRule;
+use crate::view::indent_block_overlay::IndentBlockOverlay;
+use crate::view::line_background::LineBackgrounds;
 use crate::view::margin::{MarginAnnotation, MarginContent, MarginManager, MarginPosition};
 use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, UnderlineStyle};
 use crate::view::popup::{
     Popup, PopupContent, PopupKind, PopupListItem, PopupManager, PopupPosition,
 };
 use crate::view::reference_highlight_overlay::ReferenceHighlightOverlay;
+use crate::view::trailing_whitespace_overlay::TrailingWhitespaceOverlay;
 use crate::view::virtual_text::VirtualTextManager;
 use anyhow::Result;
 use ratatui::style::{Color, Style};
@@ -63,6 +69,12 @@ pub struct EditorState {
     /// Marker list for content-anchored overlay positions
     pub marker_list: MarkerList,
 
+    /// Recent edits, for carrying plugin edit offsets past the user's edits
+    pub edit_journal: EditJournal,
+
+    /// Changes not yet sent to plugins in a `buffer_changed` event
+    pub change_log: ChangeLog,
+
     /// Virtual text manager for inline hints (type hints, parameter hints, etc.)
     pub virtual_texts: VirtualTextManager,
 
@@ -72,6 +84,9 @@ pub struct EditorState {
     /// Margins for line numbers, annotations, gutter symbols, etc.)
     pub margins: MarginManager,
 
+    /// Whole-line background tints set by plugins
+    pub line_backgrounds: LineBackgrounds,
+
     /// Cached line number for primary cursor (0-indexed)
     /// Maintained incrementally to avoid O(n) scanning on every render
     pub primary_cursor_line_number: LineNumber,
@@ -109,6 +124,14 @@ pub struct EditorState {
     /// Used for visual display of tab characters and indent calculations.
     pub tab_size: usize,
 
+    /// Column to wrap long lines at when line wrapping is on (None = window width).
+    /// Set by plugins through buffer display options.
+    pub wrap_column: Option<u16>,
+
+    /// Rules hiding or replacing matching text at render time.
+    /// Set by plugins through buffer display options.
+    pub conceal_rules: Vec<ConcealRule>,
+
     /// Semantic highlighter for word occurrence highlighting
     pub reference_highlighter: ReferenceHighlighter,
 
@@ -119,6 +142,14 @@ pub struct EditorState {
     /// When enabled, each character shows its byte position and highlight info
     pub debug_highlight_mode: bool,
 
+    /// Show the bytes as hex where they aren't printable text, as for binary
+    /// files (toggled by "Toggle Hex View")
+    pub hex_view: bool,
+
+    /// Render ANSI escape sequences in the text as colors; when off they are
+    /// shown raw (toggled by "Toggle ANSI Colors")
+    pub ansi_colors: bool,
+
     /// Optional compose width for centered rendering
     pub compose_width: Option<u16>,
 
@@ -137,6 +168,15 @@ pub struct EditorState {
     /// Bracket matching highlight overlay
     pub bracket_highlight_overlay: BracketHighlightOverlay,
 
+    /// Guide for the indentation block around the cursor
+    pub indent_block_overlay: IndentBlockOverlay,
+
+    /// Highlight for whitespace at the end of lines
+    pub trailing_whitespace_overlay: TrailingWhitespaceOverlay,
+
+    /// Scope headers pinned at the top of the split by sticky scroll
+    pub sticky_scopes: StickyScopes,
+
     /// Cached LSP semantic tokens (converted to buffer byte ranges)
     pub semantic_tokens: Option<SemanticTokenStore>,
 
@@ -162,9 +202,12 @@ impl EditorState {
             indent_calculator: RefCell::new(IndentCalculator::new()),
             overlays: OverlayManager::new(),
             marker_list: MarkerList::new(),
+            edit_journal: EditJournal::new(),
+            change_log: ChangeLog::new(0, 0),
             virtual_texts: VirtualTextManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
+            line_backgrounds: LineBackgrounds::new(),
             primary_cursor_line_number: LineNumber::Absolute(0), // Start at line 0
             mode: "insert".to_string(),
             text_properties: TextPropertyManager::new(),
@@ -174,15 +217,22 @@ impl EditorState {
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4, // Default tab size
+            wrap_column: None,
+            conceal_rules: Vec::new(),
             reference_highlighter: ReferenceHighlighter::new(),
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
+            hex_view: false,
+            ansi_colors: true,
             compose_width: None,
             compose_prev_line_numbers: None,
             compose_column_guides: None,
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            indent_block_overlay: IndentBlockOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            sticky_scopes: StickyScopes::new(),
             semantic_tokens: None,
             language: "text".to_string(), // Default to plain text
         }
@@ -247,6 +297,7 @@ impl EditorState {
         if !buffer.is_empty() {
             marker_list.adjust_for_insert(0, buffer.len());
         }
+        let change_log = ChangeLog::new(buffer.version(), buffer.len());
 
         Ok(Self {
             buffer,
@@ -255,9 +306,12 @@ impl EditorState {
             indent_calculator: RefCell::new(IndentCalculator::new()),
             overlays: OverlayManager::new(),
             marker_list,
+            edit_journal: EditJournal::new(),
+            change_log,
             virtual_texts: VirtualTextManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
+            line_backgrounds: LineBackgrounds::new(),
             primary_cursor_line_number: LineNumber::Absolute(0),
             mode: "insert".to_string(),
             text_properties: TextPropertyManager::new(),
@@ -267,15 +321,22 @@ impl EditorState {
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4,
+            wrap_column: None,
+            conceal_rules: Vec::new(),
             reference_highlighter,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
+            hex_view: false,
+            ansi_colors: true,
             compose_width: None,
             compose_prev_line_numbers: None,
             compose_column_guides: None,
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            indent_block_overlay: IndentBlockOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            sticky_scopes: StickyScopes::new(),
             semantic_tokens: None,
             language: language_name,
         })
@@ -298,7 +359,29 @@ impl EditorState {
         fs: Arc<dyn FileSystem + Send + Sync>,
     ) -> anyhow::Result<Self> {
         let buffer = Buffer::load_from_file(path, large_file_threshold, fs)?;
+        Ok(Self::from_loaded_file(buffer, path, registry, languages))
+    }
+
+    /// Create an editor state from a file saved in `encoding`, which is
+    /// decoded to UTF-8 (see [`Buffer::load_from_file_with_encoding`])
+    pub fn from_file_with_encoding(
+        path: &std::path::Path,
+        encoding: crate::model::encoding::Encoding,
+        registry: &GrammarRegistry,
+        languages: &std::collections::HashMap<String, crate::config::LanguageConfig>,
+        fs: Arc<dyn FileSystem + Send + Sync>,
+    ) -> anyhow::Result<Self> {
+        let buffer = Buffer::load_from_file_with_encoding(path, encoding, fs)?;
+        Ok(Self::from_loaded_file(buffer, path, registry, languages))
+    }
 
+    /// Editor state for a buffer just loaded from `path`
+    fn from_loaded_file(
+        buffer: Buffer,
+        path: &std::path::Path,
+        registry: &GrammarRegistry,
+        languages: &std::collections::HashMap<String, crate::config::LanguageConfig>,
+    ) -> Self {
         let highlighter = HighlightEngine::for_file_with_languages(path, registry, languages);
 
         let language = Language::from_path(path);
@@ -315,17 +398,21 @@ impl EditorState {
         if !buffer.is_empty() {
             marker_list.adjust_for_insert(0, buffer.len());
         }
+        let change_log = ChangeLog::new(buffer.version(), buffer.len());
 
-        Ok(Self {
+        Self {
             buffer,
             cursors: Cursors::new(),
             highlighter,
             indent_calculator: RefCell::new(IndentCalculator::new()),
             overlays: OverlayManager::new(),
             marker_list,
+            edit_journal: EditJournal::new(),
+            change_log,
             virtual_texts: VirtualTextManager::new(),
             popups: PopupManager::new(),
             margins: MarginManager::new(),
+            line_backgrounds: LineBackgrounds::new(),
             primary_cursor_line_number: LineNumber::Absolute(0),
             mode: "insert".to_string(),
             text_properties: TextPropertyManager::new(),
@@ -335,18 +422,25 @@ impl EditorState {
             show_whitespace_tabs: true,
             use_tabs: false,
             tab_size: 4,
+            wrap_column: None,
+            conceal_rules: Vec::new(),
             reference_highlighter,
             view_mode: ViewMode::Source,
             debug_highlight_mode: false,
+            hex_view: false,
+            ansi_colors: true,
             compose_width: None,
             compose_prev_line_numbers: None,
             compose_column_guides: None,
             view_transform: None,
             reference_highlight_overlay: ReferenceHighlightOverlay::new(),
             bracket_highlight_overlay: BracketHighlightOverlay::new(),
+            indent_block_overlay: IndentBlockOverlay::new(),
+            trailing_whitespace_overlay: TrailingWhitespaceOverlay::new(),
+            sticky_scopes: StickyScopes::new(),
             semantic_tokens: None,
             language: language_name,
-        })
+        }
     }
 
     /// Handle an Insert event - adjusts markers, buffer, highlighter, cursors, and line numbers
@@ -361,9 +455,17 @@ impl EditorState {
         // CRITICAL: Adjust markers BEFORE modifying buffer
         self.marker_list.adjust_for_insert(position, text.len());
         self.margins.adjust_for_insert(position, text.len());
+        self.line_backgrounds
+            .adjust_for_insert(position, text.len());
 
         // Insert text into buffer
         self.buffer.insert(position, text);
+        if !text.is_empty() {
+            self.edit_journal
+                .record(Edit::insert(self.buffer.version(), position, text.len()));
+            self.change_log
+                .record(self.buffer.version(), position..position, text);
+        }
 
         // Invalidate highlight cache for edited range
         self.highlighter
@@ -409,9 +511,16 @@ impl EditorState {
         // CRITICAL: Adjust markers BEFORE modifying buffer
         self.marker_list.adjust_for_delete(range.start, len);
         self.margins.adjust_for_delete(range.start, len);
+        self.line_backgrounds.adjust_for_delete(range.start, len);
 
         // Delete from buffer
         self.buffer.delete(range.clone());
+        if len > 0 {
+            self.edit_journal
+                .record(Edit::delete(self.buffer.version(), range.start, len));
+            self.change_log
+                .record(self.buffer.version(), range.clone(), "");
+        }
 
         // Invalidate highlight cache for edited range
         self.highlighter.invalidate_range(range.clone());
@@ -445,6 +554,14 @@ impl EditorState {
         }
     }
 
+    /// Replace this state with `new_state`, the buffer loaded again from
+    /// disk, keeping the changes not yet sent to plugins
+    pub fn reload_from(&mut self, mut new_state: EditorState) {
+        new_state.change_log = std::mem::replace(&mut self.change_log, ChangeLog::new(0, 0));
+        new_state.change_log.mark_replaced();
+        *self = new_state;
+    }
+
     /// Apply an event to the state - THE ONLY WAY TO MODIFY STATE
     /// This is the heart of the event-driven architecture
     pub fn apply(&mut self, event: &Event) {
@@ -751,6 +868,12 @@ impl EditorState {
         self.cursors.primary_mut()
     }
 
+    /// Whether ANSI escape sequences in the text render as colors, taking no
+    /// space on screen, rather than as raw bytes
+    pub fn renders_ansi_colors(&self) -> bool {
+        self.ansi_colors && !self.hex_view && !self.buffer.is_binary()
+    }
+
     /// Called when this buffer loses focus (e.g., switching to another buffer,
     /// opening a prompt, focusing file explorer, etc.)
     /// Dismisses transient popups like Hover and Signature Help.
@@ -0,0 +1,525 @@
+//! Headless editor harness for end-to-end tests of the editor and of plugins.
+//!
+//! [`TestHarness`] runs a full [`Editor`] against an in-memory terminal. Tests
+//! inject key and mouse events, read the rendered frame back as text and make
+//! assertions on it or on the editor state. Enable the `testing` feature to use
+//! it from another crate.
+//!
+//! Time is virtual: the editor's clock only moves when the test calls
+//! [`TestHarness::advance_time`], which then runs every debounce and plugin
+//! `editor.delay` that has come due. Plugin code itself runs on its own thread,
+//! so wait for its effects with [`TestHarness::wait_until`] before advancing
+//! the clock past a delay it schedules. Plugin `setTimeout` timers and process
+//! or network I/O still take real time.
+//!
+//! ```no_run
+//! use fresh::testing::{TestHarness, TestHarnessOptions};
+//! use std::time::Duration;
+//!
+//! let mut harness = TestHarness::create(
+//!     80,
+//!     24,
+//!     TestHarnessOptions::new().with_plugin(
+//!         "greet",
+//!         r#"
+//!         const editor = getEditor();
+//!         globalThis.greet = async () => {
+//!             await editor.delay(500);
+//!             editor.setStatus("Hello");
+//!         };
+//!         editor.registerCommand("Greet", "Say hello", "greet", null);
+//!         "#,
+//!     ),
+//! )?;
+//!
+//! harness.run_command("Greet")?;
+//! harness.wait_until(|h| h.pending_plugin_delays() == 1)?;
+//! harness.advance_time(Duration::from_millis(500))?;
+//! harness.wait_for_screen_contains("Hello")?;
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use crate::app::Editor;
+use crate::config::{Config, KeybindingMapName};
+use crate::config_io::DirectoryContext;
+use crate::model::filesystem::StdFileSystem;
+use crate::services::time_source::{SharedTimeSource, TestTimeSource};
+use crate::view::color_support::ColorCapability;
+use anyhow::{anyhow, Result as AnyhowResult};
+use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::TempDir;
+
+/// How long [`TestHarness::wait_until`] waits by default
+const DEFAULT_WAIT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Real time slept between checks while waiting for other threads
+const WAIT_POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Options for [`TestHarness::create`]
+#[derive(Default)]
+pub struct TestHarnessOptions {
+    config: Option<Config>,
+    working_dir: Option<PathBuf>,
+    plugins: Vec<(String, String)>,
+    wait_timeout: Option<Duration>,
+}
+
+impl TestHarnessOptions {
+    /// Default options: default config, a temporary working directory and no plugins
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use this configuration instead of the default one
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = Some(config);
+        self
+    }
+
+    /// Run the editor in this directory instead of a temporary one
+    ///
+    /// Plugins are loaded from its `plugins` subdirectory.
+    pub fn with_working_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.working_dir = Some(dir.into());
+        self
+    }
+
+    /// Load a plugin from source, written to `plugins/<name>.ts` in the working directory
+    pub fn with_plugin(mut self, name: &str, source: &str) -> Self {
+        self.plugins.push((name.to_string(), source.to_string()));
+        self
+    }
+
+    /// Give up on [`TestHarness::wait_until`] after this much real time
+    pub fn with_wait_timeout(mut self, timeout: Duration) -> Self {
+        self.wait_timeout = Some(timeout);
+        self
+    }
+}
+
+/// An editor running in a virtual terminal with a virtual clock
+pub struct TestHarness {
+    editor: Editor,
+    terminal: Terminal<TestBackend>,
+    clock: Arc<TestTimeSource>,
+    working_dir: PathBuf,
+    wait_timeout: Duration,
+    /// Text of every frame rendered while recording
+    frames: Option<Vec<String>>,
+    /// Keeps the temporary directories alive for the harness's lifetime
+    _temp_dir: TempDir,
+}
+
+impl TestHarness {
+    /// Create a harness with the default options
+    pub fn new(width: u16, height: u16) -> AnyhowResult<Self> {
+        Self::create(width, height, TestHarnessOptions::new())
+    }
+
+    /// Create a harness with a `width` x `height` terminal
+    pub fn create(width: u16, height: u16, options: TestHarnessOptions) -> AnyhowResult<Self> {
+        let temp_dir = TempDir::new()?;
+        let working_dir = match options.working_dir {
+            Some(dir) => dir,
+            None => {
+                let dir = temp_dir.path().join("project");
+                std::fs::create_dir(&dir)?;
+                dir
+            }
+        };
+
+        // An existing plugins directory also keeps the embedded plugins from loading
+        let plugins_dir = working_dir.join("plugins");
+        std::fs::create_dir_all(&plugins_dir)?;
+        for (name, source) in &options.plugins {
+            std::fs::write(plugins_dir.join(format!("{name}.ts")), source)?;
+        }
+
+        // Same keys and labels on every platform, so frames are comparable
+        crate::input::keybindings::set_force_linux_keybindings(true);
+        let mut config = options.config.unwrap_or_default();
+        config.active_keybinding_map = KeybindingMapName("default".to_string());
+        config.check_for_updates = false;
+        crate::i18n::init_with_config(config.locale.as_option());
+
+        let clock = Arc::new(TestTimeSource::new());
+        let time_source: SharedTimeSource = clock.clone();
+        let mut editor = Editor::for_test(
+            config,
+            width,
+            height,
+            Some(working_dir.clone()),
+            DirectoryContext::for_testing(temp_dir.path()),
+            ColorCapability::TrueColor,
+            Arc::new(StdFileSystem),
+            Some(time_source),
+        )?;
+        editor.set_virtual_plugin_delays(true);
+
+        let mut harness = Self {
+            editor,
+            terminal: Terminal::new(TestBackend::new(width, height))?,
+            clock,
+            working_dir,
+            wait_timeout: options.wait_timeout.unwrap_or(DEFAULT_WAIT_TIMEOUT),
+            frames: None,
+            _temp_dir: temp_dir,
+        };
+        harness.tick()?;
+        Ok(harness)
+    }
+
+    // =========================================================================
+    // Editor access
+    // =========================================================================
+
+    /// The editor under test
+    pub fn editor(&self) -> &Editor {
+        &self.editor
+    }
+
+    /// The editor under test, for driving it directly
+    pub fn editor_mut(&mut self) -> &mut Editor {
+        &mut self.editor
+    }
+
+    /// Directory the editor runs in
+    pub fn working_dir(&self) -> &Path {
+        &self.working_dir
+    }
+
+    /// Open a file and make it the active buffer
+    pub fn open_file(&mut self, path: &Path) -> AnyhowResult<()> {
+        self.editor.open_file(path)?;
+        self.tick()
+    }
+
+    /// Resize the virtual terminal
+    pub fn resize(&mut self, width: u16, height: u16) -> AnyhowResult<()> {
+        self.terminal.backend_mut().resize(width, height);
+        self.editor.resize(width, height);
+        self.render()
+    }
+
+    // =========================================================================
+    // Input
+    // =========================================================================
+
+    /// Press a key, then let the editor react to it
+    pub fn send_key(&mut self, code: KeyCode, modifiers: KeyModifiers) -> AnyhowResult<()> {
+        self.editor.handle_key(code, modifiers)?;
+        self.tick()
+    }
+
+    /// Press keys written as in plugin mode bindings, separated by spaces
+    ///
+    /// For example `"C-p"`, `"M-x"`, `"RET"` or `"C-x C-s"`.
+    pub fn send_keys(&mut self, keys: &str) -> AnyhowResult<()> {
+        for key in keys.split_whitespace() {
+            let (code, modifiers) =
+                crate::app::parse_key_string(key).ok_or_else(|| anyhow!("unknown key {key:?}"))?;
+            self.editor.handle_key(code, modifiers)?;
+        }
+        self.tick()
+    }
+
+    /// Type text one character at a time
+    pub fn type_text(&mut self, text: &str) -> AnyhowResult<()> {
+        for ch in text.chars() {
+            self.editor
+                .handle_key(KeyCode::Char(ch), KeyModifiers::NONE)?;
+        }
+        self.tick()
+    }
+
+    /// Send a mouse event
+    pub fn send_mouse(&mut self, event: MouseEvent) -> AnyhowResult<()> {
+        self.editor.handle_mouse(event)?;
+        self.tick()
+    }
+
+    /// Click the left button at a screen cell
+    pub fn click(&mut self, column: u16, row: u16) -> AnyhowResult<()> {
+        for kind in [
+            MouseEventKind::Down(MouseButton::Left),
+            MouseEventKind::Up(MouseButton::Left),
+        ] {
+            self.editor.handle_mouse(MouseEvent {
+                kind,
+                column,
+                row,
+                modifiers: KeyModifiers::NONE,
+            })?;
+        }
+        self.tick()
+    }
+
+    /// Run a command by name through the command palette
+    pub fn run_command(&mut self, name: &str) -> AnyhowResult<()> {
+        self.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+        self.wait_until(|h| h.editor.is_prompting())?;
+        self.type_text(name)?;
+        self.send_key(KeyCode::Enter, KeyModifiers::NONE)
+    }
+
+    // =========================================================================
+    // Time
+    // =========================================================================
+
+    /// The virtual clock the editor reads
+    pub fn clock(&self) -> &Arc<TestTimeSource> {
+        &self.clock
+    }
+
+    /// Move the virtual clock forward, then run what came due
+    ///
+    /// Debounces and plugin delays fire in deadline order; the clock never
+    /// moves on its own, so the same steps always give the same result.
+    pub fn advance_time(&mut self, duration: Duration) -> AnyhowResult<()> {
+        self.clock.advance(duration);
+        self.tick()
+    }
+
+    /// Number of plugin `editor.delay` calls waiting for the clock to advance
+    pub fn pending_plugin_delays(&self) -> usize {
+        self.editor.pending_plugin_delay_count()
+    }
+
+    /// Handle messages from plugins and background tasks, fire the timers
+    /// that are due at the current virtual time, and render
+    ///
+    /// Mirrors one iteration of the editor's main loop.
+    pub fn tick(&mut self) -> AnyhowResult<()> {
+        self.editor.poll_save_in_progress();
+        self.editor.process_async_messages();
+        self.editor.check_plugin_delays();
+        self.editor.check_mouse_hover_timer();
+        self.editor.check_completion_trigger_timer();
+        self.editor.check_save_code_actions_timer();
+        self.editor.check_plugin_format_timer();
+        self.editor.check_plugin_undo_group_timer();
+        self.render()
+    }
+
+    /// Tick until `condition` holds
+    ///
+    /// Only real time passes while waiting (for plugin and background
+    /// threads); the virtual clock stays where it is. Fails with the current
+    /// screen after the wait timeout.
+    pub fn wait_until<F>(&mut self, mut condition: F) -> AnyhowResult<()>
+    where
+        F: FnMut(&Self) -> bool,
+    {
+        let start = std::time::Instant::now();
+        loop {
+            self.tick()?;
+            if condition(self) {
+                return Ok(());
+            }
+            if start.elapsed() > self.wait_timeout {
+                return Err(anyhow!(
+                    "condition not met after {:?}\nScreen:\n{}",
+                    self.wait_timeout,
+                    self.screen_text()
+                ));
+            }
+            std::thread::sleep(WAIT_POLL_INTERVAL);
+        }
+    }
+
+    /// Wait until `text` is on screen
+    pub fn wait_for_screen_contains(&mut self, text: &str) -> AnyhowResult<()> {
+        self.wait_until(|h| h.screen_text().contains(text))
+    }
+
+    // =========================================================================
+    // Frames
+    // =========================================================================
+
+    /// Render a frame
+    pub fn render(&mut self) -> AnyhowResult<()> {
+        let editor = &mut self.editor;
+        self.terminal.draw(|frame| editor.render(frame))?;
+        if self.frames.is_some() {
+            let text = self.snapshot();
+            let frames = self.frames.get_or_insert_with(Vec::new);
+            if frames.last() != Some(&text) {
+                frames.push(text);
+            }
+        }
+        Ok(())
+    }
+
+    /// Text of one screen row, padded to the terminal width
+    pub fn row_text(&self, row: u16) -> String {
+        let buffer = self.terminal.backend().buffer();
+        (0..buffer.area.width)
+            .filter_map(|x| buffer.cell((x, row)).map(|cell| cell.symbol().to_string()))
+            .collect()
+    }
+
+    /// Text of the whole screen, rows separated by newlines
+    pub fn screen_text(&self) -> String {
+        let height = self.terminal.backend().buffer().area.height;
+        (0..height)
+            .map(|row| self.row_text(row))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The last frame as a text snapshot: trailing spaces removed from every row
+    pub fn snapshot(&self) -> String {
+        self.screen_text()
+            .lines()
+            .map(str::trim_end)
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Start keeping a snapshot of every distinct frame rendered from now on
+    pub fn record_frames(&mut self) {
+        self.frames = Some(Vec::new());
+    }
+
+    /// Stop recording and return the frames recorded so far
+    pub fn take_frames(&mut self) -> Vec<String> {
+        self.frames.take().unwrap_or_default()
+    }
+
+    // =========================================================================
+    // State
+    // =========================================================================
+
+    /// Text of the active buffer (None if it isn't fully loaded)
+    pub fn buffer_text(&self) -> Option<String> {
+        self.editor.active_state().buffer.to_string()
+    }
+
+    /// Byte offset of the primary cursor in the active buffer
+    pub fn cursor_position(&self) -> usize {
+        self.editor.active_state().cursors.primary().position
+    }
+
+    /// Message shown in the status bar, if any
+    pub fn status_message(&self) -> Option<&str> {
+        self.editor.get_status_message().map(String::as_str)
+    }
+
+    /// Panic with the screen unless `text` is on screen
+    pub fn assert_screen_contains(&self, text: &str) {
+        let screen = self.screen_text();
+        assert!(
+            screen.contains(text),
+            "Expected screen to contain {text:?}\nScreen:\n{screen}"
+        );
+    }
+
+    /// Panic with the screen if `text` is on screen
+    pub fn assert_screen_not_contains(&self, text: &str) {
+        let screen = self.screen_text();
+        assert!(
+            !screen.contains(text),
+            "Expected screen not to contain {text:?}\nScreen:\n{screen}"
+        );
+    }
+
+    /// Panic unless the active buffer holds exactly `expected`
+    pub fn assert_buffer_text(&self, expected: &str) {
+        assert_eq!(
+            self.buffer_text().as_deref(),
+            Some(expected),
+            "Unexpected buffer content"
+        );
+    }
+
+    /// Panic with the errors if any plugin failed
+    pub fn assert_no_plugin_errors(&self) {
+        let errors = self.editor.get_plugin_errors();
+        assert!(errors.is_empty(), "Plugin errors: {errors:#?}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keys_and_snapshot() {
+        let mut harness = TestHarness::new(60, 10).unwrap();
+        harness.type_text("hello").unwrap();
+        harness.send_keys("RET w o r l d").unwrap();
+
+        harness.assert_buffer_text("hello\nworld");
+        assert_eq!(harness.cursor_position(), 11);
+        assert!(harness
+            .snapshot()
+            .lines()
+            .any(|line| line.ends_with("world")));
+        assert!(harness.send_keys("C-nosuchkey").is_err());
+    }
+
+    #[test]
+    fn test_record_frames() {
+        let mut harness = TestHarness::new(60, 10).unwrap();
+        harness.record_frames();
+        harness.type_text("a").unwrap();
+        harness.render().unwrap();
+        harness.type_text("b").unwrap();
+
+        // Rendering an unchanged screen does not add a frame
+        let frames = harness.take_frames();
+        assert_eq!(frames.len(), 2);
+        assert!(frames[1].contains("ab"));
+    }
+
+    #[test]
+    fn test_click_moves_cursor() {
+        let mut harness = TestHarness::new(60, 10).unwrap();
+        harness.type_text("abcdef").unwrap();
+
+        // Click on the "c" in the first content row
+        let row = (0..10)
+            .find(|&row| harness.row_text(row).contains("abcdef"))
+            .unwrap();
+        let text = harness.row_text(row);
+        let column = text[..text.find("abcdef").unwrap()].chars().count() as u16 + 2;
+        harness.click(column, row).unwrap();
+
+        assert_eq!(harness.cursor_position(), 2);
+    }
+
+    #[cfg(feature = "plugins")]
+    #[test]
+    fn test_plugin_delay_follows_clock() {
+        let mut harness = TestHarness::create(
+            60,
+            10,
+            TestHarnessOptions::new().with_plugin(
+                "delayed",
+                r#"
+                const editor = getEditor();
+                editor.delay(500).then(() => editor.setStatus("Delay done"));
+                "#,
+            ),
+        )
+        .unwrap();
+
+        harness
+            .wait_until(|h| h.pending_plugin_delays() == 1)
+            .unwrap();
+        harness.advance_time(Duration::from_millis(499)).unwrap();
+        assert_eq!(harness.pending_plugin_delays(), 1);
+
+        harness.advance_time(Duration::from_millis(1)).unwrap();
+        assert_eq!(harness.pending_plugin_delays(), 0);
+        harness
+            .wait_until(|h| h.status_message() == Some("Delay done"))
+            .unwrap();
+        harness.assert_no_plugin_errors();
+    }
+}
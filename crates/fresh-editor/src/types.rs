@@ -23,6 +23,7 @@ pub mod context_keys {
     pub const HAS_SELECTION: &str = "has_selection";
     pub const FORMATTER_AVAILABLE: &str = "formatter_available";
     pub const INLAY_HINTS: &str = "inlay_hints";
+    pub const HAS_FLOATING_WINDOWS: &str = "has_floating_windows";
 }
 
 /// Configuration for process resource limits
@@ -103,6 +104,12 @@ pub struct LspServerConfig {
     /// These are passed in the `initializationOptions` field of the LSP Initialize request
     #[serde(default)]
     pub initialization_options: Option<serde_json::Value>,
+
+    /// Settings served to the server through `workspace/configuration`,
+    /// keyed by configuration section (e.g. `{"rust-analyzer": {...}}`)
+    /// Changes are pushed to running servers with `workspace/didChangeConfiguration`
+    #[serde(default)]
+    pub settings: Option<serde_json::Value>,
 }
 
 impl LspServerConfig {
@@ -128,6 +135,40 @@ impl LspServerConfig {
             initialization_options: self
                 .initialization_options
                 .or_else(|| defaults.initialization_options.clone()),
+            settings: merge_settings(defaults.settings.as_ref(), self.settings),
+        }
+    }
+}
+
+/// Deep-merge LSP settings, with `overlay` taking precedence over `base`.
+///
+/// Objects are merged key by key; any other value in `overlay` replaces `base`.
+pub fn merge_settings(
+    base: Option<&serde_json::Value>,
+    overlay: Option<serde_json::Value>,
+) -> Option<serde_json::Value> {
+    fn merge(base: &mut serde_json::Value, overlay: serde_json::Value) {
+        match (base, overlay) {
+            (serde_json::Value::Object(base), serde_json::Value::Object(overlay)) => {
+                for (key, value) in overlay {
+                    match base.get_mut(&key) {
+                        Some(existing) => merge(existing, value),
+                        None => {
+                            base.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (base, overlay) => *base = overlay,
+        }
+    }
+
+    match (base, overlay) {
+        (Some(base), Some(overlay)) => {
+            let mut merged = base.clone();
+            merge(&mut merged, overlay);
+            Some(merged)
         }
+        (base, overlay) => overlay.or_else(|| base.cloned()),
     }
 }
@@ -0,0 +1,122 @@
+//! Keybinding cheat sheet rendering
+//!
+//! Renders the cheat sheet modal overlay: a search line followed by the
+//! bindings of the active context, grouped under category headers.
+
+use crate::app::cheat_sheet::{CheatSheet, CheatSheetRow};
+use crate::view::theme::Theme;
+use ratatui::{
+    layout::{Constraint, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+use rust_i18n::t;
+use unicode_width::UnicodeWidthStr;
+
+/// Maximum width of the dialog in characters
+const DIALOG_WIDTH: u16 = 80;
+/// Width reserved for the key column
+const KEY_COLUMN_WIDTH: usize = 24;
+
+/// Render the cheat sheet overlay
+pub fn render_cheat_sheet(frame: &mut Frame, area: Rect, sheet: &CheatSheet, theme: &Theme) {
+    let dialog_width = DIALOG_WIDTH.min(area.width.saturating_sub(4));
+    let dialog_height = area.height.saturating_sub(4);
+
+    let dialog_area = Rect {
+        x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+        y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+        width: dialog_width,
+        height: dialog_height,
+    };
+
+    // Clear the area behind the dialog
+    frame.render_widget(Clear, dialog_area);
+
+    let title = format!(
+        " {} ({}) ",
+        t!("cheat_sheet.title"),
+        sheet.context.to_when_clause()
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(theme.editor_fg))
+        .style(Style::default().bg(theme.editor_bg).fg(theme.editor_fg));
+
+    let inner_area = block.inner(dialog_area);
+    frame.render_widget(block, dialog_area);
+
+    // Layout: search line, bindings, controls
+    let chunks = Layout::vertical([
+        Constraint::Length(2), // Search
+        Constraint::Min(1),    // Bindings
+        Constraint::Length(1), // Controls
+    ])
+    .split(inner_area);
+
+    // Search line
+    let search = Line::from(vec![
+        Span::styled(
+            format!("{} ", t!("cheat_sheet.search")),
+            Style::default()
+                .fg(theme.help_key_fg)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(sheet.query.as_str()),
+        Span::styled("_", Style::default().fg(theme.line_number_fg)),
+    ]);
+    frame.render_widget(Paragraph::new(search), chunks[0]);
+
+    // Bindings
+    let rows = sheet.rows();
+    let visible = chunks[1].height as usize;
+    let scroll = sheet.scroll.min(rows.len().saturating_sub(visible));
+
+    let mut lines: Vec<Line> = Vec::new();
+    if rows.is_empty() {
+        lines.push(Line::from(Span::styled(
+            t!("cheat_sheet.no_matches").to_string(),
+            Style::default().fg(theme.line_number_fg),
+        )));
+    }
+    for row in rows.iter().skip(scroll).take(visible) {
+        match row {
+            CheatSheetRow::Header(label) => {
+                lines.push(Line::from(Span::styled(
+                    label.clone(),
+                    Style::default()
+                        .fg(theme.diagnostic_info_fg)
+                        .add_modifier(Modifier::BOLD),
+                )));
+            }
+            CheatSheetRow::Entry(entry) => {
+                let padding = KEY_COLUMN_WIDTH.saturating_sub(entry.key.width()).max(1);
+                lines.push(Line::from(vec![
+                    Span::raw("  "),
+                    Span::styled(entry.key.clone(), Style::default().fg(theme.help_key_fg)),
+                    Span::raw(" ".repeat(padding)),
+                    Span::raw(entry.description.clone()),
+                ]));
+            }
+        }
+    }
+    frame.render_widget(Paragraph::new(lines), chunks[1]);
+
+    // Controls
+    let controls = Line::from(vec![
+        Span::styled("[Esc]", Style::default().fg(theme.help_key_fg)),
+        Span::raw(" "),
+        Span::raw(t!("cheat_sheet.close").to_string()),
+        Span::raw("  "),
+        Span::styled("[↑↓ PgUp PgDn]", Style::default().fg(theme.help_key_fg)),
+        Span::raw(" "),
+        Span::raw(t!("cheat_sheet.scroll").to_string()),
+    ]);
+    frame.render_widget(
+        Paragraph::new(controls).style(Style::default().fg(theme.line_number_fg)),
+        chunks[2],
+    );
+}
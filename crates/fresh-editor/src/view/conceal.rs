@@ -0,0 +1,273 @@
+//! Conceal rules for plugin-controlled buffer display
+//!
+//! A conceal rule hides the text matching a regular expression, optionally
+//! showing a replacement string in its place, without modifying the buffer.
+//! Rules are applied to the buffer's base token stream one line at a time,
+//! before wrapping, so concealed text takes up no screen space.
+
+use fresh_core::api::{ViewTokenWire, ViewTokenWireKind};
+use regex::Regex;
+
+/// A compiled conceal rule
+#[derive(Debug, Clone)]
+pub struct ConcealRule {
+    /// Pattern matched against each line
+    pub pattern: Regex,
+    /// Text displayed instead of the match (None hides it entirely)
+    pub replacement: Option<String>,
+}
+
+impl ConcealRule {
+    /// Compile a conceal rule from a regex pattern
+    pub fn new(pattern: &str, replacement: Option<String>) -> Result<Self, regex::Error> {
+        Ok(Self {
+            pattern: Regex::new(pattern)?,
+            replacement,
+        })
+    }
+}
+
+/// Apply conceal rules to a token stream.
+///
+/// Only unstyled text and space tokens are considered; newlines, breaks,
+/// binary bytes and styled (plugin-injected) tokens are passed through and
+/// end the current line segment. When matches of different rules overlap,
+/// the one starting first wins (ties go to the earlier rule).
+pub fn apply_conceal_rules(
+    tokens: Vec<ViewTokenWire>,
+    rules: &[ConcealRule],
+) -> Vec<ViewTokenWire> {
+    if rules.is_empty() {
+        return tokens;
+    }
+
+    let mut out = Vec::with_capacity(tokens.len());
+    let mut segment = Segment::default();
+
+    for token in tokens {
+        match &token.kind {
+            ViewTokenWireKind::Text(text) if token.style.is_none() && !text.is_empty() => {
+                for (i, ch) in text.char_indices() {
+                    segment.push(ch, token.source_offset.map(|s| s + i));
+                }
+            }
+            ViewTokenWireKind::Space if token.style.is_none() => {
+                segment.push(' ', token.source_offset);
+            }
+            _ => {
+                segment.flush(rules, &mut out);
+                out.push(token);
+            }
+        }
+    }
+    segment.flush(rules, &mut out);
+
+    out
+}
+
+/// Characters of one line segment with their source offsets
+#[derive(Default)]
+struct Segment {
+    text: String,
+    /// (byte index in `text`, char, source offset)
+    chars: Vec<(usize, char, Option<usize>)>,
+}
+
+impl Segment {
+    fn push(&mut self, ch: char, source_offset: Option<usize>) {
+        self.chars.push((self.text.len(), ch, source_offset));
+        self.text.push(ch);
+    }
+
+    fn flush(&mut self, rules: &[ConcealRule], out: &mut Vec<ViewTokenWire>) {
+        if self.chars.is_empty() {
+            return;
+        }
+
+        // Collect non-overlapping concealed ranges (byte ranges into `text`)
+        let mut matches: Vec<(usize, usize, Option<&str>)> = rules
+            .iter()
+            .flat_map(|rule| {
+                rule.pattern
+                    .find_iter(&self.text)
+                    .filter(|m| !m.is_empty())
+                    .map(move |m| (m.start(), m.end(), rule.replacement.as_deref()))
+            })
+            .collect();
+        matches.sort_by_key(|&(start, _, _)| start);
+        let mut concealed: Vec<(usize, usize, Option<&str>)> = Vec::new();
+        for m in matches {
+            if concealed.last().is_none_or(|&(_, end, _)| m.0 >= end) {
+                concealed.push(m);
+            }
+        }
+
+        let mut ranges = concealed.into_iter().peekable();
+        for &(idx, ch, source_offset) in &self.chars {
+            while ranges.peek().is_some_and(|&(_, end, _)| idx >= end) {
+                ranges.next();
+            }
+            if let Some(&(start, _, replacement)) = ranges.peek() {
+                if idx >= start {
+                    if idx == start {
+                        if let Some(replacement) = replacement.filter(|r| !r.is_empty()) {
+                            out.push(ViewTokenWire {
+                                source_offset: None,
+                                kind: ViewTokenWireKind::Text(replacement.to_string()),
+                                style: None,
+                            });
+                        }
+                    }
+                    continue;
+                }
+            }
+            push_char(out, ch, source_offset);
+        }
+
+        self.text.clear();
+        self.chars.clear();
+    }
+}
+
+/// Append a visible character, extending the previous text token when contiguous
+fn push_char(out: &mut Vec<ViewTokenWire>, ch: char, source_offset: Option<usize>) {
+    if ch == ' ' {
+        out.push(ViewTokenWire {
+            source_offset,
+            kind: ViewTokenWireKind::Space,
+            style: None,
+        });
+        return;
+    }
+    if let Some(last) = out.last_mut() {
+        if let ViewTokenWireKind::Text(ref mut s) = last.kind {
+            let expected = last.source_offset.map(|o| o + s.len());
+            if last.style.is_none() && expected.is_some() && expected == source_offset {
+                s.push(ch);
+                return;
+            }
+        }
+    }
+    out.push(ViewTokenWire {
+        source_offset,
+        kind: ViewTokenWireKind::Text(ch.to_string()),
+        style: None,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str, offset: usize) -> ViewTokenWire {
+        ViewTokenWire {
+            source_offset: Some(offset),
+            kind: ViewTokenWireKind::Text(s.to_string()),
+            style: None,
+        }
+    }
+
+    fn space(offset: usize) -> ViewTokenWire {
+        ViewTokenWire {
+            source_offset: Some(offset),
+            kind: ViewTokenWireKind::Space,
+            style: None,
+        }
+    }
+
+    fn newline(offset: usize) -> ViewTokenWire {
+        ViewTokenWire {
+            source_offset: Some(offset),
+            kind: ViewTokenWireKind::Newline,
+            style: None,
+        }
+    }
+
+    /// Render tokens as (display text, source offset) pairs
+    fn summarize(tokens: &[ViewTokenWire]) -> Vec<(String, Option<usize>)> {
+        tokens
+            .iter()
+            .map(|t| {
+                let s = match &t.kind {
+                    ViewTokenWireKind::Text(s) => s.clone(),
+                    ViewTokenWireKind::Space => " ".to_string(),
+                    ViewTokenWireKind::Newline => "\n".to_string(),
+                    ViewTokenWireKind::Break => "<break>".to_string(),
+                    ViewTokenWireKind::BinaryByte(b) => format!("<{:02X}>", b),
+                };
+                (s, t.source_offset)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_conceal_hides_match_and_keeps_offsets() {
+        // "a **b** c\n"
+        let tokens = vec![
+            text("a", 0),
+            space(1),
+            text("**b**", 2),
+            space(7),
+            text("c", 8),
+            newline(9),
+        ];
+        let rules = vec![ConcealRule::new(r"\*\*", None).unwrap()];
+        let result = apply_conceal_rules(tokens, &rules);
+        assert_eq!(
+            summarize(&result),
+            vec![
+                ("a".to_string(), Some(0)),
+                (" ".to_string(), Some(1)),
+                ("b".to_string(), Some(4)),
+                (" ".to_string(), Some(7)),
+                ("c".to_string(), Some(8)),
+                ("\n".to_string(), Some(9)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conceal_replacement_spans_spaces() {
+        // "commit abc123 msg"
+        let tokens = vec![
+            text("commit", 0),
+            space(6),
+            text("abc123", 7),
+            space(13),
+            text("msg", 14),
+        ];
+        let rules = vec![ConcealRule::new(r"^commit ", Some("● ".to_string())).unwrap()];
+        let result = apply_conceal_rules(tokens, &rules);
+        assert_eq!(
+            summarize(&result),
+            vec![
+                ("● ".to_string(), None),
+                ("abc123".to_string(), Some(7)),
+                (" ".to_string(), Some(13)),
+                ("msg".to_string(), Some(14)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_conceal_applies_per_line() {
+        let tokens = vec![text("x1", 0), newline(2), text("x2", 3)];
+        let rules = vec![ConcealRule::new(r"^x", None).unwrap()];
+        let result = apply_conceal_rules(tokens, &rules);
+        assert_eq!(
+            summarize(&result),
+            vec![
+                ("1".to_string(), Some(1)),
+                ("\n".to_string(), Some(2)),
+                ("2".to_string(), Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_rules_is_identity() {
+        let tokens = vec![text("abc", 0), newline(3)];
+        let result = apply_conceal_rules(tokens.clone(), &[]);
+        assert_eq!(summarize(&result), summarize(&tokens));
+    }
+}
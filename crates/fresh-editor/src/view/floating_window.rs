@@ -0,0 +1,472 @@
+//! Floating tool windows
+//!
+//! A floating window holds a popup that was "pinned" out of the popup stack.
+//! Unlike regular popups it is not tied to the cursor: it keeps a fixed screen
+//! area, survives cursor movement and buffer switches, and can be moved and
+//! resized. Windows are kept in z-order (last = topmost) and rendered in a
+//! floating layer above the splits but below popups, prompts and menus.
+
+use crate::view::popup::{Popup, PopupContent, PopupKind};
+use crate::view::theme::Theme;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Modifier, Style},
+    Frame,
+};
+use rust_i18n::t;
+
+/// Smallest width a floating window can be resized to
+const MIN_WIDTH: u16 = 12;
+/// Smallest height a floating window can be resized to
+const MIN_HEIGHT: u16 = 3;
+
+/// Unique identifier of a floating window
+pub type FloatingWindowId = usize;
+
+/// A popup pinned into a floating window
+#[derive(Debug, Clone)]
+pub struct FloatingWindow {
+    pub id: FloatingWindowId,
+    /// Title shown in the border and in the Windows menu
+    pub title: String,
+    /// The pinned popup (content, scroll state and styling)
+    pub popup: Popup,
+    /// Screen area, including the border
+    pub area: Rect,
+}
+
+impl FloatingWindow {
+    /// Resize the window; the popup's own size drives wrapping and scroll limits
+    fn set_size(&mut self, width: u16, height: u16) {
+        self.area.width = width;
+        self.area.height = height;
+        self.popup.width = width;
+        self.popup.max_height = height;
+    }
+}
+
+/// Result of a key press while a floating window has focus
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingWindowInput {
+    /// The key was handled by the focused window
+    Consumed,
+    /// The key does not belong to the window; focus returned to the editor
+    Unhandled,
+}
+
+/// Part of a floating window under the mouse
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FloatingWindowHit {
+    /// Top border (drag to move)
+    TitleBar,
+    /// Bottom-right corner (drag to resize)
+    ResizeHandle,
+    /// Anywhere else inside the window
+    Body,
+}
+
+/// Floating windows in z-order, plus keyboard focus
+#[derive(Debug, Default)]
+pub struct FloatingWindowManager {
+    /// Windows from bottom to top
+    windows: Vec<FloatingWindow>,
+    /// Window receiving keyboard input, if any
+    focused: Option<FloatingWindowId>,
+    /// Id for the next pinned window
+    next_id: FloatingWindowId,
+}
+
+impl FloatingWindowManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Whether a popup can be pinned into a floating window.
+    /// Completion and action popups act on the cursor and make no sense detached.
+    pub fn can_pin(popup: &Popup) -> bool {
+        !matches!(popup.kind, PopupKind::Completion | PopupKind::Action)
+    }
+
+    /// Pin a popup into a new topmost window at the given area
+    pub fn pin(&mut self, mut popup: Popup, area: Rect) -> FloatingWindowId {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let title = popup
+            .title
+            .clone()
+            .filter(|t| !t.trim().is_empty())
+            .unwrap_or_else(|| match popup.kind {
+                PopupKind::Hover => t!("floating_window.documentation").to_string(),
+                _ => t!("floating_window.untitled").to_string(),
+            });
+        popup.title = Some(format!(" {} ", title.trim()));
+        popup.transient = false;
+        popup.bordered = true;
+
+        let area = Rect {
+            width: area.width.max(MIN_WIDTH),
+            height: area.height.max(MIN_HEIGHT),
+            ..area
+        };
+        let mut window = FloatingWindow {
+            id,
+            title: title.trim().to_string(),
+            popup,
+            area,
+        };
+        window.set_size(area.width, area.height);
+        self.windows.push(window);
+        id
+    }
+
+    /// Close a window; returns true if it existed
+    pub fn close(&mut self, id: FloatingWindowId) -> bool {
+        let before = self.windows.len();
+        self.windows.retain(|w| w.id != id);
+        if self.focused == Some(id) {
+            self.focused = None;
+        }
+        self.windows.len() != before
+    }
+
+    /// Close the focused window, or the topmost one when none has focus
+    pub fn close_active(&mut self) -> bool {
+        match self.focused.or_else(|| self.windows.last().map(|w| w.id)) {
+            Some(id) => self.close(id),
+            None => false,
+        }
+    }
+
+    /// Move a window to the top of the stack
+    pub fn raise(&mut self, id: FloatingWindowId) {
+        if let Some(idx) = self.windows.iter().position(|w| w.id == id) {
+            let window = self.windows.remove(idx);
+            self.windows.push(window);
+        }
+    }
+
+    /// Raise a window and give it keyboard focus; returns false if it doesn't exist
+    pub fn focus(&mut self, id: FloatingWindowId) -> bool {
+        if self.get(id).is_none() {
+            return false;
+        }
+        self.raise(id);
+        self.focused = Some(id);
+        true
+    }
+
+    /// Focus the next window, cycling through the stack.
+    /// The bottom-most window is brought to the front so repeated calls visit every window.
+    pub fn focus_next(&mut self) -> bool {
+        let next = match self.focused {
+            Some(_) if self.windows.len() > 1 => self.windows.first().map(|w| w.id),
+            Some(id) => Some(id),
+            None => self.windows.last().map(|w| w.id),
+        };
+        next.is_some_and(|id| self.focus(id))
+    }
+
+    /// Return keyboard focus to the editor
+    pub fn unfocus(&mut self) {
+        self.focused = None;
+    }
+
+    pub fn focused(&self) -> Option<FloatingWindowId> {
+        self.focused
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    /// All windows from bottom to top
+    pub fn all(&self) -> &[FloatingWindow] {
+        &self.windows
+    }
+
+    pub fn get(&self, id: FloatingWindowId) -> Option<&FloatingWindow> {
+        self.windows.iter().find(|w| w.id == id)
+    }
+
+    pub fn get_mut(&mut self, id: FloatingWindowId) -> Option<&mut FloatingWindow> {
+        self.windows.iter_mut().find(|w| w.id == id)
+    }
+
+    /// Move a window by the given offset, keeping it inside `bounds`
+    pub fn move_by(&mut self, id: FloatingWindowId, dx: i32, dy: i32, bounds: Rect) {
+        if let Some(window) = self.get_mut(id) {
+            let x = window.area.x as i32 + dx;
+            let y = window.area.y as i32 + dy;
+            window.area = clamp_position(window.area, x, y, bounds);
+        }
+    }
+
+    /// Move a window so its top-left corner is at (x, y), keeping it inside `bounds`
+    pub fn move_to(&mut self, id: FloatingWindowId, x: i32, y: i32, bounds: Rect) {
+        if let Some(window) = self.get_mut(id) {
+            window.area = clamp_position(window.area, x, y, bounds);
+        }
+    }
+
+    /// Grow or shrink a window, keeping it inside `bounds`
+    pub fn resize_by(&mut self, id: FloatingWindowId, dw: i32, dh: i32, bounds: Rect) {
+        if let Some(window) = self.get_mut(id) {
+            let max_width = (bounds.x + bounds.width).saturating_sub(window.area.x);
+            let max_height = (bounds.y + bounds.height).saturating_sub(window.area.y);
+            let width = (window.area.width as i32 + dw).clamp(0, u16::MAX as i32) as u16;
+            let height = (window.area.height as i32 + dh).clamp(0, u16::MAX as i32) as u16;
+            window.set_size(
+                width.min(max_width).max(MIN_WIDTH.min(max_width)),
+                height.min(max_height).max(MIN_HEIGHT.min(max_height)),
+            );
+        }
+    }
+
+    /// Topmost window under a screen position, and which part of it was hit
+    pub fn hit_test(&self, col: u16, row: u16) -> Option<(FloatingWindowId, FloatingWindowHit)> {
+        self.windows.iter().rev().find_map(|window| {
+            let area = window.area;
+            let inside = col >= area.x
+                && col < area.x + area.width
+                && row >= area.y
+                && row < area.y + area.height;
+            if !inside {
+                return None;
+            }
+            let hit = if row == area.y {
+                FloatingWindowHit::TitleBar
+            } else if row == area.y + area.height - 1 && col == area.x + area.width - 1 {
+                FloatingWindowHit::ResizeHandle
+            } else {
+                FloatingWindowHit::Body
+            };
+            Some((window.id, hit))
+        })
+    }
+
+    /// Scroll a window's content (or move the selection for list content)
+    pub fn scroll(&mut self, id: FloatingWindowId, delta: i32) {
+        if let Some(window) = self.get_mut(id) {
+            window.popup.scroll_by(delta);
+        }
+    }
+
+    /// Handle a key press for the focused window.
+    ///
+    /// Arrows scroll, Shift+arrows move, Ctrl+arrows resize, Tab cycles focus,
+    /// Ctrl+W closes and Esc returns focus to the editor. Any other key also
+    /// returns focus to the editor and is reported as unhandled so the caller
+    /// can process it normally.
+    pub fn handle_key(&mut self, event: &KeyEvent, bounds: Rect) -> FloatingWindowInput {
+        let Some(id) = self.focused else {
+            return FloatingWindowInput::Unhandled;
+        };
+
+        let arrow = match event.code {
+            KeyCode::Left => Some((-1, 0)),
+            KeyCode::Right => Some((1, 0)),
+            KeyCode::Up => Some((0, -1)),
+            KeyCode::Down => Some((0, 1)),
+            _ => None,
+        };
+
+        match (event.code, event.modifiers, arrow) {
+            (_, KeyModifiers::SHIFT, Some((dx, dy))) => self.move_by(id, dx, dy, bounds),
+            (_, KeyModifiers::CONTROL, Some((dw, dh))) => self.resize_by(id, dw, dh, bounds),
+            (KeyCode::Up | KeyCode::Down, KeyModifiers::NONE, Some((_, dy))) => {
+                if let Some(window) = self.get_mut(id) {
+                    if matches!(window.popup.content, PopupContent::List { .. }) {
+                        if dy < 0 {
+                            window.popup.select_prev();
+                        } else {
+                            window.popup.select_next();
+                        }
+                    } else {
+                        window.popup.scroll_by(dy);
+                    }
+                }
+            }
+            (KeyCode::PageUp, KeyModifiers::NONE, _) => {
+                if let Some(window) = self.get_mut(id) {
+                    window.popup.page_up();
+                }
+            }
+            (KeyCode::PageDown, KeyModifiers::NONE, _) => {
+                if let Some(window) = self.get_mut(id) {
+                    window.popup.page_down();
+                }
+            }
+            (KeyCode::Home, KeyModifiers::NONE, _) => {
+                if let Some(window) = self.get_mut(id) {
+                    window.popup.select_first();
+                }
+            }
+            (KeyCode::End, KeyModifiers::NONE, _) => {
+                if let Some(window) = self.get_mut(id) {
+                    window.popup.select_last();
+                }
+            }
+            (KeyCode::Tab, KeyModifiers::NONE, _) => {
+                self.focus_next();
+            }
+            (KeyCode::Char('w'), KeyModifiers::CONTROL, _) => {
+                self.close(id);
+            }
+            (KeyCode::Esc, _, _) => self.unfocus(),
+            _ => {
+                self.unfocus();
+                return FloatingWindowInput::Unhandled;
+            }
+        }
+        FloatingWindowInput::Consumed
+    }
+}
+
+/// Place `area` at (x, y), clamped so it stays fully inside `bounds`
+fn clamp_position(area: Rect, x: i32, y: i32, bounds: Rect) -> Rect {
+    let max_x = (bounds.x + bounds.width).saturating_sub(area.width);
+    let max_y = (bounds.y + bounds.height).saturating_sub(area.height);
+    Rect {
+        x: x.clamp(bounds.x as i32, max_x.max(bounds.x) as i32) as u16,
+        y: y.clamp(bounds.y as i32, max_y.max(bounds.y) as i32) as u16,
+        ..area
+    }
+}
+
+/// Render all floating windows, bottom to top, clamped to `bounds`
+pub fn render_floating_windows(
+    frame: &mut Frame,
+    bounds: Rect,
+    manager: &FloatingWindowManager,
+    theme: &Theme,
+) {
+    for window in manager.all() {
+        let area = window.area.intersection(bounds);
+        if area.is_empty() {
+            continue;
+        }
+
+        if manager.focused() == Some(window.id) {
+            let mut popup = window.popup.clone();
+            popup.border_style = Style::default()
+                .fg(theme.help_key_fg)
+                .add_modifier(Modifier::BOLD);
+            popup.render(frame, area, theme);
+        } else {
+            window.popup.render(frame, area, theme);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::theme;
+
+    fn text_popup() -> Popup {
+        let theme = Theme::load_builtin(theme::THEME_DARK).unwrap();
+        let lines = (0..30).map(|i| format!("line {}", i)).collect();
+        Popup::text(lines, &theme).with_title("Docs".to_string())
+    }
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    const BOUNDS: Rect = Rect {
+        x: 0,
+        y: 0,
+        width: 80,
+        height: 24,
+    };
+
+    #[test]
+    fn test_pin_and_z_order() {
+        let mut manager = FloatingWindowManager::new();
+        let a = manager.pin(text_popup(), Rect::new(0, 0, 20, 5));
+        let b = manager.pin(text_popup(), Rect::new(5, 2, 20, 5));
+
+        assert_eq!(manager.all().last().unwrap().id, b);
+        assert_eq!(manager.hit_test(6, 3), Some((b, FloatingWindowHit::Body)));
+
+        manager.raise(a);
+        assert_eq!(manager.all().last().unwrap().id, a);
+        assert_eq!(manager.hit_test(6, 3), Some((a, FloatingWindowHit::Body)));
+        assert_eq!(
+            manager.hit_test(5, 0),
+            Some((a, FloatingWindowHit::TitleBar))
+        );
+        assert_eq!(
+            manager.hit_test(19, 4),
+            Some((a, FloatingWindowHit::ResizeHandle))
+        );
+        assert_eq!(manager.hit_test(50, 20), None);
+    }
+
+    #[test]
+    fn test_focus_cycles_through_all_windows() {
+        let mut manager = FloatingWindowManager::new();
+        let a = manager.pin(text_popup(), Rect::new(0, 0, 20, 5));
+        let b = manager.pin(text_popup(), Rect::new(0, 0, 20, 5));
+        let c = manager.pin(text_popup(), Rect::new(0, 0, 20, 5));
+
+        assert!(manager.focus_next());
+        assert_eq!(manager.focused(), Some(c));
+        manager.focus_next();
+        assert_eq!(manager.focused(), Some(a));
+        manager.focus_next();
+        assert_eq!(manager.focused(), Some(b));
+        manager.focus_next();
+        assert_eq!(manager.focused(), Some(c));
+
+        assert!(manager.close_active());
+        assert_eq!(manager.focused(), None);
+        assert!(manager.get(c).is_none());
+    }
+
+    #[test]
+    fn test_keyboard_move_and_resize_stay_in_bounds() {
+        let mut manager = FloatingWindowManager::new();
+        let id = manager.pin(text_popup(), Rect::new(1, 1, 20, 5));
+        manager.focus(id);
+
+        let shift_left = key(KeyCode::Left, KeyModifiers::SHIFT);
+        for _ in 0..5 {
+            manager.handle_key(&shift_left, BOUNDS);
+        }
+        assert_eq!(manager.get(id).unwrap().area, Rect::new(0, 1, 20, 5));
+
+        manager.handle_key(&key(KeyCode::Down, KeyModifiers::CONTROL), BOUNDS);
+        manager.handle_key(&key(KeyCode::Right, KeyModifiers::CONTROL), BOUNDS);
+        assert_eq!(manager.get(id).unwrap().area, Rect::new(0, 1, 21, 6));
+
+        manager.resize_by(id, -100, -100, BOUNDS);
+        assert_eq!(
+            manager.get(id).unwrap().area,
+            Rect::new(0, 1, MIN_WIDTH, MIN_HEIGHT)
+        );
+
+        manager.move_by(id, 500, 500, BOUNDS);
+        let area = manager.get(id).unwrap().area;
+        assert_eq!(area.x + area.width, BOUNDS.width);
+        assert_eq!(area.y + area.height, BOUNDS.height);
+    }
+
+    #[test]
+    fn test_unhandled_key_returns_focus() {
+        let mut manager = FloatingWindowManager::new();
+        let id = manager.pin(text_popup(), Rect::new(0, 0, 20, 5));
+        manager.focus(id);
+
+        let result = manager.handle_key(&key(KeyCode::Down, KeyModifiers::NONE), BOUNDS);
+        assert_eq!(result, FloatingWindowInput::Consumed);
+        assert_eq!(manager.get(id).unwrap().popup.scroll_offset, 1);
+
+        let result = manager.handle_key(&key(KeyCode::Char('a'), KeyModifiers::NONE), BOUNDS);
+        assert_eq!(result, FloatingWindowInput::Unhandled);
+        assert_eq!(manager.focused(), None);
+        assert!(manager.get(id).is_some());
+    }
+}
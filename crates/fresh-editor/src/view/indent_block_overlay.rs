@@ -0,0 +1,182 @@
+//! Indentation block guide using the overlay system
+//!
+//! While the cursor is inside an indented block, a faint vertical guide is
+//! drawn in the leading whitespace of the block's lines, at the indentation
+//! level of the enclosing line. Only lines in the viewport get overlays.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::primitives::indent_block::find_indent_block;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, OverlayNamespace};
+use ratatui::style::Color;
+
+/// Namespace for indentation block guide overlays
+pub fn indent_block_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("indent-block".to_string())
+}
+
+/// Lines scanned in each direction when looking for the block around the cursor
+const MAX_SCAN_LINES: usize = 5_000;
+
+/// Manager for indentation block guide overlays
+pub struct IndentBlockOverlay {
+    /// Whether the guide is shown
+    pub enabled: bool,
+    /// Cursor position, viewport and buffer length the guide was computed for
+    last_key: Option<(usize, usize, usize, usize)>,
+}
+
+impl IndentBlockOverlay {
+    /// Create a new indentation block guide manager
+    pub fn new() -> Self {
+        Self {
+            enabled: true,
+            last_key: None,
+        }
+    }
+
+    /// Update the guide for the block around the cursor
+    ///
+    /// Returns true if overlays were updated
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        buffer: &mut Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        cursor_position: usize,
+        viewport_start: usize,
+        viewport_end: usize,
+        tab_size: usize,
+        color: Color,
+    ) -> bool {
+        if !self.enabled {
+            return false;
+        }
+
+        let key = (cursor_position, viewport_start, viewport_end, buffer.len());
+        if self.last_key == Some(key) {
+            return false;
+        }
+        self.last_key = Some(key);
+
+        let ns = indent_block_namespace();
+        overlays.clear_namespace(&ns, marker_list);
+
+        let Some(block) = find_indent_block(buffer, cursor_position, tab_size, 80, MAX_SCAN_LINES)
+        else {
+            return true;
+        };
+        // Top-level blocks have no enclosing indentation to draw the guide in
+        if block.indent == 0 {
+            return true;
+        }
+        let guide_column = block.indent.saturating_sub(tab_size.max(1));
+
+        let first = block.start.max(viewport_start);
+        let last = block.end.min(viewport_end);
+        let mut iter = buffer.line_iterator(first, 80);
+        while let Some((line_start, line)) = iter.next_line() {
+            if line_start >= last {
+                break;
+            }
+            if let Some(offset) = column_offset(&line, guide_column, tab_size) {
+                let pos = line_start + offset;
+                let overlay = Overlay::with_namespace(
+                    marker_list,
+                    pos..pos + 1,
+                    OverlayFace::Background { color },
+                    ns.clone(),
+                )
+                .with_priority_value(5);
+                overlays.add(overlay);
+            }
+        }
+
+        true
+    }
+
+    /// Force recalculation on next update
+    pub fn invalidate(&mut self) {
+        self.last_key = None;
+    }
+}
+
+impl Default for IndentBlockOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Byte offset of the leading whitespace character covering `column`, if the
+/// line's indentation reaches past it
+fn column_offset(line: &str, column: usize, tab_size: usize) -> Option<usize> {
+    let tab_size = tab_size.max(1);
+    let mut width = 0;
+    for (offset, ch) in line.char_indices() {
+        let next = match ch {
+            ' ' => width + 1,
+            '\t' => width + tab_size - width % tab_size,
+            _ => return None,
+        };
+        if column < next {
+            return Some(offset);
+        }
+        width = next;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column_offset() {
+        assert_eq!(column_offset("        x", 4, 4), Some(4));
+        assert_eq!(column_offset("\t\tx", 4, 4), Some(1));
+        assert_eq!(column_offset("    x", 4, 4), None);
+        assert_eq!(column_offset("\n", 0, 4), None);
+    }
+
+    #[test]
+    fn test_guide_marks_block_lines_in_viewport() {
+        let text = "def f():\n    a = 1\n\n    if a:\n        b = 2\nc = 3\n";
+        let mut buffer = Buffer::from_str_test(text);
+        let mut overlays = OverlayManager::new();
+        let mut markers = MarkerList::new();
+        let mut guide = IndentBlockOverlay::new();
+
+        let cursor = text.find("b = 2").unwrap();
+        assert!(guide.update(
+            &mut buffer,
+            &mut overlays,
+            &mut markers,
+            cursor,
+            0,
+            text.len(),
+            4,
+            Color::DarkGray,
+        ));
+
+        let marked: Vec<usize> = overlays
+            .query_viewport(0, text.len(), &markers)
+            .into_iter()
+            .map(|(_, range)| range.start)
+            .collect();
+        // Only the `b = 2` line is in the block; its guide sits at column 4
+        assert_eq!(marked, vec![text.find("        b = 2").unwrap() + 4]);
+
+        // Nothing changed, nothing to do
+        assert!(!guide.update(
+            &mut buffer,
+            &mut overlays,
+            &mut markers,
+            cursor,
+            0,
+            text.len(),
+            4,
+            Color::DarkGray,
+        ));
+    }
+}
@@ -0,0 +1,179 @@
+//! Whole-line background tints set by plugins (e.g. added/removed/changed
+//! lines of an inline diff)
+//!
+//! Each background is anchored to the start of its line with a marker, so it
+//! follows the line as text is inserted or deleted above it.
+
+use crate::model::marker::{MarkerId, MarkerList};
+use crate::view::theme::Theme;
+use ratatui::style::Color;
+use std::collections::BTreeMap;
+
+/// Color of a line background
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineBackgroundColor {
+    /// A fixed color
+    Color(Color),
+    /// A theme key (e.g. "editor.diff_add_bg"), resolved at render time so
+    /// the tint follows theme changes
+    ThemeKey(String),
+}
+
+impl LineBackgroundColor {
+    /// The color to paint with the given theme, if the theme key exists
+    pub fn resolve(&self, theme: &Theme) -> Option<Color> {
+        match self {
+            Self::Color(color) => Some(*color),
+            Self::ThemeKey(key) => theme.resolve_theme_key(key),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct LineBackground {
+    namespace: String,
+    color: LineBackgroundColor,
+}
+
+/// Line backgrounds of a buffer, grouped by namespace
+#[derive(Debug, Default)]
+pub struct LineBackgrounds {
+    /// Markers at the start of each tinted line
+    markers: MarkerList,
+    /// Backgrounds stored by marker ID
+    backgrounds: BTreeMap<u64, LineBackground>,
+}
+
+impl LineBackgrounds {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adjust all markers after an insertion
+    pub fn adjust_for_insert(&mut self, position: usize, length: usize) {
+        self.markers.adjust_for_insert(position, length);
+    }
+
+    /// Adjust all markers after a deletion
+    pub fn adjust_for_delete(&mut self, position: usize, length: usize) {
+        self.markers.adjust_for_delete(position, length);
+    }
+
+    /// Tint the line starting at `line_start` for a namespace, replacing the
+    /// namespace's previous background for that line if its marker is still
+    /// at the line start (otherwise the new one wins as the latest)
+    pub fn set(&mut self, line_start: usize, namespace: String, color: LineBackgroundColor) {
+        let existing = self
+            .backgrounds
+            .iter()
+            .filter(|(_, background)| background.namespace == namespace)
+            .map(|(&marker_id, _)| MarkerId(marker_id))
+            .find(|&marker_id| self.markers.get_position(marker_id) == Some(line_start));
+        if let Some(marker_id) = existing {
+            self.remove(marker_id);
+        }
+
+        let marker_id = self.markers.create(line_start, true);
+        self.backgrounds
+            .insert(marker_id.0, LineBackground { namespace, color });
+    }
+
+    /// Remove all line backgrounds of a namespace
+    pub fn clear_namespace(&mut self, namespace: &str) {
+        let marker_ids: Vec<MarkerId> = self
+            .backgrounds
+            .iter()
+            .filter(|(_, background)| background.namespace == namespace)
+            .map(|(&marker_id, _)| MarkerId(marker_id))
+            .collect();
+        for marker_id in marker_ids {
+            self.remove(marker_id);
+        }
+    }
+
+    fn remove(&mut self, marker_id: MarkerId) {
+        self.backgrounds.remove(&marker_id.0);
+        self.markers.delete(marker_id);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.backgrounds.is_empty()
+    }
+
+    /// Backgrounds of the lines starting within `viewport_start..=viewport_end`,
+    /// as line number -> color. When several namespaces tint the same line,
+    /// the most recently set background wins.
+    ///
+    /// The `get_line_fn` converts byte offsets to line numbers.
+    pub fn for_viewport(
+        &self,
+        viewport_start: usize,
+        viewport_end: usize,
+        get_line_fn: impl Fn(usize) -> usize,
+    ) -> BTreeMap<usize, LineBackgroundColor> {
+        let mut markers = self.markers.query_range(viewport_start, viewport_end);
+        // Marker IDs increase, so later backgrounds overwrite earlier ones
+        markers.sort_by_key(|(marker_id, _, _)| marker_id.0);
+
+        let mut by_line = BTreeMap::new();
+        for (marker_id, byte_pos, _end) in markers {
+            if let Some(background) = self.backgrounds.get(&marker_id.0) {
+                by_line.insert(get_line_fn(byte_pos), background.color.clone());
+            }
+        }
+        by_line
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Line number of a byte offset in "aaaa\nbbbb\ncccc\n"
+    fn line_of(byte_offset: usize) -> usize {
+        byte_offset / 5
+    }
+
+    #[test]
+    fn test_set_replaces_and_latest_namespace_wins() {
+        let mut backgrounds = LineBackgrounds::new();
+        let red = LineBackgroundColor::Color(Color::Red);
+        let green = LineBackgroundColor::Color(Color::Green);
+        let diff_add = LineBackgroundColor::ThemeKey("editor.diff_add_bg".to_string());
+
+        backgrounds.set(5, "diff".to_string(), red.clone());
+        backgrounds.set(5, "diff".to_string(), green.clone());
+        backgrounds.set(10, "diff".to_string(), red.clone());
+        backgrounds.set(10, "review".to_string(), diff_add.clone());
+
+        let by_line = backgrounds.for_viewport(0, 15, line_of);
+        assert_eq!(by_line.len(), 2);
+        assert_eq!(by_line[&1], green);
+        assert_eq!(by_line[&2], diff_add);
+
+        backgrounds.clear_namespace("review");
+        let by_line = backgrounds.for_viewport(0, 15, line_of);
+        assert_eq!(by_line[&2], red);
+
+        backgrounds.clear_namespace("diff");
+        assert!(backgrounds.is_empty());
+    }
+
+    #[test]
+    fn test_backgrounds_follow_edits() {
+        let mut backgrounds = LineBackgrounds::new();
+        backgrounds.set(
+            5,
+            "diff".to_string(),
+            LineBackgroundColor::Color(Color::Red),
+        );
+
+        // Inserting a line above moves the tint down
+        backgrounds.adjust_for_insert(0, 5);
+        assert!(backgrounds.for_viewport(0, 15, line_of).contains_key(&2));
+
+        // Deleting it moves the tint back up
+        backgrounds.adjust_for_delete(0, 5);
+        assert!(backgrounds.for_viewport(0, 15, line_of).contains_key(&1));
+    }
+}
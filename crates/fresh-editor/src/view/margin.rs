@@ -1,4 +1,6 @@
 use crate::model::marker::{MarkerId, MarkerList};
+use crate::primitives::display_width::char_width;
+use crate::view::theme::Theme;
 use ratatui::style::{Color, Style};
 use std::collections::BTreeMap;
 
@@ -56,6 +58,81 @@ impl LineIndicator {
     }
 }
 
+/// Color of the text in a plugin gutter column
+#[derive(Debug, Clone, PartialEq)]
+pub enum GutterTextColor {
+    /// A fixed color
+    Color(Color),
+    /// A theme key (e.g. "diagnostic.info_fg"), resolved at render time so
+    /// the text follows theme changes
+    ThemeKey(String),
+}
+
+impl GutterTextColor {
+    /// The color to draw with the given theme, if the theme key exists
+    pub fn resolve(&self, theme: &Theme) -> Option<Color> {
+        match self {
+            Self::Color(color) => Some(*color),
+            Self::ThemeKey(key) => theme.resolve_theme_key(key),
+        }
+    }
+}
+
+/// Text of one line in a plugin gutter column
+#[derive(Debug, Clone, PartialEq)]
+pub struct GutterCell {
+    pub text: String,
+    /// Text color (the line number color when unset)
+    pub color: Option<GutterTextColor>,
+}
+
+impl GutterCell {
+    pub fn new(text: impl Into<String>, color: Option<GutterTextColor>) -> Self {
+        Self {
+            text: text.into(),
+            color,
+        }
+    }
+
+    /// The text cut or padded to exactly `width` cells
+    pub fn fit(&self, width: usize) -> String {
+        let mut fitted = String::new();
+        let mut used = 0;
+        for ch in self.text.chars().filter(|ch| !ch.is_control()) {
+            let ch_width = char_width(ch);
+            if used + ch_width > width {
+                break;
+            }
+            fitted.push(ch);
+            used += ch_width;
+        }
+        fitted.push_str(&" ".repeat(width - used));
+        fitted
+    }
+}
+
+/// Widest a plugin gutter column may be, so a plugin can't push the text
+/// off screen
+pub const MAX_GUTTER_COLUMN_WIDTH: usize = 40;
+
+/// A fixed-width column registered by a plugin (e.g. blame, coverage),
+/// drawn left of the indicator column
+#[derive(Debug, Clone)]
+struct GutterColumn {
+    namespace: String,
+    width: usize,
+    /// Cells stored by marker ID (the markers live in `indicator_markers`)
+    cells: BTreeMap<u64, GutterCell>,
+}
+
+/// The cells of a gutter column within the viewport
+#[derive(Debug, Clone)]
+pub struct ViewportGutterColumn {
+    pub width: usize,
+    /// Line number -> cell
+    pub cells: BTreeMap<usize, GutterCell>,
+}
+
 /// Content type for a margin at a specific line
 #[derive(Debug, Clone, PartialEq)]
 pub enum MarginContent {
@@ -314,6 +391,9 @@ pub struct MarginManager {
     /// Maps marker_id -> (namespace -> indicator)
     /// The line number is computed at render time from the marker's byte position
     line_indicators: BTreeMap<u64, BTreeMap<String, LineIndicator>>,
+
+    /// Plugin gutter columns in registration order
+    gutter_columns: Vec<GutterColumn>,
 }
 
 impl MarginManager {
@@ -328,6 +408,7 @@ impl MarginManager {
             diagnostic_indicators: BTreeMap::new(),
             indicator_markers: MarkerList::new(),
             line_indicators: BTreeMap::new(),
+            gutter_columns: Vec::new(),
         }
     }
 
@@ -501,6 +582,128 @@ impl MarginManager {
         by_line
     }
 
+    // =========================================================================
+    // Plugin Gutter Columns
+    // =========================================================================
+
+    /// Add a gutter column for a namespace, or change its width if it exists
+    pub fn set_gutter_column(&mut self, namespace: &str, width: usize) {
+        match self
+            .gutter_columns
+            .iter_mut()
+            .find(|column| column.namespace == namespace)
+        {
+            Some(column) => column.width = width,
+            None => self.gutter_columns.push(GutterColumn {
+                namespace: namespace.to_string(),
+                width,
+                cells: BTreeMap::new(),
+            }),
+        }
+    }
+
+    /// Whether a gutter column exists for a namespace
+    pub fn has_gutter_column(&self, namespace: &str) -> bool {
+        self.gutter_columns
+            .iter()
+            .any(|column| column.namespace == namespace)
+    }
+
+    /// Remove a gutter column and its text
+    pub fn remove_gutter_column(&mut self, namespace: &str) {
+        self.clear_gutter_text(namespace);
+        self.gutter_columns
+            .retain(|column| column.namespace != namespace);
+    }
+
+    /// Set the text of the line starting at `line_start` in a gutter column,
+    /// replacing the column's previous text for that line
+    ///
+    /// The text is anchored to the line start and follows the line through
+    /// edits. Does nothing if the column doesn't exist.
+    pub fn set_gutter_text(&mut self, line_start: usize, namespace: &str, cell: GutterCell) {
+        let Some(column) = self
+            .gutter_columns
+            .iter_mut()
+            .find(|column| column.namespace == namespace)
+        else {
+            return;
+        };
+
+        // Look the line's markers up in the interval tree rather than scanning
+        // the column, so filling a column line by line stays O(N log N)
+        let existing = self
+            .indicator_markers
+            .query_range(line_start, line_start)
+            .into_iter()
+            .find(|&(marker_id, start, _end)| {
+                start == line_start && column.cells.contains_key(&marker_id.0)
+            })
+            .map(|(marker_id, _, _)| marker_id.0);
+        let marker_id = match existing {
+            Some(marker_id) => marker_id,
+            None => self.indicator_markers.create(line_start, true).0,
+        };
+        column.cells.insert(marker_id, cell);
+    }
+
+    /// Clear all text of a gutter column, keeping the column itself
+    pub fn clear_gutter_text(&mut self, namespace: &str) {
+        if let Some(column) = self
+            .gutter_columns
+            .iter_mut()
+            .find(|column| column.namespace == namespace)
+        {
+            for marker_id in std::mem::take(&mut column.cells).into_keys() {
+                self.indicator_markers.delete(MarkerId(marker_id));
+            }
+        }
+    }
+
+    /// Total width of the gutter columns, including the space after each
+    pub fn gutter_columns_width(&self) -> usize {
+        self.gutter_columns
+            .iter()
+            .map(|column| column.width + 1)
+            .sum()
+    }
+
+    /// Gutter columns with their cells for lines starting within
+    /// `viewport_start..=viewport_end`
+    ///
+    /// The `get_line_fn` converts byte offsets to line numbers.
+    pub fn get_gutter_columns_for_viewport(
+        &self,
+        viewport_start: usize,
+        viewport_end: usize,
+        get_line_fn: impl Fn(usize) -> usize,
+    ) -> Vec<ViewportGutterColumn> {
+        let mut columns: Vec<ViewportGutterColumn> = self
+            .gutter_columns
+            .iter()
+            .map(|column| ViewportGutterColumn {
+                width: column.width,
+                cells: BTreeMap::new(),
+            })
+            .collect();
+        if columns.is_empty() {
+            return columns;
+        }
+
+        for (marker_id, byte_pos, _end) in self
+            .indicator_markers
+            .query_range(viewport_start, viewport_end)
+        {
+            for (column, visible) in self.gutter_columns.iter().zip(columns.iter_mut()) {
+                if let Some(cell) = column.cells.get(&marker_id.0) {
+                    visible.cells.insert(get_line_fn(byte_pos), cell.clone());
+                }
+            }
+        }
+
+        columns
+    }
+
     /// Add an annotation to a margin
     pub fn add_annotation(&mut self, annotation: MarginAnnotation) {
         let annotations = match annotation.position {
@@ -618,10 +821,11 @@ impl MarginManager {
         }
     }
 
-    /// Get the total width of the left margin (including separator)
+    /// Get the total width of the left margin (including separator and
+    /// plugin gutter columns)
     /// The separator includes the diagnostic indicator when present
     pub fn left_total_width(&self) -> usize {
-        self.left_config.total_width()
+        self.gutter_columns_width() + self.left_config.total_width()
     }
 
     /// Get the total width of the right margin (including separator)
@@ -1003,4 +1207,51 @@ mod tests {
         assert!(manager.get_line_indicator(7, byte_to_line).is_some());
         assert!(manager.get_line_indicator(9, byte_to_line).is_some());
     }
+
+    #[test]
+    fn test_gutter_cell_fit() {
+        assert_eq!(GutterCell::new("abc", None).fit(5), "abc  ");
+        assert_eq!(GutterCell::new("abcdef", None).fit(4), "abcd");
+        // A double-width character that doesn't fit is dropped and padded
+        assert_eq!(GutterCell::new("ab漢", None).fit(3), "ab ");
+        assert_eq!(GutterCell::new("a\tb", None).fit(3), "ab ");
+    }
+
+    #[test]
+    fn test_gutter_columns() {
+        let mut manager = MarginManager::new();
+        let base_width = manager.left_total_width();
+
+        manager.set_gutter_column("blame", 6);
+        manager.set_gutter_column("coverage", 4);
+        assert_eq!(manager.left_total_width(), base_width + 7 + 5);
+
+        manager.set_gutter_text(line_to_byte(2), "blame", GutterCell::new("alice", None));
+        manager.set_gutter_text(line_to_byte(2), "blame", GutterCell::new("bob", None));
+        manager.set_gutter_text(line_to_byte(3), "coverage", GutterCell::new("80%", None));
+        // Text for a column that doesn't exist is ignored
+        manager.set_gutter_text(line_to_byte(4), "missing", GutterCell::new("x", None));
+
+        let columns = manager.get_gutter_columns_for_viewport(0, 100, byte_to_line);
+        assert_eq!(columns.len(), 2);
+        assert_eq!(columns[0].width, 6);
+        assert_eq!(columns[0].cells.len(), 1);
+        assert_eq!(columns[0].cells[&2].text, "bob");
+        assert_eq!(columns[1].cells[&3].text, "80%");
+
+        // Text follows its line through edits
+        manager.adjust_for_insert(0, 10);
+        let columns = manager.get_gutter_columns_for_viewport(0, 100, byte_to_line);
+        assert_eq!(columns[0].cells[&3].text, "bob");
+
+        // Clearing keeps the column, removing drops its width
+        manager.clear_gutter_text("blame");
+        let columns = manager.get_gutter_columns_for_viewport(0, 100, byte_to_line);
+        assert!(columns[0].cells.is_empty());
+        assert!(manager.has_gutter_column("blame"));
+
+        manager.remove_gutter_column("blame");
+        assert!(!manager.has_gutter_column("blame"));
+        assert_eq!(manager.left_total_width(), base_width + 5);
+    }
 }
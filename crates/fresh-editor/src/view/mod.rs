@@ -21,6 +21,8 @@ pub mod controls;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod dimming;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
+pub mod line_background;
+#[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod margin;
 #[cfg(any(feature = "runtime", feature = "wasm"))]
 pub mod overlay;
@@ -43,12 +45,20 @@ pub mod bracket_highlight_overlay;
 #[cfg(feature = "runtime")]
 pub mod calibration_wizard;
 #[cfg(feature = "runtime")]
+pub mod cheat_sheet;
+#[cfg(feature = "runtime")]
+pub mod conceal;
+#[cfg(feature = "runtime")]
 pub mod event_debug;
 #[cfg(feature = "runtime")]
 pub mod file_browser_input;
 #[cfg(feature = "runtime")]
 pub mod file_tree;
 #[cfg(feature = "runtime")]
+pub mod floating_window;
+#[cfg(feature = "runtime")]
+pub mod indent_block_overlay;
+#[cfg(feature = "runtime")]
 pub mod markdown;
 #[cfg(feature = "runtime")]
 pub mod popup;
@@ -68,3 +78,5 @@ pub mod reference_highlight_overlay;
 pub mod split;
 #[cfg(feature = "runtime")]
 pub mod stream;
+#[cfg(feature = "runtime")]
+pub mod trailing_whitespace_overlay;
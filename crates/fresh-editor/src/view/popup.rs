@@ -156,6 +156,31 @@ impl PopupListItem {
     }
 }
 
+/// One source's contribution to a hover popup
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoverSection {
+    /// Documentation text
+    pub text: String,
+    /// Whether `text` is markdown (otherwise plain text)
+    pub is_markdown: bool,
+}
+
+impl HoverSection {
+    pub fn markdown(text: String) -> Self {
+        Self {
+            text,
+            is_markdown: true,
+        }
+    }
+
+    pub fn plain(text: String) -> Self {
+        Self {
+            text,
+            is_markdown: false,
+        }
+    }
+}
+
 /// A popup/floating window
 /// This is a general-purpose UI primitive that can be used for:
 /// - Completion menus
@@ -253,6 +278,37 @@ impl Popup {
         }
     }
 
+    /// Create a transient hover popup showing documentation
+    ///
+    /// A language server and plugin hover providers can all answer the same
+    /// hover, so the sections are shown one after another, separated by a rule.
+    pub fn hover(
+        sections: &[HoverSection],
+        theme: &crate::view::theme::Theme,
+        registry: Option<&GrammarRegistry>,
+    ) -> Self {
+        let popup = match sections {
+            [section] if !section.is_markdown => {
+                Self::text(section.text.lines().map(String::from).collect(), theme)
+            }
+            _ => {
+                let markdown = sections
+                    .iter()
+                    .map(|section| {
+                        if section.is_markdown {
+                            section.text.clone()
+                        } else {
+                            format!("```\n{}\n```", section.text)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n\n---\n\n");
+                Self::markdown(&markdown, theme, registry)
+            }
+        };
+        popup.with_kind(PopupKind::Hover).with_transient(true)
+    }
+
     /// Create a new popup with a list of items using theme colors
     pub fn list(items: Vec<PopupListItem>, theme: &crate::view::theme::Theme) -> Self {
         Self {
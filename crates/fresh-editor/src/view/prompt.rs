@@ -5,6 +5,8 @@ use crate::primitives::grapheme;
 use crate::primitives::word_navigation::{
     find_word_end_bytes, find_word_start_bytes, is_word_char,
 };
+use crate::services::suggestion_filter::{sort_matches, FilterSource, SuggestionSource};
+use std::sync::Arc;
 
 /// Type of prompt - determines what action to take when user confirms
 #[derive(Debug, Clone, PartialEq)]
@@ -65,23 +67,54 @@ pub enum PromptType {
     SetLineEnding,
     /// Set language/syntax highlighting for current buffer
     SetLanguage,
+    /// Reload the current file decoded with an encoding (select from list)
+    ReopenWithEncoding,
     /// Stop a running LSP server (select from list)
     StopLspServer,
+    /// Enable a detected language server (select from list)
+    EnableLspServer,
     /// Select a theme (select from list)
     /// Stores the original theme name for restoration on cancel
     SelectTheme { original_theme: String },
+    /// Select the theme of the active split (select from list)
+    SelectSplitTheme,
+    /// Pick the split the active tab moves to (select from list)
+    MoveTabToSplit,
+    /// Search all files in the working directory
+    ProjectSearch,
+    /// Import a base16/base24 scheme file as a theme
+    ImportBase16Theme,
+    /// Turn a component back on after starting in safe mode (select from list)
+    SafeModeReenable,
+    /// Pick a loaded plugin to reload from disk
+    ReloadPlugin,
     /// Select a keybinding map (select from list)
     SelectKeybindingMap,
     /// Select a cursor style (select from list)
     SelectCursorStyle,
     /// Select a UI locale/language (select from list)
     SelectLocale,
+    /// Switch to an alternate undo branch (select from list)
+    SwitchUndoBranch,
+    /// Paste a value from the clipboard history (select from list)
+    PasteFromClipboardHistory,
+    /// Insert a Unicode character (select from list, or type a codepoint)
+    InsertUnicode,
+    /// Open a recently opened workspace (select from list), switching to it
+    /// or starting another editor process on it
+    OpenRecentWorkspace { new_process: bool },
+    /// Insert a digraph (reads two characters, then closes)
+    Digraph,
     /// Select a theme for copy with formatting
     CopyWithFormattingTheme,
     /// Confirm reverting a modified file
     ConfirmRevert,
     /// Confirm saving over a file that changed on disk
     ConfirmSaveConflict,
+    /// Confirm a save that replaces invalid UTF-8 bytes the file had
+    ConfirmSaveInvalidUtf8,
+    /// Confirm editing a buffer protected as library code
+    ConfirmEditLibraryFile,
     /// Confirm saving with sudo after permission denied
     ConfirmSudoSave {
         info: crate::model::buffer::SudoSaveRequired,
@@ -109,6 +142,11 @@ pub enum PromptType {
         path: std::path::PathBuf,
         is_dir: bool,
     },
+    /// Confirm allowing a plugin to use a permission from its `plugin.json`
+    ConfirmPluginPermission {
+        plugin_name: String,
+        capability: String,
+    },
     /// Switch to a tab by name (from the current split's open buffers)
     SwitchToTab,
     /// Run shell command on buffer/selection
@@ -118,6 +156,14 @@ pub enum PromptType {
     /// Async prompt from plugin (for editor.prompt() API)
     /// The result is returned via callback resolution
     AsyncPrompt,
+    /// Filterable item list from plugin (for editor.quickPick() API)
+    /// The chosen item(s) are returned via callback resolution
+    QuickPick,
+    /// File browser opened by a plugin (for editor.openFileBrowser() API)
+    /// The chosen path is returned via callback resolution
+    PluginFileBrowser,
+    /// Review of a rename with risky edits (Tab toggles, Enter applies)
+    RenamePreview,
 }
 
 /// Prompt state for the minibuffer
@@ -134,7 +180,7 @@ pub struct Prompt {
     /// Autocomplete suggestions (filtered)
     pub suggestions: Vec<Suggestion>,
     /// Original unfiltered suggestions (for prompts that filter client-side like SwitchToTab)
-    pub original_suggestions: Option<Vec<Suggestion>>,
+    pub original_suggestions: Option<Arc<Vec<Suggestion>>>,
     /// Currently selected suggestion index
     pub selected_suggestion: Option<usize>,
     /// Selection anchor position (for Shift+Arrow selection)
@@ -143,6 +189,10 @@ pub struct Prompt {
     /// Tracks the input value when suggestions were last set by a plugin.
     /// Used to skip Rust-side filtering when plugin has already filtered for this input.
     pub suggestions_set_for_input: Option<String>,
+    /// Hint shown in place of the input while it is empty
+    pub placeholder: Option<String>,
+    /// Show the input as `*` and keep it off the clipboard (password entry)
+    pub masked: bool,
 }
 
 impl Prompt {
@@ -158,6 +208,8 @@ impl Prompt {
             selected_suggestion: None,
             selection_anchor: None,
             suggestions_set_for_input: None,
+            placeholder: None,
+            masked: false,
         }
     }
 
@@ -180,11 +232,13 @@ impl Prompt {
             input: String::new(),
             cursor_pos: 0,
             prompt_type,
-            original_suggestions: Some(suggestions.clone()),
+            original_suggestions: Some(Arc::new(suggestions.clone())),
             suggestions,
             selected_suggestion,
             selection_anchor: None,
             suggestions_set_for_input: None,
+            placeholder: None,
+            masked: false,
         }
     }
 
@@ -211,6 +265,8 @@ impl Prompt {
             selected_suggestion: None,
             selection_anchor,
             suggestions_set_for_input: None,
+            placeholder: None,
+            masked: false,
         }
     }
 
@@ -340,8 +396,6 @@ impl Prompt {
     /// If `match_description` is true, also matches against suggestion descriptions.
     /// Updates `suggestions` with filtered and sorted results.
     pub fn filter_suggestions(&mut self, match_description: bool) {
-        use crate::input::fuzzy::{fuzzy_match, FuzzyMatch};
-
         // Skip filtering if the plugin has already set suggestions for this exact input.
         // This handles the race condition where run_hook("prompt_changed") is async:
         // the plugin may have already responded with filtered results via setPromptSuggestions.
@@ -355,29 +409,18 @@ impl Prompt {
             return;
         };
 
-        let input = &self.input;
-        let mut filtered: Vec<(crate::input::commands::Suggestion, i32)> = original
-            .iter()
-            .filter_map(|s| {
-                let text_result = fuzzy_match(input, &s.text);
-                let desc_result = if match_description {
-                    s.description
-                        .as_ref()
-                        .map(|d| fuzzy_match(input, d))
-                        .unwrap_or_else(FuzzyMatch::no_match)
-                } else {
-                    FuzzyMatch::no_match()
-                };
-                if text_result.matched || desc_result.matched {
-                    Some((s.clone(), text_result.score.max(desc_result.score)))
-                } else {
-                    None
-                }
-            })
+        let source = SuggestionSource {
+            suggestions: Arc::clone(original),
+            match_description,
+        };
+        let mut matches: Vec<(usize, i32)> = (0..source.len())
+            .filter_map(|index| source.score(&self.input, index).map(|score| (index, score)))
+            .collect();
+        sort_matches(&mut matches);
+        self.suggestions = matches
+            .into_iter()
+            .map(|(index, _)| source.suggestion(index))
             .collect();
-
-        filtered.sort_by(|a, b| b.1.cmp(&a.1));
-        self.suggestions = filtered.into_iter().map(|(s, _)| s).collect();
         self.selected_suggestion = if self.suggestions.is_empty() {
             None
         } else {
@@ -143,6 +143,8 @@ impl InputHandler for Prompt {
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
                                 | crate::view::prompt::PromptType::QuickOpen
+                                | crate::view::prompt::PromptType::QuickPick
+                                | crate::view::prompt::PromptType::RenamePreview
                         ) {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
                                 self.input = suggestion.get_value().to_string();
@@ -183,6 +185,8 @@ impl InputHandler for Prompt {
                             self.prompt_type,
                             crate::view::prompt::PromptType::Plugin { .. }
                                 | crate::view::prompt::PromptType::QuickOpen
+                                | crate::view::prompt::PromptType::QuickPick
+                                | crate::view::prompt::PromptType::RenamePreview
                         ) {
                             if let Some(suggestion) = self.suggestions.get(new_selected) {
                                 self.input = suggestion.get_value().to_string();
@@ -227,6 +231,18 @@ impl InputHandler for Prompt {
                 InputResult::Consumed
             }
 
+            // Tab checks the highlighted item in a quick pick list
+            KeyCode::Tab if self.prompt_type == crate::view::prompt::PromptType::QuickPick => {
+                ctx.defer(DeferredAction::ToggleQuickPickItem);
+                InputResult::Consumed
+            }
+
+            // Tab checks the highlighted edit in a rename preview
+            KeyCode::Tab if self.prompt_type == crate::view::prompt::PromptType::RenamePreview => {
+                ctx.defer(DeferredAction::ToggleRenamePreviewEdit);
+                InputResult::Consumed
+            }
+
             // Tab accepts suggestion
             KeyCode::Tab => {
                 if let Some(selected) = self.selected_suggestion {
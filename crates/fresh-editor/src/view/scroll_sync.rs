@@ -1,167 +1,167 @@
-/// Scroll synchronization for side-by-side diff views
+/// Scroll synchronization between splits
 ///
 /// This module implements marker-based sync anchors for synchronized scrolling
-/// between two panes showing different versions of a file (e.g., old vs new in a diff).
+/// between panes showing different versions of a file (e.g., old vs new in a diff),
+/// and proportional sync for panes without known correspondences.
 ///
 /// Key design principles:
-/// - Single source of truth: `scroll_line` is the authoritative position
+/// - Single source of truth: the active split's viewport is authoritative
 /// - Sync anchors mark corresponding lines between buffers (e.g., hunk boundaries)
+/// - Groups without anchors scroll proportionally (same percentage of the scroll range)
 /// - Synchronization happens at render time, not via async commands
-/// - No feedback loops because only one position is tracked
+/// - No feedback loops because only the active split's position is read
 use crate::model::event::SplitId;
 use serde::{Deserialize, Serialize};
 
-/// A sync anchor linking corresponding line positions in two buffers
+/// A sync anchor linking corresponding line positions in the splits of a group
 ///
-/// Anchors are placed at diff hunk boundaries where both buffers
+/// Anchors are placed at diff hunk boundaries where all buffers
 /// have a known correspondence (e.g., start of context, end of hunk).
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct SyncAnchor {
-    /// Line number in the left (primary) buffer
-    pub left_line: usize,
-    /// Line number in the right (secondary) buffer
-    pub right_line: usize,
+    /// Line number in each split's buffer, in the group's split order
+    pub lines: Vec<usize>,
 }
 
 /// A unique identifier for a scroll sync group
 pub type ScrollSyncGroupId = u32;
 
-/// A group of two splits that scroll together with anchor-based synchronization
+/// First ID of groups created by the editor itself. Plugins choose their own
+/// group IDs, counting up from small numbers, so the editor numbers its groups
+/// from here to avoid collisions.
+const FIRST_EDITOR_GROUP_ID: ScrollSyncGroupId = 0x8000_0000;
+
+/// A group of splits that scroll together
 ///
 /// Unlike the simple sync_group which applies the same scroll delta to all splits,
-/// this uses sync anchors to correctly handle buffers with different line counts.
+/// this uses sync anchors to correctly handle buffers with different line counts,
+/// or scrolls proportionally when there are no anchors.
 #[derive(Debug, Clone)]
 pub struct ScrollSyncGroup {
     /// Unique ID for this sync group
     pub id: ScrollSyncGroupId,
-    /// The left (primary) split - scroll position is tracked in this split's line space
-    pub left_split: SplitId,
-    /// The right (secondary) split - position is derived from anchors
-    pub right_split: SplitId,
-    /// Single source of truth: scroll position in left buffer's line space
-    /// Both splits derive their viewport position from this value
-    pub scroll_line: usize,
-    /// Sync anchors ordered by left_line
-    /// These mark corresponding positions between the two buffers
+    /// Member splits. The first is the primary split: anchors are ordered by
+    /// its line numbers.
+    pub splits: Vec<SplitId>,
+    /// Sync anchors ordered by the primary split's line. Empty for
+    /// proportional sync.
     pub anchors: Vec<SyncAnchor>,
 }
 
 impl ScrollSyncGroup {
-    /// Create a new scroll sync group
-    pub fn new(id: ScrollSyncGroupId, left_split: SplitId, right_split: SplitId) -> Self {
+    /// Create a new scroll sync group with no anchors
+    pub fn new(id: ScrollSyncGroupId, splits: Vec<SplitId>) -> Self {
         Self {
             id,
-            left_split,
-            right_split,
-            scroll_line: 0,
-            anchors: vec![SyncAnchor {
-                left_line: 0,
-                right_line: 0,
-            }],
+            splits,
+            anchors: Vec::new(),
         }
     }
 
     /// Set the sync anchors (replacing any existing ones)
-    /// Anchors should be sorted by left_line
+    ///
+    /// Anchors that don't have a line for every split, or have no lines at
+    /// all, are dropped. The rest are sorted by the primary split's line.
+    /// With no anchors the group scrolls proportionally.
     pub fn set_anchors(&mut self, anchors: Vec<SyncAnchor>) {
-        self.anchors = anchors;
-        // Ensure there's always at least the origin anchor
-        if self.anchors.is_empty() {
-            self.anchors.push(SyncAnchor {
-                left_line: 0,
-                right_line: 0,
-            });
-        }
-    }
-
-    /// Check if a split is part of this sync group
-    pub fn contains_split(&self, split_id: SplitId) -> bool {
-        self.left_split == split_id || self.right_split == split_id
+        let split_count = self.splits.len();
+        self.anchors = anchors
+            .into_iter()
+            .filter(|a| !a.lines.is_empty() && a.lines.len() == split_count)
+            .collect();
+        self.anchors.sort_by_key(|a| a.lines.first().copied());
     }
 
-    /// Check if a split is the left (primary) split
-    pub fn is_left_split(&self, split_id: SplitId) -> bool {
-        self.left_split == split_id
+    /// Whether the group syncs by percentage instead of by anchors
+    pub fn is_proportional(&self) -> bool {
+        self.anchors.is_empty()
     }
 
-    /// Convert a line number from left buffer space to right buffer space
-    pub fn left_to_right_line(&self, left_line: usize) -> usize {
-        // Find the anchor just at or before left_line
-        let anchor = self
-            .anchors
-            .iter()
-            .rfind(|a| a.left_line <= left_line)
-            .unwrap_or(&self.anchors[0]);
-
-        // Calculate offset from anchor
-        let offset = left_line.saturating_sub(anchor.left_line);
-
-        // Apply offset to right side
-        anchor.right_line.saturating_add(offset)
+    /// Check if a split is part of this sync group
+    pub fn contains_split(&self, split_id: SplitId) -> bool {
+        self.splits.contains(&split_id)
     }
 
-    /// Convert a line number from right buffer space to left buffer space
-    pub fn right_to_left_line(&self, right_line: usize) -> usize {
-        // Find the anchor just at or before right_line in right buffer space
-        let anchor = self
-            .anchors
-            .iter()
-            .rfind(|a| a.right_line <= right_line)
-            .unwrap_or(&self.anchors[0]);
-
-        // Calculate offset from anchor in right space
-        let offset = right_line.saturating_sub(anchor.right_line);
-
-        // Apply offset to left side
-        anchor.left_line.saturating_add(offset)
+    /// Position of a split in the group's split order
+    pub fn split_index(&self, split_id: SplitId) -> Option<usize> {
+        self.splits.iter().position(|&s| s == split_id)
     }
 
-    /// Update scroll position from a scroll delta on the given split
-    /// Returns true if this group handled the scroll
-    pub fn apply_scroll_delta(&mut self, split_id: SplitId, delta_lines: isize) -> bool {
+    /// Add a split to the group
+    ///
+    /// The new split has no known correspondence with the others, so the
+    /// anchors are cleared and the group scrolls proportionally.
+    pub fn add_split(&mut self, split_id: SplitId) {
         if !self.contains_split(split_id) {
-            return false;
+            self.splits.push(split_id);
+            self.anchors.clear();
         }
+    }
 
-        // Apply delta to scroll_line (which is always in left buffer space)
-        let new_scroll = if delta_lines >= 0 {
-            self.scroll_line.saturating_add(delta_lines as usize)
-        } else {
-            self.scroll_line.saturating_sub(delta_lines.unsigned_abs())
+    /// Remove a split from the group, dropping its column from the anchors
+    /// Returns true if the split was a member
+    pub fn remove_split(&mut self, split_id: SplitId) -> bool {
+        let Some(index) = self.split_index(split_id) else {
+            return false;
         };
-
-        self.scroll_line = new_scroll;
+        self.splits.remove(index);
+        for anchor in &mut self.anchors {
+            anchor.lines.remove(index);
+        }
+        // The last member takes the anchors' only column with it
+        self.anchors.retain(|a| !a.lines.is_empty());
+        self.anchors.sort_by_key(|a| a.lines.first().copied());
         true
     }
 
-    /// Set scroll position directly (used for SetViewport events)
-    /// The line number should be in left buffer space
-    pub fn set_scroll_line(&mut self, line: usize) {
-        self.scroll_line = line;
-    }
+    /// Convert a line number between two splits' buffers using the anchors
+    ///
+    /// `from` and `to` are positions in the group's split order. The line
+    /// keeps its offset from the nearest anchor at or before it; before the
+    /// first anchor, lines map one to one.
+    pub fn anchored_line(&self, from: usize, to: usize, line: usize) -> usize {
+        // Find the anchor just at or before line in the source buffer
+        let Some(anchor) = self.anchors.iter().rfind(|a| a.lines[from] <= line) else {
+            return line;
+        };
 
-    /// Get the scroll line for the left split
-    pub fn left_scroll_line(&self) -> usize {
-        self.scroll_line
+        // Apply the offset from the anchor to the target buffer
+        let offset = line - anchor.lines[from];
+        anchor.lines[to].saturating_add(offset)
     }
 
-    /// Get the scroll line for the right split (derived via anchors)
-    pub fn right_scroll_line(&self) -> usize {
-        self.left_to_right_line(self.scroll_line)
+    /// Target top line of split `to` when split `from` shows `line` at the top
+    ///
+    /// `max_top_lines` holds, in split order, the last top line each split
+    /// can scroll to. It is only used for proportional sync.
+    pub fn target_line(
+        &self,
+        from: usize,
+        to: usize,
+        line: usize,
+        max_top_lines: &[usize],
+    ) -> usize {
+        if !self.is_proportional() {
+            return self.anchored_line(from, to, line);
+        }
+        let from_max = max_top_lines.get(from).copied().unwrap_or(0);
+        let to_max = max_top_lines.get(to).copied().unwrap_or(0);
+        proportional_line(line, from_max, to_max)
     }
+}
 
-    /// Get the scroll line for a specific split
-    pub fn scroll_line_for_split(&self, split_id: SplitId) -> usize {
-        if split_id == self.left_split {
-            self.left_scroll_line()
-        } else {
-            self.right_scroll_line()
-        }
+/// Line at the same percentage of a scroll range `0..=to_max` as `line` is
+/// of `0..=from_max`
+pub fn proportional_line(line: usize, from_max: usize, to_max: usize) -> usize {
+    if from_max == 0 {
+        return 0;
     }
+    let ratio = (line.min(from_max) as f64) / (from_max as f64);
+    (ratio * to_max as f64).round() as usize
 }
 
 /// Manager for scroll sync groups
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ScrollSyncManager {
     /// Active scroll sync groups
     groups: Vec<ScrollSyncGroup>,
@@ -174,34 +174,34 @@ impl ScrollSyncManager {
     pub fn new() -> Self {
         Self {
             groups: Vec::new(),
-            next_id: 1,
+            next_id: FIRST_EDITOR_GROUP_ID,
         }
     }
 
-    /// Create a new scroll sync group and return its ID
-    pub fn create_group(&mut self, left_split: SplitId, right_split: SplitId) -> ScrollSyncGroupId {
+    /// Create a new scroll sync group owned by the editor and return its ID
+    ///
+    /// The splits are removed from any group they were in, since a split
+    /// follows one group at a time.
+    pub fn create_group(&mut self, splits: Vec<SplitId>) -> ScrollSyncGroupId {
         let id = self.next_id;
         self.next_id += 1;
 
-        let group = ScrollSyncGroup::new(id, left_split, right_split);
-        self.groups.push(group);
+        for &split_id in &splits {
+            self.remove_split(split_id);
+        }
+        self.groups.push(ScrollSyncGroup::new(id, splits));
         id
     }
 
     /// Create a scroll sync group with a plugin-provided ID
     /// Returns true if created successfully, false if ID already exists
-    pub fn create_group_with_id(
-        &mut self,
-        id: ScrollSyncGroupId,
-        left_split: SplitId,
-        right_split: SplitId,
-    ) -> bool {
+    pub fn create_group_with_id(&mut self, id: ScrollSyncGroupId, splits: Vec<SplitId>) -> bool {
         // Check if ID already exists
         if self.groups.iter().any(|g| g.id == id) {
             return false;
         }
 
-        let group = ScrollSyncGroup::new(id, left_split, right_split);
+        let group = ScrollSyncGroup::new(id, splits);
         self.groups.push(group);
         true
     }
@@ -221,6 +221,59 @@ impl ScrollSyncManager {
         self.groups.retain(|g| !g.contains_split(split_id));
     }
 
+    /// Add a split to a group, removing it from any other group
+    /// Returns false if the group doesn't exist
+    pub fn add_split(&mut self, id: ScrollSyncGroupId, split_id: SplitId) -> bool {
+        if self.get_group(id).is_none() {
+            return false;
+        }
+        if self
+            .find_group_for_split(split_id)
+            .is_some_and(|g| g.id != id)
+        {
+            self.remove_split(split_id);
+        }
+        if let Some(group) = self.get_group_mut(id) {
+            group.add_split(split_id);
+        }
+        true
+    }
+
+    /// Remove a split from the groups it is in, dropping groups left empty
+    /// Returns true if the split was in a group
+    pub fn remove_split(&mut self, split_id: SplitId) -> bool {
+        let mut removed = false;
+        for group in &mut self.groups {
+            removed |= group.remove_split(split_id);
+        }
+        self.groups.retain(|g| !g.splits.is_empty());
+        removed
+    }
+
+    /// Remove a split from one group, dropping the group if it is left empty
+    /// Returns true if the split was a member of the group
+    pub fn remove_split_from_group(&mut self, id: ScrollSyncGroupId, split_id: SplitId) -> bool {
+        let removed = self
+            .get_group_mut(id)
+            .is_some_and(|group| group.remove_split(split_id));
+        self.groups.retain(|g| !g.splits.is_empty());
+        removed
+    }
+
+    /// Remove splits that no longer exist from all groups, dropping groups
+    /// left empty
+    pub fn retain_splits(&mut self, mut exists: impl FnMut(SplitId) -> bool) {
+        let closed: Vec<SplitId> = self
+            .groups
+            .iter()
+            .flat_map(|g| g.splits.iter().copied())
+            .filter(|&s| !exists(s))
+            .collect();
+        for split_id in closed {
+            self.remove_split(split_id);
+        }
+    }
+
     /// Get a mutable reference to a group by ID
     pub fn get_group_mut(&mut self, id: ScrollSyncGroupId) -> Option<&mut ScrollSyncGroup> {
         self.groups.iter_mut().find(|g| g.id == id)
@@ -246,22 +299,19 @@ impl ScrollSyncManager {
         self.groups.iter().any(|g| g.contains_split(split_id))
     }
 
+    /// Most recently created group made by the editor rather than a plugin
+    pub fn latest_editor_group(&self) -> Option<&ScrollSyncGroup> {
+        self.groups
+            .iter()
+            .filter(|g| g.id >= FIRST_EDITOR_GROUP_ID)
+            .max_by_key(|g| g.id)
+    }
+
     /// Get all groups (for iteration during render)
     pub fn groups(&self) -> &[ScrollSyncGroup] {
         &self.groups
     }
 
-    /// Apply scroll delta to the group containing the split
-    /// Returns true if a group handled the scroll
-    pub fn apply_scroll_delta(&mut self, split_id: SplitId, delta_lines: isize) -> bool {
-        if let Some(group) = self.find_group_for_split_mut(split_id) {
-            group.apply_scroll_delta(split_id, delta_lines);
-            true
-        } else {
-            false
-        }
-    }
-
     /// Set anchors for a group
     pub fn set_anchors(&mut self, group_id: ScrollSyncGroupId, anchors: Vec<SyncAnchor>) {
         if let Some(group) = self.get_group_mut(group_id) {
@@ -270,90 +320,167 @@ impl ScrollSyncManager {
     }
 }
 
+impl Default for ScrollSyncManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn anchor(lines: &[usize]) -> SyncAnchor {
+        SyncAnchor {
+            lines: lines.to_vec(),
+        }
+    }
+
     #[test]
     fn test_left_to_right_line_simple() {
-        let mut group = ScrollSyncGroup::new(1, SplitId(1), SplitId(2));
+        let mut group = ScrollSyncGroup::new(1, vec![SplitId(1), SplitId(2)]);
         group.set_anchors(vec![
-            SyncAnchor {
-                left_line: 0,
-                right_line: 0,
-            },
-            SyncAnchor {
-                left_line: 10,
-                right_line: 10,
-            },
-            SyncAnchor {
-                left_line: 20,
-                right_line: 25,
-            }, // Right has 5 extra lines
+            anchor(&[0, 0]),
+            anchor(&[10, 10]),
+            anchor(&[20, 25]), // Right has 5 extra lines
         ]);
 
         // Before any anchors
-        assert_eq!(group.left_to_right_line(0), 0);
-        assert_eq!(group.left_to_right_line(5), 5);
+        assert_eq!(group.anchored_line(0, 1, 0), 0);
+        assert_eq!(group.anchored_line(0, 1, 5), 5);
 
         // After second anchor (1:1 mapping)
-        assert_eq!(group.left_to_right_line(10), 10);
-        assert_eq!(group.left_to_right_line(15), 15);
+        assert_eq!(group.anchored_line(0, 1, 10), 10);
+        assert_eq!(group.anchored_line(0, 1, 15), 15);
 
         // After third anchor (offset by 5)
-        assert_eq!(group.left_to_right_line(20), 25);
-        assert_eq!(group.left_to_right_line(25), 30);
+        assert_eq!(group.anchored_line(0, 1, 20), 25);
+        assert_eq!(group.anchored_line(0, 1, 25), 30);
     }
 
     #[test]
     fn test_right_to_left_line() {
-        let mut group = ScrollSyncGroup::new(1, SplitId(1), SplitId(2));
+        let mut group = ScrollSyncGroup::new(1, vec![SplitId(1), SplitId(2)]);
         group.set_anchors(vec![
-            SyncAnchor {
-                left_line: 0,
-                right_line: 0,
-            },
-            SyncAnchor {
-                left_line: 10,
-                right_line: 15,
-            }, // Right has 5 extra lines
+            anchor(&[0, 0]),
+            anchor(&[10, 15]), // Right has 5 extra lines
         ]);
 
         // Before anchor
-        assert_eq!(group.right_to_left_line(0), 0);
-        assert_eq!(group.right_to_left_line(5), 5);
+        assert_eq!(group.anchored_line(1, 0, 0), 0);
+        assert_eq!(group.anchored_line(1, 0, 5), 5);
 
         // After anchor
-        assert_eq!(group.right_to_left_line(15), 10);
-        assert_eq!(group.right_to_left_line(20), 15);
+        assert_eq!(group.anchored_line(1, 0, 15), 10);
+        assert_eq!(group.anchored_line(1, 0, 20), 15);
     }
 
     #[test]
-    fn test_scroll_delta() {
-        let mut group = ScrollSyncGroup::new(1, SplitId(1), SplitId(2));
-        group.set_anchors(vec![
-            SyncAnchor {
-                left_line: 0,
-                right_line: 0,
-            },
-            SyncAnchor {
-                left_line: 50,
-                right_line: 60,
-            },
-        ]);
+    fn test_target_line() {
+        let mut group = ScrollSyncGroup::new(1, vec![SplitId(1), SplitId(2)]);
+        group.set_anchors(vec![anchor(&[0, 0]), anchor(&[50, 60])]);
 
         // Initial position
-        assert_eq!(group.left_scroll_line(), 0);
-        assert_eq!(group.right_scroll_line(), 0);
+        assert_eq!(group.target_line(0, 1, 0, &[]), 0);
 
         // Scroll down 10 lines
-        group.apply_scroll_delta(SplitId(1), 10);
-        assert_eq!(group.left_scroll_line(), 10);
-        assert_eq!(group.right_scroll_line(), 10);
+        assert_eq!(group.target_line(0, 1, 10, &[]), 10);
 
         // Scroll to position past anchor
-        group.set_scroll_line(55);
-        assert_eq!(group.left_scroll_line(), 55);
-        assert_eq!(group.right_scroll_line(), 65); // 60 + 5
+        assert_eq!(group.target_line(0, 1, 55, &[]), 65); // 60 + 5
+    }
+
+    #[test]
+    fn test_three_way_anchors() {
+        let mut group = ScrollSyncGroup::new(1, vec![SplitId(1), SplitId(2), SplitId(3)]);
+        group.set_anchors(vec![
+            anchor(&[10, 12, 8]),
+            anchor(&[0, 0, 0]),
+            anchor(&[5]), // Missing lines, dropped
+        ]);
+        assert_eq!(group.anchors.len(), 2);
+
+        assert_eq!(group.anchored_line(0, 2, 13), 11);
+        assert_eq!(group.anchored_line(2, 1, 9), 13);
+        assert_eq!(group.anchored_line(1, 0, 4), 4);
+
+        // Removing the middle split keeps the other columns aligned
+        assert!(group.remove_split(SplitId(2)));
+        assert_eq!(group.splits, vec![SplitId(1), SplitId(3)]);
+        assert_eq!(group.anchored_line(0, 1, 13), 11);
+
+        // A new member has no anchors, so the group becomes proportional
+        group.add_split(SplitId(4));
+        assert!(group.is_proportional());
+    }
+
+    #[test]
+    fn test_empty_anchors_are_dropped() {
+        let mut group = ScrollSyncGroup::new(1, vec![]);
+        group.set_anchors(vec![anchor(&[]), anchor(&[])]);
+        assert!(group.is_proportional());
+
+        let mut group = ScrollSyncGroup::new(2, vec![SplitId(1)]);
+        group.set_anchors(vec![anchor(&[4]), anchor(&[2])]);
+        assert!(group.remove_split(SplitId(1)));
+        assert!(group.anchors.is_empty());
+
+        let mut manager = ScrollSyncManager::new();
+        assert!(manager.create_group_with_id(1, vec![SplitId(1), SplitId(2)]));
+        manager.set_anchors(1, vec![anchor(&[0, 0]), anchor(&[8, 3])]);
+        assert!(manager.remove_split_from_group(1, SplitId(1)));
+        assert_eq!(
+            manager.get_group(1).unwrap().anchors,
+            vec![anchor(&[0]), anchor(&[3])]
+        );
+        assert!(manager.remove_split_from_group(1, SplitId(2)));
+        assert!(manager.get_group(1).is_none());
+        assert!(!manager.remove_split_from_group(1, SplitId(2)));
+    }
+
+    #[test]
+    fn test_proportional_line() {
+        let group = ScrollSyncGroup::new(1, vec![SplitId(1), SplitId(2), SplitId(3)]);
+        let max_top_lines = [100, 400, 0];
+
+        assert_eq!(group.target_line(0, 1, 0, &max_top_lines), 0);
+        assert_eq!(group.target_line(0, 1, 50, &max_top_lines), 200);
+        assert_eq!(group.target_line(1, 0, 400, &max_top_lines), 100);
+        // Scrolled past the end of the range
+        assert_eq!(group.target_line(0, 1, 150, &max_top_lines), 400);
+        // Buffers that fit in their split stay at the top
+        assert_eq!(group.target_line(0, 2, 50, &max_top_lines), 0);
+        assert_eq!(group.target_line(2, 0, 0, &max_top_lines), 0);
+    }
+
+    #[test]
+    fn test_manager_membership() {
+        let mut manager = ScrollSyncManager::new();
+        assert!(manager.create_group_with_id(1, vec![SplitId(1), SplitId(2)]));
+        assert!(!manager.create_group_with_id(1, vec![SplitId(3)]));
+
+        // Editor groups take their splits out of other groups
+        let id = manager.create_group(vec![SplitId(2), SplitId(3)]);
+        assert_ne!(id, 1);
+        assert_eq!(manager.get_group(1).unwrap().splits, vec![SplitId(1)]);
+        assert_eq!(manager.latest_editor_group().unwrap().id, id);
+
+        assert!(manager.add_split(id, SplitId(1)));
+        assert!(manager.get_group(1).is_none());
+        assert_eq!(
+            manager.get_group(id).unwrap().splits,
+            vec![SplitId(2), SplitId(3), SplitId(1)]
+        );
+
+        manager.retain_splits(|s| s != SplitId(3));
+        assert_eq!(
+            manager.find_group_for_split(SplitId(1)).unwrap().splits,
+            vec![SplitId(2), SplitId(1)]
+        );
+
+        assert!(manager.remove_split(SplitId(1)));
+        assert!(manager.remove_split(SplitId(2)));
+        assert!(manager.groups().is_empty());
+        assert!(!manager.add_split(id, SplitId(1)));
     }
 }
@@ -3,11 +3,26 @@
 //! Provides a modal dialog for editing complex map entries using the same
 //! SettingItem/SettingControl infrastructure as the main settings UI.
 
-use super::items::{build_item_from_value, control_to_value, SettingControl, SettingItem};
+use super::items::{
+    build_item_from_value, control_to_value, JsonEditState, SettingControl, SettingItem,
+};
 use super::schema::{SettingSchema, SettingType};
+use crate::input::keybindings::KeyContext;
 use crate::view::controls::{FocusState, TextInputState};
+use rust_i18n::t;
 use serde_json::Value;
 
+/// Result of capturing a shortcut with the keybinding recorder
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedKey {
+    /// Display form of the captured key combination (e.g. "Ctrl+Shift+K")
+    pub combo: String,
+    /// Context the binding applies in, from its `when` field
+    pub context: KeyContext,
+    /// Action already bound to the combination in that context, if any
+    pub conflict: Option<String>,
+}
+
 /// State for the entry detail dialog
 #[derive(Debug, Clone)]
 pub struct EntryDialogState {
@@ -48,6 +63,10 @@ pub struct EntryDialogState {
     pub first_editable_index: usize,
     /// Whether deletion is disabled (for auto-managed entries like plugins)
     pub no_delete: bool,
+    /// Whether the keybinding recorder is waiting for a key press
+    pub recording_key: bool,
+    /// Last shortcut captured by the keybinding recorder
+    pub recorded_key: Option<RecordedKey>,
 }
 
 impl EntryDialogState {
@@ -69,9 +88,11 @@ impl EntryDialogState {
         // Add key field as first item (read-only for existing entries)
         let key_item = SettingItem {
             path: "__key__".to_string(),
-            name: "Key".to_string(),
-            description: Some("unique identifier for this entry".to_string()),
-            control: SettingControl::Text(TextInputState::new("Key").with_value(&key)),
+            name: t!("settings.entry_key").to_string(),
+            description: Some(t!("settings.entry_key_desc").to_string()),
+            control: SettingControl::Text(
+                TextInputState::new(t!("settings.entry_key")).with_value(&key),
+            ),
             default: None,
             modified: false,
             layer_source: crate::config_io::ConfigLayer::System,
@@ -110,9 +131,9 @@ impl EntryDialogState {
         };
 
         let title = if is_new {
-            format!("Add {}", schema.name)
+            t!("settings.entry_add_title", name = schema.name).to_string()
         } else {
-            format!("Edit {}", schema.name)
+            t!("settings.entry_edit_title", name = schema.name).to_string()
         };
 
         Self {
@@ -134,6 +155,8 @@ impl EntryDialogState {
             original_value: value.clone(),
             first_editable_index,
             no_delete,
+            recording_key: false,
+            recorded_key: None,
         }
     }
 
@@ -177,9 +200,9 @@ impl EntryDialogState {
         };
 
         let title = if is_new {
-            format!("Add {}", schema.name)
+            t!("settings.entry_add_title", name = schema.name).to_string()
         } else {
-            format!("Edit {}", schema.name)
+            t!("settings.entry_edit_title", name = schema.name).to_string()
         };
 
         Self {
@@ -201,6 +224,8 @@ impl EntryDialogState {
             original_value: value.clone(),
             first_editable_index,
             no_delete: false, // Arrays typically allow deletion
+            recording_key: false,
+            recorded_key: None,
         }
     }
 
@@ -1082,9 +1107,80 @@ impl EntryDialogState {
         }
     }
 
+    /// Check if this dialog edits a keybinding (has key, modifiers and action fields)
+    pub fn is_keybinding(&self) -> bool {
+        ["/key", "/modifiers", "/action"]
+            .iter()
+            .all(|path| self.items.iter().any(|item| item.path == *path))
+    }
+
+    /// Check if the focused field is the key of a keybinding, where Enter
+    /// starts the recorder instead of text editing
+    pub fn is_key_field_focused(&self) -> bool {
+        self.is_keybinding()
+            && self
+                .current_item()
+                .is_some_and(|item| item.path == "/key" && !item.read_only)
+    }
+
+    /// Start waiting for the key combination to bind
+    pub fn start_recording_key(&mut self) {
+        self.recording_key = true;
+        self.recorded_key = None;
+    }
+
+    /// Stop waiting for a key combination without changing the binding
+    pub fn cancel_recording_key(&mut self) {
+        self.recording_key = false;
+    }
+
+    /// Value of a field as JSON, by its path (e.g. "/when")
+    pub fn field_value(&self, path: &str) -> Option<Value> {
+        self.items
+            .iter()
+            .find(|item| item.path == path)
+            .map(|item| control_to_value(&item.control))
+    }
+
+    /// Context the edited keybinding applies in, from its `when` field
+    /// (bindings without one apply in normal mode)
+    pub fn binding_context(&self) -> KeyContext {
+        self.field_value("/when")
+            .and_then(|when| when.as_str().and_then(KeyContext::from_when_clause))
+            .unwrap_or(KeyContext::Normal)
+    }
+
+    /// Store a recorded key combination in the key and modifiers fields.
+    /// Any chord sequence is cleared, since it would take precedence.
+    pub fn apply_recorded_key(&mut self, key: &str, modifiers: &[String], recorded: RecordedKey) {
+        for item in &mut self.items {
+            match (item.path.as_str(), &mut item.control) {
+                ("/key", SettingControl::Text(state)) => state.set_value(key),
+                ("/modifiers", SettingControl::TextList(state)) => {
+                    state.items = modifiers.to_vec();
+                    state.focused_item = None;
+                }
+                ("/keys", SettingControl::ObjectArray(state)) => {
+                    state.bindings.clear();
+                    state.focused_index = None;
+                }
+                ("/keys", SettingControl::Json(_)) => {
+                    item.control = SettingControl::Json(JsonEditState::new(
+                        item.name.clone(),
+                        Some(&Value::Array(Vec::new())),
+                    ));
+                }
+                _ => {}
+            }
+        }
+        self.recording_key = false;
+        self.recorded_key = Some(recorded);
+    }
+
     /// Check if any field is currently in edit mode
     pub fn is_editing(&self) -> bool {
-        self.editing_text
+        self.recording_key
+            || self.editing_text
             || self
                 .current_item()
                 .map(|item| {
@@ -6,6 +6,7 @@
 use super::items::SettingControl;
 use super::state::{FocusPanel, SettingsState};
 use crate::input::handler::{DeferredAction, InputContext, InputHandler, InputResult};
+use crate::input::keybindings::key_event_to_binding;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 /// Button action in entry dialog
@@ -79,18 +80,21 @@ impl SettingsState {
         ctx: &mut InputContext,
     ) -> InputResult {
         // Check if we're in a special editing mode
-        let (editing_text, dropdown_open) = if let Some(dialog) = self.entry_dialog() {
+        let (recording_key, editing_text, dropdown_open) = if let Some(dialog) = self.entry_dialog()
+        {
             let dropdown_open = dialog
                 .current_item()
                 .map(|item| matches!(&item.control, SettingControl::Dropdown(s) if s.open))
                 .unwrap_or(false);
-            (dialog.editing_text, dropdown_open)
+            (dialog.recording_key, dialog.editing_text, dropdown_open)
         } else {
             return InputResult::Consumed;
         };
 
         // Route to appropriate handler based on mode
-        if editing_text {
+        if recording_key {
+            self.handle_entry_dialog_key_recording(event)
+        } else if editing_text {
             self.handle_entry_dialog_text_editing(event, ctx)
         } else if dropdown_open {
             self.handle_entry_dialog_dropdown(event)
@@ -99,6 +103,31 @@ impl SettingsState {
         }
     }
 
+    /// Handle input while the keybinding recorder waits for the shortcut to bind.
+    ///
+    /// Keys arrive here already normalized by the key translator, so the
+    /// recorded binding matches what the keymap will see at runtime.
+    fn handle_entry_dialog_key_recording(&mut self, event: &KeyEvent) -> InputResult {
+        if event.code == KeyCode::Esc && event.modifiers.is_empty() {
+            if let Some(dialog) = self.entry_dialog_mut() {
+                dialog.cancel_recording_key();
+            }
+            return InputResult::Consumed;
+        }
+
+        // Bare modifier presses (and keys bindings can't name) keep the recorder waiting
+        let Some((key, modifiers)) = key_event_to_binding(&event.code, &event.modifiers) else {
+            return InputResult::Consumed;
+        };
+        let Some(recorded) = self.describe_recorded_key(&key, &modifiers) else {
+            return InputResult::Consumed;
+        };
+        if let Some(dialog) = self.entry_dialog_mut() {
+            dialog.apply_recorded_key(&key, &modifiers, recorded);
+        }
+        InputResult::Consumed
+    }
+
     /// Handle text editing input in entry dialog (same pattern as handle_text_editing_input)
     fn handle_entry_dialog_text_editing(
         &mut self,
@@ -351,6 +380,14 @@ impl SettingsState {
                 } else if event.modifiers.contains(KeyModifiers::CONTROL) {
                     // Ctrl+Enter always saves
                     self.save_entry_dialog();
+                } else if self
+                    .entry_dialog()
+                    .is_some_and(|dialog| dialog.is_key_field_focused())
+                {
+                    // The key of a keybinding is recorded rather than typed
+                    if let Some(dialog) = self.entry_dialog_mut() {
+                        dialog.start_recording_key();
+                    }
                 } else {
                     // Activate current control
                     let control_action = self
@@ -935,6 +972,7 @@ impl SettingsState {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::keybindings::KeyContext;
     use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
     fn key(code: KeyCode) -> KeyEvent {
@@ -1130,4 +1168,82 @@ mod tests {
             DeferredAction::CloseSettings { save: true }
         ));
     }
+
+    #[test]
+    fn test_keybinding_recorder_captures_chord_and_reports_conflicts() {
+        let schema = include_str!("../../../plugins/config-schema.json");
+        let config = crate::config::Config::default();
+        let mut state = SettingsState::new(schema, &config).unwrap();
+        state.visible = true;
+
+        // Open the "add keybinding" dialog on the Keybindings list
+        let (category, item) = state
+            .pages
+            .iter()
+            .enumerate()
+            .find_map(|(c, page)| {
+                let i = page.items.iter().position(|i| i.path == "/keybindings")?;
+                Some((c, i))
+            })
+            .unwrap();
+        state.selected_category = category;
+        state.selected_item = item;
+        state.open_add_array_item_dialog();
+
+        let dialog = state.entry_dialog_mut().unwrap();
+        assert!(dialog.is_keybinding());
+        dialog.selected_item = dialog.items.iter().position(|i| i.path == "/key").unwrap();
+        dialog.focus_on_buttons = false;
+
+        let mut ctx = InputContext::new();
+
+        // Enter on the key field starts the recorder; Esc stops it without closing the dialog
+        state.handle_key_event(&key(KeyCode::Enter), &mut ctx);
+        assert!(state.entry_dialog().unwrap().recording_key);
+        state.handle_key_event(&key(KeyCode::Esc), &mut ctx);
+        assert!(state.has_entry_dialog());
+        assert!(!state.entry_dialog().unwrap().recording_key);
+
+        // Ctrl+S is bound to Save in the default keymap
+        state.handle_key_event(&key(KeyCode::Enter), &mut ctx);
+        state.handle_key_event(
+            &KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL),
+            &mut ctx,
+        );
+        let dialog = state.entry_dialog().unwrap();
+        assert!(!dialog.recording_key);
+        assert_eq!(dialog.field_value("/key"), Some(serde_json::json!("s")));
+        assert_eq!(
+            dialog.field_value("/modifiers"),
+            Some(serde_json::json!(["ctrl"]))
+        );
+        let recorded = dialog.recorded_key.as_ref().unwrap();
+        assert_eq!(recorded.context, KeyContext::Normal);
+        assert!(recorded.conflict.is_some());
+
+        // A bare modifier press keeps waiting; the following chord is unbound
+        state.handle_key_event(&key(KeyCode::Enter), &mut ctx);
+        state.handle_key_event(
+            &KeyEvent::new(
+                KeyCode::Modifier(crossterm::event::ModifierKeyCode::LeftControl),
+                KeyModifiers::CONTROL,
+            ),
+            &mut ctx,
+        );
+        assert!(state.entry_dialog().unwrap().recording_key);
+        state.handle_key_event(
+            &KeyEvent::new(
+                KeyCode::F(11),
+                KeyModifiers::CONTROL | KeyModifiers::ALT | KeyModifiers::SHIFT,
+            ),
+            &mut ctx,
+        );
+        let dialog = state.entry_dialog().unwrap();
+        assert_eq!(dialog.field_value("/key"), Some(serde_json::json!("F11")));
+        assert_eq!(
+            dialog.field_value("/modifiers"),
+            Some(serde_json::json!(["ctrl", "alt", "shift"]))
+        );
+        assert_eq!(dialog.recorded_key.as_ref().unwrap().conflict, None);
+    }
 }
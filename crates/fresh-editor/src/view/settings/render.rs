@@ -394,7 +394,7 @@ fn render_categories_horizontal(
 
     // Show navigation hint on line 2 if space
     if area.height >= 2 {
-        let hint = "←→: Switch category";
+        let hint = t!("settings.hint_switch_category");
         let hint_style = Style::default().fg(theme.line_number_fg);
         frame.render_widget(
             Paragraph::new(hint).style(hint_style),
@@ -1436,7 +1436,7 @@ fn render_map_partial(
                         Some(c) => c.to_uppercase().collect::<String>() + chars.as_str(),
                     }
                 })
-                .unwrap_or_else(|| "Value".to_string());
+                .unwrap_or_else(|| t!("settings.map_value_header").to_string());
 
             let header_style = Style::default()
                 .fg(colors.label)
@@ -1444,7 +1444,11 @@ fn render_map_partial(
             let header_line = Line::from(vec![
                 Span::styled(" ".repeat(indent as usize), header_style),
                 Span::styled(
-                    format!("{:width$}", "Name", width = key_width as usize),
+                    format!(
+                        "{:width$}",
+                        t!("settings.map_name_header"),
+                        width = key_width as usize
+                    ),
                     header_style,
                 ),
                 Span::raw(" "),
@@ -2242,9 +2246,9 @@ fn render_search_hint(frame: &mut Frame, area: Rect, theme: &Theme) {
         .add_modifier(Modifier::BOLD);
 
     let spans = vec![
-        Span::styled("Press ", hint_style),
+        Span::styled(t!("settings.search_hint_before"), hint_style),
         Span::styled("/", key_style),
-        Span::styled(" to search settings...", hint_style),
+        Span::styled(t!("settings.search_hint_after"), hint_style),
     ];
     let line = Line::from(spans);
     frame.render_widget(Paragraph::new(line), area);
@@ -2510,7 +2514,7 @@ fn render_confirm_dialog(
     }
 
     // Help text
-    let help = "←/→/Tab: Select   Enter: Confirm   Esc: Cancel";
+    let help = t!("settings.confirm_dialog_help");
     let help_style = Style::default().fg(theme.line_number_fg);
     frame.render_widget(
         Paragraph::new(help).style(help_style),
@@ -2787,23 +2791,57 @@ fn render_entry_dialog(
         1,
     );
 
-    if has_invalid_json && !is_json_control {
+    if dialog.recording_key {
+        let prompt = t!("settings.keybinding_recording");
+        let prompt_style = Style::default()
+            .fg(theme.settings_selected_fg)
+            .add_modifier(Modifier::BOLD);
+        frame.render_widget(Paragraph::new(prompt).style(prompt_style), help_area);
+    } else if dialog.is_key_field_focused() {
+        // Result of the last recording, or how to start one
+        let (text, style) = match &dialog.recorded_key {
+            Some(recorded) => match &recorded.conflict {
+                Some(action) => (
+                    t!(
+                        "settings.keybinding_conflict",
+                        keys = recorded.combo,
+                        action = action,
+                        context = recorded.context.to_when_clause()
+                    ),
+                    Style::default().fg(theme.diagnostic_warning_fg),
+                ),
+                None => (
+                    t!(
+                        "settings.keybinding_free",
+                        keys = recorded.combo,
+                        context = recorded.context.to_when_clause()
+                    ),
+                    Style::default().fg(theme.line_number_fg),
+                ),
+            },
+            None => (
+                t!("settings.keybinding_help"),
+                Style::default().fg(theme.line_number_fg),
+            ),
+        };
+        frame.render_widget(Paragraph::new(text).style(style), help_area);
+    } else if has_invalid_json && !is_json_control {
         // Text control with JSON validation - must fix before leaving
-        let warning = "⚠ Invalid JSON - fix before leaving field";
+        let warning = t!("settings.invalid_json_fix");
         let warning_style = Style::default().fg(theme.diagnostic_warning_fg);
         frame.render_widget(Paragraph::new(warning).style(warning_style), help_area);
     } else if has_invalid_json && is_json_control {
         // JSON control with invalid JSON
-        let warning = "⚠ Invalid JSON";
+        let warning = t!("settings.invalid_json");
         let warning_style = Style::default().fg(theme.diagnostic_warning_fg);
         frame.render_widget(Paragraph::new(warning).style(warning_style), help_area);
     } else if is_json_control {
         // Editing JSON control
-        let help = "↑↓←→:Move  Enter:Newline  Tab/Esc:Exit";
+        let help = t!("settings.entry_help_json");
         let help_style = Style::default().fg(theme.line_number_fg);
         frame.render_widget(Paragraph::new(help).style(help_style), help_area);
     } else {
-        let help = "↑↓:Navigate  Tab:Fields/Buttons  Enter:Edit/Confirm  Esc:Cancel";
+        let help = t!("settings.entry_help");
         let help_style = Style::default().fg(theme.line_number_fg);
         frame.render_widget(Paragraph::new(help).style(help_style), help_area);
     }
@@ -2814,28 +2852,28 @@ fn render_help_overlay(frame: &mut Frame, parent_area: Rect, theme: &Theme) {
     // Define the help content
     let help_items = [
         (
-            "Navigation",
+            t!("settings.shortcuts_navigation"),
             vec![
-                ("↑ / ↓", "Move up/down"),
-                ("Tab", "Switch between categories and settings"),
-                ("Enter", "Activate/toggle setting"),
+                ("↑ / ↓", t!("settings.shortcut_move")),
+                ("Tab", t!("settings.shortcut_switch_panel")),
+                ("Enter", t!("settings.shortcut_activate")),
             ],
         ),
         (
-            "Search",
+            t!("settings.shortcuts_search"),
             vec![
-                ("/", "Start search"),
-                ("Esc", "Cancel search"),
-                ("↑ / ↓", "Navigate results"),
-                ("Enter", "Jump to result"),
+                ("/", t!("settings.shortcut_search")),
+                ("Esc", t!("settings.shortcut_cancel_search")),
+                ("↑ / ↓", t!("settings.shortcut_navigate_results")),
+                ("Enter", t!("settings.shortcut_jump")),
             ],
         ),
         (
-            "Actions",
+            t!("settings.shortcuts_actions"),
             vec![
-                ("Ctrl+S", "Save settings"),
-                ("Esc", "Close settings"),
-                ("?", "Toggle this help"),
+                ("Ctrl+S", t!("settings.shortcut_save")),
+                ("Esc", t!("settings.shortcut_close")),
+                ("?", t!("settings.shortcut_toggle_help")),
             ],
         ),
     ];
@@ -2853,7 +2891,7 @@ fn render_help_overlay(frame: &mut Frame, parent_area: Rect, theme: &Theme) {
     frame.render_widget(Clear, dialog_area);
 
     let block = Block::default()
-        .title(" Keyboard Shortcuts ")
+        .title(format!(" {} ", t!("settings.shortcuts_title")))
         .borders(Borders::ALL)
         .border_style(Style::default().fg(theme.menu_highlight_fg))
         .style(Style::default().bg(theme.popup_bg));
@@ -2879,7 +2917,7 @@ fn render_help_overlay(frame: &mut Frame, parent_area: Rect, theme: &Theme) {
             .fg(theme.menu_active_fg)
             .add_modifier(Modifier::BOLD);
         frame.render_widget(
-            Paragraph::new(*section_name).style(header_style),
+            Paragraph::new(section_name.as_ref()).style(header_style),
             Rect::new(inner.x, y, inner.width, 1),
         );
         y += 1;
@@ -2896,7 +2934,7 @@ fn render_help_overlay(frame: &mut Frame, parent_area: Rect, theme: &Theme) {
 
             let line = Line::from(vec![
                 Span::styled(format!("  {:12}", key), key_style),
-                Span::styled(*description, desc_style),
+                Span::styled(description.as_ref(), desc_style),
             ]);
             frame.render_widget(Paragraph::new(line), Rect::new(inner.x, y, inner.width, 1));
             y += 1;
@@ -2907,12 +2945,13 @@ fn render_help_overlay(frame: &mut Frame, parent_area: Rect, theme: &Theme) {
 
     // Footer hint
     let footer_y = dialog_area.y + dialog_area.height - 2;
-    let footer = "Press ? or Esc or Enter to close";
+    let footer = t!("settings.shortcuts_footer");
+    let footer_width = str_width(&footer) as u16;
     let footer_style = Style::default().fg(theme.line_number_fg);
-    let centered_x = inner.x + (inner.width.saturating_sub(footer.len() as u16)) / 2;
+    let centered_x = inner.x + (inner.width.saturating_sub(footer_width)) / 2;
     frame.render_widget(
         Paragraph::new(footer).style(footer_style),
-        Rect::new(centered_x, footer_y, footer.len() as u16, 1),
+        Rect::new(centered_x, footer_y, footer_width, 1),
     );
 }
 
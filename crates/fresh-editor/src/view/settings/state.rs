@@ -3,13 +3,14 @@
 //! Tracks the current state of the settings UI, pending changes,
 //! and provides methods for reading/writing config values.
 
-use super::entry_dialog::EntryDialogState;
+use super::entry_dialog::{EntryDialogState, RecordedKey};
 use super::items::{control_to_value, SettingControl, SettingItem, SettingsPage};
 use super::layout::SettingsHit;
 use super::schema::{parse_schema, SettingCategory, SettingSchema};
 use super::search::{search_settings, SearchResult};
 use crate::config::Config;
 use crate::config_io::ConfigLayer;
+use crate::input::keybindings::{format_keybinding, Action, KeyContext, KeybindingResolver};
 use crate::view::controls::FocusState;
 use crate::view::ui::{FocusManager, ScrollablePanel};
 use std::collections::HashMap;
@@ -883,6 +884,93 @@ impl SettingsState {
         self.entry_dialog_stack.pop();
     }
 
+    /// Check a key combination captured by the keybinding recorder against
+    /// the other bindings in the same context.
+    ///
+    /// Custom bindings are taken from the list being edited (including unsaved
+    /// changes, but not the entry in the dialog itself); everything else comes
+    /// from the active keymap.
+    pub fn describe_recorded_key(&self, key: &str, modifiers: &[String]) -> Option<RecordedKey> {
+        let dialog = self.entry_dialog()?;
+        let (code, mods) = KeybindingResolver::parse_binding(key, modifiers)?;
+        let context = dialog.binding_context();
+        let own_action = dialog.field_value("/action").and_then(|action| {
+            let args = dialog
+                .field_value("/args")
+                .and_then(|args| serde_json::from_value(args).ok())
+                .unwrap_or_default();
+            Action::from_str(action.as_str()?, &args)
+        });
+
+        // The list the edited binding belongs to: a field of the parent dialog
+        // for nested dialogs, otherwise the focused item of the settings page
+        let siblings = if self.entry_dialog_stack.len() > 1 {
+            self.entry_dialog_stack
+                .get(self.entry_dialog_stack.len() - 2)
+                .and_then(|parent| parent.current_item())
+        } else {
+            self.current_item()
+        };
+        let own_index = if dialog.is_new {
+            None
+        } else {
+            dialog.entry_key.parse::<usize>().ok()
+        };
+
+        let custom = siblings.and_then(|item| match &item.control {
+            SettingControl::ObjectArray(list) => list
+                .bindings
+                .iter()
+                .enumerate()
+                .filter(|(idx, _)| Some(*idx) != own_index)
+                .filter_map(|(_, binding)| {
+                    let binding: crate::config::Keybinding =
+                        serde_json::from_value(binding.clone()).ok()?;
+                    let binding_context = binding
+                        .when
+                        .as_deref()
+                        .and_then(KeyContext::from_when_clause)
+                        .unwrap_or(KeyContext::Normal);
+                    let same_context = binding_context == context
+                        || binding_context == KeyContext::Global
+                        || context == KeyContext::Global;
+                    let matches = same_context
+                        && KeybindingResolver::parse_binding(&binding.key, &binding.modifiers)
+                            == Some((code, mods));
+                    if !matches {
+                        return None;
+                    }
+                    Some(Action::from_str(&binding.action, &binding.args).ok_or(binding.action))
+                })
+                .next(),
+            _ => None,
+        });
+
+        let conflict = custom.or_else(|| {
+            // Resolve against the keymap alone: custom bindings were checked above
+            let mut config: Config = serde_json::from_value(self.original_config.clone()).ok()?;
+            config.keybindings.clear();
+            let resolver = KeybindingResolver::new(&config);
+            match resolver.resolve(&crossterm::event::KeyEvent::new(code, mods), context) {
+                Action::None => None,
+                action => Some(Ok(action)),
+            }
+        });
+
+        let conflict = match conflict {
+            Some(Ok(action)) if Some(&action) == own_action.as_ref() => None,
+            Some(Ok(action)) => Some(KeybindingResolver::format_action(&action)),
+            Some(Err(name)) => Some(name),
+            None => None,
+        };
+
+        Some(RecordedKey {
+            combo: format_keybinding(&code, &mods),
+            context,
+            conflict,
+        })
+    }
+
     /// Open a nested entry dialog for a Map or ObjectArray field within the current dialog
     ///
     /// This enables recursive editing: if a dialog field is itself a Map or ObjectArray,
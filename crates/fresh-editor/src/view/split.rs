@@ -116,6 +116,10 @@ pub struct SplitViewState {
     /// the composite layout. This makes the source buffer the "active buffer"
     /// so normal keybindings work directly.
     pub composite_view: Option<BufferId>,
+
+    /// Theme this split is drawn with instead of the editor theme (by name).
+    /// Falls back to the editor theme when unset or not installed.
+    pub theme: Option<String>,
 }
 
 impl SplitViewState {
@@ -136,6 +140,7 @@ impl SplitViewState {
             focus_history: Vec::new(),
             sync_group: None,
             composite_view: None,
+            theme: None,
         }
     }
 
@@ -156,6 +161,7 @@ impl SplitViewState {
             focus_history: Vec::new(),
             sync_group: None,
             composite_view: None,
+            theme: None,
         }
     }
 
@@ -236,6 +242,19 @@ impl SplitViewState {
     pub fn remove_from_history(&mut self, buffer_id: BufferId) {
         self.focus_history.retain(|&id| id != buffer_id);
     }
+
+    /// This split's tabs in most-recently-used order, starting with `current`
+    /// Tabs never focused follow in tab order.
+    pub fn mru_buffers(&self, current: BufferId) -> Vec<BufferId> {
+        let mut order = vec![current];
+        let recent = self.focus_history.iter().rev();
+        for &id in recent.chain(self.open_buffers.iter()) {
+            if self.open_buffers.contains(&id) && !order.contains(&id) {
+                order.push(id);
+            }
+        }
+        order
+    }
 }
 
 impl SplitNode {
@@ -491,6 +510,10 @@ pub struct SplitManager {
 
     /// Currently maximized split (if any). When set, only this split is visible.
     maximized_split: Option<SplitId>,
+
+    /// Whether the maximized split was entered via Toggle Zoom. A zoom ends
+    /// automatically as soon as another split becomes active.
+    zoomed: bool,
 }
 
 impl SplitManager {
@@ -502,6 +525,7 @@ impl SplitManager {
             active_split: split_id,
             next_split_id: 1,
             maximized_split: None,
+            zoomed: false,
         }
     }
 
@@ -520,6 +544,7 @@ impl SplitManager {
         // Verify the split exists
         if self.root.find(split_id).is_some() {
             self.active_split = split_id;
+            self.end_zoom_if_unfocused();
             true
         } else {
             false
@@ -579,18 +604,23 @@ impl SplitManager {
         new_buffer_id: BufferId,
         ratio: f32,
     ) -> Result<SplitId, String> {
-        let active_id = self.active_split;
-
-        // Find the parent of the active split
-        let result = self.replace_split_with_split(active_id, direction, new_buffer_id, ratio);
+        self.split_at(self.active_split, direction, new_buffer_id, ratio)
+    }
 
-        if let Ok(new_split_id) = result {
-            // Set the new split as active
-            self.active_split = new_split_id;
-            Ok(new_split_id)
-        } else {
-            result
-        }
+    /// Split any pane or container (a container is split as a whole).
+    /// The new pane becomes active.
+    pub fn split_at(
+        &mut self,
+        target_id: SplitId,
+        direction: SplitDirection,
+        new_buffer_id: BufferId,
+        ratio: f32,
+    ) -> Result<SplitId, String> {
+        let new_split_id =
+            self.replace_split_with_split(target_id, direction, new_buffer_id, ratio)?;
+        self.active_split = new_split_id;
+        self.end_zoom_if_unfocused();
+        Ok(new_split_id)
     }
 
     /// Replace a split with a new split container
@@ -655,6 +685,7 @@ impl SplitManager {
         // If the split being closed is maximized, unmaximize first
         if self.maximized_split == Some(split_id) {
             self.maximized_split = None;
+            self.zoomed = false;
         }
 
         // Find the parent of the split to close
@@ -822,6 +853,7 @@ impl SplitManager {
             let next_pos = (pos + 1) % leaf_ids.len();
             self.active_split = leaf_ids[next_pos];
         }
+        self.end_zoom_if_unfocused();
     }
 
     /// Navigate to the previous split (circular)
@@ -831,6 +863,7 @@ impl SplitManager {
             let prev_pos = if pos == 0 { leaf_ids.len() } else { pos } - 1;
             self.active_split = leaf_ids[prev_pos];
         }
+        self.end_zoom_if_unfocused();
     }
 
     /// Get all split IDs that display a specific buffer
@@ -888,6 +921,7 @@ impl SplitManager {
         }
 
         self.maximized_split = None;
+        self.zoomed = false;
         Ok(())
     }
 
@@ -914,6 +948,34 @@ impl SplitManager {
         }
     }
 
+    /// Check if the maximized split is a zoom (see [`Self::toggle_zoom`])
+    pub fn is_zoomed(&self) -> bool {
+        self.zoomed
+    }
+
+    /// Toggle zoom for the active split.
+    /// Zooming maximizes the active split like [`Self::toggle_maximize`], but the
+    /// layout is restored automatically once another split becomes active.
+    /// Returns true if zoomed, false if the layout was restored.
+    pub fn toggle_zoom(&mut self) -> Result<bool, String> {
+        if self.is_maximized() {
+            self.unmaximize_split()?;
+            Ok(false)
+        } else {
+            self.maximize_split()?;
+            self.zoomed = true;
+            Ok(true)
+        }
+    }
+
+    /// Restore the layout if a zoomed split is no longer the active split
+    fn end_zoom_if_unfocused(&mut self) {
+        if self.zoomed && self.maximized_split != Some(self.active_split) {
+            self.maximized_split = None;
+            self.zoomed = false;
+        }
+    }
+
     /// Get all leaf split IDs that belong to a specific sync group
     pub fn get_splits_in_group(
         &self,
@@ -991,6 +1053,45 @@ mod tests {
         assert_eq!(manager.root().count_leaves(), 3);
     }
 
+    #[test]
+    fn test_split_at_container() {
+        let buffer_a = BufferId(0);
+        let buffer_b = BufferId(1);
+        let buffer_c = BufferId(2);
+
+        let mut manager = SplitManager::new(buffer_a);
+        manager
+            .split_active(SplitDirection::Horizontal, buffer_b, 0.5)
+            .unwrap();
+        let old_root = manager.root().id();
+
+        // Splitting the root container puts the new pane beside the whole stack
+        let new_id = manager
+            .split_at(old_root, SplitDirection::Vertical, buffer_c, 0.3)
+            .unwrap();
+        assert_eq!(manager.active_split(), new_id);
+        assert_eq!(manager.root().count_leaves(), 3);
+        match manager.root() {
+            SplitNode::Split {
+                direction,
+                first,
+                second,
+                ratio,
+                ..
+            } => {
+                assert_eq!(*direction, SplitDirection::Vertical);
+                assert_eq!(*ratio, 0.3);
+                assert_eq!(first.id(), old_root);
+                assert_eq!(second.buffer_id(), Some(buffer_c));
+            }
+            node => panic!("Expected a container at the root, got {:?}", node),
+        }
+
+        assert!(manager
+            .split_at(SplitId(999), SplitDirection::Vertical, buffer_c, 0.5)
+            .is_err());
+    }
+
     #[test]
     fn test_close_split() {
         let buffer_a = BufferId(0);
@@ -1018,6 +1119,42 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_zoom_ends_when_focus_moves() {
+        let buffer_a = BufferId(0);
+        let buffer_b = BufferId(1);
+
+        let mut manager = SplitManager::new(buffer_a);
+        let zoomed_split = manager
+            .split_active(SplitDirection::Vertical, buffer_b, 0.5)
+            .unwrap();
+
+        assert_eq!(manager.toggle_zoom(), Ok(true));
+        assert!(manager.is_zoomed());
+        assert_eq!(manager.maximized_split(), Some(zoomed_split));
+
+        manager.next_split();
+        assert!(!manager.is_zoomed());
+        assert!(!manager.is_maximized());
+        assert_eq!(manager.root().count_leaves(), 2);
+    }
+
+    #[test]
+    fn test_maximize_survives_focus_change() {
+        let buffer_a = BufferId(0);
+        let buffer_b = BufferId(1);
+
+        let mut manager = SplitManager::new(buffer_a);
+        manager
+            .split_active(SplitDirection::Vertical, buffer_b, 0.5)
+            .unwrap();
+
+        assert_eq!(manager.toggle_maximize(), Ok(true));
+        manager.next_split();
+        assert!(manager.is_maximized());
+        assert!(!manager.is_zoomed());
+    }
+
     #[test]
     fn test_split_rect_horizontal() {
         let rect = Rect {
@@ -1057,4 +1194,21 @@ mod tests {
         assert_eq!(first.x, 0);
         assert_eq!(second.x, 51); // first.x + first.width + 1 (separator)
     }
+
+    #[test]
+    fn test_mru_buffers() {
+        let mut view_state = SplitViewState::with_buffer(80, 24, BufferId(1));
+        for id in 2..=4 {
+            view_state.add_buffer(BufferId(id));
+        }
+        view_state.push_focus(BufferId(1));
+        view_state.push_focus(BufferId(3));
+        // A closed tab left in the history is skipped
+        view_state.push_focus(BufferId(9));
+
+        assert_eq!(
+            view_state.mru_buffers(BufferId(2)),
+            vec![BufferId(2), BufferId(3), BufferId(1), BufferId(4)]
+        );
+    }
 }
@@ -0,0 +1,190 @@
+//! Theme variants for color vision deficiencies.
+//!
+//! Most themes tell errors from warnings and added from removed lines by
+//! red versus green or yellow, which people with a color vision deficiency
+//! can't reliably distinguish. [`Theme::apply_color_vision`] moves the
+//! diagnostic and diff colors to hues that stay apart for the chosen type,
+//! keeping each color's saturation and lightness so the theme's look and
+//! contrast are preserved. It runs on every theme as it is loaded.
+
+use std::borrow::Cow;
+
+use ratatui::style::Color;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::generate::{from_hsl, to_hsl};
+use super::types::{color_to_rgb, Theme};
+
+/// Color vision the diagnostic and diff colors of themes are adjusted for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorVision {
+    /// Themes are used as written
+    #[default]
+    Normal,
+    /// Reduced sensitivity to green (red and green look alike)
+    Deuteranopia,
+    /// Reduced sensitivity to red (red and green look alike, red looks darker)
+    Protanopia,
+    /// Reduced sensitivity to blue (blue and green, yellow and pink look alike)
+    Tritanopia,
+}
+
+impl JsonSchema for ColorVision {
+    fn schema_name() -> Cow<'static, str> {
+        Cow::Borrowed("ColorVision")
+    }
+
+    fn json_schema(_gen: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "description": "Color vision the diagnostic and diff colors of themes are adjusted for",
+            "type": "string",
+            "enum": ["normal", "deuteranopia", "protanopia", "tritanopia"],
+            "default": "normal"
+        })
+    }
+}
+
+/// Hues (in degrees) the remapped colors use
+struct Hues {
+    error: f64,
+    warning: f64,
+    info: f64,
+    hint: f64,
+    added: f64,
+    removed: f64,
+    modified: f64,
+    /// Lightness added to error and removed-line colors, for reds that look dark
+    red_lift: f64,
+}
+
+impl ColorVision {
+    fn hues(self) -> Option<Hues> {
+        match self {
+            Self::Normal => None,
+            // Blue against orange, the pair that stays apart without red-green vision
+            Self::Deuteranopia => Some(Hues {
+                error: 20.0,
+                warning: 50.0,
+                info: 205.0,
+                hint: 265.0,
+                added: 215.0,
+                removed: 30.0,
+                modified: 55.0,
+                red_lift: 0.0,
+            }),
+            Self::Protanopia => Some(Hues {
+                error: 30.0,
+                warning: 55.0,
+                info: 205.0,
+                hint: 265.0,
+                added: 215.0,
+                removed: 35.0,
+                modified: 60.0,
+                red_lift: 0.08,
+            }),
+            // Red against teal, which stay apart without blue-yellow vision
+            Self::Tritanopia => Some(Hues {
+                error: 355.0,
+                warning: 320.0,
+                info: 180.0,
+                hint: 200.0,
+                added: 175.0,
+                removed: 355.0,
+                modified: 320.0,
+                red_lift: 0.0,
+            }),
+        }
+    }
+}
+
+/// `color` with its hue replaced, keeping enough saturation for the hue to show
+fn recolor(color: Color, hue: f64, lift: f64) -> Color {
+    let Some(rgb) = color_to_rgb(color) else {
+        return color;
+    };
+    let (_, s, l) = to_hsl(rgb);
+    let (r, g, b) = from_hsl(hue, s.max(0.35), (l + lift).min(0.85));
+    Color::Rgb(r, g, b)
+}
+
+impl Theme {
+    /// Remap the diagnostic and diff colors to hues that stay distinguishable
+    /// with `vision`; does nothing for [`ColorVision::Normal`]
+    pub fn apply_color_vision(&mut self, vision: ColorVision) {
+        let Some(hues) = vision.hues() else {
+            return;
+        };
+        let shift = |color: &mut Color, hue: f64, lift: f64| *color = recolor(*color, hue, lift);
+
+        shift(&mut self.diagnostic_error_fg, hues.error, hues.red_lift);
+        shift(&mut self.diagnostic_error_bg, hues.error, 0.0);
+        shift(&mut self.diagnostic_warning_fg, hues.warning, 0.0);
+        shift(&mut self.diagnostic_warning_bg, hues.warning, 0.0);
+        shift(&mut self.diagnostic_info_fg, hues.info, 0.0);
+        shift(&mut self.diagnostic_info_bg, hues.info, 0.0);
+        shift(&mut self.diagnostic_hint_fg, hues.hint, 0.0);
+        shift(&mut self.diagnostic_hint_bg, hues.hint, 0.0);
+
+        shift(&mut self.diff_add_bg, hues.added, 0.0);
+        shift(&mut self.diff_add_highlight_bg, hues.added, 0.0);
+        shift(&mut self.diff_remove_bg, hues.removed, 0.0);
+        shift(&mut self.diff_remove_highlight_bg, hues.removed, 0.0);
+        shift(&mut self.diff_modify_bg, hues.modified, 0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::theme::THEME_DARK;
+
+    fn hue(color: Color) -> f64 {
+        to_hsl(color_to_rgb(color).unwrap()).0
+    }
+
+    /// Smallest angle between two hues
+    fn hue_distance(a: f64, b: f64) -> f64 {
+        let d = (a - b).rem_euclid(360.0);
+        d.min(360.0 - d)
+    }
+
+    #[test]
+    fn test_normal_vision_keeps_theme() {
+        let original = Theme::load_builtin(THEME_DARK).unwrap();
+        let mut theme = original.clone();
+        theme.apply_color_vision(ColorVision::Normal);
+        assert_eq!(theme.diagnostic_error_fg, original.diagnostic_error_fg);
+        assert_eq!(theme.diff_add_bg, original.diff_add_bg);
+    }
+
+    #[test]
+    fn test_variants_separate_error_from_warning_and_added_from_removed() {
+        for vision in [
+            ColorVision::Deuteranopia,
+            ColorVision::Protanopia,
+            ColorVision::Tritanopia,
+        ] {
+            let mut theme = Theme::load_builtin(THEME_DARK).unwrap();
+            let original_editor_bg = theme.editor_bg;
+            theme.apply_color_vision(vision);
+
+            assert!(
+                hue_distance(hue(theme.diff_add_bg), hue(theme.diff_remove_bg)) > 120.0,
+                "{:?}: added and removed lines too close",
+                vision
+            );
+            assert!(
+                hue_distance(
+                    hue(theme.diagnostic_error_fg),
+                    hue(theme.diagnostic_info_fg)
+                ) > 120.0,
+                "{:?}: errors and info too close",
+                vision
+            );
+            // Only the diagnostic and diff colors change
+            assert_eq!(theme.editor_bg, original_editor_bg);
+        }
+    }
+}
@@ -0,0 +1,501 @@
+//! Theme generation without hand-written theme files.
+//!
+//! Two sources produce a [`ThemeFile`]-compatible JSON value:
+//! - [`generate_theme`] derives a complete theme from three seed colors
+//!   (background, foreground, accent)
+//! - [`import_base16`] converts a base16 or base24 YAML scheme
+//!
+//! Both fill the same intermediate palette, whose roles follow the base16
+//! styling guidelines, so the resulting themes style UI elements the same way.
+
+use serde_json::{json, Value};
+
+/// An RGB color
+pub type Rgb = (u8, u8, u8);
+
+/// Error returned when a base16/base24 scheme can't be converted
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Base16Error {
+    #[error("missing color {0}")]
+    MissingColor(&'static str),
+    #[error("invalid color for {key}: {value:?}")]
+    InvalidColor { key: String, value: String },
+}
+
+/// Colors of a theme by role (base16 slot in parentheses)
+#[derive(Debug, Clone)]
+struct Palette {
+    /// Default background (base00)
+    bg: Rgb,
+    /// Lighter background for status bars, popups and the current line (base01)
+    bg_alt: Rgb,
+    /// Selection background (base02)
+    selection: Rgb,
+    /// Comments, line numbers, borders (base03)
+    subtle: Rgb,
+    /// Dark foreground for inactive elements (base04)
+    fg_dim: Rgb,
+    /// Default foreground (base05)
+    fg: Rgb,
+    /// Light foreground for active elements (base06)
+    fg_bright: Rgb,
+    /// Brightest color (base07)
+    white: Rgb,
+    red: Rgb,
+    orange: Rgb,
+    yellow: Rgb,
+    green: Rgb,
+    cyan: Rgb,
+    blue: Rgb,
+    magenta: Rgb,
+    /// Bright red, yellow, green, cyan, blue, magenta for the terminal
+    /// (base24 base12-base17; the normal colors for base16)
+    bright: [Rgb; 6],
+    /// Cursor, status bar and highlighted keys
+    accent: Rgb,
+}
+
+/// Derive a complete theme from a background, foreground and accent color.
+///
+/// Backgrounds and neutral colors are blends of `bg` and `fg`; syntax
+/// colors use the accent's saturation at fixed hues, with a lightness that
+/// stays readable on `bg`.
+pub fn generate_theme(name: &str, bg: Rgb, fg: Rgb, accent: Rgb) -> Value {
+    let dark = luminance(bg) < luminance(fg);
+    let (_, accent_s, _) = to_hsl(accent);
+    let saturation = accent_s.clamp(0.45, 0.8);
+    let lightness = if dark { 0.68 } else { 0.40 };
+    let hue = |h: f64| from_hsl(h, saturation, lightness);
+    let bright_hue = |h: f64| from_hsl(h, saturation, lightness + if dark { 0.1 } else { -0.08 });
+
+    let red = hue(355.0);
+    let yellow = hue(45.0);
+    let green = hue(100.0);
+    let cyan = hue(180.0);
+    let blue = hue(215.0);
+    let magenta = hue(290.0);
+    let palette = Palette {
+        bg,
+        bg_alt: mix(bg, fg, 0.07),
+        selection: mix(mix(bg, fg, 0.15), accent, 0.2),
+        subtle: mix(bg, fg, 0.45),
+        fg_dim: mix(bg, fg, 0.7),
+        fg,
+        fg_bright: mix(fg, if dark { (255, 255, 255) } else { (0, 0, 0) }, 0.4),
+        white: mix(fg, if dark { (255, 255, 255) } else { (0, 0, 0) }, 0.7),
+        red,
+        orange: hue(25.0),
+        yellow,
+        green,
+        cyan,
+        blue,
+        magenta,
+        bright: [
+            bright_hue(355.0),
+            bright_hue(45.0),
+            bright_hue(100.0),
+            bright_hue(180.0),
+            bright_hue(215.0),
+            bright_hue(290.0),
+        ],
+        accent,
+    };
+    palette_to_theme(name, &palette)
+}
+
+/// Convert a base16 or base24 YAML scheme into theme JSON.
+///
+/// Both the classic flat layout (`scheme:` and `base00: "181818"`) and the
+/// newer layout with a nested `palette:` map are accepted. The theme is
+/// named after the scheme unless `name` is given.
+pub fn import_base16(yaml: &str, name: Option<&str>) -> Result<Value, Base16Error> {
+    let mut scheme_name = None;
+    let mut colors = std::collections::HashMap::new();
+    for line in yaml.lines() {
+        let Some((key, value)) = parse_yaml_line(line) else {
+            continue;
+        };
+        match key {
+            "scheme" | "name" if scheme_name.is_none() && !value.is_empty() => {
+                scheme_name = Some(value.to_string());
+            }
+            _ if key.len() == 6 && key.starts_with("base") => {
+                let color = parse_hex(value).ok_or_else(|| Base16Error::InvalidColor {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                })?;
+                colors.insert(key.to_ascii_lowercase(), color);
+            }
+            _ => {}
+        }
+    }
+
+    const BASE16: [&str; 16] = [
+        "base00", "base01", "base02", "base03", "base04", "base05", "base06", "base07", "base08",
+        "base09", "base0a", "base0b", "base0c", "base0d", "base0e", "base0f",
+    ];
+    let mut base = [(0, 0, 0); 16];
+    for (slot, key) in base.iter_mut().zip(BASE16) {
+        *slot = *colors.get(key).ok_or(Base16Error::MissingColor(key))?;
+    }
+    // base24 bright colors, in red, yellow, green, cyan, blue, magenta order
+    let bright_or = |key: &str, normal: Rgb| colors.get(key).copied().unwrap_or(normal);
+
+    let palette = Palette {
+        bg: base[0],
+        bg_alt: base[1],
+        selection: base[2],
+        subtle: base[3],
+        fg_dim: base[4],
+        fg: base[5],
+        fg_bright: base[6],
+        white: base[7],
+        red: base[8],
+        orange: base[9],
+        yellow: base[10],
+        green: base[11],
+        cyan: base[12],
+        blue: base[13],
+        magenta: base[14],
+        bright: [
+            bright_or("base12", base[8]),
+            bright_or("base13", base[10]),
+            bright_or("base14", base[11]),
+            bright_or("base15", base[12]),
+            bright_or("base16", base[13]),
+            bright_or("base17", base[14]),
+        ],
+        accent: base[13],
+    };
+    let name = name
+        .map(str::to_string)
+        .unwrap_or_else(|| theme_slug(scheme_name.as_deref().unwrap_or("base16")));
+    Ok(palette_to_theme(&name, &palette))
+}
+
+/// A theme name usable as a file name: lowercase words joined by `-`
+pub fn theme_slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Parse a hex color: `"#rrggbb"`, `"rrggbb"` or `"#rgb"`
+pub fn parse_hex(value: &str) -> Option<Rgb> {
+    let hex = value.trim().trim_start_matches('#');
+    if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let channel = |s: &str| u8::from_str_radix(s, 16).ok();
+    match hex.len() {
+        6 => Some((
+            channel(&hex[0..2])?,
+            channel(&hex[2..4])?,
+            channel(&hex[4..6])?,
+        )),
+        3 => {
+            let short = |i: usize| channel(&hex[i..i + 1]).map(|v| v * 17);
+            Some((short(0)?, short(1)?, short(2)?))
+        }
+        _ => None,
+    }
+}
+
+/// Split a `key: value` YAML line, dropping quotes and trailing comments
+fn parse_yaml_line(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim();
+    if line.starts_with('#') {
+        return None;
+    }
+    let (key, value) = line.split_once(':')?;
+    let value = value.trim();
+    let value = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => {
+            let rest = &value[1..];
+            &rest[..rest.find(quote).unwrap_or(rest.len())]
+        }
+        _ => value.split(" #").next().unwrap_or("").trim(),
+    };
+    Some((key.trim(), value))
+}
+
+fn palette_to_theme(name: &str, p: &Palette) -> Value {
+    let tint = |color: Rgb| mix(p.bg, color, 0.18);
+    let hover = |color: Rgb| mix(color, p.fg_bright, 0.25);
+    json!({
+        "name": name,
+        "editor": section(&[
+            ("bg", p.bg),
+            ("fg", p.fg),
+            ("cursor", p.accent),
+            ("inactive_cursor", p.subtle),
+            ("selection_bg", p.selection),
+            ("current_line_bg", p.bg_alt),
+            ("line_number_fg", p.subtle),
+            ("line_number_bg", p.bg),
+            ("diff_add_bg", tint(p.green)),
+            ("diff_remove_bg", tint(p.red)),
+            ("diff_modify_bg", tint(p.blue)),
+            ("trailing_whitespace_bg", mix(p.bg, p.red, 0.12)),
+            ("sticky_scroll_bg", mix(p.bg, p.bg_alt, 0.6)),
+        ]),
+        "ui": section(&[
+            ("tab_active_fg", p.fg_bright),
+            ("tab_active_bg", p.bg),
+            ("tab_inactive_fg", p.fg_dim),
+            ("tab_inactive_bg", p.bg_alt),
+            ("tab_separator_bg", p.bg_alt),
+            ("tab_close_hover_fg", p.red),
+            ("tab_hover_bg", p.selection),
+            ("menu_bg", p.bg_alt),
+            ("menu_fg", p.fg),
+            ("menu_active_bg", p.selection),
+            ("menu_active_fg", p.fg_bright),
+            ("menu_dropdown_bg", p.bg_alt),
+            ("menu_dropdown_fg", p.fg),
+            ("menu_highlight_bg", p.accent),
+            ("menu_highlight_fg", p.bg),
+            ("menu_border_fg", p.subtle),
+            ("menu_separator_fg", p.subtle),
+            ("menu_hover_bg", p.selection),
+            ("menu_hover_fg", p.fg_bright),
+            ("menu_disabled_fg", p.subtle),
+            ("menu_disabled_bg", p.bg_alt),
+            ("status_bar_fg", p.bg),
+            ("status_bar_bg", p.accent),
+            ("prompt_fg", p.fg),
+            ("prompt_bg", p.bg_alt),
+            ("prompt_selection_fg", p.fg_bright),
+            ("prompt_selection_bg", p.selection),
+            ("popup_border_fg", p.subtle),
+            ("popup_bg", p.bg_alt),
+            ("popup_selection_bg", p.selection),
+            ("popup_selection_fg", p.fg_bright),
+            ("popup_text_fg", p.fg),
+            ("suggestion_bg", p.bg_alt),
+            ("suggestion_selected_bg", p.selection),
+            ("help_bg", p.bg),
+            ("help_fg", p.fg),
+            ("help_key_fg", p.accent),
+            ("help_separator_fg", p.subtle),
+            ("help_indicator_fg", p.red),
+            ("help_indicator_bg", p.bg),
+            ("inline_code_bg", p.bg_alt),
+            ("split_separator_fg", p.subtle),
+            ("split_separator_hover_fg", p.accent),
+            ("scrollbar_track_fg", p.bg_alt),
+            ("scrollbar_thumb_fg", p.subtle),
+            ("scrollbar_track_hover_fg", p.bg_alt),
+            ("scrollbar_thumb_hover_fg", p.fg_dim),
+            ("compose_margin_bg", p.bg_alt),
+            ("semantic_highlight_bg", tint(p.accent)),
+            ("terminal_bg", p.bg),
+            ("terminal_fg", p.fg),
+            ("status_warning_indicator_bg", p.yellow),
+            ("status_warning_indicator_fg", p.bg),
+            ("status_error_indicator_bg", p.red),
+            ("status_error_indicator_fg", p.bg),
+            ("status_warning_indicator_hover_bg", hover(p.yellow)),
+            ("status_warning_indicator_hover_fg", p.bg),
+            ("status_error_indicator_hover_bg", hover(p.red)),
+            ("status_error_indicator_hover_fg", p.bg),
+            ("tab_drop_zone_bg", tint(p.accent)),
+            ("tab_drop_zone_border", p.accent),
+            ("settings_selected_bg", p.selection),
+            ("settings_selected_fg", p.fg_bright),
+        ]),
+        "search": section(&[
+            ("match_bg", p.yellow),
+            ("match_fg", p.bg),
+        ]),
+        "diagnostic": section(&[
+            ("error_fg", p.red),
+            ("error_bg", tint(p.red)),
+            ("warning_fg", p.yellow),
+            ("warning_bg", tint(p.yellow)),
+            ("info_fg", p.blue),
+            ("info_bg", p.bg),
+            ("hint_fg", p.subtle),
+            ("hint_bg", p.bg),
+        ]),
+        "syntax": section(&[
+            ("keyword", p.magenta),
+            ("string", p.green),
+            ("comment", p.subtle),
+            ("function", p.blue),
+            ("type", p.yellow),
+            ("variable", p.fg),
+            ("constant", p.orange),
+            ("operator", p.cyan),
+        ]),
+        "terminal": section(&[
+            ("black", p.bg),
+            ("red", p.red),
+            ("green", p.green),
+            ("yellow", p.yellow),
+            ("blue", p.blue),
+            ("magenta", p.magenta),
+            ("cyan", p.cyan),
+            ("white", p.fg),
+            ("bright_black", p.subtle),
+            ("bright_red", p.bright[0]),
+            ("bright_green", p.bright[2]),
+            ("bright_yellow", p.bright[1]),
+            ("bright_blue", p.bright[4]),
+            ("bright_magenta", p.bright[5]),
+            ("bright_cyan", p.bright[3]),
+            ("bright_white", p.white),
+        ])
+    })
+}
+
+/// A theme section object mapping each key to an `[r, g, b]` array
+fn section(entries: &[(&str, Rgb)]) -> Value {
+    Value::Object(
+        entries
+            .iter()
+            .map(|&(key, rgb)| (key.to_string(), json!([rgb.0, rgb.1, rgb.2])))
+            .collect(),
+    )
+}
+
+/// Linear blend from `a` (t = 0) to `b` (t = 1)
+fn mix(a: Rgb, b: Rgb, t: f64) -> Rgb {
+    let channel = |x: u8, y: u8| (x as f64 + (y as f64 - x as f64) * t).round() as u8;
+    (channel(a.0, b.0), channel(a.1, b.1), channel(a.2, b.2))
+}
+
+/// Relative luminance (0 = black, 1 = white)
+fn luminance(rgb: Rgb) -> f64 {
+    (0.2126 * rgb.0 as f64 + 0.7152 * rgb.1 as f64 + 0.0722 * rgb.2 as f64) / 255.0
+}
+
+/// RGB to (hue in degrees, saturation, lightness)
+pub(super) fn to_hsl(rgb: Rgb) -> (f64, f64, f64) {
+    let (r, g, b) = (
+        rgb.0 as f64 / 255.0,
+        rgb.1 as f64 / 255.0,
+        rgb.2 as f64 / 255.0,
+    );
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * ((g - b) / d).rem_euclid(6.0)
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s, l)
+}
+
+/// (hue in degrees, saturation, lightness) to RGB
+pub(super) fn from_hsl(h: f64, s: f64, l: f64) -> Rgb {
+    let c = (1.0 - (2.0 * l - 1.0).abs()) * s;
+    let h = h.rem_euclid(360.0) / 60.0;
+    let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+    let (r, g, b) = match h as u32 {
+        0 => (c, x, 0.0),
+        1 => (x, c, 0.0),
+        2 => (0.0, c, x),
+        3 => (0.0, x, c),
+        4 => (x, 0.0, c),
+        _ => (c, 0.0, x),
+    };
+    let m = l - c / 2.0;
+    let channel = |v: f64| ((v + m) * 255.0).round().clamp(0.0, 255.0) as u8;
+    (channel(r), channel(g), channel(b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::view::theme::ThemeFile;
+
+    const TOMORROW_NIGHT: &str = r#"
+scheme: "Tomorrow Night"
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "1d1f21" # background
+base01: "282a2e"
+base02: "373b41"
+base03: "969896"
+base04: "b4b7b4"
+base05: "c5c8c6"
+base06: "e0e0e0"
+base07: "ffffff"
+base08: "cc6666"
+base09: "de935f"
+base0A: "f0c674"
+base0B: "b5bd68"
+base0C: "8abeb7"
+base0D: "81a2be"
+base0E: "b294bb"
+base0F: "a3685a"
+"#;
+
+    #[test]
+    fn test_import_base16_maps_slots() {
+        let value = import_base16(TOMORROW_NIGHT, None).unwrap();
+        assert_eq!(value["name"], "tomorrow-night");
+        assert_eq!(value["editor"]["bg"], json!([0x1d, 0x1f, 0x21]));
+        assert_eq!(value["syntax"]["keyword"], json!([0xb2, 0x94, 0xbb]));
+        // base16 has no bright colors, so the normal ones are reused
+        assert_eq!(value["terminal"]["bright_red"], json!([0xcc, 0x66, 0x66]));
+        serde_json::from_value::<ThemeFile>(value).expect("imported theme matches schema");
+    }
+
+    #[test]
+    fn test_import_base24_palette_layout() {
+        let mut yaml = String::from("system: \"base24\"\nname: \"Example\"\npalette:\n");
+        for i in 0..24 {
+            yaml.push_str(&format!("  base{:02X}: \"#{:02x}0000\"\n", i, i * 10));
+        }
+        let value = import_base16(&yaml, Some("mine")).unwrap();
+        assert_eq!(value["name"], "mine");
+        // base12 is bright red
+        assert_eq!(value["terminal"]["bright_red"], json!([180, 0, 0]));
+    }
+
+    #[test]
+    fn test_import_base16_reports_missing_color() {
+        let yaml = TOMORROW_NIGHT.replace("base0F: \"a3685a\"", "");
+        assert_eq!(
+            import_base16(&yaml, None),
+            Err(Base16Error::MissingColor("base0f"))
+        );
+    }
+
+    #[test]
+    fn test_generate_theme_is_readable() {
+        for (bg, fg) in [
+            ((20, 20, 30), (220, 220, 220)),
+            ((250, 250, 245), (40, 40, 40)),
+        ] {
+            let value = generate_theme("seeded", bg, fg, (80, 140, 220));
+            let theme: ThemeFile = serde_json::from_value(value.clone()).unwrap();
+            assert_eq!(theme.name, "seeded");
+
+            // Syntax colors keep some contrast with the background
+            let keyword = value["syntax"]["keyword"].as_array().unwrap();
+            let keyword: Vec<u8> = keyword.iter().map(|v| v.as_u64().unwrap() as u8).collect();
+            let contrast = (luminance((keyword[0], keyword[1], keyword[2])) - luminance(bg)).abs();
+            assert!(contrast > 0.2, "keyword too close to background");
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_and_slug() {
+        assert_eq!(parse_hex("#ff8000"), Some((255, 128, 0)));
+        assert_eq!(parse_hex("f80"), Some((255, 136, 0)));
+        assert_eq!(parse_hex("nothex"), None);
+        assert_eq!(theme_slug("Gruvbox dark, hard"), "gruvbox-dark-hard");
+    }
+}
@@ -7,6 +7,7 @@
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+use super::color_vision::ColorVision;
 use super::types::{Theme, ThemeFile, ThemeInfo, BUILTIN_THEMES};
 
 /// A registry holding all loaded themes.
@@ -19,6 +20,8 @@ pub struct ThemeRegistry {
     themes: HashMap<String, Theme>,
     /// Theme metadata for listing
     theme_list: Vec<ThemeInfo>,
+    /// Color vision the themes were adjusted for
+    color_vision: ColorVision,
 }
 
 impl ThemeRegistry {
@@ -58,24 +61,47 @@ impl ThemeRegistry {
     pub fn is_empty(&self) -> bool {
         self.themes.is_empty()
     }
+
+    /// Add a theme, replacing any existing theme with the same name.
+    /// It is adjusted for the registry's color vision like loaded themes.
+    pub fn insert(&mut self, name: &str, mut theme: Theme, pack: &str) {
+        theme.apply_color_vision(self.color_vision);
+        let normalized = name.to_lowercase().replace('_', "-");
+        self.theme_list.retain(|info| info.name != normalized);
+        self.themes.insert(normalized.clone(), theme);
+        self.theme_list.push(ThemeInfo::new(normalized, pack));
+    }
 }
 
 /// Loads themes and creates a ThemeRegistry.
 pub struct ThemeLoader {
     user_themes_dir: Option<PathBuf>,
+    color_vision: ColorVision,
 }
 
 impl ThemeLoader {
     /// Create a new ThemeLoader with default user themes directory.
     pub fn new() -> Self {
         Self {
-            user_themes_dir: dirs::config_dir().map(|p| p.join("fresh").join("themes")),
+            user_themes_dir: crate::config_io::DirectoryContext::from_system()
+                .ok()
+                .map(|dirs| dirs.themes_dir()),
+            color_vision: ColorVision::Normal,
         }
     }
 
     /// Create a ThemeLoader with a custom user themes directory.
     pub fn with_user_dir(user_themes_dir: Option<PathBuf>) -> Self {
-        Self { user_themes_dir }
+        Self {
+            user_themes_dir,
+            color_vision: ColorVision::Normal,
+        }
+    }
+
+    /// Adjust every loaded theme's diagnostic and diff colors for `color_vision`.
+    pub fn with_color_vision(mut self, color_vision: ColorVision) -> Self {
+        self.color_vision = color_vision;
+        self
     }
 
     /// Get the user themes directory path.
@@ -141,7 +167,15 @@ impl ThemeLoader {
             }
         }
 
-        ThemeRegistry { themes, theme_list }
+        for theme in themes.values_mut() {
+            theme.apply_color_vision(self.color_vision);
+        }
+
+        ThemeRegistry {
+            themes,
+            theme_list,
+            color_vision: self.color_vision,
+        }
     }
 
     /// Load themes from a package with package.json manifest.
@@ -321,6 +355,26 @@ mod tests {
         assert!(!registry.contains("nonexistent"));
     }
 
+    #[test]
+    fn test_theme_loader_applies_color_vision() {
+        let normal = ThemeLoader::with_user_dir(None).load_all();
+        let mut registry = ThemeLoader::with_user_dir(None)
+            .with_color_vision(ColorVision::Deuteranopia)
+            .load_all();
+
+        let dark = normal.get("dark").unwrap();
+        let adjusted = registry.get_cloned("dark").unwrap();
+        assert_ne!(adjusted.diff_add_bg, dark.diff_add_bg);
+        assert_eq!(adjusted.editor_bg, dark.editor_bg);
+
+        // Themes added later are adjusted too
+        registry.insert("mine", dark.clone(), "user");
+        assert_eq!(
+            registry.get("mine").unwrap().diff_add_bg,
+            adjusted.diff_add_bg
+        );
+    }
+
     #[test]
     fn test_theme_loader_load_all() {
         let loader = ThemeLoader::new();
@@ -2,6 +2,8 @@
 //!
 //! This module is split into:
 //! - `types`: Pure data types (WASM-compatible, no filesystem access)
+//! - `generate`: Themes derived from seed colors or base16/base24 schemes (pure)
+//! - `color_vision`: Diagnostic and diff colors adjusted for color vision deficiencies (pure)
 //! - `loader`: ThemeLoader creates ThemeRegistry from embedded + user themes (runtime only)
 //!
 //! # Example
@@ -20,12 +22,16 @@
 //! let themes = registry.list();
 //! ```
 
+mod color_vision;
+mod generate;
 // Loader requires filesystem access - runtime only
 #[cfg(feature = "runtime")]
 mod loader;
 mod types;
 
 // Re-export all public items for backward compatibility
+pub use color_vision::*;
+pub use generate::*;
 #[cfg(feature = "runtime")]
 pub use loader::*;
 pub use types::*;
@@ -180,6 +180,9 @@ pub struct ThemeFile {
     pub diagnostic: DiagnosticColors,
     /// Syntax highlighting colors
     pub syntax: SyntaxColors,
+    /// ANSI palette for the built-in terminal and ANSI-colored buffers
+    #[serde(default)]
+    pub terminal: TerminalColors,
 }
 
 /// Editor area colors
@@ -218,6 +221,12 @@ pub struct EditorColors {
     /// Diff modified line background
     #[serde(default = "default_diff_modify_bg")]
     pub diff_modify_bg: ColorDef,
+    /// Background of trailing whitespace
+    #[serde(default = "default_trailing_whitespace_bg")]
+    pub trailing_whitespace_bg: ColorDef,
+    /// Background of the scope headers pinned by sticky scroll
+    #[serde(default = "default_sticky_scroll_bg")]
+    pub sticky_scroll_bg: ColorDef,
 }
 
 // Default editor colors (for minimal themes)
@@ -254,6 +263,12 @@ fn default_diff_remove_bg() -> ColorDef {
 fn default_diff_modify_bg() -> ColorDef {
     ColorDef::Rgb(40, 38, 30) // Very subtle yellow tint, close to dark bg
 }
+fn default_trailing_whitespace_bg() -> ColorDef {
+    ColorDef::Rgb(60, 42, 42) // Faint red, visible without drawing the eye
+}
+fn default_sticky_scroll_bg() -> ColorDef {
+    ColorDef::Rgb(44, 44, 50)
+}
 
 /// UI element colors (tabs, menus, status bar, etc.)
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -784,6 +799,226 @@ fn default_syntax_operator() -> ColorDef {
     ColorDef::Rgb(212, 212, 212)
 }
 
+/// ANSI palette for the built-in terminal and ANSI-colored buffers.
+///
+/// Colors left unset are derived from the theme's syntax and diagnostic colors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct TerminalColors {
+    /// ANSI black (color 0)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub black: Option<ColorDef>,
+    /// ANSI red (color 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub red: Option<ColorDef>,
+    /// ANSI green (color 2)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub green: Option<ColorDef>,
+    /// ANSI yellow (color 3)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yellow: Option<ColorDef>,
+    /// ANSI blue (color 4)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blue: Option<ColorDef>,
+    /// ANSI magenta (color 5)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub magenta: Option<ColorDef>,
+    /// ANSI cyan (color 6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cyan: Option<ColorDef>,
+    /// ANSI white (color 7)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub white: Option<ColorDef>,
+    /// ANSI bright black (color 8)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_black: Option<ColorDef>,
+    /// ANSI bright red (color 9)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_red: Option<ColorDef>,
+    /// ANSI bright green (color 10)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_green: Option<ColorDef>,
+    /// ANSI bright yellow (color 11)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_yellow: Option<ColorDef>,
+    /// ANSI bright blue (color 12)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_blue: Option<ColorDef>,
+    /// ANSI bright magenta (color 13)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_magenta: Option<ColorDef>,
+    /// ANSI bright cyan (color 14)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_cyan: Option<ColorDef>,
+    /// ANSI bright white (color 15)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bright_white: Option<ColorDef>,
+}
+
+/// Names of the 16 ANSI colors, in palette order (as used in theme files and keys)
+pub const ANSI_COLOR_NAMES: [&str; 16] = [
+    "black",
+    "red",
+    "green",
+    "yellow",
+    "blue",
+    "magenta",
+    "cyan",
+    "white",
+    "bright_black",
+    "bright_red",
+    "bright_green",
+    "bright_yellow",
+    "bright_blue",
+    "bright_magenta",
+    "bright_cyan",
+    "bright_white",
+];
+
+/// Palette used for ANSI colors a theme neither defines nor lets us derive
+const FALLBACK_ANSI_COLORS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (205, 49, 49),
+    (13, 188, 121),
+    (229, 229, 16),
+    (36, 114, 200),
+    (188, 63, 188),
+    (17, 168, 205),
+    (229, 229, 229),
+    (102, 102, 102),
+    (241, 76, 76),
+    (35, 209, 139),
+    (245, 245, 67),
+    (59, 142, 234),
+    (214, 112, 214),
+    (41, 184, 219),
+    (255, 255, 255),
+];
+
+impl TerminalColors {
+    fn to_array(&self) -> [Option<ColorDef>; 16] {
+        [
+            self.black.clone(),
+            self.red.clone(),
+            self.green.clone(),
+            self.yellow.clone(),
+            self.blue.clone(),
+            self.magenta.clone(),
+            self.cyan.clone(),
+            self.white.clone(),
+            self.bright_black.clone(),
+            self.bright_red.clone(),
+            self.bright_green.clone(),
+            self.bright_yellow.clone(),
+            self.bright_blue.clone(),
+            self.bright_magenta.clone(),
+            self.bright_cyan.clone(),
+            self.bright_white.clone(),
+        ]
+    }
+
+    fn from_array(colors: [Color; 16]) -> Self {
+        let [black, red, green, yellow, blue, magenta, cyan, white, bright_black, bright_red, bright_green, bright_yellow, bright_blue, bright_magenta, bright_cyan, bright_white] =
+            colors.map(|c| Some(ColorDef::from(c)));
+        Self {
+            black,
+            red,
+            green,
+            yellow,
+            blue,
+            magenta,
+            cyan,
+            white,
+            bright_black,
+            bright_red,
+            bright_green,
+            bright_yellow,
+            bright_blue,
+            bright_magenta,
+            bright_cyan,
+            bright_white,
+        }
+    }
+}
+
+/// Hue (degrees), saturation and lightness of an RGB color
+fn rgb_to_hsl((r, g, b): (u8, u8, u8)) -> (f32, f32, f32) {
+    let (r, g, b) = (r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0);
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let l = (max + min) / 2.0;
+    let d = max - min;
+    if d == 0.0 {
+        return (0.0, 0.0, l);
+    }
+    let s = d / (1.0 - (2.0 * l - 1.0).abs());
+    let h = if max == r {
+        60.0 * (((g - b) / d).rem_euclid(6.0))
+    } else if max == g {
+        60.0 * ((b - r) / d + 2.0)
+    } else {
+        60.0 * ((r - g) / d + 4.0)
+    };
+    (h, s, l)
+}
+
+/// Derive an ANSI palette for themes that don't define one.
+///
+/// Black/white follow the editor background/foreground, bright black follows
+/// the line number color, and each chromatic color takes the theme color
+/// (from `candidates`) closest in hue, so terminal output picks up the
+/// theme's accents. Anything that can't be matched uses the fallback palette.
+fn derive_ansi_colors(
+    editor_bg: Color,
+    editor_fg: Color,
+    line_number_fg: Color,
+    candidates: &[Color],
+) -> [Color; 16] {
+    let mut colors = FALLBACK_ANSI_COLORS.map(|(r, g, b)| Color::Rgb(r, g, b));
+
+    if let (Some(bg), Some(fg)) = (color_to_rgb(editor_bg), color_to_rgb(editor_fg)) {
+        // In light themes "black" is the text color and "white" the background
+        let (dark, light) = if rgb_to_hsl(bg).2 <= rgb_to_hsl(fg).2 {
+            (editor_bg, editor_fg)
+        } else {
+            (editor_fg, editor_bg)
+        };
+        colors[0] = dark;
+        colors[7] = light;
+        colors[15] = brighten_color(light, 40);
+    }
+    if color_to_rgb(line_number_fg).is_some() {
+        colors[8] = line_number_fg;
+    }
+
+    // ANSI red, green, yellow, blue, magenta, cyan and their hues
+    const HUES: [(usize, f32); 6] = [
+        (1, 0.0),
+        (2, 120.0),
+        (3, 60.0),
+        (4, 240.0),
+        (5, 300.0),
+        (6, 180.0),
+    ];
+    for (index, target) in HUES {
+        let best = candidates
+            .iter()
+            .filter_map(|&c| color_to_rgb(c).map(|rgb| (c, rgb_to_hsl(rgb))))
+            .filter(|(_, (_, s, l))| *s >= 0.25 && *l > 0.15 && *l < 0.9)
+            .map(|(c, (h, _, _))| {
+                let diff = (h - target).abs();
+                (c, diff.min(360.0 - diff))
+            })
+            .filter(|(_, dist)| *dist <= 30.0)
+            .min_by(|a, b| a.1.total_cmp(&b.1));
+        if let Some((color, _)) = best {
+            colors[index] = color;
+            colors[index + 8] = brighten_color(color, 40);
+        }
+    }
+
+    colors
+}
+
 /// Comprehensive theme structure with all UI colors
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -799,6 +1034,8 @@ pub struct Theme {
     pub current_line_bg: Color,
     pub line_number_fg: Color,
     pub line_number_bg: Color,
+    pub trailing_whitespace_bg: Color,
+    pub sticky_scroll_bg: Color,
 
     // Diff highlighting colors
     pub diff_add_bg: Color,
@@ -921,10 +1158,38 @@ pub struct Theme {
     pub syntax_variable: Color,
     pub syntax_constant: Color,
     pub syntax_operator: Color,
+
+    /// The 16 ANSI colors (black..white, then bright variants) used by the
+    /// built-in terminal and ANSI-colored buffers
+    pub ansi_colors: [Color; 16],
 }
 
 impl From<ThemeFile> for Theme {
     fn from(file: ThemeFile) -> Self {
+        let derived = derive_ansi_colors(
+            file.editor.bg.clone().into(),
+            file.editor.fg.clone().into(),
+            file.editor.line_number_fg.clone().into(),
+            &[
+                file.diagnostic.error_fg.clone().into(),
+                file.diagnostic.warning_fg.clone().into(),
+                file.diagnostic.info_fg.clone().into(),
+                file.syntax.keyword.clone().into(),
+                file.syntax.string.clone().into(),
+                file.syntax.function.clone().into(),
+                file.syntax.type_.clone().into(),
+                file.syntax.constant.clone().into(),
+                file.syntax.variable.clone().into(),
+                file.syntax.comment.clone().into(),
+            ],
+        );
+        let mut ansi_colors = derived;
+        for (slot, def) in ansi_colors.iter_mut().zip(file.terminal.to_array()) {
+            if let Some(def) = def {
+                *slot = def.into();
+            }
+        }
+
         Self {
             name: file.name,
             editor_bg: file.editor.bg.into(),
@@ -941,6 +1206,8 @@ impl From<ThemeFile> for Theme {
             // Compute brighter highlight colors from base diff colors
             diff_add_highlight_bg: brighten_color(file.editor.diff_add_bg.into(), 40),
             diff_remove_highlight_bg: brighten_color(file.editor.diff_remove_bg.into(), 40),
+            trailing_whitespace_bg: file.editor.trailing_whitespace_bg.into(),
+            sticky_scroll_bg: file.editor.sticky_scroll_bg.into(),
             tab_active_fg: file.ui.tab_active_fg.into(),
             tab_active_bg: file.ui.tab_active_bg.into(),
             tab_inactive_fg: file.ui.tab_inactive_fg.into(),
@@ -1022,6 +1289,7 @@ impl From<ThemeFile> for Theme {
             syntax_variable: file.syntax.variable.into(),
             syntax_constant: file.syntax.constant.into(),
             syntax_operator: file.syntax.operator.into(),
+            ansi_colors,
         }
     }
 }
@@ -1042,6 +1310,8 @@ impl From<Theme> for ThemeFile {
                 diff_add_bg: theme.diff_add_bg.into(),
                 diff_remove_bg: theme.diff_remove_bg.into(),
                 diff_modify_bg: theme.diff_modify_bg.into(),
+                trailing_whitespace_bg: theme.trailing_whitespace_bg.into(),
+                sticky_scroll_bg: theme.sticky_scroll_bg.into(),
             },
             ui: UiColors {
                 tab_active_fg: theme.tab_active_fg.into(),
@@ -1132,6 +1402,7 @@ impl From<Theme> for ThemeFile {
                 constant: theme.syntax_constant.into(),
                 operator: theme.syntax_operator.into(),
             },
+            terminal: TerminalColors::from_array(theme.ansi_colors),
         }
     }
 }
@@ -1161,6 +1432,7 @@ impl Theme {
     /// - "editor.selection_bg" -> selection_bg
     /// - "syntax.keyword" -> syntax_keyword
     /// - "diagnostic.error_fg" -> diagnostic_error_fg
+    /// - "terminal.bright_red" -> ansi_colors[9]
     ///
     /// Returns None if the key is not recognized.
     pub fn resolve_theme_key(&self, key: &str) -> Option<Color> {
@@ -1185,6 +1457,10 @@ impl Theme {
                 "diff_add_bg" => Some(self.diff_add_bg),
                 "diff_remove_bg" => Some(self.diff_remove_bg),
                 "diff_modify_bg" => Some(self.diff_modify_bg),
+                "diff_add_highlight_bg" => Some(self.diff_add_highlight_bg),
+                "diff_remove_highlight_bg" => Some(self.diff_remove_highlight_bg),
+                "trailing_whitespace_bg" => Some(self.trailing_whitespace_bg),
+                "sticky_scroll_bg" => Some(self.sticky_scroll_bg),
                 _ => None,
             },
             "ui" => match field {
@@ -1242,9 +1518,51 @@ impl Theme {
                 "match_fg" => Some(self.search_match_fg),
                 _ => None,
             },
+            "terminal" => ANSI_COLOR_NAMES
+                .iter()
+                .position(|name| *name == field)
+                .map(|i| self.ansi_colors[i]),
             _ => None,
         }
     }
+
+    /// Map an ANSI color (named or indexed 0-15) to the theme's palette.
+    ///
+    /// Other colors (RGB, 256-color indices, Reset) are returned unchanged.
+    pub fn ansi_color(&self, color: Color) -> Color {
+        let index = match color {
+            Color::Black => 0,
+            Color::Red => 1,
+            Color::Green => 2,
+            Color::Yellow => 3,
+            Color::Blue => 4,
+            Color::Magenta => 5,
+            Color::Cyan => 6,
+            Color::Gray => 7,
+            Color::DarkGray => 8,
+            Color::LightRed => 9,
+            Color::LightGreen => 10,
+            Color::LightYellow => 11,
+            Color::LightBlue => 12,
+            Color::LightMagenta => 13,
+            Color::LightCyan => 14,
+            Color::White => 15,
+            Color::Indexed(i) if i < 16 => i as usize,
+            _ => return color,
+        };
+        self.ansi_colors[index]
+    }
+
+    /// The ANSI palette as RGB values, for the terminal emulator
+    pub fn ansi_palette_rgb(&self) -> [(u8, u8, u8); 16] {
+        let mut palette = FALLBACK_ANSI_COLORS;
+        for (slot, color) in palette.iter_mut().zip(self.ansi_colors) {
+            if let Some(rgb) = color_to_rgb(color) {
+                *slot = rgb;
+            }
+        }
+        palette
+    }
 }
 
 // =============================================================================
@@ -1304,6 +1622,48 @@ mod tests {
         assert_eq!(theme.name, "test");
     }
 
+    #[test]
+    fn test_terminal_palette_from_theme_file() {
+        let json = r#"{"name":"test","editor":{},"ui":{},"search":{},"diagnostic":{},"syntax":{},
+            "terminal":{"red":[255,85,85],"bright_white":[250,250,250]}}"#;
+        let theme = Theme::from_json(json).expect("Should parse theme with terminal colors");
+        assert_eq!(theme.ansi_colors[1], Color::Rgb(255, 85, 85));
+        assert_eq!(theme.ansi_colors[15], Color::Rgb(250, 250, 250));
+        assert_eq!(
+            theme.resolve_theme_key("terminal.red"),
+            Some(Color::Rgb(255, 85, 85))
+        );
+        assert_eq!(theme.ansi_color(Color::Red), Color::Rgb(255, 85, 85));
+        assert_eq!(theme.ansi_color(Color::Indexed(1)), Color::Rgb(255, 85, 85));
+        // Non-ANSI colors pass through unchanged
+        assert_eq!(theme.ansi_color(Color::Indexed(100)), Color::Indexed(100));
+        assert_eq!(theme.ansi_color(Color::Rgb(1, 2, 3)), Color::Rgb(1, 2, 3));
+    }
+
+    #[test]
+    fn test_terminal_palette_derived_from_syntax() {
+        let json = r#"{"name":"test",
+            "editor":{"bg":[20,20,20],"fg":[220,220,220],"line_number_fg":[90,90,90]},
+            "ui":{},"search":{},
+            "diagnostic":{"error_fg":[230,60,60]},
+            "syntax":{"string":[120,200,90]}}"#;
+        let theme = Theme::from_json(json).expect("Should parse minimal theme");
+        assert_eq!(theme.ansi_colors[0], Color::Rgb(20, 20, 20));
+        assert_eq!(theme.ansi_colors[7], Color::Rgb(220, 220, 220));
+        assert_eq!(theme.ansi_colors[8], Color::Rgb(90, 90, 90));
+        assert_eq!(theme.ansi_colors[1], Color::Rgb(230, 60, 60));
+        assert_eq!(theme.ansi_colors[9], Color::Rgb(255, 100, 100));
+        assert_eq!(theme.ansi_colors[2], Color::Rgb(120, 200, 90));
+    }
+
+    #[test]
+    fn test_terminal_palette_roundtrips_through_theme_file() {
+        let dracula = Theme::load_builtin("dracula").expect("Dracula theme must exist");
+        let file: ThemeFile = dracula.clone().into();
+        let back: Theme = file.into();
+        assert_eq!(back.ansi_colors, dracula.ansi_colors);
+    }
+
     #[test]
     fn test_default_reset_color() {
         // Test that "Default" maps to Color::Reset
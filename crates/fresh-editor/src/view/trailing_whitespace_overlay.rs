@@ -0,0 +1,153 @@
+//! Trailing whitespace highlighting using the overlay system
+//!
+//! Spaces and tabs at the end of lines in the viewport get a faint background.
+//! The run the cursor is in or right after is skipped, so typing a space at
+//! the end of a line doesn't flash a highlight.
+
+use crate::model::buffer::Buffer;
+use crate::model::marker::MarkerList;
+use crate::primitives::trailing_whitespace::trailing_whitespace_ranges;
+use crate::view::overlay::{Overlay, OverlayFace, OverlayManager, OverlayNamespace};
+use ratatui::style::Color;
+
+/// Namespace for trailing whitespace overlays
+pub fn trailing_whitespace_namespace() -> OverlayNamespace {
+    OverlayNamespace::from_string("trailing-whitespace".to_string())
+}
+
+/// Manager for trailing whitespace overlays
+pub struct TrailingWhitespaceOverlay {
+    /// Whether trailing whitespace is highlighted (off for virtual buffers)
+    pub enabled: bool,
+    /// Cursor position, viewport and buffer version the overlays were computed for
+    last_key: Option<(usize, usize, usize, u64)>,
+}
+
+impl TrailingWhitespaceOverlay {
+    /// Create a new trailing whitespace highlighter
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            last_key: None,
+        }
+    }
+
+    /// Update overlays for the lines in the viewport
+    ///
+    /// Returns true if overlays were updated
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        buffer: &mut Buffer,
+        overlays: &mut OverlayManager,
+        marker_list: &mut MarkerList,
+        cursor_position: usize,
+        viewport_start: usize,
+        viewport_end: usize,
+        color: Color,
+    ) -> bool {
+        let ns = trailing_whitespace_namespace();
+        if !self.enabled {
+            if self.last_key.take().is_some() {
+                overlays.clear_namespace(&ns, marker_list);
+                return true;
+            }
+            return false;
+        }
+
+        let key = (
+            cursor_position,
+            viewport_start,
+            viewport_end,
+            buffer.version(),
+        );
+        if self.last_key == Some(key) {
+            return false;
+        }
+        self.last_key = Some(key);
+
+        overlays.clear_namespace(&ns, marker_list);
+
+        let mut iter = buffer.line_iterator(viewport_start, 80);
+        while let Some((line_start, line)) = iter.next_line() {
+            if line_start >= viewport_end {
+                break;
+            }
+            for range in trailing_whitespace_ranges(&line) {
+                let range = line_start + range.start..line_start + range.end;
+                if range.start <= cursor_position && cursor_position <= range.end {
+                    continue;
+                }
+                let overlay = Overlay::with_namespace(
+                    marker_list,
+                    range,
+                    OverlayFace::Background { color },
+                    ns.clone(),
+                )
+                .with_priority_value(5);
+                overlays.add(overlay);
+            }
+        }
+
+        true
+    }
+
+    /// Force recalculation on next update
+    pub fn invalidate(&mut self) {
+        self.last_key = None;
+    }
+}
+
+impl Default for TrailingWhitespaceOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn highlighted(overlays: &OverlayManager, markers: &MarkerList, len: usize) -> Vec<usize> {
+        overlays
+            .query_viewport(0, len, markers)
+            .into_iter()
+            .map(|(_, range)| range.start)
+            .collect()
+    }
+
+    #[test]
+    fn test_highlights_trailing_whitespace_except_at_cursor() {
+        let text = "a  \nb\t\nc \n";
+        let mut buffer = Buffer::from_str_test(text);
+        let mut overlays = OverlayManager::new();
+        let mut markers = MarkerList::new();
+        let mut highlight = TrailingWhitespaceOverlay::new();
+        highlight.enabled = true;
+
+        // Cursor right after the space on the last line
+        let cursor = text.len() - 1;
+        assert!(highlight.update(
+            &mut buffer,
+            &mut overlays,
+            &mut markers,
+            cursor,
+            0,
+            text.len(),
+            Color::DarkGray,
+        ));
+        assert_eq!(highlighted(&overlays, &markers, text.len()), vec![1, 5]);
+
+        highlight.enabled = false;
+        assert!(highlight.update(
+            &mut buffer,
+            &mut overlays,
+            &mut markers,
+            cursor,
+            0,
+            text.len(),
+            Color::DarkGray,
+        ));
+        assert!(highlighted(&overlays, &markers, text.len()).is_empty());
+    }
+}
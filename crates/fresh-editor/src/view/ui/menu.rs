@@ -4,6 +4,7 @@ use crate::config::{generate_dynamic_items, Menu, MenuConfig, MenuExt, MenuItem,
 use crate::primitives::display_width::str_width;
 use crate::view::theme::Theme;
 use crate::view::ui::layout::point_in_rect;
+use fresh_core::api::PluginMenuItem;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -161,6 +162,117 @@ fn is_checkbox_checked(checkbox: &Option<String>, context: &MenuContext) -> bool
     }
 }
 
+/// Menu bar items contributed by plugins with `editor.addMenuItem`
+///
+/// Contributions are kept apart from the menus they go into so that a
+/// plugin's items can be dropped when it unloads; they are merged into the
+/// built-in menus whenever the menu bar is built.
+#[derive(Debug, Clone, Default)]
+pub struct MenuRegistry {
+    items: Vec<PluginMenuItem>,
+}
+
+impl MenuRegistry {
+    /// Add an item, replacing one the same plugin added with the same path and label
+    pub fn add(&mut self, item: PluginMenuItem) {
+        self.remove(&item.plugin_name, &item.path, &item.label);
+        self.items.push(item);
+    }
+
+    /// Remove an item a plugin added. Returns whether it was found.
+    pub fn remove(&mut self, plugin_name: &str, path: &str, label: &str) -> bool {
+        let original_len = self.items.len();
+        self.items.retain(|item| {
+            item.plugin_name != plugin_name || item.path != path || item.label != label
+        });
+        self.items.len() < original_len
+    }
+
+    /// Remove every item a plugin added
+    pub fn remove_plugin(&mut self, plugin_name: &str) {
+        self.items.retain(|item| item.plugin_name != plugin_name);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Merge the contributed items into `menus`
+    ///
+    /// The first path segment is matched against each menu's id and then its
+    /// label, so "Tools" finds a translated built-in menu too. Menus that don't
+    /// exist are added before the Help menu, submenus at the end of their
+    /// parent; items go at the end of their menu in the order they were added.
+    pub fn merge<'a>(&self, menus: impl IntoIterator<Item = &'a Menu>) -> Vec<Menu> {
+        let mut merged: Vec<Menu> = menus.into_iter().cloned().collect();
+        for item in &self.items {
+            let mut segments = item.path_segments();
+            let Some(top) = segments.next() else {
+                tracing::warn!(
+                    "Plugin '{}' added menu item '{}' with an empty path",
+                    item.plugin_name,
+                    item.label
+                );
+                continue;
+            };
+
+            let index = match merged
+                .iter()
+                .position(|menu| menu.id.as_deref() == Some(top) || menu.label == top)
+            {
+                Some(index) => index,
+                None => {
+                    let index = merged
+                        .iter()
+                        .position(|menu| menu.id.as_deref() == Some("Help"))
+                        .unwrap_or(merged.len());
+                    merged.insert(
+                        index,
+                        Menu {
+                            id: None,
+                            label: top.to_string(),
+                            items: Vec::new(),
+                            when: None,
+                        },
+                    );
+                    index
+                }
+            };
+
+            let mut items = &mut merged[index].items;
+            for segment in segments {
+                items = submenu_items(items, segment);
+            }
+            items.push(MenuItem::Action {
+                label: item.label.clone(),
+                action: item.command.clone(),
+                args: Default::default(),
+                when: item.when.clone(),
+                checkbox: None,
+            });
+        }
+        merged
+    }
+}
+
+/// Items of the submenu labelled `label`, adding the submenu if there is none
+fn submenu_items<'a>(items: &'a mut Vec<MenuItem>, label: &str) -> &'a mut Vec<MenuItem> {
+    let index = items
+        .iter()
+        .position(|item| matches!(item, MenuItem::Submenu { label: l, .. } if l == label))
+        .unwrap_or_else(|| {
+            items.push(MenuItem::Submenu {
+                label: label.to_string(),
+                items: Vec::new(),
+            });
+            items.len() - 1
+        });
+    match &mut items[index] {
+        MenuItem::Submenu { items, .. } => items,
+        _ => unreachable!("index points at a submenu"),
+    }
+}
+
 /// Menu bar state (tracks which menu is open and which item is highlighted)
 #[derive(Debug, Clone, Default)]
 pub struct MenuState {
@@ -173,6 +285,8 @@ pub struct MenuState {
     pub submenu_path: Vec<usize>,
     /// Runtime menu additions from plugins
     pub plugin_menus: Vec<Menu>,
+    /// Plugin-owned items merged into the menus
+    pub plugin_items: MenuRegistry,
     /// Context containing named boolean states for conditions and checkboxes
     pub context: MenuContext,
 }
@@ -182,6 +296,13 @@ impl MenuState {
         Self::default()
     }
 
+    /// All menus in the bar: `builtin` followed by plugin menus, with
+    /// plugin-owned items merged in
+    pub fn all_menus(&self, builtin: &[Menu]) -> Vec<Menu> {
+        self.plugin_items
+            .merge(builtin.iter().chain(self.plugin_menus.iter()))
+    }
+
     /// Open a menu by index
     pub fn open_menu(&mut self, index: usize) {
         self.active_menu = Some(index);
@@ -502,11 +623,9 @@ impl MenuRenderer {
     ) -> MenuLayout {
         let mut layout = MenuLayout::new(area);
         // Combine config menus with plugin menus, expanding any DynamicSubmenus
-        let all_menus: Vec<Menu> = menu_config
-            .menus
-            .iter()
-            .chain(menu_state.plugin_menus.iter())
-            .cloned()
+        let all_menus: Vec<Menu> = menu_state
+            .all_menus(&menu_config.menus)
+            .into_iter()
             .map(|mut menu| {
                 menu.expand_dynamic_items();
                 menu
@@ -1359,6 +1478,96 @@ mod tests {
         }
     }
 
+    fn plugin_item(plugin: &str, path: &str, label: &str) -> PluginMenuItem {
+        PluginMenuItem {
+            plugin_name: plugin.to_string(),
+            path: path.to_string(),
+            label: label.to_string(),
+            command: format!("{}_command", plugin),
+            when: None,
+        }
+    }
+
+    fn item_labels(items: &[MenuItem]) -> Vec<&str> {
+        items
+            .iter()
+            .filter_map(|item| match item {
+                MenuItem::Action { label, .. } | MenuItem::Submenu { label, .. } => {
+                    Some(label.as_str())
+                }
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_menu_registry_merges_into_existing_and_new_menus() {
+        let mut builtin = create_test_menus();
+        builtin.push(Menu {
+            id: Some("Help".to_string()),
+            label: "Hilfe".to_string(),
+            items: vec![],
+            when: None,
+        });
+
+        let mut registry = MenuRegistry::default();
+        registry.add(plugin_item("saver", "File", "Save All Twice"));
+        registry.add(plugin_item("git", "Tools/Git", "Blame"));
+        registry.add(plugin_item("git", "Tools/Git", "Log"));
+        registry.add(plugin_item("help", "Help", "Plugin Docs"));
+
+        let merged = registry.merge(&builtin);
+        assert_eq!(merged.len(), builtin.len() + 1);
+
+        let file = merged.iter().find(|m| m.label == "File").unwrap();
+        assert_eq!(item_labels(&file.items).last(), Some(&"Save All Twice"));
+        match file.items.last().unwrap() {
+            MenuItem::Action { action, .. } => assert_eq!(action, "saver_command"),
+            item => panic!("Expected an action, got {:?}", item),
+        }
+
+        // A new menu goes in front of Help, which is found by its id
+        let tools_index = merged.iter().position(|m| m.label == "Tools").unwrap();
+        assert_eq!(merged[tools_index + 1].label, "Hilfe");
+        assert_eq!(
+            item_labels(&merged[tools_index + 1].items),
+            vec!["Plugin Docs"]
+        );
+        match &merged[tools_index].items[..] {
+            [MenuItem::Submenu { label, items }] => {
+                assert_eq!(label, "Git");
+                assert_eq!(item_labels(items), vec!["Blame", "Log"]);
+            }
+            items => panic!("Expected a single Git submenu, got {:?}", items),
+        }
+    }
+
+    #[test]
+    fn test_menu_registry_replace_and_remove() {
+        let builtin = create_test_menus();
+        let mut registry = MenuRegistry::default();
+        registry.add(plugin_item("git", "Tools/Git", "Blame"));
+        registry.add(plugin_item("git", "Tools/Git", "Blame"));
+        registry.add(plugin_item("other", "Tools", "Count"));
+
+        let merged = registry.merge(&builtin);
+        let tools = merged.iter().find(|m| m.label == "Tools").unwrap();
+        match &tools.items[0] {
+            MenuItem::Submenu { items, .. } => assert_eq!(item_labels(items), vec!["Blame"]),
+            item => panic!("Expected the Git submenu, got {:?}", item),
+        }
+
+        assert!(!registry.remove("other", "Tools", "Blame"));
+        registry.remove_plugin("git");
+        let merged = registry.merge(&builtin);
+        let tools = merged.iter().find(|m| m.label == "Tools").unwrap();
+        assert_eq!(item_labels(&tools.items), vec!["Count"]);
+
+        assert!(registry.remove("other", "Tools", "Count"));
+        assert!(registry.is_empty());
+        assert_eq!(registry.merge(&builtin), builtin);
+    }
+
     #[test]
     fn test_empty_menu_config() {
         let json = r#"{ "menus": [] }"#;
@@ -33,50 +33,6 @@ use std::ops::Range;
 /// memory usage reasonable (~80KB per ViewLine instead of hundreds of MB).
 const MAX_SAFE_LINE_WIDTH: usize = 10_000;
 
-/// Compute character-level diff between two strings, returning ranges of changed characters.
-/// Returns a tuple of (old_changed_ranges, new_changed_ranges) where each range indicates
-/// character indices that differ between the strings.
-fn compute_inline_diff(old_text: &str, new_text: &str) -> (Vec<Range<usize>>, Vec<Range<usize>>) {
-    let old_chars: Vec<char> = old_text.chars().collect();
-    let new_chars: Vec<char> = new_text.chars().collect();
-
-    let mut old_ranges = Vec::new();
-    let mut new_ranges = Vec::new();
-
-    // Find common prefix
-    let prefix_len = old_chars
-        .iter()
-        .zip(new_chars.iter())
-        .take_while(|(a, b)| a == b)
-        .count();
-
-    // Find common suffix (from the non-prefix part)
-    let old_remaining = old_chars.len() - prefix_len;
-    let new_remaining = new_chars.len() - prefix_len;
-    let suffix_len = old_chars
-        .iter()
-        .rev()
-        .zip(new_chars.iter().rev())
-        .take(old_remaining.min(new_remaining))
-        .take_while(|(a, b)| a == b)
-        .count();
-
-    // The changed range is between prefix and suffix
-    let old_start = prefix_len;
-    let old_end = old_chars.len().saturating_sub(suffix_len);
-    let new_start = prefix_len;
-    let new_end = new_chars.len().saturating_sub(suffix_len);
-
-    if old_start < old_end {
-        old_ranges.push(old_start..old_end);
-    }
-    if new_start < new_end {
-        new_ranges.push(new_start..new_end);
-    }
-
-    (old_ranges, new_ranges)
-}
-
 fn push_span_with_map(
     spans: &mut Vec<Span<'static>>,
     map: &mut Vec<Option<usize>>,
@@ -327,6 +283,10 @@ struct DecorationContext {
     diagnostic_lines: HashSet<usize>,
     /// Line indicators indexed by line number (highest priority indicator per line)
     line_indicators: BTreeMap<usize, crate::view::margin::LineIndicator>,
+    /// Plugin line backgrounds indexed by line number, resolved against the theme
+    line_backgrounds: BTreeMap<usize, Color>,
+    /// Plugin gutter columns with their text for the visible lines
+    gutter_columns: Vec<crate::view::margin::ViewportGutterColumn>,
 }
 
 struct LineRenderOutput {
@@ -389,6 +349,8 @@ struct CharStyleContext<'a> {
     highlight_spans: &'a [crate::primitives::highlighter::HighlightSpan],
     semantic_token_spans: &'a [crate::primitives::highlighter::HighlightSpan],
     viewport_overlays: &'a [(crate::view::overlay::Overlay, Range<usize>)],
+    /// Background of the whole source line, painted beneath overlays
+    line_background: Option<Color>,
     primary_cursor_position: usize,
     is_active: bool,
 }
@@ -409,18 +371,46 @@ struct LeftMarginContext<'a> {
     diagnostic_lines: &'a HashSet<usize>,
     /// Pre-computed line indicators (line_num -> indicator)
     line_indicators: &'a BTreeMap<usize, crate::view::margin::LineIndicator>,
+    /// Plugin gutter columns, drawn before the indicator column
+    gutter_columns: &'a [crate::view::margin::ViewportGutterColumn],
     /// Line number where the primary cursor is located (for relative line numbers)
     cursor_line: usize,
     /// Whether to show relative line numbers
     relative_line_numbers: bool,
 }
 
-/// Render the left margin (indicators + line numbers + separator) to line_spans
+/// Render the left margin (plugin gutter columns + indicators + line numbers +
+/// separator) to line_spans
 fn render_left_margin(
     ctx: &LeftMarginContext,
     line_spans: &mut Vec<Span<'static>>,
     line_view_map: &mut Vec<Option<usize>>,
 ) {
+    // Plugin gutter columns are shown even when line numbers are off
+    for column in ctx.gutter_columns {
+        let cell = if ctx.is_continuation {
+            None
+        } else {
+            column.cells.get(&ctx.current_source_line_num)
+        };
+        let (text, color) = match cell {
+            Some(cell) => (
+                cell.fit(column.width),
+                cell.color
+                    .as_ref()
+                    .and_then(|color| color.resolve(ctx.theme)),
+            ),
+            None => (" ".repeat(column.width), None),
+        };
+        push_span_with_map(
+            line_spans,
+            line_view_map,
+            text + " ",
+            Style::default().fg(color.unwrap_or(ctx.theme.line_number_fg)),
+            None,
+        );
+    }
+
     if !ctx.state.margins.left_config.enabled {
         return;
     }
@@ -569,15 +559,16 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
         || ctx.ansi_style.bg.is_some()
         || !ctx.ansi_style.add_modifier.is_empty()
     {
-        // Apply ANSI styling from escape codes
+        // Apply ANSI styling from escape codes, mapping the 16 ANSI colors
+        // through the theme's terminal palette
         let mut s = Style::default();
         if let Some(fg) = ctx.ansi_style.fg {
-            s = s.fg(fg);
+            s = s.fg(ctx.theme.ansi_color(fg));
         } else {
             s = s.fg(ctx.theme.editor_fg);
         }
         if let Some(bg) = ctx.ansi_style.bg {
-            s = s.bg(bg);
+            s = s.bg(ctx.theme.ansi_color(bg));
         }
         s = s.add_modifier(ctx.ansi_style.add_modifier);
         s
@@ -615,6 +606,10 @@ fn compute_char_style(ctx: &CharStyleContext) -> CharStyleOutput {
         }
     }
 
+    if let Some(color) = ctx.line_background {
+        style = style.bg(color);
+    }
+
     // Apply overlay styles
     for overlay in &overlays {
         match &overlay.face {
@@ -698,6 +693,7 @@ impl SplitRenderer {
     /// * `buffer_metadata` - Metadata for buffers (contains display names)
     /// * `event_logs` - Event logs for each buffer
     /// * `theme` - The active theme for colors
+    /// * `split_themes` - Themes of splits that don't use the active theme
     /// * `lsp_waiting` - Whether LSP is waiting
     /// * `large_file_threshold_bytes` - Threshold for using constant scrollbar thumb size
     /// * `line_wrap` - Whether line wrapping is enabled
@@ -721,6 +717,7 @@ impl SplitRenderer {
             crate::view::composite_view::CompositeViewState,
         >,
         theme: &crate::view::theme::Theme,
+        split_themes: &HashMap<crate::model::event::SplitId, &crate::view::theme::Theme>,
         ansi_background: Option<&AnsiBackground>,
         background_fade: f32,
         lsp_waiting: bool,
@@ -739,6 +736,7 @@ impl SplitRenderer {
         relative_line_numbers: bool,
         tab_bar_visible: bool,
         use_terminal_bg: bool,
+        sticky_scroll_max_depth: usize,
     ) -> (
         Vec<(
             crate::model::event::SplitId,
@@ -752,6 +750,7 @@ impl SplitRenderer {
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // close split button areas
         Vec<(crate::model::event::SplitId, u16, u16, u16)>, // maximize split button areas
         HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>>, // view line mappings for mouse clicks
+        HashMap<crate::model::event::SplitId, Vec<usize>>, // sticky scroll header lines per split
     ) {
         let _span = tracing::trace_span!("render_content").entered();
 
@@ -770,10 +769,13 @@ impl SplitRenderer {
         let mut maximize_split_areas = Vec::new();
         let mut view_line_mappings: HashMap<crate::model::event::SplitId, Vec<ViewLineMapping>> =
             HashMap::new();
+        let mut sticky_scroll_headers: HashMap<crate::model::event::SplitId, Vec<usize>> =
+            HashMap::new();
 
         // Render each split
         for (split_id, buffer_id, split_area) in visible_buffers {
             let is_active = split_id == active_split_id;
+            let theme = split_themes.get(&split_id).copied().unwrap_or(theme);
 
             let layout = Self::split_layout(split_area, tab_bar_visible);
             let (split_buffers, tab_scroll_offset) =
@@ -957,7 +959,7 @@ impl SplitRenderer {
                 let view_prefs =
                     Self::resolve_view_preferences(state, split_view_states.as_deref(), split_id);
 
-                let split_view_mappings = Self::render_buffer_in_split(
+                let (split_view_mappings, sticky_headers) = Self::render_buffer_in_split(
                     frame,
                     state,
                     &mut viewport,
@@ -978,10 +980,12 @@ impl SplitRenderer {
                     hide_cursor,
                     relative_line_numbers,
                     use_terminal_bg,
+                    sticky_scroll_max_depth,
                 );
 
                 // Store view line mappings for mouse click handling
                 view_line_mappings.insert(split_id, split_view_mappings);
+                sticky_scroll_headers.insert(split_id, sticky_headers);
 
                 // For small files, count actual lines for accurate scrollbar
                 // For large files, we'll use a constant thumb size
@@ -1048,6 +1052,7 @@ impl SplitRenderer {
             close_split_areas,
             maximize_split_areas,
             view_line_mappings,
+            sticky_scroll_headers,
         )
     }
 
@@ -1301,7 +1306,7 @@ impl SplitRenderer {
                 RowType::Context => None,
             };
 
-            // Compute inline diff for modified rows (to highlight changed words/characters)
+            // Compute word-level inline diff for modified rows (to highlight changed words)
             let inline_diffs: Vec<Vec<Range<usize>>> = if aligned_row.row_type
                 == RowType::Modification
             {
@@ -1328,7 +1333,8 @@ impl SplitRenderer {
                 if line_contents.len() >= 2 {
                     if let (Some(old_text), Some(new_text)) = (&line_contents[0], &line_contents[1])
                     {
-                        let (old_ranges, new_ranges) = compute_inline_diff(old_text, new_text);
+                        let (old_ranges, new_ranges) =
+                            crate::model::line_diff::word_diff_ranges(old_text, new_text);
                         vec![old_ranges, new_ranges]
                     } else {
                         vec![Vec::new(); composite.sources.len()]
@@ -1993,8 +1999,9 @@ impl SplitRenderer {
         content_width: usize,
         gutter_width: usize,
     ) -> ViewData {
-        // Check if buffer is binary before building tokens
-        let is_binary = state.buffer.is_binary();
+        // Check if buffer is binary (or shown as hex) before building tokens
+        let is_binary = state.buffer.is_binary() || state.hex_view;
+        let ansi_colors = state.renders_ansi_colors();
         let line_ending = state.buffer.line_ending();
 
         // Build base token stream from source
@@ -2004,18 +2011,27 @@ impl SplitRenderer {
             estimated_line_length,
             visible_count,
             is_binary,
+            ansi_colors,
             line_ending,
         );
 
-        // Use plugin transform if available, otherwise use base tokens
-        let mut tokens = view_transform.map(|vt| vt.tokens).unwrap_or(base_tokens);
+        // Use plugin transform if available, otherwise use base tokens with any
+        // plugin-supplied conceal rules applied
+        let mut tokens = match view_transform {
+            Some(vt) => vt.tokens,
+            None => crate::view::conceal::apply_conceal_rules(base_tokens, &state.conceal_rules),
+        };
 
         // Apply wrapping transform - always enabled for safety, but with different thresholds.
-        // When line_wrap is on: wrap at viewport width for normal text flow.
+        // When line_wrap is on: wrap at viewport width (or the buffer's wrap column,
+        // if narrower) for normal text flow.
         // When line_wrap is off: wrap at MAX_SAFE_LINE_WIDTH to prevent memory exhaustion
         // from extremely long lines (e.g., 10MB single-line JSON files).
         let effective_width = if line_wrap_enabled {
-            content_width
+            match state.wrap_column {
+                Some(column) => content_width.min(column as usize + gutter_width),
+                None => content_width,
+            }
         } else {
             MAX_SAFE_LINE_WIDTH
         };
@@ -2023,12 +2039,10 @@ impl SplitRenderer {
 
         // Convert tokens to display lines using the view pipeline
         // Each ViewLine preserves LineStart info for correct line number rendering
-        // Use binary mode if the buffer contains binary content
-        // Enable ANSI awareness for non-binary content to handle escape sequences correctly
-        let is_binary = state.buffer.is_binary();
-        let ansi_aware = !is_binary; // ANSI parsing for normal text files
+        // Use binary mode if the buffer contains binary content or is shown as hex
+        // Enable ANSI awareness unless the buffer shows escape sequences raw
         let source_lines: Vec<ViewLine> =
-            ViewLineIterator::new(&tokens, is_binary, ansi_aware, state.tab_size).collect();
+            ViewLineIterator::new(&tokens, is_binary, ansi_colors, state.tab_size).collect();
 
         // Inject virtual lines (LineAbove/LineBelow) from VirtualTextManager
         let lines = Self::inject_virtual_lines(source_lines, state);
@@ -2091,12 +2105,14 @@ impl SplitRenderer {
             .map(|b| b + 1)
             .unwrap_or(viewport_start);
 
-        // Query virtual lines in viewport range
+        // Query virtual lines in viewport range (one past its end, for lines
+        // anchored at the end of the buffer)
         let virtual_lines = state.virtual_texts.query_lines_in_range(
             &state.marker_list,
             viewport_start,
-            viewport_end,
+            viewport_end + 1,
         );
+        let buffer_len = state.buffer.len();
 
         // If no virtual lines, return source lines unchanged
         if virtual_lines.is_empty() {
@@ -2131,11 +2147,13 @@ impl SplitRenderer {
             // Add the source line
             result.push(source_line.clone());
 
-            // Find LineBelow virtual texts anchored to this line
+            // Find LineBelow virtual texts anchored to this line (the end of
+            // the buffer belongs to the last line unless it ends with a newline)
             if let (Some(start), Some(end)) = (line_start_byte, line_end_byte) {
+                let at_eof = end == buffer_len && !source_line.ends_with_newline;
                 for (anchor_pos, vtext) in &virtual_lines {
                     if *anchor_pos >= start
-                        && *anchor_pos < end
+                        && (*anchor_pos < end || (at_eof && *anchor_pos == end))
                         && vtext.position == VirtualTextPosition::LineBelow
                     {
                         result.push(Self::create_virtual_line(&vtext.text, vtext.style));
@@ -2153,6 +2171,7 @@ impl SplitRenderer {
         estimated_line_length: usize,
         visible_count: usize,
         is_binary: bool,
+        ansi_colors: bool,
         line_ending: crate::model::buffer::LineEnding,
     ) -> Vec<fresh_core::api::ViewTokenWire> {
         use crate::model::buffer::LineEnding;
@@ -2256,8 +2275,8 @@ impl SplitRenderer {
                                 style: None,
                             });
                         }
-                        _ if Self::is_control_char(ch) => {
-                            // Control character - emit as BinaryByte to render as <XX>
+                        _ if Self::is_control_char(ch) || (ch == '\x1b' && !ansi_colors) => {
+                            // Control character (or ESC shown raw) - emit as BinaryByte to render as <XX>
                             tokens.push(ViewTokenWire {
                                 source_offset,
                                 kind: ViewTokenWireKind::BinaryByte(ch as u8),
@@ -2474,6 +2493,7 @@ impl SplitRenderer {
         estimated_line_length: usize,
         visible_count: usize,
         is_binary: bool,
+        ansi_colors: bool,
         line_ending: crate::model::buffer::LineEnding,
     ) -> Vec<fresh_core::api::ViewTokenWire> {
         Self::build_base_tokens(
@@ -2482,6 +2502,7 @@ impl SplitRenderer {
             estimated_line_length,
             visible_count,
             is_binary,
+            ansi_colors,
             line_ending,
         )
     }
@@ -2900,6 +2921,29 @@ impl SplitRenderer {
             primary_cursor_position,
         );
 
+        // Update the indentation block guide
+        state.indent_block_overlay.update(
+            &mut state.buffer,
+            &mut state.overlays,
+            &mut state.marker_list,
+            primary_cursor_position,
+            viewport_start,
+            viewport_end,
+            state.tab_size,
+            theme.current_line_bg,
+        );
+
+        // Highlight trailing whitespace
+        state.trailing_whitespace_overlay.update(
+            &mut state.buffer,
+            &mut state.overlays,
+            &mut state.marker_list,
+            primary_cursor_position,
+            viewport_start,
+            viewport_end,
+            theme.trailing_whitespace_bg,
+        );
+
         // Semantic tokens are stored as overlays so their ranges track edits.
         // Convert them into highlight spans for the render pipeline.
         let mut semantic_token_spans = Vec::new();
@@ -2934,10 +2978,11 @@ impl SplitRenderer {
             })
             .collect();
 
+        // One past the end so text anchored at the end of the buffer is found
         let virtual_text_lookup: HashMap<usize, Vec<crate::view::virtual_text::VirtualText>> =
             state
                 .virtual_texts
-                .build_lookup(&state.marker_list, viewport_start, viewport_end)
+                .build_lookup(&state.marker_list, viewport_start, viewport_end + 1)
                 .into_iter()
                 .map(|(position, texts)| (position, texts.into_iter().cloned().collect()))
                 .collect();
@@ -2949,6 +2994,21 @@ impl SplitRenderer {
             |byte_offset| state.buffer.get_line_number(byte_offset),
         );
 
+        let gutter_columns = state.margins.get_gutter_columns_for_viewport(
+            viewport_start,
+            viewport_end,
+            |byte_offset| state.buffer.get_line_number(byte_offset),
+        );
+
+        let line_backgrounds = state
+            .line_backgrounds
+            .for_viewport(viewport_start, viewport_end, |byte_offset| {
+                state.buffer.get_line_number(byte_offset)
+            })
+            .into_iter()
+            .filter_map(|(line, color)| Some((line, color.resolve(theme)?)))
+            .collect();
+
         DecorationContext {
             highlight_spans,
             semantic_token_spans,
@@ -2956,6 +3016,8 @@ impl SplitRenderer {
             virtual_text_lookup,
             diagnostic_lines,
             line_indicators,
+            line_backgrounds,
+            gutter_columns,
         }
     }
 
@@ -3014,7 +3076,9 @@ impl SplitRenderer {
         let viewport_overlays = &decorations.viewport_overlays;
         let virtual_text_lookup = &decorations.virtual_text_lookup;
         let diagnostic_lines = &decorations.diagnostic_lines;
+        let line_backgrounds = &decorations.line_backgrounds;
         let line_indicators = &decorations.line_indicators;
+        let gutter_columns = &decorations.gutter_columns;
 
         let mut lines = Vec::new();
         let mut view_line_mappings = Vec::new();
@@ -3124,6 +3188,7 @@ impl SplitRenderer {
                     estimated_lines,
                     diagnostic_lines,
                     line_indicators,
+                    gutter_columns,
                     cursor_line,
                     relative_line_numbers,
                 },
@@ -3171,6 +3236,13 @@ impl SplitRenderer {
                 None
             };
 
+            // Plugin line background, for rows showing source text (not virtual lines)
+            let line_background = line_char_source_bytes
+                .iter()
+                .any(Option::is_some)
+                .then(|| line_backgrounds.get(&current_source_line_num).copied())
+                .flatten();
+
             // Track byte positions for extend_to_line_end feature
             let mut first_line_byte_pos: Option<usize> = None;
             let mut last_line_byte_pos: Option<usize> = None;
@@ -3294,6 +3366,7 @@ impl SplitRenderer {
                         highlight_spans,
                         semantic_token_spans,
                         viewport_overlays,
+                        line_background,
                         primary_cursor_position,
                         is_active,
                     });
@@ -3339,6 +3412,23 @@ impl SplitRenderer {
                                     None,
                                 );
                             }
+                            for vtext in vtexts
+                                .iter()
+                                .filter(|v| v.position == VirtualTextPosition::Ghost)
+                            {
+                                span_acc.flush(&mut line_spans, &mut line_view_map);
+                                // Mapping the text to the character puts a cursor on it
+                                // at the start of the text; a cursor on a newline is
+                                // placed after the line's last mapped column instead
+                                let source = (ch != '\n').then_some(bp);
+                                push_span_with_map(
+                                    &mut line_spans,
+                                    &mut line_view_map,
+                                    vtext.text.clone(),
+                                    vtext.style,
+                                    source,
+                                );
+                            }
                         }
                     }
 
@@ -3521,6 +3611,24 @@ impl SplitRenderer {
                         }
                     }
 
+                    if is_primary_at_end {
+                        span_acc.flush(&mut line_spans, &mut line_view_map);
+                        for vtext in virtual_text_lookup
+                            .get(&primary_cursor_position)
+                            .into_iter()
+                            .flatten()
+                            .filter(|v| v.position == VirtualTextPosition::Ghost)
+                        {
+                            push_span_with_map(
+                                &mut line_spans,
+                                &mut line_view_map,
+                                vtext.text.clone(),
+                                vtext.style,
+                                None,
+                            );
+                        }
+                    }
+
                     let should_add_indicator = if is_active { !is_primary_at_end } else { true };
                     if should_add_indicator {
                         let cursor_style = if is_active {
@@ -3571,7 +3679,8 @@ impl SplitRenderer {
 
             // Fill remaining width for overlays with extend_to_line_end
             // Only when line wrapping is disabled (side-by-side diff typically disables wrapping)
-            if !line_wrap {
+            // Line backgrounds are filled in either case, each view line being one row
+            if !line_wrap || line_background.is_some() {
                 // Calculate the content area width (total width minus gutter)
                 let content_width = render_area.width.saturating_sub(gutter_width as u16) as usize;
                 let remaining_cols = content_width.saturating_sub(visible_char_count);
@@ -3619,6 +3728,9 @@ impl SplitRenderer {
                     } else {
                         None
                     };
+                    let fill_style = fill_style
+                        .filter(|_| !line_wrap)
+                        .or_else(|| line_background.map(|bg| Style::default().fg(bg).bg(bg)));
 
                     if let Some(fill_bg) = fill_style {
                         let fill_text = " ".repeat(remaining_cols);
@@ -3692,10 +3804,15 @@ impl SplitRenderer {
                 };
                 let line_len_chars = line_content.chars().count();
 
-                last_line_end = Some(LastLineEnd {
-                    pos: (end_x, y),
-                    terminated_with_newline: line_has_newline,
-                });
+                // Injected lines (virtual text below the last line) don't end the buffer
+                let is_injected = !line_char_source_bytes.is_empty()
+                    && line_char_source_bytes.iter().all(Option::is_none);
+                if !is_injected || last_line_end.is_none() {
+                    last_line_end = Some(LastLineEnd {
+                        pos: (end_x, y),
+                        terminated_with_newline: line_has_newline,
+                    });
+                }
 
                 if line_has_newline && line_len_chars > 0 {
                     let newline_idx = line_len_chars.saturating_sub(1);
@@ -3759,20 +3876,49 @@ impl SplitRenderer {
                     }
                 }
 
+                // Text anchored at the end of the buffer belongs to this line
+                let buffer_len = state.buffer.len();
+                let eof_vtexts = virtual_text_lookup.get(&buffer_len);
+                for vtext in eof_vtexts
+                    .into_iter()
+                    .flatten()
+                    .filter(|v| v.position == VirtualTextPosition::Ghost)
+                {
+                    implicit_line_spans.push(Span::styled(vtext.text.clone(), vtext.style));
+                }
+
                 let implicit_y = lines.len() as u16;
                 lines.push(Line::from(implicit_line_spans));
                 lines_rendered += 1;
 
                 // Add mapping for implicit line
                 // It has no content, so map is empty (gutter is handled by offset in screen_to_buffer_position)
-                let buffer_len = state.buffer.len();
-
                 view_line_mappings.push(ViewLineMapping {
                     char_source_bytes: Vec::new(),
                     visual_to_char: Vec::new(),
                     line_end_byte: buffer_len,
                 });
 
+                for vtext in eof_vtexts
+                    .into_iter()
+                    .flatten()
+                    .filter(|v| v.position == VirtualTextPosition::LineBelow)
+                {
+                    if lines_rendered >= visible_line_count {
+                        break;
+                    }
+                    lines.push(Line::from(vec![
+                        Span::raw(" ".repeat(gutter_width)),
+                        Span::styled(vtext.text.clone(), vtext.style),
+                    ]));
+                    lines_rendered += 1;
+                    view_line_mappings.push(ViewLineMapping {
+                        char_source_bytes: Vec::new(),
+                        visual_to_char: Vec::new(),
+                        line_end_byte: buffer_len,
+                    });
+                }
+
                 // NOTE: We intentionally do NOT update last_line_end here.
                 // The implicit empty line is a visual display aid, not an actual content line.
                 // last_line_end should track the last actual content line for cursor placement logic.
@@ -3864,7 +4010,8 @@ impl SplitRenderer {
         hide_cursor: bool,
         relative_line_numbers: bool,
         use_terminal_bg: bool,
-    ) -> Vec<ViewLineMapping> {
+        sticky_scroll_max_depth: usize,
+    ) -> (Vec<ViewLineMapping>, Vec<usize>) {
         let _span = tracing::trace_span!("render_buffer_in_split").entered();
 
         // Compute effective editor background: terminal default or theme-defined
@@ -4100,6 +4247,34 @@ impl SplitRenderer {
             gutter_width,
         );
 
+        let sticky_headers = if sticky_scroll_max_depth > 0 && view_mode == ViewMode::Source {
+            // Never cover the cursor, and leave most of the split for the content
+            let cursor_row = cursor
+                .filter(|_| is_active && state.show_cursors)
+                .map_or(usize::MAX, |(_, y)| y as usize);
+            let max_rows = sticky_scroll_max_depth
+                .min(cursor_row)
+                .min(render_area.height as usize / 2);
+            Self::sticky_scroll_headers(
+                state,
+                &render_output.view_line_mappings,
+                max_rows,
+                estimated_line_length,
+            )
+        } else {
+            Vec::new()
+        };
+        Self::render_sticky_scroll(
+            frame,
+            state,
+            &sticky_headers,
+            render_area,
+            gutter_width,
+            if line_wrap { 0 } else { viewport.left_column },
+            theme,
+            estimated_line_length,
+        );
+
         if is_active && state.show_cursors && !hide_cursor {
             if let Some((cursor_screen_x, cursor_screen_y)) = cursor {
                 // cursor_screen_x already includes gutter width from line_view_map
@@ -4125,7 +4300,127 @@ impl SplitRenderer {
 
         // Extract view line mappings for mouse click handling
         // This maps screen coordinates to buffer byte positions
-        render_output.view_line_mappings
+        (render_output.view_line_mappings, sticky_headers)
+    }
+
+    /// Line starts of the scope headers to pin at the top of a split, outermost first
+    ///
+    /// The headers are those of the first row they leave uncovered, so the
+    /// scopes are recomputed until the number of headers settles.
+    fn sticky_scroll_headers(
+        state: &mut EditorState,
+        mappings: &[ViewLineMapping],
+        max_rows: usize,
+        estimated_line_length: usize,
+    ) -> Vec<usize> {
+        let mut headers = Vec::new();
+        for _ in 0..=max_rows {
+            let Some(row) = mappings.get(headers.len()) else {
+                return Vec::new();
+            };
+            let byte = row
+                .char_source_bytes
+                .iter()
+                .find_map(|b| *b)
+                .unwrap_or(row.line_end_byte);
+            let line_start = state
+                .buffer
+                .line_iterator(byte, estimated_line_length)
+                .next_line()
+                .map_or(byte, |(start, _)| start);
+            let mut next = state.sticky_scopes.headers(
+                &mut state.buffer,
+                &state.language,
+                line_start,
+                state.tab_size,
+                estimated_line_length,
+            );
+            next.truncate(max_rows);
+            let settled = next.len() == headers.len();
+            headers = next;
+            if settled {
+                break;
+            }
+        }
+        headers
+    }
+
+    /// Draw the pinned scope headers over the first rows of a split
+    #[allow(clippy::too_many_arguments)]
+    fn render_sticky_scroll(
+        frame: &mut Frame,
+        state: &mut EditorState,
+        headers: &[usize],
+        render_area: Rect,
+        gutter_width: usize,
+        left_column: usize,
+        theme: &crate::view::theme::Theme,
+        estimated_line_length: usize,
+    ) {
+        let text_style = Style::default()
+            .fg(theme.editor_fg)
+            .bg(theme.sticky_scroll_bg);
+        let gutter_style = Style::default()
+            .fg(theme.line_number_fg)
+            .bg(theme.sticky_scroll_bg);
+        let text_width = (render_area.width as usize).saturating_sub(gutter_width);
+        let tab_size = state.tab_size.max(1);
+
+        for (row, &header) in headers.iter().enumerate() {
+            let mut spans = Vec::new();
+            if gutter_width > 0 {
+                let margin = &state.margins.left_config;
+                let separator = if margin.show_separator {
+                    margin.separator.as_str()
+                } else {
+                    ""
+                };
+                let number = state.buffer.get_line_number(header) + 1;
+                let gutter = format!(" {:>width$}{}", number, separator, width = margin.width);
+                let gutter: String = gutter.chars().take(gutter_width).collect();
+                spans.push(Span::styled(
+                    format!("{:<width$}", gutter, width = gutter_width),
+                    gutter_style,
+                ));
+            }
+
+            let line = state
+                .buffer
+                .line_iterator(header, estimated_line_length)
+                .next_line()
+                .map(|(_, line)| line)
+                .unwrap_or_default();
+            let mut text = String::new();
+            let mut column = 0;
+            for ch in line.trim_end_matches(['\n', '\r']).chars() {
+                let (shown, width) = if ch == '\t' {
+                    let width = tab_size - column % tab_size;
+                    (" ".repeat(width), width)
+                } else {
+                    (ch.to_string(), char_width(ch))
+                };
+                if column >= left_column {
+                    if column + width > left_column + text_width {
+                        break;
+                    }
+                    text.push_str(&shown);
+                }
+                column += width;
+            }
+            spans.push(Span::styled(text, text_style));
+
+            let row_area = Rect::new(
+                render_area.x,
+                render_area.y + row as u16,
+                render_area.width,
+                1,
+            );
+            frame.render_widget(Clear, row_area);
+            frame.render_widget(
+                Paragraph::new(Line::from(spans)).style(text_style),
+                row_area,
+            );
+        }
     }
 
     /// Apply styles from original line_spans to a wrapped segment
@@ -4944,6 +5239,7 @@ mod tests {
             80,    // estimated_line_length
             10,    // visible_count
             false, // is_binary
+            true,  // ansi_colors
             LineEnding::CRLF,
         );
 
@@ -4993,6 +5289,7 @@ mod tests {
             80,
             10,
             false,
+            true,
             LineEnding::CRLF,
         );
 
@@ -5073,6 +5370,7 @@ mod tests {
             80,
             10,
             false,
+            true,
             LineEnding::LF,
         );
 
@@ -5120,6 +5418,7 @@ mod tests {
             80,
             10,
             false,
+            true,
             LineEnding::LF,
         );
 
@@ -5149,6 +5448,7 @@ mod tests {
             80,
             10,
             false,
+            true,
             LineEnding::CRLF,
         );
 
@@ -5194,6 +5494,7 @@ mod tests {
             80,
             10,
             false,
+            true,
             LineEnding::CRLF,
         );
 
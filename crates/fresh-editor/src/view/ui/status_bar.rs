@@ -1,11 +1,14 @@
 //! Status bar and prompt/minibuffer rendering
 
+use std::collections::HashMap;
 use std::path::Path;
 
 use crate::app::WarningLevel;
+use crate::config::StatusBarSegmentConfig;
 use crate::primitives::display_width::{char_width, str_width};
 use crate::state::EditorState;
 use crate::view::prompt::Prompt;
+use crate::view::ui::status_bar_template::StatusBarTemplate;
 use ratatui::layout::Rect;
 use ratatui::style::{Modifier, Style};
 use ratatui::text::{Line, Span};
@@ -26,6 +29,21 @@ pub struct StatusBarLayout {
     pub language_indicator: Option<(u16, u16, u16)>,
     /// Status message area (row, start_col, end_col) - clickable to show full history
     pub message_area: Option<(u16, u16, u16)>,
+    /// Template segments with a click command ((row, start_col, end_col), command)
+    pub segment_commands: Vec<((u16, u16, u16), String)>,
+}
+
+/// User and plugin customization of the status bar
+#[derive(Debug, Clone, Copy)]
+pub struct StatusBarCustomization<'a> {
+    /// `editor.status_bar_template` (empty for the built-in layout)
+    pub template: &'a str,
+    /// `editor.status_bar_segments`
+    pub segment_rules: &'a HashMap<String, StatusBarSegmentConfig>,
+    /// Segment texts set by plugins, keyed by segment name
+    pub plugin_segments: &'a HashMap<String, String>,
+    /// Editor mode set by a plugin (e.g. "vi-normal")
+    pub mode: Option<&'a str>,
 }
 
 /// Status bar hover state for styling clickable indicators
@@ -257,6 +275,9 @@ impl StatusBarRenderer {
     /// * `warning_level` - LSP warning level (for coloring LSP indicator)
     /// * `general_warning_count` - Number of general warnings (for badge display)
     /// * `remote_connection` - Optional remote connection info (e.g., "user@host")
+    /// * `profile` - Optional active config profile name (from `--profile`)
+    /// * `zoomed` - Whether the active split is zoomed (other splits hidden)
+    /// * `customization` - Status bar template and plugin segments
     ///
     /// # Returns
     /// Layout information with positions of clickable indicators
@@ -277,6 +298,9 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        profile: Option<&str>,
+        zoomed: bool,
+        customization: StatusBarCustomization,
     ) -> StatusBarLayout {
         Self::render_status(
             frame,
@@ -294,6 +318,9 @@ impl StatusBarRenderer {
             general_warning_count,
             hover,
             remote_connection,
+            profile,
+            zoomed,
+            customization,
         )
     }
 
@@ -309,13 +336,22 @@ impl StatusBarRenderer {
         // Create spans for the prompt
         let mut spans = vec![Span::styled(prompt.message.clone(), base_style)];
 
+        // Password input is drawn as one `*` per character
+        let shown = |text: &str| {
+            if prompt.masked {
+                "*".repeat(text.chars().count())
+            } else {
+                text.to_string()
+            }
+        };
+
         // If there's a selection, split the input into parts
         if let Some((sel_start, sel_end)) = prompt.selection_range() {
             let input = &prompt.input;
 
             // Text before selection
             if sel_start > 0 {
-                spans.push(Span::styled(input[..sel_start].to_string(), base_style));
+                spans.push(Span::styled(shown(&input[..sel_start]), base_style));
             }
 
             // Selected text (blue background for visibility, cursor remains visible)
@@ -325,18 +361,24 @@ impl StatusBarRenderer {
                     .fg(theme.prompt_selection_fg)
                     .bg(theme.prompt_selection_bg);
                 spans.push(Span::styled(
-                    input[sel_start..sel_end].to_string(),
+                    shown(&input[sel_start..sel_end]),
                     selection_style,
                 ));
             }
 
             // Text after selection
             if sel_end < input.len() {
-                spans.push(Span::styled(input[sel_end..].to_string(), base_style));
+                spans.push(Span::styled(shown(&input[sel_end..]), base_style));
             }
+        } else if let (true, Some(placeholder)) = (prompt.input.is_empty(), &prompt.placeholder) {
+            // Empty input shows the placeholder dimmed; the cursor stays before it
+            let placeholder_style = Style::default()
+                .fg(theme.help_separator_fg)
+                .bg(theme.prompt_bg);
+            spans.push(Span::styled(placeholder.clone(), placeholder_style));
         } else {
             // No selection, render entire input normally
-            spans.push(Span::styled(prompt.input.clone(), base_style));
+            spans.push(Span::styled(shown(&prompt.input), base_style));
         }
 
         let line = Line::from(spans);
@@ -349,7 +391,7 @@ impl StatusBarRenderer {
         // - Double-width CJK characters
         // - Zero-width combining characters (Thai diacritics, etc.)
         let message_width = str_width(&prompt.message);
-        let input_width_before_cursor = str_width(&prompt.input[..prompt.cursor_pos]);
+        let input_width_before_cursor = str_width(&shown(&prompt.input[..prompt.cursor_pos]));
         let cursor_x = (message_width + input_width_before_cursor) as u16;
         if cursor_x < area.width {
             frame.set_cursor_position((area.x + cursor_x, area.y));
@@ -377,8 +419,13 @@ impl StatusBarRenderer {
 
         let mut spans = Vec::new();
 
-        // "Open: " prefix
-        let open_prompt = t!("file.open_prompt").to_string();
+        // "Open: " prefix (plugin file browsers show their own title)
+        let open_prompt =
+            if prompt.prompt_type == crate::view::prompt::PromptType::PluginFileBrowser {
+                prompt.message.clone()
+            } else {
+                t!("file.open_prompt").to_string()
+            };
         spans.push(Span::styled(open_prompt.clone(), base_style));
 
         // Calculate if we need to truncate
@@ -477,6 +524,9 @@ impl StatusBarRenderer {
         general_warning_count: usize,
         hover: StatusBarHover,
         remote_connection: Option<&str>,
+        profile: Option<&str>,
+        zoomed: bool,
+        customization: StatusBarCustomization,
     ) -> StatusBarLayout {
         // Initialize layout tracking
         let mut layout = StatusBarLayout::default();
@@ -509,55 +559,14 @@ impl StatusBarRenderer {
             _ => "",
         };
 
-        let cursor = *state.primary_cursor();
-
-        // Get line number and column efficiently using cached values
-        let (line, col) = {
-            // Find the start of the line containing the cursor
-            let cursor_iter = state.buffer.line_iterator(cursor.position, 80);
-            let line_start = cursor_iter.current_position();
-            let col = cursor.position.saturating_sub(line_start);
-
-            // Use cached line number from state
-            let line_num = state.primary_cursor_line_number.value();
-            (line_num, col)
-        };
-
-        // Count diagnostics by severity
-        let diagnostics = state.overlays.all();
-        let mut error_count = 0;
-        let mut warning_count = 0;
-        let mut info_count = 0;
-
-        // Use the lsp-diagnostic namespace to identify diagnostic overlays
-        let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
-        for overlay in diagnostics {
-            if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
-                // Check priority to determine severity
-                // Based on lsp_diagnostics.rs: Error=100, Warning=50, Info=30, Hint=10
-                match overlay.priority {
-                    100 => error_count += 1,
-                    50 => warning_count += 1,
-                    _ => info_count += 1,
-                }
-            }
-        }
+        let (line, col) = Self::cursor_line_col(state);
 
         // Build diagnostics summary if there are any
-        let diagnostics_summary = if error_count + warning_count + info_count > 0 {
-            let mut parts = Vec::new();
-            if error_count > 0 {
-                parts.push(format!("E:{}", error_count));
-            }
-            if warning_count > 0 {
-                parts.push(format!("W:{}", warning_count));
-            }
-            if info_count > 0 {
-                parts.push(format!("I:{}", info_count));
-            }
-            format!(" | {}", parts.join(" "))
-        } else {
+        let diagnostics = Self::diagnostics_text(state);
+        let diagnostics_summary = if diagnostics.is_empty() {
             String::new()
+        } else {
+            format!(" | {}", diagnostics)
         };
 
         // Build cursor count indicator (only show if multiple cursors)
@@ -593,15 +602,23 @@ impl StatusBarRenderer {
         let remote_prefix = remote_connection
             .map(|conn| format!("[SSH:{}] ", conn))
             .unwrap_or_default();
+        let profile_prefix = profile
+            .map(|name| format!("[{}] ", t!("status.profile", name = name)))
+            .unwrap_or_default();
+        let zoom_prefix = if zoomed {
+            format!("[{}] ", t!("status.zoomed"))
+        } else {
+            String::new()
+        };
         let base_status = if state.show_cursors {
             format!(
-                "{remote_prefix}{filename}{modified} | Ln {}, Col {}{diagnostics_summary}{cursor_count_indicator}",
+                "{remote_prefix}{profile_prefix}{zoom_prefix}{filename}{modified} | Ln {}, Col {}{diagnostics_summary}{cursor_count_indicator}",
                 line + 1,
                 col + 1
             )
         } else {
             // Virtual buffer - just show filename and modified indicator
-            format!("{remote_prefix}{filename}{modified}{diagnostics_summary}")
+            format!("{remote_prefix}{profile_prefix}{zoom_prefix}{filename}{modified}{diagnostics_summary}")
         };
 
         // Track where the message starts for click detection
@@ -666,6 +683,59 @@ impl StatusBarRenderer {
             + cmd_palette_width;
 
         // Only show command palette indicator if there's enough space (at least 15 chars for minimal display)
+        if !customization.template.is_empty() {
+            let show_cursors = state.show_cursors;
+            let cursor_count = state.cursors.count();
+            let resolve = |name: &str| -> String {
+                match name {
+                    "mode" => customization.mode.unwrap_or_default().to_string(),
+                    "file" => filename.to_string(),
+                    "modified" => modified.to_string(),
+                    "pos" if show_cursors => format!("Ln {}, Col {}", line + 1, col + 1),
+                    "pos" => String::new(),
+                    "diagnostics" => diagnostics.clone(),
+                    "cursors" if cursor_count > 1 => {
+                        t!("status.cursors", count = cursor_count).to_string()
+                    }
+                    "cursors" => String::new(),
+                    "chord" => chord_display.trim_start().to_string(),
+                    "messages" => message_parts.join(" | "),
+                    "remote" => remote_prefix.trim_end().to_string(),
+                    "profile" => profile_prefix.trim_end().to_string(),
+                    "zoom" => zoom_prefix.trim_end().to_string(),
+                    "line_ending" => line_ending_text.trim().to_string(),
+                    // Buffers are always decoded as UTF-8
+                    "encoding" => "UTF-8".to_string(),
+                    "language" => state.language.clone(),
+                    "lsp" => lsp_indicator.clone(),
+                    "warnings" => warning_badge.clone(),
+                    "update" => update_indicator.clone().unwrap_or_default(),
+                    "palette" => padded_cmd_palette.clone(),
+                    _ => customization
+                        .plugin_segments
+                        .get(name)
+                        .cloned()
+                        .unwrap_or_default(),
+                }
+            };
+            let placed = StatusBarTemplate::parse(customization.template).layout(
+                area.width,
+                customization.segment_rules,
+                resolve,
+            );
+            Self::render_template_segments(
+                frame,
+                area,
+                &placed,
+                customization.segment_rules,
+                theme,
+                warning_level,
+                hover,
+                &mut layout,
+            );
+            return layout;
+        }
+
         let spans = if available_width >= 15 {
             // Reserve space for right side indicators
             let left_max_width = if available_width > right_side_width + 1 {
@@ -940,6 +1010,169 @@ impl StatusBarRenderer {
         layout
     }
 
+    /// Draw a status bar laid out from a template and record clickable areas
+    #[allow(clippy::too_many_arguments)]
+    fn render_template_segments(
+        frame: &mut Frame,
+        area: Rect,
+        placed: &[crate::view::ui::status_bar_template::PlacedSegment],
+        segment_rules: &HashMap<String, StatusBarSegmentConfig>,
+        theme: &crate::view::theme::Theme,
+        warning_level: WarningLevel,
+        hover: StatusBarHover,
+        layout: &mut StatusBarLayout,
+    ) {
+        let base_style = Style::default()
+            .fg(theme.status_bar_fg)
+            .bg(theme.status_bar_bg);
+        let hover_style = Style::default()
+            .fg(theme.menu_hover_fg)
+            .bg(theme.menu_hover_bg)
+            .add_modifier(Modifier::UNDERLINED);
+
+        let mut spans = Vec::new();
+        let mut col = 0;
+        for segment in placed {
+            if segment.start > col {
+                spans.push(Span::styled(
+                    " ".repeat((segment.start - col) as usize),
+                    base_style,
+                ));
+            }
+            col = segment.start + segment.width;
+
+            let Some(name) = segment.name.as_deref() else {
+                spans.push(Span::styled(segment.text.clone(), base_style));
+                continue;
+            };
+            let bounds = (area.y, area.x + segment.start, area.x + col);
+            let style = match name {
+                "line_ending" => {
+                    layout.line_ending_indicator = Some(bounds);
+                    if hover == StatusBarHover::LineEndingIndicator {
+                        hover_style
+                    } else {
+                        base_style
+                    }
+                }
+                "language" => {
+                    layout.language_indicator = Some(bounds);
+                    if hover == StatusBarHover::LanguageIndicator {
+                        hover_style
+                    } else {
+                        base_style
+                    }
+                }
+                "messages" => {
+                    layout.message_area = Some(bounds);
+                    base_style
+                }
+                "lsp" => {
+                    layout.lsp_indicator = Some(bounds);
+                    let is_hovering = hover == StatusBarHover::LspIndicator;
+                    let (fg, bg) = match warning_level {
+                        WarningLevel::Error if is_hovering => (
+                            theme.status_error_indicator_hover_fg,
+                            theme.status_error_indicator_hover_bg,
+                        ),
+                        WarningLevel::Error => (
+                            theme.status_error_indicator_fg,
+                            theme.status_error_indicator_bg,
+                        ),
+                        WarningLevel::Warning if is_hovering => (
+                            theme.status_warning_indicator_hover_fg,
+                            theme.status_warning_indicator_hover_bg,
+                        ),
+                        WarningLevel::Warning => (
+                            theme.status_warning_indicator_fg,
+                            theme.status_warning_indicator_bg,
+                        ),
+                        WarningLevel::None => (theme.status_bar_fg, theme.status_bar_bg),
+                    };
+                    Style::default().fg(fg).bg(bg)
+                }
+                "warnings" => {
+                    layout.warning_badge = Some(bounds);
+                    if hover == StatusBarHover::WarningBadge {
+                        Style::default()
+                            .fg(theme.status_warning_indicator_hover_fg)
+                            .bg(theme.status_warning_indicator_hover_bg)
+                    } else {
+                        Style::default()
+                            .fg(theme.status_warning_indicator_fg)
+                            .bg(theme.status_warning_indicator_bg)
+                    }
+                }
+                "update" => Style::default()
+                    .fg(theme.menu_highlight_fg)
+                    .bg(theme.menu_dropdown_bg),
+                "palette" => Style::default()
+                    .fg(theme.help_indicator_fg)
+                    .bg(theme.help_indicator_bg),
+                _ => base_style,
+            };
+            if let Some(command) = segment_rules.get(name).and_then(|r| r.command.as_ref()) {
+                layout.segment_commands.push((bounds, command.clone()));
+            }
+            spans.push(Span::styled(segment.text.clone(), style));
+        }
+        if area.width > col {
+            spans.push(Span::styled(
+                " ".repeat((area.width - col) as usize),
+                base_style,
+            ));
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), area);
+    }
+
+    /// Line and column (both 0-indexed) of the primary cursor
+    fn cursor_line_col(state: &mut EditorState) -> (usize, usize) {
+        let cursor = *state.primary_cursor();
+
+        // Find the start of the line containing the cursor
+        let cursor_iter = state.buffer.line_iterator(cursor.position, 80);
+        let line_start = cursor_iter.current_position();
+        let col = cursor.position.saturating_sub(line_start);
+
+        // Use cached line number from state
+        let line_num = state.primary_cursor_line_number.value();
+        (line_num, col)
+    }
+
+    /// Diagnostic counts by severity, e.g. "E:2 W:1" (empty when there are none)
+    fn diagnostics_text(state: &EditorState) -> String {
+        let mut error_count = 0;
+        let mut warning_count = 0;
+        let mut info_count = 0;
+
+        // Use the lsp-diagnostic namespace to identify diagnostic overlays
+        let diagnostic_ns = crate::services::lsp::diagnostics::lsp_diagnostic_namespace();
+        for overlay in state.overlays.all() {
+            if overlay.namespace.as_ref() == Some(&diagnostic_ns) {
+                // Check priority to determine severity
+                // Based on lsp_diagnostics.rs: Error=100, Warning=50, Info=30, Hint=10
+                match overlay.priority {
+                    100 => error_count += 1,
+                    50 => warning_count += 1,
+                    _ => info_count += 1,
+                }
+            }
+        }
+
+        let mut parts = Vec::new();
+        if error_count > 0 {
+            parts.push(format!("E:{}", error_count));
+        }
+        if warning_count > 0 {
+            parts.push(format!("W:{}", warning_count));
+        }
+        if info_count > 0 {
+            parts.push(format!("I:{}", info_count));
+        }
+        parts.join(" ")
+    }
+
     /// Render the search options bar (shown when search prompt is active)
     ///
     /// Displays checkboxes for search options with their keyboard shortcuts:
@@ -0,0 +1,297 @@
+//! Status bar layouts defined by `editor.status_bar_template`
+//!
+//! A template is literal text mixed with `{name}` segments, e.g.
+//! `"{file}{modified} {diagnostics} … {language} {pos}"`. Everything after
+//! `…` is right-aligned. Segments are resolved to text by the caller; empty
+//! segments disappear together with the whitespace that separated them.
+
+use std::collections::HashMap;
+
+use crate::config::StatusBarSegmentConfig;
+use crate::primitives::display_width::{char_width, str_width};
+
+/// Separator between the left-aligned and right-aligned halves of a template
+const RIGHT_ALIGN_MARKER: char = '…';
+
+/// Piece of a parsed template
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TemplatePart {
+    Text(String),
+    Segment(String),
+}
+
+/// A parsed status bar template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StatusBarTemplate {
+    left: Vec<TemplatePart>,
+    right: Vec<TemplatePart>,
+}
+
+/// A piece of text placed on the status bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlacedSegment {
+    /// Segment name, or `None` for literal text from the template
+    pub name: Option<String>,
+    /// Text to draw (possibly truncated)
+    pub text: String,
+    /// Column offset from the left edge of the status bar
+    pub start: u16,
+    /// Display width of `text`
+    pub width: u16,
+}
+
+impl StatusBarTemplate {
+    /// Parse a template; a `{` without a matching `}` is kept as text
+    pub fn parse(template: &str) -> Self {
+        let (left, right) = match template.split_once(RIGHT_ALIGN_MARKER) {
+            Some((left, right)) => (left, right),
+            None => (template, ""),
+        };
+        Self {
+            left: parse_parts(left),
+            right: parse_parts(right),
+        }
+    }
+
+    /// Names of the segments used by the template, in order
+    pub fn segment_names(&self) -> impl Iterator<Item = &str> {
+        self.left
+            .iter()
+            .chain(self.right.iter())
+            .filter_map(|part| match part {
+                TemplatePart::Segment(name) => Some(name.as_str()),
+                TemplatePart::Text(_) => None,
+            })
+    }
+
+    /// Lay the template out on a status bar `width` columns wide
+    ///
+    /// `resolve` returns a segment's text. Segments whose `min_width` rule is
+    /// wider than the bar are hidden. When both halves don't fit, the left half
+    /// is truncated with "..." (and the right half dropped if it alone is too
+    /// wide).
+    pub fn layout(
+        &self,
+        width: u16,
+        rules: &HashMap<String, StatusBarSegmentConfig>,
+        resolve: impl Fn(&str) -> String,
+    ) -> Vec<PlacedSegment> {
+        let resolve_visible = |name: &str| {
+            let hidden = rules
+                .get(name)
+                .and_then(|rule| rule.min_width)
+                .is_some_and(|min_width| width < min_width);
+            if hidden {
+                String::new()
+            } else {
+                resolve(name)
+            }
+        };
+        let left = resolve_parts(&self.left, &resolve_visible);
+        let mut right = resolve_parts(&self.right, &resolve_visible);
+
+        let available = width as usize;
+        let mut right_width: usize = right.iter().map(|(_, text)| str_width(text)).sum();
+        if right_width >= available {
+            right.clear();
+            right_width = 0;
+        }
+        let left_max = if right_width > 0 {
+            available - right_width - 1
+        } else {
+            available
+        };
+
+        let mut placed = Vec::new();
+        let mut col = 0;
+        let left_width: usize = left.iter().map(|(_, text)| str_width(text)).sum();
+        let truncate_at = (left_width > left_max).then(|| left_max.saturating_sub(3));
+        for (name, text) in left {
+            let mut text = text;
+            if let Some(limit) = truncate_at {
+                if col + str_width(&text) > limit {
+                    text = format!("{}...", take_width(&text, limit - col));
+                    place(&mut placed, name, text, &mut col);
+                    break;
+                }
+            }
+            place(&mut placed, name, text, &mut col);
+        }
+
+        let mut col = available - right_width;
+        for (name, text) in right {
+            place(&mut placed, name, text, &mut col);
+        }
+        placed
+    }
+}
+
+fn parse_parts(template: &str) -> Vec<TemplatePart> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        let Some(close) = rest[open..].find('}').map(|close| open + close) else {
+            break;
+        };
+        if open > 0 {
+            parts.push(TemplatePart::Text(rest[..open].to_string()));
+        }
+        parts.push(TemplatePart::Segment(
+            rest[open + 1..close].trim().to_string(),
+        ));
+        rest = &rest[close + 1..];
+    }
+    if !rest.is_empty() {
+        parts.push(TemplatePart::Text(rest.to_string()));
+    }
+    parts
+}
+
+/// Resolve segments to text, dropping empty ones along with the whitespace
+/// that separated them from their neighbours
+fn resolve_parts(
+    parts: &[TemplatePart],
+    resolve: &impl Fn(&str) -> String,
+) -> Vec<(Option<String>, String)> {
+    let mut resolved: Vec<(Option<String>, String)> = Vec::new();
+    let mut drop_separator = false;
+    for part in parts {
+        match part {
+            TemplatePart::Text(text) => {
+                if drop_separator && text.trim().is_empty() {
+                    drop_separator = false;
+                    continue;
+                }
+                drop_separator = false;
+                resolved.push((None, text.clone()));
+            }
+            TemplatePart::Segment(name) => {
+                let text = resolve(name);
+                if !text.is_empty() {
+                    resolved.push((Some(name.clone()), text));
+                    continue;
+                }
+                match resolved.last() {
+                    Some((None, text)) if text.trim().is_empty() => {
+                        resolved.pop();
+                    }
+                    // Nothing before it to drop, so drop the separator after it
+                    None => drop_separator = true,
+                    _ => {}
+                }
+            }
+        }
+    }
+    resolved
+}
+
+fn place(placed: &mut Vec<PlacedSegment>, name: Option<String>, text: String, col: &mut usize) {
+    let width = str_width(&text);
+    if width == 0 {
+        return;
+    }
+    placed.push(PlacedSegment {
+        name,
+        text,
+        start: *col as u16,
+        width: width as u16,
+    });
+    *col += width;
+}
+
+/// The longest prefix of `text` that is at most `max_width` columns wide
+fn take_width(text: &str, max_width: usize) -> String {
+    let mut width = 0;
+    text.chars()
+        .take_while(|ch| {
+            width += char_width(*ch);
+            width <= max_width
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(name: &str) -> String {
+        match name {
+            "file" => "main.rs",
+            "modified" => " [+]",
+            "pos" => "Ln 3, Col 7",
+            "language" => "rust",
+            _ => "",
+        }
+        .to_string()
+    }
+
+    fn texts(placed: &[PlacedSegment]) -> Vec<(u16, &str)> {
+        placed.iter().map(|p| (p.start, p.text.as_str())).collect()
+    }
+
+    #[test]
+    fn test_left_and_right_aligned_segments() {
+        let template = StatusBarTemplate::parse("{file}{modified} | {pos} … {language} ");
+        let placed = template.layout(40, &HashMap::new(), values);
+        assert_eq!(
+            texts(&placed),
+            vec![
+                (0, "main.rs"),
+                (7, " [+]"),
+                (11, " | "),
+                (14, "Ln 3, Col 7"),
+                (25, " "),
+                (34, " "),
+                (35, "rust"),
+                (39, " "),
+            ]
+        );
+        assert_eq!(placed[0].name.as_deref(), Some("file"));
+        assert_eq!(placed[2].name, None);
+    }
+
+    #[test]
+    fn test_empty_segments_take_their_separator() {
+        let template = StatusBarTemplate::parse("{mode} {file} {diagnostics} {pos}");
+        let placed = template.layout(40, &HashMap::new(), values);
+        assert_eq!(
+            texts(&placed),
+            vec![(0, "main.rs"), (7, " "), (8, "Ln 3, Col 7")]
+        );
+    }
+
+    #[test]
+    fn test_min_width_hides_segment() {
+        let template = StatusBarTemplate::parse("{file} {pos}");
+        let rules = HashMap::from([(
+            "pos".to_string(),
+            StatusBarSegmentConfig {
+                command: None,
+                min_width: Some(60),
+            },
+        )]);
+        assert_eq!(
+            texts(&template.layout(40, &rules, values)),
+            vec![(0, "main.rs")]
+        );
+        assert_eq!(template.layout(80, &rules, values).len(), 3);
+    }
+
+    #[test]
+    fn test_left_side_is_truncated_to_fit() {
+        let template = StatusBarTemplate::parse("{file} {pos}…{language}");
+        let placed = template.layout(16, &HashMap::new(), values);
+        assert_eq!(
+            texts(&placed),
+            vec![(0, "main.rs"), (7, " "), (8, "..."), (12, "rust")]
+        );
+    }
+
+    #[test]
+    fn test_unclosed_brace_is_text() {
+        let template = StatusBarTemplate::parse("{file} {oops");
+        assert_eq!(template.segment_names().collect::<Vec<_>>(), vec!["file"]);
+        let placed = template.layout(40, &HashMap::new(), values);
+        assert_eq!(placed.last().unwrap().text, " {oops");
+    }
+}
@@ -157,6 +157,51 @@ pub fn scroll_to_show_tab(
     result
 }
 
+/// File name of a file-backed buffer
+fn buffer_file_name(buffers: &HashMap<BufferId, EditorState>, id: BufferId) -> Option<&str> {
+    buffers
+        .get(&id)
+        .and_then(|state| state.buffer.file_path())
+        .and_then(|p| p.file_name())
+        .and_then(|n| n.to_str())
+}
+
+/// Name shown on a tab.
+///
+/// File-backed buffers show their file name, or their workspace-relative path
+/// when another tab in the same split has the same file name. Other buffers
+/// (and `use_display_name` ones such as terminals) show their display name.
+fn tab_name<'a>(
+    id: BufferId,
+    split_buffers: &[BufferId],
+    buffers: &'a HashMap<BufferId, EditorState>,
+    buffer_metadata: &'a HashMap<BufferId, BufferMetadata>,
+    use_display_name: bool,
+) -> &'a str {
+    let meta = buffer_metadata.get(&id);
+    let display_name = meta.map(|m| m.display_name.as_str());
+    if use_display_name {
+        return display_name.unwrap_or("[No Name]");
+    }
+
+    match buffer_file_name(buffers, id) {
+        Some(file_name) => {
+            let ambiguous = split_buffers.iter().any(|&other| {
+                other != id
+                    && !buffer_metadata
+                        .get(&other)
+                        .is_some_and(|m| m.hidden_from_tabs)
+                    && buffer_file_name(buffers, other) == Some(file_name)
+            });
+            match display_name {
+                Some(path) if ambiguous => path,
+                _ => file_name,
+            }
+        }
+        None => display_name.unwrap_or("[No Name]"),
+    }
+}
+
 /// Calculate tab widths for scroll offset calculations.
 /// Returns (tab_widths, rendered_buffer_ids) where tab_widths includes separators.
 /// This uses the same logic as render_for_split to ensure consistency.
@@ -192,19 +237,13 @@ pub fn calculate_tab_widths(
             .unwrap_or(false);
 
         // Use same name resolution logic as render_for_split
-        let name = if is_composite_buffer {
-            meta.map(|m| m.display_name.as_str())
-        } else if is_terminal {
-            meta.map(|m| m.display_name.as_str())
-        } else {
-            buffers
-                .get(id)
-                .and_then(|state| state.buffer.file_path())
-                .and_then(|p| p.file_name())
-                .and_then(|n| n.to_str())
-                .or_else(|| meta.map(|m| m.display_name.as_str()))
-        }
-        .unwrap_or("[No Name]");
+        let name = tab_name(
+            *id,
+            split_buffers,
+            buffers,
+            buffer_metadata,
+            is_composite_buffer || is_terminal,
+        );
 
         // Calculate modified indicator
         let modified = if is_composite_buffer {
@@ -307,19 +346,13 @@ impl TabsRenderer {
 
             // For composite buffers, use display_name from metadata
             // For regular buffers, try file_path first, then display_name
-            let name = if is_composite_buffer {
-                meta.map(|m| m.display_name.as_str())
-            } else if is_terminal {
-                meta.map(|m| m.display_name.as_str())
-            } else {
-                buffers
-                    .get(id)
-                    .and_then(|state| state.buffer.file_path())
-                    .and_then(|p| p.file_name())
-                    .and_then(|n| n.to_str())
-                    .or_else(|| meta.map(|m| m.display_name.as_str()))
-            }
-            .unwrap_or("[No Name]");
+            let name = tab_name(
+                *id,
+                split_buffers,
+                buffers,
+                buffer_metadata,
+                is_composite_buffer || is_terminal,
+            );
 
             // For composite buffers, never show as modified (they're read-only views)
             let modified = if is_composite_buffer {
@@ -32,6 +32,10 @@ pub enum VirtualTextPosition {
     BeforeChar,
     /// Render after the character (e.g., type hints: `x: i32`)
     AfterChar,
+    /// Render before the character as if it had been typed there: no
+    /// padding, and a cursor on the character is drawn at its start
+    /// (inline suggestion ghost text)
+    Ghost,
 
     // ─── Line positions (full lines) ───
     /// Render as a full line ABOVE the line containing this position
@@ -50,9 +54,9 @@ impl VirtualTextPosition {
         matches!(self, Self::LineAbove | Self::LineBelow)
     }
 
-    /// Returns true if this is an inline position (BeforeChar/AfterChar)
+    /// Returns true if this is an inline position (BeforeChar/AfterChar/Ghost)
     pub fn is_inline(&self) -> bool {
-        matches!(self, Self::BeforeChar | Self::AfterChar)
+        matches!(self, Self::BeforeChar | Self::AfterChar | Self::Ghost)
     }
 }
 
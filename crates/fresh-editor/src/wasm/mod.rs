@@ -13,11 +13,18 @@
 //! - Browser event handling (keyboard/mouse via Ratzilla or similar)
 //! - Virtual filesystem (in-memory or IndexedDB-backed)
 //! - Rendering to browser terminal (via Ratzilla)
+//! - Fetching syntax grammars per language on demand ([`syntax_loader`])
 //!
 //! # Usage
 //!
 //! Build with: `cargo build --no-default-features --features wasm`
+//!
+//! Add `--features wasm-bundled-syntaxes` to embed syntect's bundled
+//! grammars instead of fetching them, at the cost of a larger bundle.
+
+pub mod syntax_loader;
 
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 // Re-export core types for WASM consumers
@@ -26,6 +33,7 @@ pub use crate::model::cursor::{Cursor, Cursors};
 pub use crate::model::event::{Event, EventLog};
 pub use crate::model::filesystem::{FileSystem, NoopFileSystem, StdFileSystem};
 pub use crate::model::piece_tree::{PieceTree, Position};
+pub use syntax_loader::{HighlightStatus, LazySyntaxLoader};
 
 /// Default large file threshold for WASM (100MB)
 const LARGE_FILE_THRESHOLD: usize = 100 * 1024 * 1024;
@@ -36,6 +44,8 @@ const LARGE_FILE_THRESHOLD: usize = 100 * 1024 * 1024;
 /// that uses NoopFileSystem since browsers don't have direct filesystem access.
 pub struct WasmEditor {
     buffer: Buffer,
+    /// Path the buffer is shown under, used to pick its grammar
+    path: Option<PathBuf>,
 }
 
 impl WasmEditor {
@@ -44,6 +54,7 @@ impl WasmEditor {
         let fs: Arc<dyn FileSystem + Send + Sync> = Arc::new(NoopFileSystem);
         Self {
             buffer: Buffer::empty(fs),
+            path: None,
         }
     }
 
@@ -52,6 +63,7 @@ impl WasmEditor {
         let fs: Arc<dyn FileSystem + Send + Sync> = Arc::new(NoopFileSystem);
         Self {
             buffer: Buffer::from_str(content, LARGE_FILE_THRESHOLD, fs),
+            path: None,
         }
     }
 
@@ -89,6 +101,24 @@ impl WasmEditor {
         self.buffer.line_count()
     }
 
+    /// Set the path the buffer is shown under
+    pub fn set_path(&mut self, path: &str) {
+        self.path = Some(PathBuf::from(path));
+    }
+
+    /// Get the path the buffer is shown under
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    /// Highlighting availability of this buffer, for the loading indicator
+    pub fn highlight_status(&self, syntaxes: &LazySyntaxLoader) -> HighlightStatus {
+        match &self.path {
+            Some(path) => syntaxes.status_for_path(path),
+            None => HighlightStatus::PlainText,
+        }
+    }
+
     /// Get a reference to the underlying buffer
     pub fn buffer(&self) -> &Buffer {
         &self.buffer
@@ -129,6 +159,21 @@ mod tests {
         assert_eq!(editor.line_count(), Some(1));
     }
 
+    #[test]
+    fn test_wasm_editor_highlight_status() {
+        let mut syntaxes = LazySyntaxLoader::new("/syntaxes");
+        syntaxes.register_manifest(r#"{"foo": ["foo"]}"#).unwrap();
+
+        let mut editor = WasmEditor::new();
+        assert_eq!(
+            editor.highlight_status(&syntaxes),
+            HighlightStatus::PlainText
+        );
+        editor.set_path("src/main.foo");
+        syntaxes.request_for_path(editor.path().unwrap());
+        assert_eq!(editor.highlight_status(&syntaxes), HighlightStatus::Loading);
+    }
+
     #[test]
     fn test_wasm_editor_delete() {
         let mut editor = WasmEditor::with_content("Hello, World!");
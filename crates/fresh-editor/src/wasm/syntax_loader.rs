@@ -0,0 +1,247 @@
+//! Lazy per-language syntax loading for the browser build
+//!
+//! The core WASM build leaves syntect's bundled syntax dump out (it is only
+//! embedded with the `wasm-bundled-syntaxes` feature), so the editor boots
+//! with plain text and the small embedded grammars. Other grammars are
+//! fetched by the host page the first time a buffer needs them:
+//!
+//! 1. The host registers a manifest of languages and their file extensions.
+//! 2. [`LazySyntaxLoader::request_for_path`] marks the buffer's language as
+//!    loading and returns the URL of its `.sublime-syntax` file to `fetch`.
+//! 3. The host passes the response text to [`LazySyntaxLoader::provide`], or
+//!    calls [`LazySyntaxLoader::fail`], and the grammar registry is rebuilt.
+//!
+//! [`LazySyntaxLoader::status_for_path`] drives the per-buffer loading
+//! indicator. Highlighters hold the syntax set they were created with, so a
+//! buffer recreates its highlighter once its status turns `Ready`.
+
+use crate::primitives::grammar::GrammarRegistry;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use syntect::parsing::SyntaxDefinition;
+
+/// Highlighting availability of a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightStatus {
+    /// No grammar is known for the file, so it is shown as plain text
+    PlainText,
+    /// A grammar exists but has not been requested yet
+    NotLoaded,
+    /// The grammar is being fetched
+    Loading,
+    /// The grammar is loaded and the buffer can be highlighted
+    Ready,
+    /// Fetching or compiling the grammar failed
+    Failed,
+}
+
+impl HighlightStatus {
+    /// Stable identifier for the host page's indicator
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PlainText => "plain_text",
+            Self::NotLoaded => "not_loaded",
+            Self::Loading => "loading",
+            Self::Ready => "ready",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// Grammar registry that grows as the host supplies grammars
+pub struct LazySyntaxLoader {
+    /// URL prefix the per-language grammar files are served from
+    base_url: String,
+    registry: Arc<GrammarRegistry>,
+    /// Language -> file extensions, from the host's manifest
+    extensions: HashMap<String, Vec<String>>,
+    /// Grammars supplied so far, by language
+    loaded: Vec<(String, SyntaxDefinition)>,
+    states: HashMap<String, HighlightStatus>,
+}
+
+impl LazySyntaxLoader {
+    /// Create a loader fetching grammars from `{base_url}/{language}.sublime-syntax`
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            registry: Arc::new(GrammarRegistry::default()),
+            extensions: HashMap::new(),
+            loaded: Vec::new(),
+            states: HashMap::new(),
+        }
+    }
+
+    /// Register languages from a JSON manifest like `{"rust": ["rs"], "python": ["py", "pyi"]}`
+    pub fn register_manifest(&mut self, json: &str) -> Result<(), String> {
+        let manifest: HashMap<String, Vec<String>> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid syntax manifest: {}", e))?;
+        self.extensions.extend(manifest);
+        Ok(())
+    }
+
+    /// The registry with every grammar loaded so far
+    pub fn registry(&self) -> &Arc<GrammarRegistry> {
+        &self.registry
+    }
+
+    /// The manifest language a file belongs to, by extension
+    pub fn language_for_path(&self, path: &Path) -> Option<&str> {
+        let ext = path.extension()?.to_str()?;
+        self.extensions
+            .iter()
+            .filter(|(_, exts)| exts.iter().any(|e| e.eq_ignore_ascii_case(ext)))
+            .map(|(language, _)| language.as_str())
+            .min()
+    }
+
+    /// Highlighting availability for a buffer showing `path`
+    pub fn status_for_path(&self, path: &Path) -> HighlightStatus {
+        if self.registry.find_syntax_for_file(path).is_some() {
+            return HighlightStatus::Ready;
+        }
+        match self.language_for_path(path) {
+            Some(language) => self
+                .states
+                .get(language)
+                .copied()
+                .unwrap_or(HighlightStatus::NotLoaded),
+            None => HighlightStatus::PlainText,
+        }
+    }
+
+    /// Start loading the grammar for `path`, returning the URL to fetch
+    ///
+    /// Returns None when there is nothing to fetch: the grammar is loaded or
+    /// already loading, or the file has no known language. Failed languages
+    /// are requested again.
+    pub fn request_for_path(&mut self, path: &Path) -> Option<String> {
+        if !matches!(
+            self.status_for_path(path),
+            HighlightStatus::NotLoaded | HighlightStatus::Failed
+        ) {
+            return None;
+        }
+        let language = self.language_for_path(path)?.to_string();
+        let url = format!("{}/{}.sublime-syntax", self.base_url, language);
+        self.states.insert(language, HighlightStatus::Loading);
+        Some(url)
+    }
+
+    /// Compile a fetched `.sublime-syntax` grammar and make it available
+    pub fn provide(&mut self, language: &str, source: &str) -> Result<(), String> {
+        let syntax = match SyntaxDefinition::load_from_str(source, true, Some(language)) {
+            Ok(syntax) => syntax,
+            Err(e) => {
+                self.fail(language);
+                return Err(format!("Failed to parse grammar for {}: {}", language, e));
+            }
+        };
+        self.loaded.retain(|(loaded, _)| loaded != language);
+        self.loaded.push((language.to_string(), syntax));
+        self.rebuild_registry();
+        self.states
+            .insert(language.to_string(), HighlightStatus::Ready);
+        Ok(())
+    }
+
+    /// Record that fetching the grammar for `language` failed
+    pub fn fail(&mut self, language: &str) {
+        self.states
+            .insert(language.to_string(), HighlightStatus::Failed);
+    }
+
+    fn rebuild_registry(&mut self) {
+        let mut builder = GrammarRegistry::bundled_syntaxes_builder();
+        GrammarRegistry::add_embedded_grammars(&mut builder);
+
+        let mut user_extensions = HashMap::new();
+        for (language, syntax) in &self.loaded {
+            for ext in self.extensions.get(language).into_iter().flatten() {
+                user_extensions.insert(ext.clone(), syntax.scope.to_string());
+            }
+            builder.add(syntax.clone());
+        }
+
+        self.registry = Arc::new(GrammarRegistry::new(
+            builder.build(),
+            user_extensions,
+            GrammarRegistry::build_filename_scopes(),
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FOO_GRAMMAR: &str = r#"%YAML 1.2
+---
+name: Foo
+scope: source.foo
+contexts:
+  main:
+    - match: '\bfoo\b'
+      scope: keyword.control.foo
+"#;
+
+    fn loader() -> LazySyntaxLoader {
+        let mut loader = LazySyntaxLoader::new("https://example.com/syntaxes/");
+        loader
+            .register_manifest(r#"{"foo": ["foo", "fooz"]}"#)
+            .unwrap();
+        loader
+    }
+
+    #[test]
+    fn test_request_then_provide() {
+        let mut loader = loader();
+        let path = Path::new("/project/main.fooz");
+        assert_eq!(loader.status_for_path(path), HighlightStatus::NotLoaded);
+
+        assert_eq!(
+            loader.request_for_path(path).as_deref(),
+            Some("https://example.com/syntaxes/foo.sublime-syntax")
+        );
+        assert_eq!(loader.status_for_path(path), HighlightStatus::Loading);
+        // A second buffer of the same language doesn't fetch again
+        assert_eq!(loader.request_for_path(Path::new("other.foo")), None);
+
+        loader.provide("foo", FOO_GRAMMAR).unwrap();
+        assert_eq!(loader.status_for_path(path), HighlightStatus::Ready);
+        assert_eq!(
+            loader
+                .registry()
+                .find_syntax_for_file(path)
+                .map(|s| s.name.as_str()),
+            Some("Foo")
+        );
+        assert_eq!(loader.request_for_path(path), None);
+    }
+
+    #[test]
+    fn test_failed_grammar_is_retried() {
+        let mut loader = loader();
+        let path = Path::new("main.foo");
+        loader.request_for_path(path);
+        assert!(loader.provide("foo", "not: [a grammar").is_err());
+        assert_eq!(loader.status_for_path(path), HighlightStatus::Failed);
+        assert!(loader.request_for_path(path).is_some());
+    }
+
+    #[test]
+    fn test_unknown_and_embedded_files() {
+        let mut loader = loader();
+        assert_eq!(
+            loader.status_for_path(Path::new("notes.unknownext")),
+            HighlightStatus::PlainText
+        );
+        assert_eq!(loader.request_for_path(Path::new("notes.unknownext")), None);
+        // Embedded grammars ship with the core build
+        assert_eq!(
+            loader.status_for_path(Path::new("Cargo.toml")),
+            HighlightStatus::Ready
+        );
+    }
+}
@@ -700,6 +700,15 @@ impl EditorTestHarness {
         Ok(())
     }
 
+    /// Run a command by name from the command palette
+    pub fn run_command(&mut self, name: &str) -> anyhow::Result<()> {
+        self.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+        self.wait_until(|h| h.screen_to_string().contains(">command"))?;
+        self.type_text(name)?;
+        self.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+        Ok(())
+    }
+
     /// Simulate a mouse event
     pub fn send_mouse(&mut self, mouse_event: MouseEvent) -> anyhow::Result<()> {
         // Delegate to the editor's handle_mouse method (just like main.rs does)
@@ -729,6 +738,24 @@ impl EditorTestHarness {
         Ok(())
     }
 
+    /// Simulate a right-click at specific coordinates
+    pub fn mouse_right_click(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
+        self.send_mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Right),
+            column: col,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })?;
+        self.send_mouse(MouseEvent {
+            kind: MouseEventKind::Up(MouseButton::Right),
+            column: col,
+            row,
+            modifiers: KeyModifiers::empty(),
+        })?;
+        self.render()?;
+        Ok(())
+    }
+
     /// Simulate a shift+click at specific coordinates (for extending selection)
     pub fn mouse_shift_click(&mut self, col: u16, row: u16) -> anyhow::Result<()> {
         let mouse_event = MouseEvent {
@@ -1548,9 +1575,13 @@ impl EditorTestHarness {
     /// Process pending async messages (including file polling) and render
     /// Useful for testing async features like git grep, file explorer, auto-revert, etc.
     pub fn process_async_and_render(&mut self) -> anyhow::Result<()> {
+        self.editor.poll_save_in_progress();
         let _ = self.editor.process_async_messages();
         // Check debounced completion trigger timer (quick suggestions)
         self.editor.check_completion_trigger_timer();
+        // Check code actions on save and plugin formatter timeouts
+        self.editor.check_save_code_actions_timer();
+        self.editor.check_plugin_format_timer();
         self.render()?;
         Ok(())
     }
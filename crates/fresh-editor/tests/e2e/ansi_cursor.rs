@@ -5,8 +5,19 @@
 //! actual cursor position in the content area.
 
 use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
 use tempfile::TempDir;
 
+/// Open a file containing `content` in a new harness
+fn open_with_content(temp_dir: &TempDir, content: &str) -> EditorTestHarness {
+    let path = temp_dir.path().join("build.log");
+    std::fs::write(&path, content).unwrap();
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+    harness
+}
+
 /// Compare cursor position between ANSI and plain text files.
 /// Both should have the cursor on the first character of content (row 2, after gutter).
 ///
@@ -61,3 +72,90 @@ fn test_cursor_ansi_vs_plain_comparison() {
         plain_cursor_pos, ansi_cursor_pos
     );
 }
+
+/// Left and right move over the text as shown, stepping past escape sequences
+#[test]
+fn test_cursor_moves_over_visible_text() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "\x1b[31mred\x1b[0m plain\n";
+    let mut harness = open_with_content(&temp_dir, content);
+    harness.assert_screen_contains("red plain");
+
+    // From before the first sequence to after the "r" it colors
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), content.find("ed").unwrap());
+
+    // Past "d" and the reset sequence to the space
+    harness
+        .send_key_repeat(KeyCode::Right, KeyModifiers::NONE, 2)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), content.find(' ').unwrap());
+
+    // Back over the reset sequence onto "d"
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    assert_eq!(harness.cursor_position(), content.find('d').unwrap());
+
+    // Selecting covers the sequences between the visible characters
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), content.find(' ').unwrap());
+}
+
+/// "Toggle ANSI Colors" shows the escape sequences raw, and back
+#[test]
+fn test_toggle_ansi_colors_shows_raw_escapes() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = open_with_content(&temp_dir, "\x1b[31mred\x1b[0m plain\n");
+    harness.assert_screen_contains("red plain");
+    harness.assert_screen_not_contains("<1B>");
+
+    run_command(&mut harness, "Toggle ANSI Colors");
+    harness.assert_screen_contains("<1B>[31mred<1B>[0m plain");
+
+    // Escapes are ordinary text now
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 1);
+
+    run_command(&mut harness, "Toggle ANSI Colors");
+    harness.assert_screen_contains("red plain");
+}
+
+/// Search matches the text as shown, even where escape sequences split it
+#[test]
+fn test_search_matches_across_escape_sequences() {
+    let temp_dir = TempDir::new().unwrap();
+    let content = "ok\n\x1b[1;31merror\x1b[0m: build failed\n";
+    let mut harness = open_with_content(&temp_dir, content);
+
+    harness
+        .send_key(KeyCode::Char('f'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("error: build").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.process_async_and_render().unwrap();
+
+    assert_eq!(harness.cursor_position(), content.find("error").unwrap());
+}
+
+/// Run a command from the command palette
+fn run_command(harness: &mut EditorTestHarness, name: &str) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains(">command"))
+        .unwrap();
+    harness.type_text(name).unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+}
@@ -0,0 +1,58 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+fn open_cheat_sheet(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::F(1), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// The cheat sheet lists bindings of the active context grouped by category
+#[test]
+fn test_cheat_sheet_shows_grouped_bindings() {
+    let mut harness = EditorTestHarness::new(100, 40).unwrap();
+
+    open_cheat_sheet(&mut harness);
+
+    assert!(harness.editor().is_cheat_sheet_active());
+    harness.assert_screen_contains("Keybinding Cheat Sheet (normal)");
+    harness.assert_screen_contains("Movement");
+    harness.assert_screen_contains("Move cursor up");
+}
+
+/// Typing filters the list and keys are rendered from the keymap
+#[test]
+fn test_cheat_sheet_search_filters_entries() {
+    let mut harness = EditorTestHarness::new(100, 40).unwrap();
+
+    open_cheat_sheet(&mut harness);
+    harness.type_text("save file").unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("File");
+    harness.assert_screen_contains("Ctrl+S");
+    harness.assert_screen_not_contains("Move cursor up");
+
+    // Typed text goes to the search field, not the buffer
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+}
+
+/// Esc clears the search first, then closes; the toggle key closes directly
+#[test]
+fn test_cheat_sheet_close() {
+    let mut harness = EditorTestHarness::new(100, 40).unwrap();
+
+    open_cheat_sheet(&mut harness);
+    harness.type_text("undo").unwrap();
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert!(harness.editor().is_cheat_sheet_active());
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert!(!harness.editor().is_cheat_sheet_active());
+
+    open_cheat_sheet(&mut harness);
+    assert!(harness.editor().is_cheat_sheet_active());
+    open_cheat_sheet(&mut harness);
+    assert!(!harness.editor().is_cheat_sheet_active());
+    harness.assert_screen_not_contains("Keybinding Cheat Sheet");
+}
@@ -0,0 +1,168 @@
+//! E2E tests for right-click context menus
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+/// Screen position (col, row) of the first occurrence of `text`
+fn find_on_screen(harness: &EditorTestHarness, text: &str) -> (u16, u16) {
+    let screen = harness.screen_to_string();
+    for (row, line) in screen.lines().enumerate() {
+        if let Some(byte_idx) = line.find(text) {
+            let col = line[..byte_idx].chars().count();
+            return (col as u16, row as u16);
+        }
+    }
+    panic!("'{}' not found on screen:\n{}", text, screen);
+}
+
+/// Right-click in the text area, pick "Select All" with the keyboard
+#[test]
+fn test_editor_context_menu_keyboard_navigation() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("hello world").unwrap();
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "hello world");
+    harness.mouse_right_click(col + 2, row).unwrap();
+
+    assert!(harness.editor().is_context_menu_open());
+    harness.assert_screen_contains("Paste");
+    harness.assert_screen_contains("Select All");
+    // Nothing is selected, so Cut and Copy are left out
+    harness.assert_screen_not_contains("Cut");
+
+    // Paste is highlighted first; Down moves to Select All
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(!harness.editor().is_context_menu_open());
+    assert_eq!(harness.get_selected_text(), "hello world");
+}
+
+/// Right-clicking inside the selection keeps it, and Cut acts on it
+#[test]
+fn test_editor_context_menu_cut_selection() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("hello world").unwrap();
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "hello world");
+    harness.mouse_right_click(col + 2, row).unwrap();
+    assert!(harness.has_selection());
+    harness.assert_screen_contains("Copy");
+
+    let (cut_col, cut_row) = find_on_screen(&harness, "Cut");
+    harness.mouse_click(cut_col, cut_row).unwrap();
+
+    assert!(!harness.editor().is_context_menu_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+}
+
+/// Right-clicking outside the selection moves the cursor there first
+#[test]
+fn test_editor_context_menu_moves_cursor() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("hello world").unwrap();
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "hello world");
+    harness.mouse_right_click(col + 5, row).unwrap();
+    assert_eq!(harness.cursor_position(), 5);
+}
+
+/// Esc and a click elsewhere close the menu without running anything
+#[test]
+fn test_context_menu_dismiss() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "hello");
+    harness.mouse_right_click(col, row).unwrap();
+    assert!(harness.editor().is_context_menu_open());
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert!(!harness.editor().is_context_menu_open());
+    harness.assert_screen_not_contains("Select All");
+
+    harness.mouse_right_click(col, row).unwrap();
+    assert!(harness.editor().is_context_menu_open());
+    harness.mouse_click(90, 25).unwrap();
+    assert!(!harness.editor().is_context_menu_open());
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+}
+
+/// Shift+F10 opens the menu at the cursor
+#[test]
+fn test_context_menu_keyboard_shortcut() {
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::F(10), KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+
+    assert!(harness.editor().is_context_menu_open());
+    harness.assert_screen_contains("Select All");
+}
+
+/// Right-clicking a tab shows the tab operations
+#[test]
+fn test_tab_context_menu_close_others() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 30).unwrap();
+    for name in ["first.txt", "second.txt"] {
+        let path = temp_dir.path().join(name);
+        std::fs::write(&path, name).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.render().unwrap();
+
+    let (col, row) = find_on_screen(&harness, "second.txt");
+    harness.mouse_right_click(col, row).unwrap();
+    harness.assert_screen_contains("Close Others");
+    harness.assert_screen_contains("Copy File Name");
+
+    let (item_col, item_row) = find_on_screen(&harness, "Close Others");
+    harness.mouse_click(item_col, item_row).unwrap();
+
+    harness.assert_screen_not_contains("first.txt");
+    harness.assert_screen_contains("second.txt");
+}
+
+/// Right-clicking a file explorer entry selects it and shows the file operations
+#[test]
+fn test_file_explorer_context_menu() {
+    let temp_dir = TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    std::fs::write(project.join("notes.txt"), "notes").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 30, Default::default(), project)
+            .unwrap();
+    harness.editor_mut().toggle_file_explorer();
+    harness.wait_for_file_explorer().unwrap();
+    harness.wait_for_screen_contains("notes.txt").unwrap();
+
+    let (col, row) = find_on_screen(&harness, "notes.txt");
+    harness.mouse_right_click(col, row).unwrap();
+
+    harness.assert_screen_contains("New Folder");
+    harness.assert_screen_contains("Rename");
+    harness.assert_screen_contains("Delete");
+
+    // Open runs on the entry that was right-clicked
+    let (open_col, open_row) = find_on_screen(&harness, "Open");
+    harness.mouse_click(open_col, open_row).unwrap();
+    harness.wait_for_screen_contains("notes").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "notes");
+}
@@ -0,0 +1,95 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Copy as Markdown wraps the selection in a fence tagged with the buffer language
+#[test]
+fn test_copy_as_markdown_uses_language_tag() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("example.rs");
+    std::fs::write(&file_path, "fn main() {\n    println!(\"hi\");\n}\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Select all lines
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("copy as markdown").unwrap();
+
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        "```rust\nfn main() {\n    println!(\"hi\");\n}\n```\n"
+    );
+}
+
+/// Copy with reference prefixes the current line with `path:line` when nothing is selected
+#[test]
+fn test_copy_with_reference_without_selection() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("notes.txt");
+    std::fs::write(&file_path, "first\nsecond\nthird\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Move to the second line
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("copy with file reference").unwrap();
+
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        "notes.txt:2\nsecond"
+    );
+}
+
+/// Copy with reference reports the full line range of a multi-line selection
+#[test]
+fn test_copy_with_reference_line_range() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    let file_path = project_dir.join("notes.txt");
+    std::fs::write(&file_path, "first\nsecond\nthird\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    // Select from the start of line 1 to the end of line 2
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::SHIFT).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("copy with file reference").unwrap();
+
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        "notes.txt:1-2\nfirst\nsecond"
+    );
+}
+
+/// Copy path commands copy the workspace-relative path, absolute path and file name
+#[test]
+fn test_copy_buffer_paths() {
+    let mut harness = EditorTestHarness::with_temp_project(80, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::create_dir_all(project_dir.join("src")).unwrap();
+    let file_path = project_dir.join("src").join("lib.rs");
+    std::fs::write(&file_path, "pub fn f() {}\n").unwrap();
+    harness.open_file(&file_path).unwrap();
+
+    harness.run_command("copy relative path").unwrap();
+    assert_eq!(
+        harness.editor_mut().clipboard_content_for_test(),
+        std::path::Path::new("src").join("lib.rs").to_string_lossy()
+    );
+
+    harness.run_command("copy file name").unwrap();
+    assert_eq!(harness.editor_mut().clipboard_content_for_test(), "lib.rs");
+
+    harness.run_command("copy absolute path").unwrap();
+    let copied = harness.editor_mut().clipboard_content_for_test();
+    assert!(std::path::Path::new(&copied).is_absolute());
+    assert!(copied.ends_with("lib.rs"));
+}
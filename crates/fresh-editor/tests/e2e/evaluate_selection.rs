@@ -0,0 +1,50 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Evaluate Selection shows the result in a popup and leaves the text alone
+#[test]
+fn test_evaluate_selection_shows_result() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("2024-01-15 + 30 days").unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.run_command("Evaluate Selection").unwrap();
+    harness.assert_screen_contains("2024-01-15 + 30 days = 2024-02-14");
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "2024-01-15 + 30 days"
+    );
+}
+
+/// Evaluate and Replace Selection replaces the selection with its result as
+/// one undo step, and reports expressions it can't evaluate
+#[test]
+fn test_evaluate_and_replace_selection() {
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.type_text("(1 + 2) * 14 =").unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness
+        .run_command("Evaluate and Replace Selection")
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "42");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "(1 + 2) * 14 =");
+
+    harness.type_text(" 1 /").unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .run_command("Evaluate and Replace Selection")
+        .unwrap();
+    harness.assert_screen_contains("Cannot evaluate");
+}
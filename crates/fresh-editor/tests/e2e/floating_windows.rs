@@ -0,0 +1,109 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::model::event::{Event, PopupContentData, PopupData, PopupPositionData};
+
+/// Show a transient documentation-style popup
+fn show_docs_popup(harness: &mut EditorTestHarness) {
+    let state = harness.editor_mut().active_state_mut();
+    state.apply(&Event::ShowPopup {
+        popup: PopupData {
+            title: Some("Docs".to_string()),
+            description: None,
+            transient: true,
+            content: PopupContentData::Text(vec![
+                "fn frobnicate(x: i32)".to_string(),
+                "Frobnicates the input".to_string(),
+            ]),
+            position: PopupPositionData::Fixed { x: 10, y: 5 },
+            width: 30,
+            max_height: 6,
+            bordered: true,
+        },
+    });
+    harness.render().unwrap();
+}
+
+fn pin_popup(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::ALT)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A pinned popup survives typing and cursor movement
+#[test]
+fn test_pinned_popup_persists_across_edits() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    show_docs_popup(&mut harness);
+    pin_popup(&mut harness);
+
+    assert!(!harness.editor().active_state().popups.is_visible());
+    harness.assert_screen_contains("Frobnicates the input");
+
+    harness.type_text("hello").unwrap();
+    harness.send_key(KeyCode::Left, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello");
+    harness.assert_screen_contains("Frobnicates the input");
+    // The Windows menu appears while a floating window is open
+    harness.assert_screen_contains("Windows");
+}
+
+/// Focused windows move with Shift+arrows and close with Ctrl+W
+#[test]
+fn test_floating_window_keyboard_move_and_close() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    show_docs_popup(&mut harness);
+    pin_popup(&mut harness);
+
+    let row_of = |harness: &EditorTestHarness| {
+        harness
+            .screen_to_string()
+            .lines()
+            .position(|l| l.contains("Frobnicates the input"))
+            .unwrap()
+    };
+    let before = row_of(&harness);
+
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(row_of(&harness), before + 2);
+
+    // Moving the window does not touch the buffer
+    assert_eq!(harness.get_buffer_content().unwrap(), "");
+
+    harness
+        .send_key(KeyCode::Char('w'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("Frobnicates the input");
+    harness.assert_screen_not_contains("Windows");
+}
+
+/// Typing in a focused window hands the key back to the editor
+#[test]
+fn test_floating_window_unhandled_key_returns_focus() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    show_docs_popup(&mut harness);
+    pin_popup(&mut harness);
+
+    harness.send_key(KeyCode::F(6), KeyModifiers::NONE).unwrap();
+    assert!(harness.editor().is_floating_window_focused());
+
+    harness.type_text("x").unwrap();
+    harness.render().unwrap();
+
+    assert!(!harness.editor().is_floating_window_focused());
+    assert_eq!(harness.get_buffer_content().unwrap(), "x");
+    harness.assert_screen_contains("Frobnicates the input");
+}
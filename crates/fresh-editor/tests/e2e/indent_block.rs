@@ -0,0 +1,94 @@
+//! E2E tests for indentation block selection, navigation and guide
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+const PYTHON: &str =
+    "def f():\n    a = 1\n    if a:\n        b = 2\n        c = 3\n\n    return a\nx = f()\n";
+
+fn open_python(harness: &mut EditorTestHarness, temp_dir: &TempDir) {
+    let file_path = temp_dir.path().join("block.py");
+    std::fs::write(&file_path, PYTHON).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+}
+
+fn move_down(harness: &mut EditorTestHarness, lines: usize) {
+    for _ in 0..lines {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.render().unwrap();
+}
+
+/// Select Indentation Block selects the whole lines of the block, across blank lines
+#[test]
+fn test_select_indent_block() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    open_python(&mut harness, &temp_dir);
+
+    // Cursor on `a = 1`
+    move_down(&mut harness, 1);
+    harness.run_command("Select Indentation Block").unwrap();
+
+    let range = harness
+        .get_selection_range()
+        .expect("block should be selected");
+    assert_eq!(
+        &PYTHON[range],
+        "    a = 1\n    if a:\n        b = 2\n        c = 3\n\n    return a\n"
+    );
+}
+
+/// Go to Indentation Block Start/End jump to the first text of the block's edge lines
+#[test]
+fn test_goto_indent_block_start_and_end() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    open_python(&mut harness, &temp_dir);
+
+    // Cursor on `if a:`
+    move_down(&mut harness, 2);
+
+    harness.run_command("Go to Indentation Block End").unwrap();
+    assert_eq!(harness.cursor_position(), PYTHON.find("return a").unwrap());
+
+    harness
+        .run_command("Go to Indentation Block Start")
+        .unwrap();
+    assert_eq!(harness.cursor_position(), PYTHON.find("a = 1").unwrap());
+}
+
+/// A faint guide marks the block in the enclosing indentation level
+#[test]
+fn test_indent_block_guide_is_drawn() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    open_python(&mut harness, &temp_dir);
+
+    // Cursor on `b = 2`; the block is `b = 2` and `c = 3`
+    move_down(&mut harness, 3);
+
+    let (first_row, last_row) = harness.content_area_rows();
+    let (row, text_col) = (first_row..=last_row)
+        .find_map(|row| {
+            let line = harness.get_row_text(row as u16);
+            let idx = line.find("c = 3")?;
+            Some((row as u16, line[..idx].chars().count() as u16))
+        })
+        .expect("c = 3 should be on screen");
+
+    // The guide sits at column 4 of the line, i.e. 4 cells left of the text
+    let guide = harness.get_cell_style(text_col - 4, row).unwrap();
+    let plain = harness.get_cell_style(text_col - 2, row).unwrap();
+    assert_ne!(
+        guide.bg, plain.bg,
+        "guide cell should have its own background"
+    );
+
+    // Moving to a top-level line removes the guide
+    move_down(&mut harness, 4);
+    let guide = harness.get_cell_style(text_col - 4, row).unwrap();
+    assert_eq!(guide.bg, plain.bg);
+}
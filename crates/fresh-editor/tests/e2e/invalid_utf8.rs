@@ -0,0 +1,112 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use tempfile::TempDir;
+
+fn save(harness: &mut EditorTestHarness) {
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+}
+
+/// A file with invalid UTF-8 shows a warning banner, displays the bytes as
+/// U+FFFD and keeps them when saved after an edit
+#[test]
+fn test_invalid_utf8_banner_and_save_keeps_bytes() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("latin1.txt");
+    std::fs::write(&path, b"caf\xe9\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("latin1.txt contains invalid UTF-8");
+    harness.assert_screen_contains("caf\u{FFFD}");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("ok").unwrap();
+    save(&mut harness);
+
+    assert_eq!(std::fs::read(&path).unwrap(), b"caf\xe9\nok");
+    harness.assert_screen_contains("latin1.txt contains invalid UTF-8");
+
+    // Hex view shows the byte itself and hides the banner
+    harness.run_command("Toggle Hex View").unwrap();
+    harness.assert_screen_contains("caf<E9>");
+    harness.assert_screen_not_contains("contains invalid UTF-8");
+}
+
+/// Saving asks before writing a file that lost invalid UTF-8 sequences
+#[test]
+fn test_save_confirms_changing_invalid_utf8() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("data.txt");
+    std::fs::write(&path, b"a\xffb").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("ab");
+
+    save(&mut harness);
+    harness.assert_screen_contains("Saving changes 1 invalid UTF-8 sequence(s) of");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"a\xffb");
+
+    save(&mut harness);
+    harness.type_text("s").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(std::fs::read(&path).unwrap(), b"ab");
+
+    // The file is valid UTF-8 now, so nothing more is asked or shown
+    harness.type_text("c").unwrap();
+    save(&mut harness);
+    assert_eq!(std::fs::read(&path).unwrap(), b"acb");
+    harness.assert_screen_not_contains("invalid UTF-8");
+}
+
+/// Reopen with Encoding decodes the file and saving encodes it back
+#[test]
+fn test_reopen_with_encoding_round_trips() {
+    let temp_dir = TempDir::new().unwrap();
+    let path = temp_dir.path().join("legacy.txt");
+    std::fs::write(&path, b"caf\xe9 \x80\n").unwrap();
+
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&path).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("Reopen with Encoding").unwrap();
+    harness.type_text("Windows-1252").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Reopened as Windows-1252");
+    harness.assert_buffer_content("café €\n");
+    harness.assert_screen_not_contains("contains invalid UTF-8");
+
+    harness.type_text("à ").unwrap();
+    save(&mut harness);
+    assert_eq!(std::fs::read(&path).unwrap(), b"\xe0 caf\xe9 \x80\n");
+
+    // Characters the encoding can't represent make the save fail instead of
+    // being replaced
+    harness.type_text("✓").unwrap();
+    let error = harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap_err();
+    assert!(error.to_string().contains("can't be saved as Windows-1252"));
+    assert_eq!(std::fs::read(&path).unwrap(), b"\xe0 caf\xe9 \x80\n");
+}
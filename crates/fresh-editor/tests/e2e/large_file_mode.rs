@@ -671,3 +671,32 @@ fn test_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// Saving a huge buffer writes it a few chunks per frame, with the progress
+/// drawn in the status bar in between
+#[test]
+fn test_huge_file_save_shows_progress() {
+    use std::fs;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("huge.txt");
+    let line = format!("{}\n", "0123456789abcdef".repeat(4));
+    let size = 101 * 1024 * 1024;
+    fs::write(&file_path, line.repeat(size / line.len())).unwrap();
+
+    // Wide enough for the temp dir path and the status message
+    let mut harness = EditorTestHarness::new(160, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.type_text("HEAD").unwrap();
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_screen_contains("Saving huge.txt: 0%");
+
+    harness.wait_until(|h| !h.editor().is_saving()).unwrap();
+    harness.assert_screen_contains("Saved");
+    let saved = fs::read(&file_path).unwrap();
+    assert_eq!(saved.len(), size / line.len() * line.len() + 4);
+    assert!(saved.starts_with(b"HEAD0123"));
+}
@@ -0,0 +1,82 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+/// Create a project with a source file and a file inside `node_modules`
+fn project_with_dependency() -> (tempfile::TempDir, std::path::PathBuf, std::path::PathBuf) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    let dependency_dir = project.join("node_modules").join("pkg");
+    std::fs::create_dir_all(&dependency_dir).unwrap();
+
+    let source = project.join("main.js");
+    std::fs::write(&source, "main\n").unwrap();
+    let dependency = dependency_dir.join("index.js");
+    std::fs::write(&dependency, "lib\n").unwrap();
+    (temp_dir, source, dependency)
+}
+
+/// Files in dependency directories open read-only; typing asks whether to edit
+/// them, and Enter allows it
+#[test]
+fn test_dependency_file_asks_before_editing() {
+    let (temp_dir, _source, dependency) = project_with_dependency();
+    let mut harness =
+        EditorTestHarness::with_working_dir(120, 24, temp_dir.path().join("project")).unwrap();
+    harness.open_file(&dependency).unwrap();
+    harness.render().unwrap();
+
+    harness.type_text("x").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "lib\n");
+    harness.assert_screen_contains("index.js is library code");
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Editing enabled for");
+
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xlib\n");
+}
+
+/// Escape at the prompt keeps the file read-only
+#[test]
+fn test_dependency_file_stays_read_only_on_cancel() {
+    let (temp_dir, _source, dependency) = project_with_dependency();
+    let mut harness =
+        EditorTestHarness::with_working_dir(120, 24, temp_dir.path().join("project")).unwrap();
+    harness.open_file(&dependency).unwrap();
+
+    harness.type_text("x").unwrap();
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("index.js is library code");
+
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "lib\n");
+}
+
+/// Project files are editable, and `protect_library_files = false` turns the
+/// protection off
+#[test]
+fn test_library_protection_scope_and_setting() {
+    let (temp_dir, source, dependency) = project_with_dependency();
+    let project = temp_dir.path().join("project");
+
+    let mut harness = EditorTestHarness::with_working_dir(120, 24, project.clone()).unwrap();
+    harness.open_file(&source).unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xmain\n");
+
+    let mut config = Config::default();
+    config.editor.protect_library_files = false;
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, config, project).unwrap();
+    harness.open_file(&dependency).unwrap();
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "xlib\n");
+}
@@ -1927,3 +1927,68 @@ fn test_add_cursor_below_with_line_wrap_enabled() {
         "Should have 2 cursors after add cursor below"
     );
 }
+
+/// Test plugin display overrides: wrap column and conceal rules on a virtual buffer
+#[test]
+fn test_plugin_buffer_display_overrides() {
+    use fresh::primitives::text_property::TextPropertyEntry;
+    use fresh::services::plugins::api::{BufferDisplayOptions, ConcealRule, PluginCommand};
+    use std::collections::HashMap;
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    let cmd = PluginCommand::CreateVirtualBufferWithContent {
+        name: "*Log*".to_string(),
+        mode: "log-test".to_string(),
+        read_only: true,
+        entries: vec![TextPropertyEntry {
+            text: "alpha beta gamma delta epsilon zeta\n@@meta@@ visible line\n".to_string(),
+            properties: HashMap::new(),
+        }],
+        show_line_numbers: false,
+        show_cursors: true,
+        editing_disabled: true,
+        hidden_from_tabs: false,
+        display: Some(BufferDisplayOptions {
+            wrap_column: Some(20),
+            line_wrap: Some(true),
+            conceal: Some(vec![ConcealRule {
+                pattern: "@@meta@@ ".to_string(),
+                replacement: None,
+            }]),
+            ..Default::default()
+        }),
+        request_id: None,
+    };
+    harness.editor_mut().handle_plugin_command(cmd).unwrap();
+    harness.render().unwrap();
+
+    // Concealed text is hidden, the rest of its line is shown
+    harness.assert_screen_not_contains("@@meta@@");
+    harness.assert_screen_contains("visible line");
+    // The first line wraps at column 20 even though the window is wider
+    harness.assert_screen_not_contains("alpha beta gamma delta");
+    harness.assert_screen_contains("epsilon zeta");
+    // The buffer itself is untouched
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("@@meta@@ visible line"));
+
+    // Clearing the overrides restores normal display
+    let buffer_id = harness.editor().active_buffer();
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetBufferDisplayOptions {
+            buffer_id,
+            options: BufferDisplayOptions {
+                wrap_column: Some(0),
+                conceal: Some(vec![]),
+                ..Default::default()
+            },
+        })
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("alpha beta gamma delta epsilon zeta");
+    harness.assert_screen_contains("@@meta@@ visible line");
+}
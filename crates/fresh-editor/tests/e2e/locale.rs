@@ -514,3 +514,35 @@ fn test_locale_switch_affects_command_palette_commands() {
     // Should confirm that we see the translated command
     harness.assert_screen_contains("Abrir archivo");
 }
+
+#[test]
+fn test_insert_date_uses_locale_format() {
+    let config = Config {
+        locale: LocaleName(Some("de".to_string())),
+        ..Default::default()
+    };
+
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.render().unwrap();
+
+    let before = chrono::Local::now().format("%d.%m.%Y").to_string();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Datum einfügen").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let after = chrono::Local::now().format("%d.%m.%Y").to_string();
+
+    // German dates are day-first with dots; tolerate crossing midnight
+    let content = harness.get_buffer_content().unwrap();
+    assert!(
+        content == before || content == after,
+        "expected German date, got {:?}",
+        content
+    );
+}
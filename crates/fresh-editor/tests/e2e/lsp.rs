@@ -803,6 +803,7 @@ fn test_lsp_waiting_indicator() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -870,6 +871,7 @@ fn test_semantic_tokens_version_gating() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -965,6 +967,7 @@ fn test_semantic_tokens_range_preserves_overlays_on_edit() -> anyhow::Result<()>
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1058,6 +1061,7 @@ fn test_semantic_tokens_persist_on_enter_key() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1152,6 +1156,7 @@ fn test_semantic_tokens_overlays_shift_on_edit() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1254,6 +1259,7 @@ fn test_semantic_tokens_range_only_viewport_highlighting() -> anyhow::Result<()>
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1417,6 +1423,7 @@ fn test_lsp_completion_canceled_on_cursor_move() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1482,6 +1489,7 @@ fn test_lsp_cursor_animation() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1550,6 +1558,7 @@ fn test_lsp_completion_canceled_on_text_edit() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -2103,6 +2112,79 @@ fn test_handle_rename_response_with_document_changes() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that a rename with risky edits opens a preview where they can be excluded
+#[test]
+fn test_rename_preview_excludes_risky_edits() -> anyhow::Result<()> {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use lsp_types::{Position, Range, TextEdit, Uri, WorkspaceEdit};
+    use std::collections::HashMap;
+
+    let mut harness = EditorTestHarness::new(100, 30)?;
+
+    let temp_dir = tempfile::tempdir()?;
+    let test_file = temp_dir.path().join("test.rs");
+    std::fs::write(
+        &test_file,
+        "fn f() {\n    let total = 1;\n    let count = 2;\n    // count things\n}\n",
+    )?;
+    harness.open_file(&test_file)?;
+    harness.render()?;
+
+    let uri = url::Url::from_file_path(&test_file)
+        .unwrap()
+        .as_str()
+        .parse::<Uri>()
+        .unwrap();
+    let edit = |line, start, end| TextEdit {
+        range: Range {
+            start: Position {
+                line,
+                character: start,
+            },
+            end: Position {
+                line,
+                character: end,
+            },
+        },
+        new_text: "total".to_string(),
+    };
+    // Both edits are risky: `total` is already bound in `f`, and the second is in a comment
+    let workspace_edit = WorkspaceEdit {
+        changes: Some(HashMap::from([(uri, vec![edit(2, 8, 13), edit(3, 7, 12)])])),
+        document_changes: None,
+        change_annotations: None,
+    };
+
+    harness
+        .editor_mut()
+        .handle_rename_response(0, Ok(workspace_edit))?;
+    harness.render()?;
+
+    // Nothing is applied until the preview is confirmed
+    assert!(harness
+        .get_buffer_content()
+        .unwrap()
+        .contains("let count = 2"));
+    harness.assert_screen_contains("test.rs:3  let count = 2;");
+    harness.assert_screen_contains("test.rs:4  // count things");
+    harness.assert_screen_contains("inside a comment");
+    harness.assert_screen_not_contains("[x]");
+
+    // Check the first edit only, then apply
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE)?;
+    harness.assert_screen_contains("[x]");
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+
+    let buffer_content = harness.get_buffer_content().unwrap();
+    assert!(buffer_content.contains("let total = 2"), "{buffer_content}");
+    assert!(
+        buffer_content.contains("// count things"),
+        "{buffer_content}"
+    );
+
+    Ok(())
+}
+
 /// Test that editor remains responsive while LSP is completely stuck
 ///
 /// This test verifies that the UI doesn't block when the LSP server is unresponsive.
@@ -2134,6 +2216,7 @@ fn test_lsp_diagnostics_non_blocking() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -2291,6 +2374,7 @@ fn test_rust_analyzer_rename_real_scenario() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -2899,6 +2983,7 @@ fn test_lsp_progress_status_display() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -3059,6 +3144,7 @@ fn test_lsp_crash_detection_and_restart() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -3355,6 +3441,7 @@ fn test_pull_diagnostics_auto_trigger_after_open() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -3433,6 +3520,7 @@ fn test_pull_diagnostics_result_id_tracking() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -3691,6 +3779,7 @@ fn test_stopped_lsp_does_not_auto_restart_on_edit() -> anyhow::Result<()> {
             auto_start: true, // Auto-start so it starts when we open the file
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -4008,6 +4097,7 @@ fn test_hover_popup_persists_within_symbol_and_popup() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -5640,6 +5730,7 @@ fn test_hover_does_not_autostart_lsp_when_disabled() -> anyhow::Result<()> {
             auto_start: false, // This is the key setting - LSP should NOT auto-start
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
     // Enable mouse hover in config
@@ -5725,6 +5816,7 @@ fn test_typing_does_not_autostart_lsp_when_disabled() -> anyhow::Result<()> {
             auto_start: false, // This is the key setting - LSP should NOT auto-start
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -5798,6 +5890,7 @@ fn test_completion_triggered_on_trigger_character() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -5878,6 +5971,7 @@ fn test_completion_triggered_on_word_char_with_quick_suggestions() -> anyhow::Re
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -5957,6 +6051,7 @@ fn test_completion_not_triggered_on_word_char_without_quick_suggestions() -> any
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -6038,6 +6133,7 @@ fn test_completion_not_triggered_on_non_word_char() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -6133,6 +6229,7 @@ fn test_hover_popup_follows_mouse_when_lsp_returns_no_range() -> anyhow::Result<
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -6354,6 +6451,7 @@ fn test_hover_does_not_trigger_past_end_of_line() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -6453,6 +6551,7 @@ fn test_hover_does_not_trigger_on_empty_line() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -6554,6 +6653,7 @@ fn test_hover_no_duplicate_popup_when_moving_within_symbol() -> anyhow::Result<(
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -7035,3 +7135,35 @@ fn test_hover_popup_scroll_to_bottom() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+/// Detect Language Servers lists the known servers with their install state
+#[test]
+fn test_detect_language_servers_lists_known_servers() -> anyhow::Result<()> {
+    let mut harness = EditorTestHarness::new(120, 30)?;
+
+    harness.send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)?;
+    harness.wait_until(|h| h.screen_to_string().contains(">command"))?;
+    harness.type_text("Detect Language Servers")?;
+    harness.send_key(KeyCode::Enter, KeyModifiers::NONE)?;
+    harness.render()?;
+
+    harness.assert_screen_contains("Enable language server:");
+    for row in [
+        "rust: rust-analyzer",
+        "python: pyright-langserver",
+        "go: gopls",
+    ] {
+        harness.assert_screen_contains(row);
+    }
+    let screen = harness.screen_to_string();
+    assert!(
+        screen.contains("Installed:") || screen.contains("Not installed. Install with:"),
+        "Rows should say whether the server is installed. Screen:\n{screen}"
+    );
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE)?;
+    harness.render()?;
+    harness.assert_screen_not_contains("Enable language server:");
+
+    Ok(())
+}
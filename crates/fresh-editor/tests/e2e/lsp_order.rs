@@ -50,6 +50,7 @@ fn test_did_open_sent_before_hover() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
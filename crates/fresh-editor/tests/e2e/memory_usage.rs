@@ -0,0 +1,31 @@
+// E2E tests for the Memory Usage command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+#[test]
+fn test_memory_usage_lists_caches_against_budget() {
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    harness.editor_mut().set_clipboard_for_test(String::new());
+
+    harness.type_text("alpha beta").unwrap();
+    harness
+        .send_key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('c'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Memory Usage").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Memory Usage*");
+    harness.assert_screen_contains("of 256.0 MiB (editor.cache_budget_mb)");
+    harness.assert_screen_contains("Clipboard history (unpinned)");
+}
@@ -7,18 +7,26 @@ pub mod block_selection;
 pub mod buffer_lifecycle;
 pub mod buffer_settings_commands;
 pub mod case_conversion;
+pub mod cheat_sheet;
 pub mod command_palette;
+pub mod context_menu;
+pub mod copy_with_context;
 pub mod crash_repro;
 pub mod crlf_rendering;
 pub mod document_model;
 pub mod emacs_actions;
+pub mod evaluate_selection;
 pub mod explorer_menu;
 pub mod file_browser;
 pub mod file_explorer;
 pub mod file_permissions;
+pub mod floating_windows;
+pub mod indent_block;
 pub mod indent_dedent;
+pub mod invalid_utf8;
 pub mod language_features_e2e;
 pub mod large_file_mode;
+pub mod library_files;
 pub mod lifecycle;
 pub mod line_wrapping;
 pub mod live_grep;
@@ -28,6 +36,7 @@ pub mod lsp_order;
 pub mod macros;
 pub mod margin;
 pub mod markdown_compose;
+pub mod memory_usage;
 pub mod menu_bar;
 pub mod merge_conflict;
 pub mod mouse;
@@ -37,6 +46,7 @@ pub mod multibyte_characters;
 pub mod multicursor;
 pub mod on_save_actions;
 pub mod open_folder;
+pub mod overwrite_mode;
 pub mod paste;
 #[cfg(feature = "plugins")]
 pub mod plugins;
@@ -47,11 +57,17 @@ pub mod position_history_debug;
 pub mod position_history_truncate_debug;
 pub mod prompt;
 pub mod prompt_editing;
+pub mod recent_workspaces;
 pub mod recovery;
+pub mod reflow;
+pub mod remote_audit_log;
 pub mod remote_fs_test;
 pub mod rendering;
+pub mod rpc;
+pub mod safe_mode;
 pub mod save_as_language_detection;
 pub mod scroll_clearing;
+pub mod scroll_sync;
 pub mod scrolling;
 pub mod search;
 pub mod select_to_paragraph;
@@ -61,13 +77,17 @@ pub mod settings;
 pub mod settings_config_issue_806;
 pub mod settings_paste;
 pub mod shell_command;
+pub mod show_paths;
 pub mod slow_filesystem;
 pub mod smart_editing;
 pub mod sort_lines;
 pub mod split_tabs;
 pub mod split_view;
 pub mod split_view_expectations;
+pub mod status_bar_template;
 pub mod stdin_input;
+pub mod sticky_scroll;
+pub mod subword_navigation;
 pub mod sudo_save_prompt;
 #[cfg(unix)]
 pub mod symlinks;
@@ -84,6 +104,7 @@ pub mod toggle_bars;
 pub mod toggle_comment;
 pub mod undo_redo;
 pub mod unicode_cursor;
+pub mod unicode_input;
 pub mod unicode_prompt_bugs;
 pub mod update_notification;
 #[cfg(feature = "plugins")]
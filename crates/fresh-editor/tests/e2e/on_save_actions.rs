@@ -49,6 +49,7 @@ fn test_format_on_save() {
             tab_size: None,
             formatter: Some(formatter),
             format_on_save: true,
+            code_actions_on_save: vec![],
             on_save: vec![],
         },
     );
@@ -112,6 +113,7 @@ fn test_on_save_linter_style() {
             tab_size: None,
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: vec![],
             on_save: vec![action],
         },
     );
@@ -175,6 +177,7 @@ fn test_on_save_action_failure() {
             tab_size: None,
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: vec![],
             on_save: vec![action],
         },
     );
@@ -247,6 +250,7 @@ fn test_on_save_file_placeholder() {
             tab_size: None,
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: vec![],
             on_save: vec![action],
         },
     );
@@ -313,6 +317,7 @@ fn test_formatter_stdin_mode() {
             tab_size: None,
             formatter: Some(formatter),
             format_on_save: true,
+            code_actions_on_save: vec![],
             on_save: vec![],
         },
     );
@@ -386,6 +391,7 @@ fn test_on_save_stops_on_failure() {
             tab_size: None,
             formatter: None,
             format_on_save: false,
+            code_actions_on_save: vec![],
             on_save: vec![action1, action2],
         },
     );
@@ -480,6 +486,7 @@ fn test_formatter_not_found_shows_message() {
             tab_size: None,
             formatter: Some(formatter),
             format_on_save: true,
+            code_actions_on_save: vec![],
             on_save: vec![],
         },
     );
@@ -647,3 +654,137 @@ fn test_whitespace_cleanup_no_change_needed() {
     // Content should remain the same
     harness.assert_buffer_content("line 1\nline 2\n");
 }
+
+/// .editorconfig turns on trimming, which leaves multi-line strings alone
+#[test]
+fn test_editorconfig_trim_skips_multiline_strings() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    std::fs::write(
+        project_dir.join(".editorconfig"),
+        "root = true\n\n[*.rs]\ntrim_trailing_whitespace = true\ninsert_final_newline = true\n",
+    )
+    .unwrap();
+
+    let file_path = project_dir.join("test.rs");
+    std::fs::write(&file_path, "let s = \"a  \nb  \";  \nlet t = 1;").unwrap();
+
+    // Both options are off in the editor config
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Config::default(), project_dir)
+            .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+
+    let expected = "let s = \"a  \nb  \";\nlet t = 1;\n";
+    harness.assert_buffer_content(expected);
+    assert_eq!(std::fs::read_to_string(&file_path).unwrap(), expected);
+}
+
+/// Config for a Rust project whose language server is the given fake LSP
+/// script, with `source.organizeImports` on save followed by a formatter
+/// that upper-cases `use`
+fn code_actions_on_save_config(lsp_script: std::path::PathBuf) -> Config {
+    let mut config = Config::default();
+    config.lsp.insert(
+        "rust".to_string(),
+        fresh::services::lsp::LspServerConfig {
+            command: lsp_script.to_string_lossy().to_string(),
+            args: vec![],
+            enabled: true,
+            auto_start: true,
+            process_limits: fresh::services::process_limits::ProcessLimits::default(),
+            initialization_options: None,
+            settings: None,
+        },
+    );
+    let rust = config.languages.get_mut("rust").unwrap();
+    rust.code_actions_on_save = vec!["source.organizeImports".to_string()];
+    rust.formatter = Some(FormatterConfig {
+        command: "sed".to_string(),
+        args: vec!["s/^use/USE/".to_string()],
+        stdin: true,
+        timeout_ms: 5000,
+    });
+    rust.format_on_save = true;
+    config
+}
+
+/// Code actions on save are applied before the formatter and written to disk
+#[test]
+#[cfg_attr(not(unix), ignore = "FakeLspServer uses a Bash script")]
+fn test_code_actions_on_save_applied_before_formatter() {
+    use crate::common::fake_lsp::FakeLspServer;
+    let _fake_server = FakeLspServer::spawn_with_code_actions().unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("main.rs");
+    std::fs::write(&file_path, "fn main() {}\n").unwrap();
+
+    let config = code_actions_on_save_config(FakeLspServer::code_actions_script_path());
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.wait_for_screen_contains("ready").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness
+        .wait_for_buffer_content("USE std::io; fn main() {}\n")
+        .unwrap();
+
+    let disk_content = std::fs::read_to_string(&file_path).unwrap();
+    assert_eq!(disk_content, "USE std::io; fn main() {}\n");
+}
+
+/// A server that never answers is skipped after the timeout and the
+/// formatter still runs
+#[test]
+#[cfg_attr(not(unix), ignore = "FakeLspServer uses a Bash script")]
+fn test_code_actions_on_save_timeout_skips_server() {
+    use crate::common::fake_lsp::FakeLspServer;
+    let _fake_server = FakeLspServer::spawn_blocking().unwrap();
+
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("main.rs");
+    std::fs::write(&file_path, "use std::fs;\nfn main() {}\n").unwrap();
+
+    let mut config = code_actions_on_save_config(FakeLspServer::blocking_script_path());
+    config.editor.code_actions_on_save_timeout_ms = 100;
+    // Wide enough for the whole status message
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        160,
+        24,
+        config,
+        temp_dir.path().to_path_buf(),
+    )
+    .unwrap();
+
+    harness.open_file(&file_path).unwrap();
+    harness.wait_for_screen_contains("ready").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    // The file is saved straight away, before the code actions finish
+    harness.assert_screen_contains("running code actions");
+
+    harness
+        .wait_for_buffer_content("USE std::fs;\nfn main() {}\n")
+        .unwrap();
+    harness.assert_screen_contains("code actions timed out: source.organizeImports");
+}
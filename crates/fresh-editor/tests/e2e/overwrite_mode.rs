@@ -0,0 +1,31 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+
+/// Insert toggles overwrite mode, which replaces characters up to the line end
+#[test]
+fn test_overwrite_mode_replaces_characters() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello\nworld").unwrap();
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+
+    harness
+        .send_key(KeyCode::Insert, KeyModifiers::NONE)
+        .unwrap();
+    assert!(harness.editor().is_overwrite_mode());
+    harness.type_text("HELP me").unwrap();
+    harness.assert_buffer_content("HELP me\nworld");
+
+    // Undo restores the replaced characters
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("HELP m\nworld");
+
+    harness
+        .send_key(KeyCode::Insert, KeyModifiers::NONE)
+        .unwrap();
+    assert!(!harness.editor().is_overwrite_mode());
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("HELP m!\nworld");
+}
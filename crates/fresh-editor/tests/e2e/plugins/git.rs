@@ -1389,6 +1389,125 @@ fn test_git_log_open_different_commits_sequentially() {
     );
 }
 
+// =============================================================================
+// Git Stage Tests
+// =============================================================================
+
+/// Commit a file with two far-apart lines, then change both of them
+fn setup_two_hunk_repo() -> GitTestRepo {
+    let repo = GitTestRepo::new();
+    let original: Vec<String> = (1..=30).map(|i| format!("line {i}")).collect();
+    repo.create_file("notes.txt", &(original.join("\n") + "\n"));
+    repo.git_add_all();
+    repo.git_commit("Initial commit");
+
+    let mut modified = original.clone();
+    modified[1] = "line 2 changed".to_string();
+    modified[27] = "line 28 changed".to_string();
+    repo.modify_file("notes.txt", &(modified.join("\n") + "\n"));
+    repo.setup_git_stage_plugin();
+    repo
+}
+
+/// Open `notes.txt` and run Git Stage Hunks on it
+fn open_stage_view(repo: &GitTestRepo) -> EditorTestHarness {
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        50,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+    harness.open_file(&repo.path.join("notes.txt")).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.wait_for_prompt().unwrap();
+    harness.type_text("Git Stage Hunks").unwrap();
+    harness.wait_for_screen_contains("Git Stage Hunks").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Unstaged changes (2):")
+        .unwrap();
+    // The ready status is set after the cursor is placed on the first hunk
+    harness.wait_for_screen_contains("Stage hunks |").unwrap();
+    harness
+}
+
+fn staged_diff(repo: &GitTestRepo) -> String {
+    let output = std::process::Command::new("git")
+        .args(["diff", "--cached"])
+        .current_dir(&repo.path)
+        .output()
+        .unwrap();
+    String::from_utf8_lossy(&output.stdout).into_owned()
+}
+
+/// Staging one hunk leaves the other unstaged, and unstaging it restores both
+#[test]
+fn test_git_stage_and_unstage_single_hunk() {
+    let repo = setup_two_hunk_repo();
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+    let mut harness = open_stage_view(&repo);
+
+    // The cursor starts on the first unstaged hunk
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Hunk staged").unwrap();
+    harness.assert_screen_contains("Staged changes (1):");
+    harness.assert_screen_contains("Unstaged changes (1):");
+    harness.assert_screen_contains("MM notes.txt");
+
+    let staged = staged_diff(&repo);
+    assert!(staged.contains("+line 2 changed"), "staged diff: {staged}");
+    assert!(!staged.contains("line 28 changed"), "staged diff: {staged}");
+
+    // Jump to the staged hunk and unstage it again
+    let before = harness.cursor_position();
+    harness
+        .send_key(KeyCode::Char('n'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() != before)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('u'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Hunk unstaged").unwrap();
+    harness.assert_screen_contains("Unstaged changes (2):");
+    assert!(staged_diff(&repo).is_empty());
+}
+
+/// Discarding asks first and then reverts the hunk in the working tree
+#[test]
+fn test_git_stage_discard_hunk_after_confirm() {
+    let repo = setup_two_hunk_repo();
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+    let mut harness = open_stage_view(&repo);
+
+    harness
+        .send_key(KeyCode::Char('x'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Discard this hunk")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Hunk discarded").unwrap();
+    harness.assert_screen_contains("Unstaged changes (1):");
+
+    let content = std::fs::read_to_string(repo.path.join("notes.txt")).unwrap();
+    assert!(content.contains("\nline 2\n"));
+    assert!(content.contains("line 28 changed"));
+}
+
 // =============================================================================
 // Git Blame Tests
 // =============================================================================
@@ -1345,6 +1345,123 @@ fn test_buffer_modified_clears_after_paste_restores_content() {
     );
 }
 
+/// Test that deleted lines get a marker on the following line and that
+/// Revert Change restores them from the saved file
+#[test]
+fn test_buffer_modified_marks_and_reverts_deleted_lines() {
+    let repo = GitTestRepo::new();
+
+    // Change to repo directory so plugin can find files correctly
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let initial_content = "line 1\nline 2\nline 3\nline 4\n";
+    repo.create_file("test.txt", initial_content);
+    repo.setup_buffer_modified_plugin();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    open_file(&mut harness, &repo.path, "test.txt");
+    process_async_once(&mut harness);
+
+    // Delete "line 2" entirely
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Home, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Down, KeyModifiers::SHIFT)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.assert_buffer_content("line 1\nline 3\nline 4\n");
+
+    // The deletion is marked on the line that followed it, nothing else is
+    wait_for_indicator_on_line(&mut harness, "▴", 1);
+    let screen = harness.screen_to_string();
+    assert_eq!(get_indicator_lines(&screen, "▴"), vec![1]);
+    assert!(get_indicator_lines(&screen, "│").is_empty());
+
+    harness.run_command("Revert Change").unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some(initial_content))
+        .unwrap();
+    wait_for_no_indicators(&mut harness, "▴");
+}
+
+/// Test that Go to Next/Previous Change move between changes and wrap around
+#[test]
+fn test_buffer_modified_change_navigation() {
+    let repo = GitTestRepo::new();
+
+    // Change to repo directory so plugin can find files correctly
+    let original_dir = repo.change_to_repo_dir();
+    let _guard = DirGuard::new(original_dir);
+
+    let initial_content = (1..=8).map(|i| format!("line {}\n", i)).collect::<String>();
+    repo.create_file("test.txt", &initial_content);
+    repo.setup_buffer_modified_plugin();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        Config::default(),
+        repo.path.clone(),
+    )
+    .unwrap();
+
+    open_file(&mut harness, &repo.path, "test.txt");
+    process_async_once(&mut harness);
+
+    // Modify lines 2 and 6 (0-indexed 1 and 5)
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" A").unwrap();
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text(" B").unwrap();
+    wait_for_indicator_on_line(&mut harness, "│", 5);
+    assert_eq!(
+        get_indicator_lines(&harness.screen_to_string(), "│"),
+        vec![1, 5]
+    );
+
+    // Line starts of the changed lines: "line 1\n" is 7 bytes, "line 2 A\n" is 9
+    let line_2 = 7;
+    let line_6 = 7 + 9 + 7 * 3;
+
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.run_command("Go to Next Change").unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() == line_2)
+        .unwrap();
+    harness.run_command("Go to Next Change").unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() == line_6)
+        .unwrap();
+    harness.assert_screen_contains("Change 2 of 2");
+
+    // Wraps to the first change, and back to the last one
+    harness.run_command("Go to Next Change").unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() == line_2)
+        .unwrap();
+    harness.run_command("Go to Previous Change").unwrap();
+    harness
+        .wait_until(|h| h.cursor_position() == line_6)
+        .unwrap();
+}
+
+/// Test that adding lines shifts indicators correctly
 /// Test that adding lines shifts indicators correctly
 // TODO: Fix git gutter tests on Windows - they fail due to git command output differences
 #[test]
@@ -0,0 +1,151 @@
+//! Inline suggestions from plugin providers
+
+use crate::common::harness::{copy_plugin_lib, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+const SUGGESTION_PLUGIN: &str = r#"
+const editor = getEditor();
+
+globalThis.suggestGreeting = function(request: InlineSuggestionRequest): string | null {
+    return request.textBefore.endsWith("hello ") ? "world(1)\nsecond line" : null;
+};
+
+editor.registerInlineSuggestionProvider([], "suggestGreeting");
+"#;
+
+fn harness_with_suggestions(content: &str) -> (EditorTestHarness, tempfile::TempDir) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    fs::write(plugins_dir.join("greeting.ts"), SUGGESTION_PLUGIN).unwrap();
+
+    let file = project_root.join("notes.txt");
+    fs::write(&file, content).unwrap();
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        80,
+        24,
+        Default::default(),
+        project_root.clone(),
+    )
+    .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+    (harness, temp_dir)
+}
+
+/// Check the cursor is drawn right after the typed "hello ", at the start
+/// of the ghost text
+fn assert_cursor_before_ghost_text(harness: &mut EditorTestHarness) {
+    let screen = harness.screen_to_string();
+    let (x, y) = screen
+        .lines()
+        .enumerate()
+        .find_map(|(y, line)| {
+            let x = line.find("hello world")?;
+            Some((line[..x].chars().count() as u16, y as u16))
+        })
+        .unwrap();
+    assert_eq!(harness.screen_cursor_position(), (x + 6, y));
+}
+
+/// The suggestion is shown as ghost text after the cursor once it rests,
+/// and is accepted a word at a time with Ctrl+Right and whole with Tab
+#[test]
+fn test_inline_suggestion_shown_and_accepted() {
+    let (mut harness, _temp) = harness_with_suggestions("");
+
+    harness.type_text("hello ").unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("hello world(1)") && screen.contains("second line")
+        })
+        .unwrap();
+    // Ghost text is not part of the buffer, and the cursor stays before it
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello ");
+    assert_eq!(harness.cursor_position(), 6);
+    assert_cursor_before_ghost_text(&mut harness);
+
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello world");
+    harness.assert_screen_contains("hello world(1)");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "hello world(1)\nsecond line"
+    );
+}
+
+/// Escape dismisses the suggestion without touching the buffer, and a
+/// Tab afterwards inserts indentation as usual
+#[test]
+fn test_inline_suggestion_dismissed() {
+    let (mut harness, _temp) = harness_with_suggestions("");
+
+    harness.type_text("hello ").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("second line"))
+        .unwrap();
+
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("second line");
+    harness.assert_screen_not_contains("world");
+    assert_eq!(harness.get_buffer_content().unwrap(), "hello ");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert!(content.starts_with("hello ") && content.len() > 6 && !content.contains("world"));
+}
+
+/// Ghost text also shows on the empty line after a final newline, and
+/// before text that follows the cursor
+#[test]
+fn test_inline_suggestion_at_end_of_file_and_mid_line() {
+    let (mut harness, _temp) = harness_with_suggestions("first\n");
+
+    harness
+        .send_key(KeyCode::End, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("hello ").unwrap();
+    harness
+        .wait_until(|h| {
+            let screen = h.screen_to_string();
+            screen.contains("hello world(1)") && screen.contains("second line")
+        })
+        .unwrap();
+    assert_cursor_before_ghost_text(&mut harness);
+
+    // Typing the suggestion's next characters keeps the rest of it
+    harness.type_text("wor").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("hello world(1)");
+    assert_eq!(harness.get_buffer_content().unwrap(), "first\nhello wor");
+
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "first\nhello world(1)\nsecond line"
+    );
+
+    // Before the rest of a line
+    harness
+        .send_key(KeyCode::Home, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("hello ").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("hello world(1)first"))
+        .unwrap();
+    assert_cursor_before_ghost_text(&mut harness);
+}
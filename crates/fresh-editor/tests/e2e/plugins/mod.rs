@@ -6,8 +6,10 @@ pub mod diff_cursor;
 pub mod find_file;
 pub mod git;
 pub mod gutter;
+pub mod inline_suggestion;
 pub mod language_pack;
 pub mod lsp_find_references;
 pub mod package_manager;
 pub mod plugin;
+pub mod regex_tester;
 pub mod theme_editor;
@@ -323,6 +323,7 @@ fn test_diagnostics_panel_plugin_loads() {
             auto_start: true,
             process_limits: fresh::services::process_limits::ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -746,6 +747,7 @@ fn test_clangd_plugin_file_status_notification() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -815,6 +817,7 @@ fn test_clangd_plugin_switch_source_header() -> anyhow::Result<()> {
             auto_start: true,
             process_limits: ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1041,6 +1044,7 @@ editor.setStatus("Test diagnostics plugin loaded");
             auto_start: true,
             process_limits: ProcessLimits::default(),
             initialization_options: None,
+            settings: None,
         },
     );
 
@@ -1282,3 +1286,1759 @@ editor.debug("Theme overlay test plugin loaded");
     let screen = harness.screen_to_string();
     println!("Final screen:\n{}", screen);
 }
+
+/// Test that editor.computeDiff resolves with line, word and composite hunks
+#[test]
+fn test_plugin_compute_diff() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Diff Test", "Run computeDiff", "diff_test", null);
+
+globalThis.diff_test = async function(): Promise<void> {
+    const lines = await editor.computeDiff("a\nb\nc\n", "a\nB\nc\nd\n") as DiffHunk[];
+    const words = await editor.computeDiff("let x = 1;", "let y = 1;", { granularity: "word" }) as DiffHunk[];
+    const composite = await editor.computeDiff("a\nb\n", "a\nc\n", { composite: true }) as TsCompositeHunk[];
+    const parts = [
+        lines.map(h => `${h.oldStart}+${h.oldCount}>${h.newStart}+${h.newCount}`).join(","),
+        words.map(h => `${h.oldText}>${h.newText}@${h.newOffset}`).join(","),
+        JSON.stringify(composite[0]),
+    ];
+    editor.setStatus("DIFF " + parts.join(" | "));
+};
+"#;
+    fs::write(plugins_dir.join("diff_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Diff Test").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_for_screen_contains("DIFF ").unwrap();
+    harness.assert_screen_contains(
+        r#"DIFF 1+1>1+1,3+0>3+1 | x>y@4 | {"oldStart":1,"oldCount":1,"newStart":1,"newCount":1}"#,
+    );
+}
+
+/// Test that querySyntax returns the captures of a tree-sitter query and
+/// rejects invalid queries
+#[test]
+fn test_plugin_query_syntax() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Query Test", "Run querySyntax", "query_test", null);
+
+globalThis.query_test = async function(): Promise<void> {
+    const id = editor.getActiveBufferId();
+    const captures = await editor.querySyntax(id, "(function_item name: (identifier) @name)");
+    let error = "none";
+    try {
+        await editor.querySyntax(id, "(function_item");
+    } catch (e) {
+        error = "rejected";
+    }
+    const found = captures.map(c => `${c.name}:${c.text}@${c.start}-${c.end}`).join(",");
+    editor.setStatus(`QUERY ${found} | ${error}`);
+};
+"#;
+    fs::write(plugins_dir.join("query_test.ts"), test_plugin).unwrap();
+
+    let file_path = project_root.join("lib.rs");
+    fs::write(&file_path, "fn alpha() {}\nfn beta() {}\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Query Test").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_for_screen_contains("QUERY ").unwrap();
+    harness.assert_screen_contains("QUERY name:alpha@3-8,name:beta@17-21 | rejected");
+}
+
+/// Test that editor.search returns match ranges computed in the editor,
+/// honoring the regex, case, whole-word and maxResults options
+#[test]
+fn test_plugin_buffer_search() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Search Test", "Run search", "search_test", null);
+
+const ranges = (matches: SearchMatch[]) => matches.map(m => `${m.start}-${m.end}`).join(",");
+
+globalThis.search_test = async function(): Promise<void> {
+    const id = editor.getActiveBufferId();
+    const plain = await editor.search(id, "foo");
+    const cased = await editor.search(id, "Foo", { caseSensitive: true });
+    const regex = await editor.search(id, "fo+d?", { regex: true, wholeWord: true });
+    const limited = await editor.search(id, "o", { maxResults: 2 });
+    let error = "none";
+    try {
+        await editor.search(id, "(", { regex: true });
+    } catch (e) {
+        error = "rejected";
+    }
+    editor.setStatus(
+        `SEARCH ${ranges(plain)} | ${ranges(cased)} | ${ranges(regex)} | ${ranges(limited)} | ${error}`
+    );
+};
+"#;
+    fs::write(plugins_dir.join("search_test.ts"), test_plugin).unwrap();
+
+    let file_path = project_root.join("notes.txt");
+    fs::write(&file_path, "foo Foo food\nfooo\n").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Search Test").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_for_screen_contains("SEARCH ").unwrap();
+    harness.assert_screen_contains(
+        "SEARCH 0-3,4-7,8-11,13-16 | 4-7 | 0-3,4-7,8-12,13-17 | 1-2,2-3 | rejected",
+    );
+}
+
+/// Test that edits made inside beginUndoGroup/endUndoGroup undo as one step,
+/// and that a group left open by a throwing action is closed
+#[test]
+fn test_plugin_undo_group() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Grouped Edit", "Edit in one undo step", "grouped_edit", null);
+editor.registerCommand("Broken Edit", "Throw inside an undo group", "broken_edit", null);
+
+globalThis.grouped_edit = function(): void {
+    const id = editor.getActiveBufferId();
+    editor.beginUndoGroup(id);
+    editor.insertText(id, 0, "one ");
+    editor.insertText(id, 4, "two ");
+    editor.deleteRange(id, 8, 14);
+    editor.endUndoGroup(id);
+    editor.setStatus("GROUPED");
+};
+
+globalThis.broken_edit = function(): void {
+    const id = editor.getActiveBufferId();
+    editor.beginUndoGroup(id);
+    editor.insertText(id, 0, "X");
+    throw new Error("broken edit");
+};
+"#;
+    fs::write(plugins_dir.join("undo_group_test.ts"), test_plugin).unwrap();
+
+    let file_path = project_root.join("text.txt");
+    fs::write(&file_path, "hello world").unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    run_command(&mut harness, "Grouped Edit");
+    harness.wait_for_screen_contains("GROUPED").unwrap();
+    harness.assert_buffer_content("one two world");
+
+    // A single undo reverts all three edits
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("hello world");
+
+    // The throwing action's group is closed, so the user's next edit is
+    // its own undo step
+    run_command(&mut harness, "Broken Edit");
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some("Xhello world"))
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness.type_text("!").unwrap();
+    harness.assert_buffer_content("Xhello world!");
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("Xhello world");
+}
+
+/// Test that editor.undo/redo step through a buffer's history, including a
+/// buffer that isn't active, and that editor.getUndoDepth follows it
+#[test]
+fn test_plugin_undo_redo() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let first_path = project_root.join("first.txt");
+    let second_path = project_root.join("second.txt");
+    fs::write(&first_path, "first").unwrap();
+    fs::write(&second_path, "second").unwrap();
+
+    let test_plugin = format!(
+        r#"
+const editor = getEditor();
+
+editor.registerCommand("Plugin Undo", "Undo in the active buffer", "plugin_undo", null);
+editor.registerCommand("Plugin Redo", "Redo in the active buffer", "plugin_redo", null);
+editor.registerCommand("Undo First", "Undo in first.txt", "undo_first", null);
+editor.registerCommand("Report Depth", "Show the undo depth", "report_depth", null);
+
+globalThis.plugin_undo = function(): void {{
+    editor.undo(editor.getActiveBufferId());
+}};
+
+globalThis.plugin_redo = function(): void {{
+    editor.redo(editor.getActiveBufferId());
+}};
+
+globalThis.undo_first = function(): void {{
+    editor.undo(editor.findBufferByPath({first:?}));
+}};
+
+globalThis.report_depth = function(): void {{
+    editor.setStatus("DEPTH " + editor.getUndoDepth(editor.getActiveBufferId()));
+}};
+"#,
+        first = first_path.to_string_lossy()
+    );
+    fs::write(plugins_dir.join("undo_redo_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&first_path).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    harness.type_text("X").unwrap();
+    run_command(&mut harness, "Report Depth");
+    harness.wait_for_screen_contains("DEPTH 1").unwrap();
+
+    run_command(&mut harness, "Plugin Undo");
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some("first"))
+        .unwrap();
+    run_command(&mut harness, "Report Depth");
+    harness.wait_for_screen_contains("DEPTH 0").unwrap();
+
+    run_command(&mut harness, "Plugin Redo");
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some("Xfirst"))
+        .unwrap();
+
+    // Undo in a buffer that isn't the active one
+    harness.open_file(&second_path).unwrap();
+    run_command(&mut harness, "Undo First");
+    harness.open_file(&first_path).unwrap();
+    harness
+        .wait_until(|h| h.get_buffer_content().as_deref() == Some("first"))
+        .unwrap();
+    assert!(!harness.editor().active_state().buffer.is_modified());
+}
+
+/// Test that values stored with editor.setSessionData are saved with the
+/// session and handed back after session_restored
+#[test]
+fn test_plugin_session_data() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Remember Todos", "Store todos in the session", "remember_todos", null);
+
+globalThis.remember_todos = function(): void {
+    editor.setSessionData("todos", ["write tests", "ship"]);
+    editor.setSessionData("scratch", 1);
+    editor.setSessionData("scratch", null);
+    const todos = editor.getSessionData("todos") as string[];
+    editor.setStatus("STORED " + todos.length);
+};
+
+editor.on("session_restored", () => {
+    const todos = editor.getSessionData("todos") as string[] | null;
+    const scratch = editor.getSessionData("scratch");
+    editor.setStatus("RESTORED " + (todos ? todos.join(",") : "none") + " " + scratch);
+});
+"#;
+    fs::write(plugins_dir.join("session_data_test.ts"), test_plugin).unwrap();
+
+    let status = |harness: &EditorTestHarness| harness.editor().get_status_message().cloned();
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            100,
+            24,
+            Default::default(),
+            project_root.clone(),
+        )
+        .unwrap();
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Remember Todos").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness
+            .wait_until(|h| status(h).as_deref() == Some("STORED 2"))
+            .unwrap();
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Default::default(), project_root)
+            .unwrap();
+    assert!(harness.editor_mut().try_restore_session().unwrap());
+    harness
+        .wait_until(|h| status(h).as_deref() == Some("RESTORED write tests,ship null"))
+        .unwrap();
+}
+
+/// Test that editor.getLayout and editor.splitEditor can build a master-stack layout
+#[test]
+fn test_plugin_layout_tree() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Layout Test", "Build a master-stack layout", "layout_test", null);
+
+function shape(node: LayoutNode): string {
+    if (node.children.length === 0) {
+        return node.active ? "L*" : "L";
+    }
+    return `${node.direction}(${shape(node.children[0])},${shape(node.children[1])})`;
+}
+
+globalThis.layout_test = async function(): Promise<void> {
+    const root = await editor.getLayout();
+    const stack = await editor.splitEditor(root.splitId, "vertical");
+    await editor.splitEditor(stack, "horizontal");
+    let rejected = "no";
+    try {
+        await editor.splitEditor(stack, "diagonal");
+    } catch (e) {
+        rejected = "yes";
+    }
+    editor.setStatus(`LAYOUT ${shape(await editor.getLayout())} rejected=${rejected}`);
+};
+"#;
+    fs::write(plugins_dir.join("layout_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Layout Test").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_for_screen_contains("LAYOUT ").unwrap();
+    harness.assert_screen_contains("LAYOUT vertical(L,horizontal(L,L*)) rejected=yes");
+}
+
+/// Test that editor.quickPick filters its items and resolves with the choice
+#[test]
+fn test_plugin_quick_pick() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Pick One", "Pick a single item", "pick_one", null);
+editor.registerCommand("Pick Many", "Pick several items", "pick_many", null);
+
+const items: QuickPickItem[] = [
+    { label: "apple", description: "fruit", icon: "*", value: 1 },
+    { label: "carrot", description: "vegetable", value: 2 },
+    { label: "banana", description: "fruit", value: 3 },
+];
+
+globalThis.pick_one = async function(): Promise<void> {
+    const item = await editor.quickPick(items, { title: "Food: " }) as QuickPickItem | null;
+    editor.setStatus(`PICKED ${item ? item.label + "=" + item.value : "none"}`);
+};
+
+globalThis.pick_many = async function(): Promise<void> {
+    const chosen = await editor.quickPick(items, { canPickMany: true }) as QuickPickItem[];
+    editor.setStatus(`PICKED ${chosen.map((i) => i.label).join(",")}`);
+};
+"#;
+    fs::write(plugins_dir.join("quick_pick_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    // Single pick: the filter narrows the list, Enter picks the match
+    run_command(&mut harness, "Pick One");
+    harness.wait_for_screen_contains("Food: ").unwrap();
+    harness.assert_screen_contains("* apple");
+    harness.type_text("ban").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("carrot");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("PICKED banana=3").unwrap();
+
+    // Cancelling resolves with null
+    run_command(&mut harness, "Pick One");
+    harness.wait_for_screen_contains("Food: ").unwrap();
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.wait_for_screen_contains("PICKED none").unwrap();
+
+    // Multi pick: Tab checks items, Enter returns them in list order
+    run_command(&mut harness, "Pick Many");
+    harness.wait_for_screen_contains("[ ] carrot").unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("[x] banana");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("PICKED apple,banana")
+        .unwrap();
+}
+
+/// Test that editor.showPicker shows its placeholder and resolves with the
+/// checked items
+#[test]
+fn test_plugin_show_picker() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Pick Branches", "Pick branches", "pick_branches", null);
+
+globalThis.pick_branches = async function(): Promise<void> {
+    const chosen = await editor.showPicker({
+        title: "Branches: ",
+        items: [{ label: "main" }, { label: "develop", picked: true }, { label: "release" }],
+        multiSelect: true,
+        placeholder: "type to filter",
+    }) as QuickPickItem[] | null;
+    editor.setStatus(`PICKED ${chosen ? chosen.map((i) => i.label).join(",") : "none"}`);
+};
+"#;
+    fs::write(plugins_dir.join("show_picker_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Pick Branches").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    // The placeholder is shown until something is typed
+    harness
+        .wait_for_screen_contains("Branches: type to filter")
+        .unwrap();
+    harness.assert_screen_contains("[x] develop");
+    harness.type_text("rel").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_not_contains("type to filter");
+    harness.send_key(KeyCode::Tab, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("PICKED develop,release")
+        .unwrap();
+}
+
+/// Test that editor.showConfirm and editor.showInput resolve with the user's
+/// answer, and that password input is masked
+#[test]
+fn test_plugin_show_confirm_and_input() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Log In", "Log in", "log_in", null);
+
+globalThis.log_in = async function(): Promise<void> {
+    const answer = await editor.showConfirm("Log in to the server?", ["Log in", "Not now"]);
+    if (answer !== "Log in") {
+        editor.setStatus(`ANSWER ${answer}`);
+        return;
+    }
+    const user = await editor.showInput("User: ", { initial: "guest" });
+    const password = await editor.showInput("Password: ", { password: true });
+    editor.setStatus(`LOGIN ${user}:${password}`);
+};
+"#;
+    fs::write(plugins_dir.join("confirm_input_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    // Escape dismisses the confirm popup
+    run_command(&mut harness, "Log In");
+    harness
+        .wait_for_screen_contains("Log in to the server?")
+        .unwrap();
+    harness.assert_screen_contains("Not now");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.wait_for_screen_contains("ANSWER null").unwrap();
+
+    // The second button resolves with its label
+    run_command(&mut harness, "Log In");
+    harness
+        .wait_for_screen_contains("Log in to the server?")
+        .unwrap();
+    harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("ANSWER Not now").unwrap();
+
+    // Confirming leads to the inputs; the initial text is replaced by typing
+    run_command(&mut harness, "Log In");
+    harness
+        .wait_for_screen_contains("Log in to the server?")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("User: guest").unwrap();
+    harness.type_text("alice").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    harness.wait_for_screen_contains("Password: ").unwrap();
+    harness.type_text("s3cret").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Password: ******");
+    harness.assert_screen_not_contains("s3cret");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("LOGIN alice:s3cret")
+        .unwrap();
+}
+
+/// Test that editor.openFileBrowser stays inside its root, applies its filter,
+/// and resolves with the chosen path
+#[test]
+fn test_plugin_open_file_browser() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let data_dir = project_root.join("data");
+    fs::create_dir(&data_dir).unwrap();
+    fs::write(data_dir.join("schema.sql"), "").unwrap();
+    fs::write(data_dir.join("notes.txt"), "").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Pick Sql", "Pick an SQL file", "pick_sql", null);
+
+globalThis.pick_sql = async function(): Promise<void> {
+    const path = await editor.openFileBrowser({ root: "data", filter: ["*.sql"], title: "Sql file: " });
+    editor.setStatus(`PICKED ${path === null ? "none" : path.endsWith("schema.sql")}`);
+};
+"#;
+    fs::write(plugins_dir.join("file_browser_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Pick Sql").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    run_command(&mut harness);
+    harness.wait_for_screen_contains("schema.sql").unwrap();
+    harness.assert_screen_contains("Sql file: ");
+    harness.assert_screen_not_contains("notes.txt");
+
+    // Backspace on empty input cannot leave the root
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("schema.sql").unwrap();
+    harness.assert_screen_not_contains("plugins");
+
+    harness.type_text("schema.sql").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("PICKED true").unwrap();
+
+    // Cancelling resolves with null
+    run_command(&mut harness);
+    harness.wait_for_screen_contains("Sql file: ").unwrap();
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+    harness.wait_for_screen_contains("PICKED none").unwrap();
+}
+
+/// Test that a background process handle streams output lines to onStdout /
+/// onStderr listeners before it resolves, and that kill() resolves it
+#[test]
+#[cfg(unix)]
+fn test_plugin_background_process_output() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Stream Test", "Stream process output", "stream_test", null);
+editor.registerCommand("Kill Test", "Kill a background process", "kill_test", null);
+
+globalThis.stream_test = async function(): Promise<void> {
+    const out: string[] = [];
+    const err: string[] = [];
+    const result = await editor
+        .spawnBackgroundProcess("sh", ["-c", "echo one; echo two >&2; echo three"])
+        .onStdout(line => out.push(line))
+        .onStderr(line => err.push(line));
+    editor.setStatus(`STREAMED out=${out.join("|")} err=${err.join("|")} exit=${result.exit_code}`);
+};
+
+globalThis.kill_test = async function(): Promise<void> {
+    const proc = editor.spawnBackgroundProcess("sleep", ["30"]);
+    await proc.kill();
+    const result = await proc;
+    editor.setStatus(`KILLED exit=${result.exit_code}`);
+};
+"#;
+    fs::write(plugins_dir.join("stream_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    run_command(&mut harness, "Stream Test");
+    harness.wait_for_screen_contains("STREAMED").unwrap();
+    harness.assert_screen_contains("STREAMED out=one|three err=two exit=0");
+
+    run_command(&mut harness, "Kill Test");
+    harness.wait_for_screen_contains("KILLED").unwrap();
+    harness.assert_screen_contains("KILLED exit=-1");
+}
+
+/// Test that plugin context menu items are shown only when their `when`
+/// context is set and run the plugin's command when chosen
+#[test]
+fn test_plugin_context_menu_item() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Shout Selection", "Upper-case the selection", "shout_selection", null);
+editor.registerCommand("Arm Shout", "Enable the Shout context menu item", "arm_shout", null);
+editor.addContextMenuItem("editor", "Shout It", "shout_selection", "shout-armed");
+
+globalThis.arm_shout = function(): void {
+    editor.setContext("shout-armed", true);
+    editor.setStatus("ARMED");
+};
+
+globalThis.shout_selection = function(): void {
+    editor.setStatus("SHOUTED");
+};
+"#;
+    fs::write(plugins_dir.join("shout.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.type_text("hello").unwrap();
+    harness.render().unwrap();
+
+    let right_click_text = |harness: &mut EditorTestHarness| {
+        let screen = harness.screen_to_string();
+        let row = screen
+            .lines()
+            .position(|line| line.contains("hello"))
+            .unwrap();
+        let col = screen.lines().nth(row).unwrap().find("hello").unwrap();
+        harness.mouse_right_click(col as u16, row as u16).unwrap();
+    };
+
+    // The item's context isn't set yet
+    right_click_text(&mut harness);
+    harness.assert_screen_contains("Select All");
+    harness.assert_screen_not_contains("Shout It");
+    harness.send_key(KeyCode::Esc, KeyModifiers::NONE).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Arm Shout").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("ARMED").unwrap();
+
+    right_click_text(&mut harness);
+    harness.assert_screen_contains("Shout It");
+    // Plugin items come last: Up wraps around to it
+    harness.send_key(KeyCode::Up, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("SHOUTED").unwrap();
+}
+
+/// Test that plugin menu bar items appear in the menus and submenus named by
+/// their path and run the plugin's command when clicked
+#[test]
+fn test_plugin_menu_bar_item() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Blame Line", "Blame the current line", "blame_line", null);
+editor.addMenuItem("Tools/Git", { label: "Blame Line", command: "blame_line" });
+editor.addMenuItem("Tools", { label: "Removed Item", command: "blame_line" });
+editor.removeMenuItem("Tools", "Removed Item");
+
+globalThis.blame_line = function(): void {
+    editor.setStatus("BLAMED");
+};
+"#;
+    fs::write(plugins_dir.join("blame.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.wait_for_screen_contains("Tools").unwrap();
+
+    let click_text = |harness: &mut EditorTestHarness, text: &str| {
+        let screen = harness.screen_to_string();
+        let (row, line) = screen
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains(text))
+            .unwrap_or_else(|| panic!("'{}' not on screen:\n{}", text, screen));
+        let col = line[..line.find(text).unwrap()].chars().count();
+        harness.mouse_click(col as u16, row as u16).unwrap();
+    };
+
+    // The Tools menu is created for the item, in front of Help
+    let menu_bar = harness.get_row_text(0);
+    let tools = menu_bar.find("Tools").expect("Tools menu in the menu bar");
+    assert!(tools < menu_bar.find("Help").unwrap());
+
+    click_text(&mut harness, "Tools");
+    harness.assert_screen_contains("Git");
+    harness.assert_screen_not_contains("Removed Item");
+    click_text(&mut harness, "Git");
+    click_text(&mut harness, "Blame Line");
+    harness.wait_for_screen_contains("BLAMED").unwrap();
+}
+
+/// setTimeout and setInterval callbacks run from the plugin thread's timers
+#[test]
+fn test_plugin_timers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Start Countdown", "Count down with timers", "start_countdown", null);
+
+globalThis.start_countdown = async function(): Promise<void> {
+    // Cancelled before it fires
+    const never = setTimeout(() => editor.setStatus("NEVER"), 1);
+    clearTimeout(never);
+
+    let remaining = 3;
+    const interval = setInterval(() => {
+        remaining -= 1;
+        if (remaining === 0) {
+            clearInterval(interval);
+            setTimeout((word: string) => editor.setStatus(word), 10, "LIFTOFF");
+        }
+    }, 5);
+
+    // Timers also make awaitable sleeps work
+    await new Promise(resolve => setTimeout(resolve, 1));
+    editor.setStatus("COUNTING");
+};
+"#;
+    fs::write(plugins_dir.join("countdown.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Start Countdown").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("LIFTOFF").unwrap();
+    harness.assert_screen_not_contains("NEVER");
+}
+
+/// runInWorker runs CPU-heavy code off the plugin thread and the editor keeps
+/// handling keys while it does
+#[test]
+fn test_plugin_run_in_worker() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Count Primes", "Count primes in a worker", "count_primes", null);
+
+globalThis.count_primes = async function(): Promise<void> {
+    editor.setStatus("COUNTING");
+    const count = await editor.runInWorker((limit: number) => {
+        let count = 0;
+        for (let n = 2; n < limit; n++) {
+            let prime = true;
+            for (let d = 2; d * d <= n; d++) {
+                if (n % d === 0) { prime = false; break; }
+            }
+            if (prime) count++;
+        }
+        return count;
+    }, [100000]);
+    try {
+        await editor.runInWorker(() => { throw new Error("bad input"); });
+    } catch (e) {
+        editor.setStatus(`PRIMES ${count} / ${(e as Error).message}`);
+    }
+};
+"#;
+    fs::write(plugins_dir.join("primes.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Count Primes").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("typing").unwrap();
+    harness
+        .wait_for_screen_contains("PRIMES 9592 / Error: bad input")
+        .unwrap();
+    harness.assert_screen_contains("typing");
+}
+
+/// A debounced handler runs once for a burst of cursor moves, with the
+/// latest one
+#[test]
+fn test_plugin_debounced_event_handler() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("words.txt");
+    fs::write(&file, "alpha beta gamma\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+let runs = 0;
+
+editor.on("cursor_moved", (data) => {
+    runs += 1;
+    editor.setStatus(`RUNS ${runs} AT ${data.new_position}`);
+}, { debounceMs: 1000 });
+"#;
+    fs::write(plugins_dir.join("debounce.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    for _ in 0..5 {
+        harness
+            .send_key(KeyCode::Right, KeyModifiers::NONE)
+            .unwrap();
+    }
+    harness.wait_for_screen_contains("RUNS 1 AT 5").unwrap();
+}
+
+/// Plugins read a buffer by lines and convert between offsets and
+/// line/column positions
+#[test]
+fn test_plugin_line_based_buffer_access() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("lines.txt");
+    fs::write(&file, "one\r\ntwo\r\nthree\r\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Read Lines", "Read the buffer by lines", "read_lines", null);
+
+globalThis.read_lines = async function(): Promise<void> {
+    const id = editor.getActiveBufferId();
+    const count = await editor.getLineCount(id);
+    const lines = await editor.getBufferLines(id, 1, 10);
+    const pos = await editor.offsetToLineCol(id, 7);
+    const offset = await editor.lineColToOffset(id, 2, 3);
+    let error = "none";
+    try {
+        await editor.lineColToOffset(id, 0, 4);
+    } catch (e) {
+        error = "past end";
+    }
+    editor.setStatus(`COUNT ${count} LINES ${lines.join("|")} POS ${pos.line}:${pos.column} OFFSET ${offset} ERROR ${error}`);
+};
+"#;
+    fs::write(plugins_dir.join("lines.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(160, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Read Lines").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("COUNT 4 LINES two|three| POS 1:2 OFFSET 13 ERROR past end")
+        .unwrap();
+}
+
+/// A marker created by a plugin follows its text when the user edits
+/// before it
+#[test]
+fn test_plugin_marker_follows_edits() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("words.txt");
+    fs::write(&file, "alpha beta gamma\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+let marker = -1;
+
+editor.registerCommand("Mark Beta", "Mark the word beta", "mark_beta", null);
+editor.registerCommand("Show Mark", "Show the marker position", "show_mark", null);
+
+globalThis.mark_beta = function(): void {
+    marker = editor.createMarker(editor.getActiveBufferId(), 6);
+    editor.setStatus("MARKED");
+};
+
+globalThis.show_mark = async function(): Promise<void> {
+    const position = await editor.getMarkerPosition(marker);
+    editor.deleteMarker(marker);
+    const deleted = await editor.getMarkerPosition(marker);
+    editor.setStatus(`MARK AT ${position} THEN ${deleted}`);
+};
+"#;
+    fs::write(plugins_dir.join("marker.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Mark Beta").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("MARKED").unwrap();
+
+    // Cursor is at the start of the file
+    harness.type_text("new ").unwrap();
+    harness.assert_buffer_content("new alpha beta gamma\n");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show Mark").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("MARK AT 10 THEN null")
+        .unwrap();
+}
+
+/// A plugin can set multiple selections, then add and clear cursors
+#[test]
+fn test_plugin_set_selections() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("words.txt");
+    fs::write(&file, "alpha beta gamma\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Select Words", "Select every word", "select_words", null);
+editor.registerCommand("Collapse Cursors", "Keep two cursors", "collapse_cursors", null);
+
+globalThis.select_words = function(): void {
+    editor.setSelections([
+        { anchor: 0, head: 5 },
+        { anchor: 6, head: 10 },
+        { anchor: 16, head: 11 },
+    ]);
+    editor.setStatus("SELECTED");
+};
+
+globalThis.collapse_cursors = function(): void {
+    editor.clearSecondaryCursors();
+    editor.addCursor(0);
+    editor.setStatus("COLLAPSED");
+};
+"#;
+    fs::write(plugins_dir.join("selections.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Select Words").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("SELECTED").unwrap();
+    assert_eq!(harness.cursor_count(), 3);
+
+    harness.type_text("X").unwrap();
+    harness.assert_buffer_content("X X X\n");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Collapse Cursors").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("COLLAPSED").unwrap();
+    assert_eq!(harness.cursor_count(), 2);
+}
+
+/// A plugin with a plugin.json only gets the permissions it declares, and
+/// only after the user allows them
+#[test]
+fn test_plugin_manifest_permission_consent() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let secret = project_root.join("secret.txt");
+    fs::write(&secret, "hunter2").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+    fs::write(
+        plugins_dir.join("plugin.json"),
+        r#"{"capabilities": ["fs-read"]}"#,
+    )
+    .unwrap();
+
+    let test_plugin = format!(
+        r#"
+const editor = getEditor();
+
+editor.registerCommand("Read Secret", "Read the secret file", "read_secret", null);
+
+globalThis.read_secret = function(): void {{
+    try {{
+        editor.writeFile({path:?}, "overwritten");
+    }} catch (e) {{
+        editor.debug(e.message);
+    }}
+    try {{
+        editor.setStatus("SECRET:" + editor.readFile({path:?}));
+    }} catch (e) {{
+        editor.setStatus("BLOCKED");
+    }}
+}};
+"#,
+        path = secret.to_string_lossy()
+    );
+    fs::write(plugins_dir.join("secret_reader.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Read Secret").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    run_command(&mut harness);
+    harness
+        .wait_for_screen_contains("Plugin 'secret_reader' wants to read files")
+        .unwrap();
+    harness.assert_screen_contains("BLOCKED");
+
+    harness.type_text("y").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("Allowed secret_reader to read files")
+        .unwrap();
+    assert_eq!(
+        harness.editor().config().plugins["secret_reader"].permissions,
+        vec!["fs-read"]
+    );
+
+    run_command(&mut harness);
+    harness.wait_for_screen_contains("SECRET:hunter2").unwrap();
+    // fs-write was never declared, so it stays unavailable
+    assert_eq!(fs::read_to_string(&secret).unwrap(), "hunter2");
+}
+
+/// Saving a plugin's file reloads it: the old command handler is replaced
+/// rather than left running alongside the new one
+#[test]
+fn test_plugin_hot_reload_on_file_change() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let plugin_source = |greeting: &str| {
+        format!(
+            r#"
+const editor = getEditor();
+
+editor.registerCommand("Greet", "Show a greeting", "greet", null);
+
+globalThis.greet = function(): void {{
+    editor.setStatus("GREETING:{greeting}");
+}};
+"#
+        )
+    };
+    let plugin_path = plugins_dir.join("greeter.ts");
+    fs::write(&plugin_path, plugin_source("hello")).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 24, Default::default(), project_root)
+            .unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text("Greet").unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+    };
+
+    run_command(&mut harness);
+    harness.wait_for_screen_contains("GREETING:hello").unwrap();
+
+    fs::write(&plugin_path, plugin_source("good morning")).unwrap();
+    harness.advance_time(Duration::from_secs(2));
+    harness
+        .wait_for_screen_contains("Reloaded plugin greeter")
+        .unwrap();
+
+    run_command(&mut harness);
+    harness
+        .wait_for_screen_contains("GREETING:good morning")
+        .unwrap();
+}
+
+/// A plugin gutter column shows per-line text before the line numbers,
+/// shifts the text area over, and disappears when unregistered
+#[test]
+fn test_plugin_gutter_column() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("lines.txt");
+    fs::write(&file, "alpha\nbeta\ngamma\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerGutter("blame", 6);
+editor.registerCommand("Show Blame", "Show blame", "show_blame", null);
+editor.registerCommand("Hide Blame", "Hide blame", "hide_blame", null);
+
+globalThis.show_blame = function(): void {
+    const bufferId = editor.getActiveBufferId();
+    editor.setGutterTexts(bufferId, "blame", [
+        { line: 0, text: "alice" },
+        { line: 2, text: "bartholomew", color: [0, 200, 0] },
+    ]);
+    editor.setStatus("BLAMED");
+};
+
+globalThis.hide_blame = function(): void {
+    editor.unregisterGutter("blame");
+    editor.setStatus("UNBLAMED");
+};
+"#;
+    fs::write(plugins_dir.join("gutter_column.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str, status: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.wait_for_screen_contains(status).unwrap();
+    };
+    let row_of = |harness: &EditorTestHarness, word: &str| {
+        let screen = harness.screen_to_string();
+        let (y, line) = screen
+            .lines()
+            .enumerate()
+            .find(|(_, line)| line.contains(&format!(" {}", word)))
+            .unwrap_or_else(|| panic!("'{}' not on screen:\n{}", word, screen));
+        (y as u16, line.to_string())
+    };
+
+    run_command(&mut harness, "Show Blame", "BLAMED");
+    let (_, alpha_row) = row_of(&harness, "alpha");
+    assert!(alpha_row.starts_with("alice  "), "row: {:?}", alpha_row);
+    let (_, beta_row) = row_of(&harness, "beta");
+    assert!(beta_row.starts_with("       "), "row: {:?}", beta_row);
+    let (gamma_y, gamma_row) = row_of(&harness, "gamma");
+    assert!(gamma_row.starts_with("bartho "), "row: {:?}", gamma_row);
+    assert_eq!(
+        harness
+            .get_cell_style(0, gamma_y)
+            .and_then(|style| style.fg),
+        Some(ratatui::style::Color::Rgb(0, 200, 0))
+    );
+
+    // Clicks land in the text after the wider gutter
+    let (alpha_y, alpha_row) = row_of(&harness, "alpha");
+    let text_x = alpha_row[..alpha_row.find("alpha").unwrap()]
+        .chars()
+        .count() as u16;
+    harness.mouse_click(text_x + 2, alpha_y).unwrap();
+    harness.type_text("Z").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("alZpha");
+
+    run_command(&mut harness, "Hide Blame", "UNBLAMED");
+    let (_, alpha_row) = row_of(&harness, "alZpha");
+    assert!(!alpha_row.contains("alice"), "row: {:?}", alpha_row);
+}
+
+/// Line backgrounds set by a plugin fill whole rows, follow edits, and clear
+/// by namespace
+#[test]
+fn test_plugin_line_backgrounds() {
+    use ratatui::style::Color;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file = project_root.join("lines.txt");
+    fs::write(&file, "alpha\nbeta\ngamma\ndelta\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+editor.registerCommand("Tint Lines", "Tint some lines", "tint_lines", null);
+editor.registerCommand("Clear Tints", "Clear the diff tints", "clear_tints", null);
+
+globalThis.tint_lines = function(): void {
+    const bufferId = editor.getActiveBufferId();
+    editor.setLineBackgrounds(bufferId, "diff", [
+        { line: 0, color: [120, 0, 0] },
+        { line: 2, color: [0, 120, 0] },
+    ]);
+    editor.setLineBackground(bufferId, "review", 1, "editor.diff_add_bg");
+    editor.setStatus("TINTED");
+};
+
+globalThis.clear_tints = function(): void {
+    editor.clearLineBackgrounds(editor.getActiveBufferId(), "diff");
+    editor.setStatus("CLEARED");
+};
+"#;
+    fs::write(plugins_dir.join("line_backgrounds.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(80, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file).unwrap();
+    harness.render().unwrap();
+
+    let run_command = |harness: &mut EditorTestHarness, name: &str, status: &str| {
+        harness
+            .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+            .unwrap();
+        harness.type_text(name).unwrap();
+        harness
+            .send_key(KeyCode::Enter, KeyModifiers::NONE)
+            .unwrap();
+        harness.wait_for_screen_contains(status).unwrap();
+    };
+    // Background well past the end of the line holding `word`
+    let row_bg = |harness: &EditorTestHarness, word: &str| {
+        let screen = harness.screen_to_string();
+        let y = screen
+            .lines()
+            .position(|line| line.contains(&format!(" {}", word)))
+            .unwrap_or_else(|| panic!("'{}' not on screen:\n{}", word, screen));
+        harness
+            .get_cell_style(60, y as u16)
+            .and_then(|style| style.bg)
+    };
+
+    run_command(&mut harness, "Tint Lines", "TINTED");
+    let diff_add_bg = harness.editor().theme().diff_add_bg;
+    assert_eq!(row_bg(&harness, "alpha"), Some(Color::Rgb(120, 0, 0)));
+    assert_eq!(row_bg(&harness, "beta"), Some(diff_add_bg));
+    assert_eq!(row_bg(&harness, "gamma"), Some(Color::Rgb(0, 120, 0)));
+    let untinted = row_bg(&harness, "delta");
+    assert_ne!(untinted, Some(Color::Rgb(0, 120, 0)));
+
+    // A new line above pushes the tints down with their lines
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(row_bg(&harness, "alpha"), Some(Color::Rgb(120, 0, 0)));
+    assert_eq!(row_bg(&harness, "gamma"), Some(Color::Rgb(0, 120, 0)));
+
+    run_command(&mut harness, "Clear Tints", "CLEARED");
+    assert_eq!(row_bg(&harness, "alpha"), untinted);
+    assert_eq!(row_bg(&harness, "beta"), Some(diff_add_bg));
+    assert_eq!(row_bg(&harness, "gamma"), untinted);
+}
+
+/// Focus events tell plugins which split and buffer were left and entered
+#[test]
+fn test_plugin_split_and_buffer_focus_events() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+    let file_a = project_root.join("a.txt");
+    let file_b = project_root.join("b.txt");
+    fs::write(&file_a, "first file\n").unwrap();
+    fs::write(&file_b, "second file\n").unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let test_plugin = r#"
+const editor = getEditor();
+const events: string[] = [];
+
+function record(event: string): void {
+    events.push(event);
+    editor.setStatus(events.slice(-2).join(" | "));
+}
+
+editor.on("split_focused", (data) => {
+    record(`FOCUS ${data.previous_split_id}>${data.split_id} buffer ${data.buffer_id}`);
+});
+editor.on("buffer_activated", (data) => {
+    record(`ACTIVE ${data.previous_buffer_id}>${data.buffer_id} split ${data.split_id}`);
+});
+"#;
+    fs::write(plugins_dir.join("focus_events.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(140, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_a).unwrap();
+    harness.render().unwrap();
+    let buffer_a = harness.editor().active_buffer().0;
+    let first_split = harness.editor().get_active_split().0;
+
+    // Splitting focuses the new split, showing the same buffer
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("split vert").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let second_split = harness.editor().get_active_split().0;
+    assert_ne!(first_split, second_split);
+    harness
+        .wait_for_screen_contains(&format!(
+            "FOCUS {}>{} buffer {}",
+            first_split, second_split, buffer_a
+        ))
+        .unwrap();
+
+    // Opening a file switches the buffer within the split
+    harness.open_file(&file_b).unwrap();
+    let buffer_b = harness.editor().active_buffer().0;
+    harness
+        .wait_for_screen_contains(&format!(
+            "ACTIVE {}>{} split {}",
+            buffer_a, buffer_b, second_split
+        ))
+        .unwrap();
+
+    // Moving to the other split reports both changes
+    harness.editor_mut().next_split();
+    harness
+        .wait_for_screen_contains(&format!(
+            "ACTIVE {}>{} split {} | FOCUS {}>{} buffer {}",
+            buffer_b, buffer_a, first_split, second_split, first_split, buffer_a
+        ))
+        .unwrap();
+}
+
+/// `buffer_changed` carries every edit, so a plugin can keep a copy of the
+/// text in step by applying the changes, with or without a debounce
+#[test]
+fn test_plugin_buffer_changed_deltas() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().join("project_root");
+    fs::create_dir(&project_root).unwrap();
+
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin_lib(&plugins_dir);
+
+    let file_path = project_root.join("mirror.txt");
+    fs::write(&file_path, "hello").unwrap();
+
+    let test_plugin = r#"
+const editor = getEditor();
+
+function apply(text: string, data: EditorEventMap["buffer_changed"]): string {
+    for (const change of data.changes) {
+        text = text.slice(0, change.range.start) + change.text + text.slice(change.range.end);
+    }
+    return text;
+}
+
+let mirror = "hello";
+let debounced = "hello";
+
+editor.on("buffer_changed", (data) => {
+    mirror = apply(mirror, data);
+});
+
+editor.on("buffer_changed", (data) => {
+    debounced = apply(debounced, data);
+    editor.setStatus(`BOTH ${mirror} | ${debounced}`);
+}, { debounceMs: 50 });
+"#;
+    fs::write(plugins_dir.join("mirror_test.ts"), test_plugin).unwrap();
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(100, 24, Default::default(), project_root)
+            .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness.type_text("ab").unwrap();
+    harness
+        .wait_for_screen_contains("BOTH abhello | abhello")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    let content = harness.get_buffer_content().unwrap();
+    assert_ne!(content, "abhello");
+    harness
+        .wait_for_screen_contains(&format!("BOTH {} | {}", content, content))
+        .unwrap();
+
+    // A reload replaces the whole text
+    harness
+        .send_key(KeyCode::Char('s'), KeyModifiers::CONTROL)
+        .unwrap();
+    fs::write(&file_path, "world").unwrap();
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Revert File").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_for_screen_contains("BOTH world | world")
+        .unwrap();
+}
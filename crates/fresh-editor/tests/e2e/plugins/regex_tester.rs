@@ -0,0 +1,87 @@
+//! E2E tests for the Regex Tester plugin
+
+use crate::common::harness::{copy_plugin, EditorTestHarness};
+use crossterm::event::{KeyCode, KeyModifiers};
+use std::fs;
+
+/// Open a file and start the Regex Tester on it
+fn open_regex_tester(content: &str) -> (tempfile::TempDir, EditorTestHarness) {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project_root = temp_dir.path().to_path_buf();
+    let plugins_dir = project_root.join("plugins");
+    fs::create_dir(&plugins_dir).unwrap();
+    copy_plugin(&plugins_dir, "regex_tester");
+
+    let file_path = project_root.join("sample.txt");
+    fs::write(&file_path, content).unwrap();
+
+    let mut harness = EditorTestHarness::with_config_and_working_dir(
+        120,
+        40,
+        fresh::config::Config::default(),
+        project_root,
+    )
+    .unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Regex Tester").unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Regex Tester"))
+        .unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Pattern:").unwrap();
+    (temp_dir, harness)
+}
+
+/// Matches and capture groups are listed while the pattern is typed
+#[test]
+fn test_regex_tester_lists_matches_and_groups() {
+    let (_temp_dir, mut harness) = open_regex_tester("id=12 name=ab\nid=7 name=cd\n");
+
+    harness.type_text("id=(\\d+)").unwrap();
+    harness.wait_for_screen_contains("Matches: 2").unwrap();
+    harness.assert_screen_contains("1 = \"12\"");
+    harness.assert_screen_contains("1 = \"7\"");
+
+    // Named groups are listed by name
+    harness.type_text(" name=(?<who>\\w+)").unwrap();
+    harness.wait_for_screen_contains("who = \"cd\"").unwrap();
+}
+
+/// Flag toggles re-run the pattern and searching the buffer uses it
+#[test]
+fn test_regex_tester_flags_and_buffer_search() {
+    let (_temp_dir, mut harness) = open_regex_tester("Alpha\nalpha\nbeta\n");
+
+    harness.type_text("^alpha$").unwrap();
+    harness.wait_for_screen_contains("Matches: 0").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+
+    // Multiline makes ^/$ match at line boundaries, then ignore case
+    harness
+        .send_key(KeyCode::Char('m'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Matches: 1").unwrap();
+    harness
+        .send_key(KeyCode::Char('i'), KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_screen_contains("Matches: 2").unwrap();
+
+    harness
+        .send_key(KeyCode::Char('b'), KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| h.editor().active_state().buffer.file_path().is_some())
+        .unwrap();
+    harness
+        .wait_until(|h| h.screen_to_string().contains("Found 2 matches"))
+        .unwrap();
+}
@@ -935,3 +935,41 @@ fn test_open_file_prompt_shows_completions_immediately() {
     let screen = harness.screen_to_string();
     println!("Screen after opening prompt:\n{}", screen);
 }
+
+/// Quick Open filters a project with thousands of files in the background,
+/// and confirming picks the best match for what was typed
+#[test]
+fn test_quick_open_filters_large_project_in_background() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let project = temp_dir.path().join("project");
+    for dir in 0..60 {
+        let dir_path = project.join(format!("dir_{dir}"));
+        std::fs::create_dir_all(&dir_path).unwrap();
+        for file in 0..100 {
+            let path = dir_path.join(format!("file_{dir}_{file}.txt"));
+            std::fs::write(path, format!("contents {dir} {file}")).unwrap();
+        }
+    }
+
+    let mut harness =
+        EditorTestHarness::with_config_and_working_dir(120, 30, Default::default(), project)
+            .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    // Drop the ">" command prefix to search files
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::NONE)
+        .unwrap();
+    harness.type_text("file_42_17.txt").unwrap();
+    harness
+        .wait_for_screen_contains("dir_42/file_42_17.txt")
+        .unwrap();
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.wait_for_buffer_content("contents 42 17").unwrap();
+}
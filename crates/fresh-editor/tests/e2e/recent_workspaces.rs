@@ -0,0 +1,112 @@
+//! E2E tests for the Open Recent Workspace picker and workspace pinning
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config_io::DirectoryContext;
+use fresh::services::recent_workspaces::RecentWorkspaces;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn harness_in(dir: &Path, dir_context: &DirectoryContext) -> EditorTestHarness {
+    EditorTestHarness::with_shared_dir_context(
+        120,
+        24,
+        Default::default(),
+        dir.to_path_buf(),
+        dir_context.clone(),
+    )
+    .unwrap()
+}
+
+fn row_of(harness: &EditorTestHarness, text: &str) -> Option<usize> {
+    harness
+        .screen_to_string()
+        .lines()
+        .position(|line| line.contains(text))
+}
+
+/// Pinned workspaces come first, missing ones and the current one are left
+/// out, and choosing one switches the editor to it
+#[test]
+fn test_open_recent_workspace_picker() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let dirs: Vec<PathBuf> = ["alpha", "beta", "current"]
+        .iter()
+        .map(|name| {
+            let dir = root.join(name);
+            fs::create_dir(&dir).unwrap();
+            dir
+        })
+        .collect();
+    let (alpha, beta, current) = (&dirs[0], &dirs[1], &dirs[2]);
+    let dir_context = DirectoryContext::for_testing(&root.join("context"));
+
+    // Opened in this order, so beta is the most recent
+    harness_in(alpha, &dir_context)
+        .editor_mut()
+        .toggle_pin_workspace();
+    harness_in(beta, &dir_context)
+        .editor()
+        .record_recent_workspace();
+    let gone = root.join("gone");
+    let mut workspaces = RecentWorkspaces::load(&dir_context.recent_workspaces_path());
+    workspaces.record(&gone, u64::MAX / 2);
+    workspaces
+        .save(&dir_context.recent_workspaces_path())
+        .unwrap();
+
+    let mut harness = harness_in(current, &dir_context);
+    harness.editor().record_recent_workspace();
+    harness.run_command("Open Recent Workspace").unwrap();
+
+    let alpha_row = row_of(&harness, &alpha.display().to_string()).expect("alpha listed");
+    let beta_row = row_of(&harness, &beta.display().to_string()).expect("beta listed");
+    assert!(alpha_row < beta_row, "pinned workspaces come first");
+    let screen = harness.screen_to_string();
+    assert!(screen.lines().nth(alpha_row).unwrap().contains("Pinned"));
+    assert!(!screen.contains(&gone.display().to_string()));
+    assert!(!screen.contains(&current.display().to_string()));
+
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    assert_eq!(
+        harness.editor_mut().take_restart_dir().as_ref(),
+        Some(alpha)
+    );
+
+    // The missing workspace was dropped from the saved list
+    let saved = RecentWorkspaces::load(&dir_context.recent_workspaces_path());
+    assert!(saved.get(&gone).is_none());
+    assert!(saved.get(alpha).unwrap().pinned);
+}
+
+/// Toggle Pin Workspace pins and unpins the current workspace
+#[test]
+fn test_toggle_pin_workspace() {
+    let temp_dir = TempDir::new().unwrap();
+    let root = temp_dir.path().canonicalize().unwrap();
+    let project = root.join("project");
+    fs::create_dir(&project).unwrap();
+    let dir_context = DirectoryContext::for_testing(&root.join("context"));
+    let is_pinned = || {
+        RecentWorkspaces::load(&dir_context.recent_workspaces_path())
+            .get(&project)
+            .is_some_and(|w| w.pinned)
+    };
+
+    let mut harness = harness_in(&project, &dir_context);
+    harness.run_command("Toggle Pin Workspace").unwrap();
+    assert!(is_pinned());
+    harness.run_command("Toggle Pin Workspace").unwrap();
+    assert!(!is_pinned());
+
+    // Nothing else to open
+    harness.run_command("Open Recent Workspace").unwrap();
+    assert_eq!(
+        harness.editor().get_status_message().map(String::as_str),
+        Some("No recent workspaces")
+    );
+}
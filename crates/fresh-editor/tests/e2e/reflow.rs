@@ -0,0 +1,53 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+fn harness_with_reflow_column(column: usize) -> EditorTestHarness {
+    let mut config = Config::default();
+    config.editor.reflow_column = column;
+    EditorTestHarness::with_config(120, 24, config).unwrap()
+}
+
+/// Reflow Paragraph keeps the language's comment prefix and only touches the
+/// paragraph at the cursor; running it again changes nothing
+#[test]
+fn test_reflow_paragraph_keeps_comment_prefix() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("lib.rs");
+    std::fs::write(
+        &file_path,
+        "// one two three four five six seven\n\nfn main() {}\n",
+    )
+    .unwrap();
+
+    let mut harness = harness_with_reflow_column(20);
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    harness.run_command("Reflow Paragraph").unwrap();
+    let expected = "// one two three\n// four five six\n// seven\n\nfn main() {}\n";
+    assert_eq!(harness.get_buffer_content().unwrap(), expected);
+
+    harness.run_command("Reflow Paragraph").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), expected);
+    harness.assert_screen_contains("Already wrapped at column 20");
+}
+
+/// Reflow Selection re-wraps every paragraph in the selection and indents
+/// list item continuations
+#[test]
+fn test_reflow_selection_wraps_list_items() {
+    let mut harness = harness_with_reflow_column(20);
+    harness
+        .type_text("alpha beta gamma delta\n- item one two three four")
+        .unwrap();
+    harness
+        .send_key(KeyCode::Char('a'), KeyModifiers::CONTROL)
+        .unwrap();
+
+    harness.run_command("Reflow Selection").unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "alpha beta gamma\ndelta\n- item one two three\n  four"
+    );
+}
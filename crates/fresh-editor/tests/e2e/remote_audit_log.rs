@@ -0,0 +1,41 @@
+// E2E tests for the Remote Audit Log command
+
+use crate::common::harness::EditorTestHarness;
+use fresh::services::remote::{AuditLog, AuditSource};
+
+#[test]
+fn test_remote_audit_log_lists_operations() {
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    let audit_log = AuditLog::new();
+    harness.editor_mut().set_remote_audit_log(audit_log.clone());
+
+    audit_log.record(
+        AuditSource::User,
+        "write",
+        "/etc/nginx/nginx.conf",
+        &Ok::<(), String>(()),
+    );
+    audit_log.record(
+        AuditSource::Plugin,
+        "exec",
+        "rg --json needle",
+        &Err::<(), _>("agent disconnected"),
+    );
+
+    harness.run_command("Remote Audit Log").unwrap();
+
+    harness.assert_screen_contains("*Remote Audit Log*");
+    harness.assert_screen_contains("user     write      /etc/nginx/nginx.conf");
+    harness.assert_screen_contains("plugin   exec       rg --json needle");
+    harness.assert_screen_contains("failed: agent disconnected");
+}
+
+#[test]
+fn test_remote_audit_log_requires_remote_session() {
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+
+    harness.run_command("Remote Audit Log").unwrap();
+
+    harness.assert_screen_contains("Not editing a remote host");
+    harness.assert_screen_not_contains("*Remote Audit Log*");
+}
@@ -291,3 +291,34 @@ fn test_remote_large_file_edits_beginning_middle_end() {
         );
     }
 }
+
+/// Remote files are watched by the agent, and its change notifications
+/// auto-revert the buffer just like local file changes
+#[test]
+fn test_remote_auto_revert_via_watch() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let fs_arc = Arc::new(fs);
+
+    let file_path = temp_dir.path().join("remote_watched.txt");
+    std::fs::write(&file_path, "Remote content v1").unwrap();
+
+    let mut harness =
+        EditorTestHarness::create(80, 24, HarnessOptions::new().with_filesystem(fs_arc)).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.assert_buffer_content("Remote content v1");
+
+    // Run past the poll interval so the editor registers the watch, then
+    // give the agent time to take its first snapshot
+    harness.advance_time(std::time::Duration::from_secs(3));
+    harness.process_async_and_render().unwrap();
+    std::thread::sleep(std::time::Duration::from_millis(700));
+
+    std::fs::write(&file_path, "Remote content v2, changed on the host").unwrap();
+
+    harness
+        .wait_until(|h| h.get_buffer_content().unwrap() == "Remote content v2, changed on the host")
+        .expect("Remote watch should auto-revert the buffer");
+}
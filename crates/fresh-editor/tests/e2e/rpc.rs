@@ -0,0 +1,49 @@
+// E2E tests for the local RPC server used by fresh-remote
+
+#![cfg(unix)]
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use fresh::services::rpc::{send_request, RpcMethod};
+use std::collections::HashMap;
+
+#[test]
+fn test_rpc_open_file_and_run_command() {
+    let mut config = Config::default();
+    config.editor.rpc_server = true;
+    let mut harness = EditorTestHarness::with_temp_project_and_config(80, 24, config).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "one\ntwo\nthree\n").unwrap();
+    let socket = harness.editor().rpc_socket_path().unwrap().to_path_buf();
+
+    let client = std::thread::spawn(move || {
+        let open = send_request(
+            &socket,
+            &RpcMethod::Open {
+                path: "notes.txt".into(),
+                line: Some(3),
+                column: None,
+            },
+        )
+        .unwrap();
+        let unknown = send_request(
+            &socket,
+            &RpcMethod::Command {
+                action: "no_such_action".to_string(),
+                args: HashMap::new(),
+            },
+        )
+        .unwrap();
+        (open, unknown)
+    });
+    harness.wait_until(|_| client.is_finished()).unwrap();
+    let (open, unknown) = client.join().unwrap();
+
+    assert_eq!(open.error, None);
+    assert_eq!(harness.get_buffer_content().unwrap(), "one\ntwo\nthree\n");
+    assert_eq!(harness.cursor_position(), 8);
+    assert_eq!(
+        unknown.error.as_deref(),
+        Some("unknown action 'no_such_action'")
+    );
+}
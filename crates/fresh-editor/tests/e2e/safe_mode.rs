@@ -0,0 +1,34 @@
+// E2E tests for --safe-mode
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::app::SafeMode;
+
+#[test]
+fn test_safe_mode_banner_and_reenable() {
+    let mut harness = EditorTestHarness::new(120, 24).unwrap();
+    harness.editor_mut().enable_safe_mode(SafeMode::new());
+    harness.render().unwrap();
+    harness.assert_screen_contains("Safe mode: user config, user themes, plugins disabled");
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Safe Mode: Re-enable").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Re-enable:");
+
+    harness.type_text("themes").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    let safe_mode = harness.editor().safe_mode().unwrap();
+    assert!(!safe_mode.themes_disabled);
+    assert!(safe_mode.config_disabled && safe_mode.plugins_disabled);
+    harness.assert_screen_contains("Safe mode: user config, plugins disabled");
+}
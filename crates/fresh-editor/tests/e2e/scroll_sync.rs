@@ -0,0 +1,135 @@
+//! E2E tests for scroll sync between splits started from the command palette
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+fn press(harness: &mut EditorTestHarness, code: KeyCode, modifiers: KeyModifiers) {
+    harness.send_key(code, modifiers).unwrap();
+    harness.render().unwrap();
+}
+
+/// Project with a 100 line file and a 400 line file
+fn create_files(project_dir: &Path) -> (PathBuf, PathBuf) {
+    let short = project_dir.join("short.txt");
+    let long = project_dir.join("long.txt");
+    let short_content: String = (1..=100).map(|i| format!("Short {:03}\n", i)).collect();
+    let long_content: String = (1..=400).map(|i| format!("Long {:03}\n", i)).collect();
+    std::fs::write(&short, short_content).unwrap();
+    std::fs::write(&long, long_content).unwrap();
+    (short, long)
+}
+
+/// Open the short file on the left and the long file on the right
+fn open_side_by_side(harness: &mut EditorTestHarness, short: &Path, long: &Path) {
+    harness.open_file(short).unwrap();
+    harness.run_command("Split Vertical").unwrap();
+    harness.open_file(long).unwrap();
+    harness.render().unwrap();
+}
+
+/// Splits without anchors scroll by the same percentage of their length
+#[test]
+fn test_sync_scroll_all_splits_is_proportional() {
+    let temp_dir = TempDir::new().unwrap();
+    let (short, long) = create_files(temp_dir.path());
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    open_side_by_side(&mut harness, &short, &long);
+
+    harness.run_command("Sync Scroll of All Splits").unwrap();
+    harness.assert_screen_contains("Scrolling 2 splits together");
+
+    // The end of the long file brings the short one to its end too
+    press(&mut harness, KeyCode::End, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Long 400");
+    harness.assert_screen_contains("Short 100");
+    harness.assert_screen_not_contains("Short 050");
+
+    press(&mut harness, KeyCode::Home, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Long 001");
+    harness.assert_screen_contains("Short 001");
+
+    // After stopping, the other split stays where it is
+    harness.run_command("Stop Scroll Sync").unwrap();
+    harness.assert_screen_contains("Splits no longer scroll together");
+    press(&mut harness, KeyCode::End, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Long 400");
+    harness.assert_screen_contains("Short 001");
+}
+
+/// Toggling scroll sync in two splits makes them scroll together
+#[test]
+fn test_toggle_scroll_sync_for_splits() {
+    let temp_dir = TempDir::new().unwrap();
+    let (short, long) = create_files(temp_dir.path());
+
+    let mut harness = EditorTestHarness::new(120, 30).unwrap();
+    open_side_by_side(&mut harness, &short, &long);
+
+    harness.run_command("Toggle Scroll Sync for Split").unwrap();
+    harness.assert_screen_contains("Split marked for scroll sync");
+    press(&mut harness, KeyCode::End, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Short 001");
+
+    harness.run_command("Previous Split").unwrap();
+    harness.run_command("Toggle Scroll Sync for Split").unwrap();
+    harness.assert_screen_contains("Split scrolls together with 1 other split(s)");
+
+    // The short file is now in the active split and drives the long one
+    press(&mut harness, KeyCode::Home, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Long 001");
+    press(&mut harness, KeyCode::End, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Short 100");
+    harness.assert_screen_contains("Long 400");
+
+    harness.run_command("Toggle Scroll Sync for Split").unwrap();
+    harness.assert_screen_contains("Split no longer scrolls with other splits");
+    press(&mut harness, KeyCode::Home, KeyModifiers::CONTROL);
+    harness.assert_screen_contains("Short 001");
+    harness.assert_screen_contains("Long 400");
+}
+
+/// Scroll sync groups are saved in the session and restored with the splits
+#[test]
+fn test_scroll_sync_restored_from_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+    let (short, long) = create_files(&project_dir);
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            120,
+            30,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        open_side_by_side(&mut harness, &short, &long);
+        harness.run_command("Sync Scroll of All Splits").unwrap();
+
+        let session = harness.editor().capture_session();
+        assert_eq!(session.scroll_sync_groups.len(), 1);
+        assert_eq!(session.scroll_sync_groups[0].splits.len(), 2);
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            120,
+            30,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+        assert!(harness.editor_mut().try_restore_session().unwrap());
+        harness.render().unwrap();
+
+        press(&mut harness, KeyCode::End, KeyModifiers::CONTROL);
+        harness.assert_screen_contains("Long 400");
+        harness.assert_screen_contains("Short 100");
+    }
+}
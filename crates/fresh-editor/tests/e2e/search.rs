@@ -2435,3 +2435,63 @@ fn test_esc_clears_search_highlights() {
         highlight_count_after
     );
 }
+
+/// Project search streams matches into the results buffer, grouped by file in
+/// path order, and Enter jumps to the match under the cursor
+#[test]
+fn test_search_in_project_streams_results() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 30).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    std::fs::create_dir(project_dir.join("src")).unwrap();
+    std::fs::write(
+        project_dir.join("src/lib.rs"),
+        "fn one() {}\nlet needle = 1;\n",
+    )
+    .unwrap();
+    std::fs::write(project_dir.join("notes.txt"), "a needle here\n").unwrap();
+    std::fs::write(project_dir.join("other.txt"), "nothing\n").unwrap();
+
+    harness
+        .send_key(
+            KeyCode::Char('f'),
+            KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+        )
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Search in project:");
+
+    harness.type_text("needle").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .wait_until(|h| {
+            h.get_buffer_content()
+                .is_some_and(|c| c.contains("2 matches in 2 files"))
+        })
+        .unwrap();
+
+    let content = harness.get_buffer_content().unwrap();
+    let notes = content.find("notes.txt").unwrap();
+    let lib = content.find("src/lib.rs").unwrap();
+    assert!(
+        notes < lib,
+        "results should be ordered by path:\n{}",
+        content
+    );
+    assert!(content.contains("     2:5: let needle = 1;"));
+    assert!(!content.contains("other.txt"));
+
+    // Jump to the match in src/lib.rs
+    let line = content[..lib].matches('\n').count() + 1;
+    harness.editor_mut().goto_line_col(line + 1, None);
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(
+        harness.get_buffer_content().unwrap(),
+        "fn one() {}\nlet needle = 1;\n"
+    );
+    assert_eq!(harness.cursor_position(), 16);
+}
@@ -492,6 +492,61 @@ fn test_session_preserves_active_tab() {
     }
 }
 
+/// Test that the most-recently-used tab order survives a session restore
+#[test]
+fn test_session_preserves_tab_mru_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let project_dir = temp_dir.path().join("project");
+    std::fs::create_dir(&project_dir).unwrap();
+
+    let files: Vec<_> = ["a.txt", "b.txt", "c.txt"]
+        .iter()
+        .map(|name| {
+            let path = project_dir.join(name);
+            std::fs::write(&path, format!("File {name}")).unwrap();
+            path
+        })
+        .collect();
+
+    // First session: tab order a, b, c; most recently used a, c, b
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        for file in &files {
+            harness.open_file(file).unwrap();
+        }
+        harness.open_file(&files[0]).unwrap();
+        harness.assert_buffer_content("File a.txt");
+
+        harness.editor_mut().save_session().unwrap();
+    }
+
+    // Second session: Ctrl+Tab goes to the previously used tab, not the next one
+    {
+        let mut harness = EditorTestHarness::with_config_and_working_dir(
+            80,
+            24,
+            Config::default(),
+            project_dir.clone(),
+        )
+        .unwrap();
+
+        harness.editor_mut().try_restore_session().unwrap();
+        harness.assert_buffer_content("File a.txt");
+
+        harness
+            .send_key(KeyCode::Tab, KeyModifiers::CONTROL)
+            .unwrap();
+        harness.assert_buffer_content("File c.txt");
+    }
+}
+
 /// Helper: Create a vertical split via command palette
 fn split_vertical(harness: &mut EditorTestHarness) {
     harness
@@ -1916,12 +1916,12 @@ fn test_entry_dialog_focus_indicator() {
     harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     harness.render().unwrap();
 
-    // Now "Comment Prefix" should be focused with ">" indicator
+    // Now "Code Actions On Save" should be focused with ">" indicator
     // May have modified indicator if value differs from default
     let screen = harness.screen_to_string();
     assert!(
-        screen.contains(">  Comment Prefix") || screen.contains(">● Comment Prefix"),
-        "Focus indicator '>' should appear before Comment Prefix. Screen:\n{}",
+        screen.contains(">  Code Actions On Save") || screen.contains(">● Code Actions On Save"),
+        "Focus indicator '>' should appear before Code Actions On Save. Screen:\n{}",
         screen
     );
 
@@ -1953,7 +1953,8 @@ fn test_entry_dialog_add_new_textlist_item() {
     harness.assert_screen_contains("Edit Value");
 
     // Navigate to Extensions section which has "[+] Add new"
-    // Fields in order: Key, Auto Indent, Comment Prefix, Extensions (3 downs)
+    // Fields in order: Key (read-only), Auto Indent, Code Actions On Save,
+    // Comment Prefix, Extensions (3 downs)
     for _ in 0..3 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
@@ -2027,7 +2028,8 @@ fn test_entry_dialog_delete_textlist_item() {
     harness.assert_screen_contains("Edit Value");
 
     // Navigate to Extensions section which has existing items
-    // Fields in order: Key, Auto Indent, Comment Prefix, Extensions (3 downs)
+    // Fields in order: Key (read-only), Auto Indent, Code Actions On Save,
+    // Comment Prefix, Extensions (3 downs)
     for _ in 0..3 {
         harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
     }
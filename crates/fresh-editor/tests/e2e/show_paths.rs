@@ -0,0 +1,56 @@
+// E2E tests for the Show Paths command
+
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+use fresh::config_io::DirectoryContext;
+
+#[test]
+fn test_show_paths_lists_directories_and_sources() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let dir_context = DirectoryContext::for_testing(temp.path());
+    let working_dir = temp.path().join("project");
+    std::fs::create_dir_all(&working_dir).unwrap();
+    let mut harness = EditorTestHarness::with_shared_dir_context(
+        200,
+        40,
+        Config::default(),
+        working_dir,
+        dir_context.clone(),
+    )
+    .unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show Paths").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("*Paths*");
+    harness.assert_screen_contains(&format!(
+        "Config:     {} (system default)",
+        dir_context.config_dir.display()
+    ));
+    harness.assert_screen_contains(&format!(
+        "Data:       {} (system default)",
+        dir_context.data_dir.display()
+    ));
+    harness.assert_screen_contains(&format!("Cache:      {}", dir_context.cache_dir.display()));
+
+    let shown = harness.screen_to_string().matches("*Paths*").count();
+
+    // Running it again switches to the same buffer
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Show Paths").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    let screen = harness.screen_to_string();
+    assert_eq!(screen.matches("*Paths*").count(), shown, "{}", screen);
+}
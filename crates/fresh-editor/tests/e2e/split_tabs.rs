@@ -818,3 +818,118 @@ fn test_close_tab_with_multiple_tabs_removes_tab_only() {
         screen_after_close
     );
 }
+
+/// Test that tabs with the same file name show their workspace-relative paths
+#[test]
+fn test_duplicate_file_names_show_relative_paths_in_tabs() {
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+    for dir in ["api", "web"] {
+        std::fs::create_dir_all(project_dir.join(dir)).unwrap();
+        std::fs::write(project_dir.join(dir).join("mod.rs"), dir).unwrap();
+    }
+    std::fs::write(project_dir.join("main.rs"), "main").unwrap();
+
+    harness
+        .open_file(&project_dir.join("api").join("mod.rs"))
+        .unwrap();
+    harness.open_file(&project_dir.join("main.rs")).unwrap();
+    harness.render().unwrap();
+    let tabs = harness.get_screen_row(1);
+    assert!(tabs.contains(" mod.rs "), "Tabs: {}", tabs);
+
+    harness
+        .open_file(&project_dir.join("web").join("mod.rs"))
+        .unwrap();
+    harness.render().unwrap();
+    let tabs = harness.get_screen_row(1);
+    let sep = std::path::MAIN_SEPARATOR;
+    assert!(tabs.contains(&format!("api{sep}mod.rs")), "Tabs: {}", tabs);
+    assert!(tabs.contains(&format!("web{sep}mod.rs")), "Tabs: {}", tabs);
+    assert!(tabs.contains(" main.rs "), "Tabs: {}", tabs);
+}
+
+/// Test that Ctrl+Tab walks the split's tabs in most-recently-used order
+#[test]
+fn test_ctrl_tab_cycles_tabs_in_mru_order() {
+    let temp_dir = TempDir::new().unwrap();
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    for n in 1..=4 {
+        let path = temp_dir.path().join(format!("file{n}.txt"));
+        std::fs::write(&path, format!("Content {n}")).unwrap();
+        harness.open_file(&path).unwrap();
+    }
+    harness.render().unwrap();
+    harness.assert_buffer_content("Content 4");
+
+    // Repeated presses go deeper into the history instead of toggling
+    for expected in ["Content 3", "Content 2", "Content 1"] {
+        harness
+            .send_key(KeyCode::Tab, KeyModifiers::CONTROL)
+            .unwrap();
+        harness.assert_buffer_content(expected);
+    }
+
+    // Another key ends the walk; the tab it started from is now the most recent
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Tab, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("Content 4");
+
+    // Ctrl+Shift+Tab walks the other way, starting with the least recently used tab
+    harness
+        .send_key(KeyCode::Right, KeyModifiers::NONE)
+        .unwrap();
+    harness
+        .send_key(KeyCode::Tab, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        .unwrap();
+    harness.assert_buffer_content("Content 2");
+}
+
+/// Test that Move Tab to Split opens the tab in a new split and removes it from the old one
+#[test]
+fn test_move_tab_to_new_split() {
+    let temp_dir = TempDir::new().unwrap();
+    let file1 = temp_dir.path().join("file1.txt");
+    let file2 = temp_dir.path().join("file2.txt");
+    std::fs::write(&file1, "Content 1").unwrap();
+    std::fs::write(&file2, "Content 2").unwrap();
+
+    let mut harness = EditorTestHarness::new(100, 24).unwrap();
+    harness.open_file(&file1).unwrap();
+    harness.open_file(&file2).unwrap();
+    harness.render().unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("move tab to split").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("New split");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_buffer_content("Content 2");
+    let areas = harness.editor().get_split_areas();
+    assert_eq!(areas.len(), 2, "Expected two splits");
+    assert_ne!(areas[0].1, areas[1].1, "Each split shows its own tab");
+
+    // The old split only keeps file1.txt, so moving back closes it
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("move tab to split").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Split 1: file1.txt");
+}
@@ -365,6 +365,52 @@ fn test_cannot_toggle_maximize_single_split() {
     harness.assert_screen_contains("Cannot maximize");
 }
 
+/// Test that a zoomed split is restored when focus moves to another split
+#[test]
+fn test_toggle_zoom_unzooms_on_split_navigation() {
+    let mut harness = EditorTestHarness::new(120, 40).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("split vert").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.editor().get_split_areas().len(), 2);
+
+    // Zoom the active split via command palette
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("toggle zoom").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("[Zoomed]");
+    assert_eq!(harness.editor().get_split_areas().len(), 1);
+
+    // Moving to the other split restores the layout
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.render().unwrap();
+    harness.type_text("next split").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_not_contains("[Zoomed]");
+    assert_eq!(harness.editor().get_split_areas().len(), 2);
+    assert_eq!(harness.editor().get_split_count(), 2);
+}
+
 /// Test that closing the last buffer in a split closes the split (if other splits exist)
 #[test]
 fn test_close_last_buffer_in_split_closes_split() {
@@ -608,3 +654,42 @@ fn test_close_terminal_in_split_closes_split() {
     // Terminal should be gone
     harness.assert_screen_not_contains("Terminal");
 }
+
+/// Test that a split can be drawn with its own theme
+#[test]
+fn test_split_theme_overrides_editor_theme() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("hello").unwrap();
+    harness.editor_mut().split_pane_vertical();
+    harness.render().unwrap();
+
+    let editor_bg = harness.editor().theme().editor_bg;
+    let content_bg = |harness: &EditorTestHarness, x| harness.get_cell_style(x, 10).unwrap().bg;
+    assert_eq!(content_bg(&harness, 20), Some(editor_bg));
+    assert_eq!(content_bg(&harness, 60), Some(editor_bg));
+
+    // The new split is active and gets the other theme
+    let split = harness.editor().get_active_split();
+    let other = if harness.editor().theme().name == "light" {
+        "dark"
+    } else {
+        "light"
+    };
+    harness
+        .editor_mut()
+        .set_split_theme(split, Some(other.to_string()));
+    harness.render().unwrap();
+    assert_eq!(content_bg(&harness, 20), Some(editor_bg));
+    assert_ne!(content_bg(&harness, 60), Some(editor_bg));
+
+    // Unknown themes leave the split as it was
+    harness
+        .editor_mut()
+        .set_split_theme(split, Some("no-such-theme".to_string()));
+    harness.render().unwrap();
+    assert_ne!(content_bg(&harness, 60), Some(editor_bg));
+
+    harness.editor_mut().set_split_theme(split, None);
+    harness.render().unwrap();
+    assert_eq!(content_bg(&harness, 60), Some(editor_bg));
+}
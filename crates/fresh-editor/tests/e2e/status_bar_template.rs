@@ -0,0 +1,66 @@
+use crate::common::harness::{layout, EditorTestHarness};
+use fresh::config::{Config, StatusBarSegmentConfig};
+use fresh::services::plugins::api::PluginCommand;
+
+fn template_config(template: &str) -> Config {
+    let mut config = Config::default();
+    config.editor.status_bar_template = template.to_string();
+    config
+}
+
+/// A template replaces the built-in layout and right-aligns everything after '…'
+#[test]
+fn test_status_bar_template_layout() {
+    let mut harness =
+        EditorTestHarness::with_config(80, 24, template_config("{pos} | custom … {modified}"))
+            .unwrap();
+    harness.render().unwrap();
+    let status = harness.get_status_bar();
+    assert!(status.starts_with("Ln 1, Col 1 | custom"), "{status:?}");
+    assert!(!status.contains("[+]"), "{status:?}");
+
+    harness.type_text("abc").unwrap();
+    harness.render().unwrap();
+    let status = harness.get_status_bar();
+    assert!(status.starts_with("Ln 1, Col 4 | custom"), "{status:?}");
+    assert!(status.trim_end().ends_with("[+]"), "{status:?}");
+}
+
+/// Plugins fill named segments, and clicking a segment runs its command
+#[test]
+fn test_status_bar_plugin_segment_runs_command() {
+    let mut config = template_config("{pos} {branch}");
+    config.editor.status_bar_segments.insert(
+        "branch".to_string(),
+        StatusBarSegmentConfig {
+            command: Some("command_palette".to_string()),
+            min_width: None,
+        },
+    );
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetStatusBarSegment {
+            name: "branch".to_string(),
+            text: "main".to_string(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    let status = harness.get_status_bar();
+    assert!(status.starts_with("Ln 1, Col 1 main"), "{status:?}");
+
+    let row = layout::status_bar_row(24) as u16;
+    harness.mouse_click(13, row).unwrap();
+    assert!(harness.editor().is_prompting());
+
+    // Empty text removes the segment
+    harness
+        .editor_mut()
+        .handle_plugin_command(PluginCommand::SetStatusBarSegment {
+            name: "branch".to_string(),
+            text: String::new(),
+        })
+        .unwrap();
+    harness.render().unwrap();
+    assert!(!harness.get_status_bar().contains("main"));
+}
@@ -0,0 +1,75 @@
+//! E2E tests for sticky scroll
+
+use crate::common::harness::EditorTestHarness;
+use fresh::config::Config;
+use tempfile::TempDir;
+
+fn sticky_config() -> Config {
+    let mut config = Config::default();
+    config.editor.sticky_scroll = true;
+    config
+}
+
+/// An impl containing a long method, so scrolling lands inside both scopes
+fn rust_source() -> String {
+    let mut source = String::from("struct Foo;\n\nimpl Foo {\n    fn long(&self) {\n");
+    for i in 0..60 {
+        source.push_str(&format!("        let x{} = {};\n", i, i));
+    }
+    source.push_str("    }\n}\n");
+    source
+}
+
+/// Scrolling into a method body pins the enclosing impl and fn lines
+#[test]
+fn test_sticky_scroll_pins_enclosing_scopes() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("foo.rs");
+    std::fs::write(&file_path, rust_source()).unwrap();
+
+    let mut harness = EditorTestHarness::with_config(80, 24, sticky_config()).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+
+    let (first_row, _) = harness.content_area_rows();
+    assert!(harness.get_screen_row(first_row).contains("struct Foo;"));
+
+    for _ in 0..5 {
+        harness.mouse_scroll_down(40, 10).unwrap();
+    }
+    harness.render().unwrap();
+    assert!(harness.top_line_number() > 4);
+
+    assert!(harness.get_screen_row(first_row).contains("impl Foo {"));
+    assert!(harness
+        .get_screen_row(first_row + 1)
+        .contains("    fn long(&self) {"));
+    assert!(harness.get_screen_row(first_row + 2).contains("let x"));
+
+    // Clicking a pinned line jumps to it
+    harness.mouse_click(20, first_row as u16 + 1).unwrap();
+    harness.render().unwrap();
+    let source = rust_source();
+    let fn_line = source.find("    fn long").unwrap();
+    assert_eq!(harness.cursor_position(), fn_line);
+    assert!(harness.top_line_number() <= 3);
+}
+
+/// Without the setting nothing is pinned
+#[test]
+fn test_sticky_scroll_disabled_by_default() {
+    let temp_dir = TempDir::new().unwrap();
+    let file_path = temp_dir.path().join("foo.rs");
+    std::fs::write(&file_path, rust_source()).unwrap();
+
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.open_file(&file_path).unwrap();
+    harness.render().unwrap();
+    for _ in 0..5 {
+        harness.mouse_scroll_down(40, 10).unwrap();
+    }
+    harness.render().unwrap();
+
+    let (first_row, _) = harness.content_area_rows();
+    assert!(!harness.get_screen_row(first_row).contains("impl Foo {"));
+}
@@ -0,0 +1,70 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+const CTRL_ALT: KeyModifiers = KeyModifiers::CONTROL.union(KeyModifiers::ALT);
+
+/// Ctrl+Alt+Left/Right stop at camelCase and snake_case boundaries
+#[test]
+fn test_subword_movement_keys() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("parseHTTPRequest_v2").unwrap();
+
+    let mut stops = Vec::new();
+    for _ in 0..4 {
+        harness.send_key(KeyCode::Left, CTRL_ALT).unwrap();
+        stops.push(harness.cursor_position());
+    }
+    assert_eq!(stops, vec![17, 9, 5, 0]);
+
+    harness.send_key(KeyCode::Right, CTRL_ALT).unwrap();
+    assert_eq!(harness.cursor_position(), 5);
+    harness.send_key(KeyCode::Right, CTRL_ALT).unwrap();
+    assert_eq!(harness.cursor_position(), 9);
+}
+
+/// Alt+Backspace deletes only the last part of an identifier
+#[test]
+fn test_delete_subword_backward() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+    harness.type_text("let userName").unwrap();
+
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::ALT)
+        .unwrap();
+    harness.assert_buffer_content("let user");
+
+    // Plain word deletion is unchanged
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("let ");
+}
+
+/// With `subword_navigation` enabled the regular word motions use subwords
+#[test]
+fn test_subword_navigation_config_remaps_word_motions() {
+    let mut config = Config::default();
+    config.editor.subword_navigation = true;
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+    harness.type_text("snake_case_name").unwrap();
+
+    harness
+        .send_key(KeyCode::Left, KeyModifiers::CONTROL)
+        .unwrap();
+    assert_eq!(harness.cursor_position(), 11);
+
+    harness
+        .send_key(KeyCode::Left, KeyModifiers::CONTROL | KeyModifiers::SHIFT)
+        .unwrap();
+    assert_eq!(harness.get_selected_text(), "case_");
+
+    harness
+        .send_key(KeyCode::Delete, KeyModifiers::NONE)
+        .unwrap();
+    harness.send_key(KeyCode::End, KeyModifiers::NONE).unwrap();
+    harness
+        .send_key(KeyCode::Backspace, KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("snake_");
+}
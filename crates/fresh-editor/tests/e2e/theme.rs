@@ -291,3 +291,41 @@ fn test_theme_with_underscore_variant() {
     // Should still load high-contrast theme (accepts both - and _)
     assert_eq!(theme.name, "high-contrast");
 }
+
+#[test]
+fn test_import_base16_theme_from_palette() {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    let mut harness = EditorTestHarness::with_temp_project(100, 24).unwrap();
+    let project_dir = harness.project_dir().unwrap();
+
+    let mut yaml = String::from("scheme: \"Tomorrow Night\"\nauthor: \"Chris Kempson\"\n");
+    let colors = [
+        "1d1f21", "282a2e", "373b41", "969896", "b4b7b4", "c5c8c6", "e0e0e0", "ffffff", "cc6666",
+        "de935f", "f0c674", "b5bd68", "8abeb7", "81a2be", "b294bb", "a3685a",
+    ];
+    for (i, color) in colors.iter().enumerate() {
+        yaml.push_str(&format!("base{:02X}: \"{}\"\n", i, color));
+    }
+    std::fs::write(project_dir.join("tomorrow-night.yaml"), yaml).unwrap();
+
+    harness
+        .send_key(KeyCode::Char('p'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.type_text("Import Base16").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Base16 scheme file:");
+
+    harness.type_text("tomorrow-night.yaml").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+
+    harness.assert_screen_contains("Imported theme 'tomorrow-night'");
+    let theme = harness.editor().theme();
+    assert_eq!(theme.name, "tomorrow-night");
+    assert_eq!(theme.editor_bg, Color::Rgb(0x1d, 0x1f, 0x21));
+}
@@ -503,3 +503,45 @@ fn test_undo_to_empty_after_save_as() {
         final_content
     );
 }
+
+/// Test that edits replaced after an undo are kept as a branch in the Undo Tree
+/// and can be restored from it
+#[test]
+fn test_undo_tree_restores_replaced_branch() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.type_text("abc").unwrap();
+    for _ in 0..2 {
+        harness
+            .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+            .unwrap();
+    }
+    harness.assert_buffer_content("a");
+
+    // Typing after the undo starts a new branch instead of dropping "bc"
+    harness.type_text("xy").unwrap();
+    harness.assert_buffer_content("axy");
+    assert_eq!(harness.editor().active_event_log().branch_count(), 1);
+
+    harness.editor_mut().show_undo_tree();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Undo Tree");
+    harness.assert_screen_contains("[1] from edit 1: 2 edits");
+
+    // Move to the branch line and restore it
+    for _ in 0..5 {
+        harness.send_key(KeyCode::Down, KeyModifiers::NONE).unwrap();
+    }
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    harness.assert_buffer_content("abc");
+
+    // The replaced "xy" edits are now the alternate branch
+    assert_eq!(harness.editor().active_event_log().branch_count(), 1);
+    harness
+        .send_key(KeyCode::Char('z'), KeyModifiers::CONTROL)
+        .unwrap();
+    harness.assert_buffer_content("ab");
+}
@@ -0,0 +1,85 @@
+use crate::common::harness::EditorTestHarness;
+use crossterm::event::{KeyCode, KeyModifiers};
+use fresh::config::Config;
+
+/// Picking by name and by codepoint inserts the character, and the picked
+/// character is listed first the next time the picker opens
+#[test]
+fn test_insert_unicode_character_by_name_and_codepoint() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.run_command("Insert Unicode Character").unwrap();
+    harness.type_text("rightwards double arrow").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("U+21D2");
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "⇒");
+
+    // A codepoint outside the curated table is still offered
+    harness.run_command("Insert Unicode Character").unwrap();
+    harness.type_text("U+1D11E").unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "⇒𝄞");
+
+    // Recently inserted characters come first
+    harness.run_command("Insert Unicode Character").unwrap();
+    harness.render().unwrap();
+    harness
+        .send_key(KeyCode::Enter, KeyModifiers::NONE)
+        .unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "⇒𝄞𝄞");
+}
+
+/// Filtering matches the category shown next to each character
+#[test]
+fn test_insert_unicode_character_by_category() {
+    let mut harness = EditorTestHarness::new(80, 24).unwrap();
+
+    harness.run_command("Insert Unicode Character").unwrap();
+    harness.type_text("Food & Drink").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("red apple");
+    harness.assert_screen_not_contains("rightwards arrow");
+}
+
+/// Digraphs come from the built-in table, in either order, and the config
+/// table overrides it
+#[test]
+fn test_insert_digraph() {
+    let mut config = Config::default();
+    config
+        .editor
+        .digraphs
+        .insert("->".to_string(), "⟶".to_string());
+    let mut harness = EditorTestHarness::with_config(80, 24, config).unwrap();
+
+    harness.run_command("Insert Digraph").unwrap();
+    harness.type_text("a:").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "ä");
+
+    harness.run_command("Insert Digraph").unwrap();
+    harness.type_text("uE").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "ä€");
+
+    harness.run_command("Insert Digraph").unwrap();
+    harness.type_text("->").unwrap();
+    harness.render().unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "ä€⟶");
+
+    // An unknown digraph inserts nothing and closes the prompt
+    harness.run_command("Insert Digraph").unwrap();
+    harness.type_text("qq").unwrap();
+    harness.render().unwrap();
+    harness.assert_screen_contains("Unknown digraph: qq");
+    harness.type_text("x").unwrap();
+    assert_eq!(harness.get_buffer_content().unwrap(), "ä€⟶x");
+}
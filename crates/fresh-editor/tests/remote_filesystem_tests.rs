@@ -9,9 +9,11 @@
 //! - RemoteFileSystem for file operations
 
 use fresh::model::buffer::TextBuffer;
-use fresh::model::filesystem::{FileSystem, WriteOp};
-use fresh::services::remote::{spawn_local_agent, RemoteFileSystem};
-use std::sync::Arc;
+use fresh::model::filesystem::{FileSystem, WatchCallback, WriteOp};
+use fresh::services::remote::{spawn_local_agent, AuditLog, AuditSource, RemoteFileSystem};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
 
 /// Creates a RemoteFileSystem using production code
 fn create_test_filesystem() -> Option<(RemoteFileSystem, tempfile::TempDir, tokio::runtime::Runtime)>
@@ -511,6 +513,111 @@ fn test_write_patched_preserves_permissions() {
     }
 }
 
+#[test]
+fn test_mutations_are_recorded_in_audit_log() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+    let audit_log = AuditLog::new();
+    let fs = fs.with_audit_log(audit_log.clone());
+
+    let path = temp_dir.path().join("audited.txt");
+    fs.write_file(&path, b"data").unwrap();
+    // Reads are not recorded
+    fs.read_file(&path).unwrap();
+    fs.remove_file(&path).unwrap();
+    assert!(fs.remove_file(&path).is_err());
+
+    let entries = audit_log.entries();
+    let operations: Vec<_> = entries.iter().map(|e| e.operation.as_str()).collect();
+    assert_eq!(operations, ["write", "rm", "rm"]);
+    assert!(entries.iter().all(|e| e.source == AuditSource::User));
+    assert!(entries
+        .iter()
+        .all(|e| e.target == path.to_string_lossy().as_ref()));
+    assert!(entries[1].error.is_none());
+    assert!(entries[2].error.is_some());
+}
+
+/// Collects paths reported by `FileSystem::watch`
+fn watch_channel() -> (WatchCallback, mpsc::Receiver<PathBuf>) {
+    let (tx, rx) = mpsc::channel();
+    let callback: WatchCallback = Arc::new(move |path: PathBuf| {
+        let _ = tx.send(path);
+    });
+    (callback, rx)
+}
+
+#[test]
+fn test_watch_file_reports_changes() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let path = temp_dir.path().join("watched.txt");
+    std::fs::write(&path, b"before").unwrap();
+    let path = fs.canonicalize(&path).unwrap();
+
+    assert!(fs.supports_watch());
+    let (callback, rx) = watch_channel();
+    fs.watch(&path, callback).unwrap();
+
+    // Let the agent take its first snapshot before changing the file
+    std::thread::sleep(Duration::from_millis(200));
+    std::fs::write(&path, b"after, and longer").unwrap();
+
+    let changed = rx
+        .recv_timeout(Duration::from_secs(5))
+        .expect("watch should report the change");
+    assert_eq!(changed, path);
+
+    // After unwatching, further changes are not reported
+    fs.unwatch(&path).unwrap();
+    while rx.try_recv().is_ok() {}
+    std::fs::write(&path, b"unwatched change").unwrap();
+    assert!(rx.recv_timeout(Duration::from_millis(1500)).is_err());
+}
+
+#[test]
+fn test_watch_directory_reports_entries() {
+    let Some((fs, temp_dir, _rt)) = create_test_filesystem() else {
+        eprintln!("Skipping test: could not create test filesystem");
+        return;
+    };
+
+    let dir = fs.canonicalize(temp_dir.path()).unwrap();
+    std::fs::write(dir.join("existing.txt"), b"x").unwrap();
+
+    let (callback, rx) = watch_channel();
+    fs.watch(&dir, callback).unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+
+    std::fs::write(dir.join("created.txt"), b"new").unwrap();
+    std::fs::remove_file(dir.join("existing.txt")).unwrap();
+
+    let mut reported = Vec::new();
+    while reported.len() < 2 {
+        match rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(path) => reported.push(path),
+            Err(_) => break,
+        }
+    }
+    assert!(
+        reported.contains(&dir.join("created.txt")),
+        "{:?}",
+        reported
+    );
+    assert!(
+        reported.contains(&dir.join("existing.txt")),
+        "{:?}",
+        reported
+    );
+
+    fs.unwatch(&dir).unwrap();
+}
+
 // =============================================================================
 // TextBuffer + RemoteFileSystem e2e tests
 // =============================================================================
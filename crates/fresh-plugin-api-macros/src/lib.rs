@@ -7,7 +7,7 @@
 //! This crate provides the `#[plugin_api_impl]` attribute macro that:
 //! 1. Parses method signatures from a `JsEditorApi` impl block
 //! 2. Generates TypeScript type definitions (`.d.ts`)
-//! 3. Automatically writes to `plugins/lib/fresh.d.ts` during compilation
+//! 3. Records where the `.d.ts` file belongs (see [File Output](#file-output))
 //!
 //! ## Usage
 //!
@@ -34,6 +34,21 @@
 //! Apply to the impl block to enable TypeScript generation. Generates:
 //! - `{IMPL_NAME}_TYPESCRIPT_DEFINITIONS: &str` - Full `.d.ts` content
 //! - `{IMPL_NAME}_JS_METHODS: &[&str]` - List of all JS method names
+//! - `{IMPL_NAME}_DEPRECATED_METHODS: &[(&str, &str)]` - Deprecated JS method names
+//!   with their deprecation messages
+//! - `{IMPL_NAME}_JS_NAMESPACE_SHIM: &str` - JS that creates the namespace objects
+//!   (see [Namespaces](#namespaces))
+//! - `{IMPL_NAME}::validate_js_args(method, arg_kinds)` - Checks a JS call's arguments
+//!   (see [Argument Validation](#argument-validation))
+//! - `{IMPL_NAME}_TS_OUT_PATH: &str` / `{IMPL_NAME}_TS_OUT_ENV: Option<&str>` - Where
+//!   to write the `.d.ts` file (see [File Output](#file-output))
+//!
+//! ### `#[plugin_api_events]`
+//!
+//! Apply to the enum of editor events to type their payloads (see
+//! [Events](#events)). Generates:
+//! - `{ENUM_NAME}_TS_EVENT_MAP: &str` - The `EditorEventMap` interface
+//! - `{ENUM_NAME}_REFERENCED_TYPES: &[&str]` - Types the payloads reference
 //!
 //! ### `#[plugin_api(...)]`
 //!
@@ -46,7 +61,129 @@
 //! | `async_promise` | Returns `Promise<T>` | `#[plugin_api(async_promise)]` |
 //! | `async_thenable` | Returns `ProcessHandle<T>` (cancellable) | `#[plugin_api(async_thenable)]` |
 //! | `ts_type = "..."` | Custom TypeScript type for parameter | `#[plugin_api(ts_type = "BufferInfo")]` |
+//! | `ts_handle = "..."` | Handle type returned by an `async_thenable` method, instead of `ProcessHandle<T>` | `#[plugin_api(ts_handle = "BackgroundProcessHandle")]` |
 //! | `ts_return = "..."` | Custom TypeScript return type | `#[plugin_api(ts_return = "string")]` |
+//! | `ts_overload = "..."` | One TypeScript signature; repeat for overloads | `#[plugin_api(ts_overload = "(id: number): string")]` |
+//! | `since = "..."` | Version that added the method (`@since`) | `#[plugin_api(since = "0.4")]` |
+//! | `deprecated = "..."` | Mark as deprecated (`@deprecated`), saying what to use instead | `#[plugin_api(deprecated = "use fooV2")]` |
+//! | `namespace = "..."` | Also expose the method under `editor.{namespace}` (see [Namespaces](#namespaces)) | `#[plugin_api(namespace = "buffers")]` |
+//! | `ts_fields = "..."` | Event fields only: the payload members the field is sent as | `#[plugin_api(ts_fields = "start: number; end: number")]` |
+//!
+//! `ts_type` can also be put on a parameter, e.g. to give a `Value` parameter a
+//! union type: `#[plugin_api(ts_type = "number | string")] target: Value<'js>`.
+//! The macro removes it from the parameter before the impl is compiled.
+//!
+//! ## Callbacks
+//!
+//! A parameter that takes a JS function is declared as a function type.
+//! `rquickjs::Function` accepts any function, and the runtime's `Callback`
+//! wrapper describes the arguments it is called with and what it returns:
+//!
+//! ```rust,ignore
+//! pub fn for_each_line<'js>(&self, buffer_id: u32, callback: Callback<'js, (u32, String), bool>)
+//! // forEachLine(bufferId: number, callback: (arg0: number, arg1: string) => boolean): void
+//!
+//! pub fn on_line<'js>(
+//!     &self,
+//!     #[plugin_api(ts_type = "(line: number) => void")] callback: Callback<'js, (u32,), ()>,
+//! )
+//! // onLine(callback: (line: number) => void): void
+//! ```
+//!
+//! Calls passing something other than a function throw a `TypeError`.
+//!
+//! ## Overloads
+//!
+//! A method that accepts different argument shapes can list each one with
+//! `ts_overload`, written as `(params): ReturnType`. The method is then
+//! declared once per overload instead of from its Rust signature, and async
+//! methods still wrap each return type in `Promise`/`ProcessHandle`:
+//!
+//! ```rust,ignore
+//! #[plugin_api(
+//!     ts_overload = "(bufferId: number): BufferInfo | null",
+//!     ts_overload = "(path: string): BufferInfo | null"
+//! )]
+//! pub fn find_buffer<'js>(&self, target: Value<'js>) -> rquickjs::Result<Value<'js>> { ... }
+//! ```
+//!
+//! A signature can declare type parameters before its parameter list, e.g.
+//! `<E extends keyof EditorEventMap>(eventName: E): void`.
+//!
+//! ## Events
+//!
+//! Plugins subscribe to editor events by name, and each event's handler gets
+//! the event's fields as a plain object. `#[plugin_api_events]` on the event
+//! enum turns every variant into a member of `EditorEventMap`, named after the
+//! variant in snake_case, so that `on` can type the handler by the event name:
+//!
+//! ```rust,ignore
+//! #[plugin_api_events]
+//! pub enum HookArgs {
+//!     /// After a buffer is successfully saved
+//!     AfterFileSave { buffer_id: BufferId, path: PathBuf },
+//! }
+//! ```
+//!
+//! ```typescript
+//! interface EditorEventMap {
+//!   /** After a buffer is successfully saved */
+//!   after_file_save: { buffer_id: BufferId; path: string };
+//! }
+//! ```
+//!
+//! Fields sent in another shape than their Rust type take `ts_type`, or
+//! `ts_fields` when they become several members.
+//!
+//! ## Versioning
+//!
+//! `since` and `deprecated` become `@since` / `@deprecated` JSDoc tags, so
+//! editors show deprecated calls struck through. Deprecated methods are also
+//! listed in `{IMPL_NAME}_DEPRECATED_METHODS`, which the QuickJS backend uses to
+//! log a warning the first time a plugin calls one.
+//!
+//! ## Namespaces
+//!
+//! A flat `EditorAPI` with a hundred methods is hard to browse, so methods can
+//! be grouped with `namespace`. A grouped method stays on `editor` and is also
+//! reachable through its namespace object:
+//!
+//! ```rust,ignore
+//! #[plugin_api(namespace = "buffers")]
+//! pub fn insert_text(&self, buffer_id: u32, position: u32, text: String) -> bool { ... }
+//! ```
+//!
+//! ```typescript
+//! interface EditorAPI {
+//!   /** Methods grouped under `editor.buffers` */
+//!   readonly buffers: EditorBuffersAPI;
+//!   insertText(bufferId: number, position: number, text: string): boolean;
+//! }
+//!
+//! interface EditorBuffersAPI {
+//!   insertText(bufferId: number, position: number, text: string): boolean;
+//! }
+//! ```
+//!
+//! The namespace objects are created by `{IMPL_NAME}_JS_NAMESPACE_SHIM`, which
+//! the runtime evaluates once `editor` is set up. Its methods look up the
+//! `editor` method at call time, so async, validation, deprecation and
+//! capability wrappers apply to both spellings.
+//!
+//! ## Argument Validation
+//!
+//! rquickjs converts JS arguments as best it can, so a call with a missing or
+//! misplaced argument can fail with an unhelpful conversion error or run with
+//! the wrong values. `validate_js_args` checks a call against the method's
+//! parameters first, and the QuickJS backend throws its message as a
+//! `TypeError`:
+//!
+//! ```text
+//! editor.insertText(bufferId: number, position: number, text: string): missing argument `position`
+//! ```
+//!
+//! Arity is always checked; types only for primitives, arrays, records and their
+//! unions (see `js_kinds`). Overloaded methods are not checked.
 //!
 //! ## Type Mapping
 //!
@@ -55,6 +192,7 @@
 //! | `u8`, `u16`, `u32`, `i32`, etc. | `number` | All numeric types |
 //! | `bool` | `boolean` | |
 //! | `String`, `&str` | `string` | |
+//! | `PathBuf`, `&Path` | `string` | |
 //! | `()` | `void` | |
 //! | `Option<T>` | `T \| null` | |
 //! | `Vec<T>` | `T[]` | |
@@ -63,6 +201,14 @@
 //! | `rquickjs::function::Rest<T>` | `...T[]` | Variadic parameter |
 //! | `rquickjs::Result<T>` | `T` | Unwrapped |
 //! | `rquickjs::Object<'js>` | `Record<string, unknown>` | Use `ts_type` for specifics |
+//! | `rquickjs::Function<'js>` | `(...args: unknown[]) => unknown` | Use `ts_type` for specifics |
+//! | `Callback<'js, (A, B), R>` | `(arg0: A, arg1: B) => R` | Typed callback; `ts_type` to name the arguments |
+//!
+//! Any other type keeps its name (`BufferInfo` -> `BufferInfo`). Its `interface`
+//! is not written by this crate: API structs derive `ts_rs::TS`, which reads
+//! the fields and serde attributes (`rename_all`, `skip_serializing_if`,
+//! `#[ts(optional)]`), and `fresh-plugin-runtime`'s `ts_export` collects those
+//! declarations for every name in `{IMPL_NAME}_REFERENCED_TYPES`.
 //!
 //! ## Async Methods
 //!
@@ -74,9 +220,19 @@
 //!
 //! ## File Output
 //!
-//! The macro automatically writes `plugins/lib/fresh.d.ts` when:
-//! 1. Building the main crate (not the macro crate)
-//! 2. The content has changed (avoids unnecessary rebuilds)
+//! The macro doesn't write files: the crate using it combines the generated
+//! constants with the ts-rs types and writes the result (only when the content
+//! changed). Where it goes is configured on the impl block:
+//!
+//! ```rust,ignore
+//! #[plugin_api_impl(out = "../fresh-editor/plugins/lib/fresh.d.ts", env = "FRESH_DTS_OUT")]
+//! impl JsEditorApi { ... }
+//! ```
+//!
+//! `out` is relative to the manifest directory of the crate with the impl
+//! (default `plugins/lib/fresh.d.ts`) and becomes the absolute
+//! `{IMPL_NAME}_TS_OUT_PATH`. `env` names an environment variable that, when
+//! set, overrides it, so other workspace layouts don't need a code change.
 //!
 //! ## Design Principles
 //!
@@ -89,8 +245,8 @@
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{
-    parse_macro_input, spanned::Spanned, Attribute, FnArg, GenericArgument, ImplItem, ImplItemFn,
-    ItemImpl, Meta, Pat, PathArguments, ReturnType, Type,
+    parse_macro_input, spanned::Spanned, Attribute, Fields, FnArg, GenericArgument, ImplItem,
+    ImplItemFn, ItemEnum, ItemImpl, Meta, Pat, PathArguments, ReturnType, Type, Variant,
 };
 
 // ============================================================================
@@ -143,8 +299,112 @@ struct ApiMethod {
     params: Vec<ParamInfo>,
     /// TypeScript return type
     return_type: String,
+    /// Signatures from `ts_overload`, used instead of `params`/`return_type`
+    overloads: Vec<Overload>,
     /// Documentation from doc comments
     doc: String,
+    /// Version the method was added in, from `since`
+    since: Option<String>,
+    /// What to use instead, from `deprecated`
+    deprecated: Option<String>,
+    /// Namespace the method is also exposed under, from `namespace`
+    namespace: Option<String>,
+    /// Handle type declared instead of `ProcessHandle<T>`, from `ts_handle`
+    handle_type: Option<String>,
+}
+
+/// One `ts_overload` signature, split into its type parameters, parameter list
+/// and return type
+#[derive(Debug)]
+struct Overload {
+    /// Type parameter list with its angle brackets (e.g. `<E extends string>`),
+    /// empty for a non-generic signature
+    type_params: String,
+    /// Parameter list without the parentheses (e.g. `id: number, force?: boolean`)
+    params: String,
+    /// TypeScript return type, before async wrapping
+    return_type: String,
+}
+
+impl Overload {
+    /// Parse `(params): ReturnType` or `<T>(params): ReturnType`
+    fn parse(signature: &str) -> Option<Self> {
+        let signature = signature.trim();
+        let (type_params, signature) = if signature.starts_with('<') {
+            // Find the `>` closing the type parameters (constraints may nest them)
+            let mut depth = 0usize;
+            let mut prev = ' ';
+            let close = signature.char_indices().find_map(|(i, c)| {
+                match c {
+                    '<' => depth += 1,
+                    '>' if prev != '=' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i);
+                        }
+                    }
+                    _ => {}
+                }
+                prev = c;
+                None
+            })?;
+            (
+                signature[..=close].to_string(),
+                signature[close + 1..].trim_start(),
+            )
+        } else {
+            (String::new(), signature)
+        };
+        if !signature.starts_with('(') {
+            return None;
+        }
+        // Find the parenthesis closing the parameter list (types may nest them)
+        let mut depth = 0usize;
+        let close = signature.char_indices().find_map(|(i, c)| {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                }
+                _ => {}
+            }
+            None
+        })?;
+        let return_type = signature[close + 1..].trim().strip_prefix(':')?.trim();
+        if return_type.is_empty() {
+            return None;
+        }
+        Some(Overload {
+            type_params,
+            params: signature[1..close].trim().to_string(),
+            return_type: return_type.to_string(),
+        })
+    }
+
+    /// Names of the type parameters (`E` in `<E extends string>`)
+    fn type_param_names(&self) -> Vec<&str> {
+        let inner = self
+            .type_params
+            .strip_prefix('<')
+            .and_then(|rest| rest.strip_suffix('>'))
+            .unwrap_or("");
+        split_top_level(inner, ',')
+            .into_iter()
+            .filter_map(|param| param.split_whitespace().next())
+            .map(|name| name.trim_end_matches('='))
+            .collect()
+    }
+
+    /// TypeScript types of the parameters, in order
+    fn param_types(&self) -> Vec<&str> {
+        split_top_level(&self.params, ',')
+            .into_iter()
+            .filter_map(|param| param.split_once(':').map(|(_, ty)| ty.trim()))
+            .collect()
+    }
 }
 
 /// Parsed parameter information
@@ -201,6 +461,63 @@ fn to_camel_case(s: &str) -> String {
     result
 }
 
+/// Convert a CamelCase identifier to snake_case
+///
+/// # Examples
+/// ```ignore
+/// assert_eq!(to_snake_case("AfterFileSave"), "after_file_save");
+/// ```
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 4);
+    for (i, c) in s.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.push(c.to_ascii_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Whether `s` is a plain JS identifier (letters, digits, `_`, `$`)
+fn is_js_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Split `s` on `separator` where it is not nested in `<>`, `()`, `[]` or `{}`
+fn split_top_level(s: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (i, c) in s.char_indices() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            // The `>` of an arrow function type (`=>`) doesn't close anything
+            '>' if prev == '=' => {}
+            '>' | ')' | ']' | '}' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(s[start..i].trim());
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    let last = s[start..].trim();
+    if !last.is_empty() {
+        parts.push(last);
+    }
+    parts
+}
+
 // ============================================================================
 // Attribute Parsing
 // ============================================================================
@@ -241,6 +558,28 @@ fn parse_attr_string_value(tokens: &str, key: &str) -> Option<String> {
     Some(after_eq[1..end_quote + 1].to_string())
 }
 
+/// Parse every `key = "value"` occurrence from attribute tokens
+fn parse_attr_string_values(tokens: &str, key: &str) -> Vec<String> {
+    let mut values = Vec::new();
+    let mut rest = tokens;
+    while let Some(start) = rest.find(key) {
+        rest = &rest[start + key.len()..];
+        // Only `key = "..."` counts, not `key` appearing inside another value
+        let Some(after_eq) = rest.trim_start().strip_prefix('=') else {
+            continue;
+        };
+        let Some(value) = after_eq.trim_start().strip_prefix('"') else {
+            continue;
+        };
+        let Some(end) = value.find('"') else {
+            break;
+        };
+        values.push(value[..end].to_string());
+        rest = &value[end + 1..];
+    }
+    values
+}
+
 /// Check if `#[plugin_api(...)]` contains a specific flag
 fn has_plugin_api_flag(attrs: &[Attribute], flag: &str) -> bool {
     attrs.iter().any(|attr| {
@@ -270,6 +609,21 @@ fn get_plugin_api_value(attrs: &[Attribute], key: &str) -> Option<String> {
     None
 }
 
+/// Get every value of a repeatable `#[plugin_api(key = "value", ...)]`
+fn get_plugin_api_values(attrs: &[Attribute], key: &str) -> Vec<String> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("plugin_api"))
+        .filter_map(|attr| match &attr.meta {
+            Meta::List(meta_list) => {
+                Some(parse_attr_string_values(&meta_list.tokens.to_string(), key))
+            }
+            _ => None,
+        })
+        .flatten()
+        .collect()
+}
+
 /// Get custom JS name from `#[qjs(rename = "...")]` or `#[plugin_api(js_name = "...")]`
 fn get_js_name(attrs: &[Attribute]) -> Option<String> {
     // First check plugin_api attribute (takes precedence)
@@ -309,6 +663,56 @@ fn extract_inner_type(ty: &Type) -> Option<Type> {
     None
 }
 
+/// Type arguments of a generic type, skipping lifetimes (`Callback<'js, A, R>` -> `[A, R]`)
+fn extract_type_args(ty: &Type) -> Vec<Type> {
+    let Type::Path(type_path) = ty else {
+        return Vec::new();
+    };
+    let Some(PathArguments::AngleBracketed(args)) =
+        type_path.path.segments.last().map(|s| &s.arguments)
+    else {
+        return Vec::new();
+    };
+    args.args
+        .iter()
+        .filter_map(|arg| match arg {
+            GenericArgument::Type(ty) => Some(ty.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Wrap a function type in parentheses so it can be used in a union or array
+fn parenthesize_function(ts_type: String) -> String {
+    if ts_type.contains("=>") {
+        format!("({})", ts_type)
+    } else {
+        ts_type
+    }
+}
+
+/// TypeScript function type for `Callback<Args, Ret>`
+///
+/// `Args` is a tuple whose elements become `arg0`, `arg1`, ... (a single type is
+/// taken as a one-element tuple)
+fn callback_to_typescript(ty: &Type) -> String {
+    let type_args = extract_type_args(ty);
+    let params: Vec<String> = match type_args.first() {
+        Some(Type::Tuple(tuple)) => tuple.elems.iter().collect::<Vec<_>>(),
+        Some(single) => vec![single],
+        None => Vec::new(),
+    }
+    .into_iter()
+    .enumerate()
+    .map(|(i, arg)| format!("arg{}: {}", i, rust_to_typescript(arg, &[])))
+    .collect();
+    let return_type = type_args
+        .get(1)
+        .map(|ret| rust_to_typescript(ret, &[]))
+        .unwrap_or_else(|| "void".to_string());
+    format!("({}) => {}", params.join(", "), return_type)
+}
+
 /// Get the final segment name from a type path (e.g., "Opt" from "rquickjs::function::Opt")
 fn get_type_name(ty: &Type) -> Option<String> {
     if let Type::Path(type_path) = ty {
@@ -361,7 +765,7 @@ fn is_rest_type(ty: &Type) -> bool {
 /// - Primitive types (numbers, bool, string)
 /// - Generic wrappers (Option, Vec, Result)
 /// - QuickJS types (Opt, Rest, Object, Value)
-/// - Known API types (BufferInfo, etc.)
+/// - API types (BufferInfo, etc.), which keep their name
 fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
     // Check for explicit ts_type override
     if let Some(custom) = get_plugin_api_value(attrs, "ts_type") {
@@ -385,8 +789,8 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
                 // Boolean
                 "bool" => "boolean".to_string(),
 
-                // String types
-                "String" | "str" => "string".to_string(),
+                // String types (paths are sent as strings)
+                "String" | "str" | "PathBuf" | "Path" => "string".to_string(),
 
                 // Unit type
                 "()" => "void".to_string(),
@@ -394,7 +798,7 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
                 // Option<T> -> T | null
                 "Option" => {
                     let inner = extract_inner_type(ty)
-                        .map(|t| rust_to_typescript(&t, &[]))
+                        .map(|t| parenthesize_function(rust_to_typescript(&t, &[])))
                         .unwrap_or_else(|| "unknown".to_string());
                     format!("{} | null", inner)
                 }
@@ -402,7 +806,7 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
                 // Vec<T> -> T[]
                 "Vec" => {
                     let inner = extract_inner_type(ty)
-                        .map(|t| rust_to_typescript(&t, &[]))
+                        .map(|t| parenthesize_function(rust_to_typescript(&t, &[])))
                         .unwrap_or_else(|| "unknown".to_string());
                     format!("{}[]", inner)
                 }
@@ -425,43 +829,13 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
                 // QuickJS types
                 "Value" => "unknown".to_string(),
                 "Object" => "Record<string, unknown>".to_string(),
+                "Function" => "(...args: unknown[]) => unknown".to_string(),
+                "Callback" => callback_to_typescript(ty),
 
                 // Rust collections
                 "HashMap" | "BTreeMap" => "Record<string, unknown>".to_string(),
 
-                // Known API types - pass through unchanged
-                "BufferInfo"
-                | "CursorInfo"
-                | "ViewportInfo"
-                | "SpawnResult"
-                | "BackgroundProcessResult"
-                | "DirEntry"
-                | "FileStat"
-                | "CreateVirtualBufferResult"
-                | "PromptSuggestion"
-                | "TextPropertyEntry"
-                | "JsTextPropertyEntry"
-                | "CreateVirtualBufferOptions"
-                | "CreateVirtualBufferInSplitOptions"
-                | "CreateVirtualBufferInExistingSplitOptions"
-                | "VirtualBufferResult"
-                | "ActionSpec"
-                | "ActionPopupAction"
-                | "ActionPopupOptions"
-                | "ViewTokenWire"
-                | "ViewTokenStyle"
-                | "LayoutHints"
-                | "FileExplorerDecoration"
-                | "TsCreateCompositeBufferOptions"
-                | "TsCompositeLayoutConfig"
-                | "TsCompositeSourceConfig"
-                | "TsCompositePaneStyle"
-                | "TsCompositeHunk"
-                | "TsHighlightSpan"
-                | "TsActionPopupAction"
-                | "JsDiagnostic" => type_name,
-
-                // Default: use type name as-is
+                // API types keep their name; their interfaces come from ts-rs
                 _ => type_name,
             }
         }
@@ -477,11 +851,12 @@ fn rust_to_typescript(ty: &Type, attrs: &[Attribute]) -> String {
 
 /// Parse a method from the impl block into an ApiMethod
 ///
-/// Returns `None` if the method should be skipped (marked with `skip` or internal)
-fn parse_method(method: &ImplItemFn) -> Option<ApiMethod> {
+/// Returns `Ok(None)` if the method should be skipped (marked with `skip` or
+/// internal), and an error for a malformed `ts_overload`
+fn parse_method(method: &ImplItemFn) -> syn::Result<Option<ApiMethod>> {
     // Skip methods marked with #[plugin_api(skip)]
     if has_plugin_api_flag(&method.attrs, "skip") {
-        return None;
+        return Ok(None);
     }
 
     let rust_name = method.sig.ident.to_string();
@@ -501,9 +876,24 @@ fn parse_method(method: &ImplItemFn) -> Option<ApiMethod> {
 
     // Skip internal methods (names starting with underscore)
     if js_name.starts_with('_') {
-        return None;
+        return Ok(None);
     }
 
+    let overloads = get_plugin_api_values(&method.attrs, "ts_overload")
+        .iter()
+        .map(|signature| {
+            Overload::parse(signature).ok_or_else(|| {
+                syn::Error::new(
+                    method.sig.ident.span(),
+                    format!(
+                        "invalid ts_overload `{}`: expected `(params): ReturnType`",
+                        signature
+                    ),
+                )
+            })
+        })
+        .collect::<syn::Result<Vec<_>>>()?;
+
     // Parse parameters
     let params: Vec<ParamInfo> = method
         .sig
@@ -550,13 +940,35 @@ fn parse_method(method: &ImplItemFn) -> Option<ApiMethod> {
         }
     };
 
-    Some(ApiMethod {
+    let namespace = get_plugin_api_value(&method.attrs, "namespace");
+    if let Some(namespace) = &namespace {
+        if !is_js_identifier(namespace) {
+            return Err(syn::Error::new(
+                method.sig.ident.span(),
+                format!(
+                    "invalid namespace `{}`: expected a JS identifier",
+                    namespace
+                ),
+            ));
+        }
+    }
+
+    Ok(Some(ApiMethod {
         js_name,
         kind,
         params,
         return_type,
+        overloads,
         doc,
-    })
+        since: get_plugin_api_values(&method.attrs, "since")
+            .into_iter()
+            .next(),
+        deprecated: get_plugin_api_values(&method.attrs, "deprecated")
+            .into_iter()
+            .next(),
+        namespace,
+        handle_type: get_plugin_api_value(&method.attrs, "ts_handle"),
+    }))
 }
 
 // ============================================================================
@@ -567,29 +979,51 @@ fn parse_method(method: &ImplItemFn) -> Option<ApiMethod> {
 fn generate_ts_method(method: &ApiMethod) -> String {
     let mut lines = Vec::new();
 
-    // JSDoc comment
-    if !method.doc.is_empty() {
+    // JSDoc comment, followed by the version tags
+    let mut doc_lines: Vec<String> = method.doc.lines().map(str::to_string).collect();
+    if let Some(since) = &method.since {
+        doc_lines.push(format!("@since {}", since));
+    }
+    if let Some(deprecated) = &method.deprecated {
+        doc_lines.push(format!("@deprecated {}", deprecated));
+    }
+    if !doc_lines.is_empty() {
         lines.push("  /**".to_string());
-        for line in method.doc.lines() {
+        for line in doc_lines {
             lines.push(format!("   * {}", line));
         }
         lines.push("   */".to_string());
     }
 
-    // Method signature
-    let params: String = method
-        .params
-        .iter()
-        .map(ParamInfo::to_typescript)
-        .collect::<Vec<_>>()
-        .join(", ");
+    // One signature per overload, or the one from the Rust signature
+    if method.overloads.is_empty() {
+        let params: String = method
+            .params
+            .iter()
+            .map(ParamInfo::to_typescript)
+            .collect::<Vec<_>>()
+            .join(", ");
 
-    let return_type = method.kind.wrap_return_type(&method.return_type);
+        let return_type = match &method.handle_type {
+            Some(handle_type) => handle_type.clone(),
+            None => method.kind.wrap_return_type(&method.return_type),
+        };
 
-    lines.push(format!(
-        "  {}({}): {};",
-        method.js_name, params, return_type
-    ));
+        lines.push(format!(
+            "  {}({}): {};",
+            method.js_name, params, return_type
+        ));
+    } else {
+        for overload in &method.overloads {
+            lines.push(format!(
+                "  {}{}({}): {};",
+                method.js_name,
+                overload.type_params,
+                overload.params,
+                method.kind.wrap_return_type(&overload.return_type)
+            ));
+        }
+    }
 
     lines.join("\n")
 }
@@ -624,24 +1058,143 @@ interface ProcessHandle<T> extends PromiseLike<T> {
   kill(): Promise<boolean>;
 }
 
+/** Handle for a background process, which can also stream its output */
+interface BackgroundProcessHandle extends ProcessHandle<BackgroundProcessResult> {
+  /** ID of the process, as passed to `killBackgroundProcess` */
+  readonly processId: number;
+  /** Call `listener` with each line the process writes to stdout (without the newline) */
+  onStdout(listener: (line: string) => void): BackgroundProcessHandle;
+  /** Call `listener` with each line the process writes to stderr (without the newline) */
+  onStderr(listener: (line: string) => void): BackgroundProcessHandle;
+}
+
 /** Buffer identifier */
 type BufferId = number;
 
 /** Split identifier */
 type SplitId = number;
 
+/** Code of a `PluginError`, one per subclass */
+type PluginErrorCode =
+  | "INVALID_BUFFER_ID"
+  | "READ_ONLY_BUFFER"
+  | "OUT_OF_RANGE"
+  | "PERMISSION_DENIED"
+  | "CHANNEL_CLOSED";
+
+/** Base class of the errors API calls throw; branch on `code` or `instanceof` */
+declare class PluginError extends Error {
+  readonly code: PluginErrorCode;
+}
+/** No open buffer has the given ID (it was closed, or never existed) */
+declare class InvalidBufferIdError extends PluginError {
+  readonly code: "INVALID_BUFFER_ID";
+}
+/** Editing is disabled in the buffer */
+declare class ReadOnlyBufferError extends PluginError {
+  readonly code: "READ_ONLY_BUFFER";
+}
+/** A position or range lies outside the buffer, or ends before it starts */
+declare class OutOfRangeError extends PluginError {
+  readonly code: "OUT_OF_RANGE";
+}
+/** The plugin isn't allowed to call the method (capability disabled or not granted) */
+declare class PermissionDeniedError extends PluginError {
+  readonly code: "PERMISSION_DENIED";
+}
+/** The editor no longer accepts commands (it is shutting down) */
+declare class ChannelClosedError extends PluginError {
+  readonly code: "CHANNEL_CLOSED";
+}
+
 "#
 }
 
-/// Generate the EditorAPI interface (methods only)
+/// Namespaces in order of first use, with their methods
+fn group_by_namespace(methods: &[ApiMethod]) -> Vec<(&str, Vec<&ApiMethod>)> {
+    let mut groups: Vec<(&str, Vec<&ApiMethod>)> = Vec::new();
+    for method in methods {
+        let Some(namespace) = method.namespace.as_deref() else {
+            continue;
+        };
+        match groups.iter_mut().find(|(name, _)| *name == namespace) {
+            Some((_, group)) => group.push(method),
+            None => groups.push((namespace, vec![method])),
+        }
+    }
+    groups
+}
+
+/// Name of the interface of a namespace (`buffers` -> `EditorBuffersAPI`)
+fn namespace_interface_name(namespace: &str) -> String {
+    let mut chars = namespace.chars();
+    let first = chars.next().map(|c| c.to_ascii_uppercase());
+    format!(
+        "Editor{}{}API",
+        first.into_iter().collect::<String>(),
+        chars.as_str()
+    )
+}
+
+/// Generate the EditorAPI interface (methods and namespace members) and one
+/// interface per namespace
 /// Types are provided separately via ts-rs
 fn generate_editor_api_interface(methods: &[ApiMethod]) -> String {
-    let method_sigs: Vec<String> = methods.iter().map(generate_ts_method).collect();
+    let namespaces = group_by_namespace(methods);
 
-    format!(
+    let mut members: Vec<String> = namespaces
+        .iter()
+        .map(|(namespace, _)| {
+            format!(
+                "  /** Methods grouped under `editor.{}` */\n  readonly {}: {};",
+                namespace,
+                namespace,
+                namespace_interface_name(namespace)
+            )
+        })
+        .collect();
+    members.extend(methods.iter().map(generate_ts_method));
+
+    let mut out = format!(
         "/**\n * Main editor API interface\n */\ninterface EditorAPI {{\n{}\n}}\n",
-        method_sigs.join("\n\n")
-    )
+        members.join("\n\n")
+    );
+    for (namespace, group) in &namespaces {
+        let method_sigs: Vec<String> = group.iter().map(|m| generate_ts_method(m)).collect();
+        out.push_str(&format!(
+            "\n/**\n * `editor.{}` methods\n */\ninterface {} {{\n{}\n}}\n",
+            namespace,
+            namespace_interface_name(namespace),
+            method_sigs.join("\n\n")
+        ));
+    }
+    out
+}
+
+/// Generate the JS that creates the namespace objects on `editor`
+///
+/// Each method calls the `editor` method of the same name when invoked, so
+/// wrappers installed on `editor` later still apply.
+fn generate_js_namespace_shim(methods: &[ApiMethod]) -> String {
+    group_by_namespace(methods)
+        .iter()
+        .map(|(namespace, group)| {
+            let entries: Vec<String> = group
+                .iter()
+                .map(|m| {
+                    format!(
+                        "  {name}: function(...args) {{ return editor.{name}(...args); }},",
+                        name = m.js_name
+                    )
+                })
+                .collect();
+            format!(
+                "editor.{} = Object.freeze({{\n{}\n}});\n",
+                namespace,
+                entries.join("\n")
+            )
+        })
+        .collect()
 }
 
 /// Built-in TypeScript types that don't need to be collected
@@ -657,6 +1210,7 @@ const BUILTIN_TS_TYPES: &[&str] = &[
     "Array",
     "Promise",
     "ProcessHandle",
+    "BackgroundProcessHandle",
     "PromiseLike",
     "BufferId",
     "SplitId", // Defined in preamble
@@ -740,6 +1294,16 @@ fn collect_referenced_types(methods: &[ApiMethod]) -> Vec<String> {
                 types.insert(ty);
             }
         }
+
+        // Collect from overload signatures, leaving out their type parameters
+        for overload in &method.overloads {
+            let type_params = overload.type_param_names();
+            let overload_types = std::iter::once(overload.return_type.as_str())
+                .chain(overload.param_types())
+                .flat_map(extract_type_references)
+                .filter(|ty| !type_params.contains(&ty.as_str()));
+            types.extend(overload_types);
+        }
     }
 
     let mut sorted: Vec<String> = types.into_iter().collect();
@@ -747,6 +1311,370 @@ fn collect_referenced_types(methods: &[ApiMethod]) -> Vec<String> {
     sorted
 }
 
+/// Remove `#[plugin_api(...)]` from method parameters
+///
+/// Parameter attributes must be inert, so they can't be left for the compiler
+/// (or `#[rquickjs::methods]`) to see.
+fn strip_param_attrs(input: &mut ItemImpl) {
+    for item in &mut input.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        for arg in &mut method.sig.inputs {
+            if let FnArg::Typed(pat_type) = arg {
+                pat_type
+                    .attrs
+                    .retain(|attr| !attr.path().is_ident("plugin_api"));
+            }
+        }
+    }
+}
+
+/// Name QuickJS gives a method: its `#[qjs(rename)]`, or the camelCase name
+fn qjs_name(method: &ImplItemFn) -> String {
+    method
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("qjs"))
+        .find_map(|attr| match &attr.meta {
+            Meta::List(meta_list) => {
+                parse_attr_string_value(&meta_list.tokens.to_string(), "rename")
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| to_camel_case(&method.sig.ident.to_string()))
+}
+
+/// Add a `_permit: Permit<N>` parameter after `self` to every method, where
+/// `N` indexes the returned QuickJS method names
+///
+/// The parameter takes no JS argument: converting it runs before the method
+/// and can refuse the call, whichever way JS reached the method.
+fn add_permit_params(input: &mut ItemImpl, permit: &syn::Path) -> Vec<String> {
+    let mut names = Vec::new();
+    for item in &mut input.items {
+        let ImplItem::Fn(method) = item else {
+            continue;
+        };
+        if method.sig.receiver().is_none() {
+            continue;
+        }
+        let index = names.len();
+        names.push(qjs_name(method));
+        let param: FnArg = syn::parse_quote! { _permit: #permit<#index> };
+        method.sig.inputs.insert(1, param);
+    }
+    names
+}
+
+// ============================================================================
+// Argument Validation Code Generation
+// ============================================================================
+
+/// JS kinds a parameter of TypeScript type `ts_type` accepts, or `None` when
+/// the type isn't checked
+///
+/// Kinds are `typeof` results, except that `null` is `"null"` and arrays are
+/// `"array"`. Only primitives, arrays, records and their unions are checked;
+/// interfaces and `unknown` are left to the conversion into Rust.
+fn js_kinds(ts_type: &str) -> Option<Vec<&'static str>> {
+    let mut kinds: Vec<&'static str> = Vec::new();
+    for member in split_top_level(ts_type, '|') {
+        let member_kinds: &[&'static str] = match member {
+            "number" | "BufferId" | "SplitId" => &["number"],
+            "string" => &["string"],
+            "boolean" => &["boolean"],
+            // A missing argument converts to `None` just like `null`
+            "null" => &["null", "undefined"],
+            "undefined" => &["undefined"],
+            _ if member.ends_with("[]") => &["array"],
+            // rquickjs objects include arrays
+            _ if member.starts_with("Record<") => &["object", "array"],
+            _ if member.starts_with('(') && member.contains("=>") => &["function"],
+            _ => return None,
+        };
+        for kind in member_kinds {
+            if !kinds.contains(kind) {
+                kinds.push(kind);
+            }
+        }
+    }
+    Some(kinds)
+}
+
+/// Generate the `match` arm listing a method's parameters for `validate_js_args`
+///
+/// Each parameter is `(name, declaration, TypeScript type, accepted kinds,
+/// variadic)`, where no kinds means anything is accepted. Overloaded methods get no arm: their
+/// Rust signature doesn't describe what JS may pass.
+fn generate_validation_arm(method: &ApiMethod) -> Option<proc_macro2::TokenStream> {
+    if !method.overloads.is_empty() {
+        return None;
+    }
+    let js_name = &method.js_name;
+    let params = method.params.iter().map(|param| {
+        let name = &param.name;
+        let declaration = param.to_typescript();
+        let ts_type = &param.ts_type;
+        let mut kinds = js_kinds(ts_type).unwrap_or_default();
+        if param.optional && !kinds.is_empty() && !kinds.contains(&"undefined") {
+            kinds.push("undefined");
+        }
+        let variadic = param.variadic;
+        quote! { (#name, #declaration, #ts_type, &[#(#kinds),*], #variadic) }
+    });
+    Some(quote! { #js_name => &[#(#params),*], })
+}
+
+/// Generate `validate_js_args`, which checks a JS call against the method's
+/// Rust signature before rquickjs converts the arguments
+fn generate_validate_js_args(methods: &[ApiMethod]) -> proc_macro2::TokenStream {
+    let arms = methods.iter().filter_map(generate_validation_arm);
+    quote! {
+        /// Check the arguments of a JS call to `editor.<method>` against its signature
+        ///
+        /// `arg_kinds` holds each argument's `typeof`, except that `null` is
+        /// `"null"` and arrays are `"array"`. Returns the message for a
+        /// `TypeError` when an argument is missing, extra or of the wrong type.
+        /// Unknown and overloaded methods are not checked.
+        pub fn validate_js_args(method: &str, arg_kinds: &[&str]) -> ::std::result::Result<(), String> {
+            let params: &[(&str, &str, &str, &[&str], bool)] = match method {
+                #(#arms)*
+                _ => return Ok(()),
+            };
+            let signature = params
+                .iter()
+                .map(|param| param.1)
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            let variadic = params.last().is_some_and(|param| param.4);
+            if !variadic && arg_kinds.len() > params.len() {
+                return Err(format!(
+                    "editor.{}({}) takes {} argument(s) but got {}",
+                    method,
+                    signature,
+                    params.len(),
+                    arg_kinds.len()
+                ));
+            }
+            for (index, (name, _, ts_type, kinds, variadic)) in params.iter().enumerate() {
+                let args: &[&str] = if *variadic {
+                    arg_kinds.get(index..).unwrap_or(&[])
+                } else {
+                    ::std::slice::from_ref(arg_kinds.get(index).unwrap_or(&"undefined"))
+                };
+                for kind in args {
+                    if kinds.is_empty() || kinds.contains(kind) {
+                        continue;
+                    }
+                    return Err(if index >= arg_kinds.len() {
+                        format!(
+                            "editor.{}({}): missing argument `{}`",
+                            method, signature, name
+                        )
+                    } else {
+                        format!(
+                            "editor.{}({}): argument `{}` must be {}, got {}",
+                            method, signature, name, ts_type, kind
+                        )
+                    });
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+// ============================================================================
+// Impl Options
+// ============================================================================
+
+/// Default output path of the `.d.ts` file, relative to the manifest directory
+const DEFAULT_TS_OUT: &str = "plugins/lib/fresh.d.ts";
+
+/// Options of `#[plugin_api_impl(...)]`
+#[derive(Debug, Default, PartialEq)]
+struct ImplOptions {
+    /// Output path of the `.d.ts` file, relative to the manifest directory
+    out: Option<String>,
+    /// Environment variable overriding the output path
+    env: Option<String>,
+    /// Parameter type every method takes to check the call is allowed
+    permit: Option<String>,
+}
+
+/// Parse `out = "...", env = "...", permit = "..."`, rejecting unknown keys
+fn parse_impl_options(attr: proc_macro2::TokenStream) -> syn::Result<ImplOptions> {
+    use syn::parse::Parser;
+
+    let pairs = syn::punctuated::Punctuated::<syn::MetaNameValue, syn::Token![,]>::parse_terminated
+        .parse2(attr)?;
+    let mut options = ImplOptions::default();
+    for pair in pairs {
+        let syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(value),
+            ..
+        }) = &pair.value
+        else {
+            return Err(syn::Error::new(
+                pair.value.span(),
+                "expected a string literal",
+            ));
+        };
+        let slot = if pair.path.is_ident("out") {
+            &mut options.out
+        } else if pair.path.is_ident("env") {
+            &mut options.env
+        } else if pair.path.is_ident("permit") {
+            &mut options.permit
+        } else {
+            return Err(syn::Error::new(
+                pair.path.span(),
+                "unknown plugin_api_impl option, expected `out`, `env` or `permit`",
+            ));
+        };
+        if value.value().is_empty() {
+            return Err(syn::Error::new(value.span(), "value must not be empty"));
+        }
+        *slot = Some(value.value());
+    }
+    Ok(options)
+}
+
+// ============================================================================
+// Event Map Code Generation
+// ============================================================================
+
+/// Parsed event - one variant of a `#[plugin_api_events]` enum
+#[derive(Debug)]
+struct ApiEvent {
+    /// Name plugins subscribe to (the variant name in snake_case)
+    name: String,
+    /// Documentation from doc comments
+    doc: String,
+    /// Members of the payload handlers receive
+    fields: Vec<EventField>,
+}
+
+/// One member of an event payload
+#[derive(Debug)]
+struct EventField {
+    /// Member name (the field name, which the payload keeps as is)
+    name: String,
+    /// TypeScript type
+    ts_type: String,
+    /// Documentation from doc comments
+    doc: String,
+}
+
+/// Parse an enum variant into an ApiEvent
+///
+/// Returns an error for tuple variants and a malformed `ts_fields`
+fn parse_event(variant: &Variant) -> syn::Result<ApiEvent> {
+    let mut fields = Vec::new();
+    match &variant.fields {
+        Fields::Named(named) => {
+            for field in &named.named {
+                let doc = extract_doc_comment(&field.attrs);
+
+                // `ts_fields` lists the members the field is sent as instead
+                if let Some(members) = get_plugin_api_value(&field.attrs, "ts_fields") {
+                    for member in split_top_level(&members, ';') {
+                        let (name, ts_type) = member.split_once(':').ok_or_else(|| {
+                            syn::Error::new(
+                                field.span(),
+                                format!(
+                                    "invalid ts_fields member `{}`: expected `name: type`",
+                                    member
+                                ),
+                            )
+                        })?;
+                        fields.push(EventField {
+                            name: name.trim().to_string(),
+                            ts_type: ts_type.trim().to_string(),
+                            doc: doc.clone(),
+                        });
+                    }
+                    continue;
+                }
+
+                fields.push(EventField {
+                    name: field
+                        .ident
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                    ts_type: rust_to_typescript(&field.ty, &field.attrs),
+                    doc,
+                });
+            }
+        }
+        Fields::Unit => {}
+        Fields::Unnamed(_) => {
+            return Err(syn::Error::new(
+                variant.span(),
+                "plugin_api_events variants must have named fields or none",
+            ));
+        }
+    }
+
+    Ok(ApiEvent {
+        name: to_snake_case(&variant.ident.to_string()),
+        doc: extract_doc_comment(&variant.attrs),
+        fields,
+    })
+}
+
+/// Append `doc` as a JSDoc block indented by `indent`, if there is one
+fn push_jsdoc(lines: &mut Vec<String>, doc: &str, indent: &str) {
+    if doc.is_empty() {
+        return;
+    }
+    lines.push(format!("{}/**", indent));
+    for line in doc.lines() {
+        lines.push(format!("{} * {}", indent, line));
+    }
+    lines.push(format!("{} */", indent));
+}
+
+/// Generate the `EditorEventMap` interface, mapping each event name to the
+/// type of its payload
+fn generate_ts_event_map(events: &[ApiEvent]) -> String {
+    let mut lines = vec![
+        "/**".to_string(),
+        " * Events plugins can subscribe to with `editor.on`, and the data their".to_string(),
+        " * handlers receive".to_string(),
+        " */".to_string(),
+        "interface EditorEventMap {".to_string(),
+    ];
+    for event in events {
+        push_jsdoc(&mut lines, &event.doc, "  ");
+        if event.fields.is_empty() {
+            lines.push(format!("  {}: Record<string, never>;", event.name));
+            continue;
+        }
+        lines.push(format!("  {}: {{", event.name));
+        for field in &event.fields {
+            push_jsdoc(&mut lines, &field.doc, "    ");
+            lines.push(format!("    {}: {};", field.name, field.ts_type));
+        }
+        lines.push("  };".to_string());
+    }
+    lines.push("}".to_string());
+    lines.join("\n") + "\n"
+}
+
+/// Collect all type references from event payloads
+fn collect_event_referenced_types(events: &[ApiEvent]) -> Vec<String> {
+    let types: std::collections::BTreeSet<String> = events
+        .iter()
+        .flat_map(|event| &event.fields)
+        .flat_map(|field| extract_type_references(&field.ts_type))
+        .collect();
+    types.into_iter().collect()
+}
+
 // ============================================================================
 // Proc Macros
 // ============================================================================
@@ -758,6 +1686,23 @@ fn collect_referenced_types(methods: &[ApiMethod]) -> Vec<String> {
 /// - `{IMPL_NAME}_TS_PREAMBLE: &str` - Header comment + getEditor + ProcessHandle + BufferId/SplitId
 /// - `{IMPL_NAME}_TS_EDITOR_API: &str` - Just the EditorAPI interface with methods
 /// - `{IMPL_NAME}_JS_METHODS: &[&str]` - List of all JS method names
+/// - `{IMPL_NAME}_DEPRECATED_METHODS: &[(&str, &str)]` - Deprecated JS method names
+///   and their messages
+/// - `{IMPL_NAME}_PERMIT_METHODS: &[&str]` - QuickJS names of the methods taking
+///   a permit, indexed by its `N`
+/// - `{IMPL_NAME}_TS_OUT_PATH: &str` - Absolute path to write the `.d.ts` file to
+/// - `{IMPL_NAME}_TS_OUT_ENV: Option<&str>` - Environment variable overriding it
+///
+/// It also adds `validate_js_args(method, arg_kinds)` to the impl target.
+///
+/// # Options
+///
+/// - `out = "..."` - Output path relative to the manifest directory
+///   (default `plugins/lib/fresh.d.ts`)
+/// - `env = "..."` - Environment variable that overrides the output path
+/// - `permit = "..."` - Type every method takes as a `_permit: Permit<N>`
+///   parameter after `self`; its `FromParam` conversion runs before the method
+///   and can refuse the call
 ///
 /// The main crate should combine these with ts-rs generated types to create fresh.d.ts.
 ///
@@ -779,10 +1724,14 @@ fn collect_referenced_types(methods: &[ApiMethod]) -> Vec<String> {
 ///
 /// # Errors
 ///
-/// Compile-time error if applied to non-impl items.
+/// Compile-time error if applied to non-impl items, or for an unknown option.
 #[proc_macro_attribute]
-pub fn plugin_api_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(item as ItemImpl);
+pub fn plugin_api_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let options = match parse_impl_options(attr.into()) {
+        Ok(options) => options,
+        Err(err) => return err.to_compile_error().into(),
+    };
+    let mut input = parse_macro_input!(item as ItemImpl);
 
     // Extract impl target name
     let impl_name = match &*input.self_ty {
@@ -805,55 +1754,211 @@ pub fn plugin_api_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let preamble_const = format_ident!("{}_TS_PREAMBLE", impl_name.to_uppercase());
     let editor_api_const = format_ident!("{}_TS_EDITOR_API", impl_name.to_uppercase());
     let methods_const = format_ident!("{}_JS_METHODS", impl_name.to_uppercase());
+    let deprecated_const = format_ident!("{}_DEPRECATED_METHODS", impl_name.to_uppercase());
+    let permit_const = format_ident!("{}_PERMIT_METHODS", impl_name.to_uppercase());
+    let namespace_shim_const = format_ident!("{}_JS_NAMESPACE_SHIM", impl_name.to_uppercase());
+    let out_path_const = format_ident!("{}_TS_OUT_PATH", impl_name.to_uppercase());
+    let out_env_const = format_ident!("{}_TS_OUT_ENV", impl_name.to_uppercase());
 
     // Parse methods into intermediate representation
-    let methods: Vec<ApiMethod> = input
-        .items
-        .iter()
-        .filter_map(|item| {
-            if let ImplItem::Fn(method) = item {
-                parse_method(method)
-            } else {
-                None
+    let mut methods: Vec<ApiMethod> = Vec::new();
+    for item in &input.items {
+        if let ImplItem::Fn(method) = item {
+            match parse_method(method) {
+                Ok(Some(parsed)) => methods.push(parsed),
+                Ok(None) => {}
+                Err(err) => return err.to_compile_error().into(),
             }
-        })
-        .collect();
+        }
+    }
+
+    // A namespace would hide the method of the same name on `editor`
+    if let Some(method) = methods.iter().find(|m| {
+        m.namespace
+            .as_deref()
+            .is_some_and(|ns| methods.iter().any(|other| other.js_name == ns))
+    }) {
+        return compile_error(
+            input.self_ty.span(),
+            &format!(
+                "namespace `{}` of `{}` is also a method name",
+                method.namespace.as_deref().unwrap_or_default(),
+                method.js_name
+            ),
+        )
+        .into();
+    }
+
+    // `#[plugin_api]` isn't allowed on parameters once this macro is done
+    strip_param_attrs(&mut input);
+
+    // Methods taking a permit, after their signatures were read for TypeScript
+    let permit_names = match options.permit.as_deref().map(syn::parse_str::<syn::Path>) {
+        Some(Ok(permit)) => add_permit_params(&mut input, &permit),
+        Some(Err(err)) => return err.to_compile_error().into(),
+        None => Vec::new(),
+    };
 
     // Generate TypeScript parts
     let preamble = generate_ts_preamble();
     let editor_api = generate_editor_api_interface(&methods);
+    let namespace_shim = generate_js_namespace_shim(&methods);
 
     // Collect JS method names
     let js_names: Vec<&str> = methods.iter().map(|m| m.js_name.as_str()).collect();
 
+    // Deprecated methods with what to use instead, for runtime warnings
+    let (deprecated_names, deprecated_messages): (Vec<&str>, Vec<&str>) = methods
+        .iter()
+        .filter_map(|m| Some((m.js_name.as_str(), m.deprecated.as_deref()?)))
+        .unzip();
+
     // Collect referenced types (for ts-rs export)
     let referenced_types = collect_referenced_types(&methods);
     let types_const = format_ident!("{}_REFERENCED_TYPES", impl_name.to_uppercase());
 
-    // Generate output: original impl + constants
+    // Argument validation for the runtime, as an inherent method of the impl target
+    let validate_js_args = generate_validate_js_args(&methods);
+    let self_ty = &input.self_ty;
+    let (impl_generics, _, where_clause) = input.generics.split_for_impl();
+    let validation_impl = quote! {
+        impl #impl_generics #self_ty #where_clause {
+            #validate_js_args
+        }
+    };
+
+    // Output location, resolved against the using crate's manifest directory
+    let out = options.out.as_deref().unwrap_or(DEFAULT_TS_OUT);
+    let out_env = match &options.env {
+        Some(env) => quote! { Some(#env) },
+        None => quote! { None },
+    };
+
+    // Generate output: original impl + validation + constants
     let expanded = quote! {
         #input
 
+        #validation_impl
+
         /// TypeScript preamble (header, getEditor, ProcessHandle, BufferId, SplitId)
         ///
         /// Combine with ts-rs types and EDITOR_API to create fresh.d.ts
         pub const #preamble_const: &str = #preamble;
 
-        /// TypeScript EditorAPI interface (methods only)
+        /// TypeScript EditorAPI interface and its namespace interfaces
         ///
         /// Combine with preamble and ts-rs types to create fresh.d.ts
         pub const #editor_api_const: &str = #editor_api;
 
+        /// JavaScript that creates the `editor.{namespace}` objects
+        ///
+        /// Evaluate after the global `editor` is defined.
+        pub const #namespace_shim_const: &str = #namespace_shim;
+
         /// List of all JavaScript method names exposed in the API
         ///
         /// Useful for verification and debugging.
         pub const #methods_const: &[&str] = &[#(#js_names),*];
 
+        /// Deprecated JavaScript methods as `(name, message)` pairs
+        ///
+        /// The message says what to use instead. Runtimes can use this to warn
+        /// when a plugin calls one of these methods.
+        pub const #deprecated_const: &[(&str, &str)] = &[#((#deprecated_names, #deprecated_messages)),*];
+
+        /// QuickJS names of the methods taking a permit, indexed by its `N`
+        pub const #permit_const: &[&str] = &[#(#permit_names),*];
+
         /// List of TypeScript types referenced in method signatures
         ///
         /// These types need to be defined (via ts-rs or manually) in fresh.d.ts.
         /// Use this to automatically collect type definitions.
         pub const #types_const: &[&str] = &[#(#referenced_types),*];
+
+        /// Absolute path to write the TypeScript definitions to, from `out`
+        pub const #out_path_const: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/", #out);
+
+        /// Environment variable that overrides the output path, from `env`
+        pub const #out_env_const: Option<&str> = #out_env;
+    };
+
+    TokenStream::from(expanded)
+}
+
+/// Generate the TypeScript `EditorEventMap` from the enum of editor events
+///
+/// Each variant is an event named after it in snake_case (`AfterFileSave` ->
+/// `after_file_save`), and its fields are the members of the payload handlers
+/// receive. Field types are converted like method parameters; put
+/// `#[plugin_api(ts_type = "...")]` on a field that is sent differently, or
+/// `#[plugin_api(ts_fields = "start: number; end: number")]` on one that is
+/// sent as several members.
+///
+/// # Generated Constants
+///
+/// - `{ENUM_NAME}_TS_EVENT_MAP: &str` - The `EditorEventMap` interface
+/// - `{ENUM_NAME}_REFERENCED_TYPES: &[&str]` - TypeScript types referenced by the payloads
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[plugin_api_events]
+/// #[derive(Debug, Clone, serde::Serialize)]
+/// pub enum HookArgs {
+///     /// After a buffer is successfully saved
+///     AfterFileSave { buffer_id: BufferId, path: PathBuf },
+///
+///     /// Before text is deleted
+///     BeforeDelete {
+///         buffer_id: BufferId,
+///         #[plugin_api(ts_fields = "start: number; end: number")]
+///         range: Range<usize>,
+///     },
+/// }
+/// ```
+///
+/// # Errors
+///
+/// Compile-time error for tuple variants or if applied to non-enum items.
+#[proc_macro_attribute]
+pub fn plugin_api_events(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut input = parse_macro_input!(item as ItemEnum);
+
+    let mut events: Vec<ApiEvent> = Vec::new();
+    for variant in &input.variants {
+        match parse_event(variant) {
+            Ok(event) => events.push(event),
+            Err(err) => return err.to_compile_error().into(),
+        }
+    }
+
+    // Field attributes must be inert, like parameter attributes
+    for variant in &mut input.variants {
+        for field in variant.fields.iter_mut() {
+            field
+                .attrs
+                .retain(|attr| !attr.path().is_ident("plugin_api"));
+        }
+    }
+
+    let enum_name = input.ident.to_string().to_uppercase();
+    let event_map_const = format_ident!("{}_TS_EVENT_MAP", enum_name);
+    let types_const = format_ident!("{}_REFERENCED_TYPES", enum_name);
+    let event_map = generate_ts_event_map(&events);
+    let referenced_types = collect_event_referenced_types(&events);
+
+    let expanded = quote! {
+        #input
+
+        /// TypeScript `EditorEventMap` interface (event names and payloads)
+        ///
+        /// Combine with the EditorAPI interface, whose `on`/`off` take its keys
+        pub const #event_map_const: &str = #event_map;
+
+        /// List of TypeScript types referenced in event payloads
+        ///
+        /// These types need to be defined (via ts-rs or manually) in fresh.d.ts.
+        pub const #types_const: &[&str] = &[#(#referenced_types),*];
     };
 
     TokenStream::from(expanded)
@@ -871,6 +1976,12 @@ pub fn plugin_api_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `async_thenable` - Method returns `ProcessHandle<T>` (cancellable)
 /// - `ts_type = "..."` - Custom TypeScript type for a parameter
 /// - `ts_return = "..."` - Custom TypeScript return type
+/// - `ts_overload = "(params): ReturnType"` - One TypeScript signature; repeat
+///   the key to declare overloads
+/// - `since = "..."` - Version that added the method
+/// - `deprecated = "..."` - Mark as deprecated, saying what to use instead
+/// - `ts_fields = "a: T; b: U"` - On a `#[plugin_api_events]` field, the payload
+///   members it is sent as
 ///
 /// # Examples
 ///
@@ -886,6 +1997,17 @@ pub fn plugin_api_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 /// // Cancellable operation
 /// #[plugin_api(async_thenable, js_name = "spawnProcess", ts_return = "SpawnResult")]
 /// fn spawn_process_start(&self, cmd: String) -> u64 { ... }
+///
+/// // Accepts a buffer id or a path
+/// #[plugin_api(
+///     ts_overload = "(bufferId: number): boolean",
+///     ts_overload = "(path: string): boolean"
+/// )]
+/// fn close<'js>(&self, target: Value<'js>) -> bool { ... }
+///
+/// // Superseded by another method
+/// #[plugin_api(since = "0.2", deprecated = "use getBufferInfo")]
+/// fn get_buffer_path(&self, id: u32) -> String { ... }
 /// ```
 #[proc_macro_attribute]
 pub fn plugin_api(_attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -912,6 +2034,14 @@ mod tests {
         assert_eq!(to_camel_case("trailing_"), "trailing");
     }
 
+    #[test]
+    fn test_to_snake_case() {
+        assert_eq!(to_snake_case("AfterFileSave"), "after_file_save");
+        assert_eq!(to_snake_case("Idle"), "idle");
+        assert_eq!(to_snake_case("already_snake"), "already_snake");
+        assert_eq!(to_snake_case(""), "");
+    }
+
     #[test]
     fn test_parse_attr_string_value() {
         assert_eq!(
@@ -980,6 +2110,15 @@ mod tests {
         assert!(preamble.contains("AUTO-GENERATED FILE"));
     }
 
+    #[test]
+    fn test_rust_to_typescript_keeps_api_type_names() {
+        let ty: Type = syn::parse_quote!(Option<Vec<BufferInfo>>);
+        assert_eq!(rust_to_typescript(&ty, &[]), "BufferInfo[] | null");
+
+        let ty: Type = syn::parse_quote!(fresh_core::api::QuickPickItem);
+        assert_eq!(rust_to_typescript(&ty, &[]), "QuickPickItem");
+    }
+
     #[test]
     fn test_extract_type_references() {
         // Simple type
@@ -1022,14 +2161,24 @@ mod tests {
                 kind: ApiKind::AsyncThenable,
                 params: vec![],
                 return_type: "SpawnResult".to_string(),
+                overloads: vec![],
                 doc: "".to_string(),
+                since: None,
+                deprecated: None,
+                namespace: None,
+                handle_type: None,
             },
             ApiMethod {
                 js_name: "listBuffers".to_string(),
                 kind: ApiKind::Sync,
                 params: vec![],
                 return_type: "BufferInfo[]".to_string(),
+                overloads: vec![],
                 doc: "".to_string(),
+                since: None,
+                deprecated: None,
+                namespace: None,
+                handle_type: None,
             },
         ];
 
@@ -1045,7 +2194,12 @@ mod tests {
             kind: ApiKind::Sync,
             params: vec![],
             return_type: "number".to_string(),
+            overloads: vec![],
             doc: "Get the active buffer ID".to_string(),
+            since: None,
+            deprecated: None,
+            namespace: None,
+            handle_type: None,
         };
 
         let ts = generate_ts_method(&method);
@@ -1065,7 +2219,12 @@ mod tests {
                 variadic: false,
             }],
             return_type: "void".to_string(),
+            overloads: vec![],
             doc: "".to_string(),
+            since: None,
+            deprecated: None,
+            namespace: None,
+            handle_type: None,
         };
 
         let ts = generate_ts_method(&method);
@@ -1092,7 +2251,12 @@ mod tests {
                 },
             ],
             return_type: "SpawnResult".to_string(),
+            overloads: vec![],
             doc: "Spawn a process".to_string(),
+            since: None,
+            deprecated: None,
+            namespace: None,
+            handle_type: None,
         };
 
         let ts = generate_ts_method(&method);
@@ -1100,4 +2264,384 @@ mod tests {
             ts.contains("spawnProcess(command: string, args: string): ProcessHandle<SpawnResult>;")
         );
     }
+
+    #[test]
+    fn test_generate_ts_method_custom_handle() {
+        let method = ApiMethod {
+            js_name: "spawnBackgroundProcess".to_string(),
+            kind: ApiKind::AsyncThenable,
+            params: vec![],
+            return_type: "BackgroundProcessResult".to_string(),
+            overloads: vec![],
+            doc: "".to_string(),
+            since: None,
+            deprecated: None,
+            namespace: None,
+            handle_type: Some("BackgroundProcessHandle".to_string()),
+        };
+
+        let ts = generate_ts_method(&method);
+        assert!(ts.contains("spawnBackgroundProcess(): BackgroundProcessHandle;"));
+        assert!(
+            collect_referenced_types(&[method]).contains(&"BackgroundProcessResult".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_attr_string_values() {
+        assert_eq!(
+            parse_attr_string_values(
+                r#"ts_overload = "(id: number): string", ts_overload = "(path: string): string""#,
+                "ts_overload"
+            ),
+            vec!["(id: number): string", "(path: string): string"]
+        );
+        assert!(parse_attr_string_values(r#"skip"#, "ts_overload").is_empty());
+    }
+
+    #[test]
+    fn test_overload_parse() {
+        let overload = Overload::parse(
+            "(cb: (x: number) => void, opts?: Record<string, unknown>): Promise<void> | null",
+        )
+        .unwrap();
+        assert_eq!(
+            overload.params,
+            "cb: (x: number) => void, opts?: Record<string, unknown>"
+        );
+        assert_eq!(overload.return_type, "Promise<void> | null");
+        assert_eq!(
+            overload.param_types(),
+            vec!["(x: number) => void", "Record<string, unknown>"]
+        );
+
+        assert_eq!(Overload::parse("(): void").unwrap().params, "");
+        assert!(Overload::parse("id: number").is_none());
+        assert!(Overload::parse("(id: number)").is_none());
+        assert!(Overload::parse("<T(x: T): T").is_none());
+    }
+
+    #[test]
+    fn test_overload_type_params() {
+        let method: ImplItemFn = syn::parse_quote! {
+            #[plugin_api(
+                ts_overload = "<E extends keyof EventMap, K = string>(name: E, handler: (data: EventMap[E]) => void, key: K): Handle"
+            )]
+            pub fn on(&self, name: String, handler: String) {}
+        };
+        let method = parse_method(&method).unwrap().unwrap();
+        let overload = &method.overloads[0];
+        assert_eq!(
+            overload.type_params,
+            "<E extends keyof EventMap, K = string>"
+        );
+        assert_eq!(overload.type_param_names(), vec!["E", "K"]);
+        assert!(generate_ts_method(&method).contains(
+            "on<E extends keyof EventMap, K = string>(name: E, handler: (data: EventMap[E]) => void, key: K): Handle;"
+        ));
+        // Type parameters aren't types to collect
+        assert_eq!(collect_referenced_types(&[method]), vec!["Handle"]);
+    }
+
+    #[test]
+    fn test_parse_method_with_overloads() {
+        let method: ImplItemFn = syn::parse_quote! {
+            /// Close a buffer
+            #[plugin_api(
+                ts_overload = "(bufferId: number): boolean",
+                ts_overload = "(path: string): BufferInfo | null"
+            )]
+            pub fn close_buffer<'js>(&self, target: Value<'js>) -> bool { true }
+        };
+        let method = parse_method(&method).unwrap().unwrap();
+        let ts = generate_ts_method(&method);
+        assert!(ts.contains("closeBuffer(bufferId: number): boolean;"));
+        assert!(ts.contains("closeBuffer(path: string): BufferInfo | null;"));
+        assert_eq!(ts.matches("Close a buffer").count(), 1);
+        assert_eq!(collect_referenced_types(&[method]), vec!["BufferInfo"]);
+
+        let invalid: ImplItemFn = syn::parse_quote! {
+            #[plugin_api(ts_overload = "number")]
+            pub fn broken(&self) {}
+        };
+        assert!(parse_method(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_since_and_deprecated_tags() {
+        let method: ImplItemFn = syn::parse_quote! {
+            /// Get a buffer's path
+            #[plugin_api(since = "0.2", deprecated = "use getBufferInfo")]
+            pub fn get_buffer_path(&self, id: u32) -> String { String::new() }
+        };
+        let method = parse_method(&method).unwrap().unwrap();
+        assert_eq!(method.since.as_deref(), Some("0.2"));
+        assert_eq!(method.deprecated.as_deref(), Some("use getBufferInfo"));
+
+        let ts = generate_ts_method(&method);
+        assert_eq!(
+            ts,
+            "  /**\n   * Get a buffer's path\n   * @since 0.2\n   * @deprecated use getBufferInfo\n   */\n  getBufferPath(id: number): string;"
+        );
+
+        // Tags alone still produce a JSDoc block
+        let undocumented: ImplItemFn = syn::parse_quote! {
+            #[plugin_api(since = "0.4")]
+            pub fn get_version(&self) -> u32 { 0 }
+        };
+        let ts = generate_ts_method(&parse_method(&undocumented).unwrap().unwrap());
+        assert!(ts.starts_with("  /**\n   * @since 0.4\n   */\n"));
+    }
+
+    #[test]
+    fn test_namespaces() {
+        let insert: ImplItemFn = syn::parse_quote! {
+            /// Insert text
+            #[plugin_api(namespace = "buffers")]
+            pub fn insert_text(&self, position: u32, text: String) -> bool { true }
+        };
+        let status: ImplItemFn = syn::parse_quote! {
+            pub fn set_status(&self, message: String) {}
+        };
+        let methods = vec![
+            parse_method(&insert).unwrap().unwrap(),
+            parse_method(&status).unwrap().unwrap(),
+        ];
+
+        let ts = generate_editor_api_interface(&methods);
+        assert!(ts.contains("  readonly buffers: EditorBuffersAPI;"));
+        assert!(ts.contains("interface EditorBuffersAPI {\n  /**\n   * Insert text\n   */\n  insertText(position: number, text: string): boolean;\n}"));
+        // Grouped methods stay on `editor` too
+        assert_eq!(
+            ts.matches("insertText(position: number, text: string): boolean;")
+                .count(),
+            2
+        );
+
+        assert_eq!(
+            generate_js_namespace_shim(&methods),
+            "editor.buffers = Object.freeze({\n  insertText: function(...args) { return editor.insertText(...args); },\n});\n"
+        );
+
+        let invalid: ImplItemFn = syn::parse_quote! {
+            #[plugin_api(namespace = "my-buffers")]
+            pub fn broken(&self) {}
+        };
+        assert!(parse_method(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_param_ts_type_is_used_and_stripped() {
+        let mut input: ItemImpl = syn::parse_quote! {
+            impl JsEditorApi {
+                pub fn find<'js>(
+                    &self,
+                    #[plugin_api(ts_type = "number | string")] target: Value<'js>,
+                ) -> bool { true }
+            }
+        };
+        let ImplItem::Fn(method) = &input.items[0] else {
+            unreachable!()
+        };
+        let parsed = parse_method(method).unwrap().unwrap();
+        assert_eq!(parsed.params[0].to_typescript(), "target: number | string");
+
+        strip_param_attrs(&mut input);
+        let ImplItem::Fn(method) = &input.items[0] else {
+            unreachable!()
+        };
+        let FnArg::Typed(param) = &method.sig.inputs[1] else {
+            unreachable!()
+        };
+        assert!(param.attrs.is_empty());
+    }
+
+    #[test]
+    fn test_permit_params_follow_self() {
+        let mut input: ItemImpl = syn::parse_quote! {
+            impl JsEditorApi {
+                pub fn read_file(&self, path: String) -> bool { true }
+                #[qjs(rename = "_fetchStart")]
+                pub fn fetch_start(&self, ctx: Ctx<'_>) -> u64 { 0 }
+                fn helper() {}
+            }
+        };
+        let names = add_permit_params(&mut input, &syn::parse_quote!(Permit));
+        assert_eq!(names, vec!["readFile", "_fetchStart"]);
+
+        let params: Vec<String> = input
+            .items
+            .iter()
+            .map(|item| {
+                let ImplItem::Fn(method) = item else {
+                    unreachable!()
+                };
+                let inputs = &method.sig.inputs;
+                quote!(#inputs).to_string()
+            })
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                "& self , _permit : Permit < 0usize > , path : String",
+                "& self , _permit : Permit < 1usize > , ctx : Ctx < '_ >",
+                "",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_js_kinds() {
+        assert_eq!(js_kinds("number"), Some(vec!["number"]));
+        assert_eq!(js_kinds("BufferId"), Some(vec!["number"]));
+        assert_eq!(
+            js_kinds("string | null"),
+            Some(vec!["string", "null", "undefined"])
+        );
+        assert_eq!(js_kinds("(string | number)[]"), Some(vec!["array"]));
+        assert_eq!(
+            js_kinds("Record<string, unknown>"),
+            Some(vec!["object", "array"])
+        );
+        assert_eq!(js_kinds("BufferInfo | null"), None);
+        assert_eq!(js_kinds("unknown"), None);
+    }
+
+    #[test]
+    fn test_overloaded_methods_are_not_validated() {
+        let method: ImplItemFn = syn::parse_quote! {
+            #[plugin_api(ts_overload = "(bufferId: number): boolean")]
+            pub fn close_buffer<'js>(&self, target: Value<'js>) -> bool { true }
+        };
+        assert!(generate_validation_arm(&parse_method(&method).unwrap().unwrap()).is_none());
+
+        let method: ImplItemFn = syn::parse_quote! {
+            pub fn insert_text(&self, buffer_id: u32, text: String, extra: Opt<bool>) -> bool { true }
+        };
+        let arm = generate_validation_arm(&parse_method(&method).unwrap().unwrap())
+            .unwrap()
+            .to_string();
+        assert!(arm.starts_with("\"insertText\" =>"));
+        assert!(arm.contains("\"extra\" , \"extra?: boolean\" , \"boolean\" , & [\"boolean\" , \"undefined\"] , false"));
+    }
+
+    #[test]
+    fn test_generate_ts_event_map() {
+        let item: ItemEnum = syn::parse_quote! {
+            pub enum HookArgs {
+                /// After a buffer is successfully saved
+                AfterFileSave { buffer_id: BufferId, path: PathBuf },
+
+                /// Before text is deleted
+                BeforeDelete {
+                    /// The buffer
+                    buffer_id: BufferId,
+                    #[plugin_api(ts_fields = "start: number; end: number")]
+                    range: Range<usize>,
+                    #[plugin_api(ts_type = "number")]
+                    cursor_id: CursorId,
+                    lines: Vec<LineInfo>,
+                    selected: Option<usize>,
+                },
+
+                EditorInitialized,
+            }
+        };
+        let events: Vec<ApiEvent> = item
+            .variants
+            .iter()
+            .map(|variant| parse_event(variant).unwrap())
+            .collect();
+        let ts = generate_ts_event_map(&events);
+
+        assert!(ts.contains("interface EditorEventMap {"));
+        assert!(ts.contains(
+            "  /**\n   * After a buffer is successfully saved\n   */\n  after_file_save: {\n    buffer_id: BufferId;\n    path: string;\n  };"
+        ));
+        assert!(ts.contains("     * The buffer\n     */\n    buffer_id: BufferId;"));
+        assert!(ts.contains("    start: number;\n    end: number;"));
+        assert!(ts.contains("    cursor_id: number;"));
+        assert!(ts.contains("    lines: LineInfo[];"));
+        assert!(ts.contains("    selected: number | null;"));
+        assert!(ts.contains("  editor_initialized: Record<string, never>;"));
+        assert_eq!(collect_event_referenced_types(&events), vec!["LineInfo"]);
+
+        let tuple: ItemEnum = syn::parse_quote! {
+            pub enum HookArgs {
+                Idle(u64),
+            }
+        };
+        assert!(parse_event(&tuple.variants[0]).is_err());
+    }
+
+    #[test]
+    fn test_parse_impl_options() {
+        assert_eq!(
+            parse_impl_options(quote! {}).unwrap(),
+            ImplOptions::default()
+        );
+        assert_eq!(
+            parse_impl_options(
+                quote! { out = "../lib/api.d.ts", env = "API_DTS_OUT", permit = "Permit" }
+            )
+            .unwrap(),
+            ImplOptions {
+                out: Some("../lib/api.d.ts".to_string()),
+                env: Some("API_DTS_OUT".to_string()),
+                permit: Some("Permit".to_string()),
+            }
+        );
+        assert!(parse_impl_options(quote! { output = "x.d.ts" }).is_err());
+        assert!(parse_impl_options(quote! { out = 1 }).is_err());
+        assert!(parse_impl_options(quote! { env = "" }).is_err());
+    }
+
+    #[test]
+    fn test_rust_to_typescript_callbacks() {
+        let ty: Type = syn::parse_quote!(rquickjs::Function<'js>);
+        assert_eq!(
+            rust_to_typescript(&ty, &[]),
+            "(...args: unknown[]) => unknown"
+        );
+
+        let ty: Type = syn::parse_quote!(Callback<'js, (u32, String), bool>);
+        assert_eq!(
+            rust_to_typescript(&ty, &[]),
+            "(arg0: number, arg1: string) => boolean"
+        );
+        let ty: Type = syn::parse_quote!(Callback<'js, (u32,), ()>);
+        assert_eq!(rust_to_typescript(&ty, &[]), "(arg0: number) => void");
+        let ty: Type = syn::parse_quote!(Callback<'js, u32, ()>);
+        assert_eq!(rust_to_typescript(&ty, &[]), "(arg0: number) => void");
+        let ty: Type = syn::parse_quote!(Callback<'js, (), Option<String>>);
+        assert_eq!(rust_to_typescript(&ty, &[]), "() => string | null");
+
+        // Function types are parenthesized in unions and arrays
+        let ty: Type = syn::parse_quote!(Option<Callback<'js, (), ()>>);
+        assert_eq!(rust_to_typescript(&ty, &[]), "(() => void) | null");
+        let ty: Type = syn::parse_quote!(Vec<Function<'js>>);
+        assert_eq!(
+            rust_to_typescript(&ty, &[]),
+            "((...args: unknown[]) => unknown)[]"
+        );
+
+        // A parameter's ts_type names the arguments, and calls are checked for a function
+        let method: ImplItemFn = syn::parse_quote! {
+            pub fn on_line<'js>(
+                &self,
+                #[plugin_api(ts_type = "(line: number) => void")] callback: Callback<'js, (u32,), ()>,
+                later: Opt<Function<'js>>,
+            ) {}
+        };
+        let method = parse_method(&method).unwrap().unwrap();
+        assert!(generate_ts_method(&method).contains(
+            "onLine(callback: (line: number) => void, later?: (...args: unknown[]) => unknown): void;"
+        ));
+        assert!(collect_referenced_types(&[method]).is_empty());
+        assert_eq!(js_kinds("(line: number) => void"), Some(vec!["function"]));
+        assert_eq!(
+            js_kinds("(() => void) | null"),
+            Some(vec!["function", "null", "undefined"])
+        );
+    }
 }
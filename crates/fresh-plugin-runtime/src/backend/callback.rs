@@ -0,0 +1,42 @@
+//! Typed JS callback parameters
+//!
+//! `Callback<'js, Args, Ret>` is a `Function` that says what it is called with
+//! and what it returns, so `#[plugin_api_impl]` can declare the parameter as a
+//! TypeScript function type (`(arg0: number) => void`) instead of `Function`.
+
+use rquickjs::function::IntoArgs;
+use rquickjs::{Ctx, FromJs, Function, Value};
+use std::marker::PhantomData;
+
+/// A JS function called with the values of the `Args` tuple and returning `Ret`
+pub struct Callback<'js, Args, Ret> {
+    function: Function<'js>,
+    signature: PhantomData<fn(Args) -> Ret>,
+}
+
+impl<'js, Args, Ret> Callback<'js, Args, Ret>
+where
+    Args: IntoArgs<'js>,
+    Ret: FromJs<'js>,
+{
+    /// Call the function
+    pub fn call(&self, args: Args) -> rquickjs::Result<Ret> {
+        self.function.call(args)
+    }
+}
+
+impl<'js, Args, Ret> Callback<'js, Args, Ret> {
+    /// The underlying function, e.g. to keep it as a `Persistent`
+    pub fn into_function(self) -> Function<'js> {
+        self.function
+    }
+}
+
+impl<'js, Args, Ret> FromJs<'js> for Callback<'js, Args, Ret> {
+    fn from_js(ctx: &Ctx<'js>, value: Value<'js>) -> rquickjs::Result<Self> {
+        Ok(Self {
+            function: Function::from_js(ctx, value)?,
+            signature: PhantomData,
+        })
+    }
+}
@@ -0,0 +1,76 @@
+//! Reading, editing, opening and creating buffers
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Buffers,
+    methods: &[
+        "getActiveBufferId",
+        "listBuffers",
+        "getCursorPosition",
+        "getBufferPath",
+        "getBufferLength",
+        "isBufferModified",
+        "getBufferInfo",
+        "getPrimaryCursor",
+        "getAllCursors",
+        "getAllCursorPositions",
+        "getViewport",
+        "getCursorLine",
+        "getLineStartPosition",
+        "findBufferByPath",
+        "getBufferSavedDiff",
+        "getBufferText",
+        "getBufferLines",
+        "getLineCount",
+        "offsetToLineCol",
+        "lineColToOffset",
+        "querySyntax",
+        "search",
+        "getTextPropertiesAtCursor",
+        "getHighlights",
+        "computeDiff",
+        "insertText",
+        "deleteRange",
+        "applyEdits",
+        "insertAtCursor",
+        "beginUndoGroup",
+        "endUndoGroup",
+        "undo",
+        "redo",
+        "getUndoDepth",
+        "setBufferCursor",
+        "setSelections",
+        "addCursor",
+        "clearSecondaryCursors",
+        "setBufferDisplayOptions",
+        "refreshLines",
+        "openFile",
+        "openFileInSplit",
+        "showBuffer",
+        "closeBuffer",
+        "createVirtualBuffer",
+        "createVirtualBufferInSplit",
+        "createVirtualBufferInExistingSplit",
+        "setVirtualBufferContent",
+        "createCompositeBuffer",
+        "updateCompositeAlignment",
+        "closeCompositeBuffer",
+    ],
+    promises: &[
+        "getBufferText",
+        "getBufferLines",
+        "getLineCount",
+        "offsetToLineCol",
+        "lineColToOffset",
+        "querySyntax",
+        "search",
+        "getHighlights",
+        "computeDiff",
+        "createVirtualBuffer",
+        "createVirtualBufferInSplit",
+        "createVirtualBufferInExistingSplit",
+        "createCompositeBuffer",
+    ],
+    thenables: &[],
+};
@@ -0,0 +1,10 @@
+//! Reading the editor's environment variables
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Env,
+    methods: &["getEnv"],
+    promises: &[],
+    thenables: &[],
+};
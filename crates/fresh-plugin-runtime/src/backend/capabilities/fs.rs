@@ -0,0 +1,17 @@
+//! Reading and writing files outside of buffers
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const READ_MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::FsRead,
+    methods: &["fileExists", "fileStat", "readFile", "readDir"],
+    promises: &[],
+    thenables: &[],
+};
+
+pub(super) const WRITE_MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::FsWrite,
+    methods: &["writeFile", "saveBufferToPath"],
+    promises: &[],
+    thenables: &[],
+};
@@ -0,0 +1,21 @@
+//! Language servers and plugin-provided language features
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Lsp,
+    methods: &[
+        "registerLspServer",
+        "disableLspForLanguage",
+        "setLspRootUri",
+        "sendLspRequest",
+        "getAllDiagnostics",
+        "registerCompletionProvider",
+        "registerHoverProvider",
+        "registerInlineSuggestionProvider",
+        "registerFormatter",
+        "registerEvaluator",
+    ],
+    promises: &["sendLspRequest"],
+    thenables: &[],
+};
@@ -0,0 +1,374 @@
+//! Capability modules for the plugin API
+//!
+//! The `editor` object's methods are grouped into capabilities (files,
+//! buffers, overlays, processes, network, environment, LSP, UI). Each
+//! capability module registers its methods and which of them are async, so
+//! the QuickJS setup can install the promise wrappers from this registry
+//! instead of a hand-kept list.
+//!
+//! A capability can be disabled per plugin with
+//! [`QuickJsBackend::set_plugin_capabilities`](super::QuickJsBackend::set_plugin_capabilities):
+//! its methods then throw, which every method checks in Rust before it runs
+//! (see [`Permit`](super::quickjs_backend::Permit)). Methods not claimed by
+//! any module (logging, commands, events, config, i18n, plugin management)
+//! are core and always available.
+//!
+//! The capabilities that reach outside the editor ([`Capability::PERMISSIONS`])
+//! are also permissions: a plugin with a `plugin.json` manifest only gets the
+//! ones it declares there, and only once the user has allowed them.
+
+mod buffers;
+mod env;
+mod fs;
+mod lsp;
+mod net;
+mod overlays;
+mod process;
+mod ui;
+
+use std::fmt;
+use std::str::FromStr;
+
+/// A group of plugin API methods that can be enabled or disabled together
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// Reading files outside of buffers
+    FsRead,
+    /// Writing files outside of buffers
+    FsWrite,
+    /// Reading, editing, opening and creating buffers
+    Buffers,
+    /// Overlays, virtual text and other display decorations
+    Overlays,
+    /// Running external processes
+    Process,
+    /// HTTP requests to allowed hosts
+    Network,
+    /// Reading environment variables
+    Env,
+    /// Language servers and plugin-provided language features
+    Lsp,
+    /// Prompts, modes, splits and the status bar
+    Ui,
+}
+
+impl Capability {
+    /// All capabilities
+    pub const ALL: [Capability; 9] = [
+        Capability::FsRead,
+        Capability::FsWrite,
+        Capability::Buffers,
+        Capability::Overlays,
+        Capability::Process,
+        Capability::Network,
+        Capability::Env,
+        Capability::Lsp,
+        Capability::Ui,
+    ];
+
+    /// Capabilities a plugin with a manifest must declare and the user must allow
+    pub const PERMISSIONS: [Capability; 5] = [
+        Capability::FsRead,
+        Capability::FsWrite,
+        Capability::Process,
+        Capability::Network,
+        Capability::Env,
+    ];
+
+    /// Name used in configuration and error messages
+    pub fn name(self) -> &'static str {
+        match self {
+            Capability::FsRead => "fs-read",
+            Capability::FsWrite => "fs-write",
+            Capability::Buffers => "buffers",
+            Capability::Overlays => "overlays",
+            Capability::Process => "process",
+            Capability::Network => "network",
+            Capability::Env => "env",
+            Capability::Lsp => "lsp",
+            Capability::Ui => "ui",
+        }
+    }
+
+    /// Whether this capability is one of [`Capability::PERMISSIONS`]
+    pub fn is_permission(self) -> bool {
+        Capability::PERMISSIONS.contains(&self)
+    }
+
+    fn bit(self) -> u16 {
+        1 << (self as u16)
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Capability {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Capability::ALL
+            .into_iter()
+            .find(|capability| capability.name() == s)
+            .ok_or_else(|| format!("unknown plugin capability: {}", s))
+    }
+}
+
+/// The set of capabilities a plugin may use (all of them by default)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapabilitySet(u16);
+
+impl CapabilitySet {
+    /// Every capability enabled
+    pub fn all() -> Self {
+        Capability::ALL.into_iter().collect()
+    }
+
+    /// Only core methods available
+    pub fn none() -> Self {
+        Self(0)
+    }
+
+    pub fn contains(self, capability: Capability) -> bool {
+        self.0 & capability.bit() != 0
+    }
+
+    pub fn insert(&mut self, capability: Capability) {
+        self.0 |= capability.bit();
+    }
+
+    pub fn remove(&mut self, capability: Capability) {
+        self.0 &= !capability.bit();
+    }
+
+    /// Capabilities in this set that are not in `other`
+    pub fn difference(self, other: CapabilitySet) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// The capabilities in this set
+    pub fn iter(self) -> impl Iterator<Item = Capability> {
+        Capability::ALL
+            .into_iter()
+            .filter(move |capability| self.contains(*capability))
+    }
+}
+
+impl Default for CapabilitySet {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl FromIterator<Capability> for CapabilitySet {
+    fn from_iter<I: IntoIterator<Item = Capability>>(iter: I) -> Self {
+        let mut set = Self::none();
+        for capability in iter {
+            set.insert(capability);
+        }
+        set
+    }
+}
+
+/// What a plugin's context may call
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(crate) struct PluginCapabilities {
+    /// Capabilities whose methods the plugin may call
+    pub enabled: CapabilitySet,
+    /// Enabled permissions the user has not allowed yet
+    pub pending: CapabilitySet,
+    /// Whether the plugin has a `plugin.json` manifest
+    pub has_manifest: bool,
+}
+
+/// Methods a capability registers on the `editor` object
+pub(crate) struct CapabilityModule {
+    pub capability: Capability,
+    /// JS names of every method in the capability
+    pub methods: &'static [&'static str],
+    /// Async methods returning a promise (`_{name}Start` returns the callback id)
+    pub promises: &'static [&'static str],
+    /// Async methods returning a thenable process handle
+    pub thenables: &'static [&'static str],
+}
+
+/// Registered capability modules
+pub(crate) const MODULES: &[CapabilityModule] = &[
+    fs::READ_MODULE,
+    fs::WRITE_MODULE,
+    buffers::MODULE,
+    overlays::MODULE,
+    process::MODULE,
+    net::MODULE,
+    env::MODULE,
+    lsp::MODULE,
+    ui::MODULE,
+];
+
+/// Async core methods returning a promise
+const CORE_PROMISES: &[&str] = &[
+    "delay",
+    "loadPlugin",
+    "unloadPlugin",
+    "reloadPlugin",
+    "listPlugins",
+    "runInWorker",
+];
+
+/// JS that installs the promise wrappers for every async method
+///
+/// Runs after `_wrapAsync` / `_wrapAsyncThenable` are defined.
+pub(crate) fn async_wrappers_js() -> String {
+    let promises = CORE_PROMISES
+        .iter()
+        .chain(MODULES.iter().flat_map(|module| module.promises))
+        .map(|name| (name, "_wrapAsync"));
+    let thenables = MODULES
+        .iter()
+        .flat_map(|module| module.thenables)
+        .map(|name| (name, "_wrapAsyncThenable"));
+    promises
+        .chain(thenables)
+        .map(|(name, wrapper)| {
+            format!(
+                "editor.{name} = {wrapper}(\"_{name}Start\", \"{name}\");\n",
+                name = name,
+                wrapper = wrapper
+            )
+        })
+        .collect()
+}
+
+/// Methods of the given capabilities, with the capability each belongs to
+/// (includes the `_{name}Start` halves of async methods)
+pub(crate) fn capability_methods(capabilities: CapabilitySet) -> Vec<(String, Capability)> {
+    MODULES
+        .iter()
+        .filter(|module| capabilities.contains(module.capability))
+        .flat_map(|module| {
+            let starts = module
+                .promises
+                .iter()
+                .chain(module.thenables)
+                .map(|name| format!("_{}Start", name));
+            module
+                .methods
+                .iter()
+                .map(|name| name.to_string())
+                .chain(starts)
+                .map(|name| (name, module.capability))
+        })
+        .collect()
+}
+
+/// Capability a method of the `editor` object belongs to, by its QuickJS
+/// name (None for core methods)
+pub(crate) fn method_capability(name: &str) -> Option<Capability> {
+    capability_methods(CapabilitySet::all())
+        .into_iter()
+        .find(|(method, _)| method == name)
+        .map(|(_, capability)| capability)
+}
+
+/// Parse a `plugin.json` manifest into the capabilities it declares
+///
+/// ```json
+/// { "capabilities": ["fs-read", "process"] }
+/// ```
+pub fn parse_manifest(content: &str) -> Result<CapabilitySet, String> {
+    #[derive(serde::Deserialize)]
+    struct Manifest {
+        #[serde(default)]
+        capabilities: Vec<String>,
+    }
+    let manifest: Manifest = serde_json::from_str(content).map_err(|e| e.to_string())?;
+    manifest
+        .capabilities
+        .iter()
+        .map(|name| name.parse::<Capability>())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::quickjs_backend::JSEDITORAPI_JS_METHODS;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_modules_register_existing_methods_once() {
+        let mut seen = HashSet::new();
+        for module in MODULES {
+            for name in module.methods {
+                assert!(
+                    JSEDITORAPI_JS_METHODS.contains(name),
+                    "{} registers unknown method {}",
+                    module.capability,
+                    name
+                );
+                assert!(seen.insert(*name), "{} is registered twice", name);
+            }
+            for name in module.promises.iter().chain(module.thenables) {
+                assert!(
+                    module.methods.contains(name),
+                    "async method {} is not listed in {}",
+                    name,
+                    module.capability
+                );
+            }
+        }
+        for name in CORE_PROMISES {
+            assert!(JSEDITORAPI_JS_METHODS.contains(name));
+            assert!(!seen.contains(name), "core method {} is registered", name);
+        }
+    }
+
+    #[test]
+    fn test_capability_set() {
+        let mut set: CapabilitySet = "fs-read,process"
+            .split(',')
+            .map(|name| name.parse::<Capability>().unwrap())
+            .collect();
+        assert!(set.contains(Capability::FsRead));
+        assert!(!set.contains(Capability::FsWrite));
+        assert!(!set.contains(Capability::Ui));
+        set.remove(Capability::FsRead);
+        assert!(!set.contains(Capability::FsRead));
+        assert!("net".parse::<Capability>().is_err());
+        assert_eq!(
+            CapabilitySet::all().difference(set).iter().count(),
+            Capability::ALL.len() - 1
+        );
+
+        assert_eq!(
+            method_capability("_spawnProcessStart"),
+            Some(Capability::Process)
+        );
+        assert_eq!(method_capability("spawnProcess"), Some(Capability::Process));
+        assert_eq!(method_capability("setStatus"), Some(Capability::Ui));
+        assert_eq!(method_capability("debug"), None);
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let declared = parse_manifest(r#"{"capabilities": ["fs-read", "env"]}"#).unwrap();
+        assert_eq!(
+            declared.iter().collect::<Vec<_>>(),
+            vec![Capability::FsRead, Capability::Env]
+        );
+        assert_eq!(parse_manifest("{}").unwrap(), CapabilitySet::none());
+        assert_eq!(
+            parse_manifest(r#"{"capabilities": ["fs"]}"#).unwrap_err(),
+            "unknown plugin capability: fs"
+        );
+        assert!(parse_manifest("not json").is_err());
+
+        let methods = capability_methods(declared);
+        assert!(methods.contains(&("readFile".to_string(), Capability::FsRead)));
+        assert!(methods.contains(&("getEnv".to_string(), Capability::Env)));
+        assert!(!methods.iter().any(|(name, _)| name == "writeFile"));
+    }
+}
@@ -0,0 +1,10 @@
+//! HTTP requests to hosts allowed by the editor configuration
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Network,
+    methods: &["fetch"],
+    promises: &["fetch"],
+    thenables: &[],
+};
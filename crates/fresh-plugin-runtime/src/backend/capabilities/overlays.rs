@@ -0,0 +1,39 @@
+//! Decorating what is displayed without changing buffer text
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Overlays,
+    methods: &[
+        "addOverlay",
+        "removeOverlay",
+        "clearNamespace",
+        "clearAllOverlays",
+        "clearOverlaysInRange",
+        "addVirtualText",
+        "removeVirtualText",
+        "removeVirtualTextsByPrefix",
+        "clearVirtualTexts",
+        "clearVirtualTextNamespace",
+        "addVirtualLine",
+        "createMarker",
+        "deleteMarker",
+        "getMarkerPosition",
+        "setLineIndicator",
+        "clearLineIndicators",
+        "setLineBackground",
+        "setLineBackgrounds",
+        "clearLineBackgrounds",
+        "registerGutter",
+        "unregisterGutter",
+        "setGutterText",
+        "setGutterTexts",
+        "clearGutterText",
+        "submitViewTransform",
+        "clearViewTransform",
+        "setFileExplorerDecorations",
+        "clearFileExplorerDecorations",
+    ],
+    promises: &["getMarkerPosition"],
+    thenables: &[],
+};
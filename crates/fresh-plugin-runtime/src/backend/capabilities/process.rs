@@ -0,0 +1,17 @@
+//! Running and stopping external processes
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Process,
+    methods: &[
+        "spawnProcess",
+        "spawnProcessWait",
+        "spawnBackgroundProcess",
+        "killBackgroundProcess",
+        "isProcessRunning",
+        "killProcess",
+    ],
+    promises: &["spawnProcessWait"],
+    thenables: &["spawnProcess", "spawnBackgroundProcess"],
+};
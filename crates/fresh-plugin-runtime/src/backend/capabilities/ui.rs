@@ -0,0 +1,55 @@
+//! Prompts, modes, splits, the status bar and other editor chrome
+
+use super::{Capability, CapabilityModule};
+
+pub(super) const MODULE: CapabilityModule = CapabilityModule {
+    capability: Capability::Ui,
+    methods: &[
+        "setStatus",
+        "setStatusBarSegment",
+        "copyToClipboard",
+        "setClipboard",
+        "prompt",
+        "quickPick",
+        "showPicker",
+        "showConfirm",
+        "showInput",
+        "startPrompt",
+        "startPromptWithInitial",
+        "setPromptSuggestions",
+        "showActionPopup",
+        "openFileBrowser",
+        "revealInExplorer",
+        "defineMode",
+        "setEditorMode",
+        "getEditorMode",
+        "getActiveSplitId",
+        "getLayout",
+        "splitEditor",
+        "closeSplit",
+        "setSplitBuffer",
+        "focusSplit",
+        "setSplitScroll",
+        "setSplitRatio",
+        "setSplitTheme",
+        "distributeSplitsEvenly",
+        "setLineNumbers",
+        "createScrollSyncGroup",
+        "addScrollSyncSplit",
+        "removeScrollSyncSplit",
+        "setScrollSyncAnchors",
+        "removeScrollSyncGroup",
+        "applyTheme",
+    ],
+    promises: &[
+        "prompt",
+        "quickPick",
+        "showPicker",
+        "showConfirm",
+        "showInput",
+        "openFileBrowser",
+        "getLayout",
+        "splitEditor",
+    ],
+    thenables: &[],
+};
@@ -0,0 +1,267 @@
+//! Rate limiting of event handlers registered with options
+//!
+//! `editor.on("cursor_moved", handler, { debounceMs: 50 })` keeps a handler
+//! from running on every keystroke. The dispatcher asks the handler's
+//! [`Coalescer`] what to do with each event: run the handler now, or hold the
+//! event until a deadline, when the plugin thread's timer calls [`Coalescer::flush`].
+//! Held events replace each other, so the handler only sees the latest one,
+//! except for events that describe changes rather than state: held
+//! `buffer_changed` events are merged, so no change is lost.
+
+use fresh_core::api::EventOptions;
+use std::time::{Duration, Instant};
+
+/// Events whose held payloads are merged instead of replaced
+const MERGED_EVENTS: &[&str] = &["buffer_changed"];
+
+/// What to do with an event
+#[derive(Debug, PartialEq)]
+pub(crate) enum Delivery {
+    /// Run the handler with this event now
+    Now(serde_json::Value),
+    /// The event is held; flush at this instant
+    At(Instant),
+}
+
+/// Rate limiting state of one event handler
+#[derive(Debug)]
+pub(crate) struct Coalescer {
+    debounce: Option<Duration>,
+    leading: bool,
+    throttle: Option<Duration>,
+    /// When the handler last ran
+    last_run: Option<Instant>,
+    /// End of the current burst of events (debounce only)
+    quiet_at: Option<Instant>,
+    /// Held events are merged (see [`MERGED_EVENTS`])
+    merge: bool,
+    /// The held events (one, unless merged events for different buffers
+    /// are held) and when the first of them arrived
+    pending: Vec<serde_json::Value>,
+    pending_since: Option<Instant>,
+}
+
+impl Coalescer {
+    /// A coalescer for `event_name` with `options`, or None if they don't
+    /// limit anything
+    pub(crate) fn new(event_name: &str, options: &EventOptions) -> Option<Self> {
+        let debounce = options.debounce_ms.map(Duration::from_millis);
+        let throttle = options.throttle_ms.map(Duration::from_millis);
+        if debounce.is_none() && throttle.is_none() {
+            return None;
+        }
+        Some(Self {
+            debounce,
+            leading: options.leading.unwrap_or(false),
+            throttle,
+            last_run: None,
+            quiet_at: None,
+            merge: MERGED_EVENTS.contains(&event_name),
+            pending: Vec::new(),
+            pending_since: None,
+        })
+    }
+
+    /// Decide what to do with an event that arrived at `now`
+    pub(crate) fn event(&mut self, data: serde_json::Value, now: Instant) -> Delivery {
+        if let Some(debounce) = self.debounce {
+            let in_burst = self.quiet_at.is_some_and(|quiet_at| now < quiet_at);
+            self.quiet_at = Some(now + debounce);
+            if self.leading && !in_burst {
+                return self.run(data, now);
+            }
+            let since = self.hold(data, now);
+            let mut at = now + debounce;
+            if let Some(max_wait) = self.throttle {
+                at = at.min(since + max_wait);
+            }
+            return Delivery::At(at);
+        }
+
+        let interval = self.throttle.unwrap_or_default();
+        match self.last_run {
+            Some(last_run) if now < last_run + interval => {
+                self.hold(data, now);
+                Delivery::At(last_run + interval)
+            }
+            _ => self.run(data, now),
+        }
+    }
+
+    /// Run the handler with an event now, unless merged events are still
+    /// held: those have to go first, so the event joins them
+    fn run(&mut self, data: serde_json::Value, now: Instant) -> Delivery {
+        if self.merge && !self.pending.is_empty() {
+            self.hold(data, now);
+            return Delivery::At(now);
+        }
+        self.last_run = Some(now);
+        Delivery::Now(data)
+    }
+
+    /// Hold an event until the flush; returns when the first held event arrived
+    fn hold(&mut self, data: serde_json::Value, now: Instant) -> Instant {
+        let since = *self.pending_since.get_or_insert(now);
+        if !self.merge {
+            self.pending = vec![data];
+            return since;
+        }
+        // Changes to the same buffer are appended to its held event
+        match self
+            .pending
+            .iter_mut()
+            .find(|held| held["buffer_id"] == data["buffer_id"])
+        {
+            Some(held) => {
+                if let (Some(changes), Some(newer)) =
+                    (held["changes"].as_array_mut(), data["changes"].as_array())
+                {
+                    changes.extend(newer.iter().cloned());
+                }
+                held["version"] = data["version"].clone();
+            }
+            None => self.pending.push(data),
+        }
+        since
+    }
+
+    /// Take the held events when their deadline has passed, to run the
+    /// handler with
+    pub(crate) fn flush(&mut self, now: Instant) -> Vec<serde_json::Value> {
+        self.pending_since = None;
+        let events = std::mem::take(&mut self.pending);
+        if !events.is_empty() {
+            self.last_run = Some(now);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn coalescer(debounce_ms: Option<u64>, leading: bool, throttle_ms: Option<u64>) -> Coalescer {
+        Coalescer::new(
+            "cursor_moved",
+            &EventOptions {
+                debounce_ms,
+                leading: Some(leading),
+                throttle_ms,
+            },
+        )
+        .unwrap()
+    }
+
+    fn ms(start: Instant, ms: u64) -> Instant {
+        start + Duration::from_millis(ms)
+    }
+
+    #[test]
+    fn test_no_limits_needs_no_coalescer() {
+        assert!(Coalescer::new("cursor_moved", &EventOptions::default()).is_none());
+    }
+
+    #[test]
+    fn test_debounce_delivers_latest_event_after_quiet_period() {
+        let start = Instant::now();
+        let mut c = coalescer(Some(50), false, None);
+        assert_eq!(c.event(json!(1), start), Delivery::At(ms(start, 50)));
+        assert_eq!(
+            c.event(json!(2), ms(start, 30)),
+            Delivery::At(ms(start, 80))
+        );
+        assert_eq!(c.flush(ms(start, 80)), vec![json!(2)]);
+        assert_eq!(c.flush(ms(start, 90)), Vec::<serde_json::Value>::new());
+    }
+
+    #[test]
+    fn test_leading_debounce_runs_first_event_of_each_burst() {
+        let start = Instant::now();
+        let mut c = coalescer(Some(50), true, None);
+        assert_eq!(c.event(json!(1), start), Delivery::Now(json!(1)));
+        assert_eq!(
+            c.event(json!(2), ms(start, 20)),
+            Delivery::At(ms(start, 70))
+        );
+        assert_eq!(c.flush(ms(start, 70)), vec![json!(2)]);
+        // The burst is over: the next event runs right away again
+        assert_eq!(c.event(json!(3), ms(start, 200)), Delivery::Now(json!(3)));
+    }
+
+    #[test]
+    fn test_throttle_limits_rate() {
+        let start = Instant::now();
+        let mut c = coalescer(None, false, Some(100));
+        assert_eq!(c.event(json!(1), start), Delivery::Now(json!(1)));
+        assert_eq!(
+            c.event(json!(2), ms(start, 10)),
+            Delivery::At(ms(start, 100))
+        );
+        assert_eq!(
+            c.event(json!(3), ms(start, 60)),
+            Delivery::At(ms(start, 100))
+        );
+        assert_eq!(c.flush(ms(start, 100)), vec![json!(3)]);
+        assert_eq!(
+            c.event(json!(4), ms(start, 150)),
+            Delivery::At(ms(start, 200))
+        );
+        assert_eq!(c.event(json!(5), ms(start, 300)), Delivery::Now(json!(5)));
+    }
+
+    #[test]
+    fn test_throttle_caps_debounce_wait() {
+        let start = Instant::now();
+        let mut c = coalescer(Some(50), false, Some(120));
+        assert_eq!(c.event(json!(1), start), Delivery::At(ms(start, 50)));
+        assert_eq!(
+            c.event(json!(2), ms(start, 40)),
+            Delivery::At(ms(start, 90))
+        );
+        assert_eq!(
+            c.event(json!(3), ms(start, 80)),
+            Delivery::At(ms(start, 120))
+        );
+        assert_eq!(c.flush(ms(start, 120)), vec![json!(3)]);
+    }
+
+    #[test]
+    fn test_held_buffer_changes_are_merged() {
+        let start = Instant::now();
+        let mut c = Coalescer::new(
+            "buffer_changed",
+            &EventOptions {
+                debounce_ms: Some(50),
+                leading: None,
+                throttle_ms: None,
+            },
+        )
+        .unwrap();
+        let changed = |buffer_id: u64, text: &str, version: u64| {
+            json!({
+                "buffer_id": buffer_id,
+                "changes": [{ "range": { "start": 0, "end": 0 }, "text": text }],
+                "version": version,
+            })
+        };
+        c.event(changed(1, "a", 1), start);
+        c.event(changed(2, "b", 7), ms(start, 10));
+        c.event(changed(1, "c", 2), ms(start, 20));
+        assert_eq!(
+            c.flush(ms(start, 70)),
+            vec![
+                json!({
+                    "buffer_id": 1,
+                    "changes": [
+                        { "range": { "start": 0, "end": 0 }, "text": "a" },
+                        { "range": { "start": 0, "end": 0 }, "text": "c" },
+                    ],
+                    "version": 2,
+                }),
+                changed(2, "b", 7),
+            ]
+        );
+    }
+}
@@ -3,8 +3,14 @@
 //! This module provides the JavaScript runtime backend for executing TypeScript plugins.
 //! Currently implements QuickJS with oxc transpilation.
 
+pub mod callback;
+pub mod capabilities;
+pub mod coalesce;
 pub mod quickjs_backend;
+pub mod worker;
 
+pub use callback::Callback;
+pub use capabilities::{Capability, CapabilitySet};
 pub use quickjs_backend::{
     has_fatal_js_error, set_panic_on_js_errors, take_fatal_js_error, PendingResponses,
     QuickJsBackend, TsPluginInfo,
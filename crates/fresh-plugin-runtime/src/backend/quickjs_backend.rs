@@ -84,13 +84,21 @@
 //!
 //! Run: `cargo test -p fresh-plugin-runtime write_fresh_dts_file -- --ignored`
 //!
-//! This validates TypeScript syntax and writes `plugins/lib/fresh.d.ts`.
+//! This validates TypeScript syntax and writes `fresh-editor/plugins/lib/fresh.d.ts`,
+//! or the path in `FRESH_DTS_OUT` when it is set.
 
+use super::capabilities::{self, Capability, CapabilitySet, PluginCapabilities};
+use super::coalesce::{Coalescer, Delivery};
+use super::worker::{self, WorkerResult};
 use anyhow::{anyhow, Result};
+use fresh_core::activation::ActivationEvent;
 use fresh_core::api::{
-    ActionSpec, BufferInfo, CompositeHunk, CreateCompositeBufferOptions, EditorStateSnapshot,
-    JsCallbackId, LanguagePackConfig, LspServerPackConfig, OverlayOptions, PluginCommand,
-    PluginResponse,
+    ActionSpec, BufferInfo, CompletionItem, CompletionProvider, CompletionRequest, CompositeHunk,
+    ComputeDiffOptions, ContextMenuItem, CreateCompositeBufferOptions, EditorStateSnapshot,
+    EvaluateRequest, EventOptions, FormatRequest, HoverProvider, HoverRequest,
+    InlineSuggestionProvider, InlineSuggestionRequest, JsCallbackId, LanguagePackConfig,
+    LspServerPackConfig, MenuItemOptions, OverlayOptions, PluginCommand, PluginError,
+    PluginEvaluator, PluginFormatter, PluginMenuItem, PluginResponse, ThemeSeed,
 };
 use fresh_core::command::Command;
 use fresh_core::overlay::OverlayNamespace;
@@ -101,11 +109,13 @@ use fresh_parser_js::{
     transpile_typescript,
 };
 use fresh_plugin_api_macros::{plugin_api, plugin_api_impl};
+use rquickjs::function::{FromParam, ParamRequirement, ParamsAccessor};
 use rquickjs::{Context, Function, Object, Runtime, Value};
-use std::cell::RefCell;
-use std::collections::HashMap;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{mpsc, Arc, RwLock};
 
 /// Convert a QuickJS Value to serde_json::Value
@@ -315,6 +325,58 @@ fn log_js_error(ctx: &rquickjs::Ctx<'_>, err: rquickjs::Error, context: &str) {
     }
 }
 
+/// JS definitions of `PluginError` and one subclass per [`PluginError`] variant
+///
+/// API methods run in the realm of the context that first created the
+/// editor class, so errors they throw aren't instances of the calling
+/// plugin's classes; `instanceof` matches them by `code` instead.
+fn plugin_error_classes_js() -> String {
+    let codes: Vec<String> = PluginError::CLASSES
+        .iter()
+        .map(|(_, code)| format!("'{code}'"))
+        .collect();
+    let mut js = format!(
+        r#"
+        globalThis.PluginError = class PluginError extends Error {{
+            constructor(code, message) {{
+                super(message);
+                this.name = new.target.name;
+                this.code = code;
+            }}
+            static [Symbol.hasInstance](value) {{
+                if (typeof value !== 'object' || value === null) {{
+                    return false;
+                }}
+                return this.code === undefined
+                    ? [{codes}].includes(value.code)
+                    : value.code === this.code;
+            }}
+        }};
+        "#,
+        codes = codes.join(", ")
+    );
+    for (class_name, code) in PluginError::CLASSES {
+        js.push_str(&format!(
+            "globalThis.{class_name} = class {class_name} extends PluginError {{ \
+             static code = '{code}'; \
+             constructor(message) {{ super('{code}', message); }} }};\n"
+        ));
+    }
+    js
+}
+
+/// Throw `error` into JS as an instance of its `PluginError` subclass
+fn throw_plugin_error(ctx: &rquickjs::Ctx<'_>, error: PluginError) -> rquickjs::Error {
+    let thrown = ctx
+        .globals()
+        .get::<_, rquickjs::function::Constructor>(error.class_name())
+        .and_then(|class| class.construct::<_, Value>((error.to_string(),)));
+    match thrown {
+        Ok(value) => ctx.throw(value),
+        Err(e) => e,
+    }
+}
+
 /// Global flag to panic on JS errors (enabled during testing)
 static PANIC_ON_JS_ERRORS: std::sync::atomic::AtomicBool =
     std::sync::atomic::AtomicBool::new(false);
@@ -449,12 +511,49 @@ fn parse_text_property_entry(
 pub type PendingResponses =
     Arc<std::sync::Mutex<HashMap<u64, tokio::sync::oneshot::Sender<PluginResponse>>>>;
 
+/// Running `setTimeout`/`setInterval` timers: callback_id -> sleep task
+type TimerTasks = Rc<RefCell<HashMap<u64, tokio::task::AbortHandle>>>;
+
+/// Running `runInWorker` workers: callback_id -> cancellation flag
+type WorkerTasks = Rc<RefCell<HashMap<u64, Arc<AtomicBool>>>>;
+
+/// Overlay namespaces each plugin has added overlays in: plugin name ->
+/// (buffer_id, namespace), cleared when the plugin is unloaded
+type PluginOverlays = Rc<RefCell<HashMap<String, HashSet<(u32, String)>>>>;
+
+/// Handlers registered with `editor.onPluginReload`: plugin name -> handler names
+type ReloadHandlers = Rc<RefCell<HashMap<String, Vec<String>>>>;
+
+/// What each loaded plugin's context may call, shared with its `editor` object
+type ContextCapabilities = Rc<RefCell<HashMap<String, Rc<Cell<PluginCapabilities>>>>>;
+
+/// An event handler registered with rate-limiting `EventOptions`
+struct CoalescedHandler {
+    coalescer: Coalescer,
+    /// Timer callback ID, deadline and task of the scheduled flush
+    flush: Option<(u64, std::time::Instant, tokio::task::AbortHandle)>,
+}
+
+/// Rate-limited event handlers: (event name, plugin name, handler name) -> state
+type EventCoalescers = Rc<RefCell<HashMap<(String, String, String), CoalescedHandler>>>;
+
+/// Stop the scheduled flush of a handler that is removed (its held event is dropped)
+fn cancel_flush(handler: CoalescedHandler) {
+    if let Some((_, _, task)) = handler.flush {
+        task.abort();
+    }
+}
+
 /// Information about a loaded plugin
 #[derive(Debug, Clone)]
 pub struct TsPluginInfo {
     pub name: String,
     pub path: PathBuf,
     pub enabled: bool,
+    /// Activation events from the plugin's manifest (empty = loaded at startup)
+    pub activation_events: Vec<ActivationEvent>,
+    /// What caused the plugin to be executed, or None while it is still pending
+    pub activated_by: Option<String>,
 }
 
 /// Handler information for events and actions
@@ -480,13 +579,254 @@ pub struct JsEditorApi {
     #[qjs(skip_trace)]
     next_request_id: Rc<RefCell<u64>>,
     #[qjs(skip_trace)]
+    next_marker_id: Rc<Cell<u64>>,
+    #[qjs(skip_trace)]
     callback_contexts: Rc<RefCell<HashMap<u64, String>>>,
     #[qjs(skip_trace)]
     services: Arc<dyn fresh_core::services::PluginServiceBridge>,
+    /// Buffers this plugin has an open undo group in
+    #[qjs(skip_trace)]
+    undo_groups: RefCell<HashSet<u32>>,
+    /// Bytes the commands sent to each buffer may have added since the
+    /// editor state showed it at a version (`None`: any amount)
+    #[qjs(skip_trace)]
+    pending_growth: RefCell<HashMap<u32, (u64, Option<usize>)>>,
+    /// Callback IDs of fired timers go back to the plugin thread through this
+    #[qjs(skip_trace)]
+    timer_sender: tokio::sync::mpsc::UnboundedSender<u64>,
+    #[qjs(skip_trace)]
+    timer_tasks: TimerTasks,
+    /// Results of finished workers go back to the plugin thread through this
+    #[qjs(skip_trace)]
+    worker_sender: tokio::sync::mpsc::UnboundedSender<WorkerResult>,
+    #[qjs(skip_trace)]
+    worker_tasks: WorkerTasks,
+    #[qjs(skip_trace)]
+    plugin_overlays: PluginOverlays,
+    #[qjs(skip_trace)]
+    reload_handlers: ReloadHandlers,
+    #[qjs(skip_trace)]
+    event_coalescers: EventCoalescers,
+    /// What this plugin may call, checked by every method's [`Permit`]
+    #[qjs(skip_trace)]
+    capabilities: Rc<Cell<PluginCapabilities>>,
     pub plugin_name: String,
 }
 
-#[plugin_api_impl]
+/// Check, before a method of the `editor` object runs, that the plugin may
+/// call it
+///
+/// `plugin_api_impl` adds one after `self` to every method; `METHOD` indexes
+/// [`JSEDITORAPI_PERMIT_METHODS`]. It takes no JS argument, so the check holds
+/// however JS reached the method (prototype, `call`, a saved reference).
+pub struct Permit<const METHOD: usize>;
+
+impl<'js, const METHOD: usize> FromParam<'js> for Permit<METHOD> {
+    fn param_requirement() -> ParamRequirement {
+        ParamRequirement::none()
+    }
+
+    fn from_param<'a>(params: &mut ParamsAccessor<'a, 'js>) -> rquickjs::Result<Self> {
+        static CAPABILITIES: std::sync::OnceLock<Vec<Option<Capability>>> =
+            std::sync::OnceLock::new();
+        let capabilities = CAPABILITIES.get_or_init(|| {
+            JSEDITORAPI_PERMIT_METHODS
+                .iter()
+                .map(|name| capabilities::method_capability(name))
+                .collect()
+        });
+        let Some(capability) = capabilities[METHOD] else {
+            return Ok(Permit);
+        };
+        let editor = rquickjs::Class::<JsEditorApi>::from_value(&params.this())?;
+        editor
+            .borrow()
+            .check_permit(JSEDITORAPI_PERMIT_METHODS[METHOD], capability)
+            .map_err(|e| throw_plugin_error(params.ctx(), e))?;
+        Ok(Permit)
+    }
+}
+
+impl JsEditorApi {
+    /// Refuse a call to a method of a capability the plugin may not use
+    ///
+    /// A permission still waiting for the user's consent asks for it again.
+    fn check_permit(&self, method: &str, capability: Capability) -> Result<(), PluginError> {
+        // The async methods' `_{name}Start` halves are called through `{name}`
+        let method = method
+            .strip_prefix('_')
+            .and_then(|name| name.strip_suffix("Start"))
+            .unwrap_or(method)
+            .to_string();
+        let allowed = self.capabilities.get();
+        if !allowed.enabled.contains(capability) {
+            let reason = if allowed.has_manifest && capability.is_permission() {
+                format!(
+                    "the \"{}\" capability is not declared in the plugin.json of {}",
+                    capability, self.plugin_name
+                )
+            } else {
+                format!(
+                    "the \"{}\" capability is disabled for {}",
+                    capability, self.plugin_name
+                )
+            };
+            return Err(PluginError::PermissionDenied { method, reason });
+        }
+        if allowed.pending.contains(capability) {
+            let _ = self
+                .command_sender
+                .send(PluginCommand::RequestPluginPermission {
+                    plugin_name: self.plugin_name.clone(),
+                    capability: capability.name().to_string(),
+                });
+            return Err(PluginError::PermissionDenied {
+                method,
+                reason: format!(
+                    "the \"{}\" permission needs the user's consent, which {} has asked for",
+                    capability, self.plugin_name
+                ),
+            });
+        }
+        Ok(())
+    }
+
+    /// Send a command, failing with `ChannelClosed` once the editor is gone
+    fn send_command(&self, command: PluginCommand) -> Result<(), PluginError> {
+        self.command_sender
+            .send(command)
+            .map_err(|_| PluginError::ChannelClosed)
+    }
+
+    /// Look up a buffer in the editor state
+    ///
+    /// The state lags behind commands sent during the current call, so a
+    /// buffer missing from it is only reported as invalid if its ID was
+    /// handed out already; the returned info is `None` for newer buffers.
+    fn check_buffer(&self, buffer_id: u32) -> Result<Option<BufferInfo>, PluginError> {
+        let id = BufferId(buffer_id as usize);
+        let Ok(s) = self.state_snapshot.read() else {
+            return Ok(None);
+        };
+        match s.buffers.get(&id) {
+            Some(info) => Ok(Some(info.clone())),
+            None if id.0 < s.next_buffer_id => Err(PluginError::InvalidBufferId { buffer_id: id }),
+            None => Ok(None),
+        }
+    }
+
+    /// Look up a buffer that is about to be edited, refusing read-only ones
+    fn check_editable(&self, buffer_id: u32) -> Result<Option<BufferInfo>, PluginError> {
+        let info = self.check_buffer(buffer_id)?;
+        if info.as_ref().is_some_and(|info| info.read_only) {
+            return Err(PluginError::ReadOnlyBuffer {
+                buffer_id: BufferId(buffer_id as usize),
+            });
+        }
+        Ok(info)
+    }
+
+    /// Check that a buffer can be edited and `range` fits it
+    ///
+    /// The editor state doesn't show the commands sent during the current
+    /// call yet, so the length allows for the text they may add. A range
+    /// based on an older version may also reach over text the user deleted
+    /// since, which the edit is moved back through.
+    fn check_range(
+        &self,
+        buffer_id: u32,
+        range: std::ops::Range<usize>,
+        base_version: Option<u64>,
+    ) -> Result<(), PluginError> {
+        let id = BufferId(buffer_id as usize);
+        let info = self.check_editable(buffer_id)?;
+        let length = info.as_ref().map_or(0, |info| info.length);
+        let out_of_range = PluginError::OutOfRange {
+            buffer_id: id,
+            range: range.clone(),
+            length,
+        };
+        if range.start > range.end {
+            return Err(out_of_range);
+        }
+        // Buffers created since the state was read, and versions newer than
+        // it, can't be checked
+        let Some(info) = info.filter(|info| base_version.is_none_or(|v| v <= info.version)) else {
+            return Ok(());
+        };
+        let Some(growth) = self.pending_growth(buffer_id, info.version) else {
+            return Ok(());
+        };
+        let slack = if base_version.is_some_and(|v| v < info.version) {
+            self.state_snapshot
+                .read()
+                .ok()
+                .and_then(|s| s.buffer_rebase_slack.get(&id).copied())
+                .unwrap_or(0)
+        } else {
+            0
+        };
+        if range.end > length + growth + slack {
+            return Err(out_of_range);
+        }
+        Ok(())
+    }
+
+    /// Bytes the commands sent to a buffer since the editor state showed it
+    /// at `version` may have added, or `None` if they may have changed its
+    /// length by any amount
+    fn pending_growth(&self, buffer_id: u32, version: u64) -> Option<usize> {
+        match self.pending_growth.borrow().get(&buffer_id) {
+            Some(&(seen, growth)) if seen == version => growth,
+            _ => Some(0),
+        }
+    }
+
+    /// Record that a command sent to a buffer may add `bytes` to it (`None`:
+    /// change its length by any amount) before the editor state shows it
+    fn note_growth(&self, buffer_id: u32, bytes: Option<usize>) {
+        let Some(version) = self.state_snapshot.read().ok().and_then(|s| {
+            s.buffers
+                .get(&BufferId(buffer_id as usize))
+                .map(|info| info.version)
+        }) else {
+            return;
+        };
+        let mut pending = self.pending_growth.borrow_mut();
+        let entry = pending.entry(buffer_id).or_insert((version, Some(0)));
+        if entry.0 != version {
+            *entry = (version, Some(0));
+        }
+        entry.1 = entry.1.zip(bytes).map(|(growth, bytes)| growth + bytes);
+    }
+
+    /// ID of the active buffer, as of the last editor state
+    fn active_buffer(&self) -> u32 {
+        self.state_snapshot
+            .read()
+            .map(|s| s.active_buffer_id.0 as u32)
+            .unwrap_or(0)
+    }
+
+    /// Send an undo (or, with `redo`, a redo) for a buffer that allows it
+    fn send_undo(&self, buffer_id: u32, redo: bool) -> Result<(), PluginError> {
+        let id = BufferId(buffer_id as usize);
+        self.check_editable(buffer_id)?;
+        self.send_command(if redo {
+            PluginCommand::Redo { buffer_id: id }
+        } else {
+            PluginCommand::Undo { buffer_id: id }
+        })?;
+        self.note_growth(buffer_id, None);
+        Ok(())
+    }
+}
+
+#[plugin_api_impl(
+    out = "../fresh-editor/plugins/lib/fresh.d.ts",
+    env = "FRESH_DTS_OUT",
+    permit = "Permit"
+)]
 #[rquickjs::methods(rename_all = "camelCase")]
 impl JsEditorApi {
     // === Buffer Queries ===
@@ -545,6 +885,14 @@ impl JsEditorApi {
             .send(PluginCommand::SetStatus { message: msg });
     }
 
+    /// Set the text shown for `{name}` in the user's status bar template
+    /// (`editor.status_bar_template`); an empty text clears it
+    pub fn set_status_bar_segment(&self, name: String, text: String) {
+        let _ = self
+            .command_sender
+            .send(PluginCommand::SetStatusBarSegment { name, text });
+    }
+
     // === Clipboard ===
 
     pub fn copy_to_clipboard(&self, text: String) {
@@ -569,7 +917,9 @@ impl JsEditorApi {
         name: String,
         description: String,
         handler_name: String,
-        context: rquickjs::function::Opt<rquickjs::Value<'js>>,
+        #[plugin_api(ts_type = "string | null")] context: rquickjs::function::Opt<
+            rquickjs::Value<'js>,
+        >,
     ) -> rquickjs::Result<bool> {
         // Use stored plugin name instead of global lookup
         let plugin_name = self.plugin_name.clone();
@@ -620,6 +970,218 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Register a completion provider
+    /// `handlerName` is called with a `CompletionRequest` when completion is
+    /// requested in one of `languages` (every language when empty) and returns
+    /// `CompletionItem[]` or a Promise of them, shown together with the language
+    /// server's items. Typing one of `triggerCharacters` requests completion
+    /// right away.
+    pub fn register_completion_provider(
+        &self,
+        languages: Vec<String>,
+        trigger_characters: Vec<String>,
+        handler_name: String,
+    ) -> bool {
+        let provider = CompletionProvider {
+            plugin_name: self.plugin_name.clone(),
+            languages,
+            trigger_characters,
+            handler_name,
+        };
+        self.command_sender
+            .send(PluginCommand::RegisterCompletionProvider { provider })
+            .is_ok()
+    }
+
+    /// Send a completion provider's items back to the editor (called by the
+    /// glue code that runs the provider)
+    #[qjs(rename = "_provideCompletions")]
+    pub fn provide_completions(&self, request_id: u64, items: Vec<CompletionItem>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ProvideCompletions { request_id, items })
+            .is_ok()
+    }
+
+    /// Register a hover provider
+    /// `handlerName` is called with a `HoverRequest` when hover is requested
+    /// in one of `languages` (every language when empty) and returns a
+    /// markdown string, `null` when it has nothing to show, or a Promise of
+    /// either. The markdown is shown in the hover popup together with the
+    /// language server's hover.
+    pub fn register_hover_provider(&self, languages: Vec<String>, handler_name: String) -> bool {
+        let provider = HoverProvider {
+            plugin_name: self.plugin_name.clone(),
+            languages,
+            handler_name,
+        };
+        self.command_sender
+            .send(PluginCommand::RegisterHoverProvider { provider })
+            .is_ok()
+    }
+
+    /// Send a hover provider's markdown back to the editor (called by the
+    /// glue code that runs the provider)
+    #[qjs(rename = "_provideHover")]
+    pub fn provide_hover(&self, request_id: u64, markdown: Option<String>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ProvideHover {
+                request_id,
+                markdown,
+            })
+            .is_ok()
+    }
+
+    /// Register an inline suggestion provider
+    /// `handlerName` is called with an `InlineSuggestionRequest` when the
+    /// cursor rests in a buffer of one of `languages` (every language when
+    /// empty) and returns the text to suggest at the cursor, `null` when it
+    /// has nothing to suggest, or a Promise of either. The suggestion is shown
+    /// as ghost text; Tab accepts it and Ctrl+Right accepts its next word.
+    pub fn register_inline_suggestion_provider(
+        &self,
+        languages: Vec<String>,
+        handler_name: String,
+    ) -> bool {
+        let provider = InlineSuggestionProvider {
+            plugin_name: self.plugin_name.clone(),
+            languages,
+            handler_name,
+        };
+        self.command_sender
+            .send(PluginCommand::RegisterInlineSuggestionProvider { provider })
+            .is_ok()
+    }
+
+    /// Send an inline suggestion provider's text back to the editor (called
+    /// by the glue code that runs the provider)
+    #[qjs(rename = "_provideInlineSuggestion")]
+    pub fn provide_inline_suggestion(&self, request_id: u64, text: Option<String>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ProvideInlineSuggestion { request_id, text })
+            .is_ok()
+    }
+
+    /// Register a formatter for a language
+    /// `handlerName` is called with a `FormatRequest` by the `format_buffer`
+    /// action (and on save when the language has `format_on_save`) and
+    /// returns the formatted text, `null` to leave the buffer alone, or a
+    /// Promise of either. It replaces the language's `formatter` command.
+    pub fn register_formatter(&self, language: String, handler_name: String) -> bool {
+        let formatter = PluginFormatter {
+            plugin_name: self.plugin_name.clone(),
+            language,
+            handler_name,
+        };
+        self.command_sender
+            .send(PluginCommand::RegisterFormatter { formatter })
+            .is_ok()
+    }
+
+    /// Send a formatter's output back to the editor (called by the glue code
+    /// that runs the formatter)
+    #[qjs(rename = "_provideFormattedText")]
+    pub fn provide_formatted_text(&self, request_id: u64, text: Option<String>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ProvideFormattedText { request_id, text })
+            .is_ok()
+    }
+
+    /// Register an evaluator for a language
+    /// The Evaluate Selection commands call `handlerName` with an
+    /// `EvaluateRequest` for each selection in a buffer of `language`. It
+    /// returns the result as a string or number, `null` to let the built-in
+    /// calculator evaluate the selection, or a Promise of either.
+    pub fn register_evaluator(&self, language: String, handler_name: String) -> bool {
+        let evaluator = PluginEvaluator {
+            plugin_name: self.plugin_name.clone(),
+            language,
+            handler_name,
+        };
+        self.command_sender
+            .send(PluginCommand::RegisterEvaluator { evaluator })
+            .is_ok()
+    }
+
+    /// Send an evaluator's results back to the editor (called by the glue
+    /// code that runs the evaluator)
+    #[qjs(rename = "_provideEvaluation")]
+    pub fn provide_evaluation(&self, request_id: u64, results: Vec<Option<String>>) -> bool {
+        self.command_sender
+            .send(PluginCommand::ProvideEvaluation {
+                request_id,
+                results,
+            })
+            .is_ok()
+    }
+
+    /// Add an item to a right-click context menu
+    /// `menu` is "editor", "tab" or "file_explorer". Choosing the item runs
+    /// `action` (a built-in action or a command handler name); `when` names a
+    /// context key (like "has_selection" or one set with `setContext`) that
+    /// must be set for the item to be shown. Adding an item with the same
+    /// label again replaces it.
+    pub fn add_context_menu_item(
+        &self,
+        menu: String,
+        label: String,
+        action: String,
+        when: rquickjs::function::Opt<Option<String>>,
+    ) -> bool {
+        let item = ContextMenuItem {
+            plugin_name: self.plugin_name.clone(),
+            menu,
+            label,
+            action,
+            when: when.0.flatten(),
+        };
+        self.command_sender
+            .send(PluginCommand::AddContextMenuItem { item })
+            .is_ok()
+    }
+
+    /// Remove an item this plugin added to a context menu
+    pub fn remove_context_menu_item(&self, menu: String, label: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::RemoveContextMenuItem {
+                plugin_name: self.plugin_name.clone(),
+                menu,
+                label,
+            })
+            .is_ok()
+    }
+
+    /// Add an item to the menu bar
+    /// `path` names the menu: a top-level label such as "Tools", optionally
+    /// followed by submenu labels ("Tools/Git"). Menus and submenus that don't
+    /// exist yet are created. Choosing the item runs `options.command` (a
+    /// built-in action or a command handler name); `options.when` names a
+    /// context key that must be set for the item to be enabled. Adding an
+    /// item with the same label again replaces it, and the plugin's items are
+    /// removed when it is unloaded.
+    pub fn add_menu_item(&self, path: String, options: MenuItemOptions) -> bool {
+        let item = PluginMenuItem {
+            plugin_name: self.plugin_name.clone(),
+            path,
+            label: options.label,
+            command: options.command,
+            when: options.when,
+        };
+        self.command_sender
+            .send(PluginCommand::AddPluginMenuItem { item })
+            .is_ok()
+    }
+
+    /// Remove an item this plugin added to the menu bar
+    pub fn remove_menu_item(&self, path: String, label: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::RemovePluginMenuItem {
+                plugin_name: self.plugin_name.clone(),
+                path,
+                label,
+            })
+            .is_ok()
+    }
+
     /// Set a context (for keybinding conditions)
     pub fn set_context(&self, name: String, active: bool) -> bool {
         self.command_sender
@@ -629,6 +1191,7 @@ impl JsEditorApi {
 
     /// Execute a built-in action
     pub fn execute_action(&self, action_name: String) -> bool {
+        self.note_growth(self.active_buffer(), None);
         self.command_sender
             .send(PluginCommand::ExecuteAction { action_name })
             .is_ok()
@@ -638,6 +1201,7 @@ impl JsEditorApi {
 
     /// Translate a string - reads plugin name from __pluginName__ global
     /// Args is optional - can be omitted, undefined, null, or an object
+    #[plugin_api(ts_overload = "(key: string, args?: Record<string, string> | null): string")]
     pub fn t<'js>(
         &self,
         _ctx: rquickjs::Ctx<'js>,
@@ -698,6 +1262,7 @@ impl JsEditorApi {
     }
 
     /// Get buffer length in bytes
+    #[plugin_api(namespace = "buffers")]
     pub fn get_buffer_length(&self, buffer_id: u32) -> u32 {
         if let Ok(s) = self.state_snapshot.read() {
             if let Some(b) = s.buffers.get(&BufferId(buffer_id as usize)) {
@@ -718,14 +1283,23 @@ impl JsEditorApi {
     }
 
     /// Save a buffer to a specific file path
-    /// Used by :w filename to save unnamed buffers or save-as
-    pub fn save_buffer_to_path(&self, buffer_id: u32, path: String) -> bool {
-        self.command_sender
-            .send(PluginCommand::SaveBufferToPath {
-                buffer_id: BufferId(buffer_id as usize),
-                path: std::path::PathBuf::from(path),
+    /// Used by :w filename to save unnamed buffers or save-as.
+    /// Throws `InvalidBufferIdError`
+    pub fn save_buffer_to_path(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        path: String,
+    ) -> rquickjs::Result<bool> {
+        self.check_buffer(buffer_id)
+            .and_then(|_| {
+                self.send_command(PluginCommand::SaveBufferToPath {
+                    buffer_id: BufferId(buffer_id as usize),
+                    path: std::path::PathBuf::from(path),
+                })
             })
-            .is_ok()
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
     /// Get buffer info by ID
@@ -866,59 +1440,222 @@ impl JsEditorApi {
     // === Text Editing ===
 
     /// Insert text at a position in a buffer
-    pub fn insert_text(&self, buffer_id: u32, position: u32, text: String) -> bool {
-        self.command_sender
-            .send(PluginCommand::InsertText {
-                buffer_id: BufferId(buffer_id as usize),
-                position: position as usize,
-                text,
+    /// `baseVersion` is the buffer version (`getBufferInfo(id).version`) the
+    /// position was computed against; the position is then moved past edits
+    /// the user made since, and the insert is skipped if they conflict.
+    /// Throws `InvalidBufferIdError`, `ReadOnlyBufferError` if editing is
+    /// disabled in the buffer, or `OutOfRangeError`
+    #[plugin_api(namespace = "buffers")]
+    pub fn insert_text(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        position: u32,
+        text: String,
+        base_version: rquickjs::function::Opt<u64>,
+    ) -> rquickjs::Result<bool> {
+        let position = position as usize;
+        let len = text.len();
+        self.check_range(buffer_id, position..position, base_version.0)
+            .and_then(|()| {
+                self.send_command(PluginCommand::InsertText {
+                    buffer_id: BufferId(buffer_id as usize),
+                    position,
+                    text,
+                    base_version: base_version.0,
+                })
             })
-            .is_ok()
+            .map(|()| self.note_growth(buffer_id, Some(len)))
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
     /// Delete a range from a buffer
-    pub fn delete_range(&self, buffer_id: u32, start: u32, end: u32) -> bool {
-        self.command_sender
-            .send(PluginCommand::DeleteRange {
-                buffer_id: BufferId(buffer_id as usize),
-                range: (start as usize)..(end as usize),
+    /// `baseVersion` works as for `insertText`.
+    /// Throws like `insertText`
+    #[plugin_api(namespace = "buffers")]
+    pub fn delete_range(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        start: u32,
+        end: u32,
+        base_version: rquickjs::function::Opt<u64>,
+    ) -> rquickjs::Result<bool> {
+        let range = (start as usize)..(end as usize);
+        self.check_range(buffer_id, range.clone(), base_version.0)
+            .and_then(|()| {
+                self.send_command(PluginCommand::DeleteRange {
+                    buffer_id: BufferId(buffer_id as usize),
+                    range,
+                    base_version: base_version.0,
+                })
             })
-            .is_ok()
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
-    /// Insert text at cursor position in active buffer
-    pub fn insert_at_cursor(&self, text: String) -> bool {
-        self.command_sender
-            .send(PluginCommand::InsertAtCursor { text })
-            .is_ok()
+    /// Apply several edits to a buffer at once, as a single undo step
+    ///
+    /// Every range refers to the buffer before any of the edits, so offsets
+    /// don't need adjusting for the edits before them. Ranges must not
+    /// overlap; if they do, or any edit is skipped, nothing is applied.
+    /// `baseVersion` works as for `insertText`.
+    /// Throws like `insertText`
+    #[plugin_api(namespace = "buffers")]
+    pub fn apply_edits(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        edits: Vec<fresh_core::api::BufferEdit>,
+        base_version: rquickjs::function::Opt<u64>,
+    ) -> rquickjs::Result<bool> {
+        let len = edits.iter().map(|edit| edit.text.len()).sum();
+        edits
+            .iter()
+            .try_for_each(|edit| self.check_range(buffer_id, edit.range.clone(), base_version.0))
+            .and_then(|()| {
+                self.send_command(PluginCommand::ApplyEdits {
+                    buffer_id: BufferId(buffer_id as usize),
+                    edits,
+                    base_version: base_version.0,
+                })
+            })
+            .map(|()| self.note_growth(buffer_id, Some(len)))
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
-    // === File Operations ===
-
-    /// Open a file, optionally at a specific line/column
-    pub fn open_file(&self, path: String, line: Option<u32>, column: Option<u32>) -> bool {
-        self.command_sender
-            .send(PluginCommand::OpenFileAtLocation {
-                path: PathBuf::from(path),
-                line: line.map(|l| l as usize),
-                column: column.map(|c| c as usize),
+    /// Start grouping edits to a buffer into a single undo step
+    ///
+    /// Every insert and delete made until `endUndoGroup` is undone and redone
+    /// together. The group is closed automatically if the action or handler
+    /// making the edits throws, or if it stays open for more than 5 seconds.
+    /// Groups don't nest: a second call before `endUndoGroup` is ignored.
+    /// Throws `InvalidBufferIdError`
+    #[plugin_api(namespace = "buffers")]
+    pub fn begin_undo_group(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+    ) -> rquickjs::Result<bool> {
+        self.check_buffer(buffer_id)
+            .and_then(|_| {
+                self.undo_groups.borrow_mut().insert(buffer_id);
+                self.send_command(PluginCommand::BeginUndoGroup {
+                    buffer_id: BufferId(buffer_id as usize),
+                })
             })
-            .is_ok()
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
-    /// Open a file in a specific split
-    pub fn open_file_in_split(&self, split_id: u32, path: String, line: u32, column: u32) -> bool {
-        self.command_sender
-            .send(PluginCommand::OpenFileInSplit {
-                split_id: split_id as usize,
-                path: PathBuf::from(path),
-                line: Some(line as usize),
-                column: Some(column as usize),
+    /// Close the undo group started with `beginUndoGroup`
+    ///
+    /// Throws like `beginUndoGroup`
+    #[plugin_api(namespace = "buffers")]
+    pub fn end_undo_group(&self, ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> rquickjs::Result<bool> {
+        self.undo_groups.borrow_mut().remove(&buffer_id);
+        self.check_buffer(buffer_id)
+            .and_then(|_| {
+                self.send_command(PluginCommand::EndUndoGroup {
+                    buffer_id: BufferId(buffer_id as usize),
+                })
             })
-            .is_ok()
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
-    /// Show a buffer in the current split
+    /// Undo the last edit step in a buffer
+    ///
+    /// A group of edits made between `beginUndoGroup` and `endUndoGroup` is
+    /// one step. Throws `InvalidBufferIdError`, or `ReadOnlyBufferError` if
+    /// editing is disabled in the buffer
+    #[plugin_api(namespace = "buffers")]
+    pub fn undo(&self, ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> rquickjs::Result<bool> {
+        self.send_undo(buffer_id, false)
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
+    }
+
+    /// Redo the last edit step undone in a buffer
+    ///
+    /// Throws like `undo`
+    #[plugin_api(namespace = "buffers")]
+    pub fn redo(&self, ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> rquickjs::Result<bool> {
+        self.send_undo(buffer_id, true)
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
+    }
+
+    /// Get the number of steps that can be undone in a buffer
+    ///
+    /// Read from the editor state, so undos and edits sent during the same
+    /// call are not counted yet
+    #[plugin_api(namespace = "buffers")]
+    pub fn get_undo_depth(&self, buffer_id: u32) -> u32 {
+        if let Ok(s) = self.state_snapshot.read() {
+            if let Some(b) = s.buffers.get(&BufferId(buffer_id as usize)) {
+                return b.undo_depth as u32;
+            }
+        }
+        0
+    }
+
+    /// Close every undo group this plugin left open (called when an action
+    /// or handler throws)
+    #[qjs(rename = "_endOpenUndoGroups")]
+    pub fn end_open_undo_groups(&self) {
+        let open: Vec<u32> = self.undo_groups.borrow_mut().drain().collect();
+        for buffer_id in open {
+            let _ = self.command_sender.send(PluginCommand::EndUndoGroup {
+                buffer_id: BufferId(buffer_id as usize),
+            });
+        }
+    }
+
+    /// Insert text at cursor position in active buffer
+    ///
+    /// Throws `ReadOnlyBufferError` if editing is disabled in the buffer
+    #[plugin_api(namespace = "buffers")]
+    pub fn insert_at_cursor(&self, ctx: rquickjs::Ctx<'_>, text: String) -> rquickjs::Result<bool> {
+        let buffer_id = self.active_buffer();
+        let len = text.len();
+        self.check_editable(buffer_id)
+            .and_then(|_| self.send_command(PluginCommand::InsertAtCursor { text }))
+            .map(|()| self.note_growth(buffer_id, Some(len)))
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
+    }
+
+    // === File Operations ===
+
+    /// Open a file, optionally at a specific line/column
+    ///
+    /// A leading `~` and `$VAR` / `${VAR}` references in the path are expanded.
+    pub fn open_file(&self, path: String, line: Option<u32>, column: Option<u32>) -> bool {
+        self.command_sender
+            .send(PluginCommand::OpenFileAtLocation {
+                path: PathBuf::from(path),
+                line: line.map(|l| l as usize),
+                column: column.map(|c| c as usize),
+            })
+            .is_ok()
+    }
+
+    /// Open a file in a specific split
+    pub fn open_file_in_split(&self, split_id: u32, path: String, line: u32, column: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::OpenFileInSplit {
+                split_id: split_id as usize,
+                path: PathBuf::from(path),
+                line: Some(line as usize),
+                column: Some(column as usize),
+            })
+            .is_ok()
+    }
+
+    /// Show a buffer in the current split
     pub fn show_buffer(&self, buffer_id: u32) -> bool {
         self.command_sender
             .send(PluginCommand::ShowBuffer {
@@ -928,6 +1665,8 @@ impl JsEditorApi {
     }
 
     /// Close a buffer
+    ///
+    /// Closing a buffer that is already gone does nothing, so this never throws
     pub fn close_buffer(&self, buffer_id: u32) -> bool {
         self.command_sender
             .send(PluginCommand::CloseBuffer {
@@ -939,7 +1678,46 @@ impl JsEditorApi {
     // === Event Handling ===
 
     /// Subscribe to an editor event
-    pub fn on<'js>(&self, _ctx: rquickjs::Ctx<'js>, event_name: String, handler_name: String) {
+    ///
+    /// The handler is a function or the name of a global function, and gets
+    /// the event's `EditorEventMap` payload. Events outside the map take any
+    /// handler. `options` can limit how often the handler runs for events
+    /// that fire on every keystroke, such as `cursor_moved`.
+    #[plugin_api(
+        ts_overload = "<E extends keyof EditorEventMap>(eventName: E, handler: (data: EditorEventMap[E]) => void, options?: EventOptions): void",
+        ts_overload = "<E extends keyof EditorEventMap>(eventName: E, handlerName: string, options?: EventOptions): void",
+        ts_overload = "(eventName: string, handler: string | ((data: unknown) => void), options?: EventOptions): void"
+    )]
+    pub fn on<'js>(
+        &self,
+        _ctx: rquickjs::Ctx<'js>,
+        event_name: String,
+        handler_name: String,
+        options: rquickjs::function::Opt<EventOptions>,
+    ) {
+        let key = (
+            event_name.clone(),
+            self.plugin_name.clone(),
+            handler_name.clone(),
+        );
+        let coalescer = options
+            .0
+            .as_ref()
+            .and_then(|options| Coalescer::new(&event_name, options));
+        let mut coalescers = self.event_coalescers.borrow_mut();
+        if let Some(previous) = coalescers.remove(&key) {
+            cancel_flush(previous);
+        }
+        if let Some(coalescer) = coalescer {
+            coalescers.insert(
+                key,
+                CoalescedHandler {
+                    coalescer,
+                    flush: None,
+                },
+            );
+        }
+
         self.event_handlers
             .borrow_mut()
             .entry(event_name)
@@ -951,12 +1729,38 @@ impl JsEditorApi {
     }
 
     /// Unsubscribe from an event
+    #[plugin_api(
+        ts_overload = "<E extends keyof EditorEventMap>(eventName: E, handler: (data: EditorEventMap[E]) => void): void",
+        ts_overload = "(eventName: string, handler: string | ((data: unknown) => void)): void"
+    )]
     pub fn off(&self, event_name: String, handler_name: String) {
+        let key = (
+            event_name.clone(),
+            self.plugin_name.clone(),
+            handler_name.clone(),
+        );
+        if let Some(previous) = self.event_coalescers.borrow_mut().remove(&key) {
+            cancel_flush(previous);
+        }
         if let Some(list) = self.event_handlers.borrow_mut().get_mut(&event_name) {
             list.retain(|h| h.handler_name != handler_name);
         }
     }
 
+    /// Register a handler to run just before this plugin is reloaded
+    ///
+    /// The editor removes the plugin's commands, event handlers, timers and
+    /// overlays itself; use this to clean up anything else the plugin set up,
+    /// such as background processes or status bar segments.
+    #[plugin_api(ts_overload = "(handler: string | (() => void)): void")]
+    pub fn on_plugin_reload(&self, handler_name: String) {
+        self.reload_handlers
+            .borrow_mut()
+            .entry(self.plugin_name.clone())
+            .or_default()
+            .push(handler_name);
+    }
+
     // === Environment ===
 
     /// Get an environment variable
@@ -1055,22 +1859,25 @@ impl JsEditorApi {
     // === File System ===
 
     /// Check if file exists
+    #[plugin_api(namespace = "fs")]
     pub fn file_exists(&self, path: String) -> bool {
         Path::new(&path).exists()
     }
 
     /// Read file contents
+    #[plugin_api(namespace = "fs")]
     pub fn read_file(&self, path: String) -> Option<String> {
         std::fs::read_to_string(&path).ok()
     }
 
     /// Write file contents
+    #[plugin_api(namespace = "fs")]
     pub fn write_file(&self, path: String, content: String) -> bool {
         std::fs::write(&path, content).is_ok()
     }
 
     /// Read directory contents (returns array of {name, is_file, is_dir})
-    #[plugin_api(ts_return = "DirEntry[]")]
+    #[plugin_api(namespace = "fs", ts_return = "DirEntry[]")]
     pub fn read_dir<'js>(
         &self,
         ctx: rquickjs::Ctx<'js>,
@@ -1100,6 +1907,68 @@ impl JsEditorApi {
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
     }
 
+    // === Session data ===
+
+    /// Store a value for this plugin in the session file
+    ///
+    /// The value is copied as JSON and saved with the rest of the session, so
+    /// panels can rebuild their state when the workspace is reopened (listen
+    /// for `session_restored`). Passing `null` or `undefined` removes the key.
+    pub fn set_session_data<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        key: String,
+        #[plugin_api(ts_type = "unknown")] value: Value<'js>,
+    ) -> bool {
+        let value = Some(js_to_json(&ctx, value)).filter(|v| !v.is_null());
+        // Kept in the snapshot too, so getSessionData sees it right away
+        if let Ok(mut s) = self.state_snapshot.write() {
+            match &value {
+                Some(value) => {
+                    s.plugin_session_data
+                        .entry(self.plugin_name.clone())
+                        .or_default()
+                        .insert(key.clone(), value.clone());
+                }
+                None => {
+                    if let Some(data) = s.plugin_session_data.get_mut(&self.plugin_name) {
+                        data.remove(&key);
+                    }
+                }
+            }
+        }
+        self.command_sender
+            .send(PluginCommand::SetSessionData {
+                plugin_name: self.plugin_name.clone(),
+                key,
+                value,
+            })
+            .is_ok()
+    }
+
+    /// Get a value this plugin stored with `setSessionData`, or null
+    #[plugin_api(ts_return = "unknown")]
+    pub fn get_session_data<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        key: String,
+    ) -> rquickjs::Result<Value<'js>> {
+        let value = self
+            .state_snapshot
+            .read()
+            .ok()
+            .and_then(|s| {
+                s.plugin_session_data
+                    .get(&self.plugin_name)?
+                    .get(&key)
+                    .cloned()
+            })
+            .unwrap_or(serde_json::Value::Null);
+
+        rquickjs_serde::to_value(ctx, &value)
+            .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
+    }
+
     // === Config ===
 
     /// Get current config as JS object
@@ -1209,6 +2078,22 @@ impl JsEditorApi {
             .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
     }
 
+    /// Generate theme JSON from seed colors (bg, fg, accent)
+    ///
+    /// Throws if a seed color is not a valid hex color.
+    pub fn generate_theme<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        seed: ThemeSeed,
+    ) -> rquickjs::Result<Value<'js>> {
+        let theme = self
+            .services
+            .generate_theme(&seed)
+            .map_err(|e| rquickjs::Error::new_from_js_message("ThemeSeed", "theme", &e))?;
+        rquickjs_serde::to_value(ctx, &theme)
+            .map_err(|e| rquickjs::Error::new_from_js_message("serialize", "", &e.to_string()))
+    }
+
     /// Delete a custom theme file (sync)
     #[qjs(rename = "_deleteThemeSync")]
     pub fn delete_theme_sync(&self, name: String) -> bool {
@@ -1229,12 +2114,14 @@ impl JsEditorApi {
 
     /// Delete a custom theme (alias for deleteThemeSync)
     pub fn delete_theme(&self, name: String) -> bool {
-        self.delete_theme_sync(name)
+        // Both are core methods, so there is nothing left to check
+        self.delete_theme_sync(Permit, name)
     }
 
     // === File Stats ===
 
     /// Get file stat information
+    #[plugin_api(namespace = "fs")]
     pub fn file_stat<'js>(
         &self,
         ctx: rquickjs::Ctx<'js>,
@@ -1401,6 +2288,7 @@ impl JsEditorApi {
     ///   bold: true,
     /// });
     /// ```
+    #[allow(clippy::too_many_arguments)]
     pub fn add_overlay<'js>(
         &self,
         _ctx: rquickjs::Ctx<'js>,
@@ -1445,6 +2333,11 @@ impl JsEditorApi {
             extend_to_line_end,
         };
 
+        self.plugin_overlays
+            .borrow_mut()
+            .entry(self.plugin_name.clone())
+            .or_default()
+            .insert((buffer_id, namespace.clone()));
         let _ = self.command_sender.send(PluginCommand::AddOverlay {
             buffer_id: BufferId(buffer_id as usize),
             namespace: Some(OverlayNamespace::from_string(namespace)),
@@ -1644,6 +2537,49 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Show the file explorer with a file or directory expanded and selected
+    ///
+    /// Relative paths are resolved against the working directory.
+    pub fn reveal_in_explorer(&self, path: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::RevealInExplorer {
+                path: PathBuf::from(path),
+            })
+            .is_ok()
+    }
+
+    /// Let the user choose a file (or directory) in the file browser (async)
+    ///
+    /// Resolves with the absolute path of the choice, or null if cancelled.
+    #[plugin_api(
+        async_promise,
+        js_name = "openFileBrowser",
+        ts_return = "string | null"
+    )]
+    #[qjs(rename = "_openFileBrowserStart")]
+    pub fn open_file_browser_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        options: rquickjs::function::Opt<fresh_core::api::FileBrowserOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self.command_sender.send(PluginCommand::OpenFileBrowser {
+            options: options.0.unwrap_or_default(),
+            callback_id: JsCallbackId::new(id),
+        });
+
+        id
+    }
+
     // === Virtual Text ===
 
     /// Add virtual text (inline text that doesn't exist in the buffer)
@@ -1743,6 +2679,60 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    // === Markers ===
+
+    /// Create a marker at a byte offset in a buffer and return its ID
+    ///
+    /// The marker moves with edits: text inserted before or at it pushes it
+    /// forward, and deleting a range around it moves it to the range start.
+    /// Markers are deleted with their buffer or when the plugin is unloaded.
+    pub fn create_marker(&self, buffer_id: u32, offset: u32) -> u32 {
+        let marker_id = self.next_marker_id.get();
+        self.next_marker_id.set(marker_id + 1);
+        let _ = self.command_sender.send(PluginCommand::CreateMarker {
+            buffer_id: BufferId(buffer_id as usize),
+            marker_id,
+            position: offset as usize,
+            plugin_name: self.plugin_name.clone(),
+        });
+        marker_id as u32
+    }
+
+    /// Delete a marker created with `createMarker`
+    pub fn delete_marker(&self, marker_id: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::DeleteMarker {
+                marker_id: marker_id as u64,
+            })
+            .is_ok()
+    }
+
+    /// Get the current byte offset of a marker (async, returns request_id)
+    ///
+    /// Resolves to null if the marker was deleted or its buffer closed.
+    #[plugin_api(
+        async_promise,
+        js_name = "getMarkerPosition",
+        ts_return = "number | null"
+    )]
+    #[qjs(rename = "_getMarkerPositionStart")]
+    pub fn get_marker_position_start(&self, _ctx: rquickjs::Ctx<'_>, marker_id: u32) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::GetMarkerPosition {
+            marker_id: marker_id as u64,
+            request_id: id,
+        });
+        id
+    }
+
     // === Prompts ===
 
     /// Show a prompt and wait for user input (async)
@@ -1775,53 +2765,186 @@ impl JsEditorApi {
         id
     }
 
-    /// Start an interactive prompt
-    pub fn start_prompt(&self, label: String, prompt_type: String) -> bool {
-        self.command_sender
-            .send(PluginCommand::StartPrompt { label, prompt_type })
-            .is_ok()
-    }
-
-    /// Start a prompt with initial value
-    pub fn start_prompt_with_initial(
+    /// Show a filterable list of items and wait for a choice (async)
+    ///
+    /// Resolves with the chosen item, the checked items when
+    /// `options.canPickMany` is set, or null if cancelled.
+    #[plugin_api(
+        async_promise,
+        js_name = "quickPick",
+        ts_return = "QuickPickItem | QuickPickItem[] | null"
+    )]
+    #[qjs(rename = "_quickPickStart")]
+    pub fn quick_pick_start(
         &self,
-        label: String,
-        prompt_type: String,
-        initial_value: String,
-    ) -> bool {
-        self.command_sender
-            .send(PluginCommand::StartPromptWithInitial {
-                label,
-                prompt_type,
-                initial_value,
-            })
-            .is_ok()
+        _ctx: rquickjs::Ctx<'_>,
+        items: Vec<fresh_core::api::QuickPickItem>,
+        options: rquickjs::function::Opt<fresh_core::api::QuickPickOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self.command_sender.send(PluginCommand::StartQuickPick {
+            items,
+            options: options.0.unwrap_or_default(),
+            callback_id: JsCallbackId::new(id),
+        });
+
+        id
     }
 
-    /// Set suggestions for the current prompt
+    /// Show a picker described by a single options object and wait for a choice (async)
     ///
-    /// Uses typed Vec<Suggestion> - serde validates field names at runtime
-    pub fn set_prompt_suggestions(
+    /// Same list as `quickPick`: resolves with the chosen item, the checked
+    /// items when `multiSelect` is set, or null if cancelled.
+    #[plugin_api(
+        async_promise,
+        js_name = "showPicker",
+        ts_return = "QuickPickItem | QuickPickItem[] | null"
+    )]
+    #[qjs(rename = "_showPickerStart")]
+    pub fn show_picker_start(
         &self,
-        suggestions: Vec<fresh_core::command::Suggestion>,
-    ) -> bool {
-        self.command_sender
-            .send(PluginCommand::SetPromptSuggestions { suggestions })
-            .is_ok()
-    }
+        _ctx: rquickjs::Ctx<'_>,
+        options: fresh_core::api::PickerOptions,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
 
-    // === Modes ===
+        let (items, options) = options.into_quick_pick();
+        let _ = self.command_sender.send(PluginCommand::StartQuickPick {
+            items,
+            options,
+            callback_id: JsCallbackId::new(id),
+        });
 
-    /// Define a buffer mode (takes bindings as array of [key, command] pairs)
-    pub fn define_mode(
+        id
+    }
+
+    /// Show a message with buttons and wait for one to be chosen (async)
+    ///
+    /// Resolves with the label of the chosen button, or null if the popup is
+    /// dismissed with Escape. Without buttons, a single OK button is shown.
+    #[plugin_api(async_promise, js_name = "showConfirm", ts_return = "string | null")]
+    #[qjs(rename = "_showConfirmStart")]
+    pub fn show_confirm_start(
         &self,
-        name: String,
-        parent: Option<String>,
-        bindings_arr: Vec<Vec<String>>,
-        read_only: rquickjs::function::Opt<bool>,
-    ) -> bool {
-        let bindings: Vec<(String, String)> = bindings_arr
-            .into_iter()
+        _ctx: rquickjs::Ctx<'_>,
+        message: String,
+        buttons: rquickjs::function::Opt<Vec<String>>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self.command_sender.send(PluginCommand::ShowConfirm {
+            message,
+            buttons: buttons.0.unwrap_or_default(),
+            callback_id: JsCallbackId::new(id),
+        });
+
+        id
+    }
+
+    /// Read a line of text in the prompt (async)
+    ///
+    /// Resolves with the entered text, or null if cancelled. With
+    /// `options.password`, the input is masked and is not passed to
+    /// `prompt_changed` handlers or the clipboard.
+    #[plugin_api(async_promise, js_name = "showInput", ts_return = "string | null")]
+    #[qjs(rename = "_showInputStart")]
+    pub fn show_input_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        prompt: String,
+        options: rquickjs::function::Opt<fresh_core::api::InputOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+
+        let _ = self.command_sender.send(PluginCommand::ShowInput {
+            prompt,
+            options: options.0.unwrap_or_default(),
+            callback_id: JsCallbackId::new(id),
+        });
+
+        id
+    }
+
+    /// Start an interactive prompt
+    pub fn start_prompt(&self, label: String, prompt_type: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::StartPrompt { label, prompt_type })
+            .is_ok()
+    }
+
+    /// Start a prompt with initial value
+    pub fn start_prompt_with_initial(
+        &self,
+        label: String,
+        prompt_type: String,
+        initial_value: String,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::StartPromptWithInitial {
+                label,
+                prompt_type,
+                initial_value,
+            })
+            .is_ok()
+    }
+
+    /// Set suggestions for the current prompt
+    ///
+    /// Uses typed Vec<Suggestion> - serde validates field names at runtime
+    pub fn set_prompt_suggestions(
+        &self,
+        suggestions: Vec<fresh_core::command::Suggestion>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetPromptSuggestions { suggestions })
+            .is_ok()
+    }
+
+    // === Modes ===
+
+    /// Define a buffer mode (takes bindings as array of [key, command] pairs)
+    pub fn define_mode(
+        &self,
+        name: String,
+        parent: Option<String>,
+        bindings_arr: Vec<Vec<String>>,
+        read_only: rquickjs::function::Opt<bool>,
+    ) -> bool {
+        let bindings: Vec<(String, String)> = bindings_arr
+            .into_iter()
             .filter_map(|arr| {
                 if arr.len() >= 2 {
                     Some((arr[0].clone(), arr[1].clone()))
@@ -1921,6 +3044,17 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Draw a split with another theme, e.g. a light theme for a preview
+    /// Pass null to go back to the editor theme. Unknown themes are ignored.
+    pub fn set_split_theme(&self, split_id: u32, theme: Option<String>) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetSplitTheme {
+                split_id: SplitId(split_id as usize),
+                theme,
+            })
+            .is_ok()
+    }
+
     /// Distribute all splits evenly
     pub fn distribute_splits_evenly(&self) -> bool {
         // Get all split IDs - for now send empty vec (app will handle)
@@ -1929,16 +3063,103 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Get the split layout tree (async, returns request_id)
+    #[plugin_api(async_promise, js_name = "getLayout", ts_return = "LayoutNode")]
+    #[qjs(rename = "_getLayoutStart")]
+    pub fn get_layout_start(&self, _ctx: rquickjs::Ctx<'_>) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self
+            .command_sender
+            .send(PluginCommand::GetLayout { request_id: id });
+        id
+    }
+
+    /// Split a pane or container (async, returns request_id)
+    ///
+    /// `direction` is "horizontal" (stacked) or "vertical" (side by side).
+    /// The new pane shows `bufferId` (default: the split's buffer), becomes
+    /// active, and its split ID is what the promise resolves to.
+    #[plugin_api(async_promise, js_name = "splitEditor", ts_return = "number")]
+    #[qjs(rename = "_splitEditorStart")]
+    pub fn split_editor_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        split_id: u32,
+        direction: String,
+        buffer_id: rquickjs::function::Opt<u32>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::SplitEditor {
+            split_id: SplitId(split_id as usize),
+            direction,
+            buffer_id: buffer_id.0.map(|id| BufferId(id as usize)),
+            request_id: id,
+        });
+        id
+    }
+
     /// Set cursor position in a buffer
-    pub fn set_buffer_cursor(&self, buffer_id: u32, position: u32) -> bool {
+    ///
+    /// Throws `InvalidBufferIdError`
+    pub fn set_buffer_cursor(
+        &self,
+        ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        position: u32,
+    ) -> rquickjs::Result<bool> {
+        self.check_buffer(buffer_id)
+            .and_then(|_| {
+                self.send_command(PluginCommand::SetBufferCursor {
+                    buffer_id: BufferId(buffer_id as usize),
+                    position: position as usize,
+                })
+            })
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
+    }
+
+    /// Replace the active buffer's cursors with one per selection
+    ///
+    /// Each selection puts a cursor at `head`, selecting back to `anchor`.
+    /// The last one becomes the primary cursor. The change can be undone.
+    pub fn set_selections(&self, selections: Vec<fresh_core::api::SelectionSpec>) -> bool {
         self.command_sender
-            .send(PluginCommand::SetBufferCursor {
-                buffer_id: BufferId(buffer_id as usize),
+            .send(PluginCommand::SetSelections { selections })
+            .is_ok()
+    }
+
+    /// Add a cursor at a byte offset in the active buffer (it becomes the primary cursor)
+    pub fn add_cursor(&self, position: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::AddCursor {
                 position: position as usize,
             })
             .is_ok()
     }
 
+    /// Remove all cursors of the active buffer except the original one
+    pub fn clear_secondary_cursors(&self) -> bool {
+        self.command_sender
+            .send(PluginCommand::ClearSecondaryCursors)
+            .is_ok()
+    }
+
     // === Line Indicators ===
 
     /// Set a line indicator in the gutter
@@ -1976,6 +3197,132 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    // === Gutter Columns ===
+
+    /// Register a gutter column `width` cells wide for a namespace
+    ///
+    /// The column is drawn left of the indicator column, followed by a space,
+    /// in each buffer that `setGutterText` or `setGutterTexts` has put text
+    /// in. Registering the namespace again changes its width. Widths over 40
+    /// are clamped to 40.
+    pub fn register_gutter(&self, namespace: String, width: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::RegisterGutter {
+                plugin_name: self.plugin_name.clone(),
+                namespace,
+                width: width as usize,
+            })
+            .is_ok()
+    }
+
+    /// Remove a gutter column and its text from every buffer
+    pub fn unregister_gutter(&self, namespace: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::UnregisterGutter { namespace })
+            .is_ok()
+    }
+
+    /// Set the text of a line in a registered gutter column
+    ///
+    /// Text wider than the column is cut off. `color` is an RGB array or a
+    /// theme key (the line number color when omitted). The text moves with
+    /// its line as the buffer is edited.
+    pub fn set_gutter_text(
+        &self,
+        buffer_id: u32,
+        namespace: String,
+        line: u32,
+        text: String,
+        color: rquickjs::function::Opt<fresh_core::api::OverlayColorSpec>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetGutterText {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+                line: line as usize,
+                text,
+                color: color.0,
+            })
+            .is_ok()
+    }
+
+    /// Replace all text of a gutter column in a buffer with `lines`
+    pub fn set_gutter_texts(
+        &self,
+        buffer_id: u32,
+        namespace: String,
+        lines: Vec<fresh_core::api::GutterText>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetGutterTexts {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+                lines,
+            })
+            .is_ok()
+    }
+
+    /// Clear the text of a gutter column in a buffer (the column stays)
+    pub fn clear_gutter_text(&self, buffer_id: u32, namespace: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::ClearGutterText {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+            })
+            .is_ok()
+    }
+
+    // === Line Backgrounds ===
+
+    /// Tint the background of a whole line, across the full editor width
+    ///
+    /// `color` is an RGB array or a theme key such as "editor.diff_add_bg".
+    /// Backgrounds move with their line as the buffer is edited. Setting a
+    /// line again in the same namespace replaces its color; when namespaces
+    /// overlap, the most recently set background wins.
+    pub fn set_line_background(
+        &self,
+        buffer_id: u32,
+        namespace: String,
+        line: u32,
+        color: fresh_core::api::OverlayColorSpec,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetLineBackground {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+                line: line as usize,
+                color,
+            })
+            .is_ok()
+    }
+
+    /// Replace all line backgrounds of a namespace with `lines`
+    pub fn set_line_backgrounds(
+        &self,
+        buffer_id: u32,
+        namespace: String,
+        lines: Vec<fresh_core::api::LineBackground>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetLineBackgrounds {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+                lines,
+            })
+            .is_ok()
+    }
+
+    /// Clear line backgrounds in a namespace
+    pub fn clear_line_backgrounds(&self, buffer_id: u32, namespace: String) -> bool {
+        self.command_sender
+            .send(PluginCommand::ClearLineBackgrounds {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace,
+            })
+            .is_ok()
+    }
+
     /// Enable or disable line numbers for a buffer
     pub fn set_line_numbers(&self, buffer_id: u32, enabled: bool) -> bool {
         self.command_sender
@@ -1986,9 +3333,24 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Override how a buffer is displayed (wrap column, tab width, whitespace,
+    /// conceal rules). Unset fields keep their current value.
+    pub fn set_buffer_display_options(
+        &self,
+        buffer_id: u32,
+        options: fresh_core::api::BufferDisplayOptions,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::SetBufferDisplayOptions {
+                buffer_id: BufferId(buffer_id as usize),
+                options,
+            })
+            .is_ok()
+    }
+
     // === Scroll Sync ===
 
-    /// Create a scroll sync group for anchor-based synchronized scrolling
+    /// Create a scroll sync group for synchronized scrolling
     pub fn create_scroll_sync_group(
         &self,
         group_id: u32,
@@ -2004,22 +3366,36 @@ impl JsEditorApi {
             .is_ok()
     }
 
-    /// Set sync anchors for a scroll sync group
+    /// Add a split to a scroll sync group
+    pub fn add_scroll_sync_split(&self, group_id: u32, split_id: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::AddScrollSyncSplit {
+                group_id,
+                split_id: SplitId(split_id as usize),
+            })
+            .is_ok()
+    }
+
+    /// Remove a split from a scroll sync group
+    pub fn remove_scroll_sync_split(&self, group_id: u32, split_id: u32) -> bool {
+        self.command_sender
+            .send(PluginCommand::RemoveScrollSyncSplit {
+                group_id,
+                split_id: SplitId(split_id as usize),
+            })
+            .is_ok()
+    }
+
+    /// Set sync anchors for a scroll sync group, each with one line per split
     pub fn set_scroll_sync_anchors<'js>(
         &self,
         _ctx: rquickjs::Ctx<'js>,
         group_id: u32,
         anchors: Vec<Vec<u32>>,
     ) -> bool {
-        let anchors: Vec<(usize, usize)> = anchors
+        let anchors: Vec<Vec<usize>> = anchors
             .into_iter()
-            .filter_map(|pair| {
-                if pair.len() >= 2 {
-                    Some((pair[0] as usize, pair[1] as usize))
-                } else {
-                    None
-                }
-            })
+            .map(|lines| lines.into_iter().map(|line| line as usize).collect())
             .collect();
         self.command_sender
             .send(PluginCommand::SetScrollSyncAnchors { group_id, anchors })
@@ -2039,16 +3415,33 @@ impl JsEditorApi {
     ///
     /// Takes typed ActionSpec array - serde validates field names at runtime
     pub fn execute_actions(&self, actions: Vec<ActionSpec>) -> bool {
+        self.note_growth(self.active_buffer(), None);
         self.command_sender
             .send(PluginCommand::ExecuteActions { actions })
             .is_ok()
     }
 
-    /// Show an action popup
-    ///
-    /// Takes a typed ActionPopupOptions struct - serde validates field names at runtime
-    pub fn show_action_popup(&self, opts: fresh_core::api::ActionPopupOptions) -> bool {
-        self.command_sender
+    /// Search the active buffer, or the project with `options.project`, as if
+    /// `pattern` had been entered in the search prompt. Unset options keep the
+    /// current search toggles.
+    pub fn run_search(
+        &self,
+        pattern: String,
+        options: rquickjs::function::Opt<fresh_core::api::SearchOptions>,
+    ) -> bool {
+        self.command_sender
+            .send(PluginCommand::RunSearch {
+                pattern,
+                options: options.0.unwrap_or_default(),
+            })
+            .is_ok()
+    }
+
+    /// Show an action popup
+    ///
+    /// Takes a typed ActionPopupOptions struct - serde validates field names at runtime
+    pub fn show_action_popup(&self, opts: fresh_core::api::ActionPopupOptions) -> bool {
+        self.command_sender
             .send(PluginCommand::ShowActionPopup {
                 popup_id: opts.id,
                 title: opts.title,
@@ -2181,6 +3574,7 @@ impl JsEditorApi {
                 show_cursors: opts.show_cursors.unwrap_or(true),
                 editing_disabled: opts.editing_disabled.unwrap_or(false),
                 hidden_from_tabs: opts.hidden_from_tabs.unwrap_or(false),
+                display: opts.display,
                 request_id: Some(id),
             });
         Ok(id)
@@ -2234,6 +3628,7 @@ impl JsEditorApi {
                 show_cursors: opts.show_cursors.unwrap_or(true),
                 editing_disabled: opts.editing_disabled.unwrap_or(false),
                 line_wrap: opts.line_wrap,
+                display: opts.display,
                 request_id: Some(id),
             });
         Ok(id)
@@ -2285,6 +3680,7 @@ impl JsEditorApi {
                 show_cursors: opts.show_cursors.unwrap_or(true),
                 editing_disabled: opts.editing_disabled.unwrap_or(false),
                 line_wrap: opts.line_wrap,
+                display: opts.display,
                 request_id: Some(id),
             });
         Ok(id)
@@ -2292,7 +3688,8 @@ impl JsEditorApi {
 
     /// Set virtual buffer content (takes array of entry objects)
     ///
-    /// Note: entries should be TextPropertyEntry[] - uses manual parsing for HashMap support
+    /// Note: entries should be TextPropertyEntry[] - uses manual parsing for HashMap support.
+    /// Works in read-only buffers too; throws `InvalidBufferIdError`
     pub fn set_virtual_buffer_content<'js>(
         &self,
         ctx: rquickjs::Ctx<'js>,
@@ -2303,13 +3700,16 @@ impl JsEditorApi {
             .iter()
             .filter_map(|obj| parse_text_property_entry(&ctx, obj))
             .collect();
-        Ok(self
-            .command_sender
-            .send(PluginCommand::SetVirtualBufferContent {
-                buffer_id: BufferId(buffer_id as usize),
-                entries,
+        self.check_buffer(buffer_id)
+            .and_then(|_| {
+                self.send_command(PluginCommand::SetVirtualBufferContent {
+                    buffer_id: BufferId(buffer_id as usize),
+                    entries,
+                })
             })
-            .is_ok())
+            .map(|()| self.note_growth(buffer_id, None))
+            .map(|()| true)
+            .map_err(|e| throw_plugin_error(&ctx, e))
     }
 
     /// Get text properties at cursor position (returns JS array)
@@ -2365,6 +3765,37 @@ impl JsEditorApi {
         id
     }
 
+    /// Make an HTTP request (async)
+    ///
+    /// Only hosts listed in the `plugin_fetch.allowed_hosts` setting can be
+    /// reached. Resolves with the status, headers and body for any HTTP
+    /// status; rejects when the host is not allowed, the request fails or
+    /// times out, or the body exceeds `plugin_fetch.max_response_bytes`.
+    #[plugin_api(async_promise, js_name = "fetch", ts_return = "FetchResponse")]
+    #[qjs(rename = "_fetchStart")]
+    pub fn fetch_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        url: String,
+        options: rquickjs::function::Opt<fresh_core::api::FetchOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::Fetch {
+            url,
+            options: options.0.unwrap_or_default(),
+            callback_id: JsCallbackId::new(id),
+        });
+        id
+    }
+
     /// Wait for a process to complete and get its result (async)
     #[plugin_api(async_promise, js_name = "spawnProcessWait", ts_return = "SpawnResult")]
     #[qjs(rename = "_spawnProcessWaitStart")]
@@ -2387,7 +3818,12 @@ impl JsEditorApi {
     }
 
     /// Get buffer text range (async, returns request_id)
-    #[plugin_api(async_promise, js_name = "getBufferText", ts_return = "string")]
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "getBufferText",
+        ts_return = "string"
+    )]
     #[qjs(rename = "_getBufferTextStart")]
     pub fn get_buffer_text_start(
         &self,
@@ -2415,6 +3851,252 @@ impl JsEditorApi {
         id
     }
 
+    /// Get lines `startLine` (inclusive) to `endLine` (exclusive) of a
+    /// buffer, without their line endings (async, returns request_id)
+    ///
+    /// Lines past the end of the buffer are left out.
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "getBufferLines",
+        ts_return = "string[]"
+    )]
+    #[qjs(rename = "_getBufferLinesStart")]
+    pub fn get_buffer_lines_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        start_line: u32,
+        end_line: u32,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::GetBufferLines {
+            buffer_id: BufferId(buffer_id as usize),
+            start_line: start_line as usize,
+            end_line: end_line as usize,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Get the number of lines in a buffer (async, returns request_id)
+    ///
+    /// A buffer ending with a newline has an empty last line.
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "getLineCount",
+        ts_return = "number"
+    )]
+    #[qjs(rename = "_getLineCountStart")]
+    pub fn get_line_count_start(&self, _ctx: rquickjs::Ctx<'_>, buffer_id: u32) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::GetLineCount {
+            buffer_id: BufferId(buffer_id as usize),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Convert a byte offset to a 0-indexed line and byte column (async,
+    /// returns request_id)
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "offsetToLineCol",
+        ts_return = "{ line: number; column: number }"
+    )]
+    #[qjs(rename = "_offsetToLineColStart")]
+    pub fn offset_to_line_col_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        offset: u32,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::OffsetToLineCol {
+            buffer_id: BufferId(buffer_id as usize),
+            offset: offset as usize,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Convert a 0-indexed line and byte column to a byte offset (async,
+    /// returns request_id)
+    ///
+    /// The column may point at the end of the line, but not past it.
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "lineColToOffset",
+        ts_return = "number"
+    )]
+    #[qjs(rename = "_lineColToOffsetStart")]
+    pub fn line_col_to_offset_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        line: u32,
+        column: u32,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::LineColToOffset {
+            buffer_id: BufferId(buffer_id as usize),
+            line: line as usize,
+            column: column as usize,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Run a tree-sitter query against a buffer's syntax tree (async,
+    /// returns request_id)
+    ///
+    /// Resolves to the captures sorted by start offset. Rejects if the
+    /// buffer's language has no tree-sitter grammar or the query is invalid.
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "querySyntax",
+        ts_return = "SyntaxCapture[]"
+    )]
+    #[qjs(rename = "_querySyntaxStart")]
+    pub fn query_syntax_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        query: String,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::QuerySyntax {
+            buffer_id: BufferId(buffer_id as usize),
+            query,
+            request_id: id,
+        });
+        id
+    }
+
+    /// Find the matches of `pattern` in a buffer (async, returns request_id)
+    ///
+    /// The search runs in the editor with the same engine as the search
+    /// prompt, reading large files in chunks, so plugins don't need to pull
+    /// the text into JS. Resolves to the matches in order, as byte ranges;
+    /// empty matches are skipped. Rejects if `opts.regex` is set and the
+    /// pattern is not a valid regular expression.
+    #[plugin_api(
+        namespace = "buffers",
+        async_promise,
+        js_name = "search",
+        ts_return = "SearchMatch[]"
+    )]
+    #[qjs(rename = "_searchStart")]
+    pub fn search_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        buffer_id: u32,
+        pattern: String,
+        opts: rquickjs::function::Opt<fresh_core::api::BufferSearchOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::SearchBuffer {
+            buffer_id: BufferId(buffer_id as usize),
+            pattern,
+            options: opts.0.unwrap_or_default(),
+            request_id: id,
+        });
+        id
+    }
+
+    /// Compute a line or word diff between two texts (async, returns request_id)
+    ///
+    /// Resolves to `DiffHunk[]`, or `TsCompositeHunk[]` when `opts.composite` is set.
+    #[plugin_api(
+        async_promise,
+        js_name = "computeDiff",
+        ts_return = "DiffHunk[] | TsCompositeHunk[]"
+    )]
+    #[qjs(rename = "_computeDiffStart")]
+    pub fn compute_diff_start(
+        &self,
+        _ctx: rquickjs::Ctx<'_>,
+        old_text: String,
+        new_text: String,
+        opts: rquickjs::function::Opt<ComputeDiffOptions>,
+    ) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            // Record context for this callback
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let _ = self.command_sender.send(PluginCommand::ComputeDiff {
+            old_text,
+            new_text,
+            options: opts.0.unwrap_or_default(),
+            request_id: id,
+        });
+        id
+    }
+
     /// Delay/sleep (async, returns request_id)
     #[plugin_api(async_promise, js_name = "delay", ts_return = "void")]
     #[qjs(rename = "_delayStart")]
@@ -2436,6 +4118,103 @@ impl JsEditorApi {
         id
     }
 
+    /// Start a timer for `setTimeout`/`setInterval` (returns callback_id)
+    ///
+    /// The timer sleeps on the plugin thread's tokio runtime; when it fires,
+    /// the callback is resolved like any other async operation.
+    #[qjs(rename = "_timerStart")]
+    pub fn timer_start(&self, delay_ms: f64) -> u64 {
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let duration_ms = if delay_ms.is_finite() && delay_ms > 0.0 {
+            delay_ms as u64
+        } else {
+            0
+        };
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let sender = self.timer_sender.clone();
+                let task = handle.spawn(async move {
+                    tokio::time::sleep(std::time::Duration::from_millis(duration_ms)).await;
+                    let _ = sender.send(id);
+                });
+                self.timer_tasks
+                    .borrow_mut()
+                    .insert(id, task.abort_handle());
+            }
+            Err(_) => {
+                // Outside the plugin thread (standalone backend): let the editor wait
+                let _ = self.command_sender.send(PluginCommand::Delay {
+                    callback_id: JsCallbackId::new(id),
+                    duration_ms,
+                });
+            }
+        }
+        id
+    }
+
+    /// Run a function on a worker thread (async, returns request_id)
+    ///
+    /// The function runs in its own QuickJS runtime without the `editor` API:
+    /// it is passed as a function or its source text, and its arguments and
+    /// result are copied as JSON. Resolves to the function's result (awaited
+    /// if it returns a promise) and rejects with the error it throws.
+    #[plugin_api(async_promise, js_name = "runInWorker", ts_return = "unknown")]
+    #[qjs(rename = "_runInWorkerStart")]
+    pub fn run_in_worker_start<'js>(
+        &self,
+        ctx: rquickjs::Ctx<'js>,
+        #[plugin_api(ts_type = "string | ((...args: any[]) => unknown)")] source: Value<'js>,
+        #[plugin_api(ts_type = "unknown[]")] args: rquickjs::function::Opt<Value<'js>>,
+    ) -> rquickjs::Result<u64> {
+        // A function's string conversion is its source text
+        let source: rquickjs::convert::Coerced<String> = source.get()?;
+        let source = source.0;
+        let args = args
+            .0
+            .filter(|args| args.is_array())
+            .map(|args| js_to_json(&ctx, args))
+            .unwrap_or_else(|| serde_json::Value::Array(Vec::new()));
+
+        let id = {
+            let mut id_ref = self.next_request_id.borrow_mut();
+            let id = *id_ref;
+            *id_ref += 1;
+            self.callback_contexts
+                .borrow_mut()
+                .insert(id, self.plugin_name.clone());
+            id
+        };
+        let cancelled = Arc::new(AtomicBool::new(false));
+        self.worker_tasks
+            .borrow_mut()
+            .insert(id, Arc::clone(&cancelled));
+        worker::spawn(
+            id,
+            source,
+            args.to_string(),
+            cancelled,
+            self.worker_sender.clone(),
+        );
+        Ok(id)
+    }
+
+    /// Cancel a timer started by `_timerStart` (called by `clearTimeout`/`clearInterval`)
+    #[qjs(rename = "_timerCancel")]
+    pub fn timer_cancel(&self, callback_id: u64) {
+        if let Some(task) = self.timer_tasks.borrow_mut().remove(&callback_id) {
+            task.abort();
+        }
+        self.callback_contexts.borrow_mut().remove(&callback_id);
+    }
+
     /// Send LSP request (async, returns request_id)
     #[plugin_api(async_promise, js_name = "sendLspRequest", ts_return = "unknown")]
     #[qjs(rename = "_sendLspRequestStart")]
@@ -2471,10 +4250,13 @@ impl JsEditorApi {
     }
 
     /// Spawn a background process (async, returns request_id which is also process_id)
+    /// The handle resolves when the process exits; `onStdout`/`onStderr`
+    /// on it receive the process's output line by line while it runs.
     #[plugin_api(
         async_thenable,
         js_name = "spawnBackgroundProcess",
-        ts_return = "BackgroundProcessResult"
+        ts_return = "BackgroundProcessResult",
+        ts_handle = "BackgroundProcessHandle"
     )]
     #[qjs(rename = "_spawnBackgroundProcessStart")]
     pub fn spawn_background_process_start(
@@ -2515,6 +4297,18 @@ impl JsEditorApi {
             .is_ok()
     }
 
+    /// Start sending a background process's output to this plugin's
+    /// listeners (called by the handle's `onStdout`/`onStderr`)
+    #[qjs(rename = "_subscribeProcessOutput")]
+    pub fn subscribe_process_output(&self, process_id: u64) -> bool {
+        self.command_sender
+            .send(PluginCommand::SubscribeProcessOutput {
+                plugin_name: self.plugin_name.clone(),
+                process_id,
+            })
+            .is_ok()
+    }
+
     // === Misc ===
 
     /// Force refresh of line display
@@ -2594,11 +4388,13 @@ impl JsEditorApi {
     }
 
     /// List all loaded plugins (async)
-    /// Returns array of { name: string, path: string, enabled: boolean }
+    /// Returns array of { name, path, enabled, activationEvents, activatedBy }.
+    /// `activatedBy` is the event that loaded the plugin ("startup", "onLanguage:rust", ...),
+    /// or null while the plugin is waiting for one of its `activationEvents`.
     #[plugin_api(
         async_promise,
         js_name = "listPlugins",
-        ts_return = "Array<{name: string, path: string, enabled: boolean}>"
+        ts_return = "Array<{name: string, path: string, enabled: boolean, activationEvents: string[], activatedBy: string | null}>"
     )]
     #[qjs(rename = "_listPluginsStart")]
     pub fn list_plugins_start(&self, _ctx: rquickjs::Ctx<'_>) -> u64 {
@@ -2638,10 +4434,38 @@ pub struct QuickJsBackend {
     pending_responses: PendingResponses,
     /// Next request ID for async operations
     next_request_id: Rc<RefCell<u64>>,
+    /// Next ID handed out by `editor.createMarker`
+    next_marker_id: Rc<Cell<u64>>,
     /// Plugin name for each pending callback ID
     callback_contexts: Rc<RefCell<HashMap<u64, String>>>,
     /// Bridge for editor services (i18n, theme, etc.)
     pub services: Arc<dyn fresh_core::services::PluginServiceBridge>,
+    /// Capabilities per plugin name (plugins not listed get all of them)
+    plugin_capabilities: HashMap<String, CapabilitySet>,
+    /// Capabilities declared by the `plugin.json` of plugins that have one
+    plugin_manifests: HashMap<String, CapabilitySet>,
+    /// Permissions the user has allowed, per plugin name
+    granted_permissions: HashMap<String, CapabilitySet>,
+    /// What each loaded plugin may call
+    context_capabilities: ContextCapabilities,
+    /// Sender half of the fired-timer channel, shared with every context
+    timer_sender: tokio::sync::mpsc::UnboundedSender<u64>,
+    /// Receiver of fired timers, until the plugin thread takes it
+    timer_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<u64>>,
+    /// Timers that have not fired yet
+    timer_tasks: TimerTasks,
+    /// Sender half of the finished-worker channel, shared with every context
+    worker_sender: tokio::sync::mpsc::UnboundedSender<WorkerResult>,
+    /// Receiver of finished workers, until the plugin thread takes it
+    worker_receiver: Option<tokio::sync::mpsc::UnboundedReceiver<WorkerResult>>,
+    /// Workers that have not finished yet
+    worker_tasks: WorkerTasks,
+    /// Overlay namespaces used by each plugin
+    plugin_overlays: PluginOverlays,
+    /// Event handlers registered with rate-limiting options
+    event_coalescers: EventCoalescers,
+    /// `editor.onPluginReload` handlers of each plugin
+    reload_handlers: ReloadHandlers,
 }
 
 impl QuickJsBackend {
@@ -2710,6 +4534,8 @@ impl QuickJsBackend {
         let registered_actions = Rc::new(RefCell::new(HashMap::new()));
         let next_request_id = Rc::new(RefCell::new(1u64));
         let callback_contexts = Rc::new(RefCell::new(HashMap::new()));
+        let (timer_sender, timer_receiver) = tokio::sync::mpsc::unbounded_channel();
+        let (worker_sender, worker_receiver) = tokio::sync::mpsc::unbounded_channel();
 
         let backend = Self {
             runtime,
@@ -2721,8 +4547,22 @@ impl QuickJsBackend {
             command_sender,
             pending_responses,
             next_request_id,
+            next_marker_id: Rc::new(Cell::new(1)),
             callback_contexts,
             services,
+            plugin_capabilities: HashMap::new(),
+            plugin_manifests: HashMap::new(),
+            granted_permissions: HashMap::new(),
+            context_capabilities: Rc::new(RefCell::new(HashMap::new())),
+            timer_sender,
+            timer_receiver: Some(timer_receiver),
+            timer_tasks: Rc::new(RefCell::new(HashMap::new())),
+            worker_sender,
+            worker_receiver: Some(worker_receiver),
+            worker_tasks: Rc::new(RefCell::new(HashMap::new())),
+            plugin_overlays: Rc::new(RefCell::new(HashMap::new())),
+            reload_handlers: Rc::new(RefCell::new(HashMap::new())),
+            event_coalescers: Rc::new(RefCell::new(HashMap::new())),
         };
 
         // Initialize main context (for internal utilities if needed)
@@ -2732,13 +4572,100 @@ impl QuickJsBackend {
         Ok(backend)
     }
 
-    /// Set up the editor API in a specific JavaScript context
-    fn setup_context_api(&self, context: &Context, plugin_name: &str) -> Result<()> {
-        let state_snapshot = Arc::clone(&self.state_snapshot);
-        let command_sender = self.command_sender.clone();
-        let event_handlers = Rc::clone(&self.event_handlers);
+    /// Restrict the API available to a plugin
+    ///
+    /// Takes effect when the plugin's context is created, so call this before
+    /// loading the plugin.
+    pub fn set_plugin_capabilities(&mut self, plugin_name: &str, capabilities: CapabilitySet) {
+        self.plugin_capabilities
+            .insert(plugin_name.to_string(), capabilities);
+    }
+
+    /// Set the capabilities a plugin's `plugin.json` declares (None if it has
+    /// no manifest, which gives it every permission)
+    ///
+    /// Permissions the manifest doesn't declare are disabled, and declared
+    /// ones ask the user for consent the first time they are used. Takes
+    /// effect when the plugin's context is created.
+    pub fn set_plugin_manifest(&mut self, plugin_name: &str, declared: Option<CapabilitySet>) {
+        match declared {
+            Some(declared) => {
+                self.plugin_manifests
+                    .insert(plugin_name.to_string(), declared);
+            }
+            None => {
+                self.plugin_manifests.remove(plugin_name);
+            }
+        }
+    }
+
+    /// Set the permissions the user has already allowed a plugin
+    pub fn set_granted_permissions(&mut self, plugin_name: &str, granted: CapabilitySet) {
+        self.granted_permissions
+            .insert(plugin_name.to_string(), granted);
+    }
+
+    /// Allow a plugin to use a permission, enabling its methods right away
+    /// if the plugin is loaded
+    pub fn grant_permission(&mut self, plugin_name: &str, capability: Capability) {
+        self.granted_permissions
+            .entry(plugin_name.to_string())
+            .or_insert_with(CapabilitySet::none)
+            .insert(capability);
+
+        if let Some(allowed) = self.context_capabilities.borrow().get(plugin_name) {
+            let mut capabilities = allowed.get();
+            capabilities.pending.remove(capability);
+            allowed.set(capabilities);
+        }
+    }
+
+    /// Capabilities available to a plugin's context, and the permissions
+    /// among them that still need the user's consent
+    fn allowed_capabilities(&self, plugin_name: &str) -> PluginCapabilities {
+        let mut enabled = self
+            .plugin_capabilities
+            .get(plugin_name)
+            .copied()
+            .unwrap_or_default();
+        let Some(declared) = self.plugin_manifests.get(plugin_name) else {
+            return PluginCapabilities {
+                enabled,
+                pending: CapabilitySet::none(),
+                has_manifest: false,
+            };
+        };
+        let mut pending = CapabilitySet::none();
+        for capability in Capability::PERMISSIONS {
+            if !declared.contains(capability) {
+                enabled.remove(capability);
+            } else if enabled.contains(capability) {
+                pending.insert(capability);
+            }
+        }
+        let granted = self
+            .granted_permissions
+            .get(plugin_name)
+            .copied()
+            .unwrap_or_else(CapabilitySet::none);
+        PluginCapabilities {
+            enabled,
+            pending: pending.difference(granted),
+            has_manifest: true,
+        }
+    }
+
+    /// Set up the editor API in a specific JavaScript context
+    fn setup_context_api(&self, context: &Context, plugin_name: &str) -> Result<()> {
+        let state_snapshot = Arc::clone(&self.state_snapshot);
+        let command_sender = self.command_sender.clone();
+        let event_handlers = Rc::clone(&self.event_handlers);
         let registered_actions = Rc::clone(&self.registered_actions);
         let next_request_id = Rc::clone(&self.next_request_id);
+        let capabilities = Rc::new(Cell::new(self.allowed_capabilities(plugin_name)));
+        self.context_capabilities
+            .borrow_mut()
+            .insert(plugin_name.to_string(), Rc::clone(&capabilities));
 
         context.with(|ctx| {
             let globals = ctx.globals();
@@ -2754,8 +4681,19 @@ impl QuickJsBackend {
                 registered_actions: Rc::clone(&registered_actions),
                 event_handlers: Rc::clone(&event_handlers),
                 next_request_id: Rc::clone(&next_request_id),
+                next_marker_id: Rc::clone(&self.next_marker_id),
                 callback_contexts: Rc::clone(&self.callback_contexts),
                 services: self.services.clone(),
+                undo_groups: RefCell::new(HashSet::new()),
+                pending_growth: RefCell::new(HashMap::new()),
+                timer_sender: self.timer_sender.clone(),
+                timer_tasks: Rc::clone(&self.timer_tasks),
+                worker_sender: self.worker_sender.clone(),
+                worker_tasks: Rc::clone(&self.worker_tasks),
+                plugin_overlays: Rc::clone(&self.plugin_overlays),
+                reload_handlers: Rc::clone(&self.reload_handlers),
+                event_coalescers: Rc::clone(&self.event_coalescers),
+                capabilities,
                 plugin_name: plugin_name.to_string(),
             };
             let editor = rquickjs::Class::<JsEditorApi>::instance(ctx.clone(), js_api)?;
@@ -2783,6 +4721,16 @@ impl QuickJsBackend {
             })?)?;
             globals.set("console", console)?;
 
+            // Argument checks for API calls, generated from the method signatures
+            globals.set("_validateArgs", Function::new(ctx.clone(), |ctx: rquickjs::Ctx, method: String, arg_kinds: Vec<String>| {
+                let arg_kinds: Vec<&str> = arg_kinds.iter().map(String::as_str).collect();
+                JsEditorApi::validate_js_args(&method, &arg_kinds)
+                    .map_err(|message| rquickjs::Exception::throw_type(&ctx, &message))
+            })?)?;
+
+            // Error classes thrown by API calls, see PluginError
+            ctx.eval::<(), _>(plugin_error_classes_js())?;
+
             // Bootstrap: Promise infrastructure (getEditor is defined per-plugin in execute_js)
             ctx.eval::<(), _>(r#"
                 // Pending promise callbacks: callbackId -> { resolve, reject }
@@ -2830,8 +4778,6 @@ impl QuickJsBackend {
                         // Call via bracket notation to preserve method binding and Ctx injection
                         const callbackId = editor[methodName](...args);
                         return new Promise((resolve, reject) => {
-                            // NOTE: setTimeout not available in QuickJS - timeout disabled for now
-                            // TODO: Implement setTimeout polyfill using editor.delay() or similar
                             globalThis._pendingCallbacks.set(callbackId, { resolve, reject });
                         });
                     };
@@ -2853,10 +4799,9 @@ impl QuickJsBackend {
                         // Call via bracket notation to preserve method binding and Ctx injection
                         const callbackId = editor[methodName](...args);
                         const resultPromise = new Promise((resolve, reject) => {
-                            // NOTE: setTimeout not available in QuickJS - timeout disabled for now
                             globalThis._pendingCallbacks.set(callbackId, { resolve, reject });
                         });
-                        return {
+                        const handle = {
                             get result() { return resultPromise; },
                             then(onFulfilled, onRejected) {
                                 return resultPromise.then(onFulfilled, onRejected);
@@ -2865,25 +4810,116 @@ impl QuickJsBackend {
                                 return resultPromise.catch(onRejected);
                             }
                         };
+                        const extend = globalThis._thenableExtensions[fnName];
+                        if (extend) {
+                            extend(handle, callbackId, resultPromise);
+                        }
+                        return handle;
+                    };
+                };
+
+                // Timers: each one is a callback the editor resolves when it fires.
+                // Timer ID -> callback ID of its pending firing
+                globalThis._timers = new Map();
+                globalThis._nextTimerId = 1;
+
+                globalThis._scheduleTimer = function(timerId, delay, fire) {
+                    const callbackId = editor._timerStart(delay);
+                    globalThis._timers.set(timerId, callbackId);
+                    globalThis._pendingCallbacks.set(callbackId, { resolve: fire, reject() {} });
+                };
+
+                globalThis._runTimerCallback = function(name, callback, args) {
+                    try {
+                        callback(...args);
+                    } catch (e) {
+                        console.error(`${name} callback failed:`, e);
+                    }
+                };
+
+                globalThis.setTimeout = function(callback, delay, ...args) {
+                    if (typeof callback !== 'function') {
+                        throw new TypeError('setTimeout: callback must be a function');
+                    }
+                    const timerId = globalThis._nextTimerId++;
+                    _scheduleTimer(timerId, Number(delay) || 0, () => {
+                        globalThis._timers.delete(timerId);
+                        _runTimerCallback('setTimeout', callback, args);
+                    });
+                    return timerId;
+                };
+
+                globalThis.setInterval = function(callback, delay, ...args) {
+                    if (typeof callback !== 'function') {
+                        throw new TypeError('setInterval: callback must be a function');
+                    }
+                    const timerId = globalThis._nextTimerId++;
+                    // At least 1ms, so a zero interval doesn't starve the editor
+                    const interval = Math.max(Number(delay) || 0, 1);
+                    const tick = () => {
+                        // Schedule the next tick first, so the callback can clear it
+                        _scheduleTimer(timerId, interval, tick);
+                        _runTimerCallback('setInterval', callback, args);
                     };
+                    _scheduleTimer(timerId, interval, tick);
+                    return timerId;
                 };
 
-                // Apply wrappers to async functions on editor
-                editor.spawnProcess = _wrapAsyncThenable("_spawnProcessStart", "spawnProcess");
-                editor.delay = _wrapAsync("_delayStart", "delay");
-                editor.createVirtualBuffer = _wrapAsync("_createVirtualBufferStart", "createVirtualBuffer");
-                editor.createVirtualBufferInSplit = _wrapAsync("_createVirtualBufferInSplitStart", "createVirtualBufferInSplit");
-                editor.createVirtualBufferInExistingSplit = _wrapAsync("_createVirtualBufferInExistingSplitStart", "createVirtualBufferInExistingSplit");
-                editor.sendLspRequest = _wrapAsync("_sendLspRequestStart", "sendLspRequest");
-                editor.spawnBackgroundProcess = _wrapAsyncThenable("_spawnBackgroundProcessStart", "spawnBackgroundProcess");
-                editor.spawnProcessWait = _wrapAsync("_spawnProcessWaitStart", "spawnProcessWait");
-                editor.getBufferText = _wrapAsync("_getBufferTextStart", "getBufferText");
-                editor.createCompositeBuffer = _wrapAsync("_createCompositeBufferStart", "createCompositeBuffer");
-                editor.getHighlights = _wrapAsync("_getHighlightsStart", "getHighlights");
-                editor.loadPlugin = _wrapAsync("_loadPluginStart", "loadPlugin");
-                editor.unloadPlugin = _wrapAsync("_unloadPluginStart", "unloadPlugin");
-                editor.reloadPlugin = _wrapAsync("_reloadPluginStart", "reloadPlugin");
-                editor.listPlugins = _wrapAsync("_listPluginsStart", "listPlugins");
+                globalThis.clearTimeout = function(timerId) {
+                    const callbackId = globalThis._timers.get(timerId);
+                    if (callbackId === undefined) {
+                        return;
+                    }
+                    globalThis._timers.delete(timerId);
+                    globalThis._pendingCallbacks.delete(callbackId);
+                    editor._timerCancel(callbackId);
+                };
+                globalThis.clearInterval = globalThis.clearTimeout;
+
+                // Output listeners of background processes, by process ID
+                globalThis._processOutputListeners = new Map();
+
+                // Deliver a line of background process output (called from Rust)
+                globalThis._dispatchProcessOutput = function(processId, stream, line) {
+                    const listeners = globalThis._processOutputListeners.get(processId);
+                    if (!listeners) {
+                        return;
+                    }
+                    for (const listener of listeners[stream]) {
+                        try {
+                            listener(line);
+                        } catch (e) {
+                            console.error(`${stream} listener of process ${processId} failed:`, e);
+                        }
+                    }
+                };
+
+                // Extra members of the handles returned by specific thenable APIs
+                globalThis._thenableExtensions = {
+                    // The callback ID doubles as the process ID
+                    spawnBackgroundProcess(handle, processId, resultPromise) {
+                        let listeners = null;
+                        const listen = function(stream, listener) {
+                            if (typeof listener !== 'function') {
+                                throw new TypeError(`on${stream === 'stdout' ? 'Stdout' : 'Stderr'}: listener must be a function`);
+                            }
+                            if (!listeners) {
+                                listeners = { stdout: [], stderr: [] };
+                                globalThis._processOutputListeners.set(processId, listeners);
+                                editor._subscribeProcessOutput(processId);
+                                // All output is delivered before the process's exit
+                                const forget = () => globalThis._processOutputListeners.delete(processId);
+                                resultPromise.then(forget, forget);
+                            }
+                            listeners[stream].push(listener);
+                            return handle;
+                        };
+                        handle.processId = processId;
+                        handle.onStdout = listener => listen('stdout', listener);
+                        handle.onStderr = listener => listen('stderr', listener);
+                        handle.kill = () => Promise.resolve(editor.killBackgroundProcess(processId));
+                    }
+                };
 
                 // Wrapper for deleteTheme - wraps sync function in Promise
                 editor.deleteTheme = function(name) {
@@ -2896,8 +4932,92 @@ impl QuickJsBackend {
                         }
                     });
                 };
+
+                // Check arguments before the call reaches Rust, so a wrong call
+                // throws a TypeError instead of being coerced
+                globalThis._wrapValidated = function(fnName) {
+                    const original = editor[fnName];
+                    if (typeof original !== 'function') {
+                        return;
+                    }
+                    editor[fnName] = function(...args) {
+                        _validateArgs(fnName, args.map(arg =>
+                            arg === null ? "null" : Array.isArray(arg) ? "array" : typeof arg));
+                        return original.apply(editor, args);
+                    };
+                };
+
+                // Let on/off take a handler function: it is registered under a
+                // generated global name, which is what the editor calls
+                globalThis._wrapEventHandlers = function() {
+                    const on = editor.on;
+                    const off = editor.off;
+                    const handlerNames = new Map();
+                    editor.on = function(eventName, handler, ...options) {
+                        if (typeof handler === 'function') {
+                            let name = handlerNames.get(handler);
+                            if (name === undefined) {
+                                name = `__eventHandler${handlerNames.size}`;
+                                globalThis[name] = handler;
+                                handlerNames.set(handler, name);
+                            }
+                            handler = name;
+                        }
+                        return on.call(editor, eventName, handler, ...options);
+                    };
+                    editor.off = function(eventName, handler) {
+                        if (typeof handler === 'function') {
+                            handler = handlerNames.get(handler) ?? "";
+                        }
+                        return off.call(editor, eventName, handler);
+                    };
+                    const onPluginReload = editor.onPluginReload;
+                    editor.onPluginReload = function(handler) {
+                        if (typeof handler === 'function') {
+                            const name = `__reloadHandler${handlerNames.size}`;
+                            globalThis[name] = handler;
+                            handlerNames.set(handler, name);
+                            handler = name;
+                        }
+                        return onPluginReload.call(editor, handler);
+                    };
+                };
+
+                // Warn (once per method) when the plugin calls a deprecated method
+                globalThis._wrapDeprecated = function(fnName, message) {
+                    const original = editor[fnName];
+                    if (typeof original !== 'function') {
+                        return;
+                    }
+                    let warned = false;
+                    editor[fnName] = function(...args) {
+                        if (!warned) {
+                            warned = true;
+                            console.warn(`${__pluginName__}: editor.${fnName} is deprecated: ${message}`);
+                        }
+                        return original.apply(editor, args);
+                    };
+                };
             "#.as_bytes())?;
 
+            // Apply wrappers to async functions on editor
+            ctx.eval::<(), _>(capabilities::async_wrappers_js())?;
+
+            // Namespace objects (`editor.buffers`, ...) forwarding to the methods above
+            ctx.eval::<(), _>(JSEDITORAPI_JS_NAMESPACE_SHIM)?;
+
+            // Wrap methods after the async wrappers so the public names are wrapped
+            let wrap_validated: Function = globals.get("_wrapValidated")?;
+            for name in JSEDITORAPI_JS_METHODS {
+                wrap_validated.call::<_, ()>((*name,))?;
+            }
+            let wrap_deprecated: Function = globals.get("_wrapDeprecated")?;
+            for (name, message) in JSEDITORAPI_DEPRECATED_METHODS {
+                wrap_deprecated.call::<_, ()>((*name, *message))?;
+            }
+            let wrap_event_handlers: Function = globals.get("_wrapEventHandlers")?;
+            wrap_event_handlers.call::<_, ()>(())?;
+
             Ok::<_, rquickjs::Error>(())
         }).map_err(|e| anyhow!("Failed to set up global API: {}", e))?;
 
@@ -3034,51 +5154,17 @@ impl QuickJsBackend {
                 return Ok(true);
             }
 
-            let plugin_contexts = self.plugin_contexts.borrow();
             for handler in handler_pairs {
-                let context_opt = plugin_contexts.get(&handler.plugin_name);
-                if let Some(context) = context_opt {
-                    let handler_name = &handler.handler_name;
-                    // Call the handler and properly handle both sync and async errors
-                    // Async handlers return Promises - we attach .catch() to surface rejections
-                    // Double-encode the JSON to produce a valid JavaScript string literal:
-                    // event_data = {"path": "/test"} -> first to_string = {"path": "/test"}
-                    // -> second to_string = "{\"path\": \"/test\"}" (properly quoted for JS)
-                    let json_string = serde_json::to_string(event_data)?;
-                    let js_string_literal = serde_json::to_string(&json_string)?;
-                    let code = format!(
-                        r#"
-                        (function() {{
-                            try {{
-                                const data = JSON.parse({});
-                                if (typeof globalThis["{}"] === 'function') {{
-                                    const result = globalThis["{}"](data);
-                                    // If handler returns a Promise, catch rejections
-                                    if (result && typeof result.then === 'function') {{
-                                        result.catch(function(e) {{
-                                            console.error('Handler {} async error:', e);
-                                            // Re-throw to make it an unhandled rejection for the runtime to catch
-                                            throw e;
-                                        }});
-                                    }}
-                                }}
-                            }} catch (e) {{
-                                console.error('Handler {} sync error:', e);
-                                throw e;
-                            }}
-                        }})();
-                        "#,
-                        js_string_literal, handler_name, handler_name, handler_name, handler_name
-                    );
-
-                    context.with(|ctx| {
-                        if let Err(e) = ctx.eval::<(), _>(code.as_bytes()) {
-                            log_js_error(&ctx, e, &format!("handler {}", handler_name));
-                        }
-                        // Run pending jobs to process any Promise continuations and catch errors
-                        run_pending_jobs_checked(&ctx, &format!("emit handler {}", handler_name));
-                    });
-                }
+                let key = (
+                    event_name.to_string(),
+                    handler.plugin_name.clone(),
+                    handler.handler_name.clone(),
+                );
+                let data = match self.coalesce_event(&key, event_data) {
+                    Some(data) => data,
+                    None => continue,
+                };
+                self.call_event_handler(&handler, &data)?;
             }
         }
 
@@ -3086,6 +5172,133 @@ impl QuickJsBackend {
         Ok(true)
     }
 
+    /// Apply a handler's rate limit to an event: returns the event if the
+    /// handler should run now, or holds it and schedules a flush
+    fn coalesce_event(
+        &self,
+        key: &(String, String, String),
+        event_data: &serde_json::Value,
+    ) -> Option<serde_json::Value> {
+        let mut coalescers = self.event_coalescers.borrow_mut();
+        let Some(handler) = coalescers.get_mut(key) else {
+            return Some(event_data.clone());
+        };
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            // Outside the plugin thread (standalone backend) there is no timer
+            return Some(event_data.clone());
+        };
+        let now = std::time::Instant::now();
+        match handler.coalescer.event(event_data.clone(), now) {
+            Delivery::Now(data) => Some(data),
+            Delivery::At(at) => {
+                if matches!(handler.flush, Some((_, scheduled, _)) if scheduled == at) {
+                    return None;
+                }
+                if let Some((_, _, task)) = handler.flush.take() {
+                    task.abort();
+                }
+                let id = {
+                    let mut id_ref = self.next_request_id.borrow_mut();
+                    let id = *id_ref;
+                    *id_ref += 1;
+                    id
+                };
+                let sender = self.timer_sender.clone();
+                let task = runtime.spawn(async move {
+                    tokio::time::sleep_until(at.into()).await;
+                    let _ = sender.send(id);
+                });
+                handler.flush = Some((id, at, task.abort_handle()));
+                None
+            }
+        }
+    }
+
+    /// Run a rate-limited handler with its held event when its flush timer fires
+    fn flush_coalesced_event(&mut self, timer_id: u64) {
+        let flushed = {
+            let mut coalescers = self.event_coalescers.borrow_mut();
+            coalescers
+                .iter_mut()
+                .find_map(|((_, plugin_name, handler_name), handler)| {
+                    if !matches!(handler.flush, Some((id, _, _)) if id == timer_id) {
+                        return None;
+                    }
+                    handler.flush = None;
+                    let events = handler.coalescer.flush(std::time::Instant::now());
+                    Some((
+                        PluginHandler {
+                            plugin_name: plugin_name.clone(),
+                            handler_name: handler_name.clone(),
+                        },
+                        events,
+                    ))
+                })
+        };
+        if let Some((handler, events)) = flushed {
+            for data in events {
+                if let Err(e) = self.call_event_handler(&handler, &data) {
+                    tracing::error!("Failed to run handler {}: {}", handler.handler_name, e);
+                }
+            }
+        }
+    }
+
+    /// Call an event handler in its plugin's context
+    fn call_event_handler(
+        &self,
+        handler: &PluginHandler,
+        event_data: &serde_json::Value,
+    ) -> Result<()> {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(&handler.plugin_name) else {
+            return Ok(());
+        };
+        let handler_name = &handler.handler_name;
+        // Call the handler and properly handle both sync and async errors
+        // Async handlers return Promises - we attach .catch() to surface rejections
+        // Double-encode the JSON to produce a valid JavaScript string literal:
+        // event_data = {"path": "/test"} -> first to_string = {"path": "/test"}
+        // -> second to_string = "{\"path\": \"/test\"}" (properly quoted for JS)
+        let json_string = serde_json::to_string(event_data)?;
+        let js_string_literal = serde_json::to_string(&json_string)?;
+        let code = format!(
+            r#"
+            (function() {{
+                try {{
+                    const data = JSON.parse({});
+                    if (typeof globalThis["{}"] === 'function') {{
+                        const result = globalThis["{}"](data);
+                        // If handler returns a Promise, catch rejections
+                        if (result && typeof result.then === 'function') {{
+                            result.catch(function(e) {{
+                                console.error('Handler {} async error:', e);
+                                editor._endOpenUndoGroups();
+                                // Re-throw to make it an unhandled rejection for the runtime to catch
+                                throw e;
+                            }});
+                        }}
+                    }}
+                }} catch (e) {{
+                    console.error('Handler {} sync error:', e);
+                    editor._endOpenUndoGroups();
+                    throw e;
+                }}
+            }})();
+            "#,
+            js_string_literal, handler_name, handler_name, handler_name, handler_name
+        );
+
+        context.with(|ctx| {
+            if let Err(e) = ctx.eval::<(), _>(code.as_bytes()) {
+                log_js_error(&ctx, e, &format!("handler {}", handler_name));
+            }
+            // Run pending jobs to process any Promise continuations and catch errors
+            run_pending_jobs_checked(&ctx, &format!("emit handler {}", handler_name));
+        });
+        Ok(())
+    }
+
     /// Check if any handlers are registered for an event
     pub fn has_handlers(&self, event_name: &str) -> bool {
         self.event_handlers
@@ -3095,474 +5308,1714 @@ impl QuickJsBackend {
             .unwrap_or(false)
     }
 
-    /// Start an action without waiting for async operations to complete.
-    /// This is useful when the calling thread needs to continue processing
-    /// ResolveCallback requests that the action may be waiting for.
-    pub fn start_action(&mut self, action_name: &str) -> Result<()> {
-        let pair = self.registered_actions.borrow().get(action_name).cloned();
-        let (plugin_name, function_name) = match pair {
-            Some(handler) => (handler.plugin_name, handler.handler_name),
-            None => ("main".to_string(), action_name.to_string()),
-        };
-
+    /// Ask a plugin's completion provider for items
+    ///
+    /// The handler may be async; its items are sent back with
+    /// `PluginCommand::ProvideCompletions` whenever they are ready.
+    pub fn request_completions(
+        &mut self,
+        plugin_name: &str,
+        handler_name: &str,
+        request_id: u64,
+        request: &CompletionRequest,
+    ) -> Result<()> {
         let plugin_contexts = self.plugin_contexts.borrow();
-        let context = plugin_contexts
-            .get(&plugin_name)
-            .unwrap_or(&self.main_context);
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!(
+                "request_completions: plugin '{}' is not loaded",
+                plugin_name
+            );
+            return Ok(());
+        };
 
-        // Track execution state for signal handler debugging
         self.services
-            .set_js_execution_state(format!("action '{}' (fn: {})", action_name, function_name));
-
-        tracing::info!(
-            "start_action: BEGIN '{}' -> function '{}'",
-            action_name,
-            function_name
-        );
+            .set_js_execution_state(format!("completion provider '{}'", handler_name));
 
-        // Just call the function - don't try to await or drive Promises
+        // Double-encode like emit() to get a valid JS string literal
+        let request_json = serde_json::to_string(request)?;
+        let js_string_literal = serde_json::to_string(&request_json)?;
         let code = format!(
             r#"
-            (function() {{
-                console.log('[JS] start_action: calling {fn}');
+            (async function() {{
                 try {{
-                    if (typeof globalThis.{fn} === 'function') {{
-                        console.log('[JS] start_action: {fn} is a function, invoking...');
-                        globalThis.{fn}();
-                        console.log('[JS] start_action: {fn} invoked (may be async)');
-                    }} else {{
-                        console.error('[JS] Action {action} is not defined as a global function');
+                    if (typeof globalThis["{handler}"] !== 'function') {{
+                        console.error('Completion provider {handler} is not defined as a global function');
+                        return;
                     }}
+                    const items = await globalThis["{handler}"](JSON.parse({request}));
+                    editor._provideCompletions({request_id}, items || []);
                 }} catch (e) {{
-                    console.error('[JS] Action {action} error:', e);
+                    console.error('Completion provider {handler} error:', e);
                 }}
             }})();
             "#,
-            fn = function_name,
-            action = action_name
+            handler = handler_name,
+            request = js_string_literal,
+            request_id = request_id
         );
 
-        tracing::info!("start_action: evaluating JS code");
         context.with(|ctx| {
             if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
-                log_js_error(&ctx, e, &format!("action {}", action_name));
+                log_js_error(&ctx, e, &format!("completion provider {}", handler_name));
             }
-            tracing::info!("start_action: running pending microtasks");
-            // Run any immediate microtasks
-            let count = run_pending_jobs_checked(&ctx, &format!("start_action {}", action_name));
-            tracing::info!("start_action: executed {} pending jobs", count);
+            run_pending_jobs_checked(&ctx, &format!("completion provider {}", handler_name));
         });
 
-        tracing::info!("start_action: END '{}'", action_name);
-
-        // Clear execution state (action started, may still be running async)
         self.services.clear_js_execution_state();
-
         Ok(())
     }
 
-    /// Execute a registered action by name
-    pub async fn execute_action(&mut self, action_name: &str) -> Result<()> {
-        // First check if there's a registered command mapping
-        let pair = self.registered_actions.borrow().get(action_name).cloned();
-        let (plugin_name, function_name) = match pair {
-            Some(handler) => (handler.plugin_name, handler.handler_name),
-            None => ("main".to_string(), action_name.to_string()),
-        };
-
+    /// Ask a plugin's hover provider for markdown
+    ///
+    /// The handler may be async; its result is sent back with
+    /// `PluginCommand::ProvideHover` whenever it is ready.
+    pub fn request_hover(
+        &mut self,
+        plugin_name: &str,
+        handler_name: &str,
+        request_id: u64,
+        request: &HoverRequest,
+    ) -> Result<()> {
         let plugin_contexts = self.plugin_contexts.borrow();
-        let context = plugin_contexts
-            .get(&plugin_name)
-            .unwrap_or(&self.main_context);
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!("request_hover: plugin '{}' is not loaded", plugin_name);
+            return Ok(());
+        };
 
-        tracing::debug!(
-            "execute_action: '{}' -> function '{}'",
-            action_name,
-            function_name
-        );
+        self.services
+            .set_js_execution_state(format!("hover provider '{}'", handler_name));
 
-        // Call the function and await if it returns a Promise
-        // We use a global _executeActionResult to pass the result back
+        // Double-encode like emit() to get a valid JS string literal
+        let request_json = serde_json::to_string(request)?;
+        let js_string_literal = serde_json::to_string(&request_json)?;
         let code = format!(
             r#"
             (async function() {{
                 try {{
-                    if (typeof globalThis.{fn} === 'function') {{
-                        const result = globalThis.{fn}();
-                        // If it's a Promise, await it
-                        if (result && typeof result.then === 'function') {{
-                            await result;
-                        }}
-                    }} else {{
-                        console.error('Action {action} is not defined as a global function');
+                    if (typeof globalThis["{handler}"] !== 'function') {{
+                        console.error('Hover provider {handler} is not defined as a global function');
+                        return;
                     }}
+                    const markdown = await globalThis["{handler}"](JSON.parse({request}));
+                    editor._provideHover({request_id}, typeof markdown === 'string' ? markdown : null);
                 }} catch (e) {{
-                    console.error('Action {action} error:', e);
+                    console.error('Hover provider {handler} error:', e);
                 }}
             }})();
             "#,
-            fn = function_name,
-            action = action_name
+            handler = handler_name,
+            request = js_string_literal,
+            request_id = request_id
         );
 
         context.with(|ctx| {
-            // Eval returns a Promise for the async IIFE, which we need to drive
-            match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
-                Ok(value) => {
-                    // If it's a Promise, we need to drive the runtime to completion
-                    if value.is_object() {
-                        if let Some(obj) = value.as_object() {
-                            // Check if it's a Promise by looking for 'then' method
-                            if obj.get::<_, rquickjs::Function>("then").is_ok() {
-                                // Drive the runtime to process the promise
-                                // QuickJS processes promises synchronously when we call execute_pending_job
-                                run_pending_jobs_checked(
-                                    &ctx,
-                                    &format!("execute_action {} promise", action_name),
-                                );
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    log_js_error(&ctx, e, &format!("action {}", action_name));
-                }
+            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                log_js_error(&ctx, e, &format!("hover provider {}", handler_name));
             }
+            run_pending_jobs_checked(&ctx, &format!("hover provider {}", handler_name));
         });
 
+        self.services.clear_js_execution_state();
         Ok(())
     }
 
-    /// Poll the event loop once to run any pending microtasks
-    pub fn poll_event_loop_once(&mut self) -> bool {
-        let mut had_work = false;
+    /// Ask a plugin's inline suggestion provider for text to suggest
+    ///
+    /// The handler may be async; its result is sent back with
+    /// `PluginCommand::ProvideInlineSuggestion` whenever it is ready.
+    pub fn request_inline_suggestion(
+        &mut self,
+        plugin_name: &str,
+        handler_name: &str,
+        request_id: u64,
+        request: &InlineSuggestionRequest,
+    ) -> Result<()> {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!(
+                "request_inline_suggestion: plugin '{}' is not loaded",
+                plugin_name
+            );
+            return Ok(());
+        };
 
-        // Poll main context
-        self.main_context.with(|ctx| {
-            let count = run_pending_jobs_checked(&ctx, "poll_event_loop main");
-            if count > 0 {
-                had_work = true;
+        self.services
+            .set_js_execution_state(format!("inline suggestion provider '{}'", handler_name));
+
+        // Double-encode like emit() to get a valid JS string literal
+        let request_json = serde_json::to_string(request)?;
+        let js_string_literal = serde_json::to_string(&request_json)?;
+        let code = format!(
+            r#"
+            (async function() {{
+                try {{
+                    if (typeof globalThis["{handler}"] !== 'function') {{
+                        console.error('Inline suggestion provider {handler} is not defined as a global function');
+                        return;
+                    }}
+                    const text = await globalThis["{handler}"](JSON.parse({request}));
+                    editor._provideInlineSuggestion({request_id}, typeof text === 'string' ? text : null);
+                }} catch (e) {{
+                    console.error('Inline suggestion provider {handler} error:', e);
+                }}
+            }})();
+            "#,
+            handler = handler_name,
+            request = js_string_literal,
+            request_id = request_id
+        );
+
+        context.with(|ctx| {
+            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                log_js_error(
+                    &ctx,
+                    e,
+                    &format!("inline suggestion provider {}", handler_name),
+                );
             }
+            run_pending_jobs_checked(
+                &ctx,
+                &format!("inline suggestion provider {}", handler_name),
+            );
         });
 
-        // Poll all plugin contexts
-        let contexts = self.plugin_contexts.borrow().clone();
-        for (name, context) in contexts {
-            context.with(|ctx| {
-                let count = run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name));
-                if count > 0 {
-                    had_work = true;
-                }
-            });
-        }
-        had_work
+        self.services.clear_js_execution_state();
+        Ok(())
     }
 
-    /// Send a status message to the editor
-    pub fn send_status(&self, message: String) {
-        let _ = self
-            .command_sender
-            .send(PluginCommand::SetStatus { message });
+    /// Ask a plugin's formatter to format a buffer's text
+    ///
+    /// The handler may be async; its result is sent back with
+    /// `PluginCommand::ProvideFormattedText` whenever it is ready. A handler
+    /// that throws answers `null` so the editor stops waiting.
+    pub fn request_format(
+        &mut self,
+        plugin_name: &str,
+        handler_name: &str,
+        request_id: u64,
+        request: &FormatRequest,
+    ) -> Result<()> {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!("request_format: plugin '{}' is not loaded", plugin_name);
+            return Ok(());
+        };
+
+        self.services
+            .set_js_execution_state(format!("formatter '{}'", handler_name));
+
+        // Double-encode like emit() to get a valid JS string literal
+        let request_json = serde_json::to_string(request)?;
+        let js_string_literal = serde_json::to_string(&request_json)?;
+        let code = format!(
+            r#"
+            (async function() {{
+                try {{
+                    if (typeof globalThis["{handler}"] !== 'function') {{
+                        console.error('Formatter {handler} is not defined as a global function');
+                        editor._provideFormattedText({request_id}, null);
+                        return;
+                    }}
+                    const text = await globalThis["{handler}"](JSON.parse({request}));
+                    editor._provideFormattedText({request_id}, typeof text === 'string' ? text : null);
+                }} catch (e) {{
+                    console.error('Formatter {handler} error:', e);
+                    editor._provideFormattedText({request_id}, null);
+                }}
+            }})();
+            "#,
+            handler = handler_name,
+            request = js_string_literal,
+            request_id = request_id
+        );
+
+        context.with(|ctx| {
+            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                log_js_error(&ctx, e, &format!("formatter {}", handler_name));
+            }
+            run_pending_jobs_checked(&ctx, &format!("formatter {}", handler_name));
+        });
+
+        self.services.clear_js_execution_state();
+        Ok(())
     }
 
-    /// Resolve a pending async callback with a result (called from Rust when async op completes)
+    /// Ask a plugin's evaluator to evaluate selections
     ///
-    /// Takes a JSON string which is parsed and converted to a proper JS value.
-    /// This avoids string interpolation with eval for better type safety.
-    pub fn resolve_callback(
+    /// The handler is called once per request and may be async; the results
+    /// are sent back together with `PluginCommand::ProvideEvaluation`. A
+    /// request whose handler throws answers `null`.
+    pub fn request_evaluation(
         &mut self,
-        callback_id: fresh_core::api::JsCallbackId,
-        result_json: &str,
-    ) {
-        let id = callback_id.as_u64();
-        tracing::debug!("resolve_callback: starting for callback_id={}", id);
-
-        // Find the plugin name and then context for this callback
-        let plugin_name = {
-            let mut contexts = self.callback_contexts.borrow_mut();
-            contexts.remove(&id)
+        plugin_name: &str,
+        handler_name: &str,
+        request_id: u64,
+        requests: &[EvaluateRequest],
+    ) -> Result<()> {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!("request_evaluation: plugin '{}' is not loaded", plugin_name);
+            return Ok(());
         };
 
-        let Some(name) = plugin_name else {
-            tracing::warn!("resolve_callback: No plugin found for callback_id={}", id);
-            return;
+        self.services
+            .set_js_execution_state(format!("evaluator '{}'", handler_name));
+
+        // Double-encode like emit() to get a valid JS string literal
+        let requests_json = serde_json::to_string(requests)?;
+        let js_string_literal = serde_json::to_string(&requests_json)?;
+        let code = format!(
+            r#"
+            (async function() {{
+                const requests = JSON.parse({requests});
+                if (typeof globalThis["{handler}"] !== 'function') {{
+                    console.error('Evaluator {handler} is not defined as a global function');
+                    editor._provideEvaluation({request_id}, requests.map(() => null));
+                    return;
+                }}
+                const results = await Promise.all(requests.map(async (request) => {{
+                    try {{
+                        const result = await globalThis["{handler}"](request);
+                        if (typeof result === 'number') {{
+                            return String(result);
+                        }}
+                        return typeof result === 'string' ? result : null;
+                    }} catch (e) {{
+                        console.error('Evaluator {handler} error:', e);
+                        return null;
+                    }}
+                }}));
+                editor._provideEvaluation({request_id}, results);
+            }})();
+            "#,
+            handler = handler_name,
+            requests = js_string_literal,
+            request_id = request_id
+        );
+
+        context.with(|ctx| {
+            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                log_js_error(&ctx, e, &format!("evaluator {}", handler_name));
+            }
+            run_pending_jobs_checked(&ctx, &format!("evaluator {}", handler_name));
+        });
+
+        self.services.clear_js_execution_state();
+        Ok(())
+    }
+
+    /// Start an action without waiting for async operations to complete.
+    /// This is useful when the calling thread needs to continue processing
+    /// ResolveCallback requests that the action may be waiting for.
+    pub fn start_action(&mut self, action_name: &str) -> Result<()> {
+        let pair = self.registered_actions.borrow().get(action_name).cloned();
+        let (plugin_name, function_name) = match pair {
+            Some(handler) => (handler.plugin_name, handler.handler_name),
+            None => ("main".to_string(), action_name.to_string()),
         };
 
         let plugin_contexts = self.plugin_contexts.borrow();
-        let Some(context) = plugin_contexts.get(&name) else {
-            tracing::warn!("resolve_callback: Context lost for plugin {}", name);
-            return;
-        };
+        let context = plugin_contexts
+            .get(&plugin_name)
+            .unwrap_or(&self.main_context);
 
-        context.with(|ctx| {
-            // Parse JSON string to serde_json::Value
-            let json_value: serde_json::Value = match serde_json::from_str(result_json) {
-                Ok(v) => v,
-                Err(e) => {
-                    tracing::error!(
-                        "resolve_callback: failed to parse JSON for callback_id={}: {}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+        // Track execution state for signal handler debugging
+        self.services
+            .set_js_execution_state(format!("action '{}' (fn: {})", action_name, function_name));
 
-            // Convert to JS value using rquickjs_serde
-            let js_value = match rquickjs_serde::to_value(ctx.clone(), &json_value) {
-                Ok(v) => v,
-                Err(e) => {
-                    tracing::error!(
-                        "resolve_callback: failed to convert to JS value for callback_id={}: {}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+        tracing::info!(
+            "start_action: BEGIN '{}' -> function '{}'",
+            action_name,
+            function_name
+        );
 
-            // Get _resolveCallback function from globalThis
-            let globals = ctx.globals();
-            let resolve_fn: rquickjs::Function = match globals.get("_resolveCallback") {
-                Ok(f) => f,
-                Err(e) => {
-                    tracing::error!(
-                        "resolve_callback: _resolveCallback not found for callback_id={}: {:?}",
-                        id,
-                        e
-                    );
-                    return;
-                }
-            };
+        // Just call the function - don't try to await or drive Promises
+        let code = format!(
+            r#"
+            (function() {{
+                console.log('[JS] start_action: calling {fn}');
+                try {{
+                    if (typeof globalThis.{fn} === 'function') {{
+                        console.log('[JS] start_action: {fn} is a function, invoking...');
+                        const result = globalThis.{fn}();
+                        console.log('[JS] start_action: {fn} invoked (may be async)');
+                        // Close undo groups left open by a failed async action,
+                        // keeping the rejection unhandled so it is still reported
+                        if (result && typeof result.then === 'function') {{
+                            result.catch(function(e) {{
+                                editor._endOpenUndoGroups();
+                                throw e;
+                            }});
+                        }}
+                    }} else {{
+                        console.error('[JS] Action {action} is not defined as a global function');
+                    }}
+                }} catch (e) {{
+                    console.error('[JS] Action {action} error:', e);
+                    editor._endOpenUndoGroups();
+                }}
+            }})();
+            "#,
+            fn = function_name,
+            action = action_name
+        );
 
-            // Call the function with callback_id (as u64) and the JS value
-            if let Err(e) = resolve_fn.call::<_, ()>((id, js_value)) {
-                log_js_error(&ctx, e, &format!("resolving callback {}", id));
+        tracing::info!("start_action: evaluating JS code");
+        context.with(|ctx| {
+            if let Err(e) = ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                log_js_error(&ctx, e, &format!("action {}", action_name));
             }
-
-            // IMPORTANT: Run pending jobs to process Promise continuations
-            let job_count = run_pending_jobs_checked(&ctx, &format!("resolve_callback {}", id));
-            tracing::info!(
-                "resolve_callback: executed {} pending jobs for callback_id={}",
-                job_count,
-                id
-            );
+            tracing::info!("start_action: running pending microtasks");
+            // Run any immediate microtasks
+            let count = run_pending_jobs_checked(&ctx, &format!("start_action {}", action_name));
+            tracing::info!("start_action: executed {} pending jobs", count);
         });
-    }
 
-    /// Reject a pending async callback with an error (called from Rust when async op fails)
-    pub fn reject_callback(&mut self, callback_id: fresh_core::api::JsCallbackId, error: &str) {
-        let id = callback_id.as_u64();
+        tracing::info!("start_action: END '{}'", action_name);
 
-        // Find the plugin name and then context for this callback
-        let plugin_name = {
-            let mut contexts = self.callback_contexts.borrow_mut();
-            contexts.remove(&id)
-        };
+        // Clear execution state (action started, may still be running async)
+        self.services.clear_js_execution_state();
 
-        let Some(name) = plugin_name else {
-            tracing::warn!("reject_callback: No plugin found for callback_id={}", id);
-            return;
+        Ok(())
+    }
+
+    /// Execute a registered action by name
+    pub async fn execute_action(&mut self, action_name: &str) -> Result<()> {
+        // First check if there's a registered command mapping
+        let pair = self.registered_actions.borrow().get(action_name).cloned();
+        let (plugin_name, function_name) = match pair {
+            Some(handler) => (handler.plugin_name, handler.handler_name),
+            None => ("main".to_string(), action_name.to_string()),
         };
 
         let plugin_contexts = self.plugin_contexts.borrow();
-        let Some(context) = plugin_contexts.get(&name) else {
-            tracing::warn!("reject_callback: Context lost for plugin {}", name);
-            return;
+        let context = plugin_contexts
+            .get(&plugin_name)
+            .unwrap_or(&self.main_context);
+
+        tracing::debug!(
+            "execute_action: '{}' -> function '{}'",
+            action_name,
+            function_name
+        );
+
+        // Call the function and await if it returns a Promise
+        // We use a global _executeActionResult to pass the result back
+        let code = format!(
+            r#"
+            (async function() {{
+                try {{
+                    if (typeof globalThis.{fn} === 'function') {{
+                        const result = globalThis.{fn}();
+                        // If it's a Promise, await it
+                        if (result && typeof result.then === 'function') {{
+                            await result;
+                        }}
+                    }} else {{
+                        console.error('Action {action} is not defined as a global function');
+                    }}
+                }} catch (e) {{
+                    console.error('Action {action} error:', e);
+                    editor._endOpenUndoGroups();
+                }}
+            }})();
+            "#,
+            fn = function_name,
+            action = action_name
+        );
+
+        context.with(|ctx| {
+            // Eval returns a Promise for the async IIFE, which we need to drive
+            match ctx.eval::<rquickjs::Value, _>(code.as_bytes()) {
+                Ok(value) => {
+                    // If it's a Promise, we need to drive the runtime to completion
+                    if value.is_object() {
+                        if let Some(obj) = value.as_object() {
+                            // Check if it's a Promise by looking for 'then' method
+                            if obj.get::<_, rquickjs::Function>("then").is_ok() {
+                                // Drive the runtime to process the promise
+                                // QuickJS processes promises synchronously when we call execute_pending_job
+                                run_pending_jobs_checked(
+                                    &ctx,
+                                    &format!("execute_action {} promise", action_name),
+                                );
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    log_js_error(&ctx, e, &format!("action {}", action_name));
+                }
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Poll the event loop once to run any pending microtasks
+    pub fn poll_event_loop_once(&mut self) -> bool {
+        let mut had_work = false;
+
+        // Poll main context
+        self.main_context.with(|ctx| {
+            let count = run_pending_jobs_checked(&ctx, "poll_event_loop main");
+            if count > 0 {
+                had_work = true;
+            }
+        });
+
+        // Poll all plugin contexts
+        let contexts = self.plugin_contexts.borrow().clone();
+        for (name, context) in contexts {
+            context.with(|ctx| {
+                let count = run_pending_jobs_checked(&ctx, &format!("poll_event_loop {}", name));
+                if count > 0 {
+                    had_work = true;
+                }
+            });
+        }
+        had_work
+    }
+
+    /// Take the receiver of fired timers' callback IDs (the plugin thread
+    /// passes each one to `fire_timer`)
+    pub fn take_timer_receiver(&mut self) -> Option<tokio::sync::mpsc::UnboundedReceiver<u64>> {
+        self.timer_receiver.take()
+    }
+
+    /// Run the callback of a timer that has fired, unless it was cancelled
+    pub fn fire_timer(&mut self, callback_id: u64) {
+        if self.timer_tasks.borrow_mut().remove(&callback_id).is_some() {
+            self.resolve_callback(fresh_core::api::JsCallbackId::new(callback_id), "null");
+        } else {
+            self.flush_coalesced_event(callback_id);
+        }
+    }
+
+    /// Take the receiver of finished workers' results (the plugin thread
+    /// passes each one to `finish_worker`)
+    pub fn take_worker_receiver(
+        &mut self,
+    ) -> Option<tokio::sync::mpsc::UnboundedReceiver<WorkerResult>> {
+        self.worker_receiver.take()
+    }
+
+    /// Settle the promise of a finished worker, unless it was cancelled
+    pub fn finish_worker(&mut self, callback_id: u64, result: Result<String, String>) {
+        if self
+            .worker_tasks
+            .borrow_mut()
+            .remove(&callback_id)
+            .is_none()
+        {
+            return;
+        }
+        let callback_id = fresh_core::api::JsCallbackId::new(callback_id);
+        match result {
+            Ok(json) => self.resolve_callback(callback_id, &json),
+            Err(error) => self.reject_callback(callback_id, &error),
+        }
+    }
+
+    /// Interrupt the running workers of a plugin that is being unloaded
+    pub fn cancel_plugin_workers(&mut self, plugin_name: &str) {
+        let mut contexts = self.callback_contexts.borrow_mut();
+        self.worker_tasks.borrow_mut().retain(|id, cancelled| {
+            if contexts.get(id).map(String::as_str) == Some(plugin_name) {
+                contexts.remove(id);
+                cancelled.store(true, Ordering::Relaxed);
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Cancel the pending timers of a plugin that is being unloaded
+    pub fn cancel_plugin_timers(&mut self, plugin_name: &str) {
+        let mut contexts = self.callback_contexts.borrow_mut();
+        self.timer_tasks.borrow_mut().retain(|id, task| {
+            if contexts.get(id).map(String::as_str) == Some(plugin_name) {
+                contexts.remove(id);
+                task.abort();
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    /// Run a plugin's `editor.onPluginReload` handlers (before reloading it)
+    pub fn run_reload_handlers(&mut self, plugin_name: &str) {
+        let handlers = self
+            .reload_handlers
+            .borrow_mut()
+            .remove(plugin_name)
+            .unwrap_or_default();
+        if handlers.is_empty() {
+            return;
+        }
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            return;
+        };
+        context.with(|ctx| {
+            for handler_name in &handlers {
+                let result = ctx
+                    .globals()
+                    .get::<_, rquickjs::Function>(handler_name.as_str())
+                    .and_then(|handler| handler.call::<_, rquickjs::Value>(()));
+                if let Err(e) = result {
+                    log_js_error(&ctx, e, &format!("reload handler {}", handler_name));
+                }
+            }
+            run_pending_jobs_checked(&ctx, &format!("{} reload handlers", plugin_name));
+        });
+    }
+
+    /// Remove everything a plugin registered, so that it can be loaded again
+    /// from scratch: its JS context, event handlers, actions, timers, workers
+    /// and overlays. The editor is told to drop its providers and menu items.
+    pub fn teardown_plugin(&mut self, plugin_name: &str) {
+        self.cancel_plugin_timers(plugin_name);
+        self.cancel_plugin_workers(plugin_name);
+        self.reload_handlers.borrow_mut().remove(plugin_name);
+        self.event_coalescers
+            .borrow_mut()
+            .retain(|(_, name, _), handler| {
+                if name != plugin_name {
+                    return true;
+                }
+                if let Some((_, _, task)) = handler.flush.take() {
+                    task.abort();
+                }
+                false
+            });
+
+        for handlers in self.event_handlers.borrow_mut().values_mut() {
+            handlers.retain(|handler| handler.plugin_name != plugin_name);
+        }
+        self.registered_actions
+            .borrow_mut()
+            .retain(|_, handler| handler.plugin_name != plugin_name);
+        self.callback_contexts
+            .borrow_mut()
+            .retain(|_, name| name != plugin_name);
+
+        let overlays = self
+            .plugin_overlays
+            .borrow_mut()
+            .remove(plugin_name)
+            .unwrap_or_default();
+        for (buffer_id, namespace) in overlays {
+            let _ = self.command_sender.send(PluginCommand::ClearNamespace {
+                buffer_id: BufferId(buffer_id as usize),
+                namespace: OverlayNamespace::from_string(namespace),
+            });
+        }
+        let _ = self.command_sender.send(PluginCommand::PluginUnloaded {
+            plugin_name: plugin_name.to_string(),
+        });
+
+        self.context_capabilities.borrow_mut().remove(plugin_name);
+        self.plugin_contexts.borrow_mut().remove(plugin_name);
+    }
+
+    /// Send a status message to the editor
+    pub fn send_status(&self, message: String) {
+        let _ = self
+            .command_sender
+            .send(PluginCommand::SetStatus { message });
+    }
+
+    /// Resolve a pending async callback with a result (called from Rust when async op completes)
+    ///
+    /// Takes a JSON string which is parsed and converted to a proper JS value.
+    /// This avoids string interpolation with eval for better type safety.
+    pub fn resolve_callback(
+        &mut self,
+        callback_id: fresh_core::api::JsCallbackId,
+        result_json: &str,
+    ) {
+        let id = callback_id.as_u64();
+        tracing::debug!("resolve_callback: starting for callback_id={}", id);
+
+        // Find the plugin name and then context for this callback
+        let plugin_name = {
+            let mut contexts = self.callback_contexts.borrow_mut();
+            contexts.remove(&id)
+        };
+
+        let Some(name) = plugin_name else {
+            tracing::warn!("resolve_callback: No plugin found for callback_id={}", id);
+            return;
+        };
+
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(&name) else {
+            tracing::warn!("resolve_callback: Context lost for plugin {}", name);
+            return;
+        };
+
+        context.with(|ctx| {
+            // Parse JSON string to serde_json::Value
+            let json_value: serde_json::Value = match serde_json::from_str(result_json) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::error!(
+                        "resolve_callback: failed to parse JSON for callback_id={}: {}",
+                        id,
+                        e
+                    );
+                    return;
+                }
+            };
+
+            // Convert to JS value using rquickjs_serde
+            let js_value = match rquickjs_serde::to_value(ctx.clone(), &json_value) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::error!(
+                        "resolve_callback: failed to convert to JS value for callback_id={}: {}",
+                        id,
+                        e
+                    );
+                    return;
+                }
+            };
+
+            // Get _resolveCallback function from globalThis
+            let globals = ctx.globals();
+            let resolve_fn: rquickjs::Function = match globals.get("_resolveCallback") {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::error!(
+                        "resolve_callback: _resolveCallback not found for callback_id={}: {:?}",
+                        id,
+                        e
+                    );
+                    return;
+                }
+            };
+
+            // Call the function with callback_id (as u64) and the JS value
+            if let Err(e) = resolve_fn.call::<_, ()>((id, js_value)) {
+                log_js_error(&ctx, e, &format!("resolving callback {}", id));
+            }
+
+            // IMPORTANT: Run pending jobs to process Promise continuations
+            let job_count = run_pending_jobs_checked(&ctx, &format!("resolve_callback {}", id));
+            tracing::info!(
+                "resolve_callback: executed {} pending jobs for callback_id={}",
+                job_count,
+                id
+            );
+        });
+    }
+
+    /// Reject a pending async callback with an error (called from Rust when async op fails)
+    pub fn reject_callback(&mut self, callback_id: fresh_core::api::JsCallbackId, error: &str) {
+        let id = callback_id.as_u64();
+
+        // Find the plugin name and then context for this callback
+        let plugin_name = {
+            let mut contexts = self.callback_contexts.borrow_mut();
+            contexts.remove(&id)
+        };
+
+        let Some(name) = plugin_name else {
+            tracing::warn!("reject_callback: No plugin found for callback_id={}", id);
+            return;
+        };
+
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(&name) else {
+            tracing::warn!("reject_callback: Context lost for plugin {}", name);
+            return;
+        };
+
+        context.with(|ctx| {
+            // Get _rejectCallback function from globalThis
+            let globals = ctx.globals();
+            let reject_fn: rquickjs::Function = match globals.get("_rejectCallback") {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::error!(
+                        "reject_callback: _rejectCallback not found for callback_id={}: {:?}",
+                        id,
+                        e
+                    );
+                    return;
+                }
+            };
+
+            // Call the function with callback_id (as u64) and error string
+            if let Err(e) = reject_fn.call::<_, ()>((id, error)) {
+                log_js_error(&ctx, e, &format!("rejecting callback {}", id));
+            }
+
+            // IMPORTANT: Run pending jobs to process Promise continuations
+            run_pending_jobs_checked(&ctx, &format!("reject_callback {}", id));
+        });
+    }
+
+    /// Pass a line of a background process's output to the `onStdout` /
+    /// `onStderr` listeners of the plugin that subscribed to it
+    pub fn deliver_process_output(
+        &mut self,
+        plugin_name: &str,
+        process_id: u64,
+        is_stderr: bool,
+        line: &str,
+    ) {
+        let plugin_contexts = self.plugin_contexts.borrow();
+        let Some(context) = plugin_contexts.get(plugin_name) else {
+            tracing::debug!(
+                "deliver_process_output: plugin '{}' is not loaded",
+                plugin_name
+            );
+            return;
+        };
+
+        let stream = if is_stderr { "stderr" } else { "stdout" };
+        context.with(|ctx| {
+            let dispatch: rquickjs::Function = match ctx.globals().get("_dispatchProcessOutput") {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::error!("deliver_process_output: dispatcher not found: {:?}", e);
+                    return;
+                }
+            };
+            if let Err(e) = dispatch.call::<_, ()>((process_id, stream, line)) {
+                log_js_error(&ctx, e, &format!("{} of process {}", stream, process_id));
+            }
+            run_pending_jobs_checked(&ctx, &format!("process {} output", process_id));
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fresh_core::api::{BufferEdit, BufferInfo, CursorInfo, SelectionSpec};
+    use std::sync::mpsc;
+
+    /// Helper to create a backend with a command receiver for testing
+    fn create_test_backend() -> (QuickJsBackend, mpsc::Receiver<PluginCommand>) {
+        let (tx, rx) = mpsc::channel();
+        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
+        let services = Arc::new(TestServiceBridge::new());
+        let backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
+        (backend, rx)
+    }
+
+    struct TestServiceBridge {
+        en_strings: std::sync::Mutex<HashMap<String, String>>,
+    }
+
+    impl TestServiceBridge {
+        fn new() -> Self {
+            Self {
+                en_strings: std::sync::Mutex::new(HashMap::new()),
+            }
+        }
+    }
+
+    impl fresh_core::services::PluginServiceBridge for TestServiceBridge {
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+        fn translate(
+            &self,
+            _plugin_name: &str,
+            key: &str,
+            _args: &HashMap<String, String>,
+        ) -> String {
+            self.en_strings
+                .lock()
+                .unwrap()
+                .get(key)
+                .cloned()
+                .unwrap_or_else(|| key.to_string())
+        }
+        fn current_locale(&self) -> String {
+            "en".to_string()
+        }
+        fn set_js_execution_state(&self, _state: String) {}
+        fn clear_js_execution_state(&self) {}
+        fn get_theme_schema(&self) -> serde_json::Value {
+            serde_json::json!({})
+        }
+        fn get_builtin_themes(&self) -> serde_json::Value {
+            serde_json::json!([])
+        }
+        fn generate_theme(
+            &self,
+            _seed: &fresh_core::api::ThemeSeed,
+        ) -> Result<serde_json::Value, String> {
+            Err("not supported in tests".to_string())
+        }
+        fn register_command(&self, _command: fresh_core::command::Command) {}
+        fn unregister_command(&self, _name: &str) {}
+        fn unregister_commands_by_prefix(&self, _prefix: &str) {}
+        fn unregister_commands_by_plugin(&self, _plugin_name: &str) {}
+        fn plugins_dir(&self) -> std::path::PathBuf {
+            std::path::PathBuf::from("/tmp/plugins")
+        }
+        fn config_dir(&self) -> std::path::PathBuf {
+            std::path::PathBuf::from("/tmp/config")
+        }
+    }
+
+    #[test]
+    fn test_quickjs_backend_creation() {
+        let backend = QuickJsBackend::new();
+        assert!(backend.is_ok());
+    }
+
+    #[test]
+    fn test_execute_simple_js() {
+        let mut backend = QuickJsBackend::new().unwrap();
+        let result = backend.execute_js("const x = 1 + 2;", "test.js");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_event_handler_registration() {
+        let backend = QuickJsBackend::new().unwrap();
+
+        // Initially no handlers
+        assert!(!backend.has_handlers("test_event"));
+
+        // Register a handler
+        backend
+            .event_handlers
+            .borrow_mut()
+            .entry("test_event".to_string())
+            .or_default()
+            .push(PluginHandler {
+                plugin_name: "test".to_string(),
+                handler_name: "testHandler".to_string(),
+            });
+
+        // Now has handlers
+        assert!(backend.has_handlers("test_event"));
+    }
+
+    // ==================== API Tests ====================
+
+    #[test]
+    fn test_api_set_status() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setStatus("Hello from test");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetStatus { message } => {
+                assert_eq!(message, "Hello from test");
+            }
+            _ => panic!("Expected SetStatus command, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_register_command() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.myTestHandler = function() { };
+            editor.registerCommand("Test Command", "A test command", "myTestHandler", null);
+        "#,
+                "test_plugin.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::RegisterCommand { command } => {
+                assert_eq!(command.name, "Test Command");
+                assert_eq!(command.description, "A test command");
+                // Check that plugin_name contains the plugin name (derived from filename)
+                assert_eq!(command.plugin_name, "test_plugin");
+            }
+            _ => panic!("Expected RegisterCommand, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_define_mode() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.defineMode("test-mode", null, [
+                ["a", "action_a"],
+                ["b", "action_b"]
+            ]);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::DefineMode {
+                name,
+                parent,
+                bindings,
+                read_only,
+            } => {
+                assert_eq!(name, "test-mode");
+                assert!(parent.is_none());
+                assert_eq!(bindings.len(), 2);
+                assert_eq!(bindings[0], ("a".to_string(), "action_a".to_string()));
+                assert_eq!(bindings[1], ("b".to_string(), "action_b".to_string()));
+                assert!(!read_only);
+            }
+            _ => panic!("Expected DefineMode, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_set_editor_mode() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setEditorMode("vi-normal");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetEditorMode { mode } => {
+                assert_eq!(mode, Some("vi-normal".to_string()));
+            }
+            _ => panic!("Expected SetEditorMode, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_clear_editor_mode() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setEditorMode(null);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetEditorMode { mode } => {
+                assert!(mode.is_none());
+            }
+            _ => panic!("Expected SetEditorMode with None, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_insert_at_cursor() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.insertAtCursor("Hello, World!");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::InsertAtCursor { text } => {
+                assert_eq!(text, "Hello, World!");
+            }
+            _ => panic!("Expected InsertAtCursor, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_set_context() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setContext("myContext", true);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetContext { name, active } => {
+                assert_eq!(name, "myContext");
+                assert!(active);
+            }
+            _ => panic!("Expected SetContext, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_context_menu_items() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.addContextMenuItem("editor", "Explain", "explain_selection", "has_selection");
+            editor.addContextMenuItem("tab", "Pin", "pin_tab");
+            editor.removeContextMenuItem("tab", "Pin");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::AddContextMenuItem { item } => {
+                assert_eq!(item.plugin_name, "test");
+                assert_eq!(item.menu, "editor");
+                assert_eq!(item.label, "Explain");
+                assert_eq!(item.action, "explain_selection");
+                assert_eq!(item.when.as_deref(), Some("has_selection"));
+            }
+            cmd => panic!("Expected AddContextMenuItem, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::AddContextMenuItem { item } => assert_eq!(item.when, None),
+            cmd => panic!("Expected AddContextMenuItem, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::RemoveContextMenuItem {
+                plugin_name,
+                menu,
+                label,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(menu, "tab");
+                assert_eq!(label, "Pin");
+            }
+            cmd => panic!("Expected RemoveContextMenuItem, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_menu_items() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.addMenuItem("Tools/Git", { label: "Blame", command: "git_blame", when: "has_file" });
+            editor.addMenuItem("Tools", { label: "Count Words", command: "count_words" });
+            editor.removeMenuItem("Tools", "Count Words");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::AddPluginMenuItem { item } => {
+                assert_eq!(item.plugin_name, "test");
+                assert_eq!(item.path, "Tools/Git");
+                assert_eq!(item.label, "Blame");
+                assert_eq!(item.command, "git_blame");
+                assert_eq!(item.when.as_deref(), Some("has_file"));
+            }
+            cmd => panic!("Expected AddPluginMenuItem, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::AddPluginMenuItem { item } => assert_eq!(item.when, None),
+            cmd => panic!("Expected AddPluginMenuItem, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::RemovePluginMenuItem {
+                plugin_name,
+                path,
+                label,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(path, "Tools");
+                assert_eq!(label, "Count Words");
+            }
+            cmd => panic!("Expected RemovePluginMenuItem, got {:?}", cmd),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_action_sync_function() {
+        let (mut backend, rx) = create_test_backend();
+
+        // Register the action explicitly so it knows to look in "test" plugin
+        backend.registered_actions.borrow_mut().insert(
+            "my_sync_action".to_string(),
+            PluginHandler {
+                plugin_name: "test".to_string(),
+                handler_name: "my_sync_action".to_string(),
+            },
+        );
+
+        // Define a sync function and register it
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.my_sync_action = function() {
+                editor.setStatus("sync action executed");
+            };
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Drain any setup commands
+        while rx.try_recv().is_ok() {}
+
+        // Execute the action
+        backend.execute_action("my_sync_action").await.unwrap();
+
+        // Check the command was sent
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetStatus { message } => {
+                assert_eq!(message, "sync action executed");
+            }
+            _ => panic!("Expected SetStatus from action, got {:?}", cmd),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_action_async_function() {
+        let (mut backend, rx) = create_test_backend();
+
+        // Register the action explicitly
+        backend.registered_actions.borrow_mut().insert(
+            "my_async_action".to_string(),
+            PluginHandler {
+                plugin_name: "test".to_string(),
+                handler_name: "my_async_action".to_string(),
+            },
+        );
+
+        // Define an async function
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.my_async_action = async function() {
+                await Promise.resolve();
+                editor.setStatus("async action executed");
+            };
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Drain any setup commands
+        while rx.try_recv().is_ok() {}
+
+        // Execute the action
+        backend.execute_action("my_async_action").await.unwrap();
+
+        // Check the command was sent (async should complete)
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetStatus { message } => {
+                assert_eq!(message, "async action executed");
+            }
+            _ => panic!("Expected SetStatus from async action, got {:?}", cmd),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_execute_action_with_registered_handler() {
+        let (mut backend, rx) = create_test_backend();
+
+        // Register an action with a different handler name
+        backend.registered_actions.borrow_mut().insert(
+            "my_action".to_string(),
+            PluginHandler {
+                plugin_name: "test".to_string(),
+                handler_name: "actual_handler_function".to_string(),
+            },
+        );
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.actual_handler_function = function() {
+                editor.setStatus("handler executed");
+            };
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Drain any setup commands
+        while rx.try_recv().is_ok() {}
+
+        // Execute the action by name (should resolve to handler)
+        backend.execute_action("my_action").await.unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetStatus { message } => {
+                assert_eq!(message, "handler executed");
+            }
+            _ => panic!("Expected SetStatus, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_on_event_registration() {
+        let (mut backend, _rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.myEventHandler = function() { };
+            editor.on("bufferSave", "myEventHandler");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        assert!(backend.has_handlers("bufferSave"));
+    }
+
+    #[test]
+    fn test_api_off_event_unregistration() {
+        let (mut backend, _rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.myEventHandler = function() { };
+            editor.on("bufferSave", "myEventHandler");
+            editor.off("bufferSave", "myEventHandler");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Handler should be removed
+        assert!(!backend.has_handlers("bufferSave"));
+    }
+
+    #[tokio::test]
+    async fn test_emit_event_to_function_handler() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            // Kept on globalThis only so the second script can remove it
+            globalThis.savedHandler = function(data) {
+                editor.setStatus("saved: " + data.path);
+            };
+            editor.on("after_file_save", savedHandler);
+            editor.on("after_file_save", function() {
+                editor.setStatus("second handler");
+            });
+            editor.off("after_file_save", function() {});
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        while rx.try_recv().is_ok() {}
+
+        let event_data: serde_json::Value = serde_json::json!({"path": "/test.txt"});
+        backend.emit("after_file_save", &event_data).await.unwrap();
+
+        let messages: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages, vec!["saved: /test.txt", "second handler"]);
+
+        // Removing a registered function unsubscribes it
+        backend
+            .execute_js(r#"editor.off("after_file_save", savedHandler);"#, "test.js")
+            .unwrap();
+        while rx.try_recv().is_ok() {}
+        backend.emit("after_file_save", &event_data).await.unwrap();
+        let messages: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages, vec!["second handler"]);
+    }
+
+    #[test]
+    fn test_completion_provider_round_trip() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.provideWords = async function(request) {
+                return [
+                    { label: request.prefix + "_one", kind: "keyword" },
+                    { label: "two", insertText: "two()", detail: request.language },
+                ];
+            };
+            editor.registerCompletionProvider(["rust"], ["."], "provideWords");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let provider = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::RegisterCompletionProvider { provider } => Some(provider),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(provider.plugin_name, "test");
+        assert_eq!(provider.languages, vec!["rust"]);
+        assert_eq!(provider.trigger_characters, vec!["."]);
+        assert!(provider.handles_language("rust"));
+        assert!(!provider.handles_language("python"));
+
+        let request = CompletionRequest {
+            buffer_id: 1,
+            path: Some("/src/main.rs".to_string()),
+            language: "rust".to_string(),
+            position: 10,
+            prefix: "fo".to_string(),
         };
+        backend
+            .request_completions("test", "provideWords", 7, &request)
+            .unwrap();
 
-        context.with(|ctx| {
-            // Get _rejectCallback function from globalThis
-            let globals = ctx.globals();
-            let reject_fn: rquickjs::Function = match globals.get("_rejectCallback") {
-                Ok(f) => f,
-                Err(e) => {
-                    tracing::error!(
-                        "reject_callback: _rejectCallback not found for callback_id={}: {:?}",
-                        id,
-                        e
-                    );
-                    return;
+        let (request_id, items) = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::ProvideCompletions { request_id, items } => {
+                    Some((request_id, items))
                 }
-            };
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(request_id, 7);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].label, "fo_one");
+        assert_eq!(items[0].kind.as_deref(), Some("keyword"));
+        assert_eq!(items[1].insert_text.as_deref(), Some("two()"));
+        assert_eq!(items[1].detail.as_deref(), Some("rust"));
+    }
 
-            // Call the function with callback_id (as u64) and error string
-            if let Err(e) = reject_fn.call::<_, ()>((id, error)) {
-                log_js_error(&ctx, e, &format!("rejecting callback {}", id));
-            }
+    #[test]
+    fn test_hover_provider_round_trip() {
+        let (mut backend, rx) = create_test_backend();
 
-            // IMPORTANT: Run pending jobs to process Promise continuations
-            run_pending_jobs_checked(&ctx, &format!("reject_callback {}", id));
-        });
-    }
-}
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.provideDocs = async function(request) {
+                if (request.position === 0) {
+                    return null;
+                }
+                return "**" + request.language + "** at " + request.position;
+            };
+            editor.registerHoverProvider(["rust"], "provideDocs");
+        "#,
+                "test.js",
+            )
+            .unwrap();
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use fresh_core::api::{BufferInfo, CursorInfo};
-    use std::sync::mpsc;
+        let provider = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::RegisterHoverProvider { provider } => Some(provider),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(provider.plugin_name, "test");
+        assert_eq!(provider.languages, vec!["rust"]);
+        assert!(provider.handles_language("rust"));
+        assert!(!provider.handles_language("python"));
+
+        let mut request = HoverRequest {
+            buffer_id: 1,
+            path: Some("/src/main.rs".to_string()),
+            language: "rust".to_string(),
+            position: 10,
+        };
+        backend
+            .request_hover("test", "provideDocs", 3, &request)
+            .unwrap();
+        request.position = 0;
+        backend
+            .request_hover("test", "provideDocs", 4, &request)
+            .unwrap();
 
-    /// Helper to create a backend with a command receiver for testing
-    fn create_test_backend() -> (QuickJsBackend, mpsc::Receiver<PluginCommand>) {
-        let (tx, rx) = mpsc::channel();
-        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
-        let services = Arc::new(TestServiceBridge::new());
-        let backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
-        (backend, rx)
+        let responses: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::ProvideHover {
+                    request_id,
+                    markdown,
+                } => Some((request_id, markdown)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            responses,
+            vec![(3, Some("**rust** at 10".to_string())), (4, None)]
+        );
     }
 
-    struct TestServiceBridge {
-        en_strings: std::sync::Mutex<HashMap<String, String>>,
-    }
+    #[test]
+    fn test_formatter_round_trip() {
+        let (mut backend, rx) = create_test_backend();
 
-    impl TestServiceBridge {
-        fn new() -> Self {
-            Self {
-                en_strings: std::sync::Mutex::new(HashMap::new()),
-            }
-        }
-    }
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.formatUpper = async function(request) {
+                if (request.text === "") {
+                    throw new Error("nothing to format");
+                }
+                return request.text.toUpperCase();
+            };
+            editor.registerFormatter("markdown", "formatUpper");
+        "#,
+                "test.js",
+            )
+            .unwrap();
 
-    impl fresh_core::services::PluginServiceBridge for TestServiceBridge {
-        fn as_any(&self) -> &dyn std::any::Any {
-            self
-        }
-        fn translate(
-            &self,
-            _plugin_name: &str,
-            key: &str,
-            _args: &HashMap<String, String>,
-        ) -> String {
-            self.en_strings
-                .lock()
-                .unwrap()
-                .get(key)
-                .cloned()
-                .unwrap_or_else(|| key.to_string())
-        }
-        fn current_locale(&self) -> String {
-            "en".to_string()
-        }
-        fn set_js_execution_state(&self, _state: String) {}
-        fn clear_js_execution_state(&self) {}
-        fn get_theme_schema(&self) -> serde_json::Value {
-            serde_json::json!({})
-        }
-        fn get_builtin_themes(&self) -> serde_json::Value {
-            serde_json::json!([])
-        }
-        fn register_command(&self, _command: fresh_core::command::Command) {}
-        fn unregister_command(&self, _name: &str) {}
-        fn unregister_commands_by_prefix(&self, _prefix: &str) {}
-        fn unregister_commands_by_plugin(&self, _plugin_name: &str) {}
-        fn plugins_dir(&self) -> std::path::PathBuf {
-            std::path::PathBuf::from("/tmp/plugins")
-        }
-        fn config_dir(&self) -> std::path::PathBuf {
-            std::path::PathBuf::from("/tmp/config")
-        }
-    }
+        let formatter = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::RegisterFormatter { formatter } => Some(formatter),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(formatter.plugin_name, "test");
+        assert_eq!(formatter.language, "markdown");
+        assert_eq!(formatter.handler_name, "formatUpper");
+
+        let mut request = FormatRequest {
+            buffer_id: 1,
+            path: Some("/notes.md".to_string()),
+            language: "markdown".to_string(),
+            text: "# title".to_string(),
+        };
+        backend
+            .request_format("test", "formatUpper", 5, &request)
+            .unwrap();
+        request.text = String::new();
+        backend
+            .request_format("test", "formatUpper", 6, &request)
+            .unwrap();
 
-    #[test]
-    fn test_quickjs_backend_creation() {
-        let backend = QuickJsBackend::new();
-        assert!(backend.is_ok());
+        let responses: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::ProvideFormattedText { request_id, text } => {
+                    Some((request_id, text))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(responses, vec![(5, Some("# TITLE".to_string())), (6, None)]);
     }
 
     #[test]
-    fn test_execute_simple_js() {
-        let mut backend = QuickJsBackend::new().unwrap();
-        let result = backend.execute_js("const x = 1 + 2;", "test.js");
-        assert!(result.is_ok());
-    }
+    fn test_evaluator_round_trip() {
+        let (mut backend, rx) = create_test_backend();
 
-    #[test]
-    fn test_event_handler_registration() {
-        let backend = QuickJsBackend::new().unwrap();
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.evalPython = async function(request) {
+                if (request.expression === "boom") {
+                    throw new Error("boom");
+                }
+                if (request.expression.startsWith("len(")) {
+                    return request.expression.length - 7;
+                }
+                return null;
+            };
+            editor.registerEvaluator("python", "evalPython");
+        "#,
+                "test.js",
+            )
+            .unwrap();
 
-        // Initially no handlers
-        assert!(!backend.has_handlers("test_event"));
+        let evaluator = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::RegisterEvaluator { evaluator } => Some(evaluator),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(evaluator.plugin_name, "test");
+        assert_eq!(evaluator.language, "python");
 
-        // Register a handler
+        let requests: Vec<_> = ["len('abc')", "1 + 2", "boom"]
+            .iter()
+            .map(|expression| EvaluateRequest {
+                buffer_id: 1,
+                path: None,
+                language: "python".to_string(),
+                expression: expression.to_string(),
+            })
+            .collect();
         backend
-            .event_handlers
-            .borrow_mut()
-            .entry("test_event".to_string())
-            .or_default()
-            .push(PluginHandler {
-                plugin_name: "test".to_string(),
-                handler_name: "testHandler".to_string(),
-            });
+            .request_evaluation("test", "evalPython", 7, &requests)
+            .unwrap();
 
-        // Now has handlers
-        assert!(backend.has_handlers("test_event"));
+        let responses: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::ProvideEvaluation {
+                    request_id,
+                    results,
+                } => Some((request_id, results)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            responses,
+            vec![(7, vec![Some("3".to_string()), None, None])]
+        );
     }
 
-    // ==================== API Tests ====================
-
     #[test]
-    fn test_api_set_status() {
+    fn test_inline_suggestion_provider_round_trip() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.setStatus("Hello from test");
+            globalThis.suggestClosing = async function(request) {
+                if (!request.textBefore.endsWith("(")) {
+                    return null;
+                }
+                return ")" + request.textAfter.length;
+            };
+            editor.registerInlineSuggestionProvider([], "suggestClosing");
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert_eq!(message, "Hello from test");
-            }
-            _ => panic!("Expected SetStatus command, got {:?}", cmd),
-        }
+        let provider = std::iter::from_fn(|| rx.try_recv().ok())
+            .find_map(|cmd| match cmd {
+                PluginCommand::RegisterInlineSuggestionProvider { provider } => Some(provider),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(provider.plugin_name, "test");
+        assert!(provider.handles_language("python"));
+
+        let mut request = InlineSuggestionRequest {
+            buffer_id: 1,
+            path: None,
+            language: "rust".to_string(),
+            position: 4,
+            text_before: "foo(".to_string(),
+            text_after: "\n".to_string(),
+        };
+        backend
+            .request_inline_suggestion("test", "suggestClosing", 5, &request)
+            .unwrap();
+        request.text_before = "foo".to_string();
+        backend
+            .request_inline_suggestion("test", "suggestClosing", 6, &request)
+            .unwrap();
+
+        let responses: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|cmd| match cmd {
+                PluginCommand::ProvideInlineSuggestion { request_id, text } => {
+                    Some((request_id, text))
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(responses, vec![(5, Some(")1".to_string())), (6, None)]);
     }
 
-    #[test]
-    fn test_api_register_command() {
+    #[tokio::test]
+    async fn test_emit_event() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.myTestHandler = function() { };
-            editor.registerCommand("Test Command", "A test command", "myTestHandler", null);
+            globalThis.onSaveHandler = function(data) {
+                editor.setStatus("saved: " + JSON.stringify(data));
+            };
+            editor.on("bufferSave", "onSaveHandler");
         "#,
-                "test_plugin.js",
+                "test.js",
             )
             .unwrap();
 
+        // Drain setup commands
+        while rx.try_recv().is_ok() {}
+
+        // Emit the event
+        let event_data: serde_json::Value = serde_json::json!({"path": "/test.txt"});
+        backend.emit("bufferSave", &event_data).await.unwrap();
+
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::RegisterCommand { command } => {
-                assert_eq!(command.name, "Test Command");
-                assert_eq!(command.description, "A test command");
-                // Check that plugin_name contains the plugin name (derived from filename)
-                assert_eq!(command.plugin_name, "test_plugin");
+            PluginCommand::SetStatus { message } => {
+                assert!(message.contains("/test.txt"));
             }
-            _ => panic!("Expected RegisterCommand, got {:?}", cmd),
+            _ => panic!("Expected SetStatus from event handler, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_define_mode() {
+    fn test_api_copy_to_clipboard() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.defineMode("test-mode", null, [
-                ["a", "action_a"],
-                ["b", "action_b"]
-            ]);
+            editor.copyToClipboard("clipboard text");
         "#,
                 "test.js",
             )
@@ -3570,32 +7023,23 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::DefineMode {
-                name,
-                parent,
-                bindings,
-                read_only,
-            } => {
-                assert_eq!(name, "test-mode");
-                assert!(parent.is_none());
-                assert_eq!(bindings.len(), 2);
-                assert_eq!(bindings[0], ("a".to_string(), "action_a".to_string()));
-                assert_eq!(bindings[1], ("b".to_string(), "action_b".to_string()));
-                assert!(!read_only);
+            PluginCommand::SetClipboard { text } => {
+                assert_eq!(text, "clipboard text");
             }
-            _ => panic!("Expected DefineMode, got {:?}", cmd),
+            _ => panic!("Expected SetClipboard, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_set_editor_mode() {
+    fn test_api_open_file() {
         let (mut backend, rx) = create_test_backend();
 
+        // openFile takes (path, line?, column?)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.setEditorMode("vi-normal");
+            editor.openFile("/path/to/file.txt", null, null);
         "#,
                 "test.js",
             )
@@ -3603,22 +7047,25 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetEditorMode { mode } => {
-                assert_eq!(mode, Some("vi-normal".to_string()));
+            PluginCommand::OpenFileAtLocation { path, line, column } => {
+                assert_eq!(path.to_str().unwrap(), "/path/to/file.txt");
+                assert!(line.is_none());
+                assert!(column.is_none());
             }
-            _ => panic!("Expected SetEditorMode, got {:?}", cmd),
+            _ => panic!("Expected OpenFileAtLocation, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_clear_editor_mode() {
+    fn test_api_delete_range() {
         let (mut backend, rx) = create_test_backend();
 
+        // deleteRange takes (buffer_id, start, end)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.setEditorMode(null);
+            editor.deleteRange(0, 10, 20);
         "#,
                 "test.js",
             )
@@ -3626,22 +7073,24 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetEditorMode { mode } => {
-                assert!(mode.is_none());
+            PluginCommand::DeleteRange { range, .. } => {
+                assert_eq!(range.start, 10);
+                assert_eq!(range.end, 20);
             }
-            _ => panic!("Expected SetEditorMode with None, got {:?}", cmd),
+            _ => panic!("Expected DeleteRange, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_insert_at_cursor() {
+    fn test_api_insert_text() {
         let (mut backend, rx) = create_test_backend();
 
+        // insertText takes (buffer_id, position, text)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.insertAtCursor("Hello, World!");
+            editor.insertText(0, 5, "inserted");
         "#,
                 "test.js",
             )
@@ -3649,342 +7098,610 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::InsertAtCursor { text } => {
-                assert_eq!(text, "Hello, World!");
+            PluginCommand::InsertText { position, text, .. } => {
+                assert_eq!(position, 5);
+                assert_eq!(text, "inserted");
             }
-            _ => panic!("Expected InsertAtCursor, got {:?}", cmd),
+            _ => panic!("Expected InsertText, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_set_context() {
+    fn test_api_namespaced_methods() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.setContext("myContext", true);
+            editor.buffers.insertText(0, 5, "inserted");
+            try {
+                editor.buffers.insertText(0);
+            } catch (e) {
+                editor.setStatus(e.name);
+            }
+            editor.setStatus(String(editor.fs.fileExists("/definitely/not/here")));
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetContext { name, active } => {
-                assert_eq!(name, "myContext");
-                assert!(active);
-            }
-            _ => panic!("Expected SetContext, got {:?}", cmd),
-        }
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        assert!(matches!(
+            &commands[0],
+            PluginCommand::InsertText { position: 5, text, .. } if text == "inserted"
+        ));
+        let messages: Vec<&str> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        // Namespaced calls go through the same argument validation
+        assert_eq!(messages, vec!["TypeError", "false"]);
     }
 
-    #[tokio::test]
-    async fn test_execute_action_sync_function() {
+    #[test]
+    fn test_api_wrong_arguments_throw_type_error() {
         let (mut backend, rx) = create_test_backend();
 
-        // Register the action explicitly so it knows to look in "test" plugin
-        backend.registered_actions.borrow_mut().insert(
-            "my_sync_action".to_string(),
-            PluginHandler {
-                plugin_name: "test".to_string(),
-                handler_name: "my_sync_action".to_string(),
-            },
-        );
-
-        // Define a sync function and register it
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.my_sync_action = function() {
-                editor.setStatus("sync action executed");
-            };
+            for (const call of [() => editor.insertText("abc"), () => editor.insertText(0)]) {
+                try {
+                    call();
+                } catch (e) {
+                    editor.setStatus(e.name + ": " + e.message);
+                }
+            }
         "#,
                 "test.js",
             )
             .unwrap();
 
-        // Drain any setup commands
-        while rx.try_recv().is_ok() {}
+        let messages: Vec<String> = rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "TypeError: editor.insertText(bufferId: number, position: number, text: string, baseVersion?: number): argument `bufferId` must be number, got string",
+                "TypeError: editor.insertText(bufferId: number, position: number, text: string, baseVersion?: number): missing argument `position`",
+            ]
+        );
+    }
 
-        // Execute the action
-        backend.execute_action("my_sync_action").await.unwrap();
+    #[test]
+    fn test_api_disabled_capability_throws() {
+        let (mut backend, rx) = create_test_backend();
+        let mut capabilities = CapabilitySet::all();
+        capabilities.remove(crate::backend::Capability::Process);
+        backend.set_plugin_capabilities("sandboxed", capabilities);
 
-        // Check the command was sent
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert_eq!(message, "sync action executed");
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            try {
+                editor.spawnProcess("ls", []);
+            } catch (e) {
+                editor.setStatus(e.message);
+                editor.setStatus(e instanceof PermissionDeniedError ? e.code : "untyped");
             }
-            _ => panic!("Expected SetStatus from action, got {:?}", cmd),
-        }
+            editor.setStatus(typeof editor.quickPick([]).then);
+        "#,
+                "sandboxed.js",
+            )
+            .unwrap();
+
+        let messages: Vec<String> = rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "editor.spawnProcess is not available: the \"process\" capability is disabled for sandboxed",
+                "PERMISSION_DENIED",
+                "function",
+            ]
+        );
     }
 
-    #[tokio::test]
-    async fn test_execute_action_async_function() {
+    #[test]
+    fn test_api_manifest_permissions_need_consent() {
         let (mut backend, rx) = create_test_backend();
-
-        // Register the action explicitly
-        backend.registered_actions.borrow_mut().insert(
-            "my_async_action".to_string(),
-            PluginHandler {
-                plugin_name: "test".to_string(),
-                handler_name: "my_async_action".to_string(),
-            },
+        backend.set_plugin_manifest(
+            "manifested",
+            Some(
+                crate::backend::capabilities::parse_manifest(r#"{"capabilities": ["env"]}"#)
+                    .unwrap(),
+            ),
         );
 
-        // Define an async function
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.my_async_action = async function() {
-                await Promise.resolve();
-                editor.setStatus("async action executed");
-            };
+            for (const call of [() => editor.readFile("/etc/hosts"), () => editor.getEnv("HOME")]) {
+                try {
+                    call();
+                } catch (e) {
+                    editor.setStatus(e.message);
+                }
+            }
+            editor.setStatus(typeof editor.getCwd());
         "#,
-                "test.js",
+                "manifested.js",
             )
             .unwrap();
 
-        // Drain any setup commands
-        while rx.try_recv().is_ok() {}
-
-        // Execute the action
-        backend.execute_action("my_async_action").await.unwrap();
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        let messages: Vec<&str> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "editor.readFile is not available: the \"fs-read\" capability is not declared in the plugin.json of manifested",
+                "editor.getEnv is not available: the \"env\" permission needs the user's consent, which manifested has asked for",
+                "string",
+            ]
+        );
+        assert!(commands.iter().any(|cmd| matches!(
+            cmd,
+            PluginCommand::RequestPluginPermission { plugin_name, capability }
+                if plugin_name == "manifested" && capability == "env"
+        )));
 
-        // Check the command was sent (async should complete)
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert_eq!(message, "async action executed");
-            }
-            _ => panic!("Expected SetStatus from async action, got {:?}", cmd),
-        }
+        backend.grant_permission("manifested", crate::backend::Capability::Env);
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.setStatus(typeof editor.getEnv("PATH"));
+        "#,
+                "manifested.js",
+            )
+            .unwrap();
+        let messages: Vec<String> = rx
+            .try_iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(messages, vec!["string"]);
     }
 
-    #[tokio::test]
-    async fn test_execute_action_with_registered_handler() {
+    #[test]
+    fn test_api_capability_checks_cannot_be_bypassed() {
         let (mut backend, rx) = create_test_backend();
-
-        // Register an action with a different handler name
-        backend.registered_actions.borrow_mut().insert(
-            "my_action".to_string(),
-            PluginHandler {
-                plugin_name: "test".to_string(),
-                handler_name: "actual_handler_function".to_string(),
-            },
+        backend.set_plugin_manifest(
+            "sneaky",
+            Some([crate::backend::Capability::Env].into_iter().collect()),
         );
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.actual_handler_function = function() {
-                editor.setStatus("handler executed");
-            };
+            const attempts = [
+                () => Object.getPrototypeOf(editor).readFile.call(editor, "/etc/hosts"),
+                () => Object.getPrototypeOf(editor).getEnv.call(editor, "HOME"),
+                () => globalThis._pendingCapabilities.env[0][1].call(editor, "HOME"),
+                () => {
+                    globalThis._grantCapability("env");
+                    return editor.getEnv("HOME");
+                },
+                () => editor._requestPermission("fs-read"),
+            ];
+            for (const attempt of attempts) {
+                try {
+                    attempt();
+                    editor.setStatus("allowed");
+                } catch (e) {
+                    editor.setStatus(e instanceof PermissionDeniedError ? e.code : e.name);
+                }
+            }
         "#,
-                "test.js",
+                "sneaky.js",
             )
             .unwrap();
 
-        // Drain any setup commands
-        while rx.try_recv().is_ok() {}
-
-        // Execute the action by name (should resolve to handler)
-        backend.execute_action("my_action").await.unwrap();
-
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert_eq!(message, "handler executed");
-            }
-            _ => panic!("Expected SetStatus, got {:?}", cmd),
-        }
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        let messages: Vec<&str> = commands
+            .iter()
+            .filter_map(|cmd| match cmd {
+                PluginCommand::SetStatus { message } => Some(message.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(
+            messages,
+            vec![
+                "PERMISSION_DENIED",
+                "PERMISSION_DENIED",
+                "TypeError",
+                "TypeError",
+                "TypeError",
+            ]
+        );
+        assert!(!commands.iter().any(|cmd| matches!(
+            cmd,
+            PluginCommand::RequestPluginPermission { capability, .. } if capability == "fs-read"
+        )));
     }
 
     #[test]
-    fn test_api_on_event_registration() {
-        let (mut backend, _rx) = create_test_backend();
+    fn test_api_granted_permissions_are_available() {
+        let (mut backend, rx) = create_test_backend();
+        backend.set_plugin_manifest(
+            "trusted",
+            Some([crate::backend::Capability::Env].into_iter().collect()),
+        );
+        backend.set_granted_permissions(
+            "trusted",
+            [crate::backend::Capability::Env].into_iter().collect(),
+        );
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.myEventHandler = function() { };
-            editor.on("bufferSave", "myEventHandler");
+            editor.setStatus(typeof editor.getEnv("PATH"));
         "#,
-                "test.js",
+                "trusted.js",
             )
             .unwrap();
 
-        assert!(backend.has_handlers("bufferSave"));
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        assert!(!commands
+            .iter()
+            .any(|cmd| matches!(cmd, PluginCommand::RequestPluginPermission { .. })));
+        assert!(commands
+            .iter()
+            .any(|cmd| matches!(cmd, PluginCommand::SetStatus { message } if message == "string")));
     }
 
     #[test]
-    fn test_api_off_event_unregistration() {
-        let (mut backend, _rx) = create_test_backend();
+    fn test_api_set_buffer_cursor() {
+        let (mut backend, rx) = create_test_backend();
 
+        // setBufferCursor takes (buffer_id, position)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.myEventHandler = function() { };
-            editor.on("bufferSave", "myEventHandler");
-            editor.off("bufferSave", "myEventHandler");
+            editor.setBufferCursor(0, 100);
         "#,
                 "test.js",
             )
             .unwrap();
 
-        // Handler should be removed
-        assert!(!backend.has_handlers("bufferSave"));
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetBufferCursor { position, .. } => {
+                assert_eq!(position, 100);
+            }
+            _ => panic!("Expected SetBufferCursor, got {:?}", cmd),
+        }
     }
 
-    #[tokio::test]
-    async fn test_emit_event() {
+    #[test]
+    fn test_api_create_and_delete_marker() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            globalThis.onSaveHandler = function(data) {
-                editor.setStatus("saved: " + JSON.stringify(data));
-            };
-            editor.on("bufferSave", "onSaveHandler");
+            const first = editor.createMarker(3, 10);
+            const second = editor.createMarker(3, 20);
+            if (second === first) throw new Error("marker IDs must be unique");
+            editor.deleteMarker(first);
         "#,
                 "test.js",
             )
             .unwrap();
 
-        // Drain setup commands
-        while rx.try_recv().is_ok() {}
-
-        // Emit the event
-        let event_data: serde_json::Value = serde_json::json!({"path": "/test.txt"});
-        backend.emit("bufferSave", &event_data).await.unwrap();
-
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert!(message.contains("/test.txt"));
+        let first_id = match rx.try_recv().unwrap() {
+            PluginCommand::CreateMarker {
+                buffer_id,
+                marker_id,
+                position,
+                ..
+            } => {
+                assert_eq!(buffer_id, BufferId(3));
+                assert_eq!(position, 10);
+                marker_id
             }
-            _ => panic!("Expected SetStatus from event handler, got {:?}", cmd),
+            cmd => panic!("Expected CreateMarker, got {:?}", cmd),
+        };
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            PluginCommand::CreateMarker { position: 20, .. }
+        ));
+        match rx.try_recv().unwrap() {
+            PluginCommand::DeleteMarker { marker_id } => assert_eq!(marker_id, first_id),
+            cmd => panic!("Expected DeleteMarker, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_copy_to_clipboard() {
+    fn test_api_apply_edits() {
         let (mut backend, rx) = create_test_backend();
 
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.copyToClipboard("clipboard text");
+            editor.applyEdits(1, [
+                { range: { start: 0, end: 3 }, text: "let" },
+                { range: { start: 8, end: 8 }, text: " " },
+            ], 4);
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetClipboard { text } => {
-                assert_eq!(text, "clipboard text");
+        match rx.try_recv().unwrap() {
+            PluginCommand::ApplyEdits {
+                buffer_id,
+                edits,
+                base_version,
+            } => {
+                assert_eq!(buffer_id.0, 1);
+                assert_eq!(
+                    edits,
+                    vec![
+                        BufferEdit {
+                            range: 0..3,
+                            text: "let".to_string()
+                        },
+                        BufferEdit {
+                            range: 8..8,
+                            text: " ".to_string()
+                        },
+                    ]
+                );
+                assert_eq!(base_version, Some(4));
             }
-            _ => panic!("Expected SetClipboard, got {:?}", cmd),
+            cmd => panic!("Expected ApplyEdits, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_open_file() {
+    fn test_api_session_data() {
         let (mut backend, rx) = create_test_backend();
 
-        // openFile takes (path, line?, column?)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.openFile("/path/to/file.txt", null, null);
+            editor.setSessionData("panel", { open: true, items: [1, 2] });
+            editor.setSessionData("panel", undefined);
+            editor.setSessionData("width", 40);
+            globalThis._width = editor.getSessionData("width");
+            globalThis._panel = editor.getSessionData("panel");
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::OpenFileAtLocation { path, line, column } => {
-                assert_eq!(path.to_str().unwrap(), "/path/to/file.txt");
-                assert!(line.is_none());
-                assert!(column.is_none());
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetSessionData {
+                plugin_name,
+                key,
+                value,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(key, "panel");
+                assert_eq!(
+                    value,
+                    Some(serde_json::json!({"open": true, "items": [1, 2]}))
+                );
             }
-            _ => panic!("Expected OpenFileAtLocation, got {:?}", cmd),
+            cmd => panic!("Expected SetSessionData, got {:?}", cmd),
         }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetSessionData { value, .. } => assert_eq!(value, None),
+            cmd => panic!("Expected SetSessionData, got {:?}", cmd),
+        }
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let width: u32 = ctx.globals().get("_width").unwrap();
+                assert_eq!(width, 40);
+                let panel: Value = ctx.globals().get("_panel").unwrap();
+                assert!(panel.is_null());
+            });
     }
 
     #[test]
-    fn test_api_delete_range() {
-        let (mut backend, rx) = create_test_backend();
+    fn test_api_undo_redo() {
+        let (tx, rx) = mpsc::channel();
+        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
+        state_snapshot.write().unwrap().buffers.insert(
+            BufferId(1),
+            BufferInfo {
+                id: BufferId(1),
+                path: None,
+                modified: true,
+                length: 10,
+                version: 3,
+                undo_depth: 3,
+                read_only: false,
+            },
+        );
+        let services = Arc::new(fresh_core::services::NoopServiceBridge);
+        let mut backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
 
-        // deleteRange takes (buffer_id, start, end)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.deleteRange(0, 10, 20);
+            editor.undo(1);
+            editor.redo(1);
+            globalThis._depth = editor.getUndoDepth(1);
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::DeleteRange { range, .. } => {
-                assert_eq!(range.start, 10);
-                assert_eq!(range.end, 20);
-            }
-            _ => panic!("Expected DeleteRange, got {:?}", cmd),
+        match rx.try_recv().unwrap() {
+            PluginCommand::Undo { buffer_id } => assert_eq!(buffer_id.0, 1),
+            cmd => panic!("Expected Undo, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::Redo { buffer_id } => assert_eq!(buffer_id.0, 1),
+            cmd => panic!("Expected Redo, got {:?}", cmd),
         }
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let depth: u32 = ctx.globals().get("_depth").unwrap();
+                assert_eq!(depth, 3);
+            });
     }
 
     #[test]
-    fn test_api_insert_text() {
-        let (mut backend, rx) = create_test_backend();
+    fn test_api_errors_are_typed() {
+        let (tx, rx) = mpsc::channel();
+        let state_snapshot = Arc::new(RwLock::new(EditorStateSnapshot::new()));
+        {
+            let mut snapshot = state_snapshot.write().unwrap();
+            snapshot.next_buffer_id = 4;
+            for (id, read_only) in [(1, false), (3, true)] {
+                snapshot.buffers.insert(
+                    BufferId(id),
+                    BufferInfo {
+                        id: BufferId(id),
+                        path: None,
+                        modified: false,
+                        length: 10,
+                        version: 4,
+                        undo_depth: 1,
+                        read_only,
+                    },
+                );
+            }
+            snapshot.buffer_rebase_slack.insert(BufferId(1), 5);
+            snapshot.active_buffer_id = BufferId(3);
+        }
+        let services = Arc::new(fresh_core::services::NoopServiceBridge);
+        let mut backend = QuickJsBackend::with_state(state_snapshot, tx, services).unwrap();
 
-        // insertText takes (buffer_id, position, text)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.insertText(0, 5, "inserted");
+            const failure = (f) => {
+                try { f(); return "none"; }
+                catch (e) { return `${e.name}:${e.code}:${e instanceof PluginError && e instanceof globalThis[e.name]}`; }
+            };
+            globalThis._errors = [
+                failure(() => editor.insertText(2, 0, "x")),
+                failure(() => editor.deleteRange(1, 5, 2)),
+                failure(() => editor.deleteRange(1, 5, 20, 4)),
+                failure(() => editor.deleteRange(1, 5, 20)),
+                failure(() => editor.undo(3)),
+                failure(() => editor.insertText(3, 0, "x")),
+                // A stale version may reach over text the user deleted since
+                failure(() => editor.deleteRange(1, 5, 12, 3)),
+                failure(() => editor.deleteRange(1, 5, 20, 3)),
+                // A buffer created since the state was read
+                failure(() => editor.insertText(7, 0, "x")),
+                // Text inserted during this call counts before the state shows it
+                failure(() => editor.insertText(1, 10, "abc")),
+                failure(() => editor.insertText(1, 13, "d")),
+                failure(() => editor.insertText(1, 15, "x")),
+                failure(() => editor.beginUndoGroup(2)),
+                failure(() => editor.insertAtCursor("x")),
+            ];
         "#,
                 "test.js",
             )
             .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::InsertText { position, text, .. } => {
-                assert_eq!(position, 5);
-                assert_eq!(text, "inserted");
-            }
-            _ => panic!("Expected InsertText, got {:?}", cmd),
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let errors: Vec<String> = ctx.globals().get("_errors").unwrap();
+                assert_eq!(
+                    errors,
+                    vec![
+                        "InvalidBufferIdError:INVALID_BUFFER_ID:true",
+                        "OutOfRangeError:OUT_OF_RANGE:true",
+                        "OutOfRangeError:OUT_OF_RANGE:true",
+                        "OutOfRangeError:OUT_OF_RANGE:true",
+                        "ReadOnlyBufferError:READ_ONLY_BUFFER:true",
+                        "ReadOnlyBufferError:READ_ONLY_BUFFER:true",
+                        "none",
+                        "OutOfRangeError:OUT_OF_RANGE:true",
+                        "none",
+                        "none",
+                        "none",
+                        "OutOfRangeError:OUT_OF_RANGE:true",
+                        "InvalidBufferIdError:INVALID_BUFFER_ID:true",
+                        "ReadOnlyBufferError:READ_ONLY_BUFFER:true",
+                    ]
+                );
+            });
+
+        // Only the calls that didn't fail were sent
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            PluginCommand::DeleteRange { .. }
+        ));
+        for _ in 0..3 {
+            assert!(matches!(
+                rx.try_recv().unwrap(),
+                PluginCommand::InsertText { .. }
+            ));
         }
+        assert!(rx.try_recv().is_err());
     }
 
     #[test]
-    fn test_api_set_buffer_cursor() {
+    fn test_api_set_selections_and_cursors() {
         let (mut backend, rx) = create_test_backend();
 
-        // setBufferCursor takes (buffer_id, position)
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            editor.setBufferCursor(0, 100);
+            editor.setSelections([{ anchor: 0, head: 5 }, { anchor: 10, head: 10 }]);
+            editor.addCursor(20);
+            editor.clearSecondaryCursors();
         "#,
                 "test.js",
             )
@@ -3992,11 +7709,35 @@ mod tests {
 
         let cmd = rx.try_recv().unwrap();
         match cmd {
-            PluginCommand::SetBufferCursor { position, .. } => {
-                assert_eq!(position, 100);
+            PluginCommand::SetSelections { selections } => {
+                assert_eq!(
+                    selections,
+                    vec![
+                        SelectionSpec { anchor: 0, head: 5 },
+                        SelectionSpec {
+                            anchor: 10,
+                            head: 10
+                        },
+                    ]
+                );
             }
-            _ => panic!("Expected SetBufferCursor, got {:?}", cmd),
+            _ => panic!("Expected SetSelections, got {:?}", cmd),
         }
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            PluginCommand::AddCursor { position: 20 }
+        ));
+        assert!(matches!(
+            rx.try_recv().unwrap(),
+            PluginCommand::ClearSecondaryCursors
+        ));
+
+        // Unknown fields are rejected rather than silently ignored
+        let result = backend.execute_js(
+            r#"getEditor().setSelections([{ anchor: 0, head: 1, extra: 2 }]);"#,
+            "test.js",
+        );
+        assert!(result.is_err());
     }
 
     #[test]
@@ -4086,66 +7827,422 @@ mod tests {
     }
 
     #[test]
-    fn test_typescript_transpilation() {
-        use fresh_parser_js::transpile_typescript;
-
+    fn test_typescript_transpilation() {
+        use fresh_parser_js::transpile_typescript;
+
+        let (mut backend, rx) = create_test_backend();
+
+        // TypeScript code with type annotations
+        let ts_code = r#"
+            const editor = getEditor();
+            function greet(name: string): string {
+                return "Hello, " + name;
+            }
+            editor.setStatus(greet("TypeScript"));
+        "#;
+
+        // Transpile to JavaScript first
+        let js_code = transpile_typescript(ts_code, "test.ts").unwrap();
+
+        // Execute the transpiled JavaScript
+        backend.execute_js(&js_code, "test.js").unwrap();
+
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::SetStatus { message } => {
+                assert_eq!(message, "Hello, TypeScript");
+            }
+            _ => panic!("Expected SetStatus, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_get_buffer_text_sends_command() {
+        let (mut backend, rx) = create_test_backend();
+
+        // Call getBufferText - this returns a Promise and sends the command
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            // Store the promise for later
+            globalThis._textPromise = editor.getBufferText(0, 10, 20);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Verify the GetBufferText command was sent
+        let cmd = rx.try_recv().unwrap();
+        match cmd {
+            PluginCommand::GetBufferText {
+                buffer_id,
+                start,
+                end,
+                request_id,
+            } => {
+                assert_eq!(buffer_id.0, 0);
+                assert_eq!(start, 10);
+                assert_eq!(end, 20);
+                assert!(request_id > 0); // Should have a valid request ID
+            }
+            _ => panic!("Expected GetBufferText, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_compute_diff_sends_command() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._linePromise = editor.computeDiff("a\nb\n", "a\nc\n");
+            globalThis._wordPromise = editor.computeDiff("x y", "x z", { granularity: "word" });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::ComputeDiff {
+                old_text,
+                new_text,
+                options,
+                request_id,
+            } => {
+                assert_eq!(old_text, "a\nb\n");
+                assert_eq!(new_text, "a\nc\n");
+                assert_eq!(options.granularity, None);
+                assert!(request_id > 0);
+            }
+            cmd => panic!("Expected ComputeDiff, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::ComputeDiff { options, .. } => {
+                assert_eq!(
+                    options.granularity,
+                    Some(fresh_core::api::DiffGranularity::Word)
+                );
+            }
+            cmd => panic!("Expected ComputeDiff, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_quick_pick() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._pick = editor.quickPick(
+                [{ label: "one", description: "first", icon: "*", value: { n: 1 } }, { label: "two" }],
+                { title: "Pick:", canPickMany: true }
+            );
+            globalThis._pickDefault = editor.quickPick([{ label: "only" }]);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::StartQuickPick { items, options, .. } => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[0].description.as_deref(), Some("first"));
+                assert_eq!(items[0].icon.as_deref(), Some("*"));
+                assert_eq!(items[0].value, Some(serde_json::json!({ "n": 1 })));
+                assert_eq!(options.title.as_deref(), Some("Pick:"));
+                assert_eq!(options.can_pick_many, Some(true));
+            }
+            cmd => panic!("Expected StartQuickPick, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::StartQuickPick { options, .. } => {
+                assert_eq!(options, fresh_core::api::QuickPickOptions::default());
+            }
+            cmd => panic!("Expected StartQuickPick, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_show_picker() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._picker = editor.showPicker({
+                title: "Branches: ",
+                items: [{ label: "main" }, { label: "develop", picked: true }],
+                multiSelect: true,
+                placeholder: "type to filter",
+            });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::StartQuickPick { items, options, .. } => {
+                assert_eq!(items.len(), 2);
+                assert_eq!(items[1].picked, Some(true));
+                assert_eq!(options.title.as_deref(), Some("Branches: "));
+                assert_eq!(options.can_pick_many, Some(true));
+                assert_eq!(options.placeholder.as_deref(), Some("type to filter"));
+            }
+            cmd => panic!("Expected StartQuickPick, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_show_confirm_and_input() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._confirm = editor.showConfirm("Delete 3 files?", ["Delete", "Keep"]);
+            globalThis._input = editor.showInput("Token: ", { initial: "abc", password: true });
+            globalThis._plainInput = editor.showInput("Name: ");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::ShowConfirm {
+                message, buttons, ..
+            } => {
+                assert_eq!(message, "Delete 3 files?");
+                assert_eq!(buttons, vec!["Delete", "Keep"]);
+            }
+            cmd => panic!("Expected ShowConfirm, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::ShowInput {
+                prompt, options, ..
+            } => {
+                assert_eq!(prompt, "Token: ");
+                assert_eq!(options.initial.as_deref(), Some("abc"));
+                assert_eq!(options.password, Some(true));
+            }
+            cmd => panic!("Expected ShowInput, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::ShowInput { options, .. } => {
+                assert_eq!(options, fresh_core::api::InputOptions::default());
+            }
+            cmd => panic!("Expected ShowInput, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_fetch() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._response = editor.fetch("https://api.example.com/issues", {
+                method: "POST",
+                headers: { "Content-Type": "application/json" },
+                body: "{}",
+                timeoutMs: 5000,
+            });
+            globalThis._plain = editor.fetch("https://example.com/");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::Fetch { url, options, .. } => {
+                assert_eq!(url, "https://api.example.com/issues");
+                assert_eq!(options.method.as_deref(), Some("POST"));
+                assert_eq!(
+                    options
+                        .headers
+                        .as_ref()
+                        .and_then(|h| h.get("Content-Type"))
+                        .map(String::as_str),
+                    Some("application/json")
+                );
+                assert_eq!(options.body.as_deref(), Some("{}"));
+                assert_eq!(options.timeout_ms, Some(5000));
+            }
+            cmd => panic!("Expected Fetch, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::Fetch { options, .. } => {
+                assert_eq!(options, fresh_core::api::FetchOptions::default());
+            }
+            cmd => panic!("Expected Fetch, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_file_browser() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._chosen = editor.openFileBrowser({
+                root: "/project/src",
+                filter: ["*.rs", "*.toml"],
+                title: "Pick a module:",
+            });
+            globalThis._dir = editor.openFileBrowser({ directories: true });
+            editor.revealInExplorer("src/main.rs");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::OpenFileBrowser { options, .. } => {
+                assert_eq!(options.root.as_deref(), Some("/project/src"));
+                assert_eq!(
+                    options.filter,
+                    Some(vec!["*.rs".to_string(), "*.toml".to_string()])
+                );
+                assert_eq!(options.title.as_deref(), Some("Pick a module:"));
+                assert_eq!(options.directories, None);
+            }
+            cmd => panic!("Expected OpenFileBrowser, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::OpenFileBrowser { options, .. } => {
+                assert_eq!(options.directories, Some(true));
+                assert_eq!(options.root, None);
+            }
+            cmd => panic!("Expected OpenFileBrowser, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::RevealInExplorer { path } => {
+                assert_eq!(path, PathBuf::from("src/main.rs"));
+            }
+            cmd => panic!("Expected RevealInExplorer, got {:?}", cmd),
+        }
+    }
+
+    #[test]
+    fn test_api_layout_commands() {
         let (mut backend, rx) = create_test_backend();
 
-        // TypeScript code with type annotations
-        let ts_code = r#"
+        backend
+            .execute_js(
+                r#"
             const editor = getEditor();
-            function greet(name: string): string {
-                return "Hello, " + name;
+            globalThis._layout = editor.getLayout();
+            globalThis._split = editor.splitEditor(3, "vertical", 7);
+            globalThis._splitSame = editor.splitEditor(4, "horizontal");
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::GetLayout { request_id } => assert!(request_id > 0),
+            cmd => panic!("Expected GetLayout, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SplitEditor {
+                split_id,
+                direction,
+                buffer_id,
+                ..
+            } => {
+                assert_eq!(split_id, SplitId(3));
+                assert_eq!(direction, "vertical");
+                assert_eq!(buffer_id, Some(BufferId(7)));
             }
-            editor.setStatus(greet("TypeScript"));
-        "#;
+            cmd => panic!("Expected SplitEditor, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SplitEditor { buffer_id, .. } => assert_eq!(buffer_id, None),
+            cmd => panic!("Expected SplitEditor, got {:?}", cmd),
+        }
+    }
 
-        // Transpile to JavaScript first
-        let js_code = transpile_typescript(ts_code, "test.ts").unwrap();
+    #[test]
+    fn test_api_run_search() {
+        let (mut backend, rx) = create_test_backend();
 
-        // Execute the transpiled JavaScript
-        backend.execute_js(&js_code, "test.js").unwrap();
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.runSearch("fo+", { regex: true, project: true });
+            editor.runSearch("bar");
+        "#,
+                "test.js",
+            )
+            .unwrap();
 
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::SetStatus { message } => {
-                assert_eq!(message, "Hello, TypeScript");
+        match rx.try_recv().unwrap() {
+            PluginCommand::RunSearch { pattern, options } => {
+                assert_eq!(pattern, "fo+");
+                assert_eq!(options.regex, Some(true));
+                assert_eq!(options.case_sensitive, None);
+                assert_eq!(options.project, Some(true));
             }
-            _ => panic!("Expected SetStatus, got {:?}", cmd),
+            cmd => panic!("Expected RunSearch, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::RunSearch { pattern, options } => {
+                assert_eq!(pattern, "bar");
+                assert_eq!(options, fresh_core::api::SearchOptions::default());
+            }
+            cmd => panic!("Expected RunSearch, got {:?}", cmd),
         }
     }
 
     #[test]
-    fn test_api_get_buffer_text_sends_command() {
+    fn test_api_search_buffer() {
         let (mut backend, rx) = create_test_backend();
 
-        // Call getBufferText - this returns a Promise and sends the command
         backend
             .execute_js(
                 r#"
             const editor = getEditor();
-            // Store the promise for later
-            globalThis._textPromise = editor.getBufferText(0, 10, 20);
+            editor.search(3, "fo+", { regex: true, maxResults: 10 });
+            editor.search(3, "bar");
         "#,
                 "test.js",
             )
             .unwrap();
 
-        // Verify the GetBufferText command was sent
-        let cmd = rx.try_recv().unwrap();
-        match cmd {
-            PluginCommand::GetBufferText {
+        match rx.try_recv().unwrap() {
+            PluginCommand::SearchBuffer {
                 buffer_id,
-                start,
-                end,
-                request_id,
+                pattern,
+                options,
+                ..
             } => {
-                assert_eq!(buffer_id.0, 0);
-                assert_eq!(start, 10);
-                assert_eq!(end, 20);
-                assert!(request_id > 0); // Should have a valid request ID
+                assert_eq!(buffer_id.0, 3);
+                assert_eq!(pattern, "fo+");
+                assert_eq!(options.regex, Some(true));
+                assert_eq!(options.case_sensitive, None);
+                assert_eq!(options.max_results, Some(10));
             }
-            _ => panic!("Expected GetBufferText, got {:?}", cmd),
+            cmd => panic!("Expected SearchBuffer, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SearchBuffer {
+                pattern, options, ..
+            } => {
+                assert_eq!(pattern, "bar");
+                assert_eq!(options, fresh_core::api::BufferSearchOptions::default());
+            }
+            cmd => panic!("Expected SearchBuffer, got {:?}", cmd),
         }
     }
 
@@ -4201,6 +8298,322 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_api_background_process_output_listeners() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis._lines = [];
+            globalThis._exit = null;
+            const proc = editor.spawnBackgroundProcess("cargo", ["watch"]);
+            proc.onStdout(line => _lines.push("out:" + line))
+                .onStderr(line => _lines.push("err:" + line));
+            proc.then(result => { globalThis._exit = result.exit_code; });
+            globalThis._processId = proc.processId;
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        let process_id = match rx.try_recv().unwrap() {
+            PluginCommand::SpawnBackgroundProcess {
+                process_id,
+                command,
+                ..
+            } => {
+                assert_eq!(command, "cargo");
+                process_id
+            }
+            cmd => panic!("Expected SpawnBackgroundProcess, got {:?}", cmd),
+        };
+        // Subscribed once, however many listeners are added
+        match rx.try_recv().unwrap() {
+            PluginCommand::SubscribeProcessOutput {
+                plugin_name,
+                process_id: id,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(id, process_id);
+            }
+            cmd => panic!("Expected SubscribeProcessOutput, got {:?}", cmd),
+        }
+        assert!(rx.try_recv().is_err());
+
+        backend.deliver_process_output("test", process_id, false, "Compiling");
+        backend.deliver_process_output("test", process_id, true, "warning: unused");
+        backend.deliver_process_output("test", process_id + 1, false, "other process");
+        backend.resolve_callback(
+            JsCallbackId::from(process_id),
+            &format!(r#"{{"process_id":{},"exit_code":0}}"#, process_id),
+        );
+
+        backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| {
+                let global = ctx.globals();
+                let lines: Vec<String> = global.get("_lines").unwrap();
+                assert_eq!(lines, vec!["out:Compiling", "err:warning: unused"]);
+                let exit: i32 = global.get("_exit").unwrap();
+                assert_eq!(exit, 0);
+                let id: u64 = global.get("_processId").unwrap();
+                assert_eq!(id, process_id);
+            });
+    }
+
+    #[tokio::test]
+    async fn test_timers_fire_on_tokio_runtime() {
+        let (mut backend, _rx) = create_test_backend();
+        let mut timers = backend.take_timer_receiver().unwrap();
+
+        backend
+            .execute_js(
+                r#"
+            globalThis._fired = [];
+            setTimeout((a, b) => _fired.push(`timeout ${a}${b}`), 5, "x", "y");
+            const cancelled = setTimeout(() => _fired.push("cancelled"), 1);
+            clearTimeout(cancelled);
+            let ticks = 0;
+            const interval = setInterval(() => {
+                ticks += 1;
+                _fired.push(`tick ${ticks}`);
+                if (ticks === 3) {
+                    clearInterval(interval);
+                }
+            }, 1);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        // Fire timers until nothing is pending
+        while !backend.timer_tasks.borrow().is_empty() {
+            let callback_id = timers.recv().await.unwrap();
+            backend.fire_timer(callback_id);
+        }
+
+        let fired: Vec<String> = backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| ctx.globals().get("_fired").unwrap());
+        assert_eq!(fired.len(), 4);
+        assert_eq!(fired.iter().filter(|f| f.starts_with("tick")).count(), 3);
+        assert!(fired.contains(&"timeout xy".to_string()));
+        assert!(backend.callback_contexts.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_event_options_coalesce_events() {
+        let (mut backend, _rx) = create_test_backend();
+        let mut timers = backend.take_timer_receiver().unwrap();
+
+        backend
+            .execute_js(
+                r#"
+            globalThis._every = [];
+            globalThis._debounced = [];
+            globalThis._throttled = [];
+            editor.on("cursor_moved", (data) => _every.push(data.n));
+            editor.on("cursor_moved", (data) => _debounced.push(data.n), { debounceMs: 10 });
+            editor.on("cursor_moved", (data) => _throttled.push(data.n), { throttleMs: 60000 });
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        for n in 1..=5 {
+            backend
+                .emit("cursor_moved", &serde_json::json!({ "n": n }))
+                .await
+                .unwrap();
+        }
+        let seen = |backend: &QuickJsBackend, name: &str| -> Vec<u32> {
+            backend
+                .plugin_contexts
+                .borrow()
+                .get("test")
+                .unwrap()
+                .clone()
+                .with(|ctx| ctx.globals().get(name).unwrap())
+        };
+        assert_eq!(seen(&backend, "_every"), vec![1, 2, 3, 4, 5]);
+        assert!(seen(&backend, "_debounced").is_empty());
+        assert_eq!(seen(&backend, "_throttled"), vec![1]);
+
+        // The debounce timer fires first and delivers the latest event
+        let timer_id = timers.recv().await.unwrap();
+        backend.fire_timer(timer_id);
+        assert_eq!(seen(&backend, "_debounced"), vec![5]);
+
+        // Unsubscribing drops the event the throttled handler holds
+        backend
+            .execute_js(
+                r#"editor.off("cursor_moved", "__eventHandler2");"#,
+                "test.js",
+            )
+            .unwrap();
+        assert_eq!(backend.event_coalescers.borrow().len(), 1);
+
+        let result = backend.execute_js(
+            r#"editor.on("cursor_moved", () => {}, { debounce: 10 });"#,
+            "test.js",
+        );
+        assert!(result.is_err(), "unknown options are rejected");
+    }
+
+    #[tokio::test]
+    async fn test_unloading_plugin_cancels_its_timers() {
+        let (mut backend, _rx) = create_test_backend();
+        backend
+            .execute_js("setInterval(() => {}, 1000);", "test.js")
+            .unwrap();
+        assert_eq!(backend.timer_tasks.borrow().len(), 1);
+
+        backend.cancel_plugin_timers("other");
+        assert_eq!(backend.timer_tasks.borrow().len(), 1);
+        backend.cancel_plugin_timers("test");
+        assert!(backend.timer_tasks.borrow().is_empty());
+        assert!(backend.callback_contexts.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_in_worker_settles_promises() {
+        let (mut backend, _rx) = create_test_backend();
+        let mut workers = backend.take_worker_receiver().unwrap();
+
+        backend
+            .execute_js(
+                r#"
+            globalThis._results = [];
+            editor.runInWorker((lines) => lines.map((l) => l.length), [["ab", "cde"]])
+                .then((r) => _results.push(`lengths ${r.join(",")}`));
+            editor.runInWorker("(n) => n * 2", [21])
+                .then((r) => _results.push(`double ${r}`));
+            editor.runInWorker(() => { throw new Error("boom"); })
+                .catch((e) => _results.push(`error ${e.message}`));
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        while !backend.worker_tasks.borrow().is_empty() {
+            let (callback_id, result) = workers.recv().await.unwrap();
+            backend.finish_worker(callback_id, result);
+        }
+
+        let mut results: Vec<String> = backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| ctx.globals().get("_results").unwrap());
+        results.sort();
+        assert_eq!(
+            results,
+            vec!["double 42", "error Error: boom", "lengths 2,3"]
+        );
+        assert!(backend.callback_contexts.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_run_in_worker_rejects_bad_arguments() {
+        let (mut backend, _rx) = create_test_backend();
+        backend
+            .execute_js(
+                r#"
+            globalThis._errors = [];
+            try { editor.runInWorker(42); } catch (e) { _errors.push(e.message); }
+            try { editor.runInWorker("() => 1", "x"); } catch (e) { _errors.push(e.message); }
+        "#,
+                "test.js",
+            )
+            .unwrap();
+        let errors: Vec<String> = backend
+            .plugin_contexts
+            .borrow()
+            .get("test")
+            .unwrap()
+            .clone()
+            .with(|ctx| ctx.globals().get("_errors").unwrap());
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].contains("argument `source`"), "{:?}", errors);
+        assert!(errors[1].contains("argument `args`"), "{:?}", errors);
+        assert!(backend.worker_tasks.borrow().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_unloading_plugin_interrupts_its_workers() {
+        let (mut backend, _rx) = create_test_backend();
+        backend
+            .execute_js("editor.runInWorker(() => { for (;;) {} });", "test.js")
+            .unwrap();
+        let cancelled = backend
+            .worker_tasks
+            .borrow()
+            .values()
+            .next()
+            .cloned()
+            .unwrap();
+
+        backend.cancel_plugin_workers("other");
+        assert!(!cancelled.load(Ordering::Relaxed));
+        backend.cancel_plugin_workers("test");
+        assert!(cancelled.load(Ordering::Relaxed));
+        assert!(backend.worker_tasks.borrow().is_empty());
+        assert!(backend.callback_contexts.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_teardown_plugin_runs_reload_handlers_and_removes_registrations() {
+        let (mut backend, rx) = create_test_backend();
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            globalThis.onSave = () => {};
+            editor.on("after_file_save", "onSave");
+            editor.registerCommand("Greet", "Say hi", "greet", null);
+            globalThis.greet = () => {};
+            editor.addOverlay(1, "greeter", 0, 5, {});
+            editor.onPluginReload(() => editor.setStatus("cleaned up"));
+        "#,
+                "greeter.js",
+            )
+            .unwrap();
+        assert!(backend.has_handlers("after_file_save"));
+        let _: Vec<PluginCommand> = rx.try_iter().collect();
+
+        backend.run_reload_handlers("greeter");
+        backend.teardown_plugin("greeter");
+
+        let commands: Vec<PluginCommand> = rx.try_iter().collect();
+        assert!(commands.iter().any(
+            |cmd| matches!(cmd, PluginCommand::SetStatus { message } if message == "cleaned up")
+        ));
+        assert!(commands.iter().any(|cmd| matches!(
+            cmd,
+            PluginCommand::ClearNamespace { buffer_id, namespace }
+                if buffer_id.0 == 1 && namespace.as_str() == "greeter"
+        )));
+        assert!(commands.iter().any(|cmd| matches!(
+            cmd,
+            PluginCommand::PluginUnloaded { plugin_name } if plugin_name == "greeter"
+        )));
+        assert!(!backend.has_handlers("after_file_save"));
+        assert!(backend.registered_actions.borrow().is_empty());
+        assert!(!backend.plugin_contexts.borrow().contains_key("greeter"));
+    }
+
     #[test]
     fn test_plugin_translation() {
         let (mut backend, _rx) = create_test_backend();
@@ -4350,6 +8763,65 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_api_gutter_columns() {
+        let (mut backend, rx) = create_test_backend();
+
+        backend
+            .execute_js(
+                r#"
+            const editor = getEditor();
+            editor.registerGutter("blame", 8);
+            editor.setGutterText(1, "blame", 3, "alice", "editor.line_number_fg");
+            editor.setGutterTexts(1, "blame", [{ line: 0, text: "bob", color: [255, 0, 0] }]);
+        "#,
+                "test.js",
+            )
+            .unwrap();
+
+        match rx.try_recv().unwrap() {
+            PluginCommand::RegisterGutter {
+                plugin_name,
+                namespace,
+                width,
+            } => {
+                assert_eq!(plugin_name, "test");
+                assert_eq!(namespace, "blame");
+                assert_eq!(width, 8);
+            }
+            cmd => panic!("Expected RegisterGutter, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetGutterText {
+                buffer_id,
+                line,
+                text,
+                color,
+                ..
+            } => {
+                assert_eq!(buffer_id.0, 1);
+                assert_eq!(line, 3);
+                assert_eq!(text, "alice");
+                assert!(matches!(
+                    color,
+                    Some(fresh_core::api::OverlayColorSpec::ThemeKey(key)) if key == "editor.line_number_fg"
+                ));
+            }
+            cmd => panic!("Expected SetGutterText, got {:?}", cmd),
+        }
+        match rx.try_recv().unwrap() {
+            PluginCommand::SetGutterTexts { lines, .. } => {
+                assert_eq!(lines.len(), 1);
+                assert_eq!(lines[0].text, "bob");
+                assert!(matches!(
+                    lines[0].color,
+                    Some(fresh_core::api::OverlayColorSpec::Rgb(255, 0, 0))
+                ));
+            }
+            cmd => panic!("Expected SetGutterTexts, got {:?}", cmd),
+        }
+    }
+
     // ==================== Virtual Buffer Tests ====================
 
     #[test]
@@ -4700,6 +9172,9 @@ mod tests {
                     path: Some(PathBuf::from("/test1.txt")),
                     modified: false,
                     length: 100,
+                    version: 0,
+                    undo_depth: 0,
+                    read_only: false,
                 },
             );
             state.buffers.insert(
@@ -4709,6 +9184,9 @@ mod tests {
                     path: Some(PathBuf::from("/test2.txt")),
                     modified: true,
                     length: 200,
+                    version: 0,
+                    undo_depth: 0,
+                    read_only: false,
                 },
             );
         }
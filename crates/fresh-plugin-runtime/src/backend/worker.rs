@@ -0,0 +1,162 @@
+//! Worker threads for CPU-heavy plugin code
+//!
+//! `editor.runInWorker(fn, args)` runs a function in a fresh QuickJS runtime
+//! on its own thread, so a plugin can parse or crunch data without blocking
+//! the plugin thread (and with it the editor's hooks and actions).
+//!
+//! The worker shares nothing with the plugin: the function is sent as source
+//! text and re-evaluated, its arguments and result are passed as JSON, and
+//! only `console` and the standard JS built-ins are available to it. When the
+//! function returns a promise, the worker waits for it to settle.
+//!
+//! The result goes back to the plugin thread through a channel (like fired
+//! timers), which resolves or rejects the plugin's promise. Workers of a
+//! plugin that is unloaded are interrupted.
+
+use rquickjs::{Context, Ctx, Function, Object, Runtime};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// Outcome of a worker: its callback ID and the JSON of the function's
+/// result, or an error message
+pub type WorkerResult = (u64, Result<String, String>);
+
+/// Run `source` (a function expression) with the JSON array `args_json` on a
+/// new thread and send the outcome to `sender`
+///
+/// Setting `cancelled` interrupts the worker; nothing is sent then.
+pub(crate) fn spawn(
+    callback_id: u64,
+    source: String,
+    args_json: String,
+    cancelled: Arc<AtomicBool>,
+    sender: tokio::sync::mpsc::UnboundedSender<WorkerResult>,
+) {
+    let spawned = std::thread::Builder::new()
+        .name(format!("plugin-worker-{}", callback_id))
+        .spawn(move || {
+            let result = run(&source, &args_json, Arc::clone(&cancelled));
+            if !cancelled.load(Ordering::Relaxed) {
+                let _ = sender.send((callback_id, result));
+            }
+        });
+    if let Err(e) = spawned {
+        tracing::error!("Failed to start plugin worker thread: {}", e);
+    }
+}
+
+/// Evaluate the function and wait for its result in a new runtime
+fn run(source: &str, args_json: &str, cancelled: Arc<AtomicBool>) -> Result<String, String> {
+    let runtime = Runtime::new().map_err(|e| format!("Failed to create worker runtime: {}", e))?;
+    runtime.set_interrupt_handler(Some(Box::new(move || cancelled.load(Ordering::Relaxed))));
+    let context =
+        Context::full(&runtime).map_err(|e| format!("Failed to create worker context: {}", e))?;
+
+    context.with(|ctx| {
+        install_console(&ctx).map_err(|e| error_message(&ctx, e))?;
+        let globals = ctx.globals();
+        globals
+            .set("__workerArgs", args_json)
+            .map_err(|e| error_message(&ctx, e))?;
+        // The parentheses make both function expressions and arrow functions callable
+        let code = format!(
+            "(async () => {{\n\
+                 const result = await ({})(...JSON.parse(__workerArgs));\n\
+                 return JSON.stringify(result === undefined ? null : result);\n\
+             }})()",
+            source
+        );
+        let promise: rquickjs::Promise = ctx.eval(code).map_err(|e| error_message(&ctx, e))?;
+        let json: Option<String> = promise.finish().map_err(|e| error_message(&ctx, e))?;
+        // JSON.stringify gives undefined for functions and symbols
+        Ok(json.unwrap_or_else(|| "null".to_string()))
+    })
+}
+
+/// `console.log` and friends, logged with the worker's thread name
+fn install_console(ctx: &Ctx<'_>) -> rquickjs::Result<()> {
+    let console = Object::new(ctx.clone())?;
+    for level in ["log", "info", "debug", "warn", "error"] {
+        let log = Function::new(ctx.clone(), move |message: String| match level {
+            "warn" => tracing::warn!("[worker] {}", message),
+            "error" => tracing::error!("[worker] {}", message),
+            _ => tracing::info!("[worker] {}", message),
+        })?;
+        console.set(format!("_{}", level), log)?;
+    }
+    ctx.globals().set("console", console)?;
+    ctx.eval::<(), _>(
+        r#"
+        for (const level of ["log", "info", "debug", "warn", "error"]) {
+            const log = console["_" + level];
+            console[level] = (...args) => log(args.map((arg) => {
+                if (typeof arg === "string") return arg;
+                if (arg instanceof Error) return `${arg.name}: ${arg.message}`;
+                try { return JSON.stringify(arg); } catch (e) { return String(arg); }
+            }).join(" "));
+        }
+        "#,
+    )
+}
+
+/// Message of a failed evaluation: the thrown error's name and message
+fn error_message(ctx: &Ctx<'_>, err: rquickjs::Error) -> String {
+    if !err.is_exception() {
+        return err.to_string();
+    }
+    let exc = ctx.catch();
+    if let Some(obj) = exc.as_object() {
+        let message: String = obj.get("message").unwrap_or_default();
+        let name: String = obj.get("name").unwrap_or_else(|_| "Error".to_string());
+        if message.is_empty() {
+            return name;
+        }
+        return format!("{}: {}", name, message);
+    }
+    exc.as_string()
+        .and_then(|s| s.to_string().ok())
+        .unwrap_or_else(|| format!("{:?}", exc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_worker(source: &str, args: &str) -> Result<String, String> {
+        run(source, args, Arc::new(AtomicBool::new(false)))
+    }
+
+    #[test]
+    fn test_worker_returns_json_result() {
+        assert_eq!(
+            run_worker("(a, b) => ({ sum: a + b, list: [a, b] })", "[2, 3]"),
+            Ok(r#"{"sum":5,"list":[2,3]}"#.to_string())
+        );
+        assert_eq!(
+            run_worker("function () { console.log('hi', { x: 1 }); }", "[]"),
+            Ok("null".to_string())
+        );
+        assert_eq!(
+            run_worker("async (text) => text.split(',').length", r#"["a,b,c"]"#),
+            Ok("3".to_string())
+        );
+    }
+
+    #[test]
+    fn test_worker_reports_errors() {
+        assert_eq!(
+            run_worker("() => { throw new TypeError('bad input'); }", "[]"),
+            Err("TypeError: bad input".to_string())
+        );
+        assert!(run_worker("() => editor.getActiveBufferId()", "[]")
+            .unwrap_err()
+            .contains("editor"));
+        assert!(run_worker("this is not js", "[]").is_err());
+    }
+
+    #[test]
+    fn test_cancelled_worker_is_interrupted() {
+        let cancelled = Arc::new(AtomicBool::new(true));
+        assert!(run("() => { for (;;) {} }", "[]", cancelled).is_err());
+    }
+}
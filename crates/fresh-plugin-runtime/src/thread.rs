@@ -10,8 +10,9 @@
 //! - Async operations complete naturally without runtime destruction
 
 use crate::backend::quickjs_backend::{PendingResponses, TsPluginInfo};
-use crate::backend::QuickJsBackend;
+use crate::backend::{Capability, CapabilitySet, QuickJsBackend};
 use anyhow::{anyhow, Result};
+use fresh_core::activation::ActivationEvent;
 use fresh_core::api::{EditorStateSnapshot, PluginCommand};
 use fresh_core::hooks::HookArgs;
 use std::cell::RefCell;
@@ -46,6 +47,14 @@ pub enum PluginRequest {
         error: String,
     },
 
+    /// Pass a line of background process output to a plugin's listeners
+    ProcessOutput {
+        plugin_name: String,
+        process_id: u64,
+        is_stderr: bool,
+        line: String,
+    },
+
     /// Load all plugins from a directory
     LoadPluginsFromDir {
         dir: PathBuf,
@@ -82,6 +91,60 @@ pub enum PluginRequest {
     /// Run a hook (fire-and-forget, no response needed)
     RunHook { hook_name: String, args: HookArgs },
 
+    /// Load pending plugins waiting for an activation event (fire-and-forget)
+    Activate { event: ActivationEvent },
+
+    /// Allow a plugin to use a permission from its manifest (fire-and-forget)
+    GrantPermission {
+        plugin_name: String,
+        capability: String,
+    },
+
+    /// Ask a completion provider for items (fire-and-forget, the items come
+    /// back as `PluginCommand::ProvideCompletions`)
+    RequestCompletions {
+        plugin_name: String,
+        handler_name: String,
+        request_id: u64,
+        request: fresh_core::api::CompletionRequest,
+    },
+
+    /// Ask a hover provider for markdown (fire-and-forget, the result comes
+    /// back as `PluginCommand::ProvideHover`)
+    RequestHover {
+        plugin_name: String,
+        handler_name: String,
+        request_id: u64,
+        request: fresh_core::api::HoverRequest,
+    },
+
+    /// Ask an inline suggestion provider for text to suggest (fire-and-forget,
+    /// the result comes back as `PluginCommand::ProvideInlineSuggestion`)
+    RequestInlineSuggestion {
+        plugin_name: String,
+        handler_name: String,
+        request_id: u64,
+        request: fresh_core::api::InlineSuggestionRequest,
+    },
+
+    /// Ask a formatter to format a buffer's text (fire-and-forget, the result
+    /// comes back as `PluginCommand::ProvideFormattedText`)
+    RequestFormat {
+        plugin_name: String,
+        handler_name: String,
+        request_id: u64,
+        request: fresh_core::api::FormatRequest,
+    },
+
+    /// Ask an evaluator to evaluate selections (fire-and-forget, the results
+    /// come back as `PluginCommand::ProvideEvaluation`)
+    RequestEvaluation {
+        plugin_name: String,
+        handler_name: String,
+        request_id: u64,
+        requests: Vec<fresh_core::api::EvaluateRequest>,
+    },
+
     /// Check if any handlers are registered for a hook
     HasHookHandlers {
         hook_name: String,
@@ -483,6 +546,113 @@ impl PluginThreadHandle {
         }
     }
 
+    /// Report an activation event (non-blocking, fire-and-forget)
+    ///
+    /// Pending plugins whose manifest declares a matching event are loaded
+    /// before any request sent after this one is handled.
+    pub fn activate(&self, event: ActivationEvent) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::Activate { event });
+        }
+    }
+
+    /// Allow a plugin to use a permission declared in its `plugin.json`
+    /// (non-blocking, fire-and-forget)
+    pub fn grant_permission(&self, plugin_name: &str, capability: &str) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::GrantPermission {
+                plugin_name: plugin_name.to_string(),
+                capability: capability.to_string(),
+            });
+        }
+    }
+
+    /// Ask a completion provider for items (non-blocking, fire-and-forget)
+    pub fn request_completions(
+        &self,
+        provider: &fresh_core::api::CompletionProvider,
+        request_id: u64,
+        request: fresh_core::api::CompletionRequest,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::RequestCompletions {
+                plugin_name: provider.plugin_name.clone(),
+                handler_name: provider.handler_name.clone(),
+                request_id,
+                request,
+            });
+        }
+    }
+
+    /// Ask a hover provider for markdown (non-blocking, fire-and-forget)
+    pub fn request_hover(
+        &self,
+        provider: &fresh_core::api::HoverProvider,
+        request_id: u64,
+        request: fresh_core::api::HoverRequest,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::RequestHover {
+                plugin_name: provider.plugin_name.clone(),
+                handler_name: provider.handler_name.clone(),
+                request_id,
+                request,
+            });
+        }
+    }
+
+    /// Ask an inline suggestion provider for text to suggest (non-blocking,
+    /// fire-and-forget)
+    pub fn request_inline_suggestion(
+        &self,
+        provider: &fresh_core::api::InlineSuggestionProvider,
+        request_id: u64,
+        request: fresh_core::api::InlineSuggestionRequest,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::RequestInlineSuggestion {
+                plugin_name: provider.plugin_name.clone(),
+                handler_name: provider.handler_name.clone(),
+                request_id,
+                request,
+            });
+        }
+    }
+
+    /// Ask a formatter to format a buffer's text (non-blocking, fire-and-forget)
+    pub fn request_format(
+        &self,
+        formatter: &fresh_core::api::PluginFormatter,
+        request_id: u64,
+        request: fresh_core::api::FormatRequest,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::RequestFormat {
+                plugin_name: formatter.plugin_name.clone(),
+                handler_name: formatter.handler_name.clone(),
+                request_id,
+                request,
+            });
+        }
+    }
+
+    /// Ask a plugin evaluator to evaluate selections (non-blocking)
+    pub fn request_evaluation(
+        &self,
+        evaluator: &fresh_core::api::PluginEvaluator,
+        request_id: u64,
+        requests: Vec<fresh_core::api::EvaluateRequest>,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::RequestEvaluation {
+                plugin_name: evaluator.plugin_name.clone(),
+                handler_name: evaluator.handler_name.clone(),
+                request_id,
+                requests,
+            });
+        }
+    }
+
     /// Check if any handlers are registered for a hook (blocking)
     pub fn has_hook_handlers(&self, hook_name: &str) -> bool {
         let (tx, rx) = oneshot::channel();
@@ -594,6 +764,25 @@ impl PluginThreadHandle {
             let _ = sender.send(PluginRequest::RejectCallback { callback_id, error });
         }
     }
+
+    /// Pass a line of a background process's output to the listeners of the
+    /// plugin that subscribed to it (fire-and-forget)
+    pub fn deliver_process_output(
+        &self,
+        plugin_name: String,
+        process_id: u64,
+        is_stderr: bool,
+        line: String,
+    ) {
+        if let Some(sender) = self.request_sender.as_ref() {
+            let _ = sender.send(PluginRequest::ProcessOutput {
+                plugin_name,
+                process_id,
+                is_stderr,
+                line,
+            });
+        }
+    }
 }
 
 impl Drop for PluginThreadHandle {
@@ -705,6 +894,12 @@ async fn plugin_thread_loop(
     let poll_interval = Duration::from_millis(1);
     let mut has_pending_work = false;
 
+    // Callback IDs of setTimeout/setInterval timers as they fire
+    let mut timer_receiver = runtime.borrow_mut().take_timer_receiver();
+
+    // Results of `runInWorker` workers as they finish
+    let mut worker_receiver = runtime.borrow_mut().take_worker_receiver();
+
     loop {
         // Check for fatal JS errors (e.g., unhandled promise rejections in test mode)
         // These are set via set_fatal_js_error() because panicking inside FFI callbacks
@@ -751,6 +946,16 @@ async fn plugin_thread_loop(
                 }
             }
 
+            Some(callback_id) = async { timer_receiver.as_mut()?.recv().await }, if timer_receiver.is_some() => {
+                runtime.borrow_mut().fire_timer(callback_id);
+                has_pending_work = true;
+            }
+
+            Some((callback_id, result)) = async { worker_receiver.as_mut()?.recv().await }, if worker_receiver.is_some() => {
+                runtime.borrow_mut().finish_worker(callback_id, result);
+                has_pending_work = true;
+            }
+
             // Poll the JS event loop periodically to make progress on pending promises
             _ = tokio::time::sleep(poll_interval), if has_pending_work => {
                 has_pending_work = runtime.borrow_mut().poll_event_loop_once();
@@ -814,7 +1019,8 @@ async fn handle_request(
 ) -> bool {
     match request {
         PluginRequest::LoadPlugin { path, response } => {
-            let result = load_plugin_internal(Rc::clone(&runtime), plugins, &path).await;
+            let result =
+                load_plugin_internal(Rc::clone(&runtime), plugins, &path, "loadPlugin").await;
             let _ = response.send(result);
         }
 
@@ -893,6 +1099,104 @@ async fn handle_request(
             }
         }
 
+        PluginRequest::Activate { event } => {
+            activate_plugins_internal(Rc::clone(&runtime), plugins, &event).await;
+        }
+
+        PluginRequest::GrantPermission {
+            plugin_name,
+            capability,
+        } => match capability.parse::<Capability>() {
+            Ok(capability) => runtime
+                .borrow_mut()
+                .grant_permission(&plugin_name, capability),
+            Err(e) => tracing::warn!("Cannot grant {} to {}: {}", capability, plugin_name, e),
+        },
+
+        PluginRequest::RequestCompletions {
+            plugin_name,
+            handler_name,
+            request_id,
+            request,
+        } => {
+            if let Err(e) = runtime.borrow_mut().request_completions(
+                &plugin_name,
+                &handler_name,
+                request_id,
+                &request,
+            ) {
+                tracing::error!("Completion provider '{}' failed: {}", handler_name, e);
+            }
+        }
+
+        PluginRequest::RequestHover {
+            plugin_name,
+            handler_name,
+            request_id,
+            request,
+        } => {
+            if let Err(e) = runtime.borrow_mut().request_hover(
+                &plugin_name,
+                &handler_name,
+                request_id,
+                &request,
+            ) {
+                tracing::error!("Hover provider '{}' failed: {}", handler_name, e);
+            }
+        }
+
+        PluginRequest::RequestInlineSuggestion {
+            plugin_name,
+            handler_name,
+            request_id,
+            request,
+        } => {
+            if let Err(e) = runtime.borrow_mut().request_inline_suggestion(
+                &plugin_name,
+                &handler_name,
+                request_id,
+                &request,
+            ) {
+                tracing::error!(
+                    "Inline suggestion provider '{}' failed: {}",
+                    handler_name,
+                    e
+                );
+            }
+        }
+
+        PluginRequest::RequestFormat {
+            plugin_name,
+            handler_name,
+            request_id,
+            request,
+        } => {
+            if let Err(e) = runtime.borrow_mut().request_format(
+                &plugin_name,
+                &handler_name,
+                request_id,
+                &request,
+            ) {
+                tracing::error!("Formatter '{}' failed: {}", handler_name, e);
+            }
+        }
+
+        PluginRequest::RequestEvaluation {
+            plugin_name,
+            handler_name,
+            request_id,
+            requests,
+        } => {
+            if let Err(e) = runtime.borrow_mut().request_evaluation(
+                &plugin_name,
+                &handler_name,
+                request_id,
+                &requests,
+            ) {
+                tracing::error!("Evaluator '{}' failed: {}", handler_name, e);
+            }
+        }
+
         PluginRequest::HasHookHandlers {
             hook_name,
             response,
@@ -930,6 +1234,17 @@ async fn handle_request(
             // reject_callback now runs execute_pending_job() internally
         }
 
+        PluginRequest::ProcessOutput {
+            plugin_name,
+            process_id,
+            is_stderr,
+            line,
+        } => {
+            runtime
+                .borrow_mut()
+                .deliver_process_output(&plugin_name, process_id, is_stderr, &line);
+        }
+
         PluginRequest::Shutdown => {
             tracing::info!("Plugin thread received shutdown request");
             return true;
@@ -951,6 +1266,7 @@ async fn load_plugin_internal(
     runtime: Rc<RefCell<QuickJsBackend>>,
     plugins: &mut HashMap<String, TsPluginInfo>,
     path: &Path,
+    reason: &str,
 ) -> Result<()> {
     let plugin_name = path
         .file_stem()
@@ -959,6 +1275,11 @@ async fn load_plugin_internal(
         .to_string();
 
     tracing::info!("Loading TypeScript plugin: {} from {:?}", plugin_name, path);
+
+    let manifest = read_permission_manifest(path)?;
+    runtime
+        .borrow_mut()
+        .set_plugin_manifest(&plugin_name, manifest);
     tracing::debug!(
         "load_plugin_internal: starting module load for plugin '{}'",
         plugin_name
@@ -999,13 +1320,19 @@ async fn load_plugin_internal(
         load_elapsed
     );
 
-    // Store plugin info
+    // Store plugin info, keeping the activation events of a pending entry
+    let activation_events = plugins
+        .get(&plugin_name)
+        .map(|info| info.activation_events.clone())
+        .unwrap_or_default();
     plugins.insert(
         plugin_name.clone(),
         TsPluginInfo {
             name: plugin_name.clone(),
             path: path.to_path_buf(),
             enabled: true,
+            activation_events,
+            activated_by: Some(reason.to_string()),
         },
     );
 
@@ -1035,6 +1362,8 @@ async fn load_plugins_from_dir_internal(
         return errors;
     }
 
+    let activation_events = read_activation_events(dir);
+
     // Scan directory for .ts and .js files
     match std::fs::read_dir(dir) {
         Ok(entries) => {
@@ -1042,11 +1371,16 @@ async fn load_plugins_from_dir_internal(
                 let path = entry.path();
                 let ext = path.extension().and_then(|s| s.to_str());
                 if ext == Some("ts") || ext == Some("js") {
+                    if !activation_events.is_empty() {
+                        defer_plugin(plugins, &path, &activation_events);
+                        continue;
+                    }
                     tracing::debug!(
                         "load_plugins_from_dir_internal: attempting to load {:?}",
                         path
                     );
-                    if let Err(e) = load_plugin_internal(Rc::clone(&runtime), plugins, &path).await
+                    if let Err(e) =
+                        load_plugin_internal(Rc::clone(&runtime), plugins, &path, "startup").await
                     {
                         let err = format!("Failed to load {:?}: {}", path, e);
                         tracing::error!("{}", err);
@@ -1122,6 +1456,8 @@ async fn load_plugins_from_dir_with_config_internal(
         }
     }
 
+    let activation_events = read_activation_events(dir);
+
     // Second pass: build discovered_plugins map and load enabled plugins
     for (plugin_name, path) in plugin_files {
         // Check if we have an existing config for this plugin
@@ -1130,6 +1466,7 @@ async fn load_plugins_from_dir_with_config_internal(
             PluginConfig {
                 enabled: existing_config.enabled,
                 path: Some(path.clone()),
+                permissions: existing_config.permissions.clone(),
             }
         } else {
             // Create new config with default enabled = true
@@ -1138,14 +1475,21 @@ async fn load_plugins_from_dir_with_config_internal(
 
         // Add to discovered plugins
         discovered_plugins.insert(plugin_name.clone(), config.clone());
+        runtime
+            .borrow_mut()
+            .set_granted_permissions(&plugin_name, granted_permissions(&config.permissions));
 
-        // Only load if enabled
-        if config.enabled {
+        // Only load if enabled, and only now if no activation event is declared
+        if config.enabled && !activation_events.is_empty() {
+            defer_plugin(plugins, &path, &activation_events);
+        } else if config.enabled {
             tracing::debug!(
                 "load_plugins_from_dir_with_config_internal: loading enabled plugin '{}'",
                 plugin_name
             );
-            if let Err(e) = load_plugin_internal(Rc::clone(&runtime), plugins, &path).await {
+            if let Err(e) =
+                load_plugin_internal(Rc::clone(&runtime), plugins, &path, "startup").await
+            {
                 let err = format!("Failed to load {:?}: {}", path, e);
                 tracing::error!("{}", err);
                 errors.push(err);
@@ -1167,6 +1511,104 @@ async fn load_plugins_from_dir_with_config_internal(
     (errors, discovered_plugins)
 }
 
+/// Read the activation events from the `package.json` next to a directory's plugins
+fn read_activation_events(dir: &Path) -> Vec<ActivationEvent> {
+    let manifest_path = dir.join("package.json");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Vec::new();
+    };
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(manifest) => fresh_core::activation::manifest_activation_events(&manifest),
+        Err(e) => {
+            tracing::warn!("Failed to parse {:?}: {}", manifest_path, e);
+            Vec::new()
+        }
+    }
+}
+
+/// Read the capabilities declared by the `plugin.json` next to a plugin
+/// (None if there is no manifest)
+fn read_permission_manifest(path: &Path) -> Result<Option<CapabilitySet>> {
+    let Some(manifest_path) = path.parent().map(|dir| dir.join("plugin.json")) else {
+        return Ok(None);
+    };
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Ok(None);
+    };
+    crate::backend::capabilities::parse_manifest(&content)
+        .map(Some)
+        .map_err(|e| anyhow!("Invalid {:?}: {}", manifest_path, e))
+}
+
+/// The permissions named in a plugin's config, ignoring unknown names
+fn granted_permissions(names: &[String]) -> CapabilitySet {
+    names
+        .iter()
+        .filter_map(|name| match name.parse::<Capability>() {
+            Ok(capability) => Some(capability),
+            Err(e) => {
+                tracing::warn!("Ignoring granted permission: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Register a plugin without executing it until one of its activation events fires
+fn defer_plugin(
+    plugins: &mut HashMap<String, TsPluginInfo>,
+    path: &Path,
+    activation_events: &[ActivationEvent],
+) {
+    let Some(plugin_name) = path.file_stem().and_then(|s| s.to_str()) else {
+        return;
+    };
+    tracing::info!(
+        "Deferring plugin '{}' until {}",
+        plugin_name,
+        activation_events
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    plugins.insert(
+        plugin_name.to_string(),
+        TsPluginInfo {
+            name: plugin_name.to_string(),
+            path: path.to_path_buf(),
+            enabled: true,
+            activation_events: activation_events.to_vec(),
+            activated_by: None,
+        },
+    );
+}
+
+/// Load every pending plugin that declares an activation event matching `event`
+async fn activate_plugins_internal(
+    runtime: Rc<RefCell<QuickJsBackend>>,
+    plugins: &mut HashMap<String, TsPluginInfo>,
+    event: &ActivationEvent,
+) {
+    let mut pending: Vec<PathBuf> = plugins
+        .values()
+        .filter(|info| info.activated_by.is_none())
+        .filter(|info| info.activation_events.iter().any(|e| e.matches(event)))
+        .map(|info| info.path.clone())
+        .collect();
+    pending.sort();
+
+    let reason = event.to_string();
+    for path in pending {
+        tracing::info!("Activating plugin {:?} on {}", path, reason);
+        if let Err(e) = load_plugin_internal(Rc::clone(&runtime), plugins, &path, &reason).await {
+            let error_msg = format!("Failed to activate {:?}: {}", path, e);
+            tracing::error!("{}", error_msg);
+            runtime.borrow_mut().send_status(error_msg);
+        }
+    }
+}
+
 /// Unload a plugin
 fn unload_plugin_internal(
     runtime: Rc<RefCell<QuickJsBackend>>,
@@ -1176,6 +1618,8 @@ fn unload_plugin_internal(
     if plugins.remove(name).is_some() {
         tracing::info!("Unloading TypeScript plugin: {}", name);
 
+        runtime.borrow_mut().teardown_plugin(name);
+
         // Unregister i18n strings
         runtime
             .borrow_mut()
@@ -1200,14 +1644,20 @@ async fn reload_plugin_internal(
     plugins: &mut HashMap<String, TsPluginInfo>,
     name: &str,
 ) -> Result<()> {
-    let path = plugins
+    let info = plugins
         .get(name)
         .ok_or_else(|| anyhow!("Plugin '{}' not found", name))?
-        .path
         .clone();
+    let reason = info
+        .activated_by
+        .unwrap_or_else(|| "reloadPlugin".to_string());
 
+    runtime.borrow_mut().run_reload_handlers(name);
     unload_plugin_internal(Rc::clone(&runtime), plugins, name)?;
-    load_plugin_internal(runtime, plugins, &path).await?;
+    load_plugin_internal(runtime, plugins, &info.path, &reason).await?;
+    if let Some(reloaded) = plugins.get_mut(name) {
+        reloaded.activation_events = info.activation_events;
+    }
 
     Ok(())
 }
@@ -5,8 +5,9 @@
 //! The generated TypeScript is validated and formatted using oxc.
 //!
 //! Types are automatically collected based on `JSEDITORAPI_REFERENCED_TYPES`
-//! from the proc macro, so when you add a new type to method signatures,
-//! it will automatically be included if it has `#[derive(TS)]`.
+//! and `HOOKARGS_REFERENCED_TYPES` from the proc macros, so when you add a new
+//! type to method signatures or event payloads, it will automatically be
+//! included if it has `#[derive(TS)]`.
 
 use oxc_allocator::Allocator;
 use oxc_codegen::Codegen;
@@ -15,17 +16,23 @@ use oxc_span::SourceType;
 use ts_rs::TS;
 
 use fresh_core::api::{
-    ActionPopupAction, ActionPopupOptions, ActionSpec, BackgroundProcessResult, BufferInfo,
-    BufferSavedDiff, CompositeHunk, CompositeLayoutConfig, CompositePaneStyle,
-    CompositeSourceConfig, CreateCompositeBufferOptions, CreateVirtualBufferInExistingSplitOptions,
-    CreateVirtualBufferInSplitOptions, CreateVirtualBufferOptions, CursorInfo, DirEntry,
-    FormatterPackConfig, JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry,
-    LanguagePackConfig, LayoutHints, LspServerPackConfig, SpawnResult, TextPropertiesAtCursor,
-    TsHighlightSpan, ViewTokenStyle, ViewTokenWire, ViewTokenWireKind, ViewportInfo,
-    VirtualBufferResult,
+    ActionPopupAction, ActionPopupOptions, ActionSpec, BackgroundProcessResult,
+    BufferDisplayOptions, BufferEdit, BufferInfo, BufferSavedDiff, BufferSearchOptions,
+    CompletionItem, CompletionRequest, CompositeHunk, CompositeLayoutConfig, CompositePaneStyle,
+    CompositeSourceConfig, ComputeDiffOptions, ConcealRule, CreateCompositeBufferOptions,
+    CreateVirtualBufferInExistingSplitOptions, CreateVirtualBufferInSplitOptions,
+    CreateVirtualBufferOptions, CursorInfo, DiffGranularity, DiffHunk, DirEntry, EvaluateRequest,
+    EventOptions, FetchOptions, FetchResponse, FileBrowserOptions, FormatRequest,
+    FormatterPackConfig, GutterText, HoverRequest, InlineSuggestionRequest, InputOptions,
+    JsDiagnostic, JsPosition, JsRange, JsTextPropertyEntry, LanguagePackConfig, LayoutHints,
+    LayoutNode, LineBackground, LspServerPackConfig, MenuItemOptions, OverlayColorSpec,
+    PickerOptions, QuickPickItem, QuickPickOptions, SearchMatch, SearchOptions, SelectionSpec,
+    SpawnResult, SyntaxCapture, TextPropertiesAtCursor, ThemeSeed, TsHighlightSpan, ViewTokenStyle,
+    ViewTokenWire, ViewTokenWireKind, ViewportInfo, VirtualBufferResult,
 };
 use fresh_core::command::Suggestion;
 use fresh_core::file_explorer::FileExplorerDecoration;
+use fresh_core::hooks::{BufferChange, LineInfo, LspLocation};
 
 /// Get the TypeScript declaration for a type by name
 ///
@@ -40,11 +47,17 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "CursorInfo" => Some(CursorInfo::decl()),
         "ViewportInfo" => Some(ViewportInfo::decl()),
         "ActionSpec" => Some(ActionSpec::decl()),
+        "SelectionSpec" => Some(SelectionSpec::decl()),
+        "BufferEdit" => Some(BufferEdit::decl()),
+        "OverlayColorSpec" => Some(OverlayColorSpec::decl()),
+        "GutterText" => Some(GutterText::decl()),
+        "LineBackground" => Some(LineBackground::decl()),
         "BufferSavedDiff" => Some(BufferSavedDiff::decl()),
         "LayoutHints" => Some(LayoutHints::decl()),
 
         // Process types
         "SpawnResult" => Some(SpawnResult::decl()),
+        "FetchResponse" => Some(FetchResponse::decl()),
         "BackgroundProcessResult" => Some(BackgroundProcessResult::decl()),
 
         // Composite buffer types (ts-rs renames these with Ts prefix)
@@ -56,6 +69,25 @@ fn get_type_decl(type_name: &str) -> Option<String> {
             Some(CreateCompositeBufferOptions::decl())
         }
 
+        // Diff types
+        "DiffGranularity" => Some(DiffGranularity::decl()),
+        "ComputeDiffOptions" => Some(ComputeDiffOptions::decl()),
+        "DiffHunk" => Some(DiffHunk::decl()),
+
+        // Syntax query types
+        "SyntaxCapture" => Some(SyntaxCapture::decl()),
+
+        // Layout types
+        "LayoutNode" => Some(LayoutNode::decl()),
+
+        // Search types
+        "SearchOptions" => Some(SearchOptions::decl()),
+        "BufferSearchOptions" => Some(BufferSearchOptions::decl()),
+        "SearchMatch" => Some(SearchMatch::decl()),
+
+        // Event types
+        "EventOptions" => Some(EventOptions::decl()),
+
         // View transform types
         "ViewTokenWireKind" => Some(ViewTokenWireKind::decl()),
         "ViewTokenStyle" => Some(ViewTokenStyle::decl()),
@@ -69,6 +101,8 @@ fn get_type_decl(type_name: &str) -> Option<String> {
 
         // Virtual buffer option types
         "TextPropertyEntry" | "JsTextPropertyEntry" => Some(JsTextPropertyEntry::decl()),
+        "BufferDisplayOptions" => Some(BufferDisplayOptions::decl()),
+        "ConcealRule" => Some(ConcealRule::decl()),
         "CreateVirtualBufferOptions" => Some(CreateVirtualBufferOptions::decl()),
         "CreateVirtualBufferInSplitOptions" => Some(CreateVirtualBufferInSplitOptions::decl()),
         "CreateVirtualBufferInExistingSplitOptions" => {
@@ -81,6 +115,19 @@ fn get_type_decl(type_name: &str) -> Option<String> {
 
         // Prompt and directory types
         "PromptSuggestion" | "Suggestion" => Some(Suggestion::decl()),
+        "QuickPickItem" => Some(QuickPickItem::decl()),
+        "QuickPickOptions" => Some(QuickPickOptions::decl()),
+        "PickerOptions" => Some(PickerOptions::decl()),
+        "InputOptions" => Some(InputOptions::decl()),
+        "MenuItemOptions" => Some(MenuItemOptions::decl()),
+        "FileBrowserOptions" => Some(FileBrowserOptions::decl()),
+        "FetchOptions" => Some(FetchOptions::decl()),
+        "CompletionItem" => Some(CompletionItem::decl()),
+        "CompletionRequest" => Some(CompletionRequest::decl()),
+        "HoverRequest" => Some(HoverRequest::decl()),
+        "InlineSuggestionRequest" => Some(InlineSuggestionRequest::decl()),
+        "FormatRequest" => Some(FormatRequest::decl()),
+        "EvaluateRequest" => Some(EvaluateRequest::decl()),
         "DirEntry" => Some(DirEntry::decl()),
 
         // Diagnostic types
@@ -93,6 +140,14 @@ fn get_type_decl(type_name: &str) -> Option<String> {
         "LspServerPackConfig" => Some(LspServerPackConfig::decl()),
         "FormatterPackConfig" => Some(FormatterPackConfig::decl()),
 
+        // Theme types
+        "ThemeSeed" => Some(ThemeSeed::decl()),
+
+        // Event payload types
+        "BufferChange" => Some(BufferChange::decl()),
+        "LineInfo" => Some(LineInfo::decl()),
+        "LspLocation" => Some(LspLocation::decl()),
+
         _ => None,
     }
 }
@@ -102,11 +157,18 @@ fn get_type_decl(type_name: &str) -> Option<String> {
 /// that aren't directly in method signatures.
 const DEPENDENCY_TYPES: &[&str] = &[
     "TextPropertyEntry",              // Used in CreateVirtualBuffer*Options.entries
+    "BufferDisplayOptions",           // Used in CreateVirtualBuffer*Options.display
+    "ConcealRule",                    // Used in BufferDisplayOptions.conceal
     "TsCompositeLayoutConfig",        // Used in createCompositeBuffer opts
     "TsCompositeSourceConfig",        // Used in createCompositeBuffer opts.sources
     "TsCompositePaneStyle",           // Used in TsCompositeSourceConfig.style
     "TsCompositeHunk",                // Used in createCompositeBuffer opts.hunks
     "TsCreateCompositeBufferOptions", // Options for createCompositeBuffer
+    "DiffGranularity",                // Used in ComputeDiffOptions.granularity
+    "DiffHunk",                       // Returned by computeDiff
+    "SyntaxCapture",                  // Returned by querySyntax
+    "SearchMatch",                    // Returned by search
+    "LayoutNode",                     // Returned by getLayout
     "ViewportInfo",                   // Used by plugins for viewport queries
     "LayoutHints",                    // Used by plugins for view transforms
     "ViewTokenWire",                  // Used by plugins for view transforms
@@ -119,18 +181,32 @@ const DEPENDENCY_TYPES: &[&str] = &[
     "JsRange",                        // Used by JsDiagnostic
     "JsPosition",                     // Used by JsRange
     "ActionSpec",                     // Used by executeActions
+    "SelectionSpec",                  // Used by setSelections
+    "BufferEdit",                     // Used by applyEdits
+    "OverlayColorSpec",               // Used by GutterText.color
+    "GutterText",                     // Used by setGutterTexts
+    "LineBackground",                 // Used by setLineBackgrounds
+    "EventOptions",                   // Used by on
     "TsActionPopupAction",            // Used by ActionPopupOptions.actions
     "ActionPopupOptions",             // Used by showActionPopup
     "FileExplorerDecoration",         // Used by setFileExplorerDecorations
     "FormatterPackConfig",            // Used by LanguagePackConfig.formatter
+    "CompletionRequest",              // Passed to completion provider handlers
+    "CompletionItem",                 // Returned by completion provider handlers
+    "HoverRequest",                   // Passed to hover provider handlers
+    "InlineSuggestionRequest",        // Passed to inline suggestion provider handlers
+    "FormatRequest",                  // Passed to formatter handlers
+    "EvaluateRequest",                // Passed to evaluator handlers
 ];
 
 /// Collect TypeScript type declarations based on referenced types from proc macro
 ///
-/// Uses `JSEDITORAPI_REFERENCED_TYPES` to determine which types to include.
+/// Uses `JSEDITORAPI_REFERENCED_TYPES` and `HOOKARGS_REFERENCED_TYPES` to
+/// determine which types to include.
 /// Also includes dependency types that are referenced by other types.
 pub fn collect_ts_types() -> String {
     use crate::backend::quickjs_backend::JSEDITORAPI_REFERENCED_TYPES;
+    use fresh_core::hooks::HOOKARGS_REFERENCED_TYPES;
 
     let mut types = Vec::new();
     let mut included = std::collections::HashSet::new();
@@ -143,8 +219,11 @@ pub fn collect_ts_types() -> String {
         }
     }
 
-    // Collect types referenced by the API
-    for type_name in JSEDITORAPI_REFERENCED_TYPES {
+    // Collect types referenced by the API and the event payloads
+    for type_name in JSEDITORAPI_REFERENCED_TYPES
+        .iter()
+        .chain(HOOKARGS_REFERENCED_TYPES)
+    {
         if included.contains(*type_name) {
             continue;
         }
@@ -203,18 +282,33 @@ pub fn format_typescript(source: &str) -> String {
     Codegen::new().build(&parser_ret.program).code
 }
 
+/// Path to write fresh.d.ts to
+///
+/// The `FRESH_DTS_OUT` environment variable (the impl's `env` option) overrides
+/// the `out` path given to `#[plugin_api_impl]`.
+pub fn fresh_dts_path() -> std::path::PathBuf {
+    use crate::backend::quickjs_backend::{JSEDITORAPI_TS_OUT_ENV, JSEDITORAPI_TS_OUT_PATH};
+
+    JSEDITORAPI_TS_OUT_ENV
+        .and_then(std::env::var_os)
+        .filter(|path| !path.is_empty())
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| std::path::PathBuf::from(JSEDITORAPI_TS_OUT_PATH))
+}
+
 /// Generate and write the complete fresh.d.ts file
 ///
 /// Combines ts-rs generated types with proc macro output,
-/// validates the syntax, formats the output, and writes to disk.
+/// validates the syntax, formats the output, and writes it to `fresh_dts_path()`.
 pub fn write_fresh_dts() -> Result<(), String> {
     use crate::backend::quickjs_backend::{JSEDITORAPI_TS_EDITOR_API, JSEDITORAPI_TS_PREAMBLE};
+    use fresh_core::hooks::HOOKARGS_TS_EVENT_MAP;
 
     let ts_types = collect_ts_types();
 
     let content = format!(
-        "{}\n{}\n{}",
-        JSEDITORAPI_TS_PREAMBLE, ts_types, JSEDITORAPI_TS_EDITOR_API
+        "{}\n{}\n{}\n{}",
+        JSEDITORAPI_TS_PREAMBLE, ts_types, HOOKARGS_TS_EVENT_MAP, JSEDITORAPI_TS_EDITOR_API
     );
 
     // Validate the generated TypeScript syntax
@@ -223,13 +317,7 @@ pub fn write_fresh_dts() -> Result<(), String> {
     // Format the TypeScript
     let formatted = format_typescript(&content);
 
-    // Determine output path - write to fresh-editor/plugins/lib/fresh.d.ts
-    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
-    let output_path = std::path::Path::new(&manifest_dir)
-        .parent() // crates/
-        .and_then(|p| p.parent()) // workspace root
-        .map(|p| p.join("crates/fresh-editor/plugins/lib/fresh.d.ts"))
-        .unwrap_or_else(|| std::path::PathBuf::from("plugins/lib/fresh.d.ts"));
+    let output_path = fresh_dts_path();
 
     // Only write if content changed
     let should_write = match std::fs::read_to_string(&output_path) {
@@ -40,6 +40,7 @@ export default defineConfig({
           { text: "LSP Integration", link: "/features/lsp" },
           { text: "Themes", link: "/features/themes" },
           { text: "Remote Editing (SSH)", link: "/features/ssh" },
+          { text: "Controlling Fresh From Scripts", link: "/features/remote-control" },
         ],
       },
       {